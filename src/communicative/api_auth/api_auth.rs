@@ -0,0 +1,449 @@
+use crate::communicative::api_auth::api_identity::ApiIdentity;
+use crate::communicative::api_auth::api_key::ApiKey;
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use crate::communicative::api_auth::api_token::ApiToken;
+use crate::communicative::api_auth::errors::api_auth_construction_error::ApiAuthConstructionError;
+use crate::communicative::api_auth::errors::api_auth_error::ApiAuthError;
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::bip322::signed_message::verify_account_key;
+use rand::{rngs::OsRng, RngCore};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Environment variable requiring authentication for reads too.
+pub const API_AUTH_ENV: &str = "CUBE_API_AUTH";
+
+/// Seconds a login challenge may be signed within.
+pub const CHALLENGE_TTL_SECS: u64 = 120;
+
+/// Seconds a session lasts.
+pub const SESSION_TTL_SECS: u64 = 3_600;
+
+/// Maximum number of pending login challenges.
+pub const MAX_PENDING_CHALLENGES: usize = 1_024;
+
+/// Maximum number of open sessions.
+pub const MAX_SESSIONS: usize = 4_096;
+
+/// Maximum number of keys allowed to log in.
+pub const MAX_API_KEYS: usize = 256;
+
+/// Maximum number of issued API tokens.
+pub const MAX_API_TOKENS: usize = 256;
+
+/// Maximum length of a token label.
+const MAX_LABEL_LEN: usize = 64;
+
+/// Returns whether reads require authentication, from `CUBE_API_AUTH=required`.
+pub fn api_auth_required_from_env() -> bool {
+    std::env::var(API_AUTH_ENV)
+        .map(|value| value.trim().eq_ignore_ascii_case("required"))
+        .unwrap_or(false)
+}
+
+/// A login challenge pending its signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChallenge {
+    // The message to sign.
+    pub message: String,
+
+    // Unix timestamp (seconds) the challenge expires at.
+    pub expires_at: u64,
+}
+
+/// A session opened by signing a challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiSession {
+    // The account key that logged in.
+    pub account_key: AccountKey,
+
+    // Unix timestamp (seconds) the session expires at.
+    pub expires_at: u64,
+}
+
+/// Identities allowed on the query APIs, and their open sessions.
+///
+/// Account keys log in by signing a one-time challenge with BIP-322, which opens a session with a
+/// short-lived bearer token. API tokens are long-lived bearer tokens issued by the operator. Both
+/// carry a read or admin permission; a session follows the current permission of its key, so that
+/// removing a key or lowering its permission takes effect at once. Only hashes of the bearer tokens
+/// are kept. Anonymous requests may read unless reads require authentication.
+pub struct ApiAuth {
+    // The chain, bound into the challenges.
+    chain: Chain,
+
+    // Whether reads require authentication.
+    read_requires_auth: bool,
+
+    // Id of the next issued token.
+    next_token_id: u64,
+
+    // In-memory keys allowed to log in.
+    keys: HashMap<AccountKey, ApiKey>,
+
+    // In-memory issued tokens by id.
+    tokens: BTreeMap<u64, ApiToken>,
+
+    // Pending login challenges by account key.
+    challenges: HashMap<AccountKey, ApiChallenge>,
+
+    // Open sessions by the hash of their bearer token.
+    sessions: HashMap<[u8; 32], ApiSession>,
+
+    // On-disk keys allowed to log in.
+    on_disk_keys: sled::Tree,
+
+    // On-disk issued tokens.
+    on_disk_tokens: sled::Tree,
+}
+
+/// Guarded 'ApiAuth'.
+#[allow(non_camel_case_types)]
+pub type API_AUTH = Arc<Mutex<ApiAuth>>;
+
+impl ApiAuth {
+    /// Constructs the API auth, loading the keys and tokens from disk.
+    pub fn new(
+        chain: Chain,
+        read_requires_auth: bool,
+    ) -> Result<API_AUTH, ApiAuthConstructionError> {
+        // 1 Open the API auth db.
+        let db_path = format!("storage/{}/api_auth", chain.to_string());
+        let db = sled::open(db_path).map_err(ApiAuthConstructionError::DBOpenError)?;
+
+        // 2 Open the keys and tokens trees.
+        let on_disk_keys = db
+            .open_tree("keys")
+            .map_err(ApiAuthConstructionError::TreeOpenError)?;
+        let on_disk_tokens = db
+            .open_tree("tokens")
+            .map_err(ApiAuthConstructionError::TreeOpenError)?;
+
+        // 3 Load the keys and tokens, skipping corrupt ones.
+        let mut keys = HashMap::new();
+        for (_, value) in on_disk_keys.iter().filter_map(|item| item.ok()) {
+            if let Some(api_key) = ApiKey::deserialize(value.as_ref()) {
+                keys.insert(api_key.account_key, api_key);
+            }
+        }
+        let mut tokens = BTreeMap::new();
+        for (_, value) in on_disk_tokens.iter().filter_map(|item| item.ok()) {
+            if let Some(api_token) = ApiToken::deserialize(value.as_ref()) {
+                tokens.insert(api_token.id, api_token);
+            }
+        }
+
+        // 4 Continue the token ids after the highest known one.
+        let next_token_id = tokens.keys().next_back().map(|id| id + 1).unwrap_or(0);
+
+        // 5 Construct the API auth.
+        let api_auth = ApiAuth {
+            chain,
+            read_requires_auth,
+            next_token_id,
+            keys,
+            tokens,
+            challenges: HashMap::new(),
+            sessions: HashMap::new(),
+            on_disk_keys,
+            on_disk_tokens,
+        };
+
+        // 6 Guard and return the API auth.
+        Ok(Arc::new(Mutex::new(api_auth)))
+    }
+
+    /// Whether reads require authentication.
+    pub fn read_requires_auth(&self) -> bool {
+        self.read_requires_auth
+    }
+
+    /// Allows an account key to log in with the given permission, replacing its previous one.
+    pub fn add_key(
+        &mut self,
+        account_key: AccountKey,
+        permission: ApiPermission,
+        now: u64,
+    ) -> Result<ApiKey, ApiAuthError> {
+        // 1 Check there is room for a new key.
+        if !self.keys.contains_key(&account_key) && self.keys.len() >= MAX_API_KEYS {
+            return Err(ApiAuthError::TooManyKeys(MAX_API_KEYS));
+        }
+
+        // 2 Persist the key.
+        let api_key = ApiKey {
+            account_key,
+            permission,
+            added_at: now,
+        };
+        self.on_disk_keys
+            .insert(account_key, api_key.serialize().unwrap_or_default())
+            .map_err(|err| ApiAuthError::DbErr(err.to_string()))?;
+        self.keys.insert(account_key, api_key.clone());
+
+        Ok(api_key)
+    }
+
+    /// Removes an account key along with its sessions. Returns whether it was allowed.
+    pub fn remove_key(&mut self, account_key: AccountKey) -> Result<bool, ApiAuthError> {
+        if self.keys.remove(&account_key).is_none() {
+            return Ok(false);
+        }
+        self.on_disk_keys
+            .remove(account_key)
+            .map_err(|err| ApiAuthError::DbErr(err.to_string()))?;
+        self.challenges.remove(&account_key);
+        self.sessions
+            .retain(|_, session| session.account_key != account_key);
+        Ok(true)
+    }
+
+    /// Issues an API token and returns it along with the token itself, which is not kept.
+    pub fn issue_token(
+        &mut self,
+        label: &str,
+        permission: ApiPermission,
+        now: u64,
+    ) -> Result<(ApiToken, String), ApiAuthError> {
+        // 1 Check the token can be issued.
+        let label = label.trim();
+        if label.is_empty()
+            || label.len() > MAX_LABEL_LEN
+            || label.chars().any(|c| c.is_whitespace())
+        {
+            return Err(ApiAuthError::InvalidLabel);
+        }
+        if self.tokens.len() >= MAX_API_TOKENS {
+            return Err(ApiAuthError::TooManyTokens(MAX_API_TOKENS));
+        }
+
+        // 2 Generate the token.
+        let token = random_token();
+
+        // 3 Persist its hash.
+        let api_token = ApiToken {
+            id: self.next_token_id,
+            label: label.to_string(),
+            token_hash: token_hash(&token),
+            permission,
+            created_at: now,
+        };
+        self.on_disk_tokens
+            .insert(
+                api_token.id.to_be_bytes(),
+                api_token.serialize().unwrap_or_default(),
+            )
+            .map_err(|err| ApiAuthError::DbErr(err.to_string()))?;
+        self.next_token_id += 1;
+        self.tokens.insert(api_token.id, api_token.clone());
+
+        Ok((api_token, token))
+    }
+
+    /// Revokes an API token. Returns whether it was issued.
+    pub fn revoke_token(&mut self, id: u64) -> Result<bool, ApiAuthError> {
+        if self.tokens.remove(&id).is_none() {
+            return Ok(false);
+        }
+        self.on_disk_tokens
+            .remove(id.to_be_bytes())
+            .map_err(|err| ApiAuthError::DbErr(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Returns a fresh login challenge for an account key allowed to log in, replacing any pending
+    /// one.
+    pub fn challenge(
+        &mut self,
+        account_key: AccountKey,
+        now: u64,
+    ) -> Result<ApiChallenge, ApiAuthError> {
+        // 1 Only keys allowed to log in get a challenge.
+        if !self.keys.contains_key(&account_key) {
+            return Err(ApiAuthError::UnknownKey);
+        }
+
+        // 2 Drop the expired challenges and check there is room for a new one.
+        self.challenges
+            .retain(|_, challenge| challenge.expires_at > now);
+        if !self.challenges.contains_key(&account_key)
+            && self.challenges.len() >= MAX_PENDING_CHALLENGES
+        {
+            return Err(ApiAuthError::TooManyChallenges(MAX_PENDING_CHALLENGES));
+        }
+
+        // 3 Construct the challenge, bound to the chain and expiring shortly.
+        let expires_at = now + CHALLENGE_TTL_SECS;
+        let challenge = ApiChallenge {
+            message: format!(
+                "cube-api-login:{}:{}:{}",
+                self.chain.to_string(),
+                random_token(),
+                expires_at
+            ),
+            expires_at,
+        };
+        self.challenges.insert(account_key, challenge.clone());
+
+        Ok(challenge)
+    }
+
+    /// Opens a session for an account key that signed its pending challenge with BIP-322, and
+    /// returns it along with its bearer token. The challenge is consumed either way.
+    pub fn open_session(
+        &mut self,
+        account_key: AccountKey,
+        signature: &str,
+        now: u64,
+    ) -> Result<(ApiSession, String), ApiAuthError> {
+        // 1 Take the pending challenge.
+        let challenge = self
+            .challenges
+            .remove(&account_key)
+            .ok_or(ApiAuthError::NoPendingChallenge)?;
+        if challenge.expires_at <= now {
+            return Err(ApiAuthError::ChallengeExpired);
+        }
+        if !self.keys.contains_key(&account_key) {
+            return Err(ApiAuthError::UnknownKey);
+        }
+
+        // 2 Verify the signature by the address of the key.
+        verify_account_key(
+            self.chain,
+            account_key,
+            challenge.message.as_bytes(),
+            signature,
+        )
+        .map_err(ApiAuthError::InvalidSignature)?;
+
+        // 3 Drop the expired sessions and check there is room for a new one.
+        self.sessions.retain(|_, session| session.expires_at > now);
+        if self.sessions.len() >= MAX_SESSIONS {
+            return Err(ApiAuthError::TooManySessions(MAX_SESSIONS));
+        }
+
+        // 4 Open the session.
+        let token = random_token();
+        let session = ApiSession {
+            account_key,
+            expires_at: now + SESSION_TTL_SECS,
+        };
+        self.sessions.insert(token_hash(&token), session.clone());
+
+        Ok((session, token))
+    }
+
+    /// Closes the session of a bearer token. Returns whether it was open.
+    pub fn close_session(&mut self, token: &str) -> bool {
+        self.sessions.remove(&token_hash(token)).is_some()
+    }
+
+    /// Authorizes a request presenting the given bearer token, if any, for the required permission.
+    pub fn authorize(
+        &mut self,
+        bearer: Option<&str>,
+        required: ApiPermission,
+        now: u64,
+    ) -> Result<ApiIdentity, ApiAuthError> {
+        // 1 Anonymous requests may only read, and only while reads do not require authentication.
+        let bearer = match bearer {
+            Some(bearer) => bearer,
+            None if required == ApiPermission::Read && !self.read_requires_auth => {
+                return Ok(ApiIdentity::Anonymous)
+            }
+            None => return Err(ApiAuthError::Unauthenticated),
+        };
+
+        // 2 Resolve the bearer token into a session or an API token.
+        let bearer_hash = token_hash(bearer);
+        let (identity, permission) = match self.sessions.get(&bearer_hash) {
+            Some(session) if session.expires_at <= now => {
+                self.sessions.remove(&bearer_hash);
+                return Err(ApiAuthError::SessionExpired);
+            }
+            Some(session) => {
+                let api_key = self
+                    .keys
+                    .get(&session.account_key)
+                    .ok_or(ApiAuthError::InvalidToken)?;
+                (ApiIdentity::Key(session.account_key), api_key.permission)
+            }
+            None => {
+                let api_token = self
+                    .tokens
+                    .values()
+                    .find(|api_token| api_token.token_hash == bearer_hash)
+                    .ok_or(ApiAuthError::InvalidToken)?;
+                (ApiIdentity::Token(api_token.id), api_token.permission)
+            }
+        };
+
+        // 3 Check the permission.
+        if !permission.allows(required) {
+            return Err(ApiAuthError::Forbidden(required));
+        }
+
+        Ok(identity)
+    }
+
+    /// Returns the number of open sessions.
+    pub fn sessions_len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Returns the keys allowed to log in as a JSON array.
+    pub fn keys_json(&self) -> Value {
+        let mut keys: Vec<&ApiKey> = self.keys.values().collect();
+        keys.sort_by_key(|api_key| api_key.added_at);
+        Value::Array(keys.into_iter().map(ApiKey::json).collect())
+    }
+
+    /// Returns the issued tokens as a JSON array, without their hashes.
+    pub fn tokens_json(&self) -> Value {
+        Value::Array(self.tokens.values().map(ApiToken::json).collect())
+    }
+
+    /// Returns the API auth as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "read_requires_auth".to_string(),
+            Value::Bool(self.read_requires_auth),
+        );
+        obj.insert("keys".to_string(), self.keys_json());
+        obj.insert("tokens".to_string(), self.tokens_json());
+        obj.insert(
+            "sessions".to_string(),
+            Value::Number(self.sessions.len().into()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Returns a random 32-byte hex token.
+fn random_token() -> String {
+    let mut token_bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut token_bytes);
+    hex::encode(token_bytes)
+}
+
+/// Returns the SHA-256 hash a bearer token is looked up by.
+fn token_hash(token: &str) -> [u8; 32] {
+    Sha256::digest(token.trim().as_bytes()).into()
+}
+
+/// Erases the API auth.
+pub fn erase_api_auth(chain: Chain) {
+    // API auth db path.
+    let db_path = format!("storage/{}/api_auth", chain.to_string());
+
+    // Erase the API auth db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
@@ -0,0 +1,25 @@
+use crate::transmutative::key::ToNostrKeyStr;
+
+/// Identity a query API request was authorized as.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ApiIdentity {
+    /// No credentials were presented.
+    Anonymous,
+    /// A session opened by an account key.
+    Key([u8; 32]),
+    /// An API token, by id.
+    Token(u64),
+}
+
+impl ToString for ApiIdentity {
+    fn to_string(&self) -> String {
+        match self {
+            ApiIdentity::Anonymous => "anonymous".to_string(),
+            ApiIdentity::Key(account_key) => match account_key.to_npub() {
+                Some(npub) => format!("key:{}", npub),
+                None => format!("key:{}", hex::encode(account_key)),
+            },
+            ApiIdentity::Token(id) => format!("token:{}", id),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use crate::transmutative::key::ToNostrKeyStr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// An account key allowed to log in to the query APIs with a signed challenge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    // The account key.
+    pub account_key: [u8; 32],
+
+    // Permission of the sessions opened by the key.
+    pub permission: ApiPermission,
+
+    // Unix timestamp (seconds) the key was added at.
+    pub added_at: u64,
+}
+
+impl ApiKey {
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes an API key from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(api_key, _)| api_key)
+    }
+
+    /// Returns the API key as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "account".to_string(),
+            match self.account_key.to_npub() {
+                Some(npub) => Value::String(npub),
+                None => Value::String(hex::encode(self.account_key)),
+            },
+        );
+        obj.insert(
+            "permission".to_string(),
+            Value::String(self.permission.to_string()),
+        );
+        obj.insert("added_at".to_string(), Value::Number(self.added_at.into()));
+        Value::Object(obj)
+    }
+}
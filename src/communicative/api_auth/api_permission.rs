@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Permission of an identity on the query APIs.
+///
+/// Ordered so that a permission allows everything a lower one does.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ApiPermission {
+    /// Queries and event stream subscriptions.
+    Read,
+    /// Reads, and admin commands.
+    Admin,
+}
+
+impl ApiPermission {
+    /// Returns all API permissions.
+    pub fn all() -> [ApiPermission; 2] {
+        [ApiPermission::Read, ApiPermission::Admin]
+    }
+
+    /// Returns the API permission of the given name.
+    pub fn from_name(name: &str) -> Option<ApiPermission> {
+        ApiPermission::all()
+            .into_iter()
+            .find(|permission| permission.to_string() == name)
+    }
+
+    /// Whether this permission allows what the given one does.
+    pub fn allows(&self, required: ApiPermission) -> bool {
+        *self >= required
+    }
+}
+
+impl ToString for ApiPermission {
+    fn to_string(&self) -> String {
+        match self {
+            ApiPermission::Read => "read".to_string(),
+            ApiPermission::Admin => "admin".to_string(),
+        }
+    }
+}
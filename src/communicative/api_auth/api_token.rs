@@ -0,0 +1,54 @@
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// An operator-issued bearer token for the query APIs.
+///
+/// Only the SHA-256 hash of the token is kept, the token itself is shown once when issued.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiToken {
+    // Id of the token, increasing in order of issuance.
+    pub id: u64,
+
+    // Operator-given label of the token.
+    pub label: String,
+
+    // SHA-256 hash of the token.
+    pub token_hash: [u8; 32],
+
+    // Permission of the token.
+    pub permission: ApiPermission,
+
+    // Unix timestamp (seconds) the token was issued at.
+    pub created_at: u64,
+}
+
+impl ApiToken {
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes an API token from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(api_token, _)| api_token)
+    }
+
+    /// Returns the API token as a JSON object, without the token hash.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::Number(self.id.into()));
+        obj.insert("label".to_string(), Value::String(self.label.clone()));
+        obj.insert(
+            "permission".to_string(),
+            Value::String(self.permission.to_string()),
+        );
+        obj.insert(
+            "created_at".to_string(),
+            Value::Number(self.created_at.into()),
+        );
+        Value::Object(obj)
+    }
+}
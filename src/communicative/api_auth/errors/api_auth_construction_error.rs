@@ -0,0 +1,6 @@
+/// Errors associated with constructing the API auth.
+#[derive(Debug, Clone)]
+pub enum ApiAuthConstructionError {
+    DBOpenError(sled::Error),
+    TreeOpenError(sled::Error),
+}
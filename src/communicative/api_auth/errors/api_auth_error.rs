@@ -0,0 +1,85 @@
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use crate::transmutative::bip322::error::Bip322Error;
+use std::fmt;
+
+/// Errors associated with authenticating on the query APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiAuthError {
+    // No credentials were presented where required.
+    Unauthenticated,
+    // The bearer token is neither a session nor an API token.
+    InvalidToken,
+    // The session expired.
+    SessionExpired,
+    // The identity lacks the required permission.
+    Forbidden(ApiPermission),
+    // The account key is not allowed to log in.
+    UnknownKey,
+    // No challenge is pending for the account key.
+    NoPendingChallenge,
+    // The challenge expired before it was signed.
+    ChallengeExpired,
+    // The signature of the challenge does not verify.
+    InvalidSignature(Bip322Error),
+    // Too many challenges are pending.
+    TooManyChallenges(usize),
+    // Too many sessions are open.
+    TooManySessions(usize),
+    // No more keys can be added.
+    TooManyKeys(usize),
+    // No more tokens can be issued.
+    TooManyTokens(usize),
+    // The token label is empty or too long.
+    InvalidLabel,
+    // Reading or writing the API auth db failed.
+    DbErr(String),
+}
+
+impl ApiAuthError {
+    /// Whether the error is a lack of permission rather than of valid credentials.
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, ApiAuthError::Forbidden(_))
+    }
+}
+
+impl fmt::Display for ApiAuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiAuthError::Unauthenticated => write!(f, "Authentication required."),
+            ApiAuthError::InvalidToken => write!(f, "Invalid bearer token."),
+            ApiAuthError::SessionExpired => write!(f, "The session expired, log in again."),
+            ApiAuthError::Forbidden(permission) => {
+                write!(
+                    f,
+                    "The '{}' permission is required.",
+                    permission.to_string()
+                )
+            }
+            ApiAuthError::UnknownKey => write!(f, "This key is not allowed to log in."),
+            ApiAuthError::NoPendingChallenge => {
+                write!(f, "No challenge is pending for this key.")
+            }
+            ApiAuthError::ChallengeExpired => write!(f, "The challenge expired."),
+            ApiAuthError::InvalidSignature(err) => {
+                write!(f, "The challenge signature does not verify: {:?}", err)
+            }
+            ApiAuthError::TooManyChallenges(max) => {
+                write!(f, "Too many pending challenges (at most {}).", max)
+            }
+            ApiAuthError::TooManySessions(max) => {
+                write!(f, "Too many open sessions (at most {}).", max)
+            }
+            ApiAuthError::TooManyKeys(max) => {
+                write!(f, "At most {} API keys may be added.", max)
+            }
+            ApiAuthError::TooManyTokens(max) => {
+                write!(f, "At most {} API tokens may be issued.", max)
+            }
+            ApiAuthError::InvalidLabel => write!(
+                f,
+                "A token label must be 1 to 64 characters without whitespace."
+            ),
+            ApiAuthError::DbErr(err) => write!(f, "API auth db error: {}", err),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod api_auth_construction_error;
+pub mod api_auth_error;
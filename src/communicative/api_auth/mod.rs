@@ -0,0 +1,6 @@
+pub mod api_auth;
+pub mod api_identity;
+pub mod api_key;
+pub mod api_permission;
+pub mod api_token;
+pub mod errors;
@@ -0,0 +1,220 @@
+use crate::communicative::handshake::announcement::schnorr_signature_64;
+use crate::communicative::handshake::capability::Capability;
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::{KeyHolder, ToNostrKeyStr};
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::net::SocketAddr;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Version of the discovery protocol spoken by this build.
+pub const DISCOVERY_PROTOCOL_VERSION: u16 = 1;
+
+/// Role a peer announces itself with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PeerAnnouncementKind {
+    // The engine of the chain.
+    Engine,
+
+    // A federation coordinator.
+    Coordinator,
+
+    // A node operator.
+    Operator,
+}
+
+impl PeerAnnouncementKind {
+    /// Returns the bytecode of the kind.
+    pub fn bytecode(&self) -> u8 {
+        match self {
+            PeerAnnouncementKind::Engine => 0x00,
+            PeerAnnouncementKind::Coordinator => 0x01,
+            PeerAnnouncementKind::Operator => 0x02,
+        }
+    }
+
+    /// Returns the kind from its bytecode.
+    pub fn from_bytecode(bytecode: u8) -> Option<Self> {
+        match bytecode {
+            0x00 => Some(PeerAnnouncementKind::Engine),
+            0x01 => Some(PeerAnnouncementKind::Coordinator),
+            0x02 => Some(PeerAnnouncementKind::Operator),
+            _ => None,
+        }
+    }
+}
+
+impl ToString for PeerAnnouncementKind {
+    fn to_string(&self) -> String {
+        match self {
+            PeerAnnouncementKind::Engine => "engine".to_string(),
+            PeerAnnouncementKind::Coordinator => "coordinator".to_string(),
+            PeerAnnouncementKind::Operator => "operator".to_string(),
+        }
+    }
+}
+
+/// A peer announcing its role, endpoints and supported chains to the network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerAnnouncement {
+    // The announcing peer key.
+    pub peer_key: PeerKey,
+
+    // Announced role.
+    pub kind: PeerAnnouncementKind,
+
+    // Discovery protocol version.
+    pub protocol_version: u16,
+
+    // Software version of the peer.
+    pub software_version: String,
+
+    // Endpoints the peer accepts TCP connections on.
+    pub endpoints: Vec<SocketAddr>,
+
+    // Chains the peer serves.
+    pub chains: Vec<Chain>,
+
+    // Announced capabilities.
+    pub capabilities: Vec<Capability>,
+
+    // Announcement timestamp.
+    pub timestamp: Timestamp,
+
+    // The Schnorr signature over the announcement message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl PeerAnnouncement {
+    /// Signs a fresh new announcement with the peer's key.
+    pub fn sign(
+        keys: &KeyHolder,
+        kind: PeerAnnouncementKind,
+        endpoints: Vec<SocketAddr>,
+        chains: Vec<Chain>,
+        capabilities: Vec<Capability>,
+        timestamp: Timestamp,
+    ) -> Option<Self> {
+        // 1 Construct the unsigned announcement.
+        let mut announcement = Self {
+            peer_key: keys.secp_public_key_bytes(),
+            kind,
+            protocol_version: DISCOVERY_PROTOCOL_VERSION,
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+            endpoints,
+            chains,
+            capabilities,
+            timestamp,
+            signature: [0u8; 64],
+        };
+
+        // 2 Sign the announcement message.
+        announcement.signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            announcement.message(),
+            SchnorrSigningMode::Cube,
+        )?;
+
+        // 3 Return the announcement.
+        Some(announcement)
+    }
+
+    /// Returns the message the peer signs.
+    pub fn message(&self) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::new();
+        preimage.extend(self.peer_key);
+        preimage.push(self.kind.bytecode());
+        preimage.extend(self.protocol_version.to_be_bytes());
+        preimage.extend((self.software_version.len() as u32).to_be_bytes());
+        preimage.extend(self.software_version.as_bytes());
+        preimage.extend((self.endpoints.len() as u32).to_be_bytes());
+        for endpoint in self.endpoints.iter() {
+            let endpoint = endpoint.to_string();
+            preimage.extend((endpoint.len() as u32).to_be_bytes());
+            preimage.extend(endpoint.as_bytes());
+        }
+        preimage.extend((self.chains.len() as u32).to_be_bytes());
+        preimage.extend(self.chains.iter().map(|c| c.bytecode()));
+        preimage.extend((self.capabilities.len() as u32).to_be_bytes());
+        preimage.extend(self.capabilities.iter().map(|c| c.bytecode()));
+        preimage.extend(self.timestamp.to_be_bytes());
+        preimage.hash(Some(HashTag::PeerAnnouncement))
+    }
+
+    /// Verifies the announcement against the announced peer key.
+    pub fn verify(&self) -> bool {
+        schnorr::verify_xonly(
+            self.peer_key,
+            self.message(),
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+
+    /// Whether the peer serves the given chain.
+    pub fn supports_chain(&self, chain: Chain) -> bool {
+        self.chains.contains(&chain)
+    }
+
+    /// Returns the announcement as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "peer".to_string(),
+            Value::String(
+                self.peer_key
+                    .to_npub()
+                    .unwrap_or_else(|| hex::encode(self.peer_key)),
+            ),
+        );
+        obj.insert("kind".to_string(), Value::String(self.kind.to_string()));
+        obj.insert(
+            "protocol_version".to_string(),
+            Value::Number(self.protocol_version.into()),
+        );
+        obj.insert(
+            "software_version".to_string(),
+            Value::String(self.software_version.clone()),
+        );
+        obj.insert(
+            "endpoints".to_string(),
+            Value::Array(
+                self.endpoints
+                    .iter()
+                    .map(|e| Value::String(e.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "chains".to_string(),
+            Value::Array(
+                self.chains
+                    .iter()
+                    .map(|c| Value::String(c.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "capabilities".to_string(),
+            Value::Array(
+                self.capabilities
+                    .iter()
+                    .map(|c| Value::String(c.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "timestamp".to_string(),
+            Value::Number(self.timestamp.into()),
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Reasons a peer announcement is left out of the peer table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerDiscoveryError {
+    InvalidSignature,
+    ProtocolVersionMismatch {
+        expected: u16,
+        announced: u16,
+    },
+    UnsupportedChain,
+    UnauthorizedPeerKind,
+    NoEndpoints,
+    AnnouncementExpired(Timestamp),
+    ClockSkewTooLarge {
+        announced: Timestamp,
+        now: Timestamp,
+    },
+    ReplayedAnnouncement {
+        last_timestamp: Timestamp,
+    },
+}
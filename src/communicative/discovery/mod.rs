@@ -0,0 +1,3 @@
+pub mod announcement;
+pub mod errors;
+pub mod peer_table;
@@ -0,0 +1,215 @@
+use crate::communicative::discovery::announcement::{
+    PeerAnnouncement, PeerAnnouncementKind, DISCOVERY_PROTOCOL_VERSION,
+};
+use crate::communicative::discovery::errors::discovery_error::PeerDiscoveryError;
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::peer::manager::engine_key;
+use crate::operative::run_args::chain::Chain;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// How long an announcement stays in the peer table without being re-published.
+pub const ANNOUNCEMENT_TTL_SECS: u64 = 6 * 60 * 60;
+
+/// How often peers re-publish their announcement, well within the announcement TTL.
+pub const ANNOUNCEMENT_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Maximum tolerated distance of an announcement timestamp into the future.
+pub const MAX_CLOCK_SKEW_SECS: u64 = 60;
+
+/// Table of the peers discovered through signed announcements on a chain.
+///
+/// Anyone may announce themselves as an operator, while the engine and coordinator roles are
+/// only accepted from the keys baked for the chain.
+#[derive(Clone)]
+pub struct PeerTable {
+    // The chain the table is built for.
+    chain: Chain,
+
+    // The only key accepted as the engine.
+    engine_key: PeerKey,
+
+    // The only keys accepted as coordinators.
+    coordinator_keys: Vec<PeerKey>,
+
+    // The latest accepted announcement of each peer.
+    announcements: HashMap<PeerKey, PeerAnnouncement>,
+}
+
+impl PeerTable {
+    /// Constructs an empty peer table for the chain, trusting its baked engine and coordinator keys.
+    pub fn new(chain: Chain) -> Self {
+        let coordinator_keys = match Federation::for_chain(chain) {
+            Some(federation) => federation.coordinator_keys().clone(),
+            None => Vec::new(),
+        };
+
+        Self::with_trusted_keys(chain, engine_key(chain), coordinator_keys)
+    }
+
+    /// Constructs an empty peer table trusting the given engine and coordinator keys.
+    pub fn with_trusted_keys(
+        chain: Chain,
+        engine_key: PeerKey,
+        coordinator_keys: Vec<PeerKey>,
+    ) -> Self {
+        Self {
+            chain,
+            engine_key,
+            coordinator_keys,
+            announcements: HashMap::new(),
+        }
+    }
+
+    /// Validates an announcement and records the announcing peer, replacing its prior announcement if any.
+    pub fn insert(
+        &mut self,
+        announcement: PeerAnnouncement,
+        now: Timestamp,
+    ) -> Result<(), PeerDiscoveryError> {
+        // 1 Verify the announcement signature.
+        if !announcement.verify() {
+            return Err(PeerDiscoveryError::InvalidSignature);
+        }
+
+        // 2 Check the protocol version.
+        if announcement.protocol_version != DISCOVERY_PROTOCOL_VERSION {
+            return Err(PeerDiscoveryError::ProtocolVersionMismatch {
+                expected: DISCOVERY_PROTOCOL_VERSION,
+                announced: announcement.protocol_version,
+            });
+        }
+
+        // 3 The peer must serve the chain of the table.
+        if !announcement.supports_chain(self.chain) {
+            return Err(PeerDiscoveryError::UnsupportedChain);
+        }
+
+        // 4 The engine and coordinator roles are reserved for their baked keys.
+        let authorized = match announcement.kind {
+            PeerAnnouncementKind::Engine => announcement.peer_key == self.engine_key,
+            PeerAnnouncementKind::Coordinator => {
+                self.coordinator_keys.contains(&announcement.peer_key)
+            }
+            PeerAnnouncementKind::Operator => announcement.peer_key != self.engine_key,
+        };
+        if !authorized {
+            return Err(PeerDiscoveryError::UnauthorizedPeerKind);
+        }
+
+        // 5 The peer must be reachable.
+        if announcement.endpoints.is_empty() {
+            return Err(PeerDiscoveryError::NoEndpoints);
+        }
+
+        // 6 Check the announcement is neither from the future nor expired.
+        if announcement.timestamp > now + MAX_CLOCK_SKEW_SECS {
+            return Err(PeerDiscoveryError::ClockSkewTooLarge {
+                announced: announcement.timestamp,
+                now,
+            });
+        }
+        if announcement.timestamp + ANNOUNCEMENT_TTL_SECS < now {
+            return Err(PeerDiscoveryError::AnnouncementExpired(
+                announcement.timestamp,
+            ));
+        }
+
+        // 7 Reject announcements that are not newer than the latest accepted one.
+        if let Some(prior_announcement) = self.announcements.get(&announcement.peer_key) {
+            if announcement.timestamp <= prior_announcement.timestamp {
+                return Err(PeerDiscoveryError::ReplayedAnnouncement {
+                    last_timestamp: prior_announcement.timestamp,
+                });
+            }
+        }
+
+        // 8 Record the announcement.
+        self.announcements
+            .insert(announcement.peer_key, announcement);
+
+        Ok(())
+    }
+
+    /// Removes the peers whose announcement expired and returns their keys.
+    pub fn prune(&mut self, now: Timestamp) -> Vec<PeerKey> {
+        let expired_keys: Vec<PeerKey> = self
+            .announcements
+            .iter()
+            .filter(|(_, announcement)| announcement.timestamp + ANNOUNCEMENT_TTL_SECS < now)
+            .map(|(peer_key, _)| *peer_key)
+            .collect();
+
+        for peer_key in expired_keys.iter() {
+            self.announcements.remove(peer_key);
+        }
+
+        expired_keys
+    }
+
+    /// Returns the chain the table is built for.
+    pub fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    /// Returns the number of peers in the table.
+    pub fn len(&self) -> usize {
+        self.announcements.len()
+    }
+
+    /// Whether the table has no peers.
+    pub fn is_empty(&self) -> bool {
+        self.announcements.is_empty()
+    }
+
+    /// Returns the latest accepted announcement of a peer.
+    pub fn announcement(&self, peer_key: PeerKey) -> Option<&PeerAnnouncement> {
+        self.announcements.get(&peer_key)
+    }
+
+    /// Returns the announced endpoints of a peer.
+    pub fn endpoints(&self, peer_key: PeerKey) -> Vec<SocketAddr> {
+        match self.announcements.get(&peer_key) {
+            Some(announcement) => announcement.endpoints.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the keys of the peers announced with the given kind, sorted.
+    pub fn peer_keys(&self, kind: PeerAnnouncementKind) -> Vec<PeerKey> {
+        let mut peer_keys: Vec<PeerKey> = self
+            .announcements
+            .values()
+            .filter(|announcement| announcement.kind == kind)
+            .map(|announcement| announcement.peer_key)
+            .collect();
+        peer_keys.sort();
+        peer_keys
+    }
+
+    /// Returns the peer table as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("chain".to_string(), Value::String(self.chain.to_string()));
+
+        let mut peer_keys: Vec<&PeerKey> = self.announcements.keys().collect();
+        peer_keys.sort();
+        obj.insert(
+            "peers".to_string(),
+            Value::Array(
+                peer_keys
+                    .iter()
+                    .map(|peer_key| self.announcements[*peer_key].json())
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+}
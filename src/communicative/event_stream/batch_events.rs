@@ -0,0 +1,63 @@
+use super::stream_event::{StreamEvent, StreamEventKind};
+use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
+use crate::constructive::entry::entry::entry::Entry;
+
+/// Returns the stream events of the entries applied by a batch, in order of execution.
+pub fn batch_stream_events(batch_record: &BatchRecord) -> Vec<StreamEvent> {
+    batch_record
+        .entries
+        .iter()
+        .map(|(entry_id, entry)| {
+            // 1 Resolve the kind, the involved accounts and contracts, and the moved value.
+            let (kind, account_keys, contract_ids, value) = match entry {
+                Entry::Move(move_entry) => (
+                    StreamEventKind::Move,
+                    vec![move_entry.from.account_key(), move_entry.to.account_key()],
+                    vec![],
+                    Some(move_entry.amount as u64),
+                ),
+                Entry::Call(call) => (
+                    StreamEventKind::Call,
+                    vec![call.account.account_key()],
+                    vec![call.contract.contract_id()],
+                    None,
+                ),
+                Entry::Liftup(liftup) => (
+                    StreamEventKind::Liftup,
+                    vec![liftup.root_account.account_key()],
+                    vec![],
+                    Some(liftup.liftup_sum_value_in_satoshis()),
+                ),
+                Entry::Swapout(swapout) => (
+                    StreamEventKind::Swapout,
+                    vec![swapout.root_account.account_key()],
+                    vec![],
+                    Some(swapout.amount as u64),
+                ),
+                Entry::Deploy(deploy) => (
+                    StreamEventKind::Deploy,
+                    vec![deploy.root_account.account_key()],
+                    vec![deploy.program.contract_id()],
+                    Some(deploy.initial_balance as u64),
+                ),
+                Entry::Config(config) => (
+                    StreamEventKind::Config,
+                    vec![config.root_account.account_key()],
+                    vec![],
+                    None,
+                ),
+            };
+
+            // 2 Construct the stream event.
+            StreamEvent {
+                kind,
+                batch_height: batch_record.batch_height,
+                entry_id: *entry_id,
+                account_keys,
+                contract_ids,
+                value,
+                entry: entry.json(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Errors parsing a subscription filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionFilterError {
+    // The filter is not a JSON object of the expected shape.
+    MalformedFilter,
+    // An account is neither an npub nor a hex account key.
+    InvalidAccountKey(String),
+    // A contract id is not a 32-byte hex string.
+    InvalidContractId(String),
+    // An event kind is unknown.
+    UnknownEventKind(String),
+    // The filter follows more accounts and contracts than allowed.
+    TooManyKeys(usize),
+}
+
+impl fmt::Display for SubscriptionFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubscriptionFilterError::MalformedFilter => write!(f, "Malformed filter."),
+            SubscriptionFilterError::InvalidAccountKey(account) => {
+                write!(f, "Invalid account key: {}", account)
+            }
+            SubscriptionFilterError::InvalidContractId(contract) => {
+                write!(f, "Invalid contract id: {}", contract)
+            }
+            SubscriptionFilterError::UnknownEventKind(kind) => {
+                write!(f, "Unknown event kind: {}", kind)
+            }
+            SubscriptionFilterError::TooManyKeys(keys) => {
+                write!(f, "Too many accounts and contracts: {}", keys)
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::stream_event::StreamEvent;
+use super::subscription_filter::SubscriptionFilter;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Number of events buffered for subscribers before the slowest ones start skipping events.
+pub const EVENT_STREAM_CAPACITY: usize = 1_024;
+
+/// Process-wide event stream.
+static EVENT_STREAM: OnceLock<EventStream> = OnceLock::new();
+
+/// Stream of the entries applied by each batch, fanned out to every subscriber.
+pub struct EventStream {
+    // Sender side of the broadcast channel.
+    sender: broadcast::Sender<Arc<StreamEvent>>,
+}
+
+impl EventStream {
+    /// Constructs a fresh new event stream with no subscribers.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_STREAM_CAPACITY);
+        EventStream { sender }
+    }
+
+    /// Publishes events to the subscribers. Returns the number of subscribers.
+    pub fn publish(&self, events: Vec<StreamEvent>) -> usize {
+        for event in events {
+            // Publishing without subscribers is not an error.
+            let _ = self.sender.send(Arc::new(event));
+        }
+        self.sender.receiver_count()
+    }
+
+    /// Subscribes to the events published from now on that pass the given filter.
+    pub fn subscribe(&self, filter: SubscriptionFilter) -> EventSubscription {
+        EventSubscription {
+            receiver: self.sender.subscribe(),
+            filter,
+        }
+    }
+
+    /// Returns the number of subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the process-wide event stream.
+pub fn event_stream() -> &'static EventStream {
+    EVENT_STREAM.get_or_init(EventStream::new)
+}
+
+/// Message delivered to a subscriber.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionMessage {
+    // An event that passed the filter.
+    Event(Arc<StreamEvent>),
+    // The subscriber fell behind and skipped the given number of events.
+    Lagged(u64),
+}
+
+/// A subscription to the event stream.
+pub struct EventSubscription {
+    // Receiver side of the broadcast channel.
+    receiver: broadcast::Receiver<Arc<StreamEvent>>,
+
+    // Filter evaluated before delivery.
+    filter: SubscriptionFilter,
+}
+
+impl EventSubscription {
+    /// Returns the filter.
+    pub fn filter(&self) -> &SubscriptionFilter {
+        &self.filter
+    }
+
+    /// Replaces the filter, taking effect from the next event.
+    pub fn set_filter(&mut self, filter: SubscriptionFilter) {
+        self.filter = filter;
+    }
+
+    /// Waits for the next event passing the filter. Returns `None` once the stream is closed.
+    ///
+    /// Cancel safe: an event is only taken off the channel when it is returned or filtered out.
+    pub async fn next(&mut self) -> Option<SubscriptionMessage> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => {
+                    if self.filter.matches(&event) {
+                        return Some(SubscriptionMessage::Event(event));
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    return Some(SubscriptionMessage::Lagged(skipped))
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
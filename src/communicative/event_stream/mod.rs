@@ -0,0 +1,5 @@
+pub mod batch_events;
+pub mod errors;
+pub mod event_stream;
+pub mod stream_event;
+pub mod subscription_filter;
@@ -0,0 +1,131 @@
+use crate::transmutative::key::ToNostrKeyStr;
+use serde_json::{Map, Value};
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Kind of a streamed event, after the kind of the applied entry.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StreamEventKind {
+    Move,
+    Call,
+    Liftup,
+    Swapout,
+    Deploy,
+    Config,
+}
+
+impl StreamEventKind {
+    /// Returns all event kinds.
+    pub fn all() -> [StreamEventKind; 6] {
+        [
+            StreamEventKind::Move,
+            StreamEventKind::Call,
+            StreamEventKind::Liftup,
+            StreamEventKind::Swapout,
+            StreamEventKind::Deploy,
+            StreamEventKind::Config,
+        ]
+    }
+
+    /// Parses an event kind from its name.
+    pub fn from_name(name: &str) -> Option<StreamEventKind> {
+        StreamEventKind::all()
+            .into_iter()
+            .find(|kind| kind.to_string() == name)
+    }
+}
+
+impl ToString for StreamEventKind {
+    fn to_string(&self) -> String {
+        match self {
+            StreamEventKind::Move => "move".to_string(),
+            StreamEventKind::Call => "call".to_string(),
+            StreamEventKind::Liftup => "liftup".to_string(),
+            StreamEventKind::Swapout => "swapout".to_string(),
+            StreamEventKind::Deploy => "deploy".to_string(),
+            StreamEventKind::Config => "config".to_string(),
+        }
+    }
+}
+
+/// An entry applied by a batch, as delivered to event stream subscribers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    // Event kind.
+    pub kind: StreamEventKind,
+
+    // Height of the batch that applied the entry.
+    pub batch_height: u64,
+
+    // Id of the applied entry.
+    pub entry_id: [u8; 32],
+
+    // Accounts involved in the entry.
+    pub account_keys: Vec<AccountKey>,
+
+    // Contracts involved in the entry.
+    pub contract_ids: Vec<ContractId>,
+
+    // Value moved by the entry in satoshis, if any.
+    pub value: Option<u64>,
+
+    // The entry as a JSON object.
+    pub entry: Value,
+}
+
+impl StreamEvent {
+    /// Whether the entry involves the given account.
+    pub fn involves_account(&self, account_key: &AccountKey) -> bool {
+        self.account_keys.contains(account_key)
+    }
+
+    /// Whether the entry involves the given contract.
+    pub fn involves_contract(&self, contract_id: &ContractId) -> bool {
+        self.contract_ids.contains(contract_id)
+    }
+
+    /// Returns the event as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("kind".to_string(), Value::String(self.kind.to_string()));
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height.into()),
+        );
+        obj.insert(
+            "entry_id".to_string(),
+            Value::String(hex::encode(self.entry_id)),
+        );
+        obj.insert(
+            "accounts".to_string(),
+            Value::Array(
+                self.account_keys
+                    .iter()
+                    .map(|key| Value::String(key.to_npub().unwrap_or_else(|| hex::encode(key))))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "contracts".to_string(),
+            Value::Array(
+                self.contract_ids
+                    .iter()
+                    .map(|id| Value::String(hex::encode(id)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "value".to_string(),
+            match self.value {
+                Some(value) => Value::Number(value.into()),
+                None => Value::Null,
+            },
+        );
+        obj.insert("entry".to_string(), self.entry.clone());
+        Value::Object(obj)
+    }
+}
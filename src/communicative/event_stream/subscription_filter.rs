@@ -0,0 +1,191 @@
+use super::errors::subscription_filter_error::SubscriptionFilterError;
+use super::stream_event::{StreamEvent, StreamEventKind};
+use crate::transmutative::key::ToNostrKeyStr;
+use crate::transmutative::secp::public_key;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Maximum number of accounts and contracts a single filter follows.
+pub const MAX_FILTER_KEYS: usize = 256;
+
+/// Filter of the events delivered to a subscriber, evaluated before delivery.
+///
+/// An event is delivered if it is of one of the listed kinds, moves at least the minimum value, and
+/// involves any of the followed accounts or contracts. Criteria left empty match every event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionFilter {
+    // Followed accounts.
+    pub account_keys: HashSet<AccountKey>,
+
+    // Followed contracts.
+    pub contract_ids: HashSet<ContractId>,
+
+    // Event kinds to deliver.
+    pub kinds: HashSet<StreamEventKind>,
+
+    // Minimum value in satoshis; events without a value do not match.
+    pub min_value: Option<u64>,
+}
+
+impl SubscriptionFilter {
+    /// Constructs a filter matching every event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a filter from a JSON object such as
+    /// `{"accounts": [npub|hex], "contracts": [hex], "kinds": ["move"], "min_value": 1000}`, where
+    /// every field is optional.
+    pub fn from_json(value: &Value) -> Result<Self, SubscriptionFilterError> {
+        // 1 The filter is a JSON object.
+        let obj = value
+            .as_object()
+            .ok_or(SubscriptionFilterError::MalformedFilter)?;
+
+        // 2 Parse the followed accounts.
+        let mut account_keys = HashSet::new();
+        for account in Self::strings(obj, "accounts")? {
+            let account_key = public_key::parse_account_key(account)
+                .map_err(|_| SubscriptionFilterError::InvalidAccountKey(account.to_string()))?;
+            account_keys.insert(account_key);
+        }
+
+        // 3 Parse the followed contracts.
+        let mut contract_ids = HashSet::new();
+        for contract in Self::strings(obj, "contracts")? {
+            let contract_id: ContractId = hex::decode(contract.trim().trim_start_matches("0x"))
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or(SubscriptionFilterError::InvalidContractId(
+                    contract.to_string(),
+                ))?;
+            contract_ids.insert(contract_id);
+        }
+
+        // 4 Check the number of followed keys.
+        let keys = account_keys.len() + contract_ids.len();
+        if keys > MAX_FILTER_KEYS {
+            return Err(SubscriptionFilterError::TooManyKeys(keys));
+        }
+
+        // 5 Parse the event kinds.
+        let mut kinds = HashSet::new();
+        for kind in Self::strings(obj, "kinds")? {
+            let kind = StreamEventKind::from_name(kind)
+                .ok_or(SubscriptionFilterError::UnknownEventKind(kind.to_string()))?;
+            kinds.insert(kind);
+        }
+
+        // 6 Parse the minimum value.
+        let min_value = match obj.get("min_value") {
+            Some(Value::Null) | None => None,
+            Some(min_value) => Some(
+                min_value
+                    .as_u64()
+                    .ok_or(SubscriptionFilterError::MalformedFilter)?,
+            ),
+        };
+
+        // 7 Return the filter.
+        Ok(Self {
+            account_keys,
+            contract_ids,
+            kinds,
+            min_value,
+        })
+    }
+
+    /// Returns the strings of an optional array field.
+    fn strings<'a>(
+        obj: &'a Map<String, Value>,
+        field: &str,
+    ) -> Result<Vec<&'a str>, SubscriptionFilterError> {
+        match obj.get(field) {
+            Some(Value::Null) | None => Ok(Vec::new()),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .ok_or(SubscriptionFilterError::MalformedFilter)
+                })
+                .collect(),
+            Some(_) => Err(SubscriptionFilterError::MalformedFilter),
+        }
+    }
+
+    /// Whether an event passes the filter.
+    pub fn matches(&self, event: &StreamEvent) -> bool {
+        // 1 Check the event kind.
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind) {
+            return false;
+        }
+
+        // 2 Check the minimum value.
+        if let Some(min_value) = self.min_value {
+            match event.value {
+                Some(value) if value >= min_value => (),
+                _ => return false,
+            }
+        }
+
+        // 3 Check the followed accounts and contracts.
+        if self.account_keys.is_empty() && self.contract_ids.is_empty() {
+            return true;
+        }
+        self.account_keys
+            .iter()
+            .any(|account_key| event.involves_account(account_key))
+            || self
+                .contract_ids
+                .iter()
+                .any(|contract_id| event.involves_contract(contract_id))
+    }
+
+    /// Returns the filter as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut account_keys: Vec<&AccountKey> = self.account_keys.iter().collect();
+        account_keys.sort();
+        let mut contract_ids: Vec<&ContractId> = self.contract_ids.iter().collect();
+        contract_ids.sort();
+        let mut kinds: Vec<String> = self.kinds.iter().map(|kind| kind.to_string()).collect();
+        kinds.sort();
+
+        let mut obj = Map::new();
+        obj.insert(
+            "accounts".to_string(),
+            Value::Array(
+                account_keys
+                    .into_iter()
+                    .map(|key| Value::String(key.to_npub().unwrap_or_else(|| hex::encode(key))))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "contracts".to_string(),
+            Value::Array(
+                contract_ids
+                    .into_iter()
+                    .map(|id| Value::String(hex::encode(id)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "kinds".to_string(),
+            Value::Array(kinds.into_iter().map(Value::String).collect()),
+        );
+        obj.insert(
+            "min_value".to_string(),
+            match self.min_value {
+                Some(min_value) => Value::Number(min_value.into()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::communicative::handshake::announcement::schnorr_signature_64;
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::{KeyHolder, ToNostrKeyStr};
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+/// A contract registered by a `Deploy` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractRegistration {
+    // The registered contract id.
+    pub contract_id: ContractId,
+
+    // The account that deployed the contract.
+    pub deployer_key: AccountKey,
+
+    // The program name.
+    pub program_name: String,
+
+    // Optional metadata of the program.
+    pub metadata: Option<Vec<u8>>,
+
+    // Number of methods of the program.
+    pub methods_len: u32,
+}
+
+/// A coordinator's signed notice that a contract was registered by an applied batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractNotice {
+    // The registered contract.
+    pub registration: ContractRegistration,
+
+    // Height of the batch that registered the contract.
+    pub batch_height: BatchHeight,
+
+    // Txid of the batch that registered the contract.
+    pub batch_txid: [u8; 32],
+
+    // The signing coordinator key.
+    pub coordinator_key: CoordinatorKey,
+
+    // The Schnorr signature over the notice message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl ContractNotice {
+    /// Signs a notice of a contract registered by the given batch with the coordinator's key.
+    pub fn sign(
+        keys: &KeyHolder,
+        registration: ContractRegistration,
+        batch_height: BatchHeight,
+        batch_txid: [u8; 32],
+    ) -> Option<Self> {
+        // 1 Construct the unsigned notice.
+        let mut notice = Self {
+            registration,
+            batch_height,
+            batch_txid,
+            coordinator_key: keys.secp_public_key_bytes(),
+            signature: [0u8; 64],
+        };
+
+        // 2 Sign the notice message.
+        notice.signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            notice.message(),
+            SchnorrSigningMode::Cube,
+        )?;
+
+        // 3 Return the notice.
+        Some(notice)
+    }
+
+    /// Returns the message the coordinator signs.
+    pub fn message(&self) -> [u8; 32] {
+        let registration = &self.registration;
+        let mut preimage = Vec::<u8>::new();
+        preimage.extend(registration.contract_id);
+        preimage.extend(registration.deployer_key);
+        preimage.extend((registration.program_name.len() as u32).to_be_bytes());
+        preimage.extend(registration.program_name.as_bytes());
+        match &registration.metadata {
+            Some(metadata) => {
+                preimage.push(0x01);
+                preimage.extend((metadata.len() as u32).to_be_bytes());
+                preimage.extend(metadata);
+            }
+            None => preimage.push(0x00),
+        }
+        preimage.extend(registration.methods_len.to_be_bytes());
+        preimage.extend(self.batch_height.to_be_bytes());
+        preimage.extend(self.batch_txid);
+        preimage.extend(self.coordinator_key);
+        preimage.hash(Some(HashTag::ContractNotice))
+    }
+
+    /// Verifies the notice against the signing coordinator key.
+    pub fn verify(&self) -> bool {
+        schnorr::verify_xonly(
+            self.coordinator_key,
+            self.message(),
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+
+    /// Serializes the notice to bincode bytes.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a notice from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(notice, _)| notice)
+    }
+
+    /// Returns the notice as a JSON object.
+    pub fn json(&self) -> Value {
+        let registration = &self.registration;
+        let mut obj = Map::new();
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(registration.contract_id)),
+        );
+        obj.insert(
+            "deployer".to_string(),
+            Value::String(
+                registration
+                    .deployer_key
+                    .to_npub()
+                    .unwrap_or_else(|| hex::encode(registration.deployer_key)),
+            ),
+        );
+        obj.insert(
+            "program_name".to_string(),
+            Value::String(registration.program_name.clone()),
+        );
+        obj.insert(
+            "metadata".to_string(),
+            match &registration.metadata {
+                Some(metadata) => Value::String(hex::encode(metadata)),
+                None => Value::Null,
+            },
+        );
+        obj.insert(
+            "methods_len".to_string(),
+            Value::Number(registration.methods_len.into()),
+        );
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height.into()),
+        );
+        obj.insert(
+            "batch_txid".to_string(),
+            Value::String(hex::encode(self.batch_txid)),
+        );
+        obj.insert(
+            "coordinator".to_string(),
+            Value::String(
+                self.coordinator_key
+                    .to_npub()
+                    .unwrap_or_else(|| hex::encode(self.coordinator_key)),
+            ),
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,336 @@
+use crate::communicative::federation::errors::delta_cosign_error::DeltaCosignError;
+use crate::communicative::federation::federation::Federation;
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::halfagg;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// Returns the message coordinators sign to attest that they applied the batch with the given txid at the given height.
+pub fn delta_attestation_message(batch_height: BatchHeight, batch_txid: [u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::<u8>::with_capacity(40);
+    preimage.extend(batch_height.to_be_bytes());
+    preimage.extend(batch_txid);
+    preimage.hash(Some(HashTag::DeltaAttestation))
+}
+
+/// A single coordinator's co-signature over an applied delta.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaCosignature {
+    // The co-signing coordinator key.
+    pub coordinator_key: CoordinatorKey,
+
+    // The Schnorr signature over the delta attestation message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl DeltaCosignature {
+    /// Co-signs the applied delta of the given batch with the coordinator's key.
+    pub fn sign(keys: &KeyHolder, batch_height: BatchHeight, batch_txid: [u8; 32]) -> Option<Self> {
+        let message = delta_attestation_message(batch_height, batch_txid);
+        let signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            message,
+            SchnorrSigningMode::Cube,
+        )?;
+
+        Some(Self {
+            coordinator_key: keys.secp_public_key_bytes(),
+            signature,
+        })
+    }
+
+    /// Verifies the co-signature against the given batch.
+    pub fn verify(&self, batch_height: BatchHeight, batch_txid: [u8; 32]) -> bool {
+        let message = delta_attestation_message(batch_height, batch_txid);
+        schnorr::verify_xonly(
+            self.coordinator_key,
+            message,
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+}
+
+/// Co-signatures collected from federation coordinators over an applied delta.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaAttestation {
+    // Batch height.
+    pub batch_height: BatchHeight,
+
+    // Batch txid.
+    pub batch_txid: [u8; 32],
+
+    // Collected co-signatures.
+    pub cosignatures: Vec<DeltaCosignature>,
+}
+
+impl DeltaAttestation {
+    /// Constructs a fresh new attestation with no co-signatures.
+    pub fn new(batch_height: BatchHeight, batch_txid: [u8; 32]) -> Self {
+        Self {
+            batch_height,
+            batch_txid,
+            cosignatures: Vec::new(),
+        }
+    }
+
+    /// Adds a co-signature after checking membership, validity and uniqueness.
+    pub fn add_cosignature(
+        &mut self,
+        federation: &Federation,
+        cosignature: DeltaCosignature,
+    ) -> Result<(), DeltaCosignError> {
+        // 1 Check if the co-signer is a coordinator of the federation.
+        if !federation.is_coordinator(cosignature.coordinator_key) {
+            return Err(DeltaCosignError::NotACoordinator(
+                cosignature.coordinator_key,
+            ));
+        }
+
+        // 2 Check if the coordinator has already co-signed.
+        if self
+            .cosignatures
+            .iter()
+            .any(|existing| existing.coordinator_key == cosignature.coordinator_key)
+        {
+            return Err(DeltaCosignError::DuplicateCosignature(
+                cosignature.coordinator_key,
+            ));
+        }
+
+        // 3 Verify the co-signature.
+        if !cosignature.verify(self.batch_height, self.batch_txid) {
+            return Err(DeltaCosignError::InvalidCosignature(
+                cosignature.coordinator_key,
+            ));
+        }
+
+        // 4 Add the co-signature.
+        self.cosignatures.push(cosignature);
+
+        // 5 Return success.
+        Ok(())
+    }
+
+    /// Checks if the attestation carries a quorum of valid co-signatures from distinct coordinators
+    /// for the given batch.
+    ///
+    /// NOTE: Co-signatures are re-verified since the attestation may come from an untrusted peer.
+    pub fn has_quorum(
+        &self,
+        federation: &Federation,
+        batch_height: BatchHeight,
+        batch_txid: [u8; 32],
+    ) -> bool {
+        // 1 Check if the attestation is for the given batch.
+        if self.batch_height != batch_height || self.batch_txid != batch_txid {
+            return false;
+        }
+
+        // 2 Count the valid co-signatures from distinct coordinators.
+        let mut counted_coordinator_keys = Vec::<CoordinatorKey>::new();
+        for cosignature in self.cosignatures.iter() {
+            if !federation.is_coordinator(cosignature.coordinator_key)
+                || counted_coordinator_keys.contains(&cosignature.coordinator_key)
+                || !cosignature.verify(batch_height, batch_txid)
+            {
+                continue;
+            }
+            counted_coordinator_keys.push(cosignature.coordinator_key);
+        }
+
+        // 3 Compare with the threshold.
+        counted_coordinator_keys.len() >= federation.threshold()
+    }
+
+    /// Half-aggregates the co-signatures into a single aggregate signature.
+    ///
+    /// Returns `None` if there is no co-signature yet.
+    pub fn half_aggregate(&self) -> Option<AggregatedDeltaAttestation> {
+        let message = delta_attestation_message(self.batch_height, self.batch_txid);
+
+        // 1 Collect the co-signatures with their keys and the common message.
+        let signatures: Vec<([u8; 32], [u8; 32], [u8; 64])> = self
+            .cosignatures
+            .iter()
+            .map(|cosignature| (cosignature.coordinator_key, message, cosignature.signature))
+            .collect();
+
+        // 2 Half-aggregate them.
+        let aggregate_signature = halfagg::aggregate(&signatures)?;
+
+        Some(AggregatedDeltaAttestation {
+            batch_height: self.batch_height,
+            batch_txid: self.batch_txid,
+            coordinator_keys: self
+                .cosignatures
+                .iter()
+                .map(|cosignature| cosignature.coordinator_key)
+                .collect(),
+            aggregate_signature,
+        })
+    }
+
+    /// Returns the attestation as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height.into()),
+        );
+        obj.insert(
+            "batch_txid".to_string(),
+            Value::String(hex::encode(self.batch_txid)),
+        );
+        obj.insert(
+            "cosignatures".to_string(),
+            Value::Array(
+                self.cosignatures
+                    .iter()
+                    .map(|cosignature| {
+                        let mut cosignature_obj = Map::new();
+                        cosignature_obj.insert(
+                            "coordinator_key".to_string(),
+                            Value::String(hex::encode(cosignature.coordinator_key)),
+                        );
+                        cosignature_obj.insert(
+                            "signature".to_string(),
+                            Value::String(hex::encode(cosignature.signature)),
+                        );
+                        Value::Object(cosignature_obj)
+                    })
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Coordinator co-signatures over an applied delta, half-aggregated into a single signature.
+///
+/// Carries `32 * (n + 1)` signature bytes instead of `64 * n`, verified at once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregatedDeltaAttestation {
+    // Batch height.
+    pub batch_height: BatchHeight,
+
+    // Batch txid.
+    pub batch_txid: [u8; 32],
+
+    // Co-signing coordinator keys, in the order of aggregation.
+    pub coordinator_keys: Vec<CoordinatorKey>,
+
+    // The half-aggregate of the co-signatures.
+    pub aggregate_signature: Vec<u8>,
+}
+
+impl AggregatedDeltaAttestation {
+    /// Checks if the aggregate carries a quorum of co-signatures from distinct coordinators for the
+    /// given batch.
+    ///
+    /// NOTE: Unlike individual co-signatures, a single invalid or non-coordinator co-signature
+    /// invalidates the whole aggregate.
+    pub fn has_quorum(
+        &self,
+        federation: &Federation,
+        batch_height: BatchHeight,
+        batch_txid: [u8; 32],
+    ) -> bool {
+        // 1 Check if the attestation is for the given batch.
+        if self.batch_height != batch_height || self.batch_txid != batch_txid {
+            return false;
+        }
+
+        // 2 Check that the co-signers are distinct coordinators.
+        let mut counted_coordinator_keys = Vec::<CoordinatorKey>::new();
+        for coordinator_key in self.coordinator_keys.iter() {
+            if !federation.is_coordinator(*coordinator_key)
+                || counted_coordinator_keys.contains(coordinator_key)
+            {
+                return false;
+            }
+            counted_coordinator_keys.push(*coordinator_key);
+        }
+
+        // 3 Compare with the threshold.
+        if counted_coordinator_keys.len() < federation.threshold() {
+            return false;
+        }
+
+        // 4 Verify the aggregate signature.
+        let message = delta_attestation_message(batch_height, batch_txid);
+        let public_keys_and_messages: Vec<([u8; 32], [u8; 32])> = self
+            .coordinator_keys
+            .iter()
+            .map(|coordinator_key| (*coordinator_key, message))
+            .collect();
+        halfagg::verify_aggregate(
+            &public_keys_and_messages,
+            &self.aggregate_signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+
+    /// Returns the aggregated attestation as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height.into()),
+        );
+        obj.insert(
+            "batch_txid".to_string(),
+            Value::String(hex::encode(self.batch_txid)),
+        );
+        obj.insert(
+            "coordinator_keys".to_string(),
+            Value::Array(
+                self.coordinator_keys
+                    .iter()
+                    .map(|coordinator_key| Value::String(hex::encode(coordinator_key)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "aggregate_signature".to_string(),
+            Value::String(hex::encode(&self.aggregate_signature)),
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,167 @@
+use crate::communicative::federation::delta_attestation::{
+    AggregatedDeltaAttestation, DeltaAttestation, DeltaCosignature,
+};
+use crate::communicative::federation::errors::delta_cosign_error::DeltaCosignError;
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Number of most recent batch heights whose attestations are retained in memory.
+const DELTA_ATTESTATION_RETENTION: u64 = 1_024;
+
+/// In-memory pool of delta attestations collected by the engine, keyed by batch height.
+pub struct DeltaAttestationPool {
+    // The federation whose coordinators co-sign the deltas.
+    federation: Federation,
+
+    // Attestations by batch height.
+    attestations: HashMap<BatchHeight, DeltaAttestation>,
+
+    // State root co-signatures by batch height.
+    state_root_cosignatures: HashMap<BatchHeight, Vec<StateRootCosignature>>,
+}
+
+/// Guarded 'DeltaAttestationPool'.
+#[allow(non_camel_case_types)]
+pub type DELTA_ATTESTATION_POOL = Arc<Mutex<DeltaAttestationPool>>;
+
+impl DeltaAttestationPool {
+    /// Constructs a fresh new delta attestation pool.
+    pub fn new(federation: Federation) -> DELTA_ATTESTATION_POOL {
+        let pool = DeltaAttestationPool {
+            federation,
+            attestations: HashMap::new(),
+            state_root_cosignatures: HashMap::new(),
+        };
+        Arc::new(Mutex::new(pool))
+    }
+
+    /// Returns the federation.
+    pub fn federation(&self) -> &Federation {
+        &self.federation
+    }
+
+    /// Returns the attestation for a given batch height.
+    pub fn attestation(&self, batch_height: BatchHeight) -> Option<DeltaAttestation> {
+        self.attestations.get(&batch_height).cloned()
+    }
+
+    /// Returns the attestation for a given batch height, with its co-signatures half-aggregated.
+    pub fn aggregated_attestation(
+        &self,
+        batch_height: BatchHeight,
+    ) -> Option<AggregatedDeltaAttestation> {
+        self.attestations
+            .get(&batch_height)
+            .and_then(|attestation| attestation.half_aggregate())
+    }
+
+    /// Adds a co-signature to the attestation of the given batch.
+    ///
+    /// Returns whether the attestation has reached a quorum.
+    pub fn insert_cosignature(
+        &mut self,
+        batch_height: BatchHeight,
+        batch_txid: [u8; 32],
+        cosignature: DeltaCosignature,
+    ) -> Result<bool, DeltaCosignError> {
+        // 1 Get or create the attestation for the batch height.
+        let attestation = self
+            .attestations
+            .entry(batch_height)
+            .or_insert_with(|| DeltaAttestation::new(batch_height, batch_txid));
+
+        // 2 Check that the co-signature is for the same batch.
+        if attestation.batch_txid != batch_txid {
+            return Err(DeltaCosignError::BatchMismatch(batch_height));
+        }
+
+        // 3 Add the co-signature.
+        attestation.add_cosignature(&self.federation, cosignature)?;
+
+        // 4 Check the quorum.
+        let has_quorum = attestation.has_quorum(&self.federation, batch_height, batch_txid);
+
+        // 5 Prune the attestations that fell out of the retention window.
+        if batch_height > DELTA_ATTESTATION_RETENTION {
+            let retention_floor = batch_height - DELTA_ATTESTATION_RETENTION;
+            self.attestations
+                .retain(|height, _| *height > retention_floor);
+        }
+
+        // 6 Return the quorum status.
+        Ok(has_quorum)
+    }
+
+    /// Returns the co-signatures over the given state root at the given batch height.
+    pub fn state_root_cosignatures(
+        &self,
+        batch_height: BatchHeight,
+        state_root: [u8; 32],
+    ) -> Vec<StateRootCosignature> {
+        self.state_root_cosignatures
+            .get(&batch_height)
+            .map(|cosignatures| {
+                cosignatures
+                    .iter()
+                    .filter(|cosignature| cosignature.state_root == state_root)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Adds a co-signature over the state root reached at the given batch height, after checking
+    /// membership, validity and uniqueness.
+    pub fn insert_state_root_cosignature(
+        &mut self,
+        batch_height: BatchHeight,
+        cosignature: StateRootCosignature,
+    ) -> Result<(), DeltaCosignError> {
+        // 1 Check if the co-signer is a coordinator of the federation.
+        if !self.federation.is_coordinator(cosignature.coordinator_key) {
+            return Err(DeltaCosignError::NotACoordinator(
+                cosignature.coordinator_key,
+            ));
+        }
+
+        // 2 Check if the coordinator has already co-signed the state root at this height.
+        let cosignatures = self
+            .state_root_cosignatures
+            .entry(batch_height)
+            .or_default();
+        if cosignatures
+            .iter()
+            .any(|existing| existing.coordinator_key == cosignature.coordinator_key)
+        {
+            return Err(DeltaCosignError::DuplicateCosignature(
+                cosignature.coordinator_key,
+            ));
+        }
+
+        // 3 Verify the co-signature.
+        if !cosignature.verify(batch_height) {
+            return Err(DeltaCosignError::InvalidCosignature(
+                cosignature.coordinator_key,
+            ));
+        }
+
+        // 4 Add the co-signature.
+        cosignatures.push(cosignature);
+
+        // 5 Prune the co-signatures that fell out of the retention window.
+        if batch_height > DELTA_ATTESTATION_RETENTION {
+            let retention_floor = batch_height - DELTA_ATTESTATION_RETENTION;
+            self.state_root_cosignatures
+                .retain(|height, _| *height > retention_floor);
+        }
+
+        // 6 Return success.
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::communicative::federation::errors::dkg_error::DkgError;
+use crate::communicative::federation::federation::Federation;
+use crate::transmutative::secp::schnorr;
+use crate::transmutative::secp::vss::{self, VssCommitment};
+use secp::{MaybePoint, MaybeScalar, Point, Scalar};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+/// Shares dealt to each coordinator.
+type DealtShares = Vec<(CoordinatorKey, [u8; 32])>;
+
+/// A coordinator's dealing in the federation distributed key generation.
+///
+/// Each coordinator shares a random secret among all coordinators with the federation threshold,
+/// broadcasts the commitment to its sharing polynomial and sends each coordinator their share privately.
+/// Once every coordinator has verified the shares they received, the group key is the sum of the dealt
+/// secret commitments and each coordinator's group share is the sum of their received shares.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgCommitment {
+    // The dealing coordinator key.
+    pub dealer_key: CoordinatorKey,
+
+    // The commitment to the dealer's sharing polynomial.
+    pub commitment: VssCommitment,
+}
+
+impl DkgCommitment {
+    /// Deals a fresh random secret among the coordinators of the federation.
+    ///
+    /// Returns the commitment to broadcast and the share of each coordinator, including the dealer's own.
+    ///
+    /// NOTE: Shares are secret and must only travel over an encrypted channel.
+    pub fn deal(
+        federation: &Federation,
+        dealer_key: CoordinatorKey,
+    ) -> Result<(Self, DealtShares), DkgError> {
+        // 1 Check if the dealer is a coordinator of the federation.
+        if !federation.is_coordinator(dealer_key) {
+            return Err(DkgError::NotACoordinator(dealer_key));
+        }
+
+        // 2 Share a random secret with the federation threshold.
+        let (commitment, shares) = vss::deal(
+            schnorr::generate_scalar(),
+            federation.threshold(),
+            federation.coordinator_keys().len(),
+        )
+        .ok_or(DkgError::DealingFailed)?;
+
+        // 3 Address the shares in coordinator order.
+        let shares = federation
+            .coordinator_keys()
+            .iter()
+            .zip(shares.iter())
+            .map(|(coordinator_key, share)| (*coordinator_key, share.serialize()))
+            .collect();
+
+        Ok((
+            Self {
+                dealer_key,
+                commitment,
+            },
+            shares,
+        ))
+    }
+
+    /// Verifies a share dealt to the given receiving coordinator against the commitment.
+    pub fn verify_share(
+        &self,
+        federation: &Federation,
+        receiver_key: CoordinatorKey,
+        share: [u8; 32],
+    ) -> Result<(), DkgError> {
+        // 1 Check if the dealer is a coordinator of the federation.
+        if !federation.is_coordinator(self.dealer_key) {
+            return Err(DkgError::NotACoordinator(self.dealer_key));
+        }
+
+        // 2 Check if the dealing uses the federation threshold.
+        if self.commitment.threshold() != federation.threshold() {
+            return Err(DkgError::ThresholdMismatch(self.commitment.threshold()));
+        }
+
+        // 3 Look up the receiver's share index.
+        let index = federation
+            .participant_index(receiver_key)
+            .ok_or(DkgError::NotACoordinator(receiver_key))?;
+
+        // 4 Verify the share.
+        let share =
+            Scalar::from_slice(&share).map_err(|_| DkgError::InvalidShare(self.dealer_key))?;
+        if !self.commitment.verify_share(index, share) {
+            return Err(DkgError::InvalidShare(self.dealer_key));
+        }
+
+        // 5 Return success.
+        Ok(())
+    }
+
+    /// Serializes the commitment for the messaging layer.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a commitment received over the messaging layer.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(commitment, _)| commitment)
+    }
+
+    /// Returns the commitment as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "dealer_key".to_string(),
+            Value::String(hex::encode(self.dealer_key)),
+        );
+        obj.insert(
+            "coefficient_commitments".to_string(),
+            Value::Array(
+                self.commitment
+                    .coefficient_commitments()
+                    .iter()
+                    .map(|coefficient_commitment| {
+                        Value::String(hex::encode(coefficient_commitment.serialize()))
+                    })
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Returns the group key of a completed key generation, the sum of the dealt secret commitments.
+///
+/// Every coordinator of the federation must have dealt exactly once.
+pub fn group_key(
+    federation: &Federation,
+    commitments: &[DkgCommitment],
+) -> Result<Point, DkgError> {
+    // 1 Check that each coordinator dealt exactly once.
+    for coordinator_key in federation.coordinator_keys().iter() {
+        match commitments
+            .iter()
+            .filter(|commitment| commitment.dealer_key == *coordinator_key)
+            .count()
+        {
+            0 => return Err(DkgError::MissingDealing(*coordinator_key)),
+            1 => {}
+            _ => return Err(DkgError::DuplicateDealing(*coordinator_key)),
+        }
+    }
+    for commitment in commitments.iter() {
+        if !federation.is_coordinator(commitment.dealer_key) {
+            return Err(DkgError::NotACoordinator(commitment.dealer_key));
+        }
+    }
+
+    // 2 Sum the secret commitments.
+    let mut group_key = MaybePoint::Infinity;
+    for commitment in commitments.iter() {
+        group_key += commitment.commitment.secret_commitment();
+    }
+
+    group_key.not_inf().map_err(|_| DkgError::DealingFailed)
+}
+
+/// Returns a coordinator's group share, the sum of the verified shares they received from every dealer.
+pub fn group_share(received_shares: &[[u8; 32]]) -> Result<[u8; 32], DkgError> {
+    // 1 Sum the received shares.
+    let mut group_share = MaybeScalar::Zero;
+    for share in received_shares.iter() {
+        group_share += MaybeScalar::from_slice(share).map_err(|_| DkgError::DealingFailed)?;
+    }
+
+    // 2 Return the group share.
+    match group_share {
+        MaybeScalar::Valid(group_share) => Ok(group_share.serialize()),
+        MaybeScalar::Zero => Err(DkgError::DealingFailed),
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+/// Errors associated with adding a co-signature to a delta attestation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeltaCosignError {
+    NotACoordinator(CoordinatorKey),
+    InvalidCosignature(CoordinatorKey),
+    DuplicateCosignature(CoordinatorKey),
+    BatchMismatch(BatchHeight),
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+/// Errors associated with the federation distributed key generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DkgError {
+    NotACoordinator(CoordinatorKey),
+    ThresholdMismatch(usize),
+    InvalidShare(CoordinatorKey),
+    MissingDealing(CoordinatorKey),
+    DuplicateDealing(CoordinatorKey),
+    DealingFailed,
+}
@@ -0,0 +1,2 @@
+pub mod delta_cosign_error;
+pub mod dkg_error;
@@ -0,0 +1,82 @@
+use crate::inscriptive::baked;
+use crate::operative::run_args::chain::Chain;
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+/// A set of coordinators that co-sign applied deltas, along with the number of
+/// co-signatures required for a delta to be accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Federation {
+    // Coordinator keys.
+    coordinator_keys: Vec<CoordinatorKey>,
+
+    // Number of co-signatures required for a quorum.
+    threshold: usize,
+}
+
+impl Federation {
+    /// Constructs a federation.
+    ///
+    /// Returns `None` if the threshold is zero, exceeds the number of coordinators,
+    /// or if a coordinator key is listed more than once.
+    pub fn new(coordinator_keys: Vec<CoordinatorKey>, threshold: usize) -> Option<Self> {
+        // 1 Check the threshold bounds.
+        if threshold == 0 || threshold > coordinator_keys.len() {
+            return None;
+        }
+
+        // 2 Check for duplicate coordinator keys.
+        for (index, coordinator_key) in coordinator_keys.iter().enumerate() {
+            if coordinator_keys[..index].contains(coordinator_key) {
+                return None;
+            }
+        }
+
+        // 3 Return the federation.
+        Some(Self {
+            coordinator_keys,
+            threshold,
+        })
+    }
+
+    /// Returns the baked federation of the given chain, or `None` if the chain runs a single coordinator.
+    pub fn for_chain(chain: Chain) -> Option<Self> {
+        let (coordinator_keys, threshold) = match chain {
+            Chain::Signet | Chain::Testbed => (
+                baked::SIGNET_FEDERATION_COORDINATOR_KEYS,
+                baked::SIGNET_FEDERATION_THRESHOLD,
+            ),
+            Chain::Mainnet => (
+                baked::MAINNET_FEDERATION_COORDINATOR_KEYS,
+                baked::MAINNET_FEDERATION_THRESHOLD,
+            ),
+        };
+
+        Self::new(coordinator_keys.to_vec(), threshold)
+    }
+
+    /// Returns the coordinator keys.
+    pub fn coordinator_keys(&self) -> &Vec<CoordinatorKey> {
+        &self.coordinator_keys
+    }
+
+    /// Returns the number of co-signatures required for a quorum.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Checks if the given key belongs to a coordinator of the federation.
+    pub fn is_coordinator(&self, key: CoordinatorKey) -> bool {
+        self.coordinator_keys.contains(&key)
+    }
+
+    /// Returns the secret sharing index of the given coordinator, its position in the coordinator keys
+    /// starting at `1`, or `None` if the key does not belong to a coordinator.
+    pub fn participant_index(&self, key: CoordinatorKey) -> Option<u32> {
+        self.coordinator_keys
+            .iter()
+            .position(|coordinator_key| *coordinator_key == key)
+            .map(|position| position as u32 + 1)
+    }
+}
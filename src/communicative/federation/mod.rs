@@ -0,0 +1,7 @@
+pub mod contract_notice;
+pub mod delta_attestation;
+pub mod delta_attestation_pool;
+pub mod dkg;
+pub mod errors;
+pub mod federation;
+pub mod state_root_cosignature;
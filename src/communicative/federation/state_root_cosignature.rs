@@ -0,0 +1,100 @@
+use crate::communicative::tcp::protocol::balance_proof::state_root_commitment_message;
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// A single coordinator's co-signature over the account balances state root it reached by applying
+/// a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateRootCosignature {
+    // The co-signing coordinator key.
+    pub coordinator_key: CoordinatorKey,
+
+    // The co-signed state root.
+    pub state_root: [u8; 32],
+
+    // The Schnorr signature over the state root commitment message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl StateRootCosignature {
+    /// Co-signs the state root reached at the given batch height with the coordinator's key.
+    pub fn sign(keys: &KeyHolder, batch_height: BatchHeight, state_root: [u8; 32]) -> Option<Self> {
+        let message = state_root_commitment_message(batch_height, state_root);
+        let signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            message,
+            SchnorrSigningMode::Cube,
+        )?;
+
+        Some(Self {
+            coordinator_key: keys.secp_public_key_bytes(),
+            state_root,
+            signature,
+        })
+    }
+
+    /// Verifies the co-signature against the given batch height.
+    pub fn verify(&self, batch_height: BatchHeight) -> bool {
+        let message = state_root_commitment_message(batch_height, self.state_root);
+        schnorr::verify_xonly(
+            self.coordinator_key,
+            message,
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+
+    /// Returns the co-signature as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "coordinator_key".to_string(),
+            Value::String(hex::encode(self.coordinator_key)),
+        );
+        obj.insert(
+            "state_root".to_string(),
+            Value::String(hex::encode(self.state_root)),
+        );
+        obj.insert(
+            "signature".to_string(),
+            Value::String(hex::encode(self.signature)),
+        );
+        Value::Object(obj)
+    }
+}
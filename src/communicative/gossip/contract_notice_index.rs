@@ -0,0 +1,180 @@
+use crate::communicative::federation::contract_notice::ContractNotice;
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::gossip::errors::contract_notice_error::ContractNoticeError;
+use crate::operative::run_args::chain::Chain;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Maximum number of notices listed at once.
+pub const CONTRACT_NOTICE_LIST_LIMIT: usize = 500;
+
+/// Persistent index of the contract registration notices broadcast by federation coordinators,
+/// keyed by contract id.
+///
+/// Every coordinator broadcasts its own notice of a registration, so only the first valid notice of a
+/// contract is indexed. Notices signed by this node as a coordinator are also queued for publishing.
+pub struct ContractNoticeIndex {
+    // The federation whose coordinators sign the notices.
+    federation: Federation,
+
+    // In-memory indexed notices.
+    notices: HashMap<ContractId, ContractNotice>,
+
+    // Ids of the contracts whose notices are yet to be published, in order of signing.
+    pending_publish: Vec<ContractId>,
+
+    // On-disk indexed notices.
+    on_disk_notices: sled::Tree,
+}
+
+/// Guarded 'ContractNoticeIndex'.
+#[allow(non_camel_case_types)]
+pub type CONTRACT_NOTICE_INDEX = Arc<Mutex<ContractNoticeIndex>>;
+
+impl ContractNoticeIndex {
+    /// Constructs the contract notice index, loading the indexed notices from disk.
+    pub fn new(chain: Chain, federation: Federation) -> Result<CONTRACT_NOTICE_INDEX, sled::Error> {
+        // 1 Open the contract notice db.
+        let db_path = format!("storage/{}/contract_notices", chain.to_string());
+        let db = sled::open(db_path)?;
+
+        // 2 Open the notices tree.
+        let on_disk_notices = db.open_tree("notices")?;
+
+        // 3 Load the indexed notices, skipping corrupt ones.
+        let notices = on_disk_notices
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter_map(|(_, value)| ContractNotice::deserialize(value.as_ref()))
+            .map(|notice| (notice.registration.contract_id, notice))
+            .collect();
+
+        // 4 Construct the contract notice index.
+        let index = ContractNoticeIndex {
+            federation,
+            notices,
+            pending_publish: Vec::new(),
+            on_disk_notices,
+        };
+
+        // 5 Guard and return the contract notice index.
+        Ok(Arc::new(Mutex::new(index)))
+    }
+
+    /// Validates a notice and indexes it. Returns false if the contract was already indexed.
+    pub fn insert(&mut self, notice: ContractNotice) -> Result<bool, ContractNoticeError> {
+        let contract_id = notice.registration.contract_id;
+
+        // 1 Deduplicate before verifying, since notices of the other coordinators are the common case.
+        if let Some(indexed) = self.notices.get(&contract_id) {
+            if indexed.registration == notice.registration
+                && indexed.batch_height == notice.batch_height
+                && indexed.batch_txid == notice.batch_txid
+            {
+                return Ok(false);
+            }
+        }
+
+        // 2 Check if the signer is a coordinator of the federation.
+        if !self.federation.is_coordinator(notice.coordinator_key) {
+            return Err(ContractNoticeError::NotACoordinator(notice.coordinator_key));
+        }
+
+        // 3 Verify the notice.
+        if !notice.verify() {
+            return Err(ContractNoticeError::InvalidSignature(contract_id));
+        }
+
+        // 4 A contract is registered once, so a differing notice means coordinators equivocated;
+        // keep the first one indexed.
+        if self.notices.contains_key(&contract_id) {
+            return Err(ContractNoticeError::ConflictingNotice(contract_id));
+        }
+
+        // 5 Persist and index the notice.
+        let bytes = notice
+            .serialize()
+            .ok_or(ContractNoticeError::SerializeError(contract_id))?;
+        self.on_disk_notices
+            .insert(contract_id, bytes)
+            .map_err(|err| ContractNoticeError::DBInsertError(err.to_string()))?;
+        self.notices.insert(contract_id, notice);
+
+        Ok(true)
+    }
+
+    /// Indexes a notice signed by this node and queues it for publishing.
+    pub fn insert_signed(&mut self, notice: ContractNotice) -> Result<bool, ContractNoticeError> {
+        let contract_id = notice.registration.contract_id;
+        let inserted = self.insert(notice)?;
+        if inserted {
+            self.pending_publish.push(contract_id);
+        }
+        Ok(inserted)
+    }
+
+    /// Takes the notices signed by this node yet to be published.
+    pub fn take_pending_publish(&mut self) -> Vec<ContractNotice> {
+        let pending_publish: Vec<ContractId> = self.pending_publish.drain(..).collect();
+        pending_publish
+            .iter()
+            .filter_map(|contract_id| self.notices.get(contract_id).cloned())
+            .collect()
+    }
+
+    /// Returns the indexed notice of a contract, if any.
+    pub fn notice(&self, contract_id: ContractId) -> Option<&ContractNotice> {
+        self.notices.get(&contract_id)
+    }
+
+    /// Returns the notices of the contracts registered at or above the given batch height, by batch
+    /// height, up to the list limit.
+    pub fn notices_since(&self, batch_height: BatchHeight) -> Vec<&ContractNotice> {
+        let mut notices: Vec<&ContractNotice> = self
+            .notices
+            .values()
+            .filter(|notice| notice.batch_height >= batch_height)
+            .collect();
+        notices.sort_by_key(|notice| (notice.batch_height, notice.registration.contract_id));
+        notices.truncate(CONTRACT_NOTICE_LIST_LIMIT);
+        notices
+    }
+
+    /// Returns the number of indexed notices.
+    pub fn len(&self) -> usize {
+        self.notices.len()
+    }
+
+    /// Whether no notice is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.notices.is_empty()
+    }
+
+    /// Returns the notices of the contracts registered at or above the given batch height as a JSON
+    /// array.
+    pub fn json(&self, batch_height: BatchHeight) -> Value {
+        Value::Array(
+            self.notices_since(batch_height)
+                .iter()
+                .map(|notice| notice.json())
+                .collect(),
+        )
+    }
+}
+
+/// Erases the contract notice index by db path.
+pub fn erase_contract_notice_index(chain: Chain) {
+    // Contract notice db path.
+    let db_path = format!("storage/{}/contract_notices", chain.to_string());
+
+    // Erase the contract notice db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
@@ -0,0 +1,134 @@
+use crate::communicative::federation::delta_attestation::AggregatedDeltaAttestation;
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::gossip::errors::delta_gossip_error::DeltaGossipError;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Number of most recent batch heights whose gossiped attestations are retained in memory.
+pub const DELTA_GOSSIP_RETENTION: u64 = 1_024;
+
+/// In-memory pool of the delta attestations a node learned, either from the engine or gossiped by
+/// other nodes, keyed by batch height.
+///
+/// Only attestations carrying a quorum of coordinator co-signatures are admitted, and each is admitted
+/// once, so that a node relays every applied delta at most once.
+pub struct DeltaGossipPool {
+    // The federation whose coordinators co-sign the deltas.
+    federation: Federation,
+
+    // Admitted attestations by batch height.
+    attestations: HashMap<BatchHeight, AggregatedDeltaAttestation>,
+
+    // Heights of the admitted attestations yet to be relayed, in order of admission.
+    pending_relay: Vec<BatchHeight>,
+
+    // The highest admitted batch height.
+    highest_batch_height: BatchHeight,
+}
+
+/// Guarded 'DeltaGossipPool'.
+#[allow(non_camel_case_types)]
+pub type DELTA_GOSSIP_POOL = Arc<Mutex<DeltaGossipPool>>;
+
+impl DeltaGossipPool {
+    /// Constructs a fresh new delta gossip pool.
+    pub fn new(federation: Federation) -> DELTA_GOSSIP_POOL {
+        let pool = DeltaGossipPool {
+            federation,
+            attestations: HashMap::new(),
+            pending_relay: Vec::new(),
+            highest_batch_height: 0,
+        };
+        Arc::new(Mutex::new(pool))
+    }
+
+    /// Returns the federation.
+    pub fn federation(&self) -> &Federation {
+        &self.federation
+    }
+
+    /// Validates an attestation and admits it for relay, unless it was admitted before.
+    pub fn insert(
+        &mut self,
+        attestation: AggregatedDeltaAttestation,
+    ) -> Result<(), DeltaGossipError> {
+        let batch_height = attestation.batch_height;
+
+        // 1 Ignore attestations that fell out of the retention window.
+        if batch_height + DELTA_GOSSIP_RETENTION <= self.highest_batch_height {
+            return Err(DeltaGossipError::BelowRetention(batch_height));
+        }
+
+        // 2 Deduplicate before verifying, since re-gossiped attestations are the common case.
+        let conflicting = match self.attestations.get(&batch_height) {
+            Some(admitted) if admitted.batch_txid == attestation.batch_txid => {
+                return Err(DeltaGossipError::AlreadySeen(batch_height));
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        // 3 Verify the coordinator quorum.
+        if !attestation.has_quorum(&self.federation, batch_height, attestation.batch_txid) {
+            return Err(DeltaGossipError::NoQuorum(batch_height));
+        }
+
+        // 4 A quorum over another batch at the same height means coordinators equivocated; keep the
+        // first one admitted.
+        if conflicting {
+            return Err(DeltaGossipError::ConflictingBatch(batch_height));
+        }
+
+        // 5 Admit the attestation.
+        self.attestations.insert(batch_height, attestation);
+        self.pending_relay.push(batch_height);
+
+        // 6 Prune the attestations that fell out of the retention window.
+        if batch_height > self.highest_batch_height {
+            self.highest_batch_height = batch_height;
+            if batch_height > DELTA_GOSSIP_RETENTION {
+                let retention_floor = batch_height - DELTA_GOSSIP_RETENTION;
+                self.attestations
+                    .retain(|height, _| *height > retention_floor);
+                self.pending_relay
+                    .retain(|height| *height > retention_floor);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the admitted attestation for the given batch, if any.
+    pub fn attestation(
+        &self,
+        batch_height: BatchHeight,
+        batch_txid: [u8; 32],
+    ) -> Option<AggregatedDeltaAttestation> {
+        self.attestations
+            .get(&batch_height)
+            .filter(|attestation| attestation.batch_txid == batch_txid)
+            .cloned()
+    }
+
+    /// Takes the admitted attestations yet to be relayed.
+    pub fn take_pending_relay(&mut self) -> Vec<AggregatedDeltaAttestation> {
+        self.pending_relay
+            .drain(..)
+            .filter_map(|height| self.attestations.get(&height).cloned())
+            .collect()
+    }
+
+    /// Returns the number of admitted attestations.
+    pub fn len(&self) -> usize {
+        self.attestations.len()
+    }
+
+    /// Whether no attestation was admitted.
+    pub fn is_empty(&self) -> bool {
+        self.attestations.is_empty()
+    }
+}
@@ -0,0 +1,15 @@
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Coordinator key.
+type CoordinatorKey = [u8; 32];
+
+/// Errors indexing a contract registration notice.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractNoticeError {
+    NotACoordinator(CoordinatorKey),
+    InvalidSignature(ContractId),
+    ConflictingNotice(ContractId),
+    SerializeError(ContractId),
+    DBInsertError(String),
+}
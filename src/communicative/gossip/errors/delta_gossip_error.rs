@@ -0,0 +1,11 @@
+/// Batch height.
+type BatchHeight = u64;
+
+/// Errors admitting a gossiped delta attestation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DeltaGossipError {
+    AlreadySeen(BatchHeight),
+    BelowRetention(BatchHeight),
+    NoQuorum(BatchHeight),
+    ConflictingBatch(BatchHeight),
+}
@@ -0,0 +1,2 @@
+pub mod contract_notice_error;
+pub mod delta_gossip_error;
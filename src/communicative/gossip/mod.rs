@@ -0,0 +1,3 @@
+pub mod contract_notice_index;
+pub mod delta_gossip_pool;
+pub mod errors;
@@ -0,0 +1,100 @@
+use crate::communicative::event_stream::errors::subscription_filter_error::SubscriptionFilterError;
+use crate::communicative::event_stream::event_stream::SubscriptionMessage;
+use crate::communicative::event_stream::stream_event::{StreamEvent, StreamEventKind};
+use crate::communicative::event_stream::subscription_filter::{
+    SubscriptionFilter, MAX_FILTER_KEYS,
+};
+use crate::communicative::grpc::messages::{self, subscribe_reply, EventKind, SubscribeRequest};
+
+/// Returns the protobuf kind of a stream event kind.
+pub fn event_kind(kind: StreamEventKind) -> EventKind {
+    match kind {
+        StreamEventKind::Move => EventKind::Move,
+        StreamEventKind::Call => EventKind::Call,
+        StreamEventKind::Liftup => EventKind::Liftup,
+        StreamEventKind::Swapout => EventKind::Swapout,
+        StreamEventKind::Deploy => EventKind::Deploy,
+        StreamEventKind::Config => EventKind::Config,
+    }
+}
+
+/// Returns the stream event kind of a protobuf kind, if specified.
+pub fn stream_event_kind(kind: EventKind) -> Option<StreamEventKind> {
+    match kind {
+        EventKind::Unspecified => None,
+        EventKind::Move => Some(StreamEventKind::Move),
+        EventKind::Call => Some(StreamEventKind::Call),
+        EventKind::Liftup => Some(StreamEventKind::Liftup),
+        EventKind::Swapout => Some(StreamEventKind::Swapout),
+        EventKind::Deploy => Some(StreamEventKind::Deploy),
+        EventKind::Config => Some(StreamEventKind::Config),
+    }
+}
+
+/// Returns the subscription filter of a `Subscribe` request, checked like a WebSocket filter.
+pub fn subscription_filter(
+    request: &SubscribeRequest,
+) -> Result<SubscriptionFilter, SubscriptionFilterError> {
+    // 1 Check the number of followed keys.
+    let keys = request.account_keys.len() + request.contract_ids.len();
+    if keys > MAX_FILTER_KEYS {
+        return Err(SubscriptionFilterError::TooManyKeys(keys));
+    }
+
+    // 2 Collect the followed accounts and contracts.
+    let mut filter = SubscriptionFilter::new();
+    for account_key in request.account_keys.iter() {
+        let account_key = account_key
+            .as_slice()
+            .try_into()
+            .map_err(|_| SubscriptionFilterError::InvalidAccountKey(hex::encode(account_key)))?;
+        filter.account_keys.insert(account_key);
+    }
+    for contract_id in request.contract_ids.iter() {
+        let contract_id = contract_id
+            .as_slice()
+            .try_into()
+            .map_err(|_| SubscriptionFilterError::InvalidContractId(hex::encode(contract_id)))?;
+        filter.contract_ids.insert(contract_id);
+    }
+
+    // 3 Collect the event kinds.
+    for kind in request.kinds.iter() {
+        let stream_event_kind = EventKind::try_from(*kind)
+            .ok()
+            .and_then(stream_event_kind)
+            .ok_or(SubscriptionFilterError::UnknownEventKind(kind.to_string()))?;
+        filter.kinds.insert(stream_event_kind);
+    }
+
+    // 4 Set the minimum value.
+    filter.min_value = request.min_value;
+
+    Ok(filter)
+}
+
+/// Returns the protobuf message of a stream event.
+pub fn stream_event_message(event: &StreamEvent) -> messages::StreamEvent {
+    messages::StreamEvent {
+        kind: event_kind(event.kind) as i32,
+        batch_height: event.batch_height,
+        entry_id: event.entry_id.to_vec(),
+        account_keys: event.account_keys.iter().map(|key| key.to_vec()).collect(),
+        contract_ids: event.contract_ids.iter().map(|id| id.to_vec()).collect(),
+        value: event.value,
+        entry_json: event.entry.to_string(),
+    }
+}
+
+/// Returns the `Subscribe` reply of a subscription message.
+pub fn subscribe_reply(message: &SubscriptionMessage) -> messages::SubscribeReply {
+    let message = match message {
+        SubscriptionMessage::Event(event) => {
+            subscribe_reply::Message::Event(stream_event_message(event))
+        }
+        SubscriptionMessage::Lagged(skipped) => subscribe_reply::Message::Lagged(*skipped),
+    };
+    messages::SubscribeReply {
+        message: Some(message),
+    }
+}
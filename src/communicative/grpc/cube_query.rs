@@ -0,0 +1,357 @@
+use crate::communicative::api_auth::api_auth::API_AUTH;
+use crate::communicative::api_auth::api_identity::ApiIdentity;
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use crate::communicative::api_auth::errors::api_auth_error::ApiAuthError;
+use crate::communicative::event_stream::event_stream::event_stream;
+use crate::communicative::grpc::conversions::{subscribe_reply, subscription_filter};
+use crate::communicative::grpc::messages::{
+    get_batch_request, Account, AccountHistory, Batch, Contract, ContractEvent, ContractEvents,
+    Entry, GetAccountHistoryRequest, GetAccountRequest, GetBatchRequest, GetContractRequest,
+    GetEntryRequest, GetEventsRequest, GetSyncStatusRequest, SimulateCallRequest, SimulatedCall,
+    SubscribeReply, SubscribeRequest, SyncStatus,
+};
+use crate::executive::abi::{abi_type::AbiType, abi_value::AbiValue};
+use crate::executive::stack::stack_item::StackItem;
+use crate::executive::vm::program_execution::call_simulation::simulate_call;
+use crate::executive::vm::program_execution::exec_event::{
+    MAX_EVENT_TOPIC_LENGTH, MIN_EVENT_TOPIC_LENGTH,
+};
+use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
+use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+use crate::inscriptive::params_manager::params_manager::PARAMS_MANAGER;
+use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::state_manager::state_manager::STATE_MANAGER;
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use chrono::Utc;
+use futures::Stream;
+use std::pin::Pin;
+use tonic::Status;
+
+/// Stream of `Subscribe` replies.
+pub type SubscribeStream = Pin<Box<dyn Stream<Item = Result<SubscribeReply, Status>> + Send>>;
+
+/// Handlers of the `cube.v1.CubeQuery` methods.
+///
+/// Answers from the same managers as the explorer, and simulates calls against them without
+/// keeping their changes. Batch, entry, history and event queries need
+/// the archive, and fail with `FAILED_PRECONDITION` outside archival resource mode. Calls are
+/// authorized for reading against the API auth.
+pub struct CubeQuery {
+    // The sync manager.
+    sync_manager: SYNC_MANAGER,
+
+    // The coin manager.
+    coin_manager: COIN_MANAGER,
+
+    // The state manager.
+    state_manager: STATE_MANAGER,
+
+    // The registery.
+    registery: REGISTERY,
+
+    // The params manager.
+    params_manager: PARAMS_MANAGER,
+
+    // The archival manager (Archival resource mode only).
+    archival_manager: Option<ARCHIVAL_MANAGER>,
+
+    // The identities allowed on the query APIs.
+    api_auth: API_AUTH,
+}
+
+impl CubeQuery {
+    /// Constructs the query handlers.
+    pub fn new(
+        sync_manager: SYNC_MANAGER,
+        coin_manager: COIN_MANAGER,
+        state_manager: STATE_MANAGER,
+        registery: REGISTERY,
+        params_manager: PARAMS_MANAGER,
+        archival_manager: Option<ARCHIVAL_MANAGER>,
+        api_auth: API_AUTH,
+    ) -> Self {
+        Self {
+            sync_manager,
+            coin_manager,
+            state_manager,
+            registery,
+            params_manager,
+            archival_manager,
+            api_auth,
+        }
+    }
+
+    /// Authorizes a call presenting the given bearer token, if any, for reading.
+    pub async fn authorize(&self, bearer: Option<&str>) -> Result<ApiIdentity, ApiAuthError> {
+        let mut _api_auth = self.api_auth.lock().await;
+        _api_auth.authorize(bearer, ApiPermission::Read, Utc::now().timestamp() as u64)
+    }
+
+    /// Returns the sync heights.
+    pub async fn get_sync_status(
+        &self,
+        _request: GetSyncStatusRequest,
+    ) -> Result<SyncStatus, Status> {
+        let _sync_manager = self.sync_manager.lock().await;
+        Ok(SyncStatus {
+            bitcoin_sync_height: _sync_manager.bitcoin_sync_height_tip(),
+            batch_sync_height: _sync_manager.cube_batch_sync_height_tip(),
+        })
+    }
+
+    /// Returns an archived batch by height or txid.
+    pub async fn get_batch(&self, request: GetBatchRequest) -> Result<Batch, Status> {
+        // 1 Look the batch record up.
+        let archival_manager = self
+            .archival_manager
+            .as_ref()
+            .ok_or_else(archival_mode_required)?;
+        let record = match request.selector {
+            Some(get_batch_request::Selector::Height(batch_height)) => {
+                let _archival_manager = archival_manager.lock().await;
+                _archival_manager.batch_record_by_height(batch_height)
+            }
+            Some(get_batch_request::Selector::Txid(batch_txid)) => {
+                let batch_txid = key_bytes(&batch_txid).ok_or_else(|| invalid_length("txid"))?;
+                let _archival_manager = archival_manager.lock().await;
+                _archival_manager.batch_record_by_txid(&batch_txid)
+            }
+            None => return Err(Status::invalid_argument("A height or txid is required.")),
+        };
+        let record = record.ok_or_else(|| Status::not_found("Batch not found."))?;
+
+        // 2 Return the batch.
+        Ok(Batch {
+            batch_height: record.batch_height,
+            batch_timestamp: record.batch_timestamp,
+            batch_txid: record.batch_txid.to_vec(),
+            entry_ids: record
+                .entries
+                .iter()
+                .map(|(entry_id, _)| entry_id.to_vec())
+                .collect(),
+            json: record.json().to_string(),
+        })
+    }
+
+    /// Returns an archived entry by id.
+    pub async fn get_entry(&self, request: GetEntryRequest) -> Result<Entry, Status> {
+        let entry_id = key_bytes(&request.entry_id).ok_or_else(|| invalid_length("entry_id"))?;
+        let archival_manager = self
+            .archival_manager
+            .as_ref()
+            .ok_or_else(archival_mode_required)?;
+        let json = {
+            let _archival_manager = archival_manager.lock().await;
+            _archival_manager.entry_record_json_by_entry_id(&entry_id)
+        };
+        let json = json.ok_or_else(|| Status::not_found("Entry not found."))?;
+        Ok(Entry {
+            entry_id: entry_id.to_vec(),
+            json: json.to_string(),
+        })
+    }
+
+    /// Returns the registration and balance of an account.
+    pub async fn get_account(&self, request: GetAccountRequest) -> Result<Account, Status> {
+        let account_key =
+            key_bytes(&request.account_key).ok_or_else(|| invalid_length("account_key"))?;
+        let _coin_manager = self.coin_manager.lock().await;
+        Ok(Account {
+            account_key: account_key.to_vec(),
+            registered: _coin_manager.is_account_registered(account_key),
+            balance: _coin_manager.get_account_balance(account_key),
+        })
+    }
+
+    /// Returns the archived entry history of an account.
+    pub async fn get_account_history(
+        &self,
+        request: GetAccountHistoryRequest,
+    ) -> Result<AccountHistory, Status> {
+        let account_key =
+            key_bytes(&request.account_key).ok_or_else(|| invalid_length("account_key"))?;
+        let archival_manager = self
+            .archival_manager
+            .as_ref()
+            .ok_or_else(archival_mode_required)?;
+        let json = {
+            let _archival_manager = archival_manager.lock().await;
+            _archival_manager.retrieve_account_history_json(account_key)
+        };
+        Ok(AccountHistory {
+            account_key: account_key.to_vec(),
+            json: json.to_string(),
+        })
+    }
+
+    /// Returns the registration and balance of a contract.
+    pub async fn get_contract(&self, request: GetContractRequest) -> Result<Contract, Status> {
+        let contract_id =
+            key_bytes(&request.contract_id).ok_or_else(|| invalid_length("contract_id"))?;
+        let _coin_manager = self.coin_manager.lock().await;
+        Ok(Contract {
+            contract_id: contract_id.to_vec(),
+            registered: _coin_manager.is_contract_registered(contract_id),
+            balance: _coin_manager.get_contract_balance(contract_id),
+        })
+    }
+
+    /// Returns the archived events of a contract between two batch heights, optionally of a topic.
+    pub async fn get_events(&self, request: GetEventsRequest) -> Result<ContractEvents, Status> {
+        // 1 Validate the contract id and the topic.
+        let contract_id =
+            key_bytes(&request.contract_id).ok_or_else(|| invalid_length("contract_id"))?;
+        if let Some(topic) = &request.topic {
+            if topic.len() < MIN_EVENT_TOPIC_LENGTH || topic.len() > MAX_EVENT_TOPIC_LENGTH {
+                return Err(Status::invalid_argument(format!(
+                    "topic must be {} to {} bytes.",
+                    MIN_EVENT_TOPIC_LENGTH, MAX_EVENT_TOPIC_LENGTH
+                )));
+            }
+        }
+
+        // 2 Scan the event indexes.
+        let archival_manager = self
+            .archival_manager
+            .as_ref()
+            .ok_or_else(archival_mode_required)?;
+        let events = {
+            let _archival_manager = archival_manager.lock().await;
+            _archival_manager.get_events(
+                contract_id,
+                request.topic.as_deref(),
+                request.from_height,
+                request.to_height,
+            )
+        };
+
+        // 3 Return the events with their batch heights.
+        Ok(ContractEvents {
+            events: events
+                .into_iter()
+                .map(|(batch_height, event)| ContractEvent {
+                    batch_height,
+                    contract_id: event.contract_id.to_vec(),
+                    topic: event.topic,
+                    data: event.data,
+                })
+                .collect(),
+        })
+    }
+
+    /// Executes a call against the current state without keeping any of its changes.
+    ///
+    /// Malformed requests fail with `INVALID_ARGUMENT`; a call failing in execution is a reply with
+    /// its error.
+    pub async fn simulate_call(
+        &self,
+        request: SimulateCallRequest,
+    ) -> Result<SimulatedCall, Status> {
+        // 1 Validate the account key, the contract id and the method index.
+        let account_key =
+            key_bytes(&request.account_key).ok_or_else(|| invalid_length("account_key"))?;
+        let contract_id =
+            key_bytes(&request.contract_id).ok_or_else(|| invalid_length("contract_id"))?;
+        let method_index = u16::try_from(request.method_index)
+            .map_err(|_| Status::invalid_argument("method_index must fit in 16 bits."))?;
+
+        // 2 Encode the args from their ABI JSON mapping, if given, or take them as raw items.
+        let args = match &request.args_json {
+            Some(args_json) => {
+                let arg_types = {
+                    let _registery = self.registery.lock().await;
+                    _registery.get_contract_method_arg_types_by_contract_id_and_method_index(
+                        contract_id,
+                        method_index,
+                    )
+                }
+                .ok_or_else(|| Status::not_found("Contract method not found."))?;
+                let arg_values: Vec<serde_json::Value> = serde_json::from_str(args_json)
+                    .map_err(|_| Status::invalid_argument("args_json must be a JSON array."))?;
+                if arg_values.len() != arg_types.len() {
+                    return Err(Status::invalid_argument(format!(
+                        "The method takes {} args.",
+                        arg_types.len()
+                    )));
+                }
+                let values = arg_types
+                    .into_iter()
+                    .zip(arg_values.iter())
+                    .map(|(arg_type, arg_value)| {
+                        AbiValue::from_json(
+                            &AbiType::from_calldata_element_type(arg_type),
+                            arg_value,
+                        )
+                    })
+                    .collect::<Result<Vec<AbiValue>, _>>()
+                    .map_err(|err| Status::invalid_argument(format!("{:?}", err)))?;
+                AbiValue::encode_values(&values)
+                    .map_err(|err| Status::invalid_argument(format!("{:?}", err)))?
+            }
+            None => request.args.into_iter().map(StackItem::new).collect(),
+        };
+
+        // 3 Simulate the call.
+        let result = simulate_call(
+            &self.state_manager,
+            &self.coin_manager,
+            &self.registery,
+            &self.params_manager,
+            account_key,
+            contract_id,
+            method_index,
+            args,
+            request.ops_budget,
+            request.ops_price,
+            Utc::now().timestamp() as u64,
+        )
+        .await;
+
+        // 4 Return the outcome.
+        Ok(match result {
+            Ok(simulated_call) => SimulatedCall {
+                passed: true,
+                return_items: simulated_call
+                    .return_items
+                    .iter()
+                    .map(|item| item.bytes().to_vec())
+                    .collect(),
+                ops_spent: simulated_call.ops_spent as u64,
+                fees_spent: simulated_call.fees_spent,
+                error: String::new(),
+            },
+            Err(err) => SimulatedCall {
+                error: err.to_string(),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Streams the applied entries passing the filter of the request.
+    pub async fn subscribe(&self, request: SubscribeRequest) -> Result<SubscribeStream, Status> {
+        let filter = subscription_filter(&request)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let subscription = event_stream().subscribe(filter);
+        let stream = futures::stream::unfold(subscription, |mut subscription| async move {
+            let message = subscription.next().await?;
+            Some((Ok(subscribe_reply(&message)), subscription))
+        });
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Parses a 32-byte key, id or txid field.
+fn key_bytes(bytes: &[u8]) -> Option<[u8; 32]> {
+    bytes.try_into().ok()
+}
+
+/// Error of a 32-byte field with another length.
+fn invalid_length(field: &str) -> Status {
+    Status::invalid_argument(format!("{} must be 32 bytes.", field))
+}
+
+/// Error of a query needing the archive outside archival resource mode.
+fn archival_mode_required() -> Status {
+    Status::failed_precondition("This query requires archival resource mode.")
+}
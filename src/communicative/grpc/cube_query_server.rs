@@ -0,0 +1,188 @@
+use crate::communicative::grpc::cube_query::{CubeQuery, SubscribeStream};
+use crate::communicative::grpc::messages::{SubscribeReply, SubscribeRequest};
+use std::future::Future;
+use std::sync::Arc;
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::codegen::{http, Body, BoxFuture, Context, Poll, Service, StdError};
+use tonic::server::{Grpc, NamedService, ServerStreamingService, UnaryService};
+use tonic::{Request, Response, Status};
+
+/// gRPC service `cube.v1.CubeQuery`, routing each method path to its `CubeQuery` handler.
+///
+/// Written out by hand rather than generated, so that building the node needs no `protoc`. The
+/// messages and paths must stay in line with `proto/cube/v1/cube.proto`.
+#[derive(Clone)]
+pub struct CubeQueryServer {
+    // The query handlers.
+    query: Arc<CubeQuery>,
+}
+
+impl CubeQueryServer {
+    /// Constructs the service.
+    pub fn new(query: CubeQuery) -> Self {
+        Self {
+            query: Arc::new(query),
+        }
+    }
+}
+
+impl NamedService for CubeQueryServer {
+    const NAME: &'static str = "cube.v1.CubeQuery";
+}
+
+impl<B> Service<http::Request<B>> for CubeQueryServer
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let query = self.query.clone();
+        Box::pin(async move {
+            // 1 Authorize the call for reading.
+            let bearer = bearer_token(&req);
+            if let Err(err) = query.authorize(bearer.as_deref()).await {
+                let status = match err.is_forbidden() {
+                    true => Status::permission_denied(err.to_string()),
+                    false => Status::unauthenticated(err.to_string()),
+                };
+                return Ok(status.into_http());
+            }
+
+            // 2 Route the call to its handler.
+            match req.uri().path() {
+                "/cube.v1.CubeQuery/GetSyncStatus" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_sync_status(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/GetBatch" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_batch(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/GetEntry" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_entry(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/GetAccount" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_account(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/GetAccountHistory" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_account_history(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/GetContract" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_contract(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/GetEvents" => {
+                    unary(req, query, |query, request| async move {
+                        query.get_events(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/SimulateCall" => {
+                    unary(req, query, |query, request| async move {
+                        query.simulate_call(request).await
+                    })
+                    .await
+                }
+                "/cube.v1.CubeQuery/Subscribe" => {
+                    let mut grpc = Grpc::new(ProstCodec::default());
+                    Ok(grpc.server_streaming(SubscribeMethod { query }, req).await)
+                }
+                _ => Ok(Status::unimplemented("").into_http()),
+            }
+        })
+    }
+}
+
+/// Returns the bearer token of the `authorization` metadata of a call, if any.
+fn bearer_token<B>(req: &http::Request<B>) -> Option<String> {
+    req.headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+}
+
+/// Serves a unary call with the given handler.
+fn unary<B, M, R, F, Fut>(
+    req: http::Request<B>,
+    query: Arc<CubeQuery>,
+    handler: F,
+) -> BoxFuture<http::Response<BoxBody>, std::convert::Infallible>
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+    M: prost::Message + Default + Send + 'static,
+    R: prost::Message + Send + 'static,
+    F: Fn(Arc<CubeQuery>, M) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<R, Status>> + Send + 'static,
+{
+    Box::pin(async move {
+        let mut grpc = Grpc::new(ProstCodec::default());
+        Ok(grpc.unary(UnaryMethod { query, handler }, req).await)
+    })
+}
+
+/// A unary method bound to the query handlers.
+struct UnaryMethod<F> {
+    query: Arc<CubeQuery>,
+    handler: F,
+}
+
+impl<M, R, F, Fut> UnaryService<M> for UnaryMethod<F>
+where
+    F: Fn(Arc<CubeQuery>, M) -> Fut,
+    Fut: Future<Output = Result<R, Status>> + Send + 'static,
+{
+    type Response = R;
+    type Future = BoxFuture<Response<R>, Status>;
+
+    fn call(&mut self, request: Request<M>) -> Self::Future {
+        let response = (self.handler)(self.query.clone(), request.into_inner());
+        Box::pin(async move { response.await.map(Response::new) })
+    }
+}
+
+/// The `Subscribe` method bound to the query handlers.
+struct SubscribeMethod {
+    query: Arc<CubeQuery>,
+}
+
+impl ServerStreamingService<SubscribeRequest> for SubscribeMethod {
+    type Response = SubscribeReply;
+    type ResponseStream = SubscribeStream;
+    type Future = BoxFuture<Response<SubscribeStream>, Status>;
+
+    fn call(&mut self, request: Request<SubscribeRequest>) -> Self::Future {
+        let query = self.query.clone();
+        Box::pin(async move {
+            query
+                .subscribe(request.into_inner())
+                .await
+                .map(Response::new)
+        })
+    }
+}
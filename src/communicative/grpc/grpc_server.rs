@@ -0,0 +1,29 @@
+use crate::communicative::grpc::cube_query::CubeQuery;
+use crate::communicative::grpc::cube_query_server::CubeQueryServer;
+use colored::Colorize;
+use std::net::SocketAddr;
+
+/// Environment variable enabling the gRPC server on the given port.
+pub const GRPC_PORT_ENV: &str = "CUBE_GRPC_PORT";
+
+/// Returns the gRPC port from the environment, if set.
+pub fn grpc_port_from_env() -> Option<u16> {
+    std::env::var(GRPC_PORT_ENV)
+        .ok()
+        .and_then(|port| port.trim().parse::<u16>().ok())
+}
+
+/// Serves the `cube.v1.CubeQuery` service on the given port until the server fails.
+pub async fn run_grpc_server(port: u16, query: CubeQuery) {
+    // 1 Bind address.
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+
+    // 2 Serve the query service.
+    if let Err(err) = tonic::transport::Server::builder()
+        .add_service(CubeQueryServer::new(query))
+        .serve(addr)
+        .await
+    {
+        eprintln!("{}", format!("gRPC server error: {}", err).red());
+    }
+}
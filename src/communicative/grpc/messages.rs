@@ -0,0 +1,246 @@
+// Protobuf messages of `proto/cube/v1/cube.proto`, kept in sync with the schema by hand so that
+// building the `grpc` feature needs no `protoc`.
+
+/// Request of `GetSyncStatus`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSyncStatusRequest {}
+
+/// Reply of `GetSyncStatus`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncStatus {
+    #[prost(uint64, tag = "1")]
+    pub bitcoin_sync_height: u64,
+    #[prost(uint64, tag = "2")]
+    pub batch_sync_height: u64,
+}
+
+/// Request of `GetBatch`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBatchRequest {
+    #[prost(oneof = "get_batch_request::Selector", tags = "1, 2")]
+    pub selector: Option<get_batch_request::Selector>,
+}
+
+pub mod get_batch_request {
+    /// Selects a batch by height or txid.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Selector {
+        #[prost(uint64, tag = "1")]
+        Height(u64),
+        #[prost(bytes = "vec", tag = "2")]
+        Txid(Vec<u8>),
+    }
+}
+
+/// Reply of `GetBatch`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Batch {
+    #[prost(uint64, tag = "1")]
+    pub batch_height: u64,
+    #[prost(uint64, tag = "2")]
+    pub batch_timestamp: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub batch_txid: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "4")]
+    pub entry_ids: Vec<Vec<u8>>,
+    #[prost(string, tag = "5")]
+    pub json: String,
+}
+
+/// Request of `GetEntry`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEntryRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub entry_id: Vec<u8>,
+}
+
+/// Reply of `GetEntry`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Entry {
+    #[prost(bytes = "vec", tag = "1")]
+    pub entry_id: Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub json: String,
+}
+
+/// Request of `GetAccount`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_key: Vec<u8>,
+}
+
+/// Reply of `GetAccount`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Account {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_key: Vec<u8>,
+    #[prost(bool, tag = "2")]
+    pub registered: bool,
+    #[prost(uint64, optional, tag = "3")]
+    pub balance: Option<u64>,
+}
+
+/// Request of `GetAccountHistory`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountHistoryRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_key: Vec<u8>,
+}
+
+/// Reply of `GetAccountHistory`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountHistory {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_key: Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub json: String,
+}
+
+/// Request of `GetContract`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetContractRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub contract_id: Vec<u8>,
+}
+
+/// Reply of `GetContract`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Contract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub contract_id: Vec<u8>,
+    #[prost(bool, tag = "2")]
+    pub registered: bool,
+    #[prost(uint64, optional, tag = "3")]
+    pub balance: Option<u64>,
+}
+
+/// Request of `GetEvents`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEventsRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub contract_id: Vec<u8>,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub topic: Option<Vec<u8>>,
+    #[prost(uint64, tag = "3")]
+    pub from_height: u64,
+    #[prost(uint64, tag = "4")]
+    pub to_height: u64,
+}
+
+/// An event emitted by a contract, with the height of its batch.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContractEvent {
+    #[prost(uint64, tag = "1")]
+    pub batch_height: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub contract_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub topic: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub data: Vec<u8>,
+}
+
+/// Reply of `GetEvents`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContractEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: Vec<ContractEvent>,
+}
+
+/// Request of `SimulateCall`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateCallRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account_key: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub contract_id: Vec<u8>,
+    #[prost(uint32, tag = "3")]
+    pub method_index: u32,
+    #[prost(bytes = "vec", repeated, tag = "4")]
+    pub args: Vec<Vec<u8>>,
+    #[prost(string, optional, tag = "5")]
+    pub args_json: Option<String>,
+    #[prost(uint32, tag = "6")]
+    pub ops_budget: u32,
+    #[prost(uint32, tag = "7")]
+    pub ops_price: u32,
+}
+
+/// Reply of `SimulateCall`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulatedCall {
+    #[prost(bool, tag = "1")]
+    pub passed: bool,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub return_items: Vec<Vec<u8>>,
+    #[prost(uint64, tag = "3")]
+    pub ops_spent: u64,
+    #[prost(uint64, tag = "4")]
+    pub fees_spent: u64,
+    #[prost(string, tag = "5")]
+    pub error: String,
+}
+
+/// Kind of a streamed event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum EventKind {
+    Unspecified = 0,
+    Move = 1,
+    Call = 2,
+    Liftup = 3,
+    Swapout = 4,
+    Deploy = 5,
+    Config = 6,
+}
+
+/// Request of `Subscribe`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeRequest {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub account_keys: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub contract_ids: Vec<Vec<u8>>,
+    #[prost(enumeration = "EventKind", repeated, tag = "3")]
+    pub kinds: Vec<i32>,
+    #[prost(uint64, optional, tag = "4")]
+    pub min_value: Option<u64>,
+}
+
+/// An applied entry streamed to a subscriber.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamEvent {
+    #[prost(enumeration = "EventKind", tag = "1")]
+    pub kind: i32,
+    #[prost(uint64, tag = "2")]
+    pub batch_height: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub entry_id: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "4")]
+    pub account_keys: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "5")]
+    pub contract_ids: Vec<Vec<u8>>,
+    #[prost(uint64, optional, tag = "6")]
+    pub value: Option<u64>,
+    #[prost(string, tag = "7")]
+    pub entry_json: String,
+}
+
+/// Message streamed by `Subscribe`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeReply {
+    #[prost(oneof = "subscribe_reply::Message", tags = "1, 2")]
+    pub message: Option<subscribe_reply::Message>,
+}
+
+pub mod subscribe_reply {
+    /// An applied entry, or the number of events skipped by a lagging subscriber.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Message {
+        #[prost(message, tag = "1")]
+        Event(super::StreamEvent),
+        #[prost(uint64, tag = "2")]
+        Lagged(u64),
+    }
+}
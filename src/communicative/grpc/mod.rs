@@ -0,0 +1,5 @@
+pub mod conversions;
+pub mod cube_query;
+pub mod cube_query_server;
+pub mod grpc_server;
+pub mod messages;
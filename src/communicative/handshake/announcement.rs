@@ -0,0 +1,207 @@
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::handshake::protocol_feature::{FeatureBitmap, ProtocolFeature};
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::{KeyHolder, ToNostrKeyStr};
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Operator key.
+type OperatorKey = [u8; 32];
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Highest version of the handshake protocol spoken by this build.
+pub const HANDSHAKE_PROTOCOL_VERSION: u16 = 1;
+
+/// Lowest version of the handshake protocol this build still speaks.
+pub const MIN_HANDSHAKE_PROTOCOL_VERSION: u16 = 1;
+
+/// Returns the highest protocol version within both the local and the remote supported ranges, if
+/// the ranges overlap.
+pub fn negotiate_protocol_version(
+    local_min: u16,
+    local_max: u16,
+    remote_min: u16,
+    remote_max: u16,
+) -> Option<u16> {
+    let version = local_max.min(remote_max);
+    match version >= local_min.max(remote_min) {
+        true => Some(version),
+        false => None,
+    }
+}
+
+pub(crate) mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// An operator announcing itself to the engine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandshakeAnnouncement {
+    // The announcing operator key.
+    pub operator_key: OperatorKey,
+
+    // Announced capabilities.
+    pub capabilities: Vec<Capability>,
+
+    // Highest supported handshake protocol version.
+    pub protocol_version: u16,
+
+    // Lowest supported handshake protocol version.
+    pub min_protocol_version: u16,
+
+    // Bitmap of the supported protocol features.
+    pub features: FeatureBitmap,
+
+    // Software version of the operator.
+    pub software_version: String,
+
+    // Cube batch sync height of the operator.
+    pub cube_batch_sync_height: BatchHeight,
+
+    // Announcement timestamp.
+    pub timestamp: Timestamp,
+
+    // The Schnorr signature over the announcement message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl HandshakeAnnouncement {
+    /// Signs a fresh new announcement with the operator's key.
+    pub fn sign(
+        keys: &KeyHolder,
+        capabilities: Vec<Capability>,
+        cube_batch_sync_height: BatchHeight,
+        timestamp: Timestamp,
+    ) -> Option<Self> {
+        // 1 Construct the unsigned announcement.
+        let mut announcement = Self {
+            operator_key: keys.secp_public_key_bytes(),
+            capabilities,
+            protocol_version: HANDSHAKE_PROTOCOL_VERSION,
+            min_protocol_version: MIN_HANDSHAKE_PROTOCOL_VERSION,
+            features: ProtocolFeature::local_bitmap(),
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+            cube_batch_sync_height,
+            timestamp,
+            signature: [0u8; 64],
+        };
+
+        // 2 Sign the announcement message.
+        announcement.signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            announcement.message(),
+            SchnorrSigningMode::Cube,
+        )?;
+
+        // 3 Return the announcement.
+        Some(announcement)
+    }
+
+    /// Returns the message the operator signs.
+    pub fn message(&self) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::new();
+        preimage.extend(self.operator_key);
+        preimage.extend((self.capabilities.len() as u32).to_be_bytes());
+        preimage.extend(self.capabilities.iter().map(|c| c.bytecode()));
+        preimage.extend(self.protocol_version.to_be_bytes());
+        preimage.extend(self.min_protocol_version.to_be_bytes());
+        preimage.extend(self.features.to_be_bytes());
+        preimage.extend((self.software_version.len() as u32).to_be_bytes());
+        preimage.extend(self.software_version.as_bytes());
+        preimage.extend(self.cube_batch_sync_height.to_be_bytes());
+        preimage.extend(self.timestamp.to_be_bytes());
+        preimage.hash(Some(HashTag::HandshakeAnnouncement))
+    }
+
+    /// Verifies the announcement against the announced operator key.
+    pub fn verify(&self) -> bool {
+        schnorr::verify_xonly(
+            self.operator_key,
+            self.message(),
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+
+    /// Returns the announcement as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "operator".to_string(),
+            Value::String(
+                self.operator_key
+                    .to_npub()
+                    .unwrap_or_else(|| hex::encode(self.operator_key)),
+            ),
+        );
+        obj.insert(
+            "capabilities".to_string(),
+            Value::Array(
+                self.capabilities
+                    .iter()
+                    .map(|c| Value::String(c.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "protocol_version".to_string(),
+            Value::Number(self.protocol_version.into()),
+        );
+        obj.insert(
+            "min_protocol_version".to_string(),
+            Value::Number(self.min_protocol_version.into()),
+        );
+        obj.insert(
+            "features".to_string(),
+            Value::Array(
+                ProtocolFeature::from_bitmap(self.features)
+                    .iter()
+                    .map(|feature| Value::String(feature.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "software_version".to_string(),
+            Value::String(self.software_version.clone()),
+        );
+        obj.insert(
+            "cube_batch_sync_height".to_string(),
+            Value::Number(self.cube_batch_sync_height.into()),
+        );
+        obj.insert(
+            "timestamp".to_string(),
+            Value::Number(self.timestamp.into()),
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Capabilities an operator announces to the engine during the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Capability {
+    // Syncs in-flight batches from the engine.
+    InFlightSync,
+
+    // Co-signs applied deltas as a federation coordinator.
+    DeltaCosign,
+
+    // Keeps archived batch records.
+    Archival,
+
+    // Accepts, validates and forwards user-submitted entries.
+    Mempool,
+}
+
+impl Capability {
+    /// Returns the bytecode of the capability.
+    pub fn bytecode(&self) -> u8 {
+        match self {
+            Capability::InFlightSync => 0x00,
+            Capability::DeltaCosign => 0x01,
+            Capability::Archival => 0x02,
+            Capability::Mempool => 0x03,
+        }
+    }
+
+    /// Returns the capability from its bytecode.
+    pub fn from_bytecode(bytecode: u8) -> Option<Self> {
+        match bytecode {
+            0x00 => Some(Capability::InFlightSync),
+            0x01 => Some(Capability::DeltaCosign),
+            0x02 => Some(Capability::Archival),
+            0x03 => Some(Capability::Mempool),
+            _ => None,
+        }
+    }
+}
+
+impl ToString for Capability {
+    fn to_string(&self) -> String {
+        match self {
+            Capability::InFlightSync => "in_flight_sync".to_string(),
+            Capability::DeltaCosign => "delta_cosign".to_string(),
+            Capability::Archival => "archival".to_string(),
+            Capability::Mempool => "mempool".to_string(),
+        }
+    }
+}
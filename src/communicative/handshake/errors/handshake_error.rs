@@ -0,0 +1,28 @@
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::peer::access_list::PeerAccessDenial;
+use serde::{Deserialize, Serialize};
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Reasons the engine rejects a handshake announcement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandshakeError {
+    InvalidSignature,
+    ProtocolVersionMismatch {
+        min_supported: u16,
+        max_supported: u16,
+        min_announced: u16,
+        max_announced: u16,
+    },
+    ClockSkewTooLarge {
+        announced: Timestamp,
+        now: Timestamp,
+    },
+    ReplayedAnnouncement {
+        last_timestamp: Timestamp,
+    },
+    EngineKeyCannotRegister,
+    PeerAccessDenied(PeerAccessDenial),
+    CapabilityNotGranted(Capability),
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Reasons the engine rejects an operator heartbeat.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeartbeatError {
+    InvalidSignature,
+    UnknownSession,
+    SessionMismatch,
+    SessionExpired(Timestamp),
+    ClockSkewTooLarge {
+        announced: Timestamp,
+        now: Timestamp,
+    },
+    ReplayedHeartbeat {
+        last_timestamp: Timestamp,
+    },
+}
+
+impl HeartbeatError {
+    /// Whether the operator has to register again through the handshake.
+    pub fn requires_registration(&self) -> bool {
+        matches!(
+            self,
+            HeartbeatError::UnknownSession
+                | HeartbeatError::SessionMismatch
+                | HeartbeatError::SessionExpired(_)
+        )
+    }
+}
@@ -0,0 +1,3 @@
+pub mod handshake_error;
+pub mod heartbeat_error;
+pub mod registration_error;
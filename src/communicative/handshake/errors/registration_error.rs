@@ -0,0 +1,22 @@
+use crate::communicative::tcp::client::HandshakeResponseError;
+use crate::communicative::tcp::request_error::RequestError;
+
+/// Errors associated with a node registering itself with the engine.
+#[derive(Debug, Clone)]
+pub enum RegistrationError {
+    AnnouncementSigningError,
+    RequestError(RequestError),
+    Rejected(HandshakeResponseError),
+    InvalidSessionParams,
+}
+
+impl RegistrationError {
+    /// Whether registering again later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RegistrationError::RequestError(_) => true,
+            RegistrationError::Rejected(HandshakeResponseError::SessionParamsSigningError) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::communicative::handshake::announcement::HandshakeAnnouncement;
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::handshake::errors::registration_error::RegistrationError;
+use crate::communicative::handshake::session_params::SessionParams;
+use crate::communicative::peer::peer::PEER;
+use crate::communicative::tcp::client::{HandshakeResponseBody, TCPClient};
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+
+/// Engine key.
+type EngineKey = [u8; 32];
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Registers the node with the engine by announcing itself, and returns the session params the engine issued.
+pub async fn register_with_engine(
+    engine_conn: &PEER,
+    keys: &KeyHolder,
+    engine_key: EngineKey,
+    capabilities: Vec<Capability>,
+    cube_batch_sync_height: BatchHeight,
+) -> Result<SessionParams, RegistrationError> {
+    // 1 Sign the announcement.
+    let announcement = HandshakeAnnouncement::sign(
+        keys,
+        capabilities,
+        cube_batch_sync_height,
+        Utc::now().timestamp() as u64,
+    )
+    .ok_or(RegistrationError::AnnouncementSigningError)?;
+
+    // 2 Send the announcement to the engine.
+    let (response_body, _) = engine_conn
+        .request_handshake(&announcement)
+        .await
+        .map_err(RegistrationError::RequestError)?;
+
+    // 3 Resolve the session params.
+    let session_params = match response_body {
+        HandshakeResponseBody::Ok(session_params) => session_params,
+        HandshakeResponseBody::Err(error) => return Err(RegistrationError::Rejected(error)),
+    };
+
+    // 4 Make sure the session params are issued by the engine to this node, within the announced
+    // protocol versions and features.
+    if !session_params.verify(engine_key)
+        || session_params.operator_key != announcement.operator_key
+        || session_params.protocol_version < announcement.min_protocol_version
+        || session_params.protocol_version > announcement.protocol_version
+        || session_params.features & !announcement.features != 0
+    {
+        return Err(RegistrationError::InvalidSessionParams);
+    }
+
+    // 5 Use the negotiated features with the engine from now on.
+    {
+        let mut _engine_conn = engine_conn.lock().await;
+        _engine_conn.set_features(session_params.features);
+    }
+
+    // 6 Return the session params.
+    Ok(session_params)
+}
@@ -0,0 +1,112 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+
+/// Sender key.
+type SenderKey = [u8; 32];
+
+/// Session id.
+type SessionId = [u8; 32];
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// A signed liveness signal exchanged over an operator session, in both directions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    // The sending key (the operator's, or the engine's in reply).
+    pub sender_key: SenderKey,
+
+    // Session id the heartbeat belongs to.
+    pub session_id: SessionId,
+
+    // Cube batch sync height of the sender.
+    pub cube_batch_sync_height: BatchHeight,
+
+    // Heartbeat timestamp.
+    pub timestamp: Timestamp,
+
+    // The Schnorr signature over the heartbeat message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl Heartbeat {
+    /// Signs a fresh new heartbeat with the sender's key.
+    pub fn sign(
+        keys: &KeyHolder,
+        session_id: SessionId,
+        cube_batch_sync_height: BatchHeight,
+        timestamp: Timestamp,
+    ) -> Option<Self> {
+        // 1 Construct the unsigned heartbeat.
+        let mut heartbeat = Self {
+            sender_key: keys.secp_public_key_bytes(),
+            session_id,
+            cube_batch_sync_height,
+            timestamp,
+            signature: [0u8; 64],
+        };
+
+        // 2 Sign the heartbeat message.
+        heartbeat.signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            heartbeat.message(),
+            SchnorrSigningMode::Cube,
+        )?;
+
+        // 3 Return the heartbeat.
+        Some(heartbeat)
+    }
+
+    /// Returns the message the sender signs.
+    pub fn message(&self) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::with_capacity(80);
+        preimage.extend(self.sender_key);
+        preimage.extend(self.session_id);
+        preimage.extend(self.cube_batch_sync_height.to_be_bytes());
+        preimage.extend(self.timestamp.to_be_bytes());
+        preimage.hash(Some(HashTag::Heartbeat))
+    }
+
+    /// Verifies the heartbeat against the sender key.
+    pub fn verify(&self) -> bool {
+        schnorr::verify_xonly(
+            self.sender_key,
+            self.message(),
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+}
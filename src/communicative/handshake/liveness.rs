@@ -0,0 +1,34 @@
+use serde_json::{Map, Value};
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Liveness of a registered operator, as tracked by the engine from its heartbeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liveness {
+    Online,
+    // Timed out at the given timestamp.
+    Offline(Timestamp),
+}
+
+impl Liveness {
+    /// Whether the operator is online.
+    pub fn is_online(&self) -> bool {
+        matches!(self, Liveness::Online)
+    }
+
+    /// Returns the liveness as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            Liveness::Online => {
+                obj.insert("status".to_string(), Value::String("online".to_string()));
+            }
+            Liveness::Offline(since) => {
+                obj.insert("status".to_string(), Value::String("offline".to_string()));
+                obj.insert("since".to_string(), Value::Number((*since).into()));
+            }
+        }
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,11 @@
+pub mod announcement;
+pub mod capability;
+pub mod errors;
+pub mod handshake;
+pub mod heartbeat;
+pub mod liveness;
+pub mod operator_session;
+pub mod operator_sessions;
+pub mod protocol_feature;
+pub mod session_params;
+pub mod work_assignment;
@@ -0,0 +1,164 @@
+use crate::communicative::handshake::announcement::HandshakeAnnouncement;
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::handshake::liveness::Liveness;
+use crate::communicative::handshake::protocol_feature::{FeatureBitmap, ProtocolFeature};
+use crate::communicative::handshake::work_assignment::WorkAssignment;
+use crate::transmutative::key::ToNostrKeyStr;
+use serde_json::{Map, Value};
+
+/// Operator key.
+type OperatorKey = [u8; 32];
+
+/// Session id.
+type SessionId = [u8; 32];
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// A registered operator as known to the engine.
+#[derive(Debug, Clone)]
+pub struct OperatorSession {
+    // Operator key.
+    pub operator_key: OperatorKey,
+
+    // Session id issued to the operator.
+    pub session_id: SessionId,
+
+    // Announced capabilities.
+    pub capabilities: Vec<Capability>,
+
+    // Negotiated handshake protocol version.
+    pub protocol_version: u16,
+
+    // Protocol features both the engine and the operator support.
+    pub features: FeatureBitmap,
+
+    // Announced software version.
+    pub software_version: String,
+
+    // Announced cube batch sync height.
+    pub cube_batch_sync_height: BatchHeight,
+
+    // Timestamp of the latest accepted announcement.
+    pub announced_at: Timestamp,
+
+    // Timestamp the session was first registered at.
+    pub registered_at: Timestamp,
+
+    // Timestamp of the latest accepted heartbeat.
+    pub last_heartbeat_at: Timestamp,
+
+    // Timestamp the operator was last heard from.
+    pub last_seen: Timestamp,
+
+    // Liveness of the operator.
+    pub liveness: Liveness,
+
+    // Work assigned to the operator and not completed yet.
+    pub pending_work: Vec<WorkAssignment>,
+}
+
+impl OperatorSession {
+    /// Constructs a fresh new session from an accepted announcement.
+    pub fn new(
+        announcement: &HandshakeAnnouncement,
+        session_id: SessionId,
+        protocol_version: u16,
+        features: FeatureBitmap,
+        registered_at: Timestamp,
+    ) -> Self {
+        Self {
+            operator_key: announcement.operator_key,
+            session_id,
+            capabilities: announcement.capabilities.clone(),
+            protocol_version,
+            features,
+            software_version: announcement.software_version.clone(),
+            cube_batch_sync_height: announcement.cube_batch_sync_height,
+            announced_at: announcement.timestamp,
+            registered_at,
+            last_heartbeat_at: announcement.timestamp,
+            last_seen: registered_at,
+            liveness: Liveness::Online,
+            pending_work: Vec::new(),
+        }
+    }
+
+    /// Whether the operator announced the given capability.
+    pub fn has_capability(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    /// Whether both the engine and the operator support the given protocol feature.
+    pub fn has_feature(&self, feature: ProtocolFeature) -> bool {
+        feature.in_bitmap(self.features)
+    }
+
+    /// Returns the session as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "operator".to_string(),
+            Value::String(
+                self.operator_key
+                    .to_npub()
+                    .unwrap_or_else(|| hex::encode(self.operator_key)),
+            ),
+        );
+        obj.insert(
+            "session_id".to_string(),
+            Value::String(hex::encode(self.session_id)),
+        );
+        obj.insert(
+            "capabilities".to_string(),
+            Value::Array(
+                self.capabilities
+                    .iter()
+                    .map(|c| Value::String(c.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "protocol_version".to_string(),
+            Value::Number(self.protocol_version.into()),
+        );
+        obj.insert(
+            "features".to_string(),
+            Value::Array(
+                ProtocolFeature::from_bitmap(self.features)
+                    .iter()
+                    .map(|feature| Value::String(feature.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "software_version".to_string(),
+            Value::String(self.software_version.clone()),
+        );
+        obj.insert(
+            "cube_batch_sync_height".to_string(),
+            Value::Number(self.cube_batch_sync_height.into()),
+        );
+        obj.insert(
+            "announced_at".to_string(),
+            Value::Number(self.announced_at.into()),
+        );
+        obj.insert(
+            "registered_at".to_string(),
+            Value::Number(self.registered_at.into()),
+        );
+        obj.insert(
+            "last_seen".to_string(),
+            Value::Number(self.last_seen.into()),
+        );
+        obj.insert("liveness".to_string(), self.liveness.json());
+        obj.insert(
+            "pending_work".to_string(),
+            Value::Array(self.pending_work.iter().map(|work| work.json()).collect()),
+        );
+        Value::Object(obj)
+    }
+}
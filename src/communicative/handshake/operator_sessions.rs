@@ -0,0 +1,329 @@
+use crate::communicative::handshake::announcement::{
+    negotiate_protocol_version, HandshakeAnnouncement, HANDSHAKE_PROTOCOL_VERSION,
+    MIN_HANDSHAKE_PROTOCOL_VERSION,
+};
+use crate::communicative::handshake::errors::handshake_error::HandshakeError;
+use crate::communicative::handshake::errors::heartbeat_error::HeartbeatError;
+use crate::communicative::handshake::heartbeat::Heartbeat;
+use crate::communicative::handshake::liveness::Liveness;
+use crate::communicative::handshake::operator_session::OperatorSession;
+use crate::communicative::handshake::protocol_feature::ProtocolFeature;
+use crate::communicative::handshake::session_params::SESSION_TIMEOUT_SECS;
+use crate::communicative::handshake::work_assignment::WorkAssignment;
+use rand::{rngs::OsRng, RngCore};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Operator key.
+type OperatorKey = [u8; 32];
+
+/// Engine key.
+type EngineKey = [u8; 32];
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Maximum tolerated difference between an announcement timestamp and the engine clock.
+pub const MAX_CLOCK_SKEW_SECS: u64 = 60;
+
+/// Guarded operator sessions.
+#[allow(non_camel_case_types)]
+pub type OPERATOR_SESSIONS = Arc<Mutex<OperatorSessions>>;
+
+/// Registry of operators that registered with the engine through the handshake.
+pub struct OperatorSessions {
+    // Sessions keyed by operator key.
+    sessions: HashMap<OperatorKey, OperatorSession>,
+
+    // Work no online operator could take on yet.
+    unassigned_work: Vec<WorkAssignment>,
+}
+
+impl OperatorSessions {
+    /// Constructs a fresh new operator session registry.
+    pub fn new() -> OPERATOR_SESSIONS {
+        Arc::new(Mutex::new(Self {
+            sessions: HashMap::new(),
+            unassigned_work: Vec::new(),
+        }))
+    }
+
+    /// Validates an announcement and registers the announcing operator, replacing its prior session if any.
+    pub fn register(
+        &mut self,
+        announcement: &HandshakeAnnouncement,
+        engine_key: EngineKey,
+        now: Timestamp,
+    ) -> Result<&OperatorSession, HandshakeError> {
+        // 1 Verify the announcement signature.
+        if !announcement.verify() {
+            return Err(HandshakeError::InvalidSignature);
+        }
+
+        // 2 Negotiate the highest protocol version both sides speak.
+        let protocol_version = negotiate_protocol_version(
+            MIN_HANDSHAKE_PROTOCOL_VERSION,
+            HANDSHAKE_PROTOCOL_VERSION,
+            announcement.min_protocol_version,
+            announcement.protocol_version,
+        )
+        .ok_or(HandshakeError::ProtocolVersionMismatch {
+            min_supported: MIN_HANDSHAKE_PROTOCOL_VERSION,
+            max_supported: HANDSHAKE_PROTOCOL_VERSION,
+            min_announced: announcement.min_protocol_version,
+            max_announced: announcement.protocol_version,
+        })?;
+
+        // 3 The engine does not register with itself.
+        if announcement.operator_key == engine_key {
+            return Err(HandshakeError::EngineKeyCannotRegister);
+        }
+
+        // 4 Check the announcement is fresh.
+        if announcement.timestamp.abs_diff(now) > MAX_CLOCK_SKEW_SECS {
+            return Err(HandshakeError::ClockSkewTooLarge {
+                announced: announcement.timestamp,
+                now,
+            });
+        }
+
+        // 5 Reject announcements that are not newer than the latest accepted one.
+        if let Some(session) = self.sessions.get(&announcement.operator_key) {
+            if announcement.timestamp <= session.announced_at {
+                return Err(HandshakeError::ReplayedAnnouncement {
+                    last_timestamp: session.announced_at,
+                });
+            }
+        }
+
+        // 6 Issue a fresh session id.
+        let mut session_id = [0u8; 32];
+        OsRng.fill_bytes(&mut session_id);
+
+        // 7 Construct the session, carrying over the work of a prior session that is still online.
+        let features = announcement.features & ProtocolFeature::local_bitmap();
+        let mut session =
+            OperatorSession::new(announcement, session_id, protocol_version, features, now);
+        if let Some(prior_session) = self.sessions.remove(&announcement.operator_key) {
+            if prior_session.liveness.is_online() {
+                session.pending_work = prior_session.pending_work;
+            }
+        }
+
+        // 8 Hand the unassigned work the operator is capable of over to it.
+        let mut unassigned_work = Vec::new();
+        for work in self.unassigned_work.drain(..) {
+            match session.has_capability(work.capability()) && !session.pending_work.contains(&work)
+            {
+                true => session.pending_work.push(work),
+                false => unassigned_work.push(work),
+            }
+        }
+        self.unassigned_work = unassigned_work;
+
+        // 9 Register the session.
+        self.sessions.insert(announcement.operator_key, session);
+
+        // 10 Return the session.
+        Ok(&self.sessions[&announcement.operator_key])
+    }
+
+    /// Validates a heartbeat and records the operator as seen.
+    pub fn heartbeat(
+        &mut self,
+        heartbeat: &Heartbeat,
+        now: Timestamp,
+    ) -> Result<&OperatorSession, HeartbeatError> {
+        // 1 Verify the heartbeat signature.
+        if !heartbeat.verify() {
+            return Err(HeartbeatError::InvalidSignature);
+        }
+
+        // 2 Get the session of the operator.
+        let session = self
+            .sessions
+            .get_mut(&heartbeat.sender_key)
+            .ok_or(HeartbeatError::UnknownSession)?;
+
+        // 3 Check the heartbeat belongs to the current session.
+        if heartbeat.session_id != session.session_id {
+            return Err(HeartbeatError::SessionMismatch);
+        }
+
+        // 4 Expired sessions have to register again.
+        if let Liveness::Offline(since) = session.liveness {
+            return Err(HeartbeatError::SessionExpired(since));
+        }
+
+        // 5 Check the heartbeat is fresh.
+        if heartbeat.timestamp.abs_diff(now) > MAX_CLOCK_SKEW_SECS {
+            return Err(HeartbeatError::ClockSkewTooLarge {
+                announced: heartbeat.timestamp,
+                now,
+            });
+        }
+
+        // 6 Reject heartbeats that are not newer than the latest accepted one.
+        if heartbeat.timestamp <= session.last_heartbeat_at {
+            return Err(HeartbeatError::ReplayedHeartbeat {
+                last_timestamp: session.last_heartbeat_at,
+            });
+        }
+
+        // 7 Record the operator as seen.
+        session.last_heartbeat_at = heartbeat.timestamp;
+        session.last_seen = now;
+        session.cube_batch_sync_height = heartbeat.cube_batch_sync_height;
+
+        // 8 Return the session.
+        Ok(session)
+    }
+
+    /// Marks the operators that have not been heard from within the session timeout as offline,
+    /// and reassigns their pending work to the online ones.
+    ///
+    /// Returns the keys of the operators that went offline.
+    pub fn sweep(&mut self, now: Timestamp) -> Vec<OperatorKey> {
+        // 1 Mark the timed out operators as offline and collect their pending work.
+        let mut timed_out_operators = Vec::new();
+        let mut orphaned_work = Vec::new();
+        for (operator_key, session) in self.sessions.iter_mut() {
+            if session.liveness.is_online()
+                && now.saturating_sub(session.last_seen) > SESSION_TIMEOUT_SECS
+            {
+                session.liveness = Liveness::Offline(now);
+                orphaned_work.extend(session.pending_work.drain(..));
+                timed_out_operators.push(*operator_key);
+            }
+        }
+
+        // 2 Reassign the orphaned work.
+        for work in orphaned_work {
+            self.place(work);
+        }
+
+        // 3 Return the timed out operators.
+        timed_out_operators
+    }
+
+    /// Assigns the given number of copies of a work to distinct online operators capable of it.
+    ///
+    /// Copies no operator could take on are kept unassigned until one registers. Returns the operators
+    /// the work was assigned to.
+    pub fn assign(&mut self, work: WorkAssignment, copies: usize) -> Vec<OperatorKey> {
+        (0..copies).filter_map(|_| self.place(work)).collect()
+    }
+
+    /// Assigns a work to the least loaded online operator capable of it that does not hold it yet,
+    /// or keeps it unassigned if there is none.
+    fn place(&mut self, work: WorkAssignment) -> Option<OperatorKey> {
+        let assignee = self
+            .sessions
+            .values_mut()
+            .filter(|session| {
+                session.liveness.is_online()
+                    && session.has_capability(work.capability())
+                    && !session.pending_work.contains(&work)
+            })
+            .min_by_key(|session| (session.pending_work.len(), session.registered_at));
+
+        match assignee {
+            Some(session) => {
+                session.pending_work.push(work);
+                Some(session.operator_key)
+            }
+            None => {
+                self.unassigned_work.push(work);
+                None
+            }
+        }
+    }
+
+    /// Marks a work as completed by an operator.
+    ///
+    /// Returns whether the work was assigned to the operator.
+    pub fn complete(&mut self, operator_key: OperatorKey, work: &WorkAssignment) -> bool {
+        match self.sessions.get_mut(&operator_key) {
+            Some(session) => {
+                let pending_work_len = session.pending_work.len();
+                session.pending_work.retain(|pending| pending != work);
+                session.pending_work.len() != pending_work_len
+            }
+            None => false,
+        }
+    }
+
+    /// Drops every assigned or unassigned work about a batch height that no longer needs to be done.
+    pub fn settle(&mut self, batch_height: BatchHeight) {
+        for session in self.sessions.values_mut() {
+            session
+                .pending_work
+                .retain(|work| work.batch_height() != batch_height);
+        }
+        self.unassigned_work
+            .retain(|work| work.batch_height() != batch_height);
+    }
+
+    /// Returns the session of an operator.
+    pub fn session(&self, operator_key: OperatorKey) -> Option<&OperatorSession> {
+        self.sessions.get(&operator_key)
+    }
+
+    /// Returns the number of registered operators.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Whether no operator is registered.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Returns the number of online operators.
+    pub fn online_count(&self) -> usize {
+        self.sessions
+            .values()
+            .filter(|session| session.liveness.is_online())
+            .count()
+    }
+
+    /// Returns the number of work copies no operator could take on yet.
+    pub fn unassigned_work_count(&self) -> usize {
+        self.unassigned_work.len()
+    }
+
+    /// Returns the registered operators as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut sessions = self.sessions.values().collect::<Vec<_>>();
+        sessions.sort_by_key(|session| session.registered_at);
+
+        let mut obj = Map::new();
+        obj.insert(
+            "operator_count".to_string(),
+            Value::Number(self.sessions.len().into()),
+        );
+        obj.insert(
+            "online_count".to_string(),
+            Value::Number(self.online_count().into()),
+        );
+        obj.insert(
+            "operators".to_string(),
+            Value::Array(sessions.iter().map(|session| session.json()).collect()),
+        );
+        obj.insert(
+            "unassigned_work".to_string(),
+            Value::Array(
+                self.unassigned_work
+                    .iter()
+                    .map(|work| work.json())
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+}
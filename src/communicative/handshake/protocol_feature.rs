@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// Feature bitmap, one bit per protocol feature.
+pub type FeatureBitmap = u64;
+
+/// Optional wire features peers negotiate during the handshake, each one bit of the feature bitmap.
+///
+/// Unlike capabilities, which are roles an operator takes on, features are wire changes both peers
+/// must understand before either one relies on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProtocolFeature {
+    // Packages sealed in NIP-44 envelopes.
+    Envelope,
+
+    // Packages encrypted over a Noise XX session.
+    NoiseTransport,
+
+    // Envelopes carrying sequence numbers, refused when replayed.
+    ReplayProtection,
+
+    // Large packages compressed with zstd.
+    Compression,
+}
+
+impl ProtocolFeature {
+    /// Returns all the features supported by this build.
+    pub fn all() -> Vec<ProtocolFeature> {
+        vec![
+            ProtocolFeature::Envelope,
+            ProtocolFeature::NoiseTransport,
+            ProtocolFeature::ReplayProtection,
+            ProtocolFeature::Compression,
+        ]
+    }
+
+    /// Returns the bit of the feature in the feature bitmap.
+    pub fn bit(&self) -> u8 {
+        match self {
+            ProtocolFeature::Envelope => 0,
+            ProtocolFeature::NoiseTransport => 1,
+            ProtocolFeature::ReplayProtection => 2,
+            ProtocolFeature::Compression => 3,
+        }
+    }
+
+    /// Returns the feature from its bit in the feature bitmap.
+    pub fn from_bit(bit: u8) -> Option<Self> {
+        match bit {
+            0 => Some(ProtocolFeature::Envelope),
+            1 => Some(ProtocolFeature::NoiseTransport),
+            2 => Some(ProtocolFeature::ReplayProtection),
+            3 => Some(ProtocolFeature::Compression),
+            _ => None,
+        }
+    }
+
+    /// Whether the feature is set in a feature bitmap.
+    pub fn in_bitmap(&self, bitmap: FeatureBitmap) -> bool {
+        bitmap & (1 << self.bit()) != 0
+    }
+
+    /// Returns the feature bitmap of the given features.
+    pub fn bitmap(features: &[ProtocolFeature]) -> FeatureBitmap {
+        features
+            .iter()
+            .fold(0, |bitmap, feature| bitmap | (1 << feature.bit()))
+    }
+
+    /// Returns the known features set in a feature bitmap, ignoring unknown bits.
+    pub fn from_bitmap(bitmap: FeatureBitmap) -> Vec<ProtocolFeature> {
+        (0..64)
+            .filter(|bit| bitmap & (1 << bit) != 0)
+            .filter_map(ProtocolFeature::from_bit)
+            .collect()
+    }
+
+    /// Returns the feature bitmap of this build.
+    pub fn local_bitmap() -> FeatureBitmap {
+        Self::bitmap(&Self::all())
+    }
+}
+
+impl ToString for ProtocolFeature {
+    fn to_string(&self) -> String {
+        match self {
+            ProtocolFeature::Envelope => "envelope".to_string(),
+            ProtocolFeature::NoiseTransport => "noise_transport".to_string(),
+            ProtocolFeature::ReplayProtection => "replay_protection".to_string(),
+            ProtocolFeature::Compression => "compression".to_string(),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::communicative::handshake::protocol_feature::{FeatureBitmap, ProtocolFeature};
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Operator key.
+type OperatorKey = [u8; 32];
+
+/// Engine key.
+type EngineKey = [u8; 32];
+
+/// Session id.
+type SessionId = [u8; 32];
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Interval at which operators are expected to check in with the engine.
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 15;
+
+/// Silence after which the engine considers an operator session expired.
+pub const SESSION_TIMEOUT_SECS: u64 = 60;
+
+mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// Session parameters the engine issues to a registered operator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionParams {
+    // Session id.
+    pub session_id: SessionId,
+
+    // The operator key the session is issued to.
+    pub operator_key: OperatorKey,
+
+    // Negotiated handshake protocol version of the session.
+    pub protocol_version: u16,
+
+    // Protocol features negotiated for the session.
+    pub features: FeatureBitmap,
+
+    // Heartbeat interval in seconds.
+    pub heartbeat_interval_secs: u64,
+
+    // Session timeout in seconds.
+    pub session_timeout_secs: u64,
+
+    // Cube batch sync height of the engine.
+    pub engine_cube_batch_sync_height: BatchHeight,
+
+    // Issuance timestamp.
+    pub issued_at: Timestamp,
+
+    // The engine's Schnorr signature over the session params message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl SessionParams {
+    /// Issues and signs session params with the engine's key.
+    pub fn sign(
+        keys: &KeyHolder,
+        session_id: SessionId,
+        operator_key: OperatorKey,
+        protocol_version: u16,
+        features: FeatureBitmap,
+        engine_cube_batch_sync_height: BatchHeight,
+        issued_at: Timestamp,
+    ) -> Option<Self> {
+        // 1 Construct the unsigned session params.
+        let mut session_params = Self {
+            session_id,
+            operator_key,
+            protocol_version,
+            features,
+            heartbeat_interval_secs: HEARTBEAT_INTERVAL_SECS,
+            session_timeout_secs: SESSION_TIMEOUT_SECS,
+            engine_cube_batch_sync_height,
+            issued_at,
+            signature: [0u8; 64],
+        };
+
+        // 2 Sign the session params message.
+        session_params.signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            session_params.message(),
+            SchnorrSigningMode::Cube,
+        )?;
+
+        // 3 Return the session params.
+        Some(session_params)
+    }
+
+    /// Returns the message the engine signs.
+    pub fn message(&self) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::new();
+        preimage.extend(self.session_id);
+        preimage.extend(self.operator_key);
+        preimage.extend(self.protocol_version.to_be_bytes());
+        preimage.extend(self.features.to_be_bytes());
+        preimage.extend(self.heartbeat_interval_secs.to_be_bytes());
+        preimage.extend(self.session_timeout_secs.to_be_bytes());
+        preimage.extend(self.engine_cube_batch_sync_height.to_be_bytes());
+        preimage.extend(self.issued_at.to_be_bytes());
+        preimage.hash(Some(HashTag::SessionParams))
+    }
+
+    /// Verifies the session params against the engine key.
+    pub fn verify(&self, engine_key: EngineKey) -> bool {
+        schnorr::verify_xonly(
+            engine_key,
+            self.message(),
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+
+    /// Returns the session params as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "session_id".to_string(),
+            Value::String(hex::encode(self.session_id)),
+        );
+        obj.insert(
+            "protocol_version".to_string(),
+            Value::Number(self.protocol_version.into()),
+        );
+        obj.insert(
+            "features".to_string(),
+            Value::Array(
+                ProtocolFeature::from_bitmap(self.features)
+                    .iter()
+                    .map(|feature| Value::String(feature.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "heartbeat_interval_secs".to_string(),
+            Value::Number(self.heartbeat_interval_secs.into()),
+        );
+        obj.insert(
+            "session_timeout_secs".to_string(),
+            Value::Number(self.session_timeout_secs.into()),
+        );
+        obj.insert(
+            "engine_cube_batch_sync_height".to_string(),
+            Value::Number(self.engine_cube_batch_sync_height.into()),
+        );
+        obj.insert(
+            "issued_at".to_string(),
+            Value::Number(self.issued_at.into()),
+        );
+        Value::Object(obj)
+    }
+}
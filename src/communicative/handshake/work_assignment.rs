@@ -0,0 +1,60 @@
+use crate::communicative::handshake::capability::Capability;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Batch txid.
+type BatchTxid = [u8; 32];
+
+/// A unit of work the engine assigns to a registered operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WorkAssignment {
+    // Co-sign the applied delta of a batch.
+    DeltaCosign {
+        batch_height: BatchHeight,
+        batch_txid: BatchTxid,
+    },
+}
+
+impl WorkAssignment {
+    /// Returns the capability an operator needs to take on the work.
+    pub fn capability(&self) -> Capability {
+        match self {
+            WorkAssignment::DeltaCosign { .. } => Capability::DeltaCosign,
+        }
+    }
+
+    /// Returns the batch height the work is about.
+    pub fn batch_height(&self) -> BatchHeight {
+        match self {
+            WorkAssignment::DeltaCosign { batch_height, .. } => *batch_height,
+        }
+    }
+
+    /// Returns the work assignment as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            WorkAssignment::DeltaCosign {
+                batch_height,
+                batch_txid,
+            } => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("delta_cosign".to_string()),
+                );
+                obj.insert(
+                    "batch_height".to_string(),
+                    Value::Number((*batch_height).into()),
+                );
+                obj.insert(
+                    "batch_txid".to_string(),
+                    Value::String(hex::encode(batch_txid)),
+                );
+            }
+        }
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,246 @@
+use crate::communicative::inbound::inbound_priority::InboundPriority;
+use crate::communicative::rate_limiter::errors::rate_limit_error::RateLimitError;
+use crate::communicative::tcp::package::PackageKind;
+use serde_json::{Map, Value};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::Notify;
+
+/// The maximum number of inbound packages processed at once.
+pub const MAX_PROCESSING_PACKAGES: usize = 64;
+
+/// The maximum number of inbound packages processed at once before low-priority packages are shed.
+pub const LOW_PRIORITY_PROCESSING_LIMIT: usize = 48;
+
+/// The maximum number of high-priority packages deferred until there is room.
+pub const MAX_DEFERRED_PACKAGES: usize = 256;
+
+/// Milliseconds a shed package is advised to wait before being sent again.
+pub const OVERLOADED_RETRY_AFTER_MS: u64 = 1_000;
+
+/// Process-wide inbound gate.
+static INBOUND_GATE: OnceLock<Mutex<InboundGate>> = OnceLock::new();
+
+/// Outcome of the admission of an inbound package.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InboundAdmission {
+    /// The package may be processed right away.
+    Admitted,
+    /// The package has to wait for a processing slot.
+    Deferred,
+    /// The package is dropped, and its sender told to retry later.
+    Shed,
+}
+
+/// Bounds the inbound packages processed at once.
+///
+/// Up to `MAX_PROCESSING_PACKAGES` packages are processed at once. Past that, high-priority packages
+/// are deferred until a slot frees up, up to `MAX_DEFERRED_PACKAGES` of them, while low-priority
+/// packages are shed as soon as processing nears its bound, so that the remaining slots are kept for
+/// entries and signing rounds.
+pub struct InboundGate {
+    // Number of packages being processed.
+    processing: usize,
+
+    // Number of packages waiting for a processing slot.
+    deferred: usize,
+
+    // Number of packages deferred since process start.
+    deferred_total: u64,
+
+    // Number of high-priority packages shed since process start.
+    shed_high: u64,
+
+    // Number of low-priority packages shed since process start.
+    shed_low: u64,
+
+    // Wakes up deferred packages whenever a slot frees up.
+    notify: Arc<Notify>,
+}
+
+impl InboundGate {
+    /// Constructs an empty inbound gate.
+    pub fn new() -> Self {
+        Self {
+            processing: 0,
+            deferred: 0,
+            deferred_total: 0,
+            shed_high: 0,
+            shed_low: 0,
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Admits, defers or sheds an inbound package of the given priority.
+    pub fn try_admit(&mut self, priority: InboundPriority) -> InboundAdmission {
+        match priority {
+            InboundPriority::High => {
+                // 1 Admit right away if there is a slot and nothing is waiting for one.
+                if self.deferred == 0 && self.processing < MAX_PROCESSING_PACKAGES {
+                    self.processing += 1;
+                    return InboundAdmission::Admitted;
+                }
+
+                // 2 Defer if there is room to wait.
+                if self.deferred < MAX_DEFERRED_PACKAGES {
+                    self.deferred += 1;
+                    self.deferred_total = self.deferred_total.saturating_add(1);
+                    return InboundAdmission::Deferred;
+                }
+
+                // 3 Shed otherwise.
+                self.shed_high = self.shed_high.saturating_add(1);
+                InboundAdmission::Shed
+            }
+            InboundPriority::Low => {
+                // 1 Admit right away if processing is not saturated.
+                if self.deferred == 0 && self.processing < LOW_PRIORITY_PROCESSING_LIMIT {
+                    self.processing += 1;
+                    return InboundAdmission::Admitted;
+                }
+
+                // 2 Shed otherwise.
+                self.shed_low = self.shed_low.saturating_add(1);
+                InboundAdmission::Shed
+            }
+        }
+    }
+
+    /// Admits a deferred package if a slot is free.
+    ///
+    /// Returns false if the package has to keep waiting.
+    pub fn try_admit_deferred(&mut self) -> bool {
+        if self.processing >= MAX_PROCESSING_PACKAGES {
+            return false;
+        }
+        self.deferred = self.deferred.saturating_sub(1);
+        self.processing += 1;
+        true
+    }
+
+    /// Abandons a deferred package, e.g. when its connection has dropped.
+    pub fn abandon_deferred(&mut self) {
+        self.deferred = self.deferred.saturating_sub(1);
+    }
+
+    /// Releases the slot of a processed package.
+    pub fn release(&mut self) {
+        self.processing = self.processing.saturating_sub(1);
+        self.notify.notify_one();
+    }
+
+    /// Returns the number of packages being processed.
+    pub fn processing(&self) -> usize {
+        self.processing
+    }
+
+    /// Returns the number of packages waiting for a processing slot.
+    pub fn deferred(&self) -> usize {
+        self.deferred
+    }
+
+    /// Returns the number of packages of the given priority shed since process start.
+    pub fn shed(&self, priority: InboundPriority) -> u64 {
+        match priority {
+            InboundPriority::High => self.shed_high,
+            InboundPriority::Low => self.shed_low,
+        }
+    }
+
+    /// Returns the gate load as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "processing".to_string(),
+            Value::Number((self.processing as u64).into()),
+        );
+        obj.insert(
+            "deferred".to_string(),
+            Value::Number((self.deferred as u64).into()),
+        );
+        obj.insert(
+            "deferred_total".to_string(),
+            Value::Number(self.deferred_total.into()),
+        );
+        let mut shed = Map::new();
+        shed.insert(
+            InboundPriority::High.to_string(),
+            Value::Number(self.shed_high.into()),
+        );
+        shed.insert(
+            InboundPriority::Low.to_string(),
+            Value::Number(self.shed_low.into()),
+        );
+        obj.insert("shed".to_string(), Value::Object(shed));
+        Value::Object(obj)
+    }
+}
+
+impl Default for InboundGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the process-wide inbound gate.
+pub fn inbound_gate() -> &'static Mutex<InboundGate> {
+    INBOUND_GATE.get_or_init(|| Mutex::new(InboundGate::new()))
+}
+
+/// A processing slot taken on the process-wide inbound gate, released when dropped.
+pub struct InboundPermit {
+    // Whether the slot is still to be released.
+    admitted: bool,
+}
+
+impl Drop for InboundPermit {
+    fn drop(&mut self) {
+        match self.admitted {
+            true => lock_inbound_gate().release(),
+            false => lock_inbound_gate().abandon_deferred(),
+        }
+    }
+}
+
+/// Waits for a processing slot for an inbound package of the given kind on the process-wide gate.
+///
+/// Returns an overloaded error to answer the sender with if the package is shed.
+pub async fn admit_inbound(kind: PackageKind) -> Result<InboundPermit, RateLimitError> {
+    // 1 Admit, defer or shed the package.
+    let (admission, notify) = {
+        let mut _inbound_gate = lock_inbound_gate();
+        (
+            _inbound_gate.try_admit(InboundPriority::of(kind)),
+            Arc::clone(&_inbound_gate.notify),
+        )
+    };
+    let mut permit = match admission {
+        InboundAdmission::Admitted => return Ok(InboundPermit { admitted: true }),
+        InboundAdmission::Shed => {
+            return Err(RateLimitError::Overloaded(OVERLOADED_RETRY_AFTER_MS))
+        }
+        // Hand out the permit, so that the wait is abandoned if this future is dropped.
+        InboundAdmission::Deferred => InboundPermit { admitted: false },
+    };
+
+    // 2 Wait for a slot to free up.
+    loop {
+        // 2.1 Register for the next wake up before checking, so that no wake up is missed.
+        let notified = notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        // 2.2 Take the slot if it is free.
+        if lock_inbound_gate().try_admit_deferred() {
+            permit.admitted = true;
+            return Ok(permit);
+        }
+        notified.await;
+    }
+}
+
+/// Locks the process-wide inbound gate.
+fn lock_inbound_gate() -> std::sync::MutexGuard<'static, InboundGate> {
+    inbound_gate()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,46 @@
+use crate::communicative::tcp::package::PackageKind;
+
+/// Priority of an inbound package when processing is saturated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InboundPriority {
+    /// Entries, signing rounds and session upkeep, deferred until there is room.
+    High,
+    /// Pings, proofs and archive lookups, shed right away.
+    Low,
+}
+
+impl InboundPriority {
+    /// Returns the priority of a package kind.
+    pub fn of(kind: PackageKind) -> InboundPriority {
+        match kind {
+            PackageKind::LiftupV1Protocol
+            | PackageKind::MoveProtocol
+            | PackageKind::SwapoutProtocol
+            | PackageKind::ConfigProtocol
+            | PackageKind::DeployProtocol
+            | PackageKind::DeltaCosignProtocol
+            | PackageKind::HandshakeProtocol
+            | PackageKind::HeartbeatProtocol
+            | PackageKind::InFlightSyncProtocol => InboundPriority::High,
+            PackageKind::Ping
+            | PackageKind::BalanceProofProtocol
+            | PackageKind::BatchRecordProtocol
+            | PackageKind::BatchContainerProtocol
+            | PackageKind::BatchContainerByPrevOutpointProtocol
+            | PackageKind::RateLimited
+            | PackageKind::Envelope
+            | PackageKind::NoiseHandshake
+            | PackageKind::NoiseTransport
+            | PackageKind::Compressed => InboundPriority::Low,
+        }
+    }
+}
+
+impl ToString for InboundPriority {
+    fn to_string(&self) -> String {
+        match self {
+            InboundPriority::High => "high".to_string(),
+            InboundPriority::Low => "low".to_string(),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod inbound_gate;
+pub mod inbound_priority;
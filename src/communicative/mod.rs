@@ -1,4 +1,17 @@
+pub mod api_auth;
+pub mod discovery;
+pub mod event_stream;
+pub mod federation;
+pub mod gossip;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod handshake;
+pub mod inbound;
 pub mod nns;
+pub mod outbox;
 pub mod peer;
+pub mod rate_limiter;
 pub mod rpc;
 pub mod tcp;
+pub mod traffic;
+pub mod webhook;
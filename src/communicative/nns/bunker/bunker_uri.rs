@@ -0,0 +1,85 @@
+use nostr_sdk::PublicKey;
+
+/// A NIP-46 `bunker://<remote-signer-pubkey>?relay=<wss://...>&secret=<secret>` connection URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BunkerURI {
+    // Public key of the remote signer.
+    pub remote_signer_public_key: PublicKey,
+
+    // Relays the remote signer listens on.
+    pub relays: Vec<String>,
+
+    // Optional single-use connection secret.
+    pub secret: Option<String>,
+}
+
+impl BunkerURI {
+    /// Parses a bunker URI.
+    ///
+    /// Returns `None` if the URI is malformed or names no relay.
+    pub fn parse(uri: &str) -> Option<Self> {
+        // 1 Strip the scheme.
+        let rest = uri.trim().strip_prefix("bunker://")?;
+
+        // 2 Split the public key from the query.
+        let (public_key_hex, query) = match rest.split_once('?') {
+            Some((public_key_hex, query)) => (public_key_hex, query),
+            None => (rest, ""),
+        };
+
+        // 3 Parse the public key of the remote signer.
+        let remote_signer_public_key = PublicKey::from_hex(public_key_hex).ok()?;
+
+        // 4 Parse the relays and the secret.
+        let mut relays = Vec::<String>::new();
+        let mut secret: Option<String> = None;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=')?;
+            let value = percent_decode(value)?;
+            match key {
+                "relay" => {
+                    if !value.starts_with("wss://") && !value.starts_with("ws://") {
+                        return None;
+                    }
+                    if !relays.contains(&value) {
+                        relays.push(value);
+                    }
+                }
+                "secret" => secret = Some(value),
+                _ => {}
+            }
+        }
+
+        // 5 A bunker is reachable through at least one relay.
+        if relays.is_empty() {
+            return None;
+        }
+
+        Some(BunkerURI {
+            remote_signer_public_key,
+            relays,
+            secret,
+        })
+    }
+}
+
+/// Decodes the `%XX` escapes of a URI query value.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::<u8>::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let hex = value.get(index + 1..index + 3)?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
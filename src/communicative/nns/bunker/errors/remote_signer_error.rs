@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// Errors associated with a NIP-46 remote signer.
+#[derive(Debug, Clone)]
+pub enum RemoteSignerError {
+    // The bunker URI is malformed.
+    InvalidBunkerURI,
+    // A relay of the bunker could not be added.
+    RelayErr(String),
+    // Subscribing to the responses of the remote signer failed.
+    SubscribeErr(String),
+    // Encrypting a request failed.
+    EncryptErr(String),
+    // Signing or publishing a request failed.
+    PublishErr(String),
+    // No response arrived within the timeout.
+    Timeout(String),
+    // The remote signer responded with an error.
+    RemoteErr(String),
+    // The response of the remote signer is malformed.
+    InvalidResponse(String),
+    // The connection secret was not acknowledged.
+    SecretMismatch,
+}
+
+impl fmt::Display for RemoteSignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteSignerError::InvalidBunkerURI => write!(f, "Invalid bunker URI."),
+            RemoteSignerError::RelayErr(err) => write!(f, "Relay error: {}", err),
+            RemoteSignerError::SubscribeErr(err) => write!(f, "Subscribe error: {}", err),
+            RemoteSignerError::EncryptErr(err) => write!(f, "Encrypt error: {}", err),
+            RemoteSignerError::PublishErr(err) => write!(f, "Publish error: {}", err),
+            RemoteSignerError::Timeout(method) => {
+                write!(f, "Timed out waiting for the '{}' response.", method)
+            }
+            RemoteSignerError::RemoteErr(err) => write!(f, "Remote signer error: {}", err),
+            RemoteSignerError::InvalidResponse(err) => write!(f, "Invalid response: {}", err),
+            RemoteSignerError::SecretMismatch => {
+                write!(f, "The remote signer did not acknowledge the secret.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RemoteSignerError {}
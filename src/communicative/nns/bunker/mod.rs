@@ -0,0 +1,3 @@
+pub mod bunker_uri;
+pub mod errors;
+pub mod remote_signer;
@@ -0,0 +1,289 @@
+use crate::communicative::nns::bunker::bunker_uri::BunkerURI;
+use crate::communicative::nns::bunker::errors::remote_signer_error::RemoteSignerError;
+use async_trait::async_trait;
+use nostr_sdk::nips::nip44;
+use nostr_sdk::signer::SignerBackend;
+use nostr_sdk::{
+    Event, EventBuilder, Filter, JsonUtil, Keys, Kind, NostrSigner, PublicKey,
+    RelayPoolNotification, SignerError, Tag, Timestamp, UnsignedEvent,
+};
+use rand::RngCore;
+use serde_json::{json, Value};
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+/// A NIP-46 remote signer ("bunker").
+///
+/// Requests are NIP-44 encrypted to the remote signer and published over its relays as kind `24133`
+/// events signed with an ephemeral client key; each awaits the response with the same id, until the
+/// timeout. The secret key never leaves the bunker.
+pub struct RemoteSigner {
+    // Nostr client connected to the relays of the bunker.
+    nostr_client: nostr_sdk::Client,
+
+    // Ephemeral keys of this client.
+    client_keys: Keys,
+
+    // Public key of the remote signer.
+    remote_signer_public_key: PublicKey,
+
+    // Public key the remote signer signs for.
+    user_public_key: PublicKey,
+
+    // How long to wait for each response.
+    timeout: Duration,
+}
+
+impl RemoteSigner {
+    /// Connects to the bunker and retrieves the public key it signs for.
+    pub async fn connect(
+        bunker_uri: &BunkerURI,
+        timeout: Duration,
+    ) -> Result<RemoteSigner, RemoteSignerError> {
+        // 1 Generate the ephemeral client keys.
+        let client_keys = Keys::generate();
+
+        // 2 Connect to the relays of the bunker.
+        let nostr_client = nostr_sdk::Client::new(client_keys.clone());
+        for relay in bunker_uri.relays.iter() {
+            nostr_client
+                .add_relay(relay.as_str())
+                .await
+                .map_err(|err| RemoteSignerError::RelayErr(err.to_string()))?;
+        }
+        nostr_client.connect().await;
+
+        // 3 Subscribe to the responses addressed to this client.
+        let filter = Filter::new()
+            .kind(Kind::NostrConnect)
+            .author(bunker_uri.remote_signer_public_key)
+            .pubkey(client_keys.public_key())
+            .since(Timestamp::now());
+        nostr_client
+            .subscribe(vec![filter], None)
+            .await
+            .map_err(|err| RemoteSignerError::SubscribeErr(err.to_string()))?;
+
+        // 4 Construct the remote signer, with the user public key not known yet.
+        let mut remote_signer = RemoteSigner {
+            nostr_client,
+            client_keys,
+            remote_signer_public_key: bunker_uri.remote_signer_public_key,
+            user_public_key: bunker_uri.remote_signer_public_key,
+            timeout,
+        };
+
+        // 5 Connect, presenting the secret, if any.
+        let secret = bunker_uri.secret.clone().unwrap_or_default();
+        let result = remote_signer
+            .request(
+                "connect",
+                vec![bunker_uri.remote_signer_public_key.to_hex(), secret.clone()],
+            )
+            .await?;
+        if result != "ack" && (secret.is_empty() || result != secret) {
+            return Err(RemoteSignerError::SecretMismatch);
+        }
+
+        // 6 Retrieve the user public key.
+        let user_public_key_hex = remote_signer.request("get_public_key", vec![]).await?;
+        remote_signer.user_public_key = PublicKey::from_hex(&user_public_key_hex)
+            .map_err(|err| RemoteSignerError::InvalidResponse(err.to_string()))?;
+
+        Ok(remote_signer)
+    }
+
+    /// Returns the public key the remote signer signs for.
+    pub fn user_public_key(&self) -> PublicKey {
+        self.user_public_key
+    }
+
+    /// Checks that the remote signer is responsive.
+    pub async fn ping(&self) -> Result<(), RemoteSignerError> {
+        match self.request("ping", vec![]).await?.as_str() {
+            "pong" => Ok(()),
+            result => Err(RemoteSignerError::InvalidResponse(result.to_string())),
+        }
+    }
+
+    /// Has the remote signer sign an event, and checks the signature.
+    pub async fn sign_unsigned_event(
+        &self,
+        unsigned: UnsignedEvent,
+    ) -> Result<Event, RemoteSignerError> {
+        // 1 Forward the event to the remote signer.
+        let result = self.request("sign_event", vec![unsigned.as_json()]).await?;
+
+        // 2 Parse the signed event.
+        let event = Event::from_json(&result)
+            .map_err(|err| RemoteSignerError::InvalidResponse(err.to_string()))?;
+
+        // 3 Check that it was signed by the user key.
+        if event.pubkey != self.user_public_key || event.verify().is_err() {
+            return Err(RemoteSignerError::InvalidResponse(
+                "Invalid event signature.".to_string(),
+            ));
+        }
+
+        Ok(event)
+    }
+
+    /// Sends a request to the remote signer and awaits its result.
+    async fn request(
+        &self,
+        method: &str,
+        params: Vec<String>,
+    ) -> Result<String, RemoteSignerError> {
+        // 1 Listen for the response before publishing the request.
+        let mut notifications = self.nostr_client.notifications();
+
+        // 2 Compose the request with a random id.
+        let id = {
+            let mut id_bytes = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut id_bytes);
+            hex::encode(id_bytes)
+        };
+        let content = json!({ "id": id, "method": method, "params": params }).to_string();
+
+        // 3 Encrypt the request to the remote signer.
+        let encrypted_content = nip44::encrypt(
+            self.client_keys.secret_key(),
+            &self.remote_signer_public_key,
+            content,
+            nip44::Version::V2,
+        )
+        .map_err(|err| RemoteSignerError::EncryptErr(err.to_string()))?;
+
+        // 4 Sign the request with the client keys and publish it.
+        let event = EventBuilder::new(Kind::NostrConnect, encrypted_content)
+            .tag(Tag::public_key(self.remote_signer_public_key))
+            .sign_with_keys(&self.client_keys)
+            .map_err(|err| RemoteSignerError::PublishErr(err.to_string()))?;
+        self.nostr_client
+            .send_event(event)
+            .await
+            .map_err(|err| RemoteSignerError::PublishErr(err.to_string()))?;
+
+        // 5 Await the response with the same id.
+        let response = tokio::time::timeout(self.timeout, async {
+            loop {
+                let event = match notifications.recv().await {
+                    Ok(RelayPoolNotification::Event { event, .. }) => event,
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => {
+                        return Err(RemoteSignerError::RelayErr(
+                            "Relay pool shut down.".to_string(),
+                        ))
+                    }
+                };
+
+                // 5.1 Skip events other than responses of the remote signer.
+                if event.kind != Kind::NostrConnect || event.pubkey != self.remote_signer_public_key
+                {
+                    continue;
+                }
+
+                // 5.2 Decrypt the response.
+                let content = match nip44::decrypt(
+                    self.client_keys.secret_key(),
+                    &self.remote_signer_public_key,
+                    &event.content,
+                ) {
+                    Ok(content) => content,
+                    Err(_) => continue,
+                };
+                let response: Value = match serde_json::from_str(&content) {
+                    Ok(response) => response,
+                    Err(_) => continue,
+                };
+
+                // 5.3 Skip responses to other requests.
+                if response.get("id").and_then(Value::as_str) != Some(id.as_str()) {
+                    continue;
+                }
+
+                let result = response.get("result").and_then(Value::as_str);
+                let error = response.get("error").and_then(Value::as_str);
+
+                // 5.4 An auth challenge carries the URL to approve the request at; keep waiting.
+                if result == Some("auth_url") {
+                    eprintln!(
+                        "Approve the '{}' request of the remote signer at: {}",
+                        method,
+                        error.unwrap_or_default()
+                    );
+                    continue;
+                }
+
+                // 5.5 Return the result or the error.
+                return match error.filter(|error| !error.is_empty()) {
+                    Some(error) => Err(RemoteSignerError::RemoteErr(error.to_string())),
+                    None => match result {
+                        Some(result) => Ok(result.to_string()),
+                        None => Err(RemoteSignerError::InvalidResponse(content)),
+                    },
+                };
+            }
+        })
+        .await;
+
+        match response {
+            Ok(response) => response,
+            Err(_) => Err(RemoteSignerError::Timeout(method.to_string())),
+        }
+    }
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("remote_signer_public_key", &self.remote_signer_public_key)
+            .field("user_public_key", &self.user_public_key)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl NostrSigner for RemoteSigner {
+    fn backend(&self) -> SignerBackend<'_> {
+        SignerBackend::NostrConnect
+    }
+
+    async fn get_public_key(&self) -> Result<PublicKey, SignerError> {
+        Ok(self.user_public_key)
+    }
+
+    async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, SignerError> {
+        self.sign_unsigned_event(unsigned)
+            .await
+            .map_err(SignerError::backend)
+    }
+
+    async fn nip44_encrypt(
+        &self,
+        public_key: &PublicKey,
+        content: &str,
+    ) -> Result<String, SignerError> {
+        self.request(
+            "nip44_encrypt",
+            vec![public_key.to_hex(), content.to_string()],
+        )
+        .await
+        .map_err(SignerError::backend)
+    }
+
+    async fn nip44_decrypt(
+        &self,
+        public_key: &PublicKey,
+        payload: &str,
+    ) -> Result<String, SignerError> {
+        self.request(
+            "nip44_decrypt",
+            vec![public_key.to_hex(), payload.to_string()],
+        )
+        .await
+        .map_err(SignerError::backend)
+    }
+}
@@ -1,25 +1,223 @@
+use super::errors::relay_list_error::RelayListError;
 use super::relay::{self, Relay};
+use super::relay_list::{RelayList, RELAY_LIST};
+use crate::communicative::discovery::announcement::PeerAnnouncement;
+use crate::communicative::federation::contract_notice::ContractNotice;
+use crate::communicative::federation::delta_attestation::AggregatedDeltaAttestation;
+use crate::inscriptive::baked;
+use crate::operative::run_args::chain::Chain;
 use crate::transmutative::key::KeyHolder;
-use nostr_sdk::{EventBuilder, Filter, FromBech32, Kind, PublicKey};
-use std::time::Duration;
+use crate::transmutative::nostr::event::NostrEvent;
+use crate::transmutative::secp::public_key;
+use nostr_sdk::{EventBuilder, Filter, JsonUtil, Kind, PublicKey, Tag};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Nostr event kind of peer announcements, addressable so that relays keep only the latest one per peer.
+pub const PEER_ANNOUNCEMENT_EVENT_KIND: u16 = 36_272;
+
+/// Maximum number of peer announcements fetched in a single query.
+const PEER_ANNOUNCEMENT_QUERY_LIMIT: usize = 500;
+
+/// Nostr event kind of gossiped delta attestations.
+pub const DELTA_GOSSIP_EVENT_KIND: u16 = 4_272;
+
+/// Maximum number of gossiped delta attestations fetched in a single query.
+const DELTA_GOSSIP_QUERY_LIMIT: usize = 200;
+
+/// Nostr event kind of contract registration notices.
+pub const CONTRACT_NOTICE_EVENT_KIND: u16 = 4_273;
+
+/// Maximum number of contract registration notices fetched in a single query.
+const CONTRACT_NOTICE_QUERY_LIMIT: usize = 200;
+
+/// Returns the identifier tag of peer announcements.
+fn peer_announcement_identifier() -> String {
+    format!("{}/{}", baked::PROJECT_TAG, "peer-announcement")
+}
 
 #[derive(Clone)]
 pub struct NNSClient {
     nostr_client: nostr_sdk::Client,
+    // Local Nostr keys, which payloads to peers are sealed with
+    local_keys: nostr_sdk::Keys,
+    // Runtime-managed relays and direct peers, if any
+    relay_list: Option<RELAY_LIST>,
 }
 
 impl NNSClient {
     pub async fn new(keys: &KeyHolder) -> Self {
-        let nostr_client = nostr_sdk::Client::new(keys.nostr_key_pair());
+        let nostr_client = nostr_sdk::Client::new(keys.nostr_signer());
         {
             nostr_client.add_default_relay_list().await;
             nostr_client.connect().await;
         }
-        NNSClient { nostr_client }
+        NNSClient {
+            nostr_client,
+            local_keys: keys.nostr_key_pair(),
+            relay_list: None,
+        }
+    }
+
+    /// Constructs a client using the relays of a relay list instead of the default ones.
+    ///
+    /// Events are then published to the healthiest relays of the list only, and their health is
+    /// recorded back into the list.
+    pub async fn with_relay_list(keys: &KeyHolder, relay_list: &RELAY_LIST) -> Self {
+        let nostr_client = nostr_sdk::Client::new(keys.nostr_signer());
+        {
+            for relay in relay_list.lock().await.relays() {
+                let _ = nostr_client.add_relay(relay).await;
+            }
+            nostr_client.connect().await;
+        }
+        NNSClient {
+            nostr_client,
+            local_keys: keys.nostr_key_pair(),
+            relay_list: Some(Arc::clone(relay_list)),
+        }
+    }
+
+    /// Returns the relay list, if the client uses one.
+    pub fn relay_list(&self) -> Option<RELAY_LIST> {
+        self.relay_list.clone()
+    }
+
+    /// Adds a relay and connects to it. Returns false if it was already in use.
+    pub async fn add_relay(&self, url: &str) -> Result<bool, RelayListError> {
+        let url = RelayList::normalize_url(url)?;
+
+        // 1 Persist the relay, if the client uses a relay list.
+        if let Some(relay_list) = &self.relay_list {
+            if !relay_list.lock().await.add_relay(&url)? {
+                return Ok(false);
+            }
+        }
+
+        // 2 Add the relay to the pool and connect to it.
+        let added = self
+            .nostr_client
+            .add_relay(&url)
+            .await
+            .map_err(|_| RelayListError::InvalidRelayUrl(url.clone()))?;
+        let _ = self.nostr_client.connect_relay(&url).await;
+
+        // 3 A relay new to the relay list counts as added even if the pool already had it.
+        Ok(added || self.relay_list.is_some())
+    }
+
+    /// Removes a relay and disconnects from it. Returns false if it was not in use.
+    pub async fn remove_relay(&self, url: &str) -> Result<bool, RelayListError> {
+        let url = RelayList::normalize_url(url)?;
+
+        // 1 Remove the relay from the relay list, if the client uses one.
+        if let Some(relay_list) = &self.relay_list {
+            if !relay_list.lock().await.remove_relay(&url)? {
+                return Ok(false);
+            }
+        }
+
+        // 2 Remove the relay from the pool.
+        Ok(self.nostr_client.remove_relay(&url).await.is_ok())
+    }
+
+    /// Returns the configured address of a direct peer, if any.
+    pub async fn direct_peer(&self, peer_key: [u8; 32]) -> Option<SocketAddr> {
+        match &self.relay_list {
+            Some(relay_list) => relay_list.lock().await.direct_peer(peer_key),
+            None => None,
+        }
+    }
+
+    /// Returns the relays queried for events.
+    async fn query_relays(&self) -> Vec<String> {
+        match &self.relay_list {
+            Some(relay_list) => relay_list.lock().await.relays(),
+            None => relay::DEFAULT_RELAY_LIST
+                .iter()
+                .map(|relay| relay.to_string())
+                .collect(),
+        }
+    }
+
+    /// Signs and publishes an event.
+    ///
+    /// With a relay list, the event is published to the healthiest relays first, falling back to the
+    /// rest of the relays if none of them accepted it. Each attempt is recorded into the relay health.
+    async fn publish(&self, builder: EventBuilder) -> Option<[u8; 32]> {
+        // 1 Without a relay list, publish to every relay.
+        let relay_list = match &self.relay_list {
+            Some(relay_list) => relay_list,
+            None => {
+                return match self.nostr_client.send_event_builder(builder).await {
+                    Ok(ok) => Some(ok.as_bytes().to_owned()),
+                    Err(_) => None,
+                };
+            }
+        };
+
+        // 2 Split the relays into the publishing ones and the fallback ones.
+        let (publishing_relays, fallback_relays) = {
+            let _relay_list = relay_list.lock().await;
+            let publishing_relays = _relay_list.publishing_relays();
+            let fallback_relays: Vec<String> = _relay_list
+                .relays()
+                .into_iter()
+                .filter(|relay| !publishing_relays.contains(relay))
+                .collect();
+            (publishing_relays, fallback_relays)
+        };
+
+        for relays in [publishing_relays, fallback_relays] {
+            if relays.is_empty() {
+                continue;
+            }
+
+            // 3 Publish to the relays, timing the round.
+            let started_at = Instant::now();
+            let output = match self
+                .nostr_client
+                .send_event_builder_to(relays.clone(), builder.clone())
+                .await
+            {
+                Ok(output) => output,
+                Err(_) => continue,
+            };
+            let latency_ms = started_at.elapsed().as_millis() as u64;
+
+            // 4 Record the relay health.
+            {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                let mut _relay_list = relay_list.lock().await;
+                for relay in output.success.iter() {
+                    _relay_list.record_success(relay.as_str_without_trailing_slash(), latency_ms);
+                }
+                for relay in output.failed.keys() {
+                    _relay_list.record_failure(relay.as_str_without_trailing_slash(), now);
+                }
+            }
+
+            // 5 Return the event id once a relay accepted it.
+            if !output.success.is_empty() {
+                return Some(output.val.as_bytes().to_owned());
+            }
+        }
+
+        None
+    }
+
+    /// Returns the local Nostr keys, even if Nostr events are signed by a remote signer.
+    pub fn local_keys(&self) -> nostr_sdk::Keys {
+        self.local_keys.clone()
     }
 
     pub async fn query_address(&self, npub: &str) -> Option<String> {
-        let public_key = PublicKey::from_bech32(npub).ok()?;
+        let account_key = public_key::npub_to_xonly(npub).ok()?;
+        let public_key = PublicKey::from_slice(&account_key).ok()?;
 
         let filter = Filter::new()
             .author(public_key)
@@ -29,7 +227,7 @@ impl NNSClient {
         let events = {
             self.nostr_client
                 .fetch_events_from(
-                    relay::DEFAULT_RELAY_LIST,
+                    self.query_relays().await,
                     vec![filter],
                     Some(Duration::from_millis(5_000)),
                 )
@@ -44,21 +242,197 @@ impl NNSClient {
             Err(_) => return None,
         };
 
-        Some(last_event.content.clone())
+        // Only trust an address signed by the npub itself.
+        let last_event = NostrEvent::from_json_str(&last_event.as_json())?;
+        if last_event.pubkey != account_key || !last_event.verify() {
+            return None;
+        }
+
+        Some(last_event.content)
     }
 
     pub async fn publish_address(&self, ip_address: &str) -> Option<[u8; 32]> {
         let note_publish_event = EventBuilder::text_note(ip_address);
 
-        match self
+        self.publish(note_publish_event).await
+    }
+
+    /// Publishes a signed peer announcement, tagged with each chain the peer serves.
+    pub async fn publish_announcement(&self, announcement: &PeerAnnouncement) -> Option<[u8; 32]> {
+        let content = serde_json::to_string(announcement).ok()?;
+
+        let announcement_publish_event =
+            EventBuilder::new(Kind::Custom(PEER_ANNOUNCEMENT_EVENT_KIND), content)
+                .tag(Tag::identifier(peer_announcement_identifier()))
+                .tags(
+                    announcement
+                        .chains
+                        .iter()
+                        .map(|c| Tag::hashtag(c.to_string())),
+                );
+
+        self.publish(announcement_publish_event).await
+    }
+
+    /// Queries the peer announcements published for a chain.
+    ///
+    /// Only announcements carried by an event signed by the announcing peer itself are returned; they
+    /// are still to be validated against a peer table.
+    pub async fn query_announcements(&self, chain: Chain) -> Vec<PeerAnnouncement> {
+        let filter = Filter::new()
+            .kind(Kind::Custom(PEER_ANNOUNCEMENT_EVENT_KIND))
+            .identifier(peer_announcement_identifier())
+            .hashtag(chain.to_string())
+            .limit(PEER_ANNOUNCEMENT_QUERY_LIMIT);
+
+        let events = match self
             .nostr_client
-            .send_event_builder(note_publish_event)
+            .fetch_events_from(
+                self.query_relays().await,
+                vec![filter],
+                Some(Duration::from_millis(5_000)),
+            )
             .await
         {
-            Ok(ok) => {
-                return Some(ok.as_bytes().to_owned());
+            Ok(events) => events,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut announcements = Vec::<PeerAnnouncement>::new();
+        for event in events.iter() {
+            let event = match NostrEvent::from_json_str(&event.as_json()) {
+                Some(event) => event,
+                None => continue,
+            };
+            if !event.verify() {
+                continue;
             }
-            Err(_) => return None,
+
+            let announcement: PeerAnnouncement = match serde_json::from_str(&event.content) {
+                Ok(announcement) => announcement,
+                Err(_) => continue,
+            };
+            if announcement.peer_key != event.pubkey {
+                continue;
+            }
+
+            announcements.push(announcement);
+        }
+
+        announcements
+    }
+
+    /// Gossips a delta attestation to other nodes of the chain.
+    pub async fn publish_delta_gossip(
+        &self,
+        chain: Chain,
+        attestation: &AggregatedDeltaAttestation,
+    ) -> Option<[u8; 32]> {
+        let content = serde_json::to_string(attestation).ok()?;
+
+        let gossip_publish_event =
+            EventBuilder::new(Kind::Custom(DELTA_GOSSIP_EVENT_KIND), content)
+                .tag(Tag::hashtag(chain.to_string()));
+
+        self.publish(gossip_publish_event).await
+    }
+
+    /// Queries the latest delta attestations gossiped on a chain.
+    ///
+    /// Gossip may be relayed by any node, so the returned attestations are still to be validated
+    /// against the federation quorum.
+    pub async fn query_delta_gossip(&self, chain: Chain) -> Vec<AggregatedDeltaAttestation> {
+        let filter = Filter::new()
+            .kind(Kind::Custom(DELTA_GOSSIP_EVENT_KIND))
+            .hashtag(chain.to_string())
+            .limit(DELTA_GOSSIP_QUERY_LIMIT);
+
+        let events = match self
+            .nostr_client
+            .fetch_events_from(
+                self.query_relays().await,
+                vec![filter],
+                Some(Duration::from_millis(5_000)),
+            )
+            .await
+        {
+            Ok(events) => events,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut attestations = Vec::<AggregatedDeltaAttestation>::new();
+        for event in events.iter() {
+            let event = match NostrEvent::from_json_str(&event.as_json()) {
+                Some(event) => event,
+                None => continue,
+            };
+            if !event.verify() {
+                continue;
+            }
+
+            match serde_json::from_str(&event.content) {
+                Ok(attestation) => attestations.push(attestation),
+                Err(_) => continue,
+            }
+        }
+
+        attestations
+    }
+
+    /// Broadcasts a signed contract registration notice to the nodes of the chain.
+    pub async fn publish_contract_notice(
+        &self,
+        chain: Chain,
+        notice: &ContractNotice,
+    ) -> Option<[u8; 32]> {
+        let content = serde_json::to_string(notice).ok()?;
+
+        let notice_publish_event =
+            EventBuilder::new(Kind::Custom(CONTRACT_NOTICE_EVENT_KIND), content)
+                .tag(Tag::hashtag(chain.to_string()));
+
+        self.publish(notice_publish_event).await
+    }
+
+    /// Queries the latest contract registration notices broadcast on a chain.
+    ///
+    /// Notices may be published by anyone, so the returned notices are still to be validated against
+    /// the federation coordinators.
+    pub async fn query_contract_notices(&self, chain: Chain) -> Vec<ContractNotice> {
+        let filter = Filter::new()
+            .kind(Kind::Custom(CONTRACT_NOTICE_EVENT_KIND))
+            .hashtag(chain.to_string())
+            .limit(CONTRACT_NOTICE_QUERY_LIMIT);
+
+        let events = match self
+            .nostr_client
+            .fetch_events_from(
+                self.query_relays().await,
+                vec![filter],
+                Some(Duration::from_millis(5_000)),
+            )
+            .await
+        {
+            Ok(events) => events,
+            Err(_) => return Vec::new(),
         };
+
+        let mut notices = Vec::<ContractNotice>::new();
+        for event in events.iter() {
+            let event = match NostrEvent::from_json_str(&event.as_json()) {
+                Some(event) => event,
+                None => continue,
+            };
+            if !event.verify() {
+                continue;
+            }
+
+            match serde_json::from_str(&event.content) {
+                Ok(notice) => notices.push(notice),
+                Err(_) => continue,
+            }
+        }
+
+        notices
     }
 }
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Errors associated with the relay list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelayListError {
+    // The relay URL is not a valid websocket URL.
+    InvalidRelayUrl(String),
+    // The last relay cannot be removed.
+    LastRelay,
+    // Reading or writing the relay list db failed.
+    DbErr(String),
+}
+
+impl fmt::Display for RelayListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelayListError::InvalidRelayUrl(url) => write!(f, "Invalid relay URL: {}", url),
+            RelayListError::LastRelay => write!(f, "The last relay cannot be removed."),
+            RelayListError::DbErr(err) => write!(f, "Relay list db error: {}", err),
+        }
+    }
+}
@@ -1,3 +1,7 @@
+pub mod bunker;
 pub mod client;
+pub mod errors;
 pub mod relay;
+pub mod relay_health;
+pub mod relay_list;
 pub mod server;
@@ -0,0 +1,107 @@
+use serde_json::{Map, Value};
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Latency assumed for a relay that has not been measured yet, in milliseconds.
+pub const DEFAULT_RELAY_LATENCY_MS: u64 = 500;
+
+/// Weight of the latest measurement in the latency moving average, out of 8.
+const LATENCY_EWMA_WEIGHT: u64 = 2;
+
+/// Health of a relay, measured from the outcome of the events published to it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelayHealth {
+    // Moving average of the publishing latency in milliseconds, if measured.
+    latency_ms: Option<u64>,
+
+    // Number of events the relay accepted.
+    successes: u64,
+
+    // Number of events the relay failed to accept.
+    failures: u64,
+
+    // Timestamp of the latest failure.
+    last_failure_at: Option<Timestamp>,
+}
+
+impl RelayHealth {
+    /// Constructs the health of a relay that has not been used yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an event the relay accepted after the given latency.
+    pub fn record_success(&mut self, latency_ms: u64) {
+        self.successes = self.successes.saturating_add(1);
+        self.latency_ms = Some(match self.latency_ms {
+            Some(average) => {
+                (average * (8 - LATENCY_EWMA_WEIGHT) + latency_ms * LATENCY_EWMA_WEIGHT) / 8
+            }
+            None => latency_ms,
+        });
+    }
+
+    /// Records an event the relay failed to accept.
+    pub fn record_failure(&mut self, now: Timestamp) {
+        self.failures = self.failures.saturating_add(1);
+        self.last_failure_at = Some(now);
+    }
+
+    /// Returns the moving average of the publishing latency, if measured.
+    pub fn latency_ms(&self) -> Option<u64> {
+        self.latency_ms
+    }
+
+    /// Returns the share of the events the relay failed to accept, `0` if none was published.
+    pub fn failure_rate(&self) -> f64 {
+        let attempts = self.successes + self.failures;
+        match attempts {
+            0 => 0.0,
+            _ => self.failures as f64 / attempts as f64,
+        }
+    }
+
+    /// Returns the expected cost of publishing to the relay, lower being healthier.
+    ///
+    /// The cost is the latency divided by a smoothed success rate, so that a relay that is fast but
+    /// often fails ranks behind a slower but reliable one, and a single failure does not rule a relay
+    /// out. Unmeasured relays are assumed to have the default latency.
+    pub fn score(&self) -> f64 {
+        let latency_ms = self.latency_ms.unwrap_or(DEFAULT_RELAY_LATENCY_MS).max(1) as f64;
+        let success_rate =
+            (self.successes as f64 + 1.0) / ((self.successes + self.failures) as f64 + 2.0);
+        latency_ms / success_rate
+    }
+
+    /// Returns the relay health as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "latency_ms".to_string(),
+            match self.latency_ms {
+                Some(latency_ms) => Value::Number(latency_ms.into()),
+                None => Value::Null,
+            },
+        );
+        obj.insert(
+            "successes".to_string(),
+            Value::Number(self.successes.into()),
+        );
+        obj.insert("failures".to_string(), Value::Number(self.failures.into()));
+        obj.insert(
+            "failure_rate".to_string(),
+            serde_json::Number::from_f64(self.failure_rate())
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+        );
+        obj.insert(
+            "last_failure_at".to_string(),
+            match self.last_failure_at {
+                Some(last_failure_at) => Value::Number(last_failure_at.into()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,256 @@
+use super::errors::relay_list_error::RelayListError;
+use super::relay::DEFAULT_RELAY_LIST;
+use super::relay_health::RelayHealth;
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::key::ToNostrKeyStr;
+use crate::transmutative::secp::public_key;
+use nostr_sdk::RelayUrl;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Number of the healthiest relays events are published to.
+pub const PUBLISHING_RELAY_COUNT: usize = 3;
+
+/// Key marking the relay list db as seeded with the default relays.
+const SEEDED_KEY: &[u8] = b"seeded";
+
+/// Persistent list of the Nostr relays and the direct peers in use, manageable at runtime.
+///
+/// The list is seeded with the default relays on first use. Every relay is queried, while events are
+/// only published to the [`PUBLISHING_RELAY_COUNT`] healthiest ones. Relay health is not persisted.
+///
+/// Direct peers are dialed on their configured address before any announced endpoint or NNS address.
+pub struct RelayList {
+    // In-memory relays and their health.
+    relays: HashMap<String, RelayHealth>,
+
+    // In-memory direct peer addresses.
+    direct_peers: HashMap<PeerKey, SocketAddr>,
+
+    // On-disk relays and direct peers.
+    on_disk_relays: sled::Tree,
+    on_disk_direct_peers: sled::Tree,
+}
+
+/// Guarded 'RelayList'.
+#[allow(non_camel_case_types)]
+pub type RELAY_LIST = Arc<Mutex<RelayList>>;
+
+impl RelayList {
+    pub fn new(chain: Chain) -> Result<RELAY_LIST, RelayListError> {
+        // 1 Open the relay list db.
+        let db_path = format!("storage/{}/relay_list", chain.to_string());
+        let db = sled::open(db_path).map_err(|err| RelayListError::DbErr(err.to_string()))?;
+
+        // 2 Open the relay & direct peer trees.
+        let on_disk_relays = db
+            .open_tree("relays")
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        let on_disk_direct_peers = db
+            .open_tree("direct_peers")
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+
+        // 3 Seed the default relays on first use.
+        let seeded = db
+            .contains_key(SEEDED_KEY)
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        if !seeded {
+            for relay in DEFAULT_RELAY_LIST {
+                on_disk_relays
+                    .insert(relay.as_bytes(), vec![])
+                    .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+            }
+            db.insert(SEEDED_KEY, vec![])
+                .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        }
+
+        // 4 Load the relays & direct peers into memory.
+        let relays = on_disk_relays
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter_map(|(key, _)| String::from_utf8(key.to_vec()).ok())
+            .map(|relay| (relay, RelayHealth::new()))
+            .collect();
+        let direct_peers = on_disk_direct_peers
+            .iter()
+            .filter_map(|item| item.ok())
+            .filter_map(|(key, value)| {
+                let peer_key = public_key::account_key_from_slice(&key).ok()?;
+                let addr = String::from_utf8(value.to_vec()).ok()?.parse().ok()?;
+                Some((peer_key, addr))
+            })
+            .collect();
+
+        // 5 Construct the relay list.
+        let relay_list = RelayList {
+            relays,
+            direct_peers,
+            on_disk_relays,
+            on_disk_direct_peers,
+        };
+
+        // 6 Guard and return the relay list.
+        Ok(Arc::new(Mutex::new(relay_list)))
+    }
+
+    /// Normalizes a relay URL, refusing anything but websocket URLs.
+    pub fn normalize_url(url: &str) -> Result<String, RelayListError> {
+        RelayUrl::parse(url)
+            .map(|relay_url| relay_url.as_str_without_trailing_slash().to_string())
+            .map_err(|_| RelayListError::InvalidRelayUrl(url.to_string()))
+    }
+
+    /// Adds a relay. Returns false if it was already in the list.
+    pub fn add_relay(&mut self, url: &str) -> Result<bool, RelayListError> {
+        let url = Self::normalize_url(url)?;
+        if self.relays.contains_key(&url) {
+            return Ok(false);
+        }
+
+        self.on_disk_relays
+            .insert(url.as_bytes(), vec![])
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        self.relays.insert(url, RelayHealth::new());
+        Ok(true)
+    }
+
+    /// Removes a relay, refusing to remove the last one. Returns false if it was not in the list.
+    pub fn remove_relay(&mut self, url: &str) -> Result<bool, RelayListError> {
+        let url = Self::normalize_url(url)?;
+        if !self.relays.contains_key(&url) {
+            return Ok(false);
+        }
+        if self.relays.len() == 1 {
+            return Err(RelayListError::LastRelay);
+        }
+
+        self.on_disk_relays
+            .remove(url.as_bytes())
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        self.relays.remove(&url);
+        Ok(true)
+    }
+
+    /// Returns the relays, the healthiest first.
+    pub fn relays(&self) -> Vec<String> {
+        let mut relays: Vec<(&String, &RelayHealth)> = self.relays.iter().collect();
+        relays.sort_by(|a, b| a.1.score().total_cmp(&b.1.score()).then(a.0.cmp(b.0)));
+        relays.into_iter().map(|(url, _)| url.clone()).collect()
+    }
+
+    /// Returns the healthiest relays, which events are published to.
+    pub fn publishing_relays(&self) -> Vec<String> {
+        self.relays()
+            .into_iter()
+            .take(PUBLISHING_RELAY_COUNT)
+            .collect()
+    }
+
+    /// Returns the health of a relay.
+    pub fn health(&self, url: &str) -> Option<&RelayHealth> {
+        self.relays.get(url)
+    }
+
+    /// Records an event a relay accepted after the given latency.
+    pub fn record_success(&mut self, url: &str, latency_ms: u64) {
+        if let Some(health) = self.relays.get_mut(url) {
+            health.record_success(latency_ms);
+        }
+    }
+
+    /// Records an event a relay failed to accept.
+    pub fn record_failure(&mut self, url: &str, now: Timestamp) {
+        if let Some(health) = self.relays.get_mut(url) {
+            health.record_failure(now);
+        }
+    }
+
+    /// Adds or updates a direct peer. Returns false if it was already set to the same address.
+    pub fn set_direct_peer(
+        &mut self,
+        peer_key: PeerKey,
+        addr: SocketAddr,
+    ) -> Result<bool, RelayListError> {
+        if self.direct_peers.get(&peer_key) == Some(&addr) {
+            return Ok(false);
+        }
+
+        self.on_disk_direct_peers
+            .insert(peer_key, addr.to_string().as_bytes())
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        self.direct_peers.insert(peer_key, addr);
+        Ok(true)
+    }
+
+    /// Removes a direct peer. Returns false if it was not a direct peer.
+    pub fn remove_direct_peer(&mut self, peer_key: PeerKey) -> Result<bool, RelayListError> {
+        self.on_disk_direct_peers
+            .remove(peer_key)
+            .map_err(|err| RelayListError::DbErr(err.to_string()))?;
+        Ok(self.direct_peers.remove(&peer_key).is_some())
+    }
+
+    /// Returns the address of a direct peer.
+    pub fn direct_peer(&self, peer_key: PeerKey) -> Option<SocketAddr> {
+        self.direct_peers.get(&peer_key).copied()
+    }
+
+    /// Returns the relays as a JSON array, the healthiest first.
+    pub fn relays_json(&self) -> Value {
+        let publishing_relays = self.publishing_relays();
+        Value::Array(
+            self.relays()
+                .iter()
+                .map(|url| {
+                    let mut relay = Map::new();
+                    relay.insert("url".to_string(), Value::String(url.clone()));
+                    relay.insert(
+                        "publishing".to_string(),
+                        Value::Bool(publishing_relays.contains(url)),
+                    );
+                    relay.insert("health".to_string(), self.relays[url].json());
+                    Value::Object(relay)
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the direct peers as a JSON object, with peer keys encoded as npubs.
+    pub fn direct_peers_json(&self) -> Value {
+        Value::Object(
+            self.direct_peers
+                .iter()
+                .map(|(key, addr)| {
+                    let npub = key.to_npub().unwrap_or_else(|| hex::encode(key));
+                    (npub, Value::String(addr.to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the relay list as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("relays".to_string(), self.relays_json());
+        obj.insert("direct_peers".to_string(), self.direct_peers_json());
+        Value::Object(obj)
+    }
+}
+
+/// Erases the relay list by db path.
+pub fn erase_relay_list(chain: Chain) {
+    // Relay list db path.
+    let db_path = format!("storage/{}/relay_list", chain.to_string());
+
+    // Erase the relay list db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
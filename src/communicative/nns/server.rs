@@ -115,7 +115,7 @@ async fn check_ip() -> Result<Option<String>, reqwest::Error> {
 
 /// Queries the dynamic IP address of the running machine.
 ///
-async fn query_ip_address() -> Result<String, reqwest::Error> {
+pub async fn query_ip_address() -> Result<String, reqwest::Error> {
     let url = "https://api.ipify.org";
     let ip = reqwest::get(url).await?.text().await?;
 
@@ -0,0 +1,2 @@
+pub mod outbox_construction_error;
+pub mod outbox_enqueue_error;
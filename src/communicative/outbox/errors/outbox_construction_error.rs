@@ -0,0 +1,6 @@
+/// Errors associated with constructing the outbox.
+#[derive(Debug, Clone)]
+pub enum OutboxConstructionError {
+    DBOpenError(sled::Error),
+    TreeOpenError(sled::Error),
+}
@@ -0,0 +1,6 @@
+/// Errors associated with enqueuing an outbound message.
+#[derive(Debug, Clone)]
+pub enum OutboxEnqueueError {
+    PeerQueueFull([u8; 32]),
+    DBInsertError(sled::Error),
+}
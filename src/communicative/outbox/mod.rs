@@ -0,0 +1,3 @@
+pub mod errors;
+pub mod outbound_message;
+pub mod outbox;
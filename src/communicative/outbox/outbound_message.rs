@@ -0,0 +1,114 @@
+use crate::communicative::tcp::package::PackageKind;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A protocol message queued for delivery to a peer, retried until the peer acknowledges it or it
+/// expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboundMessage {
+    // Id of the message, increasing in order of enqueuing.
+    pub id: u64,
+
+    // Key of the recipient peer.
+    pub peer_key: [u8; 32],
+
+    // Bytecode of the kind of the request package.
+    pub package_kind: u8,
+
+    // Payload of the request package.
+    pub payload: Vec<u8>,
+
+    // Unix timestamp (seconds) the message was enqueued at.
+    pub enqueued_at: u64,
+
+    // Unix timestamp (seconds) of the next delivery attempt.
+    pub next_attempt_at: u64,
+
+    // Number of failed delivery attempts so far.
+    pub attempts: u32,
+
+    // The error of the last failed delivery attempt.
+    pub last_error: Option<String>,
+}
+
+impl OutboundMessage {
+    /// Constructs a message due for delivery right away.
+    pub fn new(
+        id: u64,
+        peer_key: [u8; 32],
+        package_kind: PackageKind,
+        payload: Vec<u8>,
+        now: u64,
+    ) -> Self {
+        Self {
+            id,
+            peer_key,
+            package_kind: package_kind.bytecode(),
+            payload,
+            enqueued_at: now,
+            next_attempt_at: now,
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    /// Returns the kind of the request package.
+    pub fn package_kind(&self) -> Option<PackageKind> {
+        PackageKind::from_bytecode(self.package_kind)
+    }
+
+    /// Returns the on-disk key of the message, ordering the messages of a peer by id.
+    pub fn db_key(&self) -> [u8; 40] {
+        let mut db_key = [0u8; 40];
+        db_key[..32].copy_from_slice(&self.peer_key);
+        db_key[32..].copy_from_slice(&self.id.to_be_bytes());
+        db_key
+    }
+
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes an outbound message from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(outbound_message, _)| outbound_message)
+    }
+
+    /// Returns the outbound message as a JSON object, without the payload.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::Number(self.id.into()));
+        obj.insert(
+            "peer_key".to_string(),
+            Value::String(hex::encode(self.peer_key)),
+        );
+        obj.insert(
+            "package_kind".to_string(),
+            Value::Number(self.package_kind.into()),
+        );
+        obj.insert(
+            "payload_len".to_string(),
+            Value::Number(self.payload.len().into()),
+        );
+        obj.insert(
+            "enqueued_at".to_string(),
+            Value::Number(self.enqueued_at.into()),
+        );
+        obj.insert(
+            "next_attempt_at".to_string(),
+            Value::Number(self.next_attempt_at.into()),
+        );
+        obj.insert("attempts".to_string(), Value::Number(self.attempts.into()));
+        obj.insert(
+            "last_error".to_string(),
+            match &self.last_error {
+                Some(error) => Value::String(error.clone()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
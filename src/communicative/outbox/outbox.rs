@@ -0,0 +1,229 @@
+use crate::communicative::outbox::errors::outbox_construction_error::OutboxConstructionError;
+use crate::communicative::outbox::errors::outbox_enqueue_error::OutboxEnqueueError;
+use crate::communicative::outbox::outbound_message::OutboundMessage;
+use crate::communicative::tcp::package::PackageKind;
+use crate::operative::run_args::chain::Chain;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Default time after which an unacknowledged message is given up on in seconds.
+const DEFAULT_EXPIRY_SECS: u64 = 3_600;
+
+/// Delay before retrying a message after its first failed delivery attempt in seconds.
+const BASE_RETRY_BACKOFF_SECS: u64 = 2;
+
+/// Maximum delay between two delivery attempts of a message in seconds.
+const MAX_RETRY_BACKOFF_SECS: u64 = 300;
+
+/// Maximum number of messages queued for a single peer.
+const MAX_MESSAGES_PER_PEER: usize = 4_096;
+
+/// Returns the delay before the next delivery attempt of a message that failed the given number of
+/// times, doubling with each failure up to the maximum backoff.
+pub fn retry_backoff_secs(attempts: u32) -> u64 {
+    let exponent = attempts.saturating_sub(1).min(63);
+    BASE_RETRY_BACKOFF_SECS
+        .saturating_mul(1u64 << exponent)
+        .min(MAX_RETRY_BACKOFF_SECS)
+}
+
+/// Persistent per-peer queue of outbound protocol messages.
+///
+/// A message stays queued until the peer acknowledges it with a response, so that a message sent
+/// while the peer or the network is briefly unreachable is delivered once it is back rather than
+/// dropped. Failed deliveries are retried with exponential backoff, and messages unacknowledged past
+/// the expiry are given up on.
+pub struct Outbox {
+    // Time after which an unacknowledged message is given up on.
+    expiry: u64,
+
+    // Id of the next enqueued message.
+    next_id: u64,
+
+    // In-memory queued messages by peer key and id.
+    messages: BTreeMap<(PeerKey, u64), OutboundMessage>,
+
+    // On-disk queued messages.
+    on_disk_messages: sled::Tree,
+}
+
+/// Guarded 'Outbox'.
+#[allow(non_camel_case_types)]
+pub type OUTBOX = Arc<Mutex<Outbox>>;
+
+impl Outbox {
+    /// Constructs the outbox, loading the queued messages from disk.
+    ///
+    /// `CUBE_OUTBOX_EXPIRY_SECS` optionally overrides the time after which an unacknowledged message
+    /// is given up on.
+    pub fn new(chain: Chain) -> Result<OUTBOX, OutboxConstructionError> {
+        // 1 Open the outbox db.
+        let db_path = format!("storage/{}/outbox", chain.to_string());
+        let db = sled::open(db_path).map_err(OutboxConstructionError::DBOpenError)?;
+
+        // 2 Open the messages tree.
+        let on_disk_messages = db
+            .open_tree("messages")
+            .map_err(OutboxConstructionError::TreeOpenError)?;
+
+        // 3 Load the queued messages, skipping corrupt ones.
+        let mut messages = BTreeMap::new();
+        for (_, value) in on_disk_messages.iter().filter_map(|item| item.ok()) {
+            if let Some(message) = OutboundMessage::deserialize(value.as_ref()) {
+                messages.insert((message.peer_key, message.id), message);
+            }
+        }
+
+        // 4 Continue the ids after the highest queued one.
+        let next_id = messages
+            .values()
+            .map(|message| message.id + 1)
+            .max()
+            .unwrap_or(0);
+
+        // 5 Read the expiry.
+        let expiry = std::env::var("CUBE_OUTBOX_EXPIRY_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_EXPIRY_SECS);
+
+        // 6 Construct the outbox.
+        let outbox = Outbox {
+            expiry,
+            next_id,
+            messages,
+            on_disk_messages,
+        };
+
+        // 7 Guard and return the outbox.
+        Ok(Arc::new(Mutex::new(outbox)))
+    }
+
+    /// Queues a request package for delivery to a peer and returns the id of the message.
+    pub fn enqueue(
+        &mut self,
+        peer_key: PeerKey,
+        package_kind: PackageKind,
+        payload: Vec<u8>,
+        now: u64,
+    ) -> Result<u64, OutboxEnqueueError> {
+        // 1 Check the queue of the peer has room left.
+        if self.len(peer_key) >= MAX_MESSAGES_PER_PEER {
+            return Err(OutboxEnqueueError::PeerQueueFull(peer_key));
+        }
+
+        // 2 Queue the message.
+        let id = self.next_id;
+        self.next_id += 1;
+        self.persist(OutboundMessage::new(
+            id,
+            peer_key,
+            package_kind,
+            payload,
+            now,
+        ))
+        .map_err(OutboxEnqueueError::DBInsertError)?;
+
+        Ok(id)
+    }
+
+    /// Returns the messages to a peer due for a delivery attempt at the given time, in order of
+    /// enqueuing.
+    pub fn due(&self, peer_key: PeerKey, now: u64) -> Vec<OutboundMessage> {
+        self.messages
+            .range((peer_key, 0)..=(peer_key, u64::MAX))
+            .map(|(_, message)| message)
+            .filter(|message| message.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes a message the peer acknowledged.
+    pub fn acknowledge(&mut self, peer_key: PeerKey, id: u64) -> Result<(), sled::Error> {
+        if let Some(message) = self.messages.remove(&(peer_key, id)) {
+            self.on_disk_messages.remove(message.db_key())?;
+        }
+        Ok(())
+    }
+
+    /// Records a failed delivery attempt of a message, backing off its next attempt.
+    pub fn record_failure(
+        &mut self,
+        peer_key: PeerKey,
+        id: u64,
+        error: String,
+        now: u64,
+    ) -> Result<(), sled::Error> {
+        let mut message = match self.messages.remove(&(peer_key, id)) {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+        message.attempts += 1;
+        message.next_attempt_at = now + retry_backoff_secs(message.attempts);
+        message.last_error = Some(error);
+        self.persist(message)
+    }
+
+    /// Gives up on the messages enqueued before the expiry and returns them.
+    pub fn expire(&mut self, now: u64) -> Result<Vec<OutboundMessage>, sled::Error> {
+        let expired: Vec<OutboundMessage> = self
+            .messages
+            .values()
+            .filter(|message| message.enqueued_at + self.expiry <= now)
+            .cloned()
+            .collect();
+
+        for message in expired.iter() {
+            self.messages.remove(&(message.peer_key, message.id));
+            self.on_disk_messages.remove(message.db_key())?;
+        }
+        Ok(expired)
+    }
+
+    /// Returns the number of messages queued for a peer.
+    pub fn len(&self, peer_key: PeerKey) -> usize {
+        self.messages
+            .range((peer_key, 0)..=(peer_key, u64::MAX))
+            .count()
+    }
+
+    /// Whether no message is queued.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Returns the outbox as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "messages".to_string(),
+            Value::Array(self.messages.values().map(OutboundMessage::json).collect()),
+        );
+        Value::Object(obj)
+    }
+
+    /// Keeps a queued message in memory and on disk.
+    fn persist(&mut self, message: OutboundMessage) -> Result<(), sled::Error> {
+        let db_key = message.db_key();
+        let bytes = message.serialize().unwrap_or_default();
+        self.messages
+            .insert((message.peer_key, message.id), message);
+        self.on_disk_messages.insert(db_key, bytes)?;
+        Ok(())
+    }
+}
+
+/// Erases the outbox.
+pub fn erase_outbox(chain: Chain) {
+    // Outbox db path.
+    let db_path = format!("storage/{}/outbox", chain.to_string());
+
+    // Erase the outbox db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
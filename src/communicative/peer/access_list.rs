@@ -0,0 +1,233 @@
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::key::ToNostrKeyStr;
+use crate::transmutative::secp::public_key;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Unix timestamp in seconds.
+type Timestamp = u64;
+
+/// Number of protocol violations after which a peer is temporarily banned.
+const PROTOCOL_VIOLATION_BAN_THRESHOLD: u32 = 3;
+
+/// Duration of an automatic temporary ban in seconds.
+const TEMPORARY_BAN_DURATION_SECONDS: u64 = 3_600;
+
+/// Reasons a peer is denied access.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerAccessDenial {
+    NotAllowlisted,
+    Banned,
+    TemporarilyBanned(Timestamp),
+}
+
+impl PeerAccessDenial {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            PeerAccessDenial::NotAllowlisted => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("not_allowlisted".to_string()),
+                );
+            }
+            PeerAccessDenial::Banned => {
+                obj.insert("kind".to_string(), Value::String("banned".to_string()));
+            }
+            PeerAccessDenial::TemporarilyBanned(until) => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("temporarily_banned".to_string()),
+                );
+                obj.insert("until".to_string(), Value::Number((*until).into()));
+            }
+        }
+        Value::Object(obj)
+    }
+}
+
+/// Persistent allow and ban lists keyed by peer key, plus automatic temporary bans for protocol violations.
+///
+/// An empty allowlist admits every peer that is not banned.
+pub struct PeerAccessList {
+    // In-memory allow & ban lists.
+    in_memory_allowlist: HashSet<PeerKey>,
+    in_memory_banlist: HashSet<PeerKey>,
+
+    // On-disk allow & ban lists.
+    on_disk_allowlist: sled::Tree,
+    on_disk_banlist: sled::Tree,
+
+    // Temporary bans by their expiry timestamp (not persisted).
+    temporary_bans: HashMap<PeerKey, Timestamp>,
+
+    // Protocol violation counts since the last temporary ban (not persisted).
+    protocol_violations: HashMap<PeerKey, u32>,
+}
+
+/// Guarded 'PeerAccessList'.
+#[allow(non_camel_case_types)]
+pub type PEER_ACCESS_LIST = Arc<Mutex<PeerAccessList>>;
+
+impl PeerAccessList {
+    pub fn new(chain: Chain) -> Result<PEER_ACCESS_LIST, sled::Error> {
+        // 1 Open the peer access db.
+        let db_path = format!("storage/{}/peer_access", chain.to_string());
+        let db = sled::open(db_path)?;
+
+        // 2 Open the allow & ban list trees.
+        let on_disk_allowlist = db.open_tree("allowlist")?;
+        let on_disk_banlist = db.open_tree("banlist")?;
+
+        // 3 Load the allow & ban lists into memory.
+        let in_memory_allowlist = Self::load_keys(&on_disk_allowlist);
+        let in_memory_banlist = Self::load_keys(&on_disk_banlist);
+
+        // 4 Construct the peer access list.
+        let peer_access_list = PeerAccessList {
+            in_memory_allowlist,
+            in_memory_banlist,
+            on_disk_allowlist,
+            on_disk_banlist,
+            temporary_bans: HashMap::new(),
+            protocol_violations: HashMap::new(),
+        };
+
+        // 5 Guard and return the peer access list.
+        Ok(Arc::new(Mutex::new(peer_access_list)))
+    }
+
+    /// Loads the 32-byte keys of a tree.
+    fn load_keys(tree: &sled::Tree) -> HashSet<PeerKey> {
+        tree.iter()
+            .filter_map(|item| item.ok())
+            .filter_map(|(key, _)| public_key::account_key_from_slice(&key).ok())
+            .collect()
+    }
+
+    /// Checks if a peer is granted access at the given timestamp.
+    pub fn check_access(&mut self, key: PeerKey, now: Timestamp) -> Result<(), PeerAccessDenial> {
+        // 1 Check the permanent ban list.
+        if self.in_memory_banlist.contains(&key) {
+            return Err(PeerAccessDenial::Banned);
+        }
+
+        // 2 Check the temporary bans, lifting the expired one if any.
+        if let Some(until) = self.temporary_bans.get(&key).copied() {
+            if now < until {
+                return Err(PeerAccessDenial::TemporarilyBanned(until));
+            }
+            self.temporary_bans.remove(&key);
+        }
+
+        // 3 Check the allowlist if it is in use.
+        if !self.in_memory_allowlist.is_empty() && !self.in_memory_allowlist.contains(&key) {
+            return Err(PeerAccessDenial::NotAllowlisted);
+        }
+
+        // 4 Access is granted.
+        Ok(())
+    }
+
+    /// Records a protocol violation by a peer.
+    ///
+    /// Returns the expiry timestamp if this violation triggered a temporary ban.
+    pub fn record_protocol_violation(&mut self, key: PeerKey, now: Timestamp) -> Option<Timestamp> {
+        // 1 Increment the violation count.
+        let violations = self.protocol_violations.entry(key).or_insert(0);
+        *violations += 1;
+
+        // 2 Return early if the threshold is not reached yet.
+        if *violations < PROTOCOL_VIOLATION_BAN_THRESHOLD {
+            return None;
+        }
+
+        // 3 Temporarily ban the peer and reset its violation count.
+        let until = now + TEMPORARY_BAN_DURATION_SECONDS;
+        self.temporary_bans.insert(key, until);
+        self.protocol_violations.remove(&key);
+
+        // 4 Return the expiry timestamp.
+        Some(until)
+    }
+
+    /// Adds a peer to the allowlist. Returns false if it was already allowlisted.
+    pub fn allow(&mut self, key: PeerKey) -> Result<bool, sled::Error> {
+        self.on_disk_allowlist.insert(key, vec![])?;
+        Ok(self.in_memory_allowlist.insert(key))
+    }
+
+    /// Removes a peer from the allowlist. Returns false if it was not allowlisted.
+    pub fn disallow(&mut self, key: PeerKey) -> Result<bool, sled::Error> {
+        self.on_disk_allowlist.remove(key)?;
+        Ok(self.in_memory_allowlist.remove(&key))
+    }
+
+    /// Permanently bans a peer. Returns false if it was already banned.
+    pub fn ban(&mut self, key: PeerKey) -> Result<bool, sled::Error> {
+        self.on_disk_banlist.insert(key, vec![])?;
+        Ok(self.in_memory_banlist.insert(key))
+    }
+
+    /// Lifts both the permanent and the temporary ban of a peer. Returns false if it was not banned.
+    pub fn unban(&mut self, key: PeerKey) -> Result<bool, sled::Error> {
+        self.on_disk_banlist.remove(key)?;
+        let was_temporarily_banned = self.temporary_bans.remove(&key).is_some();
+        self.protocol_violations.remove(&key);
+        Ok(self.in_memory_banlist.remove(&key) || was_temporarily_banned)
+    }
+
+    /// Returns the peer access list as a JSON object, with peer keys encoded as npubs.
+    pub fn json(&self) -> Value {
+        // 1 Encode a peer key as npub, falling back to hex.
+        let encode = |key: &PeerKey| key.to_npub().unwrap_or_else(|| hex::encode(key));
+
+        // 2 Construct the peer access list JSON object.
+        let mut obj = Map::new();
+        obj.insert(
+            "allowlist".to_string(),
+            Value::Array(
+                self.in_memory_allowlist
+                    .iter()
+                    .map(|key| Value::String(encode(key)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "banlist".to_string(),
+            Value::Array(
+                self.in_memory_banlist
+                    .iter()
+                    .map(|key| Value::String(encode(key)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "temporary_bans".to_string(),
+            Value::Object(
+                self.temporary_bans
+                    .iter()
+                    .map(|(key, until)| (encode(key), Value::Number((*until).into())))
+                    .collect(),
+            ),
+        );
+
+        // 3 Return the peer access list JSON object.
+        Value::Object(obj)
+    }
+}
+
+/// Erases the peer access list by db path.
+pub fn erase_peer_access_list(chain: Chain) {
+    // Peer access db path.
+    let db_path = format!("storage/{}/peer_access", chain.to_string());
+
+    // Erase the peer access db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
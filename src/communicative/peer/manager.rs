@@ -1,8 +1,14 @@
 use super::peer::{Peer, PeerConnection, PeerKind, PEER, SOCKET};
 use crate::{
-    communicative::nns::client::NNSClient, inscriptive::baked, operative::run_args::chain::Chain,
+    communicative::{
+        discovery::{announcement::PeerAnnouncementKind, peer_table::PeerTable},
+        nns::client::NNSClient,
+    },
+    inscriptive::baked,
+    operative::run_args::chain::Chain,
 };
 use async_trait::async_trait;
+use chrono::Utc;
 use futures::future::join_all;
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
@@ -22,25 +28,28 @@ pub fn engine_key(chain: Chain) -> [u8; 32] {
 pub struct PeerManager {
     chain: Chain,
     peers: HashMap<[u8; 32], PEER>,
+    peer_table: PeerTable,
     nns_client: NNSClient,
 }
 
 impl PeerManager {
+    /// Constructs a peer manager and connects to the peers of the given kind discovered through
+    /// their signed announcements.
     pub async fn new(
         chain: Chain,
         nns_client: &NNSClient,
-        kind: PeerKind,
-        keys: &Vec<[u8; 32]>,
+        kind: PeerAnnouncementKind,
     ) -> Option<PEER_MANAGER> {
         let manager_ = PeerManager {
             chain,
             peers: HashMap::<[u8; 32], PEER>::new(),
+            peer_table: PeerTable::new(chain),
             nns_client: nns_client.to_owned(),
         };
 
         let mut manager = Arc::new(Mutex::new(manager_));
 
-        manager.add_peers(kind, keys).await;
+        manager.discover_peers(kind).await;
 
         Some(manager)
     }
@@ -61,6 +70,10 @@ impl PeerManager {
         self.chain
     }
 
+    pub fn peer_table(&self) -> &PeerTable {
+        &self.peer_table
+    }
+
     pub fn peers(&self) -> HashMap<[u8; 32], PEER> {
         self.peers.clone()
     }
@@ -113,11 +126,41 @@ impl PeerManager {
 
 #[async_trait]
 pub trait PeerManagerExt {
+    async fn discover_peers(&mut self, kind: PeerAnnouncementKind) -> u64;
     async fn add_peers(&mut self, kind: PeerKind, keys: &Vec<[u8; 32]>) -> u64;
 }
 
 #[async_trait]
 impl PeerManagerExt for PEER_MANAGER {
+    /// Refreshes the peer table from the announcements published for the chain, and tries to connect
+    /// to the announced peers of the given kind. Returns the number of peers connected.
+    async fn discover_peers(&mut self, kind: PeerAnnouncementKind) -> u64 {
+        // 1 Query the announcements published for the chain.
+        let (chain, nns_client) = {
+            let _self = self.lock().await;
+            (_self.chain(), _self.nns_client.clone())
+        };
+        let announcements = nns_client.query_announcements(chain).await;
+
+        // 2 Record the valid announcements and drop the expired ones.
+        let keys = {
+            let mut _self = self.lock().await;
+            let now = Utc::now().timestamp() as u64;
+            for announcement in announcements {
+                let _ = _self.peer_table.insert(announcement, now);
+            }
+            _self.peer_table.prune(now);
+            _self.peer_table.peer_keys(kind)
+        };
+
+        // 3 Connect to the announced peers of the kind.
+        let peer_kind = match kind {
+            PeerAnnouncementKind::Engine => PeerKind::Engine,
+            PeerAnnouncementKind::Coordinator | PeerAnnouncementKind::Operator => PeerKind::Node,
+        };
+        self.add_peers(peer_kind, &keys).await
+    }
+
     /// Tries to connect to a list of peers and returns the number of peers connected.
     async fn add_peers(&mut self, kind: PeerKind, keys: &Vec<[u8; 32]>) -> u64 {
         let chain = {
@@ -140,16 +183,17 @@ impl PeerManagerExt for PEER_MANAGER {
             let peer_list_ = Arc::clone(&peer_list_);
             let kind = kind.clone();
             let key = key.clone();
-            let nns_client = {
+            let (endpoints, nns_client) = {
                 let _self = self.lock().await;
-                _self.nns_client.clone()
+                (_self.peer_table.endpoints(key), _self.nns_client.clone())
             };
 
             tasks.push(tokio::spawn(async move {
-                let peer: PEER = match Peer::connect(chain, kind, key, &nns_client).await {
-                    Ok(peer) => peer,
-                    Err(_) => return,
-                };
+                let peer: PEER =
+                    match Peer::connect_announced(chain, kind, key, endpoints, &nns_client).await {
+                        Ok(peer) => peer,
+                        Err(_) => return,
+                    };
 
                 {
                     let mut _peer_list_ = peer_list_.lock().await;
@@ -1,2 +1,3 @@
+pub mod access_list;
 pub mod manager;
 pub mod peer;
@@ -1,14 +1,23 @@
 use crate::{
     communicative::{
+        handshake::protocol_feature::{FeatureBitmap, ProtocolFeature},
         nns::client::NNSClient,
         tcp::{
             client::TCPClient,
-            tcp::{connect_nns, TCPError},
+            compression, envelope,
+            envelope_error::TCPEnvelopeError,
+            noise::{NoiseHandshake, NoiseRole, NoiseTransport},
+            noise_error::TCPNoiseError,
+            package::{PackageKind, TCPPackage},
+            replay::{self, ReplayWindow},
+            tcp::{self, connect_endpoint, connect_nns, TCPError},
         },
+        traffic::traffic_meter::{record_traffic, TrafficDirection},
     },
     operative::run_args::chain::Chain,
 };
 use async_trait::async_trait;
+use chrono::Utc;
 use colored::Colorize;
 use std::{net::SocketAddr, sync::Arc, time::Duration};
 use tokio::sync::Mutex;
@@ -32,13 +41,32 @@ impl PeerKind {
     }
 }
 
+/// Transport packages to a peer are encrypted with.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PeerTransport {
+    /// Each package is sealed in a NIP-44 envelope.
+    Envelope,
+    /// Packages are encrypted over a Noise XX session, falling back to envelopes whenever the
+    /// session cannot be established or breaks.
+    Noise,
+}
+
+/// Guarded Noise transport.
+#[allow(non_camel_case_types)]
+pub type NOISE_TRANSPORT = Arc<Mutex<NoiseTransport>>;
+
 #[derive(Clone)]
 pub struct Peer {
     chain: Chain,
     kind: PeerKind,
     key: [u8; 32],
+    endpoints: Vec<SocketAddr>,
     nns_client: NNSClient,
     connection: Option<(SOCKET, SocketAddr)>,
+    transport: PeerTransport,
+    noise_transport: Option<NOISE_TRANSPORT>,
+    replay_window: ReplayWindow,
+    features: FeatureBitmap,
 }
 
 /// Guarded peer.
@@ -51,9 +79,20 @@ impl Peer {
         kind: PeerKind,
         key: [u8; 32],
         nns_client: &NNSClient,
+    ) -> Result<PEER, TCPError> {
+        Self::connect_announced(chain, kind, key, Vec::new(), nns_client).await
+    }
+
+    /// Connects to a peer through its announced endpoints, falling back to its NNS address.
+    pub async fn connect_announced(
+        chain: Chain,
+        kind: PeerKind,
+        key: [u8; 32],
+        endpoints: Vec<SocketAddr>,
+        nns_client: &NNSClient,
     ) -> Result<PEER, TCPError> {
         let (socket_, addr) = {
-            match connect_nns(key, &nns_client, chain).await {
+            match open_socket(chain, key, &endpoints, &nns_client).await {
                 Ok(socket) => {
                     let addr = match socket.peer_addr() {
                         Ok(addr) => addr,
@@ -74,8 +113,13 @@ impl Peer {
             chain,
             kind,
             key,
+            endpoints,
             connection,
             nns_client: nns_client.clone(),
+            transport: PeerTransport::Envelope,
+            noise_transport: None,
+            replay_window: ReplayWindow::new(),
+            features: 0,
         };
 
         let peer = Arc::new(Mutex::new(peer_));
@@ -97,6 +141,10 @@ impl Peer {
         self.key
     }
 
+    pub fn endpoints(&self) -> Vec<SocketAddr> {
+        self.endpoints.clone()
+    }
+
     pub fn nns_client(&self) -> NNSClient {
         self.nns_client.clone()
     }
@@ -116,8 +164,28 @@ impl Peer {
         Some(Arc::clone(&self.connection()?.0))
     }
 
+    /// Sets the connection, dropping the Noise session of the previous connection.
     pub fn set_connection(&mut self, connection: Option<(SOCKET, SocketAddr)>) {
         self.connection = connection;
+        self.noise_transport = None;
+    }
+
+    pub fn transport(&self) -> PeerTransport {
+        self.transport
+    }
+
+    pub fn noise_transport(&self) -> Option<NOISE_TRANSPORT> {
+        self.noise_transport.clone()
+    }
+
+    /// Returns the protocol features negotiated with the peer, none until negotiated.
+    pub fn features(&self) -> FeatureBitmap {
+        self.features
+    }
+
+    /// Sets the protocol features negotiated with the peer in the handshake.
+    pub fn set_features(&mut self, features: FeatureBitmap) {
+        self.features = features;
     }
 
     pub fn addr(&self) -> String {
@@ -132,6 +200,29 @@ impl Peer {
     }
 }
 
+/// Opens a socket to a peer, trying its direct peer address first, then its announced endpoints, and
+/// finally its NNS address.
+async fn open_socket(
+    chain: Chain,
+    key: [u8; 32],
+    endpoints: &[SocketAddr],
+    nns_client: &NNSClient,
+) -> Result<tokio::net::TcpStream, TCPError> {
+    if let Some(direct_peer) = nns_client.direct_peer(key).await {
+        if let Ok(socket) = connect_endpoint(direct_peer).await {
+            return Ok(socket);
+        }
+    }
+
+    for endpoint in endpoints.iter() {
+        if let Ok(socket) = connect_endpoint(*endpoint).await {
+            return Ok(socket);
+        }
+    }
+
+    connect_nns(key, nns_client, chain).await
+}
+
 #[async_trait]
 pub trait PeerConnection {
     async fn key(&self) -> [u8; 32];
@@ -139,6 +230,13 @@ pub trait PeerConnection {
     async fn disconnection(&self);
     async fn reconnect(&self);
     async fn set_uptimer(&self);
+    async fn select_transport(&self, transport: PeerTransport) -> Result<(), TCPError>;
+    async fn establish_noise(&self) -> Result<(), TCPError>;
+    async fn request(
+        &self,
+        package: TCPPackage,
+        timeout: Option<Duration>,
+    ) -> Result<(TCPPackage, Duration), TCPError>;
 }
 
 #[async_trait]
@@ -187,12 +285,12 @@ impl PeerConnection for PEER {
 
         let (socket_, addr) = {
             loop {
-                let (nns_key, nns_client) = {
+                let (nns_key, endpoints, nns_client) = {
                     let _peer = self.lock().await;
-                    (_peer.key(), _peer.nns_client())
+                    (_peer.key(), _peer.endpoints(), _peer.nns_client())
                 };
 
-                match connect_nns(nns_key, &nns_client, chain).await {
+                match open_socket(chain, nns_key, &endpoints, &nns_client).await {
                     Ok(socket) => {
                         let addr = match socket.peer_addr() {
                             Ok(addr) => addr,
@@ -214,9 +312,15 @@ impl PeerConnection for PEER {
 
         let socket: SOCKET = Arc::new(Mutex::new(socket_));
 
-        {
+        let transport = {
             let mut _peer = self.lock().await;
             _peer.set_connection(Some((socket, addr)));
+            _peer.transport()
+        };
+
+        // Re-establish the Noise session over the new connection, or fall back to envelopes.
+        if transport == PeerTransport::Noise {
+            let _ = self.establish_noise().await;
         }
     }
 
@@ -253,4 +357,241 @@ impl PeerConnection for PEER {
             }
         });
     }
+
+    /// Selects the transport packages to the peer are encrypted with.
+    ///
+    /// Selecting Noise establishes a session right away. Packages fall back to envelopes if it cannot
+    /// be established, in which case the error is returned, and it is tried again on reconnection.
+    async fn select_transport(&self, transport: PeerTransport) -> Result<(), TCPError> {
+        {
+            let mut _self = self.lock().await;
+            _self.transport = transport;
+            _self.noise_transport = None;
+        }
+
+        match transport {
+            PeerTransport::Envelope => Ok(()),
+            PeerTransport::Noise => self.establish_noise().await,
+        }
+    }
+
+    /// Runs a Noise XX handshake with the peer as the initiator, authenticating the peer by its key.
+    async fn establish_noise(&self) -> Result<(), TCPError> {
+        // 1 Get the socket, the peer key and the local keys.
+        let (socket, peer_key, local_keys) = {
+            let _self = self.lock().await;
+            let socket = _self.socket().ok_or(TCPError::ConnErr)?;
+            (socket, _self.key(), _self.nns_client().local_keys())
+        };
+
+        // 2 Start the handshake with the local static key.
+        let mut handshake = NoiseHandshake::new(
+            NoiseRole::Initiator,
+            local_keys.secret_key().to_secret_bytes(),
+        )
+        .map_err(TCPError::NoiseErr)?;
+
+        // 3 -> e
+        let message = handshake.write_message().map_err(TCPError::NoiseErr)?;
+        let message_package = TCPPackage::new(
+            PackageKind::NoiseHandshake,
+            Utc::now().timestamp(),
+            &message,
+        );
+        let (response_package, _) = metered_request(
+            &socket,
+            peer_key,
+            PackageKind::NoiseHandshake,
+            message_package,
+            None,
+        )
+        .await?;
+
+        // 4 <- e, ee, s, es, which must come from the peer key.
+        handshake
+            .read_message(&response_package.payload())
+            .map_err(TCPError::NoiseErr)?;
+        if handshake.remote_key() != Some(peer_key) {
+            return Err(TCPError::NoiseErr(TCPNoiseError::UnexpectedRemoteKey));
+        }
+
+        // 5 -> s, se, which the peer acknowledges with an empty handshake package.
+        let message = handshake.write_message().map_err(TCPError::NoiseErr)?;
+        let message_package = TCPPackage::new(
+            PackageKind::NoiseHandshake,
+            Utc::now().timestamp(),
+            &message,
+        );
+        let (response_package, _) = metered_request(
+            &socket,
+            peer_key,
+            PackageKind::NoiseHandshake,
+            message_package,
+            None,
+        )
+        .await?;
+        if response_package.payload_len() != 0 {
+            return Err(TCPError::NoiseErr(TCPNoiseError::MalformedMessage));
+        }
+
+        // 6 Keep the transport for the connection.
+        let noise_transport = handshake.into_transport().map_err(TCPError::NoiseErr)?;
+        {
+            let mut _self = self.lock().await;
+            _self.noise_transport = Some(Arc::new(Mutex::new(noise_transport)));
+        }
+
+        Ok(())
+    }
+
+    /// Sends a package encrypted to the peer and returns the response package, which must be encrypted
+    /// by the peer.
+    ///
+    /// The package goes over the Noise session of the peer if there is one, and is sealed in an
+    /// envelope otherwise. A broken Noise session is dropped and the package is sent again in an
+    /// envelope. Large packages are compressed first if the peer negotiated compression.
+    async fn request(
+        &self,
+        package: TCPPackage,
+        timeout: Option<Duration>,
+    ) -> Result<(TCPPackage, Duration), TCPError> {
+        // 1 Get the socket, the peer key, the local keys, the Noise session and the negotiated
+        // features.
+        let (socket, peer_key, local_keys, noise_transport, features) = {
+            let _self = self.lock().await;
+            let socket = _self.socket().ok_or(TCPError::ConnErr)?;
+            (
+                socket,
+                _self.key(),
+                _self.nns_client().local_keys(),
+                _self.noise_transport(),
+                _self.features(),
+            )
+        };
+
+        // 2 Compress the package if the peer negotiated compression.
+        let message_kind = package.kind();
+        let package = match ProtocolFeature::Compression.in_bitmap(features) {
+            true => compression::compress(package),
+            false => package,
+        };
+
+        // 3 Prefer the Noise session.
+        if let Some(noise_transport) = noise_transport {
+            match request_noise(
+                &socket,
+                peer_key,
+                message_kind,
+                &noise_transport,
+                &package,
+                timeout,
+            )
+            .await
+            {
+                // 3.a Return the response package.
+                Ok(response) => return Ok(response),
+                // 3.b Drop the broken session and fall back to an envelope.
+                Err(TCPError::NoiseErr(_)) => {
+                    let mut _self = self.lock().await;
+                    if let Some(current) = &_self.noise_transport {
+                        if Arc::ptr_eq(current, &noise_transport) {
+                            _self.noise_transport = None;
+                        }
+                    }
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        // 4 Seal the package to the peer.
+        let sealed_package =
+            envelope::seal(&local_keys, peer_key, replay::next_sequence(), &package)
+                .map_err(TCPError::EnvelopeErr)?;
+
+        // 5 Send the sealed package and get the sealed response package.
+        let (sealed_response_package, duration) =
+            metered_request(&socket, peer_key, message_kind, sealed_package, timeout).await?;
+
+        // 6 Open the response package, which must be sealed by the peer.
+        let (sender_key, sequence, response_package) =
+            envelope::open(&local_keys, &sealed_response_package).map_err(TCPError::EnvelopeErr)?;
+        if sender_key != peer_key {
+            return Err(TCPError::EnvelopeErr(TCPEnvelopeError::SenderKeyMismatch));
+        }
+
+        // 7 Refuse a replayed response package.
+        {
+            let mut _self = self.lock().await;
+            _self
+                .replay_window
+                .accept(sequence)
+                .map_err(TCPError::ReplayErr)?;
+        }
+
+        // 8 Decompress and return the response package.
+        let response_package =
+            compression::decompress(response_package).map_err(TCPError::CompressionErr)?;
+        Ok((response_package, duration))
+    }
+}
+
+/// Sends a package over a Noise session and returns the decrypted and decompressed response package.
+async fn request_noise(
+    socket: &SOCKET,
+    peer_key: [u8; 32],
+    message_kind: PackageKind,
+    noise_transport: &NOISE_TRANSPORT,
+    package: &TCPPackage,
+    timeout: Option<Duration>,
+) -> Result<(TCPPackage, Duration), TCPError> {
+    let mut _noise_transport = noise_transport.lock().await;
+
+    // 1 Encrypt the package.
+    let sealed_package = _noise_transport.seal(package).map_err(TCPError::NoiseErr)?;
+
+    // 2 Send the encrypted package and get the encrypted response package.
+    let (sealed_response_package, duration) =
+        metered_request(socket, peer_key, message_kind, sealed_package, timeout).await?;
+
+    // 3 Decrypt the response package.
+    let response_package = _noise_transport
+        .open(&sealed_response_package)
+        .map_err(TCPError::NoiseErr)?;
+
+    // 4 Decompress the response package.
+    let response_package =
+        compression::decompress(response_package).map_err(TCPError::CompressionErr)?;
+
+    Ok((response_package, duration))
+}
+
+/// Sends a package to the peer and returns the response package, accounting the traffic of both to
+/// the peer under the given message type.
+async fn metered_request(
+    socket: &SOCKET,
+    peer_key: [u8; 32],
+    message_kind: PackageKind,
+    package: TCPPackage,
+    timeout: Option<Duration>,
+) -> Result<(TCPPackage, Duration), TCPError> {
+    // 1 Account the package as sent.
+    record_traffic(
+        Some(peer_key),
+        message_kind,
+        TrafficDirection::Sent,
+        package.wire_len(),
+    );
+
+    // 2 Send the package and get the response package.
+    let (response_package, duration) = tcp::request(socket, package, timeout).await?;
+
+    // 3 Account the response package as received.
+    record_traffic(
+        Some(peer_key),
+        message_kind,
+        TrafficDirection::Received,
+        response_package.wire_len(),
+    );
+
+    Ok((response_package, duration))
 }
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Milliseconds until the next request would be admitted.
+type RetryAfterMillis = u64;
+
+/// Errors returned when an inbound request is rejected for exceeding its rate limit, or shed while the
+/// engine is overloaded (429-style).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RateLimitError {
+    IPRateLimited(RetryAfterMillis),
+    PeerRateLimited(RetryAfterMillis),
+    Overloaded(RetryAfterMillis),
+}
+
+impl RateLimitError {
+    /// Returns the milliseconds until the next request would be admitted.
+    pub fn retry_after_ms(&self) -> RetryAfterMillis {
+        match self {
+            RateLimitError::IPRateLimited(retry_after_ms) => *retry_after_ms,
+            RateLimitError::PeerRateLimited(retry_after_ms) => *retry_after_ms,
+            RateLimitError::Overloaded(retry_after_ms) => *retry_after_ms,
+        }
+    }
+
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(err, _)| err)
+    }
+
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        let kind = match self {
+            RateLimitError::IPRateLimited(_) => "ip_rate_limited_error",
+            RateLimitError::PeerRateLimited(_) => "peer_rate_limited_error",
+            RateLimitError::Overloaded(_) => "overloaded_error",
+        };
+        obj.insert("kind".to_string(), Value::String(kind.to_string()));
+        obj.insert(
+            "retry_after_ms".to_string(),
+            Value::Number(self.retry_after_ms().into()),
+        );
+        Value::Object(obj)
+    }
+}
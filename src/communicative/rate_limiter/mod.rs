@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod rate_limit_config;
+pub mod rate_limiter;
+pub mod token_bucket;
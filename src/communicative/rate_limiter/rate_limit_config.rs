@@ -0,0 +1,60 @@
+/// Default burst size per peer.
+const DEFAULT_PEER_BURST: u32 = 20;
+
+/// Default sustained requests per second per peer.
+const DEFAULT_PEER_PER_SECOND: u32 = 5;
+
+/// Default burst size per IP address.
+const DEFAULT_IP_BURST: u32 = 60;
+
+/// Default sustained requests per second per IP address.
+const DEFAULT_IP_PER_SECOND: u32 = 20;
+
+/// Token-bucket limits applied to inbound requests.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    // Burst size per peer.
+    pub peer_burst: u32,
+
+    // Sustained requests per second per peer.
+    pub peer_per_second: u32,
+
+    // Burst size per IP address.
+    pub ip_burst: u32,
+
+    // Sustained requests per second per IP address.
+    pub ip_per_second: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            peer_burst: DEFAULT_PEER_BURST,
+            peer_per_second: DEFAULT_PEER_PER_SECOND,
+            ip_burst: DEFAULT_IP_BURST,
+            ip_per_second: DEFAULT_IP_PER_SECOND,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Reads the limits from the environment, falling back to the defaults for unset or invalid values.
+    ///
+    /// `CUBE_RATE_LIMIT_PEER_BURST`, `CUBE_RATE_LIMIT_PEER_PER_SECOND`,
+    /// `CUBE_RATE_LIMIT_IP_BURST` and `CUBE_RATE_LIMIT_IP_PER_SECOND`.
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: u32| -> u32 {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            peer_burst: read("CUBE_RATE_LIMIT_PEER_BURST", DEFAULT_PEER_BURST),
+            peer_per_second: read("CUBE_RATE_LIMIT_PEER_PER_SECOND", DEFAULT_PEER_PER_SECOND),
+            ip_burst: read("CUBE_RATE_LIMIT_IP_BURST", DEFAULT_IP_BURST),
+            ip_per_second: read("CUBE_RATE_LIMIT_IP_PER_SECOND", DEFAULT_IP_PER_SECOND),
+        }
+    }
+}
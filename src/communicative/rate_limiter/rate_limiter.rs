@@ -0,0 +1,85 @@
+use crate::communicative::rate_limiter::errors::rate_limit_error::RateLimitError;
+use crate::communicative::rate_limiter::rate_limit_config::RateLimitConfig;
+use crate::communicative::rate_limiter::token_bucket::TokenBucket;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Number of tracked buckets after which full (idle) buckets are pruned.
+const BUCKET_PRUNE_THRESHOLD: usize = 4_096;
+
+/// Per-peer and per-IP token-bucket rate limiter.
+pub struct RateLimiter {
+    // Token-bucket limits.
+    config: RateLimitConfig,
+
+    // Buckets by peer key.
+    peer_buckets: HashMap<PeerKey, TokenBucket>,
+
+    // Buckets by IP address.
+    ip_buckets: HashMap<IpAddr, TokenBucket>,
+}
+
+/// Guarded 'RateLimiter'.
+#[allow(non_camel_case_types)]
+pub type RATE_LIMITER = Arc<Mutex<RateLimiter>>;
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> RATE_LIMITER {
+        let rate_limiter = RateLimiter {
+            config,
+            peer_buckets: HashMap::new(),
+            ip_buckets: HashMap::new(),
+        };
+
+        Arc::new(Mutex::new(rate_limiter))
+    }
+
+    /// Returns the token-bucket limits.
+    pub fn config(&self) -> RateLimitConfig {
+        self.config
+    }
+
+    /// Admits or rejects a request from an IP address.
+    pub fn check_ip(&mut self, ip: IpAddr, now: Instant) -> Result<(), RateLimitError> {
+        let (burst, per_second) = (self.config.ip_burst, self.config.ip_per_second);
+        take_token(&mut self.ip_buckets, ip, burst, per_second, now)
+            .map_err(RateLimitError::IPRateLimited)
+    }
+
+    /// Admits or rejects a request from a peer.
+    pub fn check_peer(&mut self, key: PeerKey, now: Instant) -> Result<(), RateLimitError> {
+        let (burst, per_second) = (self.config.peer_burst, self.config.peer_per_second);
+        take_token(&mut self.peer_buckets, key, burst, per_second, now)
+            .map_err(RateLimitError::PeerRateLimited)
+    }
+}
+
+/// Takes a token from the bucket of a key, creating the bucket if needed.
+///
+/// Returns the milliseconds until the next token on rejection.
+fn take_token<K: Eq + Hash>(
+    buckets: &mut HashMap<K, TokenBucket>,
+    key: K,
+    burst: u32,
+    per_second: u32,
+    now: Instant,
+) -> Result<(), u64> {
+    // 1 Prune the idle buckets if too many are tracked.
+    if buckets.len() >= BUCKET_PRUNE_THRESHOLD {
+        buckets.retain(|_, bucket| !bucket.is_full(now));
+    }
+
+    // 2 Take a token from the bucket.
+    buckets
+        .entry(key)
+        .or_insert_with(|| TokenBucket::new(burst, per_second, now))
+        .try_take(now)
+        .map_err(|retry_after| retry_after.as_millis().min(u64::MAX as u128) as u64)
+}
@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+/// A token bucket that refills continuously up to its capacity.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    // Maximum number of tokens (burst size).
+    capacity: f64,
+
+    // Tokens refilled per second.
+    refill_per_second: f64,
+
+    // Currently available tokens.
+    tokens: f64,
+
+    // Last time the bucket was refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Constructs a full token bucket.
+    pub fn new(capacity: u32, refill_per_second: u32, now: Instant) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_per_second: refill_per_second as f64,
+            tokens: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Refills the bucket for the time elapsed since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes a single token.
+    ///
+    /// Returns the duration to wait for the next token if the bucket is empty.
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        // 1 Refill the bucket.
+        self.refill(now);
+
+        // 2 Take a token if one is available.
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        // 3 A bucket that never refills never admits again.
+        if self.refill_per_second <= 0.0 {
+            return Err(Duration::MAX);
+        }
+
+        // 4 Return the time until the next token.
+        let missing = 1.0 - self.tokens;
+        Err(Duration::from_secs_f64(missing / self.refill_per_second))
+    }
+
+    /// Whether the bucket is full at the given time, i.e. it carries no state worth keeping.
+    pub fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens + elapsed * self.refill_per_second >= self.capacity
+    }
+}
@@ -1,32 +1,42 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_batch::batch_call;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cache::{rpc_cache, RpcCache};
 use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::{
-    BitcoinRPCBroadcastRawTransactionError, BitcoinRPCGetChainTipError, BitcoinRPCRetrieveBlockError,
-    BitcoinRPCGetMempoolFeeRateError, BitcoinRPCValidateRPCError,
+    BitcoinRPCBroadcastRawTransactionError, BitcoinRPCEstimateSmartFeeError,
+    BitcoinRPCGetChainTipError, BitcoinRPCGetMempoolFeeRateError, BitcoinRPCRetrieveBlockError,
+    BitcoinRPCRetrieveBlockFilterError, BitcoinRPCRetrieveBlockHeaderError,
+    BitcoinRPCRetrieveMempoolError, BitcoinRPCRetrieveTxOutError, BitcoinRPCValidateRPCError,
+    BitcoinRPCWaitForNewBlockError,
 };
 use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_retry::with_retry;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_transport::new_rpc_client;
 use crate::operative::run_args::chain::Chain;
-use bitcoin::{Block, BlockHash, Transaction, Txid};
-use bitcoincore_rpc::{json::GetBlockchainInfoResult, Auth, Client, RpcApi};
+use bitcoin::bip158::BlockFilter;
+use bitcoin::{Block, BlockHash, OutPoint, Transaction, Txid};
+use bitcoincore_rpc::json::{EstimateMode, GetBlockchainInfoResult};
+use bitcoincore_rpc::RpcApi;
+use serde_json::Value;
+use std::sync::MutexGuard;
+use std::time::Duration;
 
 /// Validates the Bitcoin RPC.
 pub fn validate_rpc(
     rpc_holder: &BitcoinRPCHolder,
     chain: Chain,
 ) -> Result<(), BitcoinRPCValidateRPCError> {
-    let rpc_url = rpc_holder.url();
-    let rpc_user = rpc_holder.user();
-    let rpc_password = rpc_holder.password();
-
     // Create RPC client.
-    let rpc_client = match Client::new(&rpc_url, Auth::UserPass(rpc_user, rpc_password)) {
+    let rpc_client = match new_rpc_client(rpc_holder) {
         Ok(client) => client,
-        Err(err) => return Err(BitcoinRPCValidateRPCError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCValidateRPCError::RPCErr(err.into())),
     };
 
     // Get blockchain info.
-    let blockchain_info: GetBlockchainInfoResult = match rpc_client.get_blockchain_info() {
-        Ok(result) => result,
-        Err(err) => return Err(BitcoinRPCValidateRPCError::RPCErr(err)),
-    };
+    let blockchain_info: GetBlockchainInfoResult =
+        match with_retry(|| rpc_client.get_blockchain_info()) {
+            Ok(result) => result,
+            Err(err) => return Err(BitcoinRPCValidateRPCError::RPCErr(err.into())),
+        };
+    observe_tip(blockchain_info.blocks);
 
     // Validate chain.
     match blockchain_info.chain {
@@ -56,21 +66,19 @@ pub fn validate_rpc(
 pub fn get_chain_tip(
     rpc_holder: &BitcoinRPCHolder,
 ) -> Result<(u64, bool), BitcoinRPCGetChainTipError> {
-    let rpc_url = rpc_holder.url();
-    let rpc_user = rpc_holder.user();
-    let rpc_password = rpc_holder.password();
-
     // Create RPC client.
-    let rpc_client = match Client::new(&rpc_url, Auth::UserPass(rpc_user, rpc_password)) {
+    let rpc_client = match new_rpc_client(rpc_holder) {
         Ok(client) => client,
-        Err(err) => return Err(BitcoinRPCGetChainTipError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCGetChainTipError::RPCErr(err.into())),
     };
 
     // Get blockchain info.
-    let blockchain_info: GetBlockchainInfoResult = match rpc_client.get_blockchain_info() {
-        Ok(result) => result,
-        Err(err) => return Err(BitcoinRPCGetChainTipError::RPCErr(err)),
-    };
+    let blockchain_info: GetBlockchainInfoResult =
+        match with_retry(|| rpc_client.get_blockchain_info()) {
+            Ok(result) => result,
+            Err(err) => return Err(BitcoinRPCGetChainTipError::RPCErr(err.into())),
+        };
+    observe_tip(blockchain_info.blocks);
 
     // Check if the Bitcoin node is fully synced.
     let is_synced = !blockchain_info.initial_block_download;
@@ -82,24 +90,42 @@ pub fn get_chain_tip(
     Ok((chain_height, is_synced))
 }
 
+/// Returns the height and the hash of the best block.
+pub fn retrieve_best_block(
+    rpc_holder: &BitcoinRPCHolder,
+) -> Result<(u64, BlockHash), BitcoinRPCGetChainTipError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCGetChainTipError::RPCErr(err.into())),
+    };
+
+    // Get blockchain info.
+    let blockchain_info: GetBlockchainInfoResult =
+        match with_retry(|| rpc_client.get_blockchain_info()) {
+            Ok(result) => result,
+            Err(err) => return Err(BitcoinRPCGetChainTipError::RPCErr(err.into())),
+        };
+    observe_tip(blockchain_info.blocks);
+
+    // Return the best block.
+    Ok((blockchain_info.blocks, blockchain_info.best_block_hash))
+}
+
 /// Returns mempool minimum fee rate in sat/vbyte.
 pub fn get_mempool_min_fee_rate(
     rpc_holder: &BitcoinRPCHolder,
 ) -> Result<u64, BitcoinRPCGetMempoolFeeRateError> {
-    let rpc_url = rpc_holder.url();
-    let rpc_user = rpc_holder.user();
-    let rpc_password = rpc_holder.password();
-
     // Create RPC client.
-    let rpc_client = match Client::new(&rpc_url, Auth::UserPass(rpc_user, rpc_password)) {
+    let rpc_client = match new_rpc_client(rpc_holder) {
         Ok(client) => client,
-        Err(err) => return Err(BitcoinRPCGetMempoolFeeRateError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCGetMempoolFeeRateError::RPCErr(err.into())),
     };
 
     // Get mempool info.
-    let mempool_info = match rpc_client.get_mempool_info() {
+    let mempool_info = match with_retry(|| rpc_client.get_mempool_info()) {
         Ok(result) => result,
-        Err(err) => return Err(BitcoinRPCGetMempoolFeeRateError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCGetMempoolFeeRateError::RPCErr(err.into())),
     };
 
     // Bitcoin Core returns mempool minimum fee as BTC/kvB.
@@ -110,50 +136,422 @@ pub fn get_mempool_min_fee_rate(
     Ok(mempool_min_fee_sat_per_vbyte)
 }
 
+/// Returns the fee rate in sat/vbyte estimated for confirmation within the given number of blocks.
+///
+/// Returns `None` if the Bitcoin node does not have enough data to estimate, such as shortly after
+/// starting up or on a test network with little traffic.
+pub fn estimate_smart_fee(
+    rpc_holder: &BitcoinRPCHolder,
+    conf_target: u16,
+    estimate_mode: EstimateMode,
+) -> Result<Option<u64>, BitcoinRPCEstimateSmartFeeError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCEstimateSmartFeeError::RPCErr(err.into())),
+    };
+
+    // Estimate the fee rate.
+    let estimate =
+        match with_retry(|| rpc_client.estimate_smart_fee(conf_target, Some(estimate_mode))) {
+            Ok(estimate) => estimate,
+            Err(err) => return Err(BitcoinRPCEstimateSmartFeeError::RPCErr(err.into())),
+        };
+
+    // Bitcoin Core returns the fee rate as BTC/kvB.
+    // Convert BTC/kvB -> sat/kvB -> sat/vbyte, rounded up to avoid underpaying.
+    Ok(estimate
+        .fee_rate
+        .map(|fee_rate| fee_rate.to_sat().div_ceil(1000).max(1)))
+}
+
 /// Returns the block at the given height.
 pub fn retrieve_block(
     rpc_holder: &BitcoinRPCHolder,
     height: u64,
 ) -> Result<bitcoin::blockdata::block::Block, BitcoinRPCRetrieveBlockError> {
-    let rpc_url = rpc_holder.url();
-    let rpc_user = rpc_holder.user();
-    let rpc_password = rpc_holder.password();
-
     // Create RPC client.
-    let rpc_client = match Client::new(&rpc_url, Auth::UserPass(rpc_user, rpc_password)) {
+    let rpc_client = match new_rpc_client(rpc_holder) {
         Ok(client) => client,
-        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
     };
 
     // Get block hash.
-    let block_hash: BlockHash = match rpc_client.get_block_hash(height) {
+    let block_hash: BlockHash = match with_retry(|| rpc_client.get_block_hash(height)) {
         Ok(block_hash) => block_hash,
-        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
     };
+    observe_block_height(block_hash, height);
+
+    // Serve the block from the cache if cached.
+    if let Some(block) = lock_rpc_cache().get_block(&block_hash) {
+        return Ok(block);
+    }
 
     // Get block.
-    let block: Block = match rpc_client.get_block(&block_hash) {
+    let block: Block = match with_retry(|| rpc_client.get_block(&block_hash)) {
         Ok(block) => block,
-        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
     };
+    lock_rpc_cache().insert_block(&block);
 
     // Return block.
     Ok(block)
 }
 
+/// Returns the hash of the block at the given height.
+pub fn retrieve_block_hash(
+    rpc_holder: &BitcoinRPCHolder,
+    height: u64,
+) -> Result<BlockHash, BitcoinRPCRetrieveBlockError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Get block hash.
+    match with_retry(|| rpc_client.get_block_hash(height)) {
+        Ok(block_hash) => {
+            observe_block_height(block_hash, height);
+            Ok(block_hash)
+        }
+        Err(err) => Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    }
+}
+
+/// Returns the block with the given hash.
+pub fn retrieve_block_by_hash(
+    rpc_holder: &BitcoinRPCHolder,
+    block_hash: &BlockHash,
+) -> Result<Block, BitcoinRPCRetrieveBlockError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Serve the block from the cache if cached.
+    if let Some(block) = lock_rpc_cache().get_block(block_hash) {
+        return Ok(block);
+    }
+
+    // Get block.
+    match with_retry(|| rpc_client.get_block(block_hash)) {
+        Ok(block) => {
+            lock_rpc_cache().insert_block(&block);
+            Ok(block)
+        }
+        Err(err) => Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    }
+}
+
+/// Returns the serialized block with the given hash, hex-encoded as served by the Bitcoin node.
+pub fn retrieve_block_hex_by_hash(
+    rpc_holder: &BitcoinRPCHolder,
+    block_hash: &BlockHash,
+) -> Result<String, BitcoinRPCRetrieveBlockError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Serve the block from the cache if cached.
+    if let Some(block) = lock_rpc_cache().get_block(block_hash) {
+        return Ok(bitcoin::consensus::encode::serialize_hex(&block));
+    }
+
+    // Get block hex.
+    match with_retry(|| rpc_client.get_block_hex(block_hash)) {
+        Ok(block_hex) => {
+            // Only blocks buried deeply enough to be cached are decoded.
+            let mut _rpc_cache = lock_rpc_cache();
+            if _rpc_cache.is_buried(block_hash) {
+                if let Some(block) = hex::decode(&block_hex)
+                    .ok()
+                    .and_then(|bytes| bitcoin::consensus::encode::deserialize(&bytes).ok())
+                {
+                    _rpc_cache.insert_block(&block);
+                }
+            }
+            Ok(block_hex)
+        }
+        Err(err) => Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    }
+}
+
+/// Returns the hashes of the blocks at the given heights, resolved in a single batch.
+pub fn retrieve_block_hashes(
+    rpc_holder: &BitcoinRPCHolder,
+    heights: &[u64],
+) -> Result<Vec<BlockHash>, BitcoinRPCRetrieveBlockError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Get block hashes.
+    let params: Vec<Vec<Value>> = heights
+        .iter()
+        .map(|height| vec![(*height).into()])
+        .collect();
+    let outcomes = match batch_call::<BlockHash>(&rpc_client, "getblockhash", &params) {
+        Ok(outcomes) => outcomes,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Return block hashes, failing if any of them failed.
+    let block_hashes: Vec<BlockHash> = outcomes
+        .into_iter()
+        .map(|outcome| outcome.map_err(|err| BitcoinRPCRetrieveBlockError::RPCErr(err.into())))
+        .collect::<Result<_, _>>()?;
+    {
+        let mut _rpc_cache = lock_rpc_cache();
+        for (block_hash, height) in block_hashes.iter().zip(heights) {
+            _rpc_cache.observe_block_height(*block_hash, *height);
+        }
+    }
+    Ok(block_hashes)
+}
+
+/// Returns the serialized blocks with the given hashes, hex-encoded, retrieved in a single batch.
+pub fn retrieve_blocks_hex_by_hashes(
+    rpc_holder: &BitcoinRPCHolder,
+    block_hashes: &[BlockHash],
+) -> Result<Vec<String>, BitcoinRPCRetrieveBlockError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Get block hexes, at verbosity 0.
+    let params: Vec<Vec<Value>> = block_hashes
+        .iter()
+        .map(|block_hash| vec![block_hash.to_string().into(), 0.into()])
+        .collect();
+    let outcomes = match batch_call::<String>(&rpc_client, "getblock", &params) {
+        Ok(outcomes) => outcomes,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockError::RPCErr(err.into())),
+    };
+
+    // Return block hexes, failing if any of them failed.
+    outcomes
+        .into_iter()
+        .map(|outcome| outcome.map_err(|err| BitcoinRPCRetrieveBlockError::RPCErr(err.into())))
+        .collect()
+}
+
+/// Returns the block header at the given height.
+pub fn retrieve_block_header(
+    rpc_holder: &BitcoinRPCHolder,
+    height: u64,
+) -> Result<bitcoin::blockdata::block::Header, BitcoinRPCRetrieveBlockHeaderError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockHeaderError::RPCErr(err.into())),
+    };
+
+    // Get block hash.
+    let block_hash: BlockHash = match with_retry(|| rpc_client.get_block_hash(height)) {
+        Ok(block_hash) => block_hash,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockHeaderError::RPCErr(err.into())),
+    };
+    observe_block_height(block_hash, height);
+
+    // Serve the block header from the cache if cached.
+    if let Some(block_header) = lock_rpc_cache().get_header(&block_hash) {
+        return Ok(block_header);
+    }
+
+    // Get block header.
+    let block_header = match with_retry(|| rpc_client.get_block_header(&block_hash)) {
+        Ok(block_header) => block_header,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockHeaderError::RPCErr(err.into())),
+    };
+    lock_rpc_cache().insert_header(&block_header);
+
+    // Return block header.
+    Ok(block_header)
+}
+
+/// Returns the BIP158 basic compact block filter of the block with the given hash.
+///
+/// Requires the Bitcoin node to run with `-blockfilterindex=1`.
+pub fn retrieve_block_filter(
+    rpc_holder: &BitcoinRPCHolder,
+    block_hash: &BlockHash,
+) -> Result<BlockFilter, BitcoinRPCRetrieveBlockFilterError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockFilterError::RPCErr(err.into())),
+    };
+
+    // Get block filter.
+    let block_filter_result = match with_retry(|| rpc_client.get_block_filter(block_hash)) {
+        Ok(block_filter_result) => block_filter_result,
+        Err(err) => return Err(BitcoinRPCRetrieveBlockFilterError::RPCErr(err.into())),
+    };
+
+    // Return block filter.
+    Ok(BlockFilter::new(&block_filter_result.filter))
+}
+
+/// Returns the txids of the transactions in the mempool.
+pub fn retrieve_mempool_txids(
+    rpc_holder: &BitcoinRPCHolder,
+) -> Result<Vec<Txid>, BitcoinRPCRetrieveMempoolError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveMempoolError::RPCErr(err.into())),
+    };
+
+    // Get mempool txids.
+    match with_retry(|| rpc_client.get_raw_mempool()) {
+        Ok(txids) => Ok(txids),
+        Err(err) => Err(BitcoinRPCRetrieveMempoolError::RPCErr(err.into())),
+    }
+}
+
+/// Returns the mempool transaction with the given txid.
+pub fn retrieve_mempool_transaction(
+    rpc_holder: &BitcoinRPCHolder,
+    txid: &Txid,
+) -> Result<Transaction, BitcoinRPCRetrieveMempoolError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveMempoolError::RPCErr(err.into())),
+    };
+
+    // Serve the transaction from the cache if cached.
+    if let Some(transaction) = lock_rpc_cache().get_transaction(txid) {
+        return Ok(transaction);
+    }
+
+    // Get transaction.
+    match with_retry(|| rpc_client.get_raw_transaction(txid, None)) {
+        Ok(transaction) => {
+            lock_rpc_cache().insert_transaction(&transaction);
+            Ok(transaction)
+        }
+        Err(err) => Err(BitcoinRPCRetrieveMempoolError::RPCErr(err.into())),
+    }
+}
+
+/// Returns the mempool transactions with the given txids, retrieved in batches.
+///
+/// Transactions that cannot be retrieved, such as those that left the mempool, are `None`.
+pub fn retrieve_mempool_transactions(
+    rpc_holder: &BitcoinRPCHolder,
+    txids: &[Txid],
+) -> Result<Vec<Option<Transaction>>, BitcoinRPCRetrieveMempoolError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveMempoolError::RPCErr(err.into())),
+    };
+
+    // Serve the transactions from the cache where cached.
+    let mut transactions: Vec<Option<Transaction>> = {
+        let mut _rpc_cache = lock_rpc_cache();
+        txids
+            .iter()
+            .map(|txid| _rpc_cache.get_transaction(txid))
+            .collect()
+    };
+
+    // Get the rest of the raw transactions.
+    let params: Vec<Vec<Value>> = txids
+        .iter()
+        .zip(transactions.iter())
+        .filter(|(_, transaction)| transaction.is_none())
+        .map(|(txid, _)| vec![txid.to_string().into()])
+        .collect();
+    if params.is_empty() {
+        return Ok(transactions);
+    }
+    let outcomes = match batch_call::<String>(&rpc_client, "getrawtransaction", &params) {
+        Ok(outcomes) => outcomes,
+        Err(err) => return Err(BitcoinRPCRetrieveMempoolError::RPCErr(err.into())),
+    };
+
+    // Decode the transactions and fill in the gaps.
+    let mut _rpc_cache = lock_rpc_cache();
+    let mut outcomes = outcomes.into_iter();
+    for transaction in transactions
+        .iter_mut()
+        .filter(|transaction| transaction.is_none())
+    {
+        *transaction = outcomes.next().and_then(|outcome| {
+            let raw_bytes = hex::decode(outcome.ok()?).ok()?;
+            let transaction: Transaction =
+                bitcoin::consensus::encode::deserialize(&raw_bytes).ok()?;
+            _rpc_cache.insert_transaction(&transaction);
+            Some(transaction)
+        });
+    }
+    Ok(transactions)
+}
+
+/// Returns the number of confirmations of the given unspent output, `0` if it is still in the mempool.
+///
+/// Returns `None` if the output is spent or unknown to the Bitcoin node.
+pub fn retrieve_txout_confirmations(
+    rpc_holder: &BitcoinRPCHolder,
+    outpoint: &OutPoint,
+) -> Result<Option<u32>, BitcoinRPCRetrieveTxOutError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCRetrieveTxOutError::RPCErr(err.into())),
+    };
+
+    // Get txout, including the mempool.
+    match with_retry(|| rpc_client.get_tx_out(&outpoint.txid, outpoint.vout, Some(true))) {
+        Ok(txout) => Ok(txout.map(|txout| txout.confirmations)),
+        Err(err) => Err(BitcoinRPCRetrieveTxOutError::RPCErr(err.into())),
+    }
+}
+
+/// Waits for a new block for up to the given timeout, and returns the height of the chain tip.
+///
+/// Long-polls the Bitcoin node with `waitfornewblock`, which returns as soon as a new block is connected,
+/// or with the current tip once the timeout elapses.
+pub fn wait_for_new_block(
+    rpc_holder: &BitcoinRPCHolder,
+    timeout: Duration,
+) -> Result<u64, BitcoinRPCWaitForNewBlockError> {
+    // Create RPC client.
+    let rpc_client = match new_rpc_client(rpc_holder) {
+        Ok(client) => client,
+        Err(err) => return Err(BitcoinRPCWaitForNewBlockError::RPCErr(err.into())),
+    };
+
+    // Wait for a new block.
+    match with_retry(|| rpc_client.wait_for_new_block(timeout.as_millis() as u64)) {
+        Ok(block_ref) => {
+            observe_tip(block_ref.height);
+            Ok(block_ref.height)
+        }
+        Err(err) => Err(BitcoinRPCWaitForNewBlockError::RPCErr(err.into())),
+    }
+}
+
 /// Broadcasts a raw transaction hex and returns its txid.
 pub fn broadcast_raw_transaction(
     rpc_holder: &BitcoinRPCHolder,
     raw_transaction_hex: &str,
 ) -> Result<Txid, BitcoinRPCBroadcastRawTransactionError> {
-    let rpc_url = rpc_holder.url();
-    let rpc_user = rpc_holder.user();
-    let rpc_password = rpc_holder.password();
-
     // Create RPC client.
-    let rpc_client = match Client::new(&rpc_url, Auth::UserPass(rpc_user, rpc_password)) {
+    let rpc_client = match new_rpc_client(rpc_holder) {
         Ok(client) => client,
-        Err(err) => return Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err)),
+        Err(err) => return Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err.into())),
     };
 
     // Decode raw transaction hex into a bitcoin::Transaction.
@@ -167,8 +565,25 @@ pub fn broadcast_raw_transaction(
     };
 
     // Broadcast the transaction.
-    match rpc_client.send_raw_transaction(&transaction) {
+    match with_retry(|| rpc_client.send_raw_transaction(&transaction)) {
         Ok(txid) => Ok(txid),
-        Err(err) => Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err)),
+        Err(err) => Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err.into())),
     }
 }
+
+/// Locks the response cache.
+fn lock_rpc_cache() -> MutexGuard<'static, RpcCache> {
+    rpc_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Records the height of the chain tip in the response cache.
+fn observe_tip(tip_height: u64) {
+    lock_rpc_cache().observe_tip(tip_height);
+}
+
+/// Records the height of a block hash in the response cache.
+fn observe_block_height(block_hash: BlockHash, height: u64) {
+    lock_rpc_cache().observe_block_height(block_hash, height);
+}
@@ -0,0 +1,58 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_retry::with_retry;
+use bitcoincore_rpc::jsonrpc;
+use bitcoincore_rpc::Client;
+use serde::de::DeserializeOwned;
+use serde_json::value::{to_raw_value, RawValue};
+use serde_json::Value;
+
+/// Maximum number of calls sent in a single batch; longer lists are split over several batches.
+pub const MAX_BATCH_CALLS: usize = 100;
+
+/// Sends calls of the same method as JSON-RPC batches, one HTTP round trip per batch.
+///
+/// Returns the outcome of each call in the order of the given parameters. A failed call does not
+/// fail the others; the batch as a whole only fails if it cannot be sent or its response cannot be
+/// read, in which case it is retried like any other call.
+pub fn batch_call<T: DeserializeOwned>(
+    rpc_client: &Client,
+    method: &str,
+    params: &[Vec<Value>],
+) -> Result<Vec<Result<T, bitcoincore_rpc::Error>>, bitcoincore_rpc::Error> {
+    let mut outcomes = Vec::with_capacity(params.len());
+
+    for chunk in params.chunks(MAX_BATCH_CALLS) {
+        // 1 Encode the parameters of each call.
+        let raw_params: Vec<Box<RawValue>> = chunk
+            .iter()
+            .map(to_raw_value)
+            .collect::<Result<_, _>>()
+            .map_err(bitcoincore_rpc::Error::Json)?;
+
+        // 2 Send the batch.
+        let jsonrpc_client = rpc_client.get_jsonrpc_client();
+        let responses = with_retry(|| {
+            let requests: Vec<jsonrpc::Request> = raw_params
+                .iter()
+                .map(|raw_args| jsonrpc_client.build_request(method, Some(raw_args)))
+                .collect();
+            jsonrpc_client
+                .send_batch(&requests)
+                .map_err(bitcoincore_rpc::Error::JsonRpc)
+        })?;
+
+        // 3 Read the outcome of each call.
+        for response in responses {
+            outcomes.push(match response {
+                Some(response) => response
+                    .result::<T>()
+                    .map_err(bitcoincore_rpc::Error::JsonRpc),
+                None => Err(bitcoincore_rpc::Error::ReturnedError(format!(
+                    "No response to {} in the batch.",
+                    method
+                ))),
+            });
+        }
+    }
+
+    Ok(outcomes)
+}
@@ -0,0 +1,272 @@
+use bitcoin::block::Header;
+use bitcoin::{Block, BlockHash, Transaction, Txid};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Mutex, OnceLock};
+
+/// Default number of blocks kept.
+const DEFAULT_CACHE_BLOCKS: usize = 16;
+
+/// Default number of block headers kept.
+const DEFAULT_CACHE_HEADERS: usize = 4_096;
+
+/// Default number of transactions kept.
+const DEFAULT_CACHE_TRANSACTIONS: usize = 4_096;
+
+/// Default number of confirmations after which a block is considered final.
+const DEFAULT_CACHE_CONFIRMATIONS: u64 = 6;
+
+/// Number of block heights kept, to tell how deeply the cached blocks are buried.
+const BLOCK_HEIGHT_ENTRIES: usize = 16_384;
+
+/// Process-wide response cache, read from the environment on first use.
+static RPC_CACHE: OnceLock<Mutex<RpcCache>> = OnceLock::new();
+
+/// A map of bounded size, evicting the least recently used entries first.
+pub struct LruMap<K, V> {
+    // Entries, along with when they were last used.
+    entries: HashMap<K, (V, u64)>,
+
+    // Keys by when they were last used, the least recently used first.
+    recency: BTreeMap<u64, K>,
+
+    // Tick of the next use.
+    next_tick: u64,
+
+    // Maximum number of entries; `0` disables the map.
+    capacity: usize,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LruMap<K, V> {
+    /// Constructs an empty map.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_tick: 0,
+            capacity,
+        }
+    }
+
+    /// Returns the value of the key, marking it as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let (value, tick) = self.entries.get_mut(key)?;
+        self.recency.remove(tick);
+        *tick = self.next_tick;
+        self.recency.insert(self.next_tick, key.clone());
+        self.next_tick += 1;
+        Some(value.clone())
+    }
+
+    /// Inserts a value, evicting the least recently used entries while over capacity.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some((_, tick)) = self.entries.remove(&key) {
+            self.recency.remove(&tick);
+        }
+        self.entries.insert(key.clone(), (value, self.next_tick));
+        self.recency.insert(self.next_tick, key);
+        self.next_tick += 1;
+
+        while self.entries.len() > self.capacity {
+            match self.recency.pop_first() {
+                Some((_, oldest_key)) => self.entries.remove(&oldest_key),
+                None => break,
+            };
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Cache of the Bitcoin RPC responses that can no longer change, shared by all RPC calls.
+///
+/// Transactions are kept by txid, which commits to their content. Blocks and block headers are kept by
+/// hash once buried under `min_confirmations` blocks, so that the blocks likely to be reorged out do not
+/// crowd the cache. How deeply a block is buried is told from the heights of the block hashes and the
+/// chain tip seen in earlier responses; blocks of unknown height are not cached.
+pub struct RpcCache {
+    // Number of confirmations after which a block is considered final.
+    min_confirmations: u64,
+
+    // Height of the chain tip last seen.
+    tip_height: Option<u64>,
+
+    // Heights of the block hashes seen.
+    block_heights: LruMap<BlockHash, u64>,
+
+    // Buried blocks.
+    blocks: LruMap<BlockHash, Block>,
+
+    // Headers of buried blocks.
+    headers: LruMap<BlockHash, Header>,
+
+    // Transactions.
+    transactions: LruMap<Txid, Transaction>,
+
+    // Number of responses served from the cache.
+    hits: u64,
+
+    // Number of lookups that missed the cache.
+    misses: u64,
+}
+
+impl RpcCache {
+    /// Constructs an empty cache.
+    pub fn new(
+        max_blocks: usize,
+        max_headers: usize,
+        max_transactions: usize,
+        min_confirmations: u64,
+    ) -> Self {
+        Self {
+            min_confirmations,
+            tip_height: None,
+            block_heights: LruMap::new(BLOCK_HEIGHT_ENTRIES),
+            blocks: LruMap::new(max_blocks),
+            headers: LruMap::new(max_headers),
+            transactions: LruMap::new(max_transactions),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Constructs an empty cache from the environment.
+    ///
+    /// `CUBE_BITCOIN_RPC_CACHE_BLOCKS`, `CUBE_BITCOIN_RPC_CACHE_HEADERS` and `CUBE_BITCOIN_RPC_CACHE_TXS`
+    /// optionally override the number of blocks, headers and transactions kept, `0` disabling the
+    /// respective cache. `CUBE_BITCOIN_RPC_CACHE_CONFIRMATIONS` optionally overrides the number of
+    /// confirmations after which blocks are cached.
+    pub fn from_env() -> Self {
+        let read_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        Self::new(
+            read_u64("CUBE_BITCOIN_RPC_CACHE_BLOCKS")
+                .map(|max_blocks| max_blocks as usize)
+                .unwrap_or(DEFAULT_CACHE_BLOCKS),
+            read_u64("CUBE_BITCOIN_RPC_CACHE_HEADERS")
+                .map(|max_headers| max_headers as usize)
+                .unwrap_or(DEFAULT_CACHE_HEADERS),
+            read_u64("CUBE_BITCOIN_RPC_CACHE_TXS")
+                .map(|max_transactions| max_transactions as usize)
+                .unwrap_or(DEFAULT_CACHE_TRANSACTIONS),
+            read_u64("CUBE_BITCOIN_RPC_CACHE_CONFIRMATIONS").unwrap_or(DEFAULT_CACHE_CONFIRMATIONS),
+        )
+    }
+
+    /// Records the height of the chain tip.
+    pub fn observe_tip(&mut self, tip_height: u64) {
+        self.tip_height = Some(tip_height);
+    }
+
+    /// Records the height of a block hash.
+    pub fn observe_block_height(&mut self, block_hash: BlockHash, height: u64) {
+        self.block_heights.insert(block_hash, height);
+    }
+
+    /// Whether the block is buried under enough confirmations to be cached.
+    pub fn is_buried(&mut self, block_hash: &BlockHash) -> bool {
+        match (self.tip_height, self.block_heights.get(block_hash)) {
+            (Some(tip_height), Some(height)) => {
+                tip_height >= height && tip_height - height + 1 >= self.min_confirmations
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the cached block with the given hash.
+    pub fn get_block(&mut self, block_hash: &BlockHash) -> Option<Block> {
+        let block = self.blocks.get(block_hash);
+        self.record_lookup(block.is_some());
+        block
+    }
+
+    /// Caches a block, if buried.
+    pub fn insert_block(&mut self, block: &Block) {
+        let block_hash = block.block_hash();
+        if self.is_buried(&block_hash) {
+            self.blocks.insert(block_hash, block.clone());
+        }
+    }
+
+    /// Returns the cached header of the block with the given hash.
+    pub fn get_header(&mut self, block_hash: &BlockHash) -> Option<Header> {
+        let header = self.headers.get(block_hash);
+        self.record_lookup(header.is_some());
+        header
+    }
+
+    /// Caches a block header, if its block is buried.
+    pub fn insert_header(&mut self, header: &Header) {
+        let block_hash = header.block_hash();
+        if self.is_buried(&block_hash) {
+            self.headers.insert(block_hash, *header);
+        }
+    }
+
+    /// Returns the cached transaction with the given txid.
+    pub fn get_transaction(&mut self, txid: &Txid) -> Option<Transaction> {
+        let transaction = self.transactions.get(txid);
+        self.record_lookup(transaction.is_some());
+        transaction
+    }
+
+    /// Caches a transaction.
+    pub fn insert_transaction(&mut self, transaction: &Transaction) {
+        self.transactions
+            .insert(transaction.compute_txid(), transaction.clone());
+    }
+
+    /// Records a cache hit or miss.
+    fn record_lookup(&mut self, hit: bool) {
+        match hit {
+            true => self.hits += 1,
+            false => self.misses += 1,
+        }
+    }
+
+    /// Returns the cache metrics as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "min_confirmations".to_string(),
+            Value::Number(self.min_confirmations.into()),
+        );
+        obj.insert(
+            "blocks".to_string(),
+            Value::Number(self.blocks.len().into()),
+        );
+        obj.insert(
+            "headers".to_string(),
+            Value::Number(self.headers.len().into()),
+        );
+        obj.insert(
+            "transactions".to_string(),
+            Value::Number(self.transactions.len().into()),
+        );
+        obj.insert("hits".to_string(), Value::Number(self.hits.into()));
+        obj.insert("misses".to_string(), Value::Number(self.misses.into()));
+        Value::Object(obj)
+    }
+}
+
+/// Returns the process-wide response cache.
+pub fn rpc_cache() -> &'static Mutex<RpcCache> {
+    RPC_CACHE.get_or_init(|| Mutex::new(RpcCache::from_env()))
+}
@@ -0,0 +1,169 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCTransportError;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::net::{Shutdown, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Process-wide registry of in-flight calls.
+static IN_FLIGHT_CALLS: OnceLock<Mutex<InFlightCalls>> = OnceLock::new();
+
+/// Registry of the Bitcoin RPC calls waiting on the Bitcoin node, so that they can be cancelled.
+///
+/// Cancelling a call shuts its connection down, which wakes up the thread blocked on it; the call
+/// then fails with a cancelled error, which is not retried. Once shutting down, new calls are
+/// cancelled before they are sent.
+pub struct InFlightCalls {
+    // Connections and cancellation flags of the in-flight calls, by call id.
+    calls: HashMap<u64, (TcpStream, Arc<AtomicBool>)>,
+
+    // Id of the next call.
+    next_call_id: u64,
+
+    // Whether new calls are refused.
+    shutting_down: bool,
+
+    // Number of calls cancelled.
+    cancelled_calls: u64,
+}
+
+impl InFlightCalls {
+    /// Constructs an empty registry.
+    pub fn new() -> Self {
+        Self {
+            calls: HashMap::new(),
+            next_call_id: 0,
+            shutting_down: false,
+            cancelled_calls: 0,
+        }
+    }
+
+    /// Cancels all in-flight calls, returning how many were cancelled.
+    pub fn cancel_all(&mut self) -> usize {
+        let cancelled = self.calls.len();
+        for (stream, cancelled_flag) in self.calls.values() {
+            cancelled_flag.store(true, Ordering::SeqCst);
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        self.calls.clear();
+        self.cancelled_calls += cancelled as u64;
+        cancelled
+    }
+
+    /// Returns the number of in-flight calls.
+    pub fn in_flight(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns the number of calls cancelled.
+    pub fn cancelled_calls(&self) -> u64 {
+        self.cancelled_calls
+    }
+
+    /// Whether new calls are refused.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down
+    }
+
+    /// Returns the in-flight call metrics as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "in_flight_calls".to_string(),
+            Value::Number(self.in_flight().into()),
+        );
+        obj.insert(
+            "cancelled_calls".to_string(),
+            Value::Number(self.cancelled_calls.into()),
+        );
+        obj.insert("shutting_down".to_string(), Value::Bool(self.shutting_down));
+        Value::Object(obj)
+    }
+}
+
+impl Default for InFlightCalls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An in-flight call, removed from the registry when dropped.
+pub struct InFlightCall {
+    // Id of the call.
+    call_id: u64,
+
+    // Whether the call was cancelled.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl InFlightCall {
+    /// Registers a call made over the given connection.
+    ///
+    /// Fails with a cancelled error if shutting down.
+    pub fn begin(stream: &TcpStream) -> Result<Self, BitcoinRPCTransportError> {
+        let stream = stream
+            .try_clone()
+            .map_err(BitcoinRPCTransportError::SocketErr)?;
+
+        let mut calls = in_flight_calls()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if calls.shutting_down {
+            return Err(BitcoinRPCTransportError::Cancelled);
+        }
+
+        let call_id = calls.next_call_id;
+        calls.next_call_id += 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        calls.calls.insert(call_id, (stream, cancelled.clone()));
+
+        Ok(Self { call_id, cancelled })
+    }
+
+    /// Whether the call was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        in_flight_calls()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .calls
+            .remove(&self.call_id);
+    }
+}
+
+/// Returns the process-wide registry of in-flight calls.
+pub fn in_flight_calls() -> &'static Mutex<InFlightCalls> {
+    IN_FLIGHT_CALLS.get_or_init(|| Mutex::new(InFlightCalls::new()))
+}
+
+/// Cancels the in-flight Bitcoin RPC calls, returning how many were cancelled. Later calls go
+/// through as usual.
+pub fn cancel_rpc_calls() -> usize {
+    in_flight_calls()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .cancel_all()
+}
+
+/// Cancels the in-flight Bitcoin RPC calls and refuses new ones, so that shutting down does not
+/// wait on a hung Bitcoin node. Returns how many calls were cancelled.
+pub fn shutdown_rpc_calls() -> usize {
+    let mut calls = in_flight_calls()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    calls.shutting_down = true;
+    calls.cancel_all()
+}
+
+/// Whether new Bitcoin RPC calls are refused.
+pub fn rpc_shutting_down() -> bool {
+    in_flight_calls()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .is_shutting_down()
+}
@@ -1,32 +1,215 @@
+use bitcoincore_rpc::jsonrpc;
 use std::fmt;
+use std::path::PathBuf;
+
+/// Bitcoin Core RPC error code returned while the node is still starting up.
+const RPC_IN_WARMUP: i32 = -28;
+
+/// Bitcoin Core RPC error code returned for transactions already confirmed.
+const RPC_VERIFY_ALREADY_IN_CHAIN: i32 = -27;
+
+/// JSON-RPC error code returned for methods the node does not know.
+const RPC_METHOD_NOT_FOUND: i32 = -32601;
+
+/// Class of a failed Bitcoin RPC call, so that callers can tell failures apart without inspecting
+/// error messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinRPCError {
+    /// The Bitcoin node rejected the credentials.
+    Auth,
+    /// The Bitcoin node could not be reached, or the connection failed midway.
+    Network(String),
+    /// The Bitcoin node does not know the called method.
+    MethodNotFound(String),
+    /// The Bitcoin node is still starting up.
+    WarmingUp(String),
+    /// The Bitcoin node has more requests queued than it accepts.
+    WorkQueueFull,
+    /// The response of the Bitcoin node could not be parsed.
+    Parse(String),
+    /// The Bitcoin node rejected the call.
+    Rpc { code: i32, message: String },
+    /// The call was cancelled before the Bitcoin node responded.
+    Cancelled,
+    /// The call failed otherwise, such as a misconfigured transport.
+    Other(String),
+}
 
 #[derive(Debug)]
 pub enum BitcoinRPCValidateRPCError {
     WrongChain,
     NotSynced,
-    RPCErr(bitcoincore_rpc::Error),
+    RPCErr(BitcoinRPCError),
 }
 
 #[derive(Debug)]
 pub enum BitcoinRPCGetChainTipError {
-    RPCErr(bitcoincore_rpc::Error),
+    RPCErr(BitcoinRPCError),
 }
 
 #[derive(Debug)]
 pub enum BitcoinRPCGetMempoolFeeRateError {
-    RPCErr(bitcoincore_rpc::Error),
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCEstimateSmartFeeError {
+    RPCErr(BitcoinRPCError),
 }
 
 #[derive(Debug)]
 pub enum BitcoinRPCRetrieveBlockError {
-    RPCErr(bitcoincore_rpc::Error),
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCRetrieveBlockHeaderError {
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCRetrieveBlockFilterError {
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCRetrieveMempoolError {
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCRetrieveTxOutError {
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCWaitForNewBlockError {
+    RPCErr(BitcoinRPCError),
 }
 
 #[derive(Debug)]
 pub enum BitcoinRPCBroadcastRawTransactionError {
     HexErr(hex::FromHexError),
     DecodeErr(bitcoin::consensus::encode::Error),
-    RPCErr(bitcoincore_rpc::Error),
+    RPCErr(BitcoinRPCError),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCTlsError {
+    ReadFileErr(PathBuf, std::io::Error),
+    InvalidCACertErr(native_tls::Error),
+    InvalidClientIdentityErr(native_tls::Error),
+    IncompleteClientIdentity,
+    ConnectorErr(native_tls::Error),
+}
+
+#[derive(Debug)]
+pub enum BitcoinRPCTransportError {
+    InvalidURL(String),
+    TlsErr(BitcoinRPCTlsError),
+    SocketErr(std::io::Error),
+    TlsHandshakeErr(native_tls::Error),
+    MalformedResponse(String),
+    HttpStatus(u16),
+    JsonErr(serde_json::Error),
+    Cancelled,
+}
+
+impl BitcoinRPCError {
+    /// Whether the failure is likely to go away on its own, such as the Bitcoin node restarting.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            BitcoinRPCError::Network(_)
+                | BitcoinRPCError::WarmingUp(_)
+                | BitcoinRPCError::WorkQueueFull
+        )
+    }
+
+    /// Whether the call was rejected because the transaction is already confirmed.
+    pub fn is_already_in_chain(&self) -> bool {
+        matches!(self, BitcoinRPCError::Rpc { code, .. } if *code == RPC_VERIFY_ALREADY_IN_CHAIN)
+    }
+}
+
+impl From<&bitcoincore_rpc::Error> for BitcoinRPCError {
+    fn from(err: &bitcoincore_rpc::Error) -> Self {
+        match err {
+            bitcoincore_rpc::Error::Io(err) => BitcoinRPCError::Network(err.to_string()),
+            bitcoincore_rpc::Error::InvalidCookieFile => BitcoinRPCError::Auth,
+            bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(transport_err)) => {
+                match transport_err.downcast_ref::<BitcoinRPCTransportError>() {
+                    Some(BitcoinRPCTransportError::HttpStatus(status_code)) => match status_code {
+                        401 | 403 => BitcoinRPCError::Auth,
+                        // Bitcoin Core answers with a service unavailable status once its work
+                        // queue is full.
+                        503 => BitcoinRPCError::WorkQueueFull,
+                        408 | 429 | 500..=599 => BitcoinRPCError::Network(format!(
+                            "Unexpected HTTP status {}",
+                            status_code
+                        )),
+                        _ => BitcoinRPCError::Other(format!(
+                            "Unexpected HTTP status {}",
+                            status_code
+                        )),
+                    },
+                    Some(
+                        transport_err @ (BitcoinRPCTransportError::SocketErr(_)
+                        | BitcoinRPCTransportError::TlsHandshakeErr(_)),
+                    ) => BitcoinRPCError::Network(transport_err.to_string()),
+                    Some(
+                        transport_err @ (BitcoinRPCTransportError::MalformedResponse(_)
+                        | BitcoinRPCTransportError::JsonErr(_)),
+                    ) => BitcoinRPCError::Parse(transport_err.to_string()),
+                    Some(BitcoinRPCTransportError::Cancelled) => BitcoinRPCError::Cancelled,
+                    Some(transport_err) => BitcoinRPCError::Other(transport_err.to_string()),
+                    // Unknown transports fail on the connection itself.
+                    None => BitcoinRPCError::Network(transport_err.to_string()),
+                }
+            }
+            bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Rpc(rpc_err)) => match rpc_err.code {
+                RPC_IN_WARMUP => BitcoinRPCError::WarmingUp(rpc_err.message.clone()),
+                RPC_METHOD_NOT_FOUND => BitcoinRPCError::MethodNotFound(rpc_err.message.clone()),
+                code => BitcoinRPCError::Rpc {
+                    code,
+                    message: rpc_err.message.clone(),
+                },
+            },
+            bitcoincore_rpc::Error::JsonRpc(jsonrpc_err) => {
+                BitcoinRPCError::Parse(jsonrpc_err.to_string())
+            }
+            bitcoincore_rpc::Error::Json(_)
+            | bitcoincore_rpc::Error::Hex(_)
+            | bitcoincore_rpc::Error::BitcoinSerialization(_)
+            | bitcoincore_rpc::Error::InvalidAmount(_)
+            | bitcoincore_rpc::Error::UnexpectedStructure => {
+                BitcoinRPCError::Parse(err.to_string())
+            }
+            _ => BitcoinRPCError::Other(err.to_string()),
+        }
+    }
+}
+
+impl From<bitcoincore_rpc::Error> for BitcoinRPCError {
+    fn from(err: bitcoincore_rpc::Error) -> Self {
+        BitcoinRPCError::from(&err)
+    }
+}
+
+impl fmt::Display for BitcoinRPCError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCError::Auth => write!(f, "Authentication failed."),
+            BitcoinRPCError::Network(reason) => write!(f, "Network error: {}", reason),
+            BitcoinRPCError::MethodNotFound(message) => write!(f, "Method not found: {}", message),
+            BitcoinRPCError::WarmingUp(message) => write!(f, "Node is warming up: {}", message),
+            BitcoinRPCError::WorkQueueFull => write!(f, "Node work queue is full."),
+            BitcoinRPCError::Parse(reason) => write!(f, "Parse error: {}", reason),
+            BitcoinRPCError::Rpc { code, message } => write!(f, "RPC error {}: {}", code, message),
+            BitcoinRPCError::Cancelled => write!(f, "RPC call cancelled."),
+            BitcoinRPCError::Other(reason) => write!(f, "{}", reason),
+        }
+    }
 }
 
 impl fmt::Display for BitcoinRPCValidateRPCError {
@@ -55,6 +238,14 @@ impl fmt::Display for BitcoinRPCGetMempoolFeeRateError {
     }
 }
 
+impl fmt::Display for BitcoinRPCEstimateSmartFeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCEstimateSmartFeeError::RPCErr(err) => write!(f, "RPC error: {}", err),
+        }
+    }
+}
+
 impl fmt::Display for BitcoinRPCRetrieveBlockError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -63,6 +254,46 @@ impl fmt::Display for BitcoinRPCRetrieveBlockError {
     }
 }
 
+impl fmt::Display for BitcoinRPCRetrieveBlockHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCRetrieveBlockHeaderError::RPCErr(err) => write!(f, "RPC error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinRPCRetrieveBlockFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCRetrieveBlockFilterError::RPCErr(err) => write!(f, "RPC error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinRPCRetrieveMempoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCRetrieveMempoolError::RPCErr(err) => write!(f, "RPC error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinRPCRetrieveTxOutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCRetrieveTxOutError::RPCErr(err) => write!(f, "RPC error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinRPCWaitForNewBlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCWaitForNewBlockError::RPCErr(err) => write!(f, "RPC error: {}", err),
+        }
+    }
+}
+
 impl fmt::Display for BitcoinRPCBroadcastRawTransactionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -76,3 +307,45 @@ impl fmt::Display for BitcoinRPCBroadcastRawTransactionError {
         }
     }
 }
+
+impl fmt::Display for BitcoinRPCTlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCTlsError::ReadFileErr(path, err) => {
+                write!(f, "Cannot read {}: {}", path.display(), err)
+            }
+            BitcoinRPCTlsError::InvalidCACertErr(err) => write!(f, "Invalid CA certificate: {}", err),
+            BitcoinRPCTlsError::InvalidClientIdentityErr(err) => {
+                write!(f, "Invalid client certificate or key: {}", err)
+            }
+            BitcoinRPCTlsError::IncompleteClientIdentity => write!(
+                f,
+                "A client certificate needs both CUBE_BITCOIN_RPC_TLS_CERT and CUBE_BITCOIN_RPC_TLS_KEY."
+            ),
+            BitcoinRPCTlsError::ConnectorErr(err) => write!(f, "TLS setup error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinRPCTransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitcoinRPCTransportError::InvalidURL(url) => write!(f, "Invalid RPC URL: {}", url),
+            BitcoinRPCTransportError::TlsErr(err) => write!(f, "{}", err),
+            BitcoinRPCTransportError::SocketErr(err) => write!(f, "Socket error: {}", err),
+            BitcoinRPCTransportError::TlsHandshakeErr(err) => {
+                write!(f, "TLS handshake error: {}", err)
+            }
+            BitcoinRPCTransportError::MalformedResponse(reason) => {
+                write!(f, "Malformed HTTP response: {}", reason)
+            }
+            BitcoinRPCTransportError::HttpStatus(status_code) => {
+                write!(f, "Unexpected HTTP status {}", status_code)
+            }
+            BitcoinRPCTransportError::JsonErr(err) => write!(f, "JSON error: {}", err),
+            BitcoinRPCTransportError::Cancelled => write!(f, "RPC call cancelled."),
+        }
+    }
+}
+
+impl std::error::Error for BitcoinRPCTransportError {}
@@ -0,0 +1,169 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_transport::BitcoinRPCStream;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, VecDeque};
+use std::io::BufReader;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default number of idle connections kept per Bitcoin node.
+const DEFAULT_POOL_SIZE: usize = 8;
+
+/// Default number of seconds an idle connection is kept for.
+///
+/// Kept below the 30 seconds bitcoind waits before closing an idle connection by default.
+const DEFAULT_POOL_IDLE_SECS: u64 = 15;
+
+/// Process-wide connection pool, read from the environment on first use.
+static CONNECTION_POOL: OnceLock<Mutex<ConnectionPool>> = OnceLock::new();
+
+/// A kept-alive connection to a Bitcoin node.
+pub type PooledConnection = BufReader<BitcoinRPCStream>;
+
+/// Bounded pool of kept-alive connections to the Bitcoin nodes, shared by all RPC calls.
+///
+/// Connections are taken out for a call and put back once its response is read, if the node keeps
+/// the connection alive. At most `max_idle_per_target` connections are kept per node, the most recently
+/// used first, and connections idle for longer than `idle_timeout` are closed.
+pub struct ConnectionPool {
+    // Idle connections by target, the most recently used last, along with when they were put back.
+    idle: HashMap<String, VecDeque<(PooledConnection, Instant)>>,
+
+    // Maximum number of idle connections kept per target; `0` disables reuse.
+    max_idle_per_target: usize,
+
+    // Time after which an idle connection is closed.
+    idle_timeout: Duration,
+
+    // Number of connections opened.
+    connections_opened: u64,
+
+    // Number of idle connections taken out of the pool for reuse.
+    connections_reused: u64,
+
+    // Number of reused connections found closed by the Bitcoin node.
+    stale_connections: u64,
+}
+
+impl ConnectionPool {
+    /// Constructs an empty pool.
+    pub fn new(max_idle_per_target: usize, idle_timeout: Duration) -> Self {
+        Self {
+            idle: HashMap::new(),
+            max_idle_per_target,
+            idle_timeout,
+            connections_opened: 0,
+            connections_reused: 0,
+            stale_connections: 0,
+        }
+    }
+
+    /// Constructs an empty pool from the environment.
+    ///
+    /// `CUBE_BITCOIN_RPC_POOL_SIZE` and `CUBE_BITCOIN_RPC_POOL_IDLE_SECS` optionally override the
+    /// default number of idle connections kept per node and how long they are kept for. Setting the
+    /// pool size to `0` opens a new connection for every call.
+    pub fn from_env() -> Self {
+        let read_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        Self::new(
+            read_u64("CUBE_BITCOIN_RPC_POOL_SIZE")
+                .map(|pool_size| pool_size as usize)
+                .unwrap_or(DEFAULT_POOL_SIZE),
+            Duration::from_secs(
+                read_u64("CUBE_BITCOIN_RPC_POOL_IDLE_SECS").unwrap_or(DEFAULT_POOL_IDLE_SECS),
+            ),
+        )
+    }
+
+    /// Takes the most recently used idle connection to the target out of the pool, closing the
+    /// expired ones.
+    pub fn checkout(&mut self, target: &str, now: Instant) -> Option<PooledConnection> {
+        let connections = self.idle.get_mut(target)?;
+
+        // 1 Close the expired connections, the least recently used first.
+        while let Some((_, idle_since)) = connections.front() {
+            if now.saturating_duration_since(*idle_since) < self.idle_timeout {
+                break;
+            }
+            connections.pop_front();
+        }
+
+        // 2 Take the most recently used connection.
+        let connection = connections.pop_back().map(|(connection, _)| connection);
+        if connection.is_some() {
+            self.connections_reused += 1;
+        }
+        connection
+    }
+
+    /// Puts a connection to the target back into the pool, closing the least recently used one if the
+    /// pool is full.
+    pub fn checkin(&mut self, target: &str, connection: PooledConnection, now: Instant) {
+        if self.max_idle_per_target == 0 {
+            return;
+        }
+
+        let connections = self.idle.entry(target.to_string()).or_default();
+        connections.push_back((connection, now));
+        while connections.len() > self.max_idle_per_target {
+            connections.pop_front();
+        }
+    }
+
+    /// Records a newly opened connection.
+    pub fn record_opened(&mut self) {
+        self.connections_opened += 1;
+    }
+
+    /// Records a reused connection found closed by the Bitcoin node.
+    pub fn record_stale(&mut self) {
+        self.stale_connections += 1;
+    }
+
+    /// Returns the number of idle connections across all targets.
+    pub fn idle_connections(&self) -> usize {
+        self.idle
+            .values()
+            .map(|connections| connections.len())
+            .sum()
+    }
+
+    /// Returns the pool metrics as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "max_idle_per_target".to_string(),
+            Value::Number(self.max_idle_per_target.into()),
+        );
+        obj.insert(
+            "idle_timeout_secs".to_string(),
+            Value::Number(self.idle_timeout.as_secs().into()),
+        );
+        obj.insert(
+            "idle_connections".to_string(),
+            Value::Number(self.idle_connections().into()),
+        );
+        obj.insert(
+            "connections_opened".to_string(),
+            Value::Number(self.connections_opened.into()),
+        );
+        obj.insert(
+            "connections_reused".to_string(),
+            Value::Number(self.connections_reused.into()),
+        );
+        obj.insert(
+            "stale_connections".to_string(),
+            Value::Number(self.stale_connections.into()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Returns the process-wide connection pool.
+pub fn connection_pool() -> &'static Mutex<ConnectionPool> {
+    CONNECTION_POOL.get_or_init(|| Mutex::new(ConnectionPool::from_env()))
+}
@@ -0,0 +1,166 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cancel::rpc_shutting_down;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCError;
+use rand::Rng;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Default maximum number of attempts of a Bitcoin RPC call.
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Default delay before the first retry in milliseconds.
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 250;
+
+/// Default cap on the delay between two attempts in milliseconds.
+const DEFAULT_MAX_BACKOFF_MS: u64 = 10_000;
+
+/// Default jitter applied to each delay, in percent of the delay.
+const DEFAULT_JITTER_PERCENT: u64 = 20;
+
+/// Process-wide retry policy, read from the environment on first use.
+static RETRY_POLICY: OnceLock<RetryPolicy> = OnceLock::new();
+
+/// Policy for retrying Bitcoin RPC calls that fail with a transient error.
+///
+/// The delay before each retry doubles from the initial backoff up to the cap, and is spread by a
+/// random jitter so that concurrent callers do not hammer a restarting node in lockstep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    // Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+
+    // Delay before the first retry.
+    pub initial_backoff: Duration,
+
+    // Cap on the delay between two attempts.
+    pub max_backoff: Duration,
+
+    // Jitter applied to each delay, as a fraction of the delay.
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// Constructs a retry policy.
+    pub fn new(
+        max_attempts: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        jitter: f64,
+    ) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            max_backoff: max_backoff.max(initial_backoff),
+            jitter: jitter.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Constructs a retry policy from the environment.
+    ///
+    /// `CUBE_BITCOIN_RPC_MAX_ATTEMPTS`, `CUBE_BITCOIN_RPC_BACKOFF_MS`, `CUBE_BITCOIN_RPC_MAX_BACKOFF_MS`
+    /// and `CUBE_BITCOIN_RPC_JITTER_PERCENT` optionally override the defaults. Setting the maximum number
+    /// of attempts to `1` disables retries.
+    pub fn from_env() -> Self {
+        let read_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        Self::new(
+            read_u64("CUBE_BITCOIN_RPC_MAX_ATTEMPTS")
+                .map(|max_attempts| max_attempts.min(u32::MAX as u64) as u32)
+                .unwrap_or(DEFAULT_MAX_ATTEMPTS),
+            Duration::from_millis(
+                read_u64("CUBE_BITCOIN_RPC_BACKOFF_MS").unwrap_or(DEFAULT_INITIAL_BACKOFF_MS),
+            ),
+            Duration::from_millis(
+                read_u64("CUBE_BITCOIN_RPC_MAX_BACKOFF_MS").unwrap_or(DEFAULT_MAX_BACKOFF_MS),
+            ),
+            read_u64("CUBE_BITCOIN_RPC_JITTER_PERCENT").unwrap_or(DEFAULT_JITTER_PERCENT) as f64
+                / 100.0,
+        )
+    }
+
+    /// Returns the delay before the retry following the given failed attempt, counted from `1`.
+    ///
+    /// `jitter_sample` is in `[-1, 1]` and scales the jitter applied to the delay.
+    pub fn backoff(&self, failed_attempt: u32, jitter_sample: f64) -> Duration {
+        // 1 Double the initial backoff for each failed attempt, up to the cap.
+        let exponent = failed_attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_backoff);
+
+        // 2 Spread the delay by the jitter.
+        let factor = 1.0 + self.jitter * jitter_sample.clamp(-1.0, 1.0);
+        backoff.mul_f64(factor.max(0.0))
+    }
+}
+
+/// Returns the retry policy.
+pub fn retry_policy() -> &'static RetryPolicy {
+    RETRY_POLICY.get_or_init(RetryPolicy::from_env)
+}
+
+/// Whether the error is likely to go away on its own, such as the Bitcoin node restarting.
+///
+/// Connection failures, HTTP server errors, a full work queue and the node warming up are transient.
+/// Rejected credentials, TLS failures, RPC errors and malformed responses are permanent, as retrying
+/// would fail the same way.
+pub fn is_transient(err: &bitcoincore_rpc::Error) -> bool {
+    BitcoinRPCError::from(err).is_transient()
+}
+
+/// Whether the error is the Bitcoin node not knowing the called method, such as an older node or one
+/// with the method disabled.
+pub fn is_method_not_found(err: &bitcoincore_rpc::Error) -> bool {
+    matches!(
+        BitcoinRPCError::from(err),
+        BitcoinRPCError::MethodNotFound(_)
+    )
+}
+
+/// Whether an HTTP status code returned by the Bitcoin node is worth retrying.
+pub fn is_transient_status(status_code: i32) -> bool {
+    status_code == 408 || status_code == 429 || (500..600).contains(&status_code)
+}
+
+/// Runs a Bitcoin RPC call, retrying it with the process-wide policy while it fails with a transient error.
+///
+/// Blocks the calling thread between attempts.
+pub fn with_retry<T, F>(call: F) -> Result<T, bitcoincore_rpc::Error>
+where
+    F: FnMut() -> Result<T, bitcoincore_rpc::Error>,
+{
+    with_retry_policy(retry_policy(), call)
+}
+
+/// Runs a Bitcoin RPC call, retrying it with the given policy while it fails with a transient error.
+///
+/// Blocks the calling thread between attempts.
+pub fn with_retry_policy<T, F>(
+    policy: &RetryPolicy,
+    mut call: F,
+) -> Result<T, bitcoincore_rpc::Error>
+where
+    F: FnMut() -> Result<T, bitcoincore_rpc::Error>,
+{
+    let mut attempt: u32 = 1;
+    loop {
+        match call() {
+            Ok(result) => return Ok(result),
+            Err(err) => {
+                // Give up on permanent errors, once out of attempts, and while shutting down.
+                if !is_transient(&err) || attempt >= policy.max_attempts || rpc_shutting_down() {
+                    return Err(err);
+                }
+
+                // Wait before the next attempt.
+                let jitter_sample = rand::thread_rng().gen_range(-1.0..=1.0);
+                std::thread::sleep(policy.backoff(attempt, jitter_sample));
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Default timeout of a Bitcoin RPC call in seconds.
+const DEFAULT_TIMEOUT_SECS: u64 = 15;
+
+/// Process-wide call timeouts, read from the environment on first use.
+static CALL_TIMEOUTS: OnceLock<CallTimeouts> = OnceLock::new();
+
+/// Timeouts of Bitcoin RPC calls, with optional overrides per RPC method.
+///
+/// A call fails with a timed out socket error once its timeout elapses, from sending the request to
+/// reading the last byte of the response, and is retried like any other transient failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallTimeouts {
+    // Timeout of the calls to methods without an override.
+    pub default_timeout: Duration,
+
+    // Timeouts by RPC method.
+    pub method_timeouts: HashMap<String, Duration>,
+}
+
+impl CallTimeouts {
+    /// Constructs the call timeouts.
+    pub fn new(default_timeout: Duration, method_timeouts: HashMap<String, Duration>) -> Self {
+        Self {
+            default_timeout,
+            method_timeouts,
+        }
+    }
+
+    /// Constructs the call timeouts from the environment.
+    ///
+    /// `CUBE_BITCOIN_RPC_TIMEOUT_SECS` optionally overrides the default timeout, and
+    /// `CUBE_BITCOIN_RPC_METHOD_TIMEOUTS` the timeouts of given methods, as a comma-separated list of
+    /// `<method>=<secs>` pairs such as `getblock=60,getrawmempool=30`.
+    pub fn from_env() -> Self {
+        let default_timeout = std::env::var("CUBE_BITCOIN_RPC_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let method_timeouts = std::env::var("CUBE_BITCOIN_RPC_METHOD_TIMEOUTS")
+            .map(|value| Self::parse_method_timeouts(&value))
+            .unwrap_or_default();
+
+        Self::new(Duration::from_secs(default_timeout), method_timeouts)
+    }
+
+    /// Parses a comma-separated list of `<method>=<secs>` pairs, skipping the malformed ones.
+    pub fn parse_method_timeouts(value: &str) -> HashMap<String, Duration> {
+        value
+            .split(',')
+            .filter_map(|pair| {
+                let (method, secs) = pair.split_once('=')?;
+                let secs = secs.trim().parse::<u64>().ok()?;
+                match method.trim() {
+                    "" => None,
+                    method => Some((method.to_string(), Duration::from_secs(secs))),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the timeout of a call to the given method.
+    ///
+    /// A zero timeout would make every call fail at once, so it is raised to a second.
+    pub fn timeout_for(&self, method: &str) -> Duration {
+        self.method_timeouts
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_timeout)
+            .max(Duration::from_secs(1))
+    }
+
+    /// Returns the timeout of a batch of calls, the longest timeout among its methods.
+    pub fn batch_timeout_for<'a, I>(&self, methods: I) -> Duration
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        methods
+            .into_iter()
+            .map(|method| self.timeout_for(method))
+            .max()
+            .unwrap_or_else(|| self.timeout_for(""))
+    }
+}
+
+/// Returns the call timeouts.
+pub fn call_timeouts() -> &'static CallTimeouts {
+    CALL_TIMEOUTS.get_or_init(CallTimeouts::from_env)
+}
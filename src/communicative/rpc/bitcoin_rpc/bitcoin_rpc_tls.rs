@@ -0,0 +1,92 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCTlsError;
+use native_tls::{Certificate, Identity, TlsConnector};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Process-wide TLS connector, built from the environment on first successful use.
+static TLS_CONNECTOR: OnceLock<TlsConnector> = OnceLock::new();
+
+/// TLS configuration of `https://` Bitcoin RPC connections.
+///
+/// Server certificates are verified against the system roots, plus the custom CA certificate if one
+/// is given. A client certificate is presented when the server asks for one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitcoinRPCTlsConfig {
+    // PEM file of an additional CA certificate to trust.
+    pub ca_cert_path: Option<PathBuf>,
+
+    // PEM file of the client certificate.
+    pub client_cert_path: Option<PathBuf>,
+
+    // PEM file of the PKCS#8 private key of the client certificate.
+    pub client_key_path: Option<PathBuf>,
+}
+
+impl BitcoinRPCTlsConfig {
+    /// Reads the TLS configuration from the environment.
+    ///
+    /// `CUBE_BITCOIN_RPC_TLS_CA` sets an additional CA certificate to trust, and
+    /// `CUBE_BITCOIN_RPC_TLS_CERT` together with `CUBE_BITCOIN_RPC_TLS_KEY` set the client certificate.
+    pub fn from_env() -> Self {
+        let read_path = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+
+        Self {
+            ca_cert_path: read_path("CUBE_BITCOIN_RPC_TLS_CA"),
+            client_cert_path: read_path("CUBE_BITCOIN_RPC_TLS_CERT"),
+            client_key_path: read_path("CUBE_BITCOIN_RPC_TLS_KEY"),
+        }
+    }
+
+    /// Builds a TLS connector from the configuration.
+    pub fn connector(&self) -> Result<TlsConnector, BitcoinRPCTlsError> {
+        let mut builder = TlsConnector::builder();
+
+        // 1 Trust the custom CA certificate, if any.
+        if let Some(ca_cert_path) = &self.ca_cert_path {
+            let ca_cert_pem = read_file(ca_cert_path)?;
+            let ca_cert = Certificate::from_pem(&ca_cert_pem)
+                .map_err(BitcoinRPCTlsError::InvalidCACertErr)?;
+            builder.add_root_certificate(ca_cert);
+        }
+
+        // 2 Present the client certificate, if any.
+        match (&self.client_cert_path, &self.client_key_path) {
+            (Some(client_cert_path), Some(client_key_path)) => {
+                let client_cert_pem = read_file(client_cert_path)?;
+                let client_key_pem = read_file(client_key_path)?;
+                let identity = Identity::from_pkcs8(&client_cert_pem, &client_key_pem)
+                    .map_err(BitcoinRPCTlsError::InvalidClientIdentityErr)?;
+                builder.identity(identity);
+            }
+            (None, None) => {}
+            _ => return Err(BitcoinRPCTlsError::IncompleteClientIdentity),
+        }
+
+        // 3 Build the connector.
+        builder.build().map_err(BitcoinRPCTlsError::ConnectorErr)
+    }
+}
+
+/// Returns the process-wide TLS connector.
+///
+/// The connector is built again on every call until the configuration is valid, so that a fixed
+/// certificate file is picked up without a restart.
+pub fn tls_connector() -> Result<TlsConnector, BitcoinRPCTlsError> {
+    if let Some(connector) = TLS_CONNECTOR.get() {
+        return Ok(connector.clone());
+    }
+
+    let connector = BitcoinRPCTlsConfig::from_env().connector()?;
+    Ok(TLS_CONNECTOR.get_or_init(|| connector).clone())
+}
+
+/// Reads a certificate or key file.
+fn read_file(path: &Path) -> Result<Vec<u8>, BitcoinRPCTlsError> {
+    std::fs::read(path).map_err(|err| BitcoinRPCTlsError::ReadFileErr(path.to_path_buf(), err))
+}
@@ -0,0 +1,576 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cancel::{rpc_shutting_down, InFlightCall};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCTransportError;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_pool::{connection_pool, PooledConnection};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_timeout::call_timeouts;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_tls::tls_connector;
+use bitcoincore_rpc::jsonrpc::{self, base64, Request, Response, Transport};
+use bitcoincore_rpc::Client;
+use native_tls::{HandshakeError, TlsConnector, TlsStream};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+/// Default port of plain Bitcoin RPC URLs.
+const DEFAULT_HTTP_PORT: u16 = 8332;
+
+/// Default port of `https://` Bitcoin RPC URLs.
+const DEFAULT_HTTPS_PORT: u16 = 443;
+
+/// Largest response body accepted, in bytes.
+const MAX_RESPONSE_BYTES: usize = 256 * 1024 * 1024;
+
+/// A connection to the Bitcoin node, plain or over TLS.
+pub enum BitcoinRPCStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl BitcoinRPCStream {
+    /// Returns the underlying TCP connection.
+    pub fn tcp_stream(&self) -> &TcpStream {
+        match self {
+            BitcoinRPCStream::Plain(stream) => stream,
+            BitcoinRPCStream::Tls(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl Read for BitcoinRPCStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            BitcoinRPCStream::Plain(stream) => stream.read(buf),
+            BitcoinRPCStream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for BitcoinRPCStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            BitcoinRPCStream::Plain(stream) => stream.write(buf),
+            BitcoinRPCStream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            BitcoinRPCStream::Plain(stream) => stream.flush(),
+            BitcoinRPCStream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// JSON-RPC over HTTP transport to the Bitcoin node, with TLS for `https://` URLs.
+///
+/// URLs without a scheme are plain HTTP, as with the stock Bitcoin RPC client.
+pub struct BitcoinRPCTransport {
+    // Host of the Bitcoin node, without brackets for IPv6 addresses.
+    host: String,
+
+    // Port of the Bitcoin node.
+    port: u16,
+
+    // Request path, such as `/` or `/wallet/<name>`.
+    path: String,
+
+    // Value of the `Authorization` header, if credentials are given.
+    basic_auth: Option<String>,
+
+    // TLS connector, for `https://` URLs.
+    tls: Option<TlsConnector>,
+}
+
+impl BitcoinRPCTransport {
+    /// Constructs a transport to the given URL.
+    pub fn new(url: &str, user: &str, password: &str) -> Result<Self, BitcoinRPCTransportError> {
+        let invalid_url = || BitcoinRPCTransportError::InvalidURL(url.to_string());
+
+        // 1 Split off the scheme.
+        let (is_tls, rest) = match url.trim().split_once("://") {
+            Some(("https", rest)) => (true, rest),
+            Some(("http", rest)) => (false, rest),
+            Some(_) => return Err(invalid_url()),
+            None => (false, url.trim()),
+        };
+
+        // 2 Split off the path.
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+
+        // 3 Split the host and the port; credentials go in the RPC holder, not the URL.
+        if authority.contains('@') {
+            return Err(invalid_url());
+        }
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, after_host) = bracketed.split_once(']').ok_or_else(invalid_url)?;
+                match after_host {
+                    "" => (host, None),
+                    _ => (
+                        host,
+                        Some(after_host.strip_prefix(':').ok_or_else(invalid_url)?),
+                    ),
+                }
+            }
+            None => match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        if host.is_empty() {
+            return Err(invalid_url());
+        }
+        let port = match port {
+            Some(port) => port.parse::<u16>().map_err(|_| invalid_url())?,
+            None => match is_tls {
+                true => DEFAULT_HTTPS_PORT,
+                false => DEFAULT_HTTP_PORT,
+            },
+        };
+
+        // 4 Set up TLS.
+        let tls = match is_tls {
+            true => Some(tls_connector().map_err(BitcoinRPCTransportError::TlsErr)?),
+            false => None,
+        };
+
+        // 5 Encode the credentials.
+        let basic_auth = match user.is_empty() && password.is_empty() {
+            true => None,
+            false => Some(format!(
+                "Basic {}",
+                base64::encode(format!("{}:{}", user, password))
+            )),
+        };
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+            basic_auth,
+            tls,
+        })
+    }
+
+    /// Whether the transport connects over TLS.
+    pub fn is_tls(&self) -> bool {
+        self.tls.is_some()
+    }
+
+    /// Returns the URL the transport connects to, without credentials.
+    pub fn target(&self) -> String {
+        let scheme = match self.is_tls() {
+            true => "https",
+            false => "http",
+        };
+        format!("{}://{}{}", scheme, self.host_port(), self.path)
+    }
+
+    /// Returns the host and the port, as used in the `Host` header.
+    fn host_port(&self) -> String {
+        match self.host.contains(':') {
+            true => format!("[{}]:{}", self.host, self.port),
+            false => format!("{}:{}", self.host, self.port),
+        }
+    }
+
+    /// Opens a connection to the Bitcoin node, giving up on each address after the timeout.
+    fn connect(&self, timeout: Duration) -> Result<PooledConnection, BitcoinRPCTransportError> {
+        // 1 Refuse new connections while shutting down.
+        if rpc_shutting_down() {
+            return Err(BitcoinRPCTransportError::Cancelled);
+        }
+
+        // 2 Connect to the first reachable address.
+        let addrs = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(BitcoinRPCTransportError::SocketErr)?;
+        let mut last_err = io::Error::new(io::ErrorKind::NotFound, "no address resolved");
+        let mut tcp_stream = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => {
+                    tcp_stream = Some(stream);
+                    break;
+                }
+                Err(err) => last_err = err,
+            }
+        }
+        let tcp_stream = tcp_stream.ok_or(BitcoinRPCTransportError::SocketErr(last_err))?;
+        tcp_stream
+            .set_read_timeout(Some(timeout))
+            .and_then(|_| tcp_stream.set_write_timeout(Some(timeout)))
+            .and_then(|_| tcp_stream.set_nodelay(true))
+            .map_err(BitcoinRPCTransportError::SocketErr)?;
+
+        // 3 Wrap the connection in TLS, if needed.
+        let stream = match &self.tls {
+            None => BitcoinRPCStream::Plain(tcp_stream),
+            Some(connector) => match connector.connect(&self.host, tcp_stream) {
+                Ok(tls_stream) => BitcoinRPCStream::Tls(Box::new(tls_stream)),
+                Err(HandshakeError::Failure(err)) => {
+                    return Err(BitcoinRPCTransportError::TlsHandshakeErr(err))
+                }
+                Err(HandshakeError::WouldBlock(_)) => {
+                    return Err(BitcoinRPCTransportError::SocketErr(io::Error::from(
+                        io::ErrorKind::TimedOut,
+                    )))
+                }
+            },
+        };
+
+        // 4 Record the new connection.
+        connection_pool()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .record_opened();
+
+        Ok(BufReader::new(stream))
+    }
+
+    /// Returns the key the connections to the Bitcoin node are pooled under.
+    fn pool_key(&self) -> String {
+        let scheme = match self.is_tls() {
+            true => "https",
+            false => "http",
+        };
+        format!("{}://{}", scheme, self.host_port())
+    }
+
+    /// Posts a JSON-RPC body and parses the JSON response, failing once the timeout elapses.
+    ///
+    /// The call goes over a pooled connection if there is one, and over a new connection otherwise or
+    /// if the pooled one turns out closed by the Bitcoin node. The connection is put back into the pool
+    /// if the node keeps it alive.
+    fn post<R: DeserializeOwned>(
+        &self,
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<R, BitcoinRPCTransportError> {
+        // 1 Build the request.
+        let mut head = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            self.path,
+            self.host_port(),
+            body.len()
+        );
+        if let Some(basic_auth) = &self.basic_auth {
+            head.push_str(&format!("Authorization: {}\r\n", basic_auth));
+        }
+        head.push_str("\r\n");
+        let mut request = head.into_bytes();
+        request.extend_from_slice(body);
+
+        // 2 Make the call over a pooled connection, if any.
+        let pool_key = self.pool_key();
+        let pooled_connection = connection_pool()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .checkout(&pool_key, Instant::now());
+        let exchanged = match pooled_connection {
+            Some(connection) => match exchange(connection, &request, timeout) {
+                Err(BitcoinRPCTransportError::SocketErr(err)) if !is_timeout(&err) => {
+                    // 2.a The Bitcoin node closed the idle connection; start over on a new one.
+                    connection_pool()
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .record_stale();
+                    exchange(self.connect(timeout)?, &request, timeout)
+                }
+                exchanged => exchanged,
+            },
+            None => exchange(self.connect(timeout)?, &request, timeout),
+        };
+        let (response, connection) = exchanged?;
+
+        // 3 Put the connection back into the pool if the Bitcoin node keeps it alive.
+        if response.keep_alive {
+            connection_pool()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .checkin(&pool_key, connection, Instant::now());
+        }
+
+        // 4 The Bitcoin node answers failed calls with an error status and a JSON error object,
+        // so the body is parsed before the status is looked at.
+        match serde_json::from_slice(&response.body) {
+            Ok(parsed) => Ok(parsed),
+            Err(_) if response.status_code != 200 => {
+                Err(BitcoinRPCTransportError::HttpStatus(response.status_code))
+            }
+            Err(err) => Err(BitcoinRPCTransportError::JsonErr(err)),
+        }
+    }
+}
+
+impl Transport for BitcoinRPCTransport {
+    fn send_request(&self, request: Request) -> Result<Response, jsonrpc::Error> {
+        let body = serde_json::to_vec(&request).map_err(jsonrpc::Error::Json)?;
+        let timeout = call_timeouts().timeout_for(request.method);
+        self.post(&body, timeout)
+            .map_err(|err| jsonrpc::Error::Transport(Box::new(err)))
+    }
+
+    fn send_batch(&self, requests: &[Request]) -> Result<Vec<Response>, jsonrpc::Error> {
+        let body = serde_json::to_vec(requests).map_err(jsonrpc::Error::Json)?;
+        let timeout =
+            call_timeouts().batch_timeout_for(requests.iter().map(|request| request.method));
+        self.post(&body, timeout)
+            .map_err(|err| jsonrpc::Error::Transport(Box::new(err)))
+    }
+
+    fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.target())
+    }
+}
+
+/// Creates an RPC client to the Bitcoin node, over TLS if its URL is `https://`.
+pub fn new_rpc_client(rpc_holder: &BitcoinRPCHolder) -> Result<Client, bitcoincore_rpc::Error> {
+    match BitcoinRPCTransport::new(
+        &rpc_holder.url(),
+        &rpc_holder.user(),
+        &rpc_holder.password(),
+    ) {
+        Ok(transport) => Ok(Client::from_jsonrpc(jsonrpc::Client::with_transport(
+            transport,
+        ))),
+        Err(err) => Err(bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(
+            Box::new(err),
+        ))),
+    }
+}
+
+/// Sends a request over a connection and reads the response within the timeout, handing the
+/// connection back.
+///
+/// The call is registered as in flight for as long as it waits on the Bitcoin node, and fails with a
+/// cancelled error if it gets cancelled meanwhile.
+fn exchange(
+    mut connection: PooledConnection,
+    request: &[u8],
+    timeout: Duration,
+) -> Result<(HttpResponse, PooledConnection), BitcoinRPCTransportError> {
+    // 1 Register the call so that it can be cancelled.
+    let in_flight_call = InFlightCall::begin(connection.get_ref().tcp_stream())?;
+    let deadline = Instant::now() + timeout;
+
+    // 2 Send the request.
+    let sent = {
+        let stream = connection.get_mut();
+        stream
+            .tcp_stream()
+            .set_write_timeout(Some(timeout))
+            .and_then(|_| stream.write_all(request))
+            .and_then(|_| stream.flush())
+            .map_err(BitcoinRPCTransportError::SocketErr)
+    };
+
+    // 3 Read the response before the deadline.
+    let response = sent.and_then(|_| {
+        read_response(&mut DeadlineReader {
+            connection: &mut connection,
+            deadline,
+        })
+    });
+
+    // 4 A cancelled call fails on its shut down connection; report the cancellation instead.
+    match response {
+        Ok(response) => Ok((response, connection)),
+        Err(_) if in_flight_call.is_cancelled() => Err(BitcoinRPCTransportError::Cancelled),
+        Err(err) => Err(err),
+    }
+}
+
+/// Reader over a connection that fails once the deadline passes, however slowly the data trickles in.
+struct DeadlineReader<'a> {
+    // Connection to read from.
+    connection: &'a mut PooledConnection,
+
+    // Instant after which reads fail.
+    deadline: Instant,
+}
+
+impl DeadlineReader<'_> {
+    /// Bounds the next read on the connection by the time left before the deadline.
+    fn arm(&self) -> io::Result<()> {
+        let time_left = self.deadline.saturating_duration_since(Instant::now());
+        if time_left.is_zero() {
+            return Err(io::Error::from(io::ErrorKind::TimedOut));
+        }
+        self.connection
+            .get_ref()
+            .tcp_stream()
+            .set_read_timeout(Some(time_left))
+    }
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.arm()?;
+        self.connection.read(buf)
+    }
+}
+
+impl BufRead for DeadlineReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.arm()?;
+        self.connection.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.connection.consume(amt)
+    }
+}
+
+/// Whether the socket error is a timeout, as opposed to the connection being closed.
+fn is_timeout(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
+
+/// An HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    // Status code of the response.
+    pub status_code: u16,
+
+    // Body of the response.
+    pub body: Vec<u8>,
+
+    // Whether the connection can be reused for another request.
+    pub keep_alive: bool,
+}
+
+/// Reads an HTTP response.
+///
+/// The body is delimited by its `Content-Length`, by chunked transfer encoding, or by the end of
+/// the connection. The connection is kept alive if the body is delimited and the server neither
+/// speaks HTTP/1.0 nor asks to close it.
+pub fn read_response<R: BufRead>(reader: &mut R) -> Result<HttpResponse, BitcoinRPCTransportError> {
+    // 1 Read the status line.
+    let status_line = read_line(reader)?;
+    let (is_http_1_1, status_code) = match status_line.split_whitespace().collect::<Vec<&str>>()[..]
+    {
+        [version, status_code, ..] if version.starts_with("HTTP/1.") => (
+            version == "HTTP/1.1",
+            status_code
+                .parse::<u16>()
+                .map_err(|_| malformed(format!("bad status line '{}'", status_line)))?,
+        ),
+        _ => return Err(malformed(format!("bad status line '{}'", status_line))),
+    };
+
+    // 2 Read the headers.
+    let mut content_length = None;
+    let mut is_chunked = false;
+    let mut close_requested = false;
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| malformed(format!("bad header '{}'", line)))?;
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => {
+                content_length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| malformed(format!("bad content length '{}'", value)))?,
+                )
+            }
+            "transfer-encoding" => is_chunked = value.to_ascii_lowercase().contains("chunked"),
+            "connection" => close_requested = value.to_ascii_lowercase().contains("close"),
+            _ => {}
+        }
+    }
+
+    // 3 Read the body.
+    let body = match (is_chunked, content_length) {
+        (true, _) => read_chunked_body(reader)?,
+        (false, Some(content_length)) => {
+            if content_length > MAX_RESPONSE_BYTES {
+                return Err(malformed(format!("body of {} bytes", content_length)));
+            }
+            let mut body = vec![0; content_length];
+            reader
+                .read_exact(&mut body)
+                .map_err(BitcoinRPCTransportError::SocketErr)?;
+            body
+        }
+        (false, None) => {
+            let mut body = Vec::new();
+            reader
+                .take(MAX_RESPONSE_BYTES as u64 + 1)
+                .read_to_end(&mut body)
+                .map_err(BitcoinRPCTransportError::SocketErr)?;
+            if body.len() > MAX_RESPONSE_BYTES {
+                return Err(malformed("body too large".to_string()));
+            }
+            body
+        }
+    };
+
+    Ok(HttpResponse {
+        status_code,
+        body,
+        keep_alive: is_http_1_1 && !close_requested && (is_chunked || content_length.is_some()),
+    })
+}
+
+/// Reads a body sent with chunked transfer encoding.
+fn read_chunked_body<R: BufRead>(reader: &mut R) -> Result<Vec<u8>, BitcoinRPCTransportError> {
+    let mut body = Vec::new();
+    loop {
+        // 1 Read the chunk size, ignoring chunk extensions.
+        let size_line = read_line(reader)?;
+        let size_hex = size_line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| malformed(format!("bad chunk size '{}'", size_line)))?;
+
+        // 2 The last chunk is followed by optional trailers.
+        if size == 0 {
+            while !read_line(reader)?.is_empty() {}
+            return Ok(body);
+        }
+
+        // 3 Read the chunk and the line break after it.
+        if body.len().saturating_add(size) > MAX_RESPONSE_BYTES {
+            return Err(malformed("body too large".to_string()));
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader
+            .read_exact(&mut body[start..])
+            .map_err(BitcoinRPCTransportError::SocketErr)?;
+        if !read_line(reader)?.is_empty() {
+            return Err(malformed("chunk longer than its size".to_string()));
+        }
+    }
+}
+
+/// Reads a line without its line break, failing at the end of the connection.
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, BitcoinRPCTransportError> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Err(BitcoinRPCTransportError::SocketErr(io::Error::from(
+            io::ErrorKind::UnexpectedEof,
+        ))),
+        Ok(_) => Ok(line.trim_end_matches(['\r', '\n']).to_string()),
+        Err(err) => Err(BitcoinRPCTransportError::SocketErr(err)),
+    }
+}
+
+/// Constructs a malformed response error.
+fn malformed(reason: String) -> BitcoinRPCTransportError {
+    BitcoinRPCTransportError::MalformedResponse(reason)
+}
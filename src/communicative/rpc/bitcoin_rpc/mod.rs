@@ -1,3 +1,11 @@
 pub mod bitcoin_rpc_error;
 pub mod bitcoin_rpc;
+pub mod bitcoin_rpc_batch;
+pub mod bitcoin_rpc_cache;
+pub mod bitcoin_rpc_cancel;
 pub mod bitcoin_rpc_holder;
+pub mod bitcoin_rpc_pool;
+pub mod bitcoin_rpc_retry;
+pub mod bitcoin_rpc_timeout;
+pub mod bitcoin_rpc_tls;
+pub mod bitcoin_rpc_transport;
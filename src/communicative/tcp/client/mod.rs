@@ -1,6 +1,10 @@
 mod peer_tcp_client;
 mod tcp_client;
 
+pub use crate::communicative::tcp::protocol::balance_proof::{
+    BalanceProofRequestBody, BalanceProofResponseBody, BalanceProofResponseError,
+    BalanceProofSuccessBody,
+};
 pub use crate::communicative::tcp::protocol::batchrecord::{
     BatchRecordRequestBody, BatchRecordResponseBody, BatchRecordResponseError,
     BatchRecordSuccessBody,
@@ -13,6 +17,16 @@ pub use crate::communicative::tcp::protocol::batchcontainer_by_prevoutpoint::{
     BatchContainerByPrevOutpointRequestBody, BatchContainerByPrevOutpointResponseBody,
     BatchContainerByPrevOutpointResponseError, BatchContainerByPrevOutpointSuccessBody,
 };
+pub use crate::communicative::tcp::protocol::delta_cosign::{
+    DeltaCosignRequestBody, DeltaCosignResponseBody, DeltaCosignResponseError,
+    DeltaCosignSuccessBody,
+};
+pub use crate::communicative::tcp::protocol::handshake::{
+    HandshakeRequestBody, HandshakeResponseBody, HandshakeResponseError,
+};
+pub use crate::communicative::tcp::protocol::heartbeat::{
+    HeartbeatRequestBody, HeartbeatResponseBody, HeartbeatResponseError, HeartbeatSuccessBody,
+};
 pub use crate::communicative::tcp::protocol::in_flight_sync::{
     InFlightSyncRequestBody, InFlightSyncResponseBody, InFlightSyncResponseError,
 };
@@ -1,4 +1,10 @@
+use crate::communicative::federation::delta_attestation::DeltaCosignature;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use crate::communicative::handshake::announcement::HandshakeAnnouncement;
+use crate::communicative::handshake::heartbeat::Heartbeat;
 use crate::communicative::peer::peer::PEER;
+use crate::communicative::tcp::protocol::balance_proof::client::request_balance_proof;
+use crate::communicative::tcp::protocol::balance_proof::BalanceProofResponseBody;
 use crate::communicative::tcp::protocol::batchrecord::client::request_batchrecord;
 use crate::communicative::tcp::protocol::batchrecord::BatchRecordResponseBody;
 use crate::communicative::tcp::protocol::batchcontainer::client::request_batchcontainer;
@@ -9,6 +15,12 @@ use crate::communicative::tcp::protocol::config::client::request_config;
 use crate::communicative::tcp::protocol::config::ConfigResponseBody;
 use crate::communicative::tcp::protocol::deploy::client::request_deploy;
 use crate::communicative::tcp::protocol::deploy::DeployResponseBody;
+use crate::communicative::tcp::protocol::delta_cosign::client::request_delta_cosign;
+use crate::communicative::tcp::protocol::delta_cosign::DeltaCosignResponseBody;
+use crate::communicative::tcp::protocol::handshake::client::request_handshake;
+use crate::communicative::tcp::protocol::handshake::HandshakeResponseBody;
+use crate::communicative::tcp::protocol::heartbeat::client::request_heartbeat;
+use crate::communicative::tcp::protocol::heartbeat::HeartbeatResponseBody;
 use crate::communicative::tcp::protocol::in_flight_sync::client::request_in_flight_sync::request_in_flight_sync;
 use crate::communicative::tcp::protocol::in_flight_sync::InFlightSyncResponseBody;
 use crate::communicative::tcp::protocol::liftup_v1::client::request_liftup_v1;
@@ -102,4 +114,42 @@ impl TCPClient for PEER {
     ) -> Result<(InFlightSyncResponseBody, Duration), RequestError> {
         request_in_flight_sync(self, cube_batch_sync_height_tip).await
     }
+
+    async fn request_balance_proof(
+        &self,
+        account_key: [u8; 32],
+    ) -> Result<(BalanceProofResponseBody, Duration), RequestError> {
+        request_balance_proof(self, account_key).await
+    }
+
+    async fn request_delta_cosign(
+        &self,
+        batch_height: u64,
+        batch_txid: [u8; 32],
+        cosignature: DeltaCosignature,
+        state_root_cosignature: Option<StateRootCosignature>,
+    ) -> Result<(DeltaCosignResponseBody, Duration), RequestError> {
+        request_delta_cosign(
+            self,
+            batch_height,
+            batch_txid,
+            cosignature,
+            state_root_cosignature,
+        )
+        .await
+    }
+
+    async fn request_handshake(
+        &self,
+        announcement: &HandshakeAnnouncement,
+    ) -> Result<(HandshakeResponseBody, Duration), RequestError> {
+        request_handshake(self, announcement).await
+    }
+
+    async fn request_heartbeat(
+        &self,
+        heartbeat: &Heartbeat,
+    ) -> Result<(HeartbeatResponseBody, Duration), RequestError> {
+        request_heartbeat(self, heartbeat).await
+    }
 }
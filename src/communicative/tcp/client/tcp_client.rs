@@ -1,12 +1,20 @@
+use crate::communicative::tcp::protocol::balance_proof::BalanceProofResponseBody;
 use crate::communicative::tcp::protocol::batchrecord::BatchRecordResponseBody;
 use crate::communicative::tcp::protocol::batchcontainer::BatchContainerResponseBody;
 use crate::communicative::tcp::protocol::batchcontainer_by_prevoutpoint::BatchContainerByPrevOutpointResponseBody;
 use crate::communicative::tcp::protocol::config::ConfigResponseBody;
 use crate::communicative::tcp::protocol::deploy::DeployResponseBody;
+use crate::communicative::tcp::protocol::delta_cosign::DeltaCosignResponseBody;
+use crate::communicative::tcp::protocol::handshake::HandshakeResponseBody;
+use crate::communicative::tcp::protocol::heartbeat::HeartbeatResponseBody;
 use crate::communicative::tcp::protocol::in_flight_sync::InFlightSyncResponseBody;
 use crate::communicative::tcp::protocol::liftup_v1::LiftupV1ResponseBody;
 use crate::communicative::tcp::protocol::r#move::MoveResponseBody;
 use crate::communicative::tcp::protocol::swapout::SwapoutResponseBody;
+use crate::communicative::federation::delta_attestation::DeltaCosignature;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use crate::communicative::handshake::announcement::HandshakeAnnouncement;
+use crate::communicative::handshake::heartbeat::Heartbeat;
 use crate::communicative::tcp::request_error::RequestError;
 use crate::constructive::entry::entry_kinds::config::config::Config;
 use crate::constructive::entry::entry_kinds::deploy::deploy::Deploy;
@@ -61,4 +69,23 @@ pub trait TCPClient {
         &self,
         cube_batch_sync_height_tip: u64,
     ) -> Result<(InFlightSyncResponseBody, Duration), RequestError>;
+    async fn request_balance_proof(
+        &self,
+        account_key: [u8; 32],
+    ) -> Result<(BalanceProofResponseBody, Duration), RequestError>;
+    async fn request_delta_cosign(
+        &self,
+        batch_height: u64,
+        batch_txid: [u8; 32],
+        cosignature: DeltaCosignature,
+        state_root_cosignature: Option<StateRootCosignature>,
+    ) -> Result<(DeltaCosignResponseBody, Duration), RequestError>;
+    async fn request_handshake(
+        &self,
+        announcement: &HandshakeAnnouncement,
+    ) -> Result<(HandshakeResponseBody, Duration), RequestError>;
+    async fn request_heartbeat(
+        &self,
+        heartbeat: &Heartbeat,
+    ) -> Result<(HeartbeatResponseBody, Duration), RequestError>;
 }
@@ -0,0 +1,82 @@
+use super::compression_error::TCPCompressionError;
+use super::package::{PackageKind, TCPPackage};
+use std::io::Read;
+
+/// Smallest payload worth compressing, in bytes.
+pub const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// zstd compression level.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Largest serialized package a compressed package may decompress to, in bytes.
+pub const MAX_DECOMPRESSED_LEN: usize = 128 * 1024 * 1024;
+
+/// Compresses a package with zstd.
+///
+/// The compressed package is a `Compressed` package with the same timestamp, so that responses still
+/// match their requests. Its payload is the zstd frame of the serialized package. Packages whose
+/// payload is below [`COMPRESSION_THRESHOLD`], that do not shrink, or that are already compressed are
+/// returned as they are.
+pub fn compress(package: TCPPackage) -> TCPPackage {
+    // 1 Leave small and already compressed packages as they are.
+    if (package.payload_len() as usize) < COMPRESSION_THRESHOLD
+        || package.kind() == PackageKind::Compressed
+    {
+        return package;
+    }
+
+    // 2 Compress the serialized package.
+    let serialized_package = package.serialize();
+    let compressed_payload = match zstd::encode_all(&serialized_package[..], COMPRESSION_LEVEL) {
+        Ok(compressed_payload) => compressed_payload,
+        Err(_) => return package,
+    };
+
+    // 3 Keep the package as it is if compressing does not pay off.
+    if compressed_payload.len() >= serialized_package.len() {
+        return package;
+    }
+
+    // 4 Return the compressed package.
+    TCPPackage::new(
+        PackageKind::Compressed,
+        package.timestamp(),
+        &compressed_payload,
+    )
+}
+
+/// Decompresses a package compressed with [`compress`], returning any other package as it is.
+///
+/// Decompression stops past [`MAX_DECOMPRESSED_LEN`], so that a small package cannot expand into an
+/// arbitrarily large one.
+pub fn decompress(package: TCPPackage) -> Result<TCPPackage, TCPCompressionError> {
+    // 1 Pass packages that are not compressed through.
+    if package.kind() != PackageKind::Compressed {
+        return Ok(package);
+    }
+
+    // 2 Decompress the serialized package, up to the size limit.
+    let compressed_payload = package.payload();
+    let decoder = zstd::stream::read::Decoder::new(&compressed_payload[..])
+        .map_err(|_| TCPCompressionError::DecompressionError)?;
+    let mut serialized_package = Vec::<u8>::new();
+    decoder
+        .take(MAX_DECOMPRESSED_LEN as u64 + 1)
+        .read_to_end(&mut serialized_package)
+        .map_err(|_| TCPCompressionError::DecompressionError)?;
+    if serialized_package.len() > MAX_DECOMPRESSED_LEN {
+        return Err(TCPCompressionError::PackageTooLarge);
+    }
+
+    // 3 Deserialize the package, which must not be compressed again and must keep the timestamp.
+    let decompressed_package = TCPPackage::deserialize(&serialized_package)
+        .ok_or(TCPCompressionError::MalformedPackage)?;
+    if decompressed_package.kind() == PackageKind::Compressed
+        || decompressed_package.timestamp() != package.timestamp()
+    {
+        return Err(TCPCompressionError::MalformedPackage);
+    }
+
+    // 4 Return the decompressed package.
+    Ok(decompressed_package)
+}
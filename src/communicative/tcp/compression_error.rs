@@ -0,0 +1,7 @@
+/// Errors decompressing a compressed TCP package.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TCPCompressionError {
+    DecompressionError,
+    PackageTooLarge,
+    MalformedPackage,
+}
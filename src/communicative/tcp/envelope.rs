@@ -0,0 +1,195 @@
+use super::envelope_error::TCPEnvelopeError;
+use super::package::{PackageKind, TCPPackage};
+use nostr_sdk::nips::nip44::v2::{self, ConversationKey};
+use nostr_sdk::{Keys, PublicKey};
+
+/// Largest plaintext the NIP-44 v2 implementation encrypts, `65536 - 128`.
+const NIP44_MAX_PLAINTEXT_LEN: usize = 65_408;
+
+/// Length of the shortest NIP-44 v2 payload: the version, the nonce, the smallest padded plaintext
+/// and the MAC.
+const NIP44_MIN_PAYLOAD_LEN: usize = 99;
+
+/// Length of the header each chunk plaintext starts with: the sender key, the sequence number, the
+/// chunk index and the chunk count.
+const CHUNK_HEADER_LEN: usize = 44;
+
+/// Length of the envelope header: the version, the sender key, the recipient key, the chunk count and
+/// the sequence number.
+const ENVELOPE_HEADER_LEN: usize = 75;
+
+/// Version of the encryption scheme of an envelope.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TCPEnvelopeVersion {
+    /// NIP-44 v2: secp256k1 ECDH, HKDF, padding, ChaCha20 and HMAC-SHA256.
+    Nip44V2,
+}
+
+impl TCPEnvelopeVersion {
+    pub fn bytecode(&self) -> u8 {
+        match self {
+            TCPEnvelopeVersion::Nip44V2 => 0x02,
+        }
+    }
+
+    pub fn from_bytecode(bytecode: u8) -> Option<Self> {
+        match bytecode {
+            0x02 => Some(TCPEnvelopeVersion::Nip44V2),
+            _ => None,
+        }
+    }
+}
+
+/// Seals a package to the recipient key, encrypting it with the NIP-44 conversation key of the local
+/// keys and the recipient key.
+///
+/// The sealed package is an `Envelope` package with the same timestamp, so that responses still match
+/// their requests. Its payload is the version, the sender key, the recipient key, the number of
+/// chunks and the sequence number, followed by each length-prefixed NIP-44 v2 payload. Since NIP-44
+/// encrypts at most 64 KiB, the serialized package is split into chunks, each starting with the sender
+/// key, the sequence number, its index and the chunk count, so that chunks can be neither reordered,
+/// dropped, spliced across envelopes nor reflected back to their sender.
+///
+/// The sequence number lets the recipient refuse replayed envelopes, see [`super::replay`].
+pub fn seal(
+    local_keys: &Keys,
+    recipient_key: [u8; 32],
+    sequence: u64,
+    package: &TCPPackage,
+) -> Result<TCPPackage, TCPEnvelopeError> {
+    // 1 Derive the conversation key.
+    let recipient_public_key =
+        PublicKey::from_slice(&recipient_key).map_err(|_| TCPEnvelopeError::InvalidPeerKey)?;
+    let conversation_key = ConversationKey::derive(local_keys.secret_key(), &recipient_public_key);
+    let sender_key = local_keys.public_key().to_bytes();
+
+    // 2 Split the serialized package into chunks.
+    let plaintext = package.serialize();
+    let chunks: Vec<&[u8]> = plaintext
+        .chunks(NIP44_MAX_PLAINTEXT_LEN - CHUNK_HEADER_LEN)
+        .collect();
+    let chunk_count: u16 = chunks
+        .len()
+        .try_into()
+        .map_err(|_| TCPEnvelopeError::PackageTooLarge)?;
+
+    // 3 Construct the envelope header.
+    let mut envelope = Vec::<u8>::with_capacity(ENVELOPE_HEADER_LEN + plaintext.len());
+    envelope.push(TCPEnvelopeVersion::Nip44V2.bytecode());
+    envelope.extend(sender_key);
+    envelope.extend(recipient_key);
+    envelope.extend(chunk_count.to_be_bytes());
+    envelope.extend(sequence.to_be_bytes());
+
+    // 4 Encrypt each chunk.
+    for (index, chunk) in chunks.iter().enumerate() {
+        let mut chunk_plaintext = Vec::<u8>::with_capacity(CHUNK_HEADER_LEN + chunk.len());
+        chunk_plaintext.extend(sender_key);
+        chunk_plaintext.extend(sequence.to_be_bytes());
+        chunk_plaintext.extend((index as u16).to_be_bytes());
+        chunk_plaintext.extend(chunk_count.to_be_bytes());
+        chunk_plaintext.extend(*chunk);
+
+        let ciphertext = v2::encrypt_to_bytes(&conversation_key, &chunk_plaintext)
+            .map_err(|_| TCPEnvelopeError::EncryptionError)?;
+        envelope.extend((ciphertext.len() as u32).to_be_bytes());
+        envelope.extend(ciphertext);
+    }
+
+    // 5 Return the sealed package.
+    Ok(TCPPackage::new(
+        PackageKind::Envelope,
+        package.timestamp(),
+        &envelope,
+    ))
+}
+
+/// Opens a package sealed to the local keys with [`seal`].
+///
+/// Returns the sender key and the sequence number, both authenticated by the decryption, along with
+/// the package.
+pub fn open(
+    local_keys: &Keys,
+    sealed_package: &TCPPackage,
+) -> Result<([u8; 32], u64, TCPPackage), TCPEnvelopeError> {
+    // 1 Parse the envelope header.
+    let envelope = sealed_package.payload();
+    if sealed_package.kind() != PackageKind::Envelope || envelope.len() < ENVELOPE_HEADER_LEN {
+        return Err(TCPEnvelopeError::MalformedEnvelope);
+    }
+    match TCPEnvelopeVersion::from_bytecode(envelope[0]) {
+        Some(TCPEnvelopeVersion::Nip44V2) => (),
+        None => return Err(TCPEnvelopeError::UnsupportedVersion(envelope[0])),
+    }
+    let sender_key: [u8; 32] = envelope[1..33]
+        .try_into()
+        .map_err(|_| TCPEnvelopeError::MalformedEnvelope)?;
+    let recipient_key: [u8; 32] = envelope[33..65]
+        .try_into()
+        .map_err(|_| TCPEnvelopeError::MalformedEnvelope)?;
+    let chunk_count = u16::from_be_bytes([envelope[65], envelope[66]]);
+    let sequence = u64::from_be_bytes(
+        envelope[67..75]
+            .try_into()
+            .map_err(|_| TCPEnvelopeError::MalformedEnvelope)?,
+    );
+
+    // 2 The envelope must be sealed to the local keys.
+    if recipient_key != local_keys.public_key().to_bytes() {
+        return Err(TCPEnvelopeError::RecipientKeyMismatch);
+    }
+
+    // 3 Derive the conversation key.
+    let sender_public_key =
+        PublicKey::from_slice(&sender_key).map_err(|_| TCPEnvelopeError::InvalidPeerKey)?;
+    let conversation_key = ConversationKey::derive(local_keys.secret_key(), &sender_public_key);
+
+    // 4 Decrypt each chunk, in order.
+    let mut plaintext = Vec::<u8>::new();
+    let mut rest = &envelope[ENVELOPE_HEADER_LEN..];
+    for index in 0..chunk_count {
+        // 4.1 Split the next chunk.
+        if rest.len() < 4 {
+            return Err(TCPEnvelopeError::MalformedEnvelope);
+        }
+        let ciphertext_len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        if ciphertext_len < NIP44_MIN_PAYLOAD_LEN || rest.len() < 4 + ciphertext_len {
+            return Err(TCPEnvelopeError::MalformedEnvelope);
+        }
+        let ciphertext = &rest[4..4 + ciphertext_len];
+        rest = &rest[4 + ciphertext_len..];
+
+        // 4.2 Decrypt the chunk.
+        let chunk_plaintext = v2::decrypt_to_bytes(&conversation_key, ciphertext)
+            .map_err(|_| TCPEnvelopeError::DecryptionError)?;
+        if chunk_plaintext.len() < CHUNK_HEADER_LEN {
+            return Err(TCPEnvelopeError::MalformedEnvelope);
+        }
+
+        // 4.3 Check the chunk header.
+        if chunk_plaintext[..32] != sender_key {
+            return Err(TCPEnvelopeError::SenderKeyMismatch);
+        }
+        if chunk_plaintext[32..40] != sequence.to_be_bytes()
+            || chunk_plaintext[40..42] != index.to_be_bytes()
+            || chunk_plaintext[42..44] != chunk_count.to_be_bytes()
+        {
+            return Err(TCPEnvelopeError::MalformedEnvelope);
+        }
+
+        plaintext.extend(&chunk_plaintext[CHUNK_HEADER_LEN..]);
+    }
+    if !rest.is_empty() {
+        return Err(TCPEnvelopeError::MalformedEnvelope);
+    }
+
+    // 5 Parse the package, which must not be an envelope itself and must carry the same timestamp.
+    let package = TCPPackage::deserialize(&plaintext).ok_or(TCPEnvelopeError::MalformedPackage)?;
+    if package.kind() == PackageKind::Envelope || package.timestamp() != sealed_package.timestamp()
+    {
+        return Err(TCPEnvelopeError::MalformedPackage);
+    }
+
+    // 6 Return the sender key, the sequence number and the package.
+    Ok((sender_key, sequence, package))
+}
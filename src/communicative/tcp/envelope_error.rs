@@ -0,0 +1,13 @@
+/// Errors sealing or opening the encrypted envelope of a TCP package.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TCPEnvelopeError {
+    InvalidPeerKey,
+    PackageTooLarge,
+    EncryptionError,
+    MalformedEnvelope,
+    UnsupportedVersion(u8),
+    RecipientKeyMismatch,
+    SenderKeyMismatch,
+    DecryptionError,
+    MalformedPackage,
+}
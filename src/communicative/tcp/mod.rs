@@ -1,10 +1,22 @@
 pub mod client;
+pub mod compression;
+pub mod compression_error;
+pub mod envelope;
+pub mod envelope_error;
+pub mod noise;
+pub mod noise_error;
 pub mod package;
 pub mod protocol;
+pub mod replay;
+pub mod replay_error;
 pub mod request_error;
 pub mod server;
 pub mod tcp;
 
+pub use protocol::balance_proof::{
+    BalanceProofRequestBody, BalanceProofResponseBody, BalanceProofResponseError,
+    BalanceProofSuccessBody,
+};
 pub use protocol::batchrecord::{
     BatchRecordRequestBody, BatchRecordResponseBody, BatchRecordResponseError,
     BatchRecordSuccessBody,
@@ -17,6 +29,10 @@ pub use protocol::batchcontainer_by_prevoutpoint::{
     BatchContainerByPrevOutpointRequestBody, BatchContainerByPrevOutpointResponseBody,
     BatchContainerByPrevOutpointResponseError, BatchContainerByPrevOutpointSuccessBody,
 };
+pub use protocol::delta_cosign::{
+    DeltaCosignRequestBody, DeltaCosignResponseBody, DeltaCosignResponseError,
+    DeltaCosignSuccessBody,
+};
 pub use protocol::in_flight_sync::{
     InFlightSyncRequestBody, InFlightSyncResponseBody, InFlightSyncResponseError,
 };
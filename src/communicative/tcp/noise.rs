@@ -0,0 +1,609 @@
+use super::noise_error::TCPNoiseError;
+use super::package::{PackageKind, TCPPackage};
+use crate::inscriptive::baked;
+use crate::transmutative::hash;
+use crate::transmutative::secp::schnorr::{self, Bytes32, LiftScalar};
+use bitcoin::hashes::hmac::{Hmac, HmacEngine};
+use bitcoin::hashes::{sha256, Hash as _, HashEngine};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use secp::Scalar;
+use zeroize::Zeroize;
+
+/// Noise protocol name, hashed into the initial handshake state.
+const NOISE_PROTOCOL_NAME: &[u8] = b"Noise_XX_secp256k1_ChaChaPoly_SHA256";
+
+/// Largest Noise message, `65535` bytes.
+const NOISE_MAX_MESSAGE_LEN: usize = 65_535;
+
+/// Length of the ChaChaPoly authentication tag.
+const NOISE_TAG_LEN: usize = 16;
+
+/// Length of an x-only secp256k1 public key, which is also the DH output length.
+const NOISE_DH_LEN: usize = 32;
+
+/// Length of the frame header each transport ciphertext starts with: the explicit nonce and the
+/// ciphertext length.
+const FRAME_HEADER_LEN: usize = 12;
+
+/// Role of a party in the XX handshake.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NoiseRole {
+    /// The party dialing the connection, which sends the first message.
+    Initiator,
+    /// The party accepting the connection.
+    Responder,
+}
+
+/// A ChaChaPoly key with its nonce counter.
+struct CipherState {
+    key: Option<[u8; 32]>,
+    nonce: u64,
+}
+
+impl CipherState {
+    fn new(key: Option<[u8; 32]>) -> Self {
+        Self { key, nonce: 0 }
+    }
+
+    /// Returns the 96-bit ChaChaPoly nonce, 32 zero bits followed by the little-endian counter.
+    fn nonce_bytes(nonce: u64) -> [u8; 12] {
+        let mut nonce_bytes = [0u8; 12];
+        nonce_bytes[4..].copy_from_slice(&nonce.to_le_bytes());
+        nonce_bytes
+    }
+
+    fn encrypt_with_ad(&mut self, ad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, TCPNoiseError> {
+        // 1 Plaintext passes through until a key is set.
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Ok(plaintext.to_vec()),
+        };
+
+        // 2 The maximum nonce is reserved.
+        if self.nonce == u64::MAX {
+            return Err(TCPNoiseError::NonceExhausted);
+        }
+
+        // 3 Encrypt under the current nonce.
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&Self::nonce_bytes(self.nonce)),
+                Payload {
+                    msg: plaintext,
+                    aad: ad,
+                },
+            )
+            .map_err(|_| TCPNoiseError::EncryptionError)?;
+        self.nonce += 1;
+
+        Ok(ciphertext)
+    }
+
+    fn decrypt_with_ad(&mut self, ad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, TCPNoiseError> {
+        let nonce = self.nonce;
+        let plaintext = self.decrypt_with_nonce(nonce, ad, ciphertext)?;
+        if self.key.is_some() {
+            self.nonce += 1;
+        }
+        Ok(plaintext)
+    }
+
+    fn decrypt_with_nonce(
+        &self,
+        nonce: u64,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, TCPNoiseError> {
+        // 1 Ciphertext passes through until a key is set.
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Ok(ciphertext.to_vec()),
+        };
+
+        // 2 The maximum nonce is reserved.
+        if nonce == u64::MAX {
+            return Err(TCPNoiseError::NonceExhausted);
+        }
+
+        // 3 Decrypt under the given nonce.
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+        cipher
+            .decrypt(
+                Nonce::from_slice(&Self::nonce_bytes(nonce)),
+                Payload {
+                    msg: ciphertext,
+                    aad: ad,
+                },
+            )
+            .map_err(|_| TCPNoiseError::DecryptionError)
+    }
+}
+
+impl Drop for CipherState {
+    // 1 Automatically zeroize the key on drop.
+    fn drop(&mut self) {
+        if let Some(key) = self.key.as_mut() {
+            key.zeroize();
+        }
+    }
+}
+
+/// The chaining key and handshake hash, along with the current handshake cipher.
+struct SymmetricState {
+    cipher: CipherState,
+    chaining_key: [u8; 32],
+    handshake_hash: [u8; 32],
+}
+
+impl SymmetricState {
+    fn new() -> Self {
+        // 1 The protocol name is longer than the hash, so it is hashed.
+        let handshake_hash = hash::sha256(NOISE_PROTOCOL_NAME);
+
+        Self {
+            cipher: CipherState::new(None),
+            chaining_key: handshake_hash,
+            handshake_hash,
+        }
+    }
+
+    fn mix_key(&mut self, input_key_material: &[u8]) {
+        let (chaining_key, mut temp_key) = hkdf(&self.chaining_key, input_key_material);
+        self.chaining_key = chaining_key;
+        self.cipher = CipherState::new(Some(temp_key));
+        temp_key.zeroize();
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        let mut preimage = self.handshake_hash.to_vec();
+        preimage.extend(data);
+        self.handshake_hash = hash::sha256(&preimage);
+    }
+
+    fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, TCPNoiseError> {
+        let ciphertext = self
+            .cipher
+            .encrypt_with_ad(&self.handshake_hash, plaintext)?;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, TCPNoiseError> {
+        let plaintext = self
+            .cipher
+            .decrypt_with_ad(&self.handshake_hash, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    /// Splits into the initiator-to-responder and responder-to-initiator ciphers.
+    fn split(&self) -> (CipherState, CipherState) {
+        let (mut initiator_key, mut responder_key) = hkdf(&self.chaining_key, &[]);
+        let ciphers = (
+            CipherState::new(Some(initiator_key)),
+            CipherState::new(Some(responder_key)),
+        );
+        initiator_key.zeroize();
+        responder_key.zeroize();
+        ciphers
+    }
+}
+
+impl Drop for SymmetricState {
+    // 1 Automatically zeroize the chaining key on drop.
+    fn drop(&mut self) {
+        self.chaining_key.zeroize();
+    }
+}
+
+/// Returns the HMAC-SHA256 of the concatenated data under the given key.
+fn hmac_sha256(key: &[u8; 32], data: &[&[u8]]) -> [u8; 32] {
+    let mut engine = HmacEngine::<sha256::Hash>::new(key);
+    for chunk in data {
+        engine.input(chunk);
+    }
+    Hmac::<sha256::Hash>::from_engine(engine).to_byte_array()
+}
+
+/// Returns the two outputs of the Noise HKDF of the input key material under the chaining key.
+fn hkdf(chaining_key: &[u8; 32], input_key_material: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut temp_key = hmac_sha256(chaining_key, &[input_key_material]);
+    let output_1 = hmac_sha256(&temp_key, &[&[0x01]]);
+    let output_2 = hmac_sha256(&temp_key, &[&output_1, &[0x02]]);
+    temp_key.zeroize();
+    (output_1, output_2)
+}
+
+/// Returns the secp256k1 Diffie-Hellman output, the x coordinate of the local secret times the
+/// even point of the remote x-only key.
+///
+/// The local secret is lifted to its even point, so both parties arrive at the same output from
+/// x-only keys.
+fn dh(local_secret: &[u8; 32], remote_key: &[u8; 32]) -> Result<[u8; 32], TCPNoiseError> {
+    let local_secret = Scalar::from_slice(local_secret)
+        .map_err(|_| TCPNoiseError::InvalidLocalKey)?
+        .lift();
+    let remote_point = remote_key
+        .to_even_point()
+        .ok_or(TCPNoiseError::InvalidRemoteKey)?;
+
+    Ok((remote_point * local_secret).serialize_xonly())
+}
+
+/// State of a Noise XX handshake authenticated by the static keys (npubs) of both parties.
+///
+/// `-> e`, `<- e, ee, s, es`, `-> s, se`: the initiator learns the responder key in the second
+/// message, and the responder learns the initiator key in the third. Each party checks the remote
+/// key it learns against the npub it expects.
+pub struct NoiseHandshake {
+    role: NoiseRole,
+    symmetric: SymmetricState,
+    static_secret: [u8; 32],
+    static_key: [u8; 32],
+    ephemeral_secret: Option<[u8; 32]>,
+    remote_ephemeral_key: Option<[u8; 32]>,
+    remote_static_key: Option<[u8; 32]>,
+    message_index: u8,
+}
+
+impl NoiseHandshake {
+    /// Starts a handshake with the local static secret key, the secret key of the local npub.
+    pub fn new(role: NoiseRole, mut static_secret: [u8; 32]) -> Result<Self, TCPNoiseError> {
+        // 1 Derive the static key.
+        let static_key = match static_secret.secret_to_public() {
+            Some(static_key) => static_key,
+            None => {
+                static_secret.zeroize();
+                return Err(TCPNoiseError::InvalidLocalKey);
+            }
+        };
+
+        // 2 Mix the prologue, binding the handshake to the project.
+        let mut symmetric = SymmetricState::new();
+        symmetric.mix_hash(baked::PROJECT_TAG.as_bytes());
+
+        let handshake = Self {
+            role,
+            symmetric,
+            static_secret,
+            static_key,
+            ephemeral_secret: None,
+            remote_ephemeral_key: None,
+            remote_static_key: None,
+            message_index: 0,
+        };
+        static_secret.zeroize();
+
+        Ok(handshake)
+    }
+
+    /// Returns the role of the local party.
+    pub fn role(&self) -> NoiseRole {
+        self.role
+    }
+
+    /// Returns the static key of the remote party, once learned.
+    pub fn remote_key(&self) -> Option<[u8; 32]> {
+        self.remote_static_key
+    }
+
+    /// Whether all three handshake messages were exchanged.
+    pub fn is_finished(&self) -> bool {
+        self.message_index == 3
+    }
+
+    /// Whether it is the local party's turn to write the next message.
+    fn is_writer(&self) -> bool {
+        matches!(
+            (self.role, self.message_index),
+            (NoiseRole::Initiator, 0 | 2) | (NoiseRole::Responder, 1)
+        )
+    }
+
+    fn ephemeral_secret(&self) -> Result<&[u8; 32], TCPNoiseError> {
+        self.ephemeral_secret
+            .as_ref()
+            .ok_or(TCPNoiseError::OutOfTurn)
+    }
+
+    fn remote_ephemeral_key(&self) -> Result<[u8; 32], TCPNoiseError> {
+        self.remote_ephemeral_key.ok_or(TCPNoiseError::OutOfTurn)
+    }
+
+    fn remote_static_key(&self) -> Result<[u8; 32], TCPNoiseError> {
+        self.remote_static_key.ok_or(TCPNoiseError::OutOfTurn)
+    }
+
+    /// Writes the next handshake message.
+    pub fn write_message(&mut self) -> Result<Vec<u8>, TCPNoiseError> {
+        if self.is_finished() || !self.is_writer() {
+            return Err(TCPNoiseError::OutOfTurn);
+        }
+
+        let mut message = Vec::<u8>::new();
+        match self.message_index {
+            // 1 -> e
+            0 => {
+                self.write_ephemeral(&mut message)?;
+            }
+            // 2 <- e, ee, s, es
+            1 => {
+                self.write_ephemeral(&mut message)?;
+                let ee = dh(self.ephemeral_secret()?, &self.remote_ephemeral_key()?)?;
+                self.symmetric.mix_key(&ee);
+                self.write_static(&mut message)?;
+                let es = dh(&self.static_secret, &self.remote_ephemeral_key()?)?;
+                self.symmetric.mix_key(&es);
+            }
+            // 3 -> s, se
+            _ => {
+                self.write_static(&mut message)?;
+                let se = dh(&self.static_secret, &self.remote_ephemeral_key()?)?;
+                self.symmetric.mix_key(&se);
+            }
+        }
+
+        // 4 Every message ends with an empty payload, authenticating the handshake hash.
+        message.extend(self.symmetric.encrypt_and_hash(&[])?);
+        self.message_index += 1;
+
+        Ok(message)
+    }
+
+    /// Reads the next handshake message of the remote party.
+    pub fn read_message(&mut self, message: &[u8]) -> Result<(), TCPNoiseError> {
+        if self.is_finished() || self.is_writer() {
+            return Err(TCPNoiseError::OutOfTurn);
+        }
+
+        let mut rest = message;
+        match self.message_index {
+            // 1 -> e
+            0 => {
+                self.read_ephemeral(&mut rest)?;
+            }
+            // 2 <- e, ee, s, es
+            1 => {
+                self.read_ephemeral(&mut rest)?;
+                let ee = dh(self.ephemeral_secret()?, &self.remote_ephemeral_key()?)?;
+                self.symmetric.mix_key(&ee);
+                self.read_static(&mut rest)?;
+                let es = dh(self.ephemeral_secret()?, &self.remote_static_key()?)?;
+                self.symmetric.mix_key(&es);
+            }
+            // 3 -> s, se
+            _ => {
+                self.read_static(&mut rest)?;
+                let se = dh(self.ephemeral_secret()?, &self.remote_static_key()?)?;
+                self.symmetric.mix_key(&se);
+            }
+        }
+
+        // 4 Every message ends with an empty payload, authenticating the handshake hash.
+        let payload = self.symmetric.decrypt_and_hash(rest)?;
+        if !payload.is_empty() {
+            return Err(TCPNoiseError::MalformedMessage);
+        }
+        self.message_index += 1;
+
+        Ok(())
+    }
+
+    fn write_ephemeral(&mut self, message: &mut Vec<u8>) -> Result<(), TCPNoiseError> {
+        let ephemeral_secret = schnorr::generate_secret();
+        let ephemeral_key = ephemeral_secret
+            .secret_to_public()
+            .ok_or(TCPNoiseError::InvalidLocalKey)?;
+        self.ephemeral_secret = Some(ephemeral_secret);
+        self.symmetric.mix_hash(&ephemeral_key);
+        message.extend(ephemeral_key);
+        Ok(())
+    }
+
+    fn write_static(&mut self, message: &mut Vec<u8>) -> Result<(), TCPNoiseError> {
+        let static_key = self.static_key;
+        message.extend(self.symmetric.encrypt_and_hash(&static_key)?);
+        Ok(())
+    }
+
+    fn read_ephemeral(&mut self, rest: &mut &[u8]) -> Result<(), TCPNoiseError> {
+        if rest.len() < NOISE_DH_LEN {
+            return Err(TCPNoiseError::MalformedMessage);
+        }
+        let (ephemeral_key, remaining) = rest.split_at(NOISE_DH_LEN);
+        let ephemeral_key: [u8; 32] = ephemeral_key
+            .try_into()
+            .map_err(|_| TCPNoiseError::MalformedMessage)?;
+        if !ephemeral_key.is_valid_public() {
+            return Err(TCPNoiseError::InvalidRemoteKey);
+        }
+        self.remote_ephemeral_key = Some(ephemeral_key);
+        self.symmetric.mix_hash(&ephemeral_key);
+        *rest = remaining;
+        Ok(())
+    }
+
+    fn read_static(&mut self, rest: &mut &[u8]) -> Result<(), TCPNoiseError> {
+        if rest.len() < NOISE_DH_LEN + NOISE_TAG_LEN {
+            return Err(TCPNoiseError::MalformedMessage);
+        }
+        let (ciphertext, remaining) = rest.split_at(NOISE_DH_LEN + NOISE_TAG_LEN);
+        let static_key: [u8; 32] = self
+            .symmetric
+            .decrypt_and_hash(ciphertext)?
+            .try_into()
+            .map_err(|_| TCPNoiseError::MalformedMessage)?;
+        if !static_key.is_valid_public() {
+            return Err(TCPNoiseError::InvalidRemoteKey);
+        }
+        self.remote_static_key = Some(static_key);
+        *rest = remaining;
+        Ok(())
+    }
+
+    /// Completes the handshake into a transport, once all three messages were exchanged.
+    pub fn into_transport(self) -> Result<NoiseTransport, TCPNoiseError> {
+        if !self.is_finished() {
+            return Err(TCPNoiseError::HandshakeIncomplete);
+        }
+        let remote_key = self.remote_static_key()?;
+
+        let (initiator_cipher, responder_cipher) = self.symmetric.split();
+        let (send, recv) = match self.role {
+            NoiseRole::Initiator => (initiator_cipher, responder_cipher),
+            NoiseRole::Responder => (responder_cipher, initiator_cipher),
+        };
+
+        Ok(NoiseTransport {
+            send,
+            recv,
+            remote_key,
+            handshake_hash: self.symmetric.handshake_hash,
+            last_recv_nonce: None,
+        })
+    }
+}
+
+impl Drop for NoiseHandshake {
+    // 1 Automatically zeroize the static and ephemeral secrets on drop.
+    fn drop(&mut self) {
+        self.static_secret.zeroize();
+        if let Some(ephemeral_secret) = self.ephemeral_secret.as_mut() {
+            ephemeral_secret.zeroize();
+        }
+    }
+}
+
+/// The transport ciphers of a completed Noise handshake.
+///
+/// Each ciphertext carries its nonce explicitly, so that a response dropped after a timed out request
+/// does not desynchronize the two parties. Nonces must strictly increase; replayed or reordered
+/// ciphertexts are refused.
+pub struct NoiseTransport {
+    send: CipherState,
+    recv: CipherState,
+    remote_key: [u8; 32],
+    handshake_hash: [u8; 32],
+    last_recv_nonce: Option<u64>,
+}
+
+impl NoiseTransport {
+    /// Returns the static key of the remote party.
+    pub fn remote_key(&self) -> [u8; 32] {
+        self.remote_key
+    }
+
+    /// Returns the final handshake hash, which both parties share.
+    pub fn handshake_hash(&self) -> [u8; 32] {
+        self.handshake_hash
+    }
+
+    /// Encrypts a package into a `NoiseTransport` package with the same timestamp.
+    ///
+    /// The serialized package is split into chunks of at most one Noise message each, and each chunk
+    /// is framed with its nonce and ciphertext length.
+    pub fn seal(&mut self, package: &TCPPackage) -> Result<TCPPackage, TCPNoiseError> {
+        // 1 Split the serialized package into chunks.
+        let plaintext = package.serialize();
+        let chunks: Vec<&[u8]> = plaintext
+            .chunks(NOISE_MAX_MESSAGE_LEN - NOISE_TAG_LEN)
+            .collect();
+        let chunk_count: u16 = chunks
+            .len()
+            .try_into()
+            .map_err(|_| TCPNoiseError::PackageTooLarge)?;
+
+        // 2 Encrypt each chunk, authenticating the chunk count.
+        let mut payload = Vec::<u8>::with_capacity(2 + plaintext.len());
+        payload.extend(chunk_count.to_be_bytes());
+        for chunk in chunks.iter() {
+            let nonce = self.send.nonce;
+            let ciphertext = self
+                .send
+                .encrypt_with_ad(&chunk_count.to_be_bytes(), chunk)?;
+            payload.extend(nonce.to_be_bytes());
+            payload.extend((ciphertext.len() as u32).to_be_bytes());
+            payload.extend(ciphertext);
+        }
+
+        // 3 Return the encrypted package.
+        Ok(TCPPackage::new(
+            PackageKind::NoiseTransport,
+            package.timestamp(),
+            &payload,
+        ))
+    }
+
+    /// Decrypts a package encrypted by the remote party with [`NoiseTransport::seal`].
+    pub fn open(&mut self, sealed_package: &TCPPackage) -> Result<TCPPackage, TCPNoiseError> {
+        // 1 Parse the chunk count.
+        let payload = sealed_package.payload();
+        if sealed_package.kind() != PackageKind::NoiseTransport || payload.len() < 2 {
+            return Err(TCPNoiseError::MalformedMessage);
+        }
+        let chunk_count = u16::from_be_bytes([payload[0], payload[1]]);
+
+        // 2 Decrypt each chunk, whose nonces must follow on from each other.
+        let mut plaintext = Vec::<u8>::new();
+        let mut rest = &payload[2..];
+        let mut expected_nonce: Option<u64> = None;
+        let mut last_nonce = self.last_recv_nonce;
+        for _ in 0..chunk_count {
+            // 2.1 Split the next frame.
+            if rest.len() < FRAME_HEADER_LEN {
+                return Err(TCPNoiseError::MalformedMessage);
+            }
+            let nonce = u64::from_be_bytes(rest[0..8].try_into().expect("slice of 8"));
+            let ciphertext_len = u32::from_be_bytes(rest[8..12].try_into().expect("slice of 4"));
+            let ciphertext_len = ciphertext_len as usize;
+            if !(NOISE_TAG_LEN..=NOISE_MAX_MESSAGE_LEN).contains(&ciphertext_len)
+                || rest.len() < FRAME_HEADER_LEN + ciphertext_len
+            {
+                return Err(TCPNoiseError::MalformedMessage);
+            }
+            let ciphertext = &rest[FRAME_HEADER_LEN..FRAME_HEADER_LEN + ciphertext_len];
+            rest = &rest[FRAME_HEADER_LEN + ciphertext_len..];
+
+            // 2.2 Check the nonce was not seen before.
+            if let Some(last_nonce) = last_nonce {
+                if nonce <= last_nonce {
+                    return Err(TCPNoiseError::ReplayedNonce);
+                }
+            }
+            if let Some(expected_nonce) = expected_nonce {
+                if nonce != expected_nonce {
+                    return Err(TCPNoiseError::MalformedMessage);
+                }
+            }
+
+            // 2.3 Decrypt the chunk.
+            plaintext.extend(self.recv.decrypt_with_nonce(
+                nonce,
+                &chunk_count.to_be_bytes(),
+                ciphertext,
+            )?);
+            last_nonce = Some(nonce);
+            expected_nonce = nonce.checked_add(1);
+        }
+        if chunk_count == 0 || !rest.is_empty() {
+            return Err(TCPNoiseError::MalformedMessage);
+        }
+
+        // 3 Parse the package, which must carry the same timestamp.
+        let package = TCPPackage::deserialize(&plaintext).ok_or(TCPNoiseError::MalformedPackage)?;
+        if matches!(
+            package.kind(),
+            PackageKind::NoiseTransport | PackageKind::NoiseHandshake
+        ) || package.timestamp() != sealed_package.timestamp()
+        {
+            return Err(TCPNoiseError::MalformedPackage);
+        }
+
+        // 4 Only record the nonces of an authenticated package.
+        self.last_recv_nonce = last_nonce;
+
+        Ok(package)
+    }
+}
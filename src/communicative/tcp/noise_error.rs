@@ -0,0 +1,16 @@
+/// Errors in the Noise handshake or the Noise transport of a TCP connection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TCPNoiseError {
+    InvalidLocalKey,
+    InvalidRemoteKey,
+    UnexpectedRemoteKey,
+    OutOfTurn,
+    HandshakeIncomplete,
+    MalformedMessage,
+    EncryptionError,
+    DecryptionError,
+    NonceExhausted,
+    ReplayedNonce,
+    PackageTooLarge,
+    MalformedPackage,
+}
@@ -1,8 +1,9 @@
 use super::tcp::{self, TCPError};
 use crate::communicative::peer::peer::SOCKET;
+use crate::communicative::rate_limiter::errors::rate_limit_error::RateLimitError;
 use std::time::Duration;
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub enum PackageKind {
     Ping,
     LiftupV1Protocol,
@@ -14,6 +15,15 @@ pub enum PackageKind {
     BatchContainerProtocol,
     BatchContainerByPrevOutpointProtocol,
     DeployProtocol,
+    BalanceProofProtocol,
+    DeltaCosignProtocol,
+    RateLimited,
+    HandshakeProtocol,
+    HeartbeatProtocol,
+    Envelope,
+    NoiseHandshake,
+    NoiseTransport,
+    Compressed,
 }
 
 impl PackageKind {
@@ -29,6 +39,15 @@ impl PackageKind {
             PackageKind::SwapoutProtocol => 0x07,
             PackageKind::ConfigProtocol => 0x08,
             PackageKind::DeployProtocol => 0x09,
+            PackageKind::BalanceProofProtocol => 0x0a,
+            PackageKind::DeltaCosignProtocol => 0x0b,
+            PackageKind::RateLimited => 0x0c,
+            PackageKind::HandshakeProtocol => 0x0d,
+            PackageKind::HeartbeatProtocol => 0x0e,
+            PackageKind::Envelope => 0x0f,
+            PackageKind::NoiseHandshake => 0x10,
+            PackageKind::NoiseTransport => 0x11,
+            PackageKind::Compressed => 0x12,
         }
     }
     pub fn from_bytecode(bytecode: u8) -> Option<Self> {
@@ -43,11 +62,48 @@ impl PackageKind {
             0x07 => Some(PackageKind::SwapoutProtocol),
             0x08 => Some(PackageKind::ConfigProtocol),
             0x09 => Some(PackageKind::DeployProtocol),
+            0x0a => Some(PackageKind::BalanceProofProtocol),
+            0x0b => Some(PackageKind::DeltaCosignProtocol),
+            0x0c => Some(PackageKind::RateLimited),
+            0x0d => Some(PackageKind::HandshakeProtocol),
+            0x0e => Some(PackageKind::HeartbeatProtocol),
+            0x0f => Some(PackageKind::Envelope),
+            0x10 => Some(PackageKind::NoiseHandshake),
+            0x11 => Some(PackageKind::NoiseTransport),
+            0x12 => Some(PackageKind::Compressed),
             _ => None,
         }
     }
 }
 
+impl ToString for PackageKind {
+    fn to_string(&self) -> String {
+        match self {
+            PackageKind::Ping => "ping".to_string(),
+            PackageKind::LiftupV1Protocol => "liftup_v1".to_string(),
+            PackageKind::MoveProtocol => "move".to_string(),
+            PackageKind::SwapoutProtocol => "swapout".to_string(),
+            PackageKind::ConfigProtocol => "config".to_string(),
+            PackageKind::BatchRecordProtocol => "batch_record".to_string(),
+            PackageKind::InFlightSyncProtocol => "in_flight_sync".to_string(),
+            PackageKind::BatchContainerProtocol => "batch_container".to_string(),
+            PackageKind::BatchContainerByPrevOutpointProtocol => {
+                "batch_container_by_prevoutpoint".to_string()
+            }
+            PackageKind::DeployProtocol => "deploy".to_string(),
+            PackageKind::BalanceProofProtocol => "balance_proof".to_string(),
+            PackageKind::DeltaCosignProtocol => "delta_cosign".to_string(),
+            PackageKind::RateLimited => "rate_limited".to_string(),
+            PackageKind::HandshakeProtocol => "handshake".to_string(),
+            PackageKind::HeartbeatProtocol => "heartbeat".to_string(),
+            PackageKind::Envelope => "envelope".to_string(),
+            PackageKind::NoiseHandshake => "noise_handshake".to_string(),
+            PackageKind::NoiseTransport => "noise_transport".to_string(),
+            PackageKind::Compressed => "compressed".to_string(),
+        }
+    }
+}
+
 pub struct TCPPackage {
     kind: PackageKind,
     timestamp: i64,
@@ -63,6 +119,12 @@ impl TCPPackage {
         }
     }
 
+    /// Constructs the package sent in place of a response when a request exceeds its rate limit.
+    pub fn rate_limited(timestamp: i64, err: RateLimitError) -> TCPPackage {
+        let payload = err.serialize().unwrap_or_default();
+        TCPPackage::new(PackageKind::RateLimited, timestamp, &payload)
+    }
+
     pub fn kind(&self) -> PackageKind {
         self.kind
     }
@@ -75,6 +137,11 @@ impl TCPPackage {
         self.payload.len() as u32
     }
 
+    /// Returns the size of the serialized package.
+    pub fn wire_len(&self) -> usize {
+        13 + self.payload.len()
+    }
+
     pub fn payload(&self) -> Vec<u8> {
         self.payload.clone()
     }
@@ -90,6 +157,24 @@ impl TCPPackage {
         bytes
     }
 
+    /// Deserializes a package serialized with [`TCPPackage::serialize`].
+    pub fn deserialize(bytes: &[u8]) -> Option<TCPPackage> {
+        // 1 Split the kind, the timestamp and the payload length.
+        if bytes.len() < 13 {
+            return None;
+        }
+        let kind = PackageKind::from_bytecode(bytes[0])?;
+        let timestamp = i64::from_be_bytes(bytes[1..9].try_into().ok()?);
+        let payload_len = u32::from_be_bytes(bytes[9..13].try_into().ok()?) as usize;
+
+        // 2 The payload must span the rest of the bytes.
+        if bytes.len() - 13 != payload_len {
+            return None;
+        }
+
+        Some(TCPPackage::new(kind, timestamp, &bytes[13..]))
+    }
+
     pub async fn deliver(
         &self,
         socket: &SOCKET,
@@ -0,0 +1,10 @@
+//! Bincode wire bodies for Balance proof over TCP.
+
+mod request_body;
+mod response_body;
+
+pub use request_body::BalanceProofRequestBody;
+pub use response_body::{
+    state_root_commitment_message, BalanceProofNeighbor, BalanceProofResponseBody,
+    BalanceProofResponseError, BalanceProofSuccessBody,
+};
@@ -0,0 +1,24 @@
+//! Balance proof TCP request payload (bincode body).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceProofRequestBody {
+    pub account_key: [u8; 32],
+}
+
+impl BalanceProofRequestBody {
+    pub fn new(account_key: [u8; 32]) -> Self {
+        Self { account_key }
+    }
+
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(req, _)| req)
+    }
+}
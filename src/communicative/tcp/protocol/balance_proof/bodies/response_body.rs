@@ -0,0 +1,368 @@
+//! Balance proof TCP response payload (bincode body).
+
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use crate::inscriptive::coin_manager::coin_manager::{
+    account_balance_leaf, AccountBalanceWithProof,
+};
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::merkle::{
+    verify_adjacent_merkle_proofs, verify_first_merkle_proof, verify_last_merkle_proof,
+    verify_merkle_proof, MerkleProofStep,
+};
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// Returns the message the engine signs to commit to a state root at a batch height.
+pub fn state_root_commitment_message(batch_height: u64, state_root: [u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::<u8>::with_capacity(40);
+    preimage.extend(batch_height.to_be_bytes());
+    preimage.extend(state_root);
+    preimage.hash(Some(HashTag::StateRootCommitment))
+}
+
+/// An account balance leaf next to an absent account in the sorted leaf order.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BalanceProofNeighbor {
+    pub account_key: [u8; 32],
+    pub balance: u64,
+    pub merkle_proof: Vec<MerkleProofStep>,
+}
+
+impl BalanceProofNeighbor {
+    /// Returns the leaf of the neighbor account balance.
+    pub fn leaf(&self) -> [u8; 32] {
+        account_balance_leaf(self.account_key, self.balance)
+    }
+
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "account_key".to_string(),
+            Value::String(hex::encode(self.account_key)),
+        );
+        obj.insert("balance".to_string(), Value::Number(self.balance.into()));
+        obj.insert(
+            "merkle_proof".to_string(),
+            merkle_proof_json(&self.merkle_proof),
+        );
+        Value::Object(obj)
+    }
+}
+
+impl From<AccountBalanceWithProof> for BalanceProofNeighbor {
+    fn from((account_key, balance, merkle_proof): AccountBalanceWithProof) -> Self {
+        Self {
+            account_key,
+            balance,
+            merkle_proof,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BalanceProofSuccessBody {
+    pub batch_height: u64,
+    pub state_root: [u8; 32],
+    #[serde(with = "schnorr_signature_64")]
+    pub state_root_signature: [u8; 64],
+    pub state_root_cosignatures: Vec<StateRootCosignature>,
+    pub balance: Option<u64>,
+    pub merkle_proof: Vec<MerkleProofStep>,
+    pub left_neighbor: Option<BalanceProofNeighbor>,
+    pub right_neighbor: Option<BalanceProofNeighbor>,
+}
+
+impl BalanceProofSuccessBody {
+    /// Verifies the engine signature over the state root, a quorum of coordinator co-signatures
+    /// over the state root if the chain runs a federation, and then either the Merkle inclusion
+    /// proof of the account balance or, for an absent account, the inclusion proofs of the two
+    /// leaves it would sit between.
+    pub fn verify(
+        &self,
+        engine_key: [u8; 32],
+        federation: Option<&Federation>,
+        account_key: [u8; 32],
+    ) -> bool {
+        // 1 Verify the engine signature over the state root commitment.
+        let message = state_root_commitment_message(self.batch_height, self.state_root);
+        if !schnorr::verify_xonly(
+            engine_key,
+            message,
+            self.state_root_signature,
+            SchnorrSigningMode::Cube,
+        ) {
+            return false;
+        }
+
+        // 2 Verify the coordinator co-signatures over the state root.
+        if let Some(federation) = federation {
+            if !self.verify_state_root_cosignatures(federation) {
+                return false;
+            }
+        }
+
+        // 3 Verify the Merkle inclusion or non-inclusion proof.
+        match self.balance {
+            // 3.a The account is present: its leaf must be in the tree.
+            Some(balance) => verify_merkle_proof(
+                account_balance_leaf(account_key, balance),
+                &self.merkle_proof,
+                self.state_root,
+            ),
+            // 3.b The account is absent: its neighbors must be adjacent leaves around it.
+            None => self.merkle_proof.is_empty() && self.verify_non_membership(account_key),
+        }
+    }
+
+    /// Checks that a quorum of distinct federation coordinators co-signed the state root.
+    fn verify_state_root_cosignatures(&self, federation: &Federation) -> bool {
+        // 1 Collect the distinct coordinators with a valid co-signature over the state root.
+        let mut cosigners = Vec::<[u8; 32]>::new();
+        for cosignature in self.state_root_cosignatures.iter() {
+            if cosignature.state_root != self.state_root
+                || !federation.is_coordinator(cosignature.coordinator_key)
+                || cosigners.contains(&cosignature.coordinator_key)
+                || !cosignature.verify(self.batch_height)
+            {
+                continue;
+            }
+            cosigners.push(cosignature.coordinator_key);
+        }
+
+        // 2 Check the quorum.
+        cosigners.len() >= federation.threshold()
+    }
+
+    /// Checks that the account is absent by checking that its neighbors are adjacent leaves whose
+    /// keys enclose it, or that the tree ends on the side of a missing neighbor.
+    fn verify_non_membership(&self, account_key: [u8; 32]) -> bool {
+        match (&self.left_neighbor, &self.right_neighbor) {
+            // Both neighbors: the account sorts between two adjacent leaves.
+            (Some(left), Some(right)) => {
+                left.account_key < account_key
+                    && account_key < right.account_key
+                    && verify_adjacent_merkle_proofs(
+                        left.leaf(),
+                        &left.merkle_proof,
+                        right.leaf(),
+                        &right.merkle_proof,
+                        self.state_root,
+                    )
+            }
+            // Only a left neighbor: the account sorts after the last leaf.
+            (Some(left), None) => {
+                left.account_key < account_key
+                    && verify_last_merkle_proof(left.leaf(), &left.merkle_proof, self.state_root)
+            }
+            // Only a right neighbor: the account sorts before the first leaf.
+            (None, Some(right)) => {
+                account_key < right.account_key
+                    && verify_first_merkle_proof(right.leaf(), &right.merkle_proof, self.state_root)
+            }
+            // No neighbors: the tree is empty.
+            (None, None) => self.state_root == [0x00u8; 32],
+        }
+    }
+
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height.into()),
+        );
+        obj.insert(
+            "state_root".to_string(),
+            Value::String(hex::encode(self.state_root)),
+        );
+        obj.insert(
+            "state_root_signature".to_string(),
+            Value::String(hex::encode(self.state_root_signature)),
+        );
+        obj.insert(
+            "state_root_cosignatures".to_string(),
+            Value::Array(
+                self.state_root_cosignatures
+                    .iter()
+                    .map(|cosignature| cosignature.json())
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "balance".to_string(),
+            match self.balance {
+                Some(balance) => Value::Number(balance.into()),
+                None => Value::Null,
+            },
+        );
+        obj.insert(
+            "merkle_proof".to_string(),
+            merkle_proof_json(&self.merkle_proof),
+        );
+        obj.insert(
+            "left_neighbor".to_string(),
+            match &self.left_neighbor {
+                Some(neighbor) => neighbor.json(),
+                None => Value::Null,
+            },
+        );
+        obj.insert(
+            "right_neighbor".to_string(),
+            match &self.right_neighbor {
+                Some(neighbor) => neighbor.json(),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Returns a Merkle proof as a JSON array of steps.
+fn merkle_proof_json(merkle_proof: &[MerkleProofStep]) -> Value {
+    Value::Array(
+        merkle_proof
+            .iter()
+            .map(|step| {
+                let mut step_obj = Map::new();
+                step_obj.insert(
+                    "sibling".to_string(),
+                    Value::String(hex::encode(step.sibling)),
+                );
+                step_obj.insert(
+                    "sibling_is_left".to_string(),
+                    Value::Bool(step.sibling_is_left),
+                );
+                Value::Object(step_obj)
+            })
+            .collect(),
+    )
+}
+
+/// Failure cases for a Balance proof response body.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum BalanceProofResponseError {
+    DeserializeBalanceProofRequestError,
+    StateRootSigningError,
+    InvalidAccountKey,
+}
+
+impl BalanceProofResponseError {
+    pub fn json(&self) -> Value {
+        match self {
+            BalanceProofResponseError::DeserializeBalanceProofRequestError => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("deserialize_balance_proof_request_error".to_string()),
+                );
+                Value::Object(obj)
+            }
+            BalanceProofResponseError::StateRootSigningError => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("state_root_signing_error".to_string()),
+                );
+                Value::Object(obj)
+            }
+            BalanceProofResponseError::InvalidAccountKey => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("invalid_account_key".to_string()),
+                );
+                Value::Object(obj)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum BalanceProofResponseBody {
+    Ok(BalanceProofSuccessBody),
+    Err(BalanceProofResponseError),
+}
+
+impl BalanceProofResponseBody {
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(r, _)| r)
+    }
+
+    /// JSON object: success uses [`BalanceProofSuccessBody::json`], errors use [`BalanceProofResponseError::json`].
+    pub fn json(&self) -> Value {
+        match self {
+            BalanceProofResponseBody::Ok(body) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("ok".to_string()));
+                obj.insert("result".to_string(), body.json());
+                Value::Object(obj)
+            }
+            BalanceProofResponseBody::Err(e) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("err".to_string()));
+                obj.insert("error".to_string(), e.json());
+                Value::Object(obj)
+            }
+        }
+    }
+
+    pub fn ok(
+        batch_height: u64,
+        state_root: [u8; 32],
+        state_root_signature: [u8; 64],
+        state_root_cosignatures: Vec<StateRootCosignature>,
+        balance: Option<u64>,
+        merkle_proof: Vec<MerkleProofStep>,
+        left_neighbor: Option<BalanceProofNeighbor>,
+        right_neighbor: Option<BalanceProofNeighbor>,
+    ) -> Self {
+        Self::Ok(BalanceProofSuccessBody {
+            batch_height,
+            state_root,
+            state_root_signature,
+            state_root_cosignatures,
+            balance,
+            merkle_proof,
+            left_neighbor,
+            right_neighbor,
+        })
+    }
+
+    pub fn err(e: BalanceProofResponseError) -> Self {
+        Self::Err(e)
+    }
+}
@@ -0,0 +1,5 @@
+//! Balance proof TCP send path.
+
+mod request_balance_proof;
+
+pub use request_balance_proof::request_balance_proof;
@@ -0,0 +1,10 @@
+//! Balance proof TCP: wire bodies, client send path, server handler.
+
+pub mod bodies;
+pub mod client;
+pub mod server;
+
+pub use bodies::{
+    state_root_commitment_message, BalanceProofRequestBody, BalanceProofResponseBody,
+    BalanceProofResponseError, BalanceProofSuccessBody,
+};
@@ -0,0 +1,126 @@
+use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::protocol::balance_proof::{
+    state_root_commitment_message, BalanceProofRequestBody, BalanceProofResponseBody,
+    BalanceProofResponseError,
+};
+use crate::communicative::tcp::protocol::balance_proof::bodies::BalanceProofNeighbor;
+use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::public_key;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use std::sync::Arc;
+
+pub async fn handle_balance_proof_request(
+    timestamp: i64,
+    payload: &[u8],
+    keys: &KeyHolder,
+    session_pool: &SESSION_POOL,
+) -> Option<TCPPackage> {
+    // 1 Deserialize the request body.
+    let BalanceProofRequestBody { account_key } = match BalanceProofRequestBody::deserialize(
+        payload,
+    ) {
+        Some(req) => req,
+        None => {
+            let body = BalanceProofResponseBody::err(
+                BalanceProofResponseError::DeserializeBalanceProofRequestError,
+            );
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::BalanceProofProtocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
+    // 2 Reject account keys that are not valid x-only public keys.
+    if public_key::validate_xonly(&account_key).is_err() {
+        let body = BalanceProofResponseBody::err(BalanceProofResponseError::InvalidAccountKey);
+        let bytes = body.serialize().unwrap_or_default();
+        return Some(TCPPackage::new(
+            PackageKind::BalanceProofProtocol,
+            timestamp,
+            &bytes,
+        ));
+    }
+
+    // 3 Get the coin manager, the sync manager and the delta attestation pool from the session pool.
+    let (coin_manager, sync_manager, delta_attestation_pool) = {
+        let _session_pool = session_pool.lock().await;
+        (
+            Arc::clone(&_session_pool.coin_manager),
+            Arc::clone(&_session_pool.sync_manager),
+            _session_pool.delta_attestation_pool.clone(),
+        )
+    };
+
+    // 4 Resolve the batch height, the state root and the account balance proof, or the proof that
+    // the account is absent.
+    // The coin manager lock is held while reading the sync manager so that both reflect the same batch.
+    let (batch_height, state_root, balance_proof, non_membership_proof) = {
+        let _coin_manager = coin_manager.lock().await;
+        let batch_height = {
+            let _sync_manager = sync_manager.lock().await;
+            _sync_manager.cube_batch_sync_height_tip()
+        };
+        (
+            batch_height,
+            _coin_manager.account_balances_state_root(),
+            _coin_manager.account_balance_proof(account_key),
+            _coin_manager.account_balance_non_membership_proof(account_key),
+        )
+    };
+
+    // 5 Collect the coordinator co-signatures over the state root.
+    let state_root_cosignatures = match delta_attestation_pool {
+        Some(delta_attestation_pool) => {
+            let _delta_attestation_pool = delta_attestation_pool.lock().await;
+            _delta_attestation_pool.state_root_cosignatures(batch_height, state_root)
+        }
+        None => Vec::new(),
+    };
+
+    // 6 Sign the state root commitment.
+    let message = state_root_commitment_message(batch_height, state_root);
+    let response_body = match schnorr::sign(
+        keys.secp_secret_key_bytes(),
+        message,
+        SchnorrSigningMode::Cube,
+    ) {
+        Some(state_root_signature) => {
+            let (balance, merkle_proof) = match balance_proof {
+                Some((balance, merkle_proof)) => (Some(balance), merkle_proof),
+                None => (None, Vec::new()),
+            };
+            let (left_neighbor, right_neighbor) = match non_membership_proof {
+                Some((left_neighbor, right_neighbor)) => (
+                    left_neighbor.map(BalanceProofNeighbor::from),
+                    right_neighbor.map(BalanceProofNeighbor::from),
+                ),
+                None => (None, None),
+            };
+            BalanceProofResponseBody::ok(
+                batch_height,
+                state_root,
+                state_root_signature,
+                state_root_cosignatures,
+                balance,
+                merkle_proof,
+                left_neighbor,
+                right_neighbor,
+            )
+        }
+        None => BalanceProofResponseBody::err(BalanceProofResponseError::StateRootSigningError),
+    };
+
+    // 7 Serialize the response body.
+    let response_bytes = response_body.serialize().unwrap_or_default();
+
+    // 8 Construct the response package.
+    let response_package =
+        TCPPackage::new(PackageKind::BalanceProofProtocol, timestamp, &response_bytes);
+
+    // 9 Return the response package.
+    Some(response_package)
+}
@@ -0,0 +1,5 @@
+//! Balance proof TCP server (per-request handler).
+
+mod handle_balance_proof_request;
+
+pub use handle_balance_proof_request::handle_balance_proof_request;
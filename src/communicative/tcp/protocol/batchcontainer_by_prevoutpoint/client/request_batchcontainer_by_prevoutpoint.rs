@@ -1,12 +1,11 @@
 //! Send helper for Batch container-by-prevoutpoint TCP requests.
 
-use crate::communicative::peer::peer::{PeerConnection, PEER, SOCKET};
+use crate::communicative::peer::peer::{PeerConnection, PEER};
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::protocol::batchcontainer_by_prevoutpoint::{
     BatchContainerByPrevOutpointRequestBody, BatchContainerByPrevOutpointResponseBody,
 };
 use crate::communicative::tcp::request_error::RequestError;
-use crate::communicative::tcp::tcp::{self, TCPError};
 use bitcoin::OutPoint;
 use chrono::Utc;
 use std::time::Duration;
@@ -34,27 +33,22 @@ pub async fn request_batchcontainer_by_prevoutpoint(
         &payload,
     );
 
-    // 4 Send the request package.
-    let socket: SOCKET = peer
-        .socket()
-        .await
-        .ok_or(RequestError::TCPErr(TCPError::ConnErr))?;
-
-    // 5 Set the timeout.
+    // 4 Set the timeout.
     let timeout = Duration::from_millis(BATCHCONTAINER_BY_PREVOUTPOINT_REQUEST_TIMEOUT_MS);
 
-    // 6 Send the request package and get the response package.
-    let (response_package, duration) = tcp::request(&socket, request_package, Some(timeout))
+    // 5 Send the request package sealed to the peer and get the response package.
+    let (response_package, duration) = peer
+        .request(request_package, Some(timeout))
         .await
         .map_err(RequestError::TCPErr)?;
 
-    // 7 Deserialize the response payload.
+    // 6 Deserialize the response payload.
     let response_payload = match response_package.payload_len() {
         0 => return Err(RequestError::EmptyResponse),
         _ => response_package.payload(),
     };
 
-    // 8 Return the response body.
+    // 7 Return the response body.
     BatchContainerByPrevOutpointResponseBody::deserialize(&response_payload)
         .ok_or(RequestError::ResponseDeserializationError)
         .map(|r| (r, duration))
@@ -1,6 +1,8 @@
 //! Config TCP response payload (bincode body).
 
+use crate::communicative::peer::access_list::PeerAccessDenial;
 use crate::constructive::entry::entry::entry::Entry;
+use crate::operative::tasks::engine_session::exec_scheduler::errors::exec_scheduling_error::ExecSchedulingError;
 use crate::operative::tasks::engine_session::session_pool::error::exec_config_in_pool_error::ExecConfigInPoolError;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -37,6 +39,8 @@ impl ConfigSuccessBody {
 pub enum ConfigResponseError {
     DeserializeConfigRequestError,
     ExecConfigInPoolError(ExecConfigInPoolError),
+    PeerAccessDeniedError(PeerAccessDenial),
+    ExecSchedulingError(ExecSchedulingError),
 }
 
 impl ConfigResponseError {
@@ -62,6 +66,24 @@ impl ConfigResponseError {
                 );
                 Value::Object(obj)
             }
+            ConfigResponseError::PeerAccessDeniedError(denial) => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("peer_access_denied_error".to_string()),
+                );
+                obj.insert("denial".to_string(), denial.json());
+                Value::Object(obj)
+            }
+            ConfigResponseError::ExecSchedulingError(err) => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("exec_scheduling_error".to_string()),
+                );
+                obj.insert("error".to_string(), err.json());
+                Value::Object(obj)
+            }
         }
     }
 }
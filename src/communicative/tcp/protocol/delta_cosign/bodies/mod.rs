@@ -0,0 +1,9 @@
+//! Bincode wire bodies for Delta co-sign over TCP.
+
+mod request_body;
+mod response_body;
+
+pub use request_body::DeltaCosignRequestBody;
+pub use response_body::{
+    DeltaCosignResponseBody, DeltaCosignResponseError, DeltaCosignSuccessBody,
+};
@@ -0,0 +1,39 @@
+//! Delta co-sign TCP request payload (bincode body).
+
+use crate::communicative::federation::delta_attestation::DeltaCosignature;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaCosignRequestBody {
+    pub batch_height: u64,
+    pub batch_txid: [u8; 32],
+    pub cosignature: DeltaCosignature,
+    pub state_root_cosignature: Option<StateRootCosignature>,
+}
+
+impl DeltaCosignRequestBody {
+    pub fn new(
+        batch_height: u64,
+        batch_txid: [u8; 32],
+        cosignature: DeltaCosignature,
+        state_root_cosignature: Option<StateRootCosignature>,
+    ) -> Self {
+        Self {
+            batch_height,
+            batch_txid,
+            cosignature,
+            state_root_cosignature,
+        }
+    }
+
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(req, _)| req)
+    }
+}
@@ -0,0 +1,115 @@
+//! Delta co-sign TCP response payload (bincode body).
+
+use crate::communicative::federation::errors::delta_cosign_error::DeltaCosignError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DeltaCosignSuccessBody {
+    pub quorum_reached: bool,
+}
+
+impl DeltaCosignSuccessBody {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "quorum_reached".to_string(),
+            Value::Bool(self.quorum_reached),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Failure cases for a Delta co-sign response body.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum DeltaCosignResponseError {
+    DeserializeDeltaCosignRequestError,
+    FederationNotConfiguredError,
+    ArchivalManagerUnavailableError,
+    UnknownBatchError,
+    CosignRejectedError(DeltaCosignError),
+}
+
+impl DeltaCosignResponseError {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            DeltaCosignResponseError::DeserializeDeltaCosignRequestError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("deserialize_delta_cosign_request_error".to_string()),
+                );
+            }
+            DeltaCosignResponseError::FederationNotConfiguredError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("federation_not_configured_error".to_string()),
+                );
+            }
+            DeltaCosignResponseError::ArchivalManagerUnavailableError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("archival_manager_unavailable_error".to_string()),
+                );
+            }
+            DeltaCosignResponseError::UnknownBatchError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("unknown_batch_error".to_string()),
+                );
+            }
+            DeltaCosignResponseError::CosignRejectedError(error) => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("cosign_rejected_error".to_string()),
+                );
+                obj.insert("reason".to_string(), Value::String(format!("{:?}", error)));
+            }
+        }
+        Value::Object(obj)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum DeltaCosignResponseBody {
+    Ok(DeltaCosignSuccessBody),
+    Err(DeltaCosignResponseError),
+}
+
+impl DeltaCosignResponseBody {
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(r, _)| r)
+    }
+
+    /// JSON object: success uses [`DeltaCosignSuccessBody::json`], errors use [`DeltaCosignResponseError::json`].
+    pub fn json(&self) -> Value {
+        match self {
+            DeltaCosignResponseBody::Ok(body) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("ok".to_string()));
+                obj.insert("result".to_string(), body.json());
+                Value::Object(obj)
+            }
+            DeltaCosignResponseBody::Err(e) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("err".to_string()));
+                obj.insert("error".to_string(), e.json());
+                Value::Object(obj)
+            }
+        }
+    }
+
+    pub fn ok(quorum_reached: bool) -> Self {
+        Self::Ok(DeltaCosignSuccessBody { quorum_reached })
+    }
+
+    pub fn err(e: DeltaCosignResponseError) -> Self {
+        Self::Err(e)
+    }
+}
@@ -0,0 +1,5 @@
+//! Delta co-sign TCP send path.
+
+mod request_delta_cosign;
+
+pub use request_delta_cosign::request_delta_cosign;
@@ -0,0 +1,64 @@
+//! Send helper for Delta co-sign TCP requests.
+
+use crate::communicative::federation::delta_attestation::DeltaCosignature;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use crate::communicative::peer::peer::{PeerConnection, PEER};
+use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::protocol::delta_cosign::{
+    DeltaCosignRequestBody, DeltaCosignResponseBody,
+};
+use crate::communicative::tcp::request_error::RequestError;
+use chrono::Utc;
+use std::time::Duration;
+
+/// Timeout for Delta co-sign requests.
+const DELTA_COSIGN_REQUEST_TIMEOUT_MS: u64 = 5_000;
+
+/// Sends a Delta co-sign request over the peer's TCP connection.
+pub async fn request_delta_cosign(
+    peer: &PEER,
+    batch_height: u64,
+    batch_txid: [u8; 32],
+    cosignature: DeltaCosignature,
+    state_root_cosignature: Option<StateRootCosignature>,
+) -> Result<(DeltaCosignResponseBody, Duration), RequestError> {
+    // 1 Construct the request body.
+    let request_body = DeltaCosignRequestBody::new(
+        batch_height,
+        batch_txid,
+        cosignature,
+        state_root_cosignature,
+    );
+
+    // 2 Serialize the request body.
+    let payload = request_body
+        .serialize()
+        .ok_or(RequestError::RequestSerializationError)?;
+
+    // 3 Construct the request package.
+    let request_package = TCPPackage::new(
+        PackageKind::DeltaCosignProtocol,
+        Utc::now().timestamp(),
+        &payload,
+    );
+
+    // 4 Set the timeout.
+    let timeout = Duration::from_millis(DELTA_COSIGN_REQUEST_TIMEOUT_MS);
+
+    // 5 Send the request package sealed to the peer and get the response package.
+    let (response_package, duration) = peer
+        .request(request_package, Some(timeout))
+        .await
+        .map_err(RequestError::TCPErr)?;
+
+    // 6 Deserialize the response payload.
+    let response_payload = match response_package.payload_len() {
+        0 => return Err(RequestError::EmptyResponse),
+        _ => response_package.payload(),
+    };
+
+    // 7 Return the response body.
+    DeltaCosignResponseBody::deserialize(&response_payload)
+        .ok_or(RequestError::ResponseDeserializationError)
+        .map(|r| (r, duration))
+}
@@ -0,0 +1,10 @@
+//! Delta co-sign TCP: wire bodies, client send path, server handler.
+
+pub mod bodies;
+pub mod client;
+pub mod server;
+
+pub use bodies::{
+    DeltaCosignRequestBody, DeltaCosignResponseBody, DeltaCosignResponseError,
+    DeltaCosignSuccessBody,
+};
@@ -0,0 +1,157 @@
+use crate::communicative::federation::errors::delta_cosign_error::DeltaCosignError;
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::handshake::work_assignment::WorkAssignment;
+use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::protocol::delta_cosign::{
+    DeltaCosignRequestBody, DeltaCosignResponseBody, DeltaCosignResponseError,
+};
+use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+
+pub async fn handle_delta_cosign_request(
+    timestamp: i64,
+    payload: &[u8],
+    session_pool: &SESSION_POOL,
+    operator_sessions: &OPERATOR_SESSIONS,
+) -> Option<TCPPackage> {
+    // 1 Deserialize the request body.
+    let DeltaCosignRequestBody {
+        batch_height,
+        batch_txid,
+        cosignature,
+        state_root_cosignature,
+    } = match DeltaCosignRequestBody::deserialize(payload) {
+        Some(req) => req,
+        None => {
+            let body = DeltaCosignResponseBody::err(
+                DeltaCosignResponseError::DeserializeDeltaCosignRequestError,
+            );
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::DeltaCosignProtocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
+    // 2 Get the delta attestation pool and the archival manager.
+    let (delta_attestation_pool, archival_manager) = {
+        let _session_pool = session_pool.lock().await;
+        let _exec_ctx = _session_pool.exec_ctx.lock().await;
+        (
+            _session_pool.delta_attestation_pool.clone(),
+            _exec_ctx.archival_manager.clone(),
+        )
+    };
+
+    // 3 Resolve the response body.
+    let response_body = match (delta_attestation_pool, archival_manager) {
+        (None, _) => {
+            DeltaCosignResponseBody::err(DeltaCosignResponseError::FederationNotConfiguredError)
+        }
+        (_, None) => {
+            DeltaCosignResponseBody::err(DeltaCosignResponseError::ArchivalManagerUnavailableError)
+        }
+        (Some(delta_attestation_pool), Some(archival_manager)) => {
+            // 3.1 Only accept co-signatures over batches the engine has actually applied.
+            let is_known_batch = {
+                let _archival_manager = archival_manager.lock().await;
+                _archival_manager
+                    .batch_record_by_height(batch_height)
+                    .map(|batch_record| batch_record.batch_container.batch_txid() == batch_txid)
+                    .unwrap_or(false)
+            };
+
+            match is_known_batch {
+                false => DeltaCosignResponseBody::err(DeltaCosignResponseError::UnknownBatchError),
+                true => {
+                    // 3.2 Add the state root co-signature to the pool, if it is over the state root
+                    // the engine journaled for the batch.
+                    if let Some(state_root_cosignature) = state_root_cosignature {
+                        let journaled_state_root = {
+                            let _archival_manager = archival_manager.lock().await;
+                            _archival_manager.state_root_by_height(batch_height)
+                        };
+                        match journaled_state_root == Some(state_root_cosignature.state_root) {
+                            true => {
+                                let mut _delta_attestation_pool =
+                                    delta_attestation_pool.lock().await;
+                                match _delta_attestation_pool.insert_state_root_cosignature(
+                                    batch_height,
+                                    state_root_cosignature,
+                                ) {
+                                    Ok(()) | Err(DeltaCosignError::DuplicateCosignature(_)) => {}
+                                    Err(error) => eprintln!(
+                                        "Rejected the state root co-signature of batch #{}: {:?}.",
+                                        batch_height, error
+                                    ),
+                                }
+                            }
+                            false => eprintln!(
+                                "State root co-signature of batch #{} does not match the journaled state root.",
+                                batch_height
+                            ),
+                        }
+                    }
+
+                    // 3.3 Add the co-signature to the pool.
+                    let coordinator_key = cosignature.coordinator_key;
+                    let insert_result = {
+                        let mut _delta_attestation_pool = delta_attestation_pool.lock().await;
+                        _delta_attestation_pool.insert_cosignature(
+                            batch_height,
+                            batch_txid,
+                            cosignature,
+                        )
+                    };
+
+                    match insert_result {
+                        Ok(quorum_reached) => {
+                            // 3.4 Mark the co-signing work as done, dropping the rest of it once the quorum is reached.
+                            let mut _operator_sessions = operator_sessions.lock().await;
+                            _operator_sessions.complete(
+                                coordinator_key,
+                                &WorkAssignment::DeltaCosign {
+                                    batch_height,
+                                    batch_txid,
+                                },
+                            );
+                            if quorum_reached {
+                                _operator_sessions.settle(batch_height);
+                            }
+
+                            DeltaCosignResponseBody::ok(quorum_reached)
+                        }
+                        Err(error) => {
+                            // 3.5 A coordinator that had already co-signed has done the work as well.
+                            if let DeltaCosignError::DuplicateCosignature(coordinator_key) = error {
+                                let mut _operator_sessions = operator_sessions.lock().await;
+                                _operator_sessions.complete(
+                                    coordinator_key,
+                                    &WorkAssignment::DeltaCosign {
+                                        batch_height,
+                                        batch_txid,
+                                    },
+                                );
+                            }
+
+                            DeltaCosignResponseBody::err(
+                                DeltaCosignResponseError::CosignRejectedError(error),
+                            )
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // 4 Serialize the response body.
+    let response_bytes = response_body.serialize().unwrap_or_default();
+
+    // 5 Construct the response package.
+    let response_package =
+        TCPPackage::new(PackageKind::DeltaCosignProtocol, timestamp, &response_bytes);
+
+    // 6 Return the response package.
+    Some(response_package)
+}
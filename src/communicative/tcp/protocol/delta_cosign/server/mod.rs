@@ -0,0 +1,5 @@
+//! Delta co-sign TCP server (per-request handler).
+
+mod handle_delta_cosign_request;
+
+pub use handle_delta_cosign_request::handle_delta_cosign_request;
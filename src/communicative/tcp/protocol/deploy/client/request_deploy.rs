@@ -1,10 +1,9 @@
 //! Send helper for Deploy TCP requests.
 
-use crate::communicative::peer::peer::{PeerConnection, PEER, SOCKET};
+use crate::communicative::peer::peer::{PeerConnection, PEER};
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::protocol::deploy::{DeployRequestBody, DeployResponseBody};
 use crate::communicative::tcp::request_error::RequestError;
-use crate::communicative::tcp::tcp::{self, TCPError};
 use crate::constructive::entry::entry_kinds::deploy::deploy::Deploy;
 use chrono::Utc;
 use std::time::Duration;
@@ -28,14 +27,10 @@ pub async fn request_deploy(
         &payload,
     );
 
-    let socket: SOCKET = peer
-        .socket()
-        .await
-        .ok_or(RequestError::TCPErr(TCPError::ConnErr))?;
-
     let timeout = Duration::from_millis(DEPLOY_REQUEST_TIMEOUT_MS);
 
-    let (response_package, duration) = tcp::request(&socket, request_package, Some(timeout))
+    let (response_package, duration) = peer
+        .request(request_package, Some(timeout))
         .await
         .map_err(RequestError::TCPErr)?;
 
@@ -0,0 +1,7 @@
+//! Bincode wire bodies for the handshake over TCP.
+
+mod request_body;
+mod response_body;
+
+pub use request_body::HandshakeRequestBody;
+pub use response_body::{HandshakeResponseBody, HandshakeResponseError};
@@ -0,0 +1,93 @@
+//! Handshake TCP response payload (bincode body).
+
+use crate::communicative::handshake::errors::handshake_error::HandshakeError;
+use crate::communicative::handshake::session_params::SessionParams;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Failure cases for a handshake response body.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum HandshakeResponseError {
+    DeserializeHandshakeRequestError,
+    HandshakeRejectedError(HandshakeError),
+    SessionParamsSigningError,
+}
+
+impl HandshakeResponseError {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            HandshakeResponseError::DeserializeHandshakeRequestError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("deserialize_handshake_request_error".to_string()),
+                );
+            }
+            HandshakeResponseError::HandshakeRejectedError(error) => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("handshake_rejected_error".to_string()),
+                );
+                match error {
+                    HandshakeError::PeerAccessDenied(denial) => {
+                        obj.insert("reason".to_string(), denial.json());
+                    }
+                    _ => {
+                        obj.insert("reason".to_string(), Value::String(format!("{:?}", error)));
+                    }
+                }
+            }
+            HandshakeResponseError::SessionParamsSigningError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("session_params_signing_error".to_string()),
+                );
+            }
+        }
+        Value::Object(obj)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum HandshakeResponseBody {
+    Ok(SessionParams),
+    Err(HandshakeResponseError),
+}
+
+impl HandshakeResponseBody {
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(r, _)| r)
+    }
+
+    /// JSON object: success uses [`SessionParams::json`], errors use [`HandshakeResponseError::json`].
+    pub fn json(&self) -> Value {
+        match self {
+            HandshakeResponseBody::Ok(session_params) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("ok".to_string()));
+                obj.insert("result".to_string(), session_params.json());
+                Value::Object(obj)
+            }
+            HandshakeResponseBody::Err(e) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("err".to_string()));
+                obj.insert("error".to_string(), e.json());
+                Value::Object(obj)
+            }
+        }
+    }
+
+    pub fn ok(session_params: SessionParams) -> Self {
+        Self::Ok(session_params)
+    }
+
+    pub fn err(e: HandshakeResponseError) -> Self {
+        Self::Err(e)
+    }
+}
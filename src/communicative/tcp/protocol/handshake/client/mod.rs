@@ -0,0 +1,5 @@
+//! Handshake TCP send path.
+
+mod request_handshake;
+
+pub use request_handshake::request_handshake;
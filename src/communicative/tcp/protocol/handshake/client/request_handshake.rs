@@ -0,0 +1,53 @@
+//! Send helper for handshake TCP requests.
+
+use crate::communicative::handshake::announcement::HandshakeAnnouncement;
+use crate::communicative::peer::peer::{PeerConnection, PEER};
+use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::protocol::handshake::{HandshakeRequestBody, HandshakeResponseBody};
+use crate::communicative::tcp::request_error::RequestError;
+use chrono::Utc;
+use std::time::Duration;
+
+/// Timeout for handshake requests.
+const HANDSHAKE_REQUEST_TIMEOUT_MS: u64 = 5_000;
+
+/// Sends a handshake request over the peer's TCP connection.
+pub async fn request_handshake(
+    peer: &PEER,
+    announcement: &HandshakeAnnouncement,
+) -> Result<(HandshakeResponseBody, Duration), RequestError> {
+    // 1 Construct the request body.
+    let request_body = HandshakeRequestBody::new(announcement.clone());
+
+    // 2 Serialize the request body.
+    let payload = request_body
+        .serialize()
+        .ok_or(RequestError::RequestSerializationError)?;
+
+    // 3 Construct the request package.
+    let request_package = TCPPackage::new(
+        PackageKind::HandshakeProtocol,
+        Utc::now().timestamp(),
+        &payload,
+    );
+
+    // 4 Set the timeout.
+    let timeout = Duration::from_millis(HANDSHAKE_REQUEST_TIMEOUT_MS);
+
+    // 5 Send the request package sealed to the peer and get the response package.
+    let (response_package, duration) = peer
+        .request(request_package, Some(timeout))
+        .await
+        .map_err(RequestError::TCPErr)?;
+
+    // 6 Deserialize the response payload.
+    let response_payload = match response_package.payload_len() {
+        0 => return Err(RequestError::EmptyResponse),
+        _ => response_package.payload(),
+    };
+
+    // 7 Return the response body.
+    HandshakeResponseBody::deserialize(&response_payload)
+        .ok_or(RequestError::ResponseDeserializationError)
+        .map(|r| (r, duration))
+}
@@ -0,0 +1,7 @@
+//! Handshake TCP: wire bodies, client send path, server handler.
+
+pub mod bodies;
+pub mod client;
+pub mod server;
+
+pub use bodies::{HandshakeRequestBody, HandshakeResponseBody, HandshakeResponseError};
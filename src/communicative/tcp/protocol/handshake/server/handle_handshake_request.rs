@@ -0,0 +1,124 @@
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::handshake::errors::handshake_error::HandshakeError;
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::handshake::session_params::SessionParams;
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::protocol::handshake::{
+    HandshakeRequestBody, HandshakeResponseBody, HandshakeResponseError,
+};
+use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+
+pub async fn handle_handshake_request(
+    timestamp: i64,
+    payload: &[u8],
+    keys: &KeyHolder,
+    session_pool: &SESSION_POOL,
+    peer_access_list: &PEER_ACCESS_LIST,
+    operator_sessions: &OPERATOR_SESSIONS,
+) -> Option<TCPPackage> {
+    // 1 Deserialize the request body.
+    let HandshakeRequestBody { announcement } = match HandshakeRequestBody::deserialize(payload) {
+        Some(req) => req,
+        None => {
+            let body = HandshakeResponseBody::err(
+                HandshakeResponseError::DeserializeHandshakeRequestError,
+            );
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::HandshakeProtocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
+    // 2 Check the peer access list for the announcing operator.
+    let now = Utc::now().timestamp() as u64;
+    let access_check = {
+        let mut _peer_access_list = peer_access_list.lock().await;
+        _peer_access_list.check_access(announcement.operator_key, now)
+    };
+
+    // 3 Register the operator and issue its session params.
+    let response_body = match access_check {
+        Err(denial) => HandshakeResponseBody::err(HandshakeResponseError::HandshakeRejectedError(
+            HandshakeError::PeerAccessDenied(denial),
+        )),
+        Ok(()) => {
+            // 3.1 Get the engine's cube batch sync height and the federation, if any.
+            let (engine_cube_batch_sync_height, delta_attestation_pool) = {
+                let _session_pool = session_pool.lock().await;
+                let _sync_manager = _session_pool.sync_manager.lock().await;
+                (
+                    _sync_manager.cube_batch_sync_height_tip(),
+                    _session_pool.delta_attestation_pool.clone(),
+                )
+            };
+
+            // 3.2 Only federation coordinators may take on co-signing deltas.
+            let is_coordinator = match delta_attestation_pool {
+                Some(delta_attestation_pool) => {
+                    let _delta_attestation_pool = delta_attestation_pool.lock().await;
+                    _delta_attestation_pool
+                        .federation()
+                        .is_coordinator(announcement.operator_key)
+                }
+                None => false,
+            };
+            let claims_delta_cosign = announcement.capabilities.contains(&Capability::DeltaCosign);
+
+            // 3.3 Register the operator.
+            let registration = match claims_delta_cosign && !is_coordinator {
+                true => Err(HandshakeError::CapabilityNotGranted(
+                    Capability::DeltaCosign,
+                )),
+                false => {
+                    let mut _operator_sessions = operator_sessions.lock().await;
+                    _operator_sessions
+                        .register(&announcement, keys.secp_public_key_bytes(), now)
+                        .map(|session| {
+                            (
+                                session.session_id,
+                                session.protocol_version,
+                                session.features,
+                            )
+                        })
+                }
+            };
+
+            // 3.4 Sign the session params.
+            match registration {
+                Err(error) => HandshakeResponseBody::err(
+                    HandshakeResponseError::HandshakeRejectedError(error),
+                ),
+                Ok((session_id, protocol_version, features)) => match SessionParams::sign(
+                    keys,
+                    session_id,
+                    announcement.operator_key,
+                    protocol_version,
+                    features,
+                    engine_cube_batch_sync_height,
+                    now,
+                ) {
+                    Some(session_params) => HandshakeResponseBody::ok(session_params),
+                    None => HandshakeResponseBody::err(
+                        HandshakeResponseError::SessionParamsSigningError,
+                    ),
+                },
+            }
+        }
+    };
+
+    // 4 Serialize the response body.
+    let response_bytes = response_body.serialize().unwrap_or_default();
+
+    // 5 Construct the response package.
+    let response_package =
+        TCPPackage::new(PackageKind::HandshakeProtocol, timestamp, &response_bytes);
+
+    // 6 Return the response package.
+    Some(response_package)
+}
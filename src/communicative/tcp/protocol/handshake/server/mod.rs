@@ -0,0 +1,5 @@
+//! Handshake TCP server (per-request handler).
+
+mod handle_handshake_request;
+
+pub use handle_handshake_request::handle_handshake_request;
@@ -0,0 +1,7 @@
+//! Bincode wire bodies for heartbeats over TCP.
+
+mod request_body;
+mod response_body;
+
+pub use request_body::HeartbeatRequestBody;
+pub use response_body::{HeartbeatResponseBody, HeartbeatResponseError, HeartbeatSuccessBody};
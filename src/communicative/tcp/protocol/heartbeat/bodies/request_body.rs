@@ -0,0 +1,25 @@
+//! Heartbeat TCP request payload (bincode body).
+
+use crate::communicative::handshake::heartbeat::Heartbeat;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatRequestBody {
+    pub heartbeat: Heartbeat,
+}
+
+impl HeartbeatRequestBody {
+    pub fn new(heartbeat: Heartbeat) -> Self {
+        Self { heartbeat }
+    }
+
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(req, _)| req)
+    }
+}
@@ -0,0 +1,118 @@
+//! Heartbeat TCP response payload (bincode body).
+
+use crate::communicative::handshake::errors::heartbeat_error::HeartbeatError;
+use crate::communicative::handshake::heartbeat::Heartbeat;
+use crate::communicative::handshake::work_assignment::WorkAssignment;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HeartbeatSuccessBody {
+    // The engine's heartbeat in reply.
+    pub heartbeat: Heartbeat,
+
+    // Work assigned to the operator and not completed yet.
+    pub pending_work: Vec<WorkAssignment>,
+}
+
+impl HeartbeatSuccessBody {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "engine_cube_batch_sync_height".to_string(),
+            Value::Number(self.heartbeat.cube_batch_sync_height.into()),
+        );
+        obj.insert(
+            "timestamp".to_string(),
+            Value::Number(self.heartbeat.timestamp.into()),
+        );
+        obj.insert(
+            "pending_work".to_string(),
+            Value::Array(self.pending_work.iter().map(|work| work.json()).collect()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Failure cases for a heartbeat response body.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum HeartbeatResponseError {
+    DeserializeHeartbeatRequestError,
+    HeartbeatRejectedError(HeartbeatError),
+    HeartbeatSigningError,
+}
+
+impl HeartbeatResponseError {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            HeartbeatResponseError::DeserializeHeartbeatRequestError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("deserialize_heartbeat_request_error".to_string()),
+                );
+            }
+            HeartbeatResponseError::HeartbeatRejectedError(error) => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("heartbeat_rejected_error".to_string()),
+                );
+                obj.insert("reason".to_string(), Value::String(format!("{:?}", error)));
+            }
+            HeartbeatResponseError::HeartbeatSigningError => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("heartbeat_signing_error".to_string()),
+                );
+            }
+        }
+        Value::Object(obj)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum HeartbeatResponseBody {
+    Ok(HeartbeatSuccessBody),
+    Err(HeartbeatResponseError),
+}
+
+impl HeartbeatResponseBody {
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(r, _)| r)
+    }
+
+    /// JSON object: success uses [`HeartbeatSuccessBody::json`], errors use [`HeartbeatResponseError::json`].
+    pub fn json(&self) -> Value {
+        match self {
+            HeartbeatResponseBody::Ok(body) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("ok".to_string()));
+                obj.insert("result".to_string(), body.json());
+                Value::Object(obj)
+            }
+            HeartbeatResponseBody::Err(e) => {
+                let mut obj = Map::new();
+                obj.insert("status".to_string(), Value::String("err".to_string()));
+                obj.insert("error".to_string(), e.json());
+                Value::Object(obj)
+            }
+        }
+    }
+
+    pub fn ok(heartbeat: Heartbeat, pending_work: Vec<WorkAssignment>) -> Self {
+        Self::Ok(HeartbeatSuccessBody {
+            heartbeat,
+            pending_work,
+        })
+    }
+
+    pub fn err(e: HeartbeatResponseError) -> Self {
+        Self::Err(e)
+    }
+}
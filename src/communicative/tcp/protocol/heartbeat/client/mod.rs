@@ -0,0 +1,5 @@
+//! Heartbeat TCP send path.
+
+mod request_heartbeat;
+
+pub use request_heartbeat::request_heartbeat;
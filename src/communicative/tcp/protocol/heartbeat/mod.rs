@@ -0,0 +1,9 @@
+//! Heartbeat TCP: wire bodies, client send path, server handler.
+
+pub mod bodies;
+pub mod client;
+pub mod server;
+
+pub use bodies::{
+    HeartbeatRequestBody, HeartbeatResponseBody, HeartbeatResponseError, HeartbeatSuccessBody,
+};
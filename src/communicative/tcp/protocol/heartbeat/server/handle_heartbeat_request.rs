@@ -0,0 +1,78 @@
+use crate::communicative::handshake::heartbeat::Heartbeat;
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::protocol::heartbeat::{
+    HeartbeatRequestBody, HeartbeatResponseBody, HeartbeatResponseError,
+};
+use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+
+pub async fn handle_heartbeat_request(
+    timestamp: i64,
+    payload: &[u8],
+    keys: &KeyHolder,
+    session_pool: &SESSION_POOL,
+    operator_sessions: &OPERATOR_SESSIONS,
+) -> Option<TCPPackage> {
+    // 1 Deserialize the request body.
+    let HeartbeatRequestBody { heartbeat } = match HeartbeatRequestBody::deserialize(payload) {
+        Some(req) => req,
+        None => {
+            let body = HeartbeatResponseBody::err(
+                HeartbeatResponseError::DeserializeHeartbeatRequestError,
+            );
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::HeartbeatProtocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
+    // 2 Record the operator as seen and get its pending work.
+    let now = Utc::now().timestamp() as u64;
+    let heartbeat_result = {
+        let mut _operator_sessions = operator_sessions.lock().await;
+        _operator_sessions
+            .heartbeat(&heartbeat, now)
+            .map(|session| session.pending_work.clone())
+    };
+
+    // 3 Resolve the response body.
+    let response_body = match heartbeat_result {
+        Err(error) => {
+            HeartbeatResponseBody::err(HeartbeatResponseError::HeartbeatRejectedError(error))
+        }
+        Ok(pending_work) => {
+            // 3.1 Get the engine's cube batch sync height.
+            let engine_cube_batch_sync_height = {
+                let _session_pool = session_pool.lock().await;
+                let _sync_manager = _session_pool.sync_manager.lock().await;
+                _sync_manager.cube_batch_sync_height_tip()
+            };
+
+            // 3.2 Reply with the engine's own heartbeat over the same session.
+            match Heartbeat::sign(
+                keys,
+                heartbeat.session_id,
+                engine_cube_batch_sync_height,
+                now,
+            ) {
+                Some(engine_heartbeat) => HeartbeatResponseBody::ok(engine_heartbeat, pending_work),
+                None => HeartbeatResponseBody::err(HeartbeatResponseError::HeartbeatSigningError),
+            }
+        }
+    };
+
+    // 4 Serialize the response body.
+    let response_bytes = response_body.serialize().unwrap_or_default();
+
+    // 5 Construct the response package.
+    let response_package =
+        TCPPackage::new(PackageKind::HeartbeatProtocol, timestamp, &response_bytes);
+
+    // 6 Return the response package.
+    Some(response_package)
+}
@@ -0,0 +1,5 @@
+//! Heartbeat TCP server (per-request handler).
+
+mod handle_heartbeat_request;
+
+pub use handle_heartbeat_request::handle_heartbeat_request;
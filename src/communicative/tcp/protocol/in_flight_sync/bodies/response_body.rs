@@ -1,5 +1,6 @@
 //! In-flight sync TCP response payload (bincode body).
 
+use crate::communicative::federation::delta_attestation::AggregatedDeltaAttestation;
 use crate::constructive::bitcoiny::batch_container::batch_container::BatchContainer;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -46,7 +47,7 @@ impl InFlightSyncResponseError {
 #[derive(Clone, Serialize, Deserialize)]
 pub enum InFlightSyncResponseBody {
     FullySynced,
-    BatchDownload(BatchContainer),
+    BatchDownload(BatchContainer, Option<AggregatedDeltaAttestation>),
     Err(InFlightSyncResponseError),
 }
 
@@ -72,10 +73,17 @@ impl InFlightSyncResponseBody {
                 );
                 Value::Object(obj)
             }
-            InFlightSyncResponseBody::BatchDownload(batch_container) => {
+            InFlightSyncResponseBody::BatchDownload(batch_container, delta_attestation) => {
                 let mut result = Map::new();
                 result.insert("kind".to_string(), Value::String("batch_download".to_string()));
                 result.insert("batch_container".to_string(), batch_container.json());
+                result.insert(
+                    "delta_attestation".to_string(),
+                    match delta_attestation {
+                        Some(delta_attestation) => delta_attestation.json(),
+                        None => Value::Null,
+                    },
+                );
 
                 let mut obj = Map::new();
                 obj.insert("status".to_string(), Value::String("ok".to_string()));
@@ -95,8 +103,11 @@ impl InFlightSyncResponseBody {
         Self::FullySynced
     }
 
-    pub fn batch_download(batch_container: BatchContainer) -> Self {
-        Self::BatchDownload(batch_container)
+    pub fn batch_download(
+        batch_container: BatchContainer,
+        delta_attestation: Option<AggregatedDeltaAttestation>,
+    ) -> Self {
+        Self::BatchDownload(batch_container, delta_attestation)
     }
 
     pub fn err(e: InFlightSyncResponseError) -> Self {
@@ -54,7 +54,20 @@ pub async fn handle_in_flight_sync_request(
 
                     match batch_container {
                         Some(batch_container) => {
-                            InFlightSyncResponseBody::batch_download(batch_container)
+                            // Attach the co-signatures collected so far, half-aggregated, if running as part of a federation.
+                            let delta_attestation = match &_session_pool.delta_attestation_pool {
+                                Some(delta_attestation_pool) => {
+                                    let _delta_attestation_pool =
+                                        delta_attestation_pool.lock().await;
+                                    _delta_attestation_pool
+                                        .aggregated_attestation(next_batch_height)
+                                }
+                                None => None,
+                            };
+                            InFlightSyncResponseBody::batch_download(
+                                batch_container,
+                                delta_attestation,
+                            )
                         }
                         None => InFlightSyncResponseBody::err(
                             InFlightSyncResponseError::BatchContainerNotFoundError,
@@ -1,6 +1,8 @@
 //! Liftup v1 TCP response payload (bincode body).
 
+use crate::communicative::peer::access_list::PeerAccessDenial;
 use crate::constructive::entry::entry::entry::Entry;
+use crate::operative::tasks::engine_session::exec_scheduler::errors::exec_scheduling_error::ExecSchedulingError;
 use crate::operative::tasks::engine_session::session_pool::error::exec_liftup_in_pool_error::ExecLiftupInPoolError;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -39,6 +41,8 @@ impl LiftupV1SuccessBody {
 pub enum LiftupV1ResponseError {
     DeserializeLiftupV1RequestError,
     ExecLiftupInPoolError(ExecLiftupInPoolError),
+    PeerAccessDeniedError(PeerAccessDenial),
+    ExecSchedulingError(ExecSchedulingError),
 }
 
 impl LiftupV1ResponseError {
@@ -64,6 +68,24 @@ impl LiftupV1ResponseError {
                 );
                 Value::Object(obj)
             }
+            LiftupV1ResponseError::PeerAccessDeniedError(denial) => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("peer_access_denied_error".to_string()),
+                );
+                obj.insert("denial".to_string(), denial.json());
+                Value::Object(obj)
+            }
+            LiftupV1ResponseError::ExecSchedulingError(err) => {
+                let mut obj = Map::new();
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("exec_scheduling_error".to_string()),
+                );
+                obj.insert("error".to_string(), err.json());
+                Value::Object(obj)
+            }
         }
     }
 }
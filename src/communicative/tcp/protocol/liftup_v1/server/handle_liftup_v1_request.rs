@@ -1,11 +1,17 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use crate::communicative::rate_limiter::rate_limiter::RATE_LIMITER;
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::protocol::liftup_v1::{
     LiftupV1RequestBody, LiftupV1ResponseBody, LiftupV1ResponseError,
 };
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::{
+    ExecScheduler, EXEC_SCHEDULER,
+};
 use crate::operative::tasks::engine_session::session_pool::error::exec_liftup_in_pool_error::ExecLiftupInPoolError;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use chrono::Utc;
 use tokio::time::sleep;
 
 /// Backoff when the session is not ready yet (`SessionInactive` / `SessionBreak`); lock is dropped before sleeping.
@@ -18,6 +24,9 @@ pub async fn handle_liftup_v1_request(
     timestamp: i64,
     payload: &[u8],
     session_pool: &SESSION_POOL,
+    peer_access_list: &PEER_ACCESS_LIST,
+    rate_limiter: &RATE_LIMITER,
+    exec_scheduler: &EXEC_SCHEDULER,
 ) -> Option<TCPPackage> {
     // 1 Deserialize the request body.
     let LiftupV1RequestBody {
@@ -37,6 +46,45 @@ pub async fn handle_liftup_v1_request(
         }
     };
 
+    // 1.b Check the peer access list for the signing account.
+    let peer_key = liftup.root_account.account_key();
+    let access_check = {
+        let mut _peer_access_list = peer_access_list.lock().await;
+        _peer_access_list.check_access(peer_key, Utc::now().timestamp() as u64)
+    };
+    if let Err(denial) = access_check {
+        let body = LiftupV1ResponseBody::err(LiftupV1ResponseError::PeerAccessDeniedError(denial));
+        let bytes = body.serialize().unwrap_or_default();
+        return Some(TCPPackage::new(
+            PackageKind::LiftupV1Protocol,
+            timestamp,
+            &bytes,
+        ));
+    }
+
+    // 1.c Check the rate limit of the signing account before contending for the session pool.
+    let rate_check = {
+        let mut _rate_limiter = rate_limiter.lock().await;
+        _rate_limiter.check_peer(peer_key, Instant::now())
+    };
+    if let Err(err) = rate_check {
+        return Some(TCPPackage::rate_limited(timestamp, err));
+    }
+
+    // 1.d Wait for an execution slot; under load, slots are handed out by priority and capped per account.
+    let _exec_ticket = match ExecScheduler::admit(exec_scheduler, peer_key, None).await {
+        Ok(exec_ticket) => exec_ticket,
+        Err(err) => {
+            let body = LiftupV1ResponseBody::err(LiftupV1ResponseError::ExecSchedulingError(err));
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::LiftupV1Protocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
     // 2 Execute the liftup in the session pool; up to 4 attempts with 500ms between tries on settle errors.
     let mut response: Option<LiftupV1ResponseBody> = None;
     for attempt in 1..=MAX_EXEC_ATTEMPTS {
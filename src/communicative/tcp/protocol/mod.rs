@@ -1,8 +1,12 @@
 //! TCP application protocols (ping, liftup v1, …).
 
+pub mod balance_proof;
 pub mod batchrecord;
 pub mod batchcontainer;
 pub mod batchcontainer_by_prevoutpoint;
+pub mod delta_cosign;
+pub mod handshake;
+pub mod heartbeat;
 pub mod in_flight_sync;
 pub mod liftup_v1;
 pub mod r#move;
@@ -1,10 +1,9 @@
 //! Send helper for Move TCP requests.
 
-use crate::communicative::peer::peer::{PeerConnection, PEER, SOCKET};
+use crate::communicative::peer::peer::{PeerConnection, PEER};
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::protocol::r#move::{MoveRequestBody, MoveResponseBody};
 use crate::communicative::tcp::request_error::RequestError;
-use crate::communicative::tcp::tcp::{self, TCPError};
 use crate::constructive::entry::entry_kinds::r#move::r#move::Move;
 use chrono::Utc;
 use std::time::Duration;
@@ -33,27 +32,22 @@ pub async fn request_move(
         &payload,
     );
 
-    // 4 Send the request package.
-    let socket: SOCKET = peer
-        .socket()
-        .await
-        .ok_or(RequestError::TCPErr(TCPError::ConnErr))?;
-
-    // 5 Set timeout.
+    // 4 Set timeout.
     let timeout = Duration::from_millis(MOVE_REQUEST_TIMEOUT_MS);
 
-    // 6 Send request and receive response package.
-    let (response_package, duration) = tcp::request(&socket, request_package, Some(timeout))
+    // 5 Send the request package sealed to the peer and receive the response package.
+    let (response_package, duration) = peer
+        .request(request_package, Some(timeout))
         .await
         .map_err(RequestError::TCPErr)?;
 
-    // 7 Deserialize response payload.
+    // 6 Deserialize response payload.
     let response_payload = match response_package.payload_len() {
         0 => return Err(RequestError::EmptyResponse),
         _ => response_package.payload(),
     };
 
-    // 8 Return response body.
+    // 7 Return response body.
     MoveResponseBody::deserialize(&response_payload)
         .ok_or(RequestError::ResponseDeserializationError)
         .map(|r| (r, duration))
@@ -1,11 +1,17 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use crate::communicative::rate_limiter::rate_limiter::RATE_LIMITER;
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::protocol::r#move::{
     MoveRequestBody, MoveResponseBody, MoveResponseError,
 };
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::{
+    ExecScheduler, EXEC_SCHEDULER,
+};
 use crate::operative::tasks::engine_session::session_pool::error::exec_move_in_pool_error::ExecMoveInPoolError;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use chrono::Utc;
 use tokio::time::sleep;
 
 /// Backoff when the session is not ready yet (`SessionInactive` / `SessionBreak`); lock is dropped before sleeping.
@@ -18,6 +24,9 @@ pub async fn handle_move_request(
     timestamp: i64,
     payload: &[u8],
     session_pool: &SESSION_POOL,
+    peer_access_list: &PEER_ACCESS_LIST,
+    rate_limiter: &RATE_LIMITER,
+    exec_scheduler: &EXEC_SCHEDULER,
 ) -> Option<TCPPackage> {
     // 1 Deserialize request body.
     let MoveRequestBody {
@@ -36,6 +45,45 @@ pub async fn handle_move_request(
         }
     };
 
+    // 1.b Check the peer access list for the signing account.
+    let peer_key = move_entry.from.account_key();
+    let access_check = {
+        let mut _peer_access_list = peer_access_list.lock().await;
+        _peer_access_list.check_access(peer_key, Utc::now().timestamp() as u64)
+    };
+    if let Err(denial) = access_check {
+        let body = MoveResponseBody::err(MoveResponseError::PeerAccessDeniedError(denial));
+        let bytes = body.serialize().unwrap_or_default();
+        return Some(TCPPackage::new(
+            PackageKind::MoveProtocol,
+            timestamp,
+            &bytes,
+        ));
+    }
+
+    // 1.c Check the rate limit of the signing account before contending for the session pool.
+    let rate_check = {
+        let mut _rate_limiter = rate_limiter.lock().await;
+        _rate_limiter.check_peer(peer_key, Instant::now())
+    };
+    if let Err(err) = rate_check {
+        return Some(TCPPackage::rate_limited(timestamp, err));
+    }
+
+    // 1.d Wait for an execution slot; under load, slots are handed out by priority and capped per account.
+    let _exec_ticket = match ExecScheduler::admit(exec_scheduler, peer_key, None).await {
+        Ok(exec_ticket) => exec_ticket,
+        Err(err) => {
+            let body = MoveResponseBody::err(MoveResponseError::ExecSchedulingError(err));
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::MoveProtocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
     // 2 Execute move in session pool with settle retries.
     let mut response: Option<MoveResponseBody> = None;
     for attempt in 1..=MAX_EXEC_ATTEMPTS {
@@ -68,6 +116,13 @@ pub async fn handle_move_request(
                     continue;
                 }
 
+                // An invalid signature is a protocol violation; repeated ones earn a temporary ban.
+                if matches!(err, ExecMoveInPoolError::MoveBLSVerifyError(_)) {
+                    let mut _peer_access_list = peer_access_list.lock().await;
+                    _peer_access_list
+                        .record_protocol_violation(peer_key, Utc::now().timestamp() as u64);
+                }
+
                 response = Some(MoveResponseBody::err(
                     MoveResponseError::ExecMoveInPoolError(err),
                 ));
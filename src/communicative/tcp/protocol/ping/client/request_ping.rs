@@ -1,9 +1,8 @@
 //! Send helper for ping TCP requests.
 
-use crate::communicative::peer::peer::{PeerConnection, PEER, SOCKET};
+use crate::communicative::peer::peer::{PeerConnection, PEER};
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::request_error::RequestError;
-use crate::communicative::tcp::tcp::{self, TCPError};
 use chrono::Utc;
 use std::time::Duration;
 
@@ -17,14 +16,10 @@ pub async fn request_ping(peer: &PEER) -> Result<Duration, RequestError> {
         TCPPackage::new(kind, timestamp, &payload)
     };
 
-    let socket: SOCKET = peer
-        .socket()
-        .await
-        .ok_or(RequestError::TCPErr(TCPError::ConnErr))?;
-
     let timeout = Duration::from_millis(3_000);
 
-    let (response_package, duration) = tcp::request(&socket, request_package, Some(timeout))
+    let (response_package, duration) = peer
+        .request(request_package, Some(timeout))
         .await
         .map_err(RequestError::TCPErr)?;
 
@@ -1,11 +1,17 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use crate::communicative::rate_limiter::rate_limiter::RATE_LIMITER;
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
 use crate::communicative::tcp::protocol::swapout::{
     SwapoutRequestBody, SwapoutResponseBody, SwapoutResponseError,
 };
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::{
+    ExecScheduler, EXEC_SCHEDULER,
+};
 use crate::operative::tasks::engine_session::session_pool::error::exec_swapout_in_pool_error::ExecSwapoutInPoolError;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use chrono::Utc;
 use tokio::time::sleep;
 
 const SESSION_SETTLE_MS: u64 = 500;
@@ -15,6 +21,9 @@ pub async fn handle_swapout_request(
     timestamp: i64,
     payload: &[u8],
     session_pool: &SESSION_POOL,
+    peer_access_list: &PEER_ACCESS_LIST,
+    rate_limiter: &RATE_LIMITER,
+    exec_scheduler: &EXEC_SCHEDULER,
 ) -> Option<TCPPackage> {
     let SwapoutRequestBody {
         swapout,
@@ -32,6 +41,45 @@ pub async fn handle_swapout_request(
         }
     };
 
+    // Check the peer access list for the signing account.
+    let peer_key = swapout.root_account.account_key();
+    let access_check = {
+        let mut _peer_access_list = peer_access_list.lock().await;
+        _peer_access_list.check_access(peer_key, Utc::now().timestamp() as u64)
+    };
+    if let Err(denial) = access_check {
+        let body = SwapoutResponseBody::err(SwapoutResponseError::PeerAccessDeniedError(denial));
+        let bytes = body.serialize().unwrap_or_default();
+        return Some(TCPPackage::new(
+            PackageKind::SwapoutProtocol,
+            timestamp,
+            &bytes,
+        ));
+    }
+
+    // Check the rate limit of the signing account before contending for the session pool.
+    let rate_check = {
+        let mut _rate_limiter = rate_limiter.lock().await;
+        _rate_limiter.check_peer(peer_key, Instant::now())
+    };
+    if let Err(err) = rate_check {
+        return Some(TCPPackage::rate_limited(timestamp, err));
+    }
+
+    // 1.d Wait for an execution slot; under load, slots are handed out by priority and capped per account.
+    let _exec_ticket = match ExecScheduler::admit(exec_scheduler, peer_key, None).await {
+        Ok(exec_ticket) => exec_ticket,
+        Err(err) => {
+            let body = SwapoutResponseBody::err(SwapoutResponseError::ExecSchedulingError(err));
+            let bytes = body.serialize().unwrap_or_default();
+            return Some(TCPPackage::new(
+                PackageKind::SwapoutProtocol,
+                timestamp,
+                &bytes,
+            ));
+        }
+    };
+
     let mut response: Option<SwapoutResponseBody> = None;
     for attempt in 1..=MAX_EXEC_ATTEMPTS {
         let attempt_result = {
@@ -0,0 +1,167 @@
+use super::replay_error::TCPReplayError;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Maximum distance of a package timestamp from the local clock, in either direction.
+pub const MAX_PACKAGE_AGE_SECS: i64 = 60;
+
+/// Number of sequence numbers below the highest one seen that may still arrive out of order.
+pub const REPLAY_WINDOW_SIZE: u64 = 128;
+
+/// Last sequence number handed out by this process.
+static LAST_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the next sequence number to seal a package with.
+///
+/// Sequence numbers start from the current time in microseconds, so that they keep increasing across
+/// restarts without persisting a counter, and are strictly increasing within the process.
+pub fn next_sequence() -> u64 {
+    let now = Utc::now().timestamp_micros().max(0) as u64;
+    let previous = LAST_SEQUENCE
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap_or(0);
+    now.max(previous + 1)
+}
+
+/// Checks a package timestamp is within the freshness window of the local clock.
+pub fn check_freshness(timestamp: i64, now: i64) -> Result<(), TCPReplayError> {
+    match (timestamp - now).abs() > MAX_PACKAGE_AGE_SECS {
+        true => Err(TCPReplayError::StalePackage { timestamp, now }),
+        false => Ok(()),
+    }
+}
+
+/// Sliding window of the sequence numbers received from a peer.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct ReplayWindow {
+    // The highest sequence number accepted so far.
+    highest: u64,
+
+    // Bit `i` is set if `highest - i` was accepted.
+    bitmap: u128,
+}
+
+impl ReplayWindow {
+    /// Constructs an empty replay window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the highest sequence number accepted so far.
+    pub fn highest(&self) -> u64 {
+        self.highest
+    }
+
+    /// Checks a sequence number was not accepted before, without recording it.
+    pub fn check(&self, sequence: u64) -> Result<(), TCPReplayError> {
+        // 1 Sequence numbers above the window are always new.
+        if sequence > self.highest || self.bitmap == 0 {
+            return Ok(());
+        }
+
+        // 2 Sequence numbers below the window can no longer be told apart from replays.
+        let offset = self.highest - sequence;
+        if offset >= REPLAY_WINDOW_SIZE {
+            return Err(TCPReplayError::SequenceTooOld {
+                sequence,
+                highest: self.highest,
+            });
+        }
+
+        // 3 Sequence numbers within the window must not have been accepted yet.
+        match self.bitmap & (1u128 << offset) {
+            0 => Ok(()),
+            _ => Err(TCPReplayError::DuplicateSequence(sequence)),
+        }
+    }
+
+    /// Accepts a sequence number, recording it, or rejects it if it was accepted before.
+    pub fn accept(&mut self, sequence: u64) -> Result<(), TCPReplayError> {
+        // 1 Check the sequence number.
+        self.check(sequence)?;
+
+        // 2 Record it, sliding the window forward if it is the highest.
+        if self.bitmap == 0 {
+            self.highest = sequence;
+            self.bitmap = 1;
+        } else if sequence > self.highest {
+            let shift = sequence - self.highest;
+            self.bitmap = match shift >= REPLAY_WINDOW_SIZE {
+                true => 1,
+                false => (self.bitmap << shift) | 1,
+            };
+            self.highest = sequence;
+        } else {
+            self.bitmap |= 1u128 << (self.highest - sequence);
+        }
+
+        Ok(())
+    }
+}
+
+/// Per-peer replay protection of inbound packages.
+///
+/// A package is admitted only if its timestamp is fresh and its sequence number was not seen from the
+/// same peer before. Since stale packages are refused regardless, the window of a peer is dropped once
+/// its latest package ages out of the freshness window.
+#[derive(Default)]
+pub struct ReplayGuard {
+    // Replay window and latest package timestamp by peer key.
+    windows: HashMap<PeerKey, (ReplayWindow, i64)>,
+}
+
+/// Guarded 'ReplayGuard'.
+#[allow(non_camel_case_types)]
+pub type REPLAY_GUARD = Arc<Mutex<ReplayGuard>>;
+
+impl ReplayGuard {
+    pub fn new() -> REPLAY_GUARD {
+        Arc::new(Mutex::new(ReplayGuard::default()))
+    }
+
+    /// Admits or rejects a package from a peer.
+    pub fn check(
+        &mut self,
+        peer_key: PeerKey,
+        sequence: u64,
+        timestamp: i64,
+        now: i64,
+    ) -> Result<(), TCPReplayError> {
+        // 1 Refuse stale packages.
+        check_freshness(timestamp, now)?;
+
+        // 2 Drop the windows of peers gone quiet.
+        self.prune(now);
+
+        // 3 Refuse replayed packages.
+        let (window, latest_timestamp) = self.windows.entry(peer_key).or_default();
+        window.accept(sequence)?;
+        *latest_timestamp = (*latest_timestamp).max(timestamp);
+
+        Ok(())
+    }
+
+    /// Drops the windows of the peers whose latest package is no longer fresh.
+    pub fn prune(&mut self, now: i64) {
+        self.windows
+            .retain(|_, (_, latest_timestamp)| *latest_timestamp + MAX_PACKAGE_AGE_SECS >= now);
+    }
+
+    /// Returns the number of peers tracked.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Whether no peers are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+}
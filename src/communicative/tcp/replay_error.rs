@@ -0,0 +1,7 @@
+/// Errors admitting a package that may be stale or replayed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TCPReplayError {
+    StalePackage { timestamp: i64, now: i64 },
+    SequenceTooOld { sequence: u64, highest: u64 },
+    DuplicateSequence(u64),
+}
@@ -1,11 +1,24 @@
 use super::server::{IDLE_CLIENT_TIMEOUT, PAYLOAD_READ_TIMEOUT, PAYLOAD_WRITE_TIMEOUT};
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::handshake::protocol_feature::ProtocolFeature;
+use crate::communicative::inbound::inbound_gate::admit_inbound;
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
 use crate::communicative::peer::peer::SOCKET;
+use crate::communicative::rate_limiter::rate_limiter::RATE_LIMITER;
+use crate::communicative::tcp::compression;
+use crate::communicative::tcp::envelope;
+use crate::communicative::tcp::noise::{NoiseHandshake, NoiseRole, NoiseTransport};
 use crate::communicative::tcp::package::{PackageKind, TCPPackage};
+use crate::communicative::tcp::replay::{self, REPLAY_GUARD};
 use crate::communicative::tcp::tcp;
+use crate::communicative::traffic::traffic_meter::{record_traffic, TrafficDirection};
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::operative::run_args::operating_kind::OperatingKind;
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::EXEC_SCHEDULER;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
 use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+use std::net::IpAddr;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::Instant;
@@ -13,11 +26,24 @@ use tokio::time::Instant;
 pub async fn handle_socket(
     socket: &SOCKET,
     alive: Option<&Arc<Mutex<bool>>>,
+    peer_ip: IpAddr,
     operating_kind: OperatingKind,
     _keys: &KeyHolder,
     session_pool: &SESSION_POOL,
     archival_manager: &Option<ARCHIVAL_MANAGER>,
+    peer_access_list: &PEER_ACCESS_LIST,
+    rate_limiter: &RATE_LIMITER,
+    exec_scheduler: &EXEC_SCHEDULER,
+    operator_sessions: &OPERATOR_SESSIONS,
+    replay_guard: &REPLAY_GUARD,
 ) {
+    // Local Nostr keys, which packages are sealed to and responses are sealed with.
+    let local_keys = _keys.nostr_key_pair();
+
+    // Noise handshake in progress and Noise session of the connection, if any.
+    let mut noise_handshake: Option<NoiseHandshake> = None;
+    let mut noise_transport: Option<NoiseTransport> = None;
+
     loop {
         let package = {
             let mut _socket = socket.lock().await;
@@ -86,16 +112,210 @@ pub async fn handle_socket(
 
             TCPPackage::new(package_kind, timestamp, &payload_bufer)
         };
+        let package_len = package.wire_len();
+
+        // Reject the package in place if the peer IP exceeds its rate limit.
+        let ip_check = {
+            let mut _rate_limiter = rate_limiter.lock().await;
+            _rate_limiter.check_ip(peer_ip, std::time::Instant::now())
+        };
+        if let Err(err) = ip_check {
+            record_traffic(
+                None,
+                package.kind(),
+                TrafficDirection::Received,
+                package_len,
+            );
+            let rate_limited_package = TCPPackage::rate_limited(package.timestamp(), err);
+            record_traffic(
+                None,
+                PackageKind::RateLimited,
+                TrafficDirection::Sent,
+                rate_limited_package.wire_len(),
+            );
+            let _ = rate_limited_package
+                .deliver(socket, Some(PAYLOAD_WRITE_TIMEOUT))
+                .await;
+            continue;
+        }
+
+        // Answer Noise handshake messages in place. A first message starts a fresh handshake, replacing
+        // the Noise session of the connection, and the third one completes it.
+        if package.kind() == PackageKind::NoiseHandshake {
+            record_traffic(
+                None,
+                PackageKind::NoiseHandshake,
+                TrafficDirection::Received,
+                package_len,
+            );
+            let response_payload = match noise_handshake.take() {
+                // -> e
+                None => {
+                    noise_transport = None;
+                    let mut handshake = match NoiseHandshake::new(
+                        NoiseRole::Responder,
+                        _keys.secp_secret_key_bytes(),
+                    ) {
+                        Ok(handshake) => handshake,
+                        Err(_) => continue,
+                    };
+                    if handshake.read_message(&package.payload()).is_err() {
+                        continue;
+                    }
+                    let message = match handshake.write_message() {
+                        Ok(message) => message,
+                        Err(_) => continue,
+                    };
+                    noise_handshake = Some(handshake);
+                    message
+                }
+                // -> s, se
+                Some(mut handshake) => {
+                    if handshake.read_message(&package.payload()).is_err() {
+                        continue;
+                    }
+                    noise_transport = match handshake.into_transport() {
+                        Ok(transport) => Some(transport),
+                        Err(_) => continue,
+                    };
+                    Vec::new()
+                }
+            };
+
+            let response_package = TCPPackage::new(
+                PackageKind::NoiseHandshake,
+                package.timestamp(),
+                &response_payload,
+            );
+            record_traffic(
+                None,
+                PackageKind::NoiseHandshake,
+                TrafficDirection::Sent,
+                response_package.wire_len(),
+            );
+            let _ = response_package
+                .deliver(socket, Some(PAYLOAD_WRITE_TIMEOUT))
+                .await;
+            continue;
+        }
+
+        // Open sealed and Noise-encrypted packages. Plaintext packages other than pings are refused, so
+        // that payloads are never exchanged in plaintext.
+        let over_noise = package.kind() == PackageKind::NoiseTransport;
+        let package_kind = package.kind();
+        let package_timestamp = package.timestamp();
+        let opened = match package.kind() {
+            PackageKind::Envelope => match envelope::open(&local_keys, &package) {
+                Ok((sender_key, sequence, package)) => {
+                    // Refuse stale envelopes and envelopes already received from the sender, on any
+                    // connection.
+                    let replay_check = {
+                        let mut _replay_guard = replay_guard.lock().await;
+                        _replay_guard.check(
+                            sender_key,
+                            sequence,
+                            package.timestamp(),
+                            Utc::now().timestamp(),
+                        )
+                    };
+                    match replay_check {
+                        Ok(()) => Some((package, Some(sender_key))),
+                        Err(_) => None,
+                    }
+                }
+                Err(_) => None,
+            },
+            PackageKind::NoiseTransport => {
+                match noise_transport
+                    .as_mut()
+                    .map(|transport| (transport.remote_key(), transport.open(&package)))
+                {
+                    // Noise nonces already refuse replays within the session, which is bound to the
+                    // connection, so only stale packages are left to refuse.
+                    Some((remote_key, Ok(package))) => {
+                        match replay::check_freshness(package.timestamp(), Utc::now().timestamp()) {
+                            Ok(()) => Some((package, Some(remote_key))),
+                            Err(_) => None,
+                        }
+                    }
+                    // Answer with an empty package when there is no session or it broke, so that the
+                    // peer falls back to envelopes instead of timing out.
+                    _ => {
+                        let _ =
+                            TCPPackage::new(PackageKind::NoiseTransport, package.timestamp(), &[])
+                                .deliver(socket, Some(PAYLOAD_WRITE_TIMEOUT))
+                                .await;
+                        None
+                    }
+                }
+            }
+            PackageKind::Ping => Some((package, None)),
+            _ => None,
+        };
+
+        // Decompress compressed packages.
+        let opened = opened.and_then(|(package, sender_key)| {
+            compression::decompress(package)
+                .ok()
+                .map(|package| (package, sender_key))
+        });
+
+        // Account the package to its sender under the type of the package it carries, or as
+        // unattributed if it could not be opened.
+        let (package, sender_key) = match opened {
+            Some((package, sender_key)) => {
+                record_traffic(
+                    sender_key,
+                    package.kind(),
+                    TrafficDirection::Received,
+                    package_len,
+                );
+                (package, sender_key)
+            }
+            None => {
+                record_traffic(None, package_kind, TrafficDirection::Received, package_len);
+                continue;
+            }
+        };
+
+        // Take a processing slot, or shed the package if processing is saturated. High-priority
+        // packages wait for a slot instead, holding up the rest of the connection.
+        let _inbound_permit = match admit_inbound(package.kind()).await {
+            Ok(permit) => permit,
+            Err(err) => {
+                let overloaded_package = TCPPackage::rate_limited(package_timestamp, err);
+                record_traffic(
+                    sender_key,
+                    PackageKind::RateLimited,
+                    TrafficDirection::Sent,
+                    overloaded_package.wire_len(),
+                );
+                let _ = overloaded_package
+                    .deliver(socket, Some(PAYLOAD_WRITE_TIMEOUT))
+                    .await;
+                continue;
+            }
+        };
 
         let session_pool = Arc::clone(session_pool);
         let archival_manager = archival_manager.clone();
         handle_package(
             package,
+            sender_key,
+            &local_keys,
+            match over_noise {
+                true => noise_transport.as_mut(),
+                false => None,
+            },
             socket,
             operating_kind,
             _keys,
             &session_pool,
             &archival_manager,
+            peer_access_list,
+            rate_limiter,
+            exec_scheduler,
+            operator_sessions,
         )
         .await;
     }
@@ -108,11 +328,18 @@ pub async fn handle_socket(
 
 pub async fn handle_package(
     package: TCPPackage,
+    sender_key: Option<[u8; 32]>,
+    local_keys: &nostr_sdk::Keys,
+    noise_transport: Option<&mut NoiseTransport>,
     socket: &SOCKET,
     operating_kind: OperatingKind,
     _keys: &KeyHolder,
     session_pool: &SESSION_POOL,
     archival_manager: &Option<ARCHIVAL_MANAGER>,
+    peer_access_list: &PEER_ACCESS_LIST,
+    rate_limiter: &RATE_LIMITER,
+    exec_scheduler: &EXEC_SCHEDULER,
+    operator_sessions: &OPERATOR_SESSIONS,
 ) {
     let response_package_ = {
         match operating_kind {
@@ -130,6 +357,9 @@ pub async fn handle_package(
                         package.timestamp(),
                         &package.payload(),
                         &session_pool,
+                        peer_access_list,
+                        rate_limiter,
+                        exec_scheduler,
                     )
                     .await
                 }
@@ -139,6 +369,9 @@ pub async fn handle_package(
                         package.timestamp(),
                         &package.payload(),
                         &session_pool,
+                        peer_access_list,
+                        rate_limiter,
+                        exec_scheduler,
                     )
                     .await
                 }
@@ -148,6 +381,9 @@ pub async fn handle_package(
                         package.timestamp(),
                         &package.payload(),
                         &session_pool,
+                        peer_access_list,
+                        rate_limiter,
+                        exec_scheduler,
                     )
                     .await
                 }
@@ -157,6 +393,9 @@ pub async fn handle_package(
                         package.timestamp(),
                         &package.payload(),
                         &session_pool,
+                        peer_access_list,
+                        rate_limiter,
+                        exec_scheduler,
                     )
                     .await
                 }
@@ -166,6 +405,52 @@ pub async fn handle_package(
                         package.timestamp(),
                         &package.payload(),
                         &session_pool,
+                        peer_access_list,
+                        rate_limiter,
+                        exec_scheduler,
+                    )
+                    .await
+                }
+                PackageKind::BalanceProofProtocol => {
+                    let session_pool = Arc::clone(session_pool);
+                    crate::communicative::tcp::protocol::balance_proof::server::handle_balance_proof_request(
+                        package.timestamp(),
+                        &package.payload(),
+                        _keys,
+                        &session_pool,
+                    )
+                    .await
+                }
+                PackageKind::DeltaCosignProtocol => {
+                    let session_pool = Arc::clone(session_pool);
+                    crate::communicative::tcp::protocol::delta_cosign::server::handle_delta_cosign_request(
+                        package.timestamp(),
+                        &package.payload(),
+                        &session_pool,
+                        operator_sessions,
+                    )
+                    .await
+                }
+                PackageKind::HandshakeProtocol => {
+                    let session_pool = Arc::clone(session_pool);
+                    crate::communicative::tcp::protocol::handshake::server::handle_handshake_request(
+                        package.timestamp(),
+                        &package.payload(),
+                        _keys,
+                        &session_pool,
+                        peer_access_list,
+                        operator_sessions,
+                    )
+                    .await
+                }
+                PackageKind::HeartbeatProtocol => {
+                    let session_pool = Arc::clone(session_pool);
+                    crate::communicative::tcp::protocol::heartbeat::server::handle_heartbeat_request(
+                        package.timestamp(),
+                        &package.payload(),
+                        _keys,
+                        &session_pool,
+                        operator_sessions,
                     )
                     .await
                 }
@@ -205,6 +490,11 @@ pub async fn handle_package(
                     )
                     .await
                 }
+                PackageKind::RateLimited
+                | PackageKind::Envelope
+                | PackageKind::NoiseHandshake
+                | PackageKind::NoiseTransport
+                | PackageKind::Compressed => None,
             },
             OperatingKind::Node => return,
         }
@@ -215,6 +505,50 @@ pub async fn handle_package(
         None => TCPPackage::new(package.kind(), package.timestamp(), &[]),
     };
 
+    // Compress the response if the sender negotiated compression in the handshake.
+    let response_kind = response_package.kind();
+    let compress_response = match sender_key {
+        Some(sender_key) => {
+            let _operator_sessions = operator_sessions.lock().await;
+            _operator_sessions
+                .session(sender_key)
+                .map(|session| session.has_feature(ProtocolFeature::Compression))
+                .unwrap_or(false)
+        }
+        None => false,
+    };
+    let response_package = match compress_response {
+        true => compression::compress(response_package),
+        false => response_package,
+    };
+
+    // Encrypt the response over the Noise session the package came through, or seal it to the sender
+    // of a sealed package.
+    let response_package = match (noise_transport, sender_key) {
+        (Some(noise_transport), _) => match noise_transport.seal(&response_package) {
+            Ok(sealed_package) => sealed_package,
+            Err(_) => return,
+        },
+        (None, Some(sender_key)) => {
+            match envelope::seal(
+                local_keys,
+                sender_key,
+                replay::next_sequence(),
+                &response_package,
+            ) {
+                Ok(sealed_package) => sealed_package,
+                Err(_) => return,
+            }
+        }
+        (None, None) => response_package,
+    };
+    record_traffic(
+        sender_key,
+        response_kind,
+        TrafficDirection::Sent,
+        response_package.wire_len(),
+    );
+
     let _ = response_package
         .deliver(socket, Some(PAYLOAD_WRITE_TIMEOUT))
         .await;
@@ -1,7 +1,12 @@
 use super::connection::handle_socket;
 use super::super::tcp::port_number;
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use crate::communicative::rate_limiter::rate_limiter::RATE_LIMITER;
+use crate::communicative::tcp::replay::{ReplayGuard, REPLAY_GUARD};
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::operative::run_args::{chain::Chain, operating_kind::OperatingKind};
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::EXEC_SCHEDULER;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
 use crate::transmutative::key::KeyHolder;
 use colored::Colorize;
@@ -26,6 +31,10 @@ pub async fn run(
     chain: Chain,
     keys: Arc<KeyHolder>,
     session_pool: &SESSION_POOL,
+    peer_access_list: &PEER_ACCESS_LIST,
+    rate_limiter: &RATE_LIMITER,
+    exec_scheduler: &EXEC_SCHEDULER,
+    operator_sessions: &OPERATOR_SESSIONS,
 ) {
     let port_number = port_number(chain);
     let addr = format!("{}:{}", "0.0.0.0", port_number);
@@ -44,9 +53,12 @@ pub async fn run(
         _exec_ctx.archival_manager.clone()
     };
 
+    // Replay protection shared across connections, so that a package cannot be replayed on another one.
+    let replay_guard: REPLAY_GUARD = ReplayGuard::new();
+
     match operating_kind {
         OperatingKind::Engine => loop {
-            let (socket_, _) = match listener.accept().await {
+            let (socket_, peer_addr) = match listener.accept().await {
                 Ok(conn) => (conn.0, conn.1),
                 Err(_) => continue,
             };
@@ -55,15 +67,26 @@ pub async fn run(
             let keys = Arc::clone(&keys);
             let session_pool = Arc::clone(session_pool);
             let archival_manager = archival_manager.clone();
+            let peer_access_list = Arc::clone(peer_access_list);
+            let rate_limiter = Arc::clone(rate_limiter);
+            let exec_scheduler = Arc::clone(exec_scheduler);
+            let operator_sessions = Arc::clone(operator_sessions);
+            let replay_guard = Arc::clone(&replay_guard);
 
             tokio::spawn(async move {
                 handle_socket(
                     &socket,
                     None,
+                    peer_addr.ip(),
                     operating_kind,
                     &keys,
                     &session_pool,
                     &archival_manager,
+                    &peer_access_list,
+                    &rate_limiter,
+                    &exec_scheduler,
+                    &operator_sessions,
+                    &replay_guard,
                 )
                 .await;
             });
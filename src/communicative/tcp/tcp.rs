@@ -1,9 +1,15 @@
+use super::compression_error::TCPCompressionError;
+use super::envelope_error::TCPEnvelopeError;
+use super::noise_error::TCPNoiseError;
 use super::package::{PackageKind, TCPPackage};
+use super::replay_error::TCPReplayError;
 use crate::communicative::nns::client::NNSClient;
 use crate::communicative::peer::peer::SOCKET;
+use crate::communicative::rate_limiter::errors::rate_limit_error::RateLimitError;
 use crate::transmutative::key::ToNostrKeyStr;
 use crate::{inscriptive::baked, operative::run_args::chain::Chain};
 use easy_upnp::{add_ports, PortMappingProtocol, UpnpConfig};
+use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 use std::{io, vec};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -21,6 +27,11 @@ pub enum TCPError {
     ReadErr,
     WriteErr,
     Timeout,
+    RateLimited(RateLimitError),
+    EnvelopeErr(TCPEnvelopeError),
+    NoiseErr(TCPNoiseError),
+    ReplayErr(TCPReplayError),
+    CompressionErr(TCPCompressionError),
 }
 
 pub fn port_number(chain: Chain) -> u16 {
@@ -53,8 +64,17 @@ pub async fn open_port(chain: Chain) -> bool {
 pub async fn connect(ip_address: &str, chain: Chain) -> Result<TcpStream, TCPError> {
     let port_number = port_number(chain);
     let addr = format!("{}:{}", ip_address, port_number);
+    connect_addr(&addr).await
+}
+
+/// Connects to an endpoint announced through peer discovery.
+pub async fn connect_endpoint(endpoint: SocketAddr) -> Result<TcpStream, TCPError> {
+    connect_addr(&endpoint.to_string()).await
+}
+
+async fn connect_addr(addr: &str) -> Result<TcpStream, TCPError> {
     let timeout = tokio::time::sleep(Duration::from_millis(3_000));
-    let connect = TcpStream::connect(&addr);
+    let connect = TcpStream::connect(addr);
 
     tokio::select! {
         result = connect => {
@@ -222,6 +242,14 @@ pub async fn request(
                 if response_package.kind() == package.kind() && response_package.timestamp() == package.timestamp() {
                     return Ok((response_package, start.elapsed()));
                 }
+
+                // The server rejected the request for exceeding its rate limit.
+                if response_package.kind() == PackageKind::RateLimited && response_package.timestamp() == package.timestamp() {
+                    return match RateLimitError::deserialize(&response_package.payload()) {
+                        Some(err) => Err(TCPError::RateLimited(err)),
+                        None => Err(TCPError::ReadErr),
+                    };
+                }
             }
         } => result, // Pass the loop's result directly
         _ = sleep(remaining_time) => {
@@ -0,0 +1,319 @@
+use crate::communicative::tcp::package::PackageKind;
+use crate::transmutative::key::ToNostrKeyStr;
+use serde_json::{Map, Value};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Maximum number of peers accounted for individually.
+pub const MAX_METERED_PEERS: usize = 1024;
+
+/// Process-wide traffic meter.
+static TRAFFIC_METER: OnceLock<Mutex<TrafficMeter>> = OnceLock::new();
+
+/// Direction of metered traffic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TrafficDirection {
+    Sent,
+    Received,
+}
+
+/// Number of messages and bytes exchanged.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct TrafficCounter {
+    // Number of messages.
+    pub messages: u64,
+
+    // Number of bytes on the wire.
+    pub bytes: u64,
+}
+
+impl TrafficCounter {
+    /// Accounts a message of the given size.
+    fn add(&mut self, bytes: u64) {
+        self.messages = self.messages.saturating_add(1);
+        self.bytes = self.bytes.saturating_add(bytes);
+    }
+
+    /// Returns the counter as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("messages".to_string(), Value::Number(self.messages.into()));
+        obj.insert("bytes".to_string(), Value::Number(self.bytes.into()));
+        Value::Object(obj)
+    }
+}
+
+/// Traffic exchanged with a peer, by direction and message type.
+#[derive(Clone, Default)]
+pub struct PeerTraffic {
+    // Traffic sent to the peer, by message type.
+    sent: HashMap<PackageKind, TrafficCounter>,
+
+    // Traffic received from the peer, by message type.
+    received: HashMap<PackageKind, TrafficCounter>,
+}
+
+impl PeerTraffic {
+    /// Accounts a message.
+    fn record(&mut self, kind: PackageKind, direction: TrafficDirection, bytes: u64) {
+        let counters = match direction {
+            TrafficDirection::Sent => &mut self.sent,
+            TrafficDirection::Received => &mut self.received,
+        };
+        counters.entry(kind).or_default().add(bytes);
+    }
+
+    /// Returns the traffic in one direction of the given message type.
+    pub fn counter(&self, kind: PackageKind, direction: TrafficDirection) -> TrafficCounter {
+        let counters = match direction {
+            TrafficDirection::Sent => &self.sent,
+            TrafficDirection::Received => &self.received,
+        };
+        counters.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// Returns the traffic in one direction across all message types.
+    pub fn total(&self, direction: TrafficDirection) -> TrafficCounter {
+        let counters = match direction {
+            TrafficDirection::Sent => &self.sent,
+            TrafficDirection::Received => &self.received,
+        };
+        counters
+            .values()
+            .fold(TrafficCounter::default(), |total, counter| TrafficCounter {
+                messages: total.messages.saturating_add(counter.messages),
+                bytes: total.bytes.saturating_add(counter.bytes),
+            })
+    }
+
+    /// Returns the number of bytes exchanged in both directions.
+    pub fn total_bytes(&self) -> u64 {
+        self.total(TrafficDirection::Sent)
+            .bytes
+            .saturating_add(self.total(TrafficDirection::Received).bytes)
+    }
+
+    /// Returns the traffic as a JSON object.
+    pub fn json(&self) -> Value {
+        let direction_json = |counters: &HashMap<PackageKind, TrafficCounter>,
+                              direction: TrafficDirection| {
+            let mut by_kind = Map::new();
+            for (kind, counter) in counters.iter() {
+                by_kind.insert(kind.to_string(), counter.json());
+            }
+            let mut obj = Map::new();
+            obj.insert("total".to_string(), self.total(direction).json());
+            obj.insert("by_kind".to_string(), Value::Object(by_kind));
+            Value::Object(obj)
+        };
+
+        let mut obj = Map::new();
+        obj.insert(
+            "sent".to_string(),
+            direction_json(&self.sent, TrafficDirection::Sent),
+        );
+        obj.insert(
+            "received".to_string(),
+            direction_json(&self.received, TrafficDirection::Received),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Accounts the traffic exchanged with peers over TCP, by peer and message type.
+///
+/// Messages are accounted under the type of the package they carry, and with their size on the
+/// wire, envelope or Noise overhead included. Traffic that cannot be attributed to a peer key, such
+/// as plaintext pings, Noise handshakes and packages that fail to open, is accounted separately, as
+/// is the traffic of peers beyond the first [`MAX_METERED_PEERS`].
+pub struct TrafficMeter {
+    // Traffic by peer key.
+    peers: HashMap<PeerKey, PeerTraffic>,
+
+    // Traffic not attributed to a metered peer.
+    unattributed: PeerTraffic,
+
+    // Time the meter started at.
+    started_at: Instant,
+}
+
+impl TrafficMeter {
+    /// Constructs an empty traffic meter.
+    pub fn new() -> Self {
+        Self {
+            peers: HashMap::new(),
+            unattributed: PeerTraffic::default(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Accounts a message exchanged with a peer, or with an unknown peer if no key is given.
+    pub fn record(
+        &mut self,
+        peer_key: Option<PeerKey>,
+        kind: PackageKind,
+        direction: TrafficDirection,
+        bytes: usize,
+    ) {
+        let metered = match peer_key {
+            Some(peer_key) => {
+                self.peers.contains_key(&peer_key) || self.peers.len() < MAX_METERED_PEERS
+            }
+            None => false,
+        };
+        let traffic = match (peer_key, metered) {
+            (Some(peer_key), true) => self.peers.entry(peer_key).or_default(),
+            _ => &mut self.unattributed,
+        };
+        traffic.record(kind, direction, bytes as u64);
+    }
+
+    /// Returns the traffic exchanged with a peer, if any.
+    pub fn peer(&self, peer_key: PeerKey) -> Option<&PeerTraffic> {
+        self.peers.get(&peer_key)
+    }
+
+    /// Returns the traffic not attributed to a metered peer.
+    pub fn unattributed(&self) -> &PeerTraffic {
+        &self.unattributed
+    }
+
+    /// Returns the number of metered peers.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peer is metered.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Returns the traffic in one direction across all peers and message types.
+    pub fn total(&self, direction: TrafficDirection) -> TrafficCounter {
+        self.peers
+            .values()
+            .chain(std::iter::once(&self.unattributed))
+            .map(|traffic| traffic.total(direction))
+            .fold(TrafficCounter::default(), |total, counter| TrafficCounter {
+                messages: total.messages.saturating_add(counter.messages),
+                bytes: total.bytes.saturating_add(counter.bytes),
+            })
+    }
+
+    /// Returns the metered peers, the noisiest first.
+    pub fn noisiest_peers(&self) -> Vec<(PeerKey, &PeerTraffic)> {
+        let mut peers: Vec<(PeerKey, &PeerTraffic)> = self
+            .peers
+            .iter()
+            .map(|(peer_key, traffic)| (*peer_key, traffic))
+            .collect();
+        peers.sort_by_key(|(_, traffic)| Reverse(traffic.total_bytes()));
+        peers
+    }
+
+    /// Returns the totals and the noisiest peers as a JSON object, for the runtime metrics.
+    pub fn summary_json(&self, top: usize) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "uptime_secs".to_string(),
+            Value::Number(self.started_at.elapsed().as_secs().into()),
+        );
+        obj.insert(
+            "sent".to_string(),
+            self.total(TrafficDirection::Sent).json(),
+        );
+        obj.insert(
+            "received".to_string(),
+            self.total(TrafficDirection::Received).json(),
+        );
+        obj.insert("peers".to_string(), Value::Number(self.len().into()));
+        obj.insert(
+            "noisiest_peers".to_string(),
+            Value::Array(
+                self.noisiest_peers()
+                    .iter()
+                    .take(top)
+                    .map(|(peer_key, traffic)| {
+                        let mut peer = Map::new();
+                        peer.insert("peer".to_string(), peer_json(peer_key));
+                        peer.insert(
+                            "bytes".to_string(),
+                            Value::Number(traffic.total_bytes().into()),
+                        );
+                        Value::Object(peer)
+                    })
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+
+    /// Returns the traffic of every metered peer as a JSON object, the noisiest first.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "uptime_secs".to_string(),
+            Value::Number(self.started_at.elapsed().as_secs().into()),
+        );
+        obj.insert(
+            "sent".to_string(),
+            self.total(TrafficDirection::Sent).json(),
+        );
+        obj.insert(
+            "received".to_string(),
+            self.total(TrafficDirection::Received).json(),
+        );
+        obj.insert(
+            "peers".to_string(),
+            Value::Array(
+                self.noisiest_peers()
+                    .iter()
+                    .map(|(peer_key, traffic)| {
+                        let mut peer = match traffic.json() {
+                            Value::Object(obj) => obj,
+                            _ => Map::new(),
+                        };
+                        peer.insert("peer".to_string(), peer_json(peer_key));
+                        Value::Object(peer)
+                    })
+                    .collect(),
+            ),
+        );
+        obj.insert("unattributed".to_string(), self.unattributed.json());
+        Value::Object(obj)
+    }
+}
+
+impl Default for TrafficMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns a peer key as its npub, or as hex if it is not a valid npub.
+fn peer_json(peer_key: &PeerKey) -> Value {
+    Value::String(peer_key.to_npub().unwrap_or_else(|| hex::encode(peer_key)))
+}
+
+/// Returns the process-wide traffic meter.
+pub fn traffic_meter() -> &'static Mutex<TrafficMeter> {
+    TRAFFIC_METER.get_or_init(|| Mutex::new(TrafficMeter::new()))
+}
+
+/// Accounts a message exchanged with a peer on the process-wide traffic meter.
+pub fn record_traffic(
+    peer_key: Option<PeerKey>,
+    kind: PackageKind,
+    direction: TrafficDirection,
+    bytes: usize,
+) {
+    traffic_meter()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .record(peer_key, kind, direction, bytes);
+}
@@ -0,0 +1,2 @@
+pub mod webhook_error;
+pub mod webhooks_construction_error;
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// Errors associated with the webhooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookError {
+    // The endpoint URL is not a valid HTTPS URL.
+    InvalidEndpointUrl(String),
+    // The endpoint is not subscribed to any event.
+    NoEventKinds,
+    // The endpoint is subscribed to balance changes without following any account.
+    NoFollowedAccounts,
+    // The endpoint follows more accounts than allowed.
+    TooManyAccounts(usize),
+    // No more endpoints can be added.
+    TooManyEndpoints(usize),
+    // No more deliveries can be queued.
+    DeliveryQueueFull(usize),
+    // Reading or writing the webhooks db failed.
+    DbErr(String),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::InvalidEndpointUrl(url) => {
+                write!(
+                    f,
+                    "Invalid webhook endpoint URL, expected https://: {}",
+                    url
+                )
+            }
+            WebhookError::NoEventKinds => write!(f, "A webhook must subscribe to an event."),
+            WebhookError::NoFollowedAccounts => write!(
+                f,
+                "A webhook subscribed to balance changes must follow an account."
+            ),
+            WebhookError::TooManyAccounts(max) => {
+                write!(f, "A webhook may follow at most {} accounts.", max)
+            }
+            WebhookError::TooManyEndpoints(max) => {
+                write!(f, "At most {} webhooks may be configured.", max)
+            }
+            WebhookError::DeliveryQueueFull(max) => {
+                write!(
+                    f,
+                    "The webhook delivery queue is full ({} deliveries).",
+                    max
+                )
+            }
+            WebhookError::DbErr(err) => write!(f, "Webhooks db error: {}", err),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+/// Errors associated with constructing the webhooks.
+#[derive(Debug, Clone)]
+pub enum WebhooksConstructionError {
+    DBOpenError(sled::Error),
+    TreeOpenError(sled::Error),
+}
@@ -0,0 +1,6 @@
+pub mod errors;
+pub mod webhook_delivery;
+pub mod webhook_endpoint;
+pub mod webhook_event;
+pub mod webhook_signature;
+pub mod webhooks;
@@ -0,0 +1,98 @@
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A webhook callback queued for delivery to an endpoint, retried until the endpoint accepts it or
+/// it runs out of attempts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    // Id of the delivery, increasing in order of enqueuing.
+    pub id: u64,
+
+    // Id of the recipient endpoint.
+    pub endpoint_id: u64,
+
+    // Kind of the delivered event.
+    pub kind: WebhookEventKind,
+
+    // JSON body of the callback.
+    pub body: String,
+
+    // Unix timestamp (seconds) the delivery was enqueued at.
+    pub enqueued_at: u64,
+
+    // Unix timestamp (seconds) of the next delivery attempt.
+    pub next_attempt_at: u64,
+
+    // Number of failed delivery attempts so far.
+    pub attempts: u32,
+
+    // The error of the last failed delivery attempt.
+    pub last_error: Option<String>,
+}
+
+impl WebhookDelivery {
+    /// Constructs a delivery of an event due right away.
+    ///
+    /// The body carries the delivery id, the event kind, the enqueuing time and the event data.
+    pub fn new(id: u64, endpoint_id: u64, kind: WebhookEventKind, data: Value, now: u64) -> Self {
+        // 1 Construct the body.
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::Number(id.into()));
+        obj.insert("event".to_string(), Value::String(kind.to_string()));
+        obj.insert("created_at".to_string(), Value::Number(now.into()));
+        obj.insert("data".to_string(), data);
+
+        // 2 Construct the delivery.
+        Self {
+            id,
+            endpoint_id,
+            kind,
+            body: Value::Object(obj).to_string(),
+            enqueued_at: now,
+            next_attempt_at: now,
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a webhook delivery from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(webhook_delivery, _)| webhook_delivery)
+    }
+
+    /// Returns the webhook delivery as a JSON object, without the body.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::Number(self.id.into()));
+        obj.insert(
+            "endpoint_id".to_string(),
+            Value::Number(self.endpoint_id.into()),
+        );
+        obj.insert("event".to_string(), Value::String(self.kind.to_string()));
+        obj.insert(
+            "enqueued_at".to_string(),
+            Value::Number(self.enqueued_at.into()),
+        );
+        obj.insert(
+            "next_attempt_at".to_string(),
+            Value::Number(self.next_attempt_at.into()),
+        );
+        obj.insert("attempts".to_string(), Value::Number(self.attempts.into()));
+        obj.insert(
+            "last_error".to_string(),
+            match &self.last_error {
+                Some(error) => Value::String(error.clone()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
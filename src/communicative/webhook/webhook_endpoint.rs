@@ -0,0 +1,83 @@
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use crate::transmutative::key::ToNostrKeyStr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// An HTTPS endpoint receiving signed callbacks for the events it is subscribed to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    // Id of the endpoint, increasing in order of addition.
+    pub id: u64,
+
+    // HTTPS URL the callbacks are posted to.
+    pub url: String,
+
+    // Secret the callbacks are signed with.
+    pub secret: [u8; 32],
+
+    // Event kinds the endpoint is subscribed to.
+    pub kinds: Vec<WebhookEventKind>,
+
+    // Accounts whose balance changes are delivered.
+    pub accounts: Vec<[u8; 32]>,
+
+    // Unix timestamp (seconds) the endpoint was added at.
+    pub created_at: u64,
+}
+
+impl WebhookEndpoint {
+    /// Whether the endpoint is subscribed to the given event kind.
+    pub fn subscribed_to(&self, kind: WebhookEventKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// Whether the endpoint follows the given account.
+    pub fn follows(&self, account_key: [u8; 32]) -> bool {
+        self.accounts.contains(&account_key)
+    }
+
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a webhook endpoint from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(webhook_endpoint, _)| webhook_endpoint)
+    }
+
+    /// Returns the webhook endpoint as a JSON object, without the secret.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::Number(self.id.into()));
+        obj.insert("url".to_string(), Value::String(self.url.clone()));
+        obj.insert(
+            "events".to_string(),
+            Value::Array(
+                self.kinds
+                    .iter()
+                    .map(|kind| Value::String(kind.to_string()))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "accounts".to_string(),
+            Value::Array(
+                self.accounts
+                    .iter()
+                    .map(|account_key| match account_key.to_npub() {
+                        Some(npub) => Value::String(npub),
+                        None => Value::String(hex::encode(account_key)),
+                    })
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "created_at".to_string(),
+            Value::Number(self.created_at.into()),
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Kinds of ledger events delivered to webhooks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEventKind {
+    /// A deposit to a lift of the node's own account entered the Bitcoin mempool (Node only).
+    DepositDetected,
+    /// An applied entry changed the balance of a followed account.
+    BalanceChanged,
+    /// The engine failed to execute a built batch (Engine only).
+    ExecutionFailed,
+}
+
+impl WebhookEventKind {
+    /// Returns all webhook event kinds.
+    pub fn all() -> [WebhookEventKind; 3] {
+        [
+            WebhookEventKind::DepositDetected,
+            WebhookEventKind::BalanceChanged,
+            WebhookEventKind::ExecutionFailed,
+        ]
+    }
+
+    /// Returns the webhook event kind of the given name.
+    pub fn from_name(name: &str) -> Option<WebhookEventKind> {
+        WebhookEventKind::all()
+            .into_iter()
+            .find(|kind| kind.to_string() == name)
+    }
+}
+
+impl ToString for WebhookEventKind {
+    fn to_string(&self) -> String {
+        match self {
+            WebhookEventKind::DepositDetected => "deposit_detected".to_string(),
+            WebhookEventKind::BalanceChanged => "balance_changed".to_string(),
+            WebhookEventKind::ExecutionFailed => "execution_failed".to_string(),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use bitcoin::hashes::hmac::{Hmac, HmacEngine};
+use bitcoin::hashes::{sha256, Hash as _, HashEngine};
+
+/// Header carrying the Unix timestamp (seconds) a webhook delivery was signed at.
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "X-Cube-Timestamp";
+
+/// Header carrying the HMAC-SHA256 signature of a webhook delivery.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Cube-Signature";
+
+/// Header carrying the event kind of a webhook delivery.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Cube-Event";
+
+/// Header carrying the id of a webhook delivery, unchanged across retries.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Cube-Delivery";
+
+/// Returns the HMAC-SHA256 of a webhook body under the endpoint secret.
+///
+/// The timestamp is signed along with the body, as `<timestamp>.<body>`, so that a receiver can
+/// refuse a captured delivery replayed later on.
+pub fn webhook_signature(secret: &[u8; 32], timestamp: u64, body: &str) -> [u8; 32] {
+    let mut engine = HmacEngine::<sha256::Hash>::new(secret);
+    engine.input(timestamp.to_string().as_bytes());
+    engine.input(b".");
+    engine.input(body.as_bytes());
+    Hmac::<sha256::Hash>::from_engine(engine).to_byte_array()
+}
+
+/// Returns the signature header value of a webhook body, as `sha256=<hex>`.
+pub fn webhook_signature_header(secret: &[u8; 32], timestamp: u64, body: &str) -> String {
+    format!(
+        "sha256={}",
+        hex::encode(webhook_signature(secret, timestamp, body))
+    )
+}
@@ -0,0 +1,366 @@
+use crate::communicative::webhook::errors::webhook_error::WebhookError;
+use crate::communicative::webhook::errors::webhooks_construction_error::WebhooksConstructionError;
+use crate::communicative::webhook::webhook_delivery::WebhookDelivery;
+use crate::communicative::webhook::webhook_endpoint::WebhookEndpoint;
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use crate::operative::run_args::chain::Chain;
+use rand::{rngs::OsRng, RngCore};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Maximum number of configured endpoints.
+pub const MAX_WEBHOOK_ENDPOINTS: usize = 32;
+
+/// Maximum number of accounts followed by a single endpoint.
+pub const MAX_WEBHOOK_ACCOUNTS: usize = 256;
+
+/// Maximum number of deliveries queued across all endpoints.
+pub const MAX_PENDING_DELIVERIES: usize = 10_000;
+
+/// Number of failed attempts after which a delivery is given up on.
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 12;
+
+/// Delay before retrying a delivery after its first failed attempt in seconds.
+const BASE_RETRY_BACKOFF_SECS: u64 = 10;
+
+/// Maximum delay between two attempts of a delivery in seconds.
+const MAX_RETRY_BACKOFF_SECS: u64 = 3_600;
+
+/// Returns the delay before the next attempt of a delivery that failed the given number of times,
+/// doubling with each failure up to the maximum backoff.
+pub fn webhook_retry_backoff_secs(attempts: u32) -> u64 {
+    let exponent = attempts.saturating_sub(1).min(63);
+    BASE_RETRY_BACKOFF_SECS
+        .saturating_mul(1u64 << exponent)
+        .min(MAX_RETRY_BACKOFF_SECS)
+}
+
+/// Outcome of a failed delivery attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookFailureOutcome {
+    // The delivery is retried at the given Unix timestamp (seconds).
+    RetryAt(u64),
+    // The delivery ran out of attempts and was dropped.
+    GivenUp,
+}
+
+/// Operator-configured webhook endpoints and their queued deliveries.
+///
+/// Events are turned into one delivery per subscribed endpoint when they happen, and deliveries
+/// stay queued on disk until the endpoint accepts them, so that a callback survives a restart or an
+/// endpoint briefly down. Failed deliveries are retried with exponential backoff and given up on
+/// after a bounded number of attempts.
+pub struct Webhooks {
+    // Id of the next added endpoint.
+    next_endpoint_id: u64,
+
+    // Id of the next enqueued delivery.
+    next_delivery_id: u64,
+
+    // In-memory endpoints by id.
+    endpoints: BTreeMap<u64, WebhookEndpoint>,
+
+    // In-memory queued deliveries by id.
+    deliveries: BTreeMap<u64, WebhookDelivery>,
+
+    // On-disk endpoints.
+    on_disk_endpoints: sled::Tree,
+
+    // On-disk queued deliveries.
+    on_disk_deliveries: sled::Tree,
+}
+
+/// Guarded 'Webhooks'.
+#[allow(non_camel_case_types)]
+pub type WEBHOOKS = Arc<Mutex<Webhooks>>;
+
+impl Webhooks {
+    /// Constructs the webhooks, loading the endpoints and queued deliveries from disk.
+    pub fn new(chain: Chain) -> Result<WEBHOOKS, WebhooksConstructionError> {
+        // 1 Open the webhooks db.
+        let db_path = format!("storage/{}/webhooks", chain.to_string());
+        let db = sled::open(db_path).map_err(WebhooksConstructionError::DBOpenError)?;
+
+        // 2 Open the endpoints and deliveries trees.
+        let on_disk_endpoints = db
+            .open_tree("endpoints")
+            .map_err(WebhooksConstructionError::TreeOpenError)?;
+        let on_disk_deliveries = db
+            .open_tree("deliveries")
+            .map_err(WebhooksConstructionError::TreeOpenError)?;
+
+        // 3 Load the endpoints and deliveries, skipping corrupt ones.
+        let mut endpoints = BTreeMap::new();
+        for (_, value) in on_disk_endpoints.iter().filter_map(|item| item.ok()) {
+            if let Some(endpoint) = WebhookEndpoint::deserialize(value.as_ref()) {
+                endpoints.insert(endpoint.id, endpoint);
+            }
+        }
+        let mut deliveries = BTreeMap::new();
+        for (_, value) in on_disk_deliveries.iter().filter_map(|item| item.ok()) {
+            if let Some(delivery) = WebhookDelivery::deserialize(value.as_ref()) {
+                deliveries.insert(delivery.id, delivery);
+            }
+        }
+
+        // 4 Continue the ids after the highest known ones.
+        let next_endpoint_id = endpoints.keys().next_back().map(|id| id + 1).unwrap_or(0);
+        let next_delivery_id = deliveries.keys().next_back().map(|id| id + 1).unwrap_or(0);
+
+        // 5 Construct the webhooks.
+        let webhooks = Webhooks {
+            next_endpoint_id,
+            next_delivery_id,
+            endpoints,
+            deliveries,
+            on_disk_endpoints,
+            on_disk_deliveries,
+        };
+
+        // 6 Guard and return the webhooks.
+        Ok(Arc::new(Mutex::new(webhooks)))
+    }
+
+    /// Adds an endpoint with a freshly generated secret and returns it.
+    ///
+    /// The URL must be HTTPS. Endpoints subscribed to balance changes must follow an account.
+    pub fn add_endpoint(
+        &mut self,
+        url: &str,
+        kinds: Vec<WebhookEventKind>,
+        accounts: Vec<AccountKey>,
+        now: u64,
+    ) -> Result<WebhookEndpoint, WebhookError> {
+        // 1 Check the endpoint is valid.
+        let url = url.trim();
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if parsed.scheme() == "https" && parsed.host_str().is_some() => (),
+            _ => return Err(WebhookError::InvalidEndpointUrl(url.to_string())),
+        }
+        if kinds.is_empty() {
+            return Err(WebhookError::NoEventKinds);
+        }
+        if kinds.contains(&WebhookEventKind::BalanceChanged) && accounts.is_empty() {
+            return Err(WebhookError::NoFollowedAccounts);
+        }
+        if accounts.len() > MAX_WEBHOOK_ACCOUNTS {
+            return Err(WebhookError::TooManyAccounts(MAX_WEBHOOK_ACCOUNTS));
+        }
+        if self.endpoints.len() >= MAX_WEBHOOK_ENDPOINTS {
+            return Err(WebhookError::TooManyEndpoints(MAX_WEBHOOK_ENDPOINTS));
+        }
+
+        // 2 Generate the secret.
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+
+        // 3 Deduplicate the kinds and accounts, keeping their order.
+        let mut seen_kinds = HashSet::new();
+        let kinds = kinds
+            .into_iter()
+            .filter(|kind| seen_kinds.insert(*kind))
+            .collect();
+        let mut seen_accounts = HashSet::new();
+        let accounts = accounts
+            .into_iter()
+            .filter(|account_key| seen_accounts.insert(*account_key))
+            .collect();
+
+        // 4 Persist the endpoint.
+        let endpoint = WebhookEndpoint {
+            id: self.next_endpoint_id,
+            url: url.to_string(),
+            secret,
+            kinds,
+            accounts,
+            created_at: now,
+        };
+        self.on_disk_endpoints
+            .insert(
+                endpoint.id.to_be_bytes(),
+                endpoint.serialize().unwrap_or_default(),
+            )
+            .map_err(|err| WebhookError::DbErr(err.to_string()))?;
+        self.next_endpoint_id += 1;
+        self.endpoints.insert(endpoint.id, endpoint.clone());
+
+        Ok(endpoint)
+    }
+
+    /// Removes an endpoint along with its queued deliveries. Returns whether it was configured.
+    pub fn remove_endpoint(&mut self, id: u64) -> Result<bool, WebhookError> {
+        // 1 Remove the endpoint.
+        if self.endpoints.remove(&id).is_none() {
+            return Ok(false);
+        }
+        self.on_disk_endpoints
+            .remove(id.to_be_bytes())
+            .map_err(|err| WebhookError::DbErr(err.to_string()))?;
+
+        // 2 Drop its queued deliveries.
+        let delivery_ids: Vec<u64> = self
+            .deliveries
+            .values()
+            .filter(|delivery| delivery.endpoint_id == id)
+            .map(|delivery| delivery.id)
+            .collect();
+        for delivery_id in delivery_ids {
+            self.acknowledge(delivery_id)
+                .map_err(|err| WebhookError::DbErr(err.to_string()))?;
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the endpoint of the given id.
+    pub fn endpoint(&self, id: u64) -> Option<&WebhookEndpoint> {
+        self.endpoints.get(&id)
+    }
+
+    /// Returns the configured endpoints, in order of addition.
+    pub fn endpoints(&self) -> Vec<&WebhookEndpoint> {
+        self.endpoints.values().collect()
+    }
+
+    /// Returns the accounts followed by the endpoints subscribed to balance changes.
+    pub fn followed_accounts(&self) -> HashSet<AccountKey> {
+        self.endpoints
+            .values()
+            .filter(|endpoint| endpoint.subscribed_to(WebhookEventKind::BalanceChanged))
+            .flat_map(|endpoint| endpoint.accounts.iter().cloned())
+            .collect()
+    }
+
+    /// Queues an event for delivery to every endpoint subscribed to it and returns the number of
+    /// queued deliveries.
+    ///
+    /// Balance changes carry the account they concern and only go to the endpoints following it.
+    pub fn enqueue(
+        &mut self,
+        kind: WebhookEventKind,
+        account_key: Option<AccountKey>,
+        data: Value,
+        now: u64,
+    ) -> Result<usize, WebhookError> {
+        // 1 Collect the recipient endpoints.
+        let endpoint_ids: Vec<u64> = self
+            .endpoints
+            .values()
+            .filter(|endpoint| endpoint.subscribed_to(kind))
+            .filter(|endpoint| match account_key {
+                Some(account_key) => endpoint.follows(account_key),
+                None => true,
+            })
+            .map(|endpoint| endpoint.id)
+            .collect();
+
+        // 2 Check the queue has room left.
+        if self.deliveries.len() + endpoint_ids.len() > MAX_PENDING_DELIVERIES {
+            return Err(WebhookError::DeliveryQueueFull(MAX_PENDING_DELIVERIES));
+        }
+
+        // 3 Queue a delivery to each of them.
+        for endpoint_id in endpoint_ids.iter() {
+            let delivery =
+                WebhookDelivery::new(self.next_delivery_id, *endpoint_id, kind, data.clone(), now);
+            self.next_delivery_id += 1;
+            self.persist(delivery)
+                .map_err(|err| WebhookError::DbErr(err.to_string()))?;
+        }
+
+        Ok(endpoint_ids.len())
+    }
+
+    /// Returns the deliveries due for an attempt at the given time, in order of enqueuing.
+    pub fn due(&self, now: u64) -> Vec<WebhookDelivery> {
+        self.deliveries
+            .values()
+            .filter(|delivery| delivery.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes a delivery the endpoint accepted.
+    pub fn acknowledge(&mut self, id: u64) -> Result<(), sled::Error> {
+        if self.deliveries.remove(&id).is_some() {
+            self.on_disk_deliveries.remove(id.to_be_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Records a failed delivery attempt, backing off its next attempt or giving up on it once it
+    /// ran out of attempts.
+    pub fn record_failure(
+        &mut self,
+        id: u64,
+        error: String,
+        now: u64,
+    ) -> Result<WebhookFailureOutcome, sled::Error> {
+        // 1 Take the delivery.
+        let mut delivery = match self.deliveries.remove(&id) {
+            Some(delivery) => delivery,
+            None => return Ok(WebhookFailureOutcome::GivenUp),
+        };
+
+        // 2 Give up on it once out of attempts.
+        delivery.attempts += 1;
+        if delivery.attempts >= MAX_DELIVERY_ATTEMPTS {
+            self.on_disk_deliveries.remove(id.to_be_bytes())?;
+            return Ok(WebhookFailureOutcome::GivenUp);
+        }
+
+        // 3 Otherwise back off its next attempt.
+        delivery.next_attempt_at = now + webhook_retry_backoff_secs(delivery.attempts);
+        delivery.last_error = Some(error);
+        let next_attempt_at = delivery.next_attempt_at;
+        self.persist(delivery)?;
+        Ok(WebhookFailureOutcome::RetryAt(next_attempt_at))
+    }
+
+    /// Returns the number of queued deliveries.
+    pub fn pending_len(&self) -> usize {
+        self.deliveries.len()
+    }
+
+    /// Returns the webhooks as a JSON object, without the endpoint secrets.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "endpoints".to_string(),
+            Value::Array(self.endpoints.values().map(WebhookEndpoint::json).collect()),
+        );
+        obj.insert(
+            "deliveries".to_string(),
+            Value::Array(
+                self.deliveries
+                    .values()
+                    .map(WebhookDelivery::json)
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+
+    /// Keeps a queued delivery in memory and on disk.
+    fn persist(&mut self, delivery: WebhookDelivery) -> Result<(), sled::Error> {
+        let bytes = delivery.serialize().unwrap_or_default();
+        self.on_disk_deliveries
+            .insert(delivery.id.to_be_bytes(), bytes)?;
+        self.deliveries.insert(delivery.id, delivery);
+        Ok(())
+    }
+}
+
+/// Erases the webhooks.
+pub fn erase_webhooks(chain: Chain) {
+    // Webhooks db path.
+    let db_path = format!("storage/{}/webhooks", chain.to_string());
+
+    // Erase the webhooks db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
@@ -1,5 +1,9 @@
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::codec::address::encode_p2tr;
 use crate::transmutative::codec::prefix::Prefix;
 use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::musig::keyagg::MusigKeyAggCtx;
+use crate::transmutative::secp::context::GeneratorMul;
 use secp::{MaybePoint, Point, Scalar};
 use std::cmp::Ordering;
 use std::vec;
@@ -132,6 +136,27 @@ impl TapRoot {
         }
     }
 
+    /// Key-path only taproot whose inner key is the MuSig2 aggregate of the given keys.
+    pub fn key_path_aggregated(keys: &Vec<Point>) -> Option<TapRoot> {
+        let key_agg_ctx = MusigKeyAggCtx::new(keys, None)?;
+        Some(TapRoot::key_path_only(key_agg_ctx.agg_inner_key()))
+    }
+
+    /// Key-and-script-path taproot whose inner key is the MuSig2 aggregate of the given keys.
+    pub fn key_and_script_path_aggregated(
+        keys: &Vec<Point>,
+        leaves: Vec<TapLeaf>,
+    ) -> Option<TapRoot> {
+        if leaves.is_empty() {
+            return None;
+        }
+        let key_agg_ctx = MusigKeyAggCtx::new(keys, None)?;
+        Some(TapRoot::key_and_script_path_multi(
+            key_agg_ctx.agg_inner_key(),
+            leaves,
+        ))
+    }
+
     pub fn script_path_only_single(leaf: TapLeaf) -> TapRoot {
         let inner_key = Point::from_slice(&POINT_WITH_UNKNOWN_DISCRETE_LOGARITHM).unwrap();
         TapRoot {
@@ -178,7 +203,7 @@ impl TapRoot {
     pub fn tweaked_key(&self) -> Option<Point> {
         if let Some(_) = &self.tree {
             let tweak = Scalar::from_slice(&self.tap_tweak()).ok()?;
-            let tweaked_key = self.inner_key_lifted() + tweak.base_point_mul();
+            let tweaked_key = self.inner_key_lifted() + tweak.generator_mul();
 
             match tweaked_key {
                 MaybePoint::Valid(point) => Some(point),
@@ -201,6 +226,20 @@ impl TapRoot {
         Some(spk)
     }
 
+    /// Bech32m-encoded P2TR address of the taproot output on the given chain.
+    pub fn address(&self, chain: Chain) -> Option<String> {
+        let tweaked_key = self.tweaked_key()?;
+        encode_p2tr(chain, tweaked_key.serialize_xonly())
+    }
+
+    /// Index of the leaf with the given tapscript, if any.
+    pub fn leaf_index(&self, tap_script: &Vec<u8>) -> Option<usize> {
+        let tree = self.tree.as_ref()?;
+        tree.leaves
+            .iter()
+            .position(|leaf| &leaf.tap_script == tap_script)
+    }
+
     pub fn control_block(&self, index: usize) -> Option<ControlBlock> {
         let path = match &self.tree {
             Some(tree) => tree.path(index),
@@ -212,6 +251,13 @@ impl TapRoot {
 
         Some(ControlBlock::new(inner_key, parity, path))
     }
+
+    /// Control block for spending the leaf with the given tapscript, if any.
+    pub fn control_block_for_script(&self, tap_script: &Vec<u8>) -> Option<ControlBlock> {
+        let index = self.leaf_index(tap_script)?;
+        self.control_block(index)
+    }
+
     pub fn tree(&self) -> Option<TapTree> {
         self.tree.clone()
     }
@@ -11,6 +11,8 @@ pub enum EntryFees {
         total_pre_subsidy: u64,
         /// `Some` when a PM exemption row existed and subsidy was applied; `None` when there was no row (full nominal fee).
         subsidy_breakdown: Option<ExemptionSubsidyBreakdown>,
+        /// Priority fee attached by the sender; not subsidized.
+        priority_fee: u64,
     },
     Liftup {
         base_fee: u64,
@@ -59,6 +61,7 @@ impl EntryFees {
                 liquidity_fee,
                 total_pre_subsidy,
                 subsidy_breakdown,
+                priority_fee,
             } => {
                 obj.insert("entry_kind".to_string(), Value::String("move".to_string()));
                 obj.insert("base_fee".to_string(), Value::Number((*base_fee).into()));
@@ -77,6 +80,10 @@ impl EntryFees {
                         None => Value::Null,
                     },
                 );
+                obj.insert(
+                    "priority_fee".to_string(),
+                    Value::Number((*priority_fee).into()),
+                );
             }
             EntryFees::Liftup {
                 base_fee,
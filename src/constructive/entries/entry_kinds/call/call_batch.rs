@@ -0,0 +1,108 @@
+use crate::constructive::entries::entry_kinds::call::call::Call;
+use crate::constructive::entries::entry_kinds::call::call_batch_error::CallBatchError;
+use crate::transmutative::hash::{Hash, HashTag};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// The maximum number of calls in a batch.
+pub const MAX_CALLS_PER_BATCH: usize = 16;
+
+/// An ordered batch of calls of a single account, executed all-or-nothing: either every call
+/// passes and their changes are kept, or the whole batch is rolled back.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallBatch {
+    /// The calls, in execution order.
+    pub calls: Vec<Call>,
+}
+
+impl CallBatch {
+    /// Creates a new call batch.
+    pub fn new(calls: Vec<Call>) -> Self {
+        Self { calls }
+    }
+
+    /// Returns the calls, in execution order.
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Returns the key of the account making the calls, if the batch has any.
+    pub fn account_key(&self) -> Option<[u8; 32]> {
+        self.calls.first().map(|call| call.account().account_key())
+    }
+
+    /// Checks that the batch is well-formed: it has 1 to `MAX_CALLS_PER_BATCH` distinct calls, all
+    /// made by the same account.
+    pub fn validate(&self) -> Result<(), CallBatchError> {
+        // 1 Check the number of calls.
+        if self.calls.is_empty() {
+            return Err(CallBatchError::EmptyCallBatchError);
+        }
+        if self.calls.len() > MAX_CALLS_PER_BATCH {
+            return Err(CallBatchError::CallBatchTooLargeError(self.calls.len()));
+        }
+
+        // 2 Check every call is made by the account of the first call.
+        let account_key = self.calls[0].account().account_key();
+        if let Some(index) = self
+            .calls
+            .iter()
+            .position(|call| !call.entry_validation(account_key))
+        {
+            return Err(CallBatchError::MixedCallerAccountsError(index));
+        }
+
+        // 3 Check no call appears twice.
+        let mut seen = HashSet::<[u8; 32]>::new();
+        for (index, call) in self.calls.iter().enumerate() {
+            let sighash = call
+                .sighash()
+                .map_err(|error| CallBatchError::CallSighashError(index, error))?;
+            if !seen.insert(sighash) {
+                return Err(CallBatchError::DuplicateCallError(index));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the signature message (sighash) of the batch, committing to the sighashes of its
+    /// calls in order.
+    pub fn sighash(&self) -> Result<[u8; 32], CallBatchError> {
+        // 1 Collect the call count and the call sighashes as the preimage.
+        let mut sighash_preimage = Vec::<u8>::new();
+        sighash_preimage.extend((self.calls.len() as u32).to_be_bytes());
+        for (index, call) in self.calls.iter().enumerate() {
+            let call_sighash = call
+                .sighash()
+                .map_err(|error| CallBatchError::CallSighashError(index, error))?;
+            sighash_preimage.extend(call_sighash);
+        }
+
+        // 2 Hash the preimage with the `CallBatchSighash` tag.
+        Ok(sighash_preimage.hash(Some(HashTag::CallBatchSighash)))
+    }
+
+    /// Returns the call batch as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "calls".to_string(),
+            Value::Array(self.calls.iter().map(|call| call.json()).collect()),
+        );
+        Value::Object(obj)
+    }
+
+    /// Serializes this call batch with bincode (same config as wire payloads elsewhere).
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a call batch from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(call_batch, _)| call_batch)
+    }
+}
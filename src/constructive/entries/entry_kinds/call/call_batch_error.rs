@@ -0,0 +1,16 @@
+use crate::constructive::entries::entry_kinds::call::ext::signature::sighash::error::sighash_error::CallSighashError;
+
+/// Errors associated with validating a `CallBatch`.
+#[derive(Debug, Clone)]
+pub enum CallBatchError {
+    /// The batch has no calls.
+    EmptyCallBatchError,
+    /// The batch has more calls than allowed.
+    CallBatchTooLargeError(usize),
+    /// A call of the batch is made by another account than the first call.
+    MixedCallerAccountsError(usize),
+    /// The same call appears more than once in the batch.
+    DuplicateCallError(usize),
+    /// A call of the batch could not be hashed.
+    CallSighashError(usize, CallSighashError),
+}
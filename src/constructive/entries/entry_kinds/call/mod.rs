@@ -1,2 +1,4 @@
 pub mod call;
+pub mod call_batch;
+pub mod call_batch_error;
 pub mod ext;
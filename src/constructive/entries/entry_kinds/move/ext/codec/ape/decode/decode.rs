@@ -29,11 +29,16 @@ impl Move {
             .map_err(MoveAPEDecodeError::AmountAPEDecodeError)?
             .value();
 
-        // 4 Decode the `Target`.
+        // 4 Decode the priority fee.
+        let priority_fee = ShortVal::decode_ape(bit_stream)
+            .map_err(MoveAPEDecodeError::PriorityFeeAPEDecodeError)?
+            .value();
+
+        // 5 Decode the `Target`.
         let target = Target::decode_ape(bit_stream, execution_batch_height)
             .map_err(MoveAPEDecodeError::TargetAPEDecodeError)?;
 
-        // 5 Construct and return the decoded `Move`.
-        Ok(Move::new(from, to, amount, target))
+        // 6 Construct and return the decoded `Move`.
+        Ok(Move::new(from, to, amount, priority_fee, target))
     }
 }
@@ -9,5 +9,6 @@ pub enum MoveAPEDecodeError {
     RootAccountAPEDecodeError(RootAccountAPEDecodeError),
     AccountAPEDecodeError(AccountAPEDecodeError),
     AmountAPEDecodeError(ShortValAPEDecodeError),
+    PriorityFeeAPEDecodeError(ShortValAPEDecodeError),
     TargetAPEDecodeError(TargetAPEDecodeError),
 }
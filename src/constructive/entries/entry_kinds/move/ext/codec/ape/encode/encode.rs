@@ -41,7 +41,13 @@ impl Move {
             bits.extend(amount_as_shortval.encode_ape());
         }
 
-        // 5 Encode the `Target`.
+        // 5 Encode the priority fee.
+        {
+            let priority_fee_as_shortval = ShortVal::new(self.priority_fee);
+            bits.extend(priority_fee_as_shortval.encode_ape());
+        }
+
+        // 6 Encode the `Target`.
         {
             let target_bits = self
                 .target
@@ -50,7 +56,7 @@ impl Move {
             bits.extend(target_bits);
         }
 
-        // 6 Return the bit vector.
+        // 7 Return the bit vector.
         Ok(bits)
     }
 }
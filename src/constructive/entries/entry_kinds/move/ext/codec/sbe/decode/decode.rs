@@ -74,23 +74,33 @@ impl Move {
             MoveSBEDecodeError::MoveSBEAmountBytesConversionError
         })?);
 
-        // 7 Decode target.
-        if after_to.len() < 12 {
+        // 7 Decode priority fee.
+        if after_to.len() < 8 {
+            return Err(MoveSBEDecodeError::MoveSBEInsufficientBytesForPriorityFee {
+                got_total: bytes.len(),
+            });
+        }
+        let priority_fee = u32::from_le_bytes(after_to[4..8].try_into().map_err(|_| {
+            MoveSBEDecodeError::MoveSBEPriorityFeeBytesConversionError
+        })?);
+
+        // 8 Decode target.
+        if after_to.len() < 16 {
             return Err(MoveSBEDecodeError::MoveSBEInsufficientBytesForTarget {
                 got_total: bytes.len(),
             });
         }
-        let target = Target::decode_sbe(&after_to[4..12]).map_err(MoveSBEDecodeError::MoveSBETarget)?;
+        let target = Target::decode_sbe(&after_to[8..16]).map_err(MoveSBEDecodeError::MoveSBETarget)?;
 
-        // 8 Reject trailing bytes.
-        let tail = &after_to[12..];
+        // 9 Reject trailing bytes.
+        let tail = &after_to[16..];
         if !tail.is_empty() {
             return Err(MoveSBEDecodeError::MoveSBETrailingBytesAfterMove {
                 trailing: tail.len(),
             });
         }
 
-        // 9 Return decoded `Move`.
-        Ok(Move::new(from, to, amount, target))
+        // 10 Return decoded `Move`.
+        Ok(Move::new(from, to, amount, priority_fee, target))
     }
 }
@@ -16,6 +16,8 @@ pub enum MoveSBEDecodeError {
     MoveSBEToAccount(AccountSBEDecodeError),
     MoveSBEInsufficientBytesForAmount { got_total: usize },
     MoveSBEAmountBytesConversionError,
+    MoveSBEInsufficientBytesForPriorityFee { got_total: usize },
+    MoveSBEPriorityFeeBytesConversionError,
     MoveSBEInsufficientBytesForTarget { got_total: usize },
     MoveSBETarget(TargetSBEDecodeError),
     MoveSBETrailingBytesAfterMove { trailing: usize },
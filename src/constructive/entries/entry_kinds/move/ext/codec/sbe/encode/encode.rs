@@ -33,6 +33,7 @@ impl Move {
         bytes.extend_from_slice(&to_len_u32.to_le_bytes());
         bytes.extend_from_slice(&to_bytes);
         bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.priority_fee.to_le_bytes());
         bytes.extend_from_slice(&self.target.encode_sbe());
 
         // 4 Return bytes.
@@ -50,9 +50,9 @@ impl Move {
             });
         }
 
-        // 6 Validate sender has enough balance to fund the move amount.
+        // 6 Validate sender has enough balance to fund the move amount and the priority fee.
         {
-            let required = self.amount as u64;
+            let required = self.amount as u64 + self.priority_fee as u64;
 
             // 6.1 Lock coin manager.
             let _coin_manager = coin_manager.lock().await;
@@ -16,17 +16,27 @@ pub struct Move {
     /// Amount of coins (satoshis) to move.
     pub amount: u32,
 
+    /// Priority fee (satoshis) attached by the sender on top of the entry fees.
+    pub priority_fee: u32,
+
     /// Target execution information.
     pub target: Target,
 }
 
 impl Move {
     /// Creates a new `Move` entry kind.
-    pub fn new(from: RootAccount, to: Account, amount: u32, target: Target) -> Self {
+    pub fn new(
+        from: RootAccount,
+        to: Account,
+        amount: u32,
+        priority_fee: u32,
+        target: Target,
+    ) -> Self {
         Self {
             from,
             to,
             amount,
+            priority_fee,
             target,
         }
     }
@@ -51,13 +61,19 @@ impl Move {
             Value::Number((self.amount as u64).into()),
         );
 
-        // 6 Insert the target.
+        // 6 Insert the priority fee.
+        obj.insert(
+            "priority_fee".to_string(),
+            Value::Number((self.priority_fee as u64).into()),
+        );
+
+        // 7 Insert the target.
         obj.insert(
             "target".to_string(),
             Value::Number(self.target.targeted_at_batch_height.into()),
         );
 
-        // 7 Return the JSON object.
+        // 8 Return the JSON object.
         Value::Object(obj)
     }
 
@@ -1,7 +1,6 @@
 use crate::constructive::taproot::{TapLeaf, TapRoot, P2TR};
 use crate::constructive::txn::ext::OutpointExt;
 use crate::operative::run_args::chain::Chain;
-use crate::transmutative::codec::address::encode_p2tr;
 use bitcoin::{OutPoint, TxOut};
 use hex;
 use serde::{Deserialize, Serialize};
@@ -186,13 +185,7 @@ pub fn return_liftv1_address(
     engine_key: [u8; 32],
 ) -> Option<String> {
     let taproot = return_liftv1_taproot(account_key, engine_key)?;
-    let address = match encode_p2tr(chain, taproot.tweaked_key().unwrap().serialize_xonly()) {
-        Some(address) => address,
-        None => {
-            return None;
-        }
-    };
-    Some(address)
+    taproot.address(chain)
 }
 
 /// Returns a scriptpubkey for the LiftV1 struct.
@@ -1,7 +1,6 @@
 use crate::constructive::taproot::{TapLeaf, TapRoot, P2TR};
 use crate::constructive::txn::ext::OutpointExt;
 use crate::transmutative::codec::csv::{CSVEncode, CSVFlag};
-use crate::transmutative::musig::keyagg::MusigKeyAggCtx;
 use crate::transmutative::secp::into::IntoPoint;
 use bitcoin::{OutPoint, TxOut};
 use hex;
@@ -133,13 +132,7 @@ pub fn return_liftv2_taproot(account_key: [u8; 32], engine_key: [u8; 32]) -> Opt
         keys
     };
 
-    // 2 Construct the key aggregation context
-    let key_agg_ctx = MusigKeyAggCtx::new(&keys, None)?;
-
-    // 3 Construct the inner key
-    let agg_inner_key = key_agg_ctx.agg_inner_key();
-
-    // 4 Construct the sweep path
+    // 2 Construct the sweep path
     let sweep_path_tapscript: Vec<u8> = {
         let mut tapscript = Vec::<u8>::new();
 
@@ -154,14 +147,11 @@ pub fn return_liftv2_taproot(account_key: [u8; 32], engine_key: [u8; 32]) -> Opt
         tapscript
     };
 
-    // 5 Construct the sweep path leaf
+    // 3 Construct the sweep path leaf
     let sweep_path = TapLeaf::new(sweep_path_tapscript);
 
-    // 6 Construct the taproot
-    let taproot = TapRoot::key_and_script_path_single(agg_inner_key, sweep_path);
-
-    // 7 Return the taproot
-    Some(taproot)
+    // 4 Construct the taproot with the aggregated keys as the inner key
+    TapRoot::key_and_script_path_aggregated(&keys, vec![sweep_path])
 }
 
 /// Returns a scriptpubkey for the LiftV2 struct.
@@ -115,6 +115,7 @@ impl ExecCtx {
                     .set_or_update_account_secondary_aggregation_key(
                         account_key,
                         secondary_aggregation_key.clone(),
+                        execution_timestamp,
                     )
                     .map_err(
                         ConfigExecutionError::RegisterySetOrUpdateSecondaryAggregationKeyError,
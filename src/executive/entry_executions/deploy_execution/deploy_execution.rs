@@ -28,8 +28,8 @@ impl ExecCtx {
         let base_fee = params_holder.deploy_entry_base_fee;
         deploy
             .program
-            .validate_methods()
-            .map_err(DeployExecutionError::ProgramValidateMethodsError)?;
+            .validate()
+            .map_err(DeployExecutionError::ProgramValidationError)?;
         let program_bytes_len = deploy
             .program
             .compile()
@@ -1,6 +1,6 @@
 use crate::constructive::entity::account::root_account::registered_and_configured_root_account::ext::sync_with_registery::sync_with_registery_error::RegisteredAndConfiguredRootAccountSyncWithRegisteryError;
 use crate::constructive::entity::account::root_account::registered_but_unconfigured_root_account::ext::sync_with_registery::sync_with_registery_error::RegisteredButUnconfiguredRootAccountSyncWithRegisteryError;
-use crate::executive::executable::program_error::MethodValidationError;
+use crate::executive::executable::program_error::ProgramValidationError;
 use crate::inscriptive::coin_manager::errors::balance_update_errors::CMAccountBalanceDownError;
 use crate::inscriptive::coin_manager::errors::register_errors::CMRegisterContractError;
 use crate::inscriptive::privileges_manager::errors::register_error::PMRegisterContractError;
@@ -19,7 +19,7 @@ pub enum DeployExecutionError {
     RegisteredAndConfiguredRootAccountSyncWithRegisteryError(
         RegisteredAndConfiguredRootAccountSyncWithRegisteryError,
     ),
-    ProgramValidateMethodsError(MethodValidationError),
+    ProgramValidationError(ProgramValidationError),
     ProgramCompileError,
     DeployProgramByteFeeOverflow,
     DeployTotalPreSubsidyOverflow,
@@ -9,7 +9,7 @@ use crate::inscriptive::coin_manager::errors::balance_update_errors::{
 /// Errors associated with executing a `Move` entry.
 #[derive(Debug, Clone)]
 pub enum MoveExecutionError {
-    /// `amount` plus post-subsidy entry fee does not fit in `u64`.
+    /// `amount` plus post-subsidy entry fee plus priority fee does not fit in `u64`.
     MoveSenderTotalDebitOverflow,
     FromAndToAccountKeysAreSameError([u8; 32]),
     UnexpectedUnregisteredFromRootAccountError,
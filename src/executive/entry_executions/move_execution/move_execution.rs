@@ -107,9 +107,12 @@ impl ExecCtx {
             }
         };
 
-        // 7 Receiver gets the full move `amount`; sender pays `amount` plus post-subsidy entry fees.
+        // 7 Receiver gets the full move `amount`; sender pays `amount` plus post-subsidy entry fees
+        // plus the priority fee, which is never subsidized.
+        let priority_fee = move_entry.priority_fee as u64;
         let sender_total_debit = move_amount_in_satoshis
             .checked_add(fees_after_subsidy)
+            .and_then(|debit| debit.checked_add(priority_fee))
             .ok_or(MoveExecutionError::MoveSenderTotalDebitOverflow)?;
 
         // 8 Decrease sender balance (`amount` + fees) before crediting the receiver.
@@ -121,6 +124,19 @@ impl ExecCtx {
         .await
         .map_err(MoveExecutionError::CoinManagerAccountBalanceDownError)?;
 
+        // 8.1 Distribute the priority fee: the engine share goes to the engine account if it is
+        // registered, the rest (or all of it otherwise) is burned along with the entry fees.
+        let engine_share =
+            (priority_fee * params_holder.priority_fee_engine_share_ppm.min(1_000_000)) / 1_000_000;
+        if engine_share > 0 {
+            let mut _coin_manager = self.coin_manager.lock().await;
+            if _coin_manager.get_account_balance(self.engine_key).is_some() {
+                _coin_manager
+                    .account_balance_up(self.engine_key, engine_share)
+                    .map_err(MoveExecutionError::CoinManagerAccountBalanceUpError)?;
+            }
+        }
+
         // 9 Sync/register receiver account with DB.
         match &move_entry.to {
             Account::UnregisteredAccount(unregistered_account) => {
@@ -159,6 +175,7 @@ impl ExecCtx {
             liquidity_fee,
             total_pre_subsidy: fees_pre_subsidy,
             subsidy_breakdown,
+            priority_fee,
         })
     }
 
@@ -1,4 +1,6 @@
-use crate::inscriptive::archival_manager::errors::insert_error::ArchivalManagerInsertBatchRecordError;
+use crate::inscriptive::archival_manager::errors::insert_error::{
+    ArchivalManagerInsertBatchRecordError, ArchivalManagerInsertStateRootError,
+};
 use crate::inscriptive::coin_manager::errors::apply_changes_errors::CMApplyChangesError;
 use crate::inscriptive::flame_manager::errors::apply_changes_error::FMApplyChangesError;
 use crate::inscriptive::graveyard::errors::apply_changes_error::GraveyardApplyChangesError;
@@ -15,4 +17,5 @@ pub enum ApplyChangesError {
     PrivilegesManagerApplyChangesError(sled::Error),
     FlameManagerApplyChangesError(FMApplyChangesError),
     ArchivalManagerInsertBatchRecordError(ArchivalManagerInsertBatchRecordError),
+    ArchivalManagerInsertStateRootError(ArchivalManagerInsertStateRootError),
 }
@@ -1,3 +1,5 @@
+use crate::communicative::event_stream::batch_events::batch_stream_events;
+use crate::communicative::event_stream::event_stream::event_stream;
 use crate::constructive::bitcoiny::batch_container::batch_container::BatchContainer;
 use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
 use crate::constructive::core_types::valtypes::val::long_val::long_val::LongVal;
@@ -10,8 +12,13 @@ use crate::constructive::txn::ext::OutpointExt;
 use crate::constructive::txout_types::payload::payload::Payload;
 use crate::constructive::txout_types::projector::projector::Projector;
 use crate::executive::exec_ctx::errors::batch_execution_error::BatchExecutionError;
+use crate::executive::vm::program_execution::read_call_cache::read_call_cache;
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+#[cfg(feature = "fault-injection")]
+use crate::inscriptive::fault_injection::fault_injection::fault_point;
+#[cfg(feature = "fault-injection")]
+use crate::inscriptive::fault_injection::fault_point::FaultPoint;
 use crate::inscriptive::flame_manager::flame_manager::FLAME_MANAGER;
 use crate::inscriptive::graveyard::graveyard::GRAVEYARD;
 use crate::inscriptive::params_manager::params_manager::PARAMS_MANAGER;
@@ -230,6 +237,17 @@ impl ExecCtx {
         // 5 Calculate the projector expiry height.
         let projector_expiry_height = new_batch_height + projector_expiry_gap;
 
+        // 5.b Journal the start of the commit, so that a crash midway through is caught on startup.
+        {
+            self.sync_manager
+                .lock()
+                .await
+                .begin_commit(new_batch_height);
+        }
+
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::CommitBegun);
+
         // 6 Apply changes to the flame manager.
         {
             // 6.1 Lock the flame manager.
@@ -249,6 +267,9 @@ impl ExecCtx {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::FlameManagerApplied);
+
         // 7 Apply changes to the coin manager.
         {
             // 7.1 Lock the coin manager.
@@ -260,6 +281,9 @@ impl ExecCtx {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::CoinManagerApplied);
+
         // 8 Apply changes to the graveyard.
         {
             // 8.1 Lock the graveyard.
@@ -271,6 +295,9 @@ impl ExecCtx {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::GraveyardApplied);
+
         // 9 Apply changes to the registery.
         {
             // 9.1 Lock the registery.
@@ -282,6 +309,9 @@ impl ExecCtx {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::RegisteryApplied);
+
         // 10 Apply changes to the state manager.
         {
             // 10.1 Lock the state manager.
@@ -293,6 +323,9 @@ impl ExecCtx {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::StateManagerApplied);
+
         // 11 Apply changes to the privileges manager.
         {
             let mut _privileges_manager = self.privileges_manager.lock().await;
@@ -301,6 +334,9 @@ impl ExecCtx {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::PrivilegesManagerApplied);
+
         // 12 Update tips in the sync manager.
         {
             // 12.1 Lock the sync manager.
@@ -313,6 +349,9 @@ impl ExecCtx {
             _sync_manager.set_payload_tip(new_payload);
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::SyncTipsUpdated);
+
         // 13 Safe-remove spent lift tx inputs from the utxo set (as this may be in-flight execution).
         {
             // 13.1 Lock the utxo set.
@@ -331,11 +370,44 @@ impl ExecCtx {
                 .map_err(|error| ApplyChangesError::ArchivalManagerInsertBatchRecordError(error))?;
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::BatchRecordArchived);
+
+        // 14.b Journal the end of the commit along with the resulting state root.
+        {
+            // 14.b.1 Compute the account balances state root.
+            let state_root = self.coin_manager.lock().await.account_balances_state_root();
+
+            // 14.b.2 Journal the state root along with the archived batch record, so that replays
+            // of the batch can be checked against it.
+            if let Some(archival_manager) = self.archival_manager.as_ref() {
+                archival_manager
+                    .lock()
+                    .await
+                    .insert_state_root(new_batch_height, state_root)
+                    .map_err(ApplyChangesError::ArchivalManagerInsertStateRootError)?;
+            }
+
+            #[cfg(feature = "fault-injection")]
+            fault_point(FaultPoint::StateRootJournaled);
+
+            // 14.b.3 Close the commit journal entry.
+            self.sync_manager.lock().await.end_commit(state_root);
+
+            // 14.b.4 Invalidate the read call results cached against the previous state root.
+            read_call_cache().lock().unwrap().invalidate(state_root);
+        }
+
         // 15 Flush the changes.
         {
             self.flush().await;
         }
 
+        // 15.b Stream the applied entries to the event stream subscribers, if any.
+        if event_stream().subscriber_count() > 0 {
+            event_stream().publish(batch_stream_events(batch_record));
+        }
+
         // 16 Return Ok.
         Ok(())
     }
@@ -2,8 +2,10 @@ pub mod entry_executions;
 pub mod exec_ctx;
 pub mod vm;
 
+pub use vm::abi;
 pub use vm::opcodes;
 pub use vm::opcodes as opcode;
+pub use vm::precompiles;
 pub use vm::program;
 pub use vm::program as executable;
 pub use vm::stack;
@@ -0,0 +1,28 @@
+use crate::executive::abi::abi_type::AbiType;
+
+/// Errors associated with encoding and decoding ABI values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiError {
+    /// The stack items ran out before the value was decoded.
+    MissingStackItem(AbiType),
+    /// Stack items were left over once all the values were decoded.
+    TrailingStackItems(usize),
+    /// The value count does not match the type count.
+    ValueCountMismatch(usize, usize),
+    /// The value does not have the expected type.
+    TypeMismatch(AbiType),
+    /// The integer is not in its minimal encoding.
+    NonCanonicalInteger(AbiType),
+    /// The integer does not fit the type.
+    IntegerOutOfRange(AbiType),
+    /// The boolean is neither the true nor the false item.
+    NonCanonicalBool,
+    /// The byte array does not have the length of the type.
+    InvalidBytesLength(usize, usize),
+    /// The variable-length byte array exceeds the maximum length.
+    VarbytesTooLong(usize),
+    /// The array exceeds the maximum length.
+    ArrayTooLong(usize),
+    /// The JSON value does not map to a value of the type.
+    InvalidJsonValue(AbiType),
+}
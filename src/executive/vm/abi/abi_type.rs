@@ -0,0 +1,97 @@
+use crate::constructive::core_types::calldata::calldata_elements::validation::{
+    MAX_BYTES_LEN, MIN_BYTES_LEN,
+};
+use crate::constructive::core_types::calldata::element_type::CalldataElementType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The type of a value passed to or returned from a contract method.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AbiType {
+    /// An unsigned 8-bit integer.
+    U8,
+    /// An unsigned 16-bit integer.
+    U16,
+    /// An unsigned 32-bit integer.
+    U32,
+    /// An unsigned 64-bit integer.
+    U64,
+    /// A boolean.
+    Bool,
+    /// A byte array of the given length, from 1 to 256 bytes.
+    Bytes(usize),
+    /// A byte array of up to 4095 bytes.
+    Varbytes,
+    /// A 32-byte account key.
+    AccountKey,
+    /// A 32-byte contract id.
+    ContractId,
+    /// An array of values of the inner type.
+    Array(Box<AbiType>),
+}
+
+impl AbiType {
+    /// Returns the ABI type of a calldata element type, as the method receives it on the stack.
+    pub fn from_calldata_element_type(element_type: CalldataElementType) -> AbiType {
+        match element_type {
+            CalldataElementType::U8 => AbiType::U8,
+            CalldataElementType::U16 => AbiType::U16,
+            CalldataElementType::U32 | CalldataElementType::Payable => AbiType::U32,
+            CalldataElementType::U64 => AbiType::U64,
+            CalldataElementType::Bool => AbiType::Bool,
+            CalldataElementType::Account => AbiType::AccountKey,
+            CalldataElementType::Contract => AbiType::ContractId,
+            // Byte length is the inner value + 1.
+            CalldataElementType::Bytes(index) => AbiType::Bytes(index as usize + 1),
+            CalldataElementType::Varbytes => AbiType::Varbytes,
+        }
+    }
+
+    /// Parses the ABI type from its name, such as `u64`, `bytes32`, `account_key` or `u8[]`.
+    pub fn parse(name: &str) -> Option<AbiType> {
+        // 1 Parse arrays by their inner type.
+        if let Some(inner_name) = name.strip_suffix("[]") {
+            return AbiType::parse(inner_name).map(|inner| AbiType::Array(Box::new(inner)));
+        }
+
+        // 2 Parse the fixed-length byte arrays by their length.
+        if let Some(len) = name.strip_prefix("bytes") {
+            if let Ok(len) = len.parse::<usize>() {
+                return match (MIN_BYTES_LEN..=MAX_BYTES_LEN).contains(&len) {
+                    true => Some(AbiType::Bytes(len)),
+                    false => None,
+                };
+            }
+        }
+
+        // 3 Parse the other types.
+        match name {
+            "u8" => Some(AbiType::U8),
+            "u16" => Some(AbiType::U16),
+            "u32" => Some(AbiType::U32),
+            "u64" => Some(AbiType::U64),
+            "bool" => Some(AbiType::Bool),
+            "varbytes" => Some(AbiType::Varbytes),
+            "account_key" => Some(AbiType::AccountKey),
+            "contract_id" => Some(AbiType::ContractId),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiType::U8 => write!(f, "u8"),
+            AbiType::U16 => write!(f, "u16"),
+            AbiType::U32 => write!(f, "u32"),
+            AbiType::U64 => write!(f, "u64"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Bytes(len) => write!(f, "bytes{}", len),
+            AbiType::Varbytes => write!(f, "varbytes"),
+            AbiType::AccountKey => write!(f, "account_key"),
+            AbiType::ContractId => write!(f, "contract_id"),
+            AbiType::Array(inner) => write!(f, "{}[]", inner),
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::constructive::core_types::calldata::calldata_elements::validation::MAX_VARBYTES_LEN;
+use crate::executive::stack::stack_item::StackItem;
+use crate::executive::stack::stack_uint::{SafeConverter, StackItemUintExt, StackUint};
+use crate::executive::abi::abi_error::AbiError;
+use crate::executive::abi::abi_type::AbiType;
+use serde_json::Value;
+
+/// Maximum number of elements in an ABI array.
+pub const MAX_ABI_ARRAY_LEN: usize = 256;
+
+/// A value passed to or returned from a contract method.
+///
+/// Values are encoded as stack items the same way the calldata elements are pushed: integers as
+/// minimal little-endian items (zero is the empty item), booleans as the true and false items, and
+/// byte arrays and keys as their raw bytes. An array is its element count as a `u32` item,
+/// followed by its elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiValue {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    Varbytes(Vec<u8>),
+    AccountKey([u8; 32]),
+    ContractId([u8; 32]),
+    Array(AbiType, Vec<AbiValue>),
+}
+
+impl AbiValue {
+    /// Returns the ABI type of the value.
+    pub fn abi_type(&self) -> AbiType {
+        match self {
+            AbiValue::U8(_) => AbiType::U8,
+            AbiValue::U16(_) => AbiType::U16,
+            AbiValue::U32(_) => AbiType::U32,
+            AbiValue::U64(_) => AbiType::U64,
+            AbiValue::Bool(_) => AbiType::Bool,
+            AbiValue::Bytes(bytes) => AbiType::Bytes(bytes.len()),
+            AbiValue::Varbytes(_) => AbiType::Varbytes,
+            AbiValue::AccountKey(_) => AbiType::AccountKey,
+            AbiValue::ContractId(_) => AbiType::ContractId,
+            AbiValue::Array(inner, _) => AbiType::Array(Box::new(inner.clone())),
+        }
+    }
+
+    /// Encodes the value into stack items.
+    pub fn encode(&self) -> Result<Vec<StackItem>, AbiError> {
+        let mut items = Vec::<StackItem>::new();
+        self.encode_into(&mut items)?;
+        Ok(items)
+    }
+
+    /// Encodes the value into the given stack items.
+    fn encode_into(&self, items: &mut Vec<StackItem>) -> Result<(), AbiError> {
+        match self {
+            AbiValue::U8(value) => items.push(uint_item(*value as u64)),
+            AbiValue::U16(value) => items.push(uint_item(*value as u64)),
+            AbiValue::U32(value) => items.push(uint_item(*value as u64)),
+            AbiValue::U64(value) => items.push(uint_item(*value)),
+            AbiValue::Bool(value) => items.push(match value {
+                true => StackItem::true_item(),
+                false => StackItem::false_item(),
+            }),
+            AbiValue::Bytes(bytes) => {
+                // An empty byte array has no fixed-length type.
+                if bytes.is_empty() {
+                    return Err(AbiError::InvalidBytesLength(1, 0));
+                }
+                items.push(StackItem::new(bytes.clone()));
+            }
+            AbiValue::Varbytes(bytes) => {
+                if bytes.len() > MAX_VARBYTES_LEN {
+                    return Err(AbiError::VarbytesTooLong(bytes.len()));
+                }
+                items.push(StackItem::new(bytes.clone()));
+            }
+            AbiValue::AccountKey(key) => items.push(StackItem::new(key.to_vec())),
+            AbiValue::ContractId(id) => items.push(StackItem::new(id.to_vec())),
+            AbiValue::Array(inner, elements) => {
+                if elements.len() > MAX_ABI_ARRAY_LEN {
+                    return Err(AbiError::ArrayTooLong(elements.len()));
+                }
+                items.push(uint_item(elements.len() as u64));
+                for element in elements {
+                    // Every element must be of the array type.
+                    if &element.abi_type() != inner {
+                        return Err(AbiError::TypeMismatch(inner.clone()));
+                    }
+                    element.encode_into(items)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes a value of the given type from the stack items.
+    ///
+    /// Decoding is strict: an item that `encode` would never produce is rejected, so every value
+    /// has exactly one encoding.
+    pub fn decode<'a, I>(abi_type: &AbiType, items: &mut I) -> Result<AbiValue, AbiError>
+    where
+        I: Iterator<Item = &'a StackItem>,
+    {
+        // 1 Take the next stack item.
+        let item = items
+            .next()
+            .ok_or(AbiError::MissingStackItem(abi_type.clone()))?;
+
+        // 2 Decode the item by the type.
+        let value = match abi_type {
+            AbiType::U8 => AbiValue::U8(
+                u8::try_from(decode_uint(abi_type, item)?)
+                    .map_err(|_| AbiError::IntegerOutOfRange(abi_type.clone()))?,
+            ),
+            AbiType::U16 => AbiValue::U16(
+                u16::try_from(decode_uint(abi_type, item)?)
+                    .map_err(|_| AbiError::IntegerOutOfRange(abi_type.clone()))?,
+            ),
+            AbiType::U32 => AbiValue::U32(
+                u32::try_from(decode_uint(abi_type, item)?)
+                    .map_err(|_| AbiError::IntegerOutOfRange(abi_type.clone()))?,
+            ),
+            AbiType::U64 => AbiValue::U64(decode_uint(abi_type, item)?),
+            AbiType::Bool => match item.bytes() {
+                [] => AbiValue::Bool(false),
+                [0x01] => AbiValue::Bool(true),
+                _ => return Err(AbiError::NonCanonicalBool),
+            },
+            AbiType::Bytes(len) => {
+                if item.bytes().len() != *len {
+                    return Err(AbiError::InvalidBytesLength(*len, item.bytes().len()));
+                }
+                AbiValue::Bytes(item.bytes().to_vec())
+            }
+            AbiType::Varbytes => {
+                if item.bytes().len() > MAX_VARBYTES_LEN {
+                    return Err(AbiError::VarbytesTooLong(item.bytes().len()));
+                }
+                AbiValue::Varbytes(item.bytes().to_vec())
+            }
+            AbiType::AccountKey => AbiValue::AccountKey(
+                item.bytes()
+                    .try_into()
+                    .map_err(|_| AbiError::InvalidBytesLength(32, item.bytes().len()))?,
+            ),
+            AbiType::ContractId => AbiValue::ContractId(
+                item.bytes()
+                    .try_into()
+                    .map_err(|_| AbiError::InvalidBytesLength(32, item.bytes().len()))?,
+            ),
+            AbiType::Array(inner) => {
+                // The first item is the element count.
+                let len = u32::try_from(decode_uint(&AbiType::U32, item)?)
+                    .map_err(|_| AbiError::IntegerOutOfRange(AbiType::U32))?
+                    as usize;
+                if len > MAX_ABI_ARRAY_LEN {
+                    return Err(AbiError::ArrayTooLong(len));
+                }
+                let mut elements = Vec::<AbiValue>::with_capacity(len);
+                for _ in 0..len {
+                    elements.push(AbiValue::decode(inner, items)?);
+                }
+                AbiValue::Array(inner.as_ref().clone(), elements)
+            }
+        };
+
+        // 3 Return the value.
+        Ok(value)
+    }
+
+    /// Encodes the values, in order, into stack items.
+    pub fn encode_values(values: &[AbiValue]) -> Result<Vec<StackItem>, AbiError> {
+        let mut items = Vec::<StackItem>::new();
+        for value in values {
+            value.encode_into(&mut items)?;
+        }
+        Ok(items)
+    }
+
+    /// Decodes the values of the given types from the stack items, which must all be consumed.
+    pub fn decode_values(
+        abi_types: &[AbiType],
+        items: &[StackItem],
+    ) -> Result<Vec<AbiValue>, AbiError> {
+        let mut iter = items.iter();
+        let values = abi_types
+            .iter()
+            .map(|abi_type| AbiValue::decode(abi_type, &mut iter))
+            .collect::<Result<Vec<AbiValue>, AbiError>>()?;
+
+        // Leftover items mean the types do not describe the items.
+        let trailing = iter.count();
+        if trailing != 0 {
+            return Err(AbiError::TrailingStackItems(trailing));
+        }
+
+        Ok(values)
+    }
+
+    /// Returns the value as a JSON value.
+    ///
+    /// `u64` values are decimal strings so that JSON clients limited to doubles keep every digit;
+    /// byte arrays and keys are hex strings.
+    pub fn json(&self) -> Value {
+        match self {
+            AbiValue::U8(value) => Value::from(*value),
+            AbiValue::U16(value) => Value::from(*value),
+            AbiValue::U32(value) => Value::from(*value),
+            AbiValue::U64(value) => Value::String(value.to_string()),
+            AbiValue::Bool(value) => Value::Bool(*value),
+            AbiValue::Bytes(bytes) | AbiValue::Varbytes(bytes) => Value::String(hex::encode(bytes)),
+            AbiValue::AccountKey(key) => Value::String(hex::encode(key)),
+            AbiValue::ContractId(id) => Value::String(hex::encode(id)),
+            AbiValue::Array(_, elements) => {
+                Value::Array(elements.iter().map(|element| element.json()).collect())
+            }
+        }
+    }
+
+    /// Parses a value of the given type from a JSON value.
+    ///
+    /// `u64` values are accepted either as decimal strings or as JSON numbers.
+    pub fn from_json(abi_type: &AbiType, json: &Value) -> Result<AbiValue, AbiError> {
+        let invalid = || AbiError::InvalidJsonValue(abi_type.clone());
+
+        let value = match abi_type {
+            AbiType::U8 => AbiValue::U8(
+                json.as_u64()
+                    .and_then(|value| u8::try_from(value).ok())
+                    .ok_or_else(invalid)?,
+            ),
+            AbiType::U16 => AbiValue::U16(
+                json.as_u64()
+                    .and_then(|value| u16::try_from(value).ok())
+                    .ok_or_else(invalid)?,
+            ),
+            AbiType::U32 => AbiValue::U32(
+                json.as_u64()
+                    .and_then(|value| u32::try_from(value).ok())
+                    .ok_or_else(invalid)?,
+            ),
+            AbiType::U64 => AbiValue::U64(match json {
+                Value::String(value) => value.parse::<u64>().map_err(|_| invalid())?,
+                _ => json.as_u64().ok_or_else(invalid)?,
+            }),
+            AbiType::Bool => AbiValue::Bool(json.as_bool().ok_or_else(invalid)?),
+            AbiType::Bytes(len) => {
+                let bytes = hex_bytes(json).ok_or_else(invalid)?;
+                if bytes.len() != *len {
+                    return Err(AbiError::InvalidBytesLength(*len, bytes.len()));
+                }
+                AbiValue::Bytes(bytes)
+            }
+            AbiType::Varbytes => {
+                let bytes = hex_bytes(json).ok_or_else(invalid)?;
+                if bytes.len() > MAX_VARBYTES_LEN {
+                    return Err(AbiError::VarbytesTooLong(bytes.len()));
+                }
+                AbiValue::Varbytes(bytes)
+            }
+            AbiType::AccountKey => AbiValue::AccountKey(
+                hex_bytes(json)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            AbiType::ContractId => AbiValue::ContractId(
+                hex_bytes(json)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(invalid)?,
+            ),
+            AbiType::Array(inner) => {
+                let elements = json.as_array().ok_or_else(invalid)?;
+                if elements.len() > MAX_ABI_ARRAY_LEN {
+                    return Err(AbiError::ArrayTooLong(elements.len()));
+                }
+                AbiValue::Array(
+                    inner.as_ref().clone(),
+                    elements
+                        .iter()
+                        .map(|element| AbiValue::from_json(inner, element))
+                        .collect::<Result<Vec<AbiValue>, AbiError>>()?,
+                )
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+/// Returns the minimal little-endian stack item of the integer.
+fn uint_item(value: u64) -> StackItem {
+    StackItem::from_stack_uint(StackUint::from_u64(value))
+}
+
+/// Decodes a minimal little-endian integer item into a `u64`.
+fn decode_uint(abi_type: &AbiType, item: &StackItem) -> Result<u64, AbiError> {
+    // A trailing zero byte is never produced by the minimal encoding.
+    if item.bytes().last() == Some(&0x00) {
+        return Err(AbiError::NonCanonicalInteger(abi_type.clone()));
+    }
+
+    item.to_stack_uint()
+        .and_then(|value| value.to_u64())
+        .ok_or(AbiError::IntegerOutOfRange(abi_type.clone()))
+}
+
+/// Returns the bytes of a hex string JSON value, with or without a `0x` prefix.
+fn hex_bytes(json: &Value) -> Option<Vec<u8>> {
+    let hex_str = json.as_str()?;
+    hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str)).ok()
+}
@@ -0,0 +1,3 @@
+pub mod abi_error;
+pub mod abi_type;
+pub mod abi_value;
@@ -1,4 +1,6 @@
+pub mod abi;
 pub mod opcodes;
+pub mod precompiles;
 pub mod program;
 pub mod program_execution;
 pub mod stack;
@@ -41,9 +41,11 @@ use crate::executive::opcode::opcodes::callinfo::op_caller::OP_CALLER;
 use crate::executive::opcode::opcodes::callinfo::op_opsbudget::OP_OPSBUDGET;
 use crate::executive::opcode::opcodes::callinfo::op_opscounter::OP_OPSCOUNTER;
 use crate::executive::opcode::opcodes::callinfo::op_opsprice::OP_OPSPRICE;
+use crate::executive::opcode::opcodes::callinfo::op_hostdata::OP_HOSTDATA;
 use crate::executive::opcode::opcodes::callinfo::op_timestamp::OP_TIMESTAMP;
 use crate::executive::opcode::opcodes::coin::op_ext_balance::OP_EXT_BALANCE;
 use crate::executive::opcode::opcodes::coin::op_self_balance::OP_SELF_BALANCE;
+use crate::executive::opcode::opcodes::coin::op_send::OP_SEND;
 use crate::executive::opcode::opcodes::coin::op_transfer::OP_TRANSFER;
 use crate::executive::opcode::opcodes::digest::op_blake2bvar::OP_BLAKE2BVAR;
 use crate::executive::opcode::opcodes::digest::op_blake2svar::OP_BLAKE2SVAR;
@@ -64,8 +66,10 @@ use crate::executive::opcode::opcodes::flow::op_returnall::OP_RETURNALL;
 use crate::executive::opcode::opcodes::flow::op_returnsome::OP_RETURNSOME;
 use crate::executive::opcode::opcodes::flow::op_verify::OP_VERIFY;
 use crate::executive::opcode::opcodes::memory::op_free::OP_MFREE;
+use crate::executive::opcode::opcodes::event::op_emit::OP_EMIT;
 use crate::executive::opcode::opcodes::memory::op_mread::OP_MREAD;
 use crate::executive::opcode::opcodes::memory::op_mwrite::OP_MWRITE;
+use crate::executive::opcode::opcodes::precompile::op_precompile::OP_PRECOMPILE;
 use crate::executive::opcode::opcodes::push::op_10::OP_10;
 use crate::executive::opcode::opcodes::push::op_11::OP_11;
 use crate::executive::opcode::opcodes::push::op_12::OP_12;
@@ -297,6 +301,7 @@ impl OpcodeCompiler for Opcode {
             Opcode::OP_EXT_BALANCE(_) => Ok(OP_EXT_BALANCE::bytecode()),
             Opcode::OP_SELF_BALANCE(_) => Ok(OP_SELF_BALANCE::bytecode()),
             Opcode::OP_TRANSFER(_) => Ok(OP_TRANSFER::bytecode()),
+            Opcode::OP_SEND(_) => Ok(OP_SEND::bytecode()),
 
             // Storage
             Opcode::OP_SWRITE(_) => Ok(OP_SWRITE::bytecode()),
@@ -306,6 +311,15 @@ impl OpcodeCompiler for Opcode {
             Opcode::OP_MWRITE(_) => Ok(OP_MWRITE::bytecode()),
             Opcode::OP_MREAD(_) => Ok(OP_MREAD::bytecode()),
             Opcode::OP_MFREE(_) => Ok(OP_MFREE::bytecode()),
+
+            // Precompile
+            Opcode::OP_PRECOMPILE(_) => Ok(OP_PRECOMPILE::bytecode()),
+
+            // Event
+            Opcode::OP_EMIT(_) => Ok(OP_EMIT::bytecode()),
+
+            // Host data
+            Opcode::OP_HOSTDATA(_) => Ok(OP_HOSTDATA::bytecode()),
         }
     }
 
@@ -562,6 +576,18 @@ impl OpcodeCompiler for Opcode {
             0xd1 => Ok(Opcode::OP_MREAD(OP_MREAD)),
             0xd2 => Ok(Opcode::OP_MFREE(OP_MFREE)),
 
+            // Precompile
+            0xd3 => Ok(Opcode::OP_PRECOMPILE(OP_PRECOMPILE)),
+
+            // Event
+            0xd4 => Ok(Opcode::OP_EMIT(OP_EMIT)),
+
+            // Host data
+            0xd5 => Ok(Opcode::OP_HOSTDATA(OP_HOSTDATA)),
+
+            // Coin
+            0xd6 => Ok(Opcode::OP_SEND(OP_SEND)),
+
             // Undefined
             _ => Err(OpcodeDecompileError::UndefinedOpcodeError),
         }
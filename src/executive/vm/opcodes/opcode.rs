@@ -17,20 +17,22 @@ use super::opcodes::{
     },
     call::{op_call::OP_CALL, op_callext::OP_CALLEXT},
     callinfo::{
-        op_caller::OP_CALLER, op_opsbudget::OP_OPSBUDGET, op_opscounter::OP_OPSCOUNTER,
-        op_opsprice::OP_OPSPRICE, op_timestamp::OP_TIMESTAMP,
+        op_caller::OP_CALLER, op_hostdata::OP_HOSTDATA, op_opsbudget::OP_OPSBUDGET,
+        op_opscounter::OP_OPSCOUNTER, op_opsprice::OP_OPSPRICE, op_timestamp::OP_TIMESTAMP,
     },
     digest::{
         op_blake2bvar::OP_BLAKE2BVAR, op_blake2svar::OP_BLAKE2SVAR, op_hash160::OP_HASH160,
         op_hash256::OP_HASH256, op_ripemd160::OP_RIPEMD160, op_sha1::OP_SHA1, op_sha256::OP_SHA256,
         op_taggedhash::OP_TAGGEDHASH,
     },
+    event::op_emit::OP_EMIT,
     flow::{
         op_else::OP_ELSE, op_endif::OP_ENDIF, op_fail::OP_FAIL, op_if::OP_IF, op_jump::OP_JUMP,
         op_nop::OP_NOP, op_notif::OP_NOTIF, op_returnall::OP_RETURNALL,
         op_returnsome::OP_RETURNSOME, op_verify::OP_VERIFY,
     },
     memory::{op_free::OP_MFREE, op_mread::OP_MREAD, op_mwrite::OP_MWRITE},
+    precompile::op_precompile::OP_PRECOMPILE,
     push::{
         op_10::OP_10, op_11::OP_11, op_12::OP_12, op_13::OP_13, op_14::OP_14, op_15::OP_15,
         op_16::OP_16, op_2::OP_2, op_3::OP_3, op_4::OP_4, op_5::OP_5, op_6::OP_6, op_7::OP_7,
@@ -58,7 +60,8 @@ use super::opcodes::{
 };
 use crate::executive::opcode::opcodes::{
     coin::{
-        op_ext_balance::OP_EXT_BALANCE, op_self_balance::OP_SELF_BALANCE, op_transfer::OP_TRANSFER,
+        op_ext_balance::OP_EXT_BALANCE, op_self_balance::OP_SELF_BALANCE, op_send::OP_SEND,
+        op_transfer::OP_TRANSFER,
     },
     shadowing::{
         op_shadow_alloc::OP_SHADOW_ALLOC, op_shadow_alloc_val::OP_SHADOW_ALLOC_VAL,
@@ -203,6 +206,7 @@ pub enum Opcode {
     OP_EXT_BALANCE(OP_EXT_BALANCE),
     OP_SELF_BALANCE(OP_SELF_BALANCE),
     OP_TRANSFER(OP_TRANSFER),
+    OP_SEND(OP_SEND),
     // Shadowing
     OP_SHADOW_ALLOC(OP_SHADOW_ALLOC),
     OP_SHADOW_DEALLOC(OP_SHADOW_DEALLOC),
@@ -221,6 +225,12 @@ pub enum Opcode {
     OP_MWRITE(OP_MWRITE),
     OP_MREAD(OP_MREAD),
     OP_MFREE(OP_MFREE),
+    // Precompile
+    OP_PRECOMPILE(OP_PRECOMPILE),
+    // Event
+    OP_EMIT(OP_EMIT),
+    // Host data
+    OP_HOSTDATA(OP_HOSTDATA),
 }
 
 impl Display for Opcode {
@@ -355,6 +365,7 @@ impl Display for Opcode {
             Opcode::OP_EXT_BALANCE(_) => write!(f, "OP_EXT_BALANCE"),
             Opcode::OP_SELF_BALANCE(_) => write!(f, "OP_SELF_BALANCE"),
             Opcode::OP_TRANSFER(_) => write!(f, "OP_TRANSFER"),
+            Opcode::OP_SEND(_) => write!(f, "OP_SEND"),
             // Shadowing
             Opcode::OP_SHADOW_ALLOC(_) => write!(f, "OP_SHADOW_ALLOC"),
             Opcode::OP_SHADOW_DEALLOC(_) => write!(f, "OP_SHADOW_DEALLOC"),
@@ -373,6 +384,12 @@ impl Display for Opcode {
             Opcode::OP_MWRITE(_) => write!(f, "OP_MWRITE"),
             Opcode::OP_MREAD(_) => write!(f, "OP_MREAD"),
             Opcode::OP_MFREE(_) => write!(f, "OP_MFREE"),
+            // Precompile
+            Opcode::OP_PRECOMPILE(_) => write!(f, "OP_PRECOMPILE"),
+            // Event
+            Opcode::OP_EMIT(_) => write!(f, "OP_EMIT"),
+            // Host data
+            Opcode::OP_HOSTDATA(_) => write!(f, "OP_HOSTDATA"),
         }
     }
 }
@@ -3,3 +3,4 @@ pub mod op_opsbudget;
 pub mod op_opscounter;
 pub mod op_opsprice;
 pub mod op_timestamp;
+pub mod op_hostdata;
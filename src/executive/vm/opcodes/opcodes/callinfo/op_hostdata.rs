@@ -0,0 +1,71 @@
+use crate::executive::opcode::ops::OP_HOSTDATA_OPS;
+use crate::executive::stack::{
+    stack_error::{HostDataError, StackError},
+    stack_holder::StackHolder,
+    stack_item::StackItem,
+    stack_uint::{SafeConverter, StackItemUintExt, StackUint},
+};
+use crate::executive::vm::program_execution::{
+    exec_trace::ExecTrace,
+    host_data::{MAX_HOST_KEY_LENGTH, MIN_HOST_KEY_LENGTH},
+};
+use serde::{Deserialize, Serialize};
+
+/// Reads a value from the host data attested by the coordinator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct OP_HOSTDATA;
+
+impl OP_HOSTDATA {
+    pub fn execute(
+        stack_holder: &mut StackHolder,
+        trace: &mut ExecTrace,
+    ) -> Result<(), StackError> {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Pop the key.
+        let key = stack_holder.pop()?;
+
+        // Make sure the key is within the valid length range (1 to 32 bytes).
+        let key_length = key.len() as usize;
+        if key_length < MIN_HOST_KEY_LENGTH || key_length > MAX_HOST_KEY_LENGTH {
+            return Err(StackError::HostDataError(
+                HostDataError::InvalidHostDataKeyLength(key_length),
+            ));
+        }
+
+        // Read the value, recording it in the trace.
+        let value = trace
+            .read_host_data(stack_holder.contract_id(), key.bytes())
+            .ok_or(StackError::HostDataError(
+                HostDataError::HostDataUnavailable,
+            ))?;
+
+        match value {
+            Some(value) => {
+                // Push the value.
+                stack_holder.push(StackItem::from_stack_uint(StackUint::from_u64(value)))?;
+
+                // Push true.
+                stack_holder.push(StackItem::true_item())?;
+            }
+            None => {
+                // Push false.
+                stack_holder.push(StackItem::false_item())?;
+            }
+        }
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_HOSTDATA_OPS)?;
+
+        Ok(())
+    }
+
+    /// Returns the bytecode for the `OP_HOSTDATA` opcode (0xd5).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xd5]
+    }
+}
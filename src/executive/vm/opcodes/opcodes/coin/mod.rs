@@ -1,3 +1,4 @@
 pub mod op_ext_balance;
 pub mod op_self_balance;
+pub mod op_send;
 pub mod op_transfer;
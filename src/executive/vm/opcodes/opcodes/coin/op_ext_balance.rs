@@ -1,4 +1,5 @@
 use crate::{
+    executive::opcode::ops::OP_EXT_BALANCE_OPS,
     executive::stack::{
         stack_error::{CoinBalanceGetError, StackError},
         stack_holder::StackHolder,
@@ -104,6 +105,9 @@ impl OP_EXT_BALANCE {
             }
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_EXT_BALANCE_OPS)?;
+
         Ok(())
     }
 
@@ -1,4 +1,5 @@
 use crate::{
+    executive::opcode::ops::OP_SELF_BALANCE_OPS,
     executive::stack::{
         stack_error::{CoinBalanceGetError, StackError},
         stack_holder::StackHolder,
@@ -47,6 +48,9 @@ impl OP_SELF_BALANCE {
         // Push the contract balance to the stack.
         stack_holder.push(contract_balance_as_stack_item)?;
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SELF_BALANCE_OPS)?;
+
         Ok(())
     }
 
@@ -0,0 +1,125 @@
+use crate::{
+    executive::opcode::ops::OP_SEND_OPS,
+    executive::stack::{
+        stack_error::{CoinTransferError, EventError, StackError},
+        stack_holder::StackHolder,
+        stack_uint::{SafeConverter, StackItemUintExt},
+    },
+    executive::vm::program_execution::{
+        exec_event::{ExecEvent, MAX_EVENTS_PER_CALL},
+        exec_trace::ExecTrace,
+    },
+    inscriptive::coin_manager::{
+        coin_manager::COIN_MANAGER, transfer_destination::CMTransferDestination,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// The topic of the event emitted with every `OP_SEND`.
+pub const SEND_EVENT_TOPIC: &[u8] = b"send";
+
+/// Sends coins from the contract to an account or another contract, and emits a `send` event.
+///
+/// Everything that can fail is checked before the balances are touched, and the event is emitted
+/// only once the coins have moved, so that the contract never observes a half-done transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct OP_SEND;
+
+impl OP_SEND {
+    pub async fn execute(
+        stack_holder: &mut StackHolder,
+        coin_manager: &COIN_MANAGER,
+        trace: &mut ExecTrace,
+    ) -> Result<(), StackError> {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Get the self contract id bytes.
+        let self_contract_id_bytes = stack_holder.contract_id();
+
+        // Pop the kind.
+        let kind_item = stack_holder.pop()?;
+
+        // Pop the destination.
+        let destination_item = stack_holder.pop()?;
+
+        // Match the kind.
+        let destination = match kind_item.is_false() {
+            // Interpret as account key.
+            true => match destination_item.bytes().try_into() {
+                Ok(bytes) => CMTransferDestination::Account(bytes),
+                Err(_) => {
+                    return Err(StackError::CoinTransferError(
+                        CoinTransferError::InvalidAccountKeyBytes(
+                            destination_item.bytes().to_vec(),
+                        ),
+                    ))
+                }
+            },
+            // Interpret as contract id.
+            false => match destination_item.bytes().try_into() {
+                Ok(bytes) => CMTransferDestination::Contract(bytes),
+                Err(_) => {
+                    return Err(StackError::CoinTransferError(
+                        CoinTransferError::InvalidContractIdBytes(
+                            destination_item.bytes().to_vec(),
+                        ),
+                    ))
+                }
+            },
+        };
+
+        // Pop the amount.
+        let amount = stack_holder.pop()?;
+
+        // Convert the amount to a u64.
+        let amount_as_u64 = amount
+            .to_stack_uint()
+            .and_then(|amount_as_stack_uint| amount_as_stack_uint.to_u64())
+            .ok_or(StackError::CoinTransferError(
+                CoinTransferError::InvalidAmountBytes(amount.bytes().to_vec()),
+            ))?;
+
+        // Make sure the call has not emitted the maximum number of events.
+        if trace.events().len() >= MAX_EVENTS_PER_CALL {
+            return Err(StackError::EventError(EventError::EventLimitExceeded));
+        }
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SEND_OPS)?;
+
+        {
+            // Lock the coin manager.
+            let mut _coin_manager = coin_manager.lock().await;
+
+            // Move the coins from the self contract balance to the destination.
+            _coin_manager
+                .contract_transfer(self_contract_id_bytes, destination, amount_as_u64)
+                .map_err(|error| {
+                    CoinTransferError::ContractTransferError(self_contract_id_bytes, error)
+                })
+                .map_err(StackError::CoinTransferError)?;
+        }
+
+        // Emit the send event: the destination kind byte, the destination, and the amount.
+        let mut data = Vec::<u8>::with_capacity(41);
+        data.push(destination.kind_byte());
+        data.extend(destination.bytes());
+        data.extend(amount_as_u64.to_be_bytes());
+        trace.emit(ExecEvent {
+            contract_id: self_contract_id_bytes,
+            topic: SEND_EVENT_TOPIC.to_vec(),
+            data,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the bytecode for the `OP_SEND` opcode (0xd6).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xd6]
+    }
+}
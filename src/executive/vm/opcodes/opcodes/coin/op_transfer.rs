@@ -1,4 +1,5 @@
 use crate::{
+    executive::opcode::ops::OP_TRANSFER_OPS,
     executive::stack::{
         stack_error::{CoinTransferError, StackError},
         stack_holder::StackHolder,
@@ -151,6 +152,9 @@ impl OP_TRANSFER {
             }
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_TRANSFER_OPS)?;
+
         Ok(())
     }
 
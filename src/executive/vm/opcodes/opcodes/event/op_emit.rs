@@ -0,0 +1,64 @@
+use crate::executive::opcode::ops::OP_EMIT_OPS;
+use crate::executive::stack::{
+    stack_error::{EventError, StackError},
+    stack_holder::StackHolder,
+};
+use crate::executive::vm::program_execution::{
+    exec_event::{ExecEvent, MAX_EVENTS_PER_CALL, MAX_EVENT_TOPIC_LENGTH, MIN_EVENT_TOPIC_LENGTH},
+    exec_trace::ExecTrace,
+};
+use serde::{Deserialize, Serialize};
+
+/// Emits an event of the underlying contract, indexed by its topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct OP_EMIT;
+
+impl OP_EMIT {
+    pub fn execute(
+        stack_holder: &mut StackHolder,
+        trace: &mut ExecTrace,
+    ) -> Result<(), StackError> {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Pop the topic.
+        let topic = stack_holder.pop()?;
+
+        // Make sure the topic is within the valid length range (1 to 32 bytes).
+        let topic_length = topic.len() as usize;
+        if topic_length < MIN_EVENT_TOPIC_LENGTH || topic_length > MAX_EVENT_TOPIC_LENGTH {
+            return Err(StackError::EventError(EventError::InvalidEventTopicLength(
+                topic_length,
+            )));
+        }
+
+        // Pop the data.
+        // NOTE: The data may be empty, and its length is bound by the stack item size limit.
+        let data = stack_holder.pop()?;
+
+        // Make sure the call has not emitted the maximum number of events.
+        if trace.events().len() >= MAX_EVENTS_PER_CALL {
+            return Err(StackError::EventError(EventError::EventLimitExceeded));
+        }
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_EMIT_OPS)?;
+
+        // Emit the event.
+        trace.emit(ExecEvent {
+            contract_id: stack_holder.contract_id(),
+            topic: topic.bytes().to_vec(),
+            data: data.bytes().to_vec(),
+        });
+
+        Ok(())
+    }
+
+    /// Returns the bytecode for the `OP_EMIT` opcode (0xd4).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xd4]
+    }
+}
@@ -5,8 +5,10 @@ pub mod call;
 pub mod callinfo;
 pub mod coin;
 pub mod digest;
+pub mod event;
 pub mod flow;
 pub mod memory;
+pub mod precompile;
 pub mod push;
 pub mod secp;
 pub mod shadowing;
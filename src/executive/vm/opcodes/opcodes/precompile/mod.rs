@@ -0,0 +1 @@
+pub mod op_precompile;
@@ -0,0 +1,50 @@
+use crate::executive::opcode::ops::OP_PRECOMPILE_OPS;
+use crate::executive::precompiles::precompile::Precompile;
+use crate::executive::stack::{
+    stack_error::{PrecompileError, StackError},
+    stack_holder::StackHolder,
+};
+use serde::{Deserialize, Serialize};
+
+/// Calls the precompiled native function registered under the id on top of the main stack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct OP_PRECOMPILE;
+
+impl OP_PRECOMPILE {
+    pub fn execute(stack_holder: &mut StackHolder) -> Result<(), StackError> {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Pop the precompile id from the main stack.
+        let id_item = stack_holder.pop()?;
+
+        // The precompile id is a single byte.
+        let id = match id_item.bytes() {
+            [id] => *id,
+            bytes => {
+                return Err(StackError::PrecompileError(
+                    PrecompileError::InvalidPrecompileIdBytes(bytes.to_vec()),
+                ))
+            }
+        };
+
+        // Look up the precompile.
+        let precompile = Precompile::from_id(id).ok_or(StackError::PrecompileError(
+            PrecompileError::UnknownPrecompile(id),
+        ))?;
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_PRECOMPILE_OPS)?;
+
+        // Run the precompile.
+        precompile.execute(stack_holder)
+    }
+
+    /// Returns the bytecode for the `OP_PRECOMPILE` opcode (0xd3).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xd3]
+    }
+}
@@ -1,3 +1,4 @@
+use crate::executive::opcode::ops::OP_SHADOW_ALLOC_OPS;
 use crate::executive::stack::{
     stack_error::{ShadowOpsError, StackError},
     stack_holder::StackHolder,
@@ -45,6 +46,9 @@ impl OP_SHADOW_ALLOC {
                 .map_err(StackError::ShadowOpsError)?;
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_ALLOC_OPS)?;
+
         // Return the result.
         Ok(())
     }
@@ -1,4 +1,5 @@
 use crate::{
+    executive::opcode::ops::OP_SHADOW_ALLOC_VAL_OPS,
     executive::stack::{
         stack_error::{ShadowOpsError, StackError},
         stack_holder::StackHolder,
@@ -71,6 +72,9 @@ impl OP_SHADOW_ALLOC_VAL {
             };
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_ALLOC_VAL_OPS)?;
+
         // Return the result.
         Ok(())
     }
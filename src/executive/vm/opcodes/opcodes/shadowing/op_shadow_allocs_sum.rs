@@ -1,3 +1,4 @@
+use crate::executive::opcode::ops::OP_SHADOW_ALLOCS_SUM_OPS;
 use crate::executive::stack::{
     stack_error::StackError,
     stack_holder::StackHolder,
@@ -49,6 +50,9 @@ impl OP_SHADOW_ALLOCS_SUM {
             stack_holder.push(result_item)?;
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_ALLOCS_SUM_OPS)?;
+
         // Return the result.
         Ok(())
     }
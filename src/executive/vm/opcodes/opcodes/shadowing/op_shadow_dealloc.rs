@@ -1,3 +1,4 @@
+use crate::executive::opcode::ops::{OP_SHADOW_DEALLOC_OPS, OP_SHADOW_DEALLOC_REFUND_OPS};
 use crate::executive::stack::{
     stack_error::{ShadowOpsError, StackError},
     stack_holder::StackHolder,
@@ -45,6 +46,13 @@ impl OP_SHADOW_DEALLOC {
                 .map_err(StackError::ShadowOpsError)?;
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_DEALLOC_OPS)?;
+
+        // Refund most of the ops of the allocation, as the shadow space shrinks back, up to the
+        // refund cap of the execution.
+        stack_holder.refund_ops(OP_SHADOW_DEALLOC_REFUND_OPS);
+
         // Return the result.
         Ok(())
     }
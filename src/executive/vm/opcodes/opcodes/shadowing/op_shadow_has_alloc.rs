@@ -1,3 +1,4 @@
+use crate::executive::opcode::ops::OP_SHADOW_HAS_ALLOC_OPS;
 use crate::executive::stack::{
     stack_error::{ShadowOpsError, StackError},
     stack_holder::StackHolder,
@@ -54,6 +55,9 @@ impl OP_SHADOW_HAS_ALLOC {
             stack_holder.push(result_item)?;
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_HAS_ALLOC_OPS)?;
+
         // Return the result.
         Ok(())
     }
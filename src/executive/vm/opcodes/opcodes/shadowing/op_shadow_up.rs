@@ -1,3 +1,4 @@
+use crate::executive::opcode::ops::OP_SHADOW_UP_OPS;
 use crate::executive::stack::{
     stack_error::{ShadowOpsError, StackError},
     stack_holder::StackHolder,
@@ -61,6 +62,9 @@ impl OP_SHADOW_UP {
                 .map_err(StackError::ShadowOpsError)?;
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_UP_OPS)?;
+
         // Return the result.
         Ok(())
     }
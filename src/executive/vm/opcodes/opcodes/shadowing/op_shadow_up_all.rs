@@ -1,3 +1,4 @@
+use crate::executive::opcode::ops::OP_SHADOW_UP_ALL_OPS;
 use crate::executive::stack::{
     stack_error::{ShadowOpsError, StackError},
     stack_holder::StackHolder,
@@ -47,6 +48,9 @@ impl OP_SHADOW_UP_ALL {
                 .map_err(StackError::ShadowOpsError)?;
         }
 
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SHADOW_UP_ALL_OPS)?;
+
         // Return the result.
         Ok(())
     }
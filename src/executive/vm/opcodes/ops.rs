@@ -96,6 +96,44 @@ pub const OP_MIN_OPS: u32 = 1;
 pub const OP_MAX_OPS: u32 = 1;
 pub const OP_WITHIN_OPS: u32 = 1;
 
+// Shadowing
+pub const OP_SHADOW_ALLOC_OPS: u32 = 1000;
+pub const OP_SHADOW_DEALLOC_OPS: u32 = 1;
+pub const OP_SHADOW_DEALLOC_REFUND_OPS: u32 = 900;
+pub const OP_SHADOW_HAS_ALLOC_OPS: u32 = 1;
+pub const OP_SHADOW_ALLOC_VAL_OPS: u32 = 1;
+pub const OP_SHADOW_UP_OPS: u32 = 5;
+pub const OP_SHADOW_DOWN_OPS: u32 = 5;
+pub const OP_SHADOW_UP_ALL_OPS: u32 = 50;
+pub const OP_SHADOW_DOWN_ALL_OPS: u32 = 50;
+pub const OP_SHADOW_NUM_ALLOCS_OPS: u32 = 1;
+pub const OP_SHADOW_ALLOCS_SUM_OPS: u32 = 1;
+
+// Coin
+pub const OP_EXT_BALANCE_OPS: u32 = 1;
+pub const OP_SELF_BALANCE_OPS: u32 = 1;
+pub const OP_TRANSFER_OPS: u32 = 10;
+pub const OP_SEND_OPS: u32 = 30;
+
+// Precompiles
+pub const OP_PRECOMPILE_OPS: u32 = 1;
+pub const SHA512_PRECOMPILE_OPS: u32 = 60;
+pub const HMAC_SHA256_PRECOMPILE_OPS: u32 = 90;
+pub const ECDSA_VERIFY_PRECOMPILE_OPS: u32 = 100;
+pub const MODMUL_PRECOMPILE_OPS: u32 = 10;
+pub const MODEXP_PRECOMPILE_OPS: u32 = 400;
+pub const FIXED_MUL_PRECOMPILE_OPS: u32 = 5;
+pub const FIXED_DIV_PRECOMPILE_OPS: u32 = 8;
+pub const FIXED_FROM_RATIO_PRECOMPILE_OPS: u32 = 8;
+pub const FIXED_MUL_INT_PRECOMPILE_OPS: u32 = 5;
+pub const FIXED_POW_PRECOMPILE_OPS: u32 = 60;
+
+// Event
+pub const OP_EMIT_OPS: u32 = 20;
+
+// Host data
+pub const OP_HOSTDATA_OPS: u32 = 5;
+
 // Crypto
 
 // Memory
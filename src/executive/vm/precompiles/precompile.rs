@@ -0,0 +1,315 @@
+use crate::executive::opcode::ops::{
+    ECDSA_VERIFY_PRECOMPILE_OPS, FIXED_DIV_PRECOMPILE_OPS, FIXED_FROM_RATIO_PRECOMPILE_OPS,
+    FIXED_MUL_INT_PRECOMPILE_OPS, FIXED_MUL_PRECOMPILE_OPS, FIXED_POW_PRECOMPILE_OPS,
+    HMAC_SHA256_PRECOMPILE_OPS, MODEXP_PRECOMPILE_OPS, MODMUL_PRECOMPILE_OPS,
+    SHA512_PRECOMPILE_OPS,
+};
+use crate::executive::stack::{
+    stack_error::{PrecompileError, StackError},
+    stack_fixed::StackFixed,
+    stack_holder::StackHolder,
+    stack_item::StackItem,
+    stack_uint::{StackItemUintExt, StackUint},
+};
+use crate::transmutative::{hash::sha512, secp::context::secp256k1_context};
+use bitcoin::hashes::hmac::{Hmac, HmacEngine};
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::secp256k1::{ecdsa::Signature, Message, PublicKey};
+
+/// A native function callable from contracts with `OP_PRECOMPILE`.
+///
+/// Precompiles run in Rust rather than being interpreted, and are charged a fixed number of ops
+/// regardless of their inputs, so that heavy but common operations stay affordable for contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precompile {
+    /// Hashes an item with SHA-512.
+    Sha512,
+    /// Computes the HMAC-SHA256 of a message under a key.
+    HmacSha256,
+    /// Verifies an ECDSA signature.
+    EcdsaVerify,
+    /// Multiplies two integers modulo a third.
+    ModMul,
+    /// Raises an integer to a power modulo a third.
+    ModExp,
+    /// Multiplies two 64.64 fixed-point numbers.
+    FixedMul,
+    /// Divides a 64.64 fixed-point number by another.
+    FixedDiv,
+    /// Creates a 64.64 fixed-point number from the ratio of two integers.
+    FixedFromRatio,
+    /// Multiplies a 64.64 fixed-point number by an integer, down to an integer.
+    FixedMulInt,
+    /// Raises a 64.64 fixed-point number to an integer power.
+    FixedPow,
+}
+
+impl Precompile {
+    /// Returns all registered precompiles, in id order.
+    pub fn all() -> Vec<Precompile> {
+        vec![
+            Precompile::Sha512,
+            Precompile::HmacSha256,
+            Precompile::EcdsaVerify,
+            Precompile::ModMul,
+            Precompile::ModExp,
+            Precompile::FixedMul,
+            Precompile::FixedDiv,
+            Precompile::FixedFromRatio,
+            Precompile::FixedMulInt,
+            Precompile::FixedPow,
+        ]
+    }
+
+    /// Returns the precompile registered under the given id.
+    pub fn from_id(id: u8) -> Option<Precompile> {
+        match id {
+            0x01 => Some(Precompile::Sha512),
+            0x02 => Some(Precompile::HmacSha256),
+            0x03 => Some(Precompile::EcdsaVerify),
+            0x04 => Some(Precompile::ModMul),
+            0x05 => Some(Precompile::ModExp),
+            0x06 => Some(Precompile::FixedMul),
+            0x07 => Some(Precompile::FixedDiv),
+            0x08 => Some(Precompile::FixedFromRatio),
+            0x09 => Some(Precompile::FixedMulInt),
+            0x0a => Some(Precompile::FixedPow),
+            _ => None,
+        }
+    }
+
+    /// Returns the id of the precompile.
+    pub fn id(&self) -> u8 {
+        match self {
+            Precompile::Sha512 => 0x01,
+            Precompile::HmacSha256 => 0x02,
+            Precompile::EcdsaVerify => 0x03,
+            Precompile::ModMul => 0x04,
+            Precompile::ModExp => 0x05,
+            Precompile::FixedMul => 0x06,
+            Precompile::FixedDiv => 0x07,
+            Precompile::FixedFromRatio => 0x08,
+            Precompile::FixedMulInt => 0x09,
+            Precompile::FixedPow => 0x0a,
+        }
+    }
+
+    /// Returns the fixed number of ops charged for the precompile.
+    pub fn ops(&self) -> u32 {
+        match self {
+            Precompile::Sha512 => SHA512_PRECOMPILE_OPS,
+            Precompile::HmacSha256 => HMAC_SHA256_PRECOMPILE_OPS,
+            Precompile::EcdsaVerify => ECDSA_VERIFY_PRECOMPILE_OPS,
+            Precompile::ModMul => MODMUL_PRECOMPILE_OPS,
+            Precompile::ModExp => MODEXP_PRECOMPILE_OPS,
+            Precompile::FixedMul => FIXED_MUL_PRECOMPILE_OPS,
+            Precompile::FixedDiv => FIXED_DIV_PRECOMPILE_OPS,
+            Precompile::FixedFromRatio => FIXED_FROM_RATIO_PRECOMPILE_OPS,
+            Precompile::FixedMulInt => FIXED_MUL_INT_PRECOMPILE_OPS,
+            Precompile::FixedPow => FIXED_POW_PRECOMPILE_OPS,
+        }
+    }
+
+    /// Runs the precompile against the main stack, popping its inputs and pushing its output.
+    pub fn execute(&self, stack_holder: &mut StackHolder) -> Result<(), StackError> {
+        // 1 Compute the output.
+        let output = match self {
+            Precompile::Sha512 => {
+                // 1.1 Pop the preimage.
+                let preimage = stack_holder.pop()?;
+
+                // 1.2 Hash the preimage.
+                StackItem::new(sha512(preimage.bytes()).to_vec())
+            }
+            Precompile::HmacSha256 => {
+                // 1.1 Pop the message and the key.
+                let message = stack_holder.pop()?;
+                let key = stack_holder.pop()?;
+
+                // 1.2 Authenticate the message under the key.
+                let mut engine = HmacEngine::<sha256::Hash>::new(key.bytes());
+                engine.input(message.bytes());
+                StackItem::new(
+                    Hmac::<sha256::Hash>::from_engine(engine)
+                        .to_byte_array()
+                        .to_vec(),
+                )
+            }
+            Precompile::EcdsaVerify => {
+                // 1.1 Pop the public key, the message and the signature.
+                let public_key = stack_holder.pop()?;
+                let message = stack_holder.pop()?;
+                let signature = stack_holder.pop()?;
+
+                // 1.2 Parse the compressed or uncompressed public key.
+                let public_key = PublicKey::from_slice(public_key.bytes()).map_err(|_| {
+                    StackError::PrecompileError(PrecompileError::InvalidECDSAPublicKeyBytes)
+                })?;
+
+                // 1.3 Parse the 32-byte message digest.
+                let message_bytes: [u8; 32] = message.bytes().try_into().map_err(|_| {
+                    StackError::PrecompileError(PrecompileError::InvalidECDSAMessageBytes)
+                })?;
+                let message = Message::from_digest(message_bytes);
+
+                // 1.4 Parse the 64-byte compact signature.
+                let signature = Signature::from_compact(signature.bytes()).map_err(|_| {
+                    StackError::PrecompileError(PrecompileError::InvalidECDSASignatureBytes)
+                })?;
+
+                // 1.5 Verify the signature; high-S signatures do not verify.
+                match secp256k1_context()
+                    .verify_ecdsa(&message, &signature, &public_key)
+                    .is_ok()
+                {
+                    true => StackItem::true_item(),
+                    false => StackItem::false_item(),
+                }
+            }
+            Precompile::ModMul => {
+                // 1.1 Pop the modulus and the two factors.
+                let modulus = pop_uint(stack_holder)?;
+                let y = pop_uint(stack_holder)?;
+                let x = pop_uint(stack_holder)?;
+
+                // 1.2 Multiply modulo the modulus.
+                let result = StackUint::modmul(&x, &y, &modulus)
+                    .ok_or(StackError::PrecompileError(PrecompileError::ZeroModulus))?;
+                StackItem::from_stack_uint(result)
+            }
+            Precompile::ModExp => {
+                // 1.1 Pop the modulus, the exponent and the base.
+                let modulus = pop_uint(stack_holder)?;
+                let exponent = pop_uint(stack_holder)?;
+                let base = pop_uint(stack_holder)?;
+
+                // 1.2 Exponentiate modulo the modulus.
+                let result = StackUint::modexp(&base, &exponent, &modulus)
+                    .ok_or(StackError::PrecompileError(PrecompileError::ZeroModulus))?;
+                StackItem::from_stack_uint(result)
+            }
+            Precompile::FixedMul => {
+                // 1.1 Pop the two factors.
+                let y = pop_fixed(stack_holder)?;
+                let x = pop_fixed(stack_holder)?;
+
+                // 1.2 Multiply, rounding down.
+                let result = x.checked_mul(&y).ok_or(StackError::PrecompileError(
+                    PrecompileError::FixedPointOverflow,
+                ))?;
+                StackItem::from_stack_uint(result.to_stack_uint())
+            }
+            Precompile::FixedDiv => {
+                // 1.1 Pop the divisor and the dividend.
+                let y = pop_fixed(stack_holder)?;
+                let x = pop_fixed(stack_holder)?;
+
+                // 1.2 Divide, rounding down.
+                if y.raw() == 0 {
+                    return Err(StackError::PrecompileError(
+                        PrecompileError::FixedPointDivisionByZero,
+                    ));
+                }
+                let result = x.checked_div(&y).ok_or(StackError::PrecompileError(
+                    PrecompileError::FixedPointOverflow,
+                ))?;
+                StackItem::from_stack_uint(result.to_stack_uint())
+            }
+            Precompile::FixedFromRatio => {
+                // 1.1 Pop the denominator and the numerator.
+                let denominator = pop_u128(stack_holder)?;
+                let numerator = pop_u128(stack_holder)?;
+
+                // 1.2 Divide, rounding down.
+                if denominator == 0 {
+                    return Err(StackError::PrecompileError(
+                        PrecompileError::FixedPointDivisionByZero,
+                    ));
+                }
+                let result = StackFixed::from_ratio(numerator, denominator).ok_or(
+                    StackError::PrecompileError(PrecompileError::FixedPointOverflow),
+                )?;
+                StackItem::from_stack_uint(result.to_stack_uint())
+            }
+            Precompile::FixedMulInt => {
+                // 1.1 Pop the integer and the fixed-point number.
+                let int = pop_u128(stack_holder)?;
+                let x = pop_fixed(stack_holder)?;
+
+                // 1.2 Multiply, rounding down to an integer.
+                let result = x.checked_mul_int(int).ok_or(StackError::PrecompileError(
+                    PrecompileError::FixedPointOverflow,
+                ))?;
+                StackItem::from_stack_uint(StackUint::from(result))
+            }
+            Precompile::FixedPow => {
+                // 1.1 Pop the exponent and the base.
+                let exponent = pop_u128(stack_holder)?;
+                let base = pop_fixed(stack_holder)?;
+
+                // 1.2 Exponentiate, rounding down at every step.
+                let result = u64::try_from(exponent)
+                    .ok()
+                    .and_then(|exponent| base.checked_pow(exponent))
+                    .ok_or(StackError::PrecompileError(
+                        PrecompileError::FixedPointOverflow,
+                    ))?;
+                StackItem::from_stack_uint(result.to_stack_uint())
+            }
+        };
+
+        // 2 Increment the ops counter.
+        stack_holder.increment_ops(self.ops())?;
+
+        // 3 Push the output to the main stack.
+        stack_holder.push(output)?;
+
+        Ok(())
+    }
+}
+
+impl ToString for Precompile {
+    fn to_string(&self) -> String {
+        match self {
+            Precompile::Sha512 => "sha512".to_string(),
+            Precompile::HmacSha256 => "hmac_sha256".to_string(),
+            Precompile::EcdsaVerify => "ecdsa_verify".to_string(),
+            Precompile::ModMul => "modmul".to_string(),
+            Precompile::ModExp => "modexp".to_string(),
+            Precompile::FixedMul => "fixed_mul".to_string(),
+            Precompile::FixedDiv => "fixed_div".to_string(),
+            Precompile::FixedFromRatio => "fixed_from_ratio".to_string(),
+            Precompile::FixedMulInt => "fixed_mul_int".to_string(),
+            Precompile::FixedPow => "fixed_pow".to_string(),
+        }
+    }
+}
+
+/// Pops an integer argument from the main stack.
+fn pop_uint(stack_holder: &mut StackHolder) -> Result<StackUint, StackError> {
+    let item = stack_holder.pop()?;
+    item.to_stack_uint().ok_or(StackError::PrecompileError(
+        PrecompileError::InvalidUintBytes(item.bytes().to_vec()),
+    ))
+}
+
+/// Pops an integer argument that fits in 128 bits from the main stack.
+fn pop_u128(stack_holder: &mut StackHolder) -> Result<u128, StackError> {
+    let int = pop_uint(stack_holder)?;
+    if int.bits() > 128 {
+        return Err(StackError::PrecompileError(
+            PrecompileError::FixedPointOverflow,
+        ));
+    }
+    Ok(int.low_u128())
+}
+
+/// Pops a 64.64 fixed-point argument from the main stack.
+fn pop_fixed(stack_holder: &mut StackHolder) -> Result<StackFixed, StackError> {
+    let item = stack_holder.pop()?;
+    item.to_stack_uint()
+        .and_then(|raw| StackFixed::from_stack_uint(&raw))
+        .ok_or(StackError::PrecompileError(
+            PrecompileError::InvalidFixedPointBytes(item.bytes().to_vec()),
+        ))
+}
@@ -4,6 +4,12 @@ use crate::executive::executable::method::{
     compiler::compiler::MethodCompiler, program_method::ProgramMethod,
 };
 
+/// Flag bit set when the program carries metadata.
+const PROGRAM_FLAG_METADATA: u8 = 0x01;
+
+/// Flag bit set when the program opts in to being re-entered by the contracts it calls.
+const PROGRAM_FLAG_REENTRANT: u8 = 0x02;
+
 /// A trait for compiling and decompiling a program.
 pub trait ProgramCompiler {
     /// Compiles the program into a bytecode.
@@ -25,14 +31,20 @@ impl ProgramCompiler for Program {
         // Encode program name.
         executable_bytes.extend(self.program_name().as_bytes());
 
+        // Encode the flags byte.
+        let mut flags = 0x00;
+        if self.metadata().is_some() {
+            flags |= PROGRAM_FLAG_METADATA;
+        }
+        if self.reentrant() {
+            flags |= PROGRAM_FLAG_REENTRANT;
+        }
+        executable_bytes.push(flags);
+
         // Encode metadata.
-        match self.metadata() {
-            Some(metadata) => {
-                executable_bytes.push(0x01);
-                executable_bytes.extend((metadata.len() as u16).to_le_bytes());
-                executable_bytes.extend(metadata);
-            }
-            None => executable_bytes.push(0x00),
+        if let Some(metadata) = self.metadata() {
+            executable_bytes.extend((metadata.len() as u16).to_le_bytes());
+            executable_bytes.extend(metadata);
         }
 
         // Encode method count as u8.
@@ -71,13 +83,18 @@ impl ProgramCompiler for Program {
             return Err(ProgramDecompileError::ProgramNameBytesCollectError);
         }
 
-        // Collect metadata presence byte.
-        let metadata = match bytecode_stream
+        // Collect the flags byte.
+        let flags = bytecode_stream
             .next()
-            .ok_or(ProgramDecompileError::MetadataFlagByteCollectError)?
-        {
+            .ok_or(ProgramDecompileError::MetadataFlagByteCollectError)?;
+        if flags & !(PROGRAM_FLAG_METADATA | PROGRAM_FLAG_REENTRANT) != 0 {
+            return Err(ProgramDecompileError::MetadataFlagByteCollectError);
+        }
+
+        // Collect metadata.
+        let metadata = match flags & PROGRAM_FLAG_METADATA {
             0x00 => None,
-            0x01 => {
+            _ => {
                 let metadata_len_bytes: [u8; 2] = bytecode_stream
                     .by_ref()
                     .take(2)
@@ -92,9 +109,11 @@ impl ProgramCompiler for Program {
                 }
                 Some(metadata_bytes)
             }
-            _ => return Err(ProgramDecompileError::MetadataFlagByteCollectError),
         };
 
+        // Collect the reentrancy opt-in.
+        let reentrant = flags & PROGRAM_FLAG_REENTRANT != 0;
+
         // Convert executable name bytes to string.
         let program_name = String::from_utf8_lossy(&executable_name_bytes).to_string();
 
@@ -112,7 +131,7 @@ impl ProgramCompiler for Program {
         }
 
         // Construct the executable.
-        let executable = Program::new(program_name, metadata, methods)
+        let executable = Program::new(program_name, metadata, reentrant, methods)
             .map_err(|e| ProgramDecompileError::ProgramConstructError(e))?;
 
         // Return the executable.
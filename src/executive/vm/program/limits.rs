@@ -9,3 +9,6 @@ pub const MAX_METHOD_COUNT: usize = 256;
 
 /// The minimum method count in a program.
 pub const MIN_METHOD_COUNT: usize = 1;
+
+/// The maximum compiled byte size of a program.
+pub const MAX_PROGRAM_BYTE_SIZE: usize = 256 * 1024;
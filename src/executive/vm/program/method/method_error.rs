@@ -53,3 +53,76 @@ impl fmt::Display for ScriptValidationError {
         }
     }
 }
+
+/// The error that occurs when statically analyzing the script of a method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptAnalysisError {
+    /// The opcode at the index is banned in the method type.
+    BannedOpcode { index: usize, opcode: String },
+    /// An `OP_ELSE` at the index has no preceding `OP_IF` or `OP_NOTIF`.
+    UnexpectedElse(usize),
+    /// An `OP_ELSE` at the index follows another `OP_ELSE` of the same conditional.
+    DuplicateElse(usize),
+    /// An `OP_ENDIF` at the index has no preceding `OP_IF` or `OP_NOTIF`.
+    UnexpectedEndif(usize),
+    /// The `OP_IF` or `OP_NOTIF` at the index is never closed with an `OP_ENDIF`.
+    UnclosedConditional(usize),
+    /// The opcode at the index needs more stack items than the stack can hold at that point.
+    StackUnderflow {
+        index: usize,
+        opcode: String,
+        required: u32,
+        available: u32,
+    },
+    /// The script never returns or hands over to another call.
+    MissingReturn,
+}
+
+impl fmt::Display for ScriptAnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptAnalysisError::BannedOpcode { index, opcode } => {
+                write!(
+                    f,
+                    "{} at index {} is banned in this method type",
+                    opcode, index
+                )
+            }
+            ScriptAnalysisError::UnexpectedElse(index) => {
+                write!(
+                    f,
+                    "OP_ELSE at index {} has no matching OP_IF or OP_NOTIF",
+                    index
+                )
+            }
+            ScriptAnalysisError::DuplicateElse(index) => {
+                write!(f, "OP_ELSE at index {} follows another OP_ELSE", index)
+            }
+            ScriptAnalysisError::UnexpectedEndif(index) => {
+                write!(
+                    f,
+                    "OP_ENDIF at index {} has no matching OP_IF or OP_NOTIF",
+                    index
+                )
+            }
+            ScriptAnalysisError::UnclosedConditional(index) => {
+                write!(f, "Conditional at index {} is never closed", index)
+            }
+            ScriptAnalysisError::StackUnderflow {
+                index,
+                opcode,
+                required,
+                available,
+            } => {
+                write!(
+                    f,
+                    "{} at index {} needs {} stack items but at most {} are available",
+                    opcode, index, required, available
+                )
+            }
+            ScriptAnalysisError::MissingReturn => {
+                write!(f, "Script never returns")
+            }
+        }
+    }
+}
@@ -3,3 +3,4 @@ pub mod limits;
 pub mod program_method;
 pub mod method_error;
 pub mod method_type;
+pub mod script_analysis;
@@ -3,12 +3,14 @@ use super::{
         MAX_METHOD_ARG_COUNT, MAX_METHOD_NAME_LENGTH, MAX_METHOD_OPCODE_COUNT,
         MIN_METHOD_ARG_COUNT, MIN_METHOD_NAME_LENGTH, MIN_METHOD_OPCODE_COUNT,
     },
-    method_error::{MethodConstructionError, ScriptValidationError},
+    method_error::{MethodConstructionError, ScriptAnalysisError, ScriptValidationError},
     method_type::MethodType,
+    script_analysis::StackEffect,
 };
 use crate::{
     constructive::calldata::element_type::CalldataElementType,
     executive::{
+        abi::abi_type::AbiType,
         opcode::{opcode::Opcode, opcodes::push::op_pushdata::OP_PUSHDATA},
         stack::{
             stack_item::StackItem,
@@ -93,6 +95,14 @@ impl ProgramMethod {
         self.arg_types.clone()
     }
 
+    /// Returns the ABI types of the arguments, as the method receives them on the stack.
+    pub fn abi_arg_types(&self) -> Vec<AbiType> {
+        self.arg_types
+            .iter()
+            .map(|arg_type| AbiType::from_calldata_element_type(*arg_type))
+            .collect()
+    }
+
     /// Returns the script.
     pub fn script(&self) -> &Vec<Opcode> {
         &self.script
@@ -116,6 +126,112 @@ impl ProgramMethod {
         Ok(())
     }
 
+    /// Statically analyzes the script before the method is registered.
+    ///
+    /// Checks that the conditionals are balanced, that read-only methods do not change the ledger
+    /// state, that the script can return, and that no opcode is certain to underflow the stack.
+    /// The stack is followed from the args, through both branches of the conditionals, up to the
+    /// first opcode whose effect depends on runtime values.
+    pub fn analyze_script(&self) -> Result<(), ScriptAnalysisError> {
+        // 1 Upper bound of the stack depth, or `None` where the script is unreachable.
+        let mut depth: Option<u32> = Some(self.arg_types.len() as u32);
+
+        // 2 Whether the stack is still followed.
+        let mut following = true;
+
+        // 3 The open conditionals: their index, the depth at their start, and the depth at the
+        // end of their first branch once an `OP_ELSE` is met.
+        let mut conditionals = Vec::<(usize, Option<u32>, Option<Option<u32>>)>::new();
+
+        // 4 Whether the script can return or hand over to another call.
+        let mut returns = false;
+
+        for (index, opcode) in self.script.iter().enumerate() {
+            // 4.1 Read-only methods must not change the ledger state.
+            if self.method_type == MethodType::ReadOnly && StackEffect::mutates_state(opcode) {
+                return Err(ScriptAnalysisError::BannedOpcode {
+                    index,
+                    opcode: opcode.to_string(),
+                });
+            }
+
+            // 4.2 Note the opcodes that end the execution with a result.
+            if matches!(
+                opcode,
+                Opcode::OP_RETURNALL(_)
+                    | Opcode::OP_RETURNSOME(_)
+                    | Opcode::OP_CALL(_)
+                    | Opcode::OP_CALLEXT(_)
+            ) {
+                returns = true;
+            }
+
+            // 4.3 Check that the stack holds the items the opcode needs.
+            let effect = StackEffect::of(opcode);
+            let required = match effect {
+                StackEffect::Fixed { required, .. } | StackEffect::Exit { required } => required,
+                StackEffect::Conditional => 1,
+                _ => 0,
+            };
+            if let (true, Some(available)) = (following, depth) {
+                if available < required {
+                    return Err(ScriptAnalysisError::StackUnderflow {
+                        index,
+                        opcode: opcode.to_string(),
+                        required,
+                        available,
+                    });
+                }
+            }
+
+            // 4.4 Apply the effect of the opcode.
+            match effect {
+                StackEffect::Fixed { max_net, .. } => {
+                    depth = depth.map(|depth| (depth as i64 + max_net as i64).max(0) as u32);
+                }
+                StackEffect::Conditional => {
+                    depth = depth.map(|depth| depth.saturating_sub(1));
+                    conditionals.push((index, depth, None));
+                }
+                StackEffect::Else => {
+                    let (_, start, first_branch_end) = conditionals
+                        .last_mut()
+                        .ok_or(ScriptAnalysisError::UnexpectedElse(index))?;
+                    if first_branch_end.is_some() {
+                        return Err(ScriptAnalysisError::DuplicateElse(index));
+                    }
+                    *first_branch_end = Some(depth);
+                    depth = *start;
+                }
+                StackEffect::Endif => {
+                    let (_, start, first_branch_end) = conditionals
+                        .pop()
+                        .ok_or(ScriptAnalysisError::UnexpectedEndif(index))?;
+                    // Without an `OP_ELSE`, the skipped branch leaves the stack as it started.
+                    let other_branch_end = first_branch_end.unwrap_or(start);
+                    depth = match (depth, other_branch_end) {
+                        (Some(a), Some(b)) => Some(a.max(b)),
+                        (a, b) => a.or(b),
+                    };
+                }
+                StackEffect::Exit { .. } => depth = None,
+                StackEffect::Opaque => following = false,
+            }
+        }
+
+        // 5 Every conditional must be closed.
+        if let Some((index, _, _)) = conditionals.first() {
+            return Err(ScriptAnalysisError::UnclosedConditional(*index));
+        }
+
+        // 6 The script must be able to return.
+        if !returns {
+            return Err(ScriptAnalysisError::MissingReturn);
+        }
+
+        Ok(())
+    }
+
     /// Matches the args to the arg types.
     pub fn match_args(&self, args: &Vec<StackItem>) -> bool {
         // Check if the number of args matches the number of arg types.
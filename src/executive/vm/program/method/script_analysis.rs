@@ -0,0 +1,192 @@
+use crate::executive::opcode::opcode::Opcode;
+
+/// The effect of an opcode on the main stack, as seen by the static analysis of a script.
+///
+/// Effects are upper bounds: an opcode needs at least `required` items and grows the stack by at
+/// most `max_net` items, whichever path it takes, so that the analysis never rejects a script that
+/// could run.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StackEffect {
+    /// Needs `required` items and changes the depth by at most `max_net`.
+    Fixed { required: u32, max_net: i32 },
+    /// Pops the condition and opens a conditional (`OP_IF`, `OP_NOTIF`).
+    Conditional,
+    /// Switches to the other branch of the open conditional (`OP_ELSE`).
+    Else,
+    /// Closes the open conditional (`OP_ENDIF`).
+    Endif,
+    /// Needs `required` items and ends the branch it runs in, by returning, failing or handing the
+    /// execution over to another call.
+    Exit { required: u32 },
+    /// Ends the analysis: the effect depends on runtime values, or the opcode moves the execution
+    /// to a place the analysis cannot follow.
+    Opaque,
+}
+
+impl StackEffect {
+    /// Returns the stack effect of the given opcode.
+    pub fn of(opcode: &Opcode) -> StackEffect {
+        let fixed = |required: u32, max_net: i32| StackEffect::Fixed { required, max_net };
+        match opcode {
+            // Data push
+            Opcode::OP_FALSE(_)
+            | Opcode::OP_PUSHDATA(_)
+            | Opcode::OP_TRUE(_)
+            | Opcode::OP_2(_)
+            | Opcode::OP_3(_)
+            | Opcode::OP_4(_)
+            | Opcode::OP_5(_)
+            | Opcode::OP_6(_)
+            | Opcode::OP_7(_)
+            | Opcode::OP_8(_)
+            | Opcode::OP_9(_)
+            | Opcode::OP_10(_)
+            | Opcode::OP_11(_)
+            | Opcode::OP_12(_)
+            | Opcode::OP_13(_)
+            | Opcode::OP_14(_)
+            | Opcode::OP_15(_)
+            | Opcode::OP_16(_) => fixed(0, 1),
+            // Flow
+            Opcode::OP_NOP(_) => fixed(0, 0),
+            Opcode::OP_IF(_) | Opcode::OP_NOTIF(_) => StackEffect::Conditional,
+            Opcode::OP_ELSE(_) => StackEffect::Else,
+            Opcode::OP_ENDIF(_) => StackEffect::Endif,
+            Opcode::OP_VERIFY(_) => fixed(1, -1),
+            Opcode::OP_RETURNSOME(_) => StackEffect::Exit { required: 1 },
+            Opcode::OP_FAIL(_) => StackEffect::Exit { required: 0 },
+            // `OP_RETURNALL` returns even from an inactive branch, and `OP_JUMP` pops its target.
+            Opcode::OP_RETURNALL(_) | Opcode::OP_JUMP(_) => StackEffect::Opaque,
+            // Altstack
+            Opcode::OP_TOALTSTACK(_) => fixed(1, -1),
+            Opcode::OP_FROMALTSTACK(_) => fixed(0, 1),
+            // Stack
+            Opcode::OP_2DROP(_) => fixed(2, -2),
+            Opcode::OP_2DUP(_) => fixed(2, 2),
+            Opcode::OP_3DUP(_) => fixed(3, 3),
+            Opcode::OP_2OVER(_) => fixed(4, 2),
+            Opcode::OP_2ROT(_) => fixed(6, 0),
+            Opcode::OP_2SWAP(_) => fixed(4, 0),
+            Opcode::OP_IFDUP(_) => fixed(1, 1),
+            Opcode::OP_DEPTH(_) => fixed(0, 1),
+            Opcode::OP_DROP(_) => fixed(1, -1),
+            Opcode::OP_DUP(_) => fixed(1, 1),
+            Opcode::OP_NIP(_) => fixed(2, -1),
+            Opcode::OP_OVER(_) => fixed(2, 1),
+            Opcode::OP_PICK(_) | Opcode::OP_ROLL(_) => StackEffect::Opaque,
+            Opcode::OP_ROT(_) => fixed(3, 0),
+            Opcode::OP_SWAP(_) => fixed(2, 0),
+            Opcode::OP_TUCK(_) => fixed(2, 1),
+            // Splice
+            Opcode::OP_CAT(_) | Opcode::OP_LEFT(_) | Opcode::OP_RIGHT(_) => fixed(2, -1),
+            Opcode::OP_SPLIT(_) => fixed(2, 0),
+            Opcode::OP_SIZE(_) => fixed(1, 1),
+            // Bitwise
+            Opcode::OP_INVERT(_) | Opcode::OP_REVERSE(_) => fixed(1, 0),
+            Opcode::OP_AND(_) | Opcode::OP_OR(_) | Opcode::OP_XOR(_) | Opcode::OP_EQUAL(_) => {
+                fixed(2, -1)
+            }
+            Opcode::OP_EQUALVERIFY(_) => fixed(2, -2),
+            // Arithmetic, pushing their result with a success flag, or their inputs back with a
+            // failure flag.
+            Opcode::OP_1ADD(_)
+            | Opcode::OP_1SUB(_)
+            | Opcode::OP_2MUL(_)
+            | Opcode::OP_2DIV(_)
+            | Opcode::OP_NOT(_)
+            | Opcode::OP_0NOTEQUAL(_) => fixed(1, 1),
+            Opcode::OP_ADDMOD(_)
+            | Opcode::OP_MULMOD(_)
+            | Opcode::OP_ADD(_)
+            | Opcode::OP_SUB(_)
+            | Opcode::OP_MUL(_)
+            | Opcode::OP_DIV(_)
+            | Opcode::OP_LSHIFT(_)
+            | Opcode::OP_RSHIFT(_)
+            | Opcode::OP_BOOLAND(_)
+            | Opcode::OP_BOOLOR(_)
+            | Opcode::OP_NUMEQUAL(_)
+            | Opcode::OP_NUMNOTEQUAL(_)
+            | Opcode::OP_LESSTHAN(_)
+            | Opcode::OP_GREATERTHAN(_)
+            | Opcode::OP_LESSTHANOREQUAL(_)
+            | Opcode::OP_GREATERTHANOREQUAL(_)
+            | Opcode::OP_MIN(_)
+            | Opcode::OP_MAX(_) => fixed(2, 1),
+            Opcode::OP_NUMEQUALVERIFY(_) => fixed(2, -2),
+            Opcode::OP_WITHIN(_) => fixed(3, -1),
+            // Hashing
+            Opcode::OP_RIPEMD160(_)
+            | Opcode::OP_SHA1(_)
+            | Opcode::OP_SHA256(_)
+            | Opcode::OP_HASH160(_)
+            | Opcode::OP_HASH256(_) => fixed(1, 0),
+            Opcode::OP_TAGGEDHASH(_) | Opcode::OP_BLAKE2BVAR(_) | Opcode::OP_BLAKE2SVAR(_) => {
+                fixed(2, -1)
+            }
+            // Secp
+            Opcode::OP_SECPSCALARADD(_)
+            | Opcode::OP_SECPSCALARMUL(_)
+            | Opcode::OP_SECPPOINTADD(_)
+            | Opcode::OP_SECPPOINTMUL(_) => fixed(2, -1),
+            Opcode::OP_PUSHSECPGENERATORPOINT(_) => fixed(0, 1),
+            Opcode::OP_ISZEROSECPSCALAR(_) | Opcode::OP_ISINFINITESECPPOINT(_) => fixed(1, 1),
+            // Digital signatures
+            Opcode::OP_CHECKSCHNORRSIG(_)
+            | Opcode::OP_CHECKSCHNORRSIGBIP340(_)
+            | Opcode::OP_CHECKBLSSIG(_) => fixed(3, -2),
+            Opcode::OP_CHECKBLSSIGAGG(_) => StackEffect::Opaque,
+            // Call info
+            Opcode::OP_CALLER(_) => fixed(0, 2),
+            Opcode::OP_OPSBUDGET(_)
+            | Opcode::OP_OPSCOUNTER(_)
+            | Opcode::OP_OPSPRICE(_)
+            | Opcode::OP_TIMESTAMP(_) => fixed(0, 1),
+            // Call
+            Opcode::OP_CALL(_) => StackEffect::Exit { required: 2 },
+            Opcode::OP_CALLEXT(_) => StackEffect::Exit { required: 3 },
+            // Coin
+            Opcode::OP_EXT_BALANCE(_) | Opcode::OP_TRANSFER(_) => StackEffect::Opaque,
+            Opcode::OP_SELF_BALANCE(_) => fixed(0, 1),
+            Opcode::OP_SEND(_) => fixed(3, -3),
+            // Shadowing
+            Opcode::OP_SHADOW_ALLOC(_)
+            | Opcode::OP_SHADOW_DEALLOC(_)
+            | Opcode::OP_SHADOW_UP_ALL(_)
+            | Opcode::OP_SHADOW_DOWN_ALL(_) => fixed(1, -1),
+            Opcode::OP_SHADOW_HAS_ALLOC(_) => fixed(1, 0),
+            Opcode::OP_SHADOW_ALLOC_VAL(_) => StackEffect::Opaque,
+            Opcode::OP_SHADOW_UP(_) | Opcode::OP_SHADOW_DOWN(_) => fixed(2, -2),
+            Opcode::OP_SHADOW_NUM_ALLOCS(_) | Opcode::OP_SHADOW_ALLOCS_SUM(_) => fixed(0, 1),
+            // Storage
+            Opcode::OP_SWRITE(_) => fixed(2, -2),
+            Opcode::OP_SREAD(_) => fixed(1, 0),
+            // Memory
+            Opcode::OP_MWRITE(_) => fixed(2, -1),
+            Opcode::OP_MREAD(_) | Opcode::OP_MFREE(_) => fixed(1, 0),
+            // Precompile
+            Opcode::OP_PRECOMPILE(_) => StackEffect::Opaque,
+            // Event
+            Opcode::OP_EMIT(_) => fixed(2, -2),
+            // Host data, pushing the value with a success flag, or a failure flag.
+            Opcode::OP_HOSTDATA(_) => fixed(1, 1),
+        }
+    }
+
+    /// Returns whether the opcode changes the ledger state, and so is banned in read-only methods.
+    pub fn mutates_state(opcode: &Opcode) -> bool {
+        matches!(
+            opcode,
+            Opcode::OP_TRANSFER(_)
+                | Opcode::OP_SEND(_)
+                | Opcode::OP_SHADOW_ALLOC(_)
+                | Opcode::OP_SHADOW_DEALLOC(_)
+                | Opcode::OP_SHADOW_UP(_)
+                | Opcode::OP_SHADOW_DOWN(_)
+                | Opcode::OP_SHADOW_UP_ALL(_)
+                | Opcode::OP_SHADOW_DOWN_ALL(_)
+                | Opcode::OP_SWRITE(_)
+                | Opcode::OP_EMIT(_)
+        )
+    }
+}
@@ -1,9 +1,12 @@
 use super::limits::{
-    MAX_METHOD_COUNT, MAX_PROGRAM_NAME_LENGTH, MIN_METHOD_COUNT, MIN_PROGRAM_NAME_LENGTH,
+    MAX_METHOD_COUNT, MAX_PROGRAM_BYTE_SIZE, MAX_PROGRAM_NAME_LENGTH, MIN_METHOD_COUNT,
+    MIN_PROGRAM_NAME_LENGTH,
 };
 use super::method::method_type::MethodType;
 use super::method::program_method::ProgramMethod;
-use super::program_error::{MethodValidationError, ProgramConstructionError};
+use super::program_error::{
+    MethodValidationError, ProgramConstructionError, ProgramValidationError,
+};
 use crate::constructive::valtype::val::atomic_val::atomic_val::AtomicVal;
 use crate::executive::executable::compiler::compiler::ProgramCompiler;
 use crate::transmutative::hash::{Hash, HashTag};
@@ -11,6 +14,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::collections::HashSet;
 
+/// Name of the internal method run to migrate the state of a contract right after its program is
+/// upgraded.
+pub const MIGRATION_METHOD_NAME: &str = "migrate";
+
 /// The executable associated with a `Contract`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Program {
@@ -20,6 +27,10 @@ pub struct Program {
     /// Optional metadata associated with this program.
     metadata: Option<Vec<u8>>,
 
+    /// Whether this program opts in to being re-entered by the contracts it calls.
+    #[serde(default)]
+    reentrant: bool,
+
     /// The methods to execute.
     methods: Vec<ProgramMethod>,
 }
@@ -30,6 +41,7 @@ impl Program {
         Self {
             program_name: String::new(),
             metadata: None,
+            reentrant: false,
             methods: Vec::new(),
         }
     }
@@ -38,6 +50,7 @@ impl Program {
     pub fn new(
         program_name: String,
         metadata: Option<Vec<u8>>,
+        reentrant: bool,
         methods: Vec<ProgramMethod>,
     ) -> Result<Self, ProgramConstructionError> {
         // Check program name length.
@@ -63,6 +76,7 @@ impl Program {
         let program = Self {
             program_name,
             metadata,
+            reentrant,
             methods: ordered_methods,
         };
 
@@ -80,6 +94,11 @@ impl Program {
         self.metadata.as_ref()
     }
 
+    /// Returns whether the program opts in to being re-entered by the contracts it calls.
+    pub fn reentrant(&self) -> bool {
+        self.reentrant
+    }
+
     /// Returns the method count.
     pub fn methods_len(&self) -> usize {
         self.methods.len()
@@ -97,6 +116,19 @@ impl Program {
             .position(|method| method.method_name() == method_name)
     }
 
+    /// Returns the index of the state-migration hook, an internal method named `migrate` that takes
+    /// no args, if the program has one.
+    pub fn migration_method_index(&self) -> Option<u16> {
+        self.methods
+            .iter()
+            .position(|method| {
+                method.method_name() == MIGRATION_METHOD_NAME
+                    && method.method_type() == MethodType::Internal
+                    && method.arg_types().is_empty()
+            })
+            .map(|index| index as u16)
+    }
+
     /// Returns the method at the given index.
     pub fn method_by_index(&self, index: u16) -> Option<ProgramMethod> {
         self.methods.get(index as usize).cloned()
@@ -162,6 +194,45 @@ impl Program {
         Ok(())
     }
 
+    /// Statically validates the program before it is registered, so that invalid programs are
+    /// rejected with a detailed error rather than failing at their first execution.
+    pub fn validate(&self) -> Result<(), ProgramValidationError> {
+        // 1 Check the compiled program size.
+        let size = self
+            .compile()
+            .map_err(|_| ProgramValidationError::ProgramCompileError)?
+            .len();
+        if size > MAX_PROGRAM_BYTE_SIZE {
+            return Err(ProgramValidationError::ProgramSizeLimitExceeded {
+                size,
+                limit: MAX_PROGRAM_BYTE_SIZE,
+            });
+        }
+
+        // 2 Validate the methods.
+        self.validate_methods()
+            .map_err(ProgramValidationError::MethodValidationError)?;
+
+        // 3 A method named after the migration hook must be one.
+        if self.index_by_method_name(MIGRATION_METHOD_NAME).is_some()
+            && self.migration_method_index().is_none()
+        {
+            return Err(ProgramValidationError::InvalidMigrationMethod);
+        }
+
+        // 4 Analyze the script of each method.
+        for method in self.methods.iter() {
+            method.analyze_script().map_err(|error| {
+                ProgramValidationError::ScriptAnalysisError {
+                    method_name: method.method_name().to_string(),
+                    error,
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
     /// Returns the 32-bytes contract ID.
     pub fn contract_id(&self) -> [u8; 32] {
         // Compile the executable.
@@ -200,6 +271,9 @@ impl Program {
             },
         );
 
+        // Add the reentrancy opt-in to the program JSON object.
+        obj.insert("reentrant".to_string(), Value::Bool(self.reentrant));
+
         // Add the program name to the program JSON object.
         obj.insert(
             "program_name".to_string(),
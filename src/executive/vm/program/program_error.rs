@@ -1,3 +1,4 @@
+use super::method::method_error::ScriptAnalysisError;
 use std::fmt;
 
 /// A section of program block in the `Contract`.
@@ -49,4 +50,48 @@ impl fmt::Display for MethodValidationError {
     }
 }
 
+/// The error that occurs when statically validating a program before it is registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgramValidationError {
+    /// The program does not compile.
+    ProgramCompileError,
+    /// The compiled program is larger than the limit.
+    ProgramSizeLimitExceeded { size: usize, limit: usize },
+    /// Method validation error.
+    MethodValidationError(MethodValidationError),
+    /// A method named `migrate` is not an internal method taking no args.
+    InvalidMigrationMethod,
+    /// The script of the named method failed the static analysis.
+    ScriptAnalysisError {
+        method_name: String,
+        error: ScriptAnalysisError,
+    },
+}
+
+impl fmt::Display for ProgramValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgramValidationError::ProgramCompileError => {
+                write!(f, "Program does not compile")
+            }
+            ProgramValidationError::ProgramSizeLimitExceeded { size, limit } => {
+                write!(
+                    f,
+                    "Program is {} bytes, over the {} bytes limit",
+                    size, limit
+                )
+            }
+            ProgramValidationError::MethodValidationError(err) => {
+                write!(f, "Method validation error: {}", err)
+            }
+            ProgramValidationError::InvalidMigrationMethod => {
+                write!(f, "Migration method must be internal and take no args")
+            }
+            ProgramValidationError::ScriptAnalysisError { method_name, error } => {
+                write!(f, "Method {}: {}", method_name, error)
+            }
+        }
+    }
+}
+
 pub type ExecutableConstructionError = ProgramConstructionError;
@@ -0,0 +1,182 @@
+use crate::{
+    executive::{
+        program::method::method_type::MethodType,
+        stack::stack_item::StackItem,
+        vm::program_execution::{
+            call_stack::CallStack,
+            caller::Caller,
+            exec::execute,
+            exec_error::ExecutionError,
+            exec_event::ExecEvent,
+            exec_trace::ExecTrace,
+            exec_watchdog::{exec_timeout, ExecWatchdog},
+        },
+    },
+    inscriptive::{
+        coin_manager::coin_manager::COIN_MANAGER, params_manager::params_manager::PARAMS_MANAGER,
+        registery::registery::REGISTERY, state_manager::state_manager::STATE_MANAGER,
+    },
+};
+use serde_json::{Map, Value};
+
+/// The outcome of a simulated call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedCall {
+    /// The items the method left on the stack.
+    pub return_items: Vec<StackItem>,
+    /// The ops the call spent.
+    pub ops_spent: u32,
+    /// The fees the call would be charged at the given ops price.
+    pub fees_spent: u64,
+    /// The events the call emitted.
+    pub events: Vec<ExecEvent>,
+}
+
+impl SimulatedCall {
+    /// Returns the simulated call as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "return_items".to_string(),
+            Value::Array(
+                self.return_items
+                    .iter()
+                    .map(|item| Value::String(hex::encode(item.bytes())))
+                    .collect(),
+            ),
+        );
+        obj.insert("ops_spent".to_string(), Value::from(self.ops_spent));
+        obj.insert("fees_spent".to_string(), Value::from(self.fees_spent));
+        obj.insert(
+            "events".to_string(),
+            Value::Array(self.events.iter().map(|event| event.json()).collect()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Executes a call of the account against the current state without keeping any of its changes,
+/// returning what it left on the stack and the ops and fees it spent.
+///
+/// The call runs in a throwaway delta: the managers are backed up before, and rolled back after,
+/// whether the call passes or fails. Callable methods must end with exactly one true item, as when
+/// the call is inserted. Read-only methods run on a read-only call stack and return their items
+/// as-is.
+pub async fn simulate_call(
+    state_manager: &STATE_MANAGER,
+    coin_manager: &COIN_MANAGER,
+    registery: &REGISTERY,
+    params_manager: &PARAMS_MANAGER,
+    account_key: [u8; 32],
+    contract_id: [u8; 32],
+    method_index: u16,
+    args: Vec<StackItem>,
+    ops_budget: u32,
+    ops_price: u32,
+    timestamp: u64,
+) -> Result<SimulatedCall, ExecutionError> {
+    // 1 Look the method type up; a missing contract or method fails in the execution.
+    let read_only = {
+        let _registery = registery.lock().await;
+        _registery
+            .get_contract_executable(contract_id)
+            .and_then(|executable| executable.method_by_index(method_index))
+            .map(|method| method.method_type() == MethodType::ReadOnly)
+            .unwrap_or(false)
+    };
+
+    // 2 Pre-execution backups.
+    {
+        let mut _registery = registery.lock().await;
+        _registery.pre_execution();
+    }
+    {
+        let mut _coin_manager = coin_manager.lock().await;
+        _coin_manager.pre_execution();
+    }
+    {
+        let mut _state_manager = state_manager.lock().await;
+        _state_manager.pre_execution();
+    }
+
+    // 3 Start the call stack bounded by the chain params, and an empty trace.
+    let mut call_stack = {
+        let params_holder = {
+            let _params_manager = params_manager.lock().unwrap();
+            _params_manager.get_params_holder()
+        };
+        match read_only {
+            true => CallStack::new_read_only(
+                contract_id,
+                params_holder.max_call_depth,
+                params_holder.max_call_count,
+            ),
+            false => CallStack::new(
+                contract_id,
+                params_holder.max_call_depth,
+                params_holder.max_call_count,
+            ),
+        }
+    };
+    let mut trace = ExecTrace::new();
+
+    // 4 Execute the call.
+    let watchdog = ExecWatchdog::start(exec_timeout());
+    let execution_result = execute(
+        false,
+        Caller::new_account(account_key),
+        contract_id,
+        method_index,
+        args,
+        timestamp,
+        ops_budget,
+        ops_price,
+        0,
+        0,
+        0,
+        state_manager,
+        coin_manager,
+        registery,
+        &mut call_stack,
+        &mut trace,
+        &watchdog,
+    )
+    .await;
+
+    // 5 Roll back whatever the call changed.
+    {
+        let mut _registery = registery.lock().await;
+        _registery.rollback_last();
+    }
+    {
+        let mut _coin_manager = coin_manager.lock().await;
+        _coin_manager.rollback_last();
+    }
+    {
+        let mut _state_manager = state_manager.lock().await;
+        _state_manager.rollback_last();
+    }
+
+    // 6 A callable method must end with exactly one true item.
+    let (return_items, ops_spent, _) = execution_result?;
+    if !read_only {
+        match return_items.len() {
+            1 => {
+                if !return_items[0].is_true() {
+                    return Err(ExecutionError::ReturnErrorFromStackError(
+                        return_items[0].clone(),
+                    ));
+                }
+            }
+            _ => return Err(ExecutionError::InvalidStackEndingError),
+        }
+    }
+
+    // 7 Return the outcome.
+    Ok(SimulatedCall {
+        return_items,
+        ops_spent,
+        fees_spent: ops_spent as u64 * ops_price as u64,
+        events: trace.events().to_vec(),
+    })
+}
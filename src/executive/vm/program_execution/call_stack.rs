@@ -0,0 +1,163 @@
+use super::exec_error::ExecutionError;
+
+/// The contracts an execution has entered, and the calls it has made.
+///
+/// Calls are tail calls, so a call never returns into its caller, but every call still nests
+/// the execution one level deeper. The limits come from the chain params, so that they can be
+/// changed without a release.
+///
+/// An external call back into a contract already on the stack is re-entrant. Since the balance and
+/// shadowing opcodes are not written with reentrancy in mind, such calls are rejected, unless the
+/// re-entered program opts in to them, in which case they are let through and recorded.
+#[derive(Debug, Clone)]
+pub struct CallStack {
+    // Contracts entered, starting with the called contract.
+    contracts: Vec<[u8; 32]>,
+
+    // Number of calls made, internal and external.
+    calls: u64,
+
+    // The maximum number of nested external calls.
+    max_call_depth: u64,
+
+    // The maximum number of calls made.
+    max_call_count: u64,
+
+    // Contracts re-entered with their opt-in.
+    reentered: Vec<[u8; 32]>,
+
+    // Whether the execution is a read call, whose changes are never kept.
+    read_only: bool,
+
+    // Contracts tombstoned for unpaid rent, which can no longer be called.
+    tombstoned: Vec<[u8; 32]>,
+}
+
+impl CallStack {
+    /// Creates a new call stack for an execution of the given contract.
+    pub fn new(contract_id: [u8; 32], max_call_depth: u64, max_call_count: u64) -> CallStack {
+        CallStack {
+            contracts: vec![contract_id],
+            calls: 0,
+            max_call_depth,
+            max_call_count,
+            reentered: Vec::new(),
+            read_only: false,
+            tombstoned: Vec::new(),
+        }
+    }
+
+    /// Creates a new call stack for a read call of the given contract, which may execute read-only
+    /// methods.
+    pub fn new_read_only(
+        contract_id: [u8; 32],
+        max_call_depth: u64,
+        max_call_count: u64,
+    ) -> CallStack {
+        CallStack {
+            read_only: true,
+            ..CallStack::new(contract_id, max_call_depth, max_call_count)
+        }
+    }
+
+    /// Sets the contracts tombstoned for unpaid rent, which the execution may not enter.
+    pub fn set_tombstoned(&mut self, tombstoned: Vec<[u8; 32]>) {
+        self.tombstoned = tombstoned;
+    }
+
+    /// Returns whether the execution is a read call.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns the contracts entered, starting with the called contract.
+    pub fn contracts(&self) -> &[[u8; 32]] {
+        &self.contracts
+    }
+
+    /// Returns the number of nested external calls.
+    pub fn depth(&self) -> u64 {
+        self.contracts.len().saturating_sub(1) as u64
+    }
+
+    /// Returns the number of calls made.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Returns the contracts re-entered with their opt-in.
+    pub fn reentered(&self) -> &[[u8; 32]] {
+        &self.reentered
+    }
+
+    /// Records an internal call.
+    pub fn enter_internal(&mut self) -> Result<(), ExecutionError> {
+        self.count_call()
+    }
+
+    /// Records an external call into the given contract.
+    pub fn enter_external(&mut self, contract_id: [u8; 32]) -> Result<(), ExecutionError> {
+        // 1 Check the depth limit.
+        if self.depth() >= self.max_call_depth {
+            return Err(ExecutionError::CallDepthLimitExceededError(
+                self.max_call_depth,
+            ));
+        }
+
+        // 2 Count the call.
+        self.count_call()?;
+
+        // 3 Enter the contract.
+        self.contracts.push(contract_id);
+
+        Ok(())
+    }
+
+    /// Checks that the contract last entered has not been tombstoned for unpaid rent.
+    pub fn guard_tombstoned(&self) -> Result<(), ExecutionError> {
+        match self.contracts.last() {
+            Some(contract_id) if self.tombstoned.contains(contract_id) => {
+                Err(ExecutionError::ContractTombstonedError(*contract_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether the contract last entered was already on the stack.
+    ///
+    /// Re-entrant calls are rejected, unless the contract opts in to them, in which case they are
+    /// recorded.
+    pub fn guard_reentrancy(&mut self, reentrant: bool) -> Result<(), ExecutionError> {
+        // 1 Get the contract last entered.
+        let (contract_id, callers) = match self.contracts.split_last() {
+            Some((contract_id, callers)) => (*contract_id, callers),
+            None => return Ok(()),
+        };
+
+        // 2 Nothing to guard if the contract is entered for the first time.
+        if !callers.contains(&contract_id) {
+            return Ok(());
+        }
+
+        // 3 Reject the call, unless the contract opts in to it.
+        if !reentrant {
+            return Err(ExecutionError::ReentrantCallError(contract_id));
+        }
+
+        // 4 Record the call otherwise.
+        self.reentered.push(contract_id);
+
+        Ok(())
+    }
+
+    /// Counts a call against the call count limit.
+    fn count_call(&mut self) -> Result<(), ExecutionError> {
+        if self.calls >= self.max_call_count {
+            return Err(ExecutionError::CallCountLimitExceededError(
+                self.max_call_count,
+            ));
+        }
+        self.calls += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use super::exec_event::ExecEvent;
+use crate::executive::stack::stack_item::StackItem;
+use std::sync::OnceLock;
+
+/// Whether calls are executed twice to check their determinism, read from the environment on
+/// first use.
+static DETERMINISM_CHECK: OnceLock<bool> = OnceLock::new();
+
+/// Returns whether calls are executed twice to check their determinism.
+///
+/// This is a debug mode for operators, enabled with `CUBE_DETERMINISM_CHECK=1`. It doubles the
+/// cost of every call, and is off by default.
+pub fn determinism_check_enabled() -> bool {
+    *DETERMINISM_CHECK.get_or_init(|| {
+        std::env::var("CUBE_DETERMINISM_CHECK")
+            .map(|value| matches!(value.trim(), "1" | "true"))
+            .unwrap_or(false)
+    })
+}
+
+/// What an execution of a call left behind, compared across the two executions of the call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecOutcome {
+    /// The items the method returned with the ops it spent, or the execution error.
+    pub result: Result<(Vec<Vec<u8>>, u32), String>,
+    /// The state manager delta after the execution, canonically encoded.
+    pub state_delta: Vec<u8>,
+    /// The coin manager delta after the execution, canonically encoded.
+    pub coin_delta: Vec<u8>,
+    /// The events the execution emitted.
+    pub events: Vec<ExecEvent>,
+}
+
+impl ExecOutcome {
+    /// Constructs the outcome of an execution.
+    pub fn new<E: std::fmt::Debug>(
+        result: &Result<(Vec<StackItem>, u32, u32), E>,
+        state_delta: Vec<u8>,
+        coin_delta: Vec<u8>,
+        events: Vec<ExecEvent>,
+    ) -> ExecOutcome {
+        ExecOutcome {
+            result: match result {
+                Ok((return_items, ops_spent, _)) => Ok((
+                    return_items
+                        .iter()
+                        .map(|item| item.bytes().to_vec())
+                        .collect(),
+                    *ops_spent,
+                )),
+                Err(error) => Err(format!("{:?}", error)),
+            },
+            state_delta,
+            coin_delta,
+            events,
+        }
+    }
+
+    /// Returns the first part in which the outcome diverges from the other one, if any.
+    pub fn divergence(&self, other: &ExecOutcome) -> Option<&'static str> {
+        if self.result != other.result {
+            return Some("result");
+        }
+        if self.state_delta != other.state_delta {
+            return Some("state_delta");
+        }
+        if self.coin_delta != other.coin_delta {
+            return Some("coin_delta");
+        }
+        if self.events != other.events {
+            return Some("events");
+        }
+        None
+    }
+}
@@ -1,4 +1,7 @@
-use super::{caller::Caller, exec_error::ExecutionError};
+use super::{
+    call_stack::CallStack, caller::Caller, exec_error::ExecutionError, exec_trace::ExecTrace,
+    exec_watchdog::ExecWatchdog,
+};
 use crate::{
     executive::{
         executable::method::method_type::MethodType,
@@ -23,24 +26,27 @@ use crate::{
                 },
                 call::{op_call::OP_CALL, op_callext::OP_CALLEXT},
                 callinfo::{
-                    op_caller::OP_CALLER, op_opsbudget::OP_OPSBUDGET, op_opscounter::OP_OPSCOUNTER,
-                    op_opsprice::OP_OPSPRICE, op_timestamp::OP_TIMESTAMP,
+                    op_caller::OP_CALLER, op_hostdata::OP_HOSTDATA, op_opsbudget::OP_OPSBUDGET,
+                    op_opscounter::OP_OPSCOUNTER, op_opsprice::OP_OPSPRICE,
+                    op_timestamp::OP_TIMESTAMP,
                 },
                 coin::{
                     op_ext_balance::OP_EXT_BALANCE, op_self_balance::OP_SELF_BALANCE,
-                    op_transfer::OP_TRANSFER,
+                    op_send::OP_SEND, op_transfer::OP_TRANSFER,
                 },
                 digest::{
                     op_blake2bvar::OP_BLAKE2BVAR, op_blake2svar::OP_BLAKE2SVAR,
                     op_hash160::OP_HASH160, op_hash256::OP_HASH256, op_ripemd160::OP_RIPEMD160,
                     op_sha1::OP_SHA1, op_sha256::OP_SHA256, op_taggedhash::OP_TAGGEDHASH,
                 },
+                event::op_emit::OP_EMIT,
                 flow::{
                     op_else::OP_ELSE, op_endif::OP_ENDIF, op_fail::OP_FAIL, op_if::OP_IF,
                     op_jump::OP_JUMP, op_nop::OP_NOP, op_notif::OP_NOTIF,
                     op_returnall::OP_RETURNALL, op_returnsome::OP_RETURNSOME, op_verify::OP_VERIFY,
                 },
                 memory::{op_free::OP_MFREE, op_mread::OP_MREAD, op_mwrite::OP_MWRITE},
+                precompile::op_precompile::OP_PRECOMPILE,
                 push::{
                     op_10::OP_10, op_11::OP_11, op_12::OP_12, op_13::OP_13, op_14::OP_14,
                     op_15::OP_15, op_16::OP_16, op_2::OP_2, op_3::OP_3, op_4::OP_4, op_5::OP_5,
@@ -121,22 +127,38 @@ pub async fn execute(
     internal_ops_counter: u32,
     // The external ops counter.
     external_ops_counter: ExternalOpsCounter,
+    // The ops refunded so far by the calls before this one.
+    refunded_ops: u32,
     // The state manager.
     state_manager: &STATE_MANAGER,
     // The coin manager.
     coin_manager: &COIN_MANAGER,
     // The registery.
     registery: &REGISTERY,
+    // The calls made by the execution.
+    call_stack: &mut CallStack,
+    // The opcodes run against the managers.
+    trace: &mut ExecTrace,
+    // The watchdog of the execution.
+    watchdog: &ExecWatchdog,
 ) -> Result<(Vec<StackItem>, InternalOpsCounter, ExternalOpsCounter), ExecutionError> {
-    // Get the executable by contract id.
-    let executable = {
+    // Get the executable by contract id, including a not-yet-applied upgrade, and whether the
+    // contract is paused by its pause authority.
+    let (executable, paused) = {
         let _registery = registery.lock().await;
-        _registery
-            .get_contract_body_by_contract_id(contract_id)
-            .ok_or(ExecutionError::ExecutableNotFoundError(contract_id))?
-            .executable
+        let executable = _registery
+            .get_contract_executable(contract_id)
+            .ok_or(ExecutionError::ExecutableNotFoundError(contract_id))?;
+        (executable, _registery.is_contract_paused(contract_id))
     };
 
+    // Reject external calls into contracts tombstoned for unpaid rent, and re-entrant ones,
+    // unless the contract opts in to them.
+    if !internal {
+        call_stack.guard_tombstoned()?;
+        call_stack.guard_reentrancy(executable.reentrant())?;
+    }
+
     // Get the program method by index.
     let executable_method = match executable.method_by_index(method_index) {
         Some(method) => method,
@@ -145,8 +167,12 @@ pub async fn execute(
 
     // Match the method type.
     match executable_method.method_type() {
-        // Read only methods are considered a non-executable behavior.
-        MethodType::ReadOnly => return Err(ExecutionError::ReadOnlyCallEncounteredError),
+        // Read only methods are considered a non-executable behavior, outside of read calls.
+        MethodType::ReadOnly => {
+            if !call_stack.read_only() {
+                return Err(ExecutionError::ReadOnlyCallEncounteredError);
+            }
+        }
 
         // Internal methods are *valid* if its originated from the contract itself.
         // And *invalid* if originated from an external source.
@@ -221,6 +247,12 @@ pub async fn execute(
         Err(error) => return Err(ExecutionError::StackHolderInitializationError(error)),
     };
 
+    // Carry over the ops refunded by the calls before this one.
+    stack_holder.carry_refunded_ops(refunded_ops);
+
+    // Enter the frame of the method in the trace.
+    trace.enter_frame(contract_id, method_index, internal_ops_counter);
+
     let opcodes = executable_method.script();
     let opcodes_length = opcodes.len();
 
@@ -228,12 +260,33 @@ pub async fn execute(
 
     // Execute the program method.
     while opcode_index < opcodes_length {
+        // Abort if the execution has run for longer than its timeout.
+        if watchdog.is_expired() {
+            return Err(ExecutionError::ExecutionTimeoutError);
+        }
+
         // Get the current opcode.
         let current_opcode = &opcodes[opcode_index];
 
         // Increment the opcode index.
         opcode_index += 1;
 
+        // Hold a paused contract to reads and shadow down withdrawals.
+        if paused && stack_holder.active_execution() && !permitted_while_paused(current_opcode) {
+            return Err(ExecutionError::ContractPausedError(
+                contract_id,
+                current_opcode.to_string(),
+            ));
+        }
+
+        // Peek the storage key an active `OP_SREAD` or `OP_SWRITE` is about to pop.
+        let state_key = match current_opcode {
+            Opcode::OP_SREAD(_) | Opcode::OP_SWRITE(_) if stack_holder.active_execution() => {
+                stack_holder.last_item().ok()
+            }
+            _ => None,
+        };
+
         // Execute the current opcode.
         match current_opcode {
             // Data push opcodes.
@@ -347,6 +400,7 @@ pub async fn execute(
 
                 // Get the ops spent.
                 let internal_ops_counter = stack_holder.internal_ops_counter();
+                trace.count_ops(internal_ops_counter);
 
                 // Get the up-to-date external ops counter.
                 let new_external_ops_counter = stack_holder.external_ops_counter();
@@ -366,6 +420,7 @@ pub async fn execute(
 
                 // Get the ops spent.
                 let internal_ops_counter = stack_holder.internal_ops_counter();
+                trace.count_ops(internal_ops_counter);
 
                 // Get the up-to-date external ops counter.
                 let new_external_ops_counter = stack_holder.external_ops_counter();
@@ -741,6 +796,12 @@ pub async fn execute(
                     OP_CALL::execute(&mut stack_holder)
                         .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
 
+                // Count the call against the call limits.
+                call_stack.enter_internal()?;
+
+                // Count the ops spent by this frame before handing over.
+                trace.count_ops(stack_holder.internal_ops_counter());
+
                 // Call the internal contract.
                 return Box::pin(execute(
                     true,        // Internal call.
@@ -753,9 +814,13 @@ pub async fn execute(
                     ops_price,  // Ops price is the same as the current ops price.
                     stack_holder.internal_ops_counter(), // Remainder of the internal ops counter passed to the next call.
                     stack_holder.external_ops_counter(), // Remainder of the external ops counter passed to the next call.
+                    stack_holder.refunded_ops(), // Ops refunded so far, so the refund cap holds across calls.
                     state_manager,
                     coin_manager,
                     registery,
+                    call_stack,
+                    trace,
+                    watchdog,
                 ))
                 .await;
             }
@@ -776,6 +841,12 @@ pub async fn execute(
                     return Err(ExecutionError::ExternalCallAttemptAsInternalError);
                 }
 
+                // Enter the called contract, counting the call against the call limits.
+                call_stack.enter_external(contract_id_to_be_called)?;
+
+                // Count the ops spent by this frame before handing over.
+                trace.count_ops(stack_holder.internal_ops_counter());
+
                 // The caller for the next call is the current contract id.
                 let caller = Caller::new_contract(contract_id);
 
@@ -791,9 +862,13 @@ pub async fn execute(
                     ops_price,  // Ops price is the same as the current ops price.
                     stack_holder.internal_ops_counter(), // Remainder of the internal ops counter passed to the next call.
                     stack_holder.external_ops_counter(), // Remainder of the external ops counter passed to the next call.
+                    stack_holder.refunded_ops(), // Ops refunded so far, so the refund cap holds across calls.
                     state_manager,
                     coin_manager,
                     registery,
+                    call_stack,
+                    trace,
+                    watchdog,
                 ))
                 .await;
             }
@@ -866,6 +941,11 @@ pub async fn execute(
                     .await
                     .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
             }
+            Opcode::OP_SEND(OP_SEND) => {
+                OP_SEND::execute(&mut stack_holder, coin_manager, trace)
+                    .await
+                    .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
+            }
 
             // Storage opcodes.
             Opcode::OP_SWRITE(OP_SWRITE) => {
@@ -892,8 +972,59 @@ pub async fn execute(
                 OP_MFREE::execute(&mut stack_holder)
                     .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
             }
+
+            // Precompile opcodes.
+            Opcode::OP_PRECOMPILE(OP_PRECOMPILE) => {
+                OP_PRECOMPILE::execute(&mut stack_holder)
+                    .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
+            }
+
+            // Event opcodes.
+            Opcode::OP_EMIT(OP_EMIT) => {
+                OP_EMIT::execute(&mut stack_holder, trace)
+                    .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
+            }
+
+            // Host data opcodes.
+            Opcode::OP_HOSTDATA(OP_HOSTDATA) => {
+                OP_HOSTDATA::execute(&mut stack_holder, trace)
+                    .map_err(|error| ExecutionError::OpcodeExecutionError(error))?;
+            }
         }
+
+        // Trace the opcode if it ran against a manager.
+        if stack_holder.active_execution() {
+            trace.record(contract_id, current_opcode);
+        }
+
+        // Trace the state key the opcode accessed, if any.
+        if let Some(state_key) = state_key {
+            trace.record_state_key(current_opcode, state_key.bytes());
+        }
+
+        // Count the ops spent by this frame so far.
+        trace.count_ops(stack_holder.internal_ops_counter());
     }
 
     return Err(ExecutionError::MethodNotReturnedAnyItemsError);
 }
+
+/// Returns whether the opcode may run within a paused contract.
+///
+/// A paused contract keeps reading its storage and balances, and lets accounts withdraw their
+/// shadow allocations with `OP_SHADOW_DOWN`, but can no longer write storage, move coins, change
+/// the shadow space otherwise, or call other contracts.
+pub fn permitted_while_paused(opcode: &Opcode) -> bool {
+    !matches!(
+        opcode,
+        Opcode::OP_SWRITE(_)
+            | Opcode::OP_TRANSFER(_)
+            | Opcode::OP_SEND(_)
+            | Opcode::OP_SHADOW_ALLOC(_)
+            | Opcode::OP_SHADOW_DEALLOC(_)
+            | Opcode::OP_SHADOW_UP(_)
+            | Opcode::OP_SHADOW_UP_ALL(_)
+            | Opcode::OP_SHADOW_DOWN_ALL(_)
+            | Opcode::OP_CALLEXT(_)
+    )
+}
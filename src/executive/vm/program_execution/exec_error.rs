@@ -1,4 +1,13 @@
+use crate::constructive::entry::entry_kinds::call::call_batch_error::CallBatchError;
 use crate::executive::stack::{stack_error::StackError, stack_item::StackItem};
+use crate::inscriptive::coin_manager::errors::balance_update_errors::{
+    CMAccountBalanceDownError, CMAccountBalanceUpError,
+};
+use crate::inscriptive::registery::errors::pause_contract_error::RMPauseContractError;
+use crate::inscriptive::registery::errors::upgrade_contract_error::RMUpgradeContractError;
+use crate::inscriptive::schedule_manager::errors::{
+    cancel_error::ScheduleManagerCancelError, schedule_error::ScheduleManagerScheduleError,
+};
 use std::fmt;
 
 /// A section of executable block in the `Contract`.
@@ -38,6 +47,42 @@ pub enum ExecutionError {
     BaseOpsPriceMismatchError,
     /// Opcode index out of bounds error.
     OpcodeIndexOutOfBoundsError,
+    /// Execution ran for longer than its timeout error.
+    ExecutionTimeoutError,
+    /// Call depth limit exceeded error.
+    CallDepthLimitExceededError(u64),
+    /// Call count limit exceeded error.
+    CallCountLimitExceededError(u64),
+    /// Re-entrant call into a contract already on the call stack error.
+    ReentrantCallError([u8; 32]),
+    /// Contract upgrade rejected by the registery error.
+    ContractUpgradeError(RMUpgradeContractError),
+    /// Host data not attested by the coordinator error.
+    InvalidHostDataAttestationError,
+    /// Deferred call prepaid fees not covering its ops budget error.
+    DeferredCallPrepaidFeesMismatchError(u64, u64),
+    /// Deferred call fees prepayment error.
+    DeferredCallPrepaymentError(CMAccountBalanceDownError),
+    /// Deferred call fees refund error.
+    DeferredCallRefundError(CMAccountBalanceUpError),
+    /// Deferred call rejected by the schedule manager error.
+    DeferredCallScheduleError(ScheduleManagerScheduleError),
+    /// Deferred call cancellation rejected by the schedule manager error.
+    DeferredCallCancelError(ScheduleManagerCancelError),
+    /// Malformed call batch error.
+    CallBatchError(CallBatchError),
+    /// Call of a call batch failed error.
+    CallBatchCallFailedError(usize, Box<ExecutionError>),
+    /// Call rolled back along with its failed call batch error.
+    CallBatchRolledBackError(usize),
+    /// The contract has been tombstoned for unpaid rent.
+    ContractTombstonedError([u8; 32]),
+    /// The contract is paused, and the opcode would do more than a shadow down withdrawal.
+    ContractPausedError([u8; 32], String),
+    /// Pause or unpause rejected by the registery error.
+    ContractPauseError(RMPauseContractError),
+    /// The two executions of a call diverged under the determinism check.
+    NondeterministicExecutionError([u8; 32], u16, String),
 }
 
 impl fmt::Display for ExecutionError {
@@ -94,6 +139,91 @@ impl fmt::Display for ExecutionError {
             ExecutionError::OpcodeIndexOutOfBoundsError => {
                 write!(f, "Opcode index out of bounds")
             }
+            ExecutionError::ExecutionTimeoutError => {
+                write!(f, "Execution ran for longer than its timeout")
+            }
+            ExecutionError::CallDepthLimitExceededError(limit) => {
+                write!(f, "Call depth limit of {} exceeded", limit)
+            }
+            ExecutionError::CallCountLimitExceededError(limit) => {
+                write!(f, "Call count limit of {} exceeded", limit)
+            }
+            ExecutionError::ReentrantCallError(contract_id) => {
+                write!(
+                    f,
+                    "Re-entrant call into contract: {}",
+                    hex::encode(contract_id)
+                )
+            }
+            ExecutionError::ContractUpgradeError(error) => {
+                write!(f, "Contract upgrade error: {:?}", error)
+            }
+            ExecutionError::InvalidHostDataAttestationError => {
+                write!(f, "Host data not attested by the coordinator")
+            }
+            ExecutionError::DeferredCallPrepaidFeesMismatchError(expected, prepaid) => {
+                write!(
+                    f,
+                    "Deferred call prepaid fees mismatch: expected {}, got {}",
+                    expected, prepaid
+                )
+            }
+            ExecutionError::DeferredCallPrepaymentError(error) => {
+                write!(f, "Deferred call prepayment error: {:?}", error)
+            }
+            ExecutionError::DeferredCallRefundError(error) => {
+                write!(f, "Deferred call refund error: {:?}", error)
+            }
+            ExecutionError::DeferredCallScheduleError(error) => {
+                write!(f, "Deferred call schedule error: {:?}", error)
+            }
+            ExecutionError::DeferredCallCancelError(error) => {
+                write!(f, "Deferred call cancel error: {:?}", error)
+            }
+            ExecutionError::CallBatchError(error) => {
+                write!(f, "Call batch error: {:?}", error)
+            }
+            ExecutionError::CallBatchCallFailedError(index, error) => {
+                write!(f, "Call #{} of the call batch failed: {}", index, error)
+            }
+            ExecutionError::CallBatchRolledBackError(index) => {
+                write!(
+                    f,
+                    "Rolled back along with the call batch, whose call #{} failed",
+                    index
+                )
+            }
+            ExecutionError::ContractTombstonedError(contract_id) => {
+                write!(
+                    f,
+                    "Contract tombstoned for unpaid rent: {}",
+                    hex::encode(contract_id)
+                )
+            }
+            ExecutionError::ContractPausedError(contract_id, opcode) => {
+                write!(
+                    f,
+                    "Contract paused, {} is not allowed: {}",
+                    opcode,
+                    hex::encode(contract_id)
+                )
+            }
+            ExecutionError::ContractPauseError(error) => {
+                write!(f, "Contract pause error: {:?}", error)
+            }
+            ExecutionError::NondeterministicExecutionError(
+                contract_id,
+                method_index,
+                divergence,
+            ) => {
+                write!(
+                    f,
+                    "Nondeterministic execution of method {} of contract {}: the {} diverged",
+                    method_index,
+                    hex::encode(contract_id),
+                    divergence
+                )
+            }
         }
     }
 }
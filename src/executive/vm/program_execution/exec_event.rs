@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The minimum topic length of an event in bytes.
+pub const MIN_EVENT_TOPIC_LENGTH: usize = 1;
+
+/// The maximum topic length of an event in bytes.
+pub const MAX_EVENT_TOPIC_LENGTH: usize = 32;
+
+/// The maximum number of events a single call can emit.
+pub const MAX_EVENTS_PER_CALL: usize = 64;
+
+/// The topic of the event recording a contract being paused by its pause authority.
+pub const PAUSE_EVENT_TOPIC: &[u8] = b"pause";
+
+/// The topic of the event recording a contract being unpaused by its pause authority.
+pub const UNPAUSE_EVENT_TOPIC: &[u8] = b"unpause";
+
+/// An event emitted by a contract with `OP_EMIT` during execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecEvent {
+    /// The contract emitting the event.
+    pub contract_id: [u8; 32],
+    /// The topic the event is indexed by.
+    pub topic: Vec<u8>,
+    /// The event data.
+    pub data: Vec<u8>,
+}
+
+impl ExecEvent {
+    /// Serializes the event with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes an event with bincode.
+    pub fn deserialize(bytes: &[u8]) -> Option<ExecEvent> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .ok()
+            .map(|(event, _)| event)
+    }
+
+    /// Returns the event as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(self.contract_id)),
+        );
+        obj.insert("topic".to_string(), Value::String(hex::encode(&self.topic)));
+        obj.insert("data".to_string(), Value::String(hex::encode(&self.data)));
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,131 @@
+use super::exec_trace::ExecTraceOp;
+use super::exec_usage::ExecUsage;
+use super::host_data::ExecHostRead;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The receipt of a `Call` execution, whether it passed or failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecReceipt {
+    /// The sighash of the call.
+    pub call_id: [u8; 32],
+    /// The execution timestamp.
+    pub timestamp: u64,
+    /// The account making the call.
+    pub caller: [u8; 32],
+    /// The contracts entered, starting with the called contract.
+    pub contracts: Vec<[u8; 32]>,
+    /// The opcodes run against the coin and state managers, in order.
+    pub trace: Vec<ExecTraceOp>,
+    /// The contracts re-entered with their opt-in.
+    pub reentered: Vec<[u8; 32]>,
+    /// The ops spent, if the call passed.
+    pub ops_spent: Option<u32>,
+    /// The fees spent, if the call passed.
+    pub fees_spent: Option<u32>,
+    /// The error the call failed with, if any.
+    pub error: Option<String>,
+    /// The resources the call used, including its nested calls.
+    pub usage: ExecUsage,
+    /// The host data values read by the call, in order, so that it can be re-executed as it was.
+    pub host_reads: Vec<ExecHostRead>,
+}
+
+impl ExecReceipt {
+    /// Returns whether the call passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Serializes the receipt with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a receipt with bincode.
+    pub fn deserialize(bytes: &[u8]) -> Option<ExecReceipt> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .ok()
+            .map(|(receipt, _)| receipt)
+    }
+
+    /// Returns the receipt as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Construct the receipt JSON object.
+        let mut obj = Map::new();
+
+        // 2 Insert the call id, timestamp and caller.
+        obj.insert(
+            "call_id".to_string(),
+            Value::String(hex::encode(self.call_id)),
+        );
+        obj.insert(
+            "timestamp".to_string(),
+            Value::Number(self.timestamp.into()),
+        );
+        obj.insert(
+            "caller".to_string(),
+            Value::String(hex::encode(self.caller)),
+        );
+
+        // 3 Insert the contracts entered and re-entered.
+        obj.insert(
+            "contracts".to_string(),
+            Value::Array(
+                self.contracts
+                    .iter()
+                    .map(|contract_id| Value::String(hex::encode(contract_id)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "reentered".to_string(),
+            Value::Array(
+                self.reentered
+                    .iter()
+                    .map(|contract_id| Value::String(hex::encode(contract_id)))
+                    .collect(),
+            ),
+        );
+
+        // 4 Insert the trace.
+        obj.insert(
+            "trace".to_string(),
+            Value::Array(self.trace.iter().map(|op| op.json()).collect()),
+        );
+
+        // 5 Insert the result.
+        obj.insert("passed".to_string(), Value::Bool(self.passed()));
+        obj.insert(
+            "ops_spent".to_string(),
+            self.ops_spent
+                .map(|ops| Value::Number(ops.into()))
+                .unwrap_or(Value::Null),
+        );
+        obj.insert(
+            "fees_spent".to_string(),
+            self.fees_spent
+                .map(|fees| Value::Number(fees.into()))
+                .unwrap_or(Value::Null),
+        );
+        obj.insert(
+            "error".to_string(),
+            self.error
+                .as_ref()
+                .map(|error| Value::String(error.clone()))
+                .unwrap_or(Value::Null),
+        );
+
+        // 6 Insert the resource usage.
+        obj.insert("usage".to_string(), self.usage.json());
+
+        // 7 Insert the host data reads.
+        obj.insert(
+            "host_reads".to_string(),
+            Value::Array(self.host_reads.iter().map(|read| read.json()).collect()),
+        );
+
+        // 8 Return the receipt JSON object.
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,260 @@
+use super::exec_event::ExecEvent;
+use super::exec_usage::{ExecFrameUsage, ExecUsage};
+use super::host_data::{ExecHostRead, HostData};
+use crate::executive::opcode::opcode::Opcode;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// The manager an opcode operates on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecTraceTarget {
+    /// Balances and shadow allocations.
+    CoinManager,
+    /// Contract storage.
+    StateManager,
+}
+
+impl ExecTraceTarget {
+    /// Returns the manager the given opcode operates on, if any.
+    pub fn of(opcode: &Opcode) -> Option<ExecTraceTarget> {
+        match opcode {
+            Opcode::OP_SHADOW_ALLOC(_)
+            | Opcode::OP_SHADOW_HAS_ALLOC(_)
+            | Opcode::OP_SHADOW_DEALLOC(_)
+            | Opcode::OP_SHADOW_ALLOC_VAL(_)
+            | Opcode::OP_SHADOW_UP(_)
+            | Opcode::OP_SHADOW_DOWN(_)
+            | Opcode::OP_SHADOW_UP_ALL(_)
+            | Opcode::OP_SHADOW_DOWN_ALL(_)
+            | Opcode::OP_SHADOW_NUM_ALLOCS(_)
+            | Opcode::OP_SHADOW_ALLOCS_SUM(_)
+            | Opcode::OP_EXT_BALANCE(_)
+            | Opcode::OP_SELF_BALANCE(_)
+            | Opcode::OP_TRANSFER(_)
+            | Opcode::OP_SEND(_) => Some(ExecTraceTarget::CoinManager),
+            Opcode::OP_SWRITE(_) | Opcode::OP_SREAD(_) => Some(ExecTraceTarget::StateManager),
+            _ => None,
+        }
+    }
+}
+
+impl ToString for ExecTraceTarget {
+    fn to_string(&self) -> String {
+        match self {
+            ExecTraceTarget::CoinManager => "coin_manager".to_string(),
+            ExecTraceTarget::StateManager => "state_manager".to_string(),
+        }
+    }
+}
+
+/// An opcode executed against a manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecTraceOp {
+    /// The contract executing the opcode.
+    pub contract_id: [u8; 32],
+    /// The manager the opcode operates on.
+    pub target: ExecTraceTarget,
+    /// The opcode name.
+    pub opcode: String,
+}
+
+impl ExecTraceOp {
+    /// Returns the traced opcode as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(self.contract_id)),
+        );
+        obj.insert("target".to_string(), Value::String(self.target.to_string()));
+        obj.insert("opcode".to_string(), Value::String(self.opcode.clone()));
+        Value::Object(obj)
+    }
+}
+
+/// A frame entered by an execution, with the resources it has used so far.
+#[derive(Debug, Clone)]
+struct ExecTraceFrame {
+    // The contract the frame runs.
+    contract_id: [u8; 32],
+    // The method index the frame runs.
+    method_index: u16,
+    // The ops counter when the frame was entered.
+    ops_counter_at_entry: u32,
+    // The ops spent by the frame so far.
+    ops_used: u32,
+    // The state keys read by the frame.
+    state_keys_read: BTreeSet<Vec<u8>>,
+    // The state keys written by the frame.
+    state_keys_written: BTreeSet<Vec<u8>>,
+    // The opcodes run by the frame against the coin manager.
+    coin_ops: u32,
+}
+
+/// The opcodes an execution has run against the coin and state managers, the events it has
+/// emitted, the frames it has entered and the host data it has read, in order.
+#[derive(Debug, Clone, Default)]
+pub struct ExecTrace {
+    // The traced opcodes.
+    ops: Vec<ExecTraceOp>,
+    // The emitted events.
+    events: Vec<ExecEvent>,
+    // The entered frames.
+    frames: Vec<ExecTraceFrame>,
+    // The host data exposed to the execution, if any.
+    host_data: Option<HostData>,
+    // The host data reads.
+    host_reads: Vec<ExecHostRead>,
+}
+
+impl ExecTrace {
+    /// Creates an empty trace.
+    pub fn new() -> ExecTrace {
+        ExecTrace {
+            ops: Vec::new(),
+            events: Vec::new(),
+            frames: Vec::new(),
+            host_data: None,
+            host_reads: Vec::new(),
+        }
+    }
+
+    /// Creates an empty trace exposing the given host data to the execution.
+    pub fn with_host_data(host_data: Option<HostData>) -> ExecTrace {
+        ExecTrace {
+            host_data,
+            ..ExecTrace::new()
+        }
+    }
+
+    /// Records an opcode executed by the given contract, if it operates on a manager.
+    pub fn record(&mut self, contract_id: [u8; 32], opcode: &Opcode) {
+        if let Some(target) = ExecTraceTarget::of(opcode) {
+            self.ops.push(ExecTraceOp {
+                contract_id,
+                target,
+                opcode: opcode.to_string(),
+            });
+
+            // Count the coin manager opcodes against the current frame.
+            if let (ExecTraceTarget::CoinManager, Some(frame)) = (target, self.frames.last_mut()) {
+                frame.coin_ops += 1;
+            }
+        }
+    }
+
+    /// Returns the traced opcodes.
+    pub fn ops(&self) -> &[ExecTraceOp] {
+        &self.ops
+    }
+
+    /// Records an event emitted with `OP_EMIT`.
+    pub fn emit(&mut self, event: ExecEvent) {
+        self.events.push(event);
+    }
+
+    /// Returns the emitted events.
+    pub fn events(&self) -> &[ExecEvent] {
+        &self.events
+    }
+
+    /// Reads the value under the host data key on behalf of the contract, recording the read.
+    ///
+    /// Returns `None` if the execution was given no host data, or the value under the key if any.
+    pub fn read_host_data(&mut self, contract_id: [u8; 32], key: &[u8]) -> Option<Option<u64>> {
+        let value = self.host_data.as_ref()?.value(key);
+        self.host_reads.push(ExecHostRead {
+            contract_id,
+            key: key.to_vec(),
+            value,
+        });
+        Some(value)
+    }
+
+    /// Returns the host data reads.
+    pub fn host_reads(&self) -> &[ExecHostRead] {
+        &self.host_reads
+    }
+
+    /// Enters a frame running the method of the contract, at the given ops counter.
+    pub fn enter_frame(&mut self, contract_id: [u8; 32], method_index: u16, ops_counter: u32) {
+        self.frames.push(ExecTraceFrame {
+            contract_id,
+            method_index,
+            ops_counter_at_entry: ops_counter,
+            ops_used: 0,
+            state_keys_read: BTreeSet::new(),
+            state_keys_written: BTreeSet::new(),
+            coin_ops: 0,
+        });
+    }
+
+    /// Updates the ops spent by the current frame from the current ops counter.
+    pub fn count_ops(&mut self, ops_counter: u32) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.ops_used = ops_counter.saturating_sub(frame.ops_counter_at_entry);
+        }
+    }
+
+    /// Records the state key an `OP_SREAD` or `OP_SWRITE` accessed in the current frame.
+    pub fn record_state_key(&mut self, opcode: &Opcode, key: &[u8]) {
+        if let Some(frame) = self.frames.last_mut() {
+            match opcode {
+                Opcode::OP_SREAD(_) => {
+                    frame.state_keys_read.insert(key.to_vec());
+                }
+                Opcode::OP_SWRITE(_) => {
+                    frame.state_keys_written.insert(key.to_vec());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the resources used across the entered frames, along with the wall time the
+    /// execution took.
+    pub fn usage(&self, wall_time_micros: u64) -> ExecUsage {
+        // 1 Collect the distinct state keys across the frames, by contract.
+        let mut state_keys_read = BTreeSet::<([u8; 32], &Vec<u8>)>::new();
+        let mut state_keys_written = BTreeSet::<([u8; 32], &Vec<u8>)>::new();
+        for frame in self.frames.iter() {
+            state_keys_read.extend(
+                frame
+                    .state_keys_read
+                    .iter()
+                    .map(|key| (frame.contract_id, key)),
+            );
+            state_keys_written.extend(
+                frame
+                    .state_keys_written
+                    .iter()
+                    .map(|key| (frame.contract_id, key)),
+            );
+        }
+
+        // 2 Collect the usage of each frame.
+        let frames: Vec<ExecFrameUsage> = self
+            .frames
+            .iter()
+            .map(|frame| ExecFrameUsage {
+                contract_id: frame.contract_id,
+                method_index: frame.method_index,
+                ops_used: frame.ops_used,
+                state_keys_read: frame.state_keys_read.len() as u32,
+                state_keys_written: frame.state_keys_written.len() as u32,
+                coin_ops: frame.coin_ops,
+            })
+            .collect();
+
+        // 3 Return the usage.
+        ExecUsage {
+            ops_used: frames.iter().map(|frame| frame.ops_used).sum(),
+            state_keys_read: state_keys_read.len() as u32,
+            state_keys_written: state_keys_written.len() as u32,
+            coin_ops: frames.iter().map(|frame| frame.coin_ops).sum(),
+            wall_time_micros,
+            frames,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The resources a single frame of a call used: a method of a contract entered either by the call
+/// itself or by a nested internal or external call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecFrameUsage {
+    /// The contract the frame ran.
+    pub contract_id: [u8; 32],
+    /// The method index the frame ran.
+    pub method_index: u16,
+    /// The ops the frame spent.
+    pub ops_used: u32,
+    /// The distinct state keys the frame read.
+    pub state_keys_read: u32,
+    /// The distinct state keys the frame wrote.
+    pub state_keys_written: u32,
+    /// The opcodes the frame ran against the coin manager.
+    pub coin_ops: u32,
+}
+
+impl ExecFrameUsage {
+    /// Returns the frame usage as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(self.contract_id)),
+        );
+        obj.insert(
+            "method_index".to_string(),
+            Value::Number(self.method_index.into()),
+        );
+        obj.insert("ops_used".to_string(), Value::Number(self.ops_used.into()));
+        obj.insert(
+            "state_keys_read".to_string(),
+            Value::Number(self.state_keys_read.into()),
+        );
+        obj.insert(
+            "state_keys_written".to_string(),
+            Value::Number(self.state_keys_written.into()),
+        );
+        obj.insert("coin_ops".to_string(), Value::Number(self.coin_ops.into()));
+        Value::Object(obj)
+    }
+}
+
+/// The resources a call used, including its nested calls, whether it passed or failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecUsage {
+    /// The ops spent across all frames.
+    pub ops_used: u32,
+    /// The distinct state keys read across all frames.
+    pub state_keys_read: u32,
+    /// The distinct state keys written across all frames.
+    pub state_keys_written: u32,
+    /// The opcodes run against the coin manager across all frames.
+    pub coin_ops: u32,
+    /// The wall time the call took to execute, in microseconds.
+    pub wall_time_micros: u64,
+    /// The frames the call ran, in the order they were entered.
+    pub frames: Vec<ExecFrameUsage>,
+}
+
+impl ExecUsage {
+    /// Returns the usage as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("ops_used".to_string(), Value::Number(self.ops_used.into()));
+        obj.insert(
+            "state_keys_read".to_string(),
+            Value::Number(self.state_keys_read.into()),
+        );
+        obj.insert(
+            "state_keys_written".to_string(),
+            Value::Number(self.state_keys_written.into()),
+        );
+        obj.insert("coin_ops".to_string(), Value::Number(self.coin_ops.into()));
+        obj.insert(
+            "wall_time_micros".to_string(),
+            Value::Number(self.wall_time_micros.into()),
+        );
+        obj.insert(
+            "frames".to_string(),
+            Value::Array(self.frames.iter().map(|frame| frame.json()).collect()),
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// The default wall-clock time an execution may take.
+pub const DEFAULT_EXEC_TIMEOUT: Duration = Duration::from_millis(2_000);
+
+/// Wall-clock time an execution may take, read from the environment on first use.
+static EXEC_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Returns the wall-clock time an execution may take.
+///
+/// `CUBE_EXEC_TIMEOUT_MS` overrides the default, ignoring malformed or zero values.
+pub fn exec_timeout() -> Duration {
+    *EXEC_TIMEOUT.get_or_init(|| {
+        std::env::var("CUBE_EXEC_TIMEOUT_MS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|millis| *millis > 0)
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_EXEC_TIMEOUT)
+    })
+}
+
+/// Flags an execution once it has run for longer than its timeout.
+///
+/// Ops budgets bound the work of an execution, but not its wall-clock time, so a cheap loop over
+/// slow host calls could still hold up the engine. The watchdog runs as a separate task, and the
+/// execution checks its flag before every opcode, aborting once it is raised. The task is stopped
+/// when the watchdog is dropped.
+pub struct ExecWatchdog {
+    // Raised once the timeout has passed.
+    expired: Arc<AtomicBool>,
+
+    // The task raising the flag.
+    task: JoinHandle<()>,
+}
+
+impl ExecWatchdog {
+    /// Starts a watchdog raising its flag after the given timeout.
+    pub fn start(timeout: Duration) -> ExecWatchdog {
+        // 1 Construct the flag.
+        let expired = Arc::new(AtomicBool::new(false));
+
+        // 2 Raise the flag once the timeout has passed.
+        let task = {
+            let expired = Arc::clone(&expired);
+            tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                expired.store(true, Ordering::Relaxed);
+            })
+        };
+
+        // 3 Return the watchdog.
+        ExecWatchdog { expired, task }
+    }
+
+    /// Returns whether the execution has run for longer than its timeout.
+    pub fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for ExecWatchdog {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
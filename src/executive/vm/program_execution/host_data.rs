@@ -0,0 +1,184 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// The host data key of the current Bitcoin block height.
+pub const HOST_KEY_BLOCK_HEIGHT: &[u8] = b"block_height";
+
+/// The host data key of the median time past of the current Bitcoin block.
+pub const HOST_KEY_MEDIAN_TIME: &[u8] = b"median_time";
+
+/// The prefix of the host data keys of price feeds, followed by the feed name.
+pub const HOST_KEY_PRICE_PREFIX: &[u8] = b"price/";
+
+/// The minimum length of a host data key.
+pub const MIN_HOST_KEY_LENGTH: usize = 1;
+
+/// The maximum length of a host data key.
+pub const MAX_HOST_KEY_LENGTH: usize = 32;
+
+mod schnorr_signature_64 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (a, b) = bytes.split_at(32);
+        let parts = (
+            <[u8; 32]>::try_from(a).expect("split_at(32)"),
+            <[u8; 32]>::try_from(b).expect("split_at(32)"),
+        );
+        parts.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 64], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let mut out = [0u8; 64];
+        out[0..32].copy_from_slice(&a);
+        out[32..64].copy_from_slice(&b);
+        Ok(out)
+    }
+}
+
+/// The external data the coordinator exposes to contracts for a batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostData {
+    /// The current Bitcoin block height.
+    pub block_height: u64,
+    /// The median time past of the current Bitcoin block.
+    pub median_time: u64,
+    /// The configured price feeds, by feed name.
+    pub price_feeds: BTreeMap<String, u64>,
+}
+
+impl HostData {
+    /// Returns the value under the given host data key, if any.
+    pub fn value(&self, key: &[u8]) -> Option<u64> {
+        if key == HOST_KEY_BLOCK_HEIGHT {
+            return Some(self.block_height);
+        }
+
+        if key == HOST_KEY_MEDIAN_TIME {
+            return Some(self.median_time);
+        }
+
+        let feed_name = key.strip_prefix(HOST_KEY_PRICE_PREFIX)?;
+        let feed_name = std::str::from_utf8(feed_name).ok()?;
+        self.price_feeds.get(feed_name).copied()
+    }
+
+    /// Returns the message the coordinator signs to attest the host data.
+    pub fn attestation_message(&self) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::new();
+        preimage.extend(self.block_height.to_be_bytes());
+        preimage.extend(self.median_time.to_be_bytes());
+        preimage.extend((self.price_feeds.len() as u32).to_be_bytes());
+        for (feed_name, price) in self.price_feeds.iter() {
+            preimage.extend((feed_name.len() as u32).to_be_bytes());
+            preimage.extend(feed_name.as_bytes());
+            preimage.extend(price.to_be_bytes());
+        }
+        preimage.hash(Some(HashTag::HostDataAttestation))
+    }
+
+    /// Returns the host data as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "block_height".to_string(),
+            Value::Number(self.block_height.into()),
+        );
+        obj.insert(
+            "median_time".to_string(),
+            Value::Number(self.median_time.into()),
+        );
+        obj.insert(
+            "price_feeds".to_string(),
+            Value::Object(
+                self.price_feeds
+                    .iter()
+                    .map(|(feed_name, price)| (feed_name.clone(), Value::Number((*price).into())))
+                    .collect(),
+            ),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Host data along with the coordinator's signature over it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestedHostData {
+    // The attested host data.
+    pub host_data: HostData,
+
+    // The attesting coordinator key.
+    pub coordinator_key: [u8; 32],
+
+    // The Schnorr signature over the host data attestation message.
+    #[serde(with = "schnorr_signature_64")]
+    pub signature: [u8; 64],
+}
+
+impl AttestedHostData {
+    /// Attests the host data with the coordinator's key.
+    pub fn sign(keys: &KeyHolder, host_data: HostData) -> Option<Self> {
+        let signature = schnorr::sign(
+            keys.secp_secret_key_bytes(),
+            host_data.attestation_message(),
+            SchnorrSigningMode::Cube,
+        )?;
+
+        Some(Self {
+            host_data,
+            coordinator_key: keys.secp_public_key_bytes(),
+            signature,
+        })
+    }
+
+    /// Verifies the signature over the host data.
+    pub fn verify(&self) -> bool {
+        schnorr::verify_xonly(
+            self.coordinator_key,
+            self.host_data.attestation_message(),
+            self.signature,
+            SchnorrSigningMode::Cube,
+        )
+    }
+}
+
+/// A host data key read by a contract with `OP_HOSTDATA`, and the value it got.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecHostRead {
+    /// The contract reading the key.
+    pub contract_id: [u8; 32],
+    /// The host data key.
+    pub key: Vec<u8>,
+    /// The value read, if the key was set.
+    pub value: Option<u64>,
+}
+
+impl ExecHostRead {
+    /// Returns the host read as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(self.contract_id)),
+        );
+        obj.insert("key".to_string(), Value::String(hex::encode(&self.key)));
+        obj.insert(
+            "value".to_string(),
+            self.value
+                .map(|value| Value::Number(value.into()))
+                .unwrap_or(Value::Null),
+        );
+        Value::Object(obj)
+    }
+}
@@ -2,3 +2,13 @@ pub mod caller;
 pub mod exec;
 pub mod program_exec_ctx;
 pub mod exec_error;
+pub mod exec_watchdog;
+pub mod call_stack;
+pub mod exec_trace;
+pub mod exec_receipt;
+pub mod read_call_cache;
+pub mod exec_event;
+pub mod exec_usage;
+pub mod host_data;
+pub mod call_simulation;
+pub mod determinism_check;
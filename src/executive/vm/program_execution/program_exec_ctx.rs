@@ -1,16 +1,41 @@
 use crate::{
-    constructive::entry::entry_kinds::call::call::Call,
+    constructive::entry::entry_kinds::call::{call::Call, call_batch::CallBatch},
     executive::{
-        vm::program_execution::{caller::Caller, exec::execute, exec_error::ExecutionError},
+        vm::program_execution::{
+            call_stack::CallStack,
+            caller::Caller,
+            determinism_check::{determinism_check_enabled, ExecOutcome},
+            exec::execute,
+            exec_error::ExecutionError,
+            exec_event::{ExecEvent, PAUSE_EVENT_TOPIC, UNPAUSE_EVENT_TOPIC},
+            exec_receipt::ExecReceipt,
+            exec_trace::ExecTrace,
+            exec_watchdog::{exec_timeout, ExecWatchdog},
+            host_data::{AttestedHostData, HostData},
+            read_call_cache::{read_call_cache, ReadCallKey},
+        },
+        program::program::Executable,
         stack::stack_item::StackItem,
     },
     inscriptive::{
-        coin_manager::coin_manager::COIN_MANAGER, params_manager::params_manager::PARAMS_MANAGER,
+        archival_manager::archival_manager::ARCHIVAL_MANAGER,
+        coin_manager::coin_manager::COIN_MANAGER,
+        params_manager::params_manager::PARAMS_MANAGER,
         registery::registery::REGISTERY,
+        rent_manager::{
+            errors::apply_changes_error::RentManagerApplyChangesError, rent_charge::RentCharge,
+            rent_manager::RENT_MANAGER,
+        },
+        schedule_manager::{
+            deferred_call::DeferredCall,
+            errors::apply_changes_error::ScheduleManagerApplyChangesError,
+            schedule_manager::SCHEDULE_MANAGER,
+        },
         state_manager::state_manager::STATE_MANAGER,
     },
 };
 use std::sync::Arc;
+use std::time::Instant;
 
 /// The type of the ops spent.
 type OpsSpent = u32;
@@ -28,6 +53,10 @@ pub struct ProgramExecCtx {
     registery: REGISTERY,
     // The params manager.
     _params_manager: PARAMS_MANAGER,
+    // The schedule of deferred calls.
+    schedule_manager: SCHEDULE_MANAGER,
+    // The rent standing of the contracts.
+    rent_manager: RENT_MANAGER,
     // External ops counter.
     external_ops_counter: u32,
     // The base ops price.
@@ -36,6 +65,18 @@ pub struct ProgramExecCtx {
     timestamp: u64,
     // Passed calls.
     passed_calls: Vec<(Call, OpsSpent, FeesSpent)>,
+    // Contracts re-entered by the passed calls with their opt-in.
+    reentered_contracts: Vec<[u8; 32]>,
+    // Receipts of the calls executed, passed or failed.
+    receipts: Vec<ExecReceipt>,
+    // Events emitted by the passed calls, in order.
+    events: Vec<ExecEvent>,
+    // The archival store receipts are persisted to, in archival mode.
+    archival_manager: Option<ARCHIVAL_MANAGER>,
+    // The host data attested by the coordinator, exposed to contracts with `OP_HOSTDATA`.
+    host_data: Option<HostData>,
+    // Whether a call batch is executing, sharing the backups taken before its first call.
+    in_call_batch: bool,
 }
 
 impl ProgramExecCtx {
@@ -45,6 +86,9 @@ impl ProgramExecCtx {
         coin_manager: &COIN_MANAGER,
         params_manager: &PARAMS_MANAGER,
         registery: &REGISTERY,
+        schedule_manager: &SCHEDULE_MANAGER,
+        rent_manager: &RENT_MANAGER,
+        archival_manager: Option<&ARCHIVAL_MANAGER>,
         base_ops_price: u32,
         timestamp: u64,
     ) -> Self {
@@ -53,18 +97,483 @@ impl ProgramExecCtx {
             coin_manager: Arc::clone(coin_manager),
             _params_manager: Arc::clone(params_manager),
             registery: Arc::clone(registery),
+            schedule_manager: Arc::clone(schedule_manager),
+            rent_manager: Arc::clone(rent_manager),
             external_ops_counter: 0,
             base_ops_price,
             timestamp,
             passed_calls: Vec::<(Call, OpsSpent, FeesSpent)>::new(),
+            reentered_contracts: Vec::<[u8; 32]>::new(),
+            receipts: Vec::<ExecReceipt>::new(),
+            events: Vec::<ExecEvent>::new(),
+            archival_manager: archival_manager.map(Arc::clone),
+            host_data: None,
+            in_call_batch: false,
         }
     }
 
-    /// Executes and inserts a call.
+    /// Sets the host data exposed to the calls executed from now on, after checking that it is
+    /// attested by the given coordinator.
+    pub fn set_host_data(
+        &mut self,
+        attested_host_data: AttestedHostData,
+        coordinator_key: [u8; 32],
+    ) -> Result<(), ExecutionError> {
+        // 1 Check the attesting key is the coordinator's.
+        if attested_host_data.coordinator_key != coordinator_key {
+            return Err(ExecutionError::InvalidHostDataAttestationError);
+        }
+
+        // 2 Verify the attestation.
+        if !attested_host_data.verify() {
+            return Err(ExecutionError::InvalidHostDataAttestationError);
+        }
+
+        // 3 Set the host data.
+        self.host_data = Some(attested_host_data.host_data);
+
+        Ok(())
+    }
+
+    /// Executes and inserts a call, recording its receipt whether it passes or fails.
     pub async fn exec_insert_call(&mut self, call: Call) -> Result<(), ExecutionError> {
-        // This is an external call.
-        let internal = false;
+        // 1 Start the call stack bounded by the chain params, and an empty trace.
+        let mut call_stack = {
+            let params_holder = {
+                let _params_manager = self._params_manager.lock().unwrap();
+                _params_manager.get_params_holder()
+            };
+            CallStack::new(
+                call.contract().contract_id(),
+                params_holder.max_call_depth,
+                params_holder.max_call_count,
+            )
+        };
+        let mut trace = ExecTrace::with_host_data(self.host_data.clone());
+
+        // 2 Execute the call, timing it.
+        let started_at = Instant::now();
+        let result = self.exec_call(&call, &mut call_stack, &mut trace).await;
+        let wall_time_micros = started_at.elapsed().as_micros() as u64;
+
+        // 3 Record the receipt of the call.
+        if let Ok(call_id) = call.sighash() {
+            let receipt = ExecReceipt {
+                call_id,
+                timestamp: self.timestamp,
+                caller: call.account().account_key(),
+                contracts: call_stack.contracts().to_vec(),
+                trace: trace.ops().to_vec(),
+                reentered: call_stack.reentered().to_vec(),
+                ops_spent: result.as_ref().ok().map(|(ops_spent, _)| *ops_spent),
+                fees_spent: result.as_ref().ok().map(|(_, fees_spent)| *fees_spent),
+                error: result.as_ref().err().map(|error| error.to_string()),
+                usage: trace.usage(wall_time_micros),
+                host_reads: trace.host_reads().to_vec(),
+            };
+
+            self.record_receipt(receipt).await;
+        }
+
+        // 4 Insert the call if it passed.
+        let (ops_spent, fees_spent) = result?;
+        self.passed_calls.push((call, ops_spent, fees_spent));
+
+        // 5 Record the contracts re-entered by the call.
+        self.reentered_contracts
+            .extend_from_slice(call_stack.reentered());
+
+        // 6 Record the events emitted by the call.
+        self.events.extend_from_slice(trace.events());
+
+        Ok(())
+    }
+
+    /// Executes and inserts an ordered batch of calls all-or-nothing, recording the receipts of the
+    /// calls it executed whether the batch passes or fails.
+    ///
+    /// The calls run one after another against the same delta, each seeing the changes of those
+    /// before it. If a call fails, the batch stops there and everything it changed is rolled back,
+    /// including the changes of the calls that passed before it, and none of its calls is inserted.
+    pub async fn exec_insert_call_batch(
+        &mut self,
+        call_batch: CallBatch,
+    ) -> Result<(), ExecutionError> {
+        // 1 Check the batch is well-formed.
+        call_batch
+            .validate()
+            .map_err(ExecutionError::CallBatchError)?;
+
+        // 2 Pre-execution backups, taken once for the whole batch.
+        {
+            let mut _registery = self.registery.lock().await;
+            _registery.pre_execution();
+        }
+        {
+            let mut _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.pre_execution();
+        }
+        {
+            let mut _state_manager = self.state_manager.lock().await;
+            _state_manager.pre_execution();
+        }
+        let external_ops_counter = self.external_ops_counter;
+
+        // 3 Execute the calls one by one, stopping at the first one that fails.
+        self.in_call_batch = true;
+        let mut executed = Vec::<(Call, CallStack, ExecTrace, (OpsSpent, FeesSpent))>::new();
+        let mut receipts = Vec::<ExecReceipt>::new();
+        let mut failure: Option<(usize, ExecutionError)> = None;
+        for (index, call) in call_batch.calls.into_iter().enumerate() {
+            // 3.1 Start the call stack bounded by the chain params, and an empty trace.
+            let mut call_stack = {
+                let params_holder = {
+                    let _params_manager = self._params_manager.lock().unwrap();
+                    _params_manager.get_params_holder()
+                };
+                CallStack::new(
+                    call.contract().contract_id(),
+                    params_holder.max_call_depth,
+                    params_holder.max_call_count,
+                )
+            };
+            let mut trace = ExecTrace::with_host_data(self.host_data.clone());
+
+            // 3.2 Execute the call, timing it.
+            let started_at = Instant::now();
+            let result = self.exec_call(&call, &mut call_stack, &mut trace).await;
+            let wall_time_micros = started_at.elapsed().as_micros() as u64;
+
+            // 3.3 Collect the receipt of the call.
+            if let Ok(call_id) = call.sighash() {
+                receipts.push(ExecReceipt {
+                    call_id,
+                    timestamp: self.timestamp,
+                    caller: call.account().account_key(),
+                    contracts: call_stack.contracts().to_vec(),
+                    trace: trace.ops().to_vec(),
+                    reentered: call_stack.reentered().to_vec(),
+                    ops_spent: result.as_ref().ok().map(|(ops_spent, _)| *ops_spent),
+                    fees_spent: result.as_ref().ok().map(|(_, fees_spent)| *fees_spent),
+                    error: result.as_ref().err().map(|error| error.to_string()),
+                    usage: trace.usage(wall_time_micros),
+                    host_reads: trace.host_reads().to_vec(),
+                });
+            }
+
+            // 3.4 Keep the call if it passed, or stop the batch.
+            match result {
+                Ok(spent) => executed.push((call, call_stack, trace, spent)),
+                Err(error) => {
+                    failure = Some((index, error));
+                    break;
+                }
+            }
+        }
+        self.in_call_batch = false;
+
+        // 4 Roll back the whole batch if a call failed.
+        if let Some((failed_index, error)) = failure {
+            // 4.1 Roll back the managers and the external ops counter to before the batch.
+            self.rollback_last().await;
+            self.external_ops_counter = external_ops_counter;
+
+            // 4.2 Record the receipts, the calls that passed being rolled back along with the batch.
+            for mut receipt in receipts {
+                if receipt.error.is_none() {
+                    receipt.ops_spent = None;
+                    receipt.fees_spent = None;
+                    receipt.error =
+                        Some(ExecutionError::CallBatchRolledBackError(failed_index).to_string());
+                }
+                self.record_receipt(receipt).await;
+            }
+
+            // 4.3 Return the error of the failed call.
+            return Err(ExecutionError::CallBatchCallFailedError(
+                failed_index,
+                Box::new(error),
+            ));
+        }
+
+        // 5 Record the receipts.
+        for receipt in receipts {
+            self.record_receipt(receipt).await;
+        }
+
+        // 6 Insert the calls, with the contracts they re-entered and the events they emitted.
+        for (call, call_stack, trace, (ops_spent, fees_spent)) in executed {
+            self.passed_calls.push((call, ops_spent, fees_spent));
+            self.reentered_contracts
+                .extend_from_slice(call_stack.reentered());
+            self.events.extend_from_slice(trace.events());
+        }
+
+        Ok(())
+    }
+
+    /// Records the receipt of a call, persisting it in archival mode.
+    async fn record_receipt(&mut self, receipt: ExecReceipt) {
+        // 1 Persist the receipt in archival mode.
+        if let Some(archival_manager) = &self.archival_manager {
+            let mut _archival_manager = archival_manager.lock().await;
+            if let Err(err) = _archival_manager.insert_receipt(&receipt) {
+                eprintln!(
+                    "Failed to archive the receipt of call {}: {:?}",
+                    hex::encode(receipt.call_id),
+                    err
+                );
+            }
+        }
+
+        // 2 Record the receipt.
+        self.receipts.push(receipt);
+    }
+
+    /// Schedules a call to execute at a future batch height, and returns its deferred call id.
+    ///
+    /// The scheduler prepays the fees for the whole ops budget of the call at the base ops price.
+    pub async fn exec_schedule_call(
+        &mut self,
+        deferred_call: DeferredCall,
+        batch_height: u64,
+    ) -> Result<[u8; 32], ExecutionError> {
+        // 1 Check the prepaid fees cover the whole ops budget.
+        let required_fees = deferred_call.ops_budget as u64 * self.base_ops_price as u64;
+        if deferred_call.prepaid_fees != required_fees {
+            return Err(ExecutionError::DeferredCallPrepaidFeesMismatchError(
+                required_fees,
+                deferred_call.prepaid_fees,
+            ));
+        }
+
+        // 2 Lock the schedule and coin managers.
+        let mut _schedule_manager = self.schedule_manager.lock().await;
+        let mut _coin_manager = self.coin_manager.lock().await;
+
+        // 3 Pre-execution backups.
+        _schedule_manager.pre_execution();
+        _coin_manager.pre_execution();
+
+        // 4 Schedule the call.
+        let (scheduler, prepaid_fees) = (deferred_call.scheduler, deferred_call.prepaid_fees);
+        let deferred_call_id = _schedule_manager
+            .schedule(deferred_call, batch_height)
+            .map_err(ExecutionError::DeferredCallScheduleError)?;
+
+        // 5 Debit the prepaid fees from the scheduler.
+        if let Err(error) = _coin_manager.account_balance_down(scheduler, prepaid_fees) {
+            _schedule_manager.rollback_last();
+            _coin_manager.rollback_last();
+            return Err(ExecutionError::DeferredCallPrepaymentError(error));
+        }
+
+        // 6 Return the deferred call id.
+        Ok(deferred_call_id)
+    }
+
+    /// Cancels a deferred call on behalf of the account that scheduled it, refunding its prepaid
+    /// fees in full, and returns the refunded fees.
+    pub async fn exec_cancel_scheduled_call(
+        &mut self,
+        deferred_call_id: [u8; 32],
+        account_key: [u8; 32],
+    ) -> Result<u64, ExecutionError> {
+        // 1 Lock the schedule and coin managers.
+        let mut _schedule_manager = self.schedule_manager.lock().await;
+        let mut _coin_manager = self.coin_manager.lock().await;
+
+        // 2 Pre-execution backups.
+        _schedule_manager.pre_execution();
+        _coin_manager.pre_execution();
+
+        // 3 Cancel the call.
+        let deferred_call = _schedule_manager
+            .cancel(deferred_call_id, account_key)
+            .map_err(ExecutionError::DeferredCallCancelError)?;
+
+        // 4 Refund the prepaid fees to the scheduler.
+        if let Err(error) =
+            _coin_manager.account_balance_up(deferred_call.scheduler, deferred_call.prepaid_fees)
+        {
+            _schedule_manager.rollback_last();
+            _coin_manager.rollback_last();
+            return Err(ExecutionError::DeferredCallRefundError(error));
+        }
+
+        // 5 Return the refunded fees.
+        Ok(deferred_call.prepaid_fees)
+    }
+
+    /// Drains and executes the deferred calls due at or before the batch height, in height and id
+    /// order, recording their receipts whether they pass or fail.
+    ///
+    /// The prepaid fees a passed call did not spend are refunded to its scheduler. A failed call
+    /// forfeits its prepaid fees.
+    pub async fn exec_due_calls(&mut self, batch_height: u64) {
+        // 1 Drain the due calls.
+        let due_calls = {
+            let mut _schedule_manager = self.schedule_manager.lock().await;
+            _schedule_manager.drain_due(batch_height)
+        };
+
+        // 2 Execute the due calls one by one.
+        for deferred_call in due_calls {
+            // 2.1 Start the call stack bounded by the chain params, and an empty trace.
+            let mut call_stack = {
+                let params_holder = {
+                    let _params_manager = self._params_manager.lock().unwrap();
+                    _params_manager.get_params_holder()
+                };
+                CallStack::new(
+                    deferred_call.contract_id,
+                    params_holder.max_call_depth,
+                    params_holder.max_call_count,
+                )
+            };
+            let mut trace = ExecTrace::with_host_data(self.host_data.clone());
+
+            // 2.2 Execute the call, timing it.
+            let started_at = Instant::now();
+            let result = self
+                .exec_method(
+                    Caller::new_account(deferred_call.scheduler),
+                    deferred_call.contract_id,
+                    deferred_call.method_index,
+                    deferred_call
+                        .args
+                        .iter()
+                        .map(|arg| StackItem::new(arg.clone()))
+                        .collect(),
+                    deferred_call.ops_budget,
+                    self.base_ops_price,
+                    &mut call_stack,
+                    &mut trace,
+                )
+                .await;
+            let wall_time_micros = started_at.elapsed().as_micros() as u64;
+
+            // 2.3 Refund the prepaid fees the call did not spend, if it passed.
+            if let Ok((_, fees_spent)) = &result {
+                let refund = deferred_call
+                    .prepaid_fees
+                    .saturating_sub(*fees_spent as u64);
+                let mut _coin_manager = self.coin_manager.lock().await;
+                if let Err(err) = _coin_manager.account_balance_up(deferred_call.scheduler, refund)
+                {
+                    eprintln!(
+                        "Failed to refund deferred call {}: {:?}",
+                        hex::encode(deferred_call.id()),
+                        err
+                    );
+                }
+            }
+
+            // 2.4 Record the receipt of the call.
+            let receipt = ExecReceipt {
+                call_id: deferred_call.id(),
+                timestamp: self.timestamp,
+                caller: deferred_call.scheduler,
+                contracts: call_stack.contracts().to_vec(),
+                trace: trace.ops().to_vec(),
+                reentered: call_stack.reentered().to_vec(),
+                ops_spent: result.as_ref().ok().map(|(ops_spent, _)| *ops_spent),
+                fees_spent: result.as_ref().ok().map(|(_, fees_spent)| *fees_spent),
+                error: result.as_ref().err().map(|error| error.to_string()),
+                usage: trace.usage(wall_time_micros),
+                host_reads: trace.host_reads().to_vec(),
+            };
+            self.record_receipt(receipt).await;
 
+            // 2.5 Record the contracts re-entered and the events emitted by the call, if it passed.
+            if result.is_ok() {
+                self.reentered_contracts
+                    .extend_from_slice(call_stack.reentered());
+                self.events.extend_from_slice(trace.events());
+            }
+        }
+    }
+
+    /// Charges the registered contracts the rent they owe as of the batch height, debiting it from
+    /// their balances, and returns the charges.
+    ///
+    /// A contract that cannot cover its rent is debited whatever balance it has left, and is
+    /// tombstoned once it stays insolvent past the grace window of the rent policy.
+    pub async fn exec_charge_rent(&mut self, batch_height: u64) -> Vec<RentCharge> {
+        // 1 Nothing to charge if the chain charges no rent.
+        {
+            let _rent_manager = self.rent_manager.lock().await;
+            if _rent_manager.policy().is_none() {
+                return Vec::new();
+            }
+        }
+
+        // 2 Collect the registered contracts.
+        let contract_ids = {
+            let _registery = self.registery.lock().await;
+            _registery.permanently_registered_contract_ids()
+        };
+
+        // 3 Charge the contracts one by one.
+        let mut charges = Vec::<RentCharge>::new();
+        for contract_id in contract_ids {
+            // 3.1 Get the state size of the contract.
+            let state_bytes = {
+                let _state_manager = self.state_manager.lock().await;
+                _state_manager.get_contract_state_size(contract_id)
+            };
+
+            // 3.2 Lock the rent and coin managers.
+            let mut _rent_manager = self.rent_manager.lock().await;
+            let mut _coin_manager = self.coin_manager.lock().await;
+
+            // 3.3 Pre-execution backups.
+            _rent_manager.pre_execution();
+            _coin_manager.pre_execution();
+
+            // 3.4 Charge the rent against the shadow allocations and balance of the contract.
+            let charge = match _rent_manager.charge(
+                contract_id,
+                batch_height,
+                state_bytes,
+                _coin_manager
+                    .get_contract_num_shadow_allocs(contract_id)
+                    .unwrap_or(0),
+                _coin_manager.get_contract_balance(contract_id).unwrap_or(0),
+            ) {
+                Some(charge) => charge,
+                None => continue,
+            };
+
+            // 3.5 Debit the rent from the contract balance.
+            if charge.charged > 0 {
+                if let Err(err) = _coin_manager.contract_balance_down(contract_id, charge.charged) {
+                    _rent_manager.rollback_last();
+                    _coin_manager.rollback_last();
+                    eprintln!(
+                        "Failed to charge the rent of contract {}: {:?}",
+                        hex::encode(contract_id),
+                        err
+                    );
+                    continue;
+                }
+            }
+
+            // 3.6 Record the charge.
+            charges.push(charge);
+        }
+
+        // 4 Return the charges.
+        charges
+    }
+
+    /// Executes a call, returning the ops and fees it spent.
+    async fn exec_call(
+        &mut self,
+        call: &Call,
+        call_stack: &mut CallStack,
+        trace: &mut ExecTrace,
+    ) -> Result<(OpsSpent, FeesSpent), ExecutionError> {
         // The caller is the account key.
         let caller = Caller::new_account(call.account().account_key());
 
@@ -81,9 +590,6 @@ impl ProgramExecCtx {
             .map(|calldata_element| calldata_element.into_stack_item())
             .collect::<Vec<StackItem>>();
 
-        // The timestamp is the timestamp of the execution context.
-        let timestamp = self.timestamp;
-
         // The ops budget is the ops budget of the call.
         let ops_budget = match call.ops_budget() {
             Some(ops_budget) => ops_budget,
@@ -98,32 +604,90 @@ impl ProgramExecCtx {
         // The ops price is the total ops price of the call (base + extra).
         let ops_price = call.ops_price_total();
 
+        // Execute the called method.
+        self.exec_method(
+            caller,
+            contract_id,
+            method_index,
+            args_as_stack_items,
+            ops_budget,
+            ops_price,
+            call_stack,
+            trace,
+        )
+        .await
+    }
+
+    /// Executes a method of a contract as an external call of the caller, returning the ops and
+    /// fees it spent, and rolling back whatever it changed if it fails.
+    async fn exec_method(
+        &mut self,
+        caller: Caller,
+        contract_id: [u8; 32],
+        method_index: u16,
+        args_as_stack_items: Vec<StackItem>,
+        ops_budget: u32,
+        ops_price: u32,
+        call_stack: &mut CallStack,
+        trace: &mut ExecTrace,
+    ) -> Result<(OpsSpent, FeesSpent), ExecutionError> {
+        // This is an external call.
+        let internal = false;
+
+        // The timestamp is the timestamp of the execution context.
+        let timestamp = self.timestamp;
+
         // Internal ops counter is 0.
         let internal_ops_counter = 0;
 
         // External ops counter is the external ops counter of the call.
         let external_ops_counter = self.external_ops_counter;
 
+        // Contracts tombstoned for unpaid rent may not be entered.
+        call_stack.set_tombstoned(self.tombstoned_contracts().await);
+
         // State manager.
         let state_manager = &self.state_manager;
 
-        // Pre-execution state backup.
-        {
-            let mut _state_manager = state_manager.lock().await;
-            _state_manager.pre_execution();
-        }
-
         let coin_manager = &self.coin_manager;
 
-        // Pre-execution coin holder backup.
-        {
-            let mut _coin_manager = coin_manager.lock().await;
-            _coin_manager.pre_execution();
+        // Pre-execution state and coin holder backups, unless they were taken for the whole call
+        // batch.
+        if !self.in_call_batch {
+            {
+                let mut _state_manager = state_manager.lock().await;
+                _state_manager.pre_execution();
+            }
+            {
+                let mut _coin_manager = coin_manager.lock().await;
+                _coin_manager.pre_execution();
+            }
         }
 
         // Programs repo.
         let registery = &self.registery;
 
+        // In debug mode, execute the call a first time in an isolated delta to compare against.
+        let first_outcome = match determinism_check_enabled() {
+            true => Some(
+                self.exec_isolated(
+                    caller,
+                    contract_id,
+                    method_index,
+                    args_as_stack_items.clone(),
+                    ops_budget,
+                    ops_price,
+                    call_stack,
+                    trace,
+                )
+                .await,
+            ),
+            false => None,
+        };
+
+        // Watchdog aborting the execution once it runs for longer than its timeout.
+        let watchdog = ExecWatchdog::start(exec_timeout());
+
         // Execution.
         let exectuion_result = execute(
             internal,
@@ -136,12 +700,38 @@ impl ProgramExecCtx {
             ops_price,
             internal_ops_counter,
             external_ops_counter,
+            0,
             state_manager,
             coin_manager,
             registery,
+            call_stack,
+            trace,
+            &watchdog,
         )
         .await;
 
+        // In debug mode, reject the call if the two executions diverged, before it can split
+        // consensus.
+        if let Some(first_outcome) = first_outcome {
+            let outcome = self.exec_outcome(&exectuion_result, trace).await;
+            if let Some(divergence) = first_outcome.divergence(&outcome) {
+                eprintln!(
+                    "Nondeterministic execution of method {} of contract {}: the {} diverged.",
+                    method_index,
+                    hex::encode(contract_id),
+                    divergence
+                );
+                if !self.in_call_batch {
+                    self.rollback_last().await;
+                }
+                return Err(ExecutionError::NondeterministicExecutionError(
+                    contract_id,
+                    method_index,
+                    divergence.to_string(),
+                ));
+            }
+        }
+
         match exectuion_result {
             Ok((return_items, ops_spent, new_external_ops_counter)) => {
                 // Stack must end with exactly one item and it must be true.
@@ -166,29 +756,13 @@ impl ProgramExecCtx {
                 // Update the external ops counter.
                 self.external_ops_counter = new_external_ops_counter;
 
-                // Insert the call.
-                self.passed_calls.push((call, ops_spent, fees_spent));
-
-                // Return Ok.
-                Ok(())
+                // Return the ops and fees spent.
+                Ok((ops_spent, fees_spent))
             }
             Err(error) => {
-                // Rollback last on the registery manager.
-                {
-                    let mut _registery = registery.lock().await;
-                    _registery.rollback_last();
-                }
-
-                // Rollback last on the coin manager.
-                {
-                    let mut _coin_manager = coin_manager.lock().await;
-                    _coin_manager.rollback_last();
-                }
-
-                // Rollback last on the state manager.
-                {
-                    let mut _state_manager = state_manager.lock().await;
-                    _state_manager.rollback_last();
+                // Rollback last on the managers, unless the whole call batch is to be rolled back.
+                if !self.in_call_batch {
+                    self.rollback_last().await;
                 }
 
                 // Return the error.
@@ -197,6 +771,369 @@ impl ProgramExecCtx {
         }
     }
 
+    /// Executes a method of a contract as an external call of the caller against copies of the
+    /// call stack and trace, returning its outcome and undoing whatever it changed.
+    ///
+    /// NOTE: Used by the determinism check, which compares the outcome against a second execution.
+    async fn exec_isolated(
+        &self,
+        caller: Caller,
+        contract_id: [u8; 32],
+        method_index: u16,
+        args_as_stack_items: Vec<StackItem>,
+        ops_budget: u32,
+        ops_price: u32,
+        call_stack: &CallStack,
+        trace: &ExecTrace,
+    ) -> ExecOutcome {
+        // 1 Checkpoint the deltas, leaving their backups untouched.
+        let state_checkpoint = {
+            let _state_manager = self.state_manager.lock().await;
+            _state_manager.delta_checkpoint()
+        };
+        let coin_checkpoint = {
+            let _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.delta_checkpoint()
+        };
+
+        // 2 Execute the call against copies of the call stack and trace.
+        let mut call_stack = call_stack.clone();
+        let mut trace = trace.clone();
+        let watchdog = ExecWatchdog::start(exec_timeout());
+        let execution_result = execute(
+            false,
+            caller,
+            contract_id,
+            method_index,
+            args_as_stack_items,
+            self.timestamp,
+            ops_budget,
+            ops_price,
+            0,
+            self.external_ops_counter,
+            0,
+            &self.state_manager,
+            &self.coin_manager,
+            &self.registery,
+            &mut call_stack,
+            &mut trace,
+            &watchdog,
+        )
+        .await;
+
+        // 3 Record the outcome.
+        let outcome = self.exec_outcome(&execution_result, &trace).await;
+
+        // 4 Restore the deltas from the checkpoints.
+        {
+            let mut _state_manager = self.state_manager.lock().await;
+            _state_manager.restore_delta_checkpoint(state_checkpoint);
+        }
+        {
+            let mut _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.restore_delta_checkpoint(coin_checkpoint);
+        }
+
+        // 5 Return the outcome.
+        outcome
+    }
+
+    /// Returns the outcome of an execution, with the deltas it left behind.
+    async fn exec_outcome(
+        &self,
+        execution_result: &Result<(Vec<StackItem>, u32, u32), ExecutionError>,
+        trace: &ExecTrace,
+    ) -> ExecOutcome {
+        let state_delta = {
+            let _state_manager = self.state_manager.lock().await;
+            _state_manager.delta_bytes()
+        };
+        let coin_delta = {
+            let _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.delta_bytes()
+        };
+        ExecOutcome::new(
+            execution_result,
+            state_delta,
+            coin_delta,
+            trace.events().to_vec(),
+        )
+    }
+
+    /// Upgrades the program of a contract on behalf of its upgrade authority, and runs the
+    /// state-migration hook of the new program if it has one, returning the ops and fees it spent.
+    ///
+    /// The upgrade and the migration are all-or-nothing: if the migration fails, the upgrade is
+    /// rolled back along with the migration's state changes.
+    pub async fn exec_upgrade_contract(
+        &mut self,
+        contract_id: [u8; 32],
+        upgrade_authority: [u8; 32],
+        executable: Executable,
+        ops_budget: u32,
+    ) -> Result<(OpsSpent, FeesSpent), ExecutionError> {
+        // 1 A contract tombstoned for unpaid rent can no longer be upgraded.
+        {
+            let _rent_manager = self.rent_manager.lock().await;
+            if _rent_manager.is_tombstoned(contract_id) {
+                return Err(ExecutionError::ContractTombstonedError(contract_id));
+            }
+        }
+
+        // 2 Pre-execution backups.
+        {
+            let mut _registery = self.registery.lock().await;
+            _registery.pre_execution();
+        }
+        {
+            let mut _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.pre_execution();
+        }
+        {
+            let mut _state_manager = self.state_manager.lock().await;
+            _state_manager.pre_execution();
+        }
+
+        // 3 Get the migration hook of the new program.
+        let migration_method_index = executable.migration_method_index();
+
+        // 4 Epheremally upgrade the contract.
+        {
+            let mut _registery = self.registery.lock().await;
+            if let Err(error) =
+                _registery.upgrade_contract(contract_id, upgrade_authority, executable)
+            {
+                drop(_registery);
+                self.rollback_last().await;
+                return Err(ExecutionError::ContractUpgradeError(error));
+            }
+        }
+
+        // 5 Nothing else to run if the new program has no migration hook.
+        let method_index = match migration_method_index {
+            Some(method_index) => method_index,
+            None => return Ok((0, 0)),
+        };
+
+        // 6 Start the call stack bounded by the chain params, and an empty trace.
+        let mut call_stack = {
+            let params_holder = {
+                let _params_manager = self._params_manager.lock().unwrap();
+                _params_manager.get_params_holder()
+            };
+            CallStack::new(
+                contract_id,
+                params_holder.max_call_depth,
+                params_holder.max_call_count,
+            )
+        };
+        let mut trace = ExecTrace::with_host_data(self.host_data.clone());
+
+        // 7 Run the migration hook as an internal call of the contract to itself.
+        let watchdog = ExecWatchdog::start(exec_timeout());
+        let execution_result = execute(
+            true,
+            Caller::new_contract(contract_id),
+            contract_id,
+            method_index,
+            Vec::<StackItem>::new(),
+            self.timestamp,
+            ops_budget,
+            self.base_ops_price,
+            0,
+            self.external_ops_counter,
+            0,
+            &self.state_manager,
+            &self.coin_manager,
+            &self.registery,
+            &mut call_stack,
+            &mut trace,
+            &watchdog,
+        )
+        .await;
+
+        // 8 The migration hook must end with exactly one true item.
+        let result = match execution_result {
+            Ok((return_items, ops_spent, new_external_ops_counter)) => match return_items.len() {
+                1 if return_items[0].is_true() => Ok((ops_spent, new_external_ops_counter)),
+                1 => Err(ExecutionError::ReturnErrorFromStackError(
+                    return_items[0].clone(),
+                )),
+                _ => Err(ExecutionError::InvalidStackEndingError),
+            },
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok((ops_spent, new_external_ops_counter)) => {
+                // 9.a Update the external ops counter.
+                self.external_ops_counter = new_external_ops_counter;
+
+                // 9.a.1 Return the ops and fees spent.
+                Ok((ops_spent, ops_spent * self.base_ops_price))
+            }
+            Err(error) => {
+                // 9.b Rollback the upgrade along with the migration.
+                self.rollback_last().await;
+
+                // 9.b.1 Return the error.
+                Err(error)
+            }
+        }
+    }
+
+    /// Pauses or unpauses a contract on behalf of its pause authority, recording the switch in the
+    /// registery and emitting it as an event of the contract.
+    ///
+    /// A paused contract rejects new calls, except for those withdrawing shadow allocations.
+    pub async fn exec_set_contract_paused(
+        &mut self,
+        contract_id: [u8; 32],
+        pause_authority: [u8; 32],
+        paused: bool,
+    ) -> Result<(), ExecutionError> {
+        // 1 Epheremally pause or unpause the contract.
+        {
+            let mut _registery = self.registery.lock().await;
+            _registery.pre_execution();
+            _registery
+                .set_contract_paused(contract_id, pause_authority, paused)
+                .map_err(ExecutionError::ContractPauseError)?;
+        }
+
+        // 2 Emit the switch as an event of the contract, carrying the pause authority.
+        let topic = match paused {
+            true => PAUSE_EVENT_TOPIC,
+            false => UNPAUSE_EVENT_TOPIC,
+        };
+        self.events.push(ExecEvent {
+            contract_id,
+            topic: topic.to_vec(),
+            data: pause_authority.to_vec(),
+        });
+
+        // 3 Return the result.
+        Ok(())
+    }
+
+    /// Executes a read-only method of a contract and returns the items it left on the stack.
+    ///
+    /// Read calls are made by the zero account, and whatever they change is rolled back. Results
+    /// are served from the read call cache while the state root they were executed against stays
+    /// current.
+    pub async fn exec_read_call(
+        &mut self,
+        contract_id: [u8; 32],
+        method_index: u16,
+        args: Vec<StackItem>,
+        ops_budget: u32,
+    ) -> Result<Vec<StackItem>, ExecutionError> {
+        // 1 Serve the result from the cache if it was already executed against the current state.
+        let key = {
+            let mut _read_call_cache = read_call_cache().lock().unwrap();
+            let key = ReadCallKey::new(
+                contract_id,
+                method_index,
+                &args,
+                _read_call_cache.state_root(),
+            );
+            if let Some(result) = _read_call_cache.get(&key) {
+                return Ok(result);
+            }
+            key
+        };
+
+        // 2 Pre-execution backups.
+        {
+            let mut _registery = self.registery.lock().await;
+            _registery.pre_execution();
+        }
+        {
+            let mut _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.pre_execution();
+        }
+        {
+            let mut _state_manager = self.state_manager.lock().await;
+            _state_manager.pre_execution();
+        }
+
+        // 3 Start the read-only call stack bounded by the chain params, and an empty trace.
+        let mut call_stack = {
+            let params_holder = {
+                let _params_manager = self._params_manager.lock().unwrap();
+                _params_manager.get_params_holder()
+            };
+            CallStack::new_read_only(
+                contract_id,
+                params_holder.max_call_depth,
+                params_holder.max_call_count,
+            )
+        };
+        call_stack.set_tombstoned(self.tombstoned_contracts().await);
+        let mut trace = ExecTrace::with_host_data(self.host_data.clone());
+
+        // 4 Execute the read call.
+        let watchdog = ExecWatchdog::start(exec_timeout());
+        let execution_result = execute(
+            false,
+            Caller::new_account([0x00; 32]),
+            contract_id,
+            method_index,
+            args,
+            self.timestamp,
+            ops_budget,
+            self.base_ops_price,
+            0,
+            0,
+            0,
+            &self.state_manager,
+            &self.coin_manager,
+            &self.registery,
+            &mut call_stack,
+            &mut trace,
+            &watchdog,
+        )
+        .await;
+
+        // 5 Roll back whatever the read call changed.
+        self.rollback_last().await;
+
+        // 6 Cache and return the result.
+        let (result, _, _) = execution_result?;
+        read_call_cache()
+            .lock()
+            .unwrap()
+            .insert(key, result.clone());
+        Ok(result)
+    }
+
+    /// Returns the contracts tombstoned for unpaid rent.
+    async fn tombstoned_contracts(&self) -> Vec<[u8; 32]> {
+        let _rent_manager = self.rent_manager.lock().await;
+        _rent_manager.tombstoned_contracts()
+    }
+
+    /// Reverts the epheremal changes of the last execution on the managers.
+    async fn rollback_last(&self) {
+        // Rollback last on the registery manager.
+        {
+            let mut _registery = self.registery.lock().await;
+            _registery.rollback_last();
+        }
+
+        // Rollback last on the coin manager.
+        {
+            let mut _coin_manager = self.coin_manager.lock().await;
+            _coin_manager.rollback_last();
+        }
+
+        // Rollback last on the state manager.
+        {
+            let mut _state_manager = self.state_manager.lock().await;
+            _state_manager.rollback_last();
+        }
+    }
+
     /// Flushes all the passed calls.
     pub async fn flush_all(&mut self) {
         // Flush the registery manager delta.
@@ -217,11 +1154,60 @@ impl ProgramExecCtx {
             _state_manager.flush_delta();
         }
 
+        // Flush the schedule manager delta.
+        {
+            let mut _schedule_manager = self.schedule_manager.lock().await;
+            _schedule_manager.flush_delta();
+        }
+
+        // Flush the rent manager delta.
+        {
+            let mut _rent_manager = self.rent_manager.lock().await;
+            _rent_manager.flush_delta();
+        }
+
         // Set the external ops counter to zero.
         self.external_ops_counter = 0;
 
         // Clear the passed calls.
         self.passed_calls.clear();
+
+        // Clear the re-entered contracts.
+        self.reentered_contracts.clear();
+
+        // Clear the receipts.
+        self.receipts.clear();
+
+        // Clear the events.
+        self.events.clear();
+
+        // Clear the host data.
+        self.host_data = None;
+    }
+
+    /// Persists the deferred calls scheduled, cancelled and drained since the last flush.
+    pub async fn apply_schedule_changes(&self) -> Result<(), ScheduleManagerApplyChangesError> {
+        let mut _schedule_manager = self.schedule_manager.lock().await;
+        _schedule_manager.apply_changes()
+    }
+
+    /// Persists the rent charged and the contracts tombstoned since the last flush.
+    pub async fn apply_rent_changes(&self) -> Result<(), RentManagerApplyChangesError> {
+        let mut _rent_manager = self.rent_manager.lock().await;
+        _rent_manager.apply_changes()
+    }
+
+    /// Persists the events emitted by the passed calls under the batch height, in archival mode.
+    pub async fn archive_events(&self, batch_height: u64) {
+        if let Some(archival_manager) = &self.archival_manager {
+            let mut _archival_manager = archival_manager.lock().await;
+            if let Err(err) = _archival_manager.insert_events(batch_height, &self.events) {
+                eprintln!(
+                    "Failed to archive the events of batch {}: {:?}",
+                    batch_height, err
+                );
+            }
+        }
     }
 
     /// Returns the passed calls length.
@@ -234,6 +1220,21 @@ impl ProgramExecCtx {
         self.passed_calls.clone()
     }
 
+    /// Returns the contracts re-entered by the passed calls with their opt-in.
+    pub fn reentered_contracts(&self) -> Vec<[u8; 32]> {
+        self.reentered_contracts.clone()
+    }
+
+    /// Returns the receipts of the calls executed, passed or failed.
+    pub fn receipts(&self) -> Vec<ExecReceipt> {
+        self.receipts.clone()
+    }
+
+    /// Returns the events emitted by the passed calls, in order.
+    pub fn events(&self) -> Vec<ExecEvent> {
+        self.events.clone()
+    }
+
     /// Returns the external ops counter.
     pub fn external_ops_counter(&self) -> u32 {
         self.external_ops_counter
@@ -0,0 +1,179 @@
+use crate::executive::stack::stack_item::StackItem;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+/// Default number of read call results kept.
+const DEFAULT_READ_CALL_CACHE_SIZE: usize = 1024;
+
+/// Process-wide read call cache, read from the environment on first use.
+static READ_CALL_CACHE: OnceLock<Mutex<ReadCallCache>> = OnceLock::new();
+
+/// What a read call result depends on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReadCallKey {
+    // The contract id of the called contract.
+    pub contract_id: [u8; 32],
+
+    // The index of the read-only method called.
+    pub method_index: u16,
+
+    // The bytes of the args passed.
+    pub args: Vec<Vec<u8>>,
+
+    // The state root the call was executed against.
+    pub state_root: [u8; 32],
+}
+
+impl ReadCallKey {
+    /// Constructs the key of a read call.
+    pub fn new(
+        contract_id: [u8; 32],
+        method_index: u16,
+        args: &Vec<StackItem>,
+        state_root: [u8; 32],
+    ) -> Self {
+        Self {
+            contract_id,
+            method_index,
+            args: args.iter().map(|arg| arg.bytes().to_vec()).collect(),
+            state_root,
+        }
+    }
+}
+
+/// Bounded cache of the results of read-only calls, so that query endpoints hitting the same views
+/// repeatedly don't re-execute them.
+///
+/// Results are keyed by the state root they were executed against, and the whole cache is
+/// invalidated once changes are applied and the state root moves on. Results executed against a
+/// stale state root are not cached. Once `capacity` results are kept, the oldest is evicted first.
+pub struct ReadCallCache {
+    // Cached results by key.
+    entries: HashMap<ReadCallKey, Vec<StackItem>>,
+
+    // Cached keys, the oldest first.
+    order: VecDeque<ReadCallKey>,
+
+    // Maximum number of results kept; `0` disables caching.
+    capacity: usize,
+
+    // The state root results are currently cached against.
+    state_root: [u8; 32],
+
+    // Number of lookups served from the cache.
+    hits: u64,
+
+    // Number of lookups not found in the cache.
+    misses: u64,
+
+    // Number of times the cache was invalidated.
+    invalidations: u64,
+}
+
+impl ReadCallCache {
+    /// Constructs an empty cache.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            state_root: [0x00; 32],
+            hits: 0,
+            misses: 0,
+            invalidations: 0,
+        }
+    }
+
+    /// Constructs an empty cache from the environment.
+    ///
+    /// `CUBE_READ_CALL_CACHE_SIZE` optionally overrides the default number of results kept. Setting
+    /// it to `0` executes every read call.
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("CUBE_READ_CALL_CACHE_SIZE")
+                .ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .unwrap_or(DEFAULT_READ_CALL_CACHE_SIZE),
+        )
+    }
+
+    /// Returns the state root results are currently cached against.
+    pub fn state_root(&self) -> [u8; 32] {
+        self.state_root
+    }
+
+    /// Returns the cached result of a read call.
+    pub fn get(&mut self, key: &ReadCallKey) -> Option<Vec<StackItem>> {
+        match self.entries.get(key) {
+            Some(result) => {
+                self.hits += 1;
+                Some(result.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches the result of a read call, unless it was executed against a stale state root.
+    pub fn insert(&mut self, key: ReadCallKey, result: Vec<StackItem>) {
+        // 1 Skip results executed against a stale state root, or if caching is disabled.
+        if key.state_root != self.state_root || self.capacity == 0 {
+            return;
+        }
+
+        // 2 Evict the oldest results to make room.
+        if !self.entries.contains_key(&key) {
+            while self.order.len() >= self.capacity {
+                match self.order.pop_front() {
+                    Some(oldest) => {
+                        self.entries.remove(&oldest);
+                    }
+                    None => break,
+                }
+            }
+            self.order.push_back(key.clone());
+        }
+
+        // 3 Cache the result.
+        self.entries.insert(key, result);
+    }
+
+    /// Drops all cached results, moving on to the new state root.
+    pub fn invalidate(&mut self, state_root: [u8; 32]) {
+        self.entries.clear();
+        self.order.clear();
+        self.state_root = state_root;
+        self.invalidations += 1;
+    }
+
+    /// Returns the number of results cached.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the cache metrics as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("capacity".to_string(), Value::Number(self.capacity.into()));
+        obj.insert("cached".to_string(), Value::Number(self.len().into()));
+        obj.insert(
+            "state_root".to_string(),
+            Value::String(hex::encode(self.state_root)),
+        );
+        obj.insert("hits".to_string(), Value::Number(self.hits.into()));
+        obj.insert("misses".to_string(), Value::Number(self.misses.into()));
+        obj.insert(
+            "invalidations".to_string(),
+            Value::Number(self.invalidations.into()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Returns the process-wide read call cache.
+pub fn read_call_cache() -> &'static Mutex<ReadCallCache> {
+    READ_CALL_CACHE.get_or_init(|| Mutex::new(ReadCallCache::from_env()))
+}
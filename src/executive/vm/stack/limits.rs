@@ -18,3 +18,6 @@ pub const MAX_CONTRACT_MEMORY_SIZE: u32 = 65_536;
 
 // Ops upper bound.
 pub const OPS_LIMIT: u32 = 100_000;
+
+/// The total ops refunded within an execution is capped at the ops spent divided by this quotient.
+pub const MAX_OPS_REFUND_QUOTIENT: u32 = 5;
@@ -2,6 +2,7 @@ pub mod flow;
 pub mod limits;
 pub mod stack;
 pub mod stack_error;
+pub mod stack_fixed;
 pub mod stack_holder;
 pub mod stack_item;
 pub mod stack_uint;
@@ -1,6 +1,7 @@
 use crate::inscriptive::{
     coin_manager::errors::balance_update_errors::{
         CMAccountBalanceUpError, CMContractBalanceDownError, CMContractBalanceUpError,
+        CMContractTransferError,
     },
     coin_manager::errors::shadow_alloc_errors::{
         CMContractShadowAllocAccountError, CMContractShadowDeallocAccountError,
@@ -134,6 +135,8 @@ pub enum CoinTransferError {
     AccountBalanceUpError([u8; 32], CMAccountBalanceUpError),
     /// The contract balance up error.
     ContractBalanceUpError([u8; 32], CMContractBalanceUpError),
+    /// The contract transfer error.
+    ContractTransferError([u8; 32], CMContractTransferError),
 }
 
 /// The shadow ops error.
@@ -159,6 +162,49 @@ pub enum ShadowOpsError {
     AccountKeyHasNoAllocation([u8; 32]),
 }
 
+/// The precompile error.
+#[derive(Debug, Clone)]
+pub enum PrecompileError {
+    /// The precompile id is invalid.
+    InvalidPrecompileIdBytes(Vec<u8>),
+    /// The precompile id is not registered.
+    UnknownPrecompile(u8),
+    /// The integer argument is invalid.
+    InvalidUintBytes(Vec<u8>),
+    /// The modulus is zero.
+    ZeroModulus,
+    /// The ECDSA public key is invalid.
+    InvalidECDSAPublicKeyBytes,
+    /// The ECDSA message is invalid.
+    InvalidECDSAMessageBytes,
+    /// The ECDSA signature is invalid.
+    InvalidECDSASignatureBytes,
+    /// The 64.64 fixed-point argument is invalid.
+    InvalidFixedPointBytes(Vec<u8>),
+    /// The 64.64 fixed-point result does not fit.
+    FixedPointOverflow,
+    /// The 64.64 fixed-point divisor is zero.
+    FixedPointDivisionByZero,
+}
+
+/// The event error.
+#[derive(Debug, Clone)]
+pub enum EventError {
+    /// The event topic length is invalid.
+    InvalidEventTopicLength(usize),
+    /// The call has emitted the maximum number of events.
+    EventLimitExceeded,
+}
+
+/// The host data error.
+#[derive(Debug, Clone)]
+pub enum HostDataError {
+    /// The host data key length is invalid.
+    InvalidHostDataKeyLength(usize),
+    /// The execution was given no host data.
+    HostDataUnavailable,
+}
+
 /// The stack error.
 #[derive(Debug, Clone)]
 pub enum StackError {
@@ -212,4 +258,10 @@ pub enum StackError {
     CoinTransferError(CoinTransferError),
     /// The shadow ops error.
     ShadowOpsError(ShadowOpsError),
+    /// The precompile error.
+    PrecompileError(PrecompileError),
+    /// The event error.
+    EventError(EventError),
+    /// The host data error.
+    HostDataError(HostDataError),
 }
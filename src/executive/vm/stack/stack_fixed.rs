@@ -0,0 +1,133 @@
+use super::stack_uint::StackUint;
+
+/// Number of fractional bits of a `StackFixed`.
+pub const FIXED_FRACTIONAL_BITS: usize = 64;
+
+/// An unsigned 64.64 fixed-point number, for interest and ratio computations in contracts.
+///
+/// On the stack, a fixed-point number is the integer of its raw 128-bit representation, that is the
+/// number times 2^64. Every operation rounds down and is checked, returning `None` rather than
+/// wrapping or truncating silently, so that all nodes agree on the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StackFixed(u128);
+
+impl StackFixed {
+    /// The fixed-point number one.
+    pub const ONE: StackFixed = StackFixed(1 << FIXED_FRACTIONAL_BITS);
+
+    /// Creates a fixed-point number from its raw representation.
+    pub fn from_raw(raw: u128) -> Self {
+        StackFixed(raw)
+    }
+
+    /// Returns the raw representation of the fixed-point number.
+    pub fn raw(&self) -> u128 {
+        self.0
+    }
+
+    /// Creates a fixed-point number from an integer.
+    pub fn from_int(int: u64) -> Self {
+        StackFixed((int as u128) << FIXED_FRACTIONAL_BITS)
+    }
+
+    /// Creates a fixed-point number from the ratio of two integers, rounding down.
+    ///
+    /// Returns `None` if the denominator is zero or the ratio does not fit.
+    pub fn from_ratio(numerator: u128, denominator: u128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let result =
+            (StackUint::from(numerator) << FIXED_FRACTIONAL_BITS) / StackUint::from(denominator);
+
+        Self::from_stack_uint(&result)
+    }
+
+    /// Creates a fixed-point number from the integer of its raw representation.
+    ///
+    /// Returns `None` if the integer does not fit in 128 bits.
+    pub fn from_stack_uint(value: &StackUint) -> Option<Self> {
+        if value.bits() > 128 {
+            return None;
+        }
+
+        Some(StackFixed(value.low_u128()))
+    }
+
+    /// Returns the integer of the raw representation of the fixed-point number.
+    pub fn to_stack_uint(&self) -> StackUint {
+        StackUint::from(self.0)
+    }
+
+    /// Returns the integer part of the fixed-point number.
+    pub fn floor(&self) -> u64 {
+        (self.0 >> FIXED_FRACTIONAL_BITS) as u64
+    }
+
+    /// Adds two fixed-point numbers, returning `None` on overflow.
+    pub fn checked_add(&self, other: &StackFixed) -> Option<StackFixed> {
+        self.0.checked_add(other.0).map(StackFixed)
+    }
+
+    /// Subtracts a fixed-point number, returning `None` on underflow.
+    pub fn checked_sub(&self, other: &StackFixed) -> Option<StackFixed> {
+        self.0.checked_sub(other.0).map(StackFixed)
+    }
+
+    /// Multiplies two fixed-point numbers, rounding down.
+    ///
+    /// Returns `None` if the product does not fit.
+    pub fn checked_mul(&self, other: &StackFixed) -> Option<StackFixed> {
+        // The product of two 128-bit integers fits in 256 bits.
+        let result = (StackUint::from(self.0) * StackUint::from(other.0)) >> FIXED_FRACTIONAL_BITS;
+
+        Self::from_stack_uint(&result)
+    }
+
+    /// Divides by a fixed-point number, rounding down.
+    ///
+    /// Returns `None` if the divisor is zero or the quotient does not fit.
+    pub fn checked_div(&self, other: &StackFixed) -> Option<StackFixed> {
+        Self::from_ratio(self.0, other.0)
+    }
+
+    /// Multiplies the fixed-point number by an integer, rounding the product down to an integer.
+    ///
+    /// Returns `None` if the product does not fit in 128 bits.
+    pub fn checked_mul_int(&self, int: u128) -> Option<u128> {
+        // The product of two 128-bit integers fits in 256 bits.
+        let result = (StackUint::from(self.0) * StackUint::from(int)) >> FIXED_FRACTIONAL_BITS;
+
+        if result.bits() > 128 {
+            return None;
+        }
+
+        Some(result.low_u128())
+    }
+
+    /// Raises the fixed-point number to an integer power by squaring and multiplying, rounding
+    /// down at every step.
+    ///
+    /// Returns `None` if any step does not fit.
+    pub fn checked_pow(&self, exponent: u64) -> Option<StackFixed> {
+        let mut result = StackFixed::ONE;
+        let mut base = *self;
+        let mut exponent = exponent;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+
+            exponent >>= 1;
+
+            // Skip the last squaring, which is not used and could overflow needlessly.
+            if exponent > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+
+        Some(result)
+    }
+}
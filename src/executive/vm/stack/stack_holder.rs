@@ -1,6 +1,6 @@
 use super::{
     flow::{flow_encounter::FlowEncounter, flow_status::FlowStatus},
-    limits::OPS_LIMIT,
+    limits::{MAX_OPS_REFUND_QUOTIENT, OPS_LIMIT},
     stack::Stack,
     stack_error::{OpsBudgetError, StackError},
     stack_item::StackItem,
@@ -37,6 +37,8 @@ pub struct StackHolder {
     internal_ops_counter: u32,
     // External ops counter.
     external_ops_counter: u32,
+    // Ops refunded to the internal ops counter so far.
+    refunded_ops: u32,
     // List of flow encounters nested in each other.
     // Since OP_IF/OP_NOTIF/OP_ELSE/OP_ENDIF can be nested, we need to keep track of the flow encounters.
     flow_encounters: Vec<FlowEncounter>,
@@ -83,6 +85,7 @@ impl<'a> StackHolder {
             ops_price,
             internal_ops_counter,
             external_ops_counter,
+            refunded_ops: 0,
             flow_encounters: Vec::<FlowEncounter>::new(),
         };
 
@@ -222,6 +225,36 @@ impl<'a> StackHolder {
         Ok(())
     }
 
+    /// Returns the ops refunded to the internal ops counter so far.
+    pub fn refunded_ops(&self) -> u32 {
+        self.refunded_ops
+    }
+
+    /// Carries over the ops refunded by the calls before this one, as the refund cap holds across
+    /// calls.
+    pub fn carry_refunded_ops(&mut self, refunded_ops: u32) {
+        self.refunded_ops = refunded_ops;
+    }
+
+    /// Refunds ops to the internal ops counter, e.g. for freeing what an earlier opcode allocated.
+    ///
+    /// The total refunded is capped at a fraction of the ops spent, so that refunds can only lower
+    /// the ops charged, and never let an execution run past its budget. The external ops counter
+    /// is left as it is, since the work was done regardless.
+    pub fn refund_ops(&mut self, ops: u32) {
+        // 1 The ops spent are what is counted, plus what was already refunded.
+        let spent_ops = self.internal_ops_counter.saturating_add(self.refunded_ops);
+
+        // 2 Cap the refund to what is left of the refund allowance.
+        let refund_allowance =
+            (spent_ops / MAX_OPS_REFUND_QUOTIENT).saturating_sub(self.refunded_ops);
+        let refund = ops.min(refund_allowance);
+
+        // 3 Refund the ops.
+        self.internal_ops_counter = self.internal_ops_counter.saturating_sub(refund);
+        self.refunded_ops += refund;
+    }
+
     /// Returns the contract memory.
     pub fn memory(&self) -> &HashMap<Vec<u8>, Vec<u8>> {
         &self.memory
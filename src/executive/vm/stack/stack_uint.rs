@@ -15,7 +15,7 @@ construct_uint! {
 impl From<StackUint> for U512 {
     fn from(value: StackUint) -> Self {
         let mut result = U512::zero();
-        for i in 0..8 {
+        for i in 0..4 {
             result.0[i] = value.0[i];
         }
         result
@@ -25,7 +25,7 @@ impl From<StackUint> for U512 {
 impl From<U512> for StackUint {
     fn from(value: U512) -> Self {
         let mut result = StackUint::zero();
-        for i in 0..8 {
+        for i in 0..4 {
             result.0[i] = value.0[i];
         }
         result
@@ -60,6 +60,46 @@ impl StackUint {
         StackUint::from(result_modulo_max)
     }
 
+    /// Multiply two `StackUint` values and return the result modulo the given modulus.
+    ///
+    /// Returns `None` if the modulus is zero.
+    pub fn modmul(x: &StackUint, y: &StackUint, modulus: &StackUint) -> Option<StackUint> {
+        if modulus.is_zero() {
+            return None;
+        }
+
+        let result = (U512::from(*x) * U512::from(*y)) % U512::from(*modulus);
+
+        Some(StackUint::from(result))
+    }
+
+    /// Raise a `StackUint` value to the given exponent and return the result modulo the given modulus.
+    ///
+    /// Returns `None` if the modulus is zero.
+    pub fn modexp(
+        base: &StackUint,
+        exponent: &StackUint,
+        modulus: &StackUint,
+    ) -> Option<StackUint> {
+        if modulus.is_zero() {
+            return None;
+        }
+
+        let modulus = U512::from(*modulus);
+
+        // Square and multiply, keeping both operands below the modulus so that products fit in 512 bits.
+        let mut result = U512::one() % modulus;
+        let mut base = U512::from(*base) % modulus;
+        for bit in 0..exponent.bits() {
+            if exponent.bit(bit) {
+                result = (result * base) % modulus;
+            }
+            base = (base * base) % modulus;
+        }
+
+        Some(StackUint::from(result))
+    }
+
     // function to convert to usize no panic, return none if overflow
     pub fn as_usize_safe(&self) -> Option<usize> {
         // Check length
@@ -3,13 +3,22 @@ use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
 use crate::constructive::entry::entry::entry::Entry;
 use crate::constructive::entry::entry_fees::entry_fees::EntryFees;
 use crate::inscriptive::archival_manager::errors::construction_error::ArchivalConstructionError;
-use crate::inscriptive::archival_manager::errors::insert_error::ArchivalManagerInsertBatchRecordError;
+use crate::executive::vm::program_execution::exec_event::ExecEvent;
+use crate::executive::vm::program_execution::exec_receipt::ExecReceipt;
+use crate::inscriptive::archival_manager::errors::insert_error::{
+    ArchivalManagerInsertBatchRecordError, ArchivalManagerInsertEventsError,
+    ArchivalManagerInsertReceiptError, ArchivalManagerInsertStateRootError,
+};
+use crate::inscriptive::memory_budget::memory_budget::{memory_allowance, record_memory_usage};
+use crate::inscriptive::memory_budget::memory_footprint::{BudgetedManager, MemoryFootprint};
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
 use bitcoin::hashes::Hash;
 use bitcoin::OutPoint;
 use bitcoin::Txid;
 use serde_json::{Map, Value};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -25,11 +34,36 @@ pub type BatchTimestamp = u64;
 /// Type alias for the entry id.
 pub type EntryId = [u8; 32];
 
+/// Name of the tree holding execution receipts, keyed by call sighash.
+const RECEIPTS_TREE_NAME: &[u8] = b"receipts";
+
+/// Name of the tree holding contract events, keyed by batch height and event index.
+const EVENTS_TREE_NAME: &[u8] = b"events";
+
+/// Name of the tree indexing contract events by contract id, batch height and event index.
+const EVENT_CONTRACTS_TREE_NAME: &[u8] = b"event_contracts";
+
+/// Name of the tree indexing contract events by contract id, topic, batch height and event index.
+const EVENT_TOPICS_TREE_NAME: &[u8] = b"event_topics";
+
+/// Name of the tree journaling the account balances state root each batch resulted in, keyed by
+/// batch height.
+const STATE_ROOTS_TREE_NAME: &[u8] = b"state_roots";
+
 /// Local storage manager for `BatchRecord` for nodes that run in archival mode.
+///
+/// The in-memory records are a cache over the on-disk ones: when the memory budget is exceeded the
+/// oldest records are evicted from memory and read back from disk on demand.
 pub struct ArchivalManager {
     // In-memory batch records keyed by batch height.
     in_memory_records: HashMap<BatchHeight, BatchRecord>,
 
+    // Serialized sizes of the in-memory batch records, in ascending batch height order.
+    in_memory_record_bytes: BTreeMap<BatchHeight, u64>,
+
+    // Batch heights whose records were evicted from memory and live on disk only.
+    evicted_heights: BTreeSet<BatchHeight>,
+
     // On-disk batch records.
     in_db_records: sled::Db,
 }
@@ -64,10 +98,16 @@ impl ArchivalManager {
     pub fn new(chain: Chain) -> Result<ARCHIVAL_MANAGER, ArchivalConstructionError> {
         // 1 Open the archival manager db.
         let db_path = format!("storage/{}/archival_manager", chain.to_string());
-        let in_db_records = sled::open(&db_path).map_err(ArchivalConstructionError::DBOpenError)?;
-
-        // 2 Initialize the in-memory map of loaded records.
-        let mut loaded: HashMap<BatchHeight, BatchRecord> = HashMap::new();
+        let in_db_records =
+            open_db(chain, &db_path).map_err(ArchivalConstructionError::DBOpenError)?;
+
+        // 2 Construct the archival manager with an empty cache.
+        let mut manager = ArchivalManager {
+            in_memory_records: HashMap::new(),
+            in_memory_record_bytes: BTreeMap::new(),
+            evicted_heights: BTreeSet::new(),
+            in_db_records: in_db_records.clone(),
+        };
 
         // 3 Iterate all key-value pairs in the db.
         for item in in_db_records.iter().filter_map(|r| r.ok()) {
@@ -98,30 +138,77 @@ impl ArchivalManager {
                 });
             }
 
-            // 3.6 Insert the record keyed by batch height (reject duplicate heights in db).
-            match loaded.entry(record.batch_height) {
-                std::collections::hash_map::Entry::Vacant(slot) => {
-                    slot.insert(record);
-                }
-                std::collections::hash_map::Entry::Occupied(_) => {
-                    return Err(ArchivalConstructionError::CorruptRecord(height));
-                }
+            // 3.6 Reject duplicate heights in db.
+            if manager.contains_batch_height(record.batch_height) {
+                return Err(ArchivalConstructionError::CorruptRecord(height));
             }
-        }
 
-        // 4 Construct the archival manager.
-        let manager = ArchivalManager {
-            in_memory_records: loaded,
-            in_db_records,
-        };
+            // 3.7 Cache the record, evicting older ones if the memory budget is exceeded.
+            manager.cache_record(record, v.len() as u64);
+        }
 
-        // 5 Guard the archival manager.
+        // 4 Guard the archival manager.
         let manager = Arc::new(Mutex::new(manager));
 
-        // 6 Return the guarded archival manager.
+        // 5 Return the guarded archival manager.
         Ok(manager)
     }
 
+    /// Returns whether a batch height is archived, in memory or on disk only.
+    fn contains_batch_height(&self, batch_height: BatchHeight) -> bool {
+        self.in_memory_records.contains_key(&batch_height)
+            || self.evicted_heights.contains(&batch_height)
+    }
+
+    /// Caches a record in memory, then evicts the oldest records until the cache fits its memory allowance.
+    fn cache_record(&mut self, record: BatchRecord, record_bytes: u64) {
+        // 1 Cache the record.
+        self.in_memory_record_bytes
+            .insert(record.batch_height, record_bytes);
+        self.in_memory_records.insert(record.batch_height, record);
+
+        // 2 Evict the oldest records while over the allowance.
+        if let Some(allowance) = memory_allowance(BudgetedManager::ArchivalManager) {
+            while self.memory_footprint() > allowance {
+                let Some((oldest_height, _)) = self.in_memory_record_bytes.pop_first() else {
+                    break;
+                };
+                self.in_memory_records.remove(&oldest_height);
+                self.evicted_heights.insert(oldest_height);
+            }
+        }
+
+        // 3 Record the new memory footprint.
+        record_memory_usage(BudgetedManager::ArchivalManager, self.memory_footprint());
+    }
+
+    /// Returns a record by batch height, reading it back from disk if it was evicted from memory.
+    fn record(&self, batch_height: BatchHeight) -> Option<Cow<'_, BatchRecord>> {
+        // 1 Serve the record from memory if cached.
+        if let Some(record) = self.in_memory_records.get(&batch_height) {
+            return Some(Cow::Borrowed(record));
+        }
+
+        // 2 Otherwise read the evicted record from disk.
+        if !self.evicted_heights.contains(&batch_height) {
+            return None;
+        }
+        let bytes = self
+            .in_db_records
+            .get(batch_height.to_be_bytes())
+            .ok()
+            .flatten()?;
+        BatchRecord::deserialize(bytes.as_ref()).map(Cow::Owned)
+    }
+
+    /// All archived batch heights, ascending (stable scan order).
+    fn batch_heights(&self) -> Vec<BatchHeight> {
+        let mut heights = sorted_batch_heights(&self.in_memory_records);
+        heights.extend(self.evicted_heights.iter().copied());
+        heights.sort_unstable();
+        heights
+    }
+
     /// Inserts a new `BatchRecord`. Returns an error if that batch height already exists.
     pub fn insert_batch_record(
         &mut self,
@@ -131,7 +218,7 @@ impl ArchivalManager {
         let height = record.batch_height;
 
         // 2 Reject duplicate batch height (append-only).
-        if self.in_memory_records.contains_key(&height) {
+        if self.contains_batch_height(height) {
             return Err(ArchivalManagerInsertBatchRecordError::DuplicateBatchHeight(
                 height,
             ));
@@ -143,12 +230,13 @@ impl ArchivalManager {
             .ok_or(ArchivalManagerInsertBatchRecordError::SerializeFailed)?;
 
         // 4 Insert into the db under the 8-byte height key.
+        let bytes_len = bytes.len() as u64;
         self.in_db_records
             .insert(height.to_be_bytes(), bytes)
             .map_err(|e| ArchivalManagerInsertBatchRecordError::DbError(e.to_string()))?;
 
-        // 5 Insert the in-memory record keyed by batch height.
-        self.in_memory_records.insert(height, record);
+        // 5 Cache the record, evicting older ones if the memory budget is exceeded.
+        self.cache_record(record, bytes_len);
 
         // 6 Return success.
         Ok(())
@@ -157,7 +245,7 @@ impl ArchivalManager {
     /// Returns the full `BatchRecord` for a batch height, if present.
     pub fn batch_record_by_height(&self, batch_height: u64) -> Option<BatchRecord> {
         // 1 Look up the batch record by height.
-        self.in_memory_records.get(&batch_height).cloned()
+        self.record(batch_height).map(Cow::into_owned)
     }
 
     /// JSON for a full batch record (`BatchRecord::json`), resolved by batch height.
@@ -171,11 +259,12 @@ impl ArchivalManager {
 
     /// Returns the full `BatchRecord` for a batch transaction id, if present.
     pub fn batch_record_by_txid(&self, batch_txid: &[u8; 32]) -> Option<BatchRecord> {
-        // 1 Scan in-memory records for the matching batch txid.
-        self.in_memory_records
-            .values()
+        // 1 Scan the records for the matching batch txid.
+        self.batch_heights()
+            .into_iter()
+            .filter_map(|h| self.record(h))
             .find(|r| r.batch_txid == *batch_txid)
-            .cloned()
+            .map(Cow::into_owned)
     }
 
     /// Returns the `BatchContainer` whose signed batch txn first input prevout txid matches the request outpoint txid.
@@ -183,8 +272,9 @@ impl ArchivalManager {
         &self,
         prev_payload_outpoint: &OutPoint,
     ) -> Option<BatchContainer> {
-        self.in_memory_records
-            .values()
+        self.batch_heights()
+            .into_iter()
+            .filter_map(|h| self.record(h))
             .find_map(|record| {
                 record
                     .batch_container
@@ -224,8 +314,10 @@ impl ArchivalManager {
         Option<EntryFees>,
     )> {
         // 1 Walk batches in ascending batch height order.
-        for h in sorted_batch_heights(&self.in_memory_records) {
-            let record = &self.in_memory_records[&h];
+        for h in self.batch_heights() {
+            let Some(record) = self.record(h) else {
+                continue;
+            };
             // 1.1 Walk executed entries within the batch.
             for (entry_idx, (stored_id, entry)) in record.entries.iter().enumerate() {
                 // 1.1.1 Return on first entry id match.
@@ -312,8 +404,10 @@ impl ArchivalManager {
         let mut historical_entry_records = Vec::new();
 
         // 2 Walk batches in ascending batch height order.
-        for h in sorted_batch_heights(&self.in_memory_records) {
-            let record = &self.in_memory_records[&h];
+        for h in self.batch_heights() {
+            let Some(record) = self.record(h) else {
+                continue;
+            };
             // 2.1 Walk executed entries within the batch.
             for (stored_entry_id, entry) in &record.entries {
                 // 2.1.1 Filter entries that belong to the account.
@@ -378,20 +472,226 @@ impl ArchivalManager {
         Value::Object(obj)
     }
 
-    /// Returns in-memory `BatchRecord` references sorted by `batch_height`.
-    pub fn batch_records(&self) -> Vec<&BatchRecord> {
-        sorted_batch_heights(&self.in_memory_records)
+    /// Inserts the `ExecReceipt` of a call, replacing any earlier receipt of the same call.
+    ///
+    /// Receipts live on disk only, in their own tree, so that they are kept out of the batch record
+    /// cache and its memory budget.
+    pub fn insert_receipt(
+        &mut self,
+        receipt: &ExecReceipt,
+    ) -> Result<(), ArchivalManagerInsertReceiptError> {
+        // 1 Serialize the receipt for storage.
+        let bytes = receipt
+            .serialize()
+            .ok_or(ArchivalManagerInsertReceiptError::SerializeFailed)?;
+
+        // 2 Insert into the receipts tree under the call id.
+        self.in_db_records
+            .open_tree(RECEIPTS_TREE_NAME)
+            .and_then(|tree| tree.insert(receipt.call_id, bytes))
+            .map_err(|e| ArchivalManagerInsertReceiptError::DbError(e.to_string()))?;
+
+        // 3 Return success.
+        Ok(())
+    }
+
+    /// Returns the `ExecReceipt` of a call by its sighash, if present.
+    pub fn receipt_by_call_id(&self, call_id: &[u8; 32]) -> Option<ExecReceipt> {
+        // 1 Read the receipt bytes from the receipts tree.
+        let bytes = self
+            .in_db_records
+            .open_tree(RECEIPTS_TREE_NAME)
+            .ok()?
+            .get(call_id)
+            .ok()
+            .flatten()?;
+
+        // 2 Deserialize the receipt.
+        ExecReceipt::deserialize(bytes.as_ref())
+    }
+
+    /// Inserts the `ExecEvent`s emitted in a batch, replacing any earlier events of the same batch.
+    ///
+    /// Events live on disk only, in their own tree, and are indexed by contract and by contract and
+    /// topic so that filtered queries scan only the matching events.
+    pub fn insert_events(
+        &mut self,
+        batch_height: BatchHeight,
+        events: &[ExecEvent],
+    ) -> Result<(), ArchivalManagerInsertEventsError> {
+        // 1 Open the events tree and its index trees.
+        let open_tree = |name: &[u8]| {
+            self.in_db_records
+                .open_tree(name)
+                .map_err(|e| ArchivalManagerInsertEventsError::DbError(e.to_string()))
+        };
+        let events_tree = open_tree(EVENTS_TREE_NAME)?;
+        let contracts_tree = open_tree(EVENT_CONTRACTS_TREE_NAME)?;
+        let topics_tree = open_tree(EVENT_TOPICS_TREE_NAME)?;
+
+        // 2 Remove the earlier events of the batch along with their index keys.
+        for (key, value) in events_tree
+            .scan_prefix(batch_height.to_be_bytes())
+            .filter_map(|r| r.ok())
+        {
+            if let Some(event) = ExecEvent::deserialize(value.as_ref()) {
+                let position = &key[..];
+                let _ = contracts_tree.remove(event_contract_key(&event.contract_id, position));
+                let _ =
+                    topics_tree.remove(event_topic_key(&event.contract_id, &event.topic, position));
+            }
+            events_tree
+                .remove(key)
+                .map_err(|e| ArchivalManagerInsertEventsError::DbError(e.to_string()))?;
+        }
+
+        // 3 Insert each event under its position, and index it.
+        for (index, event) in events.iter().enumerate() {
+            // 3.1 Serialize the event for storage.
+            let bytes = event
+                .serialize()
+                .ok_or(ArchivalManagerInsertEventsError::SerializeFailed)?;
+
+            // 3.2 The position is the batch height followed by the event index.
+            let mut position = batch_height.to_be_bytes().to_vec();
+            position.extend_from_slice(&(index as u32).to_be_bytes());
+
+            // 3.3 Insert the event and its index keys.
+            events_tree
+                .insert(&position, bytes)
+                .and_then(|_| {
+                    contracts_tree.insert(
+                        event_contract_key(&event.contract_id, &position),
+                        Vec::<u8>::new(),
+                    )
+                })
+                .and_then(|_| {
+                    topics_tree.insert(
+                        event_topic_key(&event.contract_id, &event.topic, &position),
+                        Vec::<u8>::new(),
+                    )
+                })
+                .map_err(|e| ArchivalManagerInsertEventsError::DbError(e.to_string()))?;
+        }
+
+        // 4 Return success.
+        Ok(())
+    }
+
+    /// Returns the `ExecEvent`s emitted by a contract between two batch heights, inclusive, with
+    /// their batch heights, in order. Only the events of the given topic are returned, if any.
+    pub fn get_events(
+        &self,
+        contract_id: [u8; 32],
+        topic: Option<&[u8]>,
+        from_height: BatchHeight,
+        to_height: BatchHeight,
+    ) -> Vec<(BatchHeight, ExecEvent)> {
+        // 1 An inverted range is empty.
+        if from_height > to_height {
+            return Vec::new();
+        }
+
+        // 2 Open the events tree and the index tree to scan.
+        let (Ok(events_tree), Ok(index_tree)) = (
+            self.in_db_records.open_tree(EVENTS_TREE_NAME),
+            self.in_db_records.open_tree(match topic {
+                Some(_) => EVENT_TOPICS_TREE_NAME,
+                None => EVENT_CONTRACTS_TREE_NAME,
+            }),
+        ) else {
+            return Vec::new();
+        };
+
+        // 3 Bound the scan from the first event of the first batch to the last event of the last.
+        let index_key = |height: BatchHeight, index: u32| {
+            let mut position = height.to_be_bytes().to_vec();
+            position.extend_from_slice(&index.to_be_bytes());
+            match topic {
+                Some(topic) => event_topic_key(&contract_id, topic, &position),
+                None => event_contract_key(&contract_id, &position),
+            }
+        };
+        let range = index_key(from_height, 0)..=index_key(to_height, u32::MAX);
+
+        // 4 Read back each indexed event by its position, the last 12 bytes of the index key.
+        index_tree
+            .range(range)
+            .filter_map(|r| r.ok())
+            .filter_map(|(key, _)| {
+                let position = &key[key.len() - 12..];
+                let height = u64::from_be_bytes(position[..8].try_into().ok()?);
+                let bytes = events_tree.get(position).ok().flatten()?;
+                Some((height, ExecEvent::deserialize(bytes.as_ref())?))
+            })
+            .collect()
+    }
+
+    /// Journals the account balances state root a batch resulted in, replacing any earlier one of
+    /// the same batch.
+    pub fn insert_state_root(
+        &mut self,
+        batch_height: BatchHeight,
+        state_root: [u8; 32],
+    ) -> Result<(), ArchivalManagerInsertStateRootError> {
+        self.in_db_records
+            .open_tree(STATE_ROOTS_TREE_NAME)
+            .and_then(|tree| tree.insert(batch_height.to_be_bytes(), state_root.to_vec()))
+            .map(|_| ())
+            .map_err(|e| ArchivalManagerInsertStateRootError::DbError(e.to_string()))
+    }
+
+    /// Returns the journaled account balances state root a batch resulted in, if present.
+    pub fn state_root_by_height(&self, batch_height: BatchHeight) -> Option<[u8; 32]> {
+        self.in_db_records
+            .open_tree(STATE_ROOTS_TREE_NAME)
+            .ok()?
+            .get(batch_height.to_be_bytes())
+            .ok()
+            .flatten()?
+            .as_ref()
+            .try_into()
+            .ok()
+    }
+
+    /// Returns all `BatchRecord`s sorted by `batch_height`, including the ones evicted from memory.
+    pub fn batch_records(&self) -> Vec<BatchRecord> {
+        self.batch_heights()
             .into_iter()
-            .filter_map(|h| self.in_memory_records.get(&h))
+            .filter_map(|h| self.record(h))
+            .map(Cow::into_owned)
             .collect()
     }
 }
 
+impl MemoryFootprint for ArchivalManager {
+    fn memory_footprint(&self) -> u64 {
+        self.in_memory_record_bytes.values().sum()
+    }
+}
+
+/// Returns the event contract index key: the contract id followed by the event position.
+fn event_contract_key(contract_id: &[u8; 32], position: &[u8]) -> Vec<u8> {
+    let mut key = contract_id.to_vec();
+    key.extend_from_slice(position);
+    key
+}
+
+/// Returns the event topic index key: the contract id, the length-prefixed topic, and the event
+/// position.
+fn event_topic_key(contract_id: &[u8; 32], topic: &[u8], position: &[u8]) -> Vec<u8> {
+    let mut key = contract_id.to_vec();
+    key.push(topic.len() as u8);
+    key.extend_from_slice(topic);
+    key.extend_from_slice(position);
+    key
+}
+
 /// Erases the archival manager database directory for the chain.
 pub fn erase_archival_manager(chain: Chain) {
     // 1 Resolve the archival manager db path.
     let path = format!("storage/{}/archival_manager", chain.to_string());
 
     // 2 Remove the directory tree.
-    let _ = std::fs::remove_dir_all(path);
+    erase_db(chain, &path);
 }
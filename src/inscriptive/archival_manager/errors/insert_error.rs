@@ -5,3 +5,23 @@ pub enum ArchivalManagerInsertBatchRecordError {
     SerializeFailed,
     DbError(String),
 }
+
+/// Errors associated with inserting an `ExecReceipt` into archival storage.
+#[derive(Debug, Clone)]
+pub enum ArchivalManagerInsertReceiptError {
+    SerializeFailed,
+    DbError(String),
+}
+
+/// Errors associated with inserting the `ExecEvent`s of a batch into archival storage.
+#[derive(Debug, Clone)]
+pub enum ArchivalManagerInsertEventsError {
+    SerializeFailed,
+    DbError(String),
+}
+
+/// Errors associated with journaling the state root of a batch into archival storage.
+#[derive(Debug, Clone)]
+pub enum ArchivalManagerInsertStateRootError {
+    DbError(String),
+}
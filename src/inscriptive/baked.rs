@@ -24,6 +24,10 @@ pub const SIGNET_GENESIS_PAYLOAD_TX_ID: [u8; 32] = [
 pub const SIGNET_GENESIS_PAYLOAD_VOUT: u32 = 0;
 // satoshi amount of the genesis payload.
 pub const SIGNET_GENESIS_PAYLOAD_AMOUNT: u64 = 20_000;
+// Federation coordinator public keys co-signing applied deltas (empty for a single coordinator).
+pub const SIGNET_FEDERATION_COORDINATOR_KEYS: &[[u8; 32]] = &[];
+// Number of coordinator co-signatures required for a delta to be accepted.
+pub const SIGNET_FEDERATION_THRESHOLD: usize = 0;
 
 /// Mainnet parameters.
 ///
@@ -45,3 +49,7 @@ pub const MAINNET_GENESIS_PAYLOAD_TX_ID: [u8; 32] = [
 pub const MAINNET_GENESIS_PAYLOAD_VOUT: u32 = 0;
 // satoshi amount of the genesis payload.
 pub const MAINNET_GENESIS_PAYLOAD_AMOUNT: u64 = 0;
+// Federation coordinator public keys co-signing applied deltas (empty for a single coordinator).
+pub const MAINNET_FEDERATION_COORDINATOR_KEYS: &[[u8; 32]] = &[];
+// Number of coordinator co-signatures required for a delta to be accepted.
+pub const MAINNET_FEDERATION_THRESHOLD: usize = 0;
@@ -7,7 +7,7 @@ use crate::inscriptive::coin_manager::errors::apply_changes_errors::{
 };
 use crate::inscriptive::coin_manager::errors::balance_update_errors::{
     CMAccountBalanceDownError, CMAccountBalanceUpError, CMContractBalanceDownError,
-    CMContractBalanceUpError,
+    CMContractBalanceUpError, CMContractTransferError,
 };
 use crate::inscriptive::coin_manager::errors::construction_errors::{
     CMConstructionAccountError, CMConstructionContractError, CMConstructionError,
@@ -22,7 +22,15 @@ use crate::inscriptive::coin_manager::errors::shadow_update_errors::{
     CMAccountShadowAllocsSumDownError, CMAccountShadowAllocsSumUpError, CMShadowDownAllError,
     CMShadowDownError, CMShadowUpAllError, CMShadowUpError,
 };
+use crate::inscriptive::coin_manager::transfer_destination::CMTransferDestination;
+#[cfg(feature = "fault-injection")]
+use crate::inscriptive::fault_injection::{fault_injection::fault_point, fault_point::FaultPoint};
+use crate::inscriptive::memory_budget::memory_budget::record_memory_usage;
+use crate::inscriptive::memory_budget::memory_footprint::{BudgetedManager, MemoryFootprint};
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::merkle::{MerkleProofStep, MerkleTree};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -34,6 +42,9 @@ type AccountKey = [u8; 32];
 /// Contract ID.
 type ContractId = [u8; 32];
 
+/// Account key, permanent balance and Merkle inclusion proof of an account balance leaf.
+pub type AccountBalanceWithProof = (AccountKey, u64, Vec<MerkleProofStep>);
+
 /// Sati-satoshi amount.
 type SatiSatoshiAmount = u128;
 
@@ -67,6 +78,12 @@ pub struct CoinManager {
 
     // Backup of state differences in case of rollback.
     backup_of_delta: CMDelta,
+
+    // Account keys in the leaf order of the account balances tree.
+    account_balance_keys: Vec<AccountKey>,
+
+    // Merkle tree over the permanent account balances, kept up to date as changes are applied.
+    account_balances_tree: MerkleTree,
 }
 
 /// Guarded 'CoinManager'.
@@ -77,7 +94,7 @@ impl CoinManager {
     pub fn new(chain: Chain) -> Result<COIN_MANAGER, CMConstructionError> {
         // 1 Open the accounts db.
         let accounts_db_path = format!("storage/{}/coins/accounts", chain.to_string());
-        let accounts_db = sled::open(accounts_db_path).map_err(|e| {
+        let accounts_db = open_db(chain, &accounts_db_path).map_err(|e| {
             CMConstructionError::AccountConstructionError(CMConstructionAccountError::DBOpenError(
                 e,
             ))
@@ -85,7 +102,7 @@ impl CoinManager {
 
         // 2 Open the contracts db.
         let contracts_db_path = format!("storage/{}/coins/contracts", chain.to_string());
-        let contracts_db = sled::open(contracts_db_path).map_err(|e| {
+        let contracts_db = open_db(chain, &contracts_db_path).map_err(|e| {
             CMConstructionError::ContractConstructionError(
                 CMConstructionContractError::DBOpenError(e),
             )
@@ -320,19 +337,27 @@ impl CoinManager {
         }
 
         // 6 Construct the coin holder.
-        let coin_holder = CoinManager {
+        let mut coin_holder = CoinManager {
             in_memory_accounts: account_bodies,
             in_memory_contracts: contract_bodies,
             on_disk_accounts: accounts_db,
             on_disk_contracts: contracts_db,
             delta: CMDelta::fresh_new(),
             backup_of_delta: CMDelta::fresh_new(),
+            account_balance_keys: Vec::new(),
+            account_balances_tree: MerkleTree::default(),
         };
 
-        // 7 Guard the coin holder.
+        // 6.1 Build the account balances tree.
+        coin_holder.rebuild_account_balances_tree();
+
+        // 7 Record the memory footprint of the coin holder.
+        record_memory_usage(BudgetedManager::CoinManager, coin_holder.memory_footprint());
+
+        // 8 Guard the coin holder.
         let guarded_coin_holder = Arc::new(Mutex::new(coin_holder));
 
-        // 8 Return the guarded coin holder.
+        // 9 Return the guarded coin holder.
         Ok(guarded_coin_holder)
     }
 
@@ -377,6 +402,22 @@ impl CoinManager {
         self.backup_delta();
     }
 
+    /// Returns the epheremal changes in the delta, canonically encoded.
+    pub fn delta_bytes(&self) -> Vec<u8> {
+        self.delta.canonical_bytes()
+    }
+
+    /// Returns a copy of the delta and its backup, so that an execution can be undone without
+    /// touching the backup.
+    pub fn delta_checkpoint(&self) -> (CMDelta, CMDelta) {
+        (self.delta.clone(), self.backup_of_delta.clone())
+    }
+
+    /// Restores the delta and its backup from a checkpoint.
+    pub fn restore_delta_checkpoint(&mut self, checkpoint: (CMDelta, CMDelta)) {
+        (self.delta, self.backup_of_delta) = checkpoint;
+    }
+
     /// Returns the account body for a given account key.
     pub fn get_account_body(&self, account_key: AccountKey) -> Option<CMAccountBody> {
         self.in_memory_accounts.get(&account_key).cloned()
@@ -944,6 +985,68 @@ impl CoinManager {
         Ok(())
     }
 
+    /// Transfers from a contract's balance to an account or another contract.
+    ///
+    /// The transfer is atomic: the destination is checked before the contract's balance is
+    /// decreased, so that it either moves the whole amount or changes nothing.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn contract_transfer(
+        &mut self,
+        contract_id: [u8; 32],
+        destination: CMTransferDestination,
+        value_in_satoshis: u64,
+    ) -> Result<(), CMContractTransferError> {
+        // 1 Get the destination's existing balance.
+        let destination_balance_in_satoshis: u64 = match destination {
+            CMTransferDestination::Account(account_key) => {
+                self.get_account_balance(account_key).ok_or(
+                    CMContractTransferError::UnableToGetDestinationAccountBalance(account_key),
+                )?
+            }
+            CMTransferDestination::Contract(destination_contract_id) => {
+                // 1.1 A contract cannot transfer to itself.
+                if destination_contract_id == contract_id {
+                    return Err(CMContractTransferError::SelfTransfer(contract_id));
+                }
+
+                self.get_contract_balance(destination_contract_id).ok_or(
+                    CMContractTransferError::UnableToGetDestinationContractBalance(
+                        destination_contract_id,
+                    ),
+                )?
+            }
+        };
+
+        // 2 Check if the increase would overflow the destination's balance.
+        if destination_balance_in_satoshis
+            .checked_add(value_in_satoshis)
+            .is_none()
+        {
+            return Err(CMContractTransferError::DestinationBalanceWouldOverflow(
+                destination_balance_in_satoshis,
+                value_in_satoshis,
+            ));
+        }
+
+        // 3 Decrease the contract's balance.
+        self.contract_balance_down(contract_id, value_in_satoshis)
+            .map_err(CMContractTransferError::ContractBalanceDownError)?;
+
+        // 4 Increase the destination's balance.
+        match destination {
+            CMTransferDestination::Account(account_key) => self
+                .account_balance_up(account_key, value_in_satoshis)
+                .map_err(CMContractTransferError::AccountBalanceUpError)?,
+            CMTransferDestination::Contract(destination_contract_id) => self
+                .contract_balance_up(destination_contract_id, value_in_satoshis)
+                .map_err(CMContractTransferError::ContractBalanceUpError)?,
+        }
+
+        // 5 Return the result.
+        Ok(())
+    }
+
     /// Allocates a new account in the contract's shadow space.
     ///
     /// NOTE: These changes are saved with the use of the `apply_changes` function.
@@ -1455,6 +1558,139 @@ impl CoinManager {
         Ok(num_affected_accounts)
     }
 
+    /// Builds the account balances tree again from the permanent account balances.
+    ///
+    /// NOTE: Only permanent states are committed; epheremal changes in the delta are excluded.
+    fn rebuild_account_balances_tree(&mut self) {
+        // 1 Collect the account keys and balances.
+        let mut accounts: Vec<(AccountKey, u64)> = self
+            .in_memory_accounts
+            .iter()
+            .map(|(account_key, account_body)| (*account_key, account_body.balance))
+            .collect();
+
+        // 2 Sort by account key so that the leaf order is deterministic.
+        accounts.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // 3 Hash each account into its leaf.
+        let leaves: Vec<[u8; 32]> = accounts
+            .iter()
+            .map(|(account_key, balance)| account_balance_leaf(*account_key, *balance))
+            .collect();
+
+        // 4 Keep the account keys in leaf order along with the tree.
+        self.account_balance_keys = accounts
+            .into_iter()
+            .map(|(account_key, _)| account_key)
+            .collect();
+        self.account_balances_tree = MerkleTree::new(leaves);
+    }
+
+    /// Brings the account balances tree up to date with the changes just applied.
+    ///
+    /// Newly registered accounts shift the leaf order, so the tree is built again; otherwise only
+    /// the branches of the accounts whose balances changed are hashed again.
+    fn update_account_balances_tree(&mut self) {
+        // 1 Build the tree again if new accounts were registered.
+        if !self.delta.new_accounts_to_register.is_empty() {
+            self.rebuild_account_balances_tree();
+            return;
+        }
+
+        // 2 Otherwise update the leaves of the accounts whose balances changed.
+        for account_key in self.delta.updated_account_balances.keys() {
+            // 2.1 Get the permanent account balance.
+            let Some(account_body) = self.in_memory_accounts.get(account_key) else {
+                continue;
+            };
+
+            // 2.2 Locate the account leaf.
+            let Ok(index) = self.account_balance_keys.binary_search(account_key) else {
+                continue;
+            };
+
+            // 2.3 Update the leaf.
+            self.account_balances_tree.update_leaf(
+                index,
+                account_balance_leaf(*account_key, account_body.balance),
+            );
+        }
+    }
+
+    /// Returns the Merkle root committing to all permanent account balances.
+    pub fn account_balances_state_root(&self) -> [u8; 32] {
+        self.account_balances_tree.root()
+    }
+
+    /// Returns the permanent balance of an account along with its Merkle inclusion proof
+    /// against the account balances state root.
+    pub fn account_balance_proof(
+        &self,
+        account_key: AccountKey,
+    ) -> Option<(u64, Vec<MerkleProofStep>)> {
+        // 1 Get the permanent account balance.
+        let balance = self.in_memory_accounts.get(&account_key)?.balance;
+
+        // 2 Locate the account leaf.
+        let index = self.account_balance_keys.binary_search(&account_key).ok()?;
+
+        // 3 Build the proof.
+        let proof = self.account_balances_tree.proof(index)?;
+
+        // 4 Return the balance and the proof.
+        Some((balance, proof))
+    }
+
+    /// Returns the account key and permanent balance of the account at the given leaf index, along
+    /// with its Merkle inclusion proof against the account balances state root.
+    fn account_balance_with_proof_at(&self, index: usize) -> Option<AccountBalanceWithProof> {
+        // 1 Get the account key at the leaf index.
+        let account_key = *self.account_balance_keys.get(index)?;
+
+        // 2 Get the permanent account balance.
+        let balance = self.in_memory_accounts.get(&account_key)?.balance;
+
+        // 3 Build the proof.
+        let proof = self.account_balances_tree.proof(index)?;
+
+        // 4 Return the account key, the balance and the proof.
+        Some((account_key, balance, proof))
+    }
+
+    /// Proves that an account is not in the account balances tree by returning the leaves
+    /// immediately to its left and right in the sorted leaf order, each with its inclusion proof.
+    ///
+    /// A missing left (right) neighbor means the account would sort before (after) every leaf.
+    /// Returns `None` if the account is registered.
+    pub fn account_balance_non_membership_proof(
+        &self,
+        account_key: AccountKey,
+    ) -> Option<(
+        Option<AccountBalanceWithProof>,
+        Option<AccountBalanceWithProof>,
+    )> {
+        // 1 Locate the position the account would take in the leaf order.
+        let index = match self.account_balance_keys.binary_search(&account_key) {
+            Ok(_) => return None,
+            Err(index) => index,
+        };
+
+        // 2 Get the left neighbor, if any.
+        let left_neighbor = match index {
+            0 => None,
+            _ => Some(self.account_balance_with_proof_at(index - 1)?),
+        };
+
+        // 3 Get the right neighbor, if any.
+        let right_neighbor = match index < self.account_balance_keys.len() {
+            true => Some(self.account_balance_with_proof_at(index)?),
+            false => None,
+        };
+
+        // 4 Return the neighbors.
+        Some((left_neighbor, right_neighbor))
+    }
+
     /// Returns the list of accounts whose coin balances or allocations are changed in one way or another.
     pub fn get_coingap_accounts_list(&self) -> Vec<AccountKey> {
         self.delta.coingap_accounts_list()
@@ -1534,6 +1770,9 @@ impl CoinManager {
             }
         }
 
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::CoinManagerAccountsRegistered);
+
         // 2 Register new contracts in-memory and on-disk.
         for (contract_id, initial_contract_balance) in self.delta.new_contracts_to_register.iter() {
             // 2.1 A fresh new contract has a zero allocs sum value.
@@ -1979,7 +2218,13 @@ impl CoinManager {
             }
         }
 
-        // 9 Return the result.
+        // 9 Bring the account balances tree up to date.
+        self.update_account_balances_tree();
+
+        // 10 Record the new memory footprint.
+        record_memory_usage(BudgetedManager::CoinManager, self.memory_footprint());
+
+        // 11 Return the result.
         Ok(())
     }
 
@@ -2052,17 +2297,48 @@ impl CoinManager {
     }
 }
 
+/// Returns the state root leaf of an account balance.
+pub fn account_balance_leaf(account_key: AccountKey, balance: u64) -> [u8; 32] {
+    let mut preimage = Vec::<u8>::with_capacity(40);
+    preimage.extend(account_key);
+    preimage.extend(balance.to_be_bytes());
+    preimage.hash(Some(HashTag::AccountBalanceLeaf))
+}
+
+impl MemoryFootprint for CoinManager {
+    fn memory_footprint(&self) -> u64 {
+        // 1 Account bodies.
+        let accounts_bytes =
+            self.in_memory_accounts.len() * (size_of::<AccountKey>() + size_of::<CMAccountBody>());
+
+        // 2 Contract bodies, including their shadow space allocations.
+        let contracts_bytes = self
+            .in_memory_contracts
+            .values()
+            .map(|contract_body| {
+                size_of::<ContractId>()
+                    + size_of::<CMContractBody>()
+                    + contract_body.shadow_space.allocs.len()
+                        * (size_of::<AccountKey>() + size_of::<SatiSatoshiAmount>())
+            })
+            .sum::<usize>();
+
+        // 3 Return the total.
+        (accounts_bytes + contracts_bytes) as u64
+    }
+}
+
 /// Erases the coin manager by db paths.
 pub fn erase_coin_manager(chain: Chain) {
     // Accounts db path.
     let accounts_db_path = format!("storage/{}/coins/accounts", chain.to_string());
 
     // Erase the accounts db path.
-    let _ = std::fs::remove_dir_all(accounts_db_path);
+    erase_db(chain, &accounts_db_path);
 
     // Contracts db path.
     let contracts_db_path = format!("storage/{}/coins/contracts", chain.to_string());
 
     // Erase the contracts db path.
-    let _ = std::fs::remove_dir_all(contracts_db_path);
+    erase_db(chain, &contracts_db_path);
 }
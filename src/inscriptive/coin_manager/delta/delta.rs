@@ -1,5 +1,5 @@
 use crate::inscriptive::coin_manager::bodies::contract_body::shadow_space::shadow_space::ShadowSpace;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Account key.
 #[allow(non_camel_case_types)]
@@ -76,6 +76,52 @@ impl CMDelta {
         self.updated_shadow_spaces.clear();
     }
 
+    /// Returns the delta encoded in a canonical order, so that two equal deltas encode to the same
+    /// bytes regardless of their hash map iteration order.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        // 1 Sort the account related values by account key.
+        let accounts = (
+            self.new_accounts_to_register
+                .iter()
+                .collect::<BTreeMap<_, _>>(),
+            self.updated_account_balances
+                .iter()
+                .collect::<BTreeMap<_, _>>(),
+            self.updated_global_shadow_allocs_sums
+                .iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+
+        // 2 Sort the contract related values by contract id, and the shadow allocs by account key.
+        let contracts = (
+            self.new_contracts_to_register
+                .iter()
+                .collect::<BTreeMap<_, _>>(),
+            self.allocs_list.iter().collect::<BTreeMap<_, _>>(),
+            self.deallocs_list.iter().collect::<BTreeMap<_, _>>(),
+            self.updated_contract_balances
+                .iter()
+                .collect::<BTreeMap<_, _>>(),
+            self.updated_shadow_spaces
+                .iter()
+                .map(|(contract_id, shadow_space)| {
+                    (
+                        *contract_id,
+                        (
+                            shadow_space.allocs_sum,
+                            shadow_space.allocs.iter().collect::<BTreeMap<_, _>>(),
+                            shadow_space.shadow_up_all_down_alls,
+                        ),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>(),
+        );
+
+        // 3 Encode the delta.
+        bincode::serde::encode_to_vec((accounts, contracts), bincode::config::standard())
+            .unwrap_or_default()
+    }
+
     /// ACCOUNT RELATED METHODS ///
     /// ------------------------------------------------------------
 
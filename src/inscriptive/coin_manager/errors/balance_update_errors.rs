@@ -37,3 +37,15 @@ pub enum CMContractBalanceDownError {
     UnableToGetContractAllocsSum(CONTRACT_ID),
     ContractBalanceWouldGoBelowAllocsSum(CONTRACT_ID, SATOSHI_AMOUNT, SATOSHI_AMOUNT),
 }
+
+/// Errors associated with transferring from a contract's balance to an account or contract.
+#[derive(Debug, Clone)]
+pub enum CMContractTransferError {
+    SelfTransfer(CONTRACT_ID),
+    UnableToGetDestinationAccountBalance(ACCOUNT_KEY),
+    UnableToGetDestinationContractBalance(CONTRACT_ID),
+    DestinationBalanceWouldOverflow(SATOSHI_AMOUNT, SATOSHI_AMOUNT),
+    ContractBalanceDownError(CMContractBalanceDownError),
+    AccountBalanceUpError(CMAccountBalanceUpError),
+    ContractBalanceUpError(CMContractBalanceUpError),
+}
@@ -2,3 +2,4 @@ pub mod bodies;
 pub mod coin_manager;
 pub mod delta;
 pub mod errors;
+pub mod transfer_destination;
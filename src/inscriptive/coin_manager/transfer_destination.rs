@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// The destination of a transfer out of a contract's balance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CMTransferDestination {
+    /// An account, by its key.
+    Account([u8; 32]),
+    /// A contract, by its id.
+    Contract([u8; 32]),
+}
+
+impl CMTransferDestination {
+    /// Returns the kind byte of the destination (0x00 for an account, 0x01 for a contract).
+    pub fn kind_byte(&self) -> u8 {
+        match self {
+            CMTransferDestination::Account(_) => 0x00,
+            CMTransferDestination::Contract(_) => 0x01,
+        }
+    }
+
+    /// Returns the account key or contract id bytes of the destination.
+    pub fn bytes(&self) -> [u8; 32] {
+        match self {
+            CMTransferDestination::Account(account_key) => *account_key,
+            CMTransferDestination::Contract(contract_id) => *contract_id,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::inscriptive::fault_injection::fault_point::FaultPoint;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Armed faults, by point, with the number of hits left before each fires.
+static ARMED_FAULTS: OnceLock<Mutex<HashMap<FaultPoint, u64>>> = OnceLock::new();
+
+/// Faults fired so far, in order.
+static FIRED_FAULTS: OnceLock<Mutex<Vec<FaultPoint>>> = OnceLock::new();
+
+/// Arms a fault at the given point, firing on its `hit`-th hit from now, `1` being the next one.
+///
+/// A fault fires once and is disarmed, so that the recovery that follows runs clean. Faults are
+/// process-wide: tests arming them should not run alongside other tests hitting the same points.
+pub fn arm_fault(point: FaultPoint, hit: u64) {
+    armed_faults().insert(point, hit.max(1));
+}
+
+/// Disarms every fault.
+pub fn disarm_faults() {
+    armed_faults().clear();
+}
+
+/// Returns the faults fired so far, in order.
+pub fn fired_faults() -> Vec<FaultPoint> {
+    fired_faults_list().clone()
+}
+
+/// Crashes here if a fault is armed at the given point and due.
+///
+/// The crash is a panic: the writes made before the point stay in storage, and the ones after it
+/// are never made, as if the process had died there.
+pub fn fault_point(point: FaultPoint) {
+    // 1 Count the hit against the armed fault, if any.
+    {
+        let mut armed_faults = armed_faults();
+        let hits_left = match armed_faults.get_mut(&point) {
+            Some(hits_left) => hits_left,
+            None => return,
+        };
+        *hits_left -= 1;
+        if *hits_left > 0 {
+            return;
+        }
+        armed_faults.remove(&point);
+    }
+
+    // 2 Record and fire the fault.
+    fired_faults_list().push(point);
+    panic!("Injected fault at {:?}.", point);
+}
+
+/// Returns the locked armed faults.
+fn armed_faults() -> MutexGuard<'static, HashMap<FaultPoint, u64>> {
+    ARMED_FAULTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Returns the locked fired faults.
+fn fired_faults_list() -> MutexGuard<'static, Vec<FaultPoint>> {
+    FIRED_FAULTS
+        .get_or_init(|| Mutex::new(Vec::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
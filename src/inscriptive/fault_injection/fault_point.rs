@@ -0,0 +1,31 @@
+/// A point in the commit path where a fault can be injected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FaultPoint {
+    /// After the start of a batch commit is journaled.
+    CommitBegun,
+    /// After the flame manager changes are applied.
+    FlameManagerApplied,
+    /// After the coin manager changes are applied.
+    CoinManagerApplied,
+    /// Inside the coin manager changes, after the new accounts are written and before the rest.
+    CoinManagerAccountsRegistered,
+    /// After the graveyard changes are applied.
+    GraveyardApplied,
+    /// After the registery changes are applied.
+    RegisteryApplied,
+    /// After the state manager changes are applied.
+    StateManagerApplied,
+    /// After the privileges manager changes are applied.
+    PrivilegesManagerApplied,
+    /// After the sync tips are updated.
+    SyncTipsUpdated,
+    /// After the batch record is archived.
+    BatchRecordArchived,
+    /// After the state root is journaled along with the archived batch record.
+    StateRootJournaled,
+    /// Inside the end of commit journal write, after the state root is written and before the
+    /// pending commit is cleared.
+    EndCommitJournalWrite,
+    /// Inside an undo journal insertion, after the block undo is written and before it is flushed.
+    UndoJournalWrite,
+}
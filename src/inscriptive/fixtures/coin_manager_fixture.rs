@@ -0,0 +1,87 @@
+use crate::inscriptive::coin_manager::coin_manager::{CoinManager, COIN_MANAGER};
+use crate::inscriptive::fixtures::errors::fixture_error::FixtureError;
+use crate::inscriptive::fixtures::fixture_storage::{fixture_namespace, FIXTURE_CHAIN};
+use crate::inscriptive::storage::storage::with_storage_namespace;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// A builder for an in-memory coin manager with accounts and contracts already registered.
+///
+/// ```ignore
+/// let coin_manager = CoinManagerFixture::new()
+///     .with_account(account_key, 10_000)
+///     .with_contract(contract_id, 5_000)
+///     .build()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CoinManagerFixture {
+    // Accounts to register, with their balances.
+    accounts: Vec<(AccountKey, u64)>,
+    // Contracts to register, with their balances.
+    contracts: Vec<(ContractId, u64)>,
+}
+
+impl CoinManagerFixture {
+    /// Constructs an empty coin manager fixture.
+    pub fn new() -> CoinManagerFixture {
+        CoinManagerFixture::default()
+    }
+
+    /// Registers an account with the given balance.
+    pub fn with_account(mut self, account_key: AccountKey, balance: u64) -> CoinManagerFixture {
+        self.accounts.push((account_key, balance));
+        self
+    }
+
+    /// Registers a contract with the given balance.
+    pub fn with_contract(mut self, contract_id: ContractId, balance: u64) -> CoinManagerFixture {
+        self.contracts.push((contract_id, balance));
+        self
+    }
+
+    /// Builds the coin manager under a namespace of its own.
+    pub async fn build(self) -> Result<COIN_MANAGER, FixtureError> {
+        self.build_in(&fixture_namespace()?).await
+    }
+
+    /// Builds the coin manager under the given namespace.
+    pub async fn build_in(self, namespace: &str) -> Result<COIN_MANAGER, FixtureError> {
+        // 1 Construct the coin manager.
+        let coin_manager = with_storage_namespace(namespace, || CoinManager::new(FIXTURE_CHAIN))
+            .map_err(FixtureError::CoinManagerConstructionError)?;
+
+        // 2 Register the accounts and the contracts, and apply the changes.
+        {
+            let mut _coin_manager = coin_manager.lock().await;
+            for (account_key, balance) in self.accounts {
+                _coin_manager
+                    .register_account(account_key, balance)
+                    .map_err(FixtureError::CoinManagerRegisterAccountError)?;
+            }
+            for (contract_id, balance) in self.contracts {
+                _coin_manager
+                    .register_contract(contract_id, balance)
+                    .map_err(FixtureError::CoinManagerRegisterContractError)?;
+            }
+            _coin_manager
+                .apply_changes()
+                .map_err(FixtureError::CoinManagerApplyChangesError)?;
+            _coin_manager.flush_delta();
+        }
+
+        // 3 Return the coin manager.
+        Ok(coin_manager)
+    }
+
+    /// Constructs the coin manager again from what was applied under the given namespace, as on a
+    /// restart.
+    pub fn reopen(namespace: &str) -> Result<COIN_MANAGER, FixtureError> {
+        with_storage_namespace(namespace, || CoinManager::new(FIXTURE_CHAIN))
+            .map_err(FixtureError::CoinManagerConstructionError)
+    }
+}
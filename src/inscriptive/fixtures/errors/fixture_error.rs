@@ -0,0 +1,35 @@
+use crate::executive::executable::method::method_error::MethodConstructionError;
+use crate::executive::executable::program_error::ProgramConstructionError;
+use crate::inscriptive::coin_manager::errors::apply_changes_errors::CMApplyChangesError;
+use crate::inscriptive::coin_manager::errors::construction_errors::CMConstructionError;
+use crate::inscriptive::coin_manager::errors::register_errors::{
+    CMRegisterAccountError, CMRegisterContractError,
+};
+use crate::inscriptive::registery::errors::apply_changes_error::RMApplyChangesError;
+use crate::inscriptive::registery::errors::construction_error::RMConstructionError;
+use crate::inscriptive::registery::errors::register_account_error::RMRegisterAccountError;
+use crate::inscriptive::registery::errors::register_contract_error::RMRegisterContractError;
+use crate::inscriptive::state_manager::errors::apply_changes_error::SMApplyChangesError;
+use crate::inscriptive::state_manager::errors::construction_error::SMConstructionError;
+use crate::inscriptive::state_manager::errors::insert_update_state_error::SMInsertUpdateStateError;
+use crate::inscriptive::state_manager::errors::register_error::SMRegisterContractError;
+
+/// Errors associated with building a ledger fixture.
+#[derive(Debug, Clone)]
+pub enum FixtureError {
+    TestbedKeptOnDisk,
+    MethodConstructionError(MethodConstructionError),
+    ProgramConstructionError(ProgramConstructionError),
+    CoinManagerConstructionError(CMConstructionError),
+    CoinManagerRegisterAccountError(CMRegisterAccountError),
+    CoinManagerRegisterContractError(CMRegisterContractError),
+    CoinManagerApplyChangesError(CMApplyChangesError),
+    RegisteryConstructionError(RMConstructionError),
+    RegisteryRegisterAccountError(RMRegisterAccountError),
+    RegisteryRegisterContractError(RMRegisterContractError),
+    RegisteryApplyChangesError(RMApplyChangesError),
+    StateManagerConstructionError(SMConstructionError),
+    StateManagerRegisterContractError(SMRegisterContractError),
+    StateManagerInsertUpdateStateError(SMInsertUpdateStateError),
+    StateManagerApplyChangesError(SMApplyChangesError),
+}
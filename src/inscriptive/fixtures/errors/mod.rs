@@ -0,0 +1 @@
+pub mod fixture_error;
@@ -0,0 +1,27 @@
+use crate::inscriptive::fixtures::errors::fixture_error::FixtureError;
+use crate::inscriptive::storage::storage::keep_testbed_in_memory;
+use crate::inscriptive::storage::storage_mode::StorageMode;
+use crate::operative::run_args::chain::Chain;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The chain fixtures are built on.
+pub const FIXTURE_CHAIN: Chain = Chain::Testbed;
+
+/// Sequence of the next fixture namespace.
+static NEXT_FIXTURE_NAMESPACE: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a storage namespace no other fixture of the process uses.
+///
+/// Fixtures keep their managers in memory, each under its own namespace, so they can be built
+/// side by side and leave nothing behind. The Testbed chain is kept in memory from the first
+/// fixture on, which fails if the Testbed chain was already opened on disk by the process.
+pub fn fixture_namespace() -> Result<String, FixtureError> {
+    // 1 Make sure the Testbed chain is kept in memory.
+    if keep_testbed_in_memory() != StorageMode::InMemory {
+        return Err(FixtureError::TestbedKeptOnDisk);
+    }
+
+    // 2 Return the next namespace.
+    let sequence = NEXT_FIXTURE_NAMESPACE.fetch_add(1, Ordering::SeqCst);
+    Ok(format!("fixture-{}", sequence))
+}
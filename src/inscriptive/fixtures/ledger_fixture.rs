@@ -0,0 +1,113 @@
+use crate::executive::executable::executable::Executable;
+use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+use crate::inscriptive::fixtures::coin_manager_fixture::CoinManagerFixture;
+use crate::inscriptive::fixtures::errors::fixture_error::FixtureError;
+use crate::inscriptive::fixtures::fixture_storage::fixture_namespace;
+use crate::inscriptive::fixtures::registery_fixture::RegisteryFixture;
+use crate::inscriptive::fixtures::state_manager_fixture::StateManagerFixture;
+use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::state_manager::state_manager::STATE_MANAGER;
+use crate::inscriptive::storage::storage::with_storage_namespace;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// A builder for an in-memory registery, coin manager and state manager that agree with each
+/// other: every account and contract in the registery is also in the coin manager, and every
+/// contract is also in the state manager.
+///
+/// ```ignore
+/// let ledger = LedgerFixture::new()
+///     .with_account(account_key, 10_000)
+///     .with_contract(contract_id, executable, 5_000)
+///     .with_state(contract_id, b"counter".to_vec(), vec![0x01])
+///     .build()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LedgerFixture {
+    coin_manager: CoinManagerFixture,
+    registery: RegisteryFixture,
+    state_manager: StateManagerFixture,
+}
+
+/// The managers built from a `LedgerFixture`.
+pub struct FixtureLedger {
+    pub coin_manager: COIN_MANAGER,
+    pub registery: REGISTERY,
+    pub state_manager: STATE_MANAGER,
+    // Storage namespace the managers are kept under.
+    namespace: String,
+}
+
+impl LedgerFixture {
+    /// Constructs an empty ledger fixture.
+    pub fn new() -> LedgerFixture {
+        LedgerFixture::default()
+    }
+
+    /// Registers an account with the given balance.
+    pub fn with_account(mut self, account_key: AccountKey, balance: u64) -> LedgerFixture {
+        self.coin_manager = self.coin_manager.with_account(account_key, balance);
+        self.registery = self.registery.with_account(account_key);
+        self
+    }
+
+    /// Deploys a contract with the given program and balance.
+    pub fn with_contract(
+        mut self,
+        contract_id: ContractId,
+        executable: Executable,
+        balance: u64,
+    ) -> LedgerFixture {
+        self.coin_manager = self.coin_manager.with_contract(contract_id, balance);
+        self.registery = self.registery.with_contract(contract_id, executable);
+        self.state_manager = self.state_manager.with_contract(contract_id);
+        self
+    }
+
+    /// Inserts a state of a contract deployed with `with_contract`.
+    pub fn with_state(
+        mut self,
+        contract_id: ContractId,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> LedgerFixture {
+        self.state_manager = self.state_manager.with_state(contract_id, key, value);
+        self
+    }
+
+    /// Builds the managers under a namespace of their own.
+    pub async fn build(self) -> Result<FixtureLedger, FixtureError> {
+        // 1 Pick the namespace shared by the managers.
+        let namespace = fixture_namespace()?;
+
+        // 2 Build the managers.
+        let coin_manager = self.coin_manager.build_in(&namespace).await?;
+        let registery = self.registery.build_in(&namespace).await?;
+        let state_manager = self.state_manager.build_in(&namespace).await?;
+
+        // 3 Return the ledger.
+        Ok(FixtureLedger {
+            coin_manager,
+            registery,
+            state_manager,
+            namespace,
+        })
+    }
+}
+
+impl FixtureLedger {
+    /// Returns the storage namespace the managers are kept under.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Constructs another manager alongside the ledger, such as a params manager.
+    pub fn open<T>(&self, f: impl FnOnce() -> T) -> T {
+        with_storage_namespace(&self.namespace, f)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod coin_manager_fixture;
+pub mod errors;
+pub mod fixture_storage;
+pub mod ledger_fixture;
+pub mod program_fixture;
+pub mod registery_fixture;
+pub mod state_manager_fixture;
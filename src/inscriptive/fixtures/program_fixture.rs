@@ -0,0 +1,39 @@
+use crate::constructive::calldata::element_type::CalldataElementType;
+use crate::executive::executable::executable::Executable;
+use crate::executive::executable::method::limits::MIN_METHOD_OPCODE_COUNT;
+use crate::executive::executable::method::method_type::MethodType;
+use crate::executive::executable::method::program_method::ProgramMethod;
+use crate::executive::opcode::opcode::Opcode;
+use crate::executive::opcode::opcodes::flow::op_nop::OP_NOP;
+use crate::executive::opcode::opcodes::flow::op_returnall::OP_RETURNALL;
+use crate::executive::opcode::opcodes::push::op_true::OP_TRUE;
+use crate::inscriptive::fixtures::errors::fixture_error::FixtureError;
+
+/// Returns a method that returns a single true item, padded with leading no-ops up to the
+/// minimum method opcode count.
+pub fn passing_method(
+    method_name: &str,
+    method_type: MethodType,
+) -> Result<ProgramMethod, FixtureError> {
+    // 1 Pad the script up to the minimum opcode count.
+    let mut script = vec![Opcode::OP_NOP(OP_NOP); MIN_METHOD_OPCODE_COUNT.saturating_sub(2)];
+
+    // 2 Push true and return it.
+    script.push(Opcode::OP_TRUE(OP_TRUE));
+    script.push(Opcode::OP_RETURNALL(OP_RETURNALL));
+
+    ProgramMethod::new(
+        method_name.to_string(),
+        method_type,
+        Vec::<CalldataElementType>::new(),
+        script,
+    )
+    .map_err(FixtureError::MethodConstructionError)
+}
+
+/// Returns a program with a single callable method returning a single true item.
+pub fn passing_program(program_name: &str) -> Result<Executable, FixtureError> {
+    let method = passing_method("call", MethodType::Callable)?;
+    Executable::new(program_name.to_string(), None, false, vec![method])
+        .map_err(FixtureError::ProgramConstructionError)
+}
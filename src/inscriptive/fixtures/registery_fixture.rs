@@ -0,0 +1,135 @@
+use crate::executive::executable::executable::Executable;
+use crate::inscriptive::fixtures::errors::fixture_error::FixtureError;
+use crate::inscriptive::fixtures::fixture_storage::{fixture_namespace, FIXTURE_CHAIN};
+use crate::inscriptive::registery::registery::{Registery, REGISTERY};
+use crate::inscriptive::storage::storage::with_storage_namespace;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Account BLS key.
+type AccountBLSKey = [u8; 48];
+
+/// Account secondary aggregation key.
+type AccountSecondaryAggregationKey = Vec<u8>;
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// A builder for an in-memory registery with accounts and contracts already registered.
+#[derive(Debug, Clone, Default)]
+pub struct RegisteryFixture {
+    // Accounts to register, with their BLS and secondary aggregation keys.
+    accounts: Vec<(
+        AccountKey,
+        Option<AccountBLSKey>,
+        Option<AccountSecondaryAggregationKey>,
+    )>,
+    // Contracts to register, with their programs and their upgrade and pause authorities.
+    contracts: Vec<(
+        ContractId,
+        Executable,
+        Option<AccountKey>,
+        Option<AccountKey>,
+    )>,
+}
+
+impl RegisteryFixture {
+    /// Constructs an empty registery fixture.
+    pub fn new() -> RegisteryFixture {
+        RegisteryFixture::default()
+    }
+
+    /// Registers an account, with no BLS key, projector or flame config.
+    pub fn with_account(self, account_key: AccountKey) -> RegisteryFixture {
+        self.with_account_keys(account_key, None, None)
+    }
+
+    /// Registers an account with the given BLS and secondary aggregation keys.
+    pub fn with_account_keys(
+        mut self,
+        account_key: AccountKey,
+        bls_key: Option<AccountBLSKey>,
+        secondary_aggregation_key: Option<AccountSecondaryAggregationKey>,
+    ) -> RegisteryFixture {
+        self.accounts
+            .push((account_key, bls_key, secondary_aggregation_key));
+        self
+    }
+
+    /// Registers a contract with the given program.
+    pub fn with_contract(
+        self,
+        contract_id: ContractId,
+        executable: Executable,
+    ) -> RegisteryFixture {
+        self.with_contract_authorities(contract_id, executable, None, None)
+    }
+
+    /// Registers a contract with the given program and upgrade and pause authorities.
+    pub fn with_contract_authorities(
+        mut self,
+        contract_id: ContractId,
+        executable: Executable,
+        upgrade_authority: Option<AccountKey>,
+        pause_authority: Option<AccountKey>,
+    ) -> RegisteryFixture {
+        self.contracts
+            .push((contract_id, executable, upgrade_authority, pause_authority));
+        self
+    }
+
+    /// Builds the registery under a namespace of its own.
+    pub async fn build(self) -> Result<REGISTERY, FixtureError> {
+        self.build_in(&fixture_namespace()?).await
+    }
+
+    /// Builds the registery under the given namespace.
+    pub async fn build_in(self, namespace: &str) -> Result<REGISTERY, FixtureError> {
+        // 1 Construct the registery.
+        let registery = with_storage_namespace(namespace, || Registery::new(FIXTURE_CHAIN))
+            .map_err(FixtureError::RegisteryConstructionError)?;
+
+        // 2 Register the accounts and the contracts, and apply the changes.
+        {
+            let mut _registery = registery.lock().await;
+            for (account_key, bls_key, secondary_aggregation_key) in self.accounts {
+                _registery
+                    .register_account(
+                        account_key,
+                        0,
+                        bls_key,
+                        secondary_aggregation_key,
+                        None,
+                        None,
+                    )
+                    .map_err(FixtureError::RegisteryRegisterAccountError)?;
+            }
+            for (contract_id, executable, upgrade_authority, pause_authority) in self.contracts {
+                _registery
+                    .register_contract_with_authorities(
+                        contract_id,
+                        0,
+                        executable,
+                        upgrade_authority,
+                        pause_authority,
+                    )
+                    .map_err(FixtureError::RegisteryRegisterContractError)?;
+            }
+            _registery
+                .apply_changes()
+                .map_err(FixtureError::RegisteryApplyChangesError)?;
+            _registery.flush_delta();
+        }
+
+        // 3 Return the registery.
+        Ok(registery)
+    }
+
+    /// Constructs the registery again from what was applied under the given namespace, as on a
+    /// restart.
+    pub fn reopen(namespace: &str) -> Result<REGISTERY, FixtureError> {
+        with_storage_namespace(namespace, || Registery::new(FIXTURE_CHAIN))
+            .map_err(FixtureError::RegisteryConstructionError)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::inscriptive::fixtures::errors::fixture_error::FixtureError;
+use crate::inscriptive::fixtures::fixture_storage::{fixture_namespace, FIXTURE_CHAIN};
+use crate::inscriptive::state_manager::state_manager::{StateManager, STATE_MANAGER};
+use crate::inscriptive::storage::storage::with_storage_namespace;
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// State key.
+type StateKey = Vec<u8>;
+
+/// State value.
+type StateValue = Vec<u8>;
+
+/// A builder for an in-memory state manager with contracts and their states already in place.
+#[derive(Debug, Clone, Default)]
+pub struct StateManagerFixture {
+    // Contracts to register.
+    contracts: Vec<ContractId>,
+    // States to insert.
+    states: Vec<(ContractId, StateKey, StateValue)>,
+}
+
+impl StateManagerFixture {
+    /// Constructs an empty state manager fixture.
+    pub fn new() -> StateManagerFixture {
+        StateManagerFixture::default()
+    }
+
+    /// Registers a contract.
+    pub fn with_contract(mut self, contract_id: ContractId) -> StateManagerFixture {
+        if !self.contracts.contains(&contract_id) {
+            self.contracts.push(contract_id);
+        }
+        self
+    }
+
+    /// Inserts a state, registering its contract if not already.
+    pub fn with_state(
+        mut self,
+        contract_id: ContractId,
+        key: StateKey,
+        value: StateValue,
+    ) -> StateManagerFixture {
+        self = self.with_contract(contract_id);
+        self.states.push((contract_id, key, value));
+        self
+    }
+
+    /// Builds the state manager under a namespace of its own.
+    pub async fn build(self) -> Result<STATE_MANAGER, FixtureError> {
+        self.build_in(&fixture_namespace()?).await
+    }
+
+    /// Builds the state manager under the given namespace.
+    pub async fn build_in(self, namespace: &str) -> Result<STATE_MANAGER, FixtureError> {
+        // 1 Construct the state manager.
+        let state_manager = with_storage_namespace(namespace, || StateManager::new(FIXTURE_CHAIN))
+            .map_err(FixtureError::StateManagerConstructionError)?;
+
+        // 2 Register the contracts, insert the states, and apply the changes.
+        {
+            let mut _state_manager = state_manager.lock().await;
+            for contract_id in self.contracts {
+                _state_manager
+                    .register_contract(contract_id)
+                    .map_err(FixtureError::StateManagerRegisterContractError)?;
+            }
+            // NOTE: The contracts are only registered in the delta yet, so the check is skipped.
+            for (contract_id, key, value) in self.states {
+                _state_manager
+                    .insert_update_state(contract_id, &key, &value, true)
+                    .map_err(FixtureError::StateManagerInsertUpdateStateError)?;
+            }
+            _state_manager
+                .apply_changes()
+                .map_err(FixtureError::StateManagerApplyChangesError)?;
+            _state_manager.flush_delta();
+        }
+
+        // 3 Return the state manager.
+        Ok(state_manager)
+    }
+
+    /// Constructs the state manager again from what was applied under the given namespace, as on a
+    /// restart.
+    pub fn reopen(namespace: &str) -> Result<STATE_MANAGER, FixtureError> {
+        with_storage_namespace(namespace, || StateManager::new(FIXTURE_CHAIN))
+            .map_err(FixtureError::StateManagerConstructionError)
+    }
+}
@@ -6,7 +6,10 @@ use crate::inscriptive::flame_manager::errors::apply_changes_error::FMApplyChang
 use crate::inscriptive::flame_manager::errors::construction_error::FMConstructionError;
 use crate::inscriptive::flame_manager::errors::register_account_error::FMRegisterAccountError;
 use crate::inscriptive::flame_manager::flame::flame::Flame;
+use crate::inscriptive::memory_budget::memory_budget::record_memory_usage;
+use crate::inscriptive::memory_budget::memory_footprint::{BudgetedManager, MemoryFootprint};
 use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
 use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet};
@@ -51,7 +54,7 @@ impl FlameManager {
         // 1 Open the accounts db.
         let accounts_db_path = format!("storage/{}/flames/accounts", chain.to_string());
         let accounts_db =
-            sled::open(accounts_db_path).map_err(FMConstructionError::AccountsDBOpenError)?;
+            open_db(chain, &accounts_db_path).map_err(FMConstructionError::AccountsDBOpenError)?;
 
         // 2 Initialize the in-memory flame set.
         let mut in_memory_flame_set =
@@ -135,10 +138,16 @@ impl FlameManager {
             backup_of_delta: FMDelta::fresh_new(),
         };
 
-        // 5 Guard the flame manager.
+        // 5 Record the memory footprint of the flame manager.
+        record_memory_usage(
+            BudgetedManager::FlameManager,
+            flame_manager.memory_footprint(),
+        );
+
+        // 6 Guard the flame manager.
         let guarded_flame_manager = Arc::new(Mutex::new(flame_manager));
 
-        // 6 Return the guarded flame manager.
+        // 7 Return the guarded flame manager.
         Ok(guarded_flame_manager)
     }
 
@@ -465,7 +474,10 @@ impl FlameManager {
             }
         }
 
-        // 8 Return the result.
+        // 8 Record the new memory footprint.
+        record_memory_usage(BudgetedManager::FlameManager, self.memory_footprint());
+
+        // 9 Return the result.
         Ok(sorted_new_flames_to_insert)
     }
 
@@ -518,11 +530,35 @@ impl FlameManager {
     }
 }
 
+impl MemoryFootprint for FlameManager {
+    fn memory_footprint(&self) -> u64 {
+        // 1 Account and projector height keys.
+        let keys_bytes = self
+            .in_memory_flame_set
+            .values()
+            .map(|flame_set| {
+                size_of::<AccountKey>() + flame_set.len() * size_of::<ProjectorHeight>()
+            })
+            .sum::<usize>();
+
+        // 2 Flames, including their script pubkeys.
+        let flames_bytes = self
+            .in_memory_flame_set
+            .values()
+            .flat_map(|flame_set| flame_set.values().flatten())
+            .map(|(_, flame)| size_of::<(FlameIndex, Flame)>() + flame.script_pubkey.len())
+            .sum::<usize>();
+
+        // 3 Return the total.
+        (keys_bytes + flames_bytes) as u64
+    }
+}
+
 /// Erases the flame manager by db path.
 pub fn erase_flame_manager(chain: Chain) {
     // Flame manager db path.
     let flame_manager_db_path = format!("storage/{}/flames/accounts", chain.to_string());
 
     // Erase the path.
-    let _ = std::fs::remove_dir_all(flame_manager_db_path);
+    erase_db(chain, &flame_manager_db_path);
 }
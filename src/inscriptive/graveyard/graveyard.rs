@@ -3,6 +3,7 @@ use crate::inscriptive::graveyard::errors::apply_changes_error::GraveyardApplyCh
 use crate::inscriptive::graveyard::errors::burry_account_error::GraveyardBurryAccountError;
 use crate::inscriptive::graveyard::errors::construction_error::GraveyardConstructionError;
 use crate::inscriptive::graveyard::errors::redeem_account_coins_error::GraveyardRedeemAccountCoinsError;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
@@ -47,7 +48,7 @@ impl Graveyard {
         // 1 Open the graveyard db.
         let graveyard_db_path = format!("storage/{}/graveyard", chain.to_string());
         let graveyard_db =
-            sled::open(graveyard_db_path).map_err(GraveyardConstructionError::DBOpenError)?;
+            open_db(chain, &graveyard_db_path).map_err(GraveyardConstructionError::DBOpenError)?;
 
         // 2 Initialize the in-memory burried accounts.
         let mut in_memory_burried_accounts =
@@ -314,5 +315,5 @@ pub fn erase_graveyard(chain: Chain) {
     let graveyard_db_path = format!("storage/{}/graveyard", chain.to_string());
 
     // Erase the path.
-    let _ = std::fs::remove_dir_all(graveyard_db_path);
+    erase_db(chain, &graveyard_db_path);
 }
@@ -0,0 +1,15 @@
+use crate::inscriptive::memory_budget::memory_footprint::BudgetedManager;
+
+/// Errors associated with exceeding the memory budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryBudgetExceededError {
+    GlobalBudgetExceeded {
+        usage_bytes: u64,
+        budget_bytes: u64,
+    },
+    ManagerBudgetExceeded {
+        manager: BudgetedManager,
+        usage_bytes: u64,
+        budget_bytes: u64,
+    },
+}
@@ -0,0 +1 @@
+pub mod memory_budget_exceeded_error;
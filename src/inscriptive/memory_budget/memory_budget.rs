@@ -0,0 +1,81 @@
+use crate::inscriptive::memory_budget::errors::memory_budget_exceeded_error::MemoryBudgetExceededError;
+use crate::inscriptive::memory_budget::memory_budget_config::MemoryBudgetConfig;
+use crate::inscriptive::memory_budget::memory_footprint::BudgetedManager;
+use serde_json::{Map, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+/// Memory budgets, read from the environment on first use.
+static MEMORY_BUDGET_CONFIG: OnceLock<MemoryBudgetConfig> = OnceLock::new();
+
+/// Last recorded memory usage of each manager, indexed by `BudgetedManager::index`.
+static MEMORY_USAGE: [AtomicU64; BudgetedManager::ALL.len()] =
+    [const { AtomicU64::new(0) }; BudgetedManager::ALL.len()];
+
+/// Returns the memory budgets.
+pub fn memory_budget_config() -> &'static MemoryBudgetConfig {
+    MEMORY_BUDGET_CONFIG.get_or_init(MemoryBudgetConfig::from_env)
+}
+
+/// Records the current memory usage of a manager.
+pub fn record_memory_usage(manager: BudgetedManager, usage_bytes: u64) {
+    MEMORY_USAGE[manager.index()].store(usage_bytes, Ordering::Relaxed);
+}
+
+/// Returns the last recorded memory usage of each manager.
+pub fn memory_usage() -> [u64; BudgetedManager::ALL.len()] {
+    std::array::from_fn(|index| MEMORY_USAGE[index].load(Ordering::Relaxed))
+}
+
+/// Checks whether a new account or contract registration fits in the memory budgets.
+pub fn check_registration_headroom() -> Result<(), MemoryBudgetExceededError> {
+    memory_budget_config().check_registration_headroom(&memory_usage())
+}
+
+/// Returns how many bytes a manager may hold, or `None` if it is unlimited.
+pub fn memory_allowance(manager: BudgetedManager) -> Option<u64> {
+    memory_budget_config().allowance(manager, &memory_usage())
+}
+
+/// Returns the memory usage and budgets as a JSON object.
+pub fn memory_budget_json() -> Value {
+    // 1 Snapshot the usage and the budgets.
+    let usage_bytes = memory_usage();
+    let config = memory_budget_config();
+
+    // 2 Encode an optional budget.
+    let budget_value = |budget_bytes: Option<u64>| match budget_bytes {
+        Some(budget_bytes) => Value::Number(budget_bytes.into()),
+        None => Value::Null,
+    };
+
+    // 3 Encode each manager.
+    let mut managers = Map::new();
+    for manager in BudgetedManager::ALL.iter() {
+        let mut manager_obj = Map::new();
+        manager_obj.insert(
+            "usage_bytes".to_string(),
+            Value::Number(usage_bytes[manager.index()].into()),
+        );
+        manager_obj.insert(
+            "budget_bytes".to_string(),
+            budget_value(config.manager_budget_bytes.get(manager).copied()),
+        );
+        managers.insert(manager.to_string(), Value::Object(manager_obj));
+    }
+
+    // 4 Construct the JSON object.
+    let mut obj = Map::new();
+    obj.insert(
+        "usage_bytes".to_string(),
+        Value::Number(usage_bytes.iter().sum::<u64>().into()),
+    );
+    obj.insert(
+        "budget_bytes".to_string(),
+        budget_value(config.global_budget_bytes),
+    );
+    obj.insert("managers".to_string(), Value::Object(managers));
+
+    // 5 Return the JSON object.
+    Value::Object(obj)
+}
@@ -0,0 +1,115 @@
+use crate::inscriptive::memory_budget::errors::memory_budget_exceeded_error::MemoryBudgetExceededError;
+use crate::inscriptive::memory_budget::memory_footprint::BudgetedManager;
+use std::collections::HashMap;
+
+/// Bytes in a mebibyte.
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Memory budgets, in bytes. Unset budgets are unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBudgetConfig {
+    // Budget shared by all managers.
+    pub global_budget_bytes: Option<u64>,
+
+    // Budgets of individual managers.
+    pub manager_budget_bytes: HashMap<BudgetedManager, u64>,
+}
+
+impl MemoryBudgetConfig {
+    /// Reads the memory budgets from the environment.
+    ///
+    /// `CUBE_MEMORY_BUDGET_MB` sets the global budget, and `CUBE_MEMORY_BUDGET_<MANAGER>_MB`
+    /// (e.g. `CUBE_MEMORY_BUDGET_ARCHIVAL_MANAGER_MB`) sets the budget of a single manager.
+    pub fn from_env() -> Self {
+        // 1 Read a budget in megabytes, ignoring unset, malformed or zero values.
+        let read_mb = |var: &str| -> Option<u64> {
+            std::env::var(var)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .filter(|mb| *mb > 0)
+                .map(|mb| mb.saturating_mul(BYTES_PER_MB))
+        };
+
+        // 2 Read the global budget.
+        let global_budget_bytes = read_mb("CUBE_MEMORY_BUDGET_MB");
+
+        // 3 Read the per-manager budgets.
+        let manager_budget_bytes = BudgetedManager::ALL
+            .iter()
+            .filter_map(|manager| {
+                let var = format!(
+                    "CUBE_MEMORY_BUDGET_{}_MB",
+                    manager.to_string().to_uppercase()
+                );
+                read_mb(&var).map(|budget_bytes| (*manager, budget_bytes))
+            })
+            .collect();
+
+        // 4 Return the config.
+        Self {
+            global_budget_bytes,
+            manager_budget_bytes,
+        }
+    }
+
+    /// Checks whether a new registration fits in the budgets, given the usage of each manager.
+    ///
+    /// Caches are counted towards the global budget, but never block a registration on their own
+    /// budget, since they evict instead.
+    pub fn check_registration_headroom(
+        &self,
+        usage_bytes: &[u64; BudgetedManager::ALL.len()],
+    ) -> Result<(), MemoryBudgetExceededError> {
+        // 1 Check the global budget.
+        if let Some(budget_bytes) = self.global_budget_bytes {
+            let total_usage_bytes = usage_bytes.iter().sum::<u64>();
+            if total_usage_bytes >= budget_bytes {
+                return Err(MemoryBudgetExceededError::GlobalBudgetExceeded {
+                    usage_bytes: total_usage_bytes,
+                    budget_bytes,
+                });
+            }
+        }
+
+        // 2 Check the budgets of the non-cache managers.
+        for manager in BudgetedManager::ALL.iter().filter(|m| !m.is_cache()) {
+            if let Some(budget_bytes) = self.manager_budget_bytes.get(manager).copied() {
+                let manager_usage_bytes = usage_bytes[manager.index()];
+                if manager_usage_bytes >= budget_bytes {
+                    return Err(MemoryBudgetExceededError::ManagerBudgetExceeded {
+                        manager: *manager,
+                        usage_bytes: manager_usage_bytes,
+                        budget_bytes,
+                    });
+                }
+            }
+        }
+
+        // 3 There is headroom.
+        Ok(())
+    }
+
+    /// Returns how many bytes a manager may hold, given the usage of each manager.
+    ///
+    /// This is its own budget, further narrowed to whatever the other managers leave of the global one.
+    pub fn allowance(
+        &self,
+        manager: BudgetedManager,
+        usage_bytes: &[u64; BudgetedManager::ALL.len()],
+    ) -> Option<u64> {
+        // 1 Resolve what is left of the global budget after the other managers.
+        let global_allowance = self.global_budget_bytes.map(|budget_bytes| {
+            let others_usage_bytes = usage_bytes.iter().sum::<u64>() - usage_bytes[manager.index()];
+            budget_bytes.saturating_sub(others_usage_bytes)
+        });
+
+        // 2 Narrow it down to the manager's own budget.
+        let manager_allowance = self.manager_budget_bytes.get(&manager).copied();
+
+        // 3 Return the tighter of the two.
+        match (global_allowance, manager_allowance) {
+            (Some(global), Some(own)) => Some(global.min(own)),
+            (global, own) => global.or(own),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Managers whose in-memory footprint is accounted against the memory budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BudgetedManager {
+    Registery,
+    CoinManager,
+    FlameManager,
+    StateManager,
+    PrivilegesManager,
+    ArchivalManager,
+}
+
+impl BudgetedManager {
+    /// All budgeted managers.
+    pub const ALL: [BudgetedManager; 6] = [
+        BudgetedManager::Registery,
+        BudgetedManager::CoinManager,
+        BudgetedManager::FlameManager,
+        BudgetedManager::StateManager,
+        BudgetedManager::PrivilegesManager,
+        BudgetedManager::ArchivalManager,
+    ];
+
+    /// Returns the index of the manager in `ALL`.
+    pub fn index(&self) -> usize {
+        match self {
+            BudgetedManager::Registery => 0,
+            BudgetedManager::CoinManager => 1,
+            BudgetedManager::FlameManager => 2,
+            BudgetedManager::StateManager => 3,
+            BudgetedManager::PrivilegesManager => 4,
+            BudgetedManager::ArchivalManager => 5,
+        }
+    }
+
+    /// Whether the manager is a cache that can evict instead of refusing growth.
+    pub fn is_cache(&self) -> bool {
+        matches!(self, BudgetedManager::ArchivalManager)
+    }
+}
+
+impl ToString for BudgetedManager {
+    fn to_string(&self) -> String {
+        match self {
+            BudgetedManager::Registery => "registery".to_string(),
+            BudgetedManager::CoinManager => "coin_manager".to_string(),
+            BudgetedManager::FlameManager => "flame_manager".to_string(),
+            BudgetedManager::StateManager => "state_manager".to_string(),
+            BudgetedManager::PrivilegesManager => "privileges_manager".to_string(),
+            BudgetedManager::ArchivalManager => "archival_manager".to_string(),
+        }
+    }
+}
+
+/// Approximate number of bytes a manager holds in memory.
+///
+/// The estimate counts the fixed size of the in-memory keys and bodies plus the lengths of their
+/// cheaply measurable heap allocations; it is meant for budgeting, not for exact accounting.
+pub trait MemoryFootprint {
+    fn memory_footprint(&self) -> u64;
+}
@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod memory_budget;
+pub mod memory_budget_config;
+pub mod memory_footprint;
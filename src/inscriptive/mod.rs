@@ -1,11 +1,19 @@
 pub mod archival_manager;
 pub mod baked;
 pub mod coin_manager;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
+pub mod fixtures;
 pub mod flame_manager;
 pub mod graveyard;
+pub mod memory_budget;
 pub mod params_manager;
 pub mod privileges_manager;
 pub mod registery;
+pub mod rent_manager;
+pub mod schedule_manager;
 pub mod state_manager;
+pub mod storage;
 pub mod sync_manager;
+pub mod undo_journal;
 pub mod utxo_set;
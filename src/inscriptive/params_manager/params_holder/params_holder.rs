@@ -15,6 +15,10 @@ pub struct ParamsHolder {
     pub liftup_entry_per_lift_base_fee: u64,
     pub move_ppm_liquidity_fee: u64,
     pub in_call_ppm_liquidity_fee: u64,
+    pub max_call_depth: u64,
+    pub max_call_count: u64,
+    pub mempool_min_fee_rate: u64,
+    pub priority_fee_engine_share_ppm: u64,
 }
 
 impl ParamsHolder {
@@ -35,6 +39,10 @@ impl ParamsHolder {
             liftup_entry_per_lift_base_fee: 50,
             move_ppm_liquidity_fee: 1000,
             in_call_ppm_liquidity_fee: 1000,
+            max_call_depth: 8,
+            max_call_count: 64,
+            mempool_min_fee_rate: 0,
+            priority_fee_engine_share_ppm: 1_000_000,
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::inscriptive::params_manager::params_holder::params_holder::ParamsHolder;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
 use std::sync::{Arc, Mutex};
 
@@ -16,6 +17,10 @@ const CONFIG_ENTRY_BASE_FEE_SPECIAL_DB_KEY: [u8; 1] = [0x0A; 1];
 const CONFIG_ENTRY_PER_CONFIG_BYTE_FEE_SPECIAL_DB_KEY: [u8; 1] = [0x0B; 1];
 const DEPLOY_ENTRY_BASE_FEE_SPECIAL_DB_KEY: [u8; 1] = [0x0C; 1];
 const DEPLOY_ENTRY_PER_PROGRAM_BYTE_FEE_SPECIAL_DB_KEY: [u8; 1] = [0x0D; 1];
+const MAX_CALL_DEPTH_SPECIAL_DB_KEY: [u8; 1] = [0x0E; 1];
+const MAX_CALL_COUNT_SPECIAL_DB_KEY: [u8; 1] = [0x0F; 1];
+const MEMPOOL_MIN_FEE_RATE_SPECIAL_DB_KEY: [u8; 1] = [0x10; 1];
+const PRIORITY_FEE_ENGINE_SHARE_PPM_SPECIAL_DB_KEY: [u8; 1] = [0x11; 1];
 
 const PARAMS_HOLDER_TREE_NAME: [u8; 13] = *b"params_holder";
 
@@ -53,7 +58,7 @@ impl ParamsManager {
     pub fn new(chain: Chain) -> Result<PARAMS_MANAGER, sled::Error> {
         // 1 Open params db.
         let params_db_path = format!("storage/{}/params", chain.to_string());
-        let params_db = sled::open(params_db_path)?;
+        let params_db = open_db(chain, &params_db_path)?;
 
         // 2 Start with the default params holder.
         let mut params_holder = ParamsHolder::origin_params_holder();
@@ -140,6 +145,26 @@ impl ParamsManager {
                         params_holder.in_call_ppm_liquidity_fee = u64::from_le_bytes(bytes);
                     }
                 }
+                MAX_CALL_DEPTH_SPECIAL_DB_KEY => {
+                    if let Ok(bytes) = value.as_ref().try_into() {
+                        params_holder.max_call_depth = u64::from_le_bytes(bytes);
+                    }
+                }
+                MAX_CALL_COUNT_SPECIAL_DB_KEY => {
+                    if let Ok(bytes) = value.as_ref().try_into() {
+                        params_holder.max_call_count = u64::from_le_bytes(bytes);
+                    }
+                }
+                MEMPOOL_MIN_FEE_RATE_SPECIAL_DB_KEY => {
+                    if let Ok(bytes) = value.as_ref().try_into() {
+                        params_holder.mempool_min_fee_rate = u64::from_le_bytes(bytes);
+                    }
+                }
+                PRIORITY_FEE_ENGINE_SHARE_PPM_SPECIAL_DB_KEY => {
+                    if let Ok(bytes) = value.as_ref().try_into() {
+                        params_holder.priority_fee_engine_share_ppm = u64::from_le_bytes(bytes);
+                    }
+                }
                 _ => (),
             }
         }
@@ -247,6 +272,23 @@ impl ParamsManager {
             .in_call_ppm_liquidity_fee = value;
     }
 
+    pub fn set_max_call_depth(&mut self, value: u64) {
+        self.get_mut_ephemeral_params_holder().max_call_depth = value;
+    }
+
+    pub fn set_max_call_count(&mut self, value: u64) {
+        self.get_mut_ephemeral_params_holder().max_call_count = value;
+    }
+
+    pub fn set_mempool_min_fee_rate(&mut self, value: u64) {
+        self.get_mut_ephemeral_params_holder().mempool_min_fee_rate = value;
+    }
+
+    pub fn set_priority_fee_engine_share_ppm(&mut self, value: u64) {
+        self.get_mut_ephemeral_params_holder()
+            .priority_fee_engine_share_ppm = value;
+    }
+
     /// Reverts the epheremal changes associated with the last execution.
     pub fn rollback_last(&mut self) {
         self.restore_delta();
@@ -363,6 +405,34 @@ impl ParamsManager {
                     .to_le_bytes()
                     .to_vec(),
             )?;
+            tree.insert(
+                MAX_CALL_DEPTH_SPECIAL_DB_KEY,
+                ephemeral_params_holder
+                    .max_call_depth
+                    .to_le_bytes()
+                    .to_vec(),
+            )?;
+            tree.insert(
+                MAX_CALL_COUNT_SPECIAL_DB_KEY,
+                ephemeral_params_holder
+                    .max_call_count
+                    .to_le_bytes()
+                    .to_vec(),
+            )?;
+            tree.insert(
+                MEMPOOL_MIN_FEE_RATE_SPECIAL_DB_KEY,
+                ephemeral_params_holder
+                    .mempool_min_fee_rate
+                    .to_le_bytes()
+                    .to_vec(),
+            )?;
+            tree.insert(
+                PRIORITY_FEE_ENGINE_SHARE_PPM_SPECIAL_DB_KEY,
+                ephemeral_params_holder
+                    .priority_fee_engine_share_ppm
+                    .to_le_bytes()
+                    .to_vec(),
+            )?;
 
             self.in_memory_params_holder = ephemeral_params_holder.clone();
         }
@@ -380,5 +450,5 @@ impl ParamsManager {
 /// Erases the params manager by db path.
 pub fn erase_params_manager(chain: Chain) {
     let params_db_path = format!("storage/{}/params", chain.to_string());
-    let _ = std::fs::remove_dir_all(params_db_path);
+    erase_db(chain, &params_db_path);
 }
@@ -1,3 +1,5 @@
+use crate::inscriptive::memory_budget::memory_budget::record_memory_usage;
+use crate::inscriptive::memory_budget::memory_footprint::{BudgetedManager, MemoryFootprint};
 use crate::inscriptive::privileges_manager::bodies::account_body::account_body::PrivilegesManagerAccountBody;
 use crate::inscriptive::privileges_manager::bodies::contract_body::contract_body::PrivilegesManagerContractBody;
 use crate::inscriptive::privileges_manager::delta::delta::PrivilegesManagerDelta;
@@ -12,6 +14,7 @@ use crate::inscriptive::privileges_manager::errors::register_error::{
 use crate::inscriptive::privileges_manager::errors::update_error::{
     PMUpdateAccountError, PMUpdateContractError,
 };
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -66,12 +69,12 @@ impl PrivilegesManager {
     pub fn new(chain: Chain) -> Result<PRIVILEGES_MANAGER, PrivilegesManagerConstructionError> {
         // 1 Open the accounts db.
         let accounts_db_path = format!("storage/{}/privileges/accounts", chain.to_string());
-        let accounts_db = sled::open(accounts_db_path)
+        let accounts_db = open_db(chain, &accounts_db_path)
             .map_err(PrivilegesManagerConstructionError::AccountsDBOpenError)?;
 
         // 2 Open the contracts db.
         let contracts_db_path = format!("storage/{}/privileges/contracts", chain.to_string());
-        let contracts_db = sled::open(contracts_db_path)
+        let contracts_db = open_db(chain, &contracts_db_path)
             .map_err(PrivilegesManagerConstructionError::ContractsDBOpenError)?;
 
         // 3 Initialize the in-memory lists of account and contract bodies.
@@ -373,10 +376,16 @@ impl PrivilegesManager {
             backup_of_delta: PrivilegesManagerDelta::fresh_new(),
         };
 
-        // 7 Guard the privileges manager.
+        // 7 Record the memory footprint of the privileges manager.
+        record_memory_usage(
+            BudgetedManager::PrivilegesManager,
+            privileges_manager.memory_footprint(),
+        );
+
+        // 8 Guard the privileges manager.
         let guarded_privileges_manager = Arc::new(Mutex::new(privileges_manager));
 
-        // 8 Return the guarded privileges manager.
+        // 9 Return the guarded privileges manager.
         Ok(guarded_privileges_manager)
     }
 
@@ -1032,6 +1041,9 @@ impl PrivilegesManager {
             }
         }
 
+        // 13 Record the new memory footprint.
+        record_memory_usage(BudgetedManager::PrivilegesManager, self.memory_footprint());
+
         Ok(())
     }
 
@@ -1042,11 +1054,21 @@ impl PrivilegesManager {
     }
 }
 
+impl MemoryFootprint for PrivilegesManager {
+    fn memory_footprint(&self) -> u64 {
+        let accounts_bytes = self.in_memory_accounts.len()
+            * (size_of::<AccountKey>() + size_of::<PrivilegesManagerAccountBody>());
+        let contracts_bytes = self.in_memory_contracts.len()
+            * (size_of::<ContractId>() + size_of::<PrivilegesManagerContractBody>());
+        (accounts_bytes + contracts_bytes) as u64
+    }
+}
+
 /// Erases the privileges manager by db paths.
 pub fn erase_privileges_manager(chain: Chain) {
     let accounts_db_path = format!("storage/{}/privileges/accounts", chain.to_string());
-    let _ = std::fs::remove_dir_all(accounts_db_path);
+    erase_db(chain, &accounts_db_path);
 
     let contracts_db_path = format!("storage/{}/privileges/contracts", chain.to_string());
-    let _ = std::fs::remove_dir_all(contracts_db_path);
+    erase_db(chain, &contracts_db_path);
 }
@@ -1,5 +1,6 @@
 use serde_json::{Map, Value};
 use crate::inscriptive::flame_manager::flame_config::flame_config::FMAccountFlameConfig;
+use crate::inscriptive::registery::bodies::account_body::account_control::RMAccountControl;
 
 /// BLS key of an account.
 type AccountBLSKey = [u8; 48];
@@ -28,11 +29,17 @@ pub struct RMAccountBody {
     // Secondary aggregation key of an account.
     pub secondary_aggregation_key: Option<AccountSecondaryAggregationKey>,
 
+    // Secondary aggregation key the account rotated away from, and the timestamp of the rotation.
+    pub previous_secondary_aggregation_key: Option<(AccountSecondaryAggregationKey, u64)>,
+
     // Projector config of an account.
     pub projector_config: Option<AccountProjectorConfig>,
 
     // Flame config of an account.
     pub flame_config: Option<FMAccountFlameConfig>,
+
+    // Control of an account whose key is an aggregated key, `None` for single-key accounts.
+    pub control: Option<RMAccountControl>,
 }
 
 impl RMAccountBody {
@@ -52,8 +59,10 @@ impl RMAccountBody {
             last_activity_timestamp,
             primary_bls_key,
             secondary_aggregation_key,
+            previous_secondary_aggregation_key: None,
             projector_config,
             flame_config,
+            control: None,
         }
     }
 
@@ -98,6 +107,23 @@ impl RMAccountBody {
             },
         );
 
+        // 6.1 Insert the previous secondary aggregation key.
+        obj.insert(
+            "previous_secondary_aggregation_key".to_string(),
+            match &self.previous_secondary_aggregation_key {
+                Some((key, rotated_at)) => {
+                    let mut previous = Map::new();
+                    previous.insert("key".to_string(), Value::String(hex::encode(key)));
+                    previous.insert(
+                        "rotated_at".to_string(),
+                        Value::String(rotated_at.to_string()),
+                    );
+                    Value::Object(previous)
+                }
+                None => Value::Null,
+            },
+        );
+
         // 7 Insert the projector config.
         obj.insert(
             "projector_config".to_string(),
@@ -116,7 +142,16 @@ impl RMAccountBody {
             },
         );
 
-        // 9 Return the account body JSON object.
+        // 9 Insert the account control.
+        obj.insert(
+            "control".to_string(),
+            match &self.control {
+                Some(control) => control.json(),
+                None => Value::Null,
+            },
+        );
+
+        // 10 Return the account body JSON object.
         Value::Object(obj)
     }
 }
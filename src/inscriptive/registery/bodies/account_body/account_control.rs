@@ -0,0 +1,221 @@
+use crate::transmutative::musig::keyagg::MusigKeyAggCtx;
+use crate::transmutative::secp::vss::VssCommitment;
+use secp::Point;
+use serde_json::{Map, Value};
+
+/// Account Key.
+type AccountKey = [u8; 32];
+
+/// Index of a FROST participant, starting at `1`.
+type ParticipantIndex = u32;
+
+/// Byte tag of a MuSig2 controlled account.
+const MUSIG_CONTROL_TAG: u8 = 0x01;
+
+/// Byte tag of a FROST controlled account.
+const FROST_CONTROL_TAG: u8 = 0x02;
+
+/// Maximum number of participants controlling an account.
+pub const ACCOUNT_CONTROL_MAX_PARTICIPANTS: usize = 255;
+
+/// How an account controlled by an aggregated key is controlled.
+///
+/// The account key is the x-only aggregate key, so authorization signatures verify against the
+/// account key exactly as for single-key accounts. The control records who the aggregate stands for,
+/// and is checked against the account key once at registration. Single-key accounts carry no control.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RMAccountControl {
+    /// n-of-n MuSig2 aggregate of the participant keys.
+    Musig { participant_keys: Vec<Point> },
+
+    /// t-of-n FROST group key, committed to by the joint Feldman commitment of the key generation.
+    ///
+    /// The threshold is the number of coefficients of the commitment, and each participant's
+    /// verification key derives from it by their index.
+    Frost {
+        group_commitment: VssCommitment,
+        participant_count: u8,
+    },
+}
+
+impl RMAccountControl {
+    /// Constructs a MuSig2 control from the participant keys.
+    ///
+    /// Returns `None` if there are fewer than two or more than `ACCOUNT_CONTROL_MAX_PARTICIPANTS` keys,
+    /// or if a key is repeated.
+    pub fn musig(participant_keys: Vec<Point>) -> Option<Self> {
+        // 1 Check the number of participants.
+        if participant_keys.len() < 2 || participant_keys.len() > ACCOUNT_CONTROL_MAX_PARTICIPANTS {
+            return None;
+        }
+
+        // 2 Check that no key is repeated.
+        for (index, participant_key) in participant_keys.iter().enumerate() {
+            if participant_keys[..index].contains(participant_key) {
+                return None;
+            }
+        }
+
+        Some(Self::Musig { participant_keys })
+    }
+
+    /// Constructs a FROST control from the joint commitment and the number of participants.
+    ///
+    /// Returns `None` if the threshold exceeds the number of participants.
+    pub fn frost(group_commitment: VssCommitment, participant_count: u8) -> Option<Self> {
+        if group_commitment.threshold() > participant_count as usize {
+            return None;
+        }
+
+        Some(Self::Frost {
+            group_commitment,
+            participant_count,
+        })
+    }
+
+    /// Returns the number of signatures needed to authorize the account.
+    pub fn threshold(&self) -> usize {
+        match self {
+            Self::Musig { participant_keys } => participant_keys.len(),
+            Self::Frost {
+                group_commitment, ..
+            } => group_commitment.threshold(),
+        }
+    }
+
+    /// Returns the number of participants controlling the account.
+    pub fn participant_count(&self) -> usize {
+        match self {
+            Self::Musig { participant_keys } => participant_keys.len(),
+            Self::Frost {
+                participant_count, ..
+            } => *participant_count as usize,
+        }
+    }
+
+    /// Returns the aggregate key: the untweaked MuSig2 key aggregate, or the FROST group key.
+    pub fn aggregate_key(&self) -> Option<Point> {
+        match self {
+            Self::Musig { participant_keys } => {
+                let key_agg_ctx = MusigKeyAggCtx::new(participant_keys, None)?;
+                Some(key_agg_ctx.agg_inner_key())
+            }
+            Self::Frost {
+                group_commitment, ..
+            } => Some(group_commitment.secret_commitment()),
+        }
+    }
+
+    /// Returns the verification key of the FROST participant at the given index.
+    ///
+    /// Returns `None` for MuSig2 controls, or if the index is out of range.
+    pub fn participant_verification_key(&self, index: ParticipantIndex) -> Option<Point> {
+        match self {
+            Self::Musig { .. } => None,
+            Self::Frost {
+                group_commitment,
+                participant_count,
+            } => {
+                if index > *participant_count as ParticipantIndex {
+                    return None;
+                }
+                group_commitment.share_public_key(index)
+            }
+        }
+    }
+
+    /// Checks whether the account key is the x-only aggregate key.
+    pub fn controls(&self, account_key: AccountKey) -> bool {
+        match self.aggregate_key() {
+            Some(aggregate_key) => aggregate_key.serialize_xonly() == account_key,
+            None => false,
+        }
+    }
+
+    /// Serializes the control as its tag followed by the participant keys, or the participant count
+    /// and the joint commitment.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::new();
+        match self {
+            Self::Musig { participant_keys } => {
+                bytes.push(MUSIG_CONTROL_TAG);
+                bytes.push(participant_keys.len() as u8);
+                for participant_key in participant_keys.iter() {
+                    bytes.extend(participant_key.serialize());
+                }
+            }
+            Self::Frost {
+                group_commitment,
+                participant_count,
+            } => {
+                bytes.push(FROST_CONTROL_TAG);
+                bytes.push(*participant_count);
+                bytes.extend(group_commitment.serialize());
+            }
+        }
+        bytes
+    }
+
+    /// Deserializes a control serialized with [`RMAccountControl::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        // 1 Split the tag and the count.
+        let (tag, rest) = bytes.split_first()?;
+        let (count, body) = rest.split_first()?;
+
+        // 2 Match the tag.
+        match *tag {
+            MUSIG_CONTROL_TAG => {
+                if body.len() != *count as usize * 33 {
+                    return None;
+                }
+                let participant_keys = body
+                    .chunks_exact(33)
+                    .map(|chunk| Point::from_slice(chunk).ok())
+                    .collect::<Option<Vec<Point>>>()?;
+                Self::musig(participant_keys)
+            }
+            FROST_CONTROL_TAG => Self::frost(VssCommitment::deserialize(body)?, *count),
+            _ => None,
+        }
+    }
+
+    /// Returns the control as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            Self::Musig { participant_keys } => {
+                obj.insert("kind".to_string(), Value::String("musig".to_string()));
+                obj.insert(
+                    "participant_keys".to_string(),
+                    Value::Array(
+                        participant_keys
+                            .iter()
+                            .map(|participant_key| {
+                                Value::String(hex::encode(participant_key.serialize()))
+                            })
+                            .collect(),
+                    ),
+                );
+            }
+            Self::Frost {
+                group_commitment,
+                participant_count,
+            } => {
+                obj.insert("kind".to_string(), Value::String("frost".to_string()));
+                obj.insert(
+                    "threshold".to_string(),
+                    Value::Number(group_commitment.threshold().into()),
+                );
+                obj.insert(
+                    "participant_count".to_string(),
+                    Value::Number((*participant_count).into()),
+                );
+                obj.insert(
+                    "group_commitment".to_string(),
+                    Value::String(hex::encode(group_commitment.serialize())),
+                );
+            }
+        }
+        Value::Object(obj)
+    }
+}
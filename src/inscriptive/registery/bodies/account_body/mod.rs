@@ -1 +1,2 @@
 pub mod account_body;
+pub mod account_control;
@@ -15,6 +15,18 @@ pub struct RMContractBody {
 
     // Decompiled executable of a contract.
     pub executable: Executable,
+
+    // Account allowed to upgrade the program of a contract, if any.
+    pub upgrade_authority: Option<[u8; 32]>,
+
+    // Version of the current program of a contract, starting from zero at registration.
+    pub program_version: u32,
+
+    // Account allowed to pause and unpause a contract, if any.
+    pub pause_authority: Option<[u8; 32]>,
+
+    // Whether a contract is paused by its pause authority.
+    pub paused: bool,
 }
 
 impl RMContractBody {
@@ -24,12 +36,20 @@ impl RMContractBody {
         call_counter: u64,
         last_activity_timestamp: u64,
         executable: Executable,
+        upgrade_authority: Option<[u8; 32]>,
+        program_version: u32,
+        pause_authority: Option<[u8; 32]>,
+        paused: bool,
     ) -> Self {
         Self {
             registery_index,
             call_counter,
             last_activity_timestamp,
             executable,
+            upgrade_authority,
+            program_version,
+            pause_authority,
+            paused,
         }
     }
 
@@ -59,7 +79,34 @@ impl RMContractBody {
         // 5 Insert the executable.
         obj.insert("executable".to_string(), self.executable.json());
 
-        // 6 Return the contract body JSON object.
+        // 6 Insert the upgrade authority.
+        obj.insert(
+            "upgrade_authority".to_string(),
+            match self.upgrade_authority {
+                Some(upgrade_authority) => Value::String(hex::encode(upgrade_authority)),
+                None => Value::Null,
+            },
+        );
+
+        // 7 Insert the program version.
+        obj.insert(
+            "program_version".to_string(),
+            Value::String(self.program_version.to_string()),
+        );
+
+        // 8 Insert the pause authority.
+        obj.insert(
+            "pause_authority".to_string(),
+            match self.pause_authority {
+                Some(pause_authority) => Value::String(hex::encode(pause_authority)),
+                None => Value::Null,
+            },
+        );
+
+        // 9 Insert the paused flag.
+        obj.insert("paused".to_string(), Value::Bool(self.paused));
+
+        // 10 Return the contract body JSON object.
         Value::Object(obj)
     }
 }
@@ -1,5 +1,6 @@
 use crate::executive::executable::executable::Executable;
 use crate::inscriptive::flame_manager::flame_config::flame_config::FMAccountFlameConfig;
+use crate::inscriptive::registery::bodies::account_body::account_control::RMAccountControl;
 use std::collections::HashMap;
 
 /// secp256k1 public key of an account.
@@ -38,6 +39,9 @@ pub struct RMDelta {
         Option<FMAccountFlameConfig>,
     )>,
 
+    // Controls of the new accounts whose keys are aggregated keys.
+    pub new_account_controls: HashMap<AccountKey, RMAccountControl>,
+
     // Updated account call counters for a given account.
     pub updated_account_call_counters: HashMap<AccountKey, CallCounterDelta>,
 
@@ -47,6 +51,9 @@ pub struct RMDelta {
     // Updated secondary aggregation keys for a given account.
     pub updated_secondary_aggregation_keys: HashMap<AccountKey, AccountSecondaryAggregationKey>,
 
+    // Timestamps at which the secondary aggregation keys were updated for a given account.
+    pub secondary_aggregation_key_rotation_timestamps: HashMap<AccountKey, ActivityTimestamp>,
+
     // Updated projector configs for a given account.
     pub updated_projector_configs: HashMap<AccountKey, AccountProjectorConfig>,
 
@@ -61,6 +68,18 @@ pub struct RMDelta {
     // New contracts to register.
     pub new_contracts_to_register: Vec<(ContractId, ActivityTimestamp, Executable)>,
 
+    // Upgrade authorities of the new contracts that are upgradable.
+    pub new_contract_upgrade_authorities: HashMap<ContractId, AccountKey>,
+
+    // Pause authorities of the new contracts that are pausable.
+    pub new_contract_pause_authorities: HashMap<ContractId, AccountKey>,
+
+    // Updated paused flags for a given contract.
+    pub updated_contract_pause_states: HashMap<ContractId, bool>,
+
+    // Upgraded programs for a given contract.
+    pub upgraded_contracts: HashMap<ContractId, Executable>,
+
     // Updated contract call counters for a given contract.
     pub updated_contract_call_counters: HashMap<ContractId, CallCounterDelta>,
 
@@ -73,13 +92,19 @@ impl RMDelta {
     pub fn fresh_new() -> Self {
         Self {
             new_accounts_to_register: Vec::new(),
+            new_account_controls: HashMap::new(),
             updated_account_call_counters: HashMap::new(),
             updated_bls_keys: HashMap::new(),
             updated_secondary_aggregation_keys: HashMap::new(),
+            secondary_aggregation_key_rotation_timestamps: HashMap::new(),
             updated_projector_configs: HashMap::new(),
             updated_account_last_activity_timestamps: HashMap::new(),
             updated_account_flame_configs: HashMap::new(),
             new_contracts_to_register: Vec::new(),
+            new_contract_upgrade_authorities: HashMap::new(),
+            new_contract_pause_authorities: HashMap::new(),
+            updated_contract_pause_states: HashMap::new(),
+            upgraded_contracts: HashMap::new(),
             updated_contract_call_counters: HashMap::new(),
             updated_contract_last_activity_timestamps: HashMap::new(),
         }
@@ -88,13 +113,19 @@ impl RMDelta {
     /// Clears all values.
     pub fn flush(&mut self) {
         self.new_accounts_to_register.clear();
+        self.new_account_controls.clear();
         self.updated_account_call_counters.clear();
         self.updated_bls_keys.clear();
         self.updated_secondary_aggregation_keys.clear();
+        self.secondary_aggregation_key_rotation_timestamps.clear();
         self.updated_projector_configs.clear();
         self.updated_account_last_activity_timestamps.clear();
         self.updated_account_flame_configs.clear();
         self.new_contracts_to_register.clear();
+        self.new_contract_upgrade_authorities.clear();
+        self.new_contract_pause_authorities.clear();
+        self.updated_contract_pause_states.clear();
+        self.upgraded_contracts.clear();
         self.updated_contract_call_counters.clear();
         self.updated_contract_last_activity_timestamps.clear();
     }
@@ -113,6 +144,11 @@ impl RMDelta {
             .any(|(id, _, _)| id == &contract_id)
     }
 
+    /// Checks if a contract has just been epheremally upgraded in the delta.
+    pub fn is_contract_epheremally_upgraded(&self, contract_id: ContractId) -> bool {
+        self.upgraded_contracts.contains_key(&contract_id)
+    }
+
     /// Epheremally registers an account in the delta.
     pub fn epheremally_register_account(
         &mut self,
@@ -133,6 +169,15 @@ impl RMDelta {
         ));
     }
 
+    /// Epheremally sets the control of a new account in the delta.
+    pub fn epheremally_set_account_control(
+        &mut self,
+        account_key: AccountKey,
+        control: RMAccountControl,
+    ) {
+        self.new_account_controls.insert(account_key, control);
+    }
+
     /// Epheremally registers a contract in the delta.
     pub fn epheremally_register_contract(
         &mut self,
@@ -144,6 +189,41 @@ impl RMDelta {
             .push((contract_id, last_activity_timestamp, executable));
     }
 
+    /// Epheremally sets the upgrade authority of a new contract in the delta.
+    pub fn epheremally_set_contract_upgrade_authority(
+        &mut self,
+        contract_id: ContractId,
+        upgrade_authority: AccountKey,
+    ) {
+        self.new_contract_upgrade_authorities
+            .insert(contract_id, upgrade_authority);
+    }
+
+    /// Epheremally sets the pause authority of a new contract in the delta.
+    pub fn epheremally_set_contract_pause_authority(
+        &mut self,
+        contract_id: ContractId,
+        pause_authority: AccountKey,
+    ) {
+        self.new_contract_pause_authorities
+            .insert(contract_id, pause_authority);
+    }
+
+    /// Epheremally pauses or unpauses a contract in the delta.
+    pub fn epheremally_set_contract_paused(&mut self, contract_id: ContractId, paused: bool) {
+        self.updated_contract_pause_states
+            .insert(contract_id, paused);
+    }
+
+    /// Epheremally upgrades the program of a contract in the delta.
+    pub fn epheremally_upgrade_contract(
+        &mut self,
+        contract_id: ContractId,
+        executable: Executable,
+    ) {
+        self.upgraded_contracts.insert(contract_id, executable);
+    }
+
     /// Epheremally increments the call counter delta of an account by one.
     pub fn epheremally_increment_account_call_counter_delta_by_one(
         &mut self,
@@ -206,7 +286,10 @@ impl RMDelta {
         &mut self,
         account_key: AccountKey,
         secondary_aggregation_key: AccountSecondaryAggregationKey,
+        rotated_at: ActivityTimestamp,
     ) -> Option<AccountSecondaryAggregationKey> {
+        self.secondary_aggregation_key_rotation_timestamps
+            .insert(account_key, rotated_at);
         self.updated_secondary_aggregation_keys
             .insert(account_key, secondary_aggregation_key)
     }
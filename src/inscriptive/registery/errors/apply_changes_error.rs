@@ -13,8 +13,10 @@ pub enum RMApplyChangesError {
     AccountLastActivityTimestampInsertError(AccountKey, u64, sled::Error),
     AccountBLSKeyInsertError(AccountKey, sled::Error),
     AccountSecondaryAggregationKeyInsertError(AccountKey, sled::Error),
+    AccountPreviousSecondaryAggregationKeyInsertError(AccountKey, sled::Error),
     AccountFlameConfigInsertError(AccountKey, sled::Error),
     AccountProjectorConfigInsertError(AccountKey, sled::Error),
+    AccountControlInsertError(AccountKey, sled::Error),
     AccountNotFoundInMemory(AccountKey),
     AccountCallCounterUpdateError(AccountKey, u64, sled::Error),
     AccountLastActivityTimestampUpdateError(AccountKey, u64, sled::Error),
@@ -23,6 +25,12 @@ pub enum RMApplyChangesError {
     ContractCallCounterInsertError(ContractId, u64, sled::Error),
     ContractLastActivityTimestampInsertError(ContractId, u64, sled::Error),
     ContractProgramBytesInsertError(ContractId, sled::Error),
+    ContractUpgradeAuthorityInsertError(ContractId, sled::Error),
+    ContractProgramVersionInsertError(ContractId, u32, sled::Error),
+    ContractPauseAuthorityInsertError(ContractId, sled::Error),
+    ContractPausedInsertError(ContractId, bool, sled::Error),
+    ContractProgramVersionsTreeOpenError(ContractId, sled::Error),
+    ContractPreviousProgramBytesInsertError(ContractId, u32, sled::Error),
     ContractNotFoundInMemory(ContractId),
     ContractCallCounterUpdateError(ContractId, u64, sled::Error),
     ContractLastActivityTimestampUpdateError(ContractId, u64, sled::Error),
@@ -22,6 +22,8 @@ pub enum RMConstructionError {
     UnableToDeserializeAccountSecondaryAggregationKeyBytesFromTreeValue(AccountKey, Vec<u8>),
     UnableToDeserializeAccountFlameConfigBytesFromTreeValue(AccountKey, Vec<u8>),
     UnableToDeserializeAccountProjectorConfigBytesFromTreeValue(AccountKey, Vec<u8>),
+    UnableToDeserializeAccountPreviousSecondaryAggregationKeyBytesFromTreeValue(AccountKey, Vec<u8>),
+    UnableToDeserializeAccountControlBytesFromTreeValue(AccountKey, Vec<u8>),
     InvalidAccountDbKeyByte(AccountKey, Vec<u8>),
 
     /// Contract related errors.
@@ -33,6 +35,10 @@ pub enum RMConstructionError {
     UnableToDeserializeContractRegisteryIndexBytesFromTreeValue(ContractId, Vec<u8>),
     UnableToDeserializeContractCallCounterBytesFromTreeValue(ContractId, Vec<u8>),
     UnableToDeserializeContractLastActivityTimestampBytesFromTreeValue(ContractId, Vec<u8>),
+    UnableToDeserializeContractUpgradeAuthorityBytesFromTreeValue(ContractId, Vec<u8>),
+    UnableToDeserializeContractProgramVersionBytesFromTreeValue(ContractId, Vec<u8>),
+    UnableToDeserializeContractPauseAuthorityBytesFromTreeValue(ContractId, Vec<u8>),
+    UnableToDeserializeContractPausedByteFromTreeValue(ContractId, Vec<u8>),
     ContractProgramDecompileError(ContractId, ProgramDecompileError),
     InvalidContractDbKeyByte(ContractId, Vec<u8>),
 }
@@ -0,0 +1,15 @@
+/// Account Key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Errors associated with pausing or unpausing a contract.
+#[derive(Debug, Clone)]
+pub enum RMPauseContractError {
+    ContractIsNotRegistered(ContractId),
+    ContractHasNoPauseAuthority(ContractId),
+    UnauthorizedPauseAuthority(ContractId, AccountKey),
+    ContractIsAlreadyPaused(ContractId),
+    ContractIsNotPaused(ContractId),
+}
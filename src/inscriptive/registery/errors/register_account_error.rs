@@ -1,3 +1,5 @@
+use crate::inscriptive::memory_budget::errors::memory_budget_exceeded_error::MemoryBudgetExceededError;
+
 /// Account Key.
 type AccountKey = [u8; 32];
 
@@ -10,4 +12,6 @@ pub enum RMRegisterAccountError {
     AccountHasJustBeenEphemerallyRegistered(AccountKey),
     AccountIsAlreadyPermanentlyRegistered(AccountKey),
     BLSKeyIsConflictingWithAnAlreadyRegisteredBLSKey(AccountBLSKey),
+    MemoryBudgetExceeded(MemoryBudgetExceededError),
+    AccountKeyIsNotTheAggregateKey(AccountKey),
 }
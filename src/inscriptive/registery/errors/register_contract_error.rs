@@ -1,3 +1,6 @@
+use crate::executive::executable::program_error::ProgramValidationError;
+use crate::inscriptive::memory_budget::errors::memory_budget_exceeded_error::MemoryBudgetExceededError;
+
 /// Contract ID.
 type ContractId = [u8; 32];
 
@@ -6,4 +9,6 @@ type ContractId = [u8; 32];
 pub enum RMRegisterContractError {
     ContractHasJustBeenEphemerallyRegistered(ContractId),
     ContractIsAlreadyPermanentlyRegistered(ContractId),
+    MemoryBudgetExceeded(MemoryBudgetExceededError),
+    InvalidProgram(ContractId, ProgramValidationError),
 }
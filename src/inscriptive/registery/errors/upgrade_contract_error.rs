@@ -0,0 +1,17 @@
+use crate::executive::executable::program_error::ProgramValidationError;
+
+/// Account Key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Errors associated with upgrading the program of a contract.
+#[derive(Debug, Clone)]
+pub enum RMUpgradeContractError {
+    ContractIsNotRegistered(ContractId),
+    ContractHasNoUpgradeAuthority(ContractId),
+    UnauthorizedUpgradeAuthority(ContractId, AccountKey),
+    ContractHasJustBeenEphemerallyUpgraded(ContractId),
+    InvalidProgram(ContractId, ProgramValidationError),
+}
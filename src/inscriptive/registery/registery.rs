@@ -4,11 +4,17 @@ use crate::constructive::entity::contract::contract::Contract;
 use crate::executive::executable::compiler::compiler::ProgramCompiler;
 use crate::executive::executable::executable::Executable;
 use crate::inscriptive::flame_manager::flame_config::flame_config::FMAccountFlameConfig;
+use crate::inscriptive::memory_budget::memory_budget::{
+    check_registration_headroom, record_memory_usage,
+};
+use crate::inscriptive::memory_budget::memory_footprint::{BudgetedManager, MemoryFootprint};
 use crate::inscriptive::registery::bodies::account_body::account_body::RMAccountBody;
+use crate::inscriptive::registery::bodies::account_body::account_control::RMAccountControl;
 use crate::inscriptive::registery::bodies::contract_body::contract_body::RMContractBody;
 use crate::inscriptive::registery::delta::delta::RMDelta;
 use crate::inscriptive::registery::errors::apply_changes_error::RMApplyChangesError;
 use crate::inscriptive::registery::errors::construction_error::RMConstructionError;
+use crate::inscriptive::registery::errors::pause_contract_error::RMPauseContractError;
 use crate::inscriptive::registery::errors::register_account_error::RMRegisterAccountError;
 use crate::inscriptive::registery::errors::register_contract_error::RMRegisterContractError;
 use crate::inscriptive::registery::errors::update_account_bls_key_error::RMUpdateAccountBLSKeyError;
@@ -17,7 +23,10 @@ use crate::inscriptive::registery::errors::update_account_flame_config_error::RM
 use crate::inscriptive::registery::errors::update_account_projector_config_error::RMUpdateAccountProjectorConfigError;
 use crate::inscriptive::registery::errors::update_account_secondary_aggregation_key_error::RMUpdateAccountSecondaryAggregationKeyError;
 use crate::inscriptive::registery::errors::update_contract_call_counter_and_last_activity_timestamp_error::RMUpdateContractCallCounterAndLastActivityTimestampError;
+use crate::inscriptive::registery::errors::upgrade_contract_error::RMUpgradeContractError;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
+use crate::transmutative::secp::public_key;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -68,6 +77,30 @@ const ACCOUNT_FLAME_CONFIG_SPECIAL_DB_KEY: [u8; 1] = [0x06; 1];
 /// Special db key for projector config (0x07..).
 const PROJECTOR_CONFIG_SPECIAL_DB_KEY: [u8; 1] = [0x07; 1];
 
+/// Special db key for the previous secondary aggregation key (0x08..).
+const PREVIOUS_SECONDARY_AGGREGATION_KEY_SPECIAL_DB_KEY: [u8; 1] = [0x08; 1];
+
+/// Special db key for the account control (0x09..).
+const ACCOUNT_CONTROL_SPECIAL_DB_KEY: [u8; 1] = [0x09; 1];
+
+/// Special db key for the contract upgrade authority (0x0a..).
+const UPGRADE_AUTHORITY_SPECIAL_DB_KEY: [u8; 1] = [0x0a; 1];
+
+/// Special db key for the contract program version (0x0b..).
+const PROGRAM_VERSION_SPECIAL_DB_KEY: [u8; 1] = [0x0b; 1];
+
+/// Special db key for the contract pause authority (0x0c..).
+const PAUSE_AUTHORITY_SPECIAL_DB_KEY: [u8; 1] = [0x0c; 1];
+
+/// Special db key for the contract paused flag (0x0d..).
+const PAUSED_SPECIAL_DB_KEY: [u8; 1] = [0x0d; 1];
+
+/// Suffix of the tree name under which the previous program versions of a contract are retained.
+const PROGRAM_VERSIONS_TREE_SUFFIX: &[u8] = b"versions";
+
+/// How long (in seconds) a rotated-away secondary aggregation key stays valid after the rotation.
+pub const SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD: u64 = 604_800;
+
 /// A struct for managing the registery of accounts and contracts.
 #[allow(dead_code)]
 pub struct Registery {
@@ -100,12 +133,12 @@ impl Registery {
         // 1 Open the accounts db.
         let accounts_db_path = format!("storage/{}/registery/accounts", chain.to_string());
         let accounts_db =
-            sled::open(accounts_db_path).map_err(RMConstructionError::AccountsDBOpenError)?;
+            open_db(chain, &accounts_db_path).map_err(RMConstructionError::AccountsDBOpenError)?;
 
         // 2 Open the contracts db.
         let contracts_db_path = format!("storage/{}/registery/contracts", chain.to_string());
-        let contracts_db =
-            sled::open(contracts_db_path).map_err(RMConstructionError::ContractsDBOpenError)?;
+        let contracts_db = open_db(chain, &contracts_db_path)
+            .map_err(RMConstructionError::ContractsDBOpenError)?;
 
         // 3 Initialize the in-memory lists of account & contract bodies.
         let mut in_memory_accounts = HashMap::<AccountKey, RMAccountBody>::new();
@@ -114,7 +147,7 @@ impl Registery {
         // 4 Iterate over all items in the accounts db to collect the account bodies.
         for tree_name in accounts_db.tree_names() {
             // 4.1 Convert the tree name to a account key.
-            let account_key: [u8; 32] = match tree_name.as_ref().try_into() {
+            let account_key: [u8; 32] = match public_key::account_key_from_slice(&tree_name) {
                 Ok(account_key) => account_key,
                 Err(_) => {
                     // Tree name is probably '__sled__default'. Skip it.
@@ -143,6 +176,12 @@ impl Registery {
             // 4.6 Initialize the projector config to None.
             let mut projector_config: Option<AccountProjectorConfig> = None;
 
+            // 4.7 Initialize the previous secondary aggregation key to None.
+            let mut previous_secondary_aggregation_key: Option<(Vec<u8>, u64)> = None;
+
+            // 4.8 Initialize the account control to None.
+            let mut control: Option<RMAccountControl> = None;
+
             // 4.5 Open the tree associated with the account.
             let tree = accounts_db
                 .open_tree(&tree_name)
@@ -251,6 +290,38 @@ impl Registery {
                             projector_config = Some(projector_config_bytes);
                         }
                     }
+                    // 0x08 key byte represents the previous secondary aggregation key, prefixed with the rotation timestamp.
+                    PREVIOUS_SECONDARY_AGGREGATION_KEY_SPECIAL_DB_KEY => {
+                        if value.as_ref().len() > 0 {
+                            // The rotation timestamp is followed by a non-empty key.
+                            if value.as_ref().len() <= 8 {
+                                return Err(RMConstructionError::UnableToDeserializeAccountPreviousSecondaryAggregationKeyBytesFromTreeValue(
+                                    account_key,
+                                    value.to_vec(),
+                                ));
+                            }
+
+                            let (rotated_at_bytes, previous_key_bytes) = value.as_ref().split_at(8);
+                            let mut rotated_at = [0u8; 8];
+                            rotated_at.copy_from_slice(rotated_at_bytes);
+
+                            previous_secondary_aggregation_key =
+                                Some((previous_key_bytes.to_vec(), u64::from_le_bytes(rotated_at)));
+                        }
+                    }
+                    // 0x09 key byte represents the account control.
+                    ACCOUNT_CONTROL_SPECIAL_DB_KEY => {
+                        if value.as_ref().len() > 0 {
+                            let control_deserialized = RMAccountControl::from_bytes(value.as_ref())
+                                .ok_or(
+                                    RMConstructionError::UnableToDeserializeAccountControlBytesFromTreeValue(
+                                        account_key,
+                                        value.to_vec(),
+                                    ),
+                                )?;
+                            control = Some(control_deserialized);
+                        }
+                    }
                     // Invalid db key byte.
                     _ => {
                         return Err(RMConstructionError::InvalidAccountDbKeyByte(
@@ -262,7 +333,7 @@ impl Registery {
             }
 
             // 4.5 Construct the account body with the collected registery index and call counter values.
-            let account_body = RMAccountBody::new(
+            let mut account_body = RMAccountBody::new(
                 registery_index,
                 call_counter,
                 last_activity_timestamp,
@@ -271,6 +342,8 @@ impl Registery {
                 projector_config,
                 flame_config,
             );
+            account_body.previous_secondary_aggregation_key = previous_secondary_aggregation_key;
+            account_body.control = control;
 
             // 4.6 Insert the account body into the in-memory list of accounts.
             in_memory_accounts.insert(account_key, account_body);
@@ -299,6 +372,16 @@ impl Registery {
             // 5.5 Construct a placeholder executable.
             let mut executable = Executable::placeholder_program();
 
+            // 5.5 Initialize the upgrade authority to none.
+            let mut upgrade_authority: Option<[u8; 32]> = None;
+
+            // 5.5 Initialize the program version to zero.
+            let mut program_version = 0;
+
+            // 5.5 Initialize the pause authority to none, and the contract as not paused.
+            let mut pause_authority: Option<[u8; 32]> = None;
+            let mut paused = false;
+
             // 5.5 Open the tree associated with the contract.
             let tree = contracts_db
                 .open_tree(&tree_name)
@@ -368,6 +451,53 @@ impl Registery {
 
                         last_activity_timestamp = u64::from_le_bytes(last_activity_timestamp_bytes);
                     }
+                    // 0x0a key byte represents the upgrade authority.
+                    UPGRADE_AUTHORITY_SPECIAL_DB_KEY => {
+                        let upgrade_authority_bytes: [u8; 32] =
+                            value.as_ref().try_into().map_err(|_| {
+                                RMConstructionError::UnableToDeserializeContractUpgradeAuthorityBytesFromTreeValue(
+                                    contract_id,
+                                    value.to_vec(),
+                                )
+                            })?;
+
+                        upgrade_authority = Some(upgrade_authority_bytes);
+                    }
+                    // 0x0b key byte represents the program version.
+                    PROGRAM_VERSION_SPECIAL_DB_KEY => {
+                        let program_version_bytes: [u8; 4] =
+                            value.as_ref().try_into().map_err(|_| {
+                                RMConstructionError::UnableToDeserializeContractProgramVersionBytesFromTreeValue(
+                                    contract_id,
+                                    value.to_vec(),
+                                )
+                            })?;
+
+                        program_version = u32::from_le_bytes(program_version_bytes);
+                    }
+                    // 0x0c key byte represents the pause authority.
+                    PAUSE_AUTHORITY_SPECIAL_DB_KEY => {
+                        let pause_authority_bytes: [u8; 32] =
+                            value.as_ref().try_into().map_err(|_| {
+                                RMConstructionError::UnableToDeserializeContractPauseAuthorityBytesFromTreeValue(
+                                    contract_id,
+                                    value.to_vec(),
+                                )
+                            })?;
+
+                        pause_authority = Some(pause_authority_bytes);
+                    }
+                    // 0x0d key byte represents the paused flag.
+                    PAUSED_SPECIAL_DB_KEY => {
+                        let paused_byte: [u8; 1] = value.as_ref().try_into().map_err(|_| {
+                            RMConstructionError::UnableToDeserializeContractPausedByteFromTreeValue(
+                                contract_id,
+                                value.to_vec(),
+                            )
+                        })?;
+
+                        paused = paused_byte[0] != 0;
+                    }
                     // Invalid db key byte.
                     _ => {
                         return Err(RMConstructionError::InvalidContractDbKeyByte(
@@ -384,6 +514,10 @@ impl Registery {
                 call_counter,
                 last_activity_timestamp,
                 executable,
+                upgrade_authority,
+                program_version,
+                pause_authority,
+                paused,
             );
 
             // 5.8 Insert the contract body into the in-memory list of contracts.
@@ -408,10 +542,13 @@ impl Registery {
             backup_of_delta: RMDelta::fresh_new(),
         };
 
-        // 10 Guard the registery manager.
+        // 10 Record the memory footprint of the registery manager.
+        record_memory_usage(BudgetedManager::Registery, registery.memory_footprint());
+
+        // 11 Guard the registery manager.
         let guarded_registery = Arc::new(Mutex::new(registery));
 
-        // 11 Return the guarded registery manager.
+        // 12 Return the guarded registery manager.
         Ok(guarded_registery)
     }
 
@@ -552,6 +689,16 @@ impl Registery {
             || self.is_contract_epheremally_registered(contract_id)
     }
 
+    /// Returns the keys of all permanently registered accounts.
+    pub fn permanently_registered_account_keys(&self) -> Vec<AccountKey> {
+        self.in_memory_accounts.keys().copied().collect()
+    }
+
+    /// Returns the ids of all permanently registered contracts.
+    pub fn permanently_registered_contract_ids(&self) -> Vec<ContractId> {
+        self.in_memory_contracts.keys().copied().collect()
+    }
+
     /// Returns the account body by its key.
     pub fn get_account_body_by_account_key(&self, account_key: [u8; 32]) -> Option<RMAccountBody> {
         self.in_memory_accounts.get(&account_key).cloned()
@@ -610,6 +757,53 @@ impl Registery {
             .and_then(|account_body| account_body.flame_config.clone())
     }
 
+    /// Returns whether the given key is a valid secondary aggregation key for an account at the given timestamp.
+    ///
+    /// NOTE: The key the account rotated away from remains valid for `SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD`
+    /// seconds after the rotation, so that sessions started with it can still complete.
+    pub fn is_account_secondary_aggregation_key_valid(
+        &self,
+        account_key: AccountKey,
+        secondary_aggregation_key: &[u8],
+        timestamp: u64,
+    ) -> bool {
+        // 1 Get the account body.
+        let account_body = match self.in_memory_accounts.get(&account_key) {
+            Some(account_body) => account_body,
+            None => return false,
+        };
+
+        // 2 The current secondary aggregation key is always valid.
+        if account_body.secondary_aggregation_key.as_deref() == Some(secondary_aggregation_key) {
+            return true;
+        }
+
+        // 3 The previous secondary aggregation key is valid within the grace period.
+        match &account_body.previous_secondary_aggregation_key {
+            Some((previous_key, rotated_at)) => {
+                previous_key.as_slice() == secondary_aggregation_key
+                    && timestamp
+                        <= rotated_at
+                            .saturating_add(SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the control of an account whose key is an aggregated key, or `None` for single-key
+    /// and unregistered accounts.
+    pub fn get_account_control(&self, account_key: AccountKey) -> Option<RMAccountControl> {
+        // 1 Try to get from the delta first (ephemeral registrations).
+        if let Some(control) = self.delta.new_account_controls.get(&account_key) {
+            return Some(control.clone());
+        }
+
+        // 2 And then try to get from the permanent in-memory states.
+        self.in_memory_accounts
+            .get(&account_key)
+            .and_then(|account_body| account_body.control.clone())
+    }
+
     /// Returns the contract body by its identifier.
     pub fn get_contract_body_by_contract_id(
         &self,
@@ -618,6 +812,110 @@ impl Registery {
         self.in_memory_contracts.get(&contract_id).cloned()
     }
 
+    /// Returns the current executable of a contract, including an epheremal upgrade.
+    pub fn get_contract_executable(&self, contract_id: ContractId) -> Option<Executable> {
+        // 1 Try to get from the delta first (ephemeral upgrades).
+        if let Some(executable) = self.delta.upgraded_contracts.get(&contract_id) {
+            return Some(executable.clone());
+        }
+
+        // 2 And then try to get from the permanent in-memory states.
+        self.in_memory_contracts
+            .get(&contract_id)
+            .map(|contract_body| contract_body.executable.clone())
+    }
+
+    /// Returns the upgrade authority of a contract, or `None` for non-upgradable and unregistered
+    /// contracts.
+    pub fn get_contract_upgrade_authority(&self, contract_id: ContractId) -> Option<AccountKey> {
+        // 1 Try to get from the delta first (ephemeral registrations).
+        if let Some(upgrade_authority) = self
+            .delta
+            .new_contract_upgrade_authorities
+            .get(&contract_id)
+        {
+            return Some(*upgrade_authority);
+        }
+
+        // 2 And then try to get from the permanent in-memory states.
+        self.in_memory_contracts
+            .get(&contract_id)
+            .and_then(|contract_body| contract_body.upgrade_authority)
+    }
+
+    /// Returns the pause authority of a contract, or `None` for non-pausable and unregistered
+    /// contracts.
+    pub fn get_contract_pause_authority(&self, contract_id: ContractId) -> Option<AccountKey> {
+        // 1 Try to get from the delta first (ephemeral registrations).
+        if let Some(pause_authority) = self.delta.new_contract_pause_authorities.get(&contract_id) {
+            return Some(*pause_authority);
+        }
+
+        // 2 And then try to get from the permanent in-memory states.
+        self.in_memory_contracts
+            .get(&contract_id)
+            .and_then(|contract_body| contract_body.pause_authority)
+    }
+
+    /// Returns whether a contract is paused by its pause authority.
+    pub fn is_contract_paused(&self, contract_id: ContractId) -> bool {
+        // 1 Try to get from the delta first (ephemeral pauses and unpauses).
+        if let Some(paused) = self.delta.updated_contract_pause_states.get(&contract_id) {
+            return *paused;
+        }
+
+        // 2 And then try to get from the permanent in-memory states.
+        self.in_memory_contracts
+            .get(&contract_id)
+            .map(|contract_body| contract_body.paused)
+            .unwrap_or(false)
+    }
+
+    /// Returns the permanent program version of a contract.
+    pub fn get_contract_program_version(&self, contract_id: ContractId) -> Option<u32> {
+        self.in_memory_contracts
+            .get(&contract_id)
+            .map(|contract_body| contract_body.program_version)
+    }
+
+    /// Returns the permanent executable of a contract at the given program version.
+    ///
+    /// NOTE: Previous versions are retained on-disk so that past calls can be replayed against the
+    /// program they originally ran.
+    pub fn get_contract_executable_by_version(
+        &self,
+        contract_id: ContractId,
+        program_version: u32,
+    ) -> Option<Executable> {
+        // 1 Get the contract body from the in-memory list.
+        let contract_body = self.in_memory_contracts.get(&contract_id)?;
+
+        // 2 The current version is held in-memory.
+        if program_version == contract_body.program_version {
+            return Some(contract_body.executable.clone());
+        }
+
+        // 3 Newer versions do not exist yet.
+        if program_version > contract_body.program_version {
+            return None;
+        }
+
+        // 4 Previous versions are retained on-disk.
+        let tree = self
+            .on_disk_contracts
+            .open_tree(Self::program_versions_tree_name(contract_id))
+            .ok()?;
+        let program_bytes = tree.get(program_version.to_be_bytes()).ok()??;
+
+        // 5 Decompile the executable from bytecode.
+        Executable::decompile(&mut program_bytes.to_vec().into_iter()).ok()
+    }
+
+    /// Returns the name of the tree that retains the previous program versions of a contract.
+    fn program_versions_tree_name(contract_id: ContractId) -> Vec<u8> {
+        [contract_id.as_slice(), PROGRAM_VERSIONS_TREE_SUFFIX].concat()
+    }
+
     /// Returns the account key by its rank.
     pub fn get_account_key_by_rank(&self, rank: u64) -> Option<AccountKey> {
         self.in_memory_account_ranks.get(&rank).cloned()
@@ -819,7 +1117,10 @@ impl Registery {
             }
         }
 
-        // 3 Epheremally register the account in the delta.
+        // 4 Refuse the registration if the memory budget is exhausted.
+        check_registration_headroom().map_err(RMRegisterAccountError::MemoryBudgetExceeded)?;
+
+        // 5 Epheremally register the account in the delta.
         self.delta.epheremally_register_account(
             account_key,
             last_activity_timestamp,
@@ -829,6 +1130,44 @@ impl Registery {
             flame_config,
         );
 
+        // 6 Return the result.
+        Ok(())
+    }
+
+    /// Epheremally registers an account controlled by an aggregated key (n-of-n MuSig2 or t-of-n FROST).
+    ///
+    /// The account key must be the x-only aggregate key of the control, against which the account's
+    /// authorization signatures are verified like any other account's.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn register_account_with_control(
+        &mut self,
+        account_key: AccountKey,
+        control: RMAccountControl,
+        last_activity_timestamp: u64,
+        bls_key: Option<AccountBLSKey>,
+        secondary_aggregation_key: Option<AccountSecondaryAggregationKey>,
+        projector_config: Option<AccountProjectorConfig>,
+        flame_config: Option<FMAccountFlameConfig>,
+    ) -> Result<(), RMRegisterAccountError> {
+        // 1 Check if the account key is the aggregate key of the control.
+        if !control.controls(account_key) {
+            return Err(RMRegisterAccountError::AccountKeyIsNotTheAggregateKey(account_key));
+        }
+
+        // 2 Epheremally register the account.
+        self.register_account(
+            account_key,
+            last_activity_timestamp,
+            bls_key,
+            secondary_aggregation_key,
+            projector_config,
+            flame_config,
+        )?;
+
+        // 3 Epheremally set the account control in the delta.
+        self.delta.epheremally_set_account_control(account_key, control);
+
         // 4 Return the result.
         Ok(())
     }
@@ -856,14 +1195,167 @@ impl Registery {
             );
         }
 
-        // 3 Epheremally register the contract in the delta.
+        // 3 Refuse the registration if the memory budget is exhausted.
+        check_registration_headroom().map_err(RMRegisterContractError::MemoryBudgetExceeded)?;
+
+        // 4 Statically validate the program.
+        executable
+            .validate()
+            .map_err(|error| RMRegisterContractError::InvalidProgram(contract_id, error))?;
+
+        // 5 Epheremally register the contract in the delta.
         self.delta
             .epheremally_register_contract(contract_id, last_activity_timestamp, executable);
 
+        // 6 Return the result.
+        Ok(())
+    }
+
+    /// Epheremally registers an upgradable contract whose program can later be upgraded by the
+    /// upgrade authority.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn register_contract_with_upgrade_authority(
+        &mut self,
+        contract_id: ContractId,
+        last_activity_timestamp: u64,
+        executable: Executable,
+        upgrade_authority: AccountKey,
+    ) -> Result<(), RMRegisterContractError> {
+        self.register_contract_with_authorities(
+            contract_id,
+            last_activity_timestamp,
+            executable,
+            Some(upgrade_authority),
+            None,
+        )
+    }
+
+    /// Epheremally registers a contract with the optional authorities allowed to upgrade its
+    /// program and to pause it.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn register_contract_with_authorities(
+        &mut self,
+        contract_id: ContractId,
+        last_activity_timestamp: u64,
+        executable: Executable,
+        upgrade_authority: Option<AccountKey>,
+        pause_authority: Option<AccountKey>,
+    ) -> Result<(), RMRegisterContractError> {
+        // 1 Epheremally register the contract.
+        self.register_contract(contract_id, last_activity_timestamp, executable)?;
+
+        // 2 Epheremally set the upgrade authority in the delta.
+        if let Some(upgrade_authority) = upgrade_authority {
+            self.delta
+                .epheremally_set_contract_upgrade_authority(contract_id, upgrade_authority);
+        }
+
+        // 3 Epheremally set the pause authority in the delta.
+        if let Some(pause_authority) = pause_authority {
+            self.delta
+                .epheremally_set_contract_pause_authority(contract_id, pause_authority);
+        }
+
         // 4 Return the result.
         Ok(())
     }
 
+    /// Epheremally upgrades the program of a contract on behalf of its upgrade authority.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn upgrade_contract(
+        &mut self,
+        contract_id: ContractId,
+        upgrade_authority: AccountKey,
+        executable: Executable,
+    ) -> Result<(), RMUpgradeContractError> {
+        // 1 Get the contract body from the in-memory list.
+        let contract_body = self
+            .in_memory_contracts
+            .get(&contract_id)
+            .ok_or(RMUpgradeContractError::ContractIsNotRegistered(contract_id))?;
+
+        // 2 Check if the contract is upgradable.
+        let recorded_upgrade_authority = contract_body.upgrade_authority.ok_or(
+            RMUpgradeContractError::ContractHasNoUpgradeAuthority(contract_id),
+        )?;
+
+        // 3 Check if the upgrade is authorized by the recorded upgrade authority.
+        if recorded_upgrade_authority != upgrade_authority {
+            return Err(RMUpgradeContractError::UnauthorizedUpgradeAuthority(
+                contract_id,
+                upgrade_authority,
+            ));
+        }
+
+        // 4 Check if the contract has just been epheremally upgraded in the delta.
+        if self.delta.is_contract_epheremally_upgraded(contract_id) {
+            return Err(
+                RMUpgradeContractError::ContractHasJustBeenEphemerallyUpgraded(contract_id),
+            );
+        }
+
+        // 5 Statically validate the new program.
+        executable
+            .validate()
+            .map_err(|error| RMUpgradeContractError::InvalidProgram(contract_id, error))?;
+
+        // 6 Epheremally upgrade the contract in the delta.
+        self.delta
+            .epheremally_upgrade_contract(contract_id, executable);
+
+        // 7 Return the result.
+        Ok(())
+    }
+
+    /// Epheremally pauses or unpauses a contract on behalf of its pause authority.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn set_contract_paused(
+        &mut self,
+        contract_id: ContractId,
+        pause_authority: AccountKey,
+        paused: bool,
+    ) -> Result<(), RMPauseContractError> {
+        // 1 Check if the contract is registered.
+        if !self.is_contract_registered(contract_id) {
+            return Err(RMPauseContractError::ContractIsNotRegistered(contract_id));
+        }
+
+        // 2 Check if the contract is pausable.
+        let recorded_pause_authority = self.get_contract_pause_authority(contract_id).ok_or(
+            RMPauseContractError::ContractHasNoPauseAuthority(contract_id),
+        )?;
+
+        // 3 Check if the pause is authorized by the recorded pause authority.
+        if recorded_pause_authority != pause_authority {
+            return Err(RMPauseContractError::UnauthorizedPauseAuthority(
+                contract_id,
+                pause_authority,
+            ));
+        }
+
+        // 4 Check if the contract is not already in the requested state.
+        match (self.is_contract_paused(contract_id), paused) {
+            (true, true) => {
+                return Err(RMPauseContractError::ContractIsAlreadyPaused(contract_id));
+            }
+            (false, false) => {
+                return Err(RMPauseContractError::ContractIsNotPaused(contract_id));
+            }
+            _ => {}
+        }
+
+        // 5 Epheremally pause or unpause the contract in the delta.
+        self.delta
+            .epheremally_set_contract_paused(contract_id, paused);
+
+        // 6 Return the result.
+        Ok(())
+    }
+
     /// Epheremally updates the call counter and last activity timestamp of an account.
     ///
     /// NOTE: These changes are saved with the use of the `apply_changes` function.
@@ -981,6 +1473,7 @@ impl Registery {
         &mut self,
         account_key: AccountKey,
         secondary_aggregation_key: AccountSecondaryAggregationKey,
+        execution_timestamp: u64,
     ) -> Result<Option<AccountSecondaryAggregationKey>, RMUpdateAccountSecondaryAggregationKeyError>
     {
         // 1 Check if the account is registered and return it's body.
@@ -998,6 +1491,7 @@ impl Registery {
             .epheremally_set_or_update_account_secondary_aggregation_key(
                 account_key,
                 secondary_aggregation_key,
+                execution_timestamp,
             )
         {
             return Err(RMUpdateAccountSecondaryAggregationKeyError::SecondaryAggregationKeyIsAlreadyEpheremallyUpdated(
@@ -1206,12 +1700,20 @@ impl Registery {
                             RMApplyChangesError::AccountProjectorConfigInsertError(*account_key, e)
                         })?;
                 }
+
+                // 1.4.9 Insert the account control on-disk if present.
+                if let Some(control) = self.delta.new_account_controls.get(account_key) {
+                    tree.insert(ACCOUNT_CONTROL_SPECIAL_DB_KEY, control.to_bytes())
+                        .map_err(|e| {
+                            RMApplyChangesError::AccountControlInsertError(*account_key, e)
+                        })?;
+                }
             }
 
             // 1.5 In-memory insertion.
             {
                 // 1.5.1 Construct the account body.
-                let account_body = RMAccountBody::new(
+                let mut account_body = RMAccountBody::new(
                     registery_index,
                     initial_call_counter,
                     *last_activity_timestamp,
@@ -1220,6 +1722,7 @@ impl Registery {
                     *projector_config,
                     flame_config.clone(),
                 );
+                account_body.control = self.delta.new_account_controls.get(account_key).cloned();
 
                 // 1.5.2 Insert the account body into the in-memory list.
                 self.in_memory_accounts.insert(*account_key, account_body);
@@ -1293,6 +1796,29 @@ impl Registery {
                     .map_err(|e| {
                         RMApplyChangesError::ContractProgramBytesInsertError(*contract_id, e)
                     })?;
+
+                // 2.5.6 Insert the upgrade authority on-disk if the contract is upgradable.
+                if let Some(upgrade_authority) =
+                    self.delta.new_contract_upgrade_authorities.get(contract_id)
+                {
+                    tree.insert(
+                        UPGRADE_AUTHORITY_SPECIAL_DB_KEY,
+                        upgrade_authority.as_slice(),
+                    )
+                    .map_err(|e| {
+                        RMApplyChangesError::ContractUpgradeAuthorityInsertError(*contract_id, e)
+                    })?;
+                }
+
+                // 2.5.7 Insert the pause authority on-disk if the contract is pausable.
+                if let Some(pause_authority) =
+                    self.delta.new_contract_pause_authorities.get(contract_id)
+                {
+                    tree.insert(PAUSE_AUTHORITY_SPECIAL_DB_KEY, pause_authority.as_slice())
+                        .map_err(|e| {
+                            RMApplyChangesError::ContractPauseAuthorityInsertError(*contract_id, e)
+                        })?;
+                }
             }
 
             // 2.6 In-memory insertion.
@@ -1303,6 +1829,16 @@ impl Registery {
                     initial_call_counter,
                     *registery_timestamp,
                     executable.clone(),
+                    self.delta
+                        .new_contract_upgrade_authorities
+                        .get(contract_id)
+                        .cloned(),
+                    0,
+                    self.delta
+                        .new_contract_pause_authorities
+                        .get(contract_id)
+                        .cloned(),
+                    false,
                 );
 
                 // 2.6.2 Insert the contract body into the in-memory list.
@@ -1514,7 +2050,34 @@ impl Registery {
                     .open_tree(account_key)
                     .map_err(|e| RMApplyChangesError::AccountTreeOpenError(*account_key, e))?;
 
-                // 6.2.2 Update the secondary aggregation key on-disk.
+                // 6.2.2 Keep the key being rotated away from, along with the rotation timestamp.
+                if let Some(previous_secondary_aggregation_key) =
+                    &mut_account_body.secondary_aggregation_key
+                {
+                    if previous_secondary_aggregation_key != secondary_aggregation_key {
+                        let rotated_at = self
+                            .delta
+                            .secondary_aggregation_key_rotation_timestamps
+                            .get(account_key)
+                            .copied()
+                            .unwrap_or(mut_account_body.last_activity_timestamp);
+
+                        let mut value = rotated_at.to_le_bytes().to_vec();
+                        value.extend_from_slice(previous_secondary_aggregation_key);
+                        tree.insert(PREVIOUS_SECONDARY_AGGREGATION_KEY_SPECIAL_DB_KEY, value)
+                            .map_err(|e| {
+                                RMApplyChangesError::AccountPreviousSecondaryAggregationKeyInsertError(
+                                    *account_key,
+                                    e,
+                                )
+                            })?;
+
+                        mut_account_body.previous_secondary_aggregation_key =
+                            Some((previous_secondary_aggregation_key.clone(), rotated_at));
+                    }
+                }
+
+                // 6.2.3 Update the secondary aggregation key on-disk.
                 tree.insert(
                     SECONDARY_AGGREGATION_KEY_SPECIAL_DB_KEY,
                     secondary_aggregation_key.as_slice(),
@@ -1586,19 +2149,126 @@ impl Registery {
             mut_account_body.flame_config = Some(flame_config.clone());
         }
 
-        // 11 Re-rank accounts after all changes.
+        // 11 Upgrade contract programs.
+        for (contract_id, executable) in self.delta.upgraded_contracts.iter() {
+            // 11.1 Get the mutable contract body from the in-memory list.
+            let mut_contract_body = self
+                .in_memory_contracts
+                .get_mut(contract_id)
+                .ok_or(RMApplyChangesError::ContractNotFoundInMemory(*contract_id))?;
+
+            // 11.2 The upgraded program takes the next version.
+            let previous_program_version = mut_contract_body.program_version;
+            let new_program_version = previous_program_version + 1;
+
+            // 11.3 Compile the previous and the upgraded executables to bytes.
+            let previous_program_bytes = mut_contract_body
+                .executable
+                .compile()
+                .map_err(|e| RMApplyChangesError::ProgramCompileError(*contract_id, e))?;
+            let program_bytes = executable
+                .compile()
+                .map_err(|e| RMApplyChangesError::ProgramCompileError(*contract_id, e))?;
+
+            // 11.4 On-disk update.
+            {
+                // 11.4.1 Open the tree retaining the previous program versions of the contract.
+                let versions_tree = self
+                    .on_disk_contracts
+                    .open_tree(Self::program_versions_tree_name(*contract_id))
+                    .map_err(|e| {
+                        RMApplyChangesError::ContractProgramVersionsTreeOpenError(*contract_id, e)
+                    })?;
+
+                // 11.4.2 Retain the previous program under its version.
+                // NOTE: Versions are big-endian keyed so that the tree iterates them in order.
+                versions_tree
+                    .insert(
+                        previous_program_version.to_be_bytes(),
+                        previous_program_bytes.as_slice(),
+                    )
+                    .map_err(|e| {
+                        RMApplyChangesError::ContractPreviousProgramBytesInsertError(
+                            *contract_id,
+                            previous_program_version,
+                            e,
+                        )
+                    })?;
+
+                // 11.4.3 Open the tree for the contract.
+                let tree = self
+                    .on_disk_contracts
+                    .open_tree(contract_id)
+                    .map_err(|e| RMApplyChangesError::ContractTreeOpenError(*contract_id, e))?;
+
+                // 11.4.4 Update the program bytes on-disk.
+                tree.insert(PROGRAM_BYTES_SPECIAL_DB_KEY, program_bytes.as_slice())
+                    .map_err(|e| {
+                        RMApplyChangesError::ContractProgramBytesInsertError(*contract_id, e)
+                    })?;
+
+                // 11.4.5 Update the program version on-disk.
+                tree.insert(
+                    PROGRAM_VERSION_SPECIAL_DB_KEY,
+                    new_program_version.to_le_bytes().to_vec(),
+                )
+                .map_err(|e| {
+                    RMApplyChangesError::ContractProgramVersionInsertError(
+                        *contract_id,
+                        new_program_version,
+                        e,
+                    )
+                })?;
+            }
+
+            // 11.5 In-memory update.
+            mut_contract_body.executable = executable.clone();
+            mut_contract_body.program_version = new_program_version;
+        }
+
+        // 12 Update contract paused flags.
+        for (contract_id, paused) in self.delta.updated_contract_pause_states.iter() {
+            // 12.1 Get the mutable contract body from the in-memory list.
+            let mut_contract_body = self
+                .in_memory_contracts
+                .get_mut(contract_id)
+                .ok_or(RMApplyChangesError::ContractNotFoundInMemory(*contract_id))?;
+
+            // 12.2 On-disk update.
+            {
+                // 12.2.1 Open the tree for the contract.
+                let tree = self
+                    .on_disk_contracts
+                    .open_tree(contract_id)
+                    .map_err(|e| RMApplyChangesError::ContractTreeOpenError(*contract_id, e))?;
+
+                // 12.2.2 Update the paused flag on-disk.
+                tree.insert(PAUSED_SPECIAL_DB_KEY, vec![*paused as u8])
+                    .map_err(|e| {
+                        RMApplyChangesError::ContractPausedInsertError(*contract_id, *paused, e)
+                    })?;
+            }
+
+            // 12.3 In-memory update.
+            mut_contract_body.paused = *paused;
+        }
+
+        // 13 Re-rank accounts after all changes.
         {
             let new_ranked_accounts = Self::rank_accounts(&self.in_memory_accounts);
             self.in_memory_account_ranks = new_ranked_accounts;
         }
 
-        // 12 Re-rank contracts after all changes.
+        // 14 Re-rank contracts after all changes.
         {
             let new_ranked_contracts = Self::rank_contracts(&self.in_memory_contracts);
             self.in_memory_contract_ranks = new_ranked_contracts;
         }
 
-        // 13 Return the result.
+        // 15 Record the new memory footprint.
+        record_memory_usage(BudgetedManager::Registery, self.memory_footprint());
+
+        // 16 Return the result.
         Ok(())
     }
 
@@ -1647,17 +2317,58 @@ impl Registery {
     }
 }
 
+impl MemoryFootprint for Registery {
+    fn memory_footprint(&self) -> u64 {
+        // 1 Account bodies, including their current and previous secondary aggregation keys and controls.
+        let accounts_bytes = self
+            .in_memory_accounts
+            .values()
+            .map(|account_body| {
+                size_of::<AccountKey>()
+                    + size_of::<RMAccountBody>()
+                    + account_body
+                        .secondary_aggregation_key
+                        .as_ref()
+                        .map(|key| key.len())
+                        .unwrap_or(0)
+                    + account_body
+                        .previous_secondary_aggregation_key
+                        .as_ref()
+                        .map(|(key, _)| key.len())
+                        .unwrap_or(0)
+                    + account_body
+                        .control
+                        .as_ref()
+                        .map(|control| control.to_bytes().len())
+                        .unwrap_or(0)
+            })
+            .sum::<usize>();
+
+        // 2 Contract bodies.
+        let contracts_bytes = self.in_memory_contracts.len()
+            * (size_of::<ContractId>() + size_of::<RMContractBody>());
+
+        // 3 Rank indexes.
+        let ranks_bytes = (self.in_memory_account_ranks.len()
+            + self.in_memory_contract_ranks.len())
+            * (size_of::<Rank>() + size_of::<[u8; 32]>());
+
+        // 4 Return the total.
+        (accounts_bytes + contracts_bytes + ranks_bytes) as u64
+    }
+}
+
 /// Erases the registery manager by db paths.
 pub fn erase_registery(chain: Chain) {
     // Accounts db path.
     let accounts_db_path = format!("storage/{}/registery/accounts", chain.to_string());
 
     // Erase the accounts db path.
-    let _ = std::fs::remove_dir_all(accounts_db_path);
+    erase_db(chain, &accounts_db_path);
 
     // Contracts db path.
     let contracts_db_path = format!("storage/{}/registery/contracts", chain.to_string());
 
     // Erase the contracts db path.
-    let _ = std::fs::remove_dir_all(contracts_db_path);
+    erase_db(chain, &contracts_db_path);
 }
@@ -0,0 +1,31 @@
+use crate::inscriptive::rent_manager::rent_status::RentStatus;
+use std::collections::HashMap;
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// A struct for containing epheremal state differences to be applied for 'RentManager'.
+#[derive(Clone)]
+pub struct RentManagerDelta {
+    // Rent statuses to be inserted or updated.
+    pub updated_statuses: HashMap<ContractId, RentStatus>,
+}
+
+impl RentManagerDelta {
+    /// Constructs a fresh new rent manager delta.
+    pub fn fresh_new() -> Self {
+        Self {
+            updated_statuses: HashMap::new(),
+        }
+    }
+
+    /// Clears all values.
+    pub fn flush(&mut self) {
+        self.updated_statuses.clear();
+    }
+
+    /// Epheremally inserts or updates a rent status.
+    pub fn epheremally_update_status(&mut self, contract_id: ContractId, rent_status: RentStatus) {
+        self.updated_statuses.insert(contract_id, rent_status);
+    }
+}
@@ -0,0 +1,9 @@
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Errors associated with applying changes to the `RentManager`.
+#[derive(Debug, Clone)]
+pub enum RentManagerApplyChangesError {
+    /// Error when inserting a rent status on disk.
+    RentStatusInsertDBError(ContractId, sled::Error),
+}
@@ -0,0 +1,7 @@
+/// Errors associated with constructing the `RentManager`.
+#[derive(Debug, Clone)]
+pub enum RentManagerConstructionError {
+    DBOpenError(sled::Error),
+    UnableToDeserializeContractIdBytesFromDBKey(Vec<u8>),
+    UnableToDeserializeRentStatusBytesFromDBValue(Vec<u8>, Vec<u8>),
+}
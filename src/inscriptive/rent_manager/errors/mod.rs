@@ -0,0 +1,2 @@
+pub mod apply_changes_error;
+pub mod construction_error;
@@ -0,0 +1,6 @@
+pub mod delta;
+pub mod errors;
+pub mod rent_charge;
+pub mod rent_manager;
+pub mod rent_policy;
+pub mod rent_status;
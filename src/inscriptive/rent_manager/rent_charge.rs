@@ -0,0 +1,32 @@
+use serde_json::{Map, Value};
+
+/// The rent charged to a contract at a batch height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RentCharge {
+    /// The contract charged.
+    pub contract_id: [u8; 32],
+    /// The rent debited from the contract balance.
+    pub charged: u64,
+    /// The rent the contract balance fell short of.
+    pub shortfall: u64,
+    /// Whether the contract was tombstoned by this charge.
+    pub tombstoned: bool,
+}
+
+impl RentCharge {
+    /// Returns the rent charge as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(self.contract_id)),
+        );
+        obj.insert("charged".to_string(), Value::Number(self.charged.into()));
+        obj.insert(
+            "shortfall".to_string(),
+            Value::Number(self.shortfall.into()),
+        );
+        obj.insert("tombstoned".to_string(), Value::Bool(self.tombstoned));
+        Value::Object(obj)
+    }
+}
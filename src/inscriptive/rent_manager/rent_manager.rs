@@ -0,0 +1,294 @@
+use crate::inscriptive::rent_manager::delta::delta::RentManagerDelta;
+use crate::inscriptive::rent_manager::errors::apply_changes_error::RentManagerApplyChangesError;
+use crate::inscriptive::rent_manager::errors::construction_error::RentManagerConstructionError;
+use crate::inscriptive::rent_manager::rent_charge::RentCharge;
+use crate::inscriptive::rent_manager::rent_policy::RentPolicy;
+use crate::inscriptive::rent_manager::rent_status::RentStatus;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
+use crate::operative::run_args::chain::Chain;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Local storage manager for the rent contracts are charged for their state size and shadow
+/// allocations.
+///
+/// High Level Overview: Contracts are first seen paid up, and charged once every rent period as
+/// batches are applied. A contract that cannot cover its rent is debited whatever balance it has
+/// left and enters a grace window. Paying a period in full clears it, while staying insolvent past
+/// the window tombstones it for good.
+pub struct RentManager {
+    // The rent policy of the chain, if the chain charges rent at all.
+    policy: Option<RentPolicy>,
+
+    // In-memory rent statuses.
+    in_memory_statuses: HashMap<ContractId, RentStatus>,
+
+    // On-disk db for storing the rent statuses.
+    on_disk_statuses: sled::Db,
+
+    // State differences to be applied.
+    delta: RentManagerDelta,
+
+    // Backup of state differences in case of rollback.
+    backup_of_delta: RentManagerDelta,
+}
+
+/// Guarded 'RentManager'.
+#[allow(non_camel_case_types)]
+pub type RENT_MANAGER = Arc<Mutex<RentManager>>;
+
+impl RentManager {
+    pub fn new(
+        chain: Chain,
+        policy: Option<RentPolicy>,
+    ) -> Result<RENT_MANAGER, RentManagerConstructionError> {
+        // 1 Open the rent db.
+        let rent_db_path = format!("storage/{}/rent", chain.to_string());
+        let rent_db =
+            open_db(chain, &rent_db_path).map_err(RentManagerConstructionError::DBOpenError)?;
+
+        // 2 Initialize the in-memory rent statuses.
+        let mut in_memory_statuses = HashMap::<ContractId, RentStatus>::new();
+
+        // 3 Iterate over all items in the rent db to collect the rent statuses.
+        for lookup in rent_db.iter() {
+            // 3.1 Get the key and value.
+            if let Ok((key, val)) = lookup {
+                // 3.1.1 Deserialize the contract id.
+                let contract_id: ContractId = key.as_ref().try_into().map_err(|_| {
+                    RentManagerConstructionError::UnableToDeserializeContractIdBytesFromDBKey(
+                        key.to_vec(),
+                    )
+                })?;
+
+                // 3.1.2 Deserialize the rent status.
+                let rent_status = RentStatus::deserialize(val.as_ref()).ok_or(
+                    RentManagerConstructionError::UnableToDeserializeRentStatusBytesFromDBValue(
+                        key.to_vec(),
+                        val.to_vec(),
+                    ),
+                )?;
+
+                // 3.1.3 Insert the rent status into the in-memory rent statuses.
+                in_memory_statuses.insert(contract_id, rent_status);
+            }
+        }
+
+        // 4 Construct the rent manager.
+        let rent_manager = RentManager {
+            policy,
+            in_memory_statuses,
+            on_disk_statuses: rent_db,
+            delta: RentManagerDelta::fresh_new(),
+            backup_of_delta: RentManagerDelta::fresh_new(),
+        };
+
+        // 5 Guard and return the rent manager.
+        Ok(Arc::new(Mutex::new(rent_manager)))
+    }
+
+    /// Clones the delta into the backup.
+    fn backup_delta(&mut self) {
+        self.backup_of_delta = self.delta.clone();
+    }
+
+    /// Restores the delta from the backup.
+    fn restore_delta(&mut self) {
+        self.delta = self.backup_of_delta.clone();
+    }
+
+    /// Prepares the rent manager prior to each execution.
+    ///
+    /// NOTE: Used by the Engine.
+    pub fn pre_execution(&mut self) {
+        self.backup_delta();
+    }
+
+    /// Returns the rent policy of the chain, if the chain charges rent at all.
+    pub fn policy(&self) -> Option<RentPolicy> {
+        self.policy
+    }
+
+    /// Returns the rent status of a contract, including the epheremal changes.
+    pub fn get_status(&self, contract_id: ContractId) -> Option<RentStatus> {
+        self.delta
+            .updated_statuses
+            .get(&contract_id)
+            .or_else(|| self.in_memory_statuses.get(&contract_id))
+            .copied()
+    }
+
+    /// Returns whether a contract has been tombstoned, including the epheremal changes.
+    pub fn is_tombstoned(&self, contract_id: ContractId) -> bool {
+        self.get_status(contract_id)
+            .map(|rent_status| rent_status.tombstoned)
+            .unwrap_or(false)
+    }
+
+    /// Returns the tombstoned contracts, including the epheremal changes.
+    pub fn tombstoned_contracts(&self) -> Vec<ContractId> {
+        let mut contract_ids: Vec<ContractId> = self
+            .in_memory_statuses
+            .keys()
+            .chain(self.delta.updated_statuses.keys())
+            .filter(|contract_id| self.is_tombstoned(**contract_id))
+            .copied()
+            .collect();
+        contract_ids.sort();
+        contract_ids.dedup();
+        contract_ids
+    }
+
+    /// Charges a contract the rent it owes for the periods elapsed up to the batch height, given
+    /// its state size, number of shadow allocations and balance, and returns the charge to debit
+    /// from its balance, if any.
+    ///
+    /// A contract seen for the first time starts paid up at the batch height.
+    ///
+    /// NOTE: The charge is debited by the caller of this function. These changes are saved with the
+    /// use of the `apply_changes` function.
+    pub fn charge(
+        &mut self,
+        contract_id: ContractId,
+        batch_height: BatchHeight,
+        state_bytes: u64,
+        num_allocs: u64,
+        balance: u64,
+    ) -> Option<RentCharge> {
+        // 1 Nothing to charge if the chain charges no rent.
+        let policy = self.policy.filter(|policy| policy.period > 0)?;
+
+        // 2 Start the contract paid up if it is seen for the first time.
+        let mut rent_status = match self.get_status(contract_id) {
+            Some(rent_status) => rent_status,
+            None => {
+                self.delta
+                    .epheremally_update_status(contract_id, RentStatus::new(batch_height));
+                return None;
+            }
+        };
+
+        // 3 Nothing to charge a tombstoned contract.
+        if rent_status.tombstoned {
+            return None;
+        }
+
+        // 4 Nothing to charge until a whole period has elapsed.
+        let periods = batch_height.saturating_sub(rent_status.paid_up_to) / policy.period;
+        if periods == 0 {
+            return None;
+        }
+
+        // 5 Charge the rent of the elapsed periods, up to the balance.
+        let rent = policy
+            .rent_per_period(state_bytes, num_allocs)
+            .saturating_mul(periods);
+        let charged = rent.min(balance);
+        let shortfall = rent - charged;
+        rent_status.paid_up_to += periods * policy.period;
+
+        // 6 Clear an insolvent contract that paid in full, or tombstone one past the grace window.
+        match shortfall {
+            0 => rent_status.insolvent_since = None,
+            _ => {
+                let insolvent_since = *rent_status.insolvent_since.get_or_insert(batch_height);
+                if batch_height - insolvent_since >= policy.grace_window() {
+                    rent_status.tombstoned = true;
+                }
+            }
+        }
+
+        // 7 Epheremally update the rent status in the delta.
+        self.delta
+            .epheremally_update_status(contract_id, rent_status);
+
+        // 8 Return the charge.
+        Some(RentCharge {
+            contract_id,
+            charged,
+            shortfall,
+            tombstoned: rent_status.tombstoned,
+        })
+    }
+
+    /// Reverts the epheremal changes associated with the last execution.
+    pub fn rollback_last(&mut self) {
+        self.restore_delta();
+    }
+
+    /// Applies the changes to the rent manager.
+    pub fn apply_changes(&mut self) -> Result<(), RentManagerApplyChangesError> {
+        // 1 Apply the updated rent statuses in-memory and on-disk.
+        for (contract_id, rent_status) in self.delta.updated_statuses.iter() {
+            // 1.1 On-disk: Insert the rent status.
+            self.on_disk_statuses
+                .insert(contract_id, rent_status.serialize().unwrap_or_default())
+                .map_err(|e| {
+                    RentManagerApplyChangesError::RentStatusInsertDBError(*contract_id, e)
+                })?;
+
+            // 1.2 In-memory: Insert the rent status.
+            self.in_memory_statuses.insert(*contract_id, *rent_status);
+        }
+
+        // 2 Flush the delta.
+        self.flush_delta();
+
+        // 3 Return success.
+        Ok(())
+    }
+
+    /// Clears all epheremal changes from the delta.
+    pub fn flush_delta(&mut self) {
+        self.delta.flush();
+        self.backup_of_delta.flush();
+    }
+
+    /// Returns the rent manager as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Construct the rent manager JSON object.
+        let mut obj = Map::new();
+
+        // 2 Insert the rent policy.
+        obj.insert(
+            "policy".to_string(),
+            match self.policy {
+                Some(policy) => policy.json(),
+                None => Value::Null,
+            },
+        );
+
+        // 3 Insert the in-memory rent statuses.
+        obj.insert(
+            "statuses".to_string(),
+            Value::Object(
+                self.in_memory_statuses
+                    .iter()
+                    .map(|(contract_id, rent_status)| {
+                        (hex::encode(contract_id), rent_status.json())
+                    })
+                    .collect(),
+            ),
+        );
+
+        // 4 Return the JSON object.
+        Value::Object(obj)
+    }
+}
+
+/// Erases the rent manager by db path.
+pub fn erase_rent_manager(chain: Chain) {
+    // Rent manager db path.
+    let rent_db_path = format!("storage/{}/rent", chain.to_string());
+
+    // Erase the path.
+    erase_db(chain, &rent_db_path);
+}
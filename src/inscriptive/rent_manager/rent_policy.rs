@@ -0,0 +1,62 @@
+use crate::operative::run_args::chain::Chain;
+use serde_json::{Map, Value};
+
+/// The rent contracts are charged for their state size and shadow allocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RentPolicy {
+    /// The number of batches a rent period spans.
+    pub period: u64,
+    /// The rent, in satoshis, per 1000 bytes of state keys and values per period.
+    pub per_kb_state_rent: u64,
+    /// The rent, in satoshis, per shadow allocation per period.
+    pub per_alloc_rent: u64,
+    /// The number of periods an insolvent contract is given to pay up before it is tombstoned.
+    pub grace_periods: u64,
+}
+
+impl RentPolicy {
+    /// Returns the rent policy of the chain, if the chain charges rent at all.
+    pub fn for_chain(chain: Chain) -> Option<RentPolicy> {
+        match chain {
+            Chain::Signet => Some(RentPolicy {
+                period: 144,
+                per_kb_state_rent: 10,
+                per_alloc_rent: 1,
+                grace_periods: 30,
+            }),
+            Chain::Testbed | Chain::Mainnet => None,
+        }
+    }
+
+    /// Returns the rent due for a single period by a contract of the given state size and number
+    /// of shadow allocations.
+    pub fn rent_per_period(&self, state_bytes: u64, num_allocs: u64) -> u64 {
+        let state_rent = (state_bytes as u128 * self.per_kb_state_rent as u128) / 1000;
+        let alloc_rent = num_allocs as u128 * self.per_alloc_rent as u128;
+        (state_rent + alloc_rent).min(u64::MAX as u128) as u64
+    }
+
+    /// Returns the number of batches an insolvent contract is given before it is tombstoned.
+    pub fn grace_window(&self) -> u64 {
+        self.grace_periods.saturating_mul(self.period)
+    }
+
+    /// Returns the rent policy as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("period".to_string(), Value::Number(self.period.into()));
+        obj.insert(
+            "per_kb_state_rent".to_string(),
+            Value::Number(self.per_kb_state_rent.into()),
+        );
+        obj.insert(
+            "per_alloc_rent".to_string(),
+            Value::Number(self.per_alloc_rent.into()),
+        );
+        obj.insert(
+            "grace_periods".to_string(),
+            Value::Number(self.grace_periods.into()),
+        );
+        Value::Object(obj)
+    }
+}
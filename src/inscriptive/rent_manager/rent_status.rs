@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// The rent standing of a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RentStatus {
+    /// The batch height the contract has paid its rent up to.
+    pub paid_up_to: BatchHeight,
+    /// The batch height the contract first failed to cover its rent at, if it is insolvent.
+    pub insolvent_since: Option<BatchHeight>,
+    /// Whether the contract has been tombstoned for staying insolvent past the grace window.
+    pub tombstoned: bool,
+}
+
+impl RentStatus {
+    /// Constructs the rent status of a contract first seen at the given batch height.
+    pub fn new(batch_height: BatchHeight) -> RentStatus {
+        RentStatus {
+            paid_up_to: batch_height,
+            insolvent_since: None,
+            tombstoned: false,
+        }
+    }
+
+    /// Serializes the rent status with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a rent status with bincode.
+    pub fn deserialize(bytes: &[u8]) -> Option<RentStatus> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .ok()
+            .map(|(rent_status, _)| rent_status)
+    }
+
+    /// Returns the rent status as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "paid_up_to".to_string(),
+            Value::Number(self.paid_up_to.into()),
+        );
+        obj.insert(
+            "insolvent_since".to_string(),
+            match self.insolvent_since {
+                Some(batch_height) => Value::Number(batch_height.into()),
+                None => Value::Null,
+            },
+        );
+        obj.insert("tombstoned".to_string(), Value::Bool(self.tombstoned));
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// A call scheduled by an account to execute once the chain reaches a future batch height.
+///
+/// The fees for the whole ops budget are prepaid by the scheduling account when the call is
+/// scheduled, and whatever the call does not spend is refunded once it executes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeferredCall {
+    /// The account scheduling, prepaying and making the call.
+    pub scheduler: [u8; 32],
+    /// The called contract.
+    pub contract_id: [u8; 32],
+    /// The called method index.
+    pub method_index: u16,
+    /// The call args, as stack items.
+    pub args: Vec<Vec<u8>>,
+    /// The ops budget of the call.
+    pub ops_budget: u32,
+    /// The fees prepaid for the whole ops budget.
+    pub prepaid_fees: u64,
+    /// The batch height at which the call executes.
+    pub execute_at: BatchHeight,
+    /// The batch height at which the call was scheduled.
+    pub scheduled_at: BatchHeight,
+}
+
+impl DeferredCall {
+    /// Returns the id of the deferred call.
+    pub fn id(&self) -> [u8; 32] {
+        self.serialize()
+            .unwrap_or_default()
+            .hash(Some(HashTag::DeferredCallID))
+    }
+
+    /// Serializes the deferred call with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a deferred call with bincode.
+    pub fn deserialize(bytes: &[u8]) -> Option<DeferredCall> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .ok()
+            .map(|(deferred_call, _)| deferred_call)
+    }
+
+    /// Returns the deferred call as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::String(hex::encode(self.id())));
+        obj.insert(
+            "scheduler".to_string(),
+            Value::String(hex::encode(self.scheduler)),
+        );
+        obj.insert(
+            "contract_id".to_string(),
+            Value::String(hex::encode(self.contract_id)),
+        );
+        obj.insert(
+            "method_index".to_string(),
+            Value::Number(self.method_index.into()),
+        );
+        obj.insert(
+            "args".to_string(),
+            Value::Array(
+                self.args
+                    .iter()
+                    .map(|arg| Value::String(hex::encode(arg)))
+                    .collect(),
+            ),
+        );
+        obj.insert(
+            "ops_budget".to_string(),
+            Value::Number(self.ops_budget.into()),
+        );
+        obj.insert(
+            "prepaid_fees".to_string(),
+            Value::Number(self.prepaid_fees.into()),
+        );
+        obj.insert(
+            "execute_at".to_string(),
+            Value::Number(self.execute_at.into()),
+        );
+        obj.insert(
+            "scheduled_at".to_string(),
+            Value::Number(self.scheduled_at.into()),
+        );
+        Value::Object(obj)
+    }
+}
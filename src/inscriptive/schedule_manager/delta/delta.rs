@@ -0,0 +1,45 @@
+use crate::inscriptive::schedule_manager::deferred_call::DeferredCall;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Deferred calls keyed by their batch height to execute at, and their id.
+type ScheduleKey = (u64, [u8; 32]);
+
+/// A struct for containing epheremal state differences to be applied for 'ScheduleManager'.
+#[derive(Clone)]
+pub struct ScheduleManagerDelta {
+    // Deferred calls to be scheduled.
+    pub calls_to_schedule: BTreeMap<ScheduleKey, DeferredCall>,
+
+    // Scheduled deferred calls to be removed, either cancelled or drained.
+    pub calls_to_remove: BTreeSet<ScheduleKey>,
+}
+
+impl ScheduleManagerDelta {
+    /// Constructs a fresh new schedule manager delta.
+    pub fn fresh_new() -> Self {
+        Self {
+            calls_to_schedule: BTreeMap::new(),
+            calls_to_remove: BTreeSet::new(),
+        }
+    }
+
+    /// Clears all values.
+    pub fn flush(&mut self) {
+        self.calls_to_schedule.clear();
+        self.calls_to_remove.clear();
+    }
+
+    /// Epheremally schedules a deferred call.
+    pub fn epheremally_schedule_call(&mut self, key: ScheduleKey, deferred_call: DeferredCall) {
+        self.calls_to_schedule.insert(key, deferred_call);
+    }
+
+    /// Epheremally removes a deferred call.
+    ///
+    /// A call scheduled within the same delta is dropped rather than marked for removal.
+    pub fn epheremally_remove_call(&mut self, key: ScheduleKey) {
+        if self.calls_to_schedule.remove(&key).is_none() {
+            self.calls_to_remove.insert(key);
+        }
+    }
+}
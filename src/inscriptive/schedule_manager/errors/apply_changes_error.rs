@@ -0,0 +1,15 @@
+/// Deferred call id.
+type DeferredCallId = [u8; 32];
+
+/// Errors associated with applying changes to the `ScheduleManager`.
+#[derive(Debug, Clone)]
+pub enum ScheduleManagerApplyChangesError {
+    /// Error when serializing a deferred call.
+    DeferredCallSerializeError(DeferredCallId),
+
+    /// Error when inserting a deferred call on disk.
+    DeferredCallInsertDBError(DeferredCallId, sled::Error),
+
+    /// Error when removing a deferred call on disk.
+    DeferredCallRemoveDBError(DeferredCallId, sled::Error),
+}
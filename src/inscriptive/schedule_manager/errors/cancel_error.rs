@@ -0,0 +1,14 @@
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Deferred call id.
+type DeferredCallId = [u8; 32];
+
+/// Errors associated with cancelling a deferred call.
+#[derive(Debug, Clone)]
+pub enum ScheduleManagerCancelError {
+    /// The call is not scheduled, or has already been cancelled or executed.
+    DeferredCallNotFound(DeferredCallId),
+    /// The account cancelling the call is not the one that scheduled it.
+    NotTheScheduler(DeferredCallId, AccountKey),
+}
@@ -0,0 +1,7 @@
+/// Errors associated with constructing the `ScheduleManager`.
+#[derive(Debug, Clone)]
+pub enum ScheduleManagerConstructionError {
+    DBOpenError(sled::Error),
+    UnableToDeserializeScheduleKeyBytesFromDBKey(Vec<u8>),
+    UnableToDeserializeDeferredCallBytesFromDBValue(Vec<u8>, Vec<u8>),
+}
@@ -0,0 +1,4 @@
+pub mod apply_changes_error;
+pub mod cancel_error;
+pub mod construction_error;
+pub mod schedule_error;
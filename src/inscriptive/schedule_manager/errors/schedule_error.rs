@@ -0,0 +1,20 @@
+/// Batch height.
+type BatchHeight = u64;
+
+/// Deferred call id.
+type DeferredCallId = [u8; 32];
+
+/// Errors associated with scheduling a deferred call.
+#[derive(Debug, Clone)]
+pub enum ScheduleManagerScheduleError {
+    /// The call is not scheduled after the current batch height.
+    ExecutionHeightNotInTheFuture(BatchHeight, BatchHeight),
+    /// The call is scheduled further ahead than the schedule horizon.
+    ExecutionHeightBeyondHorizon(BatchHeight, BatchHeight),
+    /// The batch height has as many deferred calls scheduled as it can hold.
+    BatchHeightScheduleFull(BatchHeight),
+    /// The call has no ops budget.
+    ZeroOpsBudget,
+    /// The same call is already scheduled.
+    DeferredCallAlreadyScheduled(DeferredCallId),
+}
@@ -0,0 +1,4 @@
+pub mod deferred_call;
+pub mod delta;
+pub mod errors;
+pub mod schedule_manager;
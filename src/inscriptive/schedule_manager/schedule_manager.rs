@@ -0,0 +1,375 @@
+use crate::inscriptive::schedule_manager::deferred_call::DeferredCall;
+use crate::inscriptive::schedule_manager::delta::delta::ScheduleManagerDelta;
+use crate::inscriptive::schedule_manager::errors::apply_changes_error::ScheduleManagerApplyChangesError;
+use crate::inscriptive::schedule_manager::errors::cancel_error::ScheduleManagerCancelError;
+use crate::inscriptive::schedule_manager::errors::construction_error::ScheduleManagerConstructionError;
+use crate::inscriptive::schedule_manager::errors::schedule_error::ScheduleManagerScheduleError;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
+use crate::operative::run_args::chain::Chain;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Deferred call id.
+type DeferredCallId = [u8; 32];
+
+/// Deferred calls keyed by their batch height to execute at, and their id.
+type ScheduleKey = (BatchHeight, DeferredCallId);
+
+/// How many batches ahead a call may be scheduled at most.
+pub const MAX_SCHEDULE_HORIZON: u64 = 4320;
+
+/// How many deferred calls may be scheduled at the same batch height at most.
+pub const MAX_DEFERRED_CALLS_PER_HEIGHT: usize = 64;
+
+/// Local storage manager for the calls scheduled to execute at a future batch height.
+///
+/// High Level Overview: Deferred calls are queued by the batch height they execute at, and drained
+/// by the Engine in height and id order as batches are applied. A call can be cancelled by the
+/// account that scheduled it until it is drained.
+pub struct ScheduleManager {
+    // In-memory deferred calls.
+    in_memory_deferred_calls: BTreeMap<ScheduleKey, DeferredCall>,
+
+    // On-disk db for storing the deferred calls.
+    on_disk_deferred_calls: sled::Db,
+
+    // State differences to be applied.
+    delta: ScheduleManagerDelta,
+
+    // Backup of state differences in case of rollback.
+    backup_of_delta: ScheduleManagerDelta,
+}
+
+/// Guarded 'ScheduleManager'.
+#[allow(non_camel_case_types)]
+pub type SCHEDULE_MANAGER = Arc<Mutex<ScheduleManager>>;
+
+impl ScheduleManager {
+    pub fn new(chain: Chain) -> Result<SCHEDULE_MANAGER, ScheduleManagerConstructionError> {
+        // 1 Open the schedule db.
+        let schedule_db_path = format!("storage/{}/schedule", chain.to_string());
+        let schedule_db = open_db(chain, &schedule_db_path)
+            .map_err(ScheduleManagerConstructionError::DBOpenError)?;
+
+        // 2 Initialize the in-memory deferred calls.
+        let mut in_memory_deferred_calls = BTreeMap::<ScheduleKey, DeferredCall>::new();
+
+        // 3 Iterate over all items in the schedule db to collect the deferred calls.
+        for lookup in schedule_db.iter() {
+            // 3.1 Get the key and value.
+            if let Ok((key, val)) = lookup {
+                // 3.1.1 Deserialize the schedule key.
+                let schedule_key = schedule_key_from_bytes(key.as_ref()).ok_or(
+                    ScheduleManagerConstructionError::UnableToDeserializeScheduleKeyBytesFromDBKey(
+                        key.to_vec(),
+                    ),
+                )?;
+
+                // 3.1.2 Deserialize the deferred call.
+                let deferred_call = DeferredCall::deserialize(val.as_ref()).ok_or(
+                    ScheduleManagerConstructionError::UnableToDeserializeDeferredCallBytesFromDBValue(
+                        key.to_vec(),
+                        val.to_vec(),
+                    ),
+                )?;
+
+                // 3.1.3 Insert the deferred call into the in-memory deferred calls.
+                in_memory_deferred_calls.insert(schedule_key, deferred_call);
+            }
+        }
+
+        // 4 Construct the schedule manager.
+        let schedule_manager = ScheduleManager {
+            in_memory_deferred_calls,
+            on_disk_deferred_calls: schedule_db,
+            delta: ScheduleManagerDelta::fresh_new(),
+            backup_of_delta: ScheduleManagerDelta::fresh_new(),
+        };
+
+        // 5 Guard the schedule manager.
+        let schedule_manager = Arc::new(Mutex::new(schedule_manager));
+
+        // 6 Return the schedule manager.
+        Ok(schedule_manager)
+    }
+
+    /// Clones the delta into the backup.
+    fn backup_delta(&mut self) {
+        self.backup_of_delta = self.delta.clone();
+    }
+
+    /// Restores the delta from the backup.
+    fn restore_delta(&mut self) {
+        self.delta = self.backup_of_delta.clone();
+    }
+
+    /// Prepares the schedule manager prior to each execution.
+    ///
+    /// NOTE: Used by the Engine.
+    pub fn pre_execution(&mut self) {
+        self.backup_delta();
+    }
+
+    /// Returns the deferred calls scheduled at or before the given batch height, including the
+    /// epheremal changes, in height and id order.
+    pub fn deferred_calls_until(&self, batch_height: BatchHeight) -> Vec<DeferredCall> {
+        // 1 Merge the permanent and the epheremally scheduled calls up to the batch height.
+        let mut deferred_calls = BTreeMap::<&ScheduleKey, &DeferredCall>::new();
+        deferred_calls.extend(
+            self.in_memory_deferred_calls
+                .range(..=(batch_height, [0xff; 32])),
+        );
+        deferred_calls.extend(
+            self.delta
+                .calls_to_schedule
+                .range(..=(batch_height, [0xff; 32])),
+        );
+
+        // 2 Leave out the epheremally removed calls.
+        deferred_calls
+            .into_iter()
+            .filter(|(key, _)| !self.delta.calls_to_remove.contains(key))
+            .map(|(_, deferred_call)| deferred_call.clone())
+            .collect()
+    }
+
+    /// Returns the scheduled deferred call by its id, including the epheremal changes.
+    pub fn get_deferred_call(&self, deferred_call_id: DeferredCallId) -> Option<DeferredCall> {
+        self.find(deferred_call_id)
+            .map(|(_, deferred_call)| deferred_call)
+    }
+
+    /// Returns the schedule key and the deferred call by its id, including the epheremal changes.
+    fn find(&self, deferred_call_id: DeferredCallId) -> Option<(ScheduleKey, DeferredCall)> {
+        self.in_memory_deferred_calls
+            .iter()
+            .chain(self.delta.calls_to_schedule.iter())
+            .find(|(key, _)| key.1 == deferred_call_id && !self.delta.calls_to_remove.contains(key))
+            .map(|(key, deferred_call)| (*key, deferred_call.clone()))
+    }
+
+    /// Returns the number of deferred calls scheduled at the batch height, including the epheremal
+    /// changes.
+    fn deferred_calls_count_at(&self, batch_height: BatchHeight) -> usize {
+        let range = (batch_height, [0x00; 32])..=(batch_height, [0xff; 32]);
+        let permanent = self
+            .in_memory_deferred_calls
+            .range(range.clone())
+            .filter(|(key, _)| !self.delta.calls_to_remove.contains(key))
+            .count();
+        let epheremal = self.delta.calls_to_schedule.range(range).count();
+        permanent + epheremal
+    }
+
+    /// Schedules a deferred call to execute at its batch height, and returns its id.
+    ///
+    /// NOTE: The fees are prepaid by the caller of this function. These changes are saved with the
+    /// use of the `apply_changes` function.
+    pub fn schedule(
+        &mut self,
+        deferred_call: DeferredCall,
+        current_batch_height: BatchHeight,
+    ) -> Result<DeferredCallId, ScheduleManagerScheduleError> {
+        let execute_at = deferred_call.execute_at;
+
+        // 1 Check the call executes after the current batch height.
+        if execute_at <= current_batch_height {
+            return Err(ScheduleManagerScheduleError::ExecutionHeightNotInTheFuture(
+                current_batch_height,
+                execute_at,
+            ));
+        }
+
+        // 2 Check the call executes within the schedule horizon.
+        if execute_at - current_batch_height > MAX_SCHEDULE_HORIZON {
+            return Err(ScheduleManagerScheduleError::ExecutionHeightBeyondHorizon(
+                current_batch_height,
+                execute_at,
+            ));
+        }
+
+        // 3 Check the call has an ops budget to prepay.
+        if deferred_call.ops_budget == 0 {
+            return Err(ScheduleManagerScheduleError::ZeroOpsBudget);
+        }
+
+        // 4 Check the same call is not already scheduled.
+        let deferred_call_id = deferred_call.id();
+        if self.find(deferred_call_id).is_some() {
+            return Err(ScheduleManagerScheduleError::DeferredCallAlreadyScheduled(
+                deferred_call_id,
+            ));
+        }
+
+        // 5 Check the batch height has room for another call.
+        if self.deferred_calls_count_at(execute_at) >= MAX_DEFERRED_CALLS_PER_HEIGHT {
+            return Err(ScheduleManagerScheduleError::BatchHeightScheduleFull(
+                execute_at,
+            ));
+        }
+
+        // 6 Epheremally schedule the call in the delta.
+        self.delta
+            .epheremally_schedule_call((execute_at, deferred_call_id), deferred_call);
+
+        // 7 Return the deferred call id.
+        Ok(deferred_call_id)
+    }
+
+    /// Cancels a deferred call on behalf of the account that scheduled it, and returns the call so
+    /// that its prepaid fees can be refunded.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn cancel(
+        &mut self,
+        deferred_call_id: DeferredCallId,
+        account_key: [u8; 32],
+    ) -> Result<DeferredCall, ScheduleManagerCancelError> {
+        // 1 Get the deferred call.
+        let (key, deferred_call) =
+            self.find(deferred_call_id)
+                .ok_or(ScheduleManagerCancelError::DeferredCallNotFound(
+                    deferred_call_id,
+                ))?;
+
+        // 2 Check the account is the scheduler.
+        if deferred_call.scheduler != account_key {
+            return Err(ScheduleManagerCancelError::NotTheScheduler(
+                deferred_call_id,
+                account_key,
+            ));
+        }
+
+        // 3 Epheremally remove the call in the delta.
+        self.delta.epheremally_remove_call(key);
+
+        // 4 Return the cancelled call.
+        Ok(deferred_call)
+    }
+
+    /// Drains the deferred calls due at or before the given batch height, in height and id order.
+    ///
+    /// NOTE: These changes are saved with the use of the `apply_changes` function.
+    pub fn drain_due(&mut self, batch_height: BatchHeight) -> Vec<DeferredCall> {
+        // 1 Collect the due calls.
+        let due_calls = self.deferred_calls_until(batch_height);
+
+        // 2 Epheremally remove them in the delta.
+        for deferred_call in due_calls.iter() {
+            self.delta
+                .epheremally_remove_call((deferred_call.execute_at, deferred_call.id()));
+        }
+
+        // 3 Return the due calls.
+        due_calls
+    }
+
+    /// Reverts the epheremal changes associated with the last execution.
+    pub fn rollback_last(&mut self) {
+        self.restore_delta();
+    }
+
+    /// Applies the changes to the schedule manager.
+    ///
+    /// This persists the delta to both in-memory and on-disk storage:
+    /// - Removals: Removes the cancelled and drained calls.
+    /// - Schedules: Adds the calls from `calls_to_schedule`.
+    pub fn apply_changes(&mut self) -> Result<(), ScheduleManagerApplyChangesError> {
+        // 1 Apply removals in-memory and on-disk.
+        for key in self.delta.calls_to_remove.iter() {
+            // 1.1 On-disk: Remove the deferred call.
+            self.on_disk_deferred_calls
+                .remove(schedule_key_to_bytes(key))
+                .map_err(|e| {
+                    ScheduleManagerApplyChangesError::DeferredCallRemoveDBError(key.1, e)
+                })?;
+
+            // 1.2 In-memory: Remove the deferred call.
+            self.in_memory_deferred_calls.remove(key);
+        }
+
+        // 2 Apply schedules in-memory and on-disk.
+        for (key, deferred_call) in self.delta.calls_to_schedule.iter() {
+            // 2.1 Serialize the deferred call.
+            let deferred_call_bytes = deferred_call
+                .serialize()
+                .ok_or(ScheduleManagerApplyChangesError::DeferredCallSerializeError(key.1))?;
+
+            // 2.2 On-disk: Insert the deferred call.
+            self.on_disk_deferred_calls
+                .insert(schedule_key_to_bytes(key), deferred_call_bytes)
+                .map_err(|e| {
+                    ScheduleManagerApplyChangesError::DeferredCallInsertDBError(key.1, e)
+                })?;
+
+            // 2.3 In-memory: Insert the deferred call.
+            self.in_memory_deferred_calls
+                .insert(*key, deferred_call.clone());
+        }
+
+        // 3 Flush the delta.
+        self.flush_delta();
+
+        // 4 Return success.
+        Ok(())
+    }
+
+    /// Clears all epheremal changes from the delta.
+    pub fn flush_delta(&mut self) {
+        self.delta.flush();
+        self.backup_of_delta.flush();
+    }
+
+    /// Returns the schedule manager as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Construct the schedule manager JSON object.
+        let mut obj = Map::new();
+
+        // 2 Insert the in-memory deferred calls.
+        obj.insert(
+            "deferred_calls".to_string(),
+            Value::Array(
+                self.in_memory_deferred_calls
+                    .values()
+                    .map(|deferred_call| deferred_call.json())
+                    .collect(),
+            ),
+        );
+
+        // 3 Return the JSON object.
+        Value::Object(obj)
+    }
+}
+
+/// Returns the on-disk key of a deferred call: its batch height in big-endian followed by its id,
+/// so that the db iterates in execution order.
+fn schedule_key_to_bytes(key: &ScheduleKey) -> Vec<u8> {
+    let mut bytes = Vec::<u8>::with_capacity(40);
+    bytes.extend(key.0.to_be_bytes());
+    bytes.extend(key.1);
+    bytes
+}
+
+/// Returns the schedule key from its on-disk bytes.
+fn schedule_key_from_bytes(bytes: &[u8]) -> Option<ScheduleKey> {
+    if bytes.len() != 40 {
+        return None;
+    }
+    let batch_height = u64::from_be_bytes(bytes[0..8].try_into().ok()?);
+    let deferred_call_id: [u8; 32] = bytes[8..40].try_into().ok()?;
+    Some((batch_height, deferred_call_id))
+}
+
+/// Erases the schedule manager by db path.
+pub fn erase_schedule_manager(chain: Chain) {
+    // Schedule manager db path.
+    let schedule_db_path = format!("storage/{}/schedule", chain.to_string());
+
+    // Erase the path.
+    erase_db(chain, &schedule_db_path);
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Contract ID.
 type ContractId = [u8; 32];
@@ -39,6 +39,34 @@ impl SMDelta {
         self.removed_contract_states.clear();
     }
 
+    /// Returns the delta encoded in a canonical order, so that two equal deltas encode to the same
+    /// bytes regardless of their hash map iteration order.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        // 1 Sort the new or updated states by contract id and state key.
+        let new_or_updated_contract_states = self
+            .new_or_updated_contract_states
+            .iter()
+            .map(|(contract_id, states)| (*contract_id, states.iter().collect::<BTreeMap<_, _>>()))
+            .collect::<BTreeMap<_, _>>();
+
+        // 2 Sort the removed states by contract id, keeping the removal order.
+        let removed_contract_states = self
+            .removed_contract_states
+            .iter()
+            .collect::<BTreeMap<_, _>>();
+
+        // 3 Encode the delta.
+        bincode::serde::encode_to_vec(
+            (
+                &self.new_contracts_to_register,
+                new_or_updated_contract_states,
+                removed_contract_states,
+            ),
+            bincode::config::standard(),
+        )
+        .unwrap_or_default()
+    }
+
     /// Checks if a contract has just been epheremally registered in the delta.
     pub fn is_contract_epheremally_registered(&self, contract_id: ContractId) -> bool {
         self.new_contracts_to_register.contains(&contract_id)
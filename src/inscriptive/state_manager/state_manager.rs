@@ -2,9 +2,12 @@ use super::delta::delta::SMDelta;
 use super::errors::construction_error::SMConstructionError;
 use super::errors::insert_update_state_error::SMInsertUpdateStateError;
 use super::errors::register_error::SMRegisterContractError;
+use crate::inscriptive::memory_budget::memory_budget::record_memory_usage;
+use crate::inscriptive::memory_budget::memory_footprint::{BudgetedManager, MemoryFootprint};
 use crate::inscriptive::state_manager::errors::apply_changes_error::SMApplyChangesError;
 use crate::inscriptive::state_manager::errors::remove_state_error::SMRemoveStateError;
 use crate::inscriptive::state_manager::state_holder::state_holder::SMContractStateHolder;
+use crate::inscriptive::storage::storage::{erase_db, open_db};
 use crate::operative::run_args::chain::Chain;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
@@ -44,7 +47,8 @@ impl StateManager {
     pub fn new(chain: Chain) -> Result<STATE_MANAGER, SMConstructionError> {
         // 1 Open the states db.
         let states_db_path = format!("storage/{}/states", chain.to_string());
-        let states_db = sled::open(states_db_path).map_err(SMConstructionError::DBOpenError)?;
+        let states_db =
+            open_db(chain, &states_db_path).map_err(SMConstructionError::DBOpenError)?;
 
         // 2 Initialize the in-memory states.
         let mut in_memory_states = HashMap::<ContractId, SMContractStateHolder>::new();
@@ -87,10 +91,16 @@ impl StateManager {
             backup_of_delta: SMDelta::fresh_new(),
         };
 
-        // 5 Guard the state manager.
+        // 5 Record the memory footprint of the state manager.
+        record_memory_usage(
+            BudgetedManager::StateManager,
+            state_manager.memory_footprint(),
+        );
+
+        // 6 Guard the state manager.
         let guarded_state_manager = Arc::new(Mutex::new(state_manager));
 
-        // 6 Return the guarded state manager.
+        // 7 Return the guarded state manager.
         Ok(guarded_state_manager)
     }
 
@@ -112,6 +122,22 @@ impl StateManager {
         self.backup_delta();
     }
 
+    /// Returns the epheremal changes in the delta, canonically encoded.
+    pub fn delta_bytes(&self) -> Vec<u8> {
+        self.delta.canonical_bytes()
+    }
+
+    /// Returns a copy of the delta and its backup, so that an execution can be undone without
+    /// touching the backup.
+    pub fn delta_checkpoint(&self) -> (SMDelta, SMDelta) {
+        (self.delta.clone(), self.backup_of_delta.clone())
+    }
+
+    /// Restores the delta and its backup from a checkpoint.
+    pub fn restore_delta_checkpoint(&mut self, checkpoint: (SMDelta, SMDelta)) {
+        (self.delta, self.backup_of_delta) = checkpoint;
+    }
+
     /// Checks if a contract is permanently registered.
     pub fn is_contract_registered(&self, contract_id: ContractId) -> bool {
         self.in_memory_states.contains_key(&contract_id)
@@ -135,6 +161,39 @@ impl StateManager {
             .get_state_value(key)
     }
 
+    /// Returns the size in bytes of the state keys and values of a contract, including the
+    /// epheremal changes.
+    pub fn get_contract_state_size(&self, contract_id: ContractId) -> u64 {
+        // 1 Get the epheremally inserted or updated states.
+        let epheremal_states = self.delta.new_or_updated_contract_states.get(&contract_id);
+
+        // 2 Count the permanent states that are neither removed nor overwritten in the delta.
+        let permanent_size: u64 = match self.in_memory_states.get(&contract_id) {
+            Some(state_holder) => state_holder
+                .states
+                .iter()
+                .filter(|(key, _)| {
+                    !self.delta.is_state_epheremally_removed(contract_id, key)
+                        && !epheremal_states.is_some_and(|states| states.contains_key(*key))
+                })
+                .map(|(key, value)| (key.len() + value.len()) as u64)
+                .sum(),
+            None => 0,
+        };
+
+        // 3 Count the epheremally inserted or updated states.
+        let epheremal_size: u64 = match epheremal_states {
+            Some(states) => states
+                .iter()
+                .map(|(key, value)| (key.len() + value.len()) as u64)
+                .sum(),
+            None => 0,
+        };
+
+        // 4 Return the total size.
+        permanent_size + epheremal_size
+    }
+
     /// Registers a new contract.
     ///
     /// NOTE: These changes are saved with the use of the `apply_changes` function.
@@ -345,7 +404,10 @@ impl StateManager {
             }
         }
 
-        // 4 Return the result.
+        // 4 Record the new memory footprint.
+        record_memory_usage(BudgetedManager::StateManager, self.memory_footprint());
+
+        // 5 Return the result.
         Ok(())
     }
 
@@ -381,11 +443,32 @@ impl StateManager {
     }
 }
 
+impl MemoryFootprint for StateManager {
+    fn memory_footprint(&self) -> u64 {
+        self.in_memory_states
+            .values()
+            .map(|state_holder| {
+                size_of::<ContractId>()
+                    + state_holder
+                        .states
+                        .iter()
+                        .map(|(key, value)| {
+                            size_of::<StateKey>()
+                                + size_of::<StateValue>()
+                                + key.len()
+                                + value.len()
+                        })
+                        .sum::<usize>()
+            })
+            .sum::<usize>() as u64
+    }
+}
+
 /// Erases the state manager by db path.
 pub fn erase_state_manager(chain: Chain) {
     // States db path.
     let states_db_path = format!("storage/{}/states", chain.to_string());
 
     // Erase the path.
-    let _ = std::fs::remove_dir_all(states_db_path);
+    erase_db(chain, &states_db_path);
 }
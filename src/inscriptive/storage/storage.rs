@@ -0,0 +1,103 @@
+use crate::inscriptive::storage::storage_mode::StorageMode;
+use crate::operative::run_args::chain::Chain;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// The storage mode of the Testbed chain, read from the environment on first use.
+static TESTBED_STORAGE_MODE: OnceLock<StorageMode> = OnceLock::new();
+
+/// The in-memory dbs, by db path.
+///
+/// A db is kept here until it is erased, so that a manager constructed again within the process
+/// finds what was applied before, as it would on disk.
+static IN_MEMORY_DBS: OnceLock<Mutex<HashMap<String, sled::Db>>> = OnceLock::new();
+
+thread_local! {
+    /// The namespace the in-memory dbs opened or erased on this thread are kept under, if any.
+    static STORAGE_NAMESPACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs the given closure with the in-memory dbs it opens or erases kept under the namespace.
+///
+/// This lets several instances of the same managers live side by side in one process, such as the
+/// nodes of a simulation. It has no effect on the dbs kept on disk.
+pub fn with_storage_namespace<T>(namespace: &str, f: impl FnOnce() -> T) -> T {
+    // 1 Set the namespace, keeping the enclosing one.
+    let enclosing_namespace =
+        STORAGE_NAMESPACE.with(|cell| cell.replace(Some(namespace.to_string())));
+
+    // 2 Run the closure.
+    let result = f();
+
+    // 3 Restore the enclosing namespace.
+    STORAGE_NAMESPACE.with(|cell| *cell.borrow_mut() = enclosing_namespace);
+
+    result
+}
+
+/// Returns the key an in-memory db is kept by.
+fn in_memory_key(db_path: &str) -> String {
+    STORAGE_NAMESPACE.with(|cell| match cell.borrow().as_ref() {
+        Some(namespace) => format!("{}/{}", namespace, db_path),
+        None => db_path.to_string(),
+    })
+}
+
+/// Returns where the dbs of the chain are kept.
+///
+/// Only the Testbed chain can be kept in memory; other chains are always kept on disk.
+pub fn storage_mode(chain: Chain) -> StorageMode {
+    match chain {
+        Chain::Testbed => *TESTBED_STORAGE_MODE.get_or_init(StorageMode::from_env),
+        Chain::Signet | Chain::Mainnet => StorageMode::OnDisk,
+    }
+}
+
+/// Keeps the Testbed chain in memory for the rest of the process, unless where it is kept was
+/// already read, and returns where it is kept.
+pub fn keep_testbed_in_memory() -> StorageMode {
+    *TESTBED_STORAGE_MODE.get_or_init(|| StorageMode::InMemory)
+}
+
+/// Opens the db at the given path, or its in-memory counterpart if the chain is kept in memory.
+pub fn open_db(chain: Chain, db_path: &str) -> Result<sled::Db, sled::Error> {
+    match storage_mode(chain) {
+        // 1 On-disk dbs are opened at their path.
+        StorageMode::OnDisk => sled::open(db_path),
+
+        // 2 In-memory dbs are opened once as temporary dbs, and shared from then on.
+        StorageMode::InMemory => {
+            let mut in_memory_dbs = IN_MEMORY_DBS
+                .get_or_init(|| Mutex::new(HashMap::new()))
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let key = in_memory_key(db_path);
+            if let Some(db) = in_memory_dbs.get(&key) {
+                return Ok(db.clone());
+            }
+            let db = sled::Config::new().temporary(true).open()?;
+            in_memory_dbs.insert(key, db.clone());
+            Ok(db)
+        }
+    }
+}
+
+/// Erases the db at the given path, or its in-memory counterpart if the chain is kept in memory.
+///
+/// NOTE: An in-memory db is released once the managers holding it are dropped.
+pub fn erase_db(chain: Chain, db_path: &str) {
+    match storage_mode(chain) {
+        StorageMode::OnDisk => {
+            let _ = std::fs::remove_dir_all(db_path);
+        }
+        StorageMode::InMemory => {
+            if let Some(in_memory_dbs) = IN_MEMORY_DBS.get() {
+                in_memory_dbs
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .remove(&in_memory_key(db_path));
+            }
+        }
+    }
+}
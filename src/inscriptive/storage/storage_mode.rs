@@ -0,0 +1,21 @@
+/// Where the managers keep their dbs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StorageMode {
+    /// On-disk dbs under `storage/<chain>`.
+    OnDisk,
+    /// Temporary dbs, kept for the lifetime of the process and never written under `storage`.
+    InMemory,
+}
+
+impl StorageMode {
+    /// Returns the storage mode of the Testbed chain set in the environment.
+    ///
+    /// `CUBE_TESTBED_STORAGE=memory` keeps the Testbed chain in memory, and it is kept on disk
+    /// otherwise.
+    pub fn from_env() -> StorageMode {
+        match std::env::var("CUBE_TESTBED_STORAGE") {
+            Ok(value) if value.trim().eq_ignore_ascii_case("memory") => StorageMode::InMemory,
+            _ => StorageMode::OnDisk,
+        }
+    }
+}
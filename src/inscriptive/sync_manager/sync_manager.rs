@@ -1,6 +1,11 @@
+#[cfg(feature = "fault-injection")]
+use crate::inscriptive::fault_injection::{fault_injection::fault_point, fault_point::FaultPoint};
 use crate::{
     constructive::txout_types::payload::payload::{genesis_payload, Payload},
-    inscriptive::sync_manager::errors::construction_error::SMConstructionError,
+    inscriptive::{
+        storage::storage::{erase_db, open_db},
+        sync_manager::errors::construction_error::SMConstructionError,
+    },
     operative::run_args::chain::Chain,
 };
 use bitcoin::hashes::Hash;
@@ -21,6 +26,15 @@ pub struct SyncManager {
     // Payload tip.
     payload_tip: Payload,
 
+    // Batch height whose changes are being committed, if a commit is in progress.
+    pending_commit_batch_height: Option<u64>,
+
+    // Account balances state root at the cube batch sync height tip.
+    committed_state_root: Option<[u8; 32]>,
+
+    // Batch height the archive is being backfilled up to, if a backfill is in progress.
+    backfill_target_batch_height: Option<u64>,
+
     // In-storage db.
     db: sled::Db,
 }
@@ -33,7 +47,7 @@ impl SyncManager {
     pub fn new(chain: Chain) -> Result<SYNC_MANAGER, SMConstructionError> {
         // 1 Open the sync manager db.
         let db_path = format!("storage/{}/sync_manager", chain.to_string());
-        let db = sled::open(db_path).map_err(SMConstructionError::DBOpenError)?;
+        let db = open_db(chain, &db_path).map_err(SMConstructionError::DBOpenError)?;
 
         // 2 Get the bitcoin sync height tip from the db.
         let bitcoin_sync_height_tip: u64 = db
@@ -61,19 +75,43 @@ impl SyncManager {
                 .unwrap_or_else(|| genesis_payload(chain))
         };
 
-        // 5 Construct the sync manager.
+        // 5 Get the pending commit batch height from the db, if a commit was left incomplete.
+        let pending_commit_batch_height: Option<u64> = db
+            .get(b"pending_commit_batch_height")
+            .ok()
+            .flatten()
+            .and_then(|val| val.as_ref().try_into().ok().map(u64::from_be_bytes));
+
+        // 6 Get the committed state root from the db.
+        let committed_state_root: Option<[u8; 32]> = db
+            .get(b"committed_state_root")
+            .ok()
+            .flatten()
+            .and_then(|val| val.as_ref().try_into().ok());
+
+        // 7 Get the backfill target batch height from the db, if a backfill was left incomplete.
+        let backfill_target_batch_height: Option<u64> = db
+            .get(b"backfill_target_batch_height")
+            .ok()
+            .flatten()
+            .and_then(|val| val.as_ref().try_into().ok().map(u64::from_be_bytes));
+
+        // 8 Construct the sync manager.
         let sync_manager = SyncManager {
             synced: false,
             bitcoin_sync_height_tip,
             cube_batch_sync_height_tip,
             payload_tip,
+            pending_commit_batch_height,
+            committed_state_root,
+            backfill_target_batch_height,
             db,
         };
 
-        // 6 Guard the sync manager.
+        // 9 Guard the sync manager.
         let sync_manager = Arc::new(Mutex::new(sync_manager));
 
-        // 7 Return the sync manager.
+        // 10 Return the sync manager.
         Ok(sync_manager)
     }
 
@@ -112,6 +150,21 @@ impl SyncManager {
         self.payload_tip.clone()
     }
 
+    /// Returns the batch height whose commit was left incomplete, if any.
+    pub fn pending_commit_batch_height(&self) -> Option<u64> {
+        self.pending_commit_batch_height
+    }
+
+    /// Returns the account balances state root at the cube batch sync height tip, if recorded.
+    pub fn committed_state_root(&self) -> Option<[u8; 32]> {
+        self.committed_state_root
+    }
+
+    /// Returns the batch height the archive is being backfilled up to, if a backfill is in progress.
+    pub fn backfill_target_batch_height(&self) -> Option<u64> {
+        self.backfill_target_batch_height
+    }
+
     /// Sets the bitcoin sync height tip.
     pub fn set_bitcoin_sync_height_tip(&mut self, height: u64) {
         // Update in-memory.
@@ -144,6 +197,74 @@ impl SyncManager {
             let _ = self.db.insert(b"payload_tip", payload_bytes);
         }
     }
+
+    /// Journals the start of a batch commit.
+    ///
+    /// The journal entry is flushed to disk before returning, so that a crash midway through
+    /// applying the batch changes across the managers is detected on the next startup.
+    pub fn begin_commit(&mut self, batch_height: u64) {
+        // Update in-memory.
+        self.pending_commit_batch_height = Some(batch_height);
+
+        // Update in-db.
+        let _ = self.db.insert(
+            b"pending_commit_batch_height",
+            batch_height.to_be_bytes().to_vec(),
+        );
+        let _ = self.db.flush();
+    }
+
+    /// Journals the end of a batch commit along with the resulting state root.
+    pub fn end_commit(&mut self, state_root: [u8; 32]) {
+        // Update in-memory.
+        self.pending_commit_batch_height = None;
+        self.committed_state_root = Some(state_root);
+
+        // Update in-db.
+        let _ = self.db.insert(b"committed_state_root", state_root.to_vec());
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::EndCommitJournalWrite);
+        let _ = self.db.remove(b"pending_commit_batch_height");
+        let _ = self.db.flush();
+    }
+
+    /// Clears the commit journal, e.g. after the derived state has been rebuilt from scratch.
+    pub fn clear_commit_journal(&mut self) {
+        // Update in-memory.
+        self.pending_commit_batch_height = None;
+        self.committed_state_root = None;
+
+        // Update in-db.
+        let _ = self.db.remove(b"pending_commit_batch_height");
+        let _ = self.db.remove(b"committed_state_root");
+        let _ = self.db.flush();
+    }
+
+    /// Records the start of an archive backfill up to the given batch height.
+    ///
+    /// The record is flushed to disk before returning, so that an interrupted backfill is resumed
+    /// on the next startup rather than leaving the ledger short of its former tip.
+    pub fn begin_backfill(&mut self, target_batch_height: u64) {
+        // Update in-memory.
+        self.backfill_target_batch_height = Some(target_batch_height);
+
+        // Update in-db.
+        let _ = self.db.insert(
+            b"backfill_target_batch_height",
+            target_batch_height.to_be_bytes().to_vec(),
+        );
+        let _ = self.db.flush();
+    }
+
+    /// Records the end of an archive backfill.
+    pub fn end_backfill(&mut self) {
+        // Update in-memory.
+        self.backfill_target_batch_height = None;
+
+        // Update in-db.
+        let _ = self.db.remove(b"backfill_target_batch_height");
+        let _ = self.db.flush();
+    }
 }
 
 /// Erases the sync manager by db path.
@@ -152,5 +273,5 @@ pub fn erase_sync_manager(chain: Chain) {
     let sync_manager_db_path = format!("storage/{}/sync_manager", chain.to_string());
 
     // Erase the sync manager db path.
-    let _ = std::fs::remove_dir_all(sync_manager_db_path);
+    erase_db(chain, &sync_manager_db_path);
 }
@@ -0,0 +1,56 @@
+use bitcoin::{OutPoint, TxOut};
+use serde::{Deserialize, Serialize};
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Batch transaction id.
+type BatchTxid = [u8; 32];
+
+/// The inverse of the changes a synced block applied, kept to unwind the block on a reorg.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BlockUndo {
+    // Height of the block.
+    pub height: BlockHeight,
+
+    // Hash of the block.
+    pub block_hash: [u8; 32],
+
+    // Hash of the block's parent, which the sync cursor steps back to when the block is unwound.
+    pub prev_block_hash: [u8; 32],
+
+    // Utxos the block spent, to be restored.
+    pub spent_utxos: Vec<(OutPoint, TxOut)>,
+
+    // Utxos the block created, to be removed.
+    pub created_outpoints: Vec<OutPoint>,
+
+    // Batches the block carried.
+    pub batch_txids: Vec<BatchTxid>,
+}
+
+impl BlockUndo {
+    /// Constructs an empty block undo.
+    pub fn new(height: BlockHeight, block_hash: [u8; 32], prev_block_hash: [u8; 32]) -> Self {
+        Self {
+            height,
+            block_hash,
+            prev_block_hash,
+            spent_utxos: Vec::new(),
+            created_outpoints: Vec::new(),
+            batch_txids: Vec::new(),
+        }
+    }
+
+    /// Serializes the block undo to bincode bytes.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a block undo from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(block_undo, _)| block_undo)
+    }
+}
@@ -0,0 +1,7 @@
+/// Errors associated with constructing the `UndoJournal`.
+#[derive(Debug, Clone)]
+pub enum UJConstructionError {
+    DBOpenError(sled::Error),
+    UnexpectedDbKeyLength(usize),
+    CorruptUndo(u64),
+}
@@ -0,0 +1,3 @@
+pub mod block_undo;
+pub mod errors;
+pub mod undo_journal;
@@ -0,0 +1,156 @@
+#[cfg(feature = "fault-injection")]
+use crate::inscriptive::fault_injection::{fault_injection::fault_point, fault_point::FaultPoint};
+use crate::inscriptive::storage::storage::{erase_db, open_db};
+use crate::inscriptive::undo_journal::block_undo::BlockUndo;
+use crate::inscriptive::undo_journal::errors::construction_error::UJConstructionError;
+use crate::operative::run_args::chain::Chain;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Default number of most recent blocks that can be unwound on a reorg.
+const DEFAULT_REORG_ROLLBACK_DEPTH: u64 = 12;
+
+/// Maximum number of most recent blocks that can be unwound on a reorg.
+pub const MAX_REORG_ROLLBACK_DEPTH: u64 = 144;
+
+/// A struct for keeping the undo data of the most recent synced blocks.
+///
+/// Only the undo data of the last `rollback_depth` blocks is kept; older undo data is pruned as new
+/// blocks are synced.
+pub struct UndoJournal {
+    // Number of most recent blocks whose undo data is kept.
+    rollback_depth: u64,
+
+    // In-memory undo data keyed by block height.
+    undos: BTreeMap<BlockHeight, BlockUndo>,
+
+    // In-storage db.
+    db: sled::Db,
+}
+
+/// Guarded undo journal.
+#[allow(non_camel_case_types)]
+pub type UNDO_JOURNAL = Arc<Mutex<UndoJournal>>;
+
+impl UndoJournal {
+    pub fn new(chain: Chain, rollback_depth: u64) -> Result<UNDO_JOURNAL, UJConstructionError> {
+        // 1 Open the undo journal db.
+        let db_path = format!("storage/{}/undo_journal", chain.to_string());
+        let db = open_db(chain, &db_path).map_err(UJConstructionError::DBOpenError)?;
+
+        // 2 Load the undo data from the db.
+        let mut undos = BTreeMap::<BlockHeight, BlockUndo>::new();
+        for (key, val) in db.iter().filter_map(|r| r.ok()) {
+            // 2.1 Decode the block height from the key.
+            let key_bytes: [u8; 8] = key
+                .as_ref()
+                .try_into()
+                .map_err(|_| UJConstructionError::UnexpectedDbKeyLength(key.len()))?;
+            let height = u64::from_be_bytes(key_bytes);
+
+            // 2.2 Deserialize the block undo.
+            let block_undo = BlockUndo::deserialize(val.as_ref())
+                .ok_or(UJConstructionError::CorruptUndo(height))?;
+
+            // 2.3 Insert the block undo.
+            undos.insert(height, block_undo);
+        }
+
+        // 3 Construct the undo journal.
+        let mut undo_journal = UndoJournal {
+            rollback_depth: rollback_depth.clamp(1, MAX_REORG_ROLLBACK_DEPTH),
+            undos,
+            db,
+        };
+
+        // 4 Prune the undo data beyond a possibly lowered rollback depth.
+        if let Some(tip_height) = undo_journal.undos.keys().next_back().copied() {
+            undo_journal.prune(tip_height);
+        }
+
+        // 5 Guard the undo journal.
+        let undo_journal = Arc::new(Mutex::new(undo_journal));
+
+        // 6 Return the undo journal.
+        Ok(undo_journal)
+    }
+
+    /// Reads the rollback depth from the environment.
+    ///
+    /// `CUBE_REORG_ROLLBACK_DEPTH` optionally overrides the default rollback depth.
+    pub fn rollback_depth_from_env() -> u64 {
+        std::env::var("CUBE_REORG_ROLLBACK_DEPTH")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_REORG_ROLLBACK_DEPTH)
+    }
+
+    /// Returns the number of most recent blocks that can be unwound.
+    pub fn rollback_depth(&self) -> u64 {
+        self.rollback_depth
+    }
+
+    /// Returns the undo data of the block at the given height, if kept.
+    pub fn undo_by_height(&self, height: BlockHeight) -> Option<BlockUndo> {
+        self.undos.get(&height).cloned()
+    }
+
+    /// Inserts the undo data of a newly synced block, pruning the undo data that falls out of the rollback depth.
+    ///
+    /// The undo data is flushed to disk before returning, so that it is available before the block counts as synced.
+    pub fn insert(&mut self, block_undo: BlockUndo) {
+        let height = block_undo.height;
+
+        // Update in-db.
+        if let Some(block_undo_bytes) = block_undo.serialize() {
+            let _ = self.db.insert(height.to_be_bytes(), block_undo_bytes);
+        }
+        #[cfg(feature = "fault-injection")]
+        fault_point(FaultPoint::UndoJournalWrite);
+
+        // Update in-memory.
+        self.undos.insert(height, block_undo);
+
+        // Prune the undo data that falls out of the rollback depth.
+        self.prune(height);
+
+        let _ = self.db.flush();
+    }
+
+    /// Removes the undo data of an unwound block.
+    pub fn remove(&mut self, height: BlockHeight) {
+        // Update in-memory.
+        self.undos.remove(&height);
+
+        // Update in-db.
+        let _ = self.db.remove(height.to_be_bytes());
+        let _ = self.db.flush();
+    }
+
+    /// Removes the undo data older than the rollback depth, counting back from the given tip height.
+    fn prune(&mut self, tip_height: BlockHeight) {
+        let keep_from_height = (tip_height + 1).saturating_sub(self.rollback_depth);
+        let pruned_heights: Vec<BlockHeight> = self
+            .undos
+            .range(..keep_from_height)
+            .map(|(height, _)| *height)
+            .collect();
+        for height in pruned_heights {
+            self.undos.remove(&height);
+            let _ = self.db.remove(height.to_be_bytes());
+        }
+    }
+}
+
+/// Erases the undo journal by db path.
+pub fn erase_undo_journal(chain: Chain) {
+    // Undo journal db path.
+    let undo_journal_db_path = format!("storage/{}/undo_journal", chain.to_string());
+
+    // Erase the undo journal db path.
+    erase_db(chain, &undo_journal_db_path);
+}
@@ -1 +1,2 @@
-pub mod utxo_set;
\ No newline at end of file
+pub mod sync_cursor;
+pub mod utxo_set;
@@ -0,0 +1,34 @@
+/// The last Bitcoin block whose changes were applied to the utxo set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncCursor {
+    // Height of the block.
+    pub height: u64,
+
+    // Hash of the block.
+    pub block_hash: [u8; 32],
+}
+
+impl SyncCursor {
+    /// Constructs a sync cursor.
+    pub fn new(height: u64, block_hash: [u8; 32]) -> Self {
+        Self { height, block_hash }
+    }
+
+    /// Serializes the sync cursor into 40 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(&self.height.to_be_bytes());
+        bytes.extend_from_slice(&self.block_hash);
+        bytes
+    }
+
+    /// Deserializes a sync cursor from 40 bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 40 {
+            return None;
+        }
+        let height = u64::from_be_bytes(bytes[..8].try_into().ok()?);
+        let block_hash: [u8; 32] = bytes[8..].try_into().ok()?;
+        Some(Self { height, block_hash })
+    }
+}
@@ -9,6 +9,10 @@ use crate::{
             },
         },
     },
+    inscriptive::{
+        storage::storage::{erase_db, open_db},
+        utxo_set::sync_cursor::SyncCursor,
+    },
     operative::run_args::chain::Chain,
 };
 use bitcoin::{OutPoint, TxOut};
@@ -25,14 +29,26 @@ use tokio::sync::Mutex;
 /// Since the connected Bitcoin RPC node already maintains the entire UTXO set,
 /// this set is optimized solely for quick lookup of `Lift` prevouts by the Cube nodes.
 ///
+/// The changes of a synced block are written to storage in a single atomic batch along with the
+/// sync cursor, so the stored set always matches the block the cursor points at.
+///
 pub struct UTXOSet {
     // In-memory UTXO set.
     utxos: HashMap<OutPoint, TxOut>,
 
+    // Last block whose changes were applied.
+    sync_cursor: Option<SyncCursor>,
+
+    // In-storage changes of the block being applied, if any.
+    pending_block_delta: Option<sled::Batch>,
+
     // In-storage UTXO set.
     utxos_db: sled::Db,
 }
 
+/// Db key of the sync cursor.
+const SYNC_CURSOR_KEY: &[u8] = b"sync_cursor";
+
 /// Guarded UTXO set.
 #[allow(non_camel_case_types)]
 pub type UTXO_SET = Arc<Mutex<UTXOSet>>;
@@ -42,13 +58,25 @@ impl UTXOSet {
     pub fn new(chain: Chain) -> Option<UTXO_SET> {
         // Collect UTXOs from db.
         let utxos_path = format!("{}/{}/{}", "storage", chain.to_string(), "utxo_set");
-        let utxos_db = sled::open(utxos_path).ok()?;
+        let utxos_db = open_db(chain, &utxos_path).ok()?;
 
         let mut utxos = HashMap::<OutPoint, TxOut>::new();
 
+        // Load the sync cursor from db.
+        let sync_cursor = utxos_db
+            .get(SYNC_CURSOR_KEY)
+            .ok()
+            .flatten()
+            .and_then(|val| SyncCursor::from_bytes(val.as_ref()));
+
         // Load UTXOs from db.
         for lookup in utxos_db.iter() {
             if let Ok((key, val)) = lookup {
+                // Skip the sync cursor.
+                if key.as_ref() == SYNC_CURSOR_KEY {
+                    continue;
+                }
+
                 // Deserialize outpoint.
                 let outpoint_bytes: [u8; 36] = key.as_ref().try_into().ok()?;
                 let outpoint = OutPoint::from_bytes36(&outpoint_bytes)?;
@@ -62,7 +90,12 @@ impl UTXOSet {
         }
 
         // Construct the UTXOSet instance.
-        let utxoset = UTXOSet { utxos, utxos_db };
+        let utxoset = UTXOSet {
+            utxos,
+            sync_cursor,
+            pending_block_delta: None,
+            utxos_db,
+        };
 
         // Return the UTXOSet instance.
         Some(Arc::new(Mutex::new(utxoset)))
@@ -78,12 +111,45 @@ impl UTXOSet {
         self.utxos.get(outpoint).cloned()
     }
 
+    /// Returns the last block whose changes were applied, if any.
+    pub fn sync_cursor(&self) -> Option<SyncCursor> {
+        self.sync_cursor
+    }
+
+    /// Starts collecting the in-storage changes of a block, to be written at once by `commit_block_delta`.
+    pub fn begin_block_delta(&mut self) {
+        self.pending_block_delta = Some(sled::Batch::default());
+    }
+
+    /// Writes the collected in-storage changes of a block along with the new sync cursor in a single atomic batch.
+    pub fn commit_block_delta(&mut self, sync_cursor: SyncCursor) {
+        // 1 Take the collected changes.
+        let mut block_delta = self.pending_block_delta.take().unwrap_or_default();
+
+        // 2 Add the new sync cursor.
+        block_delta.insert(SYNC_CURSOR_KEY, sync_cursor.to_bytes());
+
+        // 3 Apply the changes atomically.
+        let _ = self.utxos_db.apply_batch(block_delta);
+        let _ = self.utxos_db.flush();
+
+        // 4 Update the in-memory sync cursor.
+        self.sync_cursor = Some(sync_cursor);
+    }
+
     /// Inserts a utxo into the set.
     pub fn insert_utxo(&mut self, outpoint: &OutPoint, txout: &TxOut) {
         // Insert utxo into the in-memory set.
         if let None = self.utxos.insert(outpoint.clone(), txout.clone()) {
-            // Insert utxo into the in-storage set.
-            let _ = self.utxos_db.insert(&outpoint.bytes_36(), txout.bytes());
+            // Insert utxo into the in-storage set, or into the block delta being collected.
+            match self.pending_block_delta.as_mut() {
+                Some(block_delta) => {
+                    block_delta.insert(outpoint.bytes_36().to_vec(), txout.bytes())
+                }
+                None => {
+                    let _ = self.utxos_db.insert(&outpoint.bytes_36(), txout.bytes());
+                }
+            }
         }
     }
 
@@ -91,8 +157,13 @@ impl UTXOSet {
     pub fn remove_utxo(&mut self, outpoint: &OutPoint) {
         // Remove utxo from the in-memory set.
         if let Some(_) = self.utxos.remove(outpoint) {
-            // Remove utxo from the in-storage set.
-            let _ = self.utxos_db.remove(&outpoint.bytes_36());
+            // Remove utxo from the in-storage set, or from the block delta being collected.
+            match self.pending_block_delta.as_mut() {
+                Some(block_delta) => block_delta.remove(outpoint.bytes_36().to_vec()),
+                None => {
+                    let _ = self.utxos_db.remove(&outpoint.bytes_36());
+                }
+            }
         }
     }
 
@@ -185,5 +256,5 @@ pub fn erase_utxo_set(chain: Chain) {
     let utxo_set_db_path = format!("storage/{}/utxo_set", chain.to_string());
 
     // Erase the UTXO set db path.
-    let _ = std::fs::remove_dir_all(utxo_set_db_path);
+    erase_db(chain, &utxo_set_db_path);
 }
@@ -0,0 +1,51 @@
+use crate::operative::admin::admin_server::{admin_socket_path, admin_token_path};
+use crate::operative::admin::errors::admin_client_error::AdminClientError;
+use crate::operative::run_args::chain::Chain;
+use colored::Colorize;
+use serde_json::{to_string_pretty, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+/// Sends a command to the admin socket of a running instance and prints the response.
+#[tokio::main]
+pub async fn run(chain: Chain, command: &[String]) {
+    match request(chain, command).await {
+        Ok(response) => println!(
+            "{}",
+            to_string_pretty(&response).expect("serde_json::Value should serialize")
+        ),
+        Err(err) => eprintln!("{} {:?}", "Admin request error:".red(), err),
+    }
+}
+
+/// Sends a command to the admin socket of a running instance and returns the JSON response.
+pub async fn request(chain: Chain, command: &[String]) -> Result<Value, AdminClientError> {
+    // 1 Read the admin token.
+    let token = std::fs::read_to_string(admin_token_path(chain))
+        .map_err(|err| AdminClientError::TokenReadError(err.to_string()))?;
+
+    // 2 Connect to the admin socket.
+    let stream = UnixStream::connect(admin_socket_path(chain))
+        .await
+        .map_err(|err| AdminClientError::ConnectError(err.to_string()))?;
+    let (reader, mut writer) = stream.into_split();
+
+    // 3 Write the request line.
+    let request_line = format!("{} {}\n", token.trim(), command.join(" "));
+    writer
+        .write_all(request_line.as_bytes())
+        .await
+        .map_err(|err| AdminClientError::WriteError(err.to_string()))?;
+
+    // 4 Read the response line.
+    let response_line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await
+        .map_err(|err| AdminClientError::ReadError(err.to_string()))?
+        .ok_or(AdminClientError::ReadError("connection closed".to_string()))?;
+
+    // 5 Parse the response.
+    serde_json::from_str(&response_line)
+        .map_err(|err| AdminClientError::InvalidResponse(err.to_string()))
+}
@@ -0,0 +1,241 @@
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use crate::operative::admin::errors::admin_error::AdminError;
+use crate::operative::logging::log_level::LogLevel;
+use crate::transmutative::key::FromNostrKeyStr;
+use std::net::SocketAddr;
+
+/// Peer key.
+type PeerKey = [u8; 32];
+
+/// Batch txid.
+type BatchTxid = [u8; 32];
+
+/// Peer access list actions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PeerAccessAction {
+    Allow,
+    Disallow,
+    Ban,
+    Unban,
+}
+
+impl ToString for PeerAccessAction {
+    fn to_string(&self) -> String {
+        match self {
+            PeerAccessAction::Allow => "allow".to_string(),
+            PeerAccessAction::Disallow => "disallow".to_string(),
+            PeerAccessAction::Ban => "ban".to_string(),
+            PeerAccessAction::Unban => "unban".to_string(),
+        }
+    }
+}
+
+/// Commands accepted by the admin socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    FlushDelta,
+    RollbackLast,
+    SetLogLevel(LogLevel),
+    DumpMetrics,
+    TriggerBackup,
+    PeersList,
+    PeersUpdate(PeerAccessAction, PeerKey),
+    WorkQueue,
+    RequeueDeadLetter(BatchTxid),
+    Operators,
+    SetDownloadRate(u64, Option<u64>),
+    CancelRpc,
+    Broadcasts,
+    Traffic,
+    RelaysList,
+    AddRelay(String),
+    RemoveRelay(String),
+    DirectPeersList,
+    AddDirectPeer(PeerKey, SocketAddr),
+    RemoveDirectPeer(PeerKey),
+    Contracts(u64),
+    WebhooksList,
+    AddWebhook(String, Vec<WebhookEventKind>, Vec<PeerKey>),
+    RemoveWebhook(u64),
+    ApiKeysList,
+    AddApiKey(PeerKey, ApiPermission),
+    RemoveApiKey(PeerKey),
+    ApiTokensList,
+    IssueApiToken(ApiPermission, String),
+    RevokeApiToken(u64),
+    Receipt([u8; 32]),
+}
+
+impl AdminCommand {
+    /// Parses a command from its whitespace-separated parts.
+    pub fn parse(parts: &[&str]) -> Result<AdminCommand, AdminError> {
+        match parts {
+            ["flush-delta"] => Ok(AdminCommand::FlushDelta),
+            ["rollback-last"] => Ok(AdminCommand::RollbackLast),
+            ["set-log-level", level] => LogLevel::from_name(level)
+                .map(AdminCommand::SetLogLevel)
+                .ok_or(AdminError::InvalidArguments(
+                    "set-log-level <error|warn|info|debug>".to_string(),
+                )),
+            ["dump-metrics"] => Ok(AdminCommand::DumpMetrics),
+            ["trigger-backup"] => Ok(AdminCommand::TriggerBackup),
+            ["peers", "list"] => Ok(AdminCommand::PeersList),
+            ["peers", action, npub] => {
+                let action = match *action {
+                    "allow" => PeerAccessAction::Allow,
+                    "disallow" => PeerAccessAction::Disallow,
+                    "ban" => PeerAccessAction::Ban,
+                    "unban" => PeerAccessAction::Unban,
+                    _ => return Err(Self::peers_usage()),
+                };
+                let peer_key = npub.from_npub().ok_or(Self::peers_usage())?;
+                Ok(AdminCommand::PeersUpdate(action, peer_key))
+            }
+            ["peers", ..] => Err(Self::peers_usage()),
+            ["work-queue"] => Ok(AdminCommand::WorkQueue),
+            ["requeue-dead-letter", batch_txid] => hex::decode(batch_txid)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(AdminCommand::RequeueDeadLetter)
+                .ok_or(AdminError::InvalidArguments(
+                    "requeue-dead-letter <batch_txid_hex>".to_string(),
+                )),
+            ["operators"] => Ok(AdminCommand::Operators),
+            ["set-download-rate", rate, burst @ ..] if burst.len() <= 1 => {
+                let rate_kib = match *rate {
+                    "off" => 0,
+                    rate => rate
+                        .parse::<u64>()
+                        .map_err(|_| Self::set_download_rate_usage())?,
+                };
+                let burst_kib = match burst.first() {
+                    Some(burst) => Some(
+                        burst
+                            .parse::<u64>()
+                            .map_err(|_| Self::set_download_rate_usage())?,
+                    ),
+                    None => None,
+                };
+                Ok(AdminCommand::SetDownloadRate(rate_kib, burst_kib))
+            }
+            ["set-download-rate", ..] => Err(Self::set_download_rate_usage()),
+            ["cancel-rpc"] => Ok(AdminCommand::CancelRpc),
+            ["broadcasts"] => Ok(AdminCommand::Broadcasts),
+            ["traffic"] => Ok(AdminCommand::Traffic),
+            ["relays", "list"] => Ok(AdminCommand::RelaysList),
+            ["relays", "add", url] => Ok(AdminCommand::AddRelay(url.to_string())),
+            ["relays", "remove", url] => Ok(AdminCommand::RemoveRelay(url.to_string())),
+            ["relays", ..] => Err(Self::relays_usage()),
+            ["direct-peers", "list"] => Ok(AdminCommand::DirectPeersList),
+            ["direct-peers", "add", npub, addr] => {
+                let peer_key = npub.from_npub().ok_or(Self::direct_peers_usage())?;
+                let addr = addr
+                    .parse::<SocketAddr>()
+                    .map_err(|_| Self::direct_peers_usage())?;
+                Ok(AdminCommand::AddDirectPeer(peer_key, addr))
+            }
+            ["direct-peers", "remove", npub] => npub
+                .from_npub()
+                .map(AdminCommand::RemoveDirectPeer)
+                .ok_or(Self::direct_peers_usage()),
+            ["direct-peers", ..] => Err(Self::direct_peers_usage()),
+            ["contracts"] => Ok(AdminCommand::Contracts(0)),
+            ["contracts", since_height] => since_height
+                .parse::<u64>()
+                .map(AdminCommand::Contracts)
+                .map_err(|_| AdminError::InvalidArguments("contracts [since_height]".to_string())),
+            ["webhooks", "list"] => Ok(AdminCommand::WebhooksList),
+            ["webhooks", "add", url, events, accounts @ ..] if accounts.len() <= 1 => {
+                let kinds = events
+                    .split(',')
+                    .map(WebhookEventKind::from_name)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(Self::webhooks_usage())?;
+                let account_keys = match accounts.first() {
+                    Some(accounts) => accounts
+                        .split(',')
+                        .map(|npub| npub.from_npub())
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or(Self::webhooks_usage())?,
+                    None => Vec::new(),
+                };
+                Ok(AdminCommand::AddWebhook(
+                    url.to_string(),
+                    kinds,
+                    account_keys,
+                ))
+            }
+            ["webhooks", "remove", id] => id
+                .parse::<u64>()
+                .map(AdminCommand::RemoveWebhook)
+                .map_err(|_| Self::webhooks_usage()),
+            ["webhooks", ..] => Err(Self::webhooks_usage()),
+            ["api-keys", "list"] => Ok(AdminCommand::ApiKeysList),
+            ["api-keys", "add", npub, permission] => {
+                let account_key = npub.from_npub().ok_or(Self::api_keys_usage())?;
+                let permission =
+                    ApiPermission::from_name(permission).ok_or(Self::api_keys_usage())?;
+                Ok(AdminCommand::AddApiKey(account_key, permission))
+            }
+            ["api-keys", "remove", npub] => npub
+                .from_npub()
+                .map(AdminCommand::RemoveApiKey)
+                .ok_or(Self::api_keys_usage()),
+            ["api-keys", ..] => Err(Self::api_keys_usage()),
+            ["api-tokens", "list"] => Ok(AdminCommand::ApiTokensList),
+            ["api-tokens", "issue", permission, label] => {
+                let permission =
+                    ApiPermission::from_name(permission).ok_or(Self::api_tokens_usage())?;
+                Ok(AdminCommand::IssueApiToken(permission, label.to_string()))
+            }
+            ["api-tokens", "revoke", id] => id
+                .parse::<u64>()
+                .map(AdminCommand::RevokeApiToken)
+                .map_err(|_| Self::api_tokens_usage()),
+            ["api-tokens", ..] => Err(Self::api_tokens_usage()),
+            ["receipt", call_id] => hex::decode(call_id)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(AdminCommand::Receipt)
+                .ok_or(AdminError::InvalidArguments(
+                    "receipt <call_id_hex>".to_string(),
+                )),
+            [command, ..] => Err(AdminError::UnknownCommand(command.to_string())),
+            [] => Err(AdminError::UnknownCommand(String::new())),
+        }
+    }
+
+    fn peers_usage() -> AdminError {
+        AdminError::InvalidArguments("peers <list|allow|disallow|ban|unban> [npub]".to_string())
+    }
+
+    fn set_download_rate_usage() -> AdminError {
+        AdminError::InvalidArguments("set-download-rate <kib_per_sec|off> [burst_kib]".to_string())
+    }
+
+    fn relays_usage() -> AdminError {
+        AdminError::InvalidArguments("relays <list|add|remove> [wss_url]".to_string())
+    }
+
+    fn direct_peers_usage() -> AdminError {
+        AdminError::InvalidArguments("direct-peers <list|add|remove> [npub] [ip:port]".to_string())
+    }
+
+    fn webhooks_usage() -> AdminError {
+        AdminError::InvalidArguments(
+            "webhooks <list|add|remove> [https_url event[,event..] [npub[,npub..]] | id]"
+                .to_string(),
+        )
+    }
+
+    fn api_keys_usage() -> AdminError {
+        AdminError::InvalidArguments("api-keys <list|add|remove> [npub] [read|admin]".to_string())
+    }
+
+    fn api_tokens_usage() -> AdminError {
+        AdminError::InvalidArguments(
+            "api-tokens <list|issue|revoke> [read|admin label | id]".to_string(),
+        )
+    }
+}
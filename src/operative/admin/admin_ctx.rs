@@ -0,0 +1,74 @@
+use crate::communicative::api_auth::api_auth::API_AUTH;
+use crate::communicative::gossip::contract_notice_index::CONTRACT_NOTICE_INDEX;
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::nns::client::NNSClient;
+use crate::communicative::nns::relay_list::RELAY_LIST;
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use crate::communicative::webhook::webhooks::WEBHOOKS;
+use crate::executive::exec_ctx::exec_ctx::EXEC_CTX;
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::run_args::chain::Chain;
+use crate::operative::run_args::operating_kind::OperatingKind;
+use crate::operative::tasks::chain_health::chain_health::CHAIN_HEALTH;
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::EXEC_SCHEDULER;
+use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use crate::operative::tasks::engine_session::work_queue::work_queue::WORK_QUEUE;
+use crate::operative::tasks::fee_estimator::fee_estimator::FEE_ESTIMATOR;
+use crate::operative::tasks::rebroadcast::rebroadcast_queue::REBROADCAST_QUEUE;
+use std::time::Instant;
+
+/// Handles the admin socket operates on.
+#[derive(Clone)]
+pub struct AdminCtx {
+    // Chain and operating kind.
+    pub chain: Chain,
+    pub operating_kind: OperatingKind,
+
+    // Startup time, for the uptime metric.
+    pub started_at: Instant,
+
+    // The sync manager.
+    pub sync_manager: SYNC_MANAGER,
+
+    // The execution context whose deltas are flushed or rolled back.
+    pub exec_ctx: EXEC_CTX,
+
+    // The session pool (Engine only).
+    pub session_pool: Option<SESSION_POOL>,
+
+    // The peer access list (Engine only).
+    pub peer_access_list: Option<PEER_ACCESS_LIST>,
+
+    // The work queue of built batches (Engine only).
+    pub work_queue: Option<WORK_QUEUE>,
+
+    // The execution scheduler of inbound entries (Engine only).
+    pub exec_scheduler: Option<EXEC_SCHEDULER>,
+
+    // The registered operator sessions (Engine only).
+    pub operator_sessions: Option<OPERATOR_SESSIONS>,
+
+    // The health of the chain seen through the Bitcoin node.
+    pub chain_health: CHAIN_HEALTH,
+
+    // The fee estimator of the engine's on-chain transactions (Engine only).
+    pub fee_estimator: Option<FEE_ESTIMATOR>,
+
+    // The queue of broadcast transactions pending confirmation (Engine only).
+    pub rebroadcast_queue: Option<REBROADCAST_QUEUE>,
+
+    // The runtime-managed relays and direct peers.
+    pub relay_list: RELAY_LIST,
+
+    // The NNS client, whose relay pool follows the relay list.
+    pub nns_client: NNSClient,
+
+    // The index of contract registration notices (Node on a federated chain only).
+    pub contract_notice_index: Option<CONTRACT_NOTICE_INDEX>,
+
+    // The configured webhook endpoints and their queued deliveries.
+    pub webhooks: WEBHOOKS,
+
+    // The identities allowed on the query APIs.
+    pub api_auth: API_AUTH,
+}
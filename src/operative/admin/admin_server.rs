@@ -0,0 +1,577 @@
+use crate::communicative::inbound::inbound_gate::inbound_gate;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cache::rpc_cache;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cancel::in_flight_calls;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_pool::connection_pool;
+use crate::communicative::traffic::traffic_meter::traffic_meter;
+use crate::inscriptive::memory_budget::memory_budget::memory_budget_json;
+use crate::operative::admin::admin_command::{AdminCommand, PeerAccessAction};
+use crate::operative::admin::admin_ctx::AdminCtx;
+use crate::operative::admin::backup::backup_storage;
+use crate::operative::admin::errors::admin_error::AdminError;
+use crate::operative::logging::log_level::{log_level, set_log_level};
+use crate::operative::run_args::chain::Chain;
+use crate::operative::run_args::operating_kind::OperatingKind;
+use crate::operative::tasks::chain_sync::download_throttle::download_throttle;
+use crate::operative::tasks::engine_session::session_pool::session_pool::SessionPoolState;
+use chrono::Utc;
+use colored::Colorize;
+use rand::{rngs::OsRng, RngCore};
+use serde_json::{Map, Value};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+/// Number of the noisiest peers listed in the runtime metrics.
+const TRAFFIC_METRICS_TOP_PEERS: usize = 5;
+
+/// Returns the admin socket path of a chain.
+pub fn admin_socket_path(chain: Chain) -> String {
+    format!("storage/{}/admin.sock", chain.to_string())
+}
+
+/// Returns the admin token path of a chain.
+pub fn admin_token_path(chain: Chain) -> String {
+    format!("storage/{}/admin.token", chain.to_string())
+}
+
+/// Runs the admin socket.
+///
+/// A fresh random token is written to the owner-only token file on every start, and each request
+/// line must be prefixed with it: `<token> <command> [args...]`. Responses are single JSON lines.
+pub async fn run(ctx: AdminCtx) {
+    // 1 Generate and persist the admin token.
+    let token = {
+        let mut token_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut token_bytes);
+        hex::encode(token_bytes)
+    };
+    if let Err(err) = write_token(ctx.chain, &token) {
+        eprintln!("{} {}", "Failed to write the admin token:".red(), err);
+        return;
+    }
+
+    // 2 Bind the admin socket, replacing a stale one left by a previous run.
+    let socket_path = admin_socket_path(ctx.chain);
+    let _ = fs::remove_file(&socket_path);
+    let listener = match UnixListener::bind(&socket_path) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("{} {}", "Failed to bind the admin socket:".red(), err);
+            return;
+        }
+    };
+    let _ = fs::set_permissions(&socket_path, fs::Permissions::from_mode(0o600));
+
+    // 3 Serve the admin connections.
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(_) => continue,
+        };
+
+        let ctx = ctx.clone();
+        let token = token.clone();
+        tokio::spawn(async move {
+            handle_connection(stream, &ctx, &token).await;
+        });
+    }
+}
+
+/// Writes the admin token into an owner-only file.
+fn write_token(chain: Chain, token: &str) -> Result<(), std::io::Error> {
+    let token_path = admin_token_path(chain);
+    let _ = fs::remove_file(&token_path);
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&token_path)?;
+    file.write_all(token.as_bytes())
+}
+
+/// Serves the request lines of a single admin connection.
+async fn handle_connection(stream: UnixStream, ctx: &AdminCtx, token: &str) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        // 1 Authenticate and execute the request line.
+        let result = {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts.split_first() {
+                Some((presented_token, command_parts))
+                    if tokens_match(presented_token.as_bytes(), token.as_bytes()) =>
+                {
+                    match AdminCommand::parse(command_parts) {
+                        Ok(command) => execute(ctx, command).await,
+                        Err(err) => Err(err),
+                    }
+                }
+                _ => Err(AdminError::Unauthorized),
+            }
+        };
+
+        // 2 Construct the response line.
+        let mut response = response_json(result).to_string();
+        response.push('\n');
+
+        // 3 Write the response line.
+        if writer.write_all(response.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Compares two tokens in constant time.
+fn tokens_match(presented: &[u8], expected: &[u8]) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Returns the JSON response to an admin command: `{"ok": true, "result": ..}` or
+/// `{"ok": false, "error": ..}`.
+pub fn response_json(result: Result<Value, AdminError>) -> Value {
+    let mut obj = Map::new();
+    match result {
+        Ok(value) => {
+            obj.insert("ok".to_string(), Value::Bool(true));
+            obj.insert("result".to_string(), value);
+        }
+        Err(err) => {
+            obj.insert("ok".to_string(), Value::Bool(false));
+            obj.insert("error".to_string(), err.json());
+        }
+    }
+    Value::Object(obj)
+}
+
+/// Executes an admin command.
+pub async fn execute(ctx: &AdminCtx, command: AdminCommand) -> Result<Value, AdminError> {
+    match command {
+        AdminCommand::FlushDelta => flush_delta(ctx).await,
+        AdminCommand::RollbackLast => rollback_last(ctx).await,
+        AdminCommand::SetLogLevel(level) => {
+            set_log_level(level);
+            Ok(Value::String(level.to_string()))
+        }
+        AdminCommand::DumpMetrics => Ok(dump_metrics(ctx).await),
+        AdminCommand::TriggerBackup => {
+            // Hold the execution context so that no batch is applied mid-copy.
+            let _exec_ctx = ctx.exec_ctx.lock().await;
+            backup_storage(ctx.chain)
+                .map(Value::String)
+                .map_err(|err| AdminError::BackupError(err.to_string()))
+        }
+        AdminCommand::PeersList => {
+            let peer_access_list = ctx
+                .peer_access_list
+                .as_ref()
+                .ok_or_else(|| unavailable(ctx))?;
+            let _peer_access_list = peer_access_list.lock().await;
+            Ok(_peer_access_list.json())
+        }
+        AdminCommand::PeersUpdate(action, peer_key) => {
+            let peer_access_list = ctx
+                .peer_access_list
+                .as_ref()
+                .ok_or_else(|| unavailable(ctx))?;
+            let mut _peer_access_list = peer_access_list.lock().await;
+            let changed = match action {
+                PeerAccessAction::Allow => _peer_access_list.allow(peer_key),
+                PeerAccessAction::Disallow => _peer_access_list.disallow(peer_key),
+                PeerAccessAction::Ban => _peer_access_list.ban(peer_key),
+                PeerAccessAction::Unban => _peer_access_list.unban(peer_key),
+            }
+            .map_err(|err| AdminError::PeerAccessListError(err.to_string()))?;
+            Ok(Value::Bool(changed))
+        }
+        AdminCommand::WorkQueue => {
+            let work_queue = ctx.work_queue.as_ref().ok_or_else(|| unavailable(ctx))?;
+            let _work_queue = work_queue.lock().await;
+            Ok(_work_queue.json())
+        }
+        AdminCommand::RequeueDeadLetter(batch_txid) => {
+            let work_queue = ctx.work_queue.as_ref().ok_or_else(|| unavailable(ctx))?;
+            let mut _work_queue = work_queue.lock().await;
+            _work_queue
+                .requeue_dead_letter(batch_txid)
+                .map(Value::Bool)
+                .map_err(|err| AdminError::WorkQueueError(err.to_string()))
+        }
+        AdminCommand::Operators => {
+            let operator_sessions = ctx
+                .operator_sessions
+                .as_ref()
+                .ok_or_else(|| unavailable(ctx))?;
+            let _operator_sessions = operator_sessions.lock().await;
+            Ok(_operator_sessions.json())
+        }
+        AdminCommand::SetDownloadRate(rate_kib, burst_kib) => {
+            let mut _download_throttle = download_throttle()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            _download_throttle.set_limit(rate_kib, burst_kib, Instant::now());
+            Ok(_download_throttle.json())
+        }
+        AdminCommand::CancelRpc => {
+            let mut _in_flight_calls = in_flight_calls()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            _in_flight_calls.cancel_all();
+            Ok(_in_flight_calls.json())
+        }
+        AdminCommand::Broadcasts => {
+            let rebroadcast_queue = ctx
+                .rebroadcast_queue
+                .as_ref()
+                .ok_or_else(|| unavailable(ctx))?;
+            let _rebroadcast_queue = rebroadcast_queue.lock().await;
+            Ok(_rebroadcast_queue.json())
+        }
+        AdminCommand::Traffic => {
+            let _traffic_meter = traffic_meter()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            Ok(_traffic_meter.json())
+        }
+        AdminCommand::RelaysList => {
+            let _relay_list = ctx.relay_list.lock().await;
+            Ok(_relay_list.relays_json())
+        }
+        AdminCommand::AddRelay(url) => ctx
+            .nns_client
+            .add_relay(&url)
+            .await
+            .map(Value::Bool)
+            .map_err(|err| AdminError::RelayListError(err.to_string())),
+        AdminCommand::RemoveRelay(url) => ctx
+            .nns_client
+            .remove_relay(&url)
+            .await
+            .map(Value::Bool)
+            .map_err(|err| AdminError::RelayListError(err.to_string())),
+        AdminCommand::DirectPeersList => {
+            let _relay_list = ctx.relay_list.lock().await;
+            Ok(_relay_list.direct_peers_json())
+        }
+        AdminCommand::AddDirectPeer(peer_key, addr) => {
+            let mut _relay_list = ctx.relay_list.lock().await;
+            _relay_list
+                .set_direct_peer(peer_key, addr)
+                .map(Value::Bool)
+                .map_err(|err| AdminError::RelayListError(err.to_string()))
+        }
+        AdminCommand::RemoveDirectPeer(peer_key) => {
+            let mut _relay_list = ctx.relay_list.lock().await;
+            _relay_list
+                .remove_direct_peer(peer_key)
+                .map(Value::Bool)
+                .map_err(|err| AdminError::RelayListError(err.to_string()))
+        }
+        AdminCommand::Contracts(since_height) => {
+            let contract_notice_index = ctx
+                .contract_notice_index
+                .as_ref()
+                .ok_or_else(|| unavailable(ctx))?;
+            let _contract_notice_index = contract_notice_index.lock().await;
+            Ok(_contract_notice_index.json(since_height))
+        }
+        AdminCommand::WebhooksList => {
+            let _webhooks = ctx.webhooks.lock().await;
+            Ok(_webhooks.json())
+        }
+        AdminCommand::AddWebhook(url, kinds, account_keys) => {
+            let mut _webhooks = ctx.webhooks.lock().await;
+            let endpoint = _webhooks
+                .add_endpoint(&url, kinds, account_keys, Utc::now().timestamp() as u64)
+                .map_err(|err| AdminError::WebhookError(err.to_string()))?;
+
+            // The secret is only ever shown once, when the endpoint is added.
+            let mut obj = match endpoint.json() {
+                Value::Object(obj) => obj,
+                _ => Map::new(),
+            };
+            obj.insert(
+                "secret".to_string(),
+                Value::String(hex::encode(endpoint.secret)),
+            );
+            Ok(Value::Object(obj))
+        }
+        AdminCommand::RemoveWebhook(id) => {
+            let mut _webhooks = ctx.webhooks.lock().await;
+            _webhooks
+                .remove_endpoint(id)
+                .map(Value::Bool)
+                .map_err(|err| AdminError::WebhookError(err.to_string()))
+        }
+        AdminCommand::ApiKeysList => {
+            let _api_auth = ctx.api_auth.lock().await;
+            Ok(_api_auth.keys_json())
+        }
+        AdminCommand::AddApiKey(account_key, permission) => {
+            let mut _api_auth = ctx.api_auth.lock().await;
+            _api_auth
+                .add_key(account_key, permission, Utc::now().timestamp() as u64)
+                .map(|api_key| api_key.json())
+                .map_err(|err| AdminError::ApiAuthError(err.to_string()))
+        }
+        AdminCommand::RemoveApiKey(account_key) => {
+            let mut _api_auth = ctx.api_auth.lock().await;
+            _api_auth
+                .remove_key(account_key)
+                .map(Value::Bool)
+                .map_err(|err| AdminError::ApiAuthError(err.to_string()))
+        }
+        AdminCommand::ApiTokensList => {
+            let _api_auth = ctx.api_auth.lock().await;
+            Ok(_api_auth.tokens_json())
+        }
+        AdminCommand::IssueApiToken(permission, label) => {
+            let mut _api_auth = ctx.api_auth.lock().await;
+            let (api_token, token) = _api_auth
+                .issue_token(&label, permission, Utc::now().timestamp() as u64)
+                .map_err(|err| AdminError::ApiAuthError(err.to_string()))?;
+
+            // The token is only ever shown once, when it is issued.
+            let mut obj = match api_token.json() {
+                Value::Object(obj) => obj,
+                _ => Map::new(),
+            };
+            obj.insert("token".to_string(), Value::String(token));
+            Ok(Value::Object(obj))
+        }
+        AdminCommand::RevokeApiToken(id) => {
+            let mut _api_auth = ctx.api_auth.lock().await;
+            _api_auth
+                .revoke_token(id)
+                .map(Value::Bool)
+                .map_err(|err| AdminError::ApiAuthError(err.to_string()))
+        }
+        AdminCommand::Receipt(call_id) => {
+            let archival_manager = {
+                let _exec_ctx = ctx.exec_ctx.lock().await;
+                _exec_ctx
+                    .archival_manager
+                    .clone()
+                    .ok_or(AdminError::ArchivalModeRequired)?
+            };
+            let _archival_manager = archival_manager.lock().await;
+            Ok(_archival_manager
+                .receipt_by_call_id(&call_id)
+                .map(|receipt| receipt.json())
+                .unwrap_or(Value::Null))
+        }
+    }
+}
+
+/// Error for commands that do not apply to the running operating kind.
+fn unavailable(ctx: &AdminCtx) -> AdminError {
+    AdminError::UnavailableInOperatingKind(ctx.operating_kind.to_string())
+}
+
+/// Clears the ephemeral deltas.
+///
+/// On the Engine this also drops the pooled entries of the ongoing session, since they are
+/// only reflected in the deltas being cleared. Returns the number of dropped entries.
+async fn flush_delta(ctx: &AdminCtx) -> Result<Value, AdminError> {
+    match &ctx.session_pool {
+        Some(session_pool) => {
+            let mut _session_pool = session_pool.lock().await;
+            let dropped_entries = _session_pool.added_entries.len();
+            _session_pool.flush().await;
+            Ok(Value::Number(dropped_entries.into()))
+        }
+        None => {
+            let mut _exec_ctx = ctx.exec_ctx.lock().await;
+            _exec_ctx.flush().await;
+            Ok(Value::Number(0.into()))
+        }
+    }
+}
+
+/// Reverts the ephemeral changes of the last execution.
+///
+/// Refused on the Engine while entries are pooled, since they would no longer match the deltas.
+async fn rollback_last(ctx: &AdminCtx) -> Result<Value, AdminError> {
+    if let Some(session_pool) = &ctx.session_pool {
+        let _session_pool = session_pool.lock().await;
+        if !_session_pool.added_entries.is_empty() {
+            return Err(AdminError::SessionPoolNotEmpty(
+                _session_pool.added_entries.len(),
+            ));
+        }
+    }
+
+    let mut _exec_ctx = ctx.exec_ctx.lock().await;
+    _exec_ctx.rollback_last().await;
+    Ok(Value::Null)
+}
+
+/// Collects the runtime metrics.
+async fn dump_metrics(ctx: &AdminCtx) -> Value {
+    let mut obj = Map::new();
+
+    // 1 Runtime setup.
+    obj.insert("chain".to_string(), Value::String(ctx.chain.to_string()));
+    obj.insert(
+        "operating_kind".to_string(),
+        Value::String(ctx.operating_kind.to_string()),
+    );
+    obj.insert(
+        "uptime_secs".to_string(),
+        Value::Number(ctx.started_at.elapsed().as_secs().into()),
+    );
+    obj.insert(
+        "log_level".to_string(),
+        Value::String(log_level().to_string()),
+    );
+
+    // 2 Sync metrics.
+    {
+        let _sync_manager = ctx.sync_manager.lock().await;
+        obj.insert("synced".to_string(), Value::Bool(_sync_manager.is_synced()));
+        obj.insert(
+            "bitcoin_sync_height_tip".to_string(),
+            Value::Number(_sync_manager.bitcoin_sync_height_tip().into()),
+        );
+        obj.insert(
+            "cube_batch_sync_height_tip".to_string(),
+            Value::Number(_sync_manager.cube_batch_sync_height_tip().into()),
+        );
+    }
+
+    // 3 Session pool metrics (Engine only).
+    if let (OperatingKind::Engine, Some(session_pool)) = (ctx.operating_kind, &ctx.session_pool) {
+        let _session_pool = session_pool.lock().await;
+        let state = match _session_pool.state {
+            SessionPoolState::Inactive => "inactive",
+            SessionPoolState::Active => "active",
+            SessionPoolState::Break => "break",
+            SessionPoolState::Suspended => "suspended",
+            SessionPoolState::Overloaded => "overloaded",
+        };
+        obj.insert(
+            "session_pool_state".to_string(),
+            Value::String(state.to_string()),
+        );
+        obj.insert(
+            "session_pool_entries".to_string(),
+            Value::Number(_session_pool.added_entries.len().into()),
+        );
+    }
+
+    // 4 Execution scheduler metrics (Engine only).
+    if let Some(exec_scheduler) = &ctx.exec_scheduler {
+        let _exec_scheduler = exec_scheduler.lock().await;
+        obj.insert("exec_scheduler".to_string(), _exec_scheduler.json());
+    }
+
+    // 5 Operator liveness (Engine only).
+    if let Some(operator_sessions) = &ctx.operator_sessions {
+        let _operator_sessions = operator_sessions.lock().await;
+        let mut operators = Map::new();
+        operators.insert(
+            "registered".to_string(),
+            Value::Number(_operator_sessions.len().into()),
+        );
+        operators.insert(
+            "online".to_string(),
+            Value::Number(_operator_sessions.online_count().into()),
+        );
+        operators.insert(
+            "unassigned_work".to_string(),
+            Value::Number(_operator_sessions.unassigned_work_count().into()),
+        );
+        obj.insert("operators".to_string(), Value::Object(operators));
+    }
+
+    // 6 Memory usage against the memory budgets.
+    obj.insert("memory".to_string(), memory_budget_json());
+
+    // 7 Block download rate limit.
+    {
+        let _download_throttle = download_throttle()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        obj.insert("download_throttle".to_string(), _download_throttle.json());
+    }
+
+    // 8 Chain health alerts.
+    {
+        let _chain_health = ctx.chain_health.lock().await;
+        obj.insert("chain_health".to_string(), _chain_health.json());
+    }
+
+    // 9 Bitcoin RPC connection pool.
+    {
+        let _connection_pool = connection_pool()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        obj.insert("bitcoin_rpc_pool".to_string(), _connection_pool.json());
+    }
+
+    // 10 In-flight Bitcoin RPC calls.
+    {
+        let _in_flight_calls = in_flight_calls()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        obj.insert("bitcoin_rpc_calls".to_string(), _in_flight_calls.json());
+    }
+
+    // 11 Bitcoin RPC response cache.
+    {
+        let _rpc_cache = rpc_cache()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        obj.insert("bitcoin_rpc_cache".to_string(), _rpc_cache.json());
+    }
+
+    // 12 Fee estimates (Engine only).
+    if let Some(fee_estimator) = &ctx.fee_estimator {
+        let _fee_estimator = fee_estimator.lock().await;
+        obj.insert("fee_estimator".to_string(), _fee_estimator.json());
+    }
+
+    // 13 Peer traffic.
+    {
+        let _traffic_meter = traffic_meter()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        obj.insert(
+            "traffic".to_string(),
+            _traffic_meter.summary_json(TRAFFIC_METRICS_TOP_PEERS),
+        );
+    }
+
+    // 14 Relay health.
+    {
+        let _relay_list = ctx.relay_list.lock().await;
+        obj.insert("relays".to_string(), _relay_list.relays_json());
+    }
+
+    // 15 Queued webhook deliveries.
+    {
+        let _webhooks = ctx.webhooks.lock().await;
+        obj.insert(
+            "webhook_deliveries_pending".to_string(),
+            Value::Number(_webhooks.pending_len().into()),
+        );
+    }
+
+    // 16 Inbound package processing (Engine only).
+    if let OperatingKind::Engine = ctx.operating_kind {
+        let _inbound_gate = inbound_gate()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        obj.insert("inbound".to_string(), _inbound_gate.json());
+    }
+
+    Value::Object(obj)
+}
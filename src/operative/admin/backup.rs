@@ -0,0 +1,47 @@
+use crate::operative::run_args::chain::Chain;
+use chrono::Utc;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Copies the chain storage directory into `backups/{chain}/{timestamp}`.
+///
+/// The admin socket and token files are skipped. Returns the backup directory path.
+pub fn backup_storage(chain: Chain) -> Result<String, io::Error> {
+    // 1 Resolve the source and destination paths.
+    let source = format!("storage/{}", chain.to_string());
+    let destination = format!(
+        "backups/{}/{}",
+        chain.to_string(),
+        Utc::now().format("%Y%m%dT%H%M%SZ")
+    );
+
+    // 2 Copy the storage directory recursively.
+    copy_dir(Path::new(&source), Path::new(&destination))?;
+
+    // 3 Return the backup directory path.
+    Ok(destination)
+}
+
+/// Recursively copies a directory, skipping the admin socket and token files.
+fn copy_dir(source: &Path, destination: &Path) -> Result<(), io::Error> {
+    fs::create_dir_all(destination)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if file_name == "admin.sock" || file_name == "admin.token" {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        let target = destination.join(&file_name);
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+/// Errors associated with sending a command to the admin socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminClientError {
+    TokenReadError(String),
+    ConnectError(String),
+    WriteError(String),
+    ReadError(String),
+    InvalidResponse(String),
+}
@@ -0,0 +1,49 @@
+use serde_json::{Map, Value};
+
+/// Errors returned by the admin socket for a single command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminError {
+    Unauthorized,
+    UnknownCommand(String),
+    InvalidArguments(String),
+    UnavailableInOperatingKind(String),
+    SessionPoolNotEmpty(usize),
+    PeerAccessListError(String),
+    BackupError(String),
+    WorkQueueError(String),
+    RelayListError(String),
+    WebhookError(String),
+    ApiAuthError(String),
+    ArchivalModeRequired,
+}
+
+impl AdminError {
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        let (kind, detail) = match self {
+            AdminError::Unauthorized => ("unauthorized", None),
+            AdminError::UnknownCommand(command) => ("unknown_command", Some(command.clone())),
+            AdminError::InvalidArguments(usage) => ("invalid_arguments", Some(usage.clone())),
+            AdminError::UnavailableInOperatingKind(operating_kind) => (
+                "unavailable_in_operating_kind",
+                Some(operating_kind.clone()),
+            ),
+            AdminError::SessionPoolNotEmpty(pooled_entries) => (
+                "session_pool_not_empty",
+                Some(format!("{} pooled entries", pooled_entries)),
+            ),
+            AdminError::PeerAccessListError(err) => ("peer_access_list_error", Some(err.clone())),
+            AdminError::BackupError(err) => ("backup_error", Some(err.clone())),
+            AdminError::WorkQueueError(err) => ("work_queue_error", Some(err.clone())),
+            AdminError::RelayListError(err) => ("relay_list_error", Some(err.clone())),
+            AdminError::WebhookError(err) => ("webhook_error", Some(err.clone())),
+            AdminError::ApiAuthError(err) => ("api_auth_error", Some(err.clone())),
+            AdminError::ArchivalModeRequired => ("archival_mode_required", None),
+        };
+        obj.insert("kind".to_string(), Value::String(kind.to_string()));
+        if let Some(detail) = detail {
+            obj.insert("detail".to_string(), Value::String(detail));
+        }
+        Value::Object(obj)
+    }
+}
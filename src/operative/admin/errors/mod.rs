@@ -0,0 +1,2 @@
+pub mod admin_client_error;
+pub mod admin_error;
@@ -0,0 +1,6 @@
+pub mod admin_client;
+pub mod admin_command;
+pub mod admin_ctx;
+pub mod admin_server;
+pub mod backup;
+pub mod errors;
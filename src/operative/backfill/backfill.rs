@@ -0,0 +1,297 @@
+use crate::communicative::peer::manager::engine_key;
+use crate::communicative::peer::peer::PEER;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::retrieve_block;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::communicative::tcp::client::{BatchContainerByPrevOutpointResponseBody, TCPClient};
+use crate::constructive::txout_types::payload::payload::genesis_payload;
+use crate::executive::exec_ctx::exec_ctx::ExecCtx;
+use crate::inscriptive::archival_manager::archival_manager::{
+    erase_archival_manager, ArchivalManager,
+};
+use crate::inscriptive::baked;
+use crate::inscriptive::coin_manager::coin_manager::{erase_coin_manager, CoinManager};
+use crate::inscriptive::flame_manager::flame_manager::{erase_flame_manager, FlameManager};
+use crate::inscriptive::graveyard::graveyard::{erase_graveyard, Graveyard};
+use crate::inscriptive::params_manager::params_manager::{erase_params_manager, ParamsManager};
+use crate::inscriptive::privileges_manager::privileges_manager::{
+    erase_privileges_manager, PrivilegesManager,
+};
+use crate::inscriptive::registery::registery::{erase_registery, Registery};
+use crate::inscriptive::state_manager::state_manager::{erase_state_manager, StateManager};
+use crate::inscriptive::sync_manager::sync_manager::SyncManager;
+use crate::inscriptive::utxo_set::utxo_set::UTXOSet;
+use crate::operative::backfill::errors::backfill_error::BackfillError;
+use crate::operative::run_args::chain::Chain;
+use bitcoin::hashes::Hash;
+use colored::Colorize;
+use std::time::Duration;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Backoff after the Bitcoin RPC or the engine could not be reached.
+const BACKFILL_RETRY_SECS: u64 = 5;
+
+/// Number of scanned blocks between two progress reports.
+const BLOCK_PROGRESS_INTERVAL: u64 = 1_000;
+
+/// Returns the batch height the batch archive needs to be backfilled up to, if any.
+///
+/// This is the case when a previous backfill was left incomplete, or when the ledger has synced
+/// batches while the archive lacks batch height #1, e.g. after a pruned node was restarted in
+/// archival resource mode.
+pub async fn backfill_target(chain: Chain) -> Result<Option<BatchHeight>, BackfillError> {
+    // 1 Read the cube batch sync height tip and the backfill record.
+    let (cube_batch_sync_height_tip, backfill_target_batch_height) = {
+        let sync_manager =
+            SyncManager::new(chain).map_err(BackfillError::SyncManagerConstructionError)?;
+        let _sync_manager = sync_manager.lock().await;
+        (
+            _sync_manager.cube_batch_sync_height_tip(),
+            _sync_manager.backfill_target_batch_height(),
+        )
+    };
+
+    // 2 Resume an incomplete backfill.
+    if let Some(target_batch_height) = backfill_target_batch_height {
+        return Ok(Some(target_batch_height));
+    }
+
+    // 3 There is nothing to backfill if no batches were synced yet.
+    if cube_batch_sync_height_tip == 0 {
+        return Ok(None);
+    }
+
+    // 4 Check whether the archive starts from batch height #1.
+    let archival_manager =
+        ArchivalManager::new(chain).map_err(BackfillError::ArchivalManagerConstructionError)?;
+    let archived = archival_manager
+        .lock()
+        .await
+        .batch_record_by_height(1)
+        .is_some();
+
+    // 5 Return the target batch height.
+    match archived {
+        true => Ok(None),
+        false => Ok(Some(cube_batch_sync_height_tip)),
+    }
+}
+
+/// Rebuilds the batch archive up to the given batch height.
+///
+/// Wipes the derived ledger state and the archive while keeping the Bitcoin-side utxo set, then
+/// walks the Bitcoin blocks synced so far through the Bitcoin RPC, following the payload chain from
+/// the genesis payload. Each batch transaction found on-chain is matched against the batch container
+/// served by the engine and replayed, which re-derives the ledger state and archives a batch record
+/// per batch height.
+///
+/// Returns the last replayed batch height.
+pub async fn backfill(
+    chain: Chain,
+    rpc_holder: &BitcoinRPCHolder,
+    engine_conn: &PEER,
+    target_batch_height: BatchHeight,
+) -> Result<BatchHeight, BackfillError> {
+    // 1 Record the start of the backfill, so that it is resumed if interrupted.
+    let sync_manager =
+        SyncManager::new(chain).map_err(BackfillError::SyncManagerConstructionError)?;
+    let bitcoin_sync_height_tip = {
+        let mut _sync_manager = sync_manager.lock().await;
+        _sync_manager.begin_backfill(target_batch_height);
+        _sync_manager.bitcoin_sync_height_tip()
+    };
+
+    // 2 Wipe the derived state and the archive.
+    erase_archival_manager(chain);
+    erase_coin_manager(chain);
+    erase_flame_manager(chain);
+    erase_graveyard(chain);
+    erase_registery(chain);
+    erase_state_manager(chain);
+    erase_privileges_manager(chain);
+    erase_params_manager(chain);
+
+    // 3 Rewind the cube batch tips and the commit journal in the sync manager while keeping the Bitcoin sync height tip.
+    {
+        let mut _sync_manager = sync_manager.lock().await;
+        _sync_manager.set_cube_batch_sync_height_tip(0);
+        _sync_manager.set_payload_tip(genesis_payload(chain));
+        _sync_manager.clear_commit_journal();
+    }
+
+    // 4 Re-open the managers from scratch.
+    let utxo_set = UTXOSet::new(chain).ok_or(BackfillError::UTXOSetConstructionError)?;
+    let registery = Registery::new(chain).map_err(BackfillError::RegisteryConstructionError)?;
+    let graveyard = Graveyard::new(chain).map_err(BackfillError::GraveyardConstructionError)?;
+    let coin_manager =
+        CoinManager::new(chain).map_err(BackfillError::CoinManagerConstructionError)?;
+    let flame_manager =
+        FlameManager::new(chain).map_err(BackfillError::FlameManagerConstructionError)?;
+    let state_manager =
+        StateManager::new(chain).map_err(BackfillError::StateManagerConstructionError)?;
+    let privileges_manager =
+        PrivilegesManager::new(chain).map_err(BackfillError::PrivilegesManagerConstructionError)?;
+    let params_manager =
+        ParamsManager::new(chain).map_err(BackfillError::ParamsManagerConstructionError)?;
+    let archival_manager =
+        ArchivalManager::new(chain).map_err(BackfillError::ArchivalManagerConstructionError)?;
+
+    // 5 Construct the execution context.
+    // The archival manager is included so that every replayed batch gets archived.
+    let exec_ctx = ExecCtx::construct(
+        engine_key(chain),
+        sync_manager.clone(),
+        utxo_set.clone(),
+        registery,
+        graveyard,
+        coin_manager,
+        flame_manager,
+        state_manager,
+        privileges_manager,
+        params_manager,
+        Some(archival_manager),
+    );
+
+    // 6 Walk the synced blocks.
+    let sync_start_height = match chain {
+        Chain::Signet | Chain::Testbed => baked::SIGNET_SYNC_START_HEIGHT,
+        Chain::Mainnet => baked::MAINNET_SYNC_START_HEIGHT,
+    };
+    let mut replayed_batch_height: BatchHeight = 0;
+    'block_scan: for block_height in sync_start_height..=bitcoin_sync_height_tip {
+        // 6.1 Retrieve the block.
+        let block = loop {
+            match retrieve_block(rpc_holder, block_height) {
+                Ok(block) => break block,
+                Err(err) => {
+                    eprintln!(
+                        "{}",
+                        format!(
+                            "Retrieve block error at height #{}: {}. Retrying in {}s...",
+                            block_height, err, BACKFILL_RETRY_SECS
+                        )
+                        .yellow()
+                    );
+                    tokio::time::sleep(Duration::from_secs(BACKFILL_RETRY_SECS)).await;
+                }
+            }
+        };
+
+        // 6.2 Scan the block for the next batch transaction.
+        for transaction in block.txdata.iter() {
+            // 6.2.1 A batch transaction spends the payload tip with its first input.
+            let prev_payload_tip_outpoint = {
+                let _sync_manager = sync_manager.lock().await;
+                _sync_manager
+                    .payload_tip()
+                    .outpoint()
+                    .expect("This should never happen.")
+            };
+            match transaction.input.first() {
+                Some(first_tx_input)
+                    if first_tx_input.previous_output == prev_payload_tip_outpoint => {}
+                _ => continue,
+            }
+            let batch_height = replayed_batch_height + 1;
+
+            // 6.2.2 Request the batch container from the engine.
+            let response_body = loop {
+                match engine_conn
+                    .request_batchcontainer_by_prevoutpoint(prev_payload_tip_outpoint)
+                    .await
+                {
+                    Ok((response_body, _)) => break response_body,
+                    Err(err) => {
+                        eprintln!(
+                            "{}",
+                            format!(
+                                "Error requesting batch container #{}: {:?}. Retrying in {}s...",
+                                batch_height, err, BACKFILL_RETRY_SECS
+                            )
+                            .yellow()
+                        );
+                        tokio::time::sleep(Duration::from_secs(BACKFILL_RETRY_SECS)).await;
+                    }
+                }
+            };
+            let batch_container = match response_body {
+                BatchContainerByPrevOutpointResponseBody::Ok(success_body) => success_body
+                    .batch_container
+                    .ok_or(BackfillError::BatchContainerUnavailable(batch_height))?,
+                BatchContainerByPrevOutpointResponseBody::Err(err) => {
+                    return Err(BackfillError::BatchContainerRequestRejected(
+                        batch_height,
+                        err,
+                    ));
+                }
+            };
+
+            // 6.2.3 Make sure the batch container matches the batch transaction found on-chain.
+            let onchain_batch_txid = transaction.compute_txid().to_byte_array();
+            let served_batch_txid = batch_container.batch_txid();
+            if onchain_batch_txid != served_batch_txid {
+                return Err(BackfillError::BatchTxidMismatch {
+                    batch_height,
+                    onchain_batch_txid,
+                    served_batch_txid,
+                });
+            }
+
+            // 6.2.4 Restore the Bitcoin inputs spent by the batch, so that lift prevouts can be resolved again.
+            {
+                let mut _utxo_set = utxo_set.lock().await;
+                for (outpoint, txout, _) in batch_container.signed_batch_txn.tx_inputs.iter() {
+                    _utxo_set.insert_utxo(outpoint, txout);
+                }
+            }
+
+            // 6.2.5 Replay the batch, which also archives it.
+            {
+                let mut _exec_ctx = exec_ctx.lock().await;
+                _exec_ctx
+                    .execute_batch(&batch_container)
+                    .await
+                    .map_err(|err| BackfillError::ReplayBatchError(batch_height, err))?;
+            }
+
+            // 6.2.6 Update the replayed batch height.
+            replayed_batch_height = batch_height;
+
+            println!(
+                "Backfilled batch height #{}/#{} (block #{}).",
+                replayed_batch_height, target_batch_height, block_height
+            );
+
+            // 6.2.7 Stop scanning the block once the target batch height is reached.
+            if replayed_batch_height == target_batch_height {
+                break 'block_scan;
+            }
+        }
+
+        // 6.3 Report the scan progress every now and then.
+        if (block_height - sync_start_height) % BLOCK_PROGRESS_INTERVAL == 0 {
+            println!(
+                "Backfill scanned block #{}/#{}.",
+                block_height, bitcoin_sync_height_tip
+            );
+        }
+    }
+
+    // 7 Make sure the target batch height was reached.
+    if replayed_batch_height != target_batch_height {
+        return Err(BackfillError::TargetBatchHeightNotReached {
+            target_batch_height,
+            replayed_batch_height,
+        });
+    }
+
+    // 8 Record the end of the backfill.
+    {
+        let mut _sync_manager = sync_manager.lock().await;
+        _sync_manager.end_backfill();
+    }
+
+    // 9 Return the last replayed batch height.
+    Ok(replayed_batch_height)
+}
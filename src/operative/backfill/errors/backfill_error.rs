@@ -0,0 +1,44 @@
+use crate::communicative::tcp::client::BatchContainerByPrevOutpointResponseError;
+use crate::executive::exec_ctx::errors::batch_execution_error::BatchExecutionError;
+use crate::inscriptive::archival_manager::errors::construction_error::ArchivalConstructionError;
+use crate::inscriptive::coin_manager::errors::construction_errors::CMConstructionError;
+use crate::inscriptive::flame_manager::errors::construction_error::FMConstructionError;
+use crate::inscriptive::graveyard::errors::construction_error::GraveyardConstructionError;
+use crate::inscriptive::privileges_manager::errors::construction_error::PrivilegesManagerConstructionError;
+use crate::inscriptive::registery::errors::construction_error::RMConstructionError;
+use crate::inscriptive::state_manager::errors::construction_error::SMConstructionError as StateManagerConstructionError;
+use crate::inscriptive::sync_manager::errors::construction_error::SMConstructionError as SyncManagerConstructionError;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Errors associated with backfilling the batch archive.
+#[derive(Debug, Clone)]
+pub enum BackfillError {
+    ArchivalManagerConstructionError(ArchivalConstructionError),
+    SyncManagerConstructionError(SyncManagerConstructionError),
+    UTXOSetConstructionError,
+    RegisteryConstructionError(RMConstructionError),
+    GraveyardConstructionError(GraveyardConstructionError),
+    CoinManagerConstructionError(CMConstructionError),
+    FlameManagerConstructionError(FMConstructionError),
+    StateManagerConstructionError(StateManagerConstructionError),
+    PrivilegesManagerConstructionError(PrivilegesManagerConstructionError),
+    ParamsManagerConstructionError(sled::Error),
+    /// The engine refused to serve the batch container of the given batch height.
+    BatchContainerRequestRejected(BatchHeight, BatchContainerByPrevOutpointResponseError),
+    /// The engine has no batch container for the given batch height.
+    BatchContainerUnavailable(BatchHeight),
+    /// The batch container served by the engine does not match the batch transaction found on-chain.
+    BatchTxidMismatch {
+        batch_height: BatchHeight,
+        onchain_batch_txid: [u8; 32],
+        served_batch_txid: [u8; 32],
+    },
+    ReplayBatchError(BatchHeight, BatchExecutionError),
+    /// The blocks synced so far end before the target batch height was reached.
+    TargetBatchHeightNotReached {
+        target_batch_height: BatchHeight,
+        replayed_batch_height: BatchHeight,
+    },
+}
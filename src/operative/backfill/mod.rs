@@ -0,0 +1,2 @@
+pub mod backfill;
+pub mod errors;
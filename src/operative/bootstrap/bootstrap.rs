@@ -0,0 +1,220 @@
+use crate::inscriptive::coin_manager::coin_manager::CoinManager;
+use crate::inscriptive::sync_manager::sync_manager::SyncManager;
+use crate::operative::bootstrap::errors::bootstrap_error::BootstrapError;
+use crate::operative::bootstrap::snapshot::SnapshotBundle;
+use crate::operative::bootstrap::trust_anchors::TrustAnchors;
+use crate::operative::recovery::errors::recovery_error::RecoveryError;
+use crate::operative::recovery::recovery::check_ledger;
+use crate::operative::run_args::chain::Chain;
+use colored::Colorize;
+
+/// Runs the bootstrap procedure and prints the outcome.
+#[tokio::main]
+pub async fn run(source: &str) {
+    // 1 Print the bootstrapping message.
+    println!("{}", format!("Bootstrapping from {}.", source));
+
+    // 2 Bootstrap and print the result.
+    match bootstrap(source).await {
+        Ok(snapshot) => println!(
+            "{}",
+            format!(
+                "Bootstrap complete. Imported {} at batch height #{} (Bitcoin height #{}). Syncing resumes from there on the next run.",
+                snapshot.chain.to_string(),
+                snapshot.batch_height,
+                snapshot.bitcoin_sync_height
+            )
+            .green()
+        ),
+        Err(err) => {
+            eprintln!("{} {:?}", "Bootstrap error:".red(), err);
+            eprintln!("{}", err.remedy().yellow());
+        }
+    }
+}
+
+/// Runs the snapshot export procedure and prints the outcome.
+#[tokio::main]
+pub async fn run_export(chain: Chain, path: &str) {
+    match export(chain, path).await {
+        Ok(snapshot) => println!(
+            "{}",
+            format!(
+                "Snapshot exported to {} at batch height #{} (Bitcoin height #{}) with state root {}.",
+                path,
+                snapshot.batch_height,
+                snapshot.bitcoin_sync_height,
+                hex::encode(snapshot.state_root)
+            )
+            .green()
+        ),
+        Err(err) => {
+            eprintln!("{} {:?}", "Snapshot export error:".red(), err);
+            eprintln!("{}", err.remedy().yellow());
+        }
+    }
+}
+
+/// Imports a state snapshot from a file or an HTTP(S) URL into empty storage.
+///
+/// The state root of the snapshot must match one of the trust anchors configured through
+/// `CUBE_TRUST_ANCHORS` at its batch height. Once imported, the heights and the state root are
+/// recomputed from the imported databases and checked against the ones the snapshot declares, and the
+/// ledger is checked for inconsistencies; the imported databases are erased if any check fails.
+///
+/// Returns the imported snapshot, with its databases emptied.
+pub async fn bootstrap(source: &str) -> Result<SnapshotBundle, BootstrapError> {
+    // 1 Read the trust anchors.
+    let trust_anchors = TrustAnchors::from_env().ok_or(BootstrapError::MalformedTrustAnchors)?;
+    if trust_anchors.is_empty() {
+        return Err(BootstrapError::NoTrustAnchors);
+    }
+
+    // 2 Read and parse the snapshot.
+    let bytes = read_snapshot(source).await?;
+    let mut snapshot =
+        SnapshotBundle::from_bytes(&bytes).ok_or(BootstrapError::MalformedSnapshot)?;
+
+    // 3 Verify the state root against the trust anchors.
+    if !trust_anchors.is_trusted(snapshot.batch_height, snapshot.state_root) {
+        return Err(BootstrapError::UntrustedStateRoot(
+            snapshot.batch_height,
+            snapshot.state_root,
+        ));
+    }
+
+    // 4 Refuse to overwrite existing ledger state.
+    if SnapshotBundle::storage_occupied(snapshot.chain) {
+        return Err(BootstrapError::StorageOccupied);
+    }
+
+    // 5 Write the databases.
+    if let Err(err) = snapshot.write_databases() {
+        SnapshotBundle::erase_databases(snapshot.chain);
+        return Err(BootstrapError::DatabaseError(err));
+    }
+    snapshot.databases.clear();
+
+    // 6 Verify the imported ledger state, erasing it if it does not check out.
+    if let Err(err) = verify_imported(&snapshot).await {
+        SnapshotBundle::erase_databases(snapshot.chain);
+        return Err(err);
+    }
+
+    // 7 Return the snapshot.
+    Ok(snapshot)
+}
+
+/// Exports the ledger state of a stopped node into a snapshot file.
+///
+/// Returns the exported snapshot, with its databases emptied.
+pub async fn export(chain: Chain, path: &str) -> Result<SnapshotBundle, BootstrapError> {
+    // 1 Make sure the ledger is consistent.
+    if let Some(inconsistency) = check_ledger(chain)
+        .await
+        .map_err(BootstrapError::LedgerCheckError)?
+    {
+        return Err(BootstrapError::InconsistentLedger(inconsistency));
+    }
+
+    // 2 Collect the heights and the state root.
+    let (bitcoin_sync_height, batch_height, state_root) = ledger_tip(chain).await?;
+
+    // 3 Read the databases.
+    let databases = SnapshotBundle::read_databases(chain).map_err(BootstrapError::DatabaseError)?;
+    let mut snapshot = SnapshotBundle {
+        chain,
+        bitcoin_sync_height,
+        batch_height,
+        state_root,
+        databases,
+    };
+
+    // 4 Write the snapshot.
+    std::fs::write(path, snapshot.to_bytes())
+        .map_err(|err| BootstrapError::SnapshotWriteError(err.to_string()))?;
+    snapshot.databases.clear();
+
+    // 5 Return the snapshot.
+    Ok(snapshot)
+}
+
+/// Reads the snapshot bytes from a file or an HTTP(S) URL.
+async fn read_snapshot(source: &str) -> Result<Vec<u8>, BootstrapError> {
+    match source.starts_with("http://") || source.starts_with("https://") {
+        // a Fetch the snapshot from the URL.
+        true => {
+            let response = reqwest::get(source)
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|err| BootstrapError::SnapshotUnavailable(err.to_string()))?;
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(|err| BootstrapError::SnapshotUnavailable(err.to_string()))?;
+            Ok(bytes.to_vec())
+        }
+
+        // b Read the snapshot from the file.
+        false => std::fs::read(source)
+            .map_err(|err| BootstrapError::SnapshotUnavailable(err.to_string())),
+    }
+}
+
+/// Checks the imported heights, state root and ledger consistency against the snapshot.
+async fn verify_imported(snapshot: &SnapshotBundle) -> Result<(), BootstrapError> {
+    // 1 Check the heights and the state root.
+    let (bitcoin_sync_height, batch_height, computed_state_root) =
+        ledger_tip(snapshot.chain).await?;
+    if (bitcoin_sync_height, batch_height) != (snapshot.bitcoin_sync_height, snapshot.batch_height)
+    {
+        return Err(BootstrapError::HeightMismatch {
+            declared: (snapshot.bitcoin_sync_height, snapshot.batch_height),
+            imported: (bitcoin_sync_height, batch_height),
+        });
+    }
+    if computed_state_root != snapshot.state_root {
+        return Err(BootstrapError::StateRootMismatch {
+            declared_state_root: snapshot.state_root,
+            computed_state_root,
+        });
+    }
+
+    // 2 Check the ledger for inconsistencies.
+    if let Some(inconsistency) = check_ledger(snapshot.chain)
+        .await
+        .map_err(BootstrapError::LedgerCheckError)?
+    {
+        return Err(BootstrapError::InconsistentLedger(inconsistency));
+    }
+
+    Ok(())
+}
+
+/// Returns the Bitcoin sync height, the batch height and the account balances state root of the ledger.
+///
+/// The managers are opened here and closed before returning.
+pub async fn ledger_tip(chain: Chain) -> Result<(u64, u64, [u8; 32]), BootstrapError> {
+    // 1 Collect the heights.
+    let (bitcoin_sync_height, batch_height) = {
+        let sync_manager = SyncManager::new(chain).map_err(|err| {
+            BootstrapError::LedgerCheckError(RecoveryError::SyncManagerConstructionError(err))
+        })?;
+        let _sync_manager = sync_manager.lock().await;
+        (
+            _sync_manager.bitcoin_sync_height_tip(),
+            _sync_manager.cube_batch_sync_height_tip(),
+        )
+    };
+
+    // 2 Compute the state root.
+    let state_root = {
+        let coin_manager = CoinManager::new(chain).map_err(|err| {
+            BootstrapError::LedgerCheckError(RecoveryError::CoinManagerConstructionError(err))
+        })?;
+        let _coin_manager = coin_manager.lock().await;
+        _coin_manager.account_balances_state_root()
+    };
+
+    Ok((bitcoin_sync_height, batch_height, state_root))
+}
@@ -0,0 +1,75 @@
+use crate::operative::recovery::errors::ledger_inconsistency::LedgerInconsistency;
+use crate::operative::recovery::errors::recovery_error::RecoveryError;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Errors associated with importing or exporting a state snapshot.
+#[derive(Debug, Clone)]
+pub enum BootstrapError {
+    /// The snapshot could not be read from the file or fetched from the URL.
+    SnapshotUnavailable(String),
+    /// The snapshot is not a valid snapshot bundle.
+    MalformedSnapshot,
+    /// `CUBE_TRUST_ANCHORS` is malformed.
+    MalformedTrustAnchors,
+    /// No trust anchors are configured.
+    NoTrustAnchors,
+    /// The state root of the snapshot is not trusted at its batch height.
+    UntrustedStateRoot(BatchHeight, [u8; 32]),
+    /// The storage directory already holds ledger state.
+    StorageOccupied,
+    /// The snapshot databases could not be read or written.
+    DatabaseError(sled::Error),
+    /// The imported heights do not match the ones declared by the snapshot.
+    HeightMismatch {
+        declared: (BlockHeight, BatchHeight),
+        imported: (BlockHeight, BatchHeight),
+    },
+    /// The imported state root does not match the one declared by the snapshot.
+    StateRootMismatch {
+        declared_state_root: [u8; 32],
+        computed_state_root: [u8; 32],
+    },
+    /// The imported or to be exported ledger state is inconsistent.
+    InconsistentLedger(LedgerInconsistency),
+    /// The imported or to be exported ledger state could not be opened.
+    LedgerCheckError(RecoveryError),
+    /// The snapshot could not be written to the file.
+    SnapshotWriteError(String),
+}
+
+impl BootstrapError {
+    /// Returns what the operator can do about the error.
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            BootstrapError::SnapshotUnavailable(_) | BootstrapError::MalformedSnapshot => {
+                "Make sure the snapshot file or URL points to a snapshot bundle exported with 'snapshot export'."
+            }
+            BootstrapError::MalformedTrustAnchors | BootstrapError::NoTrustAnchors => {
+                "Set CUBE_TRUST_ANCHORS to a comma-separated list of <batch_height>:<state_root_hex> pairs obtained from a source you trust."
+            }
+            BootstrapError::UntrustedStateRoot(_, _) => {
+                "The snapshot does not match any of the trust anchors. Obtain a snapshot from a source you trust, or a trust anchor for its batch height."
+            }
+            BootstrapError::StorageOccupied => {
+                "Bootstrapping only starts from empty storage. Move the existing storage directory of the chain out of the way first."
+            }
+            BootstrapError::DatabaseError(_) | BootstrapError::LedgerCheckError(_) => {
+                "Make sure no other instance is using the storage directory, then try again."
+            }
+            BootstrapError::HeightMismatch { .. } | BootstrapError::StateRootMismatch { .. } => {
+                "The snapshot contents do not match what it declares. Obtain a snapshot from a source you trust."
+            }
+            BootstrapError::InconsistentLedger(_) => {
+                "When importing, obtain a snapshot from a source you trust. When exporting, restart the node once to let the recovery sequence repair the ledger first."
+            }
+            BootstrapError::SnapshotWriteError(_) => {
+                "Make sure the snapshot file path is writable."
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod bootstrap_error;
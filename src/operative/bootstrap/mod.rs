@@ -0,0 +1,4 @@
+pub mod bootstrap;
+pub mod errors;
+pub mod snapshot;
+pub mod trust_anchors;
@@ -0,0 +1,219 @@
+use crate::operative::run_args::chain::Chain;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Snapshot bundle format version.
+pub const SNAPSHOT_VERSION: u64 = 1;
+
+/// Databases carried by a snapshot bundle, relative to `storage/<chain>`.
+///
+/// These are the ledger state (coins, states, registery, flames, privileges, params, graveyard and
+/// the schedule of deferred calls),
+/// the sync manager holding the heights and the payload tip, and the utxo set holding the sync cursor.
+/// Archived batch records and undo data are not carried.
+pub const SNAPSHOT_DATABASES: [&str; 13] = [
+    "coins/accounts",
+    "coins/contracts",
+    "states",
+    "registery/accounts",
+    "registery/contracts",
+    "flames/accounts",
+    "privileges/accounts",
+    "privileges/contracts",
+    "params",
+    "graveyard",
+    "schedule",
+    "sync_manager",
+    "utxo_set",
+];
+
+/// Key-value pairs of a tree.
+type TreeEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Trees of a database by their name.
+type DatabaseTrees = BTreeMap<Vec<u8>, TreeEntries>;
+
+/// A full ledger state bundle taken at a given height.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotBundle {
+    // Chain the snapshot was taken on.
+    pub chain: Chain,
+
+    // Bitcoin sync height the snapshot was taken at.
+    pub bitcoin_sync_height: BlockHeight,
+
+    // Batch height the snapshot was taken at.
+    pub batch_height: BatchHeight,
+
+    // Account balances state root at the batch height.
+    pub state_root: [u8; 32],
+
+    // Databases by their path relative to `storage/<chain>`.
+    pub databases: BTreeMap<String, DatabaseTrees>,
+}
+
+impl SnapshotBundle {
+    /// Reads the snapshot databases from the storage directory of a chain.
+    ///
+    /// The databases must not be open elsewhere, i.e. the node must be stopped.
+    pub fn read_databases(chain: Chain) -> Result<BTreeMap<String, DatabaseTrees>, sled::Error> {
+        let mut databases = BTreeMap::new();
+        for database in SNAPSHOT_DATABASES.iter() {
+            // 1 Open the database.
+            let db = sled::open(format!("storage/{}/{}", chain.to_string(), database))?;
+
+            // 2 Collect the entries of each tree.
+            let mut trees = DatabaseTrees::new();
+            for tree_name in db.tree_names() {
+                let tree = db.open_tree(&tree_name)?;
+                let entries = tree
+                    .iter()
+                    .map(|entry| entry.map(|(key, value)| (key.to_vec(), value.to_vec())))
+                    .collect::<Result<TreeEntries, sled::Error>>()?;
+                trees.insert(tree_name.to_vec(), entries);
+            }
+            databases.insert(database.to_string(), trees);
+        }
+        Ok(databases)
+    }
+
+    /// Writes the snapshot databases into the storage directory of a chain.
+    pub fn write_databases(&self) -> Result<(), sled::Error> {
+        for (database, trees) in self.databases.iter() {
+            // 1 Open the database.
+            let db = sled::open(format!("storage/{}/{}", self.chain.to_string(), database))?;
+
+            // 2 Insert the entries of each tree.
+            for (tree_name, entries) in trees.iter() {
+                let tree = db.open_tree(tree_name)?;
+                let mut batch = sled::Batch::default();
+                for (key, value) in entries.iter() {
+                    batch.insert(key.as_slice(), value.as_slice());
+                }
+                tree.apply_batch(batch)?;
+            }
+
+            // 3 Flush the database.
+            db.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Whether any of the snapshot databases already exists in the storage directory of a chain.
+    pub fn storage_occupied(chain: Chain) -> bool {
+        SNAPSHOT_DATABASES.iter().any(|database| {
+            Path::new(&format!("storage/{}/{}", chain.to_string(), database)).exists()
+        })
+    }
+
+    /// Removes the snapshot databases from the storage directory of a chain.
+    pub fn erase_databases(chain: Chain) {
+        for database in SNAPSHOT_DATABASES.iter() {
+            let _ = std::fs::remove_dir_all(format!("storage/{}/{}", chain.to_string(), database));
+        }
+    }
+
+    /// Returns the snapshot bundle as JSON bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // 1 Encode the databases, keys and values in hex.
+        let mut databases = Map::new();
+        for (database, trees) in self.databases.iter() {
+            let mut trees_obj = Map::new();
+            for (tree_name, entries) in trees.iter() {
+                let entries: Vec<Value> = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Value::Array(vec![
+                            Value::String(hex::encode(key)),
+                            Value::String(hex::encode(value)),
+                        ])
+                    })
+                    .collect();
+                trees_obj.insert(hex::encode(tree_name), Value::Array(entries));
+            }
+            databases.insert(database.clone(), Value::Object(trees_obj));
+        }
+
+        // 2 Construct the bundle.
+        let mut obj = Map::new();
+        obj.insert(
+            "version".to_string(),
+            Value::Number(SNAPSHOT_VERSION.into()),
+        );
+        obj.insert("chain".to_string(), Value::String(self.chain.to_string()));
+        obj.insert(
+            "bitcoin_sync_height".to_string(),
+            Value::Number(self.bitcoin_sync_height.into()),
+        );
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height.into()),
+        );
+        obj.insert(
+            "state_root".to_string(),
+            Value::String(hex::encode(self.state_root)),
+        );
+        obj.insert("databases".to_string(), Value::Object(databases));
+
+        // 3 Return the bytes.
+        serde_json::to_vec(&Value::Object(obj)).expect("serde_json::Value should serialize")
+    }
+
+    /// Parses a snapshot bundle from JSON bytes.
+    ///
+    /// Returns `None` if the bundle is malformed, of an unknown version, or carries unknown databases.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        // 1 Parse the bundle.
+        let value: Value = serde_json::from_slice(bytes).ok()?;
+        if value.get("version")?.as_u64()? != SNAPSHOT_VERSION {
+            return None;
+        }
+        let chain = match value.get("chain")?.as_str()? {
+            "signet" => Chain::Signet,
+            "mainnet" => Chain::Mainnet,
+            "testbed" => Chain::Testbed,
+            _ => return None,
+        };
+        let bitcoin_sync_height = value.get("bitcoin_sync_height")?.as_u64()?;
+        let batch_height = value.get("batch_height")?.as_u64()?;
+        let state_root: [u8; 32] = hex::decode(value.get("state_root")?.as_str()?)
+            .ok()?
+            .try_into()
+            .ok()?;
+
+        // 2 Decode the databases.
+        let mut databases = BTreeMap::new();
+        for (database, trees_value) in value.get("databases")?.as_object()?.iter() {
+            if !SNAPSHOT_DATABASES.contains(&database.as_str()) {
+                return None;
+            }
+            let mut trees = DatabaseTrees::new();
+            for (tree_name, entries_value) in trees_value.as_object()?.iter() {
+                let mut entries = TreeEntries::new();
+                for entry in entries_value.as_array()?.iter() {
+                    let key = hex::decode(entry.get(0)?.as_str()?).ok()?;
+                    let value = hex::decode(entry.get(1)?.as_str()?).ok()?;
+                    entries.push((key, value));
+                }
+                trees.insert(hex::decode(tree_name).ok()?, entries);
+            }
+            databases.insert(database.clone(), trees);
+        }
+
+        // 3 Return the bundle.
+        Some(Self {
+            chain,
+            bitcoin_sync_height,
+            batch_height,
+            state_root,
+            databases,
+        })
+    }
+}
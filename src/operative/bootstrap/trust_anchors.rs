@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// State roots trusted at given batch heights, against which imported snapshots are verified.
+#[derive(Debug, Clone, Default)]
+pub struct TrustAnchors {
+    // Trusted account balances state roots by batch height.
+    anchors: HashMap<BatchHeight, [u8; 32]>,
+}
+
+impl TrustAnchors {
+    /// Parses trust anchors from a comma-separated list of `<batch_height>:<state_root_hex>` pairs.
+    ///
+    /// Returns `None` if any pair is malformed.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut anchors = HashMap::new();
+        for pair in value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (batch_height, state_root) = pair.split_once(':')?;
+            let batch_height = batch_height.trim().parse::<BatchHeight>().ok()?;
+            let state_root: [u8; 32] = hex::decode(state_root.trim()).ok()?.try_into().ok()?;
+            anchors.insert(batch_height, state_root);
+        }
+        Some(Self { anchors })
+    }
+
+    /// Reads the trust anchors from `CUBE_TRUST_ANCHORS`.
+    ///
+    /// Returns `None` if they are malformed; unset means no trust anchors.
+    pub fn from_env() -> Option<Self> {
+        match std::env::var("CUBE_TRUST_ANCHORS") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Some(Self::default()),
+        }
+    }
+
+    /// Whether there are no trust anchors.
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Whether the state root is the one trusted at the batch height.
+    pub fn is_trusted(&self, batch_height: BatchHeight, state_root: [u8; 32]) -> bool {
+        self.anchors.get(&batch_height) == Some(&state_root)
+    }
+}
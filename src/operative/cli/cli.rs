@@ -1,3 +1,5 @@
+use crate::communicative::api_auth::api_auth::API_AUTH;
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
 use crate::communicative::peer::peer::PEER;
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
@@ -11,10 +13,15 @@ use crate::inscriptive::state_manager::state_manager::STATE_MANAGER;
 use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
 use crate::inscriptive::utxo_set::utxo_set::UTXO_SET;
 use crate::operative::cli::commands::common_commands;
+use crate::operative::cli::commands::engine_commands;
+use crate::operative::cli::commands::light_commands;
 use crate::operative::cli::commands::node_commands;
 use crate::operative::run_args::chain::Chain;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
-use crate::transmutative::key::KeyHolder;
+use crate::operative::tasks::mempool::mempool::MEMPOOL;
+use crate::operative::tasks::mempool_watch::mempool_watch::MEMPOOL_WATCH;
+use crate::transmutative::key::{FromNostrKeyStr, KeyHolder};
+use crate::transmutative::secp::public_key;
 use colored::Colorize;
 use std::io;
 use std::io::BufRead;
@@ -30,6 +37,8 @@ pub async fn run_engine_cli(
     flame_manager: &FLAME_MANAGER,
     key_holder: &KeyHolder,
     archival_manager: Option<ARCHIVAL_MANAGER>,
+    peer_access_list: &PEER_ACCESS_LIST,
+    api_auth: &API_AUTH,
 ) {
     // 1 Print the CLI prompt.
     print_cli_prompt();
@@ -68,6 +77,8 @@ pub async fn run_engine_cli(
                     None,
                     coin_manager,
                     flame_manager,
+                    api_auth,
+                    None,
                 )
                 .await;
             }
@@ -171,6 +182,37 @@ pub async fn run_engine_cli(
                     }
                 }
             }
+            "peers" => {
+                match (
+                    parts.get(1).map(String::as_str),
+                    parts.get(2).map(String::as_str),
+                ) {
+                    (Some("list"), None) => {
+                        engine_commands::peers::peers_list_command(peer_access_list).await
+                    }
+                    (Some(action @ ("allow" | "disallow" | "ban" | "unban")), Some(npub)) => {
+                        let peer_key = match npub.from_npub() {
+                            Some(key) => key,
+                            None => {
+                                eprintln!("{}", "Invalid peer key: expected npub.".yellow());
+                                continue;
+                            }
+                        };
+                        engine_commands::peers::peers_update_command(
+                            peer_access_list,
+                            action,
+                            peer_key,
+                        )
+                        .await;
+                    }
+                    _ => {
+                        eprintln!(
+                            "{}",
+                            "Usage: peers <list|allow|disallow|ban|unban> [npub].".yellow()
+                        );
+                    }
+                }
+            }
             _ => eprintln!("{}", format!("Unknown commmand.").yellow()),
         }
     }
@@ -194,6 +236,9 @@ pub async fn run_node_cli(
     privileges_manager: &PRIVILEGES_MANAGER,
     params_manager: &PARAMS_MANAGER,
     archival_manager: Option<ARCHIVAL_MANAGER>,
+    mempool: &MEMPOOL,
+    mempool_watch: &MEMPOOL_WATCH,
+    api_auth: &API_AUTH,
 ) {
     // 1 Print the CLI prompt.
     print_cli_prompt();
@@ -232,6 +277,8 @@ pub async fn run_node_cli(
                     Some(privileges_manager),
                     coin_manager,
                     flame_manager,
+                    api_auth,
+                    None,
                 )
                 .await;
             }
@@ -410,7 +457,13 @@ pub async fn run_node_cli(
                         }
                     },
                 };
-                node_commands::coins::coins_command(coin_manager, account_key).await;
+                // Unconfirmed deposits are only watched for the node's own account.
+                let mempool_watch = match account_key == self_account_key {
+                    true => Some(mempool_watch),
+                    false => None,
+                };
+                node_commands::coins::coins_command(coin_manager, mempool_watch, account_key)
+                    .await;
             }
             "decompile" => {
                 let parts_ref: Vec<&str> = parts.iter().map(String::as_str).collect();
@@ -472,13 +525,15 @@ pub async fn run_node_cli(
                     }
                 }
             }
+            "mempool" => node_commands::mempool::mempool_command(mempool).await,
+            "pending" => node_commands::pending::pending_command(mempool_watch).await,
             "move" => {
                 let satoshi_amount: u32 = match parts.get(1).and_then(|s| s.parse().ok()) {
                     Some(amount) => amount,
                     None => {
                         eprintln!(
                             "{}",
-                            "Usage: move <satoshi_amount> <to_account_key_hex>.".yellow()
+                            "Usage: move <satoshi_amount> <to_account_key_hex> [priority_fee].".yellow()
                         );
                         continue;
                     }
@@ -498,19 +553,35 @@ pub async fn run_node_cli(
                     None => {
                         eprintln!(
                             "{}",
-                            "Usage: move <satoshi_amount> <to_account_key_hex>.".yellow()
+                            "Usage: move <satoshi_amount> <to_account_key_hex> [priority_fee].".yellow()
                         );
                         continue;
                     }
                 };
 
+                let priority_fee: u32 = match parts.get(3) {
+                    Some(priority_fee_str) => match priority_fee_str.parse() {
+                        Ok(priority_fee) => priority_fee,
+                        Err(_) => {
+                            eprintln!(
+                                "{}",
+                                "Usage: move <satoshi_amount> <to_account_key_hex> [priority_fee]."
+                                    .yellow()
+                            );
+                            continue;
+                        }
+                    },
+                    None => 0,
+                };
+
                 node_commands::r#move::move_command(
                     satoshi_amount,
                     to_account_key,
+                    priority_fee,
                     key_holder,
                     sync_manager,
                     registery,
-                    engine_conn,
+                    mempool,
                 )
                 .await;
             }
@@ -560,6 +631,33 @@ pub async fn run_node_cli(
                 )
                 .await;
             }
+            "rotatekey" => {
+                let rotation_key = match parts.get(1).map(String::as_str) {
+                    None => None,
+                    Some(arg) => {
+                        if let Ok(index) = arg.parse::<u32>() {
+                            Some(node_commands::rotatekey::RotationKey::Index(index))
+                        } else if let Some(secret_key) = arg.from_nsec() {
+                            Some(node_commands::rotatekey::RotationKey::Secret(secret_key))
+                        } else {
+                            eprintln!("{}", "Usage: rotatekey [<index> | <nsec>].".yellow());
+                            continue;
+                        }
+                    }
+                };
+
+                node_commands::rotatekey::rotatekey_command(
+                    rotation_key,
+                    key_holder,
+                    self_account_key,
+                    sync_manager,
+                    registery,
+                    coin_manager,
+                    params_manager,
+                    engine_conn,
+                )
+                .await;
+            }
             "deploy" => {
                 let initial_balance: u32 = match parts.get(1).and_then(|s| s.parse().ok()) {
                     Some(value) => value,
@@ -610,6 +708,62 @@ pub async fn run_node_cli(
     }
 }
 
+/// Runs the light client CLI.
+pub async fn run_light_cli(
+    chain: Chain,
+    engine_key: [u8; 32],
+    self_account_key: [u8; 32],
+    engine_conn: &PEER,
+    key_holder: &KeyHolder,
+) {
+    // 1 Print the CLI prompt.
+    print_cli_prompt();
+
+    // 2 Read the CLI input.
+    let stdin = io::stdin();
+    let handle = stdin.lock();
+
+    // 3 Parse the CLI input.
+    for line in handle.lines() {
+        // 3.1 Parse the CLI input.
+        let parts = match parse_cli_parts(line) {
+            Some(parts) => parts,
+            None => continue,
+        };
+
+        // 3.2 Match the CLI input.
+        match parts[0].as_str() {
+            // Main commands:
+            "exit" => break,
+            "clear" => common_commands::clear::clear_command(),
+            "engine" => common_commands::engine::engine_command(chain),
+            "conn" => node_commands::conn::conn_command(engine_conn).await,
+            "ping" => node_commands::ping::ping_command(engine_conn).await,
+            "npub" => node_commands::npub::npub_command(key_holder).await,
+            "balance" => {
+                let account_key = match parts.get(1).map(String::as_str) {
+                    None => self_account_key,
+                    Some(account_key_str) => match parse_account_key(account_key_str) {
+                        Some(key) => key,
+                        None => {
+                            eprintln!("{}", "Invalid account key: expected 32-byte hex.".yellow());
+                            continue;
+                        }
+                    },
+                };
+                light_commands::balance::balance_command(
+                    chain,
+                    engine_key,
+                    account_key,
+                    engine_conn,
+                )
+                .await;
+            }
+            _ => eprintln!("{}", format!("Unknown commmand.").yellow()),
+        }
+    }
+}
+
 /// Prints the CLI prompt.
 fn print_cli_prompt() {
     println!(
@@ -677,7 +831,7 @@ fn split_cli_args_with_quotes(input: &str) -> Option<Vec<String>> {
 }
 
 fn parse_account_key(account_key_str: &str) -> Option<[u8; 32]> {
-    parse_32_byte_hex(account_key_str)
+    public_key::parse_account_key(account_key_str).ok()
 }
 
 fn parse_contract_id(contract_id_str: &str) -> Option<[u8; 32]> {
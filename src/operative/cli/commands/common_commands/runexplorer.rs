@@ -1,3 +1,11 @@
+use crate::communicative::api_auth::api_auth::API_AUTH;
+use crate::communicative::api_auth::api_identity::ApiIdentity;
+use crate::communicative::api_auth::api_permission::ApiPermission;
+use crate::communicative::api_auth::errors::api_auth_error::ApiAuthError;
+use crate::communicative::event_stream::event_stream::{event_stream, SubscriptionMessage};
+use crate::communicative::event_stream::subscription_filter::SubscriptionFilter;
+use crate::communicative::rate_limiter::rate_limit_config::RateLimitConfig;
+use crate::communicative::rate_limiter::rate_limiter::{RateLimiter, RATE_LIMITER};
 use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
 use crate::constructive::entry::entry::entry::Entry;
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
@@ -8,14 +16,23 @@ use crate::inscriptive::privileges_manager::elements::exemption::exemption::Exem
 use crate::inscriptive::privileges_manager::elements::exemption::periodic_resource::periodic_resource::PeriodicResource;
 use crate::inscriptive::privileges_manager::privileges_manager::PRIVILEGES_MANAGER;
 use crate::inscriptive::registery::registery::REGISTERY;
+use crate::operative::admin::admin_command::AdminCommand;
+use crate::operative::admin::admin_ctx::AdminCtx;
+use crate::operative::admin::admin_server::{execute, response_json};
+use crate::operative::admin::errors::admin_error::AdminError;
 use crate::operative::run_args::chain::Chain;
 use crate::transmutative::key::{FromNostrKeyStr, ToNostrKeyStr};
+use crate::transmutative::secp::public_key;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::{Html, IntoResponse, Redirect},
-    routing::get,
-    Router,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Path, Query, Request, State,
+    },
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Extension, Json, Router,
 };
 use bitcoin::hashes::Hash;
 use bitcoin::Txid;
@@ -36,9 +53,14 @@ struct ExplorerState {
     privileges_manager: Option<PRIVILEGES_MANAGER>,
     coin_manager: COIN_MANAGER,
     flame_manager: FLAME_MANAGER,
+    api_auth: API_AUTH,
+    admin_ctx: Option<AdminCtx>,
 }
 
 /// Serves a small block-explorer-style UI for archived batches (requires archival mode).
+///
+/// Requests are authorized against the API auth, and admin commands are served at `/admin` to
+/// admin identities when an admin context is given.
 pub async fn runexplorer_command(
     chain: Chain,
     port: u16,
@@ -47,6 +69,8 @@ pub async fn runexplorer_command(
     privileges_manager: Option<&PRIVILEGES_MANAGER>,
     coin_manager: &COIN_MANAGER,
     flame_manager: &FLAME_MANAGER,
+    api_auth: &API_AUTH,
+    admin_ctx: Option<&AdminCtx>,
 ) {
     let Some(archival) = archival else {
         eprintln!(
@@ -63,6 +87,8 @@ pub async fn runexplorer_command(
         privileges_manager: privileges_manager.map(Arc::clone),
         coin_manager: Arc::clone(coin_manager),
         flame_manager: Arc::clone(flame_manager),
+        api_auth: Arc::clone(api_auth),
+        admin_ctx: admin_ctx.cloned(),
     };
 
     let app = Router::new()
@@ -78,6 +104,18 @@ pub async fn runexplorer_command(
         .route("/account/:account_id", get(page_account_root_redirect))
         .route("/contract/:contract_id/:section", get(page_contract_section))
         .route("/contract/:contract_id", get(page_contract_root_redirect))
+        .route("/stream", get(event_stream_upgrade))
+        .route("/auth/challenge", post(auth_challenge))
+        .route("/auth/session", post(auth_session).delete(auth_logout))
+        .route("/admin", post(admin_command))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            authorize_request,
+        ))
+        .layer(middleware::from_fn_with_state(
+            RateLimiter::new(RateLimitConfig::from_env()),
+            rate_limit_by_ip,
+        ))
         .with_state(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
@@ -112,10 +150,315 @@ pub async fn runexplorer_command(
     );
 
     tokio::spawn(async move {
-        let _ = axum::serve(listener, app).await;
+        let _ = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await;
     });
 }
 
+/// Rejects requests with `429 Too Many Requests` once the client IP exceeds its rate limit.
+async fn rate_limit_by_ip(
+    State(rate_limiter): State<RATE_LIMITER>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let ip_check = {
+        let mut _rate_limiter = rate_limiter.lock().await;
+        _rate_limiter.check_ip(addr.ip(), std::time::Instant::now())
+    };
+
+    match ip_check {
+        Ok(()) => next.run(request).await,
+        Err(err) => {
+            let retry_after_secs = err.retry_after_ms().div_ceil(1_000).max(1);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                Json(err.json()),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Authorizes requests against the API auth.
+///
+/// `/auth/*` is open, `/admin` requires the admin permission and every other route the read
+/// permission. The identity is handed to the route handlers as a request extension.
+async fn authorize_request(
+    State(state): State<ExplorerState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // 1 Login routes are open.
+    let path = request.uri().path();
+    if path.starts_with("/auth/") {
+        return next.run(request).await;
+    }
+    let required = match path {
+        "/admin" => ApiPermission::Admin,
+        _ => ApiPermission::Read,
+    };
+
+    // 2 Authorize the bearer token, if any.
+    let bearer = bearer_token(&request);
+    let result = {
+        let mut _api_auth = state.api_auth.lock().await;
+        _api_auth.authorize(bearer.as_deref(), required, Utc::now().timestamp() as u64)
+    };
+
+    match result {
+        Ok(identity) => {
+            request.extensions_mut().insert(identity);
+            next.run(request).await
+        }
+        Err(err) => auth_error_response(&err),
+    }
+}
+
+/// Returns the bearer token of a request, from the `Authorization` header or, for browser
+/// WebSockets which cannot set headers, from the `token` query parameter.
+fn bearer_token(request: &Request) -> Option<String> {
+    if let Some(authorization) = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+    {
+        return authorization
+            .strip_prefix("Bearer ")
+            .map(|token| token.trim().to_string());
+    }
+    request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .map(|token| token.to_string())
+    })
+}
+
+/// Returns the response to a failed authorization: `403 Forbidden` for a lack of permission,
+/// `401 Unauthorized` otherwise.
+fn auth_error_response(err: &ApiAuthError) -> Response {
+    let mut obj = Map::new();
+    obj.insert("error".to_string(), Value::String(err.to_string()));
+    match err.is_forbidden() {
+        true => (StatusCode::FORBIDDEN, Json(Value::Object(obj))).into_response(),
+        false => (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            Json(Value::Object(obj)),
+        )
+            .into_response(),
+    }
+}
+
+/// Body of a login challenge request.
+#[derive(Deserialize)]
+struct AuthChallengeRequest {
+    account: String,
+}
+
+/// Returns a login challenge for an account key allowed to log in.
+async fn auth_challenge(
+    State(state): State<ExplorerState>,
+    Json(body): Json<AuthChallengeRequest>,
+) -> Response {
+    let Some(account_key) = parse_account_key(&body.account) else {
+        return auth_error_response(&ApiAuthError::UnknownKey);
+    };
+    let result = {
+        let mut _api_auth = state.api_auth.lock().await;
+        _api_auth.challenge(account_key, Utc::now().timestamp() as u64)
+    };
+    match result {
+        Ok(challenge) => {
+            let mut obj = Map::new();
+            obj.insert("challenge".to_string(), Value::String(challenge.message));
+            obj.insert(
+                "expires_at".to_string(),
+                Value::Number(challenge.expires_at.into()),
+            );
+            Json(Value::Object(obj)).into_response()
+        }
+        Err(err) => auth_error_response(&err),
+    }
+}
+
+/// Body of a session request.
+#[derive(Deserialize)]
+struct AuthSessionRequest {
+    account: String,
+    signature: String,
+}
+
+/// Opens a session for an account key that signed its login challenge with BIP-322.
+async fn auth_session(
+    State(state): State<ExplorerState>,
+    Json(body): Json<AuthSessionRequest>,
+) -> Response {
+    let Some(account_key) = parse_account_key(&body.account) else {
+        return auth_error_response(&ApiAuthError::UnknownKey);
+    };
+    let result = {
+        let mut _api_auth = state.api_auth.lock().await;
+        _api_auth.open_session(account_key, &body.signature, Utc::now().timestamp() as u64)
+    };
+    match result {
+        Ok((session, token)) => {
+            let mut obj = Map::new();
+            obj.insert("token".to_string(), Value::String(token));
+            obj.insert(
+                "expires_at".to_string(),
+                Value::Number(session.expires_at.into()),
+            );
+            Json(Value::Object(obj)).into_response()
+        }
+        Err(err) => auth_error_response(&err),
+    }
+}
+
+/// Closes the session of the presented bearer token.
+async fn auth_logout(State(state): State<ExplorerState>, request: Request) -> Response {
+    let Some(token) = bearer_token(&request) else {
+        return auth_error_response(&ApiAuthError::Unauthenticated);
+    };
+    let closed = {
+        let mut _api_auth = state.api_auth.lock().await;
+        _api_auth.close_session(&token)
+    };
+    let mut obj = Map::new();
+    obj.insert("closed".to_string(), Value::Bool(closed));
+    Json(Value::Object(obj)).into_response()
+}
+
+/// Body of an admin command request.
+#[derive(Deserialize)]
+struct AdminRequest {
+    command: String,
+}
+
+/// Executes an admin command for an admin identity, replying like the admin socket.
+async fn admin_command(
+    State(state): State<ExplorerState>,
+    Extension(identity): Extension<ApiIdentity>,
+    Json(body): Json<AdminRequest>,
+) -> Response {
+    let Some(admin_ctx) = state.admin_ctx.as_ref() else {
+        let mut obj = Map::new();
+        obj.insert(
+            "error".to_string(),
+            Value::String("Admin commands are not served here.".to_string()),
+        );
+        return (StatusCode::NOT_FOUND, Json(Value::Object(obj))).into_response();
+    };
+
+    let parts: Vec<&str> = body.command.split_whitespace().collect();
+    println!(
+        "{}",
+        format!(
+            "Admin command '{}' by {}.",
+            parts.first().unwrap_or(&""),
+            identity.to_string()
+        )
+        .yellow()
+    );
+    let result: Result<Value, AdminError> = match AdminCommand::parse(&parts) {
+        Ok(command) => execute(admin_ctx, command).await,
+        Err(err) => Err(err),
+    };
+    Json(response_json(result)).into_response()
+}
+
+/// Upgrades a request to an event stream WebSocket.
+async fn event_stream_upgrade(ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(serve_event_stream)
+}
+
+/// Serves the event stream over a WebSocket.
+///
+/// The subscriber sends its filter as a JSON text message before any event is delivered, and may
+/// replace it at any time with another one. Only the applied entries passing the filter are sent.
+async fn serve_event_stream(mut socket: WebSocket) {
+    // 1 Wait for the first filter.
+    let filter = loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => match parse_subscription_filter(&text) {
+                Ok(filter) => break filter,
+                Err(reply) => {
+                    if socket.send(Message::Text(reply.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+            },
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+            Some(Ok(_)) => (),
+        }
+    };
+
+    // 2 Subscribe and acknowledge the filter.
+    let acknowledgement = subscribed_reply(&filter);
+    let mut subscription = event_stream().subscribe(filter);
+    if socket
+        .send(Message::Text(acknowledgement.to_string()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    // 3 Deliver the matching events, replacing the filter on request.
+    loop {
+        let reply = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match parse_subscription_filter(&text) {
+                    Ok(filter) => {
+                        let reply = subscribed_reply(&filter);
+                        subscription.set_filter(filter);
+                        reply
+                    }
+                    Err(reply) => reply,
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            message = subscription.next() => match message {
+                Some(SubscriptionMessage::Event(event)) => event.json(),
+                Some(SubscriptionMessage::Lagged(skipped)) => {
+                    let mut obj = Map::new();
+                    obj.insert("lagged".to_string(), Value::Number(skipped.into()));
+                    Value::Object(obj)
+                }
+                None => return,
+            },
+        };
+        if socket.send(Message::Text(reply.to_string())).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Parses a subscription filter message, or returns the error reply.
+fn parse_subscription_filter(text: &str) -> Result<SubscriptionFilter, Value> {
+    let error_reply = |error: String| {
+        let mut obj = Map::new();
+        obj.insert("error".to_string(), Value::String(error));
+        Value::Object(obj)
+    };
+    let value: Value =
+        serde_json::from_str(text).map_err(|_| error_reply("Malformed filter.".to_string()))?;
+    SubscriptionFilter::from_json(&value).map_err(|err| error_reply(err.to_string()))
+}
+
+/// Returns the reply acknowledging a subscription filter.
+fn subscribed_reply(filter: &SubscriptionFilter) -> Value {
+    let mut obj = Map::new();
+    obj.insert("subscribed".to_string(), filter.json());
+    Value::Object(obj)
+}
+
 fn parse_entry_id_hex(hex_str: &str) -> Option<[u8; 32]> {
     let s = hex_str.trim().trim_start_matches("0x");
     if s.len() != 64 {
@@ -126,11 +469,7 @@ fn parse_entry_id_hex(hex_str: &str) -> Option<[u8; 32]> {
 }
 
 fn parse_account_key(input: &str) -> Option<[u8; 32]> {
-    let trimmed = input.trim();
-    if let Some(key) = trimmed.from_npub() {
-        return Some(key);
-    }
-    parse_entry_id_hex(trimmed)
+    public_key::parse_account_key(input).ok()
 }
 
 fn account_url(account_key: [u8; 32]) -> String {
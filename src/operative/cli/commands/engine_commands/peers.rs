@@ -0,0 +1,48 @@
+use crate::communicative::peer::access_list::PEER_ACCESS_LIST;
+use colored::Colorize;
+use serde_json::to_string_pretty;
+
+// peers list
+pub async fn peers_list_command(peer_access_list: &PEER_ACCESS_LIST) {
+    let peer_access_list_json = {
+        let _peer_access_list = peer_access_list.lock().await;
+        _peer_access_list.json()
+    };
+
+    println!(
+        "{}",
+        to_string_pretty(&peer_access_list_json).expect("serde_json::Value should serialize")
+    );
+}
+
+// peers <allow|disallow|ban|unban> <npub>
+pub async fn peers_update_command(
+    peer_access_list: &PEER_ACCESS_LIST,
+    action: &str,
+    peer_key: [u8; 32],
+) {
+    // 1 Apply the action to the peer access list.
+    let result = {
+        let mut _peer_access_list = peer_access_list.lock().await;
+        match action {
+            "allow" => _peer_access_list.allow(peer_key),
+            "disallow" => _peer_access_list.disallow(peer_key),
+            "ban" => _peer_access_list.ban(peer_key),
+            "unban" => _peer_access_list.unban(peer_key),
+            _ => {
+                eprintln!(
+                    "{}",
+                    "Usage: peers <allow|disallow|ban|unban> <npub>.".yellow()
+                );
+                return;
+            }
+        }
+    };
+
+    // 2 Print the outcome.
+    match result {
+        Ok(true) => println!("{}", format!("Peer {}: done.", action).green()),
+        Ok(false) => println!("{}", "No changes made.".yellow()),
+        Err(err) => eprintln!("{} {:?}", "Error updating peer access list:".red(), err),
+    }
+}
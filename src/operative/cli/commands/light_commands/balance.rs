@@ -0,0 +1,76 @@
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::peer::peer::PEER;
+use crate::communicative::tcp::client::{BalanceProofResponseBody, TCPClient};
+use crate::operative::run_args::chain::Chain;
+use colored::Colorize;
+use serde_json::to_string_pretty;
+
+// balance
+pub async fn balance_command(
+    chain: Chain,
+    engine_key: [u8; 32],
+    account_key: [u8; 32],
+    engine_peer: &PEER,
+) {
+    // 1 Request the balance proof from the engine.
+    let (balance_proof_response_body, duration) =
+        match engine_peer.request_balance_proof(account_key).await {
+            Ok((body, duration)) => (body, duration),
+            Err(error) => {
+                println!(
+                    "{}",
+                    format!("Error requesting balance proof: {:?}", error).red()
+                );
+                return;
+            }
+        };
+
+    // 2 Match the balance proof result.
+    let success_body = match balance_proof_response_body {
+        BalanceProofResponseBody::Ok(success_body) => success_body,
+        BalanceProofResponseBody::Err(error) => {
+            println!(
+                "{}",
+                format!(
+                    "Error resolving balance proof: {}",
+                    to_string_pretty(&error.json()).expect("serde_json::Value should serialize")
+                )
+                .red()
+            );
+            return;
+        }
+    };
+
+    // 3 Verify the state root signatures and the inclusion or non-inclusion proof.
+    let federation = Federation::for_chain(chain);
+    if !success_body.verify(engine_key, federation.as_ref(), account_key) {
+        println!(
+            "{}",
+            "Balance proof verification failed. Not trusting the response.".red()
+        );
+        return;
+    }
+
+    // 4 Print the verified balance.
+    match success_body.balance {
+        Some(balance) => println!(
+            "{}",
+            format!(
+                "Balance: {} satoshis (verified at batch height #{}, {} ms).",
+                balance,
+                success_body.batch_height,
+                duration.as_millis()
+            )
+            .green()
+        ),
+        None => println!(
+            "{}",
+            format!(
+                "Account is not registered as of batch height #{} ({} ms).",
+                success_body.batch_height,
+                duration.as_millis()
+            )
+            .yellow()
+        ),
+    }
+}
@@ -1,3 +1,4 @@
 pub mod engine_commands;
+pub mod light_commands;
 pub mod node_commands;
 pub mod common_commands;
\ No newline at end of file
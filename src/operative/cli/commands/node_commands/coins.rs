@@ -1,8 +1,15 @@
 use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+use crate::operative::tasks::mempool_watch::mempool_watch::MEMPOOL_WATCH;
 use colored::Colorize;
 
 /// Prints an account's coin balance in satoshis from the coin manager (`coins` with no args uses the node's self account).
-pub async fn coins_command(coin_manager: &COIN_MANAGER, account_key: [u8; 32]) {
+///
+/// Deposits still waiting to confirm, or to be synced, are printed below the balance if a mempool watch is given.
+pub async fn coins_command(
+    coin_manager: &COIN_MANAGER,
+    mempool_watch: Option<&MEMPOOL_WATCH>,
+    account_key: [u8; 32],
+) {
     let balance = {
         let cm = coin_manager.lock().await;
         cm.get_account_balance(account_key)
@@ -15,4 +22,22 @@ pub async fn coins_command(coin_manager: &COIN_MANAGER, account_key: [u8; 32]) {
                 .yellow()
         ),
     }
+
+    // Print the pending deposits, if any.
+    if let Some(mempool_watch) = mempool_watch {
+        let (pending_deposit_count, pending_deposit_value) = {
+            let _mempool_watch = mempool_watch.lock().await;
+            _mempool_watch.pending_deposits()
+        };
+        if pending_deposit_count > 0 {
+            println!(
+                "{}",
+                format!(
+                    "Pending: {} sats in {} unconfirmed deposit(s) (see `pending`).",
+                    pending_deposit_value, pending_deposit_count
+                )
+                .yellow()
+            );
+        }
+    }
 }
@@ -80,10 +80,17 @@ fn comp_program_method(parts: Vec<&str>) {
 }
 
 fn comp_program(parts: Vec<&str>) {
+    // A trailing `--reentrant` opts the program in to being re-entered by the contracts it calls.
+    let reentrant = parts.last() == Some(&"--reentrant");
+    let parts = match reentrant {
+        true => parts[..parts.len() - 1].to_vec(),
+        false => parts,
+    };
+
     let Some((program_name, metadata, method_count, method_bytes_args)) =
         parse_comp_program_arguments(&parts)
     else {
-        eprintln!("Usage: comp program <program_name> <metadata_hex_or_0x> <num_methods> <0x_method1> ... <0x_methodN>");
+        eprintln!("Usage: comp program <program_name> <metadata_hex_or_0x> <num_methods> <0x_method1> ... <0x_methodN> [--reentrant]");
         eprintln!("Example: comp program my awesome app 0x 1 0x0568656c6c6f00010005005151515151");
         return;
     };
@@ -108,7 +115,7 @@ fn comp_program(parts: Vec<&str>) {
         methods.push(method);
     }
 
-    let program = match Program::new(program_name, metadata, methods) {
+    let program = match Program::new(program_name, metadata, reentrant, methods) {
         Ok(v) => v,
         Err(err) => {
             eprintln!("Failed to construct Program: {}", err);
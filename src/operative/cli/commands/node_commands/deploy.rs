@@ -41,6 +41,14 @@ pub async fn deploy_command(
         }
     };
 
+    if let Err(error) = program.validate() {
+        println!(
+            "{}",
+            format!("Error validating deploy program: {error}").red()
+        );
+        return;
+    }
+
     let batch_height_tip: u64 = {
         let _sync_manager = sync_manager.lock().await;
         _sync_manager.cube_batch_sync_height_tip()
@@ -5,7 +5,6 @@ use crate::constructive::txo::lift::lift_versions::liftv2::liftv2::{
     return_liftv2_scriptpubkey, return_liftv2_taproot,
 };
 use crate::operative::run_args::chain::Chain;
-use crate::transmutative::codec::address::encode_p2tr;
 use colored::Colorize;
 use hex;
 use serde_json::{json, to_string_pretty};
@@ -22,24 +21,17 @@ pub fn liftaddr_command(chain: Chain, engine_key: [u8; 32], self_account_key: [u
             }
         };
 
-    // 2 Get the liftv1 tweaked taproot key.
-    let liftv1_tweaked_taproot_key: [u8; 32] =
-        match return_liftv1_taproot(self_account_key, engine_key) {
-            Some(taproot) => match taproot.tweaked_key() {
-                Some(tweaked_key) => tweaked_key.serialize_xonly(),
-                None => {
-                    println!("{}", "Error getting liftv1 tweaked key.".red());
-                    return;
-                }
-            },
-            None => {
-                println!("{}", "Error getting liftv1 scriptpubkey.".red());
-                return;
-            }
-        };
+    // 2 Get the liftv1 taproot.
+    let liftv1_taproot = match return_liftv1_taproot(self_account_key, engine_key) {
+        Some(taproot) => taproot,
+        None => {
+            println!("{}", "Error getting liftv1 taproot.".red());
+            return;
+        }
+    };
 
-    // 3 Encode the liftv1 scriptpubkey into an address.
-    let liftv1_addr = match encode_p2tr(chain, liftv1_tweaked_taproot_key) {
+    // 3 Encode the liftv1 taproot output key into an address.
+    let liftv1_addr = match liftv1_taproot.address(chain) {
         Some(addr) => addr,
         None => {
             println!("{}", "Error encoding liftv1 address.".red());
@@ -57,24 +49,17 @@ pub fn liftaddr_command(chain: Chain, engine_key: [u8; 32], self_account_key: [u
             }
         };
 
-    // 5 Get the liftv2 tweaked taproot key.
-    let liftv2_tweaked_taproot_key: [u8; 32] =
-        match return_liftv2_taproot(self_account_key, engine_key) {
-            Some(taproot) => match taproot.tweaked_key() {
-                Some(tweaked_key) => tweaked_key.serialize_xonly(),
-                None => {
-                    println!("{}", "Error getting liftv2 tweaked key.".red());
-                    return;
-                }
-            },
-            None => {
-                println!("{}", "Error getting liftv2 scriptpubkey.".red());
-                return;
-            }
-        };
+    // 5 Get the liftv2 taproot.
+    let liftv2_taproot = match return_liftv2_taproot(self_account_key, engine_key) {
+        Some(taproot) => taproot,
+        None => {
+            println!("{}", "Error getting liftv2 taproot.".red());
+            return;
+        }
+    };
 
-    // 6 Encode the liftv2 tweaked taproot key into an address.
-    let liftv2_addr = match encode_p2tr(chain, liftv2_tweaked_taproot_key) {
+    // 6 Encode the liftv2 taproot output key into an address.
+    let liftv2_addr = match liftv2_taproot.address(chain) {
         Some(addr) => addr,
         None => {
             println!("{}", "Error encoding liftv2 address.".red());
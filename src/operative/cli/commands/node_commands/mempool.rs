@@ -0,0 +1,14 @@
+use crate::operative::tasks::mempool::mempool::MEMPOOL;
+use serde_json::to_string_pretty;
+
+/// Prints the pending mempool entries.
+pub async fn mempool_command(mempool: &MEMPOOL) {
+    let mempool_json = {
+        let _mempool = mempool.lock().await;
+        _mempool.json()
+    };
+    println!(
+        "{}",
+        to_string_pretty(&mempool_json).expect("serde_json::Value should serialize")
+    );
+}
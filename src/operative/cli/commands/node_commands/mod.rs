@@ -10,7 +10,10 @@ pub mod liftaddr;
 pub mod lifts;
 pub mod liftup;
 pub mod liftuplocal;
+pub mod mempool;
 pub mod r#move;
 pub mod npub;
+pub mod pending;
 pub mod ping;
+pub mod rotatekey;
 pub mod swapout;
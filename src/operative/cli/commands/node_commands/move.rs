@@ -1,23 +1,22 @@
-use crate::communicative::peer::peer::PEER;
-use crate::communicative::tcp::client::{MoveResponseBody, TCPClient};
 use crate::constructive::core_types::entities::account::account::account::Account;
 use crate::constructive::core_types::entities::account::root_account::root_account::RootAccount;
 use crate::constructive::core_types::target::target::Target;
 use crate::constructive::entry::entry_kinds::r#move::r#move::Move;
 use crate::inscriptive::registery::registery::REGISTERY;
 use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::tasks::mempool::mempool::{Mempool, MEMPOOL};
 use crate::transmutative::key::KeyHolder;
 use colored::Colorize;
-use serde_json::to_string_pretty;
 
-/// move <satoshi_amount> <to_account_key_hex>
+/// move <satoshi_amount> <to_account_key_hex> [priority_fee]
 pub async fn move_command(
     satoshi_amount: u32,
     to_account_key: [u8; 32],
+    priority_fee: u32,
     key_holder: &KeyHolder,
     sync_manager: &SYNC_MANAGER,
     registery: &REGISTERY,
-    engine_peer: &PEER,
+    mempool: &MEMPOOL,
 ) {
     // 1 Construct sender root account.
     let from = RootAccount::self_root_account_from_registery(key_holder, registery).await;
@@ -45,7 +44,7 @@ pub async fn move_command(
 
     // 6 Construct target and move entry.
     let target = Target::new(current_execution_batch_height);
-    let move_entry = Move::new(from, to, satoshi_amount, target);
+    let move_entry = Move::new(from, to, satoshi_amount, priority_fee, target);
 
     // 7 Sign move.
     let move_bls_signature: [u8; 96] = match move_entry.bls_sign(key_holder) {
@@ -56,40 +55,22 @@ pub async fn move_command(
         }
     };
 
-    // 8 Submit move request.
-    let (move_response_body, duration) = match engine_peer
-        .request_move(&move_entry, move_bls_signature)
-        .await
-    {
-        Ok((move_response_body, duration)) => (move_response_body, duration),
-        Err(error) => {
-            println!("{}", format!("Error requesting move: {:?}", error).red());
-            return;
-        }
-    };
-
-    // 9 Print response.
-    match move_response_body {
-        MoveResponseBody::Ok(success_body) => {
+    // 8 Submit the move to the mempool, which forwards it to the engine.
+    match Mempool::submit(mempool, move_entry, move_bls_signature).await {
+        Ok(sighash) => {
             println!(
                 "{}",
                 format!(
-                    "Move entry successfully executed ({} ms): {}",
-                    duration.as_millis(),
-                    to_string_pretty(&success_body.json())
-                        .expect("serde_json::Value should serialize")
+                    "Move entry accepted into the mempool: {}",
+                    hex::encode(sighash)
                 )
                 .green()
             );
         }
-        MoveResponseBody::Err(error) => {
+        Err(error) => {
             println!(
                 "{}",
-                format!(
-                    "Error executing move: {}",
-                    to_string_pretty(&error.json()).expect("serde_json::Value should serialize")
-                )
-                .red()
+                format!("Error submitting move to the mempool: {:?}", error).red()
             );
         }
     }
@@ -0,0 +1,14 @@
+use crate::operative::tasks::mempool_watch::mempool_watch::MEMPOOL_WATCH;
+use serde_json::to_string_pretty;
+
+/// Prints the relevant Bitcoin transactions still pending confirmation or sync.
+pub async fn pending_command(mempool_watch: &MEMPOOL_WATCH) {
+    let mempool_watch_json = {
+        let _mempool_watch = mempool_watch.lock().await;
+        _mempool_watch.json()
+    };
+    println!(
+        "{}",
+        to_string_pretty(&mempool_watch_json).expect("serde_json::Value should serialize")
+    );
+}
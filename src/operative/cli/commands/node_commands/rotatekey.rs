@@ -0,0 +1,121 @@
+use crate::communicative::peer::peer::PEER;
+use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+use crate::inscriptive::params_manager::params_manager::PARAMS_MANAGER;
+use crate::inscriptive::registery::registery::{
+    REGISTERY, SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD,
+};
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::cli::commands::node_commands::config::config_command;
+use crate::transmutative::key::{KeyHolder, KeyPurpose};
+use colored::Colorize;
+
+/// Number of aggregation key indexes scanned for the one currently registered.
+const AGGREGATION_KEY_SCAN_LIMIT: u32 = 1_024;
+
+/// The key to rotate the secondary aggregation key to.
+pub enum RotationKey {
+    // The aggregation key derived at the given index.
+    Index(u32),
+    // The key of the given secret.
+    Secret([u8; 32]),
+}
+
+/// rotatekey [<index> | <nsec>]
+pub async fn rotatekey_command(
+    rotation_key: Option<RotationKey>,
+    key_holder: &KeyHolder,
+    self_account_key: [u8; 32],
+    sync_manager: &SYNC_MANAGER,
+    registery: &REGISTERY,
+    coin_manager: &COIN_MANAGER,
+    params_manager: &PARAMS_MANAGER,
+    engine_peer: &PEER,
+) {
+    // 1 Get the currently registered secondary aggregation key.
+    let current_key: Option<Vec<u8>> = {
+        let _registery = registery.lock().await;
+        _registery
+            .get_account_body_by_account_key(self_account_key)
+            .and_then(|account_body| account_body.secondary_aggregation_key)
+    };
+
+    // 2 Resolve the index to derive at, following the currently registered one by default.
+    let rotation_key = match rotation_key {
+        Some(rotation_key) => rotation_key,
+        None => {
+            let current_index = current_key.as_ref().and_then(|current_key| {
+                (0..AGGREGATION_KEY_SCAN_LIMIT).find(|index| {
+                    key_holder
+                        .derive_for(KeyPurpose::Aggregation(*index))
+                        .map(|derived| derived.secp_public_key_bytes().to_vec())
+                        .as_ref()
+                        == Some(current_key)
+                })
+            });
+            RotationKey::Index(current_index.map(|index| index + 1).unwrap_or(0))
+        }
+    };
+
+    // 3 Compute the new secondary aggregation key.
+    let new_key: [u8; 32] = match rotation_key {
+        RotationKey::Index(index) => match key_holder.derive_for(KeyPurpose::Aggregation(index)) {
+            Some(derived) => {
+                println!("Rotating to the aggregation key at index {}.", index);
+                derived.secp_public_key_bytes()
+            }
+            None => {
+                println!(
+                    "{}",
+                    format!(
+                        "Error: unable to derive aggregation key at index {}.",
+                        index
+                    )
+                    .red()
+                );
+                return;
+            }
+        },
+        RotationKey::Secret(secret_key) => match KeyHolder::new(secret_key) {
+            Some(new_key_holder) => new_key_holder.secp_public_key_bytes(),
+            None => {
+                println!("{}", "Error: invalid secret key.".red());
+                return;
+            }
+        },
+    };
+
+    // 4 Nothing to rotate if the key is already registered.
+    if current_key.as_deref() == Some(new_key.as_slice()) {
+        println!(
+            "{}",
+            "Error: this key is already the registered aggregation key.".red()
+        );
+        return;
+    }
+
+    // 5 Re-register the secondary aggregation key with a config entry.
+    config_command(
+        Some(new_key.to_vec()),
+        None,
+        None,
+        key_holder,
+        sync_manager,
+        registery,
+        coin_manager,
+        params_manager,
+        engine_peer,
+    )
+    .await;
+
+    // 6 The previous key remains valid for the grace period.
+    if current_key.is_some() {
+        println!(
+            "{}",
+            format!(
+                "The previous aggregation key remains valid for {} seconds after the rotation.",
+                SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD
+            )
+            .yellow()
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::operative::key_backup::errors::key_backup_error::KeyBackupError;
+use crate::transmutative::key::{FromNcryptsecStr, KeyHolder, KeyPurpose, ToNcryptsecStr};
+use serde_json::{Map, Value};
+
+/// Key backup format version.
+pub const KEY_BACKUP_VERSION: u64 = 1;
+
+/// Key backup kind marker, telling backups apart from other JSON files.
+pub const KEY_BACKUP_KIND: &str = "cube-key-backup";
+
+/// Purposes whose derived identities are listed in a backup, by their name.
+const BACKUP_PURPOSES: [(&str, KeyPurpose); 2] = [
+    ("node_identity", KeyPurpose::NodeIdentity),
+    ("operator_signing", KeyPurpose::OperatorSigning),
+];
+
+/// An identity listed in a backup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupIdentity {
+    // Name of the key purpose, or `root` for the root key.
+    pub purpose: String,
+
+    // BIP32 path the key is derived at from the root key, `None` for the root key.
+    pub derivation_path: Option<String>,
+
+    // Public key of the identity.
+    pub npub: String,
+}
+
+/// An encrypted, versioned backup of the key material of an operator.
+///
+/// The root secret key is encrypted with the backup passphrase as a NIP-49 `ncryptsec`, from which
+/// every per-purpose key derives. The identities are listed in the clear along with their derivation
+/// paths so that a backup can be told apart without its passphrase, and are checked against the
+/// decrypted root key on import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBackup {
+    // Unix timestamp the backup was taken at.
+    pub created_at: u64,
+
+    // The root secret key, encrypted with the backup passphrase.
+    pub ncryptsec: String,
+
+    // The root identity followed by the derived identities.
+    pub identities: Vec<BackupIdentity>,
+}
+
+impl KeyBackup {
+    /// Takes a backup of the given root key holder, encrypted with the passphrase using `2^log_n`
+    /// scrypt rounds.
+    pub fn new(
+        key_holder: &KeyHolder,
+        passphrase: &str,
+        log_n: u8,
+        created_at: u64,
+    ) -> Result<Self, KeyBackupError> {
+        // 1 Encrypt the root secret key.
+        let ncryptsec = key_holder
+            .secp_secret_key_bytes()
+            .to_ncryptsec(passphrase, log_n)
+            .ok_or(KeyBackupError::EncryptionFailed)?;
+
+        // 2 List the identities.
+        let identities = Self::identities(key_holder).ok_or(KeyBackupError::EncryptionFailed)?;
+
+        // 3 Return the backup.
+        Ok(Self {
+            created_at,
+            ncryptsec,
+            identities,
+        })
+    }
+
+    /// Decrypts the root key holder with the passphrase, and checks the listed identities against it.
+    pub fn decrypt(&self, passphrase: &str) -> Result<KeyHolder, KeyBackupError> {
+        // 1 Decrypt the root secret key.
+        let secret_key_bytes = self
+            .ncryptsec
+            .as_str()
+            .from_ncryptsec(passphrase)
+            .ok_or(KeyBackupError::WrongPassphrase)?;
+        let key_holder = KeyHolder::new(secret_key_bytes).ok_or(KeyBackupError::MalformedBackup)?;
+
+        // 2 Derive the identities again and compare them with the listed ones.
+        let identities = Self::identities(&key_holder).ok_or(KeyBackupError::MalformedBackup)?;
+        if identities.len() != self.identities.len() {
+            return Err(KeyBackupError::MalformedBackup);
+        }
+        for (identity, listed_identity) in identities.iter().zip(self.identities.iter()) {
+            if identity != listed_identity {
+                return Err(KeyBackupError::IdentityMismatch(
+                    listed_identity.purpose.clone(),
+                ));
+            }
+        }
+
+        // 3 Return the root key holder.
+        Ok(key_holder)
+    }
+
+    /// Returns the root identity of the key holder followed by its derived identities.
+    fn identities(key_holder: &KeyHolder) -> Option<Vec<BackupIdentity>> {
+        // 1 The root identity.
+        let mut identities = vec![BackupIdentity {
+            purpose: "root".to_string(),
+            derivation_path: None,
+            npub: key_holder.npub(),
+        }];
+
+        // 2 The derived identities.
+        for (purpose_name, purpose) in BACKUP_PURPOSES.iter() {
+            let derived_key_holder = key_holder.derive_for(*purpose)?;
+            identities.push(BackupIdentity {
+                purpose: purpose_name.to_string(),
+                derivation_path: derived_key_holder
+                    .derivation_path()
+                    .map(|path| format!("m/{}", path)),
+                npub: derived_key_holder.npub(),
+            });
+        }
+
+        Some(identities)
+    }
+
+    /// Returns the backup as JSON bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // 1 Encode the identities.
+        let identities: Vec<Value> = self
+            .identities
+            .iter()
+            .map(|identity| {
+                let mut identity_obj = Map::new();
+                identity_obj.insert(
+                    "purpose".to_string(),
+                    Value::String(identity.purpose.clone()),
+                );
+                identity_obj.insert(
+                    "derivation_path".to_string(),
+                    match &identity.derivation_path {
+                        Some(derivation_path) => Value::String(derivation_path.clone()),
+                        None => Value::Null,
+                    },
+                );
+                identity_obj.insert("npub".to_string(), Value::String(identity.npub.clone()));
+                Value::Object(identity_obj)
+            })
+            .collect();
+
+        // 2 Construct the backup.
+        let mut obj = Map::new();
+        obj.insert(
+            "kind".to_string(),
+            Value::String(KEY_BACKUP_KIND.to_string()),
+        );
+        obj.insert(
+            "version".to_string(),
+            Value::Number(KEY_BACKUP_VERSION.into()),
+        );
+        obj.insert(
+            "created_at".to_string(),
+            Value::Number(self.created_at.into()),
+        );
+        obj.insert(
+            "ncryptsec".to_string(),
+            Value::String(self.ncryptsec.clone()),
+        );
+        obj.insert("identities".to_string(), Value::Array(identities));
+
+        // 3 Return the bytes.
+        serde_json::to_vec_pretty(&Value::Object(obj)).expect("serde_json::Value should serialize")
+    }
+
+    /// Parses a backup from JSON bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KeyBackupError> {
+        // 1 Parse the header.
+        let value: Value =
+            serde_json::from_slice(bytes).map_err(|_| KeyBackupError::MalformedBackup)?;
+        if value.get("kind").and_then(Value::as_str) != Some(KEY_BACKUP_KIND) {
+            return Err(KeyBackupError::MalformedBackup);
+        }
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or(KeyBackupError::MalformedBackup)?;
+        if version != KEY_BACKUP_VERSION {
+            return Err(KeyBackupError::UnsupportedVersion(version));
+        }
+
+        // 2 Parse the body.
+        Self::parse_body(&value).ok_or(KeyBackupError::MalformedBackup)
+    }
+
+    /// Parses the body of a backup, returning `None` if it is malformed.
+    fn parse_body(value: &Value) -> Option<Self> {
+        // 1 Parse the encrypted root secret key.
+        let created_at = value.get("created_at")?.as_u64()?;
+        let ncryptsec = value.get("ncryptsec")?.as_str()?.to_string();
+
+        // 2 Parse the identities.
+        let mut identities = Vec::<BackupIdentity>::new();
+        for identity in value.get("identities")?.as_array()?.iter() {
+            identities.push(BackupIdentity {
+                purpose: identity.get("purpose")?.as_str()?.to_string(),
+                derivation_path: match identity.get("derivation_path")? {
+                    Value::Null => None,
+                    derivation_path => Some(derivation_path.as_str()?.to_string()),
+                },
+                npub: identity.get("npub")?.as_str()?.to_string(),
+            });
+        }
+
+        Some(Self {
+            created_at,
+            ncryptsec,
+            identities,
+        })
+    }
+}
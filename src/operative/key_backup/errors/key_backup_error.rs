@@ -0,0 +1,42 @@
+/// Errors associated with exporting or importing a key backup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyBackupError {
+    /// The backup could not be read from the file.
+    BackupUnavailable(String),
+    /// The backup is not a valid key backup.
+    MalformedBackup,
+    /// The backup was written by an unsupported version of the format.
+    UnsupportedVersion(u64),
+    /// The secret key could not be encrypted.
+    EncryptionFailed,
+    /// The passphrase does not decrypt the backup.
+    WrongPassphrase,
+    /// An identity listed in the backup does not derive from the decrypted secret key.
+    IdentityMismatch(String),
+    /// The backup could not be written to the file.
+    BackupWriteError(String),
+}
+
+impl KeyBackupError {
+    /// Returns what the operator can do about the error.
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            KeyBackupError::BackupUnavailable(_) | KeyBackupError::MalformedBackup => {
+                "Make sure the backup file points to a key backup written with 'key export'."
+            }
+            KeyBackupError::UnsupportedVersion(_) => {
+                "The backup was written by a newer release. Import it with that release or a later one."
+            }
+            KeyBackupError::EncryptionFailed => {
+                "Make sure the secret key is valid, and CUBE_NCRYPTSEC_LOG_N is a reasonable scrypt work factor."
+            }
+            KeyBackupError::WrongPassphrase => {
+                "Enter the passphrase the backup was exported with."
+            }
+            KeyBackupError::IdentityMismatch(_) => {
+                "The backup has been altered since it was exported. Use another copy of the backup."
+            }
+            KeyBackupError::BackupWriteError(_) => "Make sure the backup file path is writable.",
+        }
+    }
+}
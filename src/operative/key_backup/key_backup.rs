@@ -0,0 +1,69 @@
+use crate::operative::key_backup::backup_file::KeyBackup;
+use crate::operative::key_backup::errors::key_backup_error::KeyBackupError;
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+use colored::Colorize;
+
+/// Runs the key export procedure and prints the outcome.
+pub fn run_export(key_holder: &KeyHolder, passphrase: &str, log_n: u8, path: &str) {
+    match export(key_holder, passphrase, log_n, path) {
+        Ok(backup) => {
+            println!("{}", format!("Key backup written to {}.", path).green());
+            print_identities(&backup);
+        }
+        Err(err) => {
+            eprintln!("{} {:?}", "Key export error:".red(), err);
+            eprintln!("{}", err.remedy().yellow());
+        }
+    }
+}
+
+/// Prints the identities listed in a backup.
+pub fn print_identities(backup: &KeyBackup) {
+    for identity in backup.identities.iter() {
+        println!(
+            "  {} {} {}",
+            identity.purpose,
+            identity.derivation_path.as_deref().unwrap_or("-"),
+            identity.npub
+        );
+    }
+}
+
+/// Exports the key material of the given root key holder into an encrypted backup file.
+///
+/// Returns the written backup.
+pub fn export(
+    key_holder: &KeyHolder,
+    passphrase: &str,
+    log_n: u8,
+    path: &str,
+) -> Result<KeyBackup, KeyBackupError> {
+    // 1 Take the backup.
+    let backup = KeyBackup::new(key_holder, passphrase, log_n, Utc::now().timestamp() as u64)?;
+
+    // 2 Write the backup.
+    std::fs::write(path, backup.to_bytes())
+        .map_err(|err| KeyBackupError::BackupWriteError(err.to_string()))?;
+
+    // 3 Return the backup.
+    Ok(backup)
+}
+
+/// Imports the root key holder from an encrypted backup file.
+///
+/// The identities the backup lists are checked against the ones derived from the decrypted key.
+///
+/// Returns the backup along with the root key holder.
+pub fn import(path: &str, passphrase: &str) -> Result<(KeyBackup, KeyHolder), KeyBackupError> {
+    // 1 Read and parse the backup.
+    let bytes =
+        std::fs::read(path).map_err(|err| KeyBackupError::BackupUnavailable(err.to_string()))?;
+    let backup = KeyBackup::from_bytes(&bytes)?;
+
+    // 2 Decrypt the root key holder.
+    let key_holder = backup.decrypt(passphrase)?;
+
+    // 3 Return the backup and the root key holder.
+    Ok((backup, key_holder))
+}
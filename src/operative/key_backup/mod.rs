@@ -0,0 +1,3 @@
+pub mod backup_file;
+pub mod errors;
+pub mod key_backup;
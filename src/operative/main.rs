@@ -2,7 +2,6 @@ use colored::Colorize;
 use cube::constructive::taproot::P2TR;
 use cube::constructive::txout_types::payload::payload::Payload;
 use cube::inscriptive::baked;
-use cube::transmutative::codec::address::encode_p2tr;
 use cube::{
     communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder,
     operative::{
@@ -10,15 +9,32 @@ use cube::{
             chain::Chain, operating_kind::OperatingKind, resource_mode::ResourceMode,
             sync_mode::SyncMode,
         },
+        admin::admin_client,
+        bootstrap::bootstrap,
+        key_backup::key_backup,
+        reindex::reindex,
+        replay::replay,
         runner::runner,
+        tasks::chain_sync::quarantine,
     },
     transmutative::{
-        key::{FromNostrKeyStr, KeyHolder, ToNostrKeyStr},
-        secp::schnorr::generate_secret,
+        bip322::signed_message,
+        key::{
+            FromNcryptsecStr, FromNostrKeyStr, KeyHolder, KeyPurpose, ToNcryptsecStr,
+            ToNostrKeyStr, NCRYPTSEC_LOG_N,
+        },
+        secp::{context, schnorr::generate_secret},
     },
 };
 use serde_json::json;
 use std::{env, io::BufRead};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Maximum length of a secret line read from stdin.
+///
+/// Secret lines are read into a buffer of this capacity, which is never reallocated, so that no copy
+/// of the secret is left behind in a freed buffer.
+const SECRET_LINE_CAPACITY: usize = 1024;
 
 fn main() {
     // 1 Parse arguments.
@@ -26,16 +42,46 @@ fn main() {
 
     // 2 Match the arguments length.
     match args.len() {
-        // 2.a Generate a random secret key and print it as an nsec.
+        // 2.a Encrypt an nsec into an ncryptsec.
+        2 if args[1].to_lowercase() == "encrypt-key" => encrypt_key(&args),
+
+        // 2.b Generate a random secret key and print it as an nsec.
         2 => gensec(&args),
 
-        // 2.b Print genesis parameters.
+        // 2.c Print genesis parameters.
         3 => genesis(&args),
 
-        // 2.d Run the appropriate mode based on the arguments.
+        // 2.d Import a trusted state snapshot to sync from.
+        4 if args[1].to_lowercase() == "bootstrap" => bootstrap(&args),
+
+        // 2.e Sign a message with the BIP-322 simple signature scheme.
+        4 if args[1].to_lowercase() == "sign-message" => sign_message(&args),
+
+        // 2.f Wipe and re-derive the ledger state from archived batch records.
+        4 => reindex(&args),
+
+        // 2.g Release the blocks quarantined during sync.
+        5 if args[1].to_lowercase() == "sync" => sync(&args),
+
+        // 2.h Export or import an encrypted key backup.
+        5 if args[1].to_lowercase() == "key" => key(&args),
+
+        // 2.i Export the ledger state of a stopped node into a snapshot file.
+        6 if args[1].to_lowercase() == "snapshot" => snapshot(&args),
+
+        // 2.j Verify a BIP-322 signed message.
+        6 if args[1].to_lowercase() == "verify-message" => verify_message(&args),
+
+        // 2.k Send a command to the admin socket of a running instance.
+        5..=7 => admin(&args),
+
+        // 2.l Replay archived batch records and compare them with the journal.
+        8 if args[1].to_lowercase() == "replay" => replay(&args),
+
+        // 2.m Run the appropriate mode based on the arguments.
         8 => run(&args),
 
-        // 2.e Invalid arguments.
+        // 2.n Invalid arguments.
         _ => print_correct_usage(),
     }
 }
@@ -146,9 +192,9 @@ fn gensec(args: &Vec<String>) {
                 let secret_key_bytes = generate_secret();
 
                 // 1.a.1.2 Convert the secret key to an nsec.
-                match secret_key_bytes.to_nsec() {
+                match Zeroizing::new(secret_key_bytes).to_nsec() {
                     // 1.a.1.2.a Success.
-                    Some(nsec) => nsec,
+                    Some(nsec) => Zeroizing::new(nsec),
 
                     // 1.a.2.b This not possible.
                     None => {
@@ -161,10 +207,7 @@ fn gensec(args: &Vec<String>) {
             // 1.a.2 Print the nsec in a decorative frame.
             print_nsec_frame(&nsec);
 
-            // 1.a.3 Drop the nsec.
-            drop(nsec);
-
-            // 1.a.4 Return.
+            // 1.a.3 Return.
             return;
         }
 
@@ -173,6 +216,364 @@ fn gensec(args: &Vec<String>) {
     }
 }
 
+/// Encrypts an nsec read from stdin with a passphrase into a NIP-49 ncryptsec and prints it.
+fn encrypt_key(args: &Vec<String>) {
+    // 1 Match the argument name.
+    match args[1].to_lowercase().as_str() {
+        // 1.a Command is 'encrypt-key'.
+        "encrypt-key" => {
+            // 1.a.1 Print the prompt.
+            println!("{}", "Enter nsec:".magenta());
+
+            // 1.a.2 Read the nsec and the passphrase from stdin.
+            let mut stdin = std::io::stdin().lock();
+            let nsec = match read_secret_input(&mut stdin) {
+                Some(line) => line,
+                None => {
+                    eprintln!("{}", "Invalid nsec.".red());
+                    return;
+                }
+            };
+
+            // 1.a.3 Convert the nsec to a secret key.
+            let secret_key_bytes = match nsec.trim().from_nsec() {
+                Some(secret_key_bytes) => Zeroizing::new(secret_key_bytes),
+                None => {
+                    eprintln!("{}", "Invalid nsec.".red());
+                    return;
+                }
+            };
+
+            // 1.a.4 Read the passphrase.
+            let passphrase = match read_passphrase(&mut stdin) {
+                Some(passphrase) if !passphrase.is_empty() => passphrase,
+                _ => {
+                    eprintln!("{}", "Missing passphrase.".red());
+                    return;
+                }
+            };
+
+            // 1.a.5 Read the scrypt work factor.
+            let log_n = std::env::var("CUBE_NCRYPTSEC_LOG_N")
+                .ok()
+                .and_then(|value| value.trim().parse::<u8>().ok())
+                .unwrap_or(NCRYPTSEC_LOG_N);
+
+            // 1.a.6 Encrypt the secret key.
+            let ncryptsec = secret_key_bytes.to_ncryptsec(&passphrase, log_n);
+
+            // 1.a.7 Print the ncryptsec.
+            match ncryptsec {
+                Some(ncryptsec) => println!("{}", ncryptsec),
+                None => eprintln!("{}", "Failed to encrypt the nsec.".red()),
+            }
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Exports or imports an encrypted backup of the key material.
+fn key(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+        args[3].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'key export --output'.
+        ("key", "export", "--output") => {
+            // 1.a.1 Print the prompt.
+            println!("{}", "Enter nsec:".magenta());
+
+            // 1.a.2 Read the nsec, or the ncryptsec and its passphrase, from stdin.
+            let mut stdin = std::io::stdin().lock();
+            let nsec = match read_secret_input(&mut stdin) {
+                Some(line) => line,
+                None => {
+                    eprintln!("{}", "Invalid nsec.".red());
+                    return;
+                }
+            };
+            let secret_key_bytes = Zeroizing::new(match nsec.trim().starts_with("ncryptsec1") {
+                true => {
+                    let passphrase = match read_passphrase(&mut stdin) {
+                        Some(passphrase) => passphrase,
+                        None => {
+                            eprintln!("{}", "Missing passphrase.".red());
+                            return;
+                        }
+                    };
+                    nsec.trim().from_ncryptsec(&passphrase)
+                }
+                false => nsec.trim().from_nsec(),
+            });
+
+            // 1.a.3 Create the key holder from the secret key bytes.
+            let key_holder = match secret_key_bytes.and_then(KeyHolder::new) {
+                Some(key_holder) => key_holder,
+                None => {
+                    eprintln!("{}", "Invalid nsec, ncryptsec or passphrase.".red());
+                    return;
+                }
+            };
+
+            // 1.a.4 Read the backup passphrase.
+            let backup_passphrase = match read_backup_passphrase(&mut stdin) {
+                Some(backup_passphrase) if !backup_passphrase.is_empty() => backup_passphrase,
+                _ => {
+                    eprintln!("{}", "Missing backup passphrase.".red());
+                    return;
+                }
+            };
+
+            // 1.a.5 Read the scrypt work factor.
+            let log_n = std::env::var("CUBE_NCRYPTSEC_LOG_N")
+                .ok()
+                .and_then(|value| value.trim().parse::<u8>().ok())
+                .unwrap_or(NCRYPTSEC_LOG_N);
+
+            // 1.a.6 Export the backup.
+            key_backup::run_export(&key_holder, &backup_passphrase, log_n, &args[4]);
+        }
+
+        // 1.b Command is 'key import --input'.
+        ("key", "import", "--input") => {
+            // 1.b.1 Read the backup passphrase.
+            let mut stdin = std::io::stdin().lock();
+            let backup_passphrase = match read_backup_passphrase(&mut stdin) {
+                Some(backup_passphrase) => backup_passphrase,
+                None => {
+                    eprintln!("{}", "Missing backup passphrase.".red());
+                    return;
+                }
+            };
+
+            // 1.b.2 Import the backup.
+            let imported = key_backup::import(&args[4], &backup_passphrase);
+
+            // 1.b.3 Print the identities and the root nsec.
+            match imported {
+                Ok((backup, key_holder)) => {
+                    println!(
+                        "{}",
+                        format!("Key backup taken at {} imported.", backup.created_at).green()
+                    );
+                    key_backup::print_identities(&backup);
+                    match Zeroizing::new(key_holder.secp_secret_key_bytes()).to_nsec() {
+                        Some(nsec) => print_nsec_frame(&Zeroizing::new(nsec)),
+                        None => eprintln!("{}", "Failed to convert secret key to nsec.".red()),
+                    }
+                }
+                Err(err) => {
+                    eprintln!("{} {:?}", "Key import error:".red(), err);
+                    eprintln!("{}", err.remedy().yellow());
+                }
+            }
+        }
+
+        // 1.c Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Signs a message with the BIP-322 simple signature scheme, proving control of the account key.
+fn sign_message(args: &Vec<String>) {
+    // 1 Match the argument name.
+    match args[1].to_lowercase().as_str() {
+        // 1.a Command is 'sign-message'.
+        "sign-message" => {
+            // 1.a.1 Parse chain.
+            let chain = match parse_chain(&args[2]) {
+                Some(chain) => chain,
+                None => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Print the prompt.
+            println!("{}", "Enter nsec:".magenta());
+
+            // 1.a.3 Read the nsec, or the ncryptsec and its passphrase, from stdin.
+            let mut stdin = std::io::stdin().lock();
+            let nsec = match read_secret_input(&mut stdin) {
+                Some(line) => line,
+                None => {
+                    eprintln!("{}", "Invalid nsec.".red());
+                    return;
+                }
+            };
+            let secret_key_bytes = Zeroizing::new(match nsec.trim().starts_with("ncryptsec1") {
+                true => {
+                    let passphrase = match read_passphrase(&mut stdin) {
+                        Some(passphrase) => passphrase,
+                        None => {
+                            eprintln!("{}", "Missing passphrase.".red());
+                            return;
+                        }
+                    };
+                    nsec.trim().from_ncryptsec(&passphrase)
+                }
+                false => nsec.trim().from_nsec(),
+            });
+
+            // 1.a.4 Create the key holder from the secret key bytes.
+            let key_holder = match secret_key_bytes.and_then(KeyHolder::new) {
+                Some(key_holder) => key_holder,
+                None => {
+                    eprintln!("{}", "Invalid nsec, ncryptsec or passphrase.".red());
+                    return;
+                }
+            };
+
+            // 1.a.5 Sign the message and print the address and the signature.
+            match signed_message::sign_simple(&key_holder, chain, args[3].as_bytes()) {
+                Some((address, signature)) => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "account_key": hex::encode(key_holder.secp_public_key_bytes()),
+                        "address": address,
+                        "message": args[3],
+                        "signature": signature,
+                    }))
+                    .expect("Failed to serialize the signed message.")
+                ),
+                None => eprintln!("{}", "Failed to sign the message.".red()),
+            }
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Verifies a BIP-322 simple signature of a message by a P2TR address or an account key.
+fn verify_message(args: &Vec<String>) {
+    // 1 Match the argument name.
+    match args[1].to_lowercase().as_str() {
+        // 1.a Command is 'verify-message'.
+        "verify-message" => {
+            // 1.a.1 Parse chain.
+            let chain = match parse_chain(&args[2]) {
+                Some(chain) => chain,
+                None => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Verify against the account key if given as hex, or else against the address.
+            let account_key: Option<[u8; 32]> = hex::decode(&args[3])
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok());
+            let (message, signature) = (args[4].as_bytes(), &args[5]);
+            let verified = match account_key {
+                Some(account_key) => {
+                    signed_message::verify_account_key(chain, account_key, message, signature)
+                }
+                None => signed_message::verify_simple(chain, &args[3], message, signature),
+            };
+
+            // 1.a.3 Print the outcome.
+            match verified {
+                Ok(()) => println!("{}", "Valid signature.".green()),
+                Err(err) => eprintln!("{} {:?}", "Invalid signature:".red(), err),
+            }
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Parses a chain argument.
+fn parse_chain(arg: &str) -> Option<Chain> {
+    match arg.to_lowercase().as_str() {
+        "signet" => Some(Chain::Signet),
+        "mainnet" => Some(Chain::Mainnet),
+        "testbed" => Some(Chain::Testbed),
+        _ => None,
+    }
+}
+
+/// Reads the passphrase of an ncryptsec from `CUBE_KEY_PASSPHRASE`, or else the next line of stdin.
+fn read_passphrase(reader: &mut impl BufRead) -> Option<Zeroizing<String>> {
+    read_secret_line(reader, "CUBE_KEY_PASSPHRASE", "Enter passphrase:")
+}
+
+/// Reads the passphrase of a key backup from `CUBE_BACKUP_PASSPHRASE`, or else the next line of stdin.
+fn read_backup_passphrase(reader: &mut impl BufRead) -> Option<Zeroizing<String>> {
+    read_secret_line(reader, "CUBE_BACKUP_PASSPHRASE", "Enter backup passphrase:")
+}
+
+/// Reads a secret from the given environment variable, or else prompts for it on the next line of stdin.
+fn read_secret_line(
+    reader: &mut impl BufRead,
+    env_var: &str,
+    prompt: &str,
+) -> Option<Zeroizing<String>> {
+    // 1 Return the secret from the environment, if set.
+    if let Ok(secret) = std::env::var(env_var) {
+        return Some(Zeroizing::new(secret));
+    }
+
+    // 2 Prompt for the secret and read it from stdin.
+    println!("{}", prompt.magenta());
+    read_secret_input(reader)
+}
+
+/// Reads a line holding a secret, without its line ending, into a buffer zeroized on drop.
+///
+/// Returns `None` at the end of the input, or if the line is longer than `SECRET_LINE_CAPACITY` or
+/// is not valid UTF-8.
+fn read_secret_input(reader: &mut impl BufRead) -> Option<Zeroizing<String>> {
+    // 1 Allocate the buffer once, with enough capacity for any secret line.
+    let mut line = Zeroizing::new(Vec::<u8>::with_capacity(SECRET_LINE_CAPACITY));
+    let mut read_any = false;
+
+    // 2 Copy the input up to the line ending into the buffer, refusing to grow it.
+    loop {
+        let available = reader.fill_buf().ok()?;
+        if available.is_empty() {
+            break;
+        }
+        read_any = true;
+
+        let (chunk_len, line_ended) = match available.iter().position(|byte| *byte == b'\n') {
+            Some(position) => (position, true),
+            None => (available.len(), false),
+        };
+        if line.len() + chunk_len > SECRET_LINE_CAPACITY {
+            return None;
+        }
+        line.extend_from_slice(&available[..chunk_len]);
+        reader.consume(chunk_len + line_ended as usize);
+
+        if line_ended {
+            break;
+        }
+    }
+    if !read_any {
+        return None;
+    }
+
+    // 3 Strip a carriage return.
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+
+    // 4 Move the bytes into a string, zeroizing them if they are not valid UTF-8.
+    match String::from_utf8(std::mem::take(&mut *line)) {
+        Ok(secret) => Some(Zeroizing::new(secret)),
+        Err(err) => {
+            err.into_bytes().zeroize();
+            None
+        }
+    }
+}
+
 /// Prints genesis params as pretty JSON (random engine key + genesis payload P2TR address).
 fn genesis(args: &Vec<String>) {
     // 1 Match the argument name.
@@ -268,12 +669,189 @@ fn genesis(args: &Vec<String>) {
     }
 }
 
+/// Imports a trusted state snapshot into empty storage, so that syncing starts from its height.
+fn bootstrap(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'bootstrap --snapshot'.
+        ("bootstrap", "--snapshot") => bootstrap::run(&args[3]),
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Wipes the derived ledger state and re-derives it by replaying archived batch records.
+fn reindex(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'reindex --chain'.
+        ("reindex", "--chain") => {
+            // 1.a.1 Parse chain.
+            let chain = match args[3].to_lowercase().as_str() {
+                "signet" => Chain::Signet,
+                "mainnet" => Chain::Mainnet,
+                "testbed" => Chain::Testbed,
+                _ => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Run the reindex.
+            reindex::run(chain);
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Replays a range of archived batch records and compares them with the journal.
+fn replay(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+        args[4].to_lowercase().as_str(),
+        args[6].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'replay --chain --from --to'.
+        ("replay", "--chain", "--from", "--to") => {
+            // 1.a.1 Parse chain.
+            let chain = match args[3].to_lowercase().as_str() {
+                "signet" => Chain::Signet,
+                "mainnet" => Chain::Mainnet,
+                "testbed" => Chain::Testbed,
+                _ => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Parse the batch height range.
+            let (from_batch_height, to_batch_height) =
+                match (args[5].parse::<u64>(), args[7].parse::<u64>()) {
+                    (Ok(from_batch_height), Ok(to_batch_height)) => {
+                        (from_batch_height, to_batch_height)
+                    }
+                    _ => {
+                        eprintln!("{}", "Invalid <height>.".red());
+                        return;
+                    }
+                };
+
+            // 1.a.3 Run the replay.
+            replay::run(chain, from_batch_height, to_batch_height);
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Releases the blocks quarantined during sync, so that they are retried on the next run.
+fn sync(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+        args[3].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'sync retry-quarantined --chain'.
+        ("sync", "retry-quarantined", "--chain") => {
+            // 1.a.1 Parse chain.
+            let chain = match args[4].to_lowercase().as_str() {
+                "signet" => Chain::Signet,
+                "mainnet" => Chain::Mainnet,
+                "testbed" => Chain::Testbed,
+                _ => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Release the quarantined blocks.
+            quarantine::retry_quarantined(chain);
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Exports the ledger state of a stopped node into a snapshot file.
+fn snapshot(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+        args[3].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'snapshot export --chain'.
+        ("snapshot", "export", "--chain") => {
+            // 1.a.1 Parse chain.
+            let chain = match args[4].to_lowercase().as_str() {
+                "signet" => Chain::Signet,
+                "mainnet" => Chain::Mainnet,
+                "testbed" => Chain::Testbed,
+                _ => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Export the snapshot.
+            bootstrap::run_export(chain, &args[5]);
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
+/// Sends a command to the admin socket of a running instance.
+fn admin(args: &Vec<String>) {
+    // 1 Match the argument names.
+    match (
+        args[1].to_lowercase().as_str(),
+        args[2].to_lowercase().as_str(),
+    ) {
+        // 1.a Command is 'admin --chain'.
+        ("admin", "--chain") => {
+            // 1.a.1 Parse chain.
+            let chain = match args[3].to_lowercase().as_str() {
+                "signet" => Chain::Signet,
+                "mainnet" => Chain::Mainnet,
+                "testbed" => Chain::Testbed,
+                _ => {
+                    eprintln!("{}", "Invalid <chain>.".red());
+                    return;
+                }
+            };
+
+            // 1.a.2 Send the admin command.
+            admin_client::run(chain, &args[4..]);
+        }
+
+        // 1.b Command is invalid.
+        _ => print_correct_usage(),
+    }
+}
+
 /// Runs the appropriate mode based on the arguments.
 fn run(args: &Vec<String>) {
     // 1 Parse resource mode.
     let resource_mode = match args[1].to_lowercase().as_str() {
         "pruned" => ResourceMode::Pruned,
         "archival" => ResourceMode::Archival,
+        "light" => ResourceMode::Light,
         _ => {
             println!("{}", "Invalid <resource mode>.".red());
             return;
@@ -324,55 +902,64 @@ fn run(args: &Vec<String>) {
         println!("{}", "Enter nsec:".magenta());
 
         // 6.2 Parse the secret key.
-        let secret_key: [u8; 32] = {
+        let secret_key: Zeroizing<[u8; 32]> = {
             // 6.2.1 Initialize the secret key bytes.
-            let mut secret_key_bytes = [0xffu8; 32];
+            let mut secret_key_bytes = Zeroizing::new([0xffu8; 32]);
 
             //
             // DANGER ZONE BEGIN: reading private key from stdin.
             //
             {
-                // 6.2.2 Read the input from stdin.
-                let stdin = std::io::stdin();
+                // 6.2.2 Lock stdin.
+                let mut stdin = std::io::stdin().lock();
 
-                // 6.2.3 Get the handle.
-                let handle = stdin.lock();
-
-                // 6.2.4 Drop stdin.
-                drop(stdin);
-
-                // 6.2.5 Parse the input.
-                for line in handle.lines() {
-                    // 6.2.5.1 Unwrap the line.
-                    let line = line.unwrap();
-
-                    // 6.2.5.2 Parse the parts.
+                // 6.2.3 Parse the input.
+                while let Some(line) = read_secret_input(&mut stdin) {
+                    // 6.2.3.1 Parse the parts.
                     let parts: Vec<&str> = line.trim().split_whitespace().collect();
 
-                    // 6.2.5.3 Check if the parts length is valid.
+                    // 6.2.3.2 Check if the parts length is valid.
                     if parts.len() != 1 {
                         println!("{}", "Invalid nsec.".yellow());
                     }
 
-                    // 6.2.5.4 Parse the nsec.
-                    let nsec: String = parts[0].to_owned();
-
-                    // 6.2.5.5 Drop the parts.
-                    drop(parts);
+                    // 6.2.3.3 Parse the nsec, borrowed from the line.
+                    let nsec: &str = match parts.first() {
+                        Some(nsec) => nsec,
+                        None => continue,
+                    };
 
-                    // 6.2.5.6 Convert the nsec to a secret key.
-                    secret_key_bytes = match nsec.as_str().from_nsec() {
-                        Some(secret_key) => secret_key,
-                        None => {
-                            eprintln!("{}", "Invalid nsec.".red());
-                            return;
+                    // 6.2.3.4 Convert the nsec to a secret key.
+                    *secret_key_bytes = match nsec.starts_with("ncryptsec1") {
+                        // 6.2.3.4.a The nsec is encrypted, decrypt it with the passphrase.
+                        true => {
+                            let passphrase = match read_passphrase(&mut stdin) {
+                                Some(passphrase) => passphrase,
+                                None => {
+                                    eprintln!("{}", "Missing passphrase.".red());
+                                    return;
+                                }
+                            };
+                            match nsec.from_ncryptsec(&passphrase) {
+                                Some(secret_key) => secret_key,
+                                None => {
+                                    eprintln!("{}", "Invalid ncryptsec or passphrase.".red());
+                                    return;
+                                }
+                            }
                         }
-                    };
 
-                    // 6.2.5.7 Drop the nsec.
-                    drop(nsec);
+                        // 6.2.3.4.b The nsec is plain.
+                        false => match nsec.from_nsec() {
+                            Some(secret_key) => secret_key,
+                            None => {
+                                eprintln!("{}", "Invalid nsec.".red());
+                                return;
+                            }
+                        },
+                    };
 
-                    // 6.2.5.8 Break the loop.
+                    // 6.2.3.5 Break the loop.
                     break;
                 }
             }
@@ -385,7 +972,7 @@ fn run(args: &Vec<String>) {
         };
 
         // 6.3 Create the key holder from the secret key bytes.
-        let key_holder = match KeyHolder::new(secret_key) {
+        let key_holder = match KeyHolder::new(*secret_key) {
             Some(key_holder) => key_holder,
             None => {
                 eprintln!("{}", "Invalid nsec.".red());
@@ -393,11 +980,44 @@ fn run(args: &Vec<String>) {
             }
         };
 
-        // 6.4 Return the key holder.
+        // 6.4 Optionally use the node identity key derived from the nsec as the root secret.
+        let hd_identity = matches!(
+            std::env::var("CUBE_HD_IDENTITY")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .as_deref(),
+            Some("1") | Some("true")
+        );
+        let key_holder = match hd_identity {
+            true => {
+                let derived_key_holder = match key_holder.derive_for(KeyPurpose::NodeIdentity) {
+                    Some(derived_key_holder) => derived_key_holder,
+                    None => {
+                        eprintln!("{}", "Failed to derive the node identity key.".red());
+                        return;
+                    }
+                };
+                println!(
+                    "Using the node identity key derived at m/{}: {}",
+                    derived_key_holder
+                        .derivation_path()
+                        .map(|path| path.to_string())
+                        .unwrap_or_default(),
+                    derived_key_holder.npub()
+                );
+                derived_key_holder
+            }
+            false => key_holder,
+        };
+
+        // 6.5 Return the key holder.
         key_holder
     };
 
-    // 7 Run the runner
+    // 7 Build the secp tables ahead of the sync.
+    context::warm_up();
+
+    // 8 Run the runner
     runner::run(
         resource_mode,
         chain,
@@ -413,7 +1033,7 @@ fn print_correct_usage() {
     eprintln!(
         "{}",
         format!(
-            "Usage:\n  gensec\n  genesis <mainnet|signet|testbed>\n  <mode> <chain> <kind> <bitcoin-rpc-url> <bitcoin-rpc-user> <bitcoin-rpc-password> <syncinflight?>\n\nIn engine/node CLI (archival mode): runexplorer <port>"
+            "Usage:\n  gensec\n  encrypt-key\n  key export --output <file>\n  key import --input <file>\n  sign-message <mainnet|signet|testbed> <message>\n  verify-message <mainnet|signet|testbed> <address|account-key> <message> <signature>\n  genesis <mainnet|signet|testbed>\n  bootstrap --snapshot <file|url>\n  snapshot export --chain <mainnet|signet|testbed> <file>\n  reindex --chain <mainnet|signet|testbed>\n  replay --chain <mainnet|signet|testbed> --from <height> --to <height>\n  sync retry-quarantined --chain <mainnet|signet|testbed>\n  admin --chain <mainnet|signet|testbed> <command> [args...]\n  <mode> <chain> <kind> <bitcoin-rpc-url> <bitcoin-rpc-user> <bitcoin-rpc-password> <syncinflight?>\n\nIn engine/node CLI (archival mode): runexplorer <port>"
         )
         .red()
     );
@@ -430,10 +1050,6 @@ fn genesis_payload_address(chain: Chain, engine_key: [u8; 32]) -> Option<String>
     // 3 Get the taproot for the genesis payload.
     let genesis_payload_taproot = genesis_payload_without_location.taproot()?;
 
-    // 4 Get the tweaked key for the genesis payload.
-    let genesis_payload_taproot_key: [u8; 32] =
-        genesis_payload_taproot.tweaked_key()?.serialize_xonly();
-
-    // 5 Encode the tweaked key into an address.
-    encode_p2tr(chain, genesis_payload_taproot_key)
+    // 4 Encode the taproot output key into an address.
+    genesis_payload_taproot.address(chain)
 }
@@ -1,4 +1,13 @@
+pub mod admin;
+pub mod backfill;
+pub mod bootstrap;
 pub mod cli;
+pub mod key_backup;
+pub mod logging;
+pub mod recovery;
+pub mod reindex;
+pub mod replay;
 pub mod run_args;
 pub mod runner;
+pub mod simulation;
 pub mod tasks;
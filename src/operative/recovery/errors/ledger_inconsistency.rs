@@ -0,0 +1,29 @@
+/// Batch height.
+type BatchHeight = u64;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract ID.
+type ContractId = [u8; 32];
+
+/// Inconsistencies the startup recovery sequence detects in the ledger state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerInconsistency {
+    /// A reindex was started but never completed, leaving the derived state partially rebuilt.
+    ReindexInterrupted,
+    /// A batch commit was started but never completed.
+    IncompleteCommit(BatchHeight),
+    /// The account balances state root does not match the one recorded at the last committed height.
+    StateRootMismatch {
+        batch_height: BatchHeight,
+        committed_state_root: [u8; 32],
+        computed_state_root: [u8; 32],
+    },
+    /// An account is in the registery but not in the coin manager.
+    AccountMissingFromCoinManager(AccountKey),
+    /// A contract is in the registery but not in the coin manager.
+    ContractMissingFromCoinManager(ContractId),
+    /// A contract is in the registery but not in the state manager.
+    ContractMissingFromStateManager(ContractId),
+}
@@ -0,0 +1,2 @@
+pub mod ledger_inconsistency;
+pub mod recovery_error;
@@ -0,0 +1,44 @@
+use crate::inscriptive::archival_manager::errors::construction_error::ArchivalConstructionError;
+use crate::inscriptive::coin_manager::errors::construction_errors::CMConstructionError;
+use crate::inscriptive::registery::errors::construction_error::RMConstructionError;
+use crate::inscriptive::state_manager::errors::construction_error::SMConstructionError as StateManagerConstructionError;
+use crate::inscriptive::sync_manager::errors::construction_error::SMConstructionError as SyncManagerConstructionError;
+use crate::operative::recovery::errors::ledger_inconsistency::LedgerInconsistency;
+use crate::operative::reindex::errors::reindex_error::ReindexError;
+
+/// Errors associated with the startup recovery sequence.
+#[derive(Debug, Clone)]
+pub enum RecoveryError {
+    SyncManagerConstructionError(SyncManagerConstructionError),
+    RegisteryConstructionError(RMConstructionError),
+    CoinManagerConstructionError(CMConstructionError),
+    StateManagerConstructionError(StateManagerConstructionError),
+    ArchivalManagerConstructionError(ArchivalConstructionError),
+    /// The ledger is inconsistent and there are no archived batch records to repair it from.
+    RepairUnavailable(LedgerInconsistency),
+    /// The ledger is inconsistent and reindexing it from the archived batch records failed.
+    RepairFailed(LedgerInconsistency, ReindexError),
+    /// The ledger is still inconsistent after reindexing it from the archived batch records.
+    StillInconsistentAfterRepair(LedgerInconsistency),
+}
+
+impl RecoveryError {
+    /// Returns what the operator can do about the error.
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            RecoveryError::SyncManagerConstructionError(_)
+            | RecoveryError::RegisteryConstructionError(_)
+            | RecoveryError::CoinManagerConstructionError(_)
+            | RecoveryError::StateManagerConstructionError(_)
+            | RecoveryError::ArchivalManagerConstructionError(_) => {
+                "Make sure no other instance is using the storage directory, then restart. If the storage is corrupted, restore it from a backup."
+            }
+            RecoveryError::RepairUnavailable(_) => {
+                "Restart in archival resource mode with archived batch records, or restore the storage directory from a backup taken at a clean shutdown."
+            }
+            RecoveryError::RepairFailed(_, _) | RecoveryError::StillInconsistentAfterRepair(_) => {
+                "The archived batch records could not rebuild a consistent ledger. Restore the storage directory from a backup taken at a clean shutdown."
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::inscriptive::archival_manager::archival_manager::ArchivalManager;
+use crate::inscriptive::coin_manager::coin_manager::CoinManager;
+use crate::inscriptive::registery::registery::Registery;
+use crate::inscriptive::state_manager::state_manager::StateManager;
+use crate::inscriptive::sync_manager::sync_manager::SyncManager;
+use crate::operative::recovery::errors::ledger_inconsistency::LedgerInconsistency;
+use crate::operative::recovery::errors::recovery_error::RecoveryError;
+use crate::operative::reindex::reindex::{is_reindex_in_progress, reindex};
+use crate::operative::run_args::chain::Chain;
+use crate::operative::run_args::resource_mode::ResourceMode;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Outcome of the startup recovery sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryOutcome {
+    /// The ledger was consistent.
+    Clean,
+    /// The ledger was inconsistent and has been reindexed up to the given batch height.
+    Repaired(BatchHeight),
+}
+
+/// Runs the startup recovery sequence.
+///
+/// Checks for an interrupted reindex and the commit journal for an incomplete batch commit, verifies the account balances state
+/// root against the one recorded at the last committed batch height, and cross-checks the registery
+/// against the coin manager and the state manager. An inconsistent ledger is repaired by reindexing
+/// it from the archived batch records when running in archival resource mode, and refused otherwise.
+///
+/// Must be called before the managers are opened by the caller.
+pub async fn recover(
+    chain: Chain,
+    resource_mode: ResourceMode,
+) -> Result<RecoveryOutcome, RecoveryError> {
+    // 1 Check the ledger.
+    let inconsistency = match check_ledger(chain).await? {
+        Some(inconsistency) => inconsistency,
+        None => return Ok(RecoveryOutcome::Clean),
+    };
+
+    // 2 Make sure there are archived batch records to repair from.
+    {
+        // 2.1 Only the archival resource mode keeps batch records.
+        if resource_mode != ResourceMode::Archival {
+            return Err(RecoveryError::RepairUnavailable(inconsistency));
+        }
+
+        // 2.2 Make sure the archive starts from batch height #1, which reindexing replays from.
+        let archival_manager =
+            ArchivalManager::new(chain).map_err(RecoveryError::ArchivalManagerConstructionError)?;
+        if archival_manager
+            .lock()
+            .await
+            .batch_record_by_height(1)
+            .is_none()
+        {
+            return Err(RecoveryError::RepairUnavailable(inconsistency));
+        }
+    }
+
+    // 3 Repair the ledger by reindexing it from the archived batch records.
+    let replayed_batch_height = reindex(chain)
+        .await
+        .map_err(|err| RecoveryError::RepairFailed(inconsistency, err))?;
+
+    // 4 Check the ledger once again.
+    if let Some(inconsistency) = check_ledger(chain).await? {
+        return Err(RecoveryError::StillInconsistentAfterRepair(inconsistency));
+    }
+
+    // 5 Return the outcome.
+    Ok(RecoveryOutcome::Repaired(replayed_batch_height))
+}
+
+/// Checks the ledger for inconsistencies, returning the first one found.
+///
+/// The managers are opened here and closed before returning.
+pub async fn check_ledger(chain: Chain) -> Result<Option<LedgerInconsistency>, RecoveryError> {
+    // 1 Check for a reindex that wiped the derived state but did not complete.
+    if is_reindex_in_progress(chain) {
+        return Ok(Some(LedgerInconsistency::ReindexInterrupted));
+    }
+
+    // 2 Open the sync manager.
+    let sync_manager =
+        SyncManager::new(chain).map_err(RecoveryError::SyncManagerConstructionError)?;
+    let (cube_batch_sync_height_tip, pending_commit_batch_height, committed_state_root) = {
+        let _sync_manager = sync_manager.lock().await;
+        (
+            _sync_manager.cube_batch_sync_height_tip(),
+            _sync_manager.pending_commit_batch_height(),
+            _sync_manager.committed_state_root(),
+        )
+    };
+
+    // 3 Check the commit journal for an incomplete commit.
+    if let Some(batch_height) = pending_commit_batch_height {
+        return Ok(Some(LedgerInconsistency::IncompleteCommit(batch_height)));
+    }
+
+    // 4 Open the coin manager.
+    let coin_manager =
+        CoinManager::new(chain).map_err(RecoveryError::CoinManagerConstructionError)?;
+    let _coin_manager = coin_manager.lock().await;
+
+    // 5 Verify the state root against the last committed batch height.
+    // Ledgers committed before the journal was introduced have no recorded state root.
+    if let Some(committed_state_root) = committed_state_root {
+        let computed_state_root = _coin_manager.account_balances_state_root();
+        if computed_state_root != committed_state_root {
+            return Ok(Some(LedgerInconsistency::StateRootMismatch {
+                batch_height: cube_batch_sync_height_tip,
+                committed_state_root,
+                computed_state_root,
+            }));
+        }
+    }
+
+    // 6 Open the registery and the state manager.
+    let registery = Registery::new(chain).map_err(RecoveryError::RegisteryConstructionError)?;
+    let _registery = registery.lock().await;
+    let state_manager =
+        StateManager::new(chain).map_err(RecoveryError::StateManagerConstructionError)?;
+    let _state_manager = state_manager.lock().await;
+
+    // 7 Cross-check the registered accounts against the coin manager.
+    for account_key in _registery.permanently_registered_account_keys() {
+        if !_coin_manager.is_account_registered(account_key) {
+            return Ok(Some(LedgerInconsistency::AccountMissingFromCoinManager(
+                account_key,
+            )));
+        }
+    }
+
+    // 8 Cross-check the registered contracts against the coin manager and the state manager.
+    for contract_id in _registery.permanently_registered_contract_ids() {
+        // 8.1 Check the coin manager.
+        if !_coin_manager.is_contract_registered(contract_id) {
+            return Ok(Some(LedgerInconsistency::ContractMissingFromCoinManager(
+                contract_id,
+            )));
+        }
+
+        // 8.2 Check the state manager.
+        if !_state_manager.is_contract_registered(contract_id) {
+            return Ok(Some(LedgerInconsistency::ContractMissingFromStateManager(
+                contract_id,
+            )));
+        }
+    }
+
+    // 9 The ledger is consistent.
+    Ok(None)
+}
@@ -0,0 +1 @@
+pub mod reindex_error;
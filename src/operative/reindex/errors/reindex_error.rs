@@ -0,0 +1,32 @@
+use crate::executive::exec_ctx::errors::batch_execution_error::BatchExecutionError;
+use crate::inscriptive::archival_manager::errors::construction_error::ArchivalConstructionError;
+use crate::inscriptive::coin_manager::errors::construction_errors::CMConstructionError;
+use crate::inscriptive::flame_manager::errors::construction_error::FMConstructionError;
+use crate::inscriptive::graveyard::errors::construction_error::GraveyardConstructionError;
+use crate::inscriptive::privileges_manager::errors::construction_error::PrivilegesManagerConstructionError;
+use crate::inscriptive::registery::errors::construction_error::RMConstructionError;
+use crate::inscriptive::state_manager::errors::construction_error::SMConstructionError as StateManagerConstructionError;
+use crate::inscriptive::sync_manager::errors::construction_error::SMConstructionError as SyncManagerConstructionError;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Errors associated with reindexing the derived ledger state.
+#[derive(Debug, Clone)]
+pub enum ReindexError {
+    ArchivalManagerConstructionError(ArchivalConstructionError),
+    NoArchivedBatchRecords,
+    ArchivedBatchHeightGap(BatchHeight, BatchHeight),
+    SyncManagerConstructionError(SyncManagerConstructionError),
+    UTXOSetConstructionError,
+    RegisteryConstructionError(RMConstructionError),
+    GraveyardConstructionError(GraveyardConstructionError),
+    CoinManagerConstructionError(CMConstructionError),
+    FlameManagerConstructionError(FMConstructionError),
+    StateManagerConstructionError(StateManagerConstructionError),
+    PrivilegesManagerConstructionError(PrivilegesManagerConstructionError),
+    ParamsManagerConstructionError(sled::Error),
+    ReplayBatchError(BatchHeight, BatchExecutionError),
+    MarkerWriteError(String),
+    MarkerRemoveError(String),
+}
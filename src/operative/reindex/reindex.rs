@@ -0,0 +1,238 @@
+use crate::communicative::peer::manager::engine_key;
+use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
+use crate::constructive::txout_types::payload::payload::genesis_payload;
+use crate::executive::exec_ctx::errors::batch_execution_error::BatchExecutionError;
+use crate::executive::exec_ctx::exec_ctx::{ExecCtx, EXEC_CTX};
+use crate::inscriptive::archival_manager::archival_manager::ArchivalManager;
+use crate::inscriptive::coin_manager::coin_manager::{erase_coin_manager, CoinManager};
+use crate::inscriptive::flame_manager::flame_manager::{erase_flame_manager, FlameManager};
+use crate::inscriptive::graveyard::graveyard::{erase_graveyard, Graveyard};
+use crate::inscriptive::params_manager::params_manager::{erase_params_manager, ParamsManager};
+use crate::inscriptive::privileges_manager::privileges_manager::{
+    erase_privileges_manager, PrivilegesManager,
+};
+use crate::inscriptive::registery::registery::{erase_registery, Registery};
+use crate::inscriptive::schedule_manager::schedule_manager::erase_schedule_manager;
+use crate::inscriptive::state_manager::state_manager::{erase_state_manager, StateManager};
+use crate::inscriptive::sync_manager::sync_manager::SyncManager;
+use crate::inscriptive::utxo_set::utxo_set::{UTXOSet, UTXO_SET};
+use crate::operative::reindex::errors::reindex_error::ReindexError;
+use crate::operative::run_args::chain::Chain;
+use colored::Colorize;
+use std::io::Write;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Returns the path of the marker file present while a reindex is in progress.
+fn reindex_marker_path(chain: Chain) -> String {
+    format!("storage/{}/reindex_in_progress", chain.to_string())
+}
+
+/// Returns whether a reindex was started but never completed, leaving the derived state partially rebuilt.
+pub fn is_reindex_in_progress(chain: Chain) -> bool {
+    std::path::Path::new(&reindex_marker_path(chain)).exists()
+}
+
+/// Durably marks a reindex as in progress, before any derived state is wiped.
+pub fn mark_reindex_in_progress(chain: Chain) -> Result<(), ReindexError> {
+    // 1 Make sure the storage directory of the chain exists.
+    std::fs::create_dir_all(format!("storage/{}", chain.to_string()))
+        .map_err(|err| ReindexError::MarkerWriteError(err.to_string()))?;
+
+    // 2 Write the marker and sync it to disk.
+    let mut marker = std::fs::File::create(reindex_marker_path(chain))
+        .map_err(|err| ReindexError::MarkerWriteError(err.to_string()))?;
+    marker
+        .write_all(b"reindex in progress")
+        .and_then(|_| marker.sync_all())
+        .map_err(|err| ReindexError::MarkerWriteError(err.to_string()))
+}
+
+/// Clears the reindex in progress marker, once the derived state is fully rebuilt.
+pub fn clear_reindex_in_progress(chain: Chain) -> Result<(), ReindexError> {
+    match std::fs::remove_file(reindex_marker_path(chain)) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(ReindexError::MarkerRemoveError(err.to_string())),
+    }
+}
+
+/// Runs the reindex procedure and prints the outcome.
+#[tokio::main]
+pub async fn run(chain: Chain) {
+    // 1 Print the reindexing message.
+    println!("{}", format!("Reindexing {}.", chain.to_string()));
+
+    // 2 Reindex and print the result.
+    match reindex(chain).await {
+        Ok(replayed_batch_height) => println!(
+            "{}",
+            format!(
+                "Reindex complete. Replayed up to batch height #{}.",
+                replayed_batch_height
+            )
+            .green()
+        ),
+        Err(err) => {
+            eprintln!("{} {:?}", "Reindex error:".red(), err);
+
+            // 3 Tell the operator the derived state is incomplete if it was wiped already.
+            if is_reindex_in_progress(chain) {
+                eprintln!(
+                    "{}",
+                    "The derived state is only partially rebuilt. Run reindex again, or restart in archival resource mode to resume it; the node refuses to start otherwise."
+                        .yellow()
+                );
+            }
+        }
+    }
+}
+
+/// Wipes the derived ledger state (coins, flames, graveyard, registery, states, privileges, params and
+/// deferred calls) while keeping the archived batch records and the Bitcoin-side utxo set, then
+/// re-derives everything by replaying the archived batch records in ascending batch height order.
+///
+/// A marker is written to disk before anything is wiped and removed only once every batch record is
+/// replayed, so that a reindex failing or interrupted midway is caught on startup rather than leaving
+/// the node running on a partially rebuilt state. Running the reindex again starts it over.
+///
+/// Returns the last replayed batch height.
+pub async fn reindex(chain: Chain) -> Result<BatchHeight, ReindexError> {
+    // 1 Collect the archived batch records.
+    let batch_records: Vec<BatchRecord> = {
+        // 1.1 Open the archival manager.
+        let archival_manager = ArchivalManager::new(chain)
+            .map_err(ReindexError::ArchivalManagerConstructionError)?;
+
+        // 1.2 Collect the batch records in ascending batch height order.
+        let _archival_manager = archival_manager.lock().await;
+        _archival_manager.batch_records()
+    };
+
+    // 2 Refuse to wipe anything if there is nothing to replay.
+    if batch_records.is_empty() {
+        return Err(ReindexError::NoArchivedBatchRecords);
+    }
+
+    // 3 Make sure the archived batch records are contiguous starting from batch height #1.
+    for (index, batch_record) in batch_records.iter().enumerate() {
+        let expected_batch_height = index as u64 + 1;
+        if batch_record.batch_height != expected_batch_height {
+            return Err(ReindexError::ArchivedBatchHeightGap(
+                expected_batch_height,
+                batch_record.batch_height,
+            ));
+        }
+    }
+
+    // 4 Mark the reindex as in progress, wipe the derived state and construct the execution context.
+    let (exec_ctx, utxo_set) = wipe_derived_state(chain).await?;
+
+    // 5 Replay the archived batch records one by one.
+    let mut replayed_batch_height: BatchHeight = 0;
+    for batch_record in batch_records.iter() {
+        // 5.1 Re-execute the batch.
+        replay_batch_record(&exec_ctx, &utxo_set, batch_record)
+            .await
+            .map_err(|err| ReindexError::ReplayBatchError(batch_record.batch_height, err))?;
+
+        // 5.2 Update the replayed batch height.
+        replayed_batch_height = batch_record.batch_height;
+
+        println!("Replayed batch height #{}.", replayed_batch_height);
+    }
+
+    // 6 Clear the reindex in progress marker.
+    clear_reindex_in_progress(chain)?;
+
+    // 7 Return the last replayed batch height.
+    Ok(replayed_batch_height)
+}
+
+/// Marks a reindex as in progress, wipes the derived ledger state and rewinds the cube batch tips and
+/// the commit journal, then returns an execution context over the emptied managers along with the
+/// utxo set.
+///
+/// The archival manager is left out of the execution context since the replayed batch records are
+/// already archived.
+pub async fn wipe_derived_state(chain: Chain) -> Result<(EXEC_CTX, UTXO_SET), ReindexError> {
+    // 1 Mark the reindex as in progress, then wipe the derived state.
+    mark_reindex_in_progress(chain)?;
+    erase_coin_manager(chain);
+    erase_flame_manager(chain);
+    erase_graveyard(chain);
+    erase_registery(chain);
+    erase_state_manager(chain);
+    erase_privileges_manager(chain);
+    erase_params_manager(chain);
+    erase_schedule_manager(chain);
+
+    // 2 Rewind the cube batch tips and the commit journal in the sync manager while keeping the Bitcoin sync height tip.
+    let sync_manager =
+        SyncManager::new(chain).map_err(ReindexError::SyncManagerConstructionError)?;
+    {
+        let mut _sync_manager = sync_manager.lock().await;
+        _sync_manager.set_cube_batch_sync_height_tip(0);
+        _sync_manager.set_payload_tip(genesis_payload(chain));
+        _sync_manager.clear_commit_journal();
+    }
+
+    // 3 Re-open the managers from scratch.
+    let utxo_set = UTXOSet::new(chain).ok_or(ReindexError::UTXOSetConstructionError)?;
+    let registery = Registery::new(chain).map_err(ReindexError::RegisteryConstructionError)?;
+    let graveyard = Graveyard::new(chain).map_err(ReindexError::GraveyardConstructionError)?;
+    let coin_manager =
+        CoinManager::new(chain).map_err(ReindexError::CoinManagerConstructionError)?;
+    let flame_manager =
+        FlameManager::new(chain).map_err(ReindexError::FlameManagerConstructionError)?;
+    let state_manager =
+        StateManager::new(chain).map_err(ReindexError::StateManagerConstructionError)?;
+    let privileges_manager = PrivilegesManager::new(chain)
+        .map_err(ReindexError::PrivilegesManagerConstructionError)?;
+    let params_manager =
+        ParamsManager::new(chain).map_err(ReindexError::ParamsManagerConstructionError)?;
+
+    // 4 Construct the execution context.
+    let exec_ctx = ExecCtx::construct(
+        engine_key(chain),
+        sync_manager,
+        utxo_set.clone(),
+        registery,
+        graveyard,
+        coin_manager,
+        flame_manager,
+        state_manager,
+        privileges_manager,
+        params_manager,
+        None,
+    );
+
+    // 5 Return the execution context and the utxo set.
+    Ok((exec_ctx, utxo_set))
+}
+
+/// Re-executes an archived batch record on top of the batch before it, and returns the batch record
+/// the re-execution resulted in.
+pub async fn replay_batch_record(
+    exec_ctx: &EXEC_CTX,
+    utxo_set: &UTXO_SET,
+    batch_record: &BatchRecord,
+) -> Result<BatchRecord, BatchExecutionError> {
+    // 1 Restore the Bitcoin inputs spent by the batch, so that lift prevouts can be resolved again.
+    {
+        let mut _utxo_set = utxo_set.lock().await;
+        for (outpoint, txout, _) in batch_record
+            .batch_container
+            .signed_batch_txn
+            .tx_inputs
+            .iter()
+        {
+            _utxo_set.insert_utxo(outpoint, txout);
+        }
+    }
+
+    // 2 Re-execute the batch.
+    let mut _exec_ctx = exec_ctx.lock().await;
+    _exec_ctx.execute_batch(&batch_record.batch_container).await
+}
@@ -0,0 +1 @@
+pub mod replay_error;
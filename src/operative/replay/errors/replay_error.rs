@@ -0,0 +1,22 @@
+use crate::executive::exec_ctx::errors::batch_execution_error::BatchExecutionError;
+use crate::inscriptive::archival_manager::errors::construction_error::ArchivalConstructionError;
+use crate::operative::bootstrap::errors::bootstrap_error::BootstrapError;
+use crate::operative::reindex::errors::reindex_error::ReindexError;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Errors associated with replaying archived batch records.
+#[derive(Debug, Clone)]
+pub enum ReplayError {
+    InvalidHeightRange(BatchHeight, BatchHeight),
+    ArchivalManagerConstructionError(ArchivalConstructionError),
+    ArchivedBatchHeightGap(BatchHeight, BatchHeight),
+    MissingArchivedBatchRecord(BatchHeight),
+    MissingJournaledStateRoot(BatchHeight),
+    LedgerTipError(BootstrapError),
+    SnapshotReadError(sled::Error),
+    ReindexError(ReindexError),
+    ReplayBatchError(BatchHeight, BatchExecutionError),
+    SnapshotRestoreError(sled::Error),
+}
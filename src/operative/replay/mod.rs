@@ -0,0 +1,3 @@
+pub mod errors;
+pub mod replay;
+pub mod replay_report;
@@ -0,0 +1,228 @@
+use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
+use crate::inscriptive::archival_manager::archival_manager::ArchivalManager;
+use crate::operative::bootstrap::bootstrap::ledger_tip;
+use crate::operative::bootstrap::snapshot::SnapshotBundle;
+use crate::operative::reindex::reindex::{
+    clear_reindex_in_progress, replay_batch_record, wipe_derived_state,
+};
+use crate::operative::replay::errors::replay_error::ReplayError;
+use crate::operative::replay::replay_report::{ReplayMismatch, ReplayReport};
+use crate::operative::run_args::chain::Chain;
+use colored::Colorize;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Runs the replay procedure and prints the outcome.
+#[tokio::main]
+pub async fn run(chain: Chain, from_batch_height: BatchHeight, to_batch_height: BatchHeight) {
+    // 1 Print the replaying message.
+    println!(
+        "{}",
+        format!(
+            "Replaying {} from batch height #{} to #{}.",
+            chain.to_string(),
+            from_batch_height,
+            to_batch_height
+        )
+    );
+
+    // 2 Replay and print the result.
+    match replay(chain, from_batch_height, to_batch_height).await {
+        Ok(report) if report.matches() => println!(
+            "{}",
+            "Replay complete. Every batch matches the journal.".green()
+        ),
+        Ok(report) => {
+            println!(
+                "{}",
+                format!(
+                    "Replay complete with {} mismatches.",
+                    report.mismatches.len()
+                )
+                .red()
+            );
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report.json()).unwrap_or_default()
+            );
+        }
+        Err(err) => eprintln!("{} {:?}", "Replay error:".red(), err),
+    }
+}
+
+/// Re-executes the archived batch records from `from_batch_height` to `to_batch_height`, and
+/// compares each replayed batch record and resulting account balances state root with the archived
+/// batch record and the state root journaled when the batch was first committed.
+///
+/// The ledger is rebuilt up to the batch before the range by re-executing the earlier archived batch
+/// records. The ledger state is snapshotted beforehand and restored afterwards, whether the replay
+/// succeeds or not, so that the node is left as it was. The node must be stopped.
+///
+/// Returns the report of the mismatches found.
+pub async fn replay(
+    chain: Chain,
+    from_batch_height: BatchHeight,
+    to_batch_height: BatchHeight,
+) -> Result<ReplayReport, ReplayError> {
+    // 1 Make sure the height range is valid.
+    if from_batch_height == 0 || from_batch_height > to_batch_height {
+        return Err(ReplayError::InvalidHeightRange(
+            from_batch_height,
+            to_batch_height,
+        ));
+    }
+
+    // 2 Collect the archived batch records up to the end of the range, and the journaled state roots
+    // within the range.
+    let (batch_records, journaled_state_roots) = {
+        // 2.1 Open the archival manager.
+        let archival_manager =
+            ArchivalManager::new(chain).map_err(ReplayError::ArchivalManagerConstructionError)?;
+        let _archival_manager = archival_manager.lock().await;
+
+        // 2.2 Collect the batch records in ascending batch height order.
+        let batch_records: Vec<BatchRecord> = _archival_manager
+            .batch_records()
+            .into_iter()
+            .take_while(|batch_record| batch_record.batch_height <= to_batch_height)
+            .collect();
+
+        // 2.3 Collect the journaled state roots.
+        let journaled_state_roots = (from_batch_height..=to_batch_height)
+            .map(|batch_height| {
+                _archival_manager
+                    .state_root_by_height(batch_height)
+                    .ok_or(ReplayError::MissingJournaledStateRoot(batch_height))
+            })
+            .collect::<Result<Vec<[u8; 32]>, ReplayError>>()?;
+
+        (batch_records, journaled_state_roots)
+    };
+
+    // 3 Make sure the archived batch records are contiguous from batch height #1 to the end of the range.
+    for (index, batch_record) in batch_records.iter().enumerate() {
+        let expected_batch_height = index as u64 + 1;
+        if batch_record.batch_height != expected_batch_height {
+            return Err(ReplayError::ArchivedBatchHeightGap(
+                expected_batch_height,
+                batch_record.batch_height,
+            ));
+        }
+    }
+    if (batch_records.len() as u64) < to_batch_height {
+        return Err(ReplayError::MissingArchivedBatchRecord(
+            batch_records.len() as u64 + 1,
+        ));
+    }
+
+    // 4 Snapshot the ledger state to restore once the replay is done.
+    let (bitcoin_sync_height, batch_height, state_root) = ledger_tip(chain)
+        .await
+        .map_err(ReplayError::LedgerTipError)?;
+    let databases =
+        SnapshotBundle::read_databases(chain).map_err(ReplayError::SnapshotReadError)?;
+    let snapshot = SnapshotBundle {
+        chain,
+        bitcoin_sync_height,
+        batch_height,
+        state_root,
+        databases,
+    };
+
+    // 5 Replay the batch records, then restore the snapshot.
+    let mismatches = replay_batch_records(
+        chain,
+        &batch_records,
+        from_batch_height,
+        &journaled_state_roots,
+    )
+    .await;
+    restore_snapshot(&snapshot)?;
+
+    // 6 Return the report.
+    Ok(ReplayReport {
+        from_batch_height,
+        to_batch_height,
+        mismatches: mismatches?,
+    })
+}
+
+/// Wipes the derived ledger state and re-executes the batch records, checking the ones from
+/// `from_batch_height` against the archive and the journaled state roots.
+///
+/// The managers are opened here and closed before returning.
+async fn replay_batch_records(
+    chain: Chain,
+    batch_records: &[BatchRecord],
+    from_batch_height: BatchHeight,
+    journaled_state_roots: &[[u8; 32]],
+) -> Result<Vec<ReplayMismatch>, ReplayError> {
+    // 1 Wipe the derived state and construct the execution context.
+    let (exec_ctx, utxo_set) = wipe_derived_state(chain)
+        .await
+        .map_err(ReplayError::ReindexError)?;
+
+    // 2 Replay the batch records one by one.
+    let mut mismatches = Vec::<ReplayMismatch>::new();
+    for batch_record in batch_records.iter() {
+        let batch_height = batch_record.batch_height;
+
+        // 2.1 Re-execute the batch.
+        let replayed_batch_record = replay_batch_record(&exec_ctx, &utxo_set, batch_record)
+            .await
+            .map_err(|err| ReplayError::ReplayBatchError(batch_height, err))?;
+
+        // 2.2 The batches before the range only bring the ledger up to its start.
+        if batch_height < from_batch_height {
+            continue;
+        }
+
+        // 2.3 Compare the replayed batch record with the archived one.
+        let batch_record_matches = replayed_batch_record.serialize() == batch_record.serialize();
+        if !batch_record_matches {
+            mismatches.push(ReplayMismatch::BatchRecord(batch_height));
+        }
+
+        // 2.4 Compare the resulting state root with the journaled one.
+        let replayed_state_root = {
+            let _exec_ctx = exec_ctx.lock().await;
+            let _coin_manager = _exec_ctx.coin_manager.lock().await;
+            _coin_manager.account_balances_state_root()
+        };
+        let journaled_state_root =
+            journaled_state_roots[(batch_height - from_batch_height) as usize];
+        let state_root_matches = replayed_state_root == journaled_state_root;
+        if !state_root_matches {
+            mismatches.push(ReplayMismatch::StateRoot {
+                batch_height,
+                journaled: journaled_state_root,
+                replayed: replayed_state_root,
+            });
+        }
+
+        match batch_record_matches && state_root_matches {
+            true => println!("Batch height #{} matches.", batch_height),
+            false => println!(
+                "{}",
+                format!("Batch height #{} diverges.", batch_height).red()
+            ),
+        }
+    }
+
+    // 3 Return the mismatches.
+    Ok(mismatches)
+}
+
+/// Restores the ledger state from the snapshot taken before the replay, and clears the marker left
+/// by wiping the derived state.
+fn restore_snapshot(snapshot: &SnapshotBundle) -> Result<(), ReplayError> {
+    // 1 Replace the databases with the snapshot ones.
+    SnapshotBundle::erase_databases(snapshot.chain);
+    snapshot
+        .write_databases()
+        .map_err(ReplayError::SnapshotRestoreError)?;
+
+    // 2 Clear the marker.
+    clear_reindex_in_progress(snapshot.chain).map_err(ReplayError::ReindexError)
+}
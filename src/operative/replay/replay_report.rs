@@ -0,0 +1,93 @@
+use serde_json::{Map, Value};
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// A divergence between a replayed batch and the journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayMismatch {
+    /// The replayed batch record differs from the archived one.
+    BatchRecord(BatchHeight),
+    /// The replayed state root differs from the journaled one.
+    StateRoot {
+        batch_height: BatchHeight,
+        journaled: [u8; 32],
+        replayed: [u8; 32],
+    },
+}
+
+impl ReplayMismatch {
+    /// Returns the batch height the mismatch occurred at.
+    pub fn batch_height(&self) -> BatchHeight {
+        match self {
+            ReplayMismatch::BatchRecord(batch_height) => *batch_height,
+            ReplayMismatch::StateRoot { batch_height, .. } => *batch_height,
+        }
+    }
+
+    /// Returns the mismatch as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height().into()),
+        );
+        match self {
+            ReplayMismatch::BatchRecord(_) => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("batch_record".to_string()),
+                );
+            }
+            ReplayMismatch::StateRoot {
+                journaled,
+                replayed,
+                ..
+            } => {
+                obj.insert("kind".to_string(), Value::String("state_root".to_string()));
+                obj.insert(
+                    "journaled".to_string(),
+                    Value::String(hex::encode(journaled)),
+                );
+                obj.insert("replayed".to_string(), Value::String(hex::encode(replayed)));
+            }
+        }
+        Value::Object(obj)
+    }
+}
+
+/// The outcome of replaying a range of archived batch records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// The first replayed batch height.
+    pub from_batch_height: BatchHeight,
+    /// The last replayed batch height.
+    pub to_batch_height: BatchHeight,
+    /// The divergences found, in ascending batch height order.
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+impl ReplayReport {
+    /// Returns whether every replayed batch matched the journal.
+    pub fn matches(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Returns the report as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "from_batch_height".to_string(),
+            Value::Number(self.from_batch_height.into()),
+        );
+        obj.insert(
+            "to_batch_height".to_string(),
+            Value::Number(self.to_batch_height.into()),
+        );
+        obj.insert(
+            "mismatches".to_string(),
+            Value::Array(self.mismatches.iter().map(|m| m.json()).collect()),
+        );
+        Value::Object(obj)
+    }
+}
@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// Chain type.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Chain {
     // For local tests (./tests/) involving db operations.
     Testbed,
@@ -9,6 +11,27 @@ pub enum Chain {
     Mainnet,
 }
 
+impl Chain {
+    /// Returns the bytecode of the chain.
+    pub fn bytecode(&self) -> u8 {
+        match self {
+            Chain::Testbed => 0x00,
+            Chain::Signet => 0x01,
+            Chain::Mainnet => 0x02,
+        }
+    }
+
+    /// Returns the chain from its bytecode.
+    pub fn from_bytecode(bytecode: u8) -> Option<Self> {
+        match bytecode {
+            0x00 => Some(Chain::Testbed),
+            0x01 => Some(Chain::Signet),
+            0x02 => Some(Chain::Mainnet),
+            _ => None,
+        }
+    }
+}
+
 impl ToString for Chain {
     fn to_string(&self) -> String {
         match self {
@@ -3,6 +3,7 @@
 pub enum ResourceMode {
     Pruned,
     Archival,
+    Light,
 }
 
 impl ToString for ResourceMode {
@@ -10,6 +11,7 @@ impl ToString for ResourceMode {
         match self {
             ResourceMode::Pruned => "pruned".to_string(),
             ResourceMode::Archival => "archival".to_string(),
+            ResourceMode::Light => "light".to_string(),
         }
     }
 }
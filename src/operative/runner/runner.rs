@@ -1,14 +1,40 @@
+use crate::communicative::api_auth::api_auth::{api_auth_required_from_env, ApiAuth, API_AUTH};
+use crate::communicative::discovery::announcement::PeerAnnouncementKind;
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::gossip::contract_notice_index::{
+    ContractNoticeIndex, CONTRACT_NOTICE_INDEX,
+};
+use crate::communicative::gossip::delta_gossip_pool::{DeltaGossipPool, DELTA_GOSSIP_POOL};
+#[cfg(feature = "grpc")]
+use crate::communicative::grpc::cube_query::CubeQuery;
+#[cfg(feature = "grpc")]
+use crate::communicative::grpc::grpc_server::{grpc_port_from_env, run_grpc_server};
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::handshake::handshake::register_with_engine;
+use crate::communicative::handshake::operator_sessions::{OperatorSessions, OPERATOR_SESSIONS};
 use crate::communicative::nns;
+use crate::communicative::nns::bunker::bunker_uri::BunkerURI;
+use crate::communicative::nns::bunker::remote_signer::RemoteSigner;
 use crate::communicative::nns::client::NNSClient;
+use crate::communicative::nns::relay_list::{RelayList, RELAY_LIST};
+use crate::communicative::outbox::outbox::{Outbox, OUTBOX};
+use crate::communicative::peer::access_list::{PeerAccessList, PEER_ACCESS_LIST};
 use crate::communicative::peer::manager::engine_key;
 use crate::communicative::peer::peer::Peer;
+use crate::communicative::peer::peer::PeerConnection;
 use crate::communicative::peer::peer::PeerKind;
+use crate::communicative::peer::peer::PeerTransport;
 use crate::communicative::peer::peer::PEER;
+use crate::communicative::rate_limiter::rate_limit_config::RateLimitConfig;
+use crate::communicative::rate_limiter::rate_limiter::{RateLimiter, RATE_LIMITER};
 use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::validate_rpc;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cancel::shutdown_rpc_calls;
 use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
 use crate::communicative::tcp::server as tcp_server;
 use crate::communicative::tcp::tcp::open_port;
 use crate::communicative::tcp::tcp::port_number;
+use crate::communicative::webhook::webhooks::{Webhooks, WEBHOOKS};
+use crate::executive::exec_ctx::exec_ctx::ExecCtx;
 use crate::inscriptive::archival_manager::archival_manager::ArchivalManager;
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::inscriptive::coin_manager::coin_manager::CoinManager;
@@ -27,23 +53,58 @@ use crate::inscriptive::state_manager::state_manager::StateManager;
 use crate::inscriptive::state_manager::state_manager::STATE_MANAGER;
 use crate::inscriptive::sync_manager::sync_manager::SyncManager;
 use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::inscriptive::undo_journal::undo_journal::{UndoJournal, UNDO_JOURNAL};
 use crate::inscriptive::utxo_set::utxo_set::UTXOSet;
 use crate::inscriptive::utxo_set::utxo_set::UTXO_SET;
+use crate::operative::admin::admin_ctx::AdminCtx;
+use crate::operative::admin::admin_server;
+use crate::operative::backfill::backfill::{backfill, backfill_target};
 use crate::operative::cli::cli::run_engine_cli;
+use crate::operative::cli::cli::run_light_cli;
 use crate::operative::cli::cli::run_node_cli;
 use crate::operative::cli::commands::common_commands::runexplorer;
+use crate::operative::recovery::recovery::{recover, RecoveryOutcome};
 use crate::operative::run_args::{
     chain::Chain, operating_kind::OperatingKind, resource_mode::ResourceMode, sync_mode::SyncMode,
 };
+use crate::operative::tasks::chain_health::chain_health::{
+    chain_health_background_task, ChainHealth, CHAIN_HEALTH,
+};
+use crate::operative::tasks::chain_health::chain_health_config::ChainHealthConfig;
 use crate::operative::tasks::chain_sync::chain_sync::ChainSync;
+use crate::operative::tasks::chain_sync::compact_block_filters::CompactBlockFilters;
+use crate::operative::tasks::contract_notice::contract_notice::contract_notice_background_task;
+use crate::operative::tasks::delta_gossip::delta_gossip::delta_gossip_background_task;
 use crate::operative::tasks::engine_session::engine_session::engine_batch_builder_background_task;
+use crate::operative::tasks::engine_session::exec_scheduler::exec_scheduler::{
+    ExecScheduler, EXEC_SCHEDULER,
+};
 use crate::operative::tasks::engine_session::session_pool::session_pool::SessionPool;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use crate::operative::tasks::engine_session::work_queue::work_queue::{WorkQueue, WORK_QUEUE};
+use crate::operative::tasks::fee_estimator::fee_estimator::{FeeEstimator, FEE_ESTIMATOR};
+use crate::operative::tasks::fee_estimator::fee_estimator_config::FeeEstimatorConfig;
 use crate::operative::tasks::in_flight_batch_sync::in_flight_batch_sync::in_flight_batch_sync_background_task;
+use crate::operative::tasks::liveness::heartbeat::heartbeat_background_task;
+use crate::operative::tasks::liveness::liveness_monitor::liveness_monitor_background_task;
+use crate::operative::tasks::mempool::mempool::{Mempool, MEMPOOL};
+use crate::operative::tasks::mempool::mempool_forwarder::mempool_forwarder_background_task;
+use crate::operative::tasks::mempool_watch::mempool_watch::{
+    mempool_watch_background_task, MempoolWatch, MEMPOOL_WATCH,
+};
+use crate::operative::tasks::outbox_delivery::outbox_delivery::outbox_delivery_background_task;
+use crate::operative::tasks::peer_announcement::peer_announcement::peer_announcement_background_task;
+use crate::operative::tasks::rebroadcast::rebroadcast_queue::{
+    rebroadcast_background_task, RebroadcastQueue, REBROADCAST_QUEUE,
+};
+use crate::operative::tasks::telemetry::telemetry::telemetry_background_task;
+use crate::operative::tasks::telemetry::telemetry_config::TelemetryConfig;
+use crate::operative::tasks::webhook_delivery::balance_webhooks::balance_webhooks_background_task;
+use crate::operative::tasks::webhook_delivery::webhook_delivery::webhook_delivery_background_task;
 use crate::transmutative::key::KeyHolder;
 use colored::Colorize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Whether MuSig2-based interactive lifts are enabled. Set to false for now since it's not supported yet.
 const V2_LIFT_ENABLED: bool = false;
@@ -57,9 +118,64 @@ pub async fn run(
     sync_mode: SyncMode,
     key_holder: KeyHolder,
 ) {
-    // 1 Wrap KeyHolder
+    // 1 Back the Nostr identity by a NIP-46 remote signer, if configured, and wrap KeyHolder.
+    let mut key_holder = key_holder;
+    if let Ok(bunker_uri) = std::env::var("CUBE_BUNKER_URI") {
+        // 1.1 Parse the bunker URI.
+        let bunker_uri = match BunkerURI::parse(&bunker_uri) {
+            Some(bunker_uri) => bunker_uri,
+            None => {
+                println!("{}", "Invalid CUBE_BUNKER_URI.".red());
+                return;
+            }
+        };
+
+        // 1.2 Connect to the remote signer.
+        let timeout = std::env::var("CUBE_BUNKER_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(30);
+        let remote_signer =
+            match RemoteSigner::connect(&bunker_uri, Duration::from_secs(timeout)).await {
+                Ok(remote_signer) => remote_signer,
+                Err(err) => {
+                    println!(
+                        "{} {}",
+                        "Error connecting to the remote signer: ".red(),
+                        err
+                    );
+                    return;
+                }
+            };
+
+        // 1.3 Peers resolve this node by its npub, so the remote signer must sign for the same key.
+        if remote_signer.user_public_key().to_bytes() != key_holder.secp_public_key_bytes() {
+            println!(
+                "{}",
+                "The remote signer signs for a different key than the nsec.".red()
+            );
+            return;
+        }
+
+        // 1.4 Sign Nostr events remotely.
+        println!(
+            "{}",
+            format!(
+                "Signing Nostr events with the remote signer for {}.",
+                key_holder.npub()
+            )
+            .green()
+        );
+        key_holder.set_remote_signer(Arc::new(remote_signer));
+    }
     let key_holder = Arc::new(key_holder);
 
+    // 1.b Light mode keeps no ledger state and needs no Bitcoin RPC; it verifies engine-signed proofs instead.
+    if resource_mode == ResourceMode::Light {
+        run_light(chain, operating_kind, &key_holder).await;
+        return;
+    }
+
     // 2 Validate Bitcoin RPC.
     if let Err(err) = validate_rpc(&rpc_holder, chain) {
         println!("{} {}", "Bitcoin RPC Error: ".red(), err);
@@ -76,6 +192,78 @@ pub async fn run(
         }
     }
 
+    // 3.b Run the startup recovery sequence before opening the managers.
+    match recover(chain, resource_mode).await {
+        Ok(RecoveryOutcome::Clean) => {}
+        Ok(RecoveryOutcome::Repaired(batch_height)) => {
+            println!(
+                "{}",
+                format!(
+                    "Recovered from an inconsistent ledger. Reindexed up to batch height #{}.",
+                    batch_height
+                )
+                .yellow()
+            );
+        }
+        Err(err) => {
+            println!("{} {:?}", "Startup recovery error: ".red(), err);
+            println!("{}", err.remedy());
+            return;
+        }
+    }
+
+    // 3.c Backfill the batch archive of a node that was switched from pruned to archival resource mode.
+    if resource_mode == ResourceMode::Archival && operating_kind == OperatingKind::Node {
+        // 3.c.1 Check whether the archive needs a backfill.
+        let target_batch_height = match backfill_target(chain).await {
+            Ok(target_batch_height) => target_batch_height,
+            Err(err) => {
+                println!("{} {:?}", "Backfill error: ".red(), err);
+                return;
+            }
+        };
+
+        if let Some(target_batch_height) = target_batch_height {
+            println!(
+                "{}",
+                format!(
+                    "Backfilling the batch archive up to batch height #{}.",
+                    target_batch_height
+                )
+                .yellow()
+            );
+
+            // 3.c.2 Connect to the engine, which serves the batch containers.
+            let nns_client = NNSClient::new(&key_holder).await;
+            let engine_conn = loop {
+                match Peer::connect(chain, PeerKind::Engine, engine_key(chain), &nns_client).await {
+                    Ok(connection) => break connection,
+                    Err(_) => {
+                        println!("{}", "Failed to connect. Re-trying in 5..".red());
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+            };
+
+            // 3.c.3 Replay the historical batches found on-chain into the archive.
+            match backfill(chain, &rpc_holder, &engine_conn, target_batch_height).await {
+                Ok(replayed_batch_height) => println!(
+                    "{}",
+                    format!(
+                        "Backfill complete. Replayed up to batch height #{}.",
+                        replayed_batch_height
+                    )
+                    .green()
+                ),
+                Err(err) => {
+                    println!("{} {:?}", "Backfill error: ".red(), err);
+                    return;
+                }
+            }
+        }
+    }
+
     // 4 Get the engine key and self account key.
     let (engine_key, self_account_key) = (engine_key(chain), key_holder.secp_public_key_bytes());
 
@@ -110,7 +298,7 @@ pub async fn run(
                 return;
             }
         },
-        ResourceMode::Pruned => None,
+        ResourceMode::Pruned | ResourceMode::Light => None,
     };
 
     // 7 Initialize utxo set.
@@ -122,6 +310,16 @@ pub async fn run(
         }
     };
 
+    // 7.b Initialize undo journal.
+    let undo_journal: UNDO_JOURNAL =
+        match UndoJournal::new(chain, UndoJournal::rollback_depth_from_env()) {
+            Ok(undo_journal) => undo_journal,
+            Err(err) => {
+                println!("{} {:?}", "Error initializing undo journal: ".red(), err);
+                return;
+            }
+        };
+
     // 8 Initialize graveyard.
     let graveyard: GRAVEYARD = match Graveyard::new(chain) {
         Ok(graveyard) => graveyard,
@@ -180,8 +378,15 @@ pub async fn run(
         }
     };
 
-    // 10.e Initialize NNS client.
-    let nns_client = NNSClient::new(&key_holder).await;
+    // 10.e Initialize relay list & NNS client.
+    let relay_list: RELAY_LIST = match RelayList::new(chain) {
+        Ok(relay_list) => relay_list,
+        Err(err) => {
+            println!("{} {}", "Error initializing relay list: ".red(), err);
+            return;
+        }
+    };
+    let nns_client = NNSClient::with_relay_list(&key_holder, &relay_list).await;
 
     // 10.d For node mode, pre-connect to engine so chain sync can pull batch containers.
     let pre_sync_engine_conn: Option<PEER> = match operating_kind {
@@ -214,6 +419,14 @@ pub async fn run(
         let archival_manager = archival_manager.clone();
         let sync_manager = Arc::clone(&sync_manager);
         let utxo_set = Arc::clone(&utxo_set);
+        let undo_journal = Arc::clone(&undo_journal);
+        // Only nodes can skip blocks by their compact block filters; the engine tracks every lift.
+        let compact_block_filters = match operating_kind {
+            OperatingKind::Node => {
+                CompactBlockFilters::from_env(engine_key, self_account_key, V2_LIFT_ENABLED)
+            }
+            OperatingKind::Engine => None,
+        };
         tokio::spawn(async move {
             let _ = sync_manager
                 .spawn_background_chain_syncer(
@@ -230,6 +443,8 @@ pub async fn run(
                     &params_manager,
                     &archival_manager,
                     &utxo_set,
+                    &undo_journal,
+                    &compact_block_filters,
                 )
                 .await;
         });
@@ -245,6 +460,86 @@ pub async fn run(
         println!("{}", "Syncing complete.");
     }
 
+    // 9.b Spawn the telemetry reporter if opted into.
+    if let Some(telemetry_config) = TelemetryConfig::from_env() {
+        println!(
+            "{}",
+            format!("Telemetry enabled, reporting to {}.", telemetry_config.endpoint).yellow()
+        );
+
+        let sync_manager = Arc::clone(&sync_manager);
+        tokio::spawn(async move {
+            telemetry_background_task(
+                telemetry_config,
+                chain,
+                operating_kind,
+                resource_mode,
+                &sync_manager,
+            )
+            .await;
+        });
+    }
+
+    // 9.c Watch the chain seen through the Bitcoin node for a stale tip or a split in the background.
+    let chain_health_config = ChainHealthConfig::from_env();
+    let chain_health: CHAIN_HEALTH = ChainHealth::new(chain_health_config.pause_settlement);
+    {
+        let chain_health = Arc::clone(&chain_health);
+        let rpc_holder = rpc_holder.clone();
+        tokio::spawn(async move {
+            chain_health_background_task(&chain_health, &rpc_holder, chain_health_config).await;
+        });
+    }
+
+    // 9.d Initialize the webhooks, and deliver them and watch the followed balances in the background.
+    let webhooks: WEBHOOKS = match Webhooks::new(chain) {
+        Ok(webhooks) => webhooks,
+        Err(err) => {
+            println!("{} {:?}", "Error initializing webhooks: ".red(), err);
+            return;
+        }
+    };
+    {
+        let webhooks = Arc::clone(&webhooks);
+        tokio::spawn(async move {
+            webhook_delivery_background_task(&webhooks).await;
+        });
+    }
+    {
+        let webhooks = Arc::clone(&webhooks);
+        let coin_manager = Arc::clone(&coin_manager);
+        tokio::spawn(async move {
+            balance_webhooks_background_task(&webhooks, &coin_manager).await;
+        });
+    }
+
+    // 9.e Initialize the identities allowed on the query APIs.
+    let api_auth: API_AUTH = match ApiAuth::new(chain, api_auth_required_from_env()) {
+        Ok(api_auth) => api_auth,
+        Err(err) => {
+            println!("{} {:?}", "Error initializing API auth: ".red(), err);
+            return;
+        }
+    };
+
+    // 9.f Optional gRPC query and subscription service: CUBE_GRPC_PORT (grpc feature only).
+    #[cfg(feature = "grpc")]
+    if let Some(port) = grpc_port_from_env() {
+        let query = CubeQuery::new(
+            Arc::clone(&sync_manager),
+            Arc::clone(&coin_manager),
+            Arc::clone(&state_manager),
+            Arc::clone(&registery),
+            Arc::clone(&params_manager),
+            archival_manager.clone(),
+            Arc::clone(&api_auth),
+        );
+        tokio::spawn(async move {
+            run_grpc_server(port, query).await;
+        });
+        println!("{}", format!("gRPC server listening on port {}.", port).green());
+    }
+
     // 11 Operating-kind-specific initializations.
     match operating_kind {
         // 11.a Engine-specific initializations.
@@ -264,13 +559,27 @@ pub async fn run(
                 false => (),
             }
 
-            // 11.a.3 Run NNS server in the background.
+            // 11.a.3 Run NNS server and publish the engine announcement in the background.
             {
                 let nns_client = nns_client.clone();
                 let _ = tokio::spawn(async move {
                     let _ = nns::server::run(&nns_client, operating_kind).await;
                 });
             }
+            {
+                let nns_client = nns_client.clone();
+                let key_holder = Arc::clone(&key_holder);
+                tokio::spawn(async move {
+                    peer_announcement_background_task(
+                        &nns_client,
+                        &key_holder,
+                        chain,
+                        PeerAnnouncementKind::Engine,
+                        Vec::new(),
+                    )
+                    .await;
+                });
+            }
 
             // 11.a.4 Construct session pool.
             let session_pool: SESSION_POOL = SessionPool::construct(
@@ -285,8 +594,97 @@ pub async fn run(
                 &privileges_manager,
                 &params_manager,
                 archival_manager.clone(),
+                Federation::for_chain(chain),
             );
 
+            // 11.a.4.b Initialize peer access list.
+            let peer_access_list: PEER_ACCESS_LIST = match PeerAccessList::new(chain) {
+                Ok(peer_access_list) => peer_access_list,
+                Err(err) => {
+                    println!("{} {:?}", "Error initializing peer access list: ".red(), err);
+                    return;
+                }
+            };
+
+            // 11.a.4.c Initialize inbound rate limiter.
+            let rate_limiter: RATE_LIMITER = RateLimiter::new(RateLimitConfig::from_env());
+
+            // 11.a.4.d Initialize the work queue of built batches.
+            let work_queue: WORK_QUEUE = match WorkQueue::new(chain) {
+                Ok(work_queue) => work_queue,
+                Err(err) => {
+                    println!("{} {:?}", "Error initializing work queue: ".red(), err);
+                    return;
+                }
+            };
+
+            // 11.a.4.d.a Initialize the queue of broadcast transactions and rebroadcast them until they confirm in the background.
+            let rebroadcast_queue: REBROADCAST_QUEUE = match RebroadcastQueue::new(chain) {
+                Ok(rebroadcast_queue) => rebroadcast_queue,
+                Err(err) => {
+                    println!("{} {:?}", "Error initializing rebroadcast queue: ".red(), err);
+                    return;
+                }
+            };
+            {
+                let rebroadcast_queue = Arc::clone(&rebroadcast_queue);
+                let rpc_holder = rpc_holder.clone();
+                tokio::spawn(async move {
+                    rebroadcast_background_task(&rebroadcast_queue, &rpc_holder).await;
+                });
+            }
+
+            // 11.a.4.e Initialize the execution scheduler for inbound entries.
+            let exec_scheduler: EXEC_SCHEDULER = ExecScheduler::new(&registery, &flame_manager);
+
+            // 11.a.4.e.a Initialize the fee estimator of the engine's on-chain transactions.
+            let fee_estimator: FEE_ESTIMATOR = FeeEstimator::new(FeeEstimatorConfig::from_env());
+
+            // 11.a.4.e.b Initialize the registry of operators registered through the handshake.
+            let operator_sessions: OPERATOR_SESSIONS = OperatorSessions::new();
+
+            // 11.a.4.e.c Mark operators that stopped sending heartbeats as offline in the background.
+            {
+                let operator_sessions = Arc::clone(&operator_sessions);
+                tokio::spawn(async move {
+                    liveness_monitor_background_task(&operator_sessions).await;
+                });
+            }
+
+            // 11.a.4.f Run the admin socket in the background.
+            let admin_ctx = {
+                let exec_ctx = {
+                    let _session_pool = session_pool.lock().await;
+                    Arc::clone(&_session_pool.exec_ctx)
+                };
+                AdminCtx {
+                    chain,
+                    operating_kind,
+                    started_at: Instant::now(),
+                    sync_manager: Arc::clone(&sync_manager),
+                    exec_ctx,
+                    session_pool: Some(Arc::clone(&session_pool)),
+                    peer_access_list: Some(Arc::clone(&peer_access_list)),
+                    work_queue: Some(Arc::clone(&work_queue)),
+                    exec_scheduler: Some(Arc::clone(&exec_scheduler)),
+                    operator_sessions: Some(Arc::clone(&operator_sessions)),
+                    chain_health: Arc::clone(&chain_health),
+                    fee_estimator: Some(Arc::clone(&fee_estimator)),
+                    rebroadcast_queue: Some(Arc::clone(&rebroadcast_queue)),
+                    relay_list: Arc::clone(&relay_list),
+                    nns_client: nns_client.clone(),
+                    contract_notice_index: None,
+                    webhooks: Arc::clone(&webhooks),
+                    api_auth: Arc::clone(&api_auth),
+                }
+            };
+            {
+                let admin_ctx = admin_ctx.clone();
+                tokio::spawn(async move {
+                    admin_server::run(admin_ctx).await;
+                });
+            }
+
             // 11.a.5 Spawn engine batch builder background task.
             {
                 let session_pool = Arc::clone(&session_pool);
@@ -303,6 +701,12 @@ pub async fn run(
                 let params_manager = Arc::clone(&params_manager);
                 let archival_manager = archival_manager.clone();
                 let key_holder = Arc::clone(&key_holder);
+                let work_queue = Arc::clone(&work_queue);
+                let operator_sessions = Arc::clone(&operator_sessions);
+                let chain_health = Arc::clone(&chain_health);
+                let fee_estimator = Arc::clone(&fee_estimator);
+                let rebroadcast_queue = Arc::clone(&rebroadcast_queue);
+                let webhooks = Arc::clone(&webhooks);
 
                 let _ = tokio::spawn(async move {
                     let _ = engine_batch_builder_background_task(
@@ -310,6 +714,12 @@ pub async fn run(
                         &sync_manager,
                         &rpc_holder,
                         &key_holder,
+                        &work_queue,
+                        &operator_sessions,
+                        &chain_health,
+                        &fee_estimator,
+                        &rebroadcast_queue,
+                        &webhooks,
                         engine_key,
                         &utxo_set,
                         &registery,
@@ -330,8 +740,22 @@ pub async fn run(
                 let keys = Arc::clone(&key_holder);
                 let chain = chain.clone();
                 let session_pool = Arc::clone(&session_pool);
+                let peer_access_list = Arc::clone(&peer_access_list);
+                let rate_limiter = Arc::clone(&rate_limiter);
+                let exec_scheduler = Arc::clone(&exec_scheduler);
+                let operator_sessions = Arc::clone(&operator_sessions);
                 let _ = tokio::spawn(async move {
-                    tcp_server::server::run(operating_kind, chain, keys, &session_pool).await;
+                    tcp_server::server::run(
+                        operating_kind,
+                        chain,
+                        keys,
+                        &session_pool,
+                        &peer_access_list,
+                        &rate_limiter,
+                        &exec_scheduler,
+                        &operator_sessions,
+                    )
+                    .await;
                 });
             }
 
@@ -346,6 +770,8 @@ pub async fn run(
                 &privileges_manager,
                 &coin_manager,
                 &flame_manager,
+                &api_auth,
+                &admin_ctx,
             )
             .await;
 
@@ -360,6 +786,8 @@ pub async fn run(
                 &flame_manager,
                 &key_holder,
                 archival_manager.clone(),
+                &peer_access_list,
+                &api_auth,
             )
             .await;
         }
@@ -375,9 +803,192 @@ pub async fn run(
             let engine_conn: PEER =
                 pre_sync_engine_conn.expect("Node mode must pre-connect to engine");
 
+            // 11.b.2.a Federation coordinators talk to the engine over a Noise session for lower latency.
+            let federation = Federation::for_chain(chain);
+            if let Some(federation) = &federation {
+                if federation.is_coordinator(self_account_key) {
+                    if let Err(err) = engine_conn.select_transport(PeerTransport::Noise).await {
+                        println!(
+                            "{}",
+                            format!(
+                                "Failed to establish a Noise session with the engine: {:?}. Falling back to envelopes.",
+                                err
+                            )
+                            .yellow()
+                        );
+                    }
+                }
+            }
+
+            // 11.b.2.b Register with the engine by announcing the node's capabilities.
+            // 11.b.2.b.1 Resolve the capabilities.
+            let capabilities = {
+                let mut capabilities = vec![Capability::Mempool];
+                if sync_mode == SyncMode::InFlight {
+                    capabilities.push(Capability::InFlightSync);
+                }
+                if let Some(federation) = &federation {
+                    if federation.is_coordinator(self_account_key) {
+                        capabilities.push(Capability::DeltaCosign);
+                    }
+                }
+                if resource_mode == ResourceMode::Archival {
+                    capabilities.push(Capability::Archival);
+                }
+                capabilities
+            };
+
+            // 11.b.2.b.2 Announce the node until the engine issues the session params.
+            let session_params = loop {
+                let cube_batch_sync_height = {
+                    let _sync_manager = sync_manager.lock().await;
+                    _sync_manager.cube_batch_sync_height_tip()
+                };
+                match register_with_engine(
+                    &engine_conn,
+                    &key_holder,
+                    engine_key,
+                    capabilities.clone(),
+                    cube_batch_sync_height,
+                )
+                .await
+                {
+                    Ok(session_params) => {
+                        println!(
+                            "{} {}",
+                            "Registered with the engine:".green(),
+                            session_params.json()
+                        );
+                        break session_params;
+                    }
+                    Err(err) if err.is_retryable() => {
+                        println!(
+                            "{}",
+                            format!(
+                                "Failed to register with the engine: {:?}. Re-trying in 5..",
+                                err
+                            )
+                            .red()
+                        );
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    Err(err) => {
+                        eprintln!(
+                            "{}",
+                            format!("Engine refused the registration: {:?}", err).red()
+                        );
+                        return;
+                    }
+                }
+            };
+
+            // 11.b.2.c Construct the outbox and deliver its messages to the engine in the background.
+            let outbox: OUTBOX = match Outbox::new(chain) {
+                Ok(outbox) => outbox,
+                Err(err) => {
+                    println!("{} {:?}", "Error initializing outbox: ".red(), err);
+                    return;
+                }
+            };
+            {
+                let outbox = Arc::clone(&outbox);
+                let engine_conn = Arc::clone(&engine_conn);
+                tokio::spawn(async move {
+                    outbox_delivery_background_task(&outbox, &engine_conn).await;
+                });
+            }
+
+            // 11.b.2.d Send heartbeats to the engine in the background.
+            {
+                let engine_conn = Arc::clone(&engine_conn);
+                let key_holder = Arc::clone(&key_holder);
+                let capabilities = capabilities.clone();
+                let sync_manager = Arc::clone(&sync_manager);
+                let archival_manager = archival_manager.clone();
+                let outbox = Arc::clone(&outbox);
+                tokio::spawn(async move {
+                    heartbeat_background_task(
+                        &engine_conn,
+                        &key_holder,
+                        engine_key,
+                        session_params,
+                        capabilities,
+                        &sync_manager,
+                        &archival_manager,
+                        &outbox,
+                    )
+                    .await;
+                });
+            }
+
+            // 11.b.2.e Index the contract registration notices broadcast by coordinators in the background.
+            let contract_notice_index: Option<CONTRACT_NOTICE_INDEX> = match &federation {
+                Some(federation) => {
+                    let contract_notice_index =
+                        match ContractNoticeIndex::new(chain, federation.clone()) {
+                            Ok(contract_notice_index) => contract_notice_index,
+                            Err(err) => {
+                                println!(
+                                    "{} {:?}",
+                                    "Error initializing contract notice index: ".red(),
+                                    err
+                                );
+                                return;
+                            }
+                        };
+                    {
+                        let nns_client = nns_client.clone();
+                        let contract_notice_index = Arc::clone(&contract_notice_index);
+                        tokio::spawn(async move {
+                            contract_notice_background_task(
+                                &nns_client,
+                                chain,
+                                &contract_notice_index,
+                            )
+                            .await;
+                        });
+                    }
+                    Some(contract_notice_index)
+                }
+                None => None,
+            };
+
             // 11.b.3 Run the in-flight batch syncer in the background.
+            if let Some(federation) = &federation {
+                if federation.is_coordinator(self_account_key) && sync_mode != SyncMode::InFlight {
+                    eprintln!(
+                        "{}",
+                        "Federation coordinators must sync in-flight to co-sign deltas.".yellow()
+                    );
+                }
+            }
             if sync_mode == SyncMode::InFlight {
+                // 11.b.3.a Gossip the applied deltas with other nodes in the background.
+                let delta_gossip_pool: Option<DELTA_GOSSIP_POOL> = match &federation {
+                    Some(federation) => {
+                        let delta_gossip_pool = DeltaGossipPool::new(federation.clone());
+                        {
+                            let nns_client = nns_client.clone();
+                            let delta_gossip_pool = Arc::clone(&delta_gossip_pool);
+                            tokio::spawn(async move {
+                                delta_gossip_background_task(
+                                    &nns_client,
+                                    chain,
+                                    &delta_gossip_pool,
+                                )
+                                .await;
+                            });
+                        }
+                        Some(delta_gossip_pool)
+                    }
+                    None => None,
+                };
+
                 let engine_conn = Arc::clone(&engine_conn);
+                let federation = federation.clone();
+                let contract_notice_index = contract_notice_index.clone();
+                let outbox = Arc::clone(&outbox);
+                let key_holder = Arc::clone(&key_holder);
                 let sync_manager = Arc::clone(&sync_manager);
                 let utxo_set = Arc::clone(&utxo_set);
                 let registery = Arc::clone(&registery);
@@ -392,6 +1003,11 @@ pub async fn run(
                 tokio::spawn(async move {
                     in_flight_batch_sync_background_task(
                         &engine_conn,
+                        &federation,
+                        &delta_gossip_pool,
+                        &contract_notice_index,
+                        &outbox,
+                        &key_holder,
                         &sync_manager,
                         engine_key,
                         &utxo_set,
@@ -408,6 +1024,85 @@ pub async fn run(
                 });
             }
 
+            // 11.b.3.b Run the admin socket in the background.
+            let admin_ctx = {
+                let exec_ctx = ExecCtx::construct(
+                    engine_key,
+                    Arc::clone(&sync_manager),
+                    Arc::clone(&utxo_set),
+                    Arc::clone(&registery),
+                    Arc::clone(&graveyard),
+                    Arc::clone(&coin_manager),
+                    Arc::clone(&flame_manager),
+                    Arc::clone(&state_manager),
+                    Arc::clone(&privileges_manager),
+                    Arc::clone(&params_manager),
+                    archival_manager.clone(),
+                );
+                AdminCtx {
+                    chain,
+                    operating_kind,
+                    started_at: Instant::now(),
+                    sync_manager: Arc::clone(&sync_manager),
+                    exec_ctx,
+                    session_pool: None,
+                    peer_access_list: None,
+                    work_queue: None,
+                    exec_scheduler: None,
+                    operator_sessions: None,
+                    chain_health: Arc::clone(&chain_health),
+                    fee_estimator: None,
+                    rebroadcast_queue: None,
+                    relay_list: Arc::clone(&relay_list),
+                    nns_client: nns_client.clone(),
+                    contract_notice_index: contract_notice_index.clone(),
+                    webhooks: Arc::clone(&webhooks),
+                    api_auth: Arc::clone(&api_auth),
+                }
+            };
+            {
+                let admin_ctx = admin_ctx.clone();
+                tokio::spawn(async move {
+                    admin_server::run(admin_ctx).await;
+                });
+            }
+
+            // 11.b.3.c Construct the mempool and forward its entries to the engine in the background.
+            let mempool: MEMPOOL = Mempool::new(
+                &sync_manager,
+                &registery,
+                &graveyard,
+                &coin_manager,
+                &flame_manager,
+                &params_manager,
+            );
+            {
+                let mempool = Arc::clone(&mempool);
+                let engine_conn = Arc::clone(&engine_conn);
+                tokio::spawn(async move {
+                    mempool_forwarder_background_task(&mempool, &engine_conn).await;
+                });
+            }
+
+            // 11.b.3.d Watch the Bitcoin mempool for unconfirmed deposits and commitments in the background.
+            let mempool_watch: MEMPOOL_WATCH =
+                MempoolWatch::new(engine_key, self_account_key, V2_LIFT_ENABLED);
+            {
+                let mempool_watch = Arc::clone(&mempool_watch);
+                let rpc_holder = rpc_holder.clone();
+                let sync_manager = Arc::clone(&sync_manager);
+                let webhooks = Arc::clone(&webhooks);
+                tokio::spawn(async move {
+                    mempool_watch_background_task(
+                        &mempool_watch,
+                        &rpc_holder,
+                        &sync_manager,
+                        &webhooks,
+                    )
+                    .await;
+                });
+            }
+
             // 11.b.4 Optional HTTP explorer: CUBE_EXPLORER_PORT (non-interactive / Docker).
             maybe_start_explorer_from_env(
                 chain,
@@ -417,6 +1112,8 @@ pub async fn run(
                 &privileges_manager,
                 &coin_manager,
                 &flame_manager,
+                &api_auth,
+                &admin_ctx,
             )
             .await;
 
@@ -438,10 +1135,55 @@ pub async fn run(
                 &privileges_manager,
                 &params_manager,
                 archival_manager.clone(),
+                &mempool,
+                &mempool_watch,
+                &api_auth,
             )
             .await;
         }
     }
+
+    // 12 Cancel the in-flight Bitcoin RPC calls, so that exiting does not wait on a hung Bitcoin node.
+    shutdown_rpc_calls();
+}
+
+/// Runs the light client: connects to the engine and serves balances from engine-signed state root proofs.
+async fn run_light(chain: Chain, operating_kind: OperatingKind, key_holder: &Arc<KeyHolder>) {
+    // 1 Light mode is only available for nodes.
+    if operating_kind != OperatingKind::Node {
+        eprintln!("{}", "Light mode is only available for nodes.".red());
+        return;
+    }
+
+    // 2 Print the initializing message.
+    println!("{}", "Initializing light client.");
+
+    // 3 Get the engine key and self account key.
+    let (engine_key, self_account_key) = (engine_key(chain), key_holder.secp_public_key_bytes());
+
+    // 4 Validate the node key.
+    if self_account_key == engine_key {
+        eprintln!("{}", "Engine cannot be run in light mode.".red());
+        return;
+    }
+
+    // 5 Initialize NNS client.
+    let nns_client = NNSClient::new(key_holder).await;
+
+    // 6 Connect to the engine.
+    let engine_conn: PEER = loop {
+        match Peer::connect(chain, PeerKind::Engine, engine_key, &nns_client).await {
+            Ok(connection) => break connection,
+            Err(_) => {
+                println!("{}", "Failed to connect. Re-trying in 5..".red());
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+    };
+
+    // 7 Run the light client CLI.
+    run_light_cli(chain, engine_key, self_account_key, &engine_conn, key_holder).await;
 }
 
 /// If `CUBE_EXPLORER_PORT` is set, starts the block explorer (archival mode only).
@@ -453,6 +1195,8 @@ async fn maybe_start_explorer_from_env(
     privileges_manager: &PRIVILEGES_MANAGER,
     coin_manager: &COIN_MANAGER,
     flame_manager: &FLAME_MANAGER,
+    api_auth: &API_AUTH,
+    admin_ctx: &AdminCtx,
 ) {
     let Ok(port_str) = std::env::var("CUBE_EXPLORER_PORT") else {
         return;
@@ -490,6 +1234,8 @@ async fn maybe_start_explorer_from_env(
         Some(privileges_manager),
         coin_manager,
         flame_manager,
+        api_auth,
+        Some(admin_ctx),
     )
     .await;
 }
@@ -0,0 +1,5 @@
+pub mod scripted_chain;
+pub mod sim_node;
+pub mod sim_transport;
+pub mod simulation;
+pub mod virtual_clock;
@@ -0,0 +1,105 @@
+use bitcoin::block::{Header, Version};
+use bitcoin::hashes::Hash;
+use bitcoin::{
+    absolute, transaction, Amount, Block, BlockHash, CompactTarget, OutPoint, ScriptBuf, Sequence,
+    Transaction, TxIn, TxMerkleNode, TxOut, Witness,
+};
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// The regtest proof of work limit, carried by the scripted blocks without them being mined.
+const SCRIPTED_BLOCK_BITS: u32 = 0x207fffff;
+
+/// A scripted Bitcoin chain, extended block by block by the simulation instead of being mined.
+#[derive(Debug, Clone)]
+pub struct ScriptedChain {
+    // Height of the first block.
+    start_height: BlockHeight,
+    // Blocks from the first block to the tip.
+    blocks: Vec<Block>,
+}
+
+impl ScriptedChain {
+    /// Constructs an empty chain whose first block is at the given height.
+    pub fn new(start_height: BlockHeight) -> ScriptedChain {
+        ScriptedChain {
+            start_height,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Returns the height of the tip, if any block was appended.
+    pub fn tip_height(&self) -> Option<BlockHeight> {
+        match self.blocks.len() {
+            0 => None,
+            len => Some(self.start_height + len as u64 - 1),
+        }
+    }
+
+    /// Returns the hash of the tip, or the all-zeros hash if no block was appended.
+    pub fn tip_hash(&self) -> BlockHash {
+        match self.blocks.last() {
+            Some(block) => block.block_hash(),
+            None => BlockHash::all_zeros(),
+        }
+    }
+
+    /// Returns the block at the given height.
+    pub fn block(&self, height: BlockHeight) -> Option<&Block> {
+        let index = height.checked_sub(self.start_height)?;
+        self.blocks.get(index as usize)
+    }
+
+    /// Appends a block with the given timestamp and transactions, after a coinbase transaction
+    /// committing to its height, and returns its height.
+    pub fn append(&mut self, time_secs: u32, txdata: Vec<Transaction>) -> BlockHeight {
+        // 1 Place the coinbase transaction first.
+        let height = match self.tip_height() {
+            Some(tip_height) => tip_height + 1,
+            None => self.start_height,
+        };
+        let mut block_txdata = vec![coinbase(height)];
+        block_txdata.extend(txdata);
+
+        // 2 Build the block on top of the tip.
+        let mut block = Block {
+            header: Header {
+                version: Version::TWO,
+                prev_blockhash: self.tip_hash(),
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: time_secs,
+                bits: CompactTarget::from_consensus(SCRIPTED_BLOCK_BITS),
+                nonce: 0,
+            },
+            txdata: block_txdata,
+        };
+        if let Some(merkle_root) = block.compute_merkle_root() {
+            block.header.merkle_root = merkle_root;
+        }
+
+        // 3 Append the block.
+        self.blocks.push(block);
+
+        height
+    }
+}
+
+/// Returns a coinbase transaction committing to the block height, so that no two scripted
+/// blocks share a coinbase txid.
+fn coinbase(height: BlockHeight) -> Transaction {
+    Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf::builder().push_int(height as i64).into_script(),
+            sequence: Sequence::MAX,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: ScriptBuf::new(),
+        }],
+    }
+}
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use bitcoin::Block;
+
+/// Public key of a simulated node.
+pub type NodeKey = [u8; 32];
+
+/// Virtual time, in milliseconds.
+type VirtualTime = u64;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// The role a simulated node plays.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimRole {
+    Coordinator,
+    Operator,
+    Node,
+}
+
+impl ToString for SimRole {
+    fn to_string(&self) -> String {
+        match self {
+            SimRole::Coordinator => "coordinator".to_string(),
+            SimRole::Operator => "operator".to_string(),
+            SimRole::Node => "node".to_string(),
+        }
+    }
+}
+
+/// The view a simulated node has of the simulation while handling an event, and the messages and
+/// ticks it schedules in turn.
+pub struct SimCtx {
+    // Key of the node.
+    key: NodeKey,
+    // Role of the node.
+    role: SimRole,
+    // Virtual time of the event.
+    now: VirtualTime,
+    // Keys and roles of the other nodes.
+    peers: Vec<(NodeKey, SimRole)>,
+    // Messages sent while handling the event.
+    outbox: Vec<(NodeKey, Vec<u8>)>,
+    // Delays of the ticks scheduled while handling the event.
+    ticks: Vec<u64>,
+}
+
+impl SimCtx {
+    /// Constructs the context of an event handled by the given node.
+    pub fn new(
+        key: NodeKey,
+        role: SimRole,
+        now: VirtualTime,
+        peers: Vec<(NodeKey, SimRole)>,
+    ) -> SimCtx {
+        SimCtx {
+            key,
+            role,
+            now,
+            peers,
+            outbox: Vec::new(),
+            ticks: Vec::new(),
+        }
+    }
+
+    /// Returns the key of the node.
+    pub fn key(&self) -> NodeKey {
+        self.key
+    }
+
+    /// Returns the role of the node.
+    pub fn role(&self) -> SimRole {
+        self.role
+    }
+
+    /// Returns the virtual time of the event, in milliseconds.
+    pub fn now(&self) -> VirtualTime {
+        self.now
+    }
+
+    /// Returns the keys and roles of the other nodes.
+    pub fn peers(&self) -> &[(NodeKey, SimRole)] {
+        &self.peers
+    }
+
+    /// Sends a message to a node.
+    pub fn send(&mut self, to: NodeKey, payload: Vec<u8>) {
+        self.outbox.push((to, payload));
+    }
+
+    /// Sends a message to every other node of the given role.
+    pub fn send_to_role(&mut self, role: SimRole, payload: Vec<u8>) {
+        let keys: Vec<NodeKey> = self
+            .peers
+            .iter()
+            .filter(|(_, peer_role)| *peer_role == role)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in keys {
+            self.send(key, payload.clone());
+        }
+    }
+
+    /// Sends a message to every other node.
+    pub fn broadcast(&mut self, payload: Vec<u8>) {
+        let keys: Vec<NodeKey> = self.peers.iter().map(|(key, _)| *key).collect();
+        for key in keys {
+            self.send(key, payload.clone());
+        }
+    }
+
+    /// Schedules a tick of the node after the given delay.
+    pub fn schedule_tick(&mut self, after_ms: u64) {
+        self.ticks.push(after_ms);
+    }
+
+    /// Takes the messages sent and the ticks scheduled while handling the event.
+    pub fn take(self) -> (Vec<(NodeKey, Vec<u8>)>, Vec<u64>) {
+        (self.outbox, self.ticks)
+    }
+}
+
+/// The behaviour of a simulated node.
+///
+/// Handlers run one at a time, in virtual time order, and must not read the wall clock or draw
+/// from unseeded randomness, so that a simulation replays the same way every time.
+#[async_trait]
+pub trait SimActor: Send {
+    /// Handles a Bitcoin block reaching the node.
+    async fn on_block(&mut self, ctx: &mut SimCtx, height: BlockHeight, block: &Block);
+
+    /// Handles a message reaching the node.
+    async fn on_message(&mut self, ctx: &mut SimCtx, from: NodeKey, payload: Vec<u8>);
+
+    /// Handles a tick the node scheduled.
+    async fn on_tick(&mut self, _ctx: &mut SimCtx) {}
+
+    /// Returns the root of the consensus state the node holds, compared across the nodes.
+    async fn state_root(&self) -> [u8; 32];
+}
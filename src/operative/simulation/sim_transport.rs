@@ -0,0 +1,143 @@
+use crate::operative::simulation::sim_node::NodeKey;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashSet};
+
+/// Virtual time, in milliseconds.
+type VirtualTime = u64;
+
+/// A message in flight between two simulated nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimEnvelope {
+    pub from: NodeKey,
+    pub to: NodeKey,
+    pub payload: Vec<u8>,
+    pub sent_at: VirtualTime,
+    pub deliver_at: VirtualTime,
+}
+
+/// An in-memory transport between simulated nodes.
+///
+/// Messages are delivered after a latency, with an optional jitter and drop rate drawn from a
+/// seeded rng, in the order of their delivery time and then of their sending. The same seed and
+/// the same sends always yield the same deliveries.
+pub struct SimTransport {
+    // Base latency of every message.
+    latency_ms: u64,
+    // Upper bound of the random latency added to the base latency.
+    jitter_ms: u64,
+    // Share of the messages dropped, per mille.
+    drop_per_mille: u32,
+    // Seeded rng the jitter and the drops are drawn from.
+    rng: StdRng,
+    // Pairs of nodes that cannot reach each other, in both directions.
+    partitions: HashSet<(NodeKey, NodeKey)>,
+    // Messages in flight, by delivery time and sending sequence.
+    in_flight: BTreeMap<(VirtualTime, u64), SimEnvelope>,
+    // Sequence of the next message sent.
+    next_seq: u64,
+}
+
+impl SimTransport {
+    /// Constructs a transport with a 50 ms latency, no jitter and no drops.
+    pub fn new(seed: u64) -> SimTransport {
+        SimTransport {
+            latency_ms: 50,
+            jitter_ms: 0,
+            drop_per_mille: 0,
+            rng: StdRng::seed_from_u64(seed),
+            partitions: HashSet::new(),
+            in_flight: BTreeMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Sets the base latency and the jitter of the messages sent from now on.
+    pub fn set_latency(&mut self, latency_ms: u64, jitter_ms: u64) {
+        self.latency_ms = latency_ms;
+        self.jitter_ms = jitter_ms;
+    }
+
+    /// Sets the share of the messages sent from now on that are dropped, per mille.
+    pub fn set_drop_per_mille(&mut self, drop_per_mille: u32) {
+        self.drop_per_mille = drop_per_mille.min(1_000);
+    }
+
+    /// Cuts the link between two nodes. Messages already in flight still arrive.
+    pub fn partition(&mut self, a: NodeKey, b: NodeKey) {
+        self.partitions.insert((a, b));
+        self.partitions.insert((b, a));
+    }
+
+    /// Restores the link between two nodes.
+    pub fn heal(&mut self, a: NodeKey, b: NodeKey) {
+        self.partitions.remove(&(a, b));
+        self.partitions.remove(&(b, a));
+    }
+
+    /// Restores every link.
+    pub fn heal_all(&mut self) {
+        self.partitions.clear();
+    }
+
+    /// Returns whether the link between two nodes is cut.
+    pub fn is_partitioned(&self, a: NodeKey, b: NodeKey) -> bool {
+        self.partitions.contains(&(a, b))
+    }
+
+    /// Sends a message, and returns whether it was put in flight rather than dropped.
+    pub fn send(&mut self, from: NodeKey, to: NodeKey, payload: Vec<u8>, now: VirtualTime) -> bool {
+        // 1 Drop the message if the link is cut.
+        if self.is_partitioned(from, to) {
+            return false;
+        }
+
+        // 2 Drop the message at the drop rate.
+        if self.drop_per_mille > 0 && self.rng.gen_range(0..1_000) < self.drop_per_mille {
+            return false;
+        }
+
+        // 3 Draw the latency.
+        let jitter = match self.jitter_ms {
+            0 => 0,
+            jitter_ms => self.rng.gen_range(0..=jitter_ms),
+        };
+        let deliver_at = now + self.latency_ms + jitter;
+
+        // 4 Put the message in flight.
+        let envelope = SimEnvelope {
+            from,
+            to,
+            payload,
+            sent_at: now,
+            deliver_at,
+        };
+        self.in_flight.insert((deliver_at, self.next_seq), envelope);
+        self.next_seq += 1;
+
+        true
+    }
+
+    /// Returns the delivery time of the next message in flight.
+    pub fn next_delivery_at(&self) -> Option<VirtualTime> {
+        self.in_flight
+            .keys()
+            .next()
+            .map(|(deliver_at, _)| *deliver_at)
+    }
+
+    /// Takes the next message due by the given time.
+    pub fn pop_due(&mut self, now: VirtualTime) -> Option<SimEnvelope> {
+        match self.next_delivery_at() {
+            Some(deliver_at) if deliver_at <= now => {
+                self.in_flight.pop_first().map(|(_, envelope)| envelope)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of messages in flight.
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+}
@@ -0,0 +1,282 @@
+use crate::inscriptive::storage::storage::with_storage_namespace;
+use crate::operative::simulation::scripted_chain::ScriptedChain;
+use crate::operative::simulation::sim_node::{NodeKey, SimActor, SimCtx, SimRole};
+use crate::operative::simulation::sim_transport::SimTransport;
+use crate::operative::simulation::virtual_clock::VirtualClock;
+use bitcoin::Transaction;
+use std::collections::BTreeMap;
+
+/// Virtual time, in milliseconds.
+type VirtualTime = u64;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// An event scheduled by the simulation.
+enum SimEvent {
+    /// A scripted block to append to the chain.
+    Mine(Vec<Transaction>),
+    /// A block reaching a node.
+    Block(NodeKey, BlockHeight),
+    /// A tick a node scheduled.
+    Tick(NodeKey),
+}
+
+/// What a node handled, recorded in the trace of the simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimTraceKind {
+    Block(BlockHeight),
+    Message(NodeKey, Vec<u8>),
+    Tick,
+}
+
+/// An event a node handled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimTraceEntry {
+    pub at: VirtualTime,
+    pub node: NodeKey,
+    pub kind: SimTraceKind,
+}
+
+/// A simulated node.
+struct SimNode {
+    role: SimRole,
+    actor: Box<dyn SimActor>,
+}
+
+/// A deterministic simulation of coordinator, operator and node instances in one process.
+///
+/// The nodes share a virtual clock, see the blocks of a scripted Bitcoin chain, and talk over an
+/// in-memory transport. Events are handled one at a time in virtual time order, so the same seed
+/// and script always yield the same trace.
+pub struct Simulation {
+    // Virtual clock shared by the nodes.
+    clock: VirtualClock,
+    // Scripted Bitcoin chain.
+    chain: ScriptedChain,
+    // In-memory transport between the nodes.
+    transport: SimTransport,
+    // Nodes, by key.
+    nodes: BTreeMap<NodeKey, SimNode>,
+    // Scheduled events, by time and scheduling sequence.
+    scheduled: BTreeMap<(VirtualTime, u64), SimEvent>,
+    // Sequence of the next scheduled event.
+    next_seq: u64,
+    // Delay for a block to reach the nodes once appended.
+    block_delay_ms: u64,
+    // Events the nodes handled.
+    trace: Vec<SimTraceEntry>,
+}
+
+impl Simulation {
+    /// Constructs a simulation with the given seed and the first scripted block at the given height.
+    pub fn new(seed: u64, start_height: BlockHeight) -> Simulation {
+        Simulation {
+            clock: VirtualClock::new(0),
+            chain: ScriptedChain::new(start_height),
+            transport: SimTransport::new(seed),
+            nodes: BTreeMap::new(),
+            scheduled: BTreeMap::new(),
+            next_seq: 0,
+            block_delay_ms: 0,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Adds a node, with the actor constructed by the given closure.
+    ///
+    /// The in-memory dbs the closure opens are kept under the node key, so that each node can hold
+    /// its own managers on the Testbed chain kept in memory.
+    pub fn add_node<F>(&mut self, key: NodeKey, role: SimRole, new_actor: F)
+    where
+        F: FnOnce() -> Box<dyn SimActor>,
+    {
+        let actor = with_storage_namespace(&hex::encode(key), new_actor);
+        self.nodes.insert(key, SimNode { role, actor });
+    }
+
+    /// Returns the virtual clock.
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Returns the scripted Bitcoin chain.
+    pub fn chain(&self) -> &ScriptedChain {
+        &self.chain
+    }
+
+    /// Returns the in-memory transport, to set its latency or cut links.
+    pub fn transport_mut(&mut self) -> &mut SimTransport {
+        &mut self.transport
+    }
+
+    /// Sets the delay for a block to reach the nodes once appended.
+    pub fn set_block_delay(&mut self, block_delay_ms: u64) {
+        self.block_delay_ms = block_delay_ms;
+    }
+
+    /// Returns the events the nodes handled so far.
+    pub fn trace(&self) -> &[SimTraceEntry] {
+        &self.trace
+    }
+
+    /// Appends a block with the given transactions now, and returns its height.
+    pub fn mine_block(&mut self, txdata: Vec<Transaction>) -> BlockHeight {
+        // 1 Append the block.
+        let height = self.chain.append(self.clock.now_secs() as u32, txdata);
+
+        // 2 Have it reach every node after the block delay.
+        let deliver_at = self.clock.now() + self.block_delay_ms;
+        let keys: Vec<NodeKey> = self.nodes.keys().copied().collect();
+        for key in keys {
+            self.schedule(deliver_at, SimEvent::Block(key, height));
+        }
+
+        height
+    }
+
+    /// Scripts a block with the given transactions to be appended at the given time.
+    pub fn script_block(&mut self, at: VirtualTime, txdata: Vec<Transaction>) {
+        self.schedule(at, SimEvent::Mine(txdata));
+    }
+
+    /// Schedules a tick of the given node at the given time.
+    pub fn schedule_tick(&mut self, key: NodeKey, at: VirtualTime) {
+        self.schedule(at, SimEvent::Tick(key));
+    }
+
+    /// Schedules an event.
+    fn schedule(&mut self, at: VirtualTime, event: SimEvent) {
+        self.scheduled.insert((at, self.next_seq), event);
+        self.next_seq += 1;
+    }
+
+    /// Returns the time of the next event, scheduled or in flight.
+    pub fn next_event_at(&self) -> Option<VirtualTime> {
+        let next_scheduled_at = self.scheduled.keys().next().map(|(at, _)| *at);
+        match (next_scheduled_at, self.transport.next_delivery_at()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Handles the next event, advancing the clock to it, and returns whether there was one.
+    ///
+    /// Scheduled events come before messages due at the same time.
+    pub async fn step(&mut self) -> bool {
+        // 1 Advance the clock to the next event.
+        let at = match self.next_event_at() {
+            Some(at) => at,
+            None => return false,
+        };
+        self.clock.advance_to(at);
+
+        // 2 Handle the next scheduled event, if due.
+        if let Some(entry) = self.scheduled.first_entry() {
+            if entry.key().0 <= at {
+                match entry.remove() {
+                    SimEvent::Mine(txdata) => {
+                        self.mine_block(txdata);
+                    }
+                    SimEvent::Block(key, height) => {
+                        self.dispatch(key, SimTraceKind::Block(height)).await;
+                    }
+                    SimEvent::Tick(key) => self.dispatch(key, SimTraceKind::Tick).await,
+                }
+                return true;
+            }
+        }
+
+        // 3 Otherwise deliver the next message.
+        if let Some(envelope) = self.transport.pop_due(at) {
+            self.dispatch(
+                envelope.to,
+                SimTraceKind::Message(envelope.from, envelope.payload),
+            )
+            .await;
+        }
+
+        true
+    }
+
+    /// Handles every event up to the given time, and advances the clock to it.
+    pub async fn run_until(&mut self, time: VirtualTime) {
+        while matches!(self.next_event_at(), Some(at) if at <= time) {
+            self.step().await;
+        }
+        self.clock.advance_to(time);
+    }
+
+    /// Handles events until none is left or the step limit is reached, and returns the number of
+    /// events handled.
+    pub async fn run_until_idle(&mut self, max_steps: usize) -> usize {
+        let mut steps = 0;
+        while steps < max_steps && self.step().await {
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Has a node handle an event, then sends the messages and schedules the ticks it produced.
+    async fn dispatch(&mut self, key: NodeKey, kind: SimTraceKind) {
+        // 1 Collect the other nodes.
+        let now = self.clock.now();
+        let peers: Vec<(NodeKey, SimRole)> = self
+            .nodes
+            .iter()
+            .filter(|(peer_key, _)| **peer_key != key)
+            .map(|(peer_key, peer)| (*peer_key, peer.role))
+            .collect();
+
+        // 2 Have the node handle the event.
+        let node = match self.nodes.get_mut(&key) {
+            Some(node) => node,
+            None => return,
+        };
+        let mut ctx = SimCtx::new(key, node.role, now, peers);
+        match &kind {
+            SimTraceKind::Block(height) => {
+                if let Some(block) = self.chain.block(*height) {
+                    node.actor.on_block(&mut ctx, *height, block).await;
+                }
+            }
+            SimTraceKind::Message(from, payload) => {
+                node.actor
+                    .on_message(&mut ctx, *from, payload.clone())
+                    .await;
+            }
+            SimTraceKind::Tick => node.actor.on_tick(&mut ctx).await,
+        }
+
+        // 3 Record the event.
+        self.trace.push(SimTraceEntry {
+            at: now,
+            node: key,
+            kind,
+        });
+
+        // 4 Send the messages and schedule the ticks.
+        let (outbox, ticks) = ctx.take();
+        for (to, payload) in outbox {
+            self.transport.send(key, to, payload, now);
+        }
+        for after_ms in ticks {
+            self.schedule(now + after_ms, SimEvent::Tick(key));
+        }
+    }
+
+    /// Returns the state root each node holds.
+    pub async fn state_roots(&self) -> Vec<(NodeKey, SimRole, [u8; 32])> {
+        let mut state_roots = Vec::new();
+        for (key, node) in self.nodes.iter() {
+            state_roots.push((*key, node.role, node.actor.state_root().await));
+        }
+        state_roots
+    }
+
+    /// Returns whether every node holds the same state root.
+    pub async fn converged(&self) -> bool {
+        let state_roots = self.state_roots().await;
+        state_roots.windows(2).all(|pair| pair[0].2 == pair[1].2)
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Virtual time, in milliseconds.
+type VirtualTime = u64;
+
+/// A clock shared by the nodes of a simulation, advanced only by the simulation.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualClock {
+    now: Arc<AtomicU64>,
+}
+
+impl VirtualClock {
+    /// Constructs a clock starting at the given time.
+    pub fn new(start: VirtualTime) -> VirtualClock {
+        VirtualClock {
+            now: Arc::new(AtomicU64::new(start)),
+        }
+    }
+
+    /// Returns the current time, in milliseconds.
+    pub fn now(&self) -> VirtualTime {
+        self.now.load(Ordering::SeqCst)
+    }
+
+    /// Returns the current time, in seconds.
+    pub fn now_secs(&self) -> u64 {
+        self.now() / 1_000
+    }
+
+    /// Advances the clock by the given number of milliseconds.
+    pub fn advance(&self, ms: u64) {
+        self.now.fetch_add(ms, Ordering::SeqCst);
+    }
+
+    /// Advances the clock to the given time. The clock never goes backwards.
+    pub fn advance_to(&self, time: VirtualTime) {
+        self.now.fetch_max(time, Ordering::SeqCst);
+    }
+}
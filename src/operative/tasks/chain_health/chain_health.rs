@@ -0,0 +1,304 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::{
+    retrieve_best_block, retrieve_block_hash,
+};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::operative::tasks::chain_health::chain_health_alert::ChainHealthAlert;
+use crate::operative::tasks::chain_health::chain_health_config::ChainHealthConfig;
+use bitcoin::BlockHash;
+use chrono::Utc;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// The tip of a reference source, along with the block the Bitcoin node has at the same height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceTip {
+    // Label of the source.
+    pub source: String,
+
+    // Height of the source tip.
+    pub height: BlockHeight,
+
+    // Hash of the source tip.
+    pub block_hash: BlockHash,
+
+    // Hash of the block the Bitcoin node has at the same height, if it has one.
+    pub node_block_hash: Option<BlockHash>,
+}
+
+/// Health of the chain seen through the Bitcoin node.
+///
+/// The tip of the Bitcoin node is checked for advancing, and compared against the tips of the
+/// configured reference sources. Any condition making the chain uncertain is kept as an active
+/// alert until it clears.
+pub struct ChainHealth {
+    // Tip of the Bitcoin node, and the Unix timestamp it was first seen at.
+    node_tip: Option<(BlockHeight, BlockHash, u64)>,
+
+    // Currently active alerts.
+    alerts: Vec<ChainHealthAlert>,
+
+    // Total number of alerts raised.
+    alerts_raised: u64,
+
+    // Unix timestamp of the last check.
+    last_checked_at: Option<u64>,
+
+    // Whether the engine holds off settling batches while an alert is active.
+    pause_settlement: bool,
+}
+
+/// Guarded 'ChainHealth'.
+#[allow(non_camel_case_types)]
+pub type CHAIN_HEALTH = Arc<Mutex<ChainHealth>>;
+
+impl ChainHealth {
+    /// Constructs the chain health with no checks done yet.
+    pub fn new(pause_settlement: bool) -> CHAIN_HEALTH {
+        Arc::new(Mutex::new(Self {
+            node_tip: None,
+            alerts: Vec::new(),
+            alerts_raised: 0,
+            last_checked_at: None,
+            pause_settlement,
+        }))
+    }
+
+    /// Evaluates the outcome of a check, replacing the active alerts.
+    ///
+    /// Returns the newly raised alerts, and the alerts that cleared.
+    pub fn evaluate(
+        &mut self,
+        node_tip: Result<(BlockHeight, BlockHash), String>,
+        source_tips: &[SourceTip],
+        stale_tip_after_secs: u64,
+        max_lag_blocks: u64,
+        now: u64,
+    ) -> (Vec<ChainHealthAlert>, Vec<ChainHealthAlert>) {
+        let mut alerts = Vec::new();
+
+        match node_tip {
+            // a The Bitcoin node cannot be reached.
+            Err(err) => alerts.push(ChainHealthAlert::NodeUnreachable(err)),
+
+            // b Check the tip of the Bitcoin node.
+            Ok((height, block_hash)) => {
+                // b.1 Record when the tip last changed.
+                let since = match self.node_tip {
+                    Some((tip_height, tip_hash, since))
+                        if tip_height == height && tip_hash == block_hash =>
+                    {
+                        since
+                    }
+                    _ => now,
+                };
+                self.node_tip = Some((height, block_hash, since));
+
+                // b.2 Check the tip for staleness.
+                if now.saturating_sub(since) >= stale_tip_after_secs {
+                    alerts.push(ChainHealthAlert::StaleTip { height, since });
+                }
+
+                // b.3 Compare the tip against the reference sources.
+                for source_tip in source_tips.iter() {
+                    if let Some(node_block_hash) = source_tip.node_block_hash {
+                        if node_block_hash != source_tip.block_hash {
+                            alerts.push(ChainHealthAlert::TipMismatch {
+                                source: source_tip.source.clone(),
+                                height: source_tip.height,
+                                node_block_hash: node_block_hash.to_string(),
+                                source_block_hash: source_tip.block_hash.to_string(),
+                            });
+                            continue;
+                        }
+                    }
+                    if source_tip.height > height.saturating_add(max_lag_blocks) {
+                        alerts.push(ChainHealthAlert::LaggingTip {
+                            source: source_tip.source.clone(),
+                            node_height: height,
+                            source_height: source_tip.height,
+                        });
+                    }
+                }
+            }
+        }
+
+        // c Tell the newly raised alerts and the cleared ones apart.
+        let raised: Vec<ChainHealthAlert> = alerts
+            .iter()
+            .filter(|alert| {
+                !self
+                    .alerts
+                    .iter()
+                    .any(|active| active.same_condition(alert))
+            })
+            .cloned()
+            .collect();
+        let cleared: Vec<ChainHealthAlert> = self
+            .alerts
+            .iter()
+            .filter(|active| !alerts.iter().any(|alert| alert.same_condition(active)))
+            .cloned()
+            .collect();
+
+        // d Replace the active alerts.
+        self.alerts_raised += raised.len() as u64;
+        self.alerts = alerts;
+        self.last_checked_at = Some(now);
+
+        (raised, cleared)
+    }
+
+    /// Whether no alert is active.
+    pub fn is_healthy(&self) -> bool {
+        self.alerts.is_empty()
+    }
+
+    /// Whether the engine should hold off settling batches.
+    pub fn settlement_paused(&self) -> bool {
+        self.pause_settlement && !self.is_healthy()
+    }
+
+    /// Returns the active alerts.
+    pub fn alerts(&self) -> &Vec<ChainHealthAlert> {
+        &self.alerts
+    }
+
+    /// Returns the chain health as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("healthy".to_string(), Value::Bool(self.is_healthy()));
+        obj.insert(
+            "settlement_paused".to_string(),
+            Value::Bool(self.settlement_paused()),
+        );
+        obj.insert(
+            "node_tip_height".to_string(),
+            match self.node_tip {
+                Some((height, _, _)) => Value::Number(height.into()),
+                None => Value::Null,
+            },
+        );
+        obj.insert(
+            "node_tip_since".to_string(),
+            match self.node_tip {
+                Some((_, _, since)) => Value::Number(since.into()),
+                None => Value::Null,
+            },
+        );
+        obj.insert(
+            "alerts".to_string(),
+            Value::Array(self.alerts.iter().map(|alert| alert.json()).collect()),
+        );
+        obj.insert(
+            "alerts_raised".to_string(),
+            Value::Number(self.alerts_raised.into()),
+        );
+        obj.insert(
+            "last_checked_at".to_string(),
+            match self.last_checked_at {
+                Some(last_checked_at) => Value::Number(last_checked_at.into()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Background loop to check the tip of the Bitcoin node for advancing and against the reference sources.
+pub async fn chain_health_background_task(
+    chain_health: &CHAIN_HEALTH,
+    rpc_holder: &BitcoinRPCHolder,
+    config: ChainHealthConfig,
+) {
+    loop {
+        // 1 Retrieve the tips of the Bitcoin node and the reference sources.
+        let (node_tip, source_tips) = {
+            let rpc_holder = rpc_holder.clone();
+            let task_config = config.clone();
+            match tokio::task::spawn_blocking(move || retrieve_tips(&rpc_holder, &task_config))
+                .await
+            {
+                Ok(tips) => tips,
+                Err(_) => {
+                    tokio::time::sleep(config.interval).await;
+                    continue;
+                }
+            }
+        };
+
+        // 2 Evaluate the tips.
+        let (raised, cleared, healthy) = {
+            let mut _chain_health = chain_health.lock().await;
+            let (raised, cleared) = _chain_health.evaluate(
+                node_tip,
+                &source_tips,
+                config.stale_tip_after.as_secs(),
+                config.max_lag_blocks,
+                Utc::now().timestamp() as u64,
+            );
+            (raised, cleared, _chain_health.is_healthy())
+        };
+
+        // 3 Report the raised and cleared alerts.
+        for alert in raised.iter() {
+            eprintln!(
+                "{}",
+                format!("Chain health alert: {}", alert.to_string()).red()
+            );
+        }
+        for alert in cleared.iter() {
+            println!(
+                "{}",
+                format!("Chain health alert cleared: {}", alert.to_string()).green()
+            );
+        }
+        if !cleared.is_empty() && healthy {
+            println!("{}", "Chain health restored.".green());
+        }
+
+        // 4 Wait for the next check.
+        tokio::time::sleep(config.interval).await;
+    }
+}
+
+/// Retrieves the tip of the Bitcoin node and the tips of the reachable reference sources.
+fn retrieve_tips(
+    rpc_holder: &BitcoinRPCHolder,
+    config: &ChainHealthConfig,
+) -> (Result<(BlockHeight, BlockHash), String>, Vec<SourceTip>) {
+    // 1 Retrieve the tip of the Bitcoin node.
+    let node_tip = match retrieve_best_block(rpc_holder) {
+        Ok(node_tip) => node_tip,
+        Err(err) => return (Err(err.to_string()), Vec::new()),
+    };
+
+    // 2 Retrieve the tips of the reference sources, along with the node's block at the same height.
+    let mut source_tips = Vec::new();
+    for source in config.sources.iter() {
+        // 2.1 An unreachable source says nothing about the chain; skip it.
+        let (height, block_hash) = match retrieve_best_block(&source.rpc_holder) {
+            Ok(source_tip) => source_tip,
+            Err(_) => continue,
+        };
+
+        // 2.2 Look up the node's block at the same height, if the node has one.
+        let node_block_hash = match height <= node_tip.0 {
+            true => retrieve_block_hash(rpc_holder, height).ok(),
+            false => None,
+        };
+
+        source_tips.push(SourceTip {
+            source: source.label.clone(),
+            height,
+            block_hash,
+            node_block_hash,
+        });
+    }
+
+    (Ok(node_tip), source_tips)
+}
@@ -0,0 +1,91 @@
+use serde_json::{Map, Value};
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Conditions that make the chain seen through the Bitcoin node uncertain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainHealthAlert {
+    /// The Bitcoin node cannot be reached.
+    NodeUnreachable(String),
+    /// The tip of the Bitcoin node has not advanced since the given Unix timestamp.
+    StaleTip { height: BlockHeight, since: u64 },
+    /// A reference source has a different block than the Bitcoin node at the same height.
+    TipMismatch {
+        source: String,
+        height: BlockHeight,
+        node_block_hash: String,
+        source_block_hash: String,
+    },
+    /// A reference source is further ahead of the Bitcoin node than allowed.
+    LaggingTip {
+        source: String,
+        node_height: BlockHeight,
+        source_height: BlockHeight,
+    },
+}
+
+impl ChainHealthAlert {
+    /// Returns the kind of the alert.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChainHealthAlert::NodeUnreachable(_) => "node_unreachable",
+            ChainHealthAlert::StaleTip { .. } => "stale_tip",
+            ChainHealthAlert::TipMismatch { .. } => "tip_mismatch",
+            ChainHealthAlert::LaggingTip { .. } => "lagging_tip",
+        }
+    }
+
+    /// Returns the source the alert is about, if it is about a reference source.
+    pub fn source(&self) -> Option<&str> {
+        match self {
+            ChainHealthAlert::TipMismatch { source, .. }
+            | ChainHealthAlert::LaggingTip { source, .. } => Some(source),
+            ChainHealthAlert::NodeUnreachable(_) | ChainHealthAlert::StaleTip { .. } => None,
+        }
+    }
+
+    /// Whether both alerts report the same condition, regardless of the heights involved.
+    pub fn same_condition(&self, other: &ChainHealthAlert) -> bool {
+        self.kind() == other.kind() && self.source() == other.source()
+    }
+
+    /// Returns the alert as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("kind".to_string(), Value::String(self.kind().to_string()));
+        obj.insert("message".to_string(), Value::String(self.to_string()));
+        Value::Object(obj)
+    }
+}
+
+impl ToString for ChainHealthAlert {
+    fn to_string(&self) -> String {
+        match self {
+            ChainHealthAlert::NodeUnreachable(err) => {
+                format!("The Bitcoin node cannot be reached: {}", err)
+            }
+            ChainHealthAlert::StaleTip { height, since } => format!(
+                "The Bitcoin node tip has been stuck at #{} since {}.",
+                height, since
+            ),
+            ChainHealthAlert::TipMismatch {
+                source,
+                height,
+                node_block_hash,
+                source_block_hash,
+            } => format!(
+                "Chain split at #{}: the Bitcoin node has {} while {} has {}.",
+                height, node_block_hash, source, source_block_hash
+            ),
+            ChainHealthAlert::LaggingTip {
+                source,
+                node_height,
+                source_height,
+            } => format!(
+                "The Bitcoin node tip #{} lags behind {} at #{}.",
+                node_height, source, source_height
+            ),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use std::time::Duration;
+
+/// Default interval between two chain health checks in seconds.
+const DEFAULT_CHAIN_HEALTH_INTERVAL_SECS: u64 = 60;
+
+/// Default number of seconds without a new block after which the tip is considered stale.
+const DEFAULT_STALE_TIP_SECS: u64 = 5_400;
+
+/// Default number of blocks a reference source may be ahead of the Bitcoin node.
+const DEFAULT_MAX_LAG_BLOCKS: u64 = 2;
+
+/// A reference Bitcoin RPC source the tip of the Bitcoin node is compared against.
+#[derive(Clone)]
+pub struct ChainHealthSource {
+    // URL of the source without credentials, used to refer to it in alerts.
+    pub label: String,
+
+    // RPC holder of the source.
+    pub rpc_holder: BitcoinRPCHolder,
+}
+
+impl ChainHealthSource {
+    /// Parses a source from a `<scheme>://[<user>:<password>@]<host>[:<port>]` URL.
+    ///
+    /// Returns `None` if the URL has no scheme or no host.
+    pub fn parse(value: &str) -> Option<Self> {
+        // 1 Split off the scheme.
+        let (scheme, rest) = value.trim().split_once("://")?;
+
+        // 2 Split off the credentials, if any.
+        let (user, password, host) = match rest.rsplit_once('@') {
+            Some((credentials, host)) => {
+                let (user, password) = credentials.split_once(':').unwrap_or((credentials, ""));
+                (user, password, host)
+            }
+            None => ("", "", rest),
+        };
+        if scheme.is_empty() || host.is_empty() {
+            return None;
+        }
+
+        // 3 Return the source.
+        let url = format!("{}://{}", scheme, host);
+        Some(Self {
+            label: url.clone(),
+            rpc_holder: BitcoinRPCHolder::new(url, user.to_string(), password.to_string()),
+        })
+    }
+}
+
+/// Chain health monitoring configuration.
+#[derive(Clone)]
+pub struct ChainHealthConfig {
+    // Reference sources the tip of the Bitcoin node is compared against.
+    pub sources: Vec<ChainHealthSource>,
+
+    // Interval between two checks.
+    pub interval: Duration,
+
+    // Time without a new block after which the tip is considered stale.
+    pub stale_tip_after: Duration,
+
+    // Number of blocks a reference source may be ahead of the Bitcoin node.
+    pub max_lag_blocks: u64,
+
+    // Whether the engine holds off settling batches while the chain health is uncertain.
+    pub pause_settlement: bool,
+}
+
+impl ChainHealthConfig {
+    /// Reads the chain health configuration from the environment.
+    ///
+    /// `CUBE_CHAIN_HEALTH_SOURCES` is a comma-separated list of reference Bitcoin RPC URLs, with the
+    /// credentials embedded as `<user>:<password>@`; malformed entries are ignored. The tip is checked
+    /// for staleness even without sources. `CUBE_CHAIN_HEALTH_INTERVAL_SECS`, `CUBE_CHAIN_STALE_TIP_SECS`
+    /// and `CUBE_CHAIN_HEALTH_MAX_LAG` optionally override the defaults, and setting
+    /// `CUBE_CHAIN_HEALTH_PAUSE_SETTLEMENT` to `1` or `true` pauses settlement while an alert is active.
+    pub fn from_env() -> Self {
+        let read_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        // 1 Read the reference sources.
+        let sources = std::env::var("CUBE_CHAIN_HEALTH_SOURCES")
+            .map(|value| {
+                value
+                    .split(',')
+                    .filter(|source| !source.trim().is_empty())
+                    .filter_map(ChainHealthSource::parse)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // 2 Read whether to pause settlement.
+        let pause_settlement = matches!(
+            std::env::var("CUBE_CHAIN_HEALTH_PAUSE_SETTLEMENT")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .as_deref(),
+            Some("1") | Some("true")
+        );
+
+        // 3 Return the configuration.
+        Self {
+            sources,
+            interval: Duration::from_secs(
+                read_u64("CUBE_CHAIN_HEALTH_INTERVAL_SECS")
+                    .unwrap_or(DEFAULT_CHAIN_HEALTH_INTERVAL_SECS)
+                    .max(1),
+            ),
+            stale_tip_after: Duration::from_secs(
+                read_u64("CUBE_CHAIN_STALE_TIP_SECS").unwrap_or(DEFAULT_STALE_TIP_SECS),
+            ),
+            max_lag_blocks: read_u64("CUBE_CHAIN_HEALTH_MAX_LAG").unwrap_or(DEFAULT_MAX_LAG_BLOCKS),
+            pause_settlement,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod chain_health;
+pub mod chain_health_alert;
+pub mod chain_health_config;
@@ -0,0 +1,177 @@
+use bitcoin::blockdata::block::Block;
+use bitcoin::BlockHash;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Default number of blocks kept in memory.
+const DEFAULT_BLOCK_CACHE_BLOCKS: usize = 32;
+
+/// Default number of blocks kept on disk, when the on-disk cache is enabled.
+const DEFAULT_BLOCK_CACHE_DISK_BLOCKS: usize = 1_024;
+
+/// A bounded cache of recently fetched blocks keyed by block hash.
+///
+/// Blocks are kept in memory up to a number of blocks, and optionally on disk up to a larger number
+/// of blocks, evicting the oldest ones first. This spares refetching the same blocks from the Bitcoin
+/// node when they are needed again, e.g. after a reorg or a discarded prefetch.
+pub struct BlockCache {
+    // Maximum number of blocks kept in memory.
+    memory_capacity: usize,
+
+    // In-memory blocks.
+    blocks: HashMap<BlockHash, Block>,
+
+    // In-memory block hashes, oldest first.
+    memory_order: VecDeque<BlockHash>,
+
+    // Directory of the on-disk blocks, if enabled.
+    disk_dir: Option<PathBuf>,
+
+    // Maximum number of blocks kept on disk.
+    disk_capacity: usize,
+
+    // On-disk block hashes, oldest first.
+    disk_order: VecDeque<BlockHash>,
+}
+
+/// Guarded block cache.
+#[allow(non_camel_case_types)]
+pub type BLOCK_CACHE = Arc<Mutex<BlockCache>>;
+
+impl BlockCache {
+    /// Constructs a block cache.
+    ///
+    /// Blocks already on disk are picked up, oldest first by modification time.
+    pub fn new(
+        memory_capacity: usize,
+        disk_dir: Option<PathBuf>,
+        disk_capacity: usize,
+    ) -> BLOCK_CACHE {
+        // 1 Create the on-disk cache directory and collect the blocks already in it.
+        let mut disk_order = VecDeque::new();
+        if let Some(disk_dir) = disk_dir.as_ref() {
+            let _ = std::fs::create_dir_all(disk_dir);
+            let mut on_disk: Vec<(std::time::SystemTime, BlockHash)> = std::fs::read_dir(disk_dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let block_hash = entry.file_name().to_str()?.parse::<BlockHash>().ok()?;
+                    let modified = entry.metadata().ok()?.modified().ok()?;
+                    Some((modified, block_hash))
+                })
+                .collect();
+            on_disk.sort();
+            disk_order.extend(on_disk.into_iter().map(|(_, block_hash)| block_hash));
+        }
+
+        // 2 Construct the block cache.
+        let mut block_cache = BlockCache {
+            memory_capacity: memory_capacity.max(1),
+            blocks: HashMap::new(),
+            memory_order: VecDeque::new(),
+            disk_dir,
+            disk_capacity: disk_capacity.max(1),
+            disk_order,
+        };
+
+        // 3 Evict the on-disk blocks beyond a possibly lowered capacity.
+        block_cache.evict_disk();
+
+        // 4 Guard and return the block cache.
+        Arc::new(Mutex::new(block_cache))
+    }
+
+    /// Constructs a block cache from the environment.
+    ///
+    /// `CUBE_BLOCK_CACHE_BLOCKS` optionally overrides the number of blocks kept in memory. The on-disk
+    /// cache is disabled unless `CUBE_BLOCK_CACHE_DIR` is set, in which case `CUBE_BLOCK_CACHE_DISK_BLOCKS`
+    /// optionally overrides the number of blocks kept on disk.
+    pub fn from_env() -> BLOCK_CACHE {
+        // 1 Read a block count, ignoring unset or malformed values.
+        let read_blocks = |var: &str| -> Option<usize> {
+            std::env::var(var)
+                .ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+        };
+
+        // 2 Read the on-disk cache directory.
+        let disk_dir = std::env::var("CUBE_BLOCK_CACHE_DIR")
+            .ok()
+            .map(|dir| dir.trim().to_string())
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+
+        // 3 Construct the block cache.
+        Self::new(
+            read_blocks("CUBE_BLOCK_CACHE_BLOCKS").unwrap_or(DEFAULT_BLOCK_CACHE_BLOCKS),
+            disk_dir,
+            read_blocks("CUBE_BLOCK_CACHE_DISK_BLOCKS").unwrap_or(DEFAULT_BLOCK_CACHE_DISK_BLOCKS),
+        )
+    }
+
+    /// Returns the block with the given hash, reading it back from disk if it is no longer in memory.
+    pub fn get(&mut self, block_hash: &BlockHash) -> Option<Block> {
+        // 1 Serve the block from memory if cached.
+        if let Some(block) = self.blocks.get(block_hash) {
+            return Some(block.clone());
+        }
+
+        // 2 Otherwise read it back from disk and keep it in memory again.
+        let bytes = std::fs::read(self.disk_path(block_hash)?).ok()?;
+        let block: Block = bitcoin::consensus::encode::deserialize(&bytes).ok()?;
+        self.cache_in_memory(*block_hash, block.clone());
+        Some(block)
+    }
+
+    /// Caches a block in memory and, if enabled, on disk.
+    pub fn insert(&mut self, block: &Block) {
+        let block_hash = block.block_hash();
+
+        // 1 Write the block to disk.
+        if let Some(disk_path) = self.disk_path(&block_hash) {
+            if !self.disk_order.contains(&block_hash) {
+                let bytes = bitcoin::consensus::encode::serialize(block);
+                if std::fs::write(disk_path, bytes).is_ok() {
+                    self.disk_order.push_back(block_hash);
+                    self.evict_disk();
+                }
+            }
+        }
+
+        // 2 Keep the block in memory.
+        self.cache_in_memory(block_hash, block.clone());
+    }
+
+    /// Keeps a block in memory, evicting the oldest ones while over capacity.
+    fn cache_in_memory(&mut self, block_hash: BlockHash, block: Block) {
+        if self.blocks.insert(block_hash, block).is_none() {
+            self.memory_order.push_back(block_hash);
+        }
+        while self.memory_order.len() > self.memory_capacity {
+            if let Some(oldest_hash) = self.memory_order.pop_front() {
+                self.blocks.remove(&oldest_hash);
+            }
+        }
+    }
+
+    /// Removes the oldest on-disk blocks while over capacity.
+    fn evict_disk(&mut self) {
+        while self.disk_order.len() > self.disk_capacity {
+            if let Some(oldest_hash) = self.disk_order.pop_front() {
+                if let Some(disk_path) = self.disk_path(&oldest_hash) {
+                    let _ = std::fs::remove_file(disk_path);
+                }
+            }
+        }
+    }
+
+    /// Returns the on-disk path of a block, if the on-disk cache is enabled.
+    fn disk_path(&self, block_hash: &BlockHash) -> Option<PathBuf> {
+        self.disk_dir
+            .as_ref()
+            .map(|disk_dir| disk_dir.join(block_hash.to_string()))
+    }
+}
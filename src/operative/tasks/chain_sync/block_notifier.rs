@@ -0,0 +1,197 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::wait_for_new_block;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::{
+    BitcoinRPCError, BitcoinRPCWaitForNewBlockError,
+};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_timeout::call_timeouts;
+use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use zeromq::{Socket, SocketRecv, SubSocket};
+
+/// Topics bitcoind publishes a notification on for every new block.
+const ZMQ_BLOCK_TOPICS: [&str; 2] = ["hashblock", "rawblock"];
+
+/// Backoff after the ZMQ endpoint could not be reached.
+const ZMQ_RECONNECT_SECS: u64 = 5;
+
+/// Time a long poll leaves to its call timeout, so that the Bitcoin node answers before the call times out.
+const LONG_POLL_MARGIN: Duration = Duration::from_secs(2);
+
+/// Wakes the sync loop up as soon as bitcoind announces a new block over ZMQ, or else as soon as a
+/// `waitfornewblock` long poll returns.
+///
+/// Without a ZMQ endpoint configured, or while it cannot be reached, waiting for a new block long-polls
+/// the Bitcoin node. If long-polling is disabled, unsupported by the Bitcoin node or failing, waiting
+/// falls back to the polling interval.
+pub struct BlockNotifier {
+    // Signalled on every block notification; `None` if ZMQ is not configured.
+    notify: Option<Arc<Notify>>,
+
+    // Bitcoin RPC to long-poll; `None` if long-polling is disabled.
+    long_poll_rpc: Option<BitcoinRPCHolder>,
+
+    // Whether the Bitcoin node supports long-polling, until it answers otherwise.
+    long_poll_supported: AtomicBool,
+}
+
+impl BlockNotifier {
+    /// Constructs a block notifier from the environment.
+    ///
+    /// ZMQ notifications are disabled unless `CUBE_ZMQ_BLOCK_ENDPOINT` is set to the endpoint bitcoind
+    /// publishes `hashblock` or `rawblock` on (e.g. `tcp://127.0.0.1:28332`), in which case a background
+    /// listener is spawned. Long-polling the given Bitcoin RPC is enabled unless `CUBE_BITCOIN_RPC_LONG_POLL`
+    /// is set to `0` or `false`.
+    pub fn from_env(rpc_holder: &BitcoinRPCHolder) -> Self {
+        // 1 Read whether to long-poll.
+        let long_poll_enabled = !matches!(
+            std::env::var("CUBE_BITCOIN_RPC_LONG_POLL")
+                .ok()
+                .map(|value| value.trim().to_lowercase())
+                .as_deref(),
+            Some("0") | Some("false")
+        );
+        let long_poll_rpc = match long_poll_enabled {
+            true => Some(rpc_holder.clone()),
+            false => None,
+        };
+
+        // 2 Read the endpoint; notifications stay disabled without one.
+        let endpoint = match std::env::var("CUBE_ZMQ_BLOCK_ENDPOINT") {
+            Ok(endpoint) if !endpoint.trim().is_empty() => endpoint.trim().to_string(),
+            _ => {
+                return Self {
+                    notify: None,
+                    long_poll_rpc,
+                    long_poll_supported: AtomicBool::new(true),
+                }
+            }
+        };
+
+        // 3 Spawn the listener.
+        let notify = Arc::new(Notify::new());
+        {
+            let notify = Arc::clone(&notify);
+            tokio::spawn(async move {
+                zmq_block_listener_background_task(endpoint, notify).await;
+            });
+        }
+
+        // 4 Return the notifier.
+        Self {
+            notify: Some(notify),
+            long_poll_rpc,
+            long_poll_supported: AtomicBool::new(true),
+        }
+    }
+
+    /// Waits until a new block is announced, or until the polling interval elapses.
+    pub async fn wait(&self, poll_interval: Duration) {
+        match (&self.notify, &self.long_poll_rpc) {
+            (Some(notify), _) => {
+                let _ = tokio::time::timeout(poll_interval, notify.notified()).await;
+            }
+            (None, Some(rpc_holder)) if self.long_poll_supported.load(Ordering::Relaxed) => {
+                self.long_poll(rpc_holder, poll_interval).await
+            }
+            (None, _) => tokio::time::sleep(poll_interval).await,
+        }
+    }
+
+    /// Long-polls the Bitcoin node for a new block for up to the polling interval, sleeping out the rest
+    /// of the interval if the long poll fails.
+    async fn long_poll(&self, rpc_holder: &BitcoinRPCHolder, poll_interval: Duration) {
+        // 1 Keep the long poll within its call timeout.
+        let long_poll_timeout = poll_interval.min(
+            call_timeouts()
+                .timeout_for("waitfornewblock")
+                .saturating_sub(LONG_POLL_MARGIN),
+        );
+
+        // 2 A zero timeout would wait indefinitely; sleep instead.
+        if long_poll_timeout.as_millis() == 0 {
+            tokio::time::sleep(poll_interval).await;
+            return;
+        }
+
+        // 3 Wait on the Bitcoin node.
+        let started = Instant::now();
+        let result = {
+            let rpc_holder = rpc_holder.clone();
+            tokio::task::spawn_blocking(move || wait_for_new_block(&rpc_holder, long_poll_timeout))
+                .await
+        };
+
+        // 4 Fall back to polling for good if the Bitcoin node does not support long-polling.
+        match result {
+            Ok(Ok(_)) => return,
+            Ok(Err(BitcoinRPCWaitForNewBlockError::RPCErr(BitcoinRPCError::MethodNotFound(_)))) => {
+                eprintln!(
+                    "{}",
+                    "Bitcoin node does not support waitfornewblock; polling for new blocks instead."
+                        .yellow()
+                );
+                self.long_poll_supported.store(false, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+
+        // 5 Sleep out the rest of the interval.
+        tokio::time::sleep(poll_interval.saturating_sub(started.elapsed())).await;
+    }
+}
+
+/// Background loop to listen for block notifications on the given ZMQ endpoint, reconnecting on failure.
+async fn zmq_block_listener_background_task(endpoint: String, notify: Arc<Notify>) {
+    loop {
+        // 1 Connect to the endpoint.
+        let mut socket = SubSocket::new();
+        if let Err(err) = socket.connect(&endpoint).await {
+            eprintln!(
+                "{}",
+                format!(
+                    "Error connecting to ZMQ endpoint {}: {}. Retrying in {}s...",
+                    endpoint, err, ZMQ_RECONNECT_SECS
+                )
+                .yellow()
+            );
+            tokio::time::sleep(Duration::from_secs(ZMQ_RECONNECT_SECS)).await;
+            continue;
+        }
+
+        // 2 Subscribe to the block topics.
+        let mut subscribed = true;
+        for topic in ZMQ_BLOCK_TOPICS {
+            if let Err(err) = socket.subscribe(topic).await {
+                eprintln!(
+                    "{}",
+                    format!("Error subscribing to ZMQ topic '{}': {}.", topic, err).yellow()
+                );
+                subscribed = false;
+                break;
+            }
+        }
+
+        // 3 Wake the sync loop up on every notification until the connection drops.
+        if subscribed {
+            println!("Listening for ZMQ block notifications on {}.", endpoint);
+            loop {
+                match socket.recv().await {
+                    Ok(_) => notify.notify_one(),
+                    Err(err) => {
+                        eprintln!(
+                            "{}",
+                            format!("ZMQ block notifications interrupted: {}.", err).yellow()
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+
+        // 4 Reconnect after a backoff.
+        tokio::time::sleep(Duration::from_secs(ZMQ_RECONNECT_SECS)).await;
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     communicative::peer::peer::PEER,
     communicative::rpc::bitcoin_rpc::{
-        bitcoin_rpc::{get_chain_tip, retrieve_block},
+        bitcoin_rpc::get_chain_tip, bitcoin_rpc_error::BitcoinRPCRetrieveBlockFilterError,
         bitcoin_rpc_holder::BitcoinRPCHolder,
     },
     communicative::tcp::client::TCPClient,
@@ -15,12 +15,23 @@ use crate::{
         privileges_manager::privileges_manager::PRIVILEGES_MANAGER,
         registery::registery::REGISTERY,
         state_manager::state_manager::STATE_MANAGER, sync_manager::sync_manager::SYNC_MANAGER,
-        utxo_set::utxo_set::UTXO_SET,
+        undo_journal::{block_undo::BlockUndo, undo_journal::UNDO_JOURNAL},
+        utxo_set::{sync_cursor::SyncCursor, utxo_set::UTXO_SET},
     },
     operative::run_args::chain::Chain,
+    operative::tasks::chain_sync::block_cache::BlockCache,
+    operative::tasks::chain_sync::block_notifier::BlockNotifier,
+    operative::tasks::chain_sync::compact_block_filters::CompactBlockFilters,
+    operative::tasks::chain_sync::header_chain::HeaderChain,
+    operative::tasks::chain_sync::quarantine::{
+        BlockQuarantine, QuarantinePolicy, QuarantineReason,
+    },
+    operative::tasks::chain_sync::reorg::unwind_to_fork,
+    operative::tasks::chain_sync::sync_pipeline::SyncPipeline,
 };
 use async_trait::async_trait;
-use bitcoin::OutPoint;
+use bitcoin::hashes::Hash;
+use bitcoin::{Block, BlockHash, OutPoint};
 use colored::Colorize;
 use std::sync::Arc;
 use std::time::Duration;
@@ -30,6 +41,9 @@ use tokio::time::sleep;
 /// This will require 2 on-chain confirmations for a transaction to be considered final.
 const BLOCK_DEPTH_FOR_FINALITY: u64 = 1;
 
+/// Interval between two chain tip polls, also used as a fallback when ZMQ block notifications are enabled.
+const CHAIN_TIP_POLL_INTERVAL_SECS: u64 = 10;
+
 #[async_trait]
 pub trait ChainSync {
     /// Spawns a background task to continuously sync the chain.
@@ -48,6 +62,8 @@ pub trait ChainSync {
         params_manager: &PARAMS_MANAGER,
         archival_manager: &Option<ARCHIVAL_MANAGER>,
         utxo_set: &UTXO_SET,
+        undo_journal: &UNDO_JOURNAL,
+        compact_block_filters: &Option<CompactBlockFilters>,
     );
 
     /// Awaits the chain to be fully synced to the latest chain tip.
@@ -85,6 +101,8 @@ impl ChainSync for SYNC_MANAGER {
         params_manager: &PARAMS_MANAGER,
         archival_manager: &Option<ARCHIVAL_MANAGER>,
         utxo_set: &UTXO_SET,
+        undo_journal: &UNDO_JOURNAL,
+        compact_block_filters: &Option<CompactBlockFilters>,
     ) {
         let mut synced: bool = false;
 
@@ -95,6 +113,87 @@ impl ChainSync for SYNC_MANAGER {
             Chain::Mainnet => baked::MAINNET_SYNC_START_HEIGHT,
         };
 
+        // Skip the blocks carrying nothing relevant using their compact block filters, if enabled.
+        let mut compact_block_filters = compact_block_filters.clone();
+
+        // Fetch, parse and verify the upcoming blocks in pipeline stages while they are applied in order.
+        // When blocks are skipped by their filters, only the block known to be relevant is fetched.
+        let mut sync_pipeline = match compact_block_filters {
+            Some(_) => SyncPipeline::new(rpc_holder, &BlockCache::from_env(), 1, 1),
+            None => SyncPipeline::from_env(rpc_holder),
+        };
+
+        // Get notified of new blocks over ZMQ if configured, or else by long-polling the Bitcoin node.
+        let block_notifier = BlockNotifier::from_env(rpc_holder);
+
+        // Resume from the sync cursor, which is written atomically with the utxo set changes of each block.
+        // The cube state of a batch is committed on its own when the batch is executed; a block whose batch
+        // was executed but which was not committed itself is synced again without executing the batch twice.
+        let sync_cursor = {
+            let _utxo_set = utxo_set.lock().await;
+            _utxo_set.sync_cursor()
+        };
+        if let Some(sync_cursor) = sync_cursor {
+            let mut _sync_manager = sync_manager.lock().await;
+            if _sync_manager.bitcoin_sync_height_tip() != sync_cursor.height {
+                println!(
+                    "{}",
+                    format!(
+                        "Bitcoin sync height tip #{} drifted from the sync cursor. Resuming from block #{}.",
+                        _sync_manager.bitcoin_sync_height_tip(),
+                        sync_cursor.height
+                    )
+                    .yellow()
+                );
+                _sync_manager.set_bitcoin_sync_height_tip(sync_cursor.height);
+            }
+        }
+
+        // Set blocks failing validation aside, and refuse to sync past a block halted on until it is released.
+        let block_quarantine = BlockQuarantine::new(chain);
+        let quarantine_policy = QuarantinePolicy::from_env();
+        if let Some(halted_on) = block_quarantine.halted_on() {
+            eprintln!(
+                "{}",
+                format!(
+                    "Sync halted on quarantined block #{} ({}): {}",
+                    halted_on.height, halted_on.block_hash, halted_on.reason
+                )
+                .red()
+            );
+            eprintln!(
+                "{}",
+                "Fix the cause, then run 'cube sync retry-quarantined --chain <chain>' and restart the node."
+                    .red()
+            );
+            std::process::exit(1);
+        }
+
+        // Unwind back to the blocks skipped past, if they were released to be retried.
+        if let Some(retry_height) = block_quarantine.retry_height() {
+            match unwind_to_fork(retry_height, sync_manager, utxo_set, undo_journal).await {
+                Ok(unwound_blocks) => {
+                    println!(
+                        "{}",
+                        format!(
+                            "Retrying quarantined blocks from #{} ({} block(s) unwound).",
+                            retry_height, unwound_blocks
+                        )
+                        .yellow()
+                    );
+                    block_quarantine.clear_retry_height();
+                }
+                Err(err) => {
+                    eprintln!("{} {:?}", "Quarantine retry error:".red(), err);
+                    eprintln!("{}", err.remedy().red());
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        // Sync the headers ahead of the blocks.
+        let mut header_chain = HeaderChain::new();
+
         // Initialize the Bitcoin node's chain tip.
         let mut bitcoin_node_chain_tip;
 
@@ -179,8 +278,10 @@ impl ChainSync for SYNC_MANAGER {
                                             synced = true;
                                         }
 
-                                        // Sleep for 10s.
-                                        sleep(Duration::from_secs(10)).await;
+                                        // Wait for a new block notification or long poll, or poll again after 10s.
+                                        block_notifier
+                                            .wait(Duration::from_secs(CHAIN_TIP_POLL_INTERVAL_SECS))
+                                            .await;
 
                                         // Continue checking for a new block.
                                         continue 'check_for_a_new_block;
@@ -214,18 +315,169 @@ impl ChainSync for SYNC_MANAGER {
                         false => cube_node_sync_height + 1,
                     };
 
-                    // Retrieve the block.
-                    let block = match retrieve_block(rpc_holder, height_to_sync) {
-                        Ok(block) => block,
+                    // Anchor the headers at the last applied block, so that a reorg below it is noticed.
+                    if header_chain.best_height().is_none() {
+                        let sync_cursor = {
+                            let _utxo_set = utxo_set.lock().await;
+                            _utxo_set.sync_cursor()
+                        };
+                        if let Some(sync_cursor) = sync_cursor {
+                            header_chain = HeaderChain::anchored(
+                                sync_cursor.height,
+                                BlockHash::from_byte_array(sync_cursor.block_hash),
+                            );
+                        }
+                    }
+
+                    // Sync the headers up to the Bitcoin node's chain tip ahead of the block data.
+                    if header_chain.best_height() < Some(bitcoin_node_chain_tip) {
+                        match header_chain.sync(rpc_holder, height_to_sync, bitcoin_node_chain_tip)
+                        {
+                            Ok(None) => {}
+                            Ok(Some(fork_height)) => {
+                                // Print the reorg.
+                                eprintln!(
+                                    "{}",
+                                    format!(
+                                        "Reorg detected. The chain forked at height #{}.",
+                                        fork_height
+                                    )
+                                    .red()
+                                );
+                                // Discard the blocks in the pipeline from the stale chain.
+                                sync_pipeline.reset();
+
+                                // Unwind the synced blocks that are no longer on the best chain.
+                                if fork_height <= cube_node_sync_height {
+                                    match unwind_to_fork(
+                                        fork_height,
+                                        sync_manager,
+                                        utxo_set,
+                                        undo_journal,
+                                    )
+                                    .await
+                                    {
+                                        Ok(unwound_blocks) => {
+                                            println!(
+                                                "{}",
+                                                format!("Unwound {} block(s).", unwound_blocks)
+                                                    .yellow()
+                                            );
+                                            continue 'outer_sync_iteration;
+                                        }
+                                        Err(err) => {
+                                            // The synced state can no longer be trusted; stop here.
+                                            eprintln!("{} {:?}", "Reorg error:".red(), err);
+                                            eprintln!("{}", err.remedy().red());
+                                            std::process::exit(1);
+                                        }
+                                    }
+                                }
+                            }
+                            Err(err) => {
+                                // Print the error.
+                                eprintln!(
+                                    "{}",
+                                    format!(
+                                        "Header sync error at height #{}: {}. Retrying in 5s...",
+                                        height_to_sync, err
+                                    )
+                                    .yellow()
+                                );
+
+                                // Sleep and retry.
+                                sleep(Duration::from_secs(5)).await;
+                                continue 'outer_sync_iteration;
+                            }
+                        }
+                    }
+
+                    // Skip the block without downloading it if its compact block filter rules it out.
+                    if let Some(filters) = compact_block_filters.as_ref() {
+                        if let (Some(block_hash), Some(prev_block_hash)) = (
+                            header_chain.hash_at(height_to_sync),
+                            header_chain.hash_at(height_to_sync - 1),
+                        ) {
+                            let payload_tip_spk = {
+                                let _sync_manager = sync_manager.lock().await;
+                                _sync_manager
+                                    .payload_tip()
+                                    .txout()
+                                    .expect("This should never happen.")
+                                    .script_pubkey
+                                    .to_bytes()
+                            };
+
+                            match filters.block_may_be_relevant(
+                                rpc_holder,
+                                &block_hash,
+                                &payload_tip_spk,
+                            ) {
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    // Move the sync past the block without applying it.
+                                    skip_block(
+                                        height_to_sync,
+                                        block_hash,
+                                        prev_block_hash,
+                                        sync_manager,
+                                        utxo_set,
+                                        undo_journal,
+                                    )
+                                    .await;
+
+                                    // Drop the headers no longer needed.
+                                    header_chain.prune(height_to_sync);
+
+                                    println!(
+                                        "Skipped height #{}/#{}.",
+                                        height_to_sync,
+                                        header_chain.best_height().unwrap_or(height_to_sync)
+                                    );
+
+                                    // Continue the loop.
+                                    continue 'outer_sync_iteration;
+                                }
+                                Err(BitcoinRPCRetrieveBlockFilterError::RPCErr(err))
+                                    if err.is_transient() =>
+                                {
+                                    // The Bitcoin node is briefly unavailable; download this block in full and keep using filters.
+                                    eprintln!(
+                                        "{}",
+                                        format!(
+                                            "Block filter of height #{} unavailable: {}. Downloading the full block.",
+                                            height_to_sync, err
+                                        )
+                                        .yellow()
+                                    );
+                                }
+                                Err(err) => {
+                                    // The Bitcoin node likely has no block filter index; download full blocks instead.
+                                    eprintln!(
+                                        "{}",
+                                        format!(
+                                            "Compact block filters unavailable: {}. Downloading full blocks from now on.",
+                                            err
+                                        )
+                                        .yellow()
+                                    );
+                                    compact_block_filters = None;
+                                }
+                            }
+                        }
+                    }
+
+                    // Take the verified block out of the pipeline, running the ones after it through.
+                    let verified_block = match sync_pipeline
+                        .next_block(height_to_sync, target_sync_height)
+                        .await
+                    {
+                        Ok(verified_block) => verified_block,
                         Err(err) => {
                             // Print the error.
                             eprintln!(
                                 "{}",
-                                format!(
-                                    "Retrieve block error at height #{}: {}. Retrying in 5s...",
-                                    height_to_sync, err
-                                )
-                                .yellow()
+                                format!("Sync pipeline error: {}. Retrying in 5s...", err).yellow()
                             );
 
                             // Sleep and retry.
@@ -234,6 +486,53 @@ impl ChainSync for SYNC_MANAGER {
                         }
                     };
 
+                    let block = verified_block.block;
+
+                    // Make sure the block matches its header, otherwise the chain moved since the headers were synced.
+                    if header_chain.hash_at(height_to_sync) != Some(block.block_hash()) {
+                        // Resync the headers from this height on the next iteration.
+                        header_chain.truncate(height_to_sync);
+                        sync_pipeline.reset();
+                        continue 'outer_sync_iteration;
+                    }
+
+                    // Quarantine the block if its transactions do not match its header.
+                    if let Some(reason) = verified_block.invalid_reason {
+                        quarantine_block(
+                            &block_quarantine,
+                            quarantine_policy,
+                            &block,
+                            height_to_sync,
+                            &reason,
+                        );
+
+                        // Under the skip policy, move the sync past the block without applying it.
+                        skip_block(
+                            height_to_sync,
+                            block.block_hash(),
+                            block.header.prev_blockhash,
+                            sync_manager,
+                            utxo_set,
+                            undo_journal,
+                        )
+                        .await;
+                        header_chain.prune(height_to_sync);
+                        continue 'outer_sync_iteration;
+                    }
+
+                    // Record the undo data of the block as it is applied.
+                    let mut block_undo = BlockUndo::new(
+                        height_to_sync,
+                        block.block_hash().to_byte_array(),
+                        block.header.prev_blockhash.to_byte_array(),
+                    );
+
+                    // Collect the utxo set changes of the block, to be written at once with the sync cursor.
+                    {
+                        let mut _utxo_set = utxo_set.lock().await;
+                        _utxo_set.begin_block_delta();
+                    }
+
                     // Scan block..
                     for transaction in block.txdata.iter() {
                         let inputs = transaction.input.clone();
@@ -281,21 +580,45 @@ impl ChainSync for SYNC_MANAGER {
                                 BatchContainerByPrevOutpointResponseBody::Ok(success_body) => {
                                     match success_body.batch_container {
                                         Some(batch_container) => batch_container,
-                                        None => panic!("PH BatchContainerByPrevOutpointResponseBody during on-chain-sync CANNOT be None!"),
+                                        None => {
+                                            quarantine_block(
+                                                &block_quarantine,
+                                                quarantine_policy,
+                                                &block,
+                                                height_to_sync,
+                                                &QuarantineReason::BatchContainerUnavailable(
+                                                    "The engine has no batch container for the payload tip.".to_string(),
+                                                ),
+                                            );
+                                            continue;
+                                        }
                                     }
                                 }
                                 BatchContainerByPrevOutpointResponseBody::Err(err) => {
-                                    panic!(
-                                        "{}",
-                                        format!(
-                                            "PH BatchContainerByPrevOutpointResponseBody during on-chain-sync CANNOT be Err!: {:?}",
+                                    quarantine_block(
+                                        &block_quarantine,
+                                        quarantine_policy,
+                                        &block,
+                                        height_to_sync,
+                                        &QuarantineReason::BatchContainerUnavailable(format!(
+                                            "{:?}",
                                             err
-                                        )
-                                        .yellow()
+                                        )),
                                     );
+                                    continue;
                                 }
                             };
 
+                            // Restore the Bitcoin inputs spent by the batch, as lifts created in skipped blocks are not tracked.
+                            if compact_block_filters.is_some() {
+                                let mut _utxo_set = utxo_set.lock().await;
+                                for (outpoint, txout, _) in
+                                    batch_container.signed_batch_txn.tx_inputs.iter()
+                                {
+                                    _utxo_set.insert_utxo(outpoint, txout);
+                                }
+                            }
+
                             let exec_ctx = ExecCtx::construct(
                                 engine_key,
                                 Arc::clone(sync_manager),
@@ -321,27 +644,43 @@ impl ChainSync for SYNC_MANAGER {
                                         "Executed batch during on-chain sync. Batch height: #{}.",
                                         batch_record.batch_height
                                     );
+                                    block_undo.batch_txids.push(batch_record.batch_txid);
                                 }
                                 Err(err) => {
-                                    eprintln!(
-                                        "{}",
-                                        format!(
-                                            "Error executing batch during on-chain sync: {:?}",
+                                    quarantine_block(
+                                        &block_quarantine,
+                                        quarantine_policy,
+                                        &block,
+                                        height_to_sync,
+                                        &QuarantineReason::BatchExecutionFailed(format!(
+                                            "{:?}",
                                             err
-                                        )
-                                        .yellow()
+                                        )),
                                     );
                                     continue;
                                 }
                             }
                         } else {
+                            // A batch applied ahead of its block, by the engine itself or by a run that crashed
+                            // before committing the block, no longer spends the payload tip. Its cube state is
+                            // already committed, so only its utxo set changes are replayed, and it is still
+                            // recorded as carried by the block.
+                            if txid == prev_payload_tip_outpoint.txid {
+                                block_undo.batch_txids.push(txid.to_byte_array());
+                            }
+
                             // Iterate over inputs only in CUBE transaction case.
                             for txn_input in inputs.iter() {
                                 let txn_input_outpoint = txn_input.previous_output;
 
-                                // Remove spent utxos from utxoset.
+                                // Remove spent utxos from utxoset, keeping them for the undo data.
                                 {
                                     let mut _utxo_set = utxo_set.lock().await;
+                                    if let Some(txout) =
+                                        _utxo_set.txout_by_outpoint(&txn_input_outpoint)
+                                    {
+                                        block_undo.spent_utxos.push((txn_input_outpoint, txout));
+                                    }
                                     _utxo_set.remove_utxo(&txn_input_outpoint);
                                 }
                             }
@@ -351,23 +690,48 @@ impl ChainSync for SYNC_MANAGER {
                         for (txn_output_index, txn_output) in outputs.iter().enumerate() {
                             let txn_output_outpoint = OutPoint::new(txid, txn_output_index as u32);
 
-                            // Add to utxoset.
+                            // Add to utxoset, keeping track of it for the undo data.
                             {
                                 let mut _utxo_set = utxo_set.lock().await;
+                                if _utxo_set.txout_by_outpoint(&txn_output_outpoint).is_none() {
+                                    block_undo.created_outpoints.push(txn_output_outpoint);
+                                }
                                 _utxo_set.insert_utxo(&txn_output_outpoint, txn_output);
                             }
                         }
                     }
 
+                    // Journal the undo data of the block.
+                    {
+                        let mut _undo_journal = undo_journal.lock().await;
+                        _undo_journal.insert(block_undo);
+                    }
+
+                    // Write the utxo set changes of the block along with the new sync cursor.
+                    {
+                        let mut _utxo_set = utxo_set.lock().await;
+                        _utxo_set.commit_block_delta(SyncCursor::new(
+                            height_to_sync,
+                            block.block_hash().to_byte_array(),
+                        ));
+                    }
+
                     // Set the new bitcoin sync height tip.
                     {
                         let mut _sync_manager = sync_manager.lock().await;
                         _sync_manager.set_bitcoin_sync_height_tip(height_to_sync);
                     }
 
+                    // Drop the headers no longer needed.
+                    header_chain.prune(height_to_sync);
+
                     // TODO set the new rollup sync height.
 
-                    println!("Synced height #{}.", height_to_sync);
+                    println!(
+                        "Synced height #{}/#{}.",
+                        height_to_sync,
+                        header_chain.best_height().unwrap_or(height_to_sync)
+                    );
 
                     // Continue the loop.
                     continue 'outer_sync_iteration;
@@ -376,3 +740,91 @@ impl ChainSync for SYNC_MANAGER {
         }
     }
 }
+
+/// Moves the sync past a block without applying it, journaling empty undo data so that it can still be unwound.
+async fn skip_block(
+    height: u64,
+    block_hash: BlockHash,
+    prev_block_hash: BlockHash,
+    sync_manager: &SYNC_MANAGER,
+    utxo_set: &UTXO_SET,
+    undo_journal: &UNDO_JOURNAL,
+) {
+    // 1 Journal empty undo data.
+    {
+        let mut _undo_journal = undo_journal.lock().await;
+        _undo_journal.insert(BlockUndo::new(
+            height,
+            block_hash.to_byte_array(),
+            prev_block_hash.to_byte_array(),
+        ));
+    }
+
+    // 2 Move the sync cursor past the block.
+    {
+        let mut _utxo_set = utxo_set.lock().await;
+        _utxo_set.begin_block_delta();
+        _utxo_set.commit_block_delta(SyncCursor::new(height, block_hash.to_byte_array()));
+    }
+
+    // 3 Set the new bitcoin sync height tip.
+    {
+        let mut _sync_manager = sync_manager.lock().await;
+        _sync_manager.set_bitcoin_sync_height_tip(height);
+    }
+}
+
+/// Sets a block that failed validation aside in the quarantine.
+///
+/// Under the halt policy the node stops before any change of the block is written, so that the block is
+/// synced again once released. Under the skip policy it returns, and the caller skips the offending part.
+fn quarantine_block(
+    block_quarantine: &BlockQuarantine,
+    quarantine_policy: QuarantinePolicy,
+    block: &Block,
+    height: u64,
+    reason: &QuarantineReason,
+) {
+    // 1 Persist the block and its error context.
+    let skipped = quarantine_policy == QuarantinePolicy::Skip;
+    if let Err(err) = block_quarantine.quarantine(block, height, reason, skipped) {
+        eprintln!(
+            "{}",
+            format!("Failed to quarantine block #{}: {}", height, err).red()
+        );
+    }
+
+    // 2 Halt or skip per policy.
+    match quarantine_policy {
+        QuarantinePolicy::Halt => {
+            eprintln!(
+                "{}",
+                format!(
+                    "Block #{} ({}) failed validation and was quarantined: {}",
+                    height,
+                    block.block_hash(),
+                    reason.to_string()
+                )
+                .red()
+            );
+            eprintln!(
+                "{}",
+                "Fix the cause, then run 'cube sync retry-quarantined --chain <chain>' and restart the node."
+                    .red()
+            );
+            std::process::exit(1);
+        }
+        QuarantinePolicy::Skip => {
+            eprintln!(
+                "{}",
+                format!(
+                    "Block #{} ({}) failed validation and was quarantined, skipping: {}",
+                    height,
+                    block.block_hash(),
+                    reason.to_string()
+                )
+                .yellow()
+            );
+        }
+    }
+}
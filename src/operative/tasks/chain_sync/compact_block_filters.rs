@@ -0,0 +1,80 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::retrieve_block_filter;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCRetrieveBlockFilterError;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::constructive::txo::lift::lift_versions::{
+    liftv1::liftv1::return_liftv1_scriptpubkey, liftv2::liftv2::return_liftv2_scriptpubkey,
+};
+use bitcoin::BlockHash;
+
+/// Matches blocks against their BIP158 compact block filters, so that blocks carrying nothing relevant
+/// to the node can be skipped without being downloaded.
+///
+/// A block is relevant if it spends the payload tip, which is how a batch transaction is found, or if
+/// it creates or spends a lift owned by the node's own account. Lifts of other accounts spent by a
+/// batch are resolved from the batch container instead, so they need not be tracked in the utxo set.
+///
+/// Filters are served by the Bitcoin node's block filter index (`-blockfilterindex=1`).
+#[derive(Clone)]
+pub struct CompactBlockFilters {
+    // Script pubkeys of the lifts owned by the node's own account.
+    self_lift_spks: Vec<Vec<u8>>,
+}
+
+impl CompactBlockFilters {
+    /// Constructs compact block filters watching for the lifts owned by the given account.
+    pub fn new(engine_key: [u8; 32], self_account_key: [u8; 32], v2_lift_enabled: bool) -> Self {
+        // 1 Collect the lift script pubkeys of the account.
+        let mut self_lift_spks = Vec::new();
+        if let Some(spk_v1) = return_liftv1_scriptpubkey(self_account_key, engine_key) {
+            self_lift_spks.push(spk_v1);
+        }
+        if v2_lift_enabled {
+            if let Some(spk_v2) = return_liftv2_scriptpubkey(self_account_key, engine_key) {
+                self_lift_spks.push(spk_v2);
+            }
+        }
+
+        // 2 Construct the compact block filters.
+        Self { self_lift_spks }
+    }
+
+    /// Constructs compact block filters if enabled in the environment.
+    ///
+    /// `CUBE_COMPACT_BLOCK_FILTERS` enables them when set to `1` or `true`.
+    pub fn from_env(
+        engine_key: [u8; 32],
+        self_account_key: [u8; 32],
+        v2_lift_enabled: bool,
+    ) -> Option<Self> {
+        let enabled = std::env::var("CUBE_COMPACT_BLOCK_FILTERS")
+            .ok()
+            .map(|value| value.trim().to_lowercase())
+            .is_some_and(|value| value == "1" || value == "true");
+
+        match enabled {
+            true => Some(Self::new(engine_key, self_account_key, v2_lift_enabled)),
+            false => None,
+        }
+    }
+
+    /// Returns whether the block with the given hash may be relevant, given the payload tip script pubkey.
+    ///
+    /// Filters have rare false positives but no false negatives, so a block reported as irrelevant is
+    /// safe to skip. A malformed filter is treated as a match.
+    pub fn block_may_be_relevant(
+        &self,
+        rpc_holder: &BitcoinRPCHolder,
+        block_hash: &BlockHash,
+        payload_tip_spk: &[u8],
+    ) -> Result<bool, BitcoinRPCRetrieveBlockFilterError> {
+        // 1 Retrieve the block filter.
+        let block_filter = retrieve_block_filter(rpc_holder, block_hash)?;
+
+        // 2 Match the filter against the watched script pubkeys.
+        let query = std::iter::once(payload_tip_spk)
+            .chain(self.self_lift_spks.iter().map(|spk| spk.as_slice()));
+
+        // 3 Return whether any of them matched.
+        Ok(block_filter.match_any(block_hash, query).unwrap_or(true))
+    }
+}
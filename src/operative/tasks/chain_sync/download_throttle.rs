@@ -0,0 +1,138 @@
+use serde_json::{Map, Value};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default burst allowance in KiB.
+const DEFAULT_DOWNLOAD_BURST_KIB: u64 = 4096;
+
+/// Process-wide block download throttle, read from the environment on first use and adjustable at runtime.
+static DOWNLOAD_THROTTLE: OnceLock<Mutex<DownloadThrottle>> = OnceLock::new();
+
+/// Limits the rate blocks are downloaded at during sync.
+///
+/// Downloaded bytes are taken from a byte bucket that refills at the configured rate up to the burst
+/// allowance. A block is only known to be downloaded once it is, so the bucket may go into debt, and the
+/// worker that downloaded the block then waits until the debt is paid off. As the bucket is shared by
+/// all sync workers, the download rate stays within the limit however many blocks are fetched at once.
+#[derive(Debug, Clone)]
+pub struct DownloadThrottle {
+    // Download rate limit in bytes per second; `0` means unlimited.
+    rate_bytes_per_sec: u64,
+
+    // Burst allowance in bytes.
+    burst_bytes: u64,
+
+    // Currently available bytes; negative while in debt.
+    available_bytes: f64,
+
+    // Last time the bucket was refilled.
+    last_refill: Instant,
+}
+
+impl DownloadThrottle {
+    /// Constructs a throttle with a full bucket.
+    pub fn new(rate_kib_per_sec: u64, burst_kib: u64, now: Instant) -> Self {
+        Self {
+            rate_bytes_per_sec: rate_kib_per_sec.saturating_mul(1024),
+            burst_bytes: burst_kib.saturating_mul(1024),
+            available_bytes: burst_kib.saturating_mul(1024) as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Constructs a throttle from the environment.
+    ///
+    /// Downloads are unlimited unless `CUBE_DOWNLOAD_RATE_KIB` sets a rate in KiB per second.
+    /// `CUBE_DOWNLOAD_BURST_KIB` optionally overrides the default burst allowance.
+    pub fn from_env() -> Self {
+        let read_kib = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        Self::new(
+            read_kib("CUBE_DOWNLOAD_RATE_KIB").unwrap_or(0),
+            read_kib("CUBE_DOWNLOAD_BURST_KIB").unwrap_or(DEFAULT_DOWNLOAD_BURST_KIB),
+            Instant::now(),
+        )
+    }
+
+    /// Whether downloads are unlimited.
+    pub fn is_unlimited(&self) -> bool {
+        self.rate_bytes_per_sec == 0
+    }
+
+    /// Changes the rate limit and, optionally, the burst allowance, starting over with a full bucket.
+    pub fn set_limit(&mut self, rate_kib_per_sec: u64, burst_kib: Option<u64>, now: Instant) {
+        let burst_kib = burst_kib.unwrap_or(self.burst_bytes / 1024);
+        *self = Self::new(rate_kib_per_sec, burst_kib, now);
+    }
+
+    /// Refills the bucket for the time elapsed since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.available_bytes = (self.available_bytes + elapsed * self.rate_bytes_per_sec as f64)
+            .min(self.burst_bytes as f64);
+        self.last_refill = now;
+    }
+
+    /// Takes the given number of downloaded bytes from the bucket.
+    ///
+    /// Returns how long to wait before downloading further.
+    pub fn take(&mut self, bytes: u64, now: Instant) -> Duration {
+        // 1 Nothing to wait for without a limit.
+        if self.is_unlimited() {
+            return Duration::ZERO;
+        }
+
+        // 2 Refill the bucket and take the bytes, possibly going into debt.
+        self.refill(now);
+        self.available_bytes -= bytes as f64;
+
+        // 3 Return the time until the debt is paid off.
+        match self.available_bytes < 0.0 {
+            true => Duration::from_secs_f64(-self.available_bytes / self.rate_bytes_per_sec as f64),
+            false => Duration::ZERO,
+        }
+    }
+
+    /// Returns the throttle as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "rate_kib_per_sec".to_string(),
+            match self.is_unlimited() {
+                true => Value::Null,
+                false => Value::Number((self.rate_bytes_per_sec / 1024).into()),
+            },
+        );
+        obj.insert(
+            "burst_kib".to_string(),
+            Value::Number((self.burst_bytes / 1024).into()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Returns the process-wide block download throttle.
+pub fn download_throttle() -> &'static Mutex<DownloadThrottle> {
+    DOWNLOAD_THROTTLE.get_or_init(|| Mutex::new(DownloadThrottle::from_env()))
+}
+
+/// Accounts for the given number of downloaded bytes, blocking the calling worker as long as the rate
+/// limit requires.
+pub fn throttle_download(bytes: u64) {
+    let wait = {
+        let mut _download_throttle = download_throttle()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        _download_throttle.take(bytes, Instant::now())
+    };
+
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod reorg_error;
+pub mod sync_pipeline_error;
@@ -0,0 +1,25 @@
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Errors associated with unwinding synced blocks on a reorg.
+#[derive(Debug, Clone)]
+pub enum ReorgError {
+    /// The reorg is deeper than the number of blocks undo data is kept for.
+    RollbackDepthExceeded {
+        reorg_depth: u64,
+        rollback_depth: u64,
+    },
+    /// The undo data of a block within the rollback depth is missing.
+    MissingUndoData(BlockHeight),
+}
+
+impl ReorgError {
+    /// Returns what the operator can do about the error.
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            ReorgError::RollbackDepthExceeded { .. } | ReorgError::MissingUndoData(_) => {
+                "Stop the node and reindex it from scratch by deleting the 'storage/<chain>' directory before restarting. Raise CUBE_REORG_ROLLBACK_DEPTH to unwind deeper reorgs in place."
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCRetrieveBlockError;
+use std::fmt;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Errors associated with the fetch, parse and verify stages of the sync pipeline.
+#[derive(Debug)]
+pub enum SyncPipelineError {
+    /// The block could not be fetched from the Bitcoin node.
+    FetchError(BlockHeight, BitcoinRPCRetrieveBlockError),
+    /// The block served by the Bitcoin node could not be parsed.
+    MalformedBlock(BlockHeight),
+    /// A stage stopped before handing out the block.
+    StageStopped(BlockHeight),
+}
+
+impl fmt::Display for SyncPipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncPipelineError::FetchError(height, err) => {
+                write!(f, "Fetching block #{} failed: {}", height, err)
+            }
+            SyncPipelineError::MalformedBlock(height) => {
+                write!(
+                    f,
+                    "Block #{} served by the Bitcoin node is malformed",
+                    height
+                )
+            }
+            SyncPipelineError::StageStopped(height) => {
+                write!(
+                    f,
+                    "A sync stage stopped before handing out block #{}",
+                    height
+                )
+            }
+        }
+    }
+}
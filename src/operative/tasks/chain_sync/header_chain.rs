@@ -0,0 +1,158 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::retrieve_block_header;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCRetrieveBlockHeaderError;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use bitcoin::blockdata::block::Header;
+use bitcoin::BlockHash;
+use std::collections::VecDeque;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Number of header hashes kept below the last applied block height to detect reorgs against.
+const HEADER_RETENTION: u64 = 144;
+
+/// Header hashes synced ahead of the full blocks.
+///
+/// Headers are only ever appended on top of their parent, so the chain is continuous from its base
+/// height up to the best known height. This gives the sync loop the best height to report progress
+/// against, and lets it notice a reorg as soon as a header no longer builds on the known chain,
+/// before the heavier block data is downloaded.
+pub struct HeaderChain {
+    // Height of the first header hash.
+    base_height: BlockHeight,
+
+    // Header hashes in ascending height order, starting from the base height.
+    hashes: VecDeque<BlockHash>,
+}
+
+impl HeaderChain {
+    /// Constructs an empty header chain.
+    pub fn new() -> Self {
+        Self {
+            base_height: 0,
+            hashes: VecDeque::new(),
+        }
+    }
+
+    /// Constructs a header chain anchored at an already applied block.
+    pub fn anchored(height: BlockHeight, block_hash: BlockHash) -> Self {
+        Self {
+            base_height: height,
+            hashes: VecDeque::from([block_hash]),
+        }
+    }
+
+    /// Returns the best known header height, if any header is known.
+    pub fn best_height(&self) -> Option<BlockHeight> {
+        match self.hashes.len() as u64 {
+            0 => None,
+            len => Some(self.base_height + len - 1),
+        }
+    }
+
+    /// Returns the header hash at the given height, if known.
+    pub fn hash_at(&self, height: BlockHeight) -> Option<BlockHash> {
+        let index = height.checked_sub(self.base_height)?;
+        self.hashes.get(index as usize).copied()
+    }
+
+    /// Appends the header at the given height.
+    ///
+    /// Returns false, leaving the chain untouched, if the header does not build on the best known header.
+    pub fn extend(&mut self, height: BlockHeight, header: &Header) -> bool {
+        // 1 Start the chain from the given height if it is empty.
+        let best_hash = match self.best_height() {
+            Some(best_height) => {
+                if height != best_height + 1 {
+                    return false;
+                }
+                self.hash_at(best_height)
+            }
+            None => {
+                self.base_height = height;
+                None
+            }
+        };
+
+        // 2 Make sure the header builds on the best known header.
+        if let Some(best_hash) = best_hash {
+            if header.prev_blockhash != best_hash {
+                return false;
+            }
+        }
+
+        // 3 Append the header hash.
+        self.hashes.push_back(header.block_hash());
+        true
+    }
+
+    /// Drops the header hashes at and above the given height.
+    pub fn truncate(&mut self, height: BlockHeight) {
+        let len = height.saturating_sub(self.base_height) as usize;
+        self.hashes.truncate(len);
+    }
+
+    /// Drops the header hashes that are no longer needed once the given block height is applied.
+    pub fn prune(&mut self, applied_height: BlockHeight) {
+        let keep_from_height = applied_height.saturating_sub(HEADER_RETENTION);
+        while self.base_height < keep_from_height && !self.hashes.is_empty() {
+            self.hashes.pop_front();
+            self.base_height += 1;
+        }
+    }
+
+    /// Syncs the headers up to the given tip height, starting from the given height if the chain is empty.
+    ///
+    /// Returns the height the chain forked at, if a reorg was detected on the way. The headers above the
+    /// fork point are replaced by the ones of the new best chain.
+    pub fn sync(
+        &mut self,
+        rpc_holder: &BitcoinRPCHolder,
+        start_height: BlockHeight,
+        tip_height: BlockHeight,
+    ) -> Result<Option<BlockHeight>, BitcoinRPCRetrieveBlockHeaderError> {
+        let mut fork_height: Option<BlockHeight> = None;
+
+        loop {
+            // 1 Resolve the next header height.
+            let next_height = match self.best_height() {
+                Some(best_height) => best_height + 1,
+                None => start_height,
+            };
+            if next_height > tip_height {
+                break;
+            }
+
+            // 2 Retrieve the header and append it.
+            let header = retrieve_block_header(rpc_holder, next_height)?;
+            if self.extend(next_height, &header) {
+                continue;
+            }
+
+            // 3 The header does not build on the known chain; walk back to the last common header.
+            let mut height = next_height - 1;
+            loop {
+                let known_hash = self
+                    .hash_at(height)
+                    .expect("Headers up to the best height are always known.");
+                if retrieve_block_header(rpc_holder, height)?.block_hash() == known_hash {
+                    // 3.a Resume right above the last common header.
+                    height += 1;
+                    break;
+                }
+                if height == self.base_height {
+                    // 3.b The fork is below the retained headers; start over from the base height.
+                    break;
+                }
+                height -= 1;
+            }
+
+            // 4 Drop the stale headers and carry on from the fork point.
+            self.truncate(height);
+            fork_height = Some(fork_height.map_or(height, |fork| fork.min(height)));
+        }
+
+        // 5 Return the fork height, if any.
+        Ok(fork_height)
+    }
+}
@@ -1 +1,10 @@
-pub mod chain_sync;
\ No newline at end of file
+pub mod block_cache;
+pub mod block_notifier;
+pub mod chain_sync;
+pub mod compact_block_filters;
+pub mod download_throttle;
+pub mod errors;
+pub mod header_chain;
+pub mod quarantine;
+pub mod reorg;
+pub mod sync_pipeline;
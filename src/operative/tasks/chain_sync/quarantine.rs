@@ -0,0 +1,301 @@
+use crate::operative::run_args::chain::Chain;
+use bitcoin::consensus::encode::serialize;
+use bitcoin::Block;
+use chrono::Utc;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Name of the file marking the height skipped blocks are to be retried from.
+const RETRY_MARKER_FILE: &str = "retry_from_height";
+
+/// Name of the directory released quarantined blocks are moved to.
+const RELEASED_DIR: &str = "released";
+
+/// What the chain syncer does with a block that fails validation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuarantinePolicy {
+    /// Stop syncing until the quarantined block is released with `cube sync retry-quarantined`.
+    Halt,
+    /// Skip the offending part of the block and keep syncing.
+    Skip,
+}
+
+impl QuarantinePolicy {
+    /// Reads the policy from the environment.
+    ///
+    /// `CUBE_QUARANTINE_POLICY` is either `halt` (the default) or `skip`.
+    pub fn from_env() -> Self {
+        match std::env::var("CUBE_QUARANTINE_POLICY")
+            .ok()
+            .map(|value| value.trim().to_lowercase())
+            .as_deref()
+        {
+            Some("skip") => QuarantinePolicy::Skip,
+            _ => QuarantinePolicy::Halt,
+        }
+    }
+}
+
+impl ToString for QuarantinePolicy {
+    fn to_string(&self) -> String {
+        match self {
+            QuarantinePolicy::Halt => "halt".to_string(),
+            QuarantinePolicy::Skip => "skip".to_string(),
+        }
+    }
+}
+
+/// Why a block was quarantined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuarantineReason {
+    /// The transactions of the block do not match its merkle root.
+    MerkleRootMismatch,
+    /// The witnesses of the block do not match its witness commitment.
+    WitnessCommitmentMismatch,
+    /// The batch container of a batch carried by the block could not be retrieved.
+    BatchContainerUnavailable(String),
+    /// A batch carried by the block failed to execute.
+    BatchExecutionFailed(String),
+}
+
+impl ToString for QuarantineReason {
+    fn to_string(&self) -> String {
+        match self {
+            QuarantineReason::MerkleRootMismatch => "Merkle root mismatch.".to_string(),
+            QuarantineReason::WitnessCommitmentMismatch => {
+                "Witness commitment mismatch.".to_string()
+            }
+            QuarantineReason::BatchContainerUnavailable(err) => {
+                format!("Batch container unavailable: {}", err)
+            }
+            QuarantineReason::BatchExecutionFailed(err) => {
+                format!("Batch execution failed: {}", err)
+            }
+        }
+    }
+}
+
+/// Context of a quarantined block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedBlock {
+    // Height of the block.
+    pub height: BlockHeight,
+
+    // Hash of the block.
+    pub block_hash: String,
+
+    // Why the block was quarantined.
+    pub reason: String,
+
+    // Whether the syncer skipped past the block rather than halting on it.
+    pub skipped: bool,
+
+    // Unix timestamp of when the block was quarantined.
+    pub quarantined_at: u64,
+}
+
+impl QuarantinedBlock {
+    /// Returns the quarantined block as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("height".to_string(), Value::Number(self.height.into()));
+        obj.insert(
+            "block_hash".to_string(),
+            Value::String(self.block_hash.clone()),
+        );
+        obj.insert("reason".to_string(), Value::String(self.reason.clone()));
+        obj.insert("skipped".to_string(), Value::Bool(self.skipped));
+        obj.insert(
+            "quarantined_at".to_string(),
+            Value::Number(self.quarantined_at.into()),
+        );
+        Value::Object(obj)
+    }
+
+    /// Parses a quarantined block from a JSON object.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            height: value.get("height")?.as_u64()?,
+            block_hash: value.get("block_hash")?.as_str()?.to_string(),
+            reason: value.get("reason")?.as_str()?.to_string(),
+            skipped: value.get("skipped")?.as_bool()?,
+            quarantined_at: value.get("quarantined_at")?.as_u64()?,
+        })
+    }
+}
+
+/// Directory of blocks that failed validation during sync.
+///
+/// Each quarantined block gets its own `<height>_<block_hash>` directory holding the raw block
+/// (`block.bin`) and its error context (`context.json`). Quarantined blocks stay in place until
+/// they are released with `cube sync retry-quarantined`, which moves them under `released/` and
+/// has the blocks skipped past retried on the next run.
+pub struct BlockQuarantine {
+    // Quarantine directory.
+    dir: PathBuf,
+}
+
+impl BlockQuarantine {
+    /// Constructs the block quarantine of a chain.
+    pub fn new(chain: Chain) -> Self {
+        Self {
+            dir: PathBuf::from(format!("storage/{}/quarantine", chain.to_string())),
+        }
+    }
+
+    /// Constructs a block quarantine in the given directory.
+    pub fn in_dir(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Persists the block along with its error context.
+    pub fn quarantine(
+        &self,
+        block: &Block,
+        height: BlockHeight,
+        reason: &QuarantineReason,
+        skipped: bool,
+    ) -> Result<QuarantinedBlock, std::io::Error> {
+        // 1 Construct the context.
+        let quarantined_block = QuarantinedBlock {
+            height,
+            block_hash: block.block_hash().to_string(),
+            reason: reason.to_string(),
+            skipped,
+            quarantined_at: Utc::now().timestamp() as u64,
+        };
+
+        // 2 Write the raw block and the context.
+        let entry_dir = self
+            .dir
+            .join(format!("{}_{}", height, quarantined_block.block_hash));
+        fs::create_dir_all(&entry_dir)?;
+        fs::write(entry_dir.join("block.bin"), serialize(block))?;
+        fs::write(
+            entry_dir.join("context.json"),
+            serde_json::to_vec_pretty(&quarantined_block.json())
+                .expect("serde_json::Value should serialize"),
+        )?;
+
+        // 3 Return the context.
+        Ok(quarantined_block)
+    }
+
+    /// Returns the quarantined blocks, in ascending height order.
+    pub fn list(&self) -> Vec<QuarantinedBlock> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut quarantined_blocks: Vec<QuarantinedBlock> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir() && entry.file_name() != RELEASED_DIR)
+            .filter_map(|entry| fs::read(entry.path().join("context.json")).ok())
+            .filter_map(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+            .filter_map(|value| QuarantinedBlock::from_json(&value))
+            .collect();
+        quarantined_blocks.sort_by_key(|quarantined_block| quarantined_block.height);
+        quarantined_blocks
+    }
+
+    /// Returns the lowest quarantined block the syncer halted on, if any.
+    pub fn halted_on(&self) -> Option<QuarantinedBlock> {
+        self.list()
+            .into_iter()
+            .find(|quarantined_block| !quarantined_block.skipped)
+    }
+
+    /// Releases all quarantined blocks so that they are retried on the next run.
+    ///
+    /// Returns the released blocks.
+    pub fn release_all(&self) -> Result<Vec<QuarantinedBlock>, std::io::Error> {
+        // 1 Collect the quarantined blocks.
+        let quarantined_blocks = self.list();
+        if quarantined_blocks.is_empty() {
+            return Ok(quarantined_blocks);
+        }
+
+        // 2 Mark the lowest skipped block to be retried from, keeping an earlier mark.
+        let lowest_skipped_height = quarantined_blocks
+            .iter()
+            .filter(|quarantined_block| quarantined_block.skipped)
+            .map(|quarantined_block| quarantined_block.height)
+            .min();
+        if let Some(height) = lowest_skipped_height {
+            let height = match self.retry_height() {
+                Some(marked_height) => marked_height.min(height),
+                None => height,
+            };
+            fs::write(self.dir.join(RETRY_MARKER_FILE), height.to_string())?;
+        }
+
+        // 3 Move the quarantined blocks out of the way.
+        let released_dir = self.dir.join(RELEASED_DIR);
+        fs::create_dir_all(&released_dir)?;
+        for quarantined_block in quarantined_blocks.iter() {
+            let name = format!(
+                "{}_{}",
+                quarantined_block.height, quarantined_block.block_hash
+            );
+            let released_name = format!("{}_{}", name, Utc::now().timestamp());
+            fs::rename(self.dir.join(&name), released_dir.join(released_name))?;
+        }
+
+        // 4 Return the released blocks.
+        Ok(quarantined_blocks)
+    }
+
+    /// Returns the height skipped blocks are to be retried from, if any.
+    pub fn retry_height(&self) -> Option<BlockHeight> {
+        fs::read_to_string(self.dir.join(RETRY_MARKER_FILE))
+            .ok()
+            .and_then(|value| value.trim().parse::<BlockHeight>().ok())
+    }
+
+    /// Clears the retry mark once the skipped blocks are being retried.
+    pub fn clear_retry_height(&self) {
+        let _ = fs::remove_file(self.dir.join(RETRY_MARKER_FILE));
+    }
+}
+
+/// Releases the quarantined blocks of a chain, to be retried on the next run.
+pub fn retry_quarantined(chain: Chain) {
+    // 1 Release the quarantined blocks.
+    let block_quarantine = BlockQuarantine::new(chain);
+    let released_blocks = match block_quarantine.release_all() {
+        Ok(released_blocks) => released_blocks,
+        Err(err) => {
+            eprintln!(
+                "{}",
+                format!("Failed to release the quarantined blocks: {}", err).red()
+            );
+            return;
+        }
+    };
+
+    // 2 Print the released blocks.
+    if released_blocks.is_empty() {
+        println!("{}", "No quarantined blocks.".yellow());
+        return;
+    }
+    for released_block in released_blocks.iter() {
+        println!(
+            "Released block #{} ({}): {}",
+            released_block.height, released_block.block_hash, released_block.reason
+        );
+    }
+    println!(
+        "{}",
+        format!(
+            "Released {} block(s). Restart the node to retry them.",
+            released_blocks.len()
+        )
+        .green()
+    );
+}
@@ -0,0 +1,114 @@
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::inscriptive::undo_journal::undo_journal::UNDO_JOURNAL;
+use crate::inscriptive::utxo_set::sync_cursor::SyncCursor;
+use crate::inscriptive::utxo_set::utxo_set::UTXO_SET;
+use crate::operative::tasks::chain_sync::errors::reorg_error::ReorgError;
+use colored::Colorize;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Unwinds the synced blocks at and above the given fork height using their undo data.
+///
+/// Either all blocks are unwound or none are: the undo data of every block is looked up before anything
+/// is changed. Batches carried by the unwound blocks stay executed, since the same batch transactions
+/// are expected to re-confirm on the new best chain.
+///
+/// Returns the number of unwound blocks.
+pub async fn unwind_to_fork(
+    fork_height: BlockHeight,
+    sync_manager: &SYNC_MANAGER,
+    utxo_set: &UTXO_SET,
+    undo_journal: &UNDO_JOURNAL,
+) -> Result<u64, ReorgError> {
+    // 1 Get the Bitcoin sync height tip.
+    let bitcoin_sync_height_tip = {
+        let _sync_manager = sync_manager.lock().await;
+        _sync_manager.bitcoin_sync_height_tip()
+    };
+
+    // 2 Nothing to unwind if the fork is above the synced blocks.
+    if fork_height > bitcoin_sync_height_tip {
+        return Ok(0);
+    }
+
+    // 3 Collect the undo data of the blocks to unwind, in descending height order.
+    let block_undos = {
+        let _undo_journal = undo_journal.lock().await;
+
+        // 3.1 Make sure the reorg is within the rollback depth.
+        let reorg_depth = bitcoin_sync_height_tip - fork_height + 1;
+        if reorg_depth > _undo_journal.rollback_depth() {
+            return Err(ReorgError::RollbackDepthExceeded {
+                reorg_depth,
+                rollback_depth: _undo_journal.rollback_depth(),
+            });
+        }
+
+        // 3.2 Make sure the undo data of every block is kept.
+        let mut block_undos = Vec::new();
+        for height in (fork_height..=bitcoin_sync_height_tip).rev() {
+            let block_undo = _undo_journal
+                .undo_by_height(height)
+                .ok_or(ReorgError::MissingUndoData(height))?;
+            block_undos.push(block_undo);
+        }
+        block_undos
+    };
+
+    // 4 Unwind the blocks one by one.
+    for block_undo in block_undos.iter() {
+        // 4.1 Revert the utxo set changes and step the sync cursor back to the parent block, atomically.
+        // Spent utxos are restored first, so that utxos both created and spent within the block end up removed.
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            _utxo_set.begin_block_delta();
+            for (outpoint, txout) in block_undo.spent_utxos.iter() {
+                _utxo_set.insert_utxo(outpoint, txout);
+            }
+            for outpoint in block_undo.created_outpoints.iter() {
+                _utxo_set.remove_utxo(outpoint);
+            }
+            _utxo_set.commit_block_delta(SyncCursor::new(
+                block_undo.height - 1,
+                block_undo.prev_block_hash,
+            ));
+        }
+
+        // 4.2 Step the Bitcoin sync height tip back.
+        {
+            let mut _sync_manager = sync_manager.lock().await;
+            _sync_manager.set_bitcoin_sync_height_tip(block_undo.height - 1);
+        }
+
+        // 4.3 Drop the undo data of the unwound block.
+        {
+            let mut _undo_journal = undo_journal.lock().await;
+            _undo_journal.remove(block_undo.height);
+        }
+
+        // 4.4 Print the unwound block.
+        println!(
+            "{}",
+            format!(
+                "Unwound block #{} ({}).",
+                block_undo.height,
+                hex::encode(block_undo.block_hash)
+            )
+            .yellow()
+        );
+        for batch_txid in block_undo.batch_txids.iter() {
+            println!(
+                "{}",
+                format!(
+                    "Batch {} was carried by an unwound block and is expected to re-confirm.",
+                    hex::encode(batch_txid)
+                )
+                .yellow()
+            );
+        }
+    }
+
+    // 5 Return the number of unwound blocks.
+    Ok(block_undos.len() as u64)
+}
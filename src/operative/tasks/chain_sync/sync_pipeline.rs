@@ -0,0 +1,457 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::{
+    retrieve_block_hashes, retrieve_blocks_hex_by_hashes,
+};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::operative::tasks::chain_sync::block_cache::{BlockCache, BLOCK_CACHE};
+use crate::operative::tasks::chain_sync::download_throttle::throttle_download;
+use crate::operative::tasks::chain_sync::errors::sync_pipeline_error::SyncPipelineError;
+use crate::operative::tasks::chain_sync::quarantine::QuarantineReason;
+use bitcoin::blockdata::block::Block;
+use bitcoin::consensus::encode::deserialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Bitcoin block height.
+type BlockHeight = u64;
+
+/// Default number of blocks fetched ahead of the block being applied.
+const DEFAULT_BLOCK_PREFETCH_WINDOW: usize = 16;
+
+/// Maximum number of blocks fetched ahead of the block being applied.
+const MAX_BLOCK_PREFETCH_WINDOW: usize = 128;
+
+/// Default number of consecutive blocks fetched in a single batch of RPC calls.
+const DEFAULT_BLOCK_FETCH_BATCH: usize = 4;
+
+/// A block as it comes out of the fetch stage.
+enum FetchedBlock {
+    /// The hex-encoded block as served by the Bitcoin node, yet to be parsed.
+    Hex(String),
+    /// The block served from the block cache, already parsed.
+    Cached(Block),
+}
+
+/// A block as it comes out of the verify stage, ready to be applied.
+pub struct VerifiedBlock {
+    // Height of the block.
+    pub height: BlockHeight,
+
+    // The block.
+    pub block: Block,
+
+    // Why the block failed verification, if it did.
+    pub invalid_reason: Option<QuarantineReason>,
+}
+
+/// Output of a pipeline stage.
+type StageOutput<T> = (BlockHeight, Result<T, SyncPipelineError>);
+
+/// A running pipeline, handing out the blocks from `next_height` up to `end_height`.
+struct RunningPipeline {
+    // Height of the next block handed out.
+    next_height: BlockHeight,
+
+    // Height of the last block handed out.
+    end_height: BlockHeight,
+
+    // Output of the verify stage.
+    verified_rx: mpsc::Receiver<StageOutput<VerifiedBlock>>,
+
+    // Fetch, parse and verify stage tasks.
+    stages: [JoinHandle<()>; 3],
+
+    // Cancellation flag of the fetch workers, which cannot be aborted.
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Runs the upcoming blocks through the fetch, parse and verify stages ahead of the apply stage.
+///
+/// Each stage runs as its own task and hands its output to the next one over a bounded channel, so
+/// that I/O-bound fetching, CPU-bound parsing and verification, and applying all overlap, while a slow
+/// stage holds the ones before it back once its channel is full. The fetch stage downloads up to
+/// `window` blocks at once, in batches of `batch_size` consecutive blocks each on its own blocking
+/// worker, through the block cache and within the block download rate limit, if any. A batch resolves
+/// the block hashes in one JSON-RPC batch and downloads the blocks in another, saving the per-call
+/// overhead during initial sync. Blocks come out of the pipeline in ascending height order; the apply
+/// stage is the chain syncer taking them out with `next_block`.
+pub struct SyncPipeline {
+    // Bitcoin RPC the blocks are fetched from.
+    rpc_holder: BitcoinRPCHolder,
+
+    // Cache of recently fetched blocks.
+    block_cache: BLOCK_CACHE,
+
+    // Number of blocks fetched at once, also the capacity of the channels between the stages.
+    window: usize,
+
+    // Number of consecutive blocks fetched in a single batch.
+    batch_size: usize,
+
+    // The running pipeline, if any.
+    running: Option<RunningPipeline>,
+}
+
+impl SyncPipeline {
+    /// Constructs a sync pipeline with the given window, clamped to `1..=MAX_BLOCK_PREFETCH_WINDOW`, and
+    /// the given batch size, clamped to `1..=window`.
+    pub fn new(
+        rpc_holder: &BitcoinRPCHolder,
+        block_cache: &BLOCK_CACHE,
+        window: usize,
+        batch_size: usize,
+    ) -> Self {
+        let window = window.clamp(1, MAX_BLOCK_PREFETCH_WINDOW);
+        Self {
+            rpc_holder: rpc_holder.clone(),
+            block_cache: Arc::clone(block_cache),
+            window,
+            batch_size: batch_size.clamp(1, window),
+            running: None,
+        }
+    }
+
+    /// Constructs a sync pipeline with the window, the batch size and the block cache read from the
+    /// environment.
+    ///
+    /// `CUBE_BLOCK_PREFETCH_WINDOW` and `CUBE_BLOCK_FETCH_BATCH` optionally override the default window
+    /// and batch size.
+    pub fn from_env(rpc_holder: &BitcoinRPCHolder) -> Self {
+        let read_usize = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+        };
+
+        Self::new(
+            rpc_holder,
+            &BlockCache::from_env(),
+            read_usize("CUBE_BLOCK_PREFETCH_WINDOW").unwrap_or(DEFAULT_BLOCK_PREFETCH_WINDOW),
+            read_usize("CUBE_BLOCK_FETCH_BATCH").unwrap_or(DEFAULT_BLOCK_FETCH_BATCH),
+        )
+    }
+
+    /// Returns the number of blocks fetched at once.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the number of consecutive blocks fetched in a single batch.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the verified block at the given height, running the blocks after it up to the target
+    /// height through the pipeline.
+    ///
+    /// Blocks are expected to be requested in ascending height order. Any other request, as well as a
+    /// failed stage, stops the pipeline so that it is started over from the requested height.
+    pub async fn next_block(
+        &mut self,
+        height: BlockHeight,
+        target_height: BlockHeight,
+    ) -> Result<VerifiedBlock, SyncPipelineError> {
+        // 1 Start the pipeline over if it does not hand out the requested height next.
+        let in_order = self.running.as_ref().map_or(false, |running| {
+            running.next_height == height && height <= running.end_height
+        });
+        if !in_order {
+            self.reset();
+            self.start(height, target_height.max(height));
+        }
+
+        // 2 Take the requested block out of the verify stage.
+        let running = self
+            .running
+            .as_mut()
+            .expect("The pipeline is always running here.");
+        let result = match running.verified_rx.recv().await {
+            Some((verified_height, result)) if verified_height == height => result,
+            _ => Err(SyncPipelineError::StageStopped(height)),
+        };
+        running.next_height = height + 1;
+
+        // 3 Start over on the next call if a stage failed.
+        if result.is_err() {
+            self.reset();
+        }
+
+        // 4 Return the block.
+        result
+    }
+
+    /// Stops the pipeline, discarding the blocks in it.
+    pub fn reset(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.cancelled.store(true, Ordering::SeqCst);
+            for stage in running.stages.iter() {
+                stage.abort();
+            }
+        }
+    }
+
+    /// Starts the stages for the blocks from the start height up to the end height.
+    fn start(&mut self, start_height: BlockHeight, end_height: BlockHeight) {
+        // 1 Connect the stages with bounded channels.
+        let (fetched_tx, fetched_rx) = mpsc::channel(self.window);
+        let (parsed_tx, parsed_rx) = mpsc::channel(self.window);
+        let (verified_tx, verified_rx) = mpsc::channel(self.window);
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        // 2 Spawn the stages.
+        let stages = [
+            tokio::spawn(fetch_stage(
+                self.rpc_holder.clone(),
+                Arc::clone(&self.block_cache),
+                Arc::clone(&cancelled),
+                self.window,
+                self.batch_size,
+                start_height,
+                end_height,
+                fetched_tx,
+            )),
+            tokio::spawn(parse_stage(
+                Arc::clone(&self.block_cache),
+                fetched_rx,
+                parsed_tx,
+            )),
+            tokio::spawn(verify_stage(parsed_rx, verified_tx)),
+        ];
+
+        // 3 Keep track of the running pipeline.
+        self.running = Some(RunningPipeline {
+            next_height: start_height,
+            end_height,
+            verified_rx,
+            stages,
+            cancelled,
+        });
+    }
+}
+
+impl Drop for SyncPipeline {
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
+
+/// Fetch stage: downloads the blocks in batches of `batch_size`, up to `window` blocks at once, handing
+/// them out in ascending height order.
+///
+/// Stops after the end height, on the first failed fetch, or once the parse stage is gone. Batches still
+/// in flight by then are flagged as cancelled, and stop before their next RPC call.
+async fn fetch_stage(
+    rpc_holder: BitcoinRPCHolder,
+    block_cache: BLOCK_CACHE,
+    cancelled: Arc<AtomicBool>,
+    window: usize,
+    batch_size: usize,
+    start_height: BlockHeight,
+    end_height: BlockHeight,
+    fetched_tx: mpsc::Sender<StageOutput<FetchedBlock>>,
+) {
+    let mut in_flight: VecDeque<(BlockHeight, u64, JoinHandle<_>)> = VecDeque::new();
+    let mut in_flight_blocks: u64 = 0;
+    let mut next_height = start_height;
+
+    'fetch: loop {
+        // 1 Top up the batches in flight, never going past the end height.
+        while in_flight_blocks < window as u64 && next_height <= end_height {
+            let rpc_holder = rpc_holder.clone();
+            let block_cache = Arc::clone(&block_cache);
+            let cancelled = Arc::clone(&cancelled);
+            let batch_start = next_height;
+            let batch_end = (batch_start + batch_size as u64 - 1)
+                .min(end_height)
+                .min(batch_start + (window as u64 - in_flight_blocks) - 1);
+            let fetch = tokio::task::spawn_blocking(move || {
+                fetch_blocks(
+                    &rpc_holder,
+                    &block_cache,
+                    &cancelled,
+                    batch_start,
+                    batch_end,
+                )
+            });
+            let batch_len = batch_end - batch_start + 1;
+            in_flight.push_back((batch_start, batch_len, fetch));
+            in_flight_blocks += batch_len;
+            next_height = batch_end + 1;
+        }
+
+        // 2 Await the lowest batch in flight.
+        let (batch_start, batch_len, fetch) = match in_flight.pop_front() {
+            Some(front) => front,
+            None => break,
+        };
+        in_flight_blocks -= batch_len;
+        let result = match fetch.await {
+            Ok(result) => result,
+            Err(_) => Err(SyncPipelineError::StageStopped(batch_start)),
+        };
+
+        // 3 Hand the blocks over one by one, waiting while the parse stage is behind.
+        match result {
+            Ok(fetched_blocks) => {
+                for (height, fetched_block) in (batch_start..).zip(fetched_blocks) {
+                    if fetched_tx.send((height, Ok(fetched_block))).await.is_err() {
+                        break 'fetch;
+                    }
+                }
+            }
+            Err(err) => {
+                let _ = fetched_tx.send((batch_start, Err(err))).await;
+                break;
+            }
+        }
+    }
+
+    // 4 Discard the batches still in flight.
+    cancelled.store(true, Ordering::SeqCst);
+    in_flight.clear();
+}
+
+/// Parse stage: deserializes the fetched blocks and caches them.
+async fn parse_stage(
+    block_cache: BLOCK_CACHE,
+    mut fetched_rx: mpsc::Receiver<StageOutput<FetchedBlock>>,
+    parsed_tx: mpsc::Sender<StageOutput<Block>>,
+) {
+    while let Some((height, fetched)) = fetched_rx.recv().await {
+        // 1 Parse the block off the async runtime, unless it came from the cache.
+        let result = match fetched {
+            Ok(FetchedBlock::Cached(block)) => Ok(block),
+            Ok(FetchedBlock::Hex(block_hex)) => {
+                match tokio::task::spawn_blocking(move || parse_block(&block_hex)).await {
+                    Ok(Some(block)) => {
+                        block_cache.lock().await.insert(&block);
+                        Ok(block)
+                    }
+                    Ok(None) => Err(SyncPipelineError::MalformedBlock(height)),
+                    Err(_) => Err(SyncPipelineError::StageStopped(height)),
+                }
+            }
+            Err(err) => Err(err),
+        };
+
+        // 2 Hand the block over, waiting while the verify stage is behind.
+        let failed = result.is_err();
+        if parsed_tx.send((height, result)).await.is_err() || failed {
+            break;
+        }
+    }
+}
+
+/// Verify stage: checks the transactions of the parsed blocks against their headers.
+///
+/// A block failing the checks is still handed out, along with the reason, for the apply stage to
+/// quarantine.
+async fn verify_stage(
+    mut parsed_rx: mpsc::Receiver<StageOutput<Block>>,
+    verified_tx: mpsc::Sender<StageOutput<VerifiedBlock>>,
+) {
+    while let Some((height, parsed)) = parsed_rx.recv().await {
+        // 1 Verify the block off the async runtime.
+        let result = match parsed {
+            Ok(block) => {
+                match tokio::task::spawn_blocking(move || verify_block(height, block)).await {
+                    Ok(verified_block) => Ok(verified_block),
+                    Err(_) => Err(SyncPipelineError::StageStopped(height)),
+                }
+            }
+            Err(err) => Err(err),
+        };
+
+        // 2 Hand the block over, waiting while the apply stage is behind.
+        let failed = result.is_err();
+        if verified_tx.send((height, result)).await.is_err() || failed {
+            break;
+        }
+    }
+}
+
+/// Fetches the blocks from the start height up to the end height, serving them from the block cache
+/// when possible.
+///
+/// Stops before its next RPC call once the fetch is cancelled.
+fn fetch_blocks(
+    rpc_holder: &BitcoinRPCHolder,
+    block_cache: &BLOCK_CACHE,
+    cancelled: &AtomicBool,
+    start_height: BlockHeight,
+    end_height: BlockHeight,
+) -> Result<Vec<FetchedBlock>, SyncPipelineError> {
+    // 1 Resolve the hashes of the blocks at the heights.
+    if cancelled.load(Ordering::SeqCst) {
+        return Err(SyncPipelineError::StageStopped(start_height));
+    }
+    let heights: Vec<BlockHeight> = (start_height..=end_height).collect();
+    let block_hashes = retrieve_block_hashes(rpc_holder, &heights)
+        .map_err(|err| SyncPipelineError::FetchError(start_height, err))?;
+
+    // 2 Serve the blocks from the cache where present.
+    let mut fetched_blocks: Vec<Option<FetchedBlock>> = {
+        let mut _block_cache = block_cache.blocking_lock();
+        block_hashes
+            .iter()
+            .map(|block_hash| _block_cache.get(block_hash).map(FetchedBlock::Cached))
+            .collect()
+    };
+
+    // 3 Download the rest of the blocks.
+    let missing_hashes: Vec<_> = block_hashes
+        .iter()
+        .zip(fetched_blocks.iter())
+        .filter(|(_, fetched_block)| fetched_block.is_none())
+        .map(|(block_hash, _)| *block_hash)
+        .collect();
+    if !missing_hashes.is_empty() {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(SyncPipelineError::StageStopped(start_height));
+        }
+        let block_hexes = retrieve_blocks_hex_by_hashes(rpc_holder, &missing_hashes)
+            .map_err(|err| SyncPipelineError::FetchError(start_height, err))?;
+
+        // 3.a Hold the worker back as long as the download rate limit requires.
+        let downloaded_bytes: u64 = block_hexes.iter().map(|hex| hex.len() as u64 / 2).sum();
+        throttle_download(downloaded_bytes);
+
+        // 3.b Fill in the gaps.
+        let mut block_hexes = block_hexes.into_iter();
+        for fetched_block in fetched_blocks.iter_mut().filter(|block| block.is_none()) {
+            *fetched_block = block_hexes.next().map(FetchedBlock::Hex);
+        }
+    }
+
+    // 4 Return the blocks.
+    fetched_blocks
+        .into_iter()
+        .zip(start_height..)
+        .map(|(fetched_block, height)| {
+            fetched_block.ok_or(SyncPipelineError::MalformedBlock(height))
+        })
+        .collect()
+}
+
+/// Parses a hex-encoded block.
+pub fn parse_block(block_hex: &str) -> Option<Block> {
+    let bytes = hex::decode(block_hex).ok()?;
+    deserialize(&bytes).ok()
+}
+
+/// Checks the transactions of the block against its merkle root and witness commitment.
+pub fn verify_block(height: BlockHeight, block: Block) -> VerifiedBlock {
+    let invalid_reason = match (block.check_merkle_root(), block.check_witness_commitment()) {
+        (false, _) => Some(QuarantineReason::MerkleRootMismatch),
+        (true, false) => Some(QuarantineReason::WitnessCommitmentMismatch),
+        (true, true) => None,
+    };
+
+    VerifiedBlock {
+        height,
+        block,
+        invalid_reason,
+    }
+}
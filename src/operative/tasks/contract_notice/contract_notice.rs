@@ -0,0 +1,77 @@
+use crate::communicative::gossip::contract_notice_index::CONTRACT_NOTICE_INDEX;
+use crate::communicative::gossip::errors::contract_notice_error::ContractNoticeError;
+use crate::communicative::nns::client::NNSClient;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::operative::run_args::chain::Chain;
+use std::time::Duration;
+
+/// How often contract registration notices are fetched and published.
+const CONTRACT_NOTICE_INTERVAL_SECS: u64 = 30;
+
+/// Node background loop to index the contract registration notices broadcast by coordinators.
+///
+/// Notices are validated against the federation coordinators and deduplicated before being indexed;
+/// the notices this node signed as a coordinator are then published.
+pub async fn contract_notice_background_task(
+    nns_client: &NNSClient,
+    chain: Chain,
+    contract_notice_index: &CONTRACT_NOTICE_INDEX,
+) {
+    loop {
+        // 1 Fetch the notices broadcast by coordinators.
+        let notices = nns_client.query_contract_notices(chain).await;
+
+        // 2 Validate and index them, then take the ones to publish.
+        let pending_publish = {
+            let mut _contract_notice_index = contract_notice_index.lock().await;
+            for notice in notices {
+                let contract_id = notice.registration.contract_id;
+                match _contract_notice_index.insert(notice) {
+                    Ok(true) => {
+                        if log_enabled(LogLevel::Info) {
+                            println!("Indexed new contract {}.", hex::encode(contract_id));
+                        }
+                    }
+                    Ok(false) => (),
+                    Err(ContractNoticeError::ConflictingNotice(contract_id)) => {
+                        if log_enabled(LogLevel::Warn) {
+                            eprintln!(
+                                "Received a conflicting coordinator notice for contract {}.",
+                                hex::encode(contract_id)
+                            );
+                        }
+                    }
+                    Err(_) => (),
+                }
+            }
+            _contract_notice_index.take_pending_publish()
+        };
+
+        // 3 Publish the notices signed by this node.
+        for notice in pending_publish.iter() {
+            let contract_id = hex::encode(notice.registration.contract_id);
+            match nns_client.publish_contract_notice(chain, notice).await {
+                Some(event_id) => {
+                    if log_enabled(LogLevel::Debug) {
+                        println!(
+                            "Published the registration notice of contract {}: {}",
+                            contract_id,
+                            hex::encode(event_id)
+                        );
+                    }
+                }
+                None => {
+                    if log_enabled(LogLevel::Warn) {
+                        eprintln!(
+                            "Failed to publish the registration notice of contract {}.",
+                            contract_id
+                        );
+                    }
+                }
+            }
+        }
+
+        // 4 Wait for the next round.
+        tokio::time::sleep(Duration::from_secs(CONTRACT_NOTICE_INTERVAL_SECS)).await;
+    }
+}
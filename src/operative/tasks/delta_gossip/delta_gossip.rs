@@ -0,0 +1,71 @@
+use crate::communicative::gossip::delta_gossip_pool::DELTA_GOSSIP_POOL;
+use crate::communicative::gossip::errors::delta_gossip_error::DeltaGossipError;
+use crate::communicative::nns::client::NNSClient;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::operative::run_args::chain::Chain;
+use std::time::Duration;
+
+/// How often gossiped delta attestations are fetched and relayed.
+const DELTA_GOSSIP_INTERVAL_SECS: u64 = 30;
+
+/// Node background loop to gossip the applied deltas of the chain with other nodes.
+///
+/// Attestations gossiped by other nodes are validated against the federation quorum and deduplicated
+/// before being admitted to the pool; the newly admitted ones, including those learned from the engine,
+/// are then relayed once.
+pub async fn delta_gossip_background_task(
+    nns_client: &NNSClient,
+    chain: Chain,
+    delta_gossip_pool: &DELTA_GOSSIP_POOL,
+) {
+    loop {
+        // 1 Fetch the attestations gossiped by other nodes.
+        let attestations = nns_client.query_delta_gossip(chain).await;
+
+        // 2 Validate and deduplicate them, then take the ones to relay.
+        let pending_relay = {
+            let mut _delta_gossip_pool = delta_gossip_pool.lock().await;
+            for attestation in attestations {
+                match _delta_gossip_pool.insert(attestation) {
+                    Ok(()) => (),
+                    Err(DeltaGossipError::ConflictingBatch(batch_height)) => {
+                        if log_enabled(LogLevel::Warn) {
+                            eprintln!(
+                                "Gossiped a conflicting coordinator quorum for batch #{}.",
+                                batch_height
+                            );
+                        }
+                    }
+                    Err(_) => (),
+                }
+            }
+            _delta_gossip_pool.take_pending_relay()
+        };
+
+        // 3 Relay the newly admitted attestations.
+        for attestation in pending_relay.iter() {
+            match nns_client.publish_delta_gossip(chain, attestation).await {
+                Some(event_id) => {
+                    if log_enabled(LogLevel::Debug) {
+                        println!(
+                            "Relayed delta attestation of batch #{}: {}",
+                            attestation.batch_height,
+                            hex::encode(event_id)
+                        );
+                    }
+                }
+                None => {
+                    if log_enabled(LogLevel::Warn) {
+                        eprintln!(
+                            "Failed to relay the delta attestation of batch #{}.",
+                            attestation.batch_height
+                        );
+                    }
+                }
+            }
+        }
+
+        // 4 Wait for the next gossip round.
+        tokio::time::sleep(Duration::from_secs(DELTA_GOSSIP_INTERVAL_SECS)).await;
+    }
+}
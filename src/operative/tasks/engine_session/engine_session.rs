@@ -1,6 +1,10 @@
-use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::broadcast_raw_transaction;
-use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::get_mempool_min_fee_rate;
+use crate::communicative::federation::delta_attestation::DeltaCosignature;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::handshake::work_assignment::WorkAssignment;
 use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use crate::communicative::webhook::webhooks::WEBHOOKS;
 use crate::executive::exec_ctx::exec_ctx::ExecCtx;
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
@@ -12,20 +16,39 @@ use crate::inscriptive::registery::registery::REGISTERY;
 use crate::inscriptive::state_manager::state_manager::STATE_MANAGER;
 use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
 use crate::inscriptive::utxo_set::utxo_set::UTXO_SET;
+use crate::operative::tasks::chain_health::chain_health::CHAIN_HEALTH;
 use crate::operative::tasks::engine_session::session_pool::session_pool::SESSION_POOL;
+use crate::operative::tasks::engine_session::work_queue::work_item::{WorkItem, WorkStage};
+use crate::operative::tasks::engine_session::work_queue::work_queue::{
+    WorkFailureOutcome, WORK_QUEUE,
+};
+use crate::operative::tasks::fee_estimator::fee_estimator::FEE_ESTIMATOR;
+use crate::operative::tasks::fee_estimator::fee_estimator_config::FeePurpose;
+use crate::operative::tasks::rebroadcast::rebroadcast_queue::{
+    broadcast_transaction, REBROADCAST_QUEUE,
+};
 use crate::transmutative::key::KeyHolder;
 use chrono::Utc;
-use serde_json::to_string_pretty;
+use serde_json::{to_string_pretty, Map, Value};
 use std::sync::Arc;
 
 /// The waiting window period in seconds.
 const WAITING_WINDOW_PERIOD_SECONDS: u64 = 60;
 
+/// Interval in seconds between two chain health checks while settlement is paused.
+const SETTLEMENT_PAUSE_RECHECK_SECONDS: u64 = 10;
+
 pub async fn engine_batch_builder_background_task(
     session_pool: &SESSION_POOL,
     sync_manager: &SYNC_MANAGER,
     rpc_holder: &BitcoinRPCHolder,
     engine_keyholder: &KeyHolder,
+    work_queue: &WORK_QUEUE,
+    operator_sessions: &OPERATOR_SESSIONS,
+    chain_health: &CHAIN_HEALTH,
+    fee_estimator: &FEE_ESTIMATOR,
+    rebroadcast_queue: &REBROADCAST_QUEUE,
+    webhooks: &WEBHOOKS,
     // Exec ctx params
     engine_key: [u8; 32],
     utxo_set: &UTXO_SET,
@@ -42,7 +65,55 @@ pub async fn engine_batch_builder_background_task(
         panic!("Archival manager is required for engine batch builder background task.");
     }
 
+    // Whether settlement is currently held off, so that the pause is only reported once.
+    let mut settlement_paused = false;
+
     loop {
+        // 0.a Hold off settling while the chain seen through the Bitcoin node is uncertain.
+        {
+            let paused = {
+                let _chain_health = chain_health.lock().await;
+                _chain_health.settlement_paused()
+            };
+            if paused != settlement_paused {
+                settlement_paused = paused;
+                match paused {
+                    true => eprintln!("Chain health is uncertain. Pausing settlement."),
+                    false => println!("Chain health restored. Resuming settlement."),
+                }
+            }
+            if paused {
+                tokio::time::sleep(std::time::Duration::from_secs(
+                    SETTLEMENT_PAUSE_RECHECK_SECONDS,
+                ))
+                .await;
+                continue;
+            }
+        }
+
+        // 0.b Drain the work queue first, since the next batch builds on the last executed one.
+        drain_work_queue(
+            work_queue,
+            rebroadcast_queue,
+            webhooks,
+            operator_sessions,
+            session_pool,
+            rpc_holder,
+            engine_keyholder,
+            engine_key,
+            sync_manager,
+            utxo_set,
+            registery,
+            graveyard,
+            coin_manager,
+            flame_manager,
+            state_manager,
+            privileges_manager,
+            params_manager,
+            archival_manager,
+        )
+        .await;
+
         //
         // BEGINNING OF THE SESSION.
         //
@@ -64,12 +135,16 @@ pub async fn engine_batch_builder_background_task(
             current_execution_batch_height, current_execution_timestamp
         );
 
-        // 4 Retrieve mempool minimum fee rate (sat/vbyte).
-        let bitcoin_transaction_feerate = match get_mempool_min_fee_rate(rpc_holder) {
+        // 4 Estimate the commitment transaction fee rate (sat/vbyte).
+        let feerate = {
+            let mut _fee_estimator = fee_estimator.lock().await;
+            _fee_estimator.fee_rate(rpc_holder, FeePurpose::Commitment)
+        };
+        let bitcoin_transaction_feerate = match feerate {
             Ok(feerate) => feerate,
             Err(error) => {
                 eprintln!(
-                    "Failed to retrieve mempool minimum feerate: {}. Retrying in 5 seconds.",
+                    "Failed to estimate the commitment feerate: {}. Retrying in 5 seconds.",
                     error
                 );
                 tokio::time::sleep(std::time::Duration::from_secs(5)).await;
@@ -78,7 +153,7 @@ pub async fn engine_batch_builder_background_task(
         };
 
         println!(
-            "Mempool minimum feerate: {} sat/vbyte",
+            "Commitment feerate: {} sat/vbyte",
             bitcoin_transaction_feerate
         );
 
@@ -156,23 +231,92 @@ pub async fn engine_batch_builder_background_task(
             _session_pool.end_session().await;
         }
 
-        // 11 Broadcast raw transaction.
+        // 11 Enqueue the built batch for broadcast and execution.
         {
-            // 11.1 Encode the signed batch transaction bytes as a hex string.
-            let raw_transaction_hex =
-                hex::encode(batch_container.signed_batch_txn.serialize_bytes());
+            let mut _work_queue = work_queue.lock().await;
+            if let Err(error) = _work_queue.enqueue(WorkItem::new(batch_container)) {
+                eprintln!(
+                    "Failed to persist the built batch #{} in the work queue: {:?}.",
+                    current_execution_batch_height, error
+                );
+            }
+        }
 
-            // 11.2 Broadcast the raw transaction.
-            match broadcast_raw_transaction(rpc_holder, &raw_transaction_hex) {
-                Ok(_) => (),
-                Err(error) => {
-                    eprintln!("Failed to broadcast batch transaction: {:?}", error);
-                    continue;
-                }
+        //
+        // END OF THE SESSION.
+        //
+    }
+}
+
+/// Broadcasts and executes the pending built batches in batch height order, retrying failed
+/// attempts with exponential backoff until each one either completes or is dead-lettered.
+async fn drain_work_queue(
+    work_queue: &WORK_QUEUE,
+    rebroadcast_queue: &REBROADCAST_QUEUE,
+    webhooks: &WEBHOOKS,
+    operator_sessions: &OPERATOR_SESSIONS,
+    session_pool: &SESSION_POOL,
+    rpc_holder: &BitcoinRPCHolder,
+    engine_keyholder: &KeyHolder,
+    // Exec ctx params
+    engine_key: [u8; 32],
+    sync_manager: &SYNC_MANAGER,
+    utxo_set: &UTXO_SET,
+    registery: &REGISTERY,
+    graveyard: &GRAVEYARD,
+    coin_manager: &COIN_MANAGER,
+    flame_manager: &FLAME_MANAGER,
+    state_manager: &STATE_MANAGER,
+    privileges_manager: &PRIVILEGES_MANAGER,
+    params_manager: &PARAMS_MANAGER,
+    archival_manager: &Option<ARCHIVAL_MANAGER>,
+) {
+    loop {
+        // 1 Get the next pending work item, if any.
+        let mut work_item = {
+            let _work_queue = work_queue.lock().await;
+            match _work_queue.next_pending() {
+                Some(work_item) => work_item,
+                None => return,
             }
+        };
+        let batch_height = work_item.batch_height();
+
+        // 2 Wait for the backoff to elapse.
+        let now = Utc::now().timestamp() as u64;
+        if work_item.next_attempt_at > now {
+            tokio::time::sleep(std::time::Duration::from_secs(
+                work_item.next_attempt_at - now,
+            ))
+            .await;
         }
 
-        // 12 Construct ExecCtx.
+        // 3 Broadcast the batch transaction if not broadcast yet.
+        if work_item.stage == WorkStage::Broadcast {
+            // 3.1 Serialize the signed batch transaction.
+            let raw_transaction = work_item.batch_container.signed_batch_txn.serialize_bytes();
+
+            // 3.2 Broadcast the transaction and rebroadcast it until it confirms. It may already be
+            // confirmed if an earlier attempt went through.
+            if let Err(error) =
+                broadcast_transaction(rebroadcast_queue, rpc_holder, &raw_transaction).await
+            {
+                record_work_failure(
+                    work_queue,
+                    work_item,
+                    format!("Failed to broadcast batch transaction: {:?}", error),
+                )
+                .await;
+                continue;
+            }
+
+            // 3.3 Persist the stage so that a retry does not broadcast again.
+            work_item.stage = WorkStage::Execute;
+            let mut _work_queue = work_queue.lock().await;
+            let _ = _work_queue.enqueue(work_item.clone());
+        }
+
+        // 4 Construct ExecCtx.
         let exec_ctx = ExecCtx::construct(
             engine_key,
             Arc::clone(sync_manager),
@@ -187,13 +331,13 @@ pub async fn engine_batch_builder_background_task(
             archival_manager.clone(),
         );
 
-        // 13 Try to execute the batch container.
+        // 5 Try to execute the batch container.
         let execute_batch_result = {
             let mut _exec_ctx = exec_ctx.lock().await;
-            _exec_ctx.execute_batch(&batch_container).await
+            _exec_ctx.execute_batch(&work_item.batch_container).await
         };
 
-        // 14 Match the execute batch result.
+        // 6 Match the execute batch result.
         match execute_batch_result {
             Ok(batch_record) => {
                 println!(
@@ -201,15 +345,214 @@ pub async fn engine_batch_builder_background_task(
                     to_string_pretty(&batch_record.json())
                         .expect("serde_json::Value should serialize")
                 );
+
+                // 6.1 Co-sign the applied delta if the engine is a coordinator of a federation.
+                co_sign_applied_delta(
+                    session_pool,
+                    engine_keyholder,
+                    engine_key,
+                    batch_record.batch_height,
+                    work_item.batch_container.batch_txid(),
+                )
+                .await;
+
+                // 6.1.b Assign the rest of the co-signing work to the online federation coordinators.
+                assign_delta_cosign_work(
+                    session_pool,
+                    operator_sessions,
+                    batch_record.batch_height,
+                    work_item.batch_container.batch_txid(),
+                )
+                .await;
+
+                // 6.2 Remove the completed work item.
+                let mut _work_queue = work_queue.lock().await;
+                if let Err(error) = _work_queue.complete(batch_height) {
+                    eprintln!(
+                        "Failed to remove the completed batch #{} from the work queue: {:?}.",
+                        batch_height, error
+                    );
+                }
             }
             Err(error) => {
-                eprintln!("Failed to execute the batch container during batch builder background task: {:?} at batch height: #{}, timestamp: {}, and bitcoin transaction feerate: {}.", error, current_execution_batch_height, current_execution_timestamp, bitcoin_transaction_feerate);
-                continue;
+                // 6.3 Notify the webhooks of the failed execution.
+                notify_execution_failure(
+                    webhooks,
+                    batch_height,
+                    work_item.batch_container.batch_txid(),
+                    format!("{:?}", error),
+                )
+                .await;
+
+                record_work_failure(
+                    work_queue,
+                    work_item,
+                    format!("Failed to execute the batch container: {:?}", error),
+                )
+                .await;
             }
         }
+    }
+}
 
-        //
-        // END OF THE SESSION.
-        //
+/// Queues an execution failure webhook for a batch the engine failed to execute.
+async fn notify_execution_failure(
+    webhooks: &WEBHOOKS,
+    batch_height: u64,
+    batch_txid: [u8; 32],
+    error: String,
+) {
+    let mut obj = Map::new();
+    obj.insert(
+        "batch_height".to_string(),
+        Value::Number(batch_height.into()),
+    );
+    obj.insert(
+        "batch_txid".to_string(),
+        Value::String(hex::encode(batch_txid)),
+    );
+    obj.insert("error".to_string(), Value::String(error));
+
+    let mut _webhooks = webhooks.lock().await;
+    if let Err(err) = _webhooks.enqueue(
+        WebhookEventKind::ExecutionFailed,
+        None,
+        Value::Object(obj),
+        Utc::now().timestamp() as u64,
+    ) {
+        eprintln!(
+            "Failed to queue the execution failure webhook of batch #{}: {}.",
+            batch_height, err
+        );
+    }
+}
+
+/// Records a failed work attempt and reports its outcome.
+async fn record_work_failure(work_queue: &WORK_QUEUE, work_item: WorkItem, error: String) {
+    let batch_height = work_item.batch_height();
+    eprintln!("{} (batch #{}).", error, batch_height);
+
+    let outcome = {
+        let mut _work_queue = work_queue.lock().await;
+        _work_queue.record_failure(work_item, error, Utc::now().timestamp() as u64)
+    };
+
+    match outcome {
+        Ok(WorkFailureOutcome::RetryAt(next_attempt_at)) => {
+            eprintln!("Retrying batch #{} at {}.", batch_height, next_attempt_at)
+        }
+        Ok(WorkFailureOutcome::DeadLettered) => eprintln!(
+            "Batch #{} ran out of retries and was moved to the dead-letter list.",
+            batch_height
+        ),
+        Err(error) => eprintln!(
+            "Failed to persist the work queue failure of batch #{}: {:?}.",
+            batch_height, error
+        ),
+    }
+}
+
+/// Co-signs the applied delta of a batch, and the state root it led to, if the engine is a
+/// coordinator of a federation.
+async fn co_sign_applied_delta(
+    session_pool: &SESSION_POOL,
+    engine_keyholder: &KeyHolder,
+    engine_key: [u8; 32],
+    batch_height: u64,
+    batch_txid: [u8; 32],
+) {
+    let (delta_attestation_pool, coin_manager) = {
+        let _session_pool = session_pool.lock().await;
+        (
+            _session_pool.delta_attestation_pool.clone(),
+            Arc::clone(&_session_pool.coin_manager),
+        )
+    };
+    let Some(delta_attestation_pool) = delta_attestation_pool else {
+        return;
+    };
+    let state_root = coin_manager.lock().await.account_balances_state_root();
+
+    let mut _delta_attestation_pool = delta_attestation_pool.lock().await;
+    if !_delta_attestation_pool
+        .federation()
+        .is_coordinator(engine_key)
+    {
+        return;
     }
+
+    match DeltaCosignature::sign(engine_keyholder, batch_height, batch_txid) {
+        Some(cosignature) => {
+            if let Err(error) =
+                _delta_attestation_pool.insert_cosignature(batch_height, batch_txid, cosignature)
+            {
+                eprintln!(
+                    "Failed to co-sign the applied delta of batch #{}: {:?}.",
+                    batch_height, error
+                );
+            }
+        }
+        None => eprintln!(
+            "Failed to co-sign the applied delta of batch #{}.",
+            batch_height
+        ),
+    }
+
+    match StateRootCosignature::sign(engine_keyholder, batch_height, state_root) {
+        Some(cosignature) => {
+            if let Err(error) =
+                _delta_attestation_pool.insert_state_root_cosignature(batch_height, cosignature)
+            {
+                eprintln!(
+                    "Failed to co-sign the state root of batch #{}: {:?}.",
+                    batch_height, error
+                );
+            }
+        }
+        None => eprintln!(
+            "Failed to co-sign the state root of batch #{}.",
+            batch_height
+        ),
+    }
+}
+
+/// Assigns the co-signatures the applied delta of a batch still needs to reach a quorum to the
+/// online federation coordinators.
+async fn assign_delta_cosign_work(
+    session_pool: &SESSION_POOL,
+    operator_sessions: &OPERATOR_SESSIONS,
+    batch_height: u64,
+    batch_txid: [u8; 32],
+) {
+    // 1 Get the delta attestation pool, if a federation is configured.
+    let delta_attestation_pool = {
+        let _session_pool = session_pool.lock().await;
+        _session_pool.delta_attestation_pool.clone()
+    };
+    let Some(delta_attestation_pool) = delta_attestation_pool else {
+        return;
+    };
+
+    // 2 Count the co-signatures still missing for a quorum.
+    let missing_cosignatures = {
+        let _delta_attestation_pool = delta_attestation_pool.lock().await;
+        let collected_cosignatures = _delta_attestation_pool
+            .attestation(batch_height)
+            .map(|attestation| attestation.cosignatures.len())
+            .unwrap_or(0);
+        _delta_attestation_pool
+            .federation()
+            .threshold()
+            .saturating_sub(collected_cosignatures)
+    };
+
+    // 3 Assign the co-signing work.
+    let mut _operator_sessions = operator_sessions.lock().await;
+    _operator_sessions.assign(
+        WorkAssignment::DeltaCosign {
+            batch_height,
+            batch_txid,
+        },
+        missing_cosignatures,
+    );
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Errors associated with scheduling an entry execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecSchedulingError {
+    /// The account already has the maximum number of executions in flight.
+    AccountFairnessCapReached(usize),
+    /// The wait queue is full of executions with a higher or equal priority.
+    QueueFull,
+    /// The execution was evicted from the wait queue by a higher priority one.
+    Evicted,
+}
+
+impl ExecSchedulingError {
+    /// Returns the scheduling error as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        match self {
+            ExecSchedulingError::AccountFairnessCapReached(cap) => {
+                obj.insert(
+                    "kind".to_string(),
+                    Value::String("account_fairness_cap_reached".to_string()),
+                );
+                obj.insert("cap".to_string(), Value::Number((*cap as u64).into()));
+            }
+            ExecSchedulingError::QueueFull => {
+                obj.insert("kind".to_string(), Value::String("queue_full".to_string()));
+            }
+            ExecSchedulingError::Evicted => {
+                obj.insert("kind".to_string(), Value::String("evicted".to_string()));
+            }
+        }
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1 @@
+pub mod exec_scheduling_error;
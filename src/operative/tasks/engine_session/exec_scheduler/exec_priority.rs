@@ -0,0 +1,104 @@
+use crate::inscriptive::flame_manager::flame_manager::FLAME_MANAGER;
+use crate::inscriptive::registery::registery::REGISTERY;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract id.
+type ContractId = [u8; 32];
+
+/// Rank of a contract.
+type Rank = u64;
+
+/// Scheduling score; higher scores are admitted first.
+pub type ExecScore = u64;
+
+/// Contract ranks at or beyond this ceiling get no rank weight.
+const CONTRACT_RANK_WEIGHT_CEILING: u64 = 1_000;
+
+/// Satoshis of flame value per unit of flame weight.
+const SATOSHIS_PER_FLAME_WEIGHT: u64 = 1_000;
+
+/// The flame weight is capped so that flame value alone cannot drown out contract rank.
+const FLAME_WEIGHT_CEILING: u64 = 1_000;
+
+/// The scheduling priority of an entry execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecPriority {
+    /// Rank of the called contract, if the entry calls a contract and the contract is registered.
+    pub contract_rank: Option<Rank>,
+
+    /// Total satoshi value of the caller's flames.
+    pub caller_flame_value: u64,
+
+    /// Whether the entry calls a contract at all.
+    pub calls_contract: bool,
+}
+
+impl ExecPriority {
+    /// Constructs a new priority.
+    pub fn new(contract_rank: Option<Rank>, caller_flame_value: u64, calls_contract: bool) -> Self {
+        Self {
+            contract_rank,
+            caller_flame_value,
+            calls_contract,
+        }
+    }
+
+    /// Resolves the priority of an execution from the registery and the flame manager.
+    pub async fn resolve(
+        caller_key: AccountKey,
+        contract_id: Option<ContractId>,
+        registery: &REGISTERY,
+        flame_manager: &FLAME_MANAGER,
+    ) -> Self {
+        // 1 Resolve the rank of the called contract.
+        let contract_rank = match contract_id {
+            Some(contract_id) => {
+                let _registery = registery.lock().await;
+                _registery.get_rank_by_contract_id(contract_id)
+            }
+            None => None,
+        };
+
+        // 2 Sum up the value of the caller's flames.
+        let caller_flame_value = {
+            let _flame_manager = flame_manager.lock().await;
+            _flame_manager
+                .get_account_flame_set(caller_key)
+                .map(|flame_set| {
+                    flame_set
+                        .values()
+                        .flatten()
+                        .map(|(_, flame)| flame.satoshi_amount())
+                        .sum::<u64>()
+                })
+                .unwrap_or(0)
+        };
+
+        // 3 Return the priority.
+        Self::new(contract_rank, caller_flame_value, contract_id.is_some())
+    }
+
+    /// Returns the contract rank weight.
+    ///
+    /// Lower ranks weigh more. Entries that do not call a contract are weighted as a mid-ranked
+    /// contract so they are neither favoured over nor starved by contract calls.
+    pub fn rank_weight(&self) -> u64 {
+        match (self.calls_contract, self.contract_rank) {
+            (false, _) => CONTRACT_RANK_WEIGHT_CEILING / 2,
+            (true, Some(rank)) => CONTRACT_RANK_WEIGHT_CEILING.saturating_sub(rank),
+            (true, None) => 0,
+        }
+    }
+
+    /// Returns the caller flame weight.
+    pub fn flame_weight(&self) -> u64 {
+        (self.caller_flame_value / SATOSHIS_PER_FLAME_WEIGHT).min(FLAME_WEIGHT_CEILING)
+    }
+
+    /// Returns the scheduling score.
+    pub fn score(&self) -> ExecScore {
+        self.rank_weight() + self.flame_weight()
+    }
+}
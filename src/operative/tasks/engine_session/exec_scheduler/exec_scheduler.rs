@@ -0,0 +1,387 @@
+use crate::inscriptive::flame_manager::flame_manager::FLAME_MANAGER;
+use crate::inscriptive::registery::registery::REGISTERY;
+use crate::operative::tasks::engine_session::exec_scheduler::errors::exec_scheduling_error::ExecSchedulingError;
+use crate::operative::tasks::engine_session::exec_scheduler::exec_priority::{
+    ExecPriority, ExecScore,
+};
+use serde_json::{Map, Value};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Contract id.
+type ContractId = [u8; 32];
+
+/// Ticket id, assigned in arrival order.
+type TicketId = u64;
+
+/// The maximum number of executions admitted to contend for the session pool at once.
+pub const MAX_ADMITTED_EXECUTIONS: usize = 4;
+
+/// The maximum number of executions waiting for admission.
+pub const MAX_WAITING_EXECUTIONS: usize = 512;
+
+/// The maximum number of executions a single account may have admitted or waiting at once.
+pub const MAX_IN_FLIGHT_PER_ACCOUNT: usize = 4;
+
+/// Admission scheduler for entry executions in operator mode.
+///
+/// As long as there is spare capacity executions are admitted right away, in arrival order. Once more
+/// work is queued than there is capacity, waiting executions are admitted by their scheduling score,
+/// weighted by contract rank and caller flame value, with ties broken in arrival order. Each account
+/// is capped in the number of executions it may have in flight, so that no single caller can crowd
+/// out the others.
+pub struct ExecScheduler {
+    // The registery to resolve contract ranks from.
+    registery: REGISTERY,
+
+    // The flame manager to resolve caller flame values from.
+    flame_manager: FLAME_MANAGER,
+
+    // Waiting executions ordered by descending score, then by arrival.
+    waiting: BTreeMap<(Reverse<ExecScore>, TicketId), AccountKey>,
+
+    // Scores of the waiting executions by their ticket id.
+    waiting_scores: HashMap<TicketId, ExecScore>,
+
+    // Tickets evicted from the wait queue that are yet to be told so.
+    evicted: HashSet<TicketId>,
+
+    // Number of admitted or waiting executions per account.
+    in_flight_per_account: HashMap<AccountKey, usize>,
+
+    // Number of currently admitted executions.
+    admitted: usize,
+
+    // Next ticket id.
+    next_ticket_id: TicketId,
+
+    // Wakes up waiting executions whenever the queue changes.
+    notify: Arc<Notify>,
+}
+
+/// Guarded 'ExecScheduler'.
+#[allow(non_camel_case_types)]
+pub type EXEC_SCHEDULER = Arc<Mutex<ExecScheduler>>;
+
+impl ExecScheduler {
+    /// Constructs the execution scheduler.
+    pub fn new(registery: &REGISTERY, flame_manager: &FLAME_MANAGER) -> EXEC_SCHEDULER {
+        // 1 Construct the execution scheduler.
+        let exec_scheduler = ExecScheduler {
+            registery: Arc::clone(registery),
+            flame_manager: Arc::clone(flame_manager),
+            waiting: BTreeMap::new(),
+            waiting_scores: HashMap::new(),
+            evicted: HashSet::new(),
+            in_flight_per_account: HashMap::new(),
+            admitted: 0,
+            next_ticket_id: 0,
+            notify: Arc::new(Notify::new()),
+        };
+
+        // 2 Guard and return the execution scheduler.
+        Arc::new(Mutex::new(exec_scheduler))
+    }
+
+    /// Waits until an execution by the given caller is admitted.
+    ///
+    /// The execution stays admitted until the returned ticket is dropped.
+    pub async fn admit(
+        exec_scheduler: &EXEC_SCHEDULER,
+        caller_key: AccountKey,
+        contract_id: Option<ContractId>,
+    ) -> Result<ExecTicket, ExecSchedulingError> {
+        // 1 Admit right away if there is spare capacity and nothing is waiting.
+        let (registery, flame_manager, notify) = {
+            let mut _exec_scheduler = exec_scheduler.lock().await;
+            if let Some(ticket_id) = _exec_scheduler.try_admit_uncontended(caller_key)? {
+                return Ok(ExecTicket::new(exec_scheduler, ticket_id, caller_key, true));
+            }
+            (
+                Arc::clone(&_exec_scheduler.registery),
+                Arc::clone(&_exec_scheduler.flame_manager),
+                Arc::clone(&_exec_scheduler.notify),
+            )
+        };
+
+        // 2 Resolve the priority without holding the scheduler lock.
+        let priority =
+            ExecPriority::resolve(caller_key, contract_id, &registery, &flame_manager).await;
+
+        // 3 Join the wait queue.
+        let ticket_id = {
+            let mut _exec_scheduler = exec_scheduler.lock().await;
+            _exec_scheduler.enqueue(caller_key, priority.score())?
+        };
+
+        // 4 Hand out the ticket, so that the wait is abandoned if this future is dropped.
+        let mut ticket = ExecTicket::new(exec_scheduler, ticket_id, caller_key, false);
+
+        // 5 Wait for the turn of the ticket.
+        loop {
+            // 5.1 Register for the next wake up before checking, so that no wake up is missed.
+            let notified = notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            // 5.2 Check whether the ticket got admitted or evicted.
+            let admission = {
+                let mut _exec_scheduler = exec_scheduler.lock().await;
+                _exec_scheduler.try_admit_waiting(ticket_id)
+            };
+            match admission {
+                Some(Ok(())) => {
+                    ticket.admitted = true;
+                    return Ok(ticket);
+                }
+                Some(Err(err)) => {
+                    // The eviction already gave the slot up.
+                    ticket.released = true;
+                    return Err(err);
+                }
+                None => notified.await,
+            }
+        }
+    }
+
+    /// Admits an execution right away if there is spare capacity and nothing is waiting.
+    fn try_admit_uncontended(
+        &mut self,
+        caller_key: AccountKey,
+    ) -> Result<Option<TicketId>, ExecSchedulingError> {
+        // 1 Enforce the per-account fairness cap.
+        self.check_fairness_cap(caller_key)?;
+
+        // 2 Return early if the execution has to queue up.
+        if !self.waiting.is_empty() || self.admitted >= MAX_ADMITTED_EXECUTIONS {
+            return Ok(None);
+        }
+
+        // 3 Admit the execution.
+        let ticket_id = self.next_ticket_id();
+        *self.in_flight_per_account.entry(caller_key).or_insert(0) += 1;
+        self.admitted += 1;
+
+        // 4 Return the ticket id.
+        Ok(Some(ticket_id))
+    }
+
+    /// Adds an execution to the wait queue, evicting the lowest-scored waiting one if the queue is full.
+    pub fn enqueue(
+        &mut self,
+        caller_key: AccountKey,
+        score: ExecScore,
+    ) -> Result<TicketId, ExecSchedulingError> {
+        // 1 Enforce the per-account fairness cap.
+        self.check_fairness_cap(caller_key)?;
+
+        // 2 Make room if the queue is full.
+        if self.waiting.len() >= MAX_WAITING_EXECUTIONS {
+            // 2.1 Get the lowest-scored waiting execution.
+            let (lowest_score, lowest_ticket_id) = match self.waiting.keys().next_back() {
+                Some((Reverse(score), ticket_id)) => (*score, *ticket_id),
+                None => return Err(ExecSchedulingError::QueueFull),
+            };
+
+            // 2.2 Refuse the execution if it does not outrank the lowest-scored one.
+            if score <= lowest_score {
+                return Err(ExecSchedulingError::QueueFull);
+            }
+
+            // 2.3 Evict the lowest-scored one.
+            if let Some(account_key) = self.remove_waiting(lowest_ticket_id) {
+                self.decrement_in_flight(account_key);
+            }
+            self.evicted.insert(lowest_ticket_id);
+            self.notify.notify_waiters();
+        }
+
+        // 3 Add the execution to the queue.
+        let ticket_id = self.next_ticket_id();
+        self.waiting.insert((Reverse(score), ticket_id), caller_key);
+        self.waiting_scores.insert(ticket_id, score);
+        *self.in_flight_per_account.entry(caller_key).or_insert(0) += 1;
+
+        // 4 Return the ticket id.
+        Ok(ticket_id)
+    }
+
+    /// Admits a waiting execution if it is at the head of the queue and there is spare capacity.
+    ///
+    /// Returns `None` if the execution has to keep waiting.
+    pub fn try_admit_waiting(
+        &mut self,
+        ticket_id: TicketId,
+    ) -> Option<Result<(), ExecSchedulingError>> {
+        // 1 Tell the execution if it got evicted.
+        if self.evicted.remove(&ticket_id) {
+            return Some(Err(ExecSchedulingError::Evicted));
+        }
+
+        // 2 Keep waiting if there is no spare capacity.
+        if self.admitted >= MAX_ADMITTED_EXECUTIONS {
+            return None;
+        }
+
+        // 3 Keep waiting if the execution is not at the head of the queue.
+        match self.waiting.keys().next() {
+            Some((_, head_ticket_id)) if *head_ticket_id == ticket_id => {}
+            _ => return None,
+        }
+
+        // 4 Move the execution from the queue to the admitted ones.
+        self.remove_waiting(ticket_id);
+        self.admitted += 1;
+
+        // 5 Wake up the next in line, as there may still be spare capacity.
+        self.notify.notify_waiters();
+
+        Some(Ok(()))
+    }
+
+    /// Releases an admitted execution.
+    pub fn release(&mut self, caller_key: AccountKey) {
+        self.admitted = self.admitted.saturating_sub(1);
+        self.decrement_in_flight(caller_key);
+        self.notify.notify_waiters();
+    }
+
+    /// Abandons a waiting execution, e.g. when its connection has dropped.
+    pub fn abandon(&mut self, ticket_id: TicketId, caller_key: AccountKey) {
+        // 1 An evicted execution has already given up its place.
+        if self.evicted.remove(&ticket_id) {
+            return;
+        }
+
+        // 2 Remove the execution from the queue.
+        if self.remove_waiting(ticket_id).is_some() {
+            self.decrement_in_flight(caller_key);
+            self.notify.notify_waiters();
+        }
+    }
+
+    /// Returns the number of currently admitted executions.
+    pub fn admitted(&self) -> usize {
+        self.admitted
+    }
+
+    /// Returns the number of waiting executions.
+    pub fn waiting(&self) -> usize {
+        self.waiting.len()
+    }
+
+    /// Returns the scheduler load as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "admitted".to_string(),
+            Value::Number((self.admitted as u64).into()),
+        );
+        obj.insert(
+            "waiting".to_string(),
+            Value::Number((self.waiting.len() as u64).into()),
+        );
+        obj.insert(
+            "accounts_in_flight".to_string(),
+            Value::Number((self.in_flight_per_account.len() as u64).into()),
+        );
+        Value::Object(obj)
+    }
+
+    /// Checks that the account has not reached its in-flight cap.
+    fn check_fairness_cap(&self, caller_key: AccountKey) -> Result<(), ExecSchedulingError> {
+        let in_flight = self
+            .in_flight_per_account
+            .get(&caller_key)
+            .copied()
+            .unwrap_or(0);
+        if in_flight >= MAX_IN_FLIGHT_PER_ACCOUNT {
+            return Err(ExecSchedulingError::AccountFairnessCapReached(
+                MAX_IN_FLIGHT_PER_ACCOUNT,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Removes a waiting execution from the queue and returns its account key.
+    fn remove_waiting(&mut self, ticket_id: TicketId) -> Option<AccountKey> {
+        let score = self.waiting_scores.remove(&ticket_id)?;
+        self.waiting.remove(&(Reverse(score), ticket_id))
+    }
+
+    /// Decrements the in-flight count of an account.
+    fn decrement_in_flight(&mut self, caller_key: AccountKey) {
+        if let Some(in_flight) = self.in_flight_per_account.get_mut(&caller_key) {
+            *in_flight = in_flight.saturating_sub(1);
+            if *in_flight == 0 {
+                self.in_flight_per_account.remove(&caller_key);
+            }
+        }
+    }
+
+    /// Returns the next ticket id.
+    fn next_ticket_id(&mut self) -> TicketId {
+        let ticket_id = self.next_ticket_id;
+        self.next_ticket_id += 1;
+        ticket_id
+    }
+}
+
+/// A place in the execution scheduler.
+///
+/// Dropping the ticket releases the admitted slot, or abandons the place in the queue.
+pub struct ExecTicket {
+    exec_scheduler: EXEC_SCHEDULER,
+    ticket_id: TicketId,
+    caller_key: AccountKey,
+    admitted: bool,
+    released: bool,
+}
+
+impl ExecTicket {
+    fn new(
+        exec_scheduler: &EXEC_SCHEDULER,
+        ticket_id: TicketId,
+        caller_key: AccountKey,
+        admitted: bool,
+    ) -> Self {
+        Self {
+            exec_scheduler: Arc::clone(exec_scheduler),
+            ticket_id,
+            caller_key,
+            admitted,
+            released: false,
+        }
+    }
+}
+
+impl Drop for ExecTicket {
+    fn drop(&mut self) {
+        // 1 Nothing to do if the place was already given up.
+        if self.released {
+            return;
+        }
+
+        // 2 Give up the place, deferring to a task if the scheduler is busy.
+        let (ticket_id, caller_key, admitted) = (self.ticket_id, self.caller_key, self.admitted);
+        let give_up = move |exec_scheduler: &mut ExecScheduler| match admitted {
+            true => exec_scheduler.release(caller_key),
+            false => exec_scheduler.abandon(ticket_id, caller_key),
+        };
+        match self.exec_scheduler.try_lock() {
+            Ok(mut _exec_scheduler) => give_up(&mut _exec_scheduler),
+            Err(_) => {
+                let exec_scheduler = Arc::clone(&self.exec_scheduler);
+                tokio::spawn(async move {
+                    let mut _exec_scheduler = exec_scheduler.lock().await;
+                    give_up(&mut _exec_scheduler);
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod errors;
+pub mod exec_priority;
+pub mod exec_scheduler;
@@ -1,2 +1,4 @@
 pub mod engine_session;
+pub mod exec_scheduler;
 pub mod session_pool;
+pub mod work_queue;
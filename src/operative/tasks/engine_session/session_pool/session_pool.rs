@@ -1,3 +1,7 @@
+use crate::communicative::federation::delta_attestation_pool::{
+    DeltaAttestationPool, DELTA_ATTESTATION_POOL,
+};
+use crate::communicative::federation::federation::Federation;
 use crate::constructive::bitcoiny::batch_container::batch_container::BatchContainer;
 use crate::constructive::bitcoiny::batch_txn::signed_batch_txn::signed_batch_txn::SignedBatchTxn;
 use crate::constructive::entry::entry::entry::Entry;
@@ -117,6 +121,9 @@ pub struct SessionPool {
 
     // The individual `Entry` BLS signatures that have been added.
     pub added_individual_entry_bls_signatures: Vec<[u8; 96]>,
+
+    // The co-signatures collected over applied deltas (only when running as part of a federation).
+    pub delta_attestation_pool: Option<DELTA_ATTESTATION_POOL>,
 }
 
 /// Guarded `SessionPool`.
@@ -137,6 +144,7 @@ impl SessionPool {
         privileges_manager: &PRIVILEGES_MANAGER,
         params_manager: &PARAMS_MANAGER,
         archival_manager: Option<ARCHIVAL_MANAGER>,
+        federation: Option<Federation>,
     ) -> SESSION_POOL {
         // 1 Construct the exec context.
         let exec_ctx = ExecCtx::construct(
@@ -170,6 +178,7 @@ impl SessionPool {
             exec_ctx,
             added_entries: Vec::new(),
             added_individual_entry_bls_signatures: Vec::new(),
+            delta_attestation_pool: federation.map(DeltaAttestationPool::new),
         };
 
         // 3 Guard the session pool.
@@ -180,7 +189,7 @@ impl SessionPool {
     }
 
     /// Flushes the `SessionPool`.
-    async fn flush(&mut self) {
+    pub async fn flush(&mut self) {
         // 1 Flush the execution context.
         {
             // 1.1 Lock the execution context.
@@ -0,0 +1 @@
+pub mod work_queue_construction_error;
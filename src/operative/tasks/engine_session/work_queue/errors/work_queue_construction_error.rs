@@ -0,0 +1,10 @@
+/// Batch height.
+type BatchHeight = u64;
+
+/// Errors associated with constructing the work queue.
+#[derive(Debug, Clone)]
+pub enum WorkQueueConstructionError {
+    DBOpenError(sled::Error),
+    TreeOpenError(sled::Error),
+    CorruptWorkItem(BatchHeight),
+}
@@ -0,0 +1,3 @@
+pub mod errors;
+pub mod work_item;
+pub mod work_queue;
@@ -0,0 +1,97 @@
+use crate::constructive::bitcoiny::batch_container::batch_container::BatchContainer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The stage a work item resumes from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkStage {
+    // The batch transaction is yet to be broadcast.
+    Broadcast,
+    // The batch transaction is broadcast; the batch is yet to be executed.
+    Execute,
+}
+
+impl ToString for WorkStage {
+    fn to_string(&self) -> String {
+        match self {
+            WorkStage::Broadcast => "broadcast".to_string(),
+            WorkStage::Execute => "execute".to_string(),
+        }
+    }
+}
+
+/// A built batch pending broadcast and execution by the Engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkItem {
+    // The built batch container.
+    pub batch_container: BatchContainer,
+
+    // The stage to resume from.
+    pub stage: WorkStage,
+
+    // Number of failed attempts so far.
+    pub attempts: u32,
+
+    // Unix timestamp (seconds) before which the item is not retried.
+    pub next_attempt_at: u64,
+
+    // The error of the last failed attempt.
+    pub last_error: Option<String>,
+}
+
+impl WorkItem {
+    /// Constructs a fresh work item for a built batch container.
+    pub fn new(batch_container: BatchContainer) -> Self {
+        Self {
+            batch_container,
+            stage: WorkStage::Broadcast,
+            attempts: 0,
+            next_attempt_at: 0,
+            last_error: None,
+        }
+    }
+
+    /// Returns the batch height of the work item.
+    pub fn batch_height(&self) -> u64 {
+        self.batch_container.batch_height()
+    }
+
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a work item from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(work_item, _)| work_item)
+    }
+
+    /// Returns the work item as a JSON object, without the batch container body.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "batch_height".to_string(),
+            Value::Number(self.batch_height().into()),
+        );
+        obj.insert(
+            "batch_txid".to_string(),
+            Value::String(hex::encode(self.batch_container.batch_txid())),
+        );
+        obj.insert("stage".to_string(), Value::String(self.stage.to_string()));
+        obj.insert("attempts".to_string(), Value::Number(self.attempts.into()));
+        obj.insert(
+            "next_attempt_at".to_string(),
+            Value::Number(self.next_attempt_at.into()),
+        );
+        obj.insert(
+            "last_error".to_string(),
+            match &self.last_error {
+                Some(error) => Value::String(error.clone()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
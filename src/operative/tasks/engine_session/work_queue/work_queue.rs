@@ -0,0 +1,201 @@
+use crate::operative::run_args::chain::Chain;
+use crate::operative::tasks::engine_session::work_queue::errors::work_queue_construction_error::WorkQueueConstructionError;
+use crate::operative::tasks::engine_session::work_queue::work_item::WorkItem;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Batch height.
+type BatchHeight = u64;
+
+/// Batch txid.
+type BatchTxid = [u8; 32];
+
+/// Number of failed attempts after which a work item is dead-lettered.
+const MAX_WORK_ATTEMPTS: u32 = 6;
+
+/// Backoff after the first failed attempt in seconds; doubled on every further failure.
+const BASE_BACKOFF_SECONDS: u64 = 5;
+
+/// Upper bound of the backoff in seconds.
+const MAX_BACKOFF_SECONDS: u64 = 600;
+
+/// Outcome of recording a failed attempt.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WorkFailureOutcome {
+    // Retried at the given unix timestamp.
+    RetryAt(u64),
+    // Moved to the dead-letter list.
+    DeadLettered,
+}
+
+/// Persistent queue of built batches pending broadcast and execution, with a dead-letter list
+/// for those that exhausted their retries.
+pub struct WorkQueue {
+    // In-memory pending items by batch height.
+    pending: BTreeMap<BatchHeight, WorkItem>,
+
+    // In-memory dead-lettered items by batch txid.
+    dead_letters: BTreeMap<BatchTxid, WorkItem>,
+
+    // On-disk pending items.
+    on_disk_pending: sled::Tree,
+
+    // On-disk dead-lettered items.
+    on_disk_dead_letters: sled::Tree,
+}
+
+/// Guarded 'WorkQueue'.
+#[allow(non_camel_case_types)]
+pub type WORK_QUEUE = Arc<Mutex<WorkQueue>>;
+
+impl WorkQueue {
+    pub fn new(chain: Chain) -> Result<WORK_QUEUE, WorkQueueConstructionError> {
+        // 1 Open the work queue db.
+        let db_path = format!("storage/{}/work_queue", chain.to_string());
+        let db = sled::open(db_path).map_err(WorkQueueConstructionError::DBOpenError)?;
+
+        // 2 Open the pending & dead-letter trees.
+        let on_disk_pending = db
+            .open_tree("pending")
+            .map_err(WorkQueueConstructionError::TreeOpenError)?;
+        let on_disk_dead_letters = db
+            .open_tree("dead_letters")
+            .map_err(WorkQueueConstructionError::TreeOpenError)?;
+
+        // 3 Load the pending items.
+        let mut pending = BTreeMap::new();
+        for (key, value) in on_disk_pending.iter().filter_map(|item| item.ok()) {
+            let batch_height = key
+                .as_ref()
+                .try_into()
+                .map(u64::from_be_bytes)
+                .unwrap_or_default();
+            let work_item = WorkItem::deserialize(value.as_ref())
+                .ok_or(WorkQueueConstructionError::CorruptWorkItem(batch_height))?;
+            pending.insert(work_item.batch_height(), work_item);
+        }
+
+        // 4 Load the dead-lettered items.
+        let mut dead_letters = BTreeMap::new();
+        for (_, value) in on_disk_dead_letters.iter().filter_map(|item| item.ok()) {
+            if let Some(work_item) = WorkItem::deserialize(value.as_ref()) {
+                dead_letters.insert(work_item.batch_container.batch_txid(), work_item);
+            }
+        }
+
+        // 5 Construct the work queue.
+        let work_queue = WorkQueue {
+            pending,
+            dead_letters,
+            on_disk_pending,
+            on_disk_dead_letters,
+        };
+
+        // 6 Guard and return the work queue.
+        Ok(Arc::new(Mutex::new(work_queue)))
+    }
+
+    /// Adds or replaces a pending work item.
+    ///
+    /// The item is kept in memory even if persisting it fails.
+    pub fn enqueue(&mut self, work_item: WorkItem) -> Result<(), sled::Error> {
+        let batch_height = work_item.batch_height();
+        let bytes = work_item.serialize().unwrap_or_default();
+        self.pending.insert(batch_height, work_item);
+        self.on_disk_pending
+            .insert(batch_height.to_be_bytes(), bytes)?;
+        Ok(())
+    }
+
+    /// Returns the pending work item with the lowest batch height.
+    pub fn next_pending(&self) -> Option<WorkItem> {
+        self.pending.values().next().cloned()
+    }
+
+    /// Whether there is no pending work.
+    pub fn is_idle(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Removes a completed work item.
+    pub fn complete(&mut self, batch_height: BatchHeight) -> Result<(), sled::Error> {
+        self.on_disk_pending.remove(batch_height.to_be_bytes())?;
+        self.pending.remove(&batch_height);
+        Ok(())
+    }
+
+    /// Records a failed attempt, scheduling a retry with exponential backoff or dead-lettering
+    /// the item once it runs out of attempts.
+    pub fn record_failure(
+        &mut self,
+        mut work_item: WorkItem,
+        error: String,
+        now: u64,
+    ) -> Result<WorkFailureOutcome, sled::Error> {
+        // 1 Record the attempt.
+        work_item.attempts += 1;
+        work_item.last_error = Some(error);
+
+        // 2 Dead-letter the item if it ran out of attempts.
+        if work_item.attempts >= MAX_WORK_ATTEMPTS {
+            self.complete(work_item.batch_height())?;
+            let bytes = work_item.serialize().unwrap_or_default();
+            let batch_txid = work_item.batch_container.batch_txid();
+            self.on_disk_dead_letters.insert(batch_txid, bytes)?;
+            self.dead_letters.insert(batch_txid, work_item);
+            return Ok(WorkFailureOutcome::DeadLettered);
+        }
+
+        // 3 Otherwise schedule the retry.
+        let backoff = BASE_BACKOFF_SECONDS
+            .saturating_mul(1 << (work_item.attempts - 1))
+            .min(MAX_BACKOFF_SECONDS);
+        work_item.next_attempt_at = now + backoff;
+        let next_attempt_at = work_item.next_attempt_at;
+        self.enqueue(work_item)?;
+
+        Ok(WorkFailureOutcome::RetryAt(next_attempt_at))
+    }
+
+    /// Moves a dead-lettered item back to the pending queue with a fresh retry budget.
+    ///
+    /// Returns false if there is no such dead-lettered item.
+    pub fn requeue_dead_letter(&mut self, batch_txid: BatchTxid) -> Result<bool, sled::Error> {
+        let mut work_item = match self.dead_letters.remove(&batch_txid) {
+            Some(work_item) => work_item,
+            None => return Ok(false),
+        };
+        self.on_disk_dead_letters.remove(batch_txid)?;
+
+        work_item.attempts = 0;
+        work_item.next_attempt_at = 0;
+        self.enqueue(work_item)?;
+
+        Ok(true)
+    }
+
+    /// Returns the work queue as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "pending".to_string(),
+            Value::Array(self.pending.values().map(WorkItem::json).collect()),
+        );
+        obj.insert(
+            "dead_letters".to_string(),
+            Value::Array(self.dead_letters.values().map(WorkItem::json).collect()),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Erases the work queue by db path.
+pub fn erase_work_queue(chain: Chain) {
+    // Work queue db path.
+    let db_path = format!("storage/{}/work_queue", chain.to_string());
+
+    // Erase the work queue db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
@@ -0,0 +1,24 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::{
+    BitcoinRPCEstimateSmartFeeError, BitcoinRPCGetMempoolFeeRateError,
+};
+use std::fmt;
+
+/// Errors associated with estimating the fee rate of an on-chain transaction.
+#[derive(Debug)]
+pub enum FeeEstimatorError {
+    /// The fee rate could not be estimated by the Bitcoin node.
+    EstimateErr(BitcoinRPCEstimateSmartFeeError),
+    /// The mempool minimum fee rate could not be retrieved from the Bitcoin node.
+    MempoolFeeRateErr(BitcoinRPCGetMempoolFeeRateError),
+}
+
+impl fmt::Display for FeeEstimatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeEstimatorError::EstimateErr(err) => write!(f, "Fee estimation failed: {}", err),
+            FeeEstimatorError::MempoolFeeRateErr(err) => {
+                write!(f, "Mempool minimum fee rate retrieval failed: {}", err)
+            }
+        }
+    }
+}
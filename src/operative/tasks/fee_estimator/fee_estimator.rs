@@ -0,0 +1,153 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::{
+    estimate_smart_fee, get_mempool_min_fee_rate,
+};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::operative::tasks::fee_estimator::errors::fee_estimator_error::FeeEstimatorError;
+use crate::operative::tasks::fee_estimator::fee_estimator_config::{
+    FeeEstimatorConfig, FeePurpose,
+};
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// A fee rate handed out for an on-chain transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimate {
+    // Fee rate in sat/vbyte.
+    pub fee_rate: u64,
+
+    // Whether the fee rate was estimated by the Bitcoin node, rather than falling back to the mempool
+    // minimum fee rate.
+    pub estimated: bool,
+
+    // Unix timestamp of the estimate.
+    pub estimated_at: u64,
+}
+
+/// Fee rates of the on-chain transactions built by the engine.
+///
+/// Each purpose is estimated with `estimatesmartfee` for its confirmation target, and never goes below
+/// the mempool minimum fee rate, so that the transaction is relayed, nor above the configured cap,
+/// unless the mempool minimum fee rate itself is above it. Without an estimate from the Bitcoin node,
+/// the mempool minimum fee rate is used.
+pub struct FeeEstimator {
+    // Fee estimation configuration.
+    config: FeeEstimatorConfig,
+
+    // Last estimate of each purpose.
+    last_estimates: HashMap<FeePurpose, FeeEstimate>,
+
+    // Number of estimates that fell back to the mempool minimum fee rate.
+    fallbacks: u64,
+}
+
+/// Guarded 'FeeEstimator'.
+#[allow(non_camel_case_types)]
+pub type FEE_ESTIMATOR = Arc<Mutex<FeeEstimator>>;
+
+impl FeeEstimator {
+    /// Constructs the fee estimator with no estimates made yet.
+    pub fn new(config: FeeEstimatorConfig) -> FEE_ESTIMATOR {
+        Arc::new(Mutex::new(Self {
+            config,
+            last_estimates: HashMap::new(),
+            fallbacks: 0,
+        }))
+    }
+
+    /// Returns the fee rate in sat/vbyte for a transaction of the given purpose.
+    pub fn fee_rate(
+        &mut self,
+        rpc_holder: &BitcoinRPCHolder,
+        purpose: FeePurpose,
+    ) -> Result<u64, FeeEstimatorError> {
+        // 1 Estimate the fee rate for the confirmation target.
+        let target = self.config.target_for(purpose);
+        let estimate = estimate_smart_fee(rpc_holder, target.conf_target, target.estimate_mode)
+            .map_err(FeeEstimatorError::EstimateErr)?;
+
+        // 2 Retrieve the mempool minimum fee rate.
+        let mempool_min_fee_rate =
+            get_mempool_min_fee_rate(rpc_holder).map_err(FeeEstimatorError::MempoolFeeRateErr)?;
+
+        // 3 Bound the estimate.
+        let fee_rate =
+            Self::bounded_fee_rate(estimate, mempool_min_fee_rate, self.config.max_fee_rate);
+
+        // 4 Record the estimate.
+        if estimate.is_none() {
+            self.fallbacks += 1;
+        }
+        self.last_estimates.insert(
+            purpose,
+            FeeEstimate {
+                fee_rate,
+                estimated: estimate.is_some(),
+                estimated_at: Utc::now().timestamp() as u64,
+            },
+        );
+
+        Ok(fee_rate)
+    }
+
+    /// Bounds an estimated fee rate by the mempool minimum fee rate and the cap, falling back to the
+    /// mempool minimum fee rate without an estimate.
+    pub fn bounded_fee_rate(
+        estimate: Option<u64>,
+        mempool_min_fee_rate: u64,
+        max_fee_rate: u64,
+    ) -> u64 {
+        estimate
+            .unwrap_or(mempool_min_fee_rate)
+            .min(max_fee_rate)
+            .max(mempool_min_fee_rate)
+    }
+
+    /// Returns the last estimate of the given purpose, if any.
+    pub fn last_estimate(&self, purpose: FeePurpose) -> Option<&FeeEstimate> {
+        self.last_estimates.get(&purpose)
+    }
+
+    /// Returns the configuration and the last estimates as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        for purpose in [FeePurpose::Commitment, FeePurpose::Payout] {
+            let target = self.config.target_for(purpose);
+            let mut purpose_obj = Map::new();
+            purpose_obj.insert(
+                "conf_target".to_string(),
+                Value::Number(target.conf_target.into()),
+            );
+            purpose_obj.insert(
+                "estimate_mode".to_string(),
+                Value::String(target.mode_name().to_string()),
+            );
+            if let Some(last_estimate) = self.last_estimate(purpose) {
+                purpose_obj.insert(
+                    "fee_rate".to_string(),
+                    Value::Number(last_estimate.fee_rate.into()),
+                );
+                purpose_obj.insert(
+                    "estimated".to_string(),
+                    Value::Bool(last_estimate.estimated),
+                );
+                purpose_obj.insert(
+                    "estimated_at".to_string(),
+                    Value::Number(last_estimate.estimated_at.into()),
+                );
+            }
+            obj.insert(purpose.name().to_string(), Value::Object(purpose_obj));
+        }
+        obj.insert(
+            "max_fee_rate".to_string(),
+            Value::Number(self.config.max_fee_rate.into()),
+        );
+        obj.insert(
+            "fallbacks".to_string(),
+            Value::Number(self.fallbacks.into()),
+        );
+        Value::Object(obj)
+    }
+}
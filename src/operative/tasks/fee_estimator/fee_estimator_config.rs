@@ -0,0 +1,125 @@
+use bitcoincore_rpc::json::EstimateMode;
+
+/// Default confirmation target of commitment transactions in blocks.
+const DEFAULT_COMMITMENT_TARGET_BLOCKS: u16 = 2;
+
+/// Default confirmation target of payout transactions in blocks.
+const DEFAULT_PAYOUT_TARGET_BLOCKS: u16 = 6;
+
+/// Default cap on estimated fee rates in sat/vbyte.
+const DEFAULT_MAX_FEE_RATE: u64 = 1_000;
+
+/// What an on-chain transaction is for, each purpose with its own confirmation target.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FeePurpose {
+    /// Batch transactions committing the Cube state on-chain.
+    Commitment,
+    /// Transactions paying out to Bitcoin addresses.
+    Payout,
+}
+
+impl FeePurpose {
+    /// Returns the name of the purpose.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FeePurpose::Commitment => "commitment",
+            FeePurpose::Payout => "payout",
+        }
+    }
+}
+
+/// Confirmation target of a fee estimate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FeeTarget {
+    // Number of blocks to confirm within.
+    pub conf_target: u16,
+
+    // Whether to estimate conservatively, accounting for longer fee history, or economically.
+    pub estimate_mode: EstimateMode,
+}
+
+impl FeeTarget {
+    /// Constructs a fee target.
+    pub fn new(conf_target: u16, estimate_mode: EstimateMode) -> Self {
+        Self {
+            // Bitcoin Core estimates for 1 to 1008 blocks.
+            conf_target: conf_target.clamp(1, 1_008),
+            estimate_mode,
+        }
+    }
+
+    /// Parses a fee target from `<blocks>` or `<blocks>:<conservative|economical>`, conservative by default.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (blocks, mode) = match value.trim().split_once(':') {
+            Some((blocks, mode)) => (blocks, Some(mode)),
+            None => (value.trim(), None),
+        };
+        let conf_target = blocks.trim().parse::<u16>().ok()?;
+        let estimate_mode = match mode.map(|mode| mode.trim().to_lowercase()).as_deref() {
+            None | Some("conservative") => EstimateMode::Conservative,
+            Some("economical") => EstimateMode::Economical,
+            Some(_) => return None,
+        };
+        Some(Self::new(conf_target, estimate_mode))
+    }
+
+    /// Returns the name of the estimate mode.
+    pub fn mode_name(&self) -> &'static str {
+        match self.estimate_mode {
+            EstimateMode::Economical => "economical",
+            EstimateMode::Conservative => "conservative",
+            EstimateMode::Unset => "unset",
+        }
+    }
+}
+
+/// Fee estimation configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimatorConfig {
+    // Confirmation target of commitment transactions.
+    pub commitment: FeeTarget,
+
+    // Confirmation target of payout transactions.
+    pub payout: FeeTarget,
+
+    // Cap on estimated fee rates in sat/vbyte.
+    pub max_fee_rate: u64,
+}
+
+impl FeeEstimatorConfig {
+    /// Reads the fee estimation configuration from the environment.
+    ///
+    /// `CUBE_FEE_COMMITMENT_TARGET` and `CUBE_FEE_PAYOUT_TARGET` optionally override the confirmation
+    /// targets, as `<blocks>` or `<blocks>:<conservative|economical>`; malformed values are ignored.
+    /// `CUBE_FEE_MAX_RATE` optionally overrides the cap on estimated fee rates in sat/vbyte.
+    pub fn from_env() -> Self {
+        let read_target = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| FeeTarget::parse(&value))
+        };
+
+        Self {
+            commitment: read_target("CUBE_FEE_COMMITMENT_TARGET").unwrap_or(FeeTarget::new(
+                DEFAULT_COMMITMENT_TARGET_BLOCKS,
+                EstimateMode::Conservative,
+            )),
+            payout: read_target("CUBE_FEE_PAYOUT_TARGET").unwrap_or(FeeTarget::new(
+                DEFAULT_PAYOUT_TARGET_BLOCKS,
+                EstimateMode::Economical,
+            )),
+            max_fee_rate: std::env::var("CUBE_FEE_MAX_RATE")
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(DEFAULT_MAX_FEE_RATE),
+        }
+    }
+
+    /// Returns the confirmation target of the given purpose.
+    pub fn target_for(&self, purpose: FeePurpose) -> FeeTarget {
+        match purpose {
+            FeePurpose::Commitment => self.commitment,
+            FeePurpose::Payout => self.payout,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod errors;
+pub mod fee_estimator;
+pub mod fee_estimator_config;
@@ -1,6 +1,20 @@
-use crate::communicative::peer::peer::PEER;
+use crate::communicative::federation::contract_notice::{ContractNotice, ContractRegistration};
+use crate::communicative::federation::delta_attestation::DeltaCosignature;
+use crate::communicative::federation::federation::Federation;
+use crate::communicative::federation::state_root_cosignature::StateRootCosignature;
+use crate::communicative::gossip::contract_notice_index::CONTRACT_NOTICE_INDEX;
+use crate::communicative::gossip::delta_gossip_pool::DELTA_GOSSIP_POOL;
+use crate::communicative::outbox::outbox::OUTBOX;
+use crate::communicative::peer::peer::{PeerConnection, PEER};
 use crate::communicative::tcp::client::TCPClient;
+use crate::communicative::tcp::package::PackageKind;
+use crate::communicative::tcp::protocol::delta_cosign::{
+    DeltaCosignRequestBody, DeltaCosignResponseBody,
+};
 use crate::communicative::tcp::protocol::in_flight_sync::InFlightSyncResponseBody;
+use crate::communicative::tcp::request_error::RequestError;
+use crate::constructive::bitcoiny::batch_record::batch_record::BatchRecord;
+use crate::constructive::entry::entry::entry::Entry;
 use crate::executive::exec_ctx::exec_ctx::ExecCtx;
 use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
 use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
@@ -12,12 +26,26 @@ use crate::inscriptive::registery::registery::REGISTERY;
 use crate::inscriptive::state_manager::state_manager::STATE_MANAGER;
 use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
 use crate::inscriptive::utxo_set::utxo_set::UTXO_SET;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
 use std::sync::Arc;
 use std::time::Duration;
 
 /// Node background loop to fetch in-flight Cube batches from the Engine one-by-one.
+///
+/// When the chain runs a federation, batches are only applied once they carry a quorum of
+/// coordinator co-signatures; coordinators themselves apply, then co-sign back to the Engine. The
+/// quorum may also come from delta attestations gossiped by other nodes, and quorums received from the
+/// Engine are gossiped on in turn. Coordinators also sign a notice of each contract registered by an
+/// applied batch, to be broadcast to other nodes.
 pub async fn in_flight_batch_sync_background_task(
     engine_conn: &PEER,
+    federation: &Option<Federation>,
+    delta_gossip_pool: &Option<DELTA_GOSSIP_POOL>,
+    contract_notice_index: &Option<CONTRACT_NOTICE_INDEX>,
+    outbox: &OUTBOX,
+    key_holder: &KeyHolder,
     sync_manager: &SYNC_MANAGER,
     engine_key: [u8; 32],
     utxo_set: &UTXO_SET,
@@ -42,7 +70,12 @@ pub async fn in_flight_batch_sync_background_task(
         {
             Ok((response_body, _)) => response_body,
             Err(error) => {
-                eprintln!("In-flight sync request failed: {:?}. Retrying in 5s...", error);
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!(
+                        "In-flight sync request failed: {:?}. Retrying in 5s...",
+                        error
+                    );
+                }
                 tokio::time::sleep(Duration::from_secs(5)).await;
                 continue;
             }
@@ -53,7 +86,54 @@ pub async fn in_flight_batch_sync_background_task(
                 tokio::time::sleep(Duration::from_secs(5)).await;
                 continue;
             }
-            InFlightSyncResponseBody::BatchDownload(batch_container) => {
+            InFlightSyncResponseBody::BatchDownload(batch_container, delta_attestation) => {
+                let batch_height = batch_container.batch_height();
+                let batch_txid = batch_container.batch_txid();
+
+                // Check whether this node co-signs deltas rather than awaiting a quorum.
+                let is_coordinator = match federation {
+                    Some(federation) => {
+                        federation.is_coordinator(key_holder.secp_public_key_bytes())
+                    }
+                    None => false,
+                };
+
+                // Non-coordinators only accept deltas carrying a quorum of coordinator co-signatures.
+                if let Some(federation) = federation {
+                    if !is_coordinator {
+                        let mut has_quorum = match &delta_attestation {
+                            Some(delta_attestation) => {
+                                delta_attestation.has_quorum(federation, batch_height, batch_txid)
+                            }
+                            None => false,
+                        };
+
+                        // Gossip the quorum received from the Engine, or fall back to a gossiped one.
+                        if let Some(delta_gossip_pool) = delta_gossip_pool {
+                            let mut _delta_gossip_pool = delta_gossip_pool.lock().await;
+                            match (has_quorum, &delta_attestation) {
+                                (true, Some(delta_attestation)) => {
+                                    let _ = _delta_gossip_pool.insert(delta_attestation.clone());
+                                }
+                                _ => {
+                                    has_quorum = _delta_gossip_pool
+                                        .attestation(batch_height, batch_txid)
+                                        .is_some();
+                                }
+                            }
+                        }
+
+                        if !has_quorum {
+                            println!(
+                                "In-flight batch #{} is awaiting a coordinator quorum. Retrying in 5s...",
+                                batch_height
+                            );
+                            tokio::time::sleep(Duration::from_secs(5)).await;
+                            continue;
+                        }
+                    }
+                }
+
                 let exec_ctx = ExecCtx::construct(
                     engine_key,
                     Arc::clone(sync_manager),
@@ -79,12 +159,37 @@ pub async fn in_flight_batch_sync_background_task(
                             "In-flight sync applied batch #{}.",
                             batch_record.batch_height
                         );
+
+                        // Coordinators co-sign the applied delta, along with the state root it
+                        // led to, back to the Engine.
+                        if is_coordinator {
+                            let state_root =
+                                coin_manager.lock().await.account_balances_state_root();
+                            co_sign_applied_delta(
+                                engine_conn,
+                                outbox,
+                                key_holder,
+                                batch_height,
+                                batch_txid,
+                                Some(state_root),
+                            )
+                            .await;
+
+                            // Coordinators sign a notice of each contract the batch registered.
+                            if let Some(contract_notice_index) = contract_notice_index {
+                                sign_contract_notices(
+                                    contract_notice_index,
+                                    key_holder,
+                                    &batch_record,
+                                )
+                                .await;
+                            }
+                        }
                     }
                     Err(error) => {
                         eprintln!(
                             "In-flight sync failed to execute batch #{}: {:?}. Retrying in 5s...",
-                            batch_container.batch_height(),
-                            error
+                            batch_height, error
                         );
                         tokio::time::sleep(Duration::from_secs(5)).await;
                         continue;
@@ -102,3 +207,143 @@ pub async fn in_flight_batch_sync_background_task(
         }
     }
 }
+
+/// Co-signs an applied delta, and the state root it led to if known, and sends the co-signatures
+/// to the Engine.
+///
+/// A co-signature that fails to reach the Engine is queued in the outbox, to be delivered once the
+/// Engine is reachable again.
+pub(crate) async fn co_sign_applied_delta(
+    engine_conn: &PEER,
+    outbox: &OUTBOX,
+    key_holder: &KeyHolder,
+    batch_height: u64,
+    batch_txid: [u8; 32],
+    state_root: Option<[u8; 32]>,
+) {
+    // 1 Co-sign the applied delta.
+    let cosignature = match DeltaCosignature::sign(key_holder, batch_height, batch_txid) {
+        Some(cosignature) => cosignature,
+        None => {
+            eprintln!(
+                "Failed to co-sign the applied delta of batch #{}.",
+                batch_height
+            );
+            return;
+        }
+    };
+
+    // 1.1 Co-sign the state root, so that light clients do not take it on the Engine's word alone.
+    let state_root_cosignature = state_root
+        .and_then(|state_root| StateRootCosignature::sign(key_holder, batch_height, state_root));
+
+    // 2 Send the co-signatures to the Engine.
+    match engine_conn
+        .request_delta_cosign(
+            batch_height,
+            batch_txid,
+            cosignature.clone(),
+            state_root_cosignature.clone(),
+        )
+        .await
+    {
+        Ok((DeltaCosignResponseBody::Ok(success_body), _)) => {
+            if success_body.quorum_reached {
+                println!("Coordinator quorum reached for batch #{}.", batch_height);
+            }
+        }
+        Ok((DeltaCosignResponseBody::Err(error), _)) => {
+            eprintln!(
+                "Engine rejected the co-signature for batch #{}: {:?}.",
+                batch_height, error
+            );
+        }
+        // 3 Queue the co-signature if it did not reach the Engine.
+        Err(error @ (RequestError::TCPErr(_) | RequestError::EmptyResponse)) => {
+            eprintln!(
+                "Delta co-sign request for batch #{} failed: {:?}. Queued for retry.",
+                batch_height, error
+            );
+            let payload = match DeltaCosignRequestBody::new(
+                batch_height,
+                batch_txid,
+                cosignature,
+                state_root_cosignature,
+            )
+            .serialize()
+            {
+                Some(payload) => payload,
+                None => return,
+            };
+            let engine_key = engine_conn.key().await;
+            let mut _outbox = outbox.lock().await;
+            if let Err(err) = _outbox.enqueue(
+                engine_key,
+                PackageKind::DeltaCosignProtocol,
+                payload,
+                Utc::now().timestamp() as u64,
+            ) {
+                eprintln!(
+                    "Failed to queue the co-signature of batch #{}: {:?}.",
+                    batch_height, err
+                );
+            }
+        }
+        Err(error) => {
+            eprintln!(
+                "Delta co-sign request for batch #{} failed: {:?}.",
+                batch_height, error
+            );
+        }
+    }
+}
+
+/// Signs a notice of each contract registered by an applied batch and queues it for publishing.
+async fn sign_contract_notices(
+    contract_notice_index: &CONTRACT_NOTICE_INDEX,
+    key_holder: &KeyHolder,
+    batch_record: &BatchRecord,
+) {
+    for (_, entry) in batch_record.entries.iter() {
+        // 1 Only `Deploy` entries register contracts.
+        let deploy = match entry {
+            Entry::Deploy(deploy) => deploy,
+            _ => continue,
+        };
+
+        // 2 Sign the notice of the registered contract.
+        let registration = ContractRegistration {
+            contract_id: deploy.program.contract_id(),
+            deployer_key: deploy.root_account.account_key(),
+            program_name: deploy.program.program_name().to_string(),
+            metadata: deploy.program.metadata().cloned(),
+            methods_len: deploy.program.methods_len() as u32,
+        };
+        let contract_id = registration.contract_id;
+        let notice = match ContractNotice::sign(
+            key_holder,
+            registration,
+            batch_record.batch_height,
+            batch_record.batch_txid,
+        ) {
+            Some(notice) => notice,
+            None => {
+                eprintln!(
+                    "Failed to sign the registration notice of contract {}.",
+                    hex::encode(contract_id)
+                );
+                continue;
+            }
+        };
+
+        // 3 Index the notice and queue it for publishing.
+        let mut _contract_notice_index = contract_notice_index.lock().await;
+        if let Err(err) = _contract_notice_index.insert_signed(notice) {
+            eprintln!(
+                "Failed to index the registration notice of contract {}: {:?}.",
+                hex::encode(contract_id),
+                err
+            );
+        }
+    }
+}
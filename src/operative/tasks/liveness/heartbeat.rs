@@ -0,0 +1,183 @@
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::handshake::handshake::register_with_engine;
+use crate::communicative::handshake::heartbeat::Heartbeat;
+use crate::communicative::handshake::session_params::SessionParams;
+use crate::communicative::handshake::work_assignment::WorkAssignment;
+use crate::communicative::outbox::outbox::OUTBOX;
+use crate::communicative::peer::peer::PEER;
+use crate::communicative::tcp::client::{HeartbeatResponseBody, HeartbeatResponseError, TCPClient};
+use crate::inscriptive::archival_manager::archival_manager::ARCHIVAL_MANAGER;
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::operative::tasks::in_flight_batch_sync::in_flight_batch_sync::co_sign_applied_delta;
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+use std::time::Duration;
+
+/// Engine key.
+type EngineKey = [u8; 32];
+
+/// Node background loop to send signed heartbeats to the Engine over the registered session.
+///
+/// Registers again whenever the Engine no longer recognizes the session, and takes on the
+/// co-signing work the Engine assigned once the assigned batch can be verified locally.
+pub async fn heartbeat_background_task(
+    engine_conn: &PEER,
+    key_holder: &KeyHolder,
+    engine_key: EngineKey,
+    mut session_params: SessionParams,
+    capabilities: Vec<Capability>,
+    sync_manager: &SYNC_MANAGER,
+    archival_manager: &Option<ARCHIVAL_MANAGER>,
+    outbox: &OUTBOX,
+) {
+    loop {
+        // 1 Wait for the next heartbeat interval.
+        tokio::time::sleep(Duration::from_secs(session_params.heartbeat_interval_secs)).await;
+
+        // 2 Get the cube batch sync height.
+        let cube_batch_sync_height = {
+            let _sync_manager = sync_manager.lock().await;
+            _sync_manager.cube_batch_sync_height_tip()
+        };
+
+        // 3 Sign the heartbeat.
+        let heartbeat = match Heartbeat::sign(
+            key_holder,
+            session_params.session_id,
+            cube_batch_sync_height,
+            Utc::now().timestamp() as u64,
+        ) {
+            Some(heartbeat) => heartbeat,
+            None => {
+                eprintln!("Failed to sign the heartbeat.");
+                continue;
+            }
+        };
+
+        // 4 Send the heartbeat to the Engine.
+        match engine_conn.request_heartbeat(&heartbeat).await {
+            // 4.a The Engine acknowledged the heartbeat.
+            Ok((HeartbeatResponseBody::Ok(success_body), _)) => {
+                // 4.a.1 Make sure the acknowledgement is the Engine's, over the same session.
+                let engine_heartbeat = &success_body.heartbeat;
+                if engine_heartbeat.sender_key != engine_key
+                    || engine_heartbeat.session_id != session_params.session_id
+                    || !engine_heartbeat.verify()
+                {
+                    if log_enabled(LogLevel::Warn) {
+                        eprintln!("Ignoring a heartbeat acknowledgement not signed by the engine.");
+                    }
+                    continue;
+                }
+
+                // 4.a.2 Take on the assigned work.
+                for work in success_body.pending_work.iter() {
+                    take_on_work(engine_conn, key_holder, archival_manager, outbox, work).await;
+                }
+            }
+
+            // 4.b The Engine no longer recognizes the session; register again.
+            Ok((
+                HeartbeatResponseBody::Err(HeartbeatResponseError::HeartbeatRejectedError(error)),
+                _,
+            )) if error.requires_registration() => {
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!(
+                        "Heartbeat session rejected: {:?}. Registering again...",
+                        error
+                    );
+                }
+                match register_with_engine(
+                    engine_conn,
+                    key_holder,
+                    engine_key,
+                    capabilities.clone(),
+                    cube_batch_sync_height,
+                )
+                .await
+                {
+                    Ok(new_session_params) => session_params = new_session_params,
+                    Err(error) => {
+                        if log_enabled(LogLevel::Warn) {
+                            eprintln!("Failed to register with the engine again: {:?}.", error);
+                        }
+                    }
+                }
+            }
+
+            // 4.c The Engine rejected the heartbeat.
+            Ok((HeartbeatResponseBody::Err(error), _)) => {
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!("Heartbeat rejected by the engine: {}", error.json());
+                }
+            }
+
+            // 4.d The Engine could not be reached.
+            Err(error) => {
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!("Heartbeat request failed: {:?}.", error);
+                }
+            }
+        }
+    }
+}
+
+/// Takes on a work the Engine assigned to the node.
+async fn take_on_work(
+    engine_conn: &PEER,
+    key_holder: &KeyHolder,
+    archival_manager: &Option<ARCHIVAL_MANAGER>,
+    outbox: &OUTBOX,
+    work: &WorkAssignment,
+) {
+    match work {
+        WorkAssignment::DeltaCosign {
+            batch_height,
+            batch_txid,
+        } => {
+            // 1 Only co-sign a delta the node has applied and archived itself.
+            let is_applied_batch = match archival_manager {
+                Some(archival_manager) => {
+                    let _archival_manager = archival_manager.lock().await;
+                    _archival_manager
+                        .batch_record_by_height(*batch_height)
+                        .map(|batch_record| {
+                            batch_record.batch_container.batch_txid() == *batch_txid
+                        })
+                        .unwrap_or(false)
+                }
+                None => false,
+            };
+            if !is_applied_batch {
+                if log_enabled(LogLevel::Debug) {
+                    println!(
+                        "Deferring the assigned co-signature of batch #{} until it is applied.",
+                        batch_height
+                    );
+                }
+                return;
+            }
+
+            // 2 Get the state root journaled for the batch.
+            let state_root = match archival_manager {
+                Some(archival_manager) => archival_manager
+                    .lock()
+                    .await
+                    .state_root_by_height(*batch_height),
+                None => None,
+            };
+
+            // 3 Co-sign the applied delta along with the state root.
+            co_sign_applied_delta(
+                engine_conn,
+                outbox,
+                key_holder,
+                *batch_height,
+                *batch_txid,
+                state_root,
+            )
+            .await;
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::communicative::handshake::operator_sessions::OPERATOR_SESSIONS;
+use crate::communicative::handshake::session_params::HEARTBEAT_INTERVAL_SECS;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::transmutative::key::ToNostrKeyStr;
+use chrono::Utc;
+use std::time::Duration;
+
+/// Engine background loop to mark operators that stopped sending heartbeats as offline and
+/// reassign their pending work.
+pub async fn liveness_monitor_background_task(operator_sessions: &OPERATOR_SESSIONS) {
+    loop {
+        // 1 Wait for the next heartbeat interval.
+        tokio::time::sleep(Duration::from_secs(HEARTBEAT_INTERVAL_SECS)).await;
+
+        // 2 Sweep the timed out operators.
+        let (timed_out_operators, unassigned_work_count) = {
+            let mut _operator_sessions = operator_sessions.lock().await;
+            let timed_out_operators = _operator_sessions.sweep(Utc::now().timestamp() as u64);
+            (
+                timed_out_operators,
+                _operator_sessions.unassigned_work_count(),
+            )
+        };
+
+        // 3 Report them.
+        if log_enabled(LogLevel::Warn) {
+            for operator_key in timed_out_operators.iter() {
+                eprintln!(
+                    "Operator {} went offline; its pending work was reassigned ({} unassigned).",
+                    operator_key
+                        .to_npub()
+                        .unwrap_or_else(|| hex::encode(operator_key)),
+                    unassigned_work_count
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod heartbeat;
+pub mod liveness_monitor;
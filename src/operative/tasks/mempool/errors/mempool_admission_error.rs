@@ -0,0 +1,27 @@
+use crate::constructive::entries::entry_kinds::r#move::ext::codec::sbe::encode::error::encode_error::MoveSBEEncodeError;
+use crate::constructive::entries::entry_kinds::r#move::ext::signature::bls_verify::error::bls_verify_error::MoveBLSVerifyError;
+use crate::constructive::entries::entry_kinds::r#move::ext::signature::sighash::error::sighash_error::MoveSighashError;
+use crate::constructive::entry::entry_kinds::r#move::ext::pre_validations::validate_overall::validate_overall_error::MoveValidateOverallError;
+
+/// Errors associated with admitting an entry into the mempool.
+#[derive(Debug, Clone)]
+pub enum MempoolAdmissionError {
+    SighashError(MoveSighashError),
+    BLSVerifyError(MoveBLSVerifyError),
+    SBEEncodeError(MoveSBEEncodeError),
+    /// The same entry is already pending.
+    DuplicateEntryError([u8; 32]),
+    ValidateOverallError(MoveValidateOverallError),
+    /// The balance does not cover this move on top of the moves the account already has pending.
+    InsufficientBalanceForPendingMovesError {
+        account_key: [u8; 32],
+        required: u64,
+        available: u64,
+    },
+    /// The fee rate, in satoshis per 1000 bytes, is below the configured minimum.
+    FeeRateBelowMinimumError { fee_rate: u64, min_fee_rate: u64 },
+    /// The account already has the maximum number of entries pending.
+    AccountPendingCapReached(usize),
+    /// The mempool is full of entries with a higher or equal priority.
+    MempoolFull,
+}
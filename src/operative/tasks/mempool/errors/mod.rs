@@ -0,0 +1 @@
+pub mod mempool_admission_error;
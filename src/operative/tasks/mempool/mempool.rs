@@ -0,0 +1,438 @@
+use crate::constructive::entry::entry_kinds::r#move::r#move::Move;
+use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+use crate::inscriptive::flame_manager::flame_manager::FLAME_MANAGER;
+use crate::inscriptive::graveyard::graveyard::GRAVEYARD;
+use crate::inscriptive::params_manager::params_manager::PARAMS_MANAGER;
+use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::tasks::engine_session::exec_scheduler::exec_priority::ExecPriority;
+use crate::operative::tasks::mempool::errors::mempool_admission_error::MempoolAdmissionError;
+use crate::operative::tasks::mempool::mempool_entry::{
+    fee_rate, ArrivalSeq, MempoolEntry, MempoolOrderKey,
+};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Entry sighash.
+type Sighash = [u8; 32];
+
+/// The maximum number of entries held in the mempool.
+pub const MAX_MEMPOOL_ENTRIES: usize = 1024;
+
+/// The maximum number of entries a single account may have pending at once.
+pub const MAX_PENDING_PER_ACCOUNT: usize = 16;
+
+/// Pending-entry pool of a node.
+///
+/// User-submitted entries are validated against the node's local ledger view before they are
+/// held here: the signature is verified, the target batch height must be within the execution
+/// window (the target is what keeps an entry from being replayed), and the sender balance, as
+/// read through the delta-aware coin manager, must cover the entry on top of whatever else the
+/// sender already has pending. Entries pay the nominal fee plus an optional priority fee, and are
+/// forwarded to the engine in order of fee rate (per 1000 encoded bytes), then sender flame value,
+/// then arrival; entries below the configured minimum fee rate are rejected. Entries whose target falls out of the execution window are
+/// evicted, and once the pool is full the lowest priority entry gives way to a higher one.
+pub struct Mempool {
+    // Managers to validate entries against.
+    sync_manager: SYNC_MANAGER,
+    registery: REGISTERY,
+    graveyard: GRAVEYARD,
+    coin_manager: COIN_MANAGER,
+    flame_manager: FLAME_MANAGER,
+    params_manager: PARAMS_MANAGER,
+
+    // Pending entries by their sighash.
+    entries: HashMap<Sighash, MempoolEntry>,
+
+    // Sighashes of the pending entries in forwarding order.
+    queue: BTreeMap<MempoolOrderKey, Sighash>,
+
+    // Number of pending entries per account.
+    pending_per_account: HashMap<AccountKey, usize>,
+
+    // Sum of the pending move amounts and priority fees per account.
+    pending_outflow_per_account: HashMap<AccountKey, u64>,
+
+    // Next arrival sequence number.
+    next_arrival_seq: ArrivalSeq,
+
+    // Wakes up the forwarder whenever an entry is inserted.
+    notify: Arc<Notify>,
+}
+
+/// Guarded 'Mempool'.
+#[allow(non_camel_case_types)]
+pub type MEMPOOL = Arc<Mutex<Mempool>>;
+
+impl Mempool {
+    /// Constructs the mempool.
+    pub fn new(
+        sync_manager: &SYNC_MANAGER,
+        registery: &REGISTERY,
+        graveyard: &GRAVEYARD,
+        coin_manager: &COIN_MANAGER,
+        flame_manager: &FLAME_MANAGER,
+        params_manager: &PARAMS_MANAGER,
+    ) -> MEMPOOL {
+        // 1 Construct the mempool.
+        let mempool = Mempool {
+            sync_manager: Arc::clone(sync_manager),
+            registery: Arc::clone(registery),
+            graveyard: Arc::clone(graveyard),
+            coin_manager: Arc::clone(coin_manager),
+            flame_manager: Arc::clone(flame_manager),
+            params_manager: Arc::clone(params_manager),
+            entries: HashMap::new(),
+            queue: BTreeMap::new(),
+            pending_per_account: HashMap::new(),
+            pending_outflow_per_account: HashMap::new(),
+            next_arrival_seq: 0,
+            notify: Arc::new(Notify::new()),
+        };
+
+        // 2 Guard and return the mempool.
+        Arc::new(Mutex::new(mempool))
+    }
+
+    /// Validates a signed move entry and holds it in the mempool.
+    ///
+    /// Returns the sighash the entry is identified by.
+    pub async fn submit(
+        mempool: &MEMPOOL,
+        move_entry: Move,
+        move_bls_signature: [u8; 96],
+    ) -> Result<Sighash, MempoolAdmissionError> {
+        // 1 Compute the sighash and verify the signature.
+        let sighash = move_entry
+            .sighash()
+            .map_err(MempoolAdmissionError::SighashError)?;
+        move_entry
+            .bls_verify(move_bls_signature)
+            .map_err(MempoolAdmissionError::BLSVerifyError)?;
+
+        // 2 Clone the managers so that validation does not hold the mempool lock.
+        let (sync_manager, registery, graveyard, coin_manager, flame_manager, params_manager) = {
+            let _mempool = mempool.lock().await;
+            if _mempool.entries.contains_key(&sighash) {
+                return Err(MempoolAdmissionError::DuplicateEntryError(sighash));
+            }
+            (
+                Arc::clone(&_mempool.sync_manager),
+                Arc::clone(&_mempool.registery),
+                Arc::clone(&_mempool.graveyard),
+                Arc::clone(&_mempool.coin_manager),
+                Arc::clone(&_mempool.flame_manager),
+                Arc::clone(&_mempool.params_manager),
+            )
+        };
+
+        // 3 The entry would execute in the batch following the current tip.
+        let execution_batch_height = {
+            let _sync_manager = sync_manager.lock().await;
+            _sync_manager.cube_batch_sync_height_tip() + 1
+        };
+
+        // 4 Validate the entry end-to-end, as the engine would.
+        move_entry
+            .validate_overall(
+                execution_batch_height,
+                &registery,
+                &graveyard,
+                &coin_manager,
+            )
+            .await
+            .map_err(MempoolAdmissionError::ValidateOverallError)?;
+
+        // 5 Resolve the sender balance through the delta-aware read path.
+        let sender_key = move_entry.from.account_key();
+        let available_balance = {
+            let _coin_manager = coin_manager.lock().await;
+            _coin_manager.get_account_balance(sender_key).unwrap_or(0)
+        };
+
+        // 6 Resolve the nominal fee plus the priority fee, and reject a fee rate below the minimum.
+        let (fee, fee_rate) = {
+            let params_holder = params_manager.lock().unwrap().get_params_holder();
+            let liquidity_fee =
+                (move_entry.amount as u64 * params_holder.move_ppm_liquidity_fee) / 1_000_000;
+            let fee =
+                params_holder.move_entry_base_fee + liquidity_fee + move_entry.priority_fee as u64;
+            let entry_size = move_entry
+                .encode_sbe()
+                .map_err(MempoolAdmissionError::SBEEncodeError)?
+                .len();
+            let fee_rate = fee_rate(fee, entry_size);
+            if fee_rate < params_holder.mempool_min_fee_rate {
+                return Err(MempoolAdmissionError::FeeRateBelowMinimumError {
+                    fee_rate,
+                    min_fee_rate: params_holder.mempool_min_fee_rate,
+                });
+            }
+            (fee, fee_rate)
+        };
+
+        // 7 Resolve the sender flame value.
+        let sender_flame_value =
+            ExecPriority::resolve(sender_key, None, &registery, &flame_manager)
+                .await
+                .caller_flame_value;
+
+        // 8 Insert the entry.
+        {
+            let mut _mempool = mempool.lock().await;
+            _mempool.evict_stale(execution_batch_height);
+            _mempool.insert(
+                move_entry,
+                move_bls_signature,
+                sighash,
+                fee,
+                fee_rate,
+                sender_flame_value,
+                available_balance,
+            )?;
+        }
+
+        // 9 Return the sighash.
+        Ok(sighash)
+    }
+
+    /// Inserts a validated entry, making room for it if the mempool is full.
+    fn insert(
+        &mut self,
+        move_entry: Move,
+        move_bls_signature: [u8; 96],
+        sighash: Sighash,
+        fee: u64,
+        fee_rate: u64,
+        sender_flame_value: u64,
+        available_balance: u64,
+    ) -> Result<(), MempoolAdmissionError> {
+        // 1 Reject duplicates that arrived while the entry was being validated.
+        if self.entries.contains_key(&sighash) {
+            return Err(MempoolAdmissionError::DuplicateEntryError(sighash));
+        }
+
+        // 2 Check the per-account cap.
+        let sender_key = move_entry.from.account_key();
+        let pending = self
+            .pending_per_account
+            .get(&sender_key)
+            .copied()
+            .unwrap_or(0);
+        if pending >= MAX_PENDING_PER_ACCOUNT {
+            return Err(MempoolAdmissionError::AccountPendingCapReached(
+                MAX_PENDING_PER_ACCOUNT,
+            ));
+        }
+
+        // 3 Check the balance against the moves the sender already has pending.
+        let pending_outflow = self
+            .pending_outflow_per_account
+            .get(&sender_key)
+            .copied()
+            .unwrap_or(0);
+        let required = pending_outflow + move_outflow(&move_entry);
+        if available_balance < required {
+            return Err(
+                MempoolAdmissionError::InsufficientBalanceForPendingMovesError {
+                    account_key: sender_key,
+                    required,
+                    available: available_balance,
+                },
+            );
+        }
+
+        // 4 Construct the entry.
+        let entry = MempoolEntry {
+            move_entry,
+            move_bls_signature,
+            sighash,
+            fee,
+            fee_rate,
+            sender_flame_value,
+            arrival_seq: self.next_arrival_seq,
+        };
+
+        // 5 Make room if the mempool is full, evicting the lowest priority entry if it ranks below.
+        if self.entries.len() >= MAX_MEMPOOL_ENTRIES {
+            let lowest = self
+                .queue
+                .last_key_value()
+                .map(|(key, sighash)| (*key, *sighash));
+            match lowest {
+                Some((lowest_key, lowest_sighash)) if entry.order_key() < lowest_key => {
+                    self.remove(&lowest_sighash);
+                }
+                _ => return Err(MempoolAdmissionError::MempoolFull),
+            }
+        }
+
+        // 6 Insert the entry.
+        self.next_arrival_seq += 1;
+        self.track(entry);
+
+        // 7 Wake up the forwarder.
+        self.notify.notify_one();
+
+        Ok(())
+    }
+
+    /// Puts an entry that could not be forwarded back into the mempool, keeping its position.
+    pub fn requeue(&mut self, entry: MempoolEntry) {
+        // 1 Skip entries that have been re-submitted in the meantime.
+        if self.entries.contains_key(&entry.sighash) {
+            return;
+        }
+
+        // 2 Insert the entry back.
+        self.track(entry);
+
+        // 3 Wake up the forwarder.
+        self.notify.notify_one();
+    }
+
+    /// Waits for the highest priority entry that is still within the execution window and takes it out.
+    pub async fn next(mempool: &MEMPOOL) -> MempoolEntry {
+        loop {
+            // 1 Register for the next wake up before checking, so that no wake up is missed.
+            let (notify, sync_manager) = {
+                let _mempool = mempool.lock().await;
+                (
+                    Arc::clone(&_mempool.notify),
+                    Arc::clone(&_mempool.sync_manager),
+                )
+            };
+            let notified = notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            // 2 Resolve the current execution batch height.
+            let execution_batch_height = {
+                let _sync_manager = sync_manager.lock().await;
+                _sync_manager.cube_batch_sync_height_tip() + 1
+            };
+
+            // 3 Take out the highest priority entry, if any.
+            {
+                let mut _mempool = mempool.lock().await;
+                _mempool.evict_stale(execution_batch_height);
+                if let Some(sighash) = _mempool.queue.values().next().copied() {
+                    if let Some(entry) = _mempool.remove(&sighash) {
+                        return entry;
+                    }
+                }
+            }
+
+            // 4 Wait for an insertion.
+            notified.await;
+        }
+    }
+
+    /// Evicts entries whose target is no longer within the execution window.
+    pub fn evict_stale(&mut self, execution_batch_height: u64) {
+        // 1 Collect the stale entries.
+        let stale: Vec<Sighash> = self
+            .entries
+            .values()
+            .filter(|entry| {
+                entry
+                    .move_entry
+                    .target
+                    .validate(execution_batch_height)
+                    .is_err()
+            })
+            .map(|entry| entry.sighash)
+            .collect();
+
+        // 2 Remove them.
+        for sighash in stale {
+            self.remove(&sighash);
+        }
+    }
+
+    /// Returns the number of pending entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the mempool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the pending entries, in forwarding order, as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Collect the entries in forwarding order.
+        let entries: Vec<Value> = self
+            .queue
+            .values()
+            .filter_map(|sighash| self.entries.get(sighash))
+            .map(|entry| entry.json())
+            .collect();
+
+        // 2 Construct the JSON object.
+        let mut obj = Map::new();
+        obj.insert(
+            "pending".to_string(),
+            Value::Number((self.entries.len() as u64).into()),
+        );
+        obj.insert(
+            "capacity".to_string(),
+            Value::Number((MAX_MEMPOOL_ENTRIES as u64).into()),
+        );
+        obj.insert("entries".to_string(), Value::Array(entries));
+
+        // 3 Return the JSON object.
+        Value::Object(obj)
+    }
+
+    /// Adds an entry to the indexes.
+    fn track(&mut self, entry: MempoolEntry) {
+        // 1 Account for the sender.
+        let sender_key = entry.move_entry.from.account_key();
+        *self.pending_per_account.entry(sender_key).or_insert(0) += 1;
+        *self
+            .pending_outflow_per_account
+            .entry(sender_key)
+            .or_insert(0) += move_outflow(&entry.move_entry);
+
+        // 2 Index the entry.
+        self.queue.insert(entry.order_key(), entry.sighash);
+        self.entries.insert(entry.sighash, entry);
+    }
+
+    /// Removes an entry from the indexes.
+    fn remove(&mut self, sighash: &Sighash) -> Option<MempoolEntry> {
+        // 1 Remove the entry.
+        let entry = self.entries.remove(sighash)?;
+        self.queue.remove(&entry.order_key());
+
+        // 2 Release the sender accounting.
+        let sender_key = entry.move_entry.from.account_key();
+        if let Some(pending) = self.pending_per_account.get_mut(&sender_key) {
+            *pending -= 1;
+            if *pending == 0 {
+                self.pending_per_account.remove(&sender_key);
+            }
+        }
+        if let Some(outflow) = self.pending_outflow_per_account.get_mut(&sender_key) {
+            *outflow = outflow.saturating_sub(move_outflow(&entry.move_entry));
+            if *outflow == 0 {
+                self.pending_outflow_per_account.remove(&sender_key);
+            }
+        }
+
+        // 3 Return the entry.
+        Some(entry)
+    }
+}
+
+/// Returns what a move takes out of the sender balance on top of the entry fees.
+fn move_outflow(move_entry: &Move) -> u64 {
+    move_entry.amount as u64 + move_entry.priority_fee as u64
+}
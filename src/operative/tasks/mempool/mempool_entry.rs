@@ -0,0 +1,83 @@
+use crate::constructive::entry::entry_kinds::r#move::r#move::Move;
+use serde_json::{Map, Value};
+use std::cmp::Reverse;
+
+/// Arrival sequence number, assigned in submission order.
+pub type ArrivalSeq = u64;
+
+/// Ordering key of a pending entry: higher fee rates first, then higher sender flame values, then arrival.
+pub type MempoolOrderKey = (Reverse<u64>, Reverse<u64>, ArrivalSeq);
+
+/// Returns the fee rate, in satoshis per 1000 bytes, of a fee paid for an entry of the given size.
+pub fn fee_rate(fee: u64, entry_size: usize) -> u64 {
+    match entry_size {
+        0 => 0,
+        _ => ((fee as u128 * 1000) / entry_size as u128).min(u64::MAX as u128) as u64,
+    }
+}
+
+/// A validated entry waiting in the mempool to be forwarded to the engine.
+#[derive(Clone)]
+pub struct MempoolEntry {
+    /// The move entry.
+    pub move_entry: Move,
+
+    /// The BLS signature over the move entry.
+    pub move_bls_signature: [u8; 96],
+
+    /// The sighash of the move entry, identifying it in the mempool.
+    pub sighash: [u8; 32],
+
+    /// Nominal (pre-subsidy) entry fee plus the priority fee, in satoshis.
+    pub fee: u64,
+
+    /// Fee rate in satoshis per 1000 bytes of the SBE-encoded entry.
+    pub fee_rate: u64,
+
+    /// Total satoshi value of the sender's flames at submission time.
+    pub sender_flame_value: u64,
+
+    /// Arrival sequence number.
+    pub arrival_seq: ArrivalSeq,
+}
+
+impl MempoolEntry {
+    /// Returns the ordering key of the entry.
+    pub fn order_key(&self) -> MempoolOrderKey {
+        (
+            Reverse(self.fee_rate),
+            Reverse(self.sender_flame_value),
+            self.arrival_seq,
+        )
+    }
+
+    /// Returns the mempool entry as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Construct the JSON object.
+        let mut obj = Map::new();
+
+        // 2 Insert the sighash.
+        obj.insert(
+            "sighash".to_string(),
+            Value::String(hex::encode(self.sighash)),
+        );
+
+        // 3 Insert the fee, the fee rate and the sender flame value.
+        obj.insert("fee".to_string(), Value::Number(self.fee.into()));
+        obj.insert(
+            "priority_fee".to_string(),
+            Value::Number((self.move_entry.priority_fee as u64).into()),
+        );
+        obj.insert("fee_rate".to_string(), Value::Number(self.fee_rate.into()));
+        obj.insert(
+            "sender_flame_value".to_string(),
+            Value::Number(self.sender_flame_value.into()),
+        );
+
+        // 4 Insert the entry.
+        obj.insert("entry".to_string(), self.move_entry.json());
+
+        // 5 Return the JSON object.
+        Value::Object(obj)
+    }
+}
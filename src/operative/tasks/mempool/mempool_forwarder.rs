@@ -0,0 +1,62 @@
+use crate::communicative::peer::peer::PEER;
+use crate::communicative::tcp::client::{MoveResponseBody, TCPClient};
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::operative::tasks::mempool::mempool::{Mempool, MEMPOOL};
+use std::time::Duration;
+
+/// Backoff after the engine could not be reached.
+const FORWARD_RETRY_SECS: u64 = 5;
+
+/// Node background loop to forward pending mempool entries to the Engine, highest priority first.
+///
+/// Entries that could not be delivered are put back into the mempool; entries the Engine rejects
+/// are dropped.
+pub async fn mempool_forwarder_background_task(mempool: &MEMPOOL, engine_conn: &PEER) {
+    loop {
+        // 1 Wait for the next entry.
+        let entry = Mempool::next(mempool).await;
+
+        // 2 Forward it to the engine.
+        match engine_conn
+            .request_move(&entry.move_entry, entry.move_bls_signature)
+            .await
+        {
+            // 2.a The engine executed the entry.
+            Ok((MoveResponseBody::Ok(success_body), duration)) => {
+                if log_enabled(LogLevel::Info) {
+                    println!(
+                        "Forwarded move {} executed ({} ms): {}",
+                        hex::encode(entry.sighash),
+                        duration.as_millis(),
+                        success_body.json()
+                    );
+                }
+            }
+
+            // 2.b The engine rejected the entry.
+            Ok((MoveResponseBody::Err(error), _)) => {
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!(
+                        "Forwarded move {} rejected by the engine: {}",
+                        hex::encode(entry.sighash),
+                        error.json()
+                    );
+                }
+            }
+
+            // 2.c The engine could not be reached; put the entry back and retry later.
+            Err(error) => {
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!(
+                        "Forwarding move {} failed: {:?}. Retrying in {}s...",
+                        hex::encode(entry.sighash),
+                        error,
+                        FORWARD_RETRY_SECS
+                    );
+                }
+                mempool.lock().await.requeue(entry);
+                tokio::time::sleep(Duration::from_secs(FORWARD_RETRY_SECS)).await;
+            }
+        }
+    }
+}
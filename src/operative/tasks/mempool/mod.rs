@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod mempool;
+pub mod mempool_entry;
+pub mod mempool_forwarder;
@@ -0,0 +1,381 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::{
+    get_chain_tip, retrieve_mempool_transactions, retrieve_mempool_txids,
+    retrieve_txout_confirmations,
+};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use crate::communicative::webhook::webhooks::WEBHOOKS;
+use crate::constructive::txo::lift::lift_versions::{
+    liftv1::liftv1::return_liftv1_scriptpubkey, liftv2::liftv2::return_liftv2_scriptpubkey,
+};
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::tasks::mempool_watch::pending_txn::{PendingTxn, PendingTxnKind};
+use bitcoin::{OutPoint, Transaction, Txid};
+use chrono::Utc;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default interval between two mempool polls.
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 10;
+
+/// Watches the Bitcoin mempool for unconfirmed transactions relevant to the node.
+///
+/// Deposits to the lifts of the node's own account, and commitment transactions spending the
+/// payload tip, are tracked from the moment they enter the mempool until the node has synced the
+/// block confirming them, at which point they are reflected by the ledger itself. Transactions
+/// evicted from the mempool or replaced before confirming are dropped.
+pub struct MempoolWatch {
+    // Script pubkeys of the lifts owned by the node's own account.
+    self_lift_spks: Vec<Vec<u8>>,
+
+    // Interval between two mempool polls.
+    poll_interval: Duration,
+
+    // Pending transactions by their watched outpoint.
+    pending: HashMap<OutPoint, PendingTxn>,
+
+    // Mempool txids already looked at.
+    seen: HashSet<Txid>,
+}
+
+/// Guarded 'MempoolWatch'.
+#[allow(non_camel_case_types)]
+pub type MEMPOOL_WATCH = Arc<Mutex<MempoolWatch>>;
+
+impl MempoolWatch {
+    /// Constructs the mempool watch for the lifts owned by the given account.
+    ///
+    /// `CUBE_MEMPOOL_WATCH_INTERVAL_SECS` overrides the interval between two mempool polls.
+    pub fn new(
+        engine_key: [u8; 32],
+        self_account_key: [u8; 32],
+        v2_lift_enabled: bool,
+    ) -> MEMPOOL_WATCH {
+        // 1 Collect the lift script pubkeys of the account.
+        let mut self_lift_spks = Vec::new();
+        if let Some(spk_v1) = return_liftv1_scriptpubkey(self_account_key, engine_key) {
+            self_lift_spks.push(spk_v1);
+        }
+        if v2_lift_enabled {
+            if let Some(spk_v2) = return_liftv2_scriptpubkey(self_account_key, engine_key) {
+                self_lift_spks.push(spk_v2);
+            }
+        }
+
+        // 2 Read the poll interval.
+        let poll_interval_secs = std::env::var("CUBE_MEMPOOL_WATCH_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+
+        // 3 Construct the mempool watch.
+        let mempool_watch = MempoolWatch {
+            self_lift_spks,
+            poll_interval: Duration::from_secs(poll_interval_secs),
+            pending: HashMap::new(),
+            seen: HashSet::new(),
+        };
+
+        // 4 Return the guarded mempool watch.
+        Arc::new(Mutex::new(mempool_watch))
+    }
+
+    /// Returns the interval between two mempool polls.
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Returns the given mempool txids not looked at yet, forgetting the ones that left the mempool.
+    pub fn unseen_txids(&mut self, mempool_txids: &HashSet<Txid>) -> Vec<Txid> {
+        self.seen.retain(|txid| mempool_txids.contains(txid));
+        mempool_txids
+            .iter()
+            .filter(|txid| !self.seen.contains(*txid))
+            .cloned()
+            .collect()
+    }
+
+    /// Looks at a mempool transaction and tracks it if it is relevant.
+    ///
+    /// Returns the deposits tracked for the first time.
+    pub fn observe(
+        &mut self,
+        txn: &Transaction,
+        payload_tip_outpoint: Option<OutPoint>,
+        now: u64,
+    ) -> Vec<PendingTxn> {
+        let txid = txn.compute_txid();
+        self.seen.insert(txid);
+
+        // 1 A transaction spending the payload tip commits the next batch.
+        if let Some(payload_tip_outpoint) = payload_tip_outpoint {
+            if txn
+                .input
+                .iter()
+                .any(|input| input.previous_output == payload_tip_outpoint)
+            {
+                let watched_outpoint = OutPoint::new(txid, 0);
+                let value = txn
+                    .output
+                    .first()
+                    .map(|txout| txout.value.to_sat())
+                    .unwrap_or(0);
+                self.pending.entry(watched_outpoint).or_insert_with(|| {
+                    PendingTxn::new(
+                        txid,
+                        PendingTxnKind::Commitment,
+                        watched_outpoint,
+                        value,
+                        now,
+                    )
+                });
+                return Vec::new();
+            }
+        }
+
+        // 2 Outputs paying to a lift of the account are deposits.
+        let mut new_deposits = Vec::new();
+        for (vout, txout) in txn.output.iter().enumerate() {
+            if !self
+                .self_lift_spks
+                .iter()
+                .any(|spk| spk.as_slice() == txout.script_pubkey.as_bytes())
+            {
+                continue;
+            }
+
+            let watched_outpoint = OutPoint::new(txid, vout as u32);
+            if self.pending.contains_key(&watched_outpoint) {
+                continue;
+            }
+            let deposit = PendingTxn::new(
+                txid,
+                PendingTxnKind::Deposit,
+                watched_outpoint,
+                txout.value.to_sat(),
+                now,
+            );
+            self.pending.insert(watched_outpoint, deposit.clone());
+            new_deposits.push(deposit);
+        }
+
+        new_deposits
+    }
+
+    /// Returns the watched outpoints.
+    pub fn watched_outpoints(&self) -> Vec<OutPoint> {
+        self.pending.keys().cloned().collect()
+    }
+
+    /// Updates the confirmations of a watched outpoint, dropping it once it no longer needs tracking.
+    ///
+    /// `confirmations` is `None` if the output is spent or unknown to the Bitcoin node.
+    pub fn update_confirmations(
+        &mut self,
+        watched_outpoint: &OutPoint,
+        confirmations: Option<u32>,
+        in_mempool: bool,
+        chain_tip: u64,
+        bitcoin_sync_height_tip: u64,
+    ) {
+        match confirmations {
+            // Still unconfirmed.
+            Some(0) => {
+                if let Some(pending_txn) = self.pending.get_mut(watched_outpoint) {
+                    pending_txn.confirmations = 0;
+                    pending_txn.confirmed_height = None;
+                }
+            }
+            // Confirmed; drop it once the node has synced the confirming block.
+            Some(confirmations) => {
+                let confirmed_height = (chain_tip + 1).saturating_sub(confirmations as u64);
+                if bitcoin_sync_height_tip >= confirmed_height {
+                    self.pending.remove(watched_outpoint);
+                } else if let Some(pending_txn) = self.pending.get_mut(watched_outpoint) {
+                    pending_txn.confirmations = confirmations;
+                    pending_txn.confirmed_height = Some(confirmed_height);
+                }
+            }
+            // Spent or gone; keep it only while its transaction is still in the mempool.
+            None => {
+                if !in_mempool {
+                    self.pending.remove(watched_outpoint);
+                }
+            }
+        }
+    }
+
+    /// Returns the number and total value in satoshis of the pending deposits.
+    pub fn pending_deposits(&self) -> (usize, u64) {
+        self.pending
+            .values()
+            .filter(|pending_txn| pending_txn.kind == PendingTxnKind::Deposit)
+            .fold((0, 0), |(count, value), pending_txn| {
+                (count + 1, value + pending_txn.value)
+            })
+    }
+
+    /// Returns the mempool watch as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Collect the pending transactions, oldest first.
+        let mut pending_txns: Vec<&PendingTxn> = self.pending.values().collect();
+        pending_txns
+            .sort_by_key(|pending_txn| (pending_txn.first_seen, pending_txn.watched_outpoint));
+        let pending_txns: Vec<Value> = pending_txns
+            .into_iter()
+            .map(|pending_txn| pending_txn.json())
+            .collect();
+
+        // 2 Construct the JSON object.
+        let (pending_deposit_count, pending_deposit_value) = self.pending_deposits();
+        let mut obj = Map::new();
+        obj.insert(
+            "pending_deposits".to_string(),
+            Value::Number((pending_deposit_count as u64).into()),
+        );
+        obj.insert(
+            "pending_deposit_value".to_string(),
+            Value::Number(pending_deposit_value.into()),
+        );
+        obj.insert("txns".to_string(), Value::Array(pending_txns));
+
+        // 3 Return the JSON object.
+        Value::Object(obj)
+    }
+}
+
+/// Node background loop to poll the Bitcoin mempool for relevant transactions and track their confirmations.
+pub async fn mempool_watch_background_task(
+    mempool_watch: &MEMPOOL_WATCH,
+    rpc_holder: &BitcoinRPCHolder,
+    sync_manager: &SYNC_MANAGER,
+    webhooks: &WEBHOOKS,
+) {
+    let poll_interval = {
+        let _mempool_watch = mempool_watch.lock().await;
+        _mempool_watch.poll_interval()
+    };
+
+    loop {
+        // 1 Wait for the next poll.
+        tokio::time::sleep(poll_interval).await;
+
+        // 2 Retrieve the mempool txids.
+        let mempool_txids: HashSet<Txid> = {
+            let rpc_holder = rpc_holder.clone();
+            match tokio::task::spawn_blocking(move || retrieve_mempool_txids(&rpc_holder)).await {
+                Ok(Ok(txids)) => txids.into_iter().collect(),
+                Ok(Err(err)) => {
+                    eprintln!(
+                        "{}",
+                        format!("Error retrieving mempool txids: {}", err).yellow()
+                    );
+                    continue;
+                }
+                Err(_) => continue,
+            }
+        };
+
+        // 3 Retrieve the transactions not looked at yet.
+        let unseen_txids = {
+            let mut _mempool_watch = mempool_watch.lock().await;
+            _mempool_watch.unseen_txids(&mempool_txids)
+        };
+        let unseen_txns: Vec<Transaction> = {
+            let rpc_holder = rpc_holder.clone();
+            tokio::task::spawn_blocking(move || {
+                retrieve_mempool_transactions(&rpc_holder, &unseen_txids)
+                    .map(|txns| {
+                        txns.into_iter()
+                            // Transactions that left the mempool in the meantime are skipped.
+                            .flatten()
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            })
+            .await
+            .unwrap_or_default()
+        };
+
+        // 4 Look at them.
+        let (payload_tip_outpoint, bitcoin_sync_height_tip) = {
+            let _sync_manager = sync_manager.lock().await;
+            (
+                _sync_manager.payload_tip().outpoint(),
+                _sync_manager.bitcoin_sync_height_tip(),
+            )
+        };
+        let now = Utc::now().timestamp() as u64;
+        let new_deposits: Vec<PendingTxn> = {
+            let mut _mempool_watch = mempool_watch.lock().await;
+            unseen_txns
+                .iter()
+                .flat_map(|txn| _mempool_watch.observe(txn, payload_tip_outpoint, now))
+                .collect()
+        };
+
+        // 4.a Notify the webhooks of the newly detected deposits.
+        if !new_deposits.is_empty() {
+            let mut _webhooks = webhooks.lock().await;
+            for deposit in new_deposits.iter() {
+                if let Err(err) =
+                    _webhooks.enqueue(WebhookEventKind::DepositDetected, None, deposit.json(), now)
+                {
+                    eprintln!(
+                        "{}",
+                        format!("Failed to queue a deposit webhook: {}", err).yellow()
+                    );
+                }
+            }
+        }
+
+        // 5 Retrieve the confirmations of the watched outpoints.
+        let watched_outpoints = {
+            let _mempool_watch = mempool_watch.lock().await;
+            _mempool_watch.watched_outpoints()
+        };
+        if watched_outpoints.is_empty() {
+            continue;
+        }
+        let confirmations = {
+            let rpc_holder = rpc_holder.clone();
+            tokio::task::spawn_blocking(move || {
+                let (chain_tip, _) = get_chain_tip(&rpc_holder).ok()?;
+                let confirmations: Vec<(OutPoint, Option<u32>)> = watched_outpoints
+                    .into_iter()
+                    .map(|outpoint| {
+                        retrieve_txout_confirmations(&rpc_holder, &outpoint)
+                            .ok()
+                            .map(|confirmations| (outpoint, confirmations))
+                    })
+                    .collect::<Option<_>>()?;
+                Some((chain_tip, confirmations))
+            })
+            .await
+        };
+        let (chain_tip, confirmations) = match confirmations {
+            Ok(Some(confirmations)) => confirmations,
+            // Retry on the next poll.
+            _ => continue,
+        };
+
+        // 6 Update them.
+        {
+            let mut _mempool_watch = mempool_watch.lock().await;
+            for (outpoint, confirmations) in confirmations.iter() {
+                _mempool_watch.update_confirmations(
+                    outpoint,
+                    *confirmations,
+                    mempool_txids.contains(&outpoint.txid),
+                    chain_tip,
+                    bitcoin_sync_height_tip,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mempool_watch;
+pub mod pending_txn;
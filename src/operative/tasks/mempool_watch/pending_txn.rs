@@ -0,0 +1,101 @@
+use bitcoin::{OutPoint, Txid};
+use serde_json::{Map, Value};
+
+/// Kind of a watched unconfirmed transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PendingTxnKind {
+    /// Funds a lift owned by the node's own account.
+    Deposit,
+    /// Spends the payload tip, i.e. commits the next batch.
+    Commitment,
+}
+
+impl PendingTxnKind {
+    /// Returns the kind as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingTxnKind::Deposit => "deposit",
+            PendingTxnKind::Commitment => "commitment",
+        }
+    }
+}
+
+/// A relevant transaction seen in the Bitcoin mempool and tracked until the node has synced it.
+#[derive(Clone, Debug)]
+pub struct PendingTxn {
+    // Txid of the transaction.
+    pub txid: Txid,
+
+    // Kind of the transaction.
+    pub kind: PendingTxnKind,
+
+    // Output whose confirmations are tracked.
+    pub watched_outpoint: OutPoint,
+
+    // Value of the watched output in satoshis.
+    pub value: u64,
+
+    // Unix timestamp of when the transaction was first seen.
+    pub first_seen: u64,
+
+    // Number of confirmations, `0` while unconfirmed.
+    pub confirmations: u32,
+
+    // Height of the block the transaction confirmed in.
+    pub confirmed_height: Option<u64>,
+}
+
+impl PendingTxn {
+    /// Constructs a new, unconfirmed pending transaction.
+    pub fn new(
+        txid: Txid,
+        kind: PendingTxnKind,
+        watched_outpoint: OutPoint,
+        value: u64,
+        first_seen: u64,
+    ) -> Self {
+        Self {
+            txid,
+            kind,
+            watched_outpoint,
+            value,
+            first_seen,
+            confirmations: 0,
+            confirmed_height: None,
+        }
+    }
+
+    /// Returns the pending transaction as a JSON object.
+    pub fn json(&self) -> Value {
+        // 1 Construct the JSON object.
+        let mut obj = Map::new();
+        obj.insert("txid".to_string(), Value::String(self.txid.to_string()));
+        obj.insert(
+            "kind".to_string(),
+            Value::String(self.kind.as_str().to_string()),
+        );
+        obj.insert(
+            "outpoint".to_string(),
+            Value::String(self.watched_outpoint.to_string()),
+        );
+        obj.insert("value".to_string(), Value::Number(self.value.into()));
+        obj.insert(
+            "first_seen".to_string(),
+            Value::Number(self.first_seen.into()),
+        );
+        obj.insert(
+            "confirmations".to_string(),
+            Value::Number(self.confirmations.into()),
+        );
+        obj.insert(
+            "confirmed_height".to_string(),
+            match self.confirmed_height {
+                Some(height) => Value::Number(height.into()),
+                None => Value::Null,
+            },
+        );
+
+        // 2 Return the JSON object.
+        Value::Object(obj)
+    }
+}
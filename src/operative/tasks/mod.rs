@@ -1,3 +1,15 @@
+pub mod chain_health;
 pub mod chain_sync;
+pub mod contract_notice;
+pub mod delta_gossip;
 pub mod engine_session;
+pub mod fee_estimator;
 pub mod in_flight_batch_sync;
+pub mod liveness;
+pub mod mempool;
+pub mod mempool_watch;
+pub mod outbox_delivery;
+pub mod peer_announcement;
+pub mod rebroadcast;
+pub mod telemetry;
+pub mod webhook_delivery;
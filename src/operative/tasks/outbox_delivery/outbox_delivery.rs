@@ -0,0 +1,95 @@
+use crate::communicative::outbox::outbox::OUTBOX;
+use crate::communicative::peer::peer::{PeerConnection, PEER};
+use crate::communicative::tcp::package::TCPPackage;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use chrono::Utc;
+use colored::Colorize;
+use std::time::Duration;
+
+/// Interval between two checks of the outbox.
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Timeout for the delivery of a single outbound message.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Background loop to deliver the messages queued in the outbox for a peer, until the peer
+/// acknowledges them or they expire.
+///
+/// A non-empty response is taken as the acknowledgement, since the peer handled the message; an empty
+/// response or a connection error is a failed attempt, retried with backoff. Delivery to the peer
+/// pauses at the first connection error, so that messages keep their order while it is unreachable.
+pub async fn outbox_delivery_background_task(outbox: &OUTBOX, peer: &PEER) {
+    loop {
+        // 1 Wait for the next check.
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        // 2 Get the messages due for delivery.
+        let peer_key = peer.key().await;
+        let now = Utc::now().timestamp() as u64;
+        let due = {
+            let _outbox = outbox.lock().await;
+            _outbox.due(peer_key, now)
+        };
+
+        // 3 Deliver them in order.
+        for message in due {
+            // 3.a Drop messages of an unknown package kind.
+            let package_kind = match message.package_kind() {
+                Some(package_kind) => package_kind,
+                None => {
+                    let mut _outbox = outbox.lock().await;
+                    let _ = _outbox.acknowledge(peer_key, message.id);
+                    continue;
+                }
+            };
+
+            // 3.b Send the request package, timestamped afresh.
+            let package = TCPPackage::new(package_kind, Utc::now().timestamp(), &message.payload);
+            let result = peer.request(package, Some(DELIVERY_TIMEOUT)).await;
+
+            // 3.c Acknowledge or back off the message.
+            let mut _outbox = outbox.lock().await;
+            match result {
+                Ok((response_package, _)) if response_package.payload_len() > 0 => {
+                    let _ = _outbox.acknowledge(peer_key, message.id);
+                    if log_enabled(LogLevel::Debug) {
+                        println!(
+                            "Delivered outbound message #{} after {} failed attempts.",
+                            message.id, message.attempts
+                        );
+                    }
+                }
+                Ok(_) => {
+                    let _ = _outbox.record_failure(
+                        peer_key,
+                        message.id,
+                        "Empty response".to_string(),
+                        now,
+                    );
+                }
+                Err(err) => {
+                    let _ = _outbox.record_failure(peer_key, message.id, format!("{:?}", err), now);
+                    break;
+                }
+            }
+        }
+
+        // 4 Give up on the messages unacknowledged past the expiry.
+        let expired = {
+            let mut _outbox = outbox.lock().await;
+            _outbox.expire(now).unwrap_or_default()
+        };
+        for message in expired {
+            eprintln!(
+                "{}",
+                format!(
+                    "Outbound message #{} expired after {} failed attempts: {}",
+                    message.id,
+                    message.attempts,
+                    message.last_error.unwrap_or_default()
+                )
+                .yellow()
+            );
+        }
+    }
+}
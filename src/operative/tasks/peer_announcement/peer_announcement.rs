@@ -0,0 +1,79 @@
+use crate::communicative::discovery::announcement::{PeerAnnouncement, PeerAnnouncementKind};
+use crate::communicative::discovery::peer_table::ANNOUNCEMENT_INTERVAL_SECS;
+use crate::communicative::handshake::capability::Capability;
+use crate::communicative::nns::client::NNSClient;
+use crate::communicative::nns::server::query_ip_address;
+use crate::communicative::tcp::tcp::port_number;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::key::KeyHolder;
+use chrono::Utc;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// Background loop to publish a signed announcement of the running peer on every announcement
+/// interval, so that other peers can discover it and keep it in their peer table.
+pub async fn peer_announcement_background_task(
+    nns_client: &NNSClient,
+    key_holder: &KeyHolder,
+    chain: Chain,
+    kind: PeerAnnouncementKind,
+    capabilities: Vec<Capability>,
+) {
+    loop {
+        // 1 Resolve the endpoint of the running machine.
+        let endpoint = match query_ip_address().await {
+            Ok(ip_address) => match ip_address.trim().parse::<IpAddr>() {
+                Ok(ip_address) => SocketAddr::new(ip_address, port_number(chain)),
+                Err(_) => {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+            },
+            Err(_) => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        // 2 Sign the announcement.
+        let announcement = match PeerAnnouncement::sign(
+            key_holder,
+            kind,
+            vec![endpoint],
+            vec![chain],
+            capabilities.clone(),
+            Utc::now().timestamp() as u64,
+        ) {
+            Some(announcement) => announcement,
+            None => {
+                eprintln!("Failed to sign the peer announcement.");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        // 3 Publish the announcement.
+        match nns_client.publish_announcement(&announcement).await {
+            Some(event_id) => {
+                if log_enabled(LogLevel::Debug) {
+                    println!(
+                        "Published peer announcement {}: {}",
+                        hex::encode(event_id),
+                        announcement.json()
+                    );
+                }
+            }
+            None => {
+                if log_enabled(LogLevel::Warn) {
+                    eprintln!("Failed to publish the peer announcement. Re-trying in 5..");
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        }
+
+        // 4 Wait for the next announcement interval.
+        tokio::time::sleep(Duration::from_secs(ANNOUNCEMENT_INTERVAL_SECS)).await;
+    }
+}
@@ -0,0 +1,121 @@
+use bitcoin::hashes::Hash;
+use bitcoin::Txid;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Status of a broadcast transaction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BroadcastStatus {
+    // Not confirmed yet; rebroadcast periodically.
+    Pending,
+    // Confirmed in a block.
+    Confirmed,
+    // Given up on without confirming.
+    Expired,
+}
+
+impl ToString for BroadcastStatus {
+    fn to_string(&self) -> String {
+        match self {
+            BroadcastStatus::Pending => "pending".to_string(),
+            BroadcastStatus::Confirmed => "confirmed".to_string(),
+            BroadcastStatus::Expired => "expired".to_string(),
+        }
+    }
+}
+
+/// A transaction broadcast to the Bitcoin network and tracked until it confirms or expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BroadcastTxn {
+    // Txid of the transaction.
+    pub txid: [u8; 32],
+
+    // Consensus-encoded transaction bytes.
+    pub raw_transaction: Vec<u8>,
+
+    // Number of outputs of the transaction.
+    pub output_count: u32,
+
+    // Status of the transaction.
+    pub status: BroadcastStatus,
+
+    // Unix timestamp (seconds) of the first broadcast.
+    pub first_broadcast_at: u64,
+
+    // Unix timestamp (seconds) of the last broadcast.
+    pub last_broadcast_at: u64,
+
+    // Number of broadcasts so far.
+    pub broadcasts: u32,
+
+    // Number of confirmations, `0` while unconfirmed.
+    pub confirmations: u32,
+
+    // The error of the last failed broadcast.
+    pub last_error: Option<String>,
+}
+
+impl BroadcastTxn {
+    /// Constructs a pending transaction, first broadcast at the given time.
+    pub fn new(txid: [u8; 32], raw_transaction: Vec<u8>, output_count: u32, now: u64) -> Self {
+        Self {
+            txid,
+            raw_transaction,
+            output_count,
+            status: BroadcastStatus::Pending,
+            first_broadcast_at: now,
+            last_broadcast_at: now,
+            broadcasts: 1,
+            confirmations: 0,
+            last_error: None,
+        }
+    }
+
+    /// Returns the txid of the transaction.
+    pub fn txid(&self) -> Txid {
+        Txid::from_byte_array(self.txid)
+    }
+
+    /// Serializes this value with bincode.
+    pub fn serialize(&self) -> Option<Vec<u8>> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).ok()
+    }
+
+    /// Deserializes a broadcast transaction from bincode bytes.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        bincode::serde::decode_from_slice::<Self, _>(bytes, bincode::config::standard())
+            .ok()
+            .map(|(broadcast_txn, _)| broadcast_txn)
+    }
+
+    /// Returns the broadcast transaction as a JSON object, without the transaction bytes.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("txid".to_string(), Value::String(self.txid().to_string()));
+        obj.insert("status".to_string(), Value::String(self.status.to_string()));
+        obj.insert(
+            "first_broadcast_at".to_string(),
+            Value::Number(self.first_broadcast_at.into()),
+        );
+        obj.insert(
+            "last_broadcast_at".to_string(),
+            Value::Number(self.last_broadcast_at.into()),
+        );
+        obj.insert(
+            "broadcasts".to_string(),
+            Value::Number(self.broadcasts.into()),
+        );
+        obj.insert(
+            "confirmations".to_string(),
+            Value::Number(self.confirmations.into()),
+        );
+        obj.insert(
+            "last_error".to_string(),
+            match &self.last_error {
+                Some(error) => Value::String(error.clone()),
+                None => Value::Null,
+            },
+        );
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1 @@
+pub mod rebroadcast_queue_construction_error;
@@ -0,0 +1,6 @@
+/// Errors associated with constructing the rebroadcast queue.
+#[derive(Debug, Clone)]
+pub enum RebroadcastQueueConstructionError {
+    DBOpenError(sled::Error),
+    TreeOpenError(sled::Error),
+}
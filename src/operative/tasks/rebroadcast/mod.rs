@@ -0,0 +1,3 @@
+pub mod broadcast_txn;
+pub mod errors;
+pub mod rebroadcast_queue;
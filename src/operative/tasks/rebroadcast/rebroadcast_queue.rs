@@ -0,0 +1,409 @@
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc::{
+    broadcast_raw_transaction, retrieve_txout_confirmations,
+};
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCBroadcastRawTransactionError;
+use crate::communicative::rpc::bitcoin_rpc::bitcoin_rpc_holder::BitcoinRPCHolder;
+use crate::operative::run_args::chain::Chain;
+use crate::operative::tasks::rebroadcast::broadcast_txn::{BroadcastStatus, BroadcastTxn};
+use crate::operative::tasks::rebroadcast::errors::rebroadcast_queue_construction_error::RebroadcastQueueConstructionError;
+use bitcoin::hashes::Hash;
+use bitcoin::{OutPoint, Transaction, Txid};
+use chrono::Utc;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default interval between two broadcasts of an unconfirmed transaction in seconds.
+const DEFAULT_REBROADCAST_INTERVAL_SECS: u64 = 600;
+
+/// Default time after which an unconfirmed transaction is given up on in seconds, matching the
+/// default mempool expiry of Bitcoin Core.
+const DEFAULT_EXPIRY_SECS: u64 = 1_209_600;
+
+/// Number of confirmations after which a transaction is no longer tracked.
+const FINAL_CONFIRMATIONS: u32 = 6;
+
+/// Number of confirmed or expired transactions kept for status queries.
+const MAX_SETTLED_TXNS: usize = 64;
+
+/// Interval between two checks of the tracked transactions.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Persistent queue of transactions broadcast to the Bitcoin network, rebroadcast periodically
+/// until they confirm or expire.
+///
+/// A transaction dropped from the mempools of the Bitcoin network, such as after a restart of the
+/// Bitcoin node or a fee spike, is resubmitted every rebroadcast interval while unconfirmed.
+/// Transactions that confirm are tracked until they are buried, so that one reorged out is
+/// rebroadcast too.
+pub struct RebroadcastQueue {
+    // Interval between two broadcasts of an unconfirmed transaction.
+    rebroadcast_interval: u64,
+
+    // Time after which an unconfirmed transaction is given up on.
+    expiry: u64,
+
+    // In-memory pending transactions by txid.
+    pending: BTreeMap<[u8; 32], BroadcastTxn>,
+
+    // In-memory confirmed or expired transactions, the most recently settled last.
+    settled: VecDeque<BroadcastTxn>,
+
+    // On-disk pending transactions.
+    on_disk_pending: sled::Tree,
+}
+
+/// Guarded 'RebroadcastQueue'.
+#[allow(non_camel_case_types)]
+pub type REBROADCAST_QUEUE = Arc<Mutex<RebroadcastQueue>>;
+
+impl RebroadcastQueue {
+    /// Constructs the rebroadcast queue, loading the pending transactions from disk.
+    ///
+    /// `CUBE_REBROADCAST_INTERVAL_SECS` and `CUBE_REBROADCAST_EXPIRY_SECS` optionally override the
+    /// interval between two broadcasts of an unconfirmed transaction and the time after which it
+    /// is given up on.
+    pub fn new(chain: Chain) -> Result<REBROADCAST_QUEUE, RebroadcastQueueConstructionError> {
+        // 1 Open the rebroadcast queue db.
+        let db_path = format!("storage/{}/rebroadcast_queue", chain.to_string());
+        let db = sled::open(db_path).map_err(RebroadcastQueueConstructionError::DBOpenError)?;
+
+        // 2 Open the pending tree.
+        let on_disk_pending = db
+            .open_tree("pending")
+            .map_err(RebroadcastQueueConstructionError::TreeOpenError)?;
+
+        // 3 Load the pending transactions, skipping corrupt ones.
+        let mut pending = BTreeMap::new();
+        for (_, value) in on_disk_pending.iter().filter_map(|item| item.ok()) {
+            if let Some(broadcast_txn) = BroadcastTxn::deserialize(value.as_ref()) {
+                pending.insert(broadcast_txn.txid, broadcast_txn);
+            }
+        }
+
+        // 4 Read the intervals.
+        let read_u64 = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+        };
+
+        // 5 Construct the rebroadcast queue.
+        let rebroadcast_queue = RebroadcastQueue {
+            rebroadcast_interval: read_u64("CUBE_REBROADCAST_INTERVAL_SECS")
+                .unwrap_or(DEFAULT_REBROADCAST_INTERVAL_SECS),
+            expiry: read_u64("CUBE_REBROADCAST_EXPIRY_SECS").unwrap_or(DEFAULT_EXPIRY_SECS),
+            pending,
+            settled: VecDeque::new(),
+            on_disk_pending,
+        };
+
+        // 6 Guard and return the rebroadcast queue.
+        Ok(Arc::new(Mutex::new(rebroadcast_queue)))
+    }
+
+    /// Tracks a transaction broadcast at the given time, or records another broadcast of an
+    /// already tracked one.
+    ///
+    /// The transaction is tracked in memory even if persisting it fails.
+    pub fn track(
+        &mut self,
+        transaction: &Transaction,
+        error: Option<String>,
+        now: u64,
+    ) -> Result<(), sled::Error> {
+        let txid = transaction.compute_txid();
+        if self.pending.contains_key(&txid.to_byte_array()) {
+            return self.record_rebroadcast(&txid, error, now);
+        }
+
+        let broadcast_txn = BroadcastTxn::new(
+            txid.to_byte_array(),
+            bitcoin::consensus::encode::serialize(transaction),
+            transaction.output.len() as u32,
+            now,
+        );
+        self.persist(BroadcastTxn {
+            last_error: error,
+            ..broadcast_txn
+        })
+    }
+
+    /// Returns the pending transactions.
+    pub fn pending_txns(&self) -> Vec<BroadcastTxn> {
+        self.pending.values().cloned().collect()
+    }
+
+    /// Returns the unconfirmed transactions due for a rebroadcast at the given time.
+    pub fn due_for_rebroadcast(&self, now: u64) -> Vec<BroadcastTxn> {
+        self.pending
+            .values()
+            .filter(|broadcast_txn| {
+                broadcast_txn.confirmations == 0
+                    && broadcast_txn.last_broadcast_at + self.rebroadcast_interval <= now
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Records a rebroadcast of a pending transaction.
+    pub fn record_rebroadcast(
+        &mut self,
+        txid: &Txid,
+        error: Option<String>,
+        now: u64,
+    ) -> Result<(), sled::Error> {
+        let mut broadcast_txn = match self.pending.remove(&txid.to_byte_array()) {
+            Some(broadcast_txn) => broadcast_txn,
+            None => return Ok(()),
+        };
+        broadcast_txn.last_broadcast_at = now;
+        broadcast_txn.broadcasts += 1;
+        broadcast_txn.last_error = error;
+        self.persist(broadcast_txn)
+    }
+
+    /// Updates the confirmations of a pending transaction, settling it once buried.
+    pub fn update_confirmations(
+        &mut self,
+        txid: &Txid,
+        confirmations: u32,
+    ) -> Result<(), sled::Error> {
+        let mut broadcast_txn = match self.pending.remove(&txid.to_byte_array()) {
+            Some(broadcast_txn) => broadcast_txn,
+            None => return Ok(()),
+        };
+        broadcast_txn.confirmations = confirmations;
+        match confirmations >= FINAL_CONFIRMATIONS {
+            true => self.settle(broadcast_txn, BroadcastStatus::Confirmed),
+            false => self.persist(broadcast_txn),
+        }
+    }
+
+    /// Stops tracking the transactions first broadcast before the expiry, returning the txids of
+    /// those given up on without confirming.
+    ///
+    /// Transactions seen confirmed are settled as confirmed, as their outputs may be spent by the
+    /// time they are buried.
+    pub fn expire(&mut self, now: u64) -> Result<Vec<Txid>, sled::Error> {
+        let expired: Vec<BroadcastTxn> = self
+            .pending
+            .values()
+            .filter(|broadcast_txn| broadcast_txn.first_broadcast_at + self.expiry <= now)
+            .cloned()
+            .collect();
+
+        let mut expired_txids = Vec::new();
+        for broadcast_txn in expired {
+            self.pending.remove(&broadcast_txn.txid);
+            match broadcast_txn.confirmations {
+                0 => {
+                    expired_txids.push(broadcast_txn.txid());
+                    self.settle(broadcast_txn, BroadcastStatus::Expired)?;
+                }
+                _ => self.settle(broadcast_txn, BroadcastStatus::Confirmed)?,
+            }
+        }
+        Ok(expired_txids)
+    }
+
+    /// Returns the tracked transaction with the given txid, pending or recently settled.
+    pub fn get(&self, txid: &Txid) -> Option<&BroadcastTxn> {
+        let txid = txid.to_byte_array();
+        self.pending.get(&txid).or_else(|| {
+            self.settled
+                .iter()
+                .rev()
+                .find(|broadcast_txn| broadcast_txn.txid == txid)
+        })
+    }
+
+    /// Returns the rebroadcast queue as a JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(
+            "pending".to_string(),
+            Value::Array(self.pending.values().map(BroadcastTxn::json).collect()),
+        );
+        obj.insert(
+            "settled".to_string(),
+            Value::Array(self.settled.iter().map(BroadcastTxn::json).collect()),
+        );
+        Value::Object(obj)
+    }
+
+    /// Keeps a pending transaction in memory and on disk.
+    fn persist(&mut self, broadcast_txn: BroadcastTxn) -> Result<(), sled::Error> {
+        let txid = broadcast_txn.txid;
+        let bytes = broadcast_txn.serialize().unwrap_or_default();
+        self.pending.insert(txid, broadcast_txn);
+        self.on_disk_pending.insert(txid, bytes)?;
+        Ok(())
+    }
+
+    /// Stops tracking a transaction, keeping it among the recently settled ones.
+    fn settle(
+        &mut self,
+        mut broadcast_txn: BroadcastTxn,
+        status: BroadcastStatus,
+    ) -> Result<(), sled::Error> {
+        let txid = broadcast_txn.txid;
+        broadcast_txn.status = status;
+        self.settled.push_back(broadcast_txn);
+        while self.settled.len() > MAX_SETTLED_TXNS {
+            self.settled.pop_front();
+        }
+        self.on_disk_pending.remove(txid)?;
+        Ok(())
+    }
+}
+
+/// Broadcasts a raw transaction and tracks it for rebroadcasting until it confirms or expires.
+///
+/// A transaction that fails to broadcast with a transient error is tracked all the same, so that
+/// it is rebroadcast even if the caller gives up on it. A transaction already confirmed is not
+/// tracked.
+pub async fn broadcast_transaction(
+    rebroadcast_queue: &REBROADCAST_QUEUE,
+    rpc_holder: &BitcoinRPCHolder,
+    raw_transaction: &[u8],
+) -> Result<Txid, BitcoinRPCBroadcastRawTransactionError> {
+    // 1 Decode the transaction.
+    let transaction: Transaction = bitcoin::consensus::encode::deserialize(raw_transaction)
+        .map_err(BitcoinRPCBroadcastRawTransactionError::DecodeErr)?;
+    let txid = transaction.compute_txid();
+
+    // 2 Broadcast the transaction.
+    let result = broadcast_raw_transaction(rpc_holder, &hex::encode(raw_transaction));
+
+    // 3 Track the transaction unless it was rejected for good.
+    let now = Utc::now().timestamp() as u64;
+    let mut _rebroadcast_queue = rebroadcast_queue.lock().await;
+    match result {
+        Ok(_) => {
+            let _ = _rebroadcast_queue.track(&transaction, None, now);
+            Ok(txid)
+        }
+        Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err)) if err.is_already_in_chain() => {
+            Ok(txid)
+        }
+        Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err)) if err.is_transient() => {
+            let _ = _rebroadcast_queue.track(&transaction, Some(err.to_string()), now);
+            Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Background loop to rebroadcast the tracked transactions until they confirm or expire.
+pub async fn rebroadcast_background_task(
+    rebroadcast_queue: &REBROADCAST_QUEUE,
+    rpc_holder: &BitcoinRPCHolder,
+) {
+    loop {
+        // 1 Wait for the next check.
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        // 2 Retrieve the confirmations of the pending transactions from their first unspent output.
+        let pending_txns = {
+            let _rebroadcast_queue = rebroadcast_queue.lock().await;
+            _rebroadcast_queue.pending_txns()
+        };
+        if pending_txns.is_empty() {
+            continue;
+        }
+        let confirmations: Vec<(Txid, u32)> = {
+            let rpc_holder = rpc_holder.clone();
+            tokio::task::spawn_blocking(move || {
+                pending_txns
+                    .iter()
+                    .filter_map(|broadcast_txn| {
+                        let txid = broadcast_txn.txid();
+                        (0..broadcast_txn.output_count)
+                            .map(|vout| {
+                                retrieve_txout_confirmations(
+                                    &rpc_holder,
+                                    &OutPoint::new(txid, vout),
+                                )
+                            })
+                            .find_map(|confirmations| confirmations.ok().flatten())
+                            .map(|confirmations| (txid, confirmations))
+                    })
+                    .collect()
+            })
+            .await
+            .unwrap_or_default()
+        };
+        {
+            let mut _rebroadcast_queue = rebroadcast_queue.lock().await;
+            for (txid, confirmations) in confirmations.iter() {
+                let _ = _rebroadcast_queue.update_confirmations(txid, *confirmations);
+            }
+        }
+
+        // 3 Rebroadcast the unconfirmed transactions due for it.
+        let now = Utc::now().timestamp() as u64;
+        let due = {
+            let _rebroadcast_queue = rebroadcast_queue.lock().await;
+            _rebroadcast_queue.due_for_rebroadcast(now)
+        };
+        for broadcast_txn in due {
+            let txid = broadcast_txn.txid();
+            let result = {
+                let rpc_holder = rpc_holder.clone();
+                let raw_transaction_hex = hex::encode(&broadcast_txn.raw_transaction);
+                tokio::task::spawn_blocking(move || {
+                    broadcast_raw_transaction(&rpc_holder, &raw_transaction_hex)
+                })
+                .await
+            };
+
+            let mut _rebroadcast_queue = rebroadcast_queue.lock().await;
+            match result {
+                Ok(Ok(_)) => {
+                    let _ = _rebroadcast_queue.record_rebroadcast(&txid, None, now);
+                }
+                Ok(Err(BitcoinRPCBroadcastRawTransactionError::RPCErr(err)))
+                    if err.is_already_in_chain() =>
+                {
+                    // Settled once its confirmations are seen on the next check.
+                    let _ = _rebroadcast_queue.record_rebroadcast(&txid, None, now);
+                }
+                Ok(Err(err)) => {
+                    eprintln!(
+                        "{}",
+                        format!("Failed to rebroadcast transaction {}: {}", txid, err).yellow()
+                    );
+                    let _ =
+                        _rebroadcast_queue.record_rebroadcast(&txid, Some(err.to_string()), now);
+                }
+                Err(_) => {}
+            }
+        }
+
+        // 4 Give up on the transactions unconfirmed past the expiry.
+        let expired = {
+            let mut _rebroadcast_queue = rebroadcast_queue.lock().await;
+            _rebroadcast_queue.expire(now).unwrap_or_default()
+        };
+        for txid in expired {
+            eprintln!(
+                "{}",
+                format!("Transaction {} expired without confirming.", txid).yellow()
+            );
+        }
+    }
+}
+
+/// Erases the rebroadcast queue by db path.
+pub fn erase_rebroadcast_queue(chain: Chain) {
+    // Rebroadcast queue db path.
+    let db_path = format!("storage/{}/rebroadcast_queue", chain.to_string());
+
+    // Erase the rebroadcast queue db path.
+    let _ = std::fs::remove_dir_all(db_path);
+}
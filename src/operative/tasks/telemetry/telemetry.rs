@@ -0,0 +1,151 @@
+use crate::inscriptive::sync_manager::sync_manager::SYNC_MANAGER;
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use crate::operative::run_args::chain::Chain;
+use crate::operative::run_args::operating_kind::OperatingKind;
+use crate::operative::run_args::resource_mode::ResourceMode;
+use crate::operative::tasks::telemetry::telemetry_config::TelemetryConfig;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Timeout for posting a single report.
+const TELEMETRY_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Anonymous telemetry report.
+///
+/// Carries no keys, addresses or account data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryReport {
+    // Cube version.
+    pub version: String,
+
+    // Chain and operating setup.
+    pub chain: String,
+    pub operating_kind: String,
+    pub resource_mode: String,
+
+    // Sync heights.
+    pub bitcoin_sync_height_tip: u64,
+    pub cube_batch_sync_height_tip: u64,
+
+    // Coarse performance stats.
+    pub uptime_secs: u64,
+    pub batches_synced_since_start: u64,
+    pub batches_synced_per_hour: u64,
+}
+
+impl TelemetryReport {
+    /// Constructs a report from the setup, the sync heights and the uptime.
+    ///
+    /// `starting_cube_batch_sync_height_tip` is the batch sync height tip when the node started, which
+    /// the performance stats are computed against.
+    pub fn new(
+        chain: Chain,
+        operating_kind: OperatingKind,
+        resource_mode: ResourceMode,
+        bitcoin_sync_height_tip: u64,
+        cube_batch_sync_height_tip: u64,
+        starting_cube_batch_sync_height_tip: u64,
+        uptime_secs: u64,
+    ) -> Self {
+        // 1 Compute the coarse performance stats.
+        let batches_synced_since_start =
+            cube_batch_sync_height_tip.saturating_sub(starting_cube_batch_sync_height_tip);
+        let batches_synced_per_hour = match uptime_secs {
+            0 => 0,
+            _ => batches_synced_since_start * 3_600 / uptime_secs,
+        };
+
+        // 2 Return the report.
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            chain: chain.to_string(),
+            operating_kind: operating_kind.to_string(),
+            resource_mode: resource_mode.to_string(),
+            bitcoin_sync_height_tip,
+            cube_batch_sync_height_tip,
+            uptime_secs,
+            batches_synced_since_start,
+            batches_synced_per_hour,
+        }
+    }
+}
+
+/// Periodically posts an anonymous telemetry report to the configured endpoint.
+///
+/// Only spawned when telemetry is opted into; failed reports, including ones the collector answers
+/// with a non-2xx status, are logged and skipped, never retried.
+pub async fn telemetry_background_task(
+    config: TelemetryConfig,
+    chain: Chain,
+    operating_kind: OperatingKind,
+    resource_mode: ResourceMode,
+    sync_manager: &SYNC_MANAGER,
+) {
+    // 1 Record the starting point for the performance stats.
+    let started_at = Instant::now();
+    let starting_cube_batch_sync_height_tip = {
+        let _sync_manager = sync_manager.lock().await;
+        _sync_manager.cube_batch_sync_height_tip()
+    };
+
+    // 2 Construct the HTTP client.
+    let client = match reqwest::Client::builder()
+        .timeout(TELEMETRY_REQUEST_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(_) => return,
+    };
+
+    loop {
+        // 3 Wait for the next reporting interval.
+        tokio::time::sleep(config.interval).await;
+
+        // 4 Collect the sync heights.
+        let (bitcoin_sync_height_tip, cube_batch_sync_height_tip) = {
+            let _sync_manager = sync_manager.lock().await;
+            (
+                _sync_manager.bitcoin_sync_height_tip(),
+                _sync_manager.cube_batch_sync_height_tip(),
+            )
+        };
+
+        // 5 Construct the report.
+        let report = TelemetryReport::new(
+            chain,
+            operating_kind,
+            resource_mode,
+            bitcoin_sync_height_tip,
+            cube_batch_sync_height_tip,
+            starting_cube_batch_sync_height_tip,
+            started_at.elapsed().as_secs(),
+        );
+
+        // 6 Post the report.
+        let body = match serde_json::to_string(&report) {
+            Ok(body) => body,
+            Err(_) => continue,
+        };
+        let result = client
+            .post(&config.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await;
+
+        // 7 Log the failed reports, whether the collector was unreachable or refused the report.
+        if !log_enabled(LogLevel::Warn) {
+            continue;
+        }
+        match result {
+            Ok(response) if !response.status().is_success() => eprintln!(
+                "{} Telemetry report refused by the collector with status {}.",
+                "Warning:".yellow(),
+                response.status()
+            ),
+            Ok(_) => {}
+            Err(err) => eprintln!("{} Telemetry report failed: {}", "Warning:".yellow(), err),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+/// Default reporting interval in seconds.
+const DEFAULT_TELEMETRY_INTERVAL_SECS: u64 = 3_600;
+
+/// Minimum reporting interval in seconds.
+const MIN_TELEMETRY_INTERVAL_SECS: u64 = 60;
+
+/// Opt-in telemetry configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    // Endpoint the reports are posted to.
+    pub endpoint: String,
+
+    // Interval between two reports.
+    pub interval: Duration,
+}
+
+impl TelemetryConfig {
+    /// Reads the telemetry configuration from the environment.
+    ///
+    /// Telemetry is disabled (returns `None`) unless `CUBE_TELEMETRY_ENDPOINT` is set.
+    /// `CUBE_TELEMETRY_INTERVAL_SECS` optionally overrides the reporting interval.
+    pub fn from_env() -> Option<Self> {
+        // 1 Read the endpoint; telemetry stays disabled without one.
+        let endpoint = std::env::var("CUBE_TELEMETRY_ENDPOINT").ok()?;
+        let endpoint = endpoint.trim();
+        if endpoint.is_empty() {
+            return None;
+        }
+
+        // 2 Read the reporting interval, falling back to the default.
+        let interval_secs = std::env::var("CUBE_TELEMETRY_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TELEMETRY_INTERVAL_SECS)
+            .max(MIN_TELEMETRY_INTERVAL_SECS);
+
+        // 3 Return the configuration.
+        Some(Self {
+            endpoint: endpoint.to_string(),
+            interval: Duration::from_secs(interval_secs),
+        })
+    }
+}
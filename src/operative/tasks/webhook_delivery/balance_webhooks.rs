@@ -0,0 +1,174 @@
+use crate::communicative::event_stream::event_stream::{
+    event_stream, EventSubscription, SubscriptionMessage,
+};
+use crate::communicative::event_stream::stream_event::StreamEvent;
+use crate::communicative::event_stream::subscription_filter::SubscriptionFilter;
+use crate::communicative::webhook::webhook_event::WebhookEventKind;
+use crate::communicative::webhook::webhooks::WEBHOOKS;
+use crate::inscriptive::coin_manager::coin_manager::COIN_MANAGER;
+use crate::transmutative::key::ToNostrKeyStr;
+use chrono::Utc;
+use colored::Colorize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Account key.
+type AccountKey = [u8; 32];
+
+/// Interval between two refreshes of the followed accounts.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Background loop to queue a balance change webhook whenever an applied entry changes the balance
+/// of an account followed by an endpoint.
+///
+/// The applied entries are read from the event stream, filtered down to the followed accounts, so
+/// that nothing is streamed while no endpoint follows balances. Balances are compared against the
+/// last known one, so an account touched by several entries of a batch is reported once per batch.
+pub async fn balance_webhooks_background_task(webhooks: &WEBHOOKS, coin_manager: &COIN_MANAGER) {
+    // 1 Last known balances of the followed accounts.
+    let mut balances: HashMap<AccountKey, Option<u64>> = HashMap::new();
+
+    // 2 Subscription to the events of the followed accounts, if any.
+    let mut subscription: Option<EventSubscription> = None;
+
+    let mut refresh = tokio::time::interval(REFRESH_INTERVAL);
+    loop {
+        // 3 Wait for the next event or refresh.
+        let message = match subscription.as_mut() {
+            Some(_subscription) => tokio::select! {
+                message = _subscription.next() => message,
+                _ = refresh.tick() => None,
+            },
+            None => {
+                refresh.tick().await;
+                None
+            }
+        };
+
+        match message {
+            // 4 Report the followed accounts involved in the applied entry.
+            Some(SubscriptionMessage::Event(event)) => {
+                for account_key in event.account_keys.iter() {
+                    if balances.contains_key(account_key) {
+                        check_balance(
+                            webhooks,
+                            coin_manager,
+                            &mut balances,
+                            *account_key,
+                            Some(&event),
+                        )
+                        .await;
+                    }
+                }
+            }
+            // 5 Missed events are caught up on by checking every followed account.
+            Some(SubscriptionMessage::Lagged(_)) => {
+                let account_keys: Vec<AccountKey> = balances.keys().cloned().collect();
+                for account_key in account_keys {
+                    check_balance(webhooks, coin_manager, &mut balances, account_key, None).await;
+                }
+            }
+            // 6 Follow the accounts of the endpoints added or removed in the meantime.
+            None => {
+                let followed_accounts = {
+                    let _webhooks = webhooks.lock().await;
+                    _webhooks.followed_accounts()
+                };
+
+                // 6.a Record the current balance of the newly followed accounts.
+                balances.retain(|account_key, _| followed_accounts.contains(account_key));
+                for account_key in followed_accounts.iter() {
+                    if !balances.contains_key(account_key) {
+                        let balance = {
+                            let _coin_manager = coin_manager.lock().await;
+                            _coin_manager.get_account_balance(*account_key)
+                        };
+                        balances.insert(*account_key, balance);
+                    }
+                }
+
+                // 6.b Resubscribe with the followed accounts, or unsubscribe once none is left.
+                if followed_accounts.is_empty() {
+                    subscription = None;
+                    continue;
+                }
+                let mut filter = SubscriptionFilter::new();
+                filter.account_keys = followed_accounts;
+                match subscription.as_mut() {
+                    Some(_subscription) => _subscription.set_filter(filter),
+                    None => subscription = Some(event_stream().subscribe(filter)),
+                }
+            }
+        }
+    }
+}
+
+/// Queues a balance change webhook if the balance of a followed account differs from the last
+/// known one.
+async fn check_balance(
+    webhooks: &WEBHOOKS,
+    coin_manager: &COIN_MANAGER,
+    balances: &mut HashMap<AccountKey, Option<u64>>,
+    account_key: AccountKey,
+    event: Option<&StreamEvent>,
+) {
+    // 1 Read the balance and compare it with the last known one.
+    let balance = {
+        let _coin_manager = coin_manager.lock().await;
+        _coin_manager.get_account_balance(account_key)
+    };
+    let previous_balance = match balances.insert(account_key, balance) {
+        Some(previous_balance) if previous_balance != balance => previous_balance,
+        _ => return,
+    };
+
+    // 2 Construct the event data.
+    let mut obj = Map::new();
+    obj.insert(
+        "account".to_string(),
+        match account_key.to_npub() {
+            Some(npub) => Value::String(npub),
+            None => Value::String(hex::encode(account_key)),
+        },
+    );
+    obj.insert(
+        "balance".to_string(),
+        balance.map_or(Value::Null, |balance| Value::Number(balance.into())),
+    );
+    obj.insert(
+        "previous_balance".to_string(),
+        previous_balance.map_or(Value::Null, |balance| Value::Number(balance.into())),
+    );
+    obj.insert(
+        "batch_height".to_string(),
+        event.map_or(Value::Null, |event| {
+            Value::Number(event.batch_height.into())
+        }),
+    );
+    obj.insert(
+        "entry_id".to_string(),
+        event.map_or(Value::Null, |event| {
+            Value::String(hex::encode(event.entry_id))
+        }),
+    );
+    obj.insert(
+        "entry_kind".to_string(),
+        event.map_or(Value::Null, |event| Value::String(event.kind.to_string())),
+    );
+
+    // 3 Queue the webhook.
+    let now = Utc::now().timestamp() as u64;
+    let mut _webhooks = webhooks.lock().await;
+    if let Err(err) = _webhooks.enqueue(
+        WebhookEventKind::BalanceChanged,
+        Some(account_key),
+        Value::Object(obj),
+        now,
+    ) {
+        eprintln!(
+            "{}",
+            format!("Failed to queue a balance change webhook: {}", err).yellow()
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod balance_webhooks;
+pub mod webhook_delivery;
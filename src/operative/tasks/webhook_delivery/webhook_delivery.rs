@@ -0,0 +1,113 @@
+use crate::communicative::webhook::webhook_signature::{
+    webhook_signature_header, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER,
+    WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+};
+use crate::communicative::webhook::webhooks::{WebhookFailureOutcome, WEBHOOKS};
+use crate::operative::logging::log_level::{log_enabled, LogLevel};
+use chrono::Utc;
+use colored::Colorize;
+use std::time::Duration;
+
+/// Interval between two checks of the queued deliveries.
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Timeout for a single delivery attempt.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Background loop to post the queued webhook deliveries to their endpoints.
+///
+/// Each delivery is signed afresh with the endpoint secret at every attempt. A 2xx response is taken
+/// as the acknowledgement; any other status or a connection error is a failed attempt, retried with
+/// backoff until the delivery runs out of attempts.
+pub async fn webhook_delivery_background_task(webhooks: &WEBHOOKS) {
+    // 1 Construct the HTTP client.
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!(
+                "{}",
+                format!("Failed to construct the webhook HTTP client: {}", err).red()
+            );
+            return;
+        }
+    };
+
+    loop {
+        // 2 Wait for the next check.
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        // 3 Get the deliveries due for an attempt, along with their endpoints.
+        let now = Utc::now().timestamp() as u64;
+        let due = {
+            let _webhooks = webhooks.lock().await;
+            _webhooks
+                .due(now)
+                .into_iter()
+                .map(|delivery| {
+                    let endpoint = _webhooks.endpoint(delivery.endpoint_id).cloned();
+                    (delivery, endpoint)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // 4 Post them.
+        for (delivery, endpoint) in due {
+            // 4.a Drop deliveries whose endpoint was removed in the meantime.
+            let Some(endpoint) = endpoint else {
+                let mut _webhooks = webhooks.lock().await;
+                let _ = _webhooks.acknowledge(delivery.id);
+                continue;
+            };
+
+            // 4.b Sign and post the body.
+            let timestamp = Utc::now().timestamp() as u64;
+            let result = client
+                .post(&endpoint.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(WEBHOOK_EVENT_HEADER, delivery.kind.to_string())
+                .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+                .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+                .header(
+                    WEBHOOK_SIGNATURE_HEADER,
+                    webhook_signature_header(&endpoint.secret, timestamp, &delivery.body),
+                )
+                .body(delivery.body.clone())
+                .send()
+                .await;
+
+            // 4.c Acknowledge or back off the delivery.
+            let error = match result {
+                Ok(response) if response.status().is_success() => {
+                    let mut _webhooks = webhooks.lock().await;
+                    let _ = _webhooks.acknowledge(delivery.id);
+                    if log_enabled(LogLevel::Debug) {
+                        println!(
+                            "Delivered webhook #{} to {} after {} failed attempts.",
+                            delivery.id, endpoint.url, delivery.attempts
+                        );
+                    }
+                    continue;
+                }
+                Ok(response) => format!("HTTP {}", response.status()),
+                Err(err) => err.to_string(),
+            };
+            let outcome = {
+                let mut _webhooks = webhooks.lock().await;
+                _webhooks.record_failure(delivery.id, error.clone(), now)
+            };
+            if let Ok(WebhookFailureOutcome::GivenUp) = outcome {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Webhook #{} to {} given up on after {} failed attempts: {}",
+                        delivery.id,
+                        endpoint.url,
+                        delivery.attempts + 1,
+                        error
+                    )
+                    .yellow()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+/// Errors associated with verifying a BIP-322 signed message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bip322Error {
+    // The address does not decode for the chain.
+    InvalidAddress,
+    // The address is valid but not a P2TR address.
+    UnsupportedAddress,
+    // The signature is not a base64-encoded witness with a single Schnorr signature.
+    MalformedSignature,
+    // The signature commits to a sighash type other than SIGHASH_DEFAULT or SIGHASH_ALL.
+    UnsupportedSighashType(u8),
+    // The signature does not verify against the output key of the address.
+    InvalidSignature,
+}
@@ -0,0 +1,187 @@
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::bip322::error::Bip322Error;
+use crate::transmutative::codec::address::{address_to_spk, encode_p2tr};
+use crate::transmutative::codec::varint::encode_varint;
+use crate::transmutative::hash::{sha256, Hash, HashTag};
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::context::GeneratorMul;
+use crate::transmutative::secp::schnorr::{self, Bytes32, SchnorrSigningMode};
+use bitcoincore_rpc::jsonrpc::base64;
+use secp::{MaybePoint, Scalar};
+
+/// BIP-341 default sighash type, committing to the whole transaction with a 64-byte signature.
+const SIGHASH_DEFAULT: u8 = 0x00;
+
+/// SIGHASH_ALL, committing to the whole transaction with a 65-byte signature.
+const SIGHASH_ALL: u8 = 0x01;
+
+/// Script of the single `to_sign` output, `OP_RETURN`.
+const TO_SIGN_OUTPUT_SCRIPT: [u8; 1] = [0x6a];
+
+/// Returns the BIP-322 message hash, `hash_BIP0322-signed-message(message)`.
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    message.hash(Some(HashTag::BIP322SignedMessage))
+}
+
+/// Returns the txid of the virtual `to_spend` transaction, whose single output pays to the script
+/// pubkey of the address and whose single input commits to the message.
+pub fn to_spend_txid(script_pubkey: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut tx = Vec::<u8>::new();
+
+    // 1 Version 0 and a single input.
+    tx.extend(0u32.to_le_bytes());
+    tx.extend(encode_varint(1));
+
+    // 2 The input spends the null outpoint with `OP_0 PUSH32 message_hash`, with sequence 0.
+    tx.extend([0u8; 32]);
+    tx.extend(u32::MAX.to_le_bytes());
+    tx.extend(encode_varint(34));
+    tx.push(0x00);
+    tx.push(0x20);
+    tx.extend(message_hash(message));
+    tx.extend(0u32.to_le_bytes());
+
+    // 3 A single zero-value output paying to the script pubkey.
+    tx.extend(encode_varint(1));
+    tx.extend(0u64.to_le_bytes());
+    tx.extend(encode_varint(script_pubkey.len() as u64));
+    tx.extend(script_pubkey);
+
+    // 4 Locktime 0.
+    tx.extend(0u32.to_le_bytes());
+
+    sha256(&sha256(&tx))
+}
+
+/// Returns the BIP-341 key-path sighash of the single input of the virtual `to_sign` transaction,
+/// which spends the `to_spend` output into a single zero-value `OP_RETURN` output.
+///
+/// Returns `None` for sighash types other than SIGHASH_DEFAULT and SIGHASH_ALL.
+pub fn to_sign_sighash(script_pubkey: &[u8], message: &[u8], hash_type: u8) -> Option<[u8; 32]> {
+    // 1 Only the sighash types committing to the whole transaction are supported.
+    if hash_type != SIGHASH_DEFAULT && hash_type != SIGHASH_ALL {
+        return None;
+    }
+
+    // 2 Hash the prevouts, amounts, script pubkeys, sequences and outputs.
+    let mut prevouts = to_spend_txid(script_pubkey, message).to_vec();
+    prevouts.extend(0u32.to_le_bytes());
+
+    let mut script_pubkeys = encode_varint(script_pubkey.len() as u64);
+    script_pubkeys.extend(script_pubkey);
+
+    let mut outputs = 0u64.to_le_bytes().to_vec();
+    outputs.extend(encode_varint(TO_SIGN_OUTPUT_SCRIPT.len() as u64));
+    outputs.extend(TO_SIGN_OUTPUT_SCRIPT);
+
+    // 3 Construct the signature message (epoch 0, version 0, locktime 0, key-path spend of input 0).
+    let mut sigmsg = vec![0x00, hash_type];
+    sigmsg.extend(0u32.to_le_bytes());
+    sigmsg.extend(0u32.to_le_bytes());
+    sigmsg.extend(sha256(&prevouts));
+    sigmsg.extend(sha256(&0u64.to_le_bytes()));
+    sigmsg.extend(sha256(&script_pubkeys));
+    sigmsg.extend(sha256(&0u32.to_le_bytes()));
+    sigmsg.extend(sha256(&outputs));
+    sigmsg.push(0x00);
+    sigmsg.extend(0u32.to_le_bytes());
+
+    Some(sigmsg.hash(Some(HashTag::TapSighash)))
+}
+
+/// Returns the BIP-86 P2TR address of an x-only account key, the key tweaked to commit to no script
+/// path.
+pub fn account_address(chain: Chain, account_key: [u8; 32]) -> Option<String> {
+    // 1 Lift the account key to its even point.
+    let internal_key = account_key.to_even_point()?;
+
+    // 2 Tweak the key with hash_TapTweak(internal_key).
+    let tweak = Scalar::from_slice(&account_key.hash(Some(HashTag::TapTweak))).ok()?;
+    let output_key = match internal_key + tweak.generator_mul() {
+        MaybePoint::Valid(point) => point,
+        MaybePoint::Infinity => return None,
+    };
+
+    // 3 Encode the output key.
+    encode_p2tr(chain, output_key.serialize_xonly())
+}
+
+/// Signs a message with the BIP-322 simple signature scheme, proving control of the BIP-86 P2TR
+/// address of the key holder.
+///
+/// Returns the address and the base64-encoded witness of the signature.
+pub fn sign_simple(
+    key_holder: &KeyHolder,
+    chain: Chain,
+    message: &[u8],
+) -> Option<(String, String)> {
+    // 1 Get the address and its script pubkey.
+    let address = key_holder.taproot_address(chain, None)?;
+    let script_pubkey = address_to_spk(chain, &address)?;
+
+    // 2 Sign the sighash with the tweaked key.
+    let sighash = to_sign_sighash(&script_pubkey, message, SIGHASH_DEFAULT)?;
+    let signature = key_holder.sign_taproot(sighash, None)?;
+
+    // 3 Encode the witness holding the signature.
+    let mut witness = encode_varint(1);
+    witness.extend(encode_varint(signature.len() as u64));
+    witness.extend(signature);
+
+    Some((address, base64::encode(witness)))
+}
+
+/// Verifies a BIP-322 simple signature of a message by a P2TR address.
+pub fn verify_simple(
+    chain: Chain,
+    address: &str,
+    message: &[u8],
+    signature: &str,
+) -> Result<(), Bip322Error> {
+    // 1 Decode the address into a P2TR script pubkey.
+    let script_pubkey = address_to_spk(chain, address).ok_or(Bip322Error::InvalidAddress)?;
+    if script_pubkey.len() != 34 || script_pubkey[0] != 0x51 || script_pubkey[1] != 0x20 {
+        return Err(Bip322Error::UnsupportedAddress);
+    }
+    let mut output_key = [0u8; 32];
+    output_key.copy_from_slice(&script_pubkey[2..]);
+
+    // 2 Decode the witness, which must hold a single signature.
+    let witness = base64::decode(signature.trim()).map_err(|_| Bip322Error::MalformedSignature)?;
+    let (signature, hash_type) = match witness.as_slice() {
+        [0x01, 0x40, signature @ ..] if signature.len() == 64 => (signature, SIGHASH_DEFAULT),
+        [0x01, 0x41, signature @ .., hash_type] if signature.len() == 64 => {
+            if *hash_type != SIGHASH_ALL {
+                return Err(Bip322Error::UnsupportedSighashType(*hash_type));
+            }
+            (signature, *hash_type)
+        }
+        _ => return Err(Bip322Error::MalformedSignature),
+    };
+    let mut signature_bytes = [0u8; 64];
+    signature_bytes.copy_from_slice(signature);
+
+    // 3 Verify the signature against the output key.
+    let sighash = to_sign_sighash(&script_pubkey, message, hash_type)
+        .ok_or(Bip322Error::UnsupportedSighashType(hash_type))?;
+    match schnorr::verify_xonly(
+        output_key,
+        sighash,
+        signature_bytes,
+        SchnorrSigningMode::BIP340,
+    ) {
+        true => Ok(()),
+        false => Err(Bip322Error::InvalidSignature),
+    }
+}
+
+/// Verifies a BIP-322 simple signature of a message by the BIP-86 P2TR address of an account key.
+pub fn verify_account_key(
+    chain: Chain,
+    account_key: [u8; 32],
+    message: &[u8],
+    signature: &str,
+) -> Result<(), Bip322Error> {
+    let address = account_address(chain, account_key).ok_or(Bip322Error::InvalidAddress)?;
+    verify_simple(chain, &address, message, signature)
+}
@@ -8,6 +8,7 @@ pub enum HashTag {
     BIP340Challenge,
     SecretNonce,
     SecretKey,
+    HalfAggRandomizer,
     TapLeaf,
     TapBranch,
     TapTweak,
@@ -16,6 +17,7 @@ pub enum HashTag {
     KeyAggList,
     KeyAggCoef,
     MusigNonceCoef,
+    MusigNonceSession,
     // BLSSecretKey
     BLSSecretKey,
     // Custom
@@ -34,6 +36,7 @@ pub enum HashTag {
     ConfigEntrySighash,
     DeployEntrySighash,
     CallEntrySighash,
+    CallBatchSighash,
     // Entry ID tags
     LiftupEntryID,
     SwapoutEntryID,
@@ -41,6 +44,32 @@ pub enum HashTag {
     ConfigEntryID,
     DeployEntryID,
     CallEntryID,
+    // Merkle
+    MerkleBranch,
+    // State root commitments
+    AccountBalanceLeaf,
+    StateRootCommitment,
+    // Federation
+    DeltaAttestation,
+    ContractNotice,
+    // Host data
+    HostDataAttestation,
+    // Deferred calls
+    DeferredCallID,
+    // Handshake
+    HandshakeAnnouncement,
+    SessionParams,
+    Heartbeat,
+    // Peer discovery
+    PeerAnnouncement,
+    // Pedersen commitments
+    PedersenGenerator,
+    PedersenBitProof,
+    // Contract commitments
+    PayToContract,
+    SignToContract,
+    // BIP-322 signed messages
+    BIP322SignedMessage,
 }
 
 impl HashTag {
@@ -50,6 +79,7 @@ impl HashTag {
             HashTag::BIP340Challenge => format!("{}/{}", "BIP0340", "challenge"),
             HashTag::SecretNonce => format!("{}/{}", baked::PROJECT_TAG, "secretnonce"),
             HashTag::SecretKey => format!("{}/{}", baked::PROJECT_TAG, "secretkey"),
+            HashTag::HalfAggRandomizer => format!("{}/{}", "HalfAgg", "randomizer"),
             HashTag::TapLeaf => format!("TapLeaf"),
             HashTag::TapBranch => format!("TapBranch"),
             HashTag::TapTweak => format!("TapTweak"),
@@ -57,6 +87,9 @@ impl HashTag {
             HashTag::KeyAggList => format!("KeyAgg list"),
             HashTag::KeyAggCoef => format!("KeyAgg coefficient"),
             HashTag::MusigNonceCoef => format!("MuSig/noncecoef"),
+            HashTag::MusigNonceSession => {
+                format!("{}/{}", baked::PROJECT_TAG, "musig/noncesession")
+            }
             HashTag::BLSSecretKey => format!("{}/{}", baked::PROJECT_TAG, "bls/secretkey"),
             HashTag::CustomString(tag) => tag.clone(),
             HashTag::CustomBytes(tag) => tag.clone().into_iter().map(|b| b as char).collect(),
@@ -72,6 +105,7 @@ impl HashTag {
             HashTag::ConfigEntrySighash => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "sighash", "entry", "config"),
             HashTag::DeployEntrySighash => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "sighash", "entry", "deploy"),
             HashTag::CallEntrySighash => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "sighash", "entry", "call"),
+            HashTag::CallBatchSighash => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "sighash", "entry", "callbatch"),
             // Entry IDs
             HashTag::LiftupEntryID => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "id", "entry", "liftup"),
             HashTag::SwapoutEntryID => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "id", "entry", "swapout"),
@@ -79,6 +113,32 @@ impl HashTag {
             HashTag::ConfigEntryID => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "id", "entry", "config"),
             HashTag::DeployEntryID => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "id", "entry", "deploy"),
             HashTag::CallEntryID => format!("{}/{}/{}/{}", baked::PROJECT_TAG, "id", "entry", "call"),
+            // Merkle
+            HashTag::MerkleBranch => format!("{}/{}", baked::PROJECT_TAG, "merkle/branch"),
+            // State root commitments
+            HashTag::AccountBalanceLeaf => format!("{}/{}", baked::PROJECT_TAG, "stateroot/accountbalance"),
+            HashTag::StateRootCommitment => format!("{}/{}", baked::PROJECT_TAG, "stateroot/commitment"),
+            // Federation
+            HashTag::DeltaAttestation => format!("{}/{}", baked::PROJECT_TAG, "federation/deltaattestation"),
+            HashTag::ContractNotice => format!("{}/{}", baked::PROJECT_TAG, "federation/contractnotice"),
+            // Host data
+            HashTag::HostDataAttestation => format!("{}/{}", baked::PROJECT_TAG, "vm/hostdataattestation"),
+            // Deferred calls
+            HashTag::DeferredCallID => format!("{}/{}/{}", baked::PROJECT_TAG, "id", "deferredcall"),
+            // Handshake
+            HashTag::HandshakeAnnouncement => format!("{}/{}", baked::PROJECT_TAG, "handshake/announcement"),
+            HashTag::SessionParams => format!("{}/{}", baked::PROJECT_TAG, "handshake/sessionparams"),
+            HashTag::Heartbeat => format!("{}/{}", baked::PROJECT_TAG, "handshake/heartbeat"),
+            // Peer discovery
+            HashTag::PeerAnnouncement => format!("{}/{}", baked::PROJECT_TAG, "discovery/announcement"),
+            // Pedersen commitments
+            HashTag::PedersenGenerator => format!("{}/{}", baked::PROJECT_TAG, "pedersen/generator"),
+            HashTag::PedersenBitProof => format!("{}/{}", baked::PROJECT_TAG, "pedersen/bitproof"),
+            // Contract commitments
+            HashTag::PayToContract => format!("{}/{}", baked::PROJECT_TAG, "paytocontract"),
+            HashTag::SignToContract => format!("{}/{}", baked::PROJECT_TAG, "signtocontract"),
+            // BIP-322 signed messages
+            HashTag::BIP322SignedMessage => format!("BIP0322-signed-message"),
         }
     }
 }
@@ -3,11 +3,21 @@ use super::bls::key::{
     bls_secret_key_to_bls_public_key, secp_secret_key_bytes_to_bls_secret_key_bytes, BLSPublicKey,
     BLSSecretKey,
 };
-use crate::transmutative::secp::schnorr::Bytes32;
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::codec::address::encode_p2tr;
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::{secp256k1_context, GeneratorMul};
+use crate::transmutative::secp::public_key;
+use crate::transmutative::secp::schnorr::{self, Bytes32, LiftScalar, SchnorrSigningMode};
 use bech32::{Bech32, Hrp};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv};
+use bitcoin::NetworkKind;
 use libc;
-use secp::{Point, Scalar};
-use zeroize::Zeroize;
+use nostr_sdk::nips::nip49::{EncryptedSecretKey, KeySecurity};
+use nostr_sdk::{FromBech32, NostrSigner, SecretKey, ToBech32};
+use secp::{MaybeScalar, Point, Scalar};
+use std::sync::Arc;
+use zeroize::{Zeroize, Zeroizing};
 
 /// A secure wrapper for 32-byte secret key bytes that prevents accidental exposure
 /// and automatically zeroizes memory on drop.
@@ -18,9 +28,11 @@ struct SecretBytes32 {
 }
 
 impl SecretBytes32 {
-    // 1 Create a new SecretBytes32 instance.
-    fn new(bytes: [u8; 32]) -> Self {
-        Self { bytes }
+    // 1 Create a new SecretBytes32 instance, zeroizing the moved-in copy of the bytes.
+    fn new(mut bytes: [u8; 32]) -> Self {
+        let secret_bytes = Self { bytes };
+        bytes.zeroize();
+        secret_bytes
     }
 
     // 2 Expose the secret bytes. Use with extreme caution.
@@ -52,9 +64,11 @@ struct SecretBytes48 {
 }
 
 impl SecretBytes48 {
-    // 1 Create a new SecretBytes48 instance.
-    fn new(bytes: [u8; 48]) -> Self {
-        Self { bytes }
+    // 1 Create a new SecretBytes48 instance, zeroizing the moved-in copy of the bytes.
+    fn new(mut bytes: [u8; 48]) -> Self {
+        let secret_bytes = Self { bytes };
+        bytes.zeroize();
+        secret_bytes
     }
 
     // 2 Expose the secret bytes. Use with extreme caution.
@@ -77,6 +91,42 @@ impl Drop for SecretBytes48 {
     }
 }
 
+/// BIP32 purpose of the keys derived for Cube, "CUBE" in ASCII.
+const CUBE_DERIVATION_PURPOSE: u32 = 0x4355_4245;
+
+/// What a derived key is used for, each purpose with its own derivation path.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyPurpose {
+    /// The identity of the node towards the engine and its peers.
+    NodeIdentity,
+    /// Signing as an operator.
+    OperatorSigning,
+    /// Receiving deposits, one key per index.
+    Deposit(u32),
+    /// The secondary aggregation key of the account, one key per rotation.
+    Aggregation(u32),
+}
+
+impl KeyPurpose {
+    /// Returns the hardened derivation path of the purpose, `m/1129660997'/<purpose>'/<index>'`.
+    ///
+    /// Returns `None` if the deposit or aggregation index is out of the hardened range.
+    pub fn derivation_path(&self) -> Option<DerivationPath> {
+        let (purpose, index) = match self {
+            KeyPurpose::NodeIdentity => (0, 0),
+            KeyPurpose::OperatorSigning => (1, 0),
+            KeyPurpose::Deposit(index) => (2, *index),
+            KeyPurpose::Aggregation(index) => (3, *index),
+        };
+        let path = [CUBE_DERIVATION_PURPOSE, purpose, index]
+            .into_iter()
+            .map(ChildNumber::from_hardened_idx)
+            .collect::<Result<Vec<ChildNumber>, _>>()
+            .ok()?;
+        Some(DerivationPath::from(path))
+    }
+}
+
 /// A secure key holder that stores cryptographic keys in memory with automatic zeroization.
 ///
 /// This struct implements best security practices:
@@ -100,6 +150,10 @@ pub struct KeyHolder {
     bls_public_key_bytes: [u8; 48],
     // Track if memory is locked for cleanup
     memory_locked: bool,
+    // BIP32 path the key was derived at from a root key, if derived
+    derivation_path: Option<DerivationPath>,
+    // NIP-46 remote signer that signs Nostr events in place of the local key, if any
+    remote_signer: Option<Arc<dyn NostrSigner>>,
 }
 
 impl KeyHolder {
@@ -109,10 +163,13 @@ impl KeyHolder {
     /// All sensitive data will be automatically zeroed when the `KeyHolder` is dropped.
     /// Memory is locked using `mlock` to prevent swapping to disk.
     ///
+    /// NOTE: The bytes are copied in; callers should keep their own copy in a zeroize-on-drop buffer.
+    ///
     /// Returns `None` if the secret key is invalid.
-    pub fn new(secret_key_bytes: [u8; 32]) -> Option<Self> {
+    pub fn new(mut secret_key_bytes: [u8; 32]) -> Option<Self> {
         // 1 Wrap secp256k1 secret key bytes immediately to prevent accidental exposure.
         let secp_secret_key_bytes = SecretBytes32::new(secret_key_bytes);
+        secret_key_bytes.zeroize();
 
         // 2 Access the secret key bytes for processing.
         let secp_secret_key_bytes_ref = secp_secret_key_bytes.expose_secret();
@@ -124,7 +181,7 @@ impl KeyHolder {
             Err(_) => return None,
         };
         // 3.2 Compute the public key point.
-        let mut secp_public_key_point = secp_scalar.base_point_mul();
+        let mut secp_public_key_point = secp_scalar.generator_mul();
         // 3.3 Normalize the point to even parity.
         secp_public_key_point = secp_public_key_point.negate_if(secp_public_key_point.parity());
         // 3.4 Serialize to 32 bytes (x-only public key).
@@ -160,6 +217,8 @@ impl KeyHolder {
             bls_secret_key_bytes,
             bls_public_key_bytes,
             memory_locked: false,
+            derivation_path: None,
+            remote_signer: None,
         };
 
         // 7 Lock memory to prevent swapping to disk.
@@ -169,6 +228,59 @@ impl KeyHolder {
         Some(key_holder)
     }
 
+    /// Derives a child key holder at the given BIP32 derivation path.
+    ///
+    /// The secret key of this holder is used as the BIP32 seed, so one root secret deterministically
+    /// derives any number of per-purpose keys. Paths are always taken from this holder's secret,
+    /// not appended to the path it was derived at itself.
+    ///
+    /// Returns `None` if the derivation fails, which is vanishingly unlikely.
+    pub fn derive(&self, derivation_path: &DerivationPath) -> Option<KeyHolder> {
+        // 1 Compute the master extended key from the secret key bytes as the seed.
+        let mut master_xpriv = Xpriv::new_master(
+            NetworkKind::Main,
+            self.secp_secret_key_bytes.expose_secret(),
+        )
+        .ok()?;
+
+        // 2 Derive the child extended key.
+        let child_xpriv = master_xpriv.derive_priv(secp256k1_context(), derivation_path);
+
+        // 2.1 Erase the master secret key immediately after use.
+        master_xpriv.private_key.non_secure_erase();
+
+        // 2.2 Take the child secret key bytes.
+        let mut child_xpriv = child_xpriv.ok()?;
+        let mut child_secret_key_bytes = child_xpriv.private_key.secret_bytes();
+        child_xpriv.private_key.non_secure_erase();
+
+        // 3 Construct the child key holder.
+        let child_key_holder = KeyHolder::new(child_secret_key_bytes).map(|mut key_holder| {
+            key_holder.derivation_path = Some(derivation_path.clone());
+            key_holder
+        });
+
+        // 3.1 Zeroize the temporary child secret key bytes.
+        child_secret_key_bytes.zeroize();
+
+        // 4 Return the child key holder.
+        child_key_holder
+    }
+
+    /// Derives the key holder of the given purpose.
+    ///
+    /// Returns `None` if the purpose has no valid derivation path or the derivation fails.
+    pub fn derive_for(&self, purpose: KeyPurpose) -> Option<KeyHolder> {
+        // 1 Derive the key holder at the derivation path of the purpose.
+        self.derive(&purpose.derivation_path()?)
+    }
+
+    /// Returns the BIP32 path the key was derived at from a root key, `None` for a root key.
+    pub fn derivation_path(&self) -> Option<&DerivationPath> {
+        // 1 Return the derivation path.
+        self.derivation_path.as_ref()
+    }
+
     /// Locks the memory containing sensitive data to prevent swapping to disk.
     ///
     /// This uses `mlock` to prevent the OS from paging sensitive memory to disk.
@@ -276,17 +388,9 @@ impl KeyHolder {
 
     /// Returns the schnorr public key as a Point.
     pub fn secp_public_key_point(&self) -> Point {
-        // 1 Reconstruct Point from x-only bytes.
-        // 1.1 Create a 33-byte buffer with even point prefix.
-        let mut point_bytes = Vec::with_capacity(33);
-        point_bytes.push(0x02);
-        point_bytes.extend_from_slice(&self.secp_public_key_bytes);
-
-        // 1.2 Parse the point (this should always work for valid keys).
-        use secp::MaybePoint;
-        match MaybePoint::from_slice(&point_bytes) {
-            Ok(MaybePoint::Valid(point)) => point,
-            Ok(MaybePoint::Infinity) => panic!("Public key is infinity (should never happen)"),
+        // 1 Lift the x-only bytes to the even point (this should always work for valid keys).
+        match public_key::xonly_to_point(&self.secp_public_key_bytes) {
+            Ok(point) => point,
             Err(_) => panic!("Failed to reconstruct Point from bytes"),
         }
     }
@@ -309,9 +413,9 @@ impl KeyHolder {
     /// This method exposes the secret key. Use with extreme caution.
     pub fn bls_secret_key(&self) -> BLSSecretKey {
         // 1 Get the BLS secret key bytes.
-        let secret_key_bytes = self.bls_secret_key_bytes();
+        let secret_key_bytes = Zeroizing::new(self.bls_secret_key_bytes());
         // 2 Convert BLS secret key bytes to BLS secret key.
-        bls_secret_key_bytes_to_bls_secret_key(secret_key_bytes)
+        bls_secret_key_bytes_to_bls_secret_key(*secret_key_bytes)
     }
 
     /// Returns the BLS public key.
@@ -339,18 +443,38 @@ impl KeyHolder {
         // 1 Get the secp256k1 secret key as bytes.
         let secret_bytes = self.secp_secret_key_bytes.expose_secret();
 
-        // 2 Convert secret key bytes to scalar.
-        let scalar =
-            Scalar::from_slice(secret_bytes).expect("Invalid secret key bytes in KeyHolder");
+        // 2 Convert the secret key bytes directly, without an intermediate nsec string. The Nostr
+        // secret key erases itself on drop.
+        let secret_key =
+            SecretKey::from_slice(secret_bytes).expect("Invalid secret key bytes in KeyHolder");
 
-        // 3 Serialize scalar and convert to nsec.
-        let nsec = scalar
-            .serialize()
-            .to_nsec()
-            .expect("Failed to convert secret key to nsec");
+        // 3 Construct the Nostr keypair.
+        nostr_sdk::Keys::new(secret_key)
+    }
 
-        // 4 Parse the nsec to Nostr keypair.
-        nostr_sdk::Keys::parse(&nsec).expect("Failed to parse nsec to nostr keypair")
+    /// Backs the Nostr identity of this key holder by a NIP-46 remote signer, so that Nostr events are
+    /// signed by the remote signer rather than with the local key.
+    pub fn set_remote_signer(&mut self, remote_signer: Arc<dyn NostrSigner>) {
+        // 1 Set the remote signer.
+        self.remote_signer = Some(remote_signer);
+    }
+
+    /// Returns whether Nostr events are signed by a NIP-46 remote signer.
+    pub fn is_remote_signed(&self) -> bool {
+        // 1 Check if a remote signer is set.
+        self.remote_signer.is_some()
+    }
+
+    /// Returns the signer of Nostr events: the remote signer if set, the Nostr keypair otherwise.
+    pub fn nostr_signer(&self) -> Arc<dyn NostrSigner> {
+        // 1 Match the remote signer.
+        match &self.remote_signer {
+            // 1.a Sign remotely.
+            Some(remote_signer) => Arc::clone(remote_signer),
+
+            // 1.b Sign with the local Nostr keypair.
+            None => Arc::new(self.nostr_key_pair()),
+        }
     }
 
     /// Returns the Nostr `npub` string (compatibility method).
@@ -364,6 +488,102 @@ impl KeyHolder {
             .to_npub()
             .expect("Failed to convert public key to npub")
     }
+
+    /// Returns the BIP-341 tweaked key pair of the schnorr key, committing to the script tree with the
+    /// given merkle root, or to no script path (BIP-86) if `None`.
+    ///
+    /// Returns `None` if the tweak is out of range (negligible probability).
+    pub fn taproot_key_pair(&self, merkle_root: Option<[u8; 32]>) -> Option<TaprootKeyPair> {
+        // 1 Lift the secret key so that the internal key has an even y coordinate.
+        let internal_secret_key = self.secp_secret_key_scalar().lift();
+        let internal_key = internal_secret_key.generator_mul();
+
+        // 2 Compute the tweak, hash_TapTweak(internal_key || merkle_root).
+        let mut tweak_preimage = internal_key.serialize_xonly().to_vec();
+        if let Some(merkle_root) = merkle_root {
+            tweak_preimage.extend(merkle_root);
+        }
+        let tweak = Scalar::from_slice(&tweak_preimage.hash(Some(HashTag::TapTweak))).ok()?;
+
+        // 3 Tweak the secret key.
+        let output_secret_key = match internal_secret_key + tweak {
+            MaybeScalar::Valid(scalar) => scalar,
+            MaybeScalar::Zero => return None,
+        };
+        let output_key = output_secret_key.generator_mul();
+
+        // 4 Return the tweaked key pair.
+        Some(TaprootKeyPair {
+            output_secret_key_bytes: SecretBytes32::new(output_secret_key.serialize()),
+            output_key_bytes: output_key.serialize_xonly(),
+            output_key_parity: output_key.parity().into(),
+        })
+    }
+
+    /// Returns the x-only taproot output key of the schnorr key for the given merkle root.
+    pub fn taproot_output_key(&self, merkle_root: Option<[u8; 32]>) -> Option<[u8; 32]> {
+        // 1 Return the output key of the tweaked key pair.
+        Some(self.taproot_key_pair(merkle_root)?.output_key_bytes())
+    }
+
+    /// Returns the Bech32m-encoded P2TR address of the schnorr key on the given chain, for the given
+    /// merkle root.
+    pub fn taproot_address(&self, chain: Chain, merkle_root: Option<[u8; 32]>) -> Option<String> {
+        // 1 Encode the output key as a P2TR address.
+        encode_p2tr(chain, self.taproot_output_key(merkle_root)?)
+    }
+
+    /// Signs a key-path spend sighash with the key tweaked for the given merkle root.
+    pub fn sign_taproot(
+        &self,
+        sighash: [u8; 32],
+        merkle_root: Option<[u8; 32]>,
+    ) -> Option<[u8; 64]> {
+        // 1 Sign with the tweaked key pair.
+        self.taproot_key_pair(merkle_root)?.sign(sighash)
+    }
+}
+
+/// A BIP-341 tweaked key pair, for key-path spends of a taproot output.
+///
+/// The tweaked secret key is zeroized on drop. Not `Clone`, like the `KeyHolder` it is derived from.
+pub struct TaprootKeyPair {
+    // Tweaked secret key
+    output_secret_key_bytes: SecretBytes32,
+    // x-only output key
+    output_key_bytes: [u8; 32],
+    // Whether the output key has an odd y coordinate
+    output_key_parity: bool,
+}
+
+impl TaprootKeyPair {
+    /// Returns the x-only output key.
+    pub fn output_key_bytes(&self) -> [u8; 32] {
+        self.output_key_bytes
+    }
+
+    /// Returns the parity of the output key, as committed to in control blocks.
+    pub fn output_key_parity(&self) -> bool {
+        self.output_key_parity
+    }
+
+    /// Returns the tweaked secret key.
+    ///
+    /// # Security Warning
+    ///
+    /// This method exposes the secret key bytes. Use with extreme caution.
+    pub fn output_secret_key_bytes(&self) -> [u8; 32] {
+        *self.output_secret_key_bytes.expose_secret()
+    }
+
+    /// Signs a message with the tweaked secret key, as a BIP-340 signature valid for the output key.
+    pub fn sign(&self, message: [u8; 32]) -> Option<[u8; 64]> {
+        schnorr::sign(
+            *self.output_secret_key_bytes.expose_secret(),
+            message,
+            SchnorrSigningMode::BIP340,
+        )
+    }
 }
 
 // KeyHolder is intentionally NOT Clone to prevent multiple copies of secrets in memory.
@@ -439,33 +659,15 @@ impl ToNostrKeyStr for [u8; 32] {
     }
 
     fn to_npub(&self) -> Option<String> {
-        // 1 Validate that the bytes represent a valid public key.
-        if !self.is_valid_public() {
-            return None;
-        }
-
-        // 2 Parse the "npub" human-readable part.
-        let hrp = match Hrp::parse("npub") {
-            Ok(hrp) => hrp,
-            Err(_) => return None,
-        };
-
-        // 3 Encode the public key bytes as a Bech32 npub string.
-        let npub = match bech32::encode::<Bech32>(hrp, self) {
-            Ok(encoded) => encoded,
-            Err(_) => return None,
-        };
-
-        // 4 Return the npub string.
-        Some(npub)
+        public_key::xonly_to_npub(self).ok()
     }
 }
 
 impl FromNostrKeyStr for &str {
     fn from_nsec(&self) -> Option<[u8; 32]> {
-        // 1 Decode the Bech32 string.
+        // 1 Decode the Bech32 string, keeping the decoded secret in a zeroize-on-drop buffer.
         let (hrp, decoded_bytes) = match bech32::decode(self) {
-            Ok(decoded) => decoded,
+            Ok((hrp, decoded_bytes)) => (hrp, Zeroizing::new(decoded_bytes)),
             Err(_) => return None,
         };
 
@@ -480,7 +682,7 @@ impl FromNostrKeyStr for &str {
         }
 
         // 4 Convert decoded bytes to a 32-byte array.
-        let secret_key: [u8; 32] = decoded_bytes.try_into().ok()?;
+        let secret_key: [u8; 32] = decoded_bytes.as_slice().try_into().ok()?;
 
         // 5 Validate that the bytes represent a valid secret key.
         if !secret_key.is_valid_secret() {
@@ -492,31 +694,66 @@ impl FromNostrKeyStr for &str {
     }
 
     fn from_npub(&self) -> Option<[u8; 32]> {
-        // 1 Decode the Bech32 string.
-        let (hrp, decoded_bytes) = match bech32::decode(self) {
-            Ok(decoded) => decoded,
-            Err(_) => return None,
-        };
+        public_key::npub_to_xonly(self).ok()
+    }
+}
 
-        // 2 Validate that the human-readable part is "npub".
-        if hrp.as_str() != "npub" {
-            return None;
-        }
+/// Default scrypt work factor (`log_n`) of `ncryptsec` strings.
+pub const NCRYPTSEC_LOG_N: u8 = 16;
 
-        // 3 Validate that the decoded bytes length is 32.
-        if decoded_bytes.len() != 32 {
+/// Trait for encrypting 32-byte secret keys into [NIP-49](https://nips.nostr.com/49) `ncryptsec` strings.
+pub trait ToNcryptsecStr {
+    /// Encrypts a 32-byte secret key with the passphrase into a Bech32-encoded `ncryptsec` string,
+    /// using `2^log_n` scrypt rounds.
+    ///
+    /// Returns `None` if the key is invalid.
+    fn to_ncryptsec(&self, passphrase: &str, log_n: u8) -> Option<String>;
+}
+
+/// Trait for decrypting [NIP-49](https://nips.nostr.com/49) `ncryptsec` strings into 32-byte secret keys.
+pub trait FromNcryptsecStr {
+    /// Decrypts a Bech32-encoded `ncryptsec` string with the passphrase into a 32-byte secret key.
+    ///
+    /// Returns `None` if the string is invalid or the passphrase is wrong.
+    fn from_ncryptsec(&self, passphrase: &str) -> Option<[u8; 32]>;
+}
+
+impl ToNcryptsecStr for [u8; 32] {
+    fn to_ncryptsec(&self, passphrase: &str, log_n: u8) -> Option<String> {
+        // 1 Validate that the bytes represent a valid secret key.
+        if !self.is_valid_secret() {
             return None;
         }
 
-        // 4 Convert decoded bytes to a 32-byte array.
-        let public_key: [u8; 32] = decoded_bytes.try_into().ok()?;
+        // 2 Convert the secret key bytes to a secret key.
+        let secret_key = SecretKey::from_slice(self).ok()?;
+
+        // 3 Encrypt the secret key with the passphrase.
+        let encrypted_secret_key =
+            EncryptedSecretKey::new(&secret_key, passphrase, log_n, KeySecurity::Unknown).ok()?;
+
+        // 4 Encode the encrypted secret key as a Bech32 ncryptsec string.
+        encrypted_secret_key.to_bech32().ok()
+    }
+}
+
+impl FromNcryptsecStr for &str {
+    fn from_ncryptsec(&self, passphrase: &str) -> Option<[u8; 32]> {
+        // 1 Decode the Bech32 ncryptsec string.
+        let encrypted_secret_key = EncryptedSecretKey::from_bech32(*self).ok()?;
+
+        // 2 Decrypt the secret key with the passphrase.
+        let secret_key = encrypted_secret_key.to_secret_key(passphrase).ok()?;
+
+        // 3 Convert the secret key to bytes.
+        let secret_key_bytes: [u8; 32] = secret_key.to_secret_bytes();
 
-        // 5 Validate that the bytes represent a valid public key.
-        if !public_key.is_valid_public() {
+        // 4 Validate that the bytes represent a valid secret key.
+        if !secret_key_bytes.is_valid_secret() {
             return None;
         }
 
-        // 6 Return the public key bytes.
-        Some(public_key)
+        // 5 Return the secret key bytes.
+        Some(secret_key_bytes)
     }
 }
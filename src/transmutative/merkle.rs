@@ -0,0 +1,279 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use serde::{Deserialize, Serialize};
+
+/// A single step of a Merkle inclusion proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProofStep {
+    // The sibling node hash.
+    pub sibling: [u8; 32],
+
+    // Whether the sibling sits on the left-hand side of the branch.
+    pub sibling_is_left: bool,
+}
+
+/// Hashes a pair of nodes into their parent branch node.
+fn branch_hash(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::<u8>::with_capacity(64);
+    preimage.extend(left);
+    preimage.extend(right);
+    preimage.hash(Some(HashTag::MerkleBranch))
+}
+
+/// Reduces one level of the tree into its parent level.
+///
+/// An odd node out is carried up to the parent level unchanged.
+fn parent_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => branch_hash(*left, *right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Folds the steps of a Merkle proof into the node they lead up to.
+fn fold_merkle_proof(leaf: [u8; 32], proof: &[MerkleProofStep]) -> [u8; 32] {
+    proof
+        .iter()
+        .fold(leaf, |node, step| match step.sibling_is_left {
+            true => branch_hash(step.sibling, node),
+            false => branch_hash(node, step.sibling),
+        })
+}
+
+/// Returns the Merkle root of a list of leaf hashes.
+///
+/// Returns an all-zero root for an empty list of leaves.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    // 1 Return the all-zero root for an empty list of leaves.
+    if leaves.is_empty() {
+        return [0x00u8; 32];
+    }
+
+    // 2 Reduce the levels until a single root node is left.
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = parent_level(&level);
+    }
+
+    // 3 Return the root.
+    level[0]
+}
+
+/// Returns the Merkle inclusion proof of the leaf at the given index.
+pub fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Option<Vec<MerkleProofStep>> {
+    // 1 Check if the index is within bounds.
+    if index >= leaves.len() {
+        return None;
+    }
+
+    // 2 Walk the levels up to the root and collect the siblings.
+    let mut proof = Vec::<MerkleProofStep>::new();
+    let mut level = leaves.to_vec();
+    let mut position = index;
+    while level.len() > 1 {
+        // 2.1 Resolve the sibling position.
+        let sibling_position = position ^ 1;
+
+        // 2.2 Record the sibling unless the node is an odd node out.
+        if sibling_position < level.len() {
+            proof.push(MerkleProofStep {
+                sibling: level[sibling_position],
+                sibling_is_left: sibling_position < position,
+            });
+        }
+
+        // 2.3 Move up one level.
+        level = parent_level(&level);
+        position /= 2;
+    }
+
+    // 3 Return the proof.
+    Some(proof)
+}
+
+/// A Merkle tree that keeps all of its levels.
+///
+/// The root is read and proofs are built without hashing the leaves again, and updating a leaf
+/// only hashes its branch. The root and proofs match `merkle_root` and `merkle_proof`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleTree {
+    // The levels of the tree, from the leaves up to the root.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Constructs the tree of a list of leaf hashes.
+    pub fn new(leaves: Vec<[u8; 32]>) -> MerkleTree {
+        // 1 Start with the leaves.
+        let mut levels = vec![leaves];
+
+        // 2 Reduce the levels until a single root node is left.
+        while levels[levels.len() - 1].len() > 1 {
+            let parent = parent_level(&levels[levels.len() - 1]);
+            levels.push(parent);
+        }
+
+        // 3 Return the tree.
+        MerkleTree { levels }
+    }
+
+    /// Returns the number of leaves.
+    pub fn len(&self) -> usize {
+        self.levels.first().map(|leaves| leaves.len()).unwrap_or(0)
+    }
+
+    /// Returns whether the tree has no leaves.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the leaf at the given index.
+    pub fn leaf(&self, index: usize) -> Option<[u8; 32]> {
+        self.levels.first()?.get(index).copied()
+    }
+
+    /// Returns the Merkle root, or an all-zero root for an empty tree.
+    pub fn root(&self) -> [u8; 32] {
+        match self.levels.last() {
+            Some(level) if level.len() == 1 => level[0],
+            _ => [0x00u8; 32],
+        }
+    }
+
+    /// Returns the Merkle inclusion proof of the leaf at the given index.
+    pub fn proof(&self, index: usize) -> Option<Vec<MerkleProofStep>> {
+        // 1 Check if the index is within bounds.
+        if index >= self.len() {
+            return None;
+        }
+
+        // 2 Walk the levels below the root and collect the siblings.
+        let mut proof = Vec::<MerkleProofStep>::new();
+        let mut position = index;
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            // 2.1 Resolve the sibling position.
+            let sibling_position = position ^ 1;
+
+            // 2.2 Record the sibling unless the node is an odd node out.
+            if sibling_position < level.len() {
+                proof.push(MerkleProofStep {
+                    sibling: level[sibling_position],
+                    sibling_is_left: sibling_position < position,
+                });
+            }
+
+            // 2.3 Move up one level.
+            position /= 2;
+        }
+
+        // 3 Return the proof.
+        Some(proof)
+    }
+
+    /// Replaces the leaf at the given index, and hashes its branch up to the root again.
+    ///
+    /// Returns false if the index is out of bounds.
+    pub fn update_leaf(&mut self, index: usize, leaf: [u8; 32]) -> bool {
+        // 1 Check if the index is within bounds.
+        if index >= self.len() {
+            return false;
+        }
+
+        // 2 Replace the leaf.
+        self.levels[0][index] = leaf;
+
+        // 3 Hash the branch up to the root again.
+        let mut position = index;
+        for depth in 1..self.levels.len() {
+            // 3.1 Resolve the pair of child nodes.
+            let left_position = position & !1;
+            let child_level = &self.levels[depth - 1];
+            let node = match child_level.get(left_position + 1) {
+                Some(right) => branch_hash(child_level[left_position], *right),
+                None => child_level[left_position],
+            };
+
+            // 3.2 Replace the parent node.
+            position /= 2;
+            self.levels[depth][position] = node;
+        }
+
+        true
+    }
+}
+
+/// Verifies a Merkle inclusion proof of a leaf against a root.
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[MerkleProofStep], root: [u8; 32]) -> bool {
+    // 1 Fold the proof steps into a candidate root.
+    let candidate_root = fold_merkle_proof(leaf, proof);
+
+    // 2 Compare the candidate root with the expected root.
+    candidate_root == root
+}
+
+/// Verifies a Merkle inclusion proof of the first leaf of a tree against a root.
+///
+/// The first leaf is the left-hand node at every level, so none of its siblings sit on the left.
+pub fn verify_first_merkle_proof(
+    leaf: [u8; 32],
+    proof: &[MerkleProofStep],
+    root: [u8; 32],
+) -> bool {
+    proof.iter().all(|step| !step.sibling_is_left) && verify_merkle_proof(leaf, proof, root)
+}
+
+/// Verifies a Merkle inclusion proof of the last leaf of a tree against a root.
+///
+/// The last leaf is the right-hand or the odd node out at every level, so none of its siblings sit
+/// on the right.
+pub fn verify_last_merkle_proof(leaf: [u8; 32], proof: &[MerkleProofStep], root: [u8; 32]) -> bool {
+    proof.iter().all(|step| step.sibling_is_left) && verify_merkle_proof(leaf, proof, root)
+}
+
+/// Verifies the Merkle inclusion proofs of two adjacent leaves against a root, the left leaf
+/// immediately followed by the right leaf.
+///
+/// The branches of two adjacent leaves meet at a node whose children are their ancestors. Below
+/// it, the left leaf is the last leaf of its subtree and the right leaf the first of its subtree,
+/// and above it, both branches are the same.
+pub fn verify_adjacent_merkle_proofs(
+    left_leaf: [u8; 32],
+    left_proof: &[MerkleProofStep],
+    right_leaf: [u8; 32],
+    right_proof: &[MerkleProofStep],
+    root: [u8; 32],
+) -> bool {
+    // 1 Verify both proofs against the root.
+    if !verify_merkle_proof(left_leaf, left_proof, root)
+        || !verify_merkle_proof(right_leaf, right_proof, root)
+    {
+        return false;
+    }
+
+    // 2 The left branch climbs as a right-hand node up to where the branches meet.
+    let left_depth = match left_proof.iter().position(|step| !step.sibling_is_left) {
+        Some(depth) => depth,
+        None => return false,
+    };
+
+    // 3 The right branch climbs as a left-hand node up to where the branches meet.
+    let right_depth = match right_proof.iter().position(|step| step.sibling_is_left) {
+        Some(depth) => depth,
+        None => return false,
+    };
+
+    // 4 Where the branches meet, each ancestor must be the sibling of the other.
+    let left_ancestor = fold_merkle_proof(left_leaf, &left_proof[..left_depth]);
+    let right_ancestor = fold_merkle_proof(right_leaf, &right_proof[..right_depth]);
+    if left_proof[left_depth].sibling != right_ancestor
+        || right_proof[right_depth].sibling != left_ancestor
+    {
+        return false;
+    }
+
+    // 5 Above where the branches meet, both branches must be the same.
+    left_proof[left_depth + 1..] == right_proof[right_depth + 1..]
+}
@@ -1,6 +1,11 @@
+pub mod bip322;
 pub mod bls;
 pub mod codec;
 pub mod hash;
 pub mod key;
+pub mod merkle;
 pub mod musig;
+pub mod nostr;
+#[cfg(feature = "confidential")]
+pub mod pedersen;
 pub mod secp;
@@ -0,0 +1,14 @@
+/// Errors for the musig nonce guard.
+#[derive(Debug)]
+pub enum MusigNonceGuardError {
+    DBOpenError(sled::Error),
+    DBInsertError(sled::Error),
+    DBFlushError(sled::Error),
+    // The session has not collected the nonces of all signers yet.
+    SessionNotReady,
+    // The nonces were already consumed by a different session.
+    NonceReuse,
+    // The generated nonces could not be inserted into the session.
+    NonceInsertError,
+    PartialSignError,
+}
@@ -1,4 +1,5 @@
 use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::GeneratorMul;
 use crate::transmutative::secp::into::IntoScalar;
 use secp::{MaybePoint, Point, Scalar};
 use serde::{Deserialize, Serialize};
@@ -20,7 +21,7 @@ impl MusigKeyAggCtx {
 
         let agg_key = match tweak {
             Some(tweak) => {
-                match agg_inner_key.negate_if(agg_inner_key.parity()) + tweak.base_point_mul() {
+                match agg_inner_key.negate_if(agg_inner_key.parity()) + tweak.generator_mul() {
                     MaybePoint::Valid(point) => point,
                     MaybePoint::Infinity => return None,
                 }
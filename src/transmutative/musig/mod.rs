@@ -1,2 +1,5 @@
+pub mod error;
 pub mod keyagg;
+pub mod nonce_guard;
+pub mod secret_nonces;
 pub mod session;
@@ -0,0 +1,158 @@
+use super::error::MusigNonceGuardError;
+use super::secret_nonces::MusigSecretNonces;
+use super::session::MusigSessionCtx;
+use crate::operative::run_args::chain::Chain;
+use crate::transmutative::hash::{Hash, HashTag};
+use secp::{Point, Scalar};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Persistent record of the nonces consumed by interactive signing sessions.
+///
+/// A nonce is recorded, and flushed to disk, before the partial signature made with it is released.
+/// After a crash, a nonce can only be signed with again for the exact same session, which yields the
+/// same partial signature; signing with it for any other session is refused, as two partial signatures
+/// with the same nonce leak the secret key.
+pub struct MusigNonceGuard {
+    // Session fingerprints by the public nonces they consumed.
+    on_disk_nonces: sled::Tree,
+}
+
+/// Guarded 'MusigNonceGuard'.
+#[allow(non_camel_case_types)]
+pub type MUSIG_NONCE_GUARD = Arc<Mutex<MusigNonceGuard>>;
+
+impl MusigNonceGuard {
+    pub fn new(chain: Chain) -> Result<MUSIG_NONCE_GUARD, MusigNonceGuardError> {
+        // 1 Open the nonce guard db.
+        let db_path = format!("storage/{}/nonce_guard", chain.to_string());
+        let db = sled::open(db_path).map_err(MusigNonceGuardError::DBOpenError)?;
+
+        // 2 Open the consumed nonces tree.
+        let on_disk_nonces = db
+            .open_tree("nonces")
+            .map_err(MusigNonceGuardError::DBOpenError)?;
+
+        // 3 Guard and return the nonce guard.
+        Ok(Arc::new(Mutex::new(MusigNonceGuard { on_disk_nonces })))
+    }
+
+    /// Generates a fresh secret nonce pair, never one consumed by a session.
+    pub fn generate_nonces(&self) -> MusigSecretNonces {
+        loop {
+            let secret_nonces = MusigSecretNonces::generate();
+            if let Some((hiding_public_nonce, binding_public_nonce)) = secret_nonces.public_nonces()
+            {
+                if !self.is_nonce_consumed(hiding_public_nonce, binding_public_nonce) {
+                    return secret_nonces;
+                }
+            }
+        }
+    }
+
+    /// Returns whether the public nonces were consumed by a session.
+    pub fn is_nonce_consumed(
+        &self,
+        hiding_public_nonce: Point,
+        binding_public_nonce: Point,
+    ) -> bool {
+        let nonce_key = nonce_key(hiding_public_nonce, binding_public_nonce);
+        matches!(self.on_disk_nonces.contains_key(nonce_key), Ok(true))
+    }
+
+    /// Records the public nonces as consumed by the session with the given fingerprint.
+    ///
+    /// Succeeds if the nonces are fresh or were consumed by the same session, and fails otherwise.
+    /// Usable by any interactive protocol whose nonces are a hiding and binding pair.
+    pub fn consume_nonce(
+        &self,
+        hiding_public_nonce: Point,
+        binding_public_nonce: Point,
+        session_fingerprint: [u8; 32],
+    ) -> Result<(), MusigNonceGuardError> {
+        let nonce_key = nonce_key(hiding_public_nonce, binding_public_nonce);
+
+        // 1 Atomically record the fingerprint, unless the nonces were already consumed.
+        let previous = self
+            .on_disk_nonces
+            .compare_and_swap(
+                nonce_key,
+                None as Option<&[u8]>,
+                Some(session_fingerprint.as_slice()),
+            )
+            .map_err(MusigNonceGuardError::DBInsertError)?;
+
+        // 2 Only the same session may consume the nonces again.
+        if let Err(compare_and_swap_error) = previous {
+            if compare_and_swap_error.current.as_deref() != Some(session_fingerprint.as_slice()) {
+                return Err(MusigNonceGuardError::NonceReuse);
+            }
+        }
+
+        // 3 Make the record durable before any signature is released.
+        self.on_disk_nonces
+            .flush()
+            .map_err(MusigNonceGuardError::DBFlushError)?;
+
+        Ok(())
+    }
+
+    /// Partially signs a MuSig2 session, after recording its nonces as consumed.
+    pub fn partial_sign(
+        &self,
+        session_ctx: &MusigSessionCtx,
+        secret_key: Scalar,
+        secret_nonces: &MusigSecretNonces,
+    ) -> Result<Scalar, MusigNonceGuardError> {
+        // 1 Fingerprint the session.
+        let session_fingerprint =
+            session_fingerprint(session_ctx).ok_or(MusigNonceGuardError::SessionNotReady)?;
+
+        // 2 Consume the nonces.
+        let (hiding_public_nonce, binding_public_nonce) = secret_nonces
+            .public_nonces()
+            .ok_or(MusigNonceGuardError::PartialSignError)?;
+        self.consume_nonce(
+            hiding_public_nonce,
+            binding_public_nonce,
+            session_fingerprint,
+        )?;
+
+        // 3 Partially sign.
+        let (secret_hiding_nonce, secret_binding_nonce) = secret_nonces
+            .scalars()
+            .ok_or(MusigNonceGuardError::PartialSignError)?;
+        session_ctx
+            .partial_sign_with_nonces(secret_key, secret_hiding_nonce, secret_binding_nonce)
+            .ok_or(MusigNonceGuardError::PartialSignError)
+    }
+}
+
+/// Returns the db key of a public nonce pair.
+fn nonce_key(hiding_public_nonce: Point, binding_public_nonce: Point) -> Vec<u8> {
+    let mut nonce_key = Vec::<u8>::with_capacity(66);
+    nonce_key.extend(hiding_public_nonce.serialize());
+    nonce_key.extend(binding_public_nonce.serialize());
+    nonce_key
+}
+
+/// Returns the fingerprint of a ready session, committing to everything a partial signature depends on
+/// other than the secrets.
+fn session_fingerprint(session_ctx: &MusigSessionCtx) -> Option<[u8; 32]> {
+    let agg_nonce = session_ctx.agg_nonce()?;
+    let nonce_coef = session_ctx.nonce_coef()?;
+
+    let mut preimage = Vec::<u8>::with_capacity(129);
+    preimage.extend(session_ctx.key_agg_ctx().agg_key().serialize());
+    preimage.extend(agg_nonce.serialize());
+    preimage.extend(nonce_coef.serialize());
+    preimage.extend(session_ctx.message());
+
+    Some(preimage.hash(Some(HashTag::MusigNonceSession)))
+}
+
+/// Erases the nonce guard by db path.
+pub fn erase_nonce_guard(chain: Chain) {
+    let db_path = format!("storage/{}/nonce_guard", chain.to_string());
+    let _ = std::fs::remove_dir_all(db_path);
+}
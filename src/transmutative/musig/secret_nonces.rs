@@ -0,0 +1,58 @@
+use crate::transmutative::secp::context::GeneratorMul;
+use crate::transmutative::secp::schnorr;
+use secp::{Point, Scalar};
+use zeroize::Zeroize;
+
+/// The secret hiding and binding nonces of a signer in an interactive signing session.
+///
+/// The nonces are held as bytes and zeroized on drop, since a leaked secret nonce leaks the secret key
+/// along with the partial signature made with it.
+///
+/// This type is intentionally NOT Clone, as a secret nonce pair must only ever be signed with once.
+pub struct MusigSecretNonces {
+    hiding: [u8; 32],
+    binding: [u8; 32],
+}
+
+impl MusigSecretNonces {
+    /// Generates a fresh secret nonce pair.
+    pub fn generate() -> Self {
+        Self::new(schnorr::generate_scalar(), schnorr::generate_scalar())
+    }
+
+    /// Constructs a secret nonce pair from the hiding and binding nonces.
+    pub fn new(hiding: Scalar, binding: Scalar) -> Self {
+        Self {
+            hiding: hiding.serialize(),
+            binding: binding.serialize(),
+        }
+    }
+
+    /// Returns the public hiding and binding nonces to share with the other signers.
+    pub fn public_nonces(&self) -> Option<(Point, Point)> {
+        let (hiding, binding) = self.scalars()?;
+        Some((hiding.generator_mul(), binding.generator_mul()))
+    }
+
+    /// Returns the secret hiding and binding nonces as scalars. Use with extreme caution.
+    pub(crate) fn scalars(&self) -> Option<(Scalar, Scalar)> {
+        let hiding = Scalar::from_slice(&self.hiding).ok()?;
+        let binding = Scalar::from_slice(&self.binding).ok()?;
+        Some((hiding, binding))
+    }
+}
+
+impl Zeroize for MusigSecretNonces {
+    // 1 Zeroize the secret nonces.
+    fn zeroize(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+impl Drop for MusigSecretNonces {
+    // 1 Automatically zeroize memory on drop.
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
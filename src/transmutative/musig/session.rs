@@ -1,5 +1,9 @@
+use super::error::MusigNonceGuardError;
 use super::keyagg::MusigKeyAggCtx;
+use super::nonce_guard::MusigNonceGuard;
+use super::secret_nonces::MusigSecretNonces;
 use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::GeneratorMul;
 use crate::transmutative::secp::into::IntoScalar;
 use crate::transmutative::secp::schnorr::{challenge, SchnorrSigningMode};
 use secp::{MaybePoint, MaybeScalar, Point, Scalar};
@@ -112,13 +116,42 @@ impl MusigSessionCtx {
         self.agg_nonce
     }
 
+    /// Generates the nonces of the signer through the nonce guard, and inserts its public nonces.
+    pub fn generate_nonces(
+        &mut self,
+        nonce_guard: &MusigNonceGuard,
+        key: Point,
+    ) -> Result<MusigSecretNonces, MusigNonceGuardError> {
+        let secret_nonces = nonce_guard.generate_nonces();
+        let (hiding_nonce, binding_nonce) = secret_nonces
+            .public_nonces()
+            .ok_or(MusigNonceGuardError::PartialSignError)?;
+
+        if !self.insert_nonce(key, hiding_nonce, binding_nonce) {
+            return Err(MusigNonceGuardError::NonceInsertError);
+        }
+
+        Ok(secret_nonces)
+    }
+
+    /// Partially signs, consuming the nonces through the nonce guard so that they are never signed
+    /// with for another session.
     pub fn partial_sign(
+        &self,
+        nonce_guard: &MusigNonceGuard,
+        secret_key: Scalar,
+        secret_nonces: &MusigSecretNonces,
+    ) -> Result<Scalar, MusigNonceGuardError> {
+        nonce_guard.partial_sign(self, secret_key, secret_nonces)
+    }
+
+    pub(super) fn partial_sign_with_nonces(
         &self,
         secret_key: Scalar,
         secret_hiding_nonce: Scalar,
         secet_binding_nonce: Scalar,
     ) -> Option<Scalar> {
-        let public_key = secret_key.base_point_mul();
+        let public_key = secret_key.generator_mul();
         let key_coef = match self.key_agg_ctx.key_coef(public_key) {
             Some(coef) => coef,
             None => return None,
@@ -129,11 +162,11 @@ impl MusigSessionCtx {
             None => return None,
         };
 
-        if secret_hiding_nonce.base_point_mul() != hiding_public_nonce.to_owned() {
+        if secret_hiding_nonce.generator_mul() != hiding_public_nonce.to_owned() {
             return None;
         };
 
-        if secet_binding_nonce.base_point_mul() != binding_public_nonce.to_owned() {
+        if secet_binding_nonce.generator_mul() != binding_public_nonce.to_owned() {
             return None;
         };
 
@@ -213,7 +246,7 @@ impl MusigSessionCtx {
             MaybePoint::Infinity => return false,
         };
 
-        if eq != partial_sig.base_point_mul() {
+        if eq != partial_sig.generator_mul() {
             return false;
         };
 
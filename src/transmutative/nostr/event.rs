@@ -0,0 +1,162 @@
+use crate::transmutative::key::KeyHolder;
+use crate::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Nostr event kind of a short text note.
+pub const NOSTR_TEXT_NOTE_KIND: u16 = 1;
+
+/// A signed Nostr event, as specified in NIP-01.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NostrEvent {
+    // Event id, the SHA-256 of the serialized event.
+    pub id: [u8; 32],
+
+    // X-only public key of the author.
+    pub pubkey: [u8; 32],
+
+    // Unix timestamp (seconds) of the creation.
+    pub created_at: u64,
+
+    // Event kind.
+    pub kind: u16,
+
+    // Event tags.
+    pub tags: Vec<Vec<String>>,
+
+    // Event content.
+    pub content: String,
+
+    // BIP-340 Schnorr signature over the event id.
+    pub sig: [u8; 64],
+}
+
+impl NostrEvent {
+    /// Constructs and signs an event with the key holder's secp key.
+    pub fn sign(
+        key_holder: &KeyHolder,
+        created_at: u64,
+        kind: u16,
+        tags: Vec<Vec<String>>,
+        content: String,
+    ) -> Option<Self> {
+        // 1 Compute the event id.
+        let pubkey = key_holder.secp_public_key_bytes();
+        let id = Self::compute_id(pubkey, created_at, kind, &tags, &content);
+
+        // 2 Sign the event id.
+        let sig = schnorr::sign(
+            key_holder.secp_secret_key_bytes(),
+            id,
+            SchnorrSigningMode::BIP340,
+        )?;
+
+        // 3 Return the event.
+        Some(Self {
+            id,
+            pubkey,
+            created_at,
+            kind,
+            tags,
+            content,
+            sig,
+        })
+    }
+
+    /// Computes the id of an event: the SHA-256 of `[0, <pubkey>, <created_at>, <kind>, <tags>, <content>]`
+    /// serialized as compact JSON.
+    pub fn compute_id(
+        pubkey: [u8; 32],
+        created_at: u64,
+        kind: u16,
+        tags: &Vec<Vec<String>>,
+        content: &str,
+    ) -> [u8; 32] {
+        let serialized =
+            json!([0, hex::encode(pubkey), created_at, kind, tags, content]).to_string();
+        Sha256::digest(serialized.as_bytes()).into()
+    }
+
+    /// Verifies the event id and signature.
+    pub fn verify(&self) -> bool {
+        // 1 The id must commit to the event.
+        if self.id
+            != Self::compute_id(
+                self.pubkey,
+                self.created_at,
+                self.kind,
+                &self.tags,
+                &self.content,
+            )
+        {
+            return false;
+        }
+
+        // 2 The signature must be valid for the id.
+        schnorr::verify_xonly(self.pubkey, self.id, self.sig, SchnorrSigningMode::BIP340)
+    }
+
+    /// Parses an event from its JSON string. The event is not verified.
+    pub fn from_json_str(json_str: &str) -> Option<Self> {
+        // 1 Parse the JSON object.
+        let value: Value = serde_json::from_str(json_str).ok()?;
+        let obj = value.as_object()?;
+
+        // 2 Parse the hex fields.
+        let id: [u8; 32] = hex::decode(obj.get("id")?.as_str()?)
+            .ok()?
+            .try_into()
+            .ok()?;
+        let pubkey: [u8; 32] = hex::decode(obj.get("pubkey")?.as_str()?)
+            .ok()?
+            .try_into()
+            .ok()?;
+        let sig: [u8; 64] = hex::decode(obj.get("sig")?.as_str()?)
+            .ok()?
+            .try_into()
+            .ok()?;
+
+        // 3 Parse the tags, each an array of strings.
+        let tags = obj
+            .get("tags")?
+            .as_array()?
+            .iter()
+            .map(|tag| {
+                tag.as_array()?
+                    .iter()
+                    .map(|item| item.as_str().map(|item| item.to_string()))
+                    .collect::<Option<Vec<String>>>()
+            })
+            .collect::<Option<Vec<Vec<String>>>>()?;
+
+        // 4 Return the event.
+        Some(Self {
+            id,
+            pubkey,
+            created_at: obj.get("created_at")?.as_u64()?,
+            kind: u16::try_from(obj.get("kind")?.as_u64()?).ok()?,
+            tags,
+            content: obj.get("content")?.as_str()?.to_string(),
+            sig,
+        })
+    }
+
+    /// Returns the event as a NIP-01 JSON object.
+    pub fn json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("id".to_string(), Value::String(hex::encode(self.id)));
+        obj.insert(
+            "pubkey".to_string(),
+            Value::String(hex::encode(self.pubkey)),
+        );
+        obj.insert(
+            "created_at".to_string(),
+            Value::Number(self.created_at.into()),
+        );
+        obj.insert("kind".to_string(), Value::Number(self.kind.into()));
+        obj.insert("tags".to_string(), json!(self.tags));
+        obj.insert("content".to_string(), Value::String(self.content.clone()));
+        obj.insert("sig".to_string(), Value::String(hex::encode(self.sig)));
+        Value::Object(obj)
+    }
+}
@@ -0,0 +1,126 @@
+use super::generator::value_generator;
+use crate::transmutative::secp::context::GeneratorMul;
+use crate::transmutative::secp::schnorr;
+use secp::{MaybePoint, MaybeScalar, Point, Scalar};
+
+/// A Pedersen commitment `v·H + r·G` to a value `v` with blinding factor `r`.
+///
+/// Commitments are hiding (the blinding factor masks the value) and binding (a commitment opens to a
+/// single value, as nobody knows the discrete logarithm of `H`). They are additively homomorphic, so
+/// the commitments of inputs and outputs can be checked to balance without revealing amounts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PedersenCommitment {
+    point: Point,
+}
+
+impl PedersenCommitment {
+    /// Commits to a value with the given blinding factor.
+    ///
+    /// Returns `None` if the commitment is the point at infinity (negligible probability).
+    pub fn commit(value: u64, blinding: Scalar) -> Option<Self> {
+        // 1 Compute v·H + r·G.
+        let value_point = MaybeScalar::from(value as u128) * value_generator();
+        match value_point + blinding.generator_mul() {
+            MaybePoint::Valid(point) => Some(Self { point }),
+            MaybePoint::Infinity => None,
+        }
+    }
+
+    /// Constructs a commitment from its curve point.
+    pub fn from_point(point: Point) -> Self {
+        Self { point }
+    }
+
+    /// Returns the curve point of the commitment.
+    pub fn point(&self) -> Point {
+        self.point
+    }
+
+    /// Returns whether the commitment opens to the given value and blinding factor.
+    pub fn opens_to(&self, value: u64, blinding: Scalar) -> bool {
+        match Self::commit(value, blinding) {
+            Some(commitment) => commitment == *self,
+            None => false,
+        }
+    }
+
+    /// Adds two commitments, committing to the sum of values with the sum of blinding factors.
+    pub fn add(&self, other: &Self) -> Option<Self> {
+        match self.point + other.point {
+            MaybePoint::Valid(point) => Some(Self { point }),
+            MaybePoint::Infinity => None,
+        }
+    }
+
+    /// Subtracts a commitment, committing to the difference of values and blinding factors.
+    pub fn sub(&self, other: &Self) -> Option<Self> {
+        match self.point - other.point {
+            MaybePoint::Valid(point) => Some(Self { point }),
+            MaybePoint::Infinity => None,
+        }
+    }
+
+    /// Serializes the commitment as a 33-byte compressed point.
+    pub fn serialize(&self) -> [u8; 33] {
+        self.point.serialize()
+    }
+
+    /// Deserializes a commitment from a 33-byte compressed point.
+    pub fn deserialize(bytes: &[u8; 33]) -> Option<Self> {
+        let point = Point::from_slice(bytes).ok()?;
+        Some(Self { point })
+    }
+}
+
+/// Returns the excess `Σ inputs − Σ outputs` of a set of commitments.
+///
+/// The commitments balance, meaning the input and output values sum up the same, if and only if the
+/// excess is `r·G` for the excess of blinding factors `r` (see [`blinding_excess`]). A spender proves
+/// this by signing with `r` for the excess point, without revealing any value.
+pub fn commitment_excess(
+    inputs: &[PedersenCommitment],
+    outputs: &[PedersenCommitment],
+) -> MaybePoint {
+    // 1 Sum the inputs.
+    let mut excess = MaybePoint::Infinity;
+    for input in inputs {
+        excess += input.point;
+    }
+
+    // 2 Subtract the outputs.
+    for output in outputs {
+        excess -= output.point;
+    }
+
+    excess
+}
+
+/// Returns the excess `Σ inputs − Σ outputs` of a set of blinding factors.
+pub fn blinding_excess(inputs: &[Scalar], outputs: &[Scalar]) -> MaybeScalar {
+    // 1 Sum the inputs.
+    let mut excess = MaybeScalar::Zero;
+    for input in inputs {
+        excess += *input;
+    }
+
+    // 2 Subtract the outputs.
+    for output in outputs {
+        excess -= *output;
+    }
+
+    excess
+}
+
+/// Returns whether the commitments balance with the given blinding excess.
+pub fn is_balanced(
+    inputs: &[PedersenCommitment],
+    outputs: &[PedersenCommitment],
+    blinding_excess: MaybeScalar,
+) -> bool {
+    commitment_excess(inputs, outputs) == blinding_excess.generator_mul()
+}
+
+/// Returns a uniformly random blinding factor.
+pub fn random_blinding() -> Scalar {
+    schnorr::generate_scalar()
+}
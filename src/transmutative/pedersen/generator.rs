@@ -0,0 +1,33 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::public_key;
+use secp::Point;
+use std::sync::OnceLock;
+
+/// The value generator `H`, derived on first use.
+static VALUE_GENERATOR: OnceLock<Point> = OnceLock::new();
+
+/// Returns the value generator `H` of Pedersen commitments.
+///
+/// `H` is hashed to the curve from a fixed seed, so nobody knows its discrete logarithm relative to
+/// `G`, which is what makes commitments binding.
+pub fn value_generator() -> Point {
+    *VALUE_GENERATOR.get_or_init(|| hash_to_point(b"value"))
+}
+
+/// Hashes a seed to a point with an even y coordinate, by try-and-increment.
+pub fn hash_to_point(seed: &[u8]) -> Point {
+    // 1 Hash the seed with an increasing counter until the hash is the x coordinate of a point.
+    // About half of the candidates are on the curve, so this ends after a couple of tries.
+    let mut counter: u32 = 0;
+    loop {
+        let mut preimage = seed.to_vec();
+        preimage.extend(counter.to_le_bytes());
+
+        let x_coordinate = preimage.hash(Some(HashTag::PedersenGenerator));
+        if let Ok(point) = public_key::xonly_to_point(&x_coordinate) {
+            return point;
+        }
+
+        counter += 1;
+    }
+}
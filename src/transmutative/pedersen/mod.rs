@@ -0,0 +1,3 @@
+pub mod commitment;
+pub mod generator;
+pub mod range_proof;
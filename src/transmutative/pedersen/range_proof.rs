@@ -0,0 +1,290 @@
+use super::commitment::{random_blinding, PedersenCommitment};
+use super::generator::value_generator;
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::GeneratorMul;
+use secp::{MaybePoint, MaybeScalar, Scalar};
+
+/// Maximum number of bits a range proof covers.
+pub const RANGE_PROOF_MAX_BITS: u8 = 64;
+
+/// Size of a serialized bit: the bit commitment and its proof.
+const BIT_SIZE: usize = 33 + 128;
+
+/// A proof that a commitment `C` opens to `0` or `1`.
+///
+/// An OR of two Schnorr proofs of knowledge of `r`, for `C = r·G` or `C − H = r·G`: the branch that
+/// does not hold is simulated, and the two challenges must sum up to the Fiat-Shamir challenge, so only
+/// one of them can be chosen freely and the verifier cannot tell which.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BitProof {
+    // Challenge of the `0` branch.
+    e0: MaybeScalar,
+    // Challenge of the `1` branch.
+    e1: MaybeScalar,
+    // Response of the `0` branch.
+    s0: MaybeScalar,
+    // Response of the `1` branch.
+    s1: MaybeScalar,
+}
+
+impl BitProof {
+    /// Proves that the commitment opens to the given bit with the given blinding factor.
+    ///
+    /// The context binds the proof, typically to the commitment and position of the bit.
+    pub fn prove(
+        bit_commitment: &PedersenCommitment,
+        bit: bool,
+        blinding: Scalar,
+        context: &[u8],
+    ) -> Option<Self> {
+        // 1 The keys of the two branches, P0 = C and P1 = C − H.
+        let keys = Self::branch_keys(bit_commitment);
+
+        // 2 Simulate the branch that does not hold with a random challenge and response.
+        let simulated_challenge = MaybeScalar::from(random_blinding());
+        let simulated_response = MaybeScalar::from(random_blinding());
+        let simulated_key = if bit { keys.0 } else { keys.1 };
+        let simulated_nonce =
+            simulated_response.generator_mul() - simulated_key * simulated_challenge;
+
+        // 3 Commit to a nonce for the branch that holds.
+        let nonce = random_blinding();
+        let real_nonce = MaybePoint::Valid(nonce.generator_mul());
+
+        // 4 Compute the Fiat-Shamir challenge over both nonces.
+        let (nonce0, nonce1) = if bit {
+            (simulated_nonce, real_nonce)
+        } else {
+            (real_nonce, simulated_nonce)
+        };
+        let challenge = Self::challenge(bit_commitment, nonce0, nonce1, context);
+
+        // 5 Answer the branch that holds with the rest of the challenge.
+        let real_challenge = challenge - simulated_challenge;
+        let real_response = nonce + real_challenge * blinding;
+
+        // 6 Order the branches.
+        let bit_proof = if bit {
+            BitProof {
+                e0: simulated_challenge,
+                e1: real_challenge,
+                s0: simulated_response,
+                s1: real_response,
+            }
+        } else {
+            BitProof {
+                e0: real_challenge,
+                e1: simulated_challenge,
+                s0: real_response,
+                s1: simulated_response,
+            }
+        };
+
+        Some(bit_proof)
+    }
+
+    /// Verifies that the commitment opens to `0` or `1`.
+    pub fn verify(&self, bit_commitment: &PedersenCommitment, context: &[u8]) -> bool {
+        // 1 The keys of the two branches.
+        let (key0, key1) = Self::branch_keys(bit_commitment);
+
+        // 2 Recover the nonces, R = s·G − e·P.
+        let nonce0 = self.s0.generator_mul() - key0 * self.e0;
+        let nonce1 = self.s1.generator_mul() - key1 * self.e1;
+
+        // 3 The challenges must sum up to the Fiat-Shamir challenge over the nonces.
+        self.e0 + self.e1 == Self::challenge(bit_commitment, nonce0, nonce1, context)
+    }
+
+    /// Serializes the proof as `e0 || e1 || s0 || s1`.
+    pub fn serialize(&self) -> [u8; 128] {
+        let mut bytes = [0u8; 128];
+        bytes[..32].copy_from_slice(&self.e0.serialize());
+        bytes[32..64].copy_from_slice(&self.e1.serialize());
+        bytes[64..96].copy_from_slice(&self.s0.serialize());
+        bytes[96..].copy_from_slice(&self.s1.serialize());
+        bytes
+    }
+
+    /// Deserializes a proof from `e0 || e1 || s0 || s1`.
+    pub fn deserialize(bytes: &[u8; 128]) -> Option<Self> {
+        Some(BitProof {
+            e0: MaybeScalar::from_slice(&bytes[..32]).ok()?,
+            e1: MaybeScalar::from_slice(&bytes[32..64]).ok()?,
+            s0: MaybeScalar::from_slice(&bytes[64..96]).ok()?,
+            s1: MaybeScalar::from_slice(&bytes[96..]).ok()?,
+        })
+    }
+
+    /// Returns the keys of the two branches, `C` and `C − H`.
+    fn branch_keys(bit_commitment: &PedersenCommitment) -> (MaybePoint, MaybePoint) {
+        let key0 = MaybePoint::Valid(bit_commitment.point());
+        let key1 = key0 - value_generator();
+        (key0, key1)
+    }
+
+    /// Returns the Fiat-Shamir challenge over the commitment, both nonces and the context.
+    fn challenge(
+        bit_commitment: &PedersenCommitment,
+        nonce0: MaybePoint,
+        nonce1: MaybePoint,
+        context: &[u8],
+    ) -> MaybeScalar {
+        let mut preimage = Vec::<u8>::with_capacity(33 * 3 + context.len());
+        preimage.extend(bit_commitment.serialize());
+        preimage.extend(nonce0.serialize());
+        preimage.extend(nonce1.serialize());
+        preimage.extend(context);
+
+        MaybeScalar::reduce_from(&preimage.hash(Some(HashTag::PedersenBitProof)))
+    }
+}
+
+/// A range proof that a commitment opens to a value in `[0, 2^bits)`.
+///
+/// The value is split into bits, each committed to and proven to be `0` or `1`, with blinding factors
+/// chosen so that the bit commitments weighted by powers of two add up to the commitment. Proofs grow
+/// linearly with the number of bits, 161 bytes per bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeProof {
+    // Commitments to the bits of the value, least significant first.
+    bit_commitments: Vec<PedersenCommitment>,
+
+    // Proofs that each bit commitment opens to `0` or `1`.
+    bit_proofs: Vec<BitProof>,
+}
+
+impl RangeProof {
+    /// Proves that the commitment to the value with the given blinding factor opens to a value in
+    /// `[0, 2^bits)`.
+    ///
+    /// Returns `None` if the value is out of range or `bits` is not in `1..=64`.
+    pub fn prove(value: u64, blinding: Scalar, bits: u8) -> Option<Self> {
+        // 1 Check the range.
+        if bits == 0 || bits > RANGE_PROOF_MAX_BITS {
+            return None;
+        }
+        if bits < 64 && value >> bits != 0 {
+            return None;
+        }
+
+        // 2 The commitment the proof is for.
+        let commitment = PedersenCommitment::commit(value, blinding)?;
+
+        // 3 Choose random blinding factors for all but the most significant bit.
+        let mut bit_blindings = Vec::<Scalar>::with_capacity(bits as usize);
+        let mut weighted_sum = MaybeScalar::Zero;
+        for index in 0..(bits - 1) {
+            let bit_blinding = random_blinding();
+            weighted_sum += power_of_two(index) * bit_blinding;
+            bit_blindings.push(bit_blinding);
+        }
+
+        // 4 Choose the last blinding factor so that Σ 2^i·r_i = r.
+        let top_weight = power_of_two(bits - 1).not_zero().ok()?;
+        let top_blinding = ((MaybeScalar::Valid(blinding) - weighted_sum) * top_weight.invert())
+            .not_zero()
+            .ok()?;
+        bit_blindings.push(top_blinding);
+
+        // 5 Commit to each bit and prove it.
+        let mut bit_commitments = Vec::<PedersenCommitment>::with_capacity(bits as usize);
+        let mut bit_proofs = Vec::<BitProof>::with_capacity(bits as usize);
+        for (index, bit_blinding) in bit_blindings.into_iter().enumerate() {
+            let bit = (value >> index) & 1 == 1;
+            let bit_commitment = PedersenCommitment::commit(bit as u64, bit_blinding)?;
+            let context = Self::bit_context(&commitment, index as u8);
+            bit_proofs.push(BitProof::prove(
+                &bit_commitment,
+                bit,
+                bit_blinding,
+                &context,
+            )?);
+            bit_commitments.push(bit_commitment);
+        }
+
+        Some(RangeProof {
+            bit_commitments,
+            bit_proofs,
+        })
+    }
+
+    /// Returns the number of bits the proof covers.
+    pub fn bits(&self) -> u8 {
+        self.bit_commitments.len() as u8
+    }
+
+    /// Verifies that the commitment opens to a value in `[0, 2^bits)`.
+    pub fn verify(&self, commitment: &PedersenCommitment) -> bool {
+        // 1 Check the shape.
+        let bits = self.bit_commitments.len();
+        if bits == 0 || bits > RANGE_PROOF_MAX_BITS as usize || self.bit_proofs.len() != bits {
+            return false;
+        }
+
+        // 2 Check each bit proof, and sum up the bit commitments weighted by powers of two.
+        let mut weighted_sum = MaybePoint::Infinity;
+        for (index, (bit_commitment, bit_proof)) in self
+            .bit_commitments
+            .iter()
+            .zip(self.bit_proofs.iter())
+            .enumerate()
+        {
+            let context = Self::bit_context(commitment, index as u8);
+            if !bit_proof.verify(bit_commitment, &context) {
+                return false;
+            }
+            weighted_sum += bit_commitment.point() * power_of_two(index as u8);
+        }
+
+        // 3 The weighted sum must be the commitment.
+        weighted_sum == MaybePoint::Valid(commitment.point())
+    }
+
+    /// Serializes the proof as the bit count followed by each bit commitment and its proof.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::with_capacity(1 + self.bit_commitments.len() * BIT_SIZE);
+        bytes.push(self.bits());
+        for (bit_commitment, bit_proof) in self.bit_commitments.iter().zip(self.bit_proofs.iter()) {
+            bytes.extend(bit_commitment.serialize());
+            bytes.extend(bit_proof.serialize());
+        }
+        bytes
+    }
+
+    /// Deserializes a proof serialized with [`RangeProof::serialize`].
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        // 1 Check the length against the bit count.
+        let (bits, body) = bytes.split_first()?;
+        if *bits == 0 || *bits > RANGE_PROOF_MAX_BITS || body.len() != *bits as usize * BIT_SIZE {
+            return None;
+        }
+
+        // 2 Parse each bit commitment and its proof.
+        let mut bit_commitments = Vec::<PedersenCommitment>::with_capacity(*bits as usize);
+        let mut bit_proofs = Vec::<BitProof>::with_capacity(*bits as usize);
+        for chunk in body.chunks_exact(BIT_SIZE) {
+            bit_commitments.push(PedersenCommitment::deserialize(
+                chunk[..33].try_into().ok()?,
+            )?);
+            bit_proofs.push(BitProof::deserialize(chunk[33..].try_into().ok()?)?);
+        }
+
+        Some(RangeProof {
+            bit_commitments,
+            bit_proofs,
+        })
+    }
+
+    /// Binds a bit proof to the commitment and the position of the bit.
+    fn bit_context(commitment: &PedersenCommitment, index: u8) -> Vec<u8> {
+        let mut context = commitment.serialize().to_vec();
+        context.push(index);
+        context
+    }
+}
+
+/// Returns `2^index` as a scalar.
+fn power_of_two(index: u8) -> MaybeScalar {
+    MaybeScalar::from(1u128 << index)
+}
@@ -0,0 +1,61 @@
+use bitcoin::secp256k1::{All, Secp256k1};
+use k256::elliptic_curve::ops::MulByGenerator;
+use k256::{NonZeroScalar, ProjectivePoint, PublicKey};
+use secp::{MaybePoint, MaybeScalar, Point, Scalar};
+use std::sync::OnceLock;
+
+/// Shared libsecp256k1 context, for the `bitcoin` APIs that take one (BIP32 derivation).
+static SECP256K1_CONTEXT: OnceLock<Secp256k1<All>> = OnceLock::new();
+
+/// Returns the shared libsecp256k1 context, created on first use.
+///
+/// Creating a context allocates and precomputes its tables, so it is created once per process
+/// rather than per call.
+pub fn secp256k1_context() -> &'static Secp256k1<All> {
+    SECP256K1_CONTEXT.get_or_init(Secp256k1::new)
+}
+
+/// Multiplication by the generator point using precomputed tables.
+///
+/// `Scalar::base_point_mul` runs a generic variable-base multiplication; this uses the k256 generator
+/// lookup table instead, built once on first use. Signing, verification and key derivation all
+/// multiply by the generator, so they should go through this.
+pub trait GeneratorMul {
+    type Output;
+    fn generator_mul(&self) -> Self::Output;
+}
+
+impl GeneratorMul for Scalar {
+    type Output = Point;
+
+    fn generator_mul(&self) -> Point {
+        // 1 Multiply the generator by the scalar with the precomputed tables.
+        let point = ProjectivePoint::mul_by_generator(NonZeroScalar::from(*self).as_ref());
+
+        // 2 Convert the point back; a non-zero scalar never yields the point at infinity.
+        let public_key = PublicKey::from_affine(point.to_affine())
+            .expect("Non-zero scalar multiplied to the point at infinity.");
+        Point::from(public_key)
+    }
+}
+
+impl GeneratorMul for MaybeScalar {
+    type Output = MaybePoint;
+
+    fn generator_mul(&self) -> MaybePoint {
+        match self {
+            MaybeScalar::Valid(scalar) => MaybePoint::Valid(scalar.generator_mul()),
+            MaybeScalar::Zero => MaybePoint::Infinity,
+        }
+    }
+}
+
+/// Builds the precomputed tables and the shared context ahead of first use, so the first
+/// verifications of a sync do not pay for them.
+pub fn warm_up() {
+    // 1 Build the generator tables.
+    let _ = Scalar::one().generator_mul();
+
+    // 2 Create the shared libsecp256k1 context.
+    let _ = secp256k1_context();
+}
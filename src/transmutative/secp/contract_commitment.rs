@@ -0,0 +1,147 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::GeneratorMul;
+use crate::transmutative::secp::schnorr::{
+    challenge, rfc6979_nonce, Bytes32, LiftScalar, SchnorrSigningMode,
+};
+use secp::{MaybePoint, MaybeScalar, Point, Scalar};
+
+/// Returns the tweak committing the point to the data, `hash(P || data)` with `P` compressed.
+///
+/// The point is committed to along with its parity, so a commitment opens to a single point.
+fn contract_tweak(point: Point, data: &[u8], tag: HashTag) -> Option<Scalar> {
+    let mut preimage = Vec::<u8>::with_capacity(33 + data.len());
+    preimage.extend(point.serialize());
+    preimage.extend(data);
+
+    Scalar::from_slice(&preimage.hash(Some(tag))).ok()
+}
+
+/// Commits the data into an x-only public key, `Q = P + hash(P || data)·G` (pay-to-contract).
+///
+/// The committed key can be paid to like any other key, for instance as the internal key of a Taproot
+/// output, and only reveals the commitment once the data and the original key are disclosed.
+///
+/// Returns the x-only committed key and whether its y coordinate is odd, or `None` if the key is
+/// invalid or the tweak is out of range (negligible probability).
+pub fn pay_to_contract_key(public_key: [u8; 32], data: &[u8]) -> Option<([u8; 32], bool)> {
+    // 1 Lift the key to its even point.
+    let public_key_point = public_key.to_even_point()?;
+
+    // 2 Tweak the key.
+    let tweak = contract_tweak(public_key_point, data, HashTag::PayToContract)?;
+    let committed_key = match public_key_point + tweak.generator_mul() {
+        MaybePoint::Valid(point) => point,
+        MaybePoint::Infinity => return None,
+    };
+
+    // 3 Return the committed key.
+    Some((
+        committed_key.serialize_xonly(),
+        committed_key.parity().into(),
+    ))
+}
+
+/// Returns the secret key of the key committed to the data with [`pay_to_contract_key`],
+/// `x + hash(P || data)`, for spending from it.
+///
+/// Returns `None` if the key is invalid or the tweak is out of range (negligible probability).
+pub fn pay_to_contract_secret(secret_key: [u8; 32], data: &[u8]) -> Option<[u8; 32]> {
+    // 1 Lift the secret key so that its public key has an even y coordinate.
+    let secret_key_scalar = secret_key.to_scalar()?.lift();
+    let public_key_point = secret_key_scalar.generator_mul();
+
+    // 2 Tweak the secret key.
+    let tweak = contract_tweak(public_key_point, data, HashTag::PayToContract)?;
+    match secret_key_scalar + tweak {
+        MaybeScalar::Valid(scalar) => Some(scalar.serialize()),
+        MaybeScalar::Zero => None,
+    }
+}
+
+/// Verifies that the x-only committed key commits to the data under the x-only public key.
+pub fn verify_pay_to_contract(public_key: [u8; 32], data: &[u8], committed_key: [u8; 32]) -> bool {
+    match pay_to_contract_key(public_key, data) {
+        Some((expected_committed_key, _)) => expected_committed_key == committed_key,
+        None => false,
+    }
+}
+
+/// Signs a Schnorr message while committing the data into the signature nonce,
+/// `R' = R + hash(R || data)·G` (sign-to-contract).
+///
+/// The signature is a regular Schnorr signature that verifies as usual. The original nonce `R` is
+/// returned as a compressed point, and opens the commitment along with the data.
+///
+/// Returns the signature and the original nonce, or `None` if the key is invalid.
+pub fn sign_to_contract(
+    secret_key: [u8; 32],
+    message: [u8; 32],
+    data: &[u8],
+    mode: SchnorrSigningMode,
+) -> Option<([u8; 64], [u8; 33])> {
+    // 1 Lift the secret key so that its public key has an even y coordinate.
+    let secret_key_scalar = secret_key.to_scalar()?.lift();
+    let public_key_point = secret_key_scalar.generator_mul();
+
+    // 2 Derive the original nonce from the key, the message, the mode and the data. The nonce must
+    // depend on the data: two signatures with the same nonce but different tweaks leak the key.
+    let mode_tag = match mode {
+        SchnorrSigningMode::Cube => "cube",
+        SchnorrSigningMode::BIP340 => "bip340",
+    };
+    let mut extra_data_preimage = mode_tag.as_bytes().to_vec();
+    extra_data_preimage.extend(data);
+    let extra_data = extra_data_preimage.hash(Some(HashTag::SignToContract));
+    let original_nonce_scalar =
+        rfc6979_nonce(secret_key_scalar.serialize(), message, Some(extra_data))?;
+    let original_nonce_point = original_nonce_scalar.generator_mul();
+
+    // 3 Tweak the nonce, and lift it so that the signed nonce has an even y coordinate.
+    let tweak = contract_tweak(original_nonce_point, data, HashTag::SignToContract)?;
+    let nonce_scalar = match original_nonce_scalar + tweak {
+        MaybeScalar::Valid(scalar) => scalar.lift(),
+        MaybeScalar::Zero => return None,
+    };
+    let nonce_point = nonce_scalar.generator_mul();
+
+    // 4 Compute the challenge and the commitment.
+    let challenge_scalar = match challenge(nonce_point, public_key_point, message, mode) {
+        MaybeScalar::Valid(scalar) => scalar,
+        MaybeScalar::Zero => return None,
+    };
+    let commitment_scalar = match (secret_key_scalar * challenge_scalar) + nonce_scalar {
+        MaybeScalar::Valid(scalar) => scalar,
+        MaybeScalar::Zero => return None,
+    };
+
+    // 5 Return the signature and the original nonce.
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&nonce_point.serialize_xonly());
+    signature[32..].copy_from_slice(&commitment_scalar.serialize());
+
+    Some((signature, original_nonce_point.serialize()))
+}
+
+/// Verifies that the nonce of the signature commits to the data under the compressed original nonce.
+///
+/// NOTE: This only checks the commitment; the signature itself is verified as any other.
+pub fn verify_sign_to_contract(signature: [u8; 64], original_nonce: [u8; 33], data: &[u8]) -> bool {
+    // 1 Parse the original nonce.
+    let original_nonce_point = match Point::from_slice(&original_nonce) {
+        Ok(point) => point,
+        Err(_) => return false,
+    };
+
+    // 2 Tweak the original nonce.
+    let tweak = match contract_tweak(original_nonce_point, data, HashTag::SignToContract) {
+        Some(tweak) => tweak,
+        None => return false,
+    };
+    let nonce_point = match original_nonce_point + tweak.generator_mul() {
+        MaybePoint::Valid(point) => point,
+        MaybePoint::Infinity => return false,
+    };
+
+    // 3 Compare with the nonce of the signature.
+    signature[..32] == nonce_point.serialize_xonly()
+}
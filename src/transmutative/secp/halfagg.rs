@@ -0,0 +1,129 @@
+use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::GeneratorMul;
+use crate::transmutative::secp::schnorr::{challenge, Bytes32, SchnorrSigningMode};
+use secp::{MaybePoint, MaybeScalar, Scalar};
+
+/// Half-aggregates Schnorr signatures, each given with its x-only public key and message.
+///
+/// The aggregate is the nonces of the signatures followed by a single randomized sum of their commitments,
+/// `32 * (n + 1)` bytes instead of `64 * n`. The signatures are not verified.
+pub fn aggregate(signatures: &[([u8; 32], [u8; 32], [u8; 64])]) -> Option<Vec<u8>> {
+    // 1 There must be at least one signature.
+    if signatures.is_empty() {
+        return None;
+    }
+
+    // 2 Collect the nonces.
+    let public_nonces: Vec<[u8; 32]> = signatures
+        .iter()
+        .map(|(_, _, signature)| signature[..32].try_into().ok())
+        .collect::<Option<Vec<[u8; 32]>>>()?;
+
+    // 3 Sum the commitments, weighted by their randomizers.
+    let public_keys_and_messages: Vec<([u8; 32], [u8; 32])> = signatures
+        .iter()
+        .map(|(public_key, message, _)| (*public_key, *message))
+        .collect();
+    let randomizers = randomizers(&public_keys_and_messages, &public_nonces);
+
+    let mut commitment_sum = MaybeScalar::Zero;
+    for ((_, _, signature), randomizer) in signatures.iter().zip(randomizers.iter()) {
+        let commitment_scalar = MaybeScalar::from_slice(&signature[32..]).ok()?;
+        commitment_sum += commitment_scalar * *randomizer;
+    }
+
+    // 4 Serialize the nonces followed by the commitment sum.
+    let mut aggregate_signature = Vec::<u8>::with_capacity(32 * (signatures.len() + 1));
+    for public_nonce in public_nonces.iter() {
+        aggregate_signature.extend(public_nonce);
+    }
+    aggregate_signature.extend(commitment_sum.serialize());
+
+    Some(aggregate_signature)
+}
+
+/// Verifies a half-aggregate signature against the x-only public keys and messages, in the order they were aggregated.
+pub fn verify_aggregate(
+    public_keys_and_messages: &[([u8; 32], [u8; 32])],
+    aggregate_signature: &[u8],
+    mode: SchnorrSigningMode,
+) -> bool {
+    // 1 There must be at least one signature, with a nonce each and the commitment sum.
+    let count = public_keys_and_messages.len();
+    if count == 0 || aggregate_signature.len() != 32 * (count + 1) {
+        return false;
+    }
+
+    // 2 Parse the nonces and the commitment sum.
+    let public_nonces: Vec<[u8; 32]> = match aggregate_signature[..32 * count]
+        .chunks_exact(32)
+        .map(|chunk| chunk.try_into().ok())
+        .collect::<Option<Vec<[u8; 32]>>>()
+    {
+        Some(public_nonces) => public_nonces,
+        None => return false,
+    };
+    let commitment_sum = match MaybeScalar::from_slice(&aggregate_signature[32 * count..]) {
+        Ok(commitment_sum) => commitment_sum,
+        Err(_) => return false,
+    };
+
+    // 3 Sum the per-signature equation points, weighted by their randomizers.
+    let randomizers = randomizers(public_keys_and_messages, &public_nonces);
+
+    let mut equation_sum = MaybePoint::Infinity;
+    for (((public_key, message), public_nonce), randomizer) in public_keys_and_messages
+        .iter()
+        .zip(public_nonces.iter())
+        .zip(randomizers.iter())
+    {
+        let public_key_point = match public_key.to_even_point() {
+            Some(point) => point,
+            None => return false,
+        };
+        let public_nonce_point = match public_nonce.to_even_point() {
+            Some(point) => point,
+            None => return false,
+        };
+
+        let challenge_scalar =
+            match challenge(public_nonce_point, public_key_point, *message, mode.clone()) {
+                MaybeScalar::Valid(scalar) => scalar,
+                MaybeScalar::Zero => return false,
+            };
+
+        let equation_point = (public_key_point * challenge_scalar) + public_nonce_point;
+        equation_sum += equation_point * *randomizer;
+    }
+
+    // 4 The commitment sum must open the weighted sum.
+    commitment_sum.generator_mul() == equation_sum
+}
+
+/// Returns the randomizers of the signatures: `1` for the first, and for each next one the tagged hash of
+/// all nonces, public keys and messages up to and including its own.
+fn randomizers(
+    public_keys_and_messages: &[([u8; 32], [u8; 32])],
+    public_nonces: &[[u8; 32]],
+) -> Vec<MaybeScalar> {
+    let mut preimage = Vec::<u8>::with_capacity(96 * public_nonces.len());
+    let mut randomizers = Vec::<MaybeScalar>::with_capacity(public_nonces.len());
+
+    for (index, ((public_key, message), public_nonce)) in public_keys_and_messages
+        .iter()
+        .zip(public_nonces.iter())
+        .enumerate()
+    {
+        preimage.extend(public_nonce);
+        preimage.extend(public_key);
+        preimage.extend(message);
+
+        let randomizer = match index {
+            0 => MaybeScalar::Valid(Scalar::one()),
+            _ => MaybeScalar::reduce_from(&preimage.hash(Some(HashTag::HalfAggRandomizer))),
+        };
+        randomizers.push(randomizer);
+    }
+
+    randomizers
+}
@@ -1,4 +1,9 @@
 pub mod authenticable;
+pub mod context;
+pub mod contract_commitment;
 pub mod error;
+pub mod halfagg;
 pub mod into;
+pub mod public_key;
 pub mod schnorr;
+pub mod vss;
@@ -0,0 +1,123 @@
+use bech32::{Bech32, Hrp};
+use secp::{MaybePoint, Point};
+
+/// Human-readable part of Bech32-encoded public keys.
+const NPUB_HRP: &str = "npub";
+
+/// Errors of public key conversions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKeyError {
+    // Neither 32 (x-only) nor 33 (compressed) bytes.
+    InvalidLength(usize),
+    // Compressed key prefix other than `0x02` or `0x03`.
+    InvalidPrefix(u8),
+    // Not a point on the curve.
+    InvalidPoint,
+    // Malformed hex string.
+    InvalidHex,
+    // Malformed or non-`npub` Bech32 string.
+    InvalidNpub,
+}
+
+/// Lifts an x-only public key to its even point.
+pub fn xonly_to_point(xonly: &[u8; 32]) -> Result<Point, PublicKeyError> {
+    // 1 Prefix the x coordinate with the even parity byte.
+    let mut compressed = [0u8; 33];
+    compressed[0] = 0x02;
+    compressed[1..].copy_from_slice(xonly);
+
+    // 2 Parse the point.
+    match MaybePoint::from_slice(&compressed) {
+        Ok(MaybePoint::Valid(point)) => Ok(point),
+        _ => Err(PublicKeyError::InvalidPoint),
+    }
+}
+
+/// Validates an x-only public key.
+pub fn validate_xonly(xonly: &[u8; 32]) -> Result<(), PublicKeyError> {
+    xonly_to_point(xonly).map(|_| ())
+}
+
+/// Converts an x-only public key into its even compressed SEC1 encoding.
+pub fn xonly_to_compressed(xonly: &[u8; 32]) -> Result<[u8; 33], PublicKeyError> {
+    Ok(xonly_to_point(xonly)?.serialize())
+}
+
+/// Converts a compressed SEC1 public key into its x-only encoding, dropping the parity.
+pub fn compressed_to_xonly(compressed: &[u8; 33]) -> Result<[u8; 32], PublicKeyError> {
+    // 1 Check the parity prefix.
+    if compressed[0] != 0x02 && compressed[0] != 0x03 {
+        return Err(PublicKeyError::InvalidPrefix(compressed[0]));
+    }
+
+    // 2 Parse the point.
+    match MaybePoint::from_slice(compressed) {
+        Ok(MaybePoint::Valid(point)) => Ok(point.serialize_xonly()),
+        _ => Err(PublicKeyError::InvalidPoint),
+    }
+}
+
+/// Converts an x-only public key into a Bech32-encoded `npub` string.
+pub fn xonly_to_npub(xonly: &[u8; 32]) -> Result<String, PublicKeyError> {
+    // 1 Validate the key.
+    validate_xonly(xonly)?;
+
+    // 2 Encode the key with the "npub" human-readable part.
+    let hrp = Hrp::parse(NPUB_HRP).map_err(|_| PublicKeyError::InvalidNpub)?;
+    bech32::encode::<Bech32>(hrp, xonly).map_err(|_| PublicKeyError::InvalidNpub)
+}
+
+/// Decodes a Bech32-encoded `npub` string into an x-only public key.
+pub fn npub_to_xonly(npub: &str) -> Result<[u8; 32], PublicKeyError> {
+    // 1 Decode the Bech32 string.
+    let (hrp, bytes) = bech32::decode(npub).map_err(|_| PublicKeyError::InvalidNpub)?;
+
+    // 2 Check the human-readable part.
+    if hrp.as_str() != NPUB_HRP {
+        return Err(PublicKeyError::InvalidNpub);
+    }
+
+    // 3 Check the length and validate the key.
+    let xonly: [u8; 32] = bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| PublicKeyError::InvalidLength(bytes.len()))?;
+    validate_xonly(&xonly)?;
+
+    Ok(xonly)
+}
+
+/// Converts raw x-only (32-byte) or compressed SEC1 (33-byte) public key bytes into an account key.
+pub fn account_key_from_slice(bytes: &[u8]) -> Result<[u8; 32], PublicKeyError> {
+    match bytes.len() {
+        32 => {
+            let xonly: [u8; 32] = bytes
+                .try_into()
+                .map_err(|_| PublicKeyError::InvalidLength(32))?;
+            validate_xonly(&xonly)?;
+            Ok(xonly)
+        }
+        33 => {
+            let compressed: [u8; 33] = bytes
+                .try_into()
+                .map_err(|_| PublicKeyError::InvalidLength(33))?;
+            compressed_to_xonly(&compressed)
+        }
+        len => Err(PublicKeyError::InvalidLength(len)),
+    }
+}
+
+/// Parses an account key given as an `npub`, or as x-only or compressed SEC1 hex, optionally `0x` prefixed.
+pub fn parse_account_key(input: &str) -> Result<[u8; 32], PublicKeyError> {
+    // 1 Trim the input.
+    let input = input.trim();
+
+    // 2 Decode an npub.
+    if input.starts_with(NPUB_HRP) {
+        return npub_to_xonly(input);
+    }
+
+    // 3 Decode hex.
+    let hex_str = input.strip_prefix("0x").unwrap_or(input);
+    let bytes = hex::decode(hex_str).map_err(|_| PublicKeyError::InvalidHex)?;
+    account_key_from_slice(&bytes)
+}
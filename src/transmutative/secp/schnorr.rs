@@ -1,7 +1,12 @@
 use crate::transmutative::hash::{Hash, HashTag};
+use crate::transmutative::secp::context::GeneratorMul;
 use crate::transmutative::secp::into::IntoSigTuple;
+use crate::transmutative::secp::public_key;
+use bitcoin::hashes::hmac::{Hmac, HmacEngine};
+use bitcoin::hashes::{sha256, Hash as _, HashEngine};
 use rand::{rngs::OsRng, RngCore};
 use secp::{MaybePoint, MaybeScalar, Point, Scalar};
+use zeroize::Zeroize;
 
 /// The signing mode of Schnorr signatures.
 #[derive(Clone, PartialEq)]
@@ -16,15 +21,12 @@ pub fn sign(secret_key: [u8; 32], message: [u8; 32], mode: SchnorrSigningMode) -
 
     let secret_key_scalar_ = secret_key.to_scalar()?;
     let secret_key_scalar = secret_key_scalar_.lift();
-    let public_key_point = secret_key_scalar.base_point_mul();
+    let public_key_point = secret_key_scalar.generator_mul();
 
     // Secret-public nonce pairs.
-    let secret_nonce_scalar_ = match secret_nonce(secret_key_scalar.serialize(), message) {
-        MaybeScalar::Valid(scalar) => scalar,
-        MaybeScalar::Zero => return None,
-    };
+    let secret_nonce_scalar_ = secret_nonce(secret_key_scalar.serialize(), message, &mode)?;
     let secret_nonce_scalar = secret_nonce_scalar_.lift();
-    let public_nonce_point = secret_nonce_scalar.base_point_mul();
+    let public_nonce_point = secret_nonce_scalar.generator_mul();
 
     // Signature challenge.
     let challenge_scalar = match challenge(public_nonce_point, public_key_point, message, mode) {
@@ -74,7 +76,7 @@ pub fn verify_xonly(
         MaybePoint::Valid(point) => point,
     };
 
-    s_commitment_scalar.base_point_mul() == equation_point
+    s_commitment_scalar.generator_mul() == equation_point
 }
 
 /// Verifies a Schnorr message against a compressed public key.
@@ -106,7 +108,7 @@ pub fn verify_compressed(
         MaybePoint::Valid(point) => point,
     };
 
-    s_commitment_scalar.base_point_mul() == equation_point
+    s_commitment_scalar.generator_mul() == equation_point
 }
 
 /// Verifies a Schnorr message against a uncompressed public key.
@@ -138,7 +140,7 @@ pub fn verify_uncompressed(
         MaybePoint::Valid(point) => point,
     };
 
-    s_commitment_scalar.base_point_mul() == equation_point
+    s_commitment_scalar.generator_mul() == equation_point
 }
 
 /// Returns signature challenge.
@@ -163,15 +165,80 @@ pub fn challenge(
 }
 
 /// Deterministicially generates secret nonce for signing.
-fn secret_nonce(secret_key: [u8; 32], message: [u8; 32]) -> MaybeScalar {
-    let mut secret_nonce_preimage = Vec::<u8>::new();
+///
+/// The signing mode is mixed in, since the challenges of the two modes differ for the same message and
+/// reusing a nonce across them would leak the secret key.
+fn secret_nonce(
+    secret_key: [u8; 32],
+    message: [u8; 32],
+    mode: &SchnorrSigningMode,
+) -> Option<Scalar> {
+    let mode_tag = match mode {
+        SchnorrSigningMode::Cube => "cube",
+        SchnorrSigningMode::BIP340 => "bip340",
+    };
+    let extra_data = mode_tag
+        .as_bytes()
+        .to_vec()
+        .hash(Some(HashTag::SecretNonce));
 
-    secret_nonce_preimage.extend(secret_key);
-    secret_nonce_preimage.extend(message);
+    rfc6979_nonce(secret_key, message, Some(extra_data))
+}
 
-    let secret_nonce = secret_nonce_preimage.hash(Some(HashTag::SecretNonce));
+/// Derives a secret nonce from a secret key and a message as specified in RFC 6979 (section 3.2),
+/// with HMAC-SHA256.
+///
+/// The optional extra data is appended to the HMAC inputs as the additional data of section 3.6.
+pub fn rfc6979_nonce(
+    secret_key: [u8; 32],
+    message: [u8; 32],
+    extra_data: Option<[u8; 32]>,
+) -> Option<Scalar> {
+    // 1 The secret key must be a valid scalar.
+    secret_key.to_scalar()?;
+
+    // 2 Reduce the message modulo the curve order.
+    let message_reduced = MaybeScalar::reduce_from(&message).serialize();
+    let extra_data: &[u8] = match &extra_data {
+        Some(extra_data) => extra_data,
+        None => &[],
+    };
 
-    MaybeScalar::reduce_from(&secret_nonce)
+    // 3 Seed the HMAC-DRBG.
+    let mut v = [0x01u8; 32];
+    let mut k = [0x00u8; 32];
+
+    k = hmac_sha256(
+        &k,
+        &[&v, &[0x00], &secret_key, &message_reduced, extra_data],
+    );
+    v = hmac_sha256(&k, &[&v]);
+    k = hmac_sha256(
+        &k,
+        &[&v, &[0x01], &secret_key, &message_reduced, extra_data],
+    );
+    v = hmac_sha256(&k, &[&v]);
+
+    // 4 Generate candidates until one is a valid scalar.
+    loop {
+        v = hmac_sha256(&k, &[&v]);
+
+        if let Ok(secret_nonce) = Scalar::from_slice(&v) {
+            return Some(secret_nonce);
+        }
+
+        k = hmac_sha256(&k, &[&v, &[0x00]]);
+        v = hmac_sha256(&k, &[&v]);
+    }
+}
+
+/// Returns the HMAC-SHA256 of the concatenated data under the given key.
+fn hmac_sha256(key: &[u8; 32], data: &[&[u8]]) -> [u8; 32] {
+    let mut engine = HmacEngine::<sha256::Hash>::new(key);
+    for chunk in data {
+        engine.input(chunk);
+    }
+    Hmac::<sha256::Hash>::from_engine(engine).to_byte_array()
 }
 
 /// Generates a random secret.
@@ -179,15 +246,34 @@ pub fn generate_secret() -> [u8; 32] {
     let mut random_entropy = [0u8; 32];
     OsRng.fill_bytes(&mut random_entropy);
 
-    let secret = random_entropy.hash(Some(HashTag::SecretKey));
+    let mut secret = random_entropy.hash(Some(HashTag::SecretKey));
+    random_entropy.zeroize();
     let secret_scalar = match MaybeScalar::reduce_from(&secret) {
         MaybeScalar::Valid(scalar) => scalar,
         MaybeScalar::Zero => Scalar::reduce_from(&secret),
     };
+    secret.zeroize();
 
     secret_scalar.lift().serialize()
 }
 
+/// Generates a uniformly random non-zero scalar.
+///
+/// Unlike `generate_secret`, the scalar is not lifted to an even point, which would leak a bit of it
+/// through its point. Blinding factors, polynomial coefficients and proof nonces must use this.
+pub fn generate_scalar() -> Scalar {
+    // 1 Draw 32 bytes until they are a valid non-zero scalar.
+    loop {
+        let mut random_entropy = [0u8; 32];
+        OsRng.fill_bytes(&mut random_entropy);
+        let scalar = Scalar::from_slice(&random_entropy);
+        random_entropy.zeroize();
+        if let Ok(scalar) = scalar {
+            return scalar;
+        }
+    }
+}
+
 pub trait Bytes32 {
     // Conversions
     fn secret_to_public(&self) -> Option<[u8; 32]>;
@@ -203,7 +289,7 @@ impl Bytes32 for [u8; 32] {
     /// Returns [u8; 32] x-only public key from a [u8; 32] secret key.
     fn secret_to_public(&self) -> Option<[u8; 32]> {
         let secret_key_scalar = self.to_owned().to_scalar()?;
-        Some(secret_key_scalar.base_point_mul().serialize_xonly())
+        Some(secret_key_scalar.generator_mul().serialize_xonly())
     }
 
     /// Converts [u8; 32] into a scalar.
@@ -217,18 +303,7 @@ impl Bytes32 for [u8; 32] {
 
     /// Converts [u8; 32] into an even point.
     fn to_even_point(&self) -> Option<Point> {
-        let mut point_bytes = Vec::with_capacity(33);
-
-        point_bytes.push(0x02);
-        point_bytes.extend(self);
-
-        match MaybePoint::from_slice(&point_bytes) {
-            Ok(maybe_point) => match maybe_point {
-                MaybePoint::Valid(point) => Some(point),
-                MaybePoint::Infinity => None,
-            },
-            Err(_) => None,
-        }
+        public_key::xonly_to_point(self).ok()
     }
 
     /// Returns whether the given bytes represent a valid scalar.
@@ -256,7 +331,7 @@ pub trait LiftScalar {
 impl LiftScalar for Scalar {
     fn lift(&self) -> Self {
         let mut secret_to_lift = *self;
-        let point = secret_to_lift.base_point_mul();
+        let point = secret_to_lift.generator_mul();
         secret_to_lift = secret_to_lift.negate_if(point.parity());
         secret_to_lift
     }
@@ -0,0 +1,227 @@
+use crate::transmutative::secp::context::GeneratorMul;
+use crate::transmutative::secp::schnorr;
+use secp::{MaybePoint, MaybeScalar, Point, Scalar};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Index of a participant, starting at `1`; the secret sits at index `0`.
+type ParticipantIndex = u32;
+
+/// Maximum number of coefficients in a commitment.
+pub const VSS_MAX_THRESHOLD: usize = 255;
+
+/// Feldman commitments `A_j = a_j·G` to the coefficients of a secret sharing polynomial
+/// `f(x) = a_0 + a_1·x + … + a_{t−1}·x^{t−1}`.
+///
+/// The dealer broadcasts the commitment and sends each participant their share `f(i)` privately. A
+/// participant checks their share against the commitment without learning anything about the other
+/// shares, and the commitment to `a_0` is the dealer's contribution to the group key. This is the
+/// sharing each participant runs in a Pedersen DKG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VssCommitment {
+    // Commitments to the coefficients, constant term first.
+    coefficient_commitments: Vec<Point>,
+}
+
+impl VssCommitment {
+    /// Constructs a commitment from its coefficient commitments, constant term first.
+    ///
+    /// Returns `None` if there are no coefficients or more than `VSS_MAX_THRESHOLD`.
+    pub fn new(coefficient_commitments: Vec<Point>) -> Option<Self> {
+        if coefficient_commitments.is_empty() || coefficient_commitments.len() > VSS_MAX_THRESHOLD {
+            return None;
+        }
+
+        Some(Self {
+            coefficient_commitments,
+        })
+    }
+
+    /// Returns the coefficient commitments, constant term first.
+    pub fn coefficient_commitments(&self) -> &Vec<Point> {
+        &self.coefficient_commitments
+    }
+
+    /// Returns the number of shares needed to recover the secret.
+    pub fn threshold(&self) -> usize {
+        self.coefficient_commitments.len()
+    }
+
+    /// Returns the commitment to the secret, `a_0·G`.
+    pub fn secret_commitment(&self) -> Point {
+        self.coefficient_commitments[0]
+    }
+
+    /// Returns the public key `f(i)·G` of the share at the given index, computed from the commitment
+    /// alone as `Σ i^j·A_j`.
+    ///
+    /// Returns `None` for index `0`, which is the secret, or if the key is the point at infinity.
+    pub fn share_public_key(&self, index: ParticipantIndex) -> Option<Point> {
+        // 1 Index 0 is the secret.
+        if index == 0 {
+            return None;
+        }
+
+        // 2 Evaluate the committed polynomial at the index, highest coefficient first.
+        let x = MaybeScalar::from(index as u128);
+        let mut share_public_key = MaybePoint::Infinity;
+        for coefficient_commitment in self.coefficient_commitments.iter().rev() {
+            share_public_key = share_public_key * x + *coefficient_commitment;
+        }
+
+        share_public_key.not_inf().ok()
+    }
+
+    /// Verifies a share received at the given index against the commitment.
+    pub fn verify_share(&self, index: ParticipantIndex, share: Scalar) -> bool {
+        match self.share_public_key(index) {
+            Some(share_public_key) => share.generator_mul() == share_public_key,
+            None => false,
+        }
+    }
+
+    /// Serializes the commitment as the number of coefficients followed by each 33-byte commitment.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::with_capacity(1 + 33 * self.coefficient_commitments.len());
+        bytes.push(self.coefficient_commitments.len() as u8);
+        for coefficient_commitment in self.coefficient_commitments.iter() {
+            bytes.extend(coefficient_commitment.serialize());
+        }
+        bytes
+    }
+
+    /// Deserializes a commitment serialized with [`VssCommitment::serialize`].
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        // 1 Check the length against the number of coefficients.
+        let (count, body) = bytes.split_first()?;
+        if body.len() != *count as usize * 33 {
+            return None;
+        }
+
+        // 2 Parse each coefficient commitment.
+        let coefficient_commitments = body
+            .chunks_exact(33)
+            .map(|chunk| Point::from_slice(chunk).ok())
+            .collect::<Option<Vec<Point>>>()?;
+
+        Self::new(coefficient_commitments)
+    }
+}
+
+impl Serialize for VssCommitment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        VssCommitment::serialize(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VssCommitment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        VssCommitment::deserialize(&bytes)
+            .ok_or_else(|| serde::de::Error::custom("Invalid VSS commitment."))
+    }
+}
+
+/// Splits a secret into shares for participants `1..=participants`, any `threshold` of which recover it.
+///
+/// Returns the commitment to broadcast and the shares in participant order, or `None` if the threshold
+/// is zero, exceeds the number of participants or `VSS_MAX_THRESHOLD`.
+///
+/// NOTE: Shares are secret and must only travel over an encrypted channel.
+pub fn deal(
+    secret: Scalar,
+    threshold: usize,
+    participants: usize,
+) -> Option<(VssCommitment, Vec<Scalar>)> {
+    // 1 Check the bounds.
+    if threshold == 0
+        || threshold > participants
+        || threshold > VSS_MAX_THRESHOLD
+        || participants > ParticipantIndex::MAX as usize
+    {
+        return None;
+    }
+
+    // 2 Draw the polynomial, with the secret as constant term.
+    let mut coefficients = Vec::<Scalar>::with_capacity(threshold);
+    coefficients.push(secret);
+    for _ in 1..threshold {
+        coefficients.push(schnorr::generate_scalar());
+    }
+
+    // 3 Commit to the coefficients.
+    let commitment = VssCommitment::new(
+        coefficients
+            .iter()
+            .map(|coefficient| coefficient.generator_mul())
+            .collect(),
+    )?;
+
+    // 4 Evaluate the polynomial at each participant index.
+    let mut shares = Vec::<Scalar>::with_capacity(participants);
+    for index in 1..=participants as ParticipantIndex {
+        let share = evaluate(&coefficients, index).not_zero().ok()?;
+        shares.push(share);
+    }
+
+    Some((commitment, shares))
+}
+
+/// Recovers the secret from shares at distinct indexes, by Lagrange interpolation at `0`.
+///
+/// Returns `None` if there are no shares, an index is `0` or repeated. Fewer shares than the threshold
+/// yield a wrong secret, so check the result against the commitment to the secret.
+pub fn recover(shares: &[(ParticipantIndex, Scalar)]) -> Option<Scalar> {
+    // 1 Check the indexes.
+    if shares.is_empty() {
+        return None;
+    }
+    for (position, (index, _)) in shares.iter().enumerate() {
+        if *index == 0 || shares[..position].iter().any(|(other, _)| other == index) {
+            return None;
+        }
+    }
+
+    // 2 Sum the shares weighted by their Lagrange coefficients.
+    let mut secret = MaybeScalar::Zero;
+    for (index, share) in shares.iter() {
+        let indexes: Vec<ParticipantIndex> = shares.iter().map(|(index, _)| *index).collect();
+        secret += lagrange_coefficient(*index, &indexes)? * *share;
+    }
+
+    secret.not_zero().ok()
+}
+
+/// Returns the Lagrange coefficient at `0` of the given index among the given indexes.
+pub fn lagrange_coefficient(
+    index: ParticipantIndex,
+    indexes: &[ParticipantIndex],
+) -> Option<Scalar> {
+    // 1 Multiply up `x_j / (x_j − x_i)` over the other indexes.
+    let x_i = MaybeScalar::from(index as u128);
+    let mut numerator = Scalar::one();
+    let mut denominator = Scalar::one();
+    for other in indexes.iter().filter(|other| **other != index) {
+        let x_j = MaybeScalar::from(*other as u128).not_zero().ok()?;
+        numerator *= x_j;
+        denominator *= (MaybeScalar::Valid(x_j) - x_i).not_zero().ok()?;
+    }
+
+    // 2 Divide.
+    Some(numerator * denominator.invert())
+}
+
+/// Evaluates a polynomial at the given index, highest coefficient first.
+fn evaluate(coefficients: &[Scalar], index: ParticipantIndex) -> MaybeScalar {
+    let x = MaybeScalar::from(index as u128);
+    let mut value = MaybeScalar::Zero;
+    for coefficient in coefficients.iter().rev() {
+        value = value * x + *coefficient;
+    }
+    value
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod abi_tests {
+    use cube::constructive::calldata::element_type::CalldataElementType;
+    use cube::executive::abi::{abi_error::AbiError, abi_type::AbiType, abi_value::AbiValue};
+    use cube::executive::stack::stack_item::StackItem;
+    use serde_json::json;
+
+    #[test]
+    fn abi_type_name_test() {
+        // Type names round-trip through their parser.
+        for name in [
+            "u8",
+            "u16",
+            "u32",
+            "u64",
+            "bool",
+            "bytes1",
+            "bytes256",
+            "varbytes",
+            "account_key",
+            "contract_id",
+            "u64[]",
+            "bytes32[][]",
+        ] {
+            let abi_type = AbiType::parse(name).unwrap();
+            assert_eq!(abi_type.to_string(), name);
+        }
+
+        // Out of range byte lengths and unknown names are rejected.
+        assert_eq!(AbiType::parse("bytes0"), None);
+        assert_eq!(AbiType::parse("bytes257"), None);
+        assert_eq!(AbiType::parse("u128"), None);
+
+        // Calldata element types map to the types the method receives.
+        assert_eq!(
+            AbiType::from_calldata_element_type(CalldataElementType::Bytes(31)),
+            AbiType::Bytes(32)
+        );
+        assert_eq!(
+            AbiType::from_calldata_element_type(CalldataElementType::Payable),
+            AbiType::U32
+        );
+    }
+
+    #[test]
+    fn abi_value_roundtrip_test() -> Result<(), String> {
+        let values = vec![
+            AbiValue::U8(0),
+            AbiValue::U16(0x1234),
+            AbiValue::U32(u32::MAX),
+            AbiValue::U64(u64::MAX),
+            AbiValue::Bool(true),
+            AbiValue::Bytes(vec![0x00, 0x01]),
+            AbiValue::Varbytes(vec![]),
+            AbiValue::AccountKey([0xa1; 32]),
+            AbiValue::ContractId([0xc1; 32]),
+            AbiValue::Array(AbiType::U64, vec![AbiValue::U64(1), AbiValue::U64(256)]),
+        ];
+        let abi_types: Vec<AbiType> = values.iter().map(|value| value.abi_type()).collect();
+
+        // Integers are minimal little-endian items, and arrays are prefixed by their length.
+        let items = AbiValue::encode_values(&values).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(items[0], StackItem::new(vec![]));
+        assert_eq!(items[1], StackItem::new(vec![0x34, 0x12]));
+        assert_eq!(items[9], StackItem::new(vec![0x02]));
+        assert_eq!(items[11], StackItem::new(vec![0x00, 0x01]));
+
+        // The values round-trip through the stack items.
+        let decoded =
+            AbiValue::decode_values(&abi_types, &items).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(decoded, values);
+
+        // The values round-trip through JSON.
+        for value in values.iter() {
+            let parsed = AbiValue::from_json(&value.abi_type(), &value.json())
+                .map_err(|e| format!("{:?}", e))?;
+            assert_eq!(&parsed, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn abi_value_rejection_test() {
+        // Non-minimal integers are rejected.
+        let items = vec![StackItem::new(vec![0x01, 0x00])];
+        assert_eq!(
+            AbiValue::decode_values(&[AbiType::U16], &items),
+            Err(AbiError::NonCanonicalInteger(AbiType::U16))
+        );
+
+        // As are integers out of the range of their type.
+        let items = vec![StackItem::new(vec![0x00, 0x01])];
+        assert_eq!(
+            AbiValue::decode_values(&[AbiType::U8], &items),
+            Err(AbiError::IntegerOutOfRange(AbiType::U8))
+        );
+
+        // Booleans other than the true and false items are rejected.
+        let items = vec![StackItem::new(vec![0x02])];
+        assert_eq!(
+            AbiValue::decode_values(&[AbiType::Bool], &items),
+            Err(AbiError::NonCanonicalBool)
+        );
+
+        // Keys must be exactly 32 bytes.
+        let items = vec![StackItem::new(vec![0xa1; 31])];
+        assert_eq!(
+            AbiValue::decode_values(&[AbiType::AccountKey], &items),
+            Err(AbiError::InvalidBytesLength(32, 31))
+        );
+
+        // Missing and leftover items are rejected.
+        assert_eq!(
+            AbiValue::decode_values(&[AbiType::U32], &[]),
+            Err(AbiError::MissingStackItem(AbiType::U32))
+        );
+        let items = vec![StackItem::new(vec![0x01]), StackItem::new(vec![0x01])];
+        assert_eq!(
+            AbiValue::decode_values(&[AbiType::U32], &items),
+            Err(AbiError::TrailingStackItems(1))
+        );
+
+        // Array elements must be of the array type.
+        let value = AbiValue::Array(AbiType::U8, vec![AbiValue::U16(1)]);
+        assert_eq!(value.encode(), Err(AbiError::TypeMismatch(AbiType::U8)));
+
+        // JSON values must fit their type.
+        assert_eq!(
+            AbiValue::from_json(&AbiType::U8, &json!(256)),
+            Err(AbiError::InvalidJsonValue(AbiType::U8))
+        );
+        assert_eq!(
+            AbiValue::from_json(&AbiType::U64, &json!("18446744073709551615")),
+            Ok(AbiValue::U64(u64::MAX))
+        );
+        assert_eq!(
+            AbiValue::from_json(&AbiType::Bytes(2), &json!("0xaabbcc")),
+            Err(AbiError::InvalidBytesLength(2, 3))
+        );
+    }
+}
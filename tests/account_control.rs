@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod account_control_tests {
+    use cube::inscriptive::registery::bodies::account_body::account_control::RMAccountControl;
+    use cube::transmutative::secp::context::GeneratorMul;
+    use cube::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+    use cube::transmutative::secp::vss;
+    use secp::{Point, Scalar};
+
+    #[test]
+    fn musig_control() -> Result<(), String> {
+        // 1 Three participants.
+        let participant_keys: Vec<Point> = (0..3)
+            .map(|_| schnorr::generate_scalar().generator_mul())
+            .collect();
+        let control = RMAccountControl::musig(participant_keys.clone())
+            .ok_or("Failed to construct the control.".to_string())?;
+        assert_eq!(control.threshold(), 3);
+        assert_eq!(control.participant_count(), 3);
+
+        // 2 The control holds for the x-only aggregate key only.
+        let aggregate_key = control
+            .aggregate_key()
+            .ok_or("Failed to aggregate.".to_string())?;
+        assert!(control.controls(aggregate_key.serialize_xonly()));
+        assert!(!control.controls(participant_keys[0].serialize_xonly()));
+
+        // 3 The participant order does not matter.
+        let reordered_keys = vec![
+            participant_keys[2],
+            participant_keys[0],
+            participant_keys[1],
+        ];
+        let reordered_control = RMAccountControl::musig(reordered_keys)
+            .ok_or("Failed to construct the control.".to_string())?;
+        assert!(reordered_control.controls(aggregate_key.serialize_xonly()));
+
+        // 4 Round-trip the control.
+        assert_eq!(
+            RMAccountControl::from_bytes(&control.to_bytes()),
+            Some(control)
+        );
+
+        // 5 A single or repeated key is not a MuSig2 control.
+        assert!(RMAccountControl::musig(vec![participant_keys[0]]).is_none());
+        assert!(RMAccountControl::musig(vec![participant_keys[0], participant_keys[0]]).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn frost_control() -> Result<(), String> {
+        // 1 A 2-of-3 group.
+        let group_secret = schnorr::generate_scalar();
+        let (group_commitment, shares) =
+            vss::deal(group_secret, 2, 3).ok_or("Failed to deal.".to_string())?;
+        let control = RMAccountControl::frost(group_commitment.clone(), 3)
+            .ok_or("Failed to construct the control.".to_string())?;
+        assert_eq!(control.threshold(), 2);
+        assert_eq!(control.participant_count(), 3);
+
+        // 2 The account key is the x-only group key.
+        let account_key = group_secret.generator_mul().serialize_xonly();
+        assert!(control.controls(account_key));
+
+        // 3 Each participant's verification key matches their share.
+        for (position, share) in shares.iter().enumerate() {
+            assert_eq!(
+                control.participant_verification_key(position as u32 + 1),
+                Some(share.generator_mul())
+            );
+        }
+        assert_eq!(control.participant_verification_key(0), None);
+        assert_eq!(control.participant_verification_key(4), None);
+
+        // 4 A signature under the group secret authorizes the account.
+        let indexed: Vec<(u32, Scalar)> = vec![(1, shares[0]), (3, shares[2])];
+        let recovered_secret = vss::recover(&indexed).ok_or("Failed to recover.".to_string())?;
+        let message = [0xabu8; 32];
+        let signature = schnorr::sign(
+            recovered_secret.serialize(),
+            message,
+            SchnorrSigningMode::Cube,
+        )
+        .ok_or("Failed to sign.".to_string())?;
+        assert!(schnorr::verify_xonly(
+            account_key,
+            message,
+            signature,
+            SchnorrSigningMode::Cube
+        ));
+
+        // 5 Round-trip the control.
+        assert_eq!(
+            RMAccountControl::from_bytes(&control.to_bytes()),
+            Some(control)
+        );
+
+        // 6 The threshold cannot exceed the number of participants.
+        assert!(RMAccountControl::frost(group_commitment, 1).is_none());
+
+        Ok(())
+    }
+}
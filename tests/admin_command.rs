@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod admin_command_tests {
+    use cube::communicative::api_auth::api_permission::ApiPermission;
+    use cube::communicative::webhook::webhook_event::WebhookEventKind;
+    use cube::operative::admin::admin_command::{AdminCommand, PeerAccessAction};
+    use cube::operative::admin::errors::admin_error::AdminError;
+    use cube::operative::logging::log_level::LogLevel;
+    use cube::transmutative::key::ToNostrKeyStr;
+
+    #[test]
+    fn admin_command_parse_test() -> Result<(), String> {
+        assert_eq!(
+            AdminCommand::parse(&["flush-delta"]),
+            Ok(AdminCommand::FlushDelta)
+        );
+        assert_eq!(
+            AdminCommand::parse(&["set-log-level", "debug"]),
+            Ok(AdminCommand::SetLogLevel(LogLevel::Debug))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["set-log-level", "loud"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["operators"]),
+            Ok(AdminCommand::Operators)
+        );
+        assert_eq!(
+            AdminCommand::parse(&["set-download-rate", "512", "2048"]),
+            Ok(AdminCommand::SetDownloadRate(512, Some(2048)))
+        );
+        assert_eq!(
+            AdminCommand::parse(&["set-download-rate", "off"]),
+            Ok(AdminCommand::SetDownloadRate(0, None))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["set-download-rate", "fast"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["cancel-rpc"]),
+            Ok(AdminCommand::CancelRpc)
+        );
+        assert_eq!(
+            AdminCommand::parse(&["broadcasts"]),
+            Ok(AdminCommand::Broadcasts)
+        );
+        assert_eq!(AdminCommand::parse(&["traffic"]), Ok(AdminCommand::Traffic));
+        assert_eq!(
+            AdminCommand::parse(&["contracts"]),
+            Ok(AdminCommand::Contracts(0))
+        );
+        assert_eq!(
+            AdminCommand::parse(&["contracts", "120"]),
+            Ok(AdminCommand::Contracts(120))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["contracts", "latest"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["receipt", &hex::encode([0xab; 32])]),
+            Ok(AdminCommand::Receipt([0xab; 32]))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["receipt", "abcd"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            AdminCommand::parse(&["reboot"]),
+            Err(AdminError::UnknownCommand(_))
+        ));
+
+        // Secp256k1 generator point x-coordinate.
+        let peer_key: [u8; 32] = [
+            0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+            0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b,
+            0x16, 0xf8, 0x17, 0x98,
+        ];
+        let npub = peer_key.to_npub().ok_or("npub encoding failed")?;
+        assert_eq!(
+            AdminCommand::parse(&["peers", "ban", &npub]),
+            Ok(AdminCommand::PeersUpdate(PeerAccessAction::Ban, peer_key))
+        );
+        assert_eq!(
+            AdminCommand::parse(&["relays", "list"]),
+            Ok(AdminCommand::RelaysList)
+        );
+        assert_eq!(
+            AdminCommand::parse(&["relays", "add", "wss://relay.example.com"]),
+            Ok(AdminCommand::AddRelay(
+                "wss://relay.example.com".to_string()
+            ))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["relays", "add"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["direct-peers", "add", &npub, "10.0.0.1:6272"]),
+            Ok(AdminCommand::AddDirectPeer(
+                peer_key,
+                "10.0.0.1:6272".parse().unwrap()
+            ))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["direct-peers", "add", &npub, "10.0.0.1"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["direct-peers", "remove", &npub]),
+            Ok(AdminCommand::RemoveDirectPeer(peer_key))
+        );
+        assert_eq!(
+            AdminCommand::parse(&[
+                "webhooks",
+                "add",
+                "https://hooks.example.com/cube",
+                "deposit_detected,balance_changed",
+                &npub
+            ]),
+            Ok(AdminCommand::AddWebhook(
+                "https://hooks.example.com/cube".to_string(),
+                vec![
+                    WebhookEventKind::DepositDetected,
+                    WebhookEventKind::BalanceChanged
+                ],
+                vec![peer_key]
+            ))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["webhooks", "add", "https://hooks.example.com", "minted"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["webhooks", "remove", "3"]),
+            Ok(AdminCommand::RemoveWebhook(3))
+        );
+        assert_eq!(
+            AdminCommand::parse(&["api-keys", "add", &npub, "admin"]),
+            Ok(AdminCommand::AddApiKey(peer_key, ApiPermission::Admin))
+        );
+        assert!(matches!(
+            AdminCommand::parse(&["api-keys", "add", &npub, "root"]),
+            Err(AdminError::InvalidArguments(_))
+        ));
+        assert_eq!(
+            AdminCommand::parse(&["api-tokens", "issue", "read", "dashboard"]),
+            Ok(AdminCommand::IssueApiToken(
+                ApiPermission::Read,
+                "dashboard".to_string()
+            ))
+        );
+        assert_eq!(
+            AdminCommand::parse(&["api-tokens", "revoke", "2"]),
+            Ok(AdminCommand::RevokeApiToken(2))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod api_auth_tests {
+    use cube::communicative::api_auth::api_auth::{
+        erase_api_auth, ApiAuth, API_AUTH, CHALLENGE_TTL_SECS, SESSION_TTL_SECS,
+    };
+    use cube::communicative::api_auth::api_identity::ApiIdentity;
+    use cube::communicative::api_auth::api_permission::ApiPermission;
+    use cube::communicative::api_auth::errors::api_auth_error::ApiAuthError;
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::bip322::signed_message::sign_simple;
+    use cube::transmutative::key::KeyHolder;
+
+    #[tokio::test]
+    async fn api_auth_tokens_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+
+        // 2 Erase first the API auth.
+        erase_api_auth(chain);
+
+        let (read_token, admin_token) = {
+            let api_auth: API_AUTH = ApiAuth::new(chain, false)
+                .map_err(|err| format!("Error constructing API auth: {:?}", err))?;
+            let mut _api_auth = api_auth.lock().await;
+
+            // 3 Anonymous requests may read, but not run admin commands.
+            assert_eq!(
+                _api_auth.authorize(None, ApiPermission::Read, 0),
+                Ok(ApiIdentity::Anonymous)
+            );
+            assert_eq!(
+                _api_auth.authorize(None, ApiPermission::Admin, 0),
+                Err(ApiAuthError::Unauthenticated)
+            );
+
+            // 4 Labels must be a single word.
+            assert_eq!(
+                _api_auth
+                    .issue_token("two words", ApiPermission::Read, 0)
+                    .map(|_| ()),
+                Err(ApiAuthError::InvalidLabel)
+            );
+
+            // 5 Issue a read and an admin token.
+            let (read_api_token, read_token) = _api_auth
+                .issue_token("dashboard", ApiPermission::Read, 0)
+                .map_err(|err| err.to_string())?;
+            let (admin_api_token, admin_token) = _api_auth
+                .issue_token("ops", ApiPermission::Admin, 0)
+                .map_err(|err| err.to_string())?;
+            assert_eq!((read_api_token.id, admin_api_token.id), (0, 1));
+
+            // 6 A read token may read but not run admin commands, an admin token may do both.
+            assert_eq!(
+                _api_auth.authorize(Some(&read_token), ApiPermission::Read, 0),
+                Ok(ApiIdentity::Token(0))
+            );
+            assert_eq!(
+                _api_auth.authorize(Some(&read_token), ApiPermission::Admin, 0),
+                Err(ApiAuthError::Forbidden(ApiPermission::Admin))
+            );
+            assert_eq!(
+                _api_auth.authorize(Some(&admin_token), ApiPermission::Admin, 0),
+                Ok(ApiIdentity::Token(1))
+            );
+
+            // 7 Unknown tokens are refused even for reads.
+            assert_eq!(
+                _api_auth.authorize(Some("deadbeef"), ApiPermission::Read, 0),
+                Err(ApiAuthError::InvalidToken)
+            );
+
+            (read_token, admin_token)
+        };
+
+        // 8 Tokens survive a restart, and reads now require authentication.
+        {
+            let api_auth: API_AUTH = ApiAuth::new(chain, true)
+                .map_err(|err| format!("Error constructing API auth: {:?}", err))?;
+            let mut _api_auth = api_auth.lock().await;
+            assert_eq!(
+                _api_auth.authorize(None, ApiPermission::Read, 0),
+                Err(ApiAuthError::Unauthenticated)
+            );
+            assert_eq!(
+                _api_auth.authorize(Some(&read_token), ApiPermission::Read, 0),
+                Ok(ApiIdentity::Token(0))
+            );
+
+            // 9 A revoked token is refused, and token ids keep increasing.
+            assert_eq!(_api_auth.revoke_token(1), Ok(true));
+            assert_eq!(
+                _api_auth.authorize(Some(&admin_token), ApiPermission::Admin, 0),
+                Err(ApiAuthError::InvalidToken)
+            );
+            let (api_token, _) = _api_auth
+                .issue_token("ops", ApiPermission::Admin, 0)
+                .map_err(|err| err.to_string())?;
+            assert_eq!(api_token.id, 2);
+        }
+
+        // 10 Erase the API auth.
+        erase_api_auth(chain);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn api_auth_sessions_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Signet;
+        let key_holder = KeyHolder::new([0x11; 32]).ok_or("Failed to construct the key holder.")?;
+        let account_key = key_holder.secp_public_key_bytes();
+        let now = 1_700_000_000;
+
+        // 2 Erase first the API auth.
+        erase_api_auth(chain);
+
+        {
+            let api_auth: API_AUTH = ApiAuth::new(chain, false)
+                .map_err(|err| format!("Error constructing API auth: {:?}", err))?;
+            let mut _api_auth = api_auth.lock().await;
+
+            // 3 Only keys allowed to log in get a challenge.
+            assert_eq!(
+                _api_auth.challenge(account_key, now).map(|_| ()),
+                Err(ApiAuthError::UnknownKey)
+            );
+            _api_auth
+                .add_key(account_key, ApiPermission::Read, now)
+                .map_err(|err| err.to_string())?;
+
+            // 4 A signature of another message is refused, and consumes the challenge.
+            _api_auth
+                .challenge(account_key, now)
+                .map_err(|err| err.to_string())?;
+            let (_, signature) =
+                sign_simple(&key_holder, chain, b"another message").ok_or("Failed to sign.")?;
+            assert!(matches!(
+                _api_auth.open_session(account_key, &signature, now),
+                Err(ApiAuthError::InvalidSignature(_))
+            ));
+            assert_eq!(
+                _api_auth
+                    .open_session(account_key, &signature, now)
+                    .map(|_| ()),
+                Err(ApiAuthError::NoPendingChallenge)
+            );
+
+            // 5 A challenge signed too late is refused.
+            let challenge = _api_auth
+                .challenge(account_key, now)
+                .map_err(|err| err.to_string())?;
+            let (_, signature) = sign_simple(&key_holder, chain, challenge.message.as_bytes())
+                .ok_or("Failed to sign.")?;
+            assert_eq!(
+                _api_auth
+                    .open_session(account_key, &signature, now + CHALLENGE_TTL_SECS)
+                    .map(|_| ()),
+                Err(ApiAuthError::ChallengeExpired)
+            );
+
+            // 6 Sign the challenge to open a session.
+            let challenge = _api_auth
+                .challenge(account_key, now)
+                .map_err(|err| err.to_string())?;
+            let (_, signature) = sign_simple(&key_holder, chain, challenge.message.as_bytes())
+                .ok_or("Failed to sign.")?;
+            let (session, token) = _api_auth
+                .open_session(account_key, &signature, now)
+                .map_err(|err| err.to_string())?;
+            assert_eq!(session.expires_at, now + SESSION_TTL_SECS);
+
+            // 7 The session follows the current permission of its key.
+            assert_eq!(
+                _api_auth.authorize(Some(&token), ApiPermission::Admin, now),
+                Err(ApiAuthError::Forbidden(ApiPermission::Admin))
+            );
+            _api_auth
+                .add_key(account_key, ApiPermission::Admin, now)
+                .map_err(|err| err.to_string())?;
+            assert_eq!(
+                _api_auth.authorize(Some(&token), ApiPermission::Admin, now),
+                Ok(ApiIdentity::Key(account_key))
+            );
+
+            // 8 Sessions expire.
+            assert_eq!(
+                _api_auth.authorize(Some(&token), ApiPermission::Read, now + SESSION_TTL_SECS),
+                Err(ApiAuthError::SessionExpired)
+            );
+
+            // 9 Removing the key closes its sessions.
+            let challenge = _api_auth
+                .challenge(account_key, now)
+                .map_err(|err| err.to_string())?;
+            let (_, signature) = sign_simple(&key_holder, chain, challenge.message.as_bytes())
+                .ok_or("Failed to sign.")?;
+            let (_, token) = _api_auth
+                .open_session(account_key, &signature, now)
+                .map_err(|err| err.to_string())?;
+            assert_eq!(_api_auth.remove_key(account_key), Ok(true));
+            assert_eq!(
+                _api_auth.authorize(Some(&token), ApiPermission::Read, now),
+                Err(ApiAuthError::InvalidToken)
+            );
+        }
+
+        // 10 Erase the API auth.
+        erase_api_auth(chain);
+
+        Ok(())
+    }
+}
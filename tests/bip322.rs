@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod bip322_tests {
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::bip322::error::Bip322Error;
+    use cube::transmutative::bip322::signed_message;
+    use cube::transmutative::codec::address::address_to_spk;
+    use cube::transmutative::key::KeyHolder;
+
+    /// Secret key of the BIP-322 P2TR test vector.
+    const SECRET_KEY_HEX: &str = "bb051cd0dda0246f33c5a9e133ebd8e7bc02a92af6c41adc131ccd7826c5b004";
+
+    /// P2TR address of the BIP-322 test vector.
+    const ADDRESS: &str = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+
+    #[test]
+    fn bip322_vectors() -> Result<(), String> {
+        // 1 Message hashes.
+        assert_eq!(
+            hex::encode(signed_message::message_hash(b"")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(signed_message::message_hash(b"Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+
+        // 2 The `to_spend` txids of the P2WPKH vector, in display order.
+        let script_pubkey =
+            address_to_spk(Chain::Mainnet, "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l")
+                .ok_or("Failed to decode the address.".to_string())?;
+        let mut txid = signed_message::to_spend_txid(&script_pubkey, b"");
+        txid.reverse();
+        assert_eq!(
+            hex::encode(txid),
+            "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7"
+        );
+        let mut txid = signed_message::to_spend_txid(&script_pubkey, b"Hello World");
+        txid.reverse();
+        assert_eq!(
+            hex::encode(txid),
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b"
+        );
+
+        // 3 The SIGHASH_ALL signature of the P2TR vector verifies.
+        let signature = "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==";
+        assert_eq!(
+            signed_message::verify_simple(Chain::Mainnet, ADDRESS, b"Hello World", signature),
+            Ok(())
+        );
+        assert_eq!(
+            signed_message::verify_simple(Chain::Mainnet, ADDRESS, b"Hello World!", signature),
+            Err(Bip322Error::InvalidSignature)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn bip322_sign_and_verify() -> Result<(), String> {
+        // 1 The key holder of the P2TR vector.
+        let secret_key: [u8; 32] = hex::decode(SECRET_KEY_HEX)
+            .map_err(|e| e.to_string())?
+            .try_into()
+            .map_err(|_| "Invalid secret key length.".to_string())?;
+        let key_holder =
+            KeyHolder::new(secret_key).ok_or("Failed to construct the key holder.".to_string())?;
+        let account_key = key_holder.secp_public_key_bytes();
+
+        // 2 The account address is the address of the vector.
+        assert_eq!(
+            signed_message::account_address(Chain::Mainnet, account_key),
+            Some(ADDRESS.to_string())
+        );
+
+        // 3 Sign and verify, by address and by account key.
+        let (address, signature) =
+            signed_message::sign_simple(&key_holder, Chain::Mainnet, b"alias:satoshi")
+                .ok_or("Failed to sign.".to_string())?;
+        assert_eq!(address, ADDRESS);
+        assert_eq!(
+            signed_message::verify_simple(Chain::Mainnet, &address, b"alias:satoshi", &signature),
+            Ok(())
+        );
+        assert_eq!(
+            signed_message::verify_account_key(
+                Chain::Mainnet,
+                account_key,
+                b"alias:satoshi",
+                &signature
+            ),
+            Ok(())
+        );
+
+        // 4 Another message, another chain or a malformed signature do not verify.
+        assert_eq!(
+            signed_message::verify_simple(Chain::Mainnet, &address, b"alias:hal", &signature),
+            Err(Bip322Error::InvalidSignature)
+        );
+        assert_eq!(
+            signed_message::verify_account_key(
+                Chain::Signet,
+                account_key,
+                b"alias:satoshi",
+                &signature
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            signed_message::verify_simple(Chain::Signet, &address, b"alias:satoshi", &signature),
+            Err(Bip322Error::InvalidAddress)
+        );
+        assert_eq!(
+            signed_message::verify_simple(Chain::Mainnet, &address, b"alias:satoshi", "AAAA"),
+            Err(Bip322Error::MalformedSignature)
+        );
+
+        Ok(())
+    }
+}
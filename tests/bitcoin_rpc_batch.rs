@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod bitcoin_rpc_batch_tests {
+    use bitcoincore_rpc::{jsonrpc, Client};
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_batch::{batch_call, MAX_BATCH_CALLS};
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_transport::BitcoinRPCTransport;
+    use serde_json::{json, Value};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Serves the given number of batches, answering each call with its first parameter doubled, or
+    /// with an error if the parameter is odd. Returns the number of calls in each batch.
+    fn serve_batches(listener: TcpListener, batches: usize) -> Vec<usize> {
+        let mut batch_sizes = Vec::new();
+        for _ in 0..batches {
+            // Read the request.
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse::<usize>().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let requests: Vec<Value> = serde_json::from_slice(&body).unwrap();
+            batch_sizes.push(requests.len());
+
+            // Answer each call, in reverse order.
+            let responses: Vec<Value> = requests
+                .iter()
+                .rev()
+                .map(|request| {
+                    let param = request["params"][0].as_u64().unwrap();
+                    match param % 2 {
+                        0 => json!({ "result": param * 2, "error": null, "id": request["id"] }),
+                        _ => json!({
+                            "result": null,
+                            "error": { "code": -8, "message": "odd" },
+                            "id": request["id"]
+                        }),
+                    }
+                })
+                .collect();
+            let response = Value::Array(responses).to_string();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+        }
+        batch_sizes
+    }
+
+    #[test]
+    fn batch_call_test() -> Result<(), String> {
+        let listener = TcpListener::bind("127.0.0.1:0").map_err(|err| err.to_string())?;
+        let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+        let server = std::thread::spawn(move || serve_batches(listener, 2));
+
+        // Make more calls than fit in a single batch.
+        let transport = BitcoinRPCTransport::new(&format!("http://127.0.0.1:{}", port), "", "")
+            .map_err(|err| err.to_string())?;
+        let client = Client::from_jsonrpc(jsonrpc::Client::with_transport(transport));
+        let params: Vec<Vec<Value>> = (0..MAX_BATCH_CALLS as u64 + 10)
+            .map(|param| vec![param.into()])
+            .collect();
+        let outcomes =
+            batch_call::<u64>(&client, "double", &params).map_err(|err| err.to_string())?;
+
+        // The outcomes are in the order of the calls, failed calls failing on their own.
+        assert_eq!(outcomes.len(), params.len());
+        for (param, outcome) in outcomes.into_iter().enumerate() {
+            match param % 2 {
+                0 => assert_eq!(outcome.ok(), Some(param as u64 * 2)),
+                _ => assert!(outcome.is_err()),
+            }
+        }
+
+        // The calls were split over two batches.
+        let batch_sizes = server.join().map_err(|_| "server panicked".to_string())?;
+        assert_eq!(batch_sizes, vec![MAX_BATCH_CALLS, 10]);
+
+        Ok(())
+    }
+}
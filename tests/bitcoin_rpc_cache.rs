@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod bitcoin_rpc_cache_tests {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::block::{Header, Version};
+    use bitcoin::hashes::Hash;
+    use bitcoin::{transaction, Block, BlockHash, CompactTarget, Transaction, TxMerkleNode};
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cache::{LruMap, RpcCache};
+
+    /// Returns a distinct block for each nonce.
+    fn block(nonce: u32) -> Block {
+        Block {
+            header: Header {
+                version: Version::ONE,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: 0,
+                bits: CompactTarget::from_consensus(0x1d00ffff),
+                nonce,
+            },
+            txdata: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lru_map_test() -> Result<(), String> {
+        let mut lru_map: LruMap<u32, &str> = LruMap::new(2);
+        lru_map.insert(1, "one");
+        lru_map.insert(2, "two");
+
+        // Using an entry spares it from the next eviction.
+        assert_eq!(lru_map.get(&1), Some("one"));
+        lru_map.insert(3, "three");
+        assert_eq!(lru_map.len(), 2);
+        assert_eq!(lru_map.get(&2), None);
+        assert_eq!(lru_map.get(&1), Some("one"));
+        assert_eq!(lru_map.get(&3), Some("three"));
+
+        // A zero capacity disables the map.
+        let mut lru_map: LruMap<u32, &str> = LruMap::new(0);
+        lru_map.insert(1, "one");
+        assert!(lru_map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rpc_cache_test() -> Result<(), String> {
+        let mut rpc_cache = RpcCache::new(4, 4, 4, 6);
+        let (shallow_block, deep_block, unknown_block) = (block(1), block(2), block(3));
+        rpc_cache.observe_block_height(shallow_block.block_hash(), 105);
+        rpc_cache.observe_block_height(deep_block.block_hash(), 100);
+        rpc_cache.observe_tip(105);
+
+        // Only the block buried under six confirmations is cached.
+        for block in [&shallow_block, &deep_block, &unknown_block] {
+            rpc_cache.insert_block(block);
+            rpc_cache.insert_header(&block.header);
+        }
+        assert_eq!(rpc_cache.get_block(&shallow_block.block_hash()), None);
+        assert_eq!(rpc_cache.get_block(&unknown_block.block_hash()), None);
+        assert_eq!(
+            rpc_cache.get_block(&deep_block.block_hash()),
+            Some(deep_block.clone())
+        );
+        assert_eq!(
+            rpc_cache.get_header(&deep_block.block_hash()),
+            Some(deep_block.header)
+        );
+
+        // The shallow block is cached once the chain grows over it.
+        rpc_cache.observe_tip(110);
+        rpc_cache.insert_block(&shallow_block);
+        assert_eq!(
+            rpc_cache.get_block(&shallow_block.block_hash()),
+            Some(shallow_block.clone())
+        );
+
+        // Transactions are cached by txid regardless of confirmations.
+        let transaction = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: Vec::new(),
+            output: Vec::new(),
+        };
+        rpc_cache.insert_transaction(&transaction);
+        assert_eq!(
+            rpc_cache.get_transaction(&transaction.compute_txid()),
+            Some(transaction.clone())
+        );
+
+        // Hits and misses are counted.
+        let json = rpc_cache.json();
+        assert_eq!(json["hits"], 4);
+        assert_eq!(json["misses"], 2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod bitcoin_rpc_cancel_tests {
+    use bitcoincore_rpc::{jsonrpc, Client, RpcApi};
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_cancel::{
+        cancel_rpc_calls, in_flight_calls,
+    };
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCTransportError;
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_transport::BitcoinRPCTransport;
+    use std::net::TcpListener;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn cancel_rpc_calls_test() -> Result<(), String> {
+        // A Bitcoin node that accepts the connection but never answers.
+        let listener = TcpListener::bind("127.0.0.1:0").map_err(|err| err.to_string())?;
+        let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+        let server = std::thread::spawn(move || listener.accept().map(|(stream, _)| stream));
+
+        // Make a call that hangs.
+        let transport = BitcoinRPCTransport::new(&format!("http://127.0.0.1:{}", port), "", "")
+            .map_err(|err| err.to_string())?;
+        let call = std::thread::spawn(move || {
+            let client = Client::from_jsonrpc(jsonrpc::Client::with_transport(transport));
+            client.get_block_count()
+        });
+
+        // Wait for the call to be in flight, then cancel it.
+        let started = Instant::now();
+        while in_flight_calls().lock().unwrap().in_flight() == 0 {
+            if started.elapsed() > Duration::from_secs(5) {
+                return Err("call never got in flight".to_string());
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(cancel_rpc_calls(), 1);
+
+        // The call fails as cancelled well before its timeout.
+        let result = call.join().map_err(|_| "call panicked".to_string())?;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        match result {
+            Err(bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(err))) => {
+                assert!(matches!(
+                    err.downcast_ref::<BitcoinRPCTransportError>(),
+                    Some(BitcoinRPCTransportError::Cancelled)
+                ))
+            }
+            other => return Err(format!("unexpected result: {:?}", other)),
+        }
+        assert_eq!(in_flight_calls().lock().unwrap().in_flight(), 0);
+        server
+            .join()
+            .map_err(|_| "server panicked".to_string())?
+            .map_err(|err| err.to_string())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod bitcoin_rpc_error_tests {
+    use bitcoincore_rpc::jsonrpc;
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::{
+        BitcoinRPCError, BitcoinRPCTransportError,
+    };
+
+    fn rpc_error(code: i32) -> bitcoincore_rpc::Error {
+        bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Rpc(jsonrpc::error::RpcError {
+            code,
+            message: "error".to_string(),
+            data: None,
+        }))
+    }
+
+    fn transport_error(err: BitcoinRPCTransportError) -> bitcoincore_rpc::Error {
+        bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(Box::new(err)))
+    }
+
+    #[test]
+    fn rpc_error_classification_test() -> Result<(), String> {
+        // Rejected credentials.
+        assert_eq!(
+            BitcoinRPCError::from(transport_error(BitcoinRPCTransportError::HttpStatus(401))),
+            BitcoinRPCError::Auth
+        );
+        assert_eq!(
+            BitcoinRPCError::from(bitcoincore_rpc::Error::InvalidCookieFile),
+            BitcoinRPCError::Auth
+        );
+
+        // Connection failures and server errors.
+        let refused = bitcoincore_rpc::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionRefused,
+            "connection refused",
+        ));
+        assert!(matches!(
+            BitcoinRPCError::from(refused),
+            BitcoinRPCError::Network(_)
+        ));
+        assert!(matches!(
+            BitcoinRPCError::from(transport_error(BitcoinRPCTransportError::HttpStatus(502))),
+            BitcoinRPCError::Network(_)
+        ));
+
+        // A full work queue.
+        assert_eq!(
+            BitcoinRPCError::from(transport_error(BitcoinRPCTransportError::HttpStatus(503))),
+            BitcoinRPCError::WorkQueueFull
+        );
+
+        // RPC errors by code.
+        assert!(matches!(
+            BitcoinRPCError::from(rpc_error(-28)),
+            BitcoinRPCError::WarmingUp(_)
+        ));
+        assert!(matches!(
+            BitcoinRPCError::from(rpc_error(-32601)),
+            BitcoinRPCError::MethodNotFound(_)
+        ));
+        assert_eq!(
+            BitcoinRPCError::from(rpc_error(-8)),
+            BitcoinRPCError::Rpc {
+                code: -8,
+                message: "error".to_string()
+            }
+        );
+        assert!(BitcoinRPCError::from(rpc_error(-27)).is_already_in_chain());
+        assert!(!BitcoinRPCError::from(rpc_error(-26)).is_already_in_chain());
+
+        // Malformed responses and cancelled calls.
+        assert!(matches!(
+            BitcoinRPCError::from(transport_error(
+                BitcoinRPCTransportError::MalformedResponse("truncated".to_string())
+            )),
+            BitcoinRPCError::Parse(_)
+        ));
+        assert!(matches!(
+            BitcoinRPCError::from(bitcoincore_rpc::Error::UnexpectedStructure),
+            BitcoinRPCError::Parse(_)
+        ));
+        assert_eq!(
+            BitcoinRPCError::from(transport_error(BitcoinRPCTransportError::Cancelled)),
+            BitcoinRPCError::Cancelled
+        );
+
+        // Only connection failures, a full work queue and the node warming up are transient.
+        assert!(BitcoinRPCError::WorkQueueFull.is_transient());
+        assert!(BitcoinRPCError::WarmingUp("Loading block index".to_string()).is_transient());
+        assert!(!BitcoinRPCError::Auth.is_transient());
+        assert!(!BitcoinRPCError::Cancelled.is_transient());
+        assert!(!BitcoinRPCError::Parse("truncated".to_string()).is_transient());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod bitcoin_rpc_pool_tests {
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_pool::{
+        ConnectionPool, PooledConnection,
+    };
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_transport::BitcoinRPCStream;
+    use std::io::BufReader;
+    use std::net::{TcpListener, TcpStream};
+    use std::time::{Duration, Instant};
+
+    fn connection(listener: &TcpListener) -> PooledConnection {
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        BufReader::new(BitcoinRPCStream::Plain(stream))
+    }
+
+    #[test]
+    fn connection_pool_test() -> Result<(), String> {
+        let listener = TcpListener::bind("127.0.0.1:0").map_err(|err| err.to_string())?;
+        let mut pool = ConnectionPool::new(2, Duration::from_secs(15));
+        let now = Instant::now();
+
+        // Nothing to reuse yet.
+        assert!(pool.checkout("http://127.0.0.1:8332", now).is_none());
+
+        // At most two idle connections are kept per target.
+        for _ in 0..3 {
+            pool.checkin("http://127.0.0.1:8332", connection(&listener), now);
+        }
+        pool.checkin("http://127.0.0.1:18443", connection(&listener), now);
+        assert_eq!(pool.idle_connections(), 3);
+
+        // Idle connections are reused per target.
+        assert!(pool.checkout("http://127.0.0.1:8332", now).is_some());
+        assert_eq!(pool.idle_connections(), 2);
+
+        // Expired connections are closed instead of reused.
+        let later = now + Duration::from_secs(15);
+        assert!(pool.checkout("http://127.0.0.1:8332", later).is_none());
+        assert_eq!(pool.idle_connections(), 1);
+
+        // The metrics count the reuse.
+        assert_eq!(pool.json()["connections_reused"], 1);
+        assert_eq!(pool.json()["idle_connections"], 1);
+
+        // A pool of size zero keeps nothing.
+        let mut pool = ConnectionPool::new(0, Duration::from_secs(15));
+        pool.checkin("http://127.0.0.1:8332", connection(&listener), now);
+        assert_eq!(pool.idle_connections(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod bitcoin_rpc_retry_tests {
+    use bitcoincore_rpc::jsonrpc;
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_error::BitcoinRPCTransportError;
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_retry::{
+        is_method_not_found, is_transient, is_transient_status, with_retry_policy, RetryPolicy,
+    };
+    use std::time::Duration;
+
+    fn io_error() -> bitcoincore_rpc::Error {
+        bitcoincore_rpc::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionRefused,
+            "connection refused",
+        ))
+    }
+
+    fn rpc_error(code: i32) -> bitcoincore_rpc::Error {
+        bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Rpc(jsonrpc::error::RpcError {
+            code,
+            message: "error".to_string(),
+            data: None,
+        }))
+    }
+
+    fn http_error(status_code: u16) -> bitcoincore_rpc::Error {
+        bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(Box::new(
+            BitcoinRPCTransportError::HttpStatus(status_code),
+        )))
+    }
+
+    #[test]
+    fn retry_backoff_test() -> Result<(), String> {
+        let policy = RetryPolicy::new(
+            5,
+            Duration::from_millis(100),
+            Duration::from_millis(1_000),
+            0.5,
+        );
+
+        // The delay doubles with each failed attempt, up to the cap.
+        assert_eq!(policy.backoff(1, 0.0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2, 0.0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4, 0.0), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5, 0.0), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff(u32::MAX, 0.0), Duration::from_millis(1_000));
+
+        // The jitter spreads the delay both ways.
+        assert_eq!(policy.backoff(1, 1.0), Duration::from_millis(150));
+        assert_eq!(policy.backoff(1, -1.0), Duration::from_millis(50));
+
+        // At least one attempt is always made.
+        assert_eq!(
+            RetryPolicy::new(0, Duration::ZERO, Duration::ZERO, 0.0).max_attempts,
+            1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn retry_classification_test() -> Result<(), String> {
+        // Connection failures and the node warming up are transient.
+        assert!(is_transient(&io_error()));
+        assert!(is_transient(&rpc_error(-28)));
+
+        // RPC errors are permanent.
+        assert!(!is_transient(&rpc_error(-8)));
+        assert!(!is_transient(&bitcoincore_rpc::Error::ReturnedError(
+            "error".to_string()
+        )));
+
+        // Server errors are transient, rejected credentials are not.
+        assert!(is_transient_status(503));
+        assert!(is_transient_status(429));
+        assert!(!is_transient_status(401));
+        assert!(!is_transient_status(403));
+        assert!(is_transient(&http_error(503)));
+        assert!(!is_transient(&http_error(401)));
+
+        // Unknown methods are told apart from other RPC errors.
+        assert!(is_method_not_found(&rpc_error(-32601)));
+        assert!(!is_method_not_found(&rpc_error(-8)));
+        assert!(!is_transient(&rpc_error(-32601)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_retry_test() -> Result<(), String> {
+        let policy = RetryPolicy::new(3, Duration::ZERO, Duration::ZERO, 0.0);
+
+        // A transient error is retried until the call succeeds.
+        let mut attempts = 0;
+        let result = with_retry_policy(&policy, || {
+            attempts += 1;
+            match attempts {
+                1 | 2 => Err(io_error()),
+                _ => Ok(attempts),
+            }
+        });
+        assert_eq!(result.ok(), Some(3));
+
+        // A transient error is given up on once out of attempts.
+        let mut attempts = 0;
+        let result: Result<(), _> = with_retry_policy(&policy, || {
+            attempts += 1;
+            Err(io_error())
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+
+        // A permanent error is not retried.
+        let mut attempts = 0;
+        let result: Result<(), _> = with_retry_policy(&policy, || {
+            attempts += 1;
+            Err(rpc_error(-8))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod bitcoin_rpc_timeout_tests {
+    use cube::communicative::rpc::bitcoin_rpc::bitcoin_rpc_timeout::CallTimeouts;
+    use std::time::Duration;
+
+    #[test]
+    fn call_timeouts_test() -> Result<(), String> {
+        // Malformed pairs are skipped.
+        let method_timeouts =
+            CallTimeouts::parse_method_timeouts(" getblock = 60,getrawmempool=30,bad,=5,x=y");
+        assert_eq!(method_timeouts.len(), 2);
+        let timeouts = CallTimeouts::new(Duration::from_secs(15), method_timeouts);
+
+        // Overridden methods and the default.
+        assert_eq!(timeouts.timeout_for("getblock"), Duration::from_secs(60));
+        assert_eq!(
+            timeouts.timeout_for("getblockcount"),
+            Duration::from_secs(15)
+        );
+
+        // A batch takes the longest timeout among its methods.
+        assert_eq!(
+            timeouts.batch_timeout_for(["getblockcount", "getrawmempool"]),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            timeouts.batch_timeout_for(Vec::<&str>::new()),
+            Duration::from_secs(15)
+        );
+
+        // Zero timeouts are raised to a second.
+        let timeouts = CallTimeouts::new(Duration::ZERO, Default::default());
+        assert_eq!(timeouts.timeout_for("getblock"), Duration::from_secs(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod bunker_uri_tests {
+    use cube::communicative::nns::bunker::bunker_uri::BunkerURI;
+
+    const REMOTE_SIGNER_PUBLIC_KEY: &str =
+        "cbecda1c7d37d4c0aa5466243bb4a0018c31bf06d74fa7338290dd3068db4fed";
+
+    #[test]
+    fn bunker_uri_test() -> Result<(), String> {
+        // Relays are percent-decoded and deduplicated.
+        let uri = format!(
+            "bunker://{}?relay=wss%3A%2F%2Frelay.example.com&relay=wss://relay.example.com&relay=wss://relay2.example.com&secret=s3cret",
+            REMOTE_SIGNER_PUBLIC_KEY
+        );
+        let bunker_uri =
+            BunkerURI::parse(&uri).ok_or_else(|| "Failed to parse bunker URI.".to_string())?;
+        assert_eq!(
+            bunker_uri.remote_signer_public_key.to_hex(),
+            REMOTE_SIGNER_PUBLIC_KEY
+        );
+        assert_eq!(
+            bunker_uri.relays,
+            vec![
+                "wss://relay.example.com".to_string(),
+                "wss://relay2.example.com".to_string()
+            ]
+        );
+        assert_eq!(bunker_uri.secret, Some("s3cret".to_string()));
+
+        // The secret is optional.
+        let uri = format!(
+            "bunker://{}?relay=wss://relay.example.com",
+            REMOTE_SIGNER_PUBLIC_KEY
+        );
+        assert_eq!(
+            BunkerURI::parse(&uri).map(|bunker_uri| bunker_uri.secret),
+            Some(None)
+        );
+
+        // A bunker needs a relay, a valid public key and the bunker scheme.
+        assert!(BunkerURI::parse(&format!("bunker://{}", REMOTE_SIGNER_PUBLIC_KEY)).is_none());
+        assert!(BunkerURI::parse("bunker://abcd?relay=wss://relay.example.com").is_none());
+        assert!(BunkerURI::parse(&format!(
+            "nostrconnect://{}?relay=wss://relay.example.com",
+            REMOTE_SIGNER_PUBLIC_KEY
+        ))
+        .is_none());
+        assert!(BunkerURI::parse(&format!(
+            "bunker://{}?relay=https://relay.example.com",
+            REMOTE_SIGNER_PUBLIC_KEY
+        ))
+        .is_none());
+
+        Ok(())
+    }
+}
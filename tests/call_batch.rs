@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod call_batch_tests {
+    use cube::constructive::core_types::method_index::method_index::MethodIndex;
+    use cube::constructive::core_types::ops_budget::ops_budget::OpsBudget;
+    use cube::constructive::core_types::ops_price::ops_price::OpsPrice;
+    use cube::constructive::core_types::target::target::Target;
+    use cube::constructive::entity::account::root_account::registered_and_configured_root_account::registered_and_configured_root_account::RegisteredAndConfiguredRootAccount;
+    use cube::constructive::entity::account::root_account::root_account::RootAccount;
+    use cube::constructive::entity::contract::contract::Contract;
+    use cube::constructive::entry::entry_kinds::call::{
+        call::Call,
+        call_batch::{CallBatch, MAX_CALLS_PER_BATCH},
+        call_batch_error::CallBatchError,
+    };
+
+    /// Returns a call of the account to the method of the contract.
+    fn call(account_key: [u8; 32], method_index: u16) -> Call {
+        Call::new(
+            RootAccount::RegisteredAndConfiguredRootAccount(
+                RegisteredAndConfiguredRootAccount::new(account_key, 0, [0x02; 48]),
+            ),
+            Contract::new([0xc1; 32], 0),
+            MethodIndex::new(method_index),
+            vec![],
+            OpsBudget::new(Some(1_000)),
+            OpsPrice::new(0),
+            Target::new(1),
+        )
+    }
+
+    #[test]
+    fn call_batch_validate_test() {
+        // A batch of distinct calls of one account is well-formed.
+        let call_batch = CallBatch::new(vec![call([0xa1; 32], 0), call([0xa1; 32], 1)]);
+        assert!(call_batch.validate().is_ok());
+        assert_eq!(call_batch.account_key(), Some([0xa1; 32]));
+
+        // An empty batch is not.
+        assert!(matches!(
+            CallBatch::new(vec![]).validate(),
+            Err(CallBatchError::EmptyCallBatchError)
+        ));
+
+        // Nor is a batch over the size limit.
+        let calls = (0..=MAX_CALLS_PER_BATCH as u16)
+            .map(|method_index| call([0xa1; 32], method_index))
+            .collect();
+        assert!(matches!(
+            CallBatch::new(calls).validate(),
+            Err(CallBatchError::CallBatchTooLargeError(len)) if len == MAX_CALLS_PER_BATCH + 1
+        ));
+
+        // Nor is a batch mixing the calls of several accounts.
+        let call_batch = CallBatch::new(vec![call([0xa1; 32], 0), call([0xa2; 32], 1)]);
+        assert!(matches!(
+            call_batch.validate(),
+            Err(CallBatchError::MixedCallerAccountsError(1))
+        ));
+
+        // Nor is a batch repeating a call.
+        let call_batch = CallBatch::new(vec![
+            call([0xa1; 32], 0),
+            call([0xa1; 32], 1),
+            call([0xa1; 32], 0),
+        ]);
+        assert!(matches!(
+            call_batch.validate(),
+            Err(CallBatchError::DuplicateCallError(2))
+        ));
+    }
+
+    #[test]
+    fn call_batch_sighash_test() -> Result<(), String> {
+        let first = call([0xa1; 32], 0);
+        let second = call([0xa1; 32], 1);
+
+        // The sighash commits to the calls and their order.
+        let sighash = CallBatch::new(vec![first.clone(), second.clone()])
+            .sighash()
+            .map_err(|e| format!("{:?}", e))?;
+        let reordered_sighash = CallBatch::new(vec![second.clone(), first.clone()])
+            .sighash()
+            .map_err(|e| format!("{:?}", e))?;
+        let truncated_sighash = CallBatch::new(vec![first.clone()])
+            .sighash()
+            .map_err(|e| format!("{:?}", e))?;
+        assert_ne!(sighash, reordered_sighash);
+        assert_ne!(sighash, truncated_sighash);
+
+        // A batch of a single call is not signed as the call itself.
+        assert_ne!(
+            truncated_sighash,
+            first.sighash().map_err(|e| format!("{:?}", e))?
+        );
+
+        // The batch round-trips through its wire encoding.
+        let call_batch = CallBatch::new(vec![first, second]);
+        let bytes = call_batch
+            .serialize()
+            .ok_or("Failed to serialize the call batch.".to_string())?;
+        assert!(CallBatch::deserialize(&bytes) == Some(call_batch));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod call_simulation_tests {
+    use cube::{
+        constructive::calldata::element_type::CalldataElementType,
+        executive::{
+            executable::{
+                executable::Executable,
+                method::{
+                    limits::MIN_METHOD_OPCODE_COUNT, method_type::MethodType,
+                    program_method::ProgramMethod,
+                },
+            },
+            opcode::{
+                opcode::Opcode,
+                opcodes::{
+                    flow::{op_nop::OP_NOP, op_returnall::OP_RETURNALL},
+                    push::op_true::OP_TRUE,
+                },
+            },
+            stack::stack_item::StackItem,
+            vm::program_execution::{call_simulation::simulate_call, exec_error::ExecutionError},
+        },
+        inscriptive::{
+            fixtures::ledger_fixture::LedgerFixture,
+            params_manager::params_manager::{ParamsManager, PARAMS_MANAGER},
+        },
+        operative::run_args::chain::Chain,
+    };
+
+    // Account key.
+    const ACCOUNT_KEY: [u8; 32] = [0xa1; 32];
+
+    // Contract id.
+    const CONTRACT_ID: [u8; 32] = [0xc1; 32];
+
+    /// Returns a method pushing the given number of true items and returning them all.
+    ///
+    /// The script is padded with leading no-ops up to the minimum method opcode count.
+    fn method(method_name: &str, method_type: MethodType, trues: usize) -> ProgramMethod {
+        let padding = MIN_METHOD_OPCODE_COUNT.saturating_sub(trues + 1);
+        let mut script = vec![Opcode::OP_NOP(OP_NOP); padding];
+        script.extend(vec![Opcode::OP_TRUE(OP_TRUE); trues]);
+        script.push(Opcode::OP_RETURNALL(OP_RETURNALL));
+        ProgramMethod::new(
+            method_name.to_string(),
+            method_type,
+            Vec::<CalldataElementType>::new(),
+            script,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn call_simulation_test() -> Result<(), String> {
+        // 1 Build an in-memory ledger with a contract with a passing, a failing and a read-only
+        // method, and a params manager alongside it.
+        let program = Executable::new(
+            "simulated".to_string(),
+            None,
+            false,
+            vec![
+                method("pass", MethodType::Callable, 1),
+                method("fail", MethodType::Callable, 2),
+                method("view", MethodType::ReadOnly, 2),
+            ],
+        )
+        .map_err(|e| format!("{:?}", e))?;
+        let ledger = LedgerFixture::new()
+            .with_contract(CONTRACT_ID, program, 0)
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let params_manager: PARAMS_MANAGER = ledger
+            .open(|| ParamsManager::new(Chain::Testbed))
+            .map_err(|e| format!("{:?}", e))?;
+        let (registery, coin_manager, state_manager) =
+            (ledger.registery, ledger.coin_manager, ledger.state_manager);
+
+        // 2 A passing call returns its ops, and its fees at the given ops price.
+        let simulated_call = simulate_call(
+            &state_manager,
+            &coin_manager,
+            &registery,
+            &params_manager,
+            ACCOUNT_KEY,
+            CONTRACT_ID,
+            0,
+            vec![],
+            1_000,
+            3,
+            1715619200,
+        )
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(simulated_call.return_items, vec![StackItem::true_item()]);
+        assert!(simulated_call.ops_spent > 0);
+        assert_eq!(
+            simulated_call.fees_spent,
+            simulated_call.ops_spent as u64 * 3
+        );
+
+        // 3 A callable method must end with a single true item.
+        let result = simulate_call(
+            &state_manager,
+            &coin_manager,
+            &registery,
+            &params_manager,
+            ACCOUNT_KEY,
+            CONTRACT_ID,
+            1,
+            vec![],
+            1_000,
+            3,
+            1715619200,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidStackEndingError)
+        ));
+
+        // 4 A read-only method returns its items as-is.
+        let simulated_call = simulate_call(
+            &state_manager,
+            &coin_manager,
+            &registery,
+            &params_manager,
+            ACCOUNT_KEY,
+            CONTRACT_ID,
+            2,
+            vec![],
+            1_000,
+            3,
+            1715619200,
+        )
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(simulated_call.return_items.len(), 2);
+
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 #[cfg(test)]
 mod coin_manager_tests {
-    use cube::inscriptive::coin_manager::coin_manager::{
-        erase_coin_manager, CoinManager, COIN_MANAGER,
+    use cube::inscriptive::coin_manager::coin_manager::{account_balance_leaf, COIN_MANAGER};
+    use cube::inscriptive::fixtures::coin_manager_fixture::CoinManagerFixture;
+    use cube::transmutative::merkle::{
+        merkle_root, verify_adjacent_merkle_proofs, verify_first_merkle_proof,
+        verify_last_merkle_proof, verify_merkle_proof,
     };
-    use cube::operative::run_args::chain::Chain;
 
     // First account key.
     const ACCOUNT_KEY_1: [u8; 32] = [
@@ -42,672 +44,874 @@ mod coin_manager_tests {
 
     #[tokio::test]
     async fn coin_manager_tests() -> Result<(), String> {
-        // 1 Set the chain for local tests.
-        let chain = Chain::Testbed;
+        // 1 Construct an empty coin manager.
+        let coin_manager: COIN_MANAGER = CoinManagerFixture::new()
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
 
-        // 2 Erase first the coin manager.
-        erase_coin_manager(chain);
-
-        // 3 Construct the coin manager.
-        let coin_manager: COIN_MANAGER = CoinManager::new(chain).unwrap();
-
-        // 4 Registering an account with special keys should fail.
+        // 2 Registering an account with special keys should fail.
         {
-            // 4.1 Special db key 1.
+            // 2.1 Special db key 1.
             let special_db_key_1 = [0x00; 32];
-            // 4.2 Special db key 2.
+            // 2.2 Special db key 2.
             let special_db_key_2 = [0x01; 32];
 
-            // 4.3 Lock the coin manager.
+            // 2.3 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 4.4 Register the account.
+            // 2.4 Register the account.
             let result_1 = _coin_manager.register_account(special_db_key_1, 0);
             let result_2 = _coin_manager.register_account(special_db_key_2, 0);
 
-            // 4.5 The result should be an error.
+            // 2.5 The result should be an error.
             assert!(result_1.is_err());
             assert!(result_2.is_err());
         }
 
-        // 5 First account operations.
+        // 3 First account operations.
         {
-            // 5.1 Lock the coin manager.
+            // 3.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 5.2 Register the account.
+            // 3.2 Register the account.
             let result = _coin_manager.register_account(ACCOUNT_KEY_1, 0);
 
-            // 5.3 The result should be ok.
+            // 3.3 The result should be ok.
             assert!(result.is_ok());
 
-            // 5.4 Check if the account is registered.
+            // 3.4 Check if the account is registered.
             let is_registered = _coin_manager.is_account_registered(ACCOUNT_KEY_1);
 
-            // 5.5 The account should not be registered yet because changes are not applied yet.
+            // 3.5 The account should not be registered yet because changes are not applied yet.
             assert_eq!(is_registered, false);
 
-            // 5.6 Apply the changes.
+            // 3.6 Apply the changes.
             let result = _coin_manager.apply_changes();
 
-            // 5.7 The result should be ok.
+            // 3.7 The result should be ok.
             assert!(result.is_ok());
 
-            // 5.9 Flush the delta.
+            // 3.9 Flush the delta.
             _coin_manager.flush_delta();
 
-            // 5.11 Check if the account is registered.
+            // 3.11 Check if the account is registered.
             let is_registered = _coin_manager.is_account_registered(ACCOUNT_KEY_1);
             assert_eq!(is_registered, true);
 
-            // 5.12 Check if the account balance is 0.
+            // 3.12 Check if the account balance is 0.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(0));
 
-            // 5.13 Try to register the account again.
+            // 3.13 Try to register the account again.
             let result = _coin_manager.register_account(ACCOUNT_KEY_1, 0);
 
-            // 5.14 The result should be an error.
+            // 3.14 The result should be an error.
             assert!(result.is_err());
 
-            // 5.15 Account balance up.
+            // 3.15 Account balance up.
             let result = _coin_manager.account_balance_up(ACCOUNT_KEY_1, 1000);
 
-            // 5.16 The result should be ok.
+            // 3.16 The result should be ok.
             assert!(result.is_ok());
 
-            // 5.17 Check if the account balance is 1000.
+            // 3.17 Check if the account balance is 1000.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(1000));
 
-            // 5.18 Flush the delta without applying the changes.
+            // 3.18 Flush the delta without applying the changes.
             _coin_manager.flush_delta();
 
-            // 5.19 The account balance should be back to 0.
+            // 3.19 The account balance should be back to 0.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(0));
 
-            // 5.20 Account balance up again.
+            // 3.20 Account balance up again.
             let result = _coin_manager.account_balance_up(ACCOUNT_KEY_1, 5000);
 
-            // 5.21 The result should be ok.
+            // 3.21 The result should be ok.
             assert!(result.is_ok());
 
-            // 5.22 Check if the account balance is 5000.
+            // 3.22 Check if the account balance is 5000.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(5000));
 
-            // 5.23 This time apply changes.
+            // 3.23 This time apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 5.24 Flush the delta.
+            // 3.24 Flush the delta.
             _coin_manager.flush_delta();
 
-            // 5.25 Check if the account balance is still 5000.
+            // 3.25 Check if the account balance is still 5000.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(5000));
 
-            // 5.26 Account balance down.
+            // 3.26 Account balance down.
             let result = _coin_manager.account_balance_down(ACCOUNT_KEY_1, 2000);
 
-            // 5.27 The result should be ok.
+            // 3.27 The result should be ok.
             assert!(result.is_ok());
 
-            // 5.28 Check if the account balance is 3000.
+            // 3.28 Check if the account balance is 3000.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(3000));
 
-            // 5.29 Balance up again.
+            // 3.29 Balance up again.
             let result = _coin_manager.account_balance_up(ACCOUNT_KEY_1, 250);
             assert!(result.is_ok());
 
-            // 5.30 Check if the account balance is 3250.
+            // 3.30 Check if the account balance is 3250.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_1);
             assert_eq!(account_balance, Some(3250));
 
-            // 5.31 Account balance down excessively. This should fail.
+            // 3.31 Account balance down excessively. This should fail.
             let result = _coin_manager.account_balance_down(ACCOUNT_KEY_1, 3251);
             assert!(result.is_err());
 
-            // 5.32 Apply changes.
+            // 3.32 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 5.33 Flush the delta.
+            // 3.33 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 6 Second account operations.
+        // 4 Second account operations.
         {
-            // 6.1 Lock the coin manager.
+            // 4.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 6.2 Register the account with initial balance 500.
+            // 4.2 Register the account with initial balance 500.
             let result = _coin_manager.register_account(ACCOUNT_KEY_2, 500);
             assert!(result.is_ok());
 
-            // 6.3 Apply changes.
+            // 4.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 6.4 Flush the delta.
+            // 4.4 Flush the delta.
             _coin_manager.flush_delta();
 
-            // 6.5 Check if the account is registered.
+            // 4.5 Check if the account is registered.
             let is_registered = _coin_manager.is_account_registered(ACCOUNT_KEY_2);
             assert_eq!(is_registered, true);
 
-            // 6.6 Check if the account balance is 500.
+            // 4.6 Check if the account balance is 500.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_2);
             assert_eq!(account_balance, Some(500));
 
-            // 6.7 Account balance down.
+            // 4.7 Account balance down.
             let result = _coin_manager.account_balance_down(ACCOUNT_KEY_2, 100);
             assert!(result.is_ok());
 
-            // 6.8 Check if the account balance is 400.
+            // 4.8 Check if the account balance is 400.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_2);
             assert_eq!(account_balance, Some(400));
 
-            // 6.9 Balance down again.
+            // 4.9 Balance down again.
             let result = _coin_manager.account_balance_down(ACCOUNT_KEY_2, 50);
             assert!(result.is_ok());
 
-            // 6.10 Check if the account balance is 350.
+            // 4.10 Check if the account balance is 350.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_2);
             assert_eq!(account_balance, Some(350));
 
-            // 6.11 Balance up.
+            // 4.11 Balance up.
             let result = _coin_manager.account_balance_up(ACCOUNT_KEY_2, 1000);
             assert!(result.is_ok());
 
-            // 6.12 Check if the account balance is 1350.
+            // 4.12 Check if the account balance is 1350.
             let account_balance = _coin_manager.get_account_balance(ACCOUNT_KEY_2);
             assert_eq!(account_balance, Some(1350));
 
-            // 6.13 Apply changes.
+            // 4.13 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 6.14 Flush the delta.
+            // 4.14 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 7 Third account operations.
+        // 5 Third account operations.
         {
-            // 7.1 Lock the coin manager.
+            // 5.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 7.2 Register the account.
+            // 5.2 Register the account.
             let result = _coin_manager.register_account(ACCOUNT_KEY_3, 0);
             assert!(result.is_ok());
 
-            // 7.3 Apply changes.
+            // 5.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 7.4 Flush the delta.
+            // 5.4 Flush the delta.
             _coin_manager.flush_delta();
 
-            // 7.5 Check if the account is registered.
+            // 5.5 Check if the account is registered.
             let is_registered = _coin_manager.is_account_registered(ACCOUNT_KEY_3);
             assert_eq!(is_registered, true);
         }
 
-        // 8 Register a contract.
+        // 6 Register a contract.
         {
-            // 8.2 Lock the coin manager.
+            // 6.2 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 8.3 Register the contract with initial balance 100,000.
+            // 6.3 Register the contract with initial balance 100,000.
             let result = _coin_manager.register_contract(CONTRACT_ID_1, 100_000);
             assert!(result.is_ok());
 
-            // 8.7 Check if the contract is registered. Should fail since changes are not applied yet.
+            // 6.7 Check if the contract is registered. Should fail since changes are not applied yet.
             let is_registered = _coin_manager.is_contract_registered(CONTRACT_ID_1);
             assert_eq!(is_registered, false);
 
-            // 8.8 Apply changes.
+            // 6.8 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 8.9 Flush the delta.
+            // 6.9 Flush the delta.
             _coin_manager.flush_delta();
 
-            // 8.10 Check if the contract is registered.
+            // 6.10 Check if the contract is registered.
             let is_registered = _coin_manager.is_contract_registered(CONTRACT_ID_1);
             assert_eq!(is_registered, true);
         }
 
-        // 9 Contract balance updates.
+        // 7 Contract balance updates.
         {
-            // 9.1 Lock the coin manager.
+            // 7.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 9.2 Contract balance up by 5000.
+            // 7.2 Contract balance up by 5000.
             let result = _coin_manager.contract_balance_up(CONTRACT_ID_1, 5000);
             assert!(result.is_ok());
 
-            // 9.3 Check if the contract balance is 105,000.
+            // 7.3 Check if the contract balance is 105,000.
             let contract_balance = _coin_manager.get_contract_balance(CONTRACT_ID_1);
             assert_eq!(contract_balance, Some(105_000));
 
-            // 9.4 Contract balance down by 2000.
+            // 7.4 Contract balance down by 2000.
             let result = _coin_manager.contract_balance_down(CONTRACT_ID_1, 2000);
             assert!(result.is_ok());
 
-            // 9.5 Check if the contract balance is 103,000.
+            // 7.5 Check if the contract balance is 103,000.
             let contract_balance = _coin_manager.get_contract_balance(CONTRACT_ID_1);
             assert_eq!(contract_balance, Some(103_000));
 
-            // 9.6 Excessive contract balance down. This should fail.
+            // 7.6 Excessive contract balance down. This should fail.
             let result = _coin_manager.contract_balance_down(CONTRACT_ID_1, 103_001);
             assert!(result.is_err());
 
-            // 9.7 Apply changes.
+            // 7.7 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 9.8 Flush the delta.
+            // 7.8 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 10 Allocate the first account in the contract shadow space.
+        // 8 Allocate the first account in the contract shadow space.
         {
-            // 10.1 Lock the coin manager.
+            // 8.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 10.2 Allocate the first account in the contract shadow space.
+            // 8.2 Allocate the first account in the contract shadow space.
             let result = _coin_manager.contract_shadow_alloc_account(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert!(result.is_ok());
 
-            // 10.3 Get alloc value in sati-satoshis. Initially it should be zero.
+            // 8.3 Get alloc value in sati-satoshis. Initially it should be zero.
             let alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(alloc_value, Some(0));
 
-            // 10.4 Try to allocate the account again. This should fail.
+            // 8.4 Try to allocate the account again. This should fail.
             let result = _coin_manager.contract_shadow_alloc_account(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert!(result.is_err());
 
-            // 10.5 Apply changes.
+            // 8.5 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 10.6 Flush the delta.
+            // 8.6 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 11 Try to get alloc value from a non-allocated account.
+        // 9 Try to get alloc value from a non-allocated account.
         {
-            // 11.1 Lock the coin manager.
+            // 9.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 11.2 Get alloc value in sati-satoshis. Should be none.
+            // 9.2 Get alloc value in sati-satoshis. Should be none.
             let alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(alloc_value, None);
         }
 
-        // 12 Try to read balance of a non-registered contract.
+        // 10 Try to read balance of a non-registered contract.
         {
-            // 12.1 Lock the coin manager.
+            // 10.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 12.2 Get contract balance. Should be none.
+            // 10.2 Get contract balance. Should be none.
             let contract_balance = _coin_manager.get_contract_balance(CONTRACT_ID_2);
             assert_eq!(contract_balance, None);
         }
 
-        // 13 Shadow up.
+        // 11 Shadow up.
         {
-            // 13.1 Lock the coin manager.
+            // 11.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 13.2 Shadow up by 1000.
+            // 11.2 Shadow up by 1000.
             let result = _coin_manager.shadow_up(CONTRACT_ID_1, ACCOUNT_KEY_1, 1000);
             assert!(result.is_ok());
 
-            // 13.3 Check if shadow alloc value is 1000.
+            // 11.3 Check if shadow alloc value is 1000.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value, Some(1000));
 
-            // 13.4 Apply changes.
+            // 11.4 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 13.5 Flush the delta.
+            // 11.5 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 14 Shadow down.
+        // 12 Shadow down.
         {
-            // 14.1 Lock the coin manager.
+            // 12.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 14.2 Shadow down by 500.
+            // 12.2 Shadow down by 500.
             let result = _coin_manager.shadow_down(CONTRACT_ID_1, ACCOUNT_KEY_1, 500);
             assert!(result.is_ok());
 
-            // 13.5 Check if shadow alloc value is 500.
+            // 11.5 Check if shadow alloc value is 500.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value, Some(500));
 
-            // 13.6 Apply changes.
+            // 11.6 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 13.7 Flush the delta.
+            // 11.7 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 15 Shadow up all.
+        // 13 Shadow up all.
         {
-            // 15.1 Lock the coin manager.
+            // 13.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 15.2 Shadow up all by 25.
+            // 13.2 Shadow up all by 25.
             let result = _coin_manager.shadow_up_all(CONTRACT_ID_1, 25);
             assert!(result.is_ok());
 
-            // 15.3 Check if shadow alloc value is 525.
+            // 13.3 Check if shadow alloc value is 525.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value, Some(525));
 
-            // 15.4 Apply changes.
+            // 13.4 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 15.5 Flush the delta.
+            // 13.5 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 16 Shadow down all.
+        // 14 Shadow down all.
         {
-            // 16.1 Lock the coin manager.
+            // 14.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 16.2 Shadow down all by 100.
+            // 14.2 Shadow down all by 100.
             let result = _coin_manager.shadow_down_all(CONTRACT_ID_1, 100);
             assert!(result.is_ok());
 
-            // 16.3 Check if shadow alloc value is 425.
+            // 14.3 Check if shadow alloc value is 425.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value, Some(425));
 
-            // 16.4 Apply changes.
+            // 14.4 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 16.5 Flush the delta.
+            // 14.5 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 17 Allocate the second account in the contract shadow space.
+        // 15 Allocate the second account in the contract shadow space.
         {
-            // 17.1 Lock the coin manager.
+            // 15.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 17.2 Allocate the second account in the contract shadow space.
+            // 15.2 Allocate the second account in the contract shadow space.
             let result = _coin_manager.contract_shadow_alloc_account(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert!(result.is_ok());
 
-            // 17.3 Get alloc value in sati-satoshis. Initially it should be zero.
+            // 15.3 Get alloc value in sati-satoshis. Initially it should be zero.
             let alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(alloc_value, Some(0));
 
-            // 17.4 Apply changes.
+            // 15.4 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 17.5 Flush the delta.
+            // 15.5 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 18 Shadow up all.
+        // 16 Shadow up all.
         {
-            // 18.1 Lock the coin manager.
+            // 16.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 18.2 Shadow up all by 25.
+            // 16.2 Shadow up all by 25.
             let result = _coin_manager.shadow_up_all(CONTRACT_ID_1, 100);
             assert!(result.is_ok());
 
-            // 18.3 First account shadow alloc value should be 525.
+            // 16.3 First account shadow alloc value should be 525.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value, Some(525));
 
-            // 18.4 Second account shadow alloc value should remain zero.
+            // 16.4 Second account shadow alloc value should remain zero.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value, Some(0));
 
-            // 18.5 Apply changes.
+            // 16.5 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 18.6 Flush the delta.
+            // 16.6 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 19 Shadow down all.
+        // 17 Shadow down all.
         {
-            // 19.1 Lock the coin manager.
+            // 17.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 19.2 Shadow down all by 50.
+            // 17.2 Shadow down all by 50.
             let result = _coin_manager.shadow_down_all(CONTRACT_ID_1, 1);
             assert!(result.is_ok());
 
-            // 19.3 First account shadow alloc value should be 524.
+            // 17.3 First account shadow alloc value should be 524.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value, Some(524));
 
-            // 19.4 Second account shadow alloc value should remain zero.
+            // 17.4 Second account shadow alloc value should remain zero.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value, Some(0));
 
-            // 19.5 Apply changes.
+            // 17.5 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 19.6 Flush the delta.
+            // 17.6 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 20 Shadow up second account.
+        // 18 Shadow up second account.
         {
-            // 20.1 Lock the coin manager.
+            // 18.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 20.2 Shadow up second account by 5.
+            // 18.2 Shadow up second account by 5.
             let result = _coin_manager.shadow_up(CONTRACT_ID_1, ACCOUNT_KEY_2, 5);
             assert!(result.is_ok());
 
-            // 20.3 Check if shadow alloc value is 5.
+            // 18.3 Check if shadow alloc value is 5.
             let shadow_alloc_value =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value, Some(5));
 
-            // 20.4 Apply changes.
+            // 18.4 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 20.5 Flush the delta.
+            // 18.5 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 21 Shadow up all.
+        // 19 Shadow up all.
         {
-            // 21.1 Lock the coin manager.
+            // 19.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 21.2 Shadow up all by 100.
+            // 19.2 Shadow up all by 100.
             let result = _coin_manager.shadow_up_all(CONTRACT_ID_1, 100);
             assert!(result.is_ok());
 
-            // 21.3 Apply changes.
+            // 19.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 21.4 Flush the delta.
+            // 19.4 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 22 Proportioning checks.
+        // 20 Proportioning checks.
         {
-            // 22.1 Lock the coin manager.
+            // 20.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 22.2 Get shadow alloc value of first account in sati-satoshis.
+            // 20.2 Get shadow alloc value of first account in sati-satoshis.
             let shadow_alloc_value_in_sati_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value_in_sati_satoshis, Some(62305482041));
 
-            // 22.3 Get shadow alloc value of first account in satoshis.
+            // 20.3 Get shadow alloc value of first account in satoshis.
             let shadow_alloc_value_in_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value_in_satoshis, Some(623)); // Proportionally increased by a little over than 99 satoshis.
 
-            // 22.4 Get shadow alloc value of second account in sati-satoshis.
+            // 20.4 Get shadow alloc value of second account in sati-satoshis.
             let shadow_alloc_value_in_sati_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value_in_sati_satoshis, Some(594517958));
 
-            // 22.5 Get shadow alloc value of second account in satoshis.
+            // 20.5 Get shadow alloc value of second account in satoshis.
             let shadow_alloc_value_in_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value_in_satoshis, Some(5)); // Proportionally increased by slighly less than 1 satoshi.
         }
 
-        // 23 Shadow up all again.
+        // 21 Shadow up all again.
         {
-            // 23.1 Lock the coin manager.
+            // 21.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 23.2 Shadow up all by 1000.
+            // 21.2 Shadow up all by 1000.
             let result = _coin_manager.shadow_up_all(CONTRACT_ID_1, 1000);
             assert!(result.is_ok());
 
-            // 23.3 Apply changes.
+            // 21.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 23.4 Flush the delta.
+            // 21.4 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 24 Proportioning checks.
+        // 22 Proportioning checks.
         {
-            // 24.1 Lock the coin manager.
+            // 22.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 24.2 Get shadow alloc value of first account in sati-satoshis.
+            // 22.2 Get shadow alloc value of first account in sati-satoshis.
             let shadow_alloc_value_in_sati_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value_in_sati_satoshis, Some(161360302455));
 
-            // 24.3 Get shadow alloc value of first account in satoshis.
+            // 22.3 Get shadow alloc value of first account in satoshis.
             let shadow_alloc_value_in_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_value_in_satoshis, Some(1613)); // Proportionally increased by 990.
 
-            // 24.4 Get shadow alloc value of second account in sati-satoshis.
+            // 22.4 Get shadow alloc value of second account in sati-satoshis.
             let shadow_alloc_value_in_sati_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_sati_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value_in_sati_satoshis, Some(1539697541));
 
-            // 24.5 Get shadow alloc value of second account in satoshis.
+            // 22.5 Get shadow alloc value of second account in satoshis.
             let shadow_alloc_value_in_satoshis =
                 _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_1, ACCOUNT_KEY_2);
             assert_eq!(shadow_alloc_value_in_satoshis, Some(15)); // Proportionally increased by 10.
         }
 
-        // 25 Register the second contract.
+        // 23 Register the second contract.
         {
-            // 25.1 Lock the coin manager.
+            // 23.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 25.2 Register the seconds contract.
+            // 23.2 Register the seconds contract.
             let result = _coin_manager.register_contract(CONTRACT_ID_2, 10_000);
             assert!(result.is_ok());
 
-            // 25.3 Apply changes.
+            // 23.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 25.4 Flush the delta.
+            // 23.4 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 26 Allocate the first account in the second contract shadow space.
+        // 24 Allocate the first account in the second contract shadow space.
         {
-            // 26.1 Lock the coin manager.
+            // 24.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 26.2 Allocate the first account in the second contract shadow space.
+            // 24.2 Allocate the first account in the second contract shadow space.
             let result = _coin_manager.contract_shadow_alloc_account(CONTRACT_ID_2, ACCOUNT_KEY_1);
             assert!(result.is_ok());
 
-            // 26.3 Apply changes.
+            // 24.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 26.4 Flush the delta.
+            // 24.4 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 27 Check shadow alloc overall sum of the first contract.
+        // 25 Check shadow alloc overall sum of the first contract.
         {
-            // 27.1 Lock the coin manager.
+            // 25.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 27.2 Get shadow alloc overall sum of the first contract.
+            // 25.2 Get shadow alloc overall sum of the first contract.
             let shadow_alloc_overall_sum =
                 _coin_manager.get_account_global_shadow_allocs_sum_in_satoshis(ACCOUNT_KEY_1);
 
             assert_eq!(shadow_alloc_overall_sum, Some(1613));
         }
 
-        // 28 Shadow up first account in second contract.
+        // 26 Shadow up first account in second contract.
         {
-            // 28.1 Lock the coin manager.
+            // 26.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 28.2 Shadow up first account in second contract by 3.
+            // 26.2 Shadow up first account in second contract by 3.
             let result = _coin_manager.shadow_up(CONTRACT_ID_2, ACCOUNT_KEY_1, 3);
             assert!(result.is_ok());
 
-            // 28.3 Apply changes.
+            // 26.3 Apply changes.
             let result = _coin_manager.apply_changes();
             assert!(result.is_ok());
 
-            // 28.4 Flush the delta.
+            // 26.4 Flush the delta.
             _coin_manager.flush_delta();
         }
 
-        // 29 Check (again) shadow alloc overall sum of the first contract.
+        // 27 Check (again) shadow alloc overall sum of the first contract.
         {
-            // 29.1 Lock the coin manager.
+            // 27.1 Lock the coin manager.
             let mut _coin_manager = coin_manager.lock().await;
 
-            // 29.2 Get shadow alloc overall sum of the first contract.
+            // 27.2 Get shadow alloc overall sum of the first contract.
             let shadow_alloc_overall_sum =
                 _coin_manager.get_account_global_shadow_allocs_sum_in_satoshis(ACCOUNT_KEY_1);
             assert_eq!(shadow_alloc_overall_sum, Some(1616)); // Has increased by 3.
         }
 
+        // 28 Shadow space reads see the uncommitted changes of the same execution.
+        {
+            // 28.1 Lock the coin manager.
+            let mut _coin_manager = coin_manager.lock().await;
+
+            // 28.2 Shadow up first account in second contract by 7, without applying changes.
+            let result = _coin_manager.shadow_up(CONTRACT_ID_2, ACCOUNT_KEY_1, 7);
+            assert!(result.is_ok());
+
+            // 28.3 Allocate the second account in the second contract shadow space.
+            let result = _coin_manager.contract_shadow_alloc_account(CONTRACT_ID_2, ACCOUNT_KEY_2);
+            assert!(result.is_ok());
+
+            // 28.4 Check the number of allocs and the allocs sum of the second contract.
+            assert_eq!(
+                _coin_manager.get_contract_num_shadow_allocs(CONTRACT_ID_2),
+                Some(2)
+            );
+            assert_eq!(
+                _coin_manager.get_contract_shadow_allocs_sum_in_satoshis(CONTRACT_ID_2),
+                Some(10)
+            );
+
+            // 28.5 Shadow up all accounts in second contract by 10, without applying changes.
+            let result = _coin_manager.shadow_up_all(CONTRACT_ID_2, 10);
+            assert!(result.is_ok());
+
+            // 28.6 Check the alloc values, with the deferred proportional change applied.
+            assert_eq!(
+                _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_2, ACCOUNT_KEY_1),
+                Some(20)
+            );
+            assert_eq!(
+                _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_2, ACCOUNT_KEY_2),
+                Some(0)
+            );
+            assert_eq!(
+                _coin_manager.get_contract_shadow_allocs_sum_in_satoshis(CONTRACT_ID_2),
+                Some(20)
+            );
+
+            // 28.7 Flush the delta, discarding the changes.
+            _coin_manager.flush_delta();
+        }
+
+        // 29 Shadow space reads are back to the committed state.
+        {
+            // 29.1 Lock the coin manager.
+            let mut _coin_manager = coin_manager.lock().await;
+
+            // 29.2 Check the second contract shadow space.
+            assert_eq!(
+                _coin_manager.get_contract_num_shadow_allocs(CONTRACT_ID_2),
+                Some(1)
+            );
+            assert_eq!(
+                _coin_manager.get_contract_shadow_allocs_sum_in_satoshis(CONTRACT_ID_2),
+                Some(3)
+            );
+            assert_eq!(
+                _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_2, ACCOUNT_KEY_1),
+                Some(3)
+            );
+            assert_eq!(
+                _coin_manager.get_shadow_alloc_value_in_satoshis(CONTRACT_ID_2, ACCOUNT_KEY_2),
+                None
+            );
+        }
+
         //println!("Coin manager y: {}", coin_manager.lock().await.json());
 
         Ok(())
     }
+
+    /// Returns the account balances state root built from scratch.
+    fn expected_state_root(mut accounts: Vec<([u8; 32], u64)>) -> [u8; 32] {
+        accounts.sort_by(|a, b| a.0.cmp(&b.0));
+        let leaves: Vec<[u8; 32]> = accounts
+            .into_iter()
+            .map(|(account_key, balance)| account_balance_leaf(account_key, balance))
+            .collect();
+        merkle_root(&leaves)
+    }
+
+    #[tokio::test]
+    async fn account_balances_tree_test() -> Result<(), String> {
+        // 1 Construct the coin manager with two accounts.
+        let coin_manager: COIN_MANAGER = CoinManagerFixture::new()
+            .with_account(ACCOUNT_KEY_1, 1_000)
+            .with_account(ACCOUNT_KEY_2, 500)
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let mut _coin_manager = coin_manager.lock().await;
+
+        // 1.1 The root commits to both balances.
+        assert_eq!(
+            _coin_manager.account_balances_state_root(),
+            expected_state_root(vec![(ACCOUNT_KEY_1, 1_000), (ACCOUNT_KEY_2, 500)])
+        );
+
+        // 2 Update a balance.
+        _coin_manager
+            .account_balance_up(ACCOUNT_KEY_2, 250)
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 2.1 The root only commits to permanent balances.
+        assert_eq!(
+            _coin_manager.account_balances_state_root(),
+            expected_state_root(vec![(ACCOUNT_KEY_1, 1_000), (ACCOUNT_KEY_2, 500)])
+        );
+
+        // 2.2 Once applied, the root commits to the updated balance.
+        _coin_manager
+            .apply_changes()
+            .map_err(|e| format!("{:?}", e))?;
+        _coin_manager.flush_delta();
+        let state_root = _coin_manager.account_balances_state_root();
+        assert_eq!(
+            state_root,
+            expected_state_root(vec![(ACCOUNT_KEY_1, 1_000), (ACCOUNT_KEY_2, 750)])
+        );
+
+        // 2.3 The proof of the updated balance verifies against the root.
+        let (balance, proof) = _coin_manager
+            .account_balance_proof(ACCOUNT_KEY_2)
+            .ok_or("Proof not found.".to_string())?;
+        assert_eq!(balance, 750);
+        assert!(verify_merkle_proof(
+            account_balance_leaf(ACCOUNT_KEY_2, balance),
+            &proof,
+            state_root
+        ));
+
+        // 3 Register a new account, shifting the leaf order.
+        _coin_manager
+            .register_account(ACCOUNT_KEY_3, 100)
+            .map_err(|e| format!("{:?}", e))?;
+        _coin_manager
+            .apply_changes()
+            .map_err(|e| format!("{:?}", e))?;
+        _coin_manager.flush_delta();
+
+        // 3.1 The root commits to all three balances.
+        assert_eq!(
+            _coin_manager.account_balances_state_root(),
+            expected_state_root(vec![
+                (ACCOUNT_KEY_1, 1_000),
+                (ACCOUNT_KEY_2, 750),
+                (ACCOUNT_KEY_3, 100)
+            ])
+        );
+
+        // 3.2 Unknown accounts have no proof.
+        assert!(_coin_manager.account_balance_proof([0x02; 32]).is_none());
+
+        // 4 Registered accounts have no non-membership proof.
+        assert!(_coin_manager
+            .account_balance_non_membership_proof(ACCOUNT_KEY_3)
+            .is_none());
+
+        // 4.1 An account sorting before every leaf is proven absent by the first leaf.
+        let state_root = _coin_manager.account_balances_state_root();
+        let (left, right) = _coin_manager
+            .account_balance_non_membership_proof([0x02; 32])
+            .ok_or("Non-membership proof not found.".to_string())?;
+        assert!(left.is_none());
+        let (right_key, right_balance, right_proof) =
+            right.ok_or("Right neighbor not found.".to_string())?;
+        assert_eq!((right_key, right_balance), (ACCOUNT_KEY_2, 750));
+        assert!(verify_first_merkle_proof(
+            account_balance_leaf(right_key, right_balance),
+            &right_proof,
+            state_root
+        ));
+
+        // 4.2 An account sorting between two leaves is proven absent by both.
+        let (left, right) = _coin_manager
+            .account_balance_non_membership_proof([0x80; 32])
+            .ok_or("Non-membership proof not found.".to_string())?;
+        let (left_key, left_balance, left_proof) =
+            left.ok_or("Left neighbor not found.".to_string())?;
+        let (right_key, right_balance, right_proof) =
+            right.ok_or("Right neighbor not found.".to_string())?;
+        assert_eq!((left_key, right_key), (ACCOUNT_KEY_2, ACCOUNT_KEY_3));
+        assert!(verify_adjacent_merkle_proofs(
+            account_balance_leaf(left_key, left_balance),
+            &left_proof,
+            account_balance_leaf(right_key, right_balance),
+            &right_proof,
+            state_root
+        ));
+
+        // 4.3 An account sorting after every leaf is proven absent by the last leaf.
+        let (left, right) = _coin_manager
+            .account_balance_non_membership_proof([0xff; 32])
+            .ok_or("Non-membership proof not found.".to_string())?;
+        assert!(right.is_none());
+        let (left_key, left_balance, left_proof) =
+            left.ok_or("Left neighbor not found.".to_string())?;
+        assert_eq!((left_key, left_balance), (ACCOUNT_KEY_1, 1_000));
+        assert!(verify_last_merkle_proof(
+            account_balance_leaf(left_key, left_balance),
+            &left_proof,
+            state_root
+        ));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod contract_commitment_tests {
+    use cube::transmutative::secp::contract_commitment::{
+        pay_to_contract_key, pay_to_contract_secret, sign_to_contract, verify_pay_to_contract,
+        verify_sign_to_contract,
+    };
+    use cube::transmutative::secp::schnorr::{self, Bytes32, SchnorrSigningMode};
+
+    #[test]
+    fn pay_to_contract() -> Result<(), String> {
+        let secret_key = schnorr::generate_secret();
+        let public_key = secret_key
+            .secret_to_public()
+            .ok_or("Failed to derive public key.".to_string())?;
+        let state_root = [0x5a; 32];
+
+        // 1 Commit a state root into the key.
+        let (committed_key, _) =
+            pay_to_contract_key(public_key, &state_root).ok_or("Failed to commit.".to_string())?;
+        assert_ne!(committed_key, public_key);
+        assert!(verify_pay_to_contract(
+            public_key,
+            &state_root,
+            committed_key
+        ));
+        assert!(!verify_pay_to_contract(
+            public_key,
+            &[0x5b; 32],
+            committed_key
+        ));
+
+        // 2 The committed secret key spends from the committed key.
+        let committed_secret_key = pay_to_contract_secret(secret_key, &state_root)
+            .ok_or("Failed to tweak secret key.".to_string())?;
+        assert_eq!(committed_secret_key.secret_to_public(), Some(committed_key));
+
+        let message = [0xab; 32];
+        let signature = schnorr::sign(committed_secret_key, message, SchnorrSigningMode::BIP340)
+            .ok_or("Failed to sign.".to_string())?;
+        assert!(schnorr::verify_xonly(
+            committed_key,
+            message,
+            signature,
+            SchnorrSigningMode::BIP340
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sign_to_contract_test() -> Result<(), String> {
+        let secret_key = schnorr::generate_secret();
+        let public_key = secret_key
+            .secret_to_public()
+            .ok_or("Failed to derive public key.".to_string())?;
+        let message = [0xcd; 32];
+        let state_root = [0x5a; 32];
+
+        for mode in [SchnorrSigningMode::Cube, SchnorrSigningMode::BIP340] {
+            // 1 Sign while committing a state root into the nonce.
+            let (signature, original_nonce) =
+                sign_to_contract(secret_key, message, &state_root, mode.clone())
+                    .ok_or("Failed to sign.".to_string())?;
+
+            // 2 The signature verifies as any other.
+            assert!(schnorr::verify_xonly(
+                public_key,
+                message,
+                signature,
+                mode.clone()
+            ));
+
+            // 3 The nonce opens to the state root only.
+            assert!(verify_sign_to_contract(
+                signature,
+                original_nonce,
+                &state_root
+            ));
+            assert!(!verify_sign_to_contract(
+                signature,
+                original_nonce,
+                &[0x5b; 32]
+            ));
+
+            // 4 Committing to other data uses another nonce.
+            let (other_signature, other_original_nonce) =
+                sign_to_contract(secret_key, message, &[0x5b; 32], mode.clone())
+                    .ok_or("Failed to sign.".to_string())?;
+            assert_ne!(other_original_nonce, original_nonce);
+            assert_ne!(other_signature[..32], signature[..32]);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod contract_notice_tests {
+    use cube::communicative::federation::contract_notice::{ContractNotice, ContractRegistration};
+    use cube::communicative::federation::federation::Federation;
+    use cube::communicative::gossip::contract_notice_index::{
+        erase_contract_notice_index, ContractNoticeIndex, CONTRACT_NOTICE_INDEX,
+    };
+    use cube::communicative::gossip::errors::contract_notice_error::ContractNoticeError;
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::secp::schnorr;
+
+    /// Returns the registration of a contract with the given id.
+    fn registration(contract_id: [u8; 32]) -> ContractRegistration {
+        ContractRegistration {
+            contract_id,
+            deployer_key: [0xaa; 32],
+            program_name: "token".to_string(),
+            metadata: Some(vec![0x01, 0x02]),
+            methods_len: 3,
+        }
+    }
+
+    #[test]
+    fn contract_notice_test() -> Result<(), String> {
+        // 1 Sign a notice.
+        let coordinator = KeyHolder::new(schnorr::generate_secret())
+            .ok_or("Failed to construct coordinator keys.".to_string())?;
+        let notice = ContractNotice::sign(&coordinator, registration([0x01; 32]), 7, [0x07; 32])
+            .ok_or("Failed to sign notice.".to_string())?;
+        assert!(notice.verify());
+
+        // 2 The notice survives serialization.
+        let bytes = notice
+            .serialize()
+            .ok_or("Failed to serialize.".to_string())?;
+        assert_eq!(ContractNotice::deserialize(&bytes), Some(notice.clone()));
+        let json = serde_json::to_string(&notice).map_err(|e| e.to_string())?;
+        let decoded: ContractNotice = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        assert_eq!(decoded, notice);
+
+        // 3 A tampered notice does not verify.
+        let mut tampered = notice.clone();
+        tampered.registration.metadata = None;
+        assert!(!tampered.verify());
+        let mut tampered = notice.clone();
+        tampered.batch_height = 8;
+        assert!(!tampered.verify());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn contract_notice_index_test() -> Result<(), String> {
+        // 1 Set the chain for local tests and construct a 2-of-2 federation.
+        let chain = Chain::Testbed;
+        let coordinators: Vec<KeyHolder> = (0..2)
+            .map(|_| KeyHolder::new(schnorr::generate_secret()))
+            .collect::<Option<Vec<KeyHolder>>>()
+            .ok_or("Failed to construct coordinator keys.".to_string())?;
+        let federation = Federation::new(
+            coordinators
+                .iter()
+                .map(|coordinator| coordinator.secp_public_key_bytes())
+                .collect(),
+            2,
+        )
+        .ok_or("Failed to construct federation.".to_string())?;
+        let outsider = KeyHolder::new(schnorr::generate_secret())
+            .ok_or("Failed to construct outsider keys.".to_string())?;
+
+        // 2 Erase first the contract notice index.
+        erase_contract_notice_index(chain);
+
+        {
+            let index: CONTRACT_NOTICE_INDEX = ContractNoticeIndex::new(chain, federation.clone())
+                .map_err(|err| format!("Error constructing contract notice index: {:?}", err))?;
+            let mut _index = index.lock().await;
+
+            // 3 Notices not signed by a coordinator are refused.
+            let notice = ContractNotice::sign(&outsider, registration([0x01; 32]), 7, [0x07; 32])
+                .ok_or("Failed to sign notice.".to_string())?;
+            assert_eq!(
+                _index.insert(notice),
+                Err(ContractNoticeError::NotACoordinator(
+                    outsider.secp_public_key_bytes()
+                ))
+            );
+
+            // 4 A coordinator's own notice is indexed and queued for publishing, once.
+            let notice =
+                ContractNotice::sign(&coordinators[0], registration([0x01; 32]), 7, [0x07; 32])
+                    .ok_or("Failed to sign notice.".to_string())?;
+            assert_eq!(_index.insert_signed(notice.clone()), Ok(true));
+            assert_eq!(_index.take_pending_publish(), vec![notice.clone()]);
+            assert!(_index.take_pending_publish().is_empty());
+
+            // 5 The other coordinator's notice of the same registration is deduplicated.
+            let duplicate =
+                ContractNotice::sign(&coordinators[1], registration([0x01; 32]), 7, [0x07; 32])
+                    .ok_or("Failed to sign notice.".to_string())?;
+            assert_eq!(_index.insert(duplicate), Ok(false));
+
+            // 6 A notice registering the same contract at another batch conflicts.
+            let conflicting =
+                ContractNotice::sign(&coordinators[1], registration([0x01; 32]), 9, [0x09; 32])
+                    .ok_or("Failed to sign notice.".to_string())?;
+            assert_eq!(
+                _index.insert(conflicting),
+                Err(ContractNoticeError::ConflictingNotice([0x01; 32]))
+            );
+
+            // 7 Index a later contract.
+            let notice =
+                ContractNotice::sign(&coordinators[1], registration([0x02; 32]), 12, [0x0c; 32])
+                    .ok_or("Failed to sign notice.".to_string())?;
+            assert_eq!(_index.insert(notice), Ok(true));
+        }
+
+        // 8 Reopen the index; the notices are persisted.
+        let index: CONTRACT_NOTICE_INDEX = ContractNoticeIndex::new(chain, federation)
+            .map_err(|err| format!("Error constructing contract notice index: {:?}", err))?;
+        let _index = index.lock().await;
+        assert_eq!(_index.len(), 2);
+        assert_eq!(
+            _index.notice([0x01; 32]).map(|notice| notice.batch_height),
+            Some(7)
+        );
+        assert_eq!(
+            _index
+                .notices_since(0)
+                .iter()
+                .map(|notice| notice.registration.contract_id)
+                .collect::<Vec<[u8; 32]>>(),
+            vec![[0x01; 32], [0x02; 32]]
+        );
+        assert_eq!(_index.notices_since(8).len(), 1);
+
+        // 9 Erase the contract notice index.
+        drop(_index);
+        drop(index);
+        erase_contract_notice_index(chain);
+
+        Ok(())
+    }
+}
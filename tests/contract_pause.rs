@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod contract_pause_tests {
+    use cube::{
+        constructive::calldata::element_type::CalldataElementType,
+        executive::{
+            executable::{
+                executable::Executable,
+                method::{method_type::MethodType, program_method::ProgramMethod},
+            },
+            opcode::{
+                opcode::Opcode,
+                opcodes::{
+                    coin::op_send::OP_SEND,
+                    flow::op_returnall::OP_RETURNALL,
+                    push::op_true::OP_TRUE,
+                    shadowing::{op_shadow_down::OP_SHADOW_DOWN, op_shadow_up::OP_SHADOW_UP},
+                    storage::{op_sread::OP_SREAD, op_swrite::OP_SWRITE},
+                },
+            },
+            vm::program_execution::exec::permitted_while_paused,
+        },
+        inscriptive::{
+            fixtures::{fixture_storage::fixture_namespace, registery_fixture::RegisteryFixture},
+            registery::{errors::pause_contract_error::RMPauseContractError, registery::REGISTERY},
+        },
+    };
+
+    // Pausable contract id.
+    const CONTRACT_ID: [u8; 32] = [0x0c; 32];
+
+    // Non-pausable contract id.
+    const UNPAUSABLE_CONTRACT_ID: [u8; 32] = [0x0f; 32];
+
+    // Pause authority of the pausable contract.
+    const PAUSE_AUTHORITY: [u8; 32] = [0xaa; 32];
+
+    /// Returns a program with a single callable method that pushes true and returns.
+    fn program() -> Executable {
+        let method = ProgramMethod::new(
+            "increment".to_string(),
+            MethodType::Callable,
+            Vec::<CalldataElementType>::new(),
+            vec![
+                Opcode::OP_TRUE(OP_TRUE),
+                Opcode::OP_TRUE(OP_TRUE),
+                Opcode::OP_TRUE(OP_TRUE),
+                Opcode::OP_RETURNALL(OP_RETURNALL),
+            ],
+        )
+        .unwrap();
+        Executable::new("counter".to_string(), None, false, vec![method]).unwrap()
+    }
+
+    #[test]
+    fn permitted_while_paused_test() {
+        // Reads and shadow down withdrawals are permitted.
+        assert!(permitted_while_paused(&Opcode::OP_TRUE(OP_TRUE)));
+        assert!(permitted_while_paused(&Opcode::OP_SREAD(OP_SREAD)));
+        assert!(permitted_while_paused(&Opcode::OP_SHADOW_DOWN(
+            OP_SHADOW_DOWN
+        )));
+
+        // Writes, coin moves and shadow up allocations are not.
+        assert!(!permitted_while_paused(&Opcode::OP_SWRITE(OP_SWRITE)));
+        assert!(!permitted_while_paused(&Opcode::OP_SEND(OP_SEND)));
+        assert!(!permitted_while_paused(&Opcode::OP_SHADOW_UP(OP_SHADOW_UP)));
+    }
+
+    #[tokio::test]
+    async fn contract_pause_test() -> Result<(), String> {
+        // 1 Construct the registery with a pausable and a non-pausable contract registered.
+        let namespace = fixture_namespace().map_err(|e| format!("{:?}", e))?;
+        let registery: REGISTERY = RegisteryFixture::new()
+            .with_contract_authorities(CONTRACT_ID, program(), None, Some(PAUSE_AUTHORITY))
+            .with_contract(UNPAUSABLE_CONTRACT_ID, program())
+            .build_in(&namespace)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        {
+            let mut _registery = registery.lock().await;
+
+            // 1.1 Only the pausable contract has a pause authority, and neither is paused.
+            assert_eq!(
+                _registery.get_contract_pause_authority(CONTRACT_ID),
+                Some(PAUSE_AUTHORITY)
+            );
+            assert_eq!(_registery.get_contract_upgrade_authority(CONTRACT_ID), None);
+            assert!(!_registery.is_contract_paused(CONTRACT_ID));
+
+            // 2 Only the pause authority can pause a pausable contract.
+            assert!(matches!(
+                _registery.set_contract_paused(UNPAUSABLE_CONTRACT_ID, PAUSE_AUTHORITY, true),
+                Err(RMPauseContractError::ContractHasNoPauseAuthority(_))
+            ));
+            assert!(matches!(
+                _registery.set_contract_paused(CONTRACT_ID, [0xbb; 32], true),
+                Err(RMPauseContractError::UnauthorizedPauseAuthority(_, _))
+            ));
+            assert!(matches!(
+                _registery.set_contract_paused([0xee; 32], PAUSE_AUTHORITY, true),
+                Err(RMPauseContractError::ContractIsNotRegistered(_))
+            ));
+            assert!(matches!(
+                _registery.set_contract_paused(CONTRACT_ID, PAUSE_AUTHORITY, false),
+                Err(RMPauseContractError::ContractIsNotPaused(_))
+            ));
+
+            // 3 Pause the contract.
+            _registery
+                .set_contract_paused(CONTRACT_ID, PAUSE_AUTHORITY, true)
+                .map_err(|e| format!("{:?}", e))?;
+
+            // 3.1 Calls see the pause before the changes are applied.
+            assert!(_registery.is_contract_paused(CONTRACT_ID));
+            assert!(matches!(
+                _registery.set_contract_paused(CONTRACT_ID, PAUSE_AUTHORITY, true),
+                Err(RMPauseContractError::ContractIsAlreadyPaused(_))
+            ));
+
+            _registery.apply_changes().map_err(|e| format!("{:?}", e))?;
+            _registery.flush_delta();
+        }
+
+        // 4 Construct the registery again from what was applied.
+        drop(registery);
+        let registery: REGISTERY =
+            RegisteryFixture::reopen(&namespace).map_err(|e| format!("{:?}", e))?;
+
+        {
+            let mut _registery = registery.lock().await;
+
+            // 4.1 The pause authority and the pause were persisted.
+            assert_eq!(
+                _registery.get_contract_pause_authority(CONTRACT_ID),
+                Some(PAUSE_AUTHORITY)
+            );
+            assert_eq!(
+                _registery.get_contract_pause_authority(UNPAUSABLE_CONTRACT_ID),
+                None
+            );
+            assert!(_registery.is_contract_paused(CONTRACT_ID));
+            assert!(!_registery.is_contract_paused(UNPAUSABLE_CONTRACT_ID));
+
+            // 5 Unpause the contract, and roll the unpause back.
+            _registery.pre_execution();
+            _registery
+                .set_contract_paused(CONTRACT_ID, PAUSE_AUTHORITY, false)
+                .map_err(|e| format!("{:?}", e))?;
+            assert!(!_registery.is_contract_paused(CONTRACT_ID));
+            _registery.rollback_last();
+            assert!(_registery.is_contract_paused(CONTRACT_ID));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod contract_transfer_tests {
+    use cube::executive::{
+        opcode::opcodes::coin::op_send::{OP_SEND, SEND_EVENT_TOPIC},
+        stack::{
+            stack_error::{CoinTransferError, StackError},
+            stack_holder::StackHolder,
+            stack_item::StackItem,
+            stack_uint::{SafeConverter, StackItemUintExt, StackUint},
+        },
+        vm::program_execution::{caller::Caller, exec_event::ExecEvent, exec_trace::ExecTrace},
+    };
+    use cube::inscriptive::coin_manager::{
+        coin_manager::COIN_MANAGER,
+        errors::balance_update_errors::{CMContractBalanceDownError, CMContractTransferError},
+        transfer_destination::CMTransferDestination,
+    };
+    use cube::inscriptive::fixtures::coin_manager_fixture::CoinManagerFixture;
+
+    // Account key.
+    const ACCOUNT_KEY: [u8; 32] = [0xa1; 32];
+
+    // Sending contract ID.
+    const CONTRACT_ID_1: [u8; 32] = [0xc1; 32];
+
+    // Receiving contract ID.
+    const CONTRACT_ID_2: [u8; 32] = [0xc2; 32];
+
+    // Unregistered contract ID.
+    const CONTRACT_ID_3: [u8; 32] = [0xc3; 32];
+
+    /// Returns a fresh stack holder of the given contract.
+    fn stack_holder(contract_id: [u8; 32]) -> Result<StackHolder, StackError> {
+        StackHolder::new(
+            Caller::new_account([0; 32]),
+            contract_id,
+            1715619200,
+            0,
+            10_000,
+            1,
+            0,
+            0,
+        )
+    }
+
+    /// Pushes the `OP_SEND` arguments onto the stack: the amount, the destination and the kind.
+    fn push_send_args(
+        stack_holder: &mut StackHolder,
+        destination: CMTransferDestination,
+        amount: u64,
+    ) -> Result<(), StackError> {
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from_u64(amount)))?;
+        stack_holder.push(StackItem::new(destination.bytes().to_vec()))?;
+        match destination {
+            CMTransferDestination::Account(_) => stack_holder.push(StackItem::new(vec![])),
+            CMTransferDestination::Contract(_) => stack_holder.push(StackItem::new(vec![0x01])),
+        }
+    }
+
+    /// Returns the `send` event of the contract.
+    fn send_event(
+        contract_id: [u8; 32],
+        destination: CMTransferDestination,
+        amount: u64,
+    ) -> ExecEvent {
+        let mut data = vec![destination.kind_byte()];
+        data.extend(destination.bytes());
+        data.extend(amount.to_be_bytes());
+        ExecEvent {
+            contract_id,
+            topic: SEND_EVENT_TOPIC.to_vec(),
+            data,
+        }
+    }
+
+    #[tokio::test]
+    async fn contract_transfer_tests() -> Result<(), String> {
+        // 1 Construct an in-memory coin manager with the account and the contracts registered.
+        let coin_manager: COIN_MANAGER = CoinManagerFixture::new()
+            .with_account(ACCOUNT_KEY, 0)
+            .with_contract(CONTRACT_ID_1, 1_000)
+            .with_contract(CONTRACT_ID_2, 0)
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 2 The transfer primitive rejects a self transfer and an unregistered destination, leaving
+        // the balances untouched.
+        {
+            let mut _coin_manager = coin_manager.lock().await;
+            let result = _coin_manager.contract_transfer(
+                CONTRACT_ID_1,
+                CMTransferDestination::Contract(CONTRACT_ID_1),
+                100,
+            );
+            assert!(matches!(
+                result,
+                Err(CMContractTransferError::SelfTransfer(CONTRACT_ID_1))
+            ));
+
+            let result = _coin_manager.contract_transfer(
+                CONTRACT_ID_1,
+                CMTransferDestination::Contract(CONTRACT_ID_3),
+                100,
+            );
+            assert!(matches!(
+                result,
+                Err(CMContractTransferError::UnableToGetDestinationContractBalance(CONTRACT_ID_3))
+            ));
+            assert_eq!(
+                _coin_manager.get_contract_balance(CONTRACT_ID_1),
+                Some(1_000)
+            );
+        }
+
+        // 3 Send to the account.
+        let mut trace = ExecTrace::new();
+        {
+            let mut stack_holder = stack_holder(CONTRACT_ID_1).map_err(|e| format!("{:?}", e))?;
+            let destination = CMTransferDestination::Account(ACCOUNT_KEY);
+            push_send_args(&mut stack_holder, destination, 300).map_err(|e| format!("{:?}", e))?;
+            OP_SEND::execute(&mut stack_holder, &coin_manager, &mut trace)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let _coin_manager = coin_manager.lock().await;
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID_1), Some(700));
+            assert_eq!(_coin_manager.get_account_balance(ACCOUNT_KEY), Some(300));
+            assert_eq!(
+                trace.events(),
+                &[send_event(CONTRACT_ID_1, destination, 300)]
+            );
+        }
+
+        // 4 Send to the other contract.
+        {
+            let mut stack_holder = stack_holder(CONTRACT_ID_1).map_err(|e| format!("{:?}", e))?;
+            let destination = CMTransferDestination::Contract(CONTRACT_ID_2);
+            push_send_args(&mut stack_holder, destination, 200).map_err(|e| format!("{:?}", e))?;
+            OP_SEND::execute(&mut stack_holder, &coin_manager, &mut trace)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let _coin_manager = coin_manager.lock().await;
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID_1), Some(500));
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID_2), Some(200));
+            assert_eq!(trace.events().len(), 2);
+            assert_eq!(
+                trace.events()[1],
+                send_event(CONTRACT_ID_1, destination, 200)
+            );
+        }
+
+        // 5 Sending more than the balance fails without moving sats or emitting an event.
+        {
+            let mut stack_holder = stack_holder(CONTRACT_ID_1).map_err(|e| format!("{:?}", e))?;
+            let destination = CMTransferDestination::Contract(CONTRACT_ID_2);
+            push_send_args(&mut stack_holder, destination, 501).map_err(|e| format!("{:?}", e))?;
+            let result = OP_SEND::execute(&mut stack_holder, &coin_manager, &mut trace).await;
+            assert!(matches!(
+                result,
+                Err(StackError::CoinTransferError(
+                    CoinTransferError::ContractTransferError(
+                        CONTRACT_ID_1,
+                        CMContractTransferError::ContractBalanceDownError(
+                            CMContractBalanceDownError::ContractBalanceWouldGoBelowZero(
+                                CONTRACT_ID_1,
+                                500,
+                                501
+                            )
+                        )
+                    )
+                ))
+            ));
+
+            let _coin_manager = coin_manager.lock().await;
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID_1), Some(500));
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID_2), Some(200));
+            assert_eq!(trace.events().len(), 2);
+        }
+
+        // 6 Sending to an unregistered contract fails without debiting the sender.
+        {
+            let mut stack_holder = stack_holder(CONTRACT_ID_1).map_err(|e| format!("{:?}", e))?;
+            let destination = CMTransferDestination::Contract(CONTRACT_ID_3);
+            push_send_args(&mut stack_holder, destination, 100).map_err(|e| format!("{:?}", e))?;
+            let result = OP_SEND::execute(&mut stack_holder, &coin_manager, &mut trace).await;
+            assert!(result.is_err());
+
+            let _coin_manager = coin_manager.lock().await;
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID_1), Some(500));
+            assert_eq!(trace.events().len(), 2);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod contract_upgrade_tests {
+    use cube::{
+        constructive::calldata::element_type::CalldataElementType,
+        executive::{
+            executable::{
+                executable::Executable,
+                method::{method_type::MethodType, program_method::ProgramMethod},
+                program_error::ProgramValidationError,
+            },
+            opcode::{
+                opcode::Opcode,
+                opcodes::{flow::op_returnall::OP_RETURNALL, push::op_true::OP_TRUE},
+            },
+        },
+        inscriptive::{
+            fixtures::{fixture_storage::fixture_namespace, registery_fixture::RegisteryFixture},
+            registery::{
+                errors::upgrade_contract_error::RMUpgradeContractError, registery::REGISTERY,
+            },
+        },
+    };
+
+    // Upgradable contract id.
+    const CONTRACT_ID: [u8; 32] = [0x0c; 32];
+
+    // Non-upgradable contract id.
+    const FROZEN_CONTRACT_ID: [u8; 32] = [0x0f; 32];
+
+    // Upgrade authority of the upgradable contract.
+    const UPGRADE_AUTHORITY: [u8; 32] = [0xaa; 32];
+
+    /// Returns a method that pushes true and returns.
+    fn method(method_name: &str, method_type: MethodType) -> ProgramMethod {
+        ProgramMethod::new(
+            method_name.to_string(),
+            method_type,
+            Vec::<CalldataElementType>::new(),
+            vec![
+                Opcode::OP_TRUE(OP_TRUE),
+                Opcode::OP_TRUE(OP_TRUE),
+                Opcode::OP_TRUE(OP_TRUE),
+                Opcode::OP_RETURNALL(OP_RETURNALL),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// Returns a program with the given name and methods.
+    fn program(program_name: &str, methods: Vec<ProgramMethod>) -> Executable {
+        Executable::new(program_name.to_string(), None, false, methods).unwrap()
+    }
+
+    #[test]
+    fn migration_method_index_test() -> Result<(), String> {
+        // A program without a migration hook.
+        let program_v0 = program(
+            "counter_v0",
+            vec![method("increment", MethodType::Callable)],
+        );
+        assert_eq!(program_v0.migration_method_index(), None);
+
+        // The migration hook is found after the callable methods it is ordered behind.
+        let program_v1 = program(
+            "counter_v1",
+            vec![
+                method("migrate", MethodType::Internal),
+                method("increment", MethodType::Callable),
+            ],
+        );
+        assert_eq!(program_v1.migration_method_index(), Some(1));
+
+        // A callable method named `migrate` is not a migration hook.
+        let program_v2 = program("counter_v2", vec![method("migrate", MethodType::Callable)]);
+        assert_eq!(program_v2.migration_method_index(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn contract_upgrade_test() -> Result<(), String> {
+        let program_v0 = program(
+            "counter_v0",
+            vec![method("increment", MethodType::Callable)],
+        );
+        let program_v1 = program(
+            "counter_v1",
+            vec![
+                method("increment", MethodType::Callable),
+                method("migrate", MethodType::Internal),
+            ],
+        );
+
+        // 1 Construct the registery with an upgradable and a non-upgradable contract registered.
+        let namespace = fixture_namespace().map_err(|e| format!("{:?}", e))?;
+        let registery: REGISTERY = RegisteryFixture::new()
+            .with_contract_authorities(
+                CONTRACT_ID,
+                program_v0.clone(),
+                Some(UPGRADE_AUTHORITY),
+                None,
+            )
+            .with_contract(FROZEN_CONTRACT_ID, program_v0.clone())
+            .build_in(&namespace)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        {
+            let mut _registery = registery.lock().await;
+
+            // 1.1 The upgradable contract has an upgrade authority, and is at its first version.
+            assert_eq!(
+                _registery.get_contract_upgrade_authority(CONTRACT_ID),
+                Some(UPGRADE_AUTHORITY)
+            );
+            assert_eq!(
+                _registery.get_contract_program_version(CONTRACT_ID),
+                Some(0)
+            );
+
+            // 2 Only the upgrade authority can upgrade an upgradable contract.
+            assert!(matches!(
+                _registery.upgrade_contract(
+                    FROZEN_CONTRACT_ID,
+                    UPGRADE_AUTHORITY,
+                    program_v1.clone()
+                ),
+                Err(RMUpgradeContractError::ContractHasNoUpgradeAuthority(_))
+            ));
+            assert!(matches!(
+                _registery.upgrade_contract(CONTRACT_ID, [0xbb; 32], program_v1.clone()),
+                Err(RMUpgradeContractError::UnauthorizedUpgradeAuthority(_, _))
+            ));
+            assert!(matches!(
+                _registery.upgrade_contract([0xee; 32], UPGRADE_AUTHORITY, program_v1.clone()),
+                Err(RMUpgradeContractError::ContractIsNotRegistered(_))
+            ));
+
+            // 2.1 A program failing the static validation is refused.
+            let invalid_program = program(
+                "counter_v1",
+                vec![
+                    method("increment", MethodType::Callable),
+                    method("migrate", MethodType::Callable),
+                ],
+            );
+            assert!(matches!(
+                _registery.upgrade_contract(CONTRACT_ID, UPGRADE_AUTHORITY, invalid_program),
+                Err(RMUpgradeContractError::InvalidProgram(
+                    _,
+                    ProgramValidationError::InvalidMigrationMethod
+                ))
+            ));
+
+            // 3 Upgrade the contract.
+            _registery
+                .upgrade_contract(CONTRACT_ID, UPGRADE_AUTHORITY, program_v1.clone())
+                .map_err(|e| format!("{:?}", e))?;
+
+            // 3.1 Calls see the upgraded program before the changes are applied.
+            assert_eq!(
+                _registery.get_contract_executable(CONTRACT_ID),
+                Some(program_v1.clone())
+            );
+            assert_eq!(
+                _registery.get_contract_program_version(CONTRACT_ID),
+                Some(0)
+            );
+
+            // 3.2 A contract is upgraded at most once per batch.
+            assert!(matches!(
+                _registery.upgrade_contract(CONTRACT_ID, UPGRADE_AUTHORITY, program_v0.clone()),
+                Err(RMUpgradeContractError::ContractHasJustBeenEphemerallyUpgraded(_))
+            ));
+
+            _registery.apply_changes().map_err(|e| format!("{:?}", e))?;
+            _registery.flush_delta();
+        }
+
+        // 4 Construct the registery again from what was applied.
+        drop(registery);
+        let registery: REGISTERY =
+            RegisteryFixture::reopen(&namespace).map_err(|e| format!("{:?}", e))?;
+        let _registery = registery.lock().await;
+
+        // 4.1 The upgrade authority and the new version were persisted.
+        assert_eq!(
+            _registery.get_contract_upgrade_authority(CONTRACT_ID),
+            Some(UPGRADE_AUTHORITY)
+        );
+        assert_eq!(
+            _registery.get_contract_upgrade_authority(FROZEN_CONTRACT_ID),
+            None
+        );
+        assert_eq!(
+            _registery.get_contract_program_version(CONTRACT_ID),
+            Some(1)
+        );
+        assert_eq!(
+            _registery.get_contract_executable(CONTRACT_ID),
+            Some(program_v1.clone())
+        );
+
+        // 4.2 The previous version is retained for archival replay.
+        assert_eq!(
+            _registery.get_contract_executable_by_version(CONTRACT_ID, 0),
+            Some(program_v0)
+        );
+        assert_eq!(
+            _registery.get_contract_executable_by_version(CONTRACT_ID, 1),
+            Some(program_v1)
+        );
+        assert_eq!(
+            _registery.get_contract_executable_by_version(CONTRACT_ID, 2),
+            None
+        );
+
+        Ok(())
+    }
+}
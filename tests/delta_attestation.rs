@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod delta_attestation_tests {
+    use cube::communicative::federation::delta_attestation::{DeltaAttestation, DeltaCosignature};
+    use cube::communicative::federation::federation::Federation;
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::secp::schnorr;
+
+    #[test]
+    fn aggregated_delta_attestation_test() -> Result<(), String> {
+        let batch_height = 42;
+        let batch_txid = [0xab; 32];
+
+        // 1 Construct a 3-of-4 federation.
+        let coordinators: Vec<KeyHolder> = (0..4)
+            .map(|_| KeyHolder::new(schnorr::generate_secret()))
+            .collect::<Option<Vec<KeyHolder>>>()
+            .ok_or("Failed to construct coordinator keys.".to_string())?;
+        let federation = Federation::new(
+            coordinators
+                .iter()
+                .map(|coordinator| coordinator.secp_public_key_bytes())
+                .collect(),
+            3,
+        )
+        .ok_or("Failed to construct federation.".to_string())?;
+
+        // 2 Collect co-signatures from three coordinators.
+        let mut attestation = DeltaAttestation::new(batch_height, batch_txid);
+        assert_eq!(attestation.half_aggregate(), None);
+        for coordinator in coordinators[..3].iter() {
+            let cosignature = DeltaCosignature::sign(coordinator, batch_height, batch_txid)
+                .ok_or("Failed to co-sign.".to_string())?;
+            attestation
+                .add_cosignature(&federation, cosignature)
+                .map_err(|e| format!("{:?}", e))?;
+        }
+
+        // 3 Half-aggregate the co-signatures.
+        let aggregated = attestation
+            .half_aggregate()
+            .ok_or("Failed to half-aggregate.".to_string())?;
+        assert_eq!(aggregated.aggregate_signature.len(), 32 * 4);
+        assert!(aggregated.has_quorum(&federation, batch_height, batch_txid));
+
+        // 3.1 Not for another batch.
+        assert!(!aggregated.has_quorum(&federation, batch_height + 1, batch_txid));
+        assert!(!aggregated.has_quorum(&federation, batch_height, [0xcd; 32]));
+
+        // 3.2 Not below the threshold.
+        let mut below_threshold = DeltaAttestation::new(batch_height, batch_txid);
+        below_threshold.cosignatures = attestation.cosignatures[..2].to_vec();
+        let below_threshold = below_threshold
+            .half_aggregate()
+            .ok_or("Failed to half-aggregate.".to_string())?;
+        assert!(!below_threshold.has_quorum(&federation, batch_height, batch_txid));
+
+        // 3.3 Not with a co-signer listed twice.
+        let mut duplicated = aggregated.clone();
+        duplicated.coordinator_keys[2] = duplicated.coordinator_keys[0];
+        assert!(!duplicated.has_quorum(&federation, batch_height, batch_txid));
+
+        // 3.4 Not with a co-signer claimed without its co-signature.
+        let mut claimed = aggregated.clone();
+        claimed.coordinator_keys[2] = coordinators[3].secp_public_key_bytes();
+        assert!(!claimed.has_quorum(&federation, batch_height, batch_txid));
+
+        Ok(())
+    }
+}
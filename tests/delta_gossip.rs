@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod delta_gossip_tests {
+    use cube::communicative::federation::delta_attestation::{
+        AggregatedDeltaAttestation, DeltaAttestation, DeltaCosignature,
+    };
+    use cube::communicative::federation::federation::Federation;
+    use cube::communicative::gossip::delta_gossip_pool::{DeltaGossipPool, DELTA_GOSSIP_RETENTION};
+    use cube::communicative::gossip::errors::delta_gossip_error::DeltaGossipError;
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::secp::schnorr;
+
+    /// Half-aggregates the co-signatures of the given coordinators over a batch.
+    fn attest(
+        coordinators: &[KeyHolder],
+        batch_height: u64,
+        batch_txid: [u8; 32],
+    ) -> Result<AggregatedDeltaAttestation, String> {
+        let mut attestation = DeltaAttestation::new(batch_height, batch_txid);
+        for coordinator in coordinators.iter() {
+            let cosignature = DeltaCosignature::sign(coordinator, batch_height, batch_txid)
+                .ok_or("Failed to co-sign.".to_string())?;
+            attestation.cosignatures.push(cosignature);
+        }
+        attestation
+            .half_aggregate()
+            .ok_or("Failed to half-aggregate.".to_string())
+    }
+
+    #[test]
+    fn delta_gossip_pool_test() -> Result<(), String> {
+        // 1 Construct a 2-of-3 federation.
+        let coordinators: Vec<KeyHolder> = (0..3)
+            .map(|_| KeyHolder::new(schnorr::generate_secret()))
+            .collect::<Option<Vec<KeyHolder>>>()
+            .ok_or("Failed to construct coordinator keys.".to_string())?;
+        let federation = Federation::new(
+            coordinators
+                .iter()
+                .map(|coordinator| coordinator.secp_public_key_bytes())
+                .collect(),
+            2,
+        )
+        .ok_or("Failed to construct federation.".to_string())?;
+        let delta_gossip_pool = DeltaGossipPool::new(federation);
+        let mut delta_gossip_pool = delta_gossip_pool.try_lock().map_err(|e| e.to_string())?;
+
+        // 2 Attestations below the quorum are not admitted.
+        let below_quorum = attest(&coordinators[..1], 10, [0x10; 32])?;
+        assert_eq!(
+            delta_gossip_pool.insert(below_quorum).err(),
+            Some(DeltaGossipError::NoQuorum(10))
+        );
+
+        // 3 An attestation carrying a quorum is admitted, once.
+        let attestation = attest(&coordinators[..2], 10, [0x10; 32])?;
+        delta_gossip_pool
+            .insert(attestation.clone())
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            delta_gossip_pool.insert(attestation.clone()).err(),
+            Some(DeltaGossipError::AlreadySeen(10))
+        );
+        assert_eq!(
+            delta_gossip_pool.attestation(10, [0x10; 32]),
+            Some(attestation.clone())
+        );
+        assert_eq!(delta_gossip_pool.attestation(10, [0x11; 32]), None);
+
+        // 4 A quorum over another batch at the same height is reported, keeping the first one.
+        let conflicting = attest(&coordinators[1..], 10, [0x11; 32])?;
+        assert_eq!(
+            delta_gossip_pool.insert(conflicting).err(),
+            Some(DeltaGossipError::ConflictingBatch(10))
+        );
+
+        // 5 Admitted attestations are relayed once.
+        assert_eq!(
+            delta_gossip_pool.take_pending_relay(),
+            vec![attestation.clone()]
+        );
+        assert!(delta_gossip_pool.take_pending_relay().is_empty());
+
+        // 6 Attestations falling out of the retention window are pruned and no longer admitted.
+        let latest = attest(&coordinators[..2], 10 + DELTA_GOSSIP_RETENTION, [0x20; 32])?;
+        delta_gossip_pool
+            .insert(latest)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(delta_gossip_pool.len(), 1);
+        assert_eq!(
+            delta_gossip_pool.insert(attestation).err(),
+            Some(DeltaGossipError::BelowRetention(10))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod determinism_check_tests {
+    use cube::executive::stack::stack_item::StackItem;
+    use cube::executive::vm::program_execution::determinism_check::ExecOutcome;
+    use cube::executive::vm::program_execution::exec_event::ExecEvent;
+    use cube::inscriptive::coin_manager::delta::delta::CMDelta;
+    use cube::inscriptive::state_manager::delta::delta::SMDelta;
+    use std::collections::HashMap;
+
+    /// Contract ID.
+    const CONTRACT_ID: [u8; 32] = [0xc1; 32];
+
+    #[test]
+    fn canonical_delta_bytes_test() {
+        // 1 Two state deltas with the same states encode the same, whatever their insertion order.
+        let mut states = HashMap::new();
+        let mut reversed_states = HashMap::new();
+        for key in 0..64u8 {
+            states.insert(vec![key], vec![key, key]);
+        }
+        for key in (0..64u8).rev() {
+            reversed_states.insert(vec![key], vec![key, key]);
+        }
+        let mut state_delta = SMDelta::fresh_new();
+        let mut reversed_state_delta = SMDelta::fresh_new();
+        state_delta
+            .new_or_updated_contract_states
+            .insert(CONTRACT_ID, states);
+        reversed_state_delta
+            .new_or_updated_contract_states
+            .insert(CONTRACT_ID, reversed_states);
+        assert_eq!(
+            state_delta.canonical_bytes(),
+            reversed_state_delta.canonical_bytes()
+        );
+
+        // 2 A different value encodes differently.
+        reversed_state_delta
+            .new_or_updated_contract_states
+            .get_mut(&CONTRACT_ID)
+            .unwrap()
+            .insert(vec![0], vec![0xff]);
+        assert_ne!(
+            state_delta.canonical_bytes(),
+            reversed_state_delta.canonical_bytes()
+        );
+
+        // 3 Likewise for the coin deltas.
+        let mut coin_delta = CMDelta::fresh_new();
+        let mut reversed_coin_delta = CMDelta::fresh_new();
+        for key in 0..64u8 {
+            coin_delta
+                .updated_account_balances
+                .insert([key; 32], key as u64);
+        }
+        for key in (0..64u8).rev() {
+            reversed_coin_delta
+                .updated_account_balances
+                .insert([key; 32], key as u64);
+        }
+        assert_eq!(
+            coin_delta.canonical_bytes(),
+            reversed_coin_delta.canonical_bytes()
+        );
+    }
+
+    #[test]
+    fn exec_outcome_divergence_test() {
+        // 1 An outcome of a call returning true after 10 ops, with one event.
+        let result: Result<(Vec<StackItem>, u32, u32), String> =
+            Ok((vec![StackItem::new(vec![0x01])], 10, 0));
+        let event = ExecEvent {
+            contract_id: CONTRACT_ID,
+            topic: b"topic".to_vec(),
+            data: vec![],
+        };
+        let outcome = ExecOutcome::new(&result, vec![0x01], vec![0x02], vec![event.clone()]);
+
+        // 2 An identical outcome does not diverge.
+        assert_eq!(
+            outcome.divergence(&ExecOutcome::new(
+                &result,
+                vec![0x01],
+                vec![0x02],
+                vec![event.clone()]
+            )),
+            None
+        );
+
+        // 3 Each diverging part is named.
+        let other_result: Result<(Vec<StackItem>, u32, u32), String> =
+            Ok((vec![StackItem::new(vec![0x01])], 11, 0));
+        assert_eq!(
+            outcome.divergence(&ExecOutcome::new(
+                &other_result,
+                vec![0x01],
+                vec![0x02],
+                vec![event.clone()]
+            )),
+            Some("result")
+        );
+        assert_eq!(
+            outcome.divergence(&ExecOutcome::new(
+                &result,
+                vec![0x03],
+                vec![0x02],
+                vec![event.clone()]
+            )),
+            Some("state_delta")
+        );
+        assert_eq!(
+            outcome.divergence(&ExecOutcome::new(
+                &result,
+                vec![0x01],
+                vec![0x03],
+                vec![event]
+            )),
+            Some("coin_delta")
+        );
+        assert_eq!(
+            outcome.divergence(&ExecOutcome::new(&result, vec![0x01], vec![0x02], vec![])),
+            Some("events")
+        );
+
+        // 4 The external ops counter is not part of the outcome.
+        let later_result: Result<(Vec<StackItem>, u32, u32), String> =
+            Ok((vec![StackItem::new(vec![0x01])], 10, 5));
+        assert_eq!(
+            ExecOutcome::new(&result, vec![], vec![], vec![]).divergence(&ExecOutcome::new(
+                &later_result,
+                vec![],
+                vec![],
+                vec![]
+            )),
+            None
+        );
+    }
+}
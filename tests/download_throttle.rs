@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod download_throttle_tests {
+    use cube::operative::tasks::chain_sync::download_throttle::DownloadThrottle;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn download_throttle_test() -> Result<(), String> {
+        let start = Instant::now();
+
+        // Without a rate limit, downloads never wait.
+        let mut unlimited = DownloadThrottle::new(0, 4096, start);
+        assert!(unlimited.is_unlimited());
+        assert_eq!(unlimited.take(u64::MAX / 2, start), Duration::ZERO);
+
+        // 1 MiB/s with a 2 MiB burst allowance.
+        let mut throttle = DownloadThrottle::new(1024, 2048, start);
+
+        // The burst allowance is downloaded without waiting.
+        assert_eq!(throttle.take(2 * 1024 * 1024, start), Duration::ZERO);
+
+        // Going past it waits for the debt to be paid off.
+        assert_eq!(throttle.take(512 * 1024, start), Duration::from_millis(500));
+
+        // The bucket refills over time.
+        let later = start + Duration::from_secs(1);
+        assert_eq!(throttle.take(256 * 1024, later), Duration::ZERO);
+
+        // Lifting the limit at runtime stops the waiting.
+        throttle.set_limit(0, None, later);
+        assert_eq!(throttle.take(64 * 1024 * 1024, later), Duration::ZERO);
+
+        Ok(())
+    }
+}
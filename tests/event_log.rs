@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod event_log_tests {
+    use cube::executive::{
+        opcode::opcodes::event::op_emit::OP_EMIT,
+        stack::{
+            stack_error::{EventError, StackError},
+            stack_holder::StackHolder,
+            stack_item::StackItem,
+        },
+        vm::program_execution::{
+            caller::Caller,
+            exec_event::{ExecEvent, MAX_EVENTS_PER_CALL},
+            exec_trace::ExecTrace,
+        },
+    };
+    use cube::inscriptive::archival_manager::archival_manager::{
+        erase_archival_manager, ArchivalManager, ARCHIVAL_MANAGER,
+    };
+    use cube::operative::run_args::chain::Chain;
+
+    /// Returns a fresh stack holder of the given contract with the given ops budget.
+    fn stack_holder(contract_id: [u8; 32], ops_budget: u32) -> Result<StackHolder, StackError> {
+        StackHolder::new(
+            Caller::new_account([0; 32]),
+            contract_id,
+            1715619200,
+            0,
+            ops_budget,
+            1,
+            0,
+            0,
+        )
+    }
+
+    /// Returns an event of the contract under the topic.
+    fn event(contract_id: [u8; 32], topic: &[u8], data: &[u8]) -> ExecEvent {
+        ExecEvent {
+            contract_id,
+            topic: topic.to_vec(),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn op_emit_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder([0xaa; 32], 10_000)?;
+        let mut trace = ExecTrace::new();
+
+        // The data is below the topic.
+        stack_holder.push(StackItem::new(b"100 sats".to_vec()))?;
+        stack_holder.push(StackItem::new(b"deposit".to_vec()))?;
+        OP_EMIT::execute(&mut stack_holder, &mut trace)?;
+        assert_eq!(
+            trace.events(),
+            &[event([0xaa; 32], b"deposit", b"100 sats")]
+        );
+
+        // The data may be empty.
+        stack_holder.push(StackItem::new(vec![]))?;
+        stack_holder.push(StackItem::new(b"ping".to_vec()))?;
+        OP_EMIT::execute(&mut stack_holder, &mut trace)?;
+        assert_eq!(trace.events()[1], event([0xaa; 32], b"ping", b""));
+
+        // An empty topic fails.
+        stack_holder.push(StackItem::new(b"data".to_vec()))?;
+        stack_holder.push(StackItem::new(vec![]))?;
+        assert!(matches!(
+            OP_EMIT::execute(&mut stack_holder, &mut trace),
+            Err(StackError::EventError(EventError::InvalidEventTopicLength(
+                0
+            )))
+        ));
+
+        // A topic over 32 bytes fails.
+        stack_holder.push(StackItem::new(b"data".to_vec()))?;
+        stack_holder.push(StackItem::new(vec![0x01; 33]))?;
+        assert!(matches!(
+            OP_EMIT::execute(&mut stack_holder, &mut trace),
+            Err(StackError::EventError(EventError::InvalidEventTopicLength(
+                33
+            )))
+        ));
+
+        // Emitting past the limit of a call fails.
+        let mut trace = ExecTrace::new();
+        for _ in 0..MAX_EVENTS_PER_CALL {
+            stack_holder.push(StackItem::new(vec![]))?;
+            stack_holder.push(StackItem::new(b"tick".to_vec()))?;
+            OP_EMIT::execute(&mut stack_holder, &mut trace)?;
+        }
+        stack_holder.push(StackItem::new(vec![]))?;
+        stack_holder.push(StackItem::new(b"tick".to_vec()))?;
+        assert!(matches!(
+            OP_EMIT::execute(&mut stack_holder, &mut trace),
+            Err(StackError::EventError(EventError::EventLimitExceeded))
+        ));
+        assert_eq!(trace.events().len(), MAX_EVENTS_PER_CALL);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn archived_events_test() -> Result<(), String> {
+        // 1 Erase and construct the archival manager.
+        let chain = Chain::Testbed;
+        erase_archival_manager(chain);
+        let archival_manager: ARCHIVAL_MANAGER =
+            ArchivalManager::new(chain).map_err(|e| format!("{:?}", e))?;
+        let mut _archival_manager = archival_manager.lock().await;
+
+        // 2 Archive the events of three batches, across two contracts.
+        let (alice, bob) = ([0xaa; 32], [0xbb; 32]);
+        _archival_manager
+            .insert_events(
+                1,
+                &[
+                    event(alice, b"deposit", b"1"),
+                    event(bob, b"deposit", b"2"),
+                    event(alice, b"withdraw", b"3"),
+                ],
+            )
+            .map_err(|e| format!("{:?}", e))?;
+        _archival_manager
+            .insert_events(2, &[event(alice, b"deposit", b"4")])
+            .map_err(|e| format!("{:?}", e))?;
+        _archival_manager
+            .insert_events(3, &[event(alice, b"deposit", b"5")])
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 3 Every event of a contract, in order.
+        assert_eq!(
+            _archival_manager.get_events(alice, None, 0, u64::MAX),
+            vec![
+                (1, event(alice, b"deposit", b"1")),
+                (1, event(alice, b"withdraw", b"3")),
+                (2, event(alice, b"deposit", b"4")),
+                (3, event(alice, b"deposit", b"5")),
+            ]
+        );
+
+        // 4 The events of a topic, within the height range.
+        assert_eq!(
+            _archival_manager.get_events(alice, Some(&b"deposit"[..]), 2, 3),
+            vec![
+                (2, event(alice, b"deposit", b"4")),
+                (3, event(alice, b"deposit", b"5")),
+            ]
+        );
+        assert_eq!(
+            _archival_manager.get_events(bob, Some(&b"deposit"[..]), 0, u64::MAX),
+            vec![(1, event(bob, b"deposit", b"2"))]
+        );
+
+        // 5 A topic prefix does not match a longer topic.
+        assert!(_archival_manager
+            .get_events(alice, Some(&b"dep"[..]), 0, u64::MAX)
+            .is_empty());
+
+        // 6 An inverted range is empty.
+        assert!(_archival_manager.get_events(alice, None, 3, 1).is_empty());
+
+        // 7 Re-archiving a batch replaces its events and their indexes.
+        _archival_manager
+            .insert_events(1, &[event(bob, b"withdraw", b"6")])
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            _archival_manager.get_events(alice, None, 1, 1),
+            Vec::<(u64, ExecEvent)>::new()
+        );
+        assert_eq!(
+            _archival_manager.get_events(bob, None, 0, u64::MAX),
+            vec![(1, event(bob, b"withdraw", b"6"))]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod event_stream_tests {
+    use cube::communicative::event_stream::errors::subscription_filter_error::SubscriptionFilterError;
+    use cube::communicative::event_stream::event_stream::{EventStream, SubscriptionMessage};
+    use cube::communicative::event_stream::stream_event::{StreamEvent, StreamEventKind};
+    use cube::communicative::event_stream::subscription_filter::{
+        SubscriptionFilter, MAX_FILTER_KEYS,
+    };
+    use cube::transmutative::key::ToNostrKeyStr;
+    use serde_json::{json, Value};
+
+    // Secp256k1 generator point x-coordinate.
+    const ACCOUNT_KEY: [u8; 32] = [
+        0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87, 0x0b,
+        0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16, 0xf8,
+        0x17, 0x98,
+    ];
+
+    /// Returns a stream event of the given kind involving the given account and contract.
+    fn event(
+        kind: StreamEventKind,
+        account_key: [u8; 32],
+        contract_id: Option<[u8; 32]>,
+        value: Option<u64>,
+    ) -> StreamEvent {
+        StreamEvent {
+            kind,
+            batch_height: 1,
+            entry_id: [0x00; 32],
+            account_keys: vec![account_key],
+            contract_ids: contract_id.into_iter().collect(),
+            value,
+            entry: Value::Null,
+        }
+    }
+
+    #[test]
+    fn subscription_filter_parse_test() -> Result<(), String> {
+        // 1 Parse a filter following an account by npub and a contract by hex.
+        let npub = ACCOUNT_KEY.to_npub().ok_or("npub encoding failed")?;
+        let filter = SubscriptionFilter::from_json(&json!({
+            "accounts": [npub],
+            "contracts": [hex::encode([0x0c; 32])],
+            "kinds": ["move", "call"],
+            "min_value": 1000
+        }))
+        .map_err(|err| err.to_string())?;
+        assert!(filter.account_keys.contains(&ACCOUNT_KEY));
+        assert!(filter.contract_ids.contains(&[0x0c; 32]));
+        assert_eq!(filter.kinds.len(), 2);
+        assert_eq!(filter.min_value, Some(1000));
+
+        // 2 The filter round-trips through its JSON.
+        assert_eq!(SubscriptionFilter::from_json(&filter.json()), Ok(filter));
+
+        // 3 Every field is optional.
+        assert_eq!(
+            SubscriptionFilter::from_json(&json!({})),
+            Ok(SubscriptionFilter::new())
+        );
+
+        // 4 Invalid filters are refused.
+        assert_eq!(
+            SubscriptionFilter::from_json(&json!([])),
+            Err(SubscriptionFilterError::MalformedFilter)
+        );
+        assert_eq!(
+            SubscriptionFilter::from_json(&json!({ "kinds": ["mint"] })),
+            Err(SubscriptionFilterError::UnknownEventKind(
+                "mint".to_string()
+            ))
+        );
+        assert_eq!(
+            SubscriptionFilter::from_json(&json!({ "contracts": ["0c0c"] })),
+            Err(SubscriptionFilterError::InvalidContractId(
+                "0c0c".to_string()
+            ))
+        );
+        assert!(matches!(
+            SubscriptionFilter::from_json(&json!({ "accounts": ["npub1invalid"] })),
+            Err(SubscriptionFilterError::InvalidAccountKey(_))
+        ));
+        let contracts: Vec<String> = (0..=MAX_FILTER_KEYS)
+            .map(|i| hex::encode([[i as u8; 16], [(i >> 8) as u8; 16]].concat()))
+            .collect();
+        assert_eq!(
+            SubscriptionFilter::from_json(&json!({ "contracts": contracts })),
+            Err(SubscriptionFilterError::TooManyKeys(MAX_FILTER_KEYS + 1))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn subscription_filter_match_test() {
+        let followed = [0x01; 32];
+        let other = [0x02; 32];
+        let contract = [0x0c; 32];
+
+        // 1 An empty filter matches every event.
+        let filter = SubscriptionFilter::new();
+        assert!(filter.matches(&event(StreamEventKind::Config, other, None, None)));
+
+        // 2 Events involving a followed account or contract match.
+        let mut filter = SubscriptionFilter::new();
+        filter.account_keys.insert(followed);
+        filter.contract_ids.insert(contract);
+        assert!(filter.matches(&event(StreamEventKind::Move, followed, None, Some(5))));
+        assert!(filter.matches(&event(StreamEventKind::Call, other, Some(contract), None)));
+        assert!(!filter.matches(&event(StreamEventKind::Move, other, None, Some(5))));
+
+        // 3 Kinds and minimum value narrow the followed events down.
+        filter.kinds.insert(StreamEventKind::Move);
+        filter.min_value = Some(10);
+        assert!(filter.matches(&event(StreamEventKind::Move, followed, None, Some(10))));
+        assert!(!filter.matches(&event(StreamEventKind::Move, followed, None, Some(9))));
+        assert!(!filter.matches(&event(StreamEventKind::Call, followed, None, None)));
+    }
+
+    #[tokio::test]
+    async fn event_stream_test() {
+        let followed = [0x01; 32];
+        let other = [0x02; 32];
+
+        // 1 Subscribe to the events of a followed account.
+        let event_stream = EventStream::new();
+        let mut filter = SubscriptionFilter::new();
+        filter.account_keys.insert(followed);
+        let mut subscription = event_stream.subscribe(filter);
+        assert_eq!(event_stream.subscriber_count(), 1);
+
+        // 2 Only the events involving the followed account are delivered.
+        let delivered = event(StreamEventKind::Move, followed, None, Some(1));
+        event_stream.publish(vec![
+            event(StreamEventKind::Move, other, None, Some(1)),
+            delivered.clone(),
+        ]);
+        match subscription.next().await {
+            Some(SubscriptionMessage::Event(event)) => assert_eq!(*event, delivered),
+            _ => panic!("Expected an event."),
+        }
+
+        // 3 Replacing the filter takes effect from the next event.
+        subscription.set_filter(SubscriptionFilter::new());
+        let delivered = event(StreamEventKind::Config, other, None, None);
+        event_stream.publish(vec![delivered.clone()]);
+        match subscription.next().await {
+            Some(SubscriptionMessage::Event(event)) => assert_eq!(*event, delivered),
+            _ => panic!("Expected an event."),
+        }
+
+        // 4 The stream closes once dropped.
+        drop(event_stream);
+        assert_eq!(subscription.next().await, None);
+    }
+}
@@ -1,8 +1,207 @@
 #[cfg(test)]
 mod exec_tests {
+    use cube::executive::{
+        opcode::{
+            opcode::Opcode,
+            opcodes::{
+                coin::op_transfer::OP_TRANSFER,
+                push::op_true::OP_TRUE,
+                storage::{op_sread::OP_SREAD, op_swrite::OP_SWRITE},
+            },
+        },
+        vm::program_execution::{
+            call_stack::CallStack,
+            exec_error::ExecutionError,
+            exec_receipt::ExecReceipt,
+            exec_trace::{ExecTrace, ExecTraceTarget},
+            exec_usage::ExecUsage,
+            exec_watchdog::ExecWatchdog,
+        },
+    };
+    use std::time::Duration;
 
     #[test]
     fn exec_test() -> Result<(), String> {
         Ok(())
     }
+
+    #[tokio::test]
+    async fn exec_watchdog_test() -> Result<(), String> {
+        // The flag is down until the timeout has passed.
+        let watchdog = ExecWatchdog::start(Duration::from_millis(50));
+        assert!(!watchdog.is_expired());
+
+        // It is raised once the timeout has passed.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(watchdog.is_expired());
+
+        Ok(())
+    }
+
+    #[test]
+    fn call_stack_test() -> Result<(), String> {
+        // Up to two nested external calls, and up to three calls in total.
+        let mut call_stack = CallStack::new([0x00; 32], 2, 3);
+
+        // Internal calls count, but do not nest.
+        call_stack.enter_internal().map_err(|e| e.to_string())?;
+        assert_eq!(call_stack.depth(), 0);
+
+        // External calls nest up to the depth limit.
+        call_stack
+            .enter_external([0x01; 32])
+            .map_err(|e| e.to_string())?;
+        assert_eq!(call_stack.depth(), 1);
+        assert!(matches!(
+            CallStack::new([0x00; 32], 0, 3).enter_external([0x01; 32]),
+            Err(ExecutionError::CallDepthLimitExceededError(0))
+        ));
+
+        // Calls are counted up to the count limit.
+        call_stack
+            .enter_external([0x02; 32])
+            .map_err(|e| e.to_string())?;
+        assert_eq!(call_stack.calls(), 3);
+        assert!(matches!(
+            call_stack.enter_internal(),
+            Err(ExecutionError::CallCountLimitExceededError(3))
+        ));
+
+        // The depth limit is checked ahead of the count limit.
+        assert!(matches!(
+            call_stack.enter_external([0x03; 32]),
+            Err(ExecutionError::CallDepthLimitExceededError(2))
+        ));
+        assert_eq!(call_stack.contracts().len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn call_stack_reentrancy_test() -> Result<(), String> {
+        let mut call_stack = CallStack::new([0x0a; 32], 8, 64);
+
+        // A calls B: no reentrancy.
+        call_stack
+            .enter_external([0x0b; 32])
+            .map_err(|e| e.to_string())?;
+        call_stack
+            .guard_reentrancy(false)
+            .map_err(|e| e.to_string())?;
+
+        // B calls A back: rejected, unless A opts in.
+        call_stack
+            .enter_external([0x0a; 32])
+            .map_err(|e| e.to_string())?;
+        assert!(matches!(
+            call_stack.clone().guard_reentrancy(false),
+            Err(ExecutionError::ReentrantCallError(contract_id)) if contract_id == [0x0a; 32]
+        ));
+        call_stack
+            .guard_reentrancy(true)
+            .map_err(|e| e.to_string())?;
+        assert_eq!(call_stack.reentered(), &[[0x0a; 32]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn call_stack_read_only_test() -> Result<(), String> {
+        // Only read calls may execute read-only methods.
+        assert!(!CallStack::new([0x0a; 32], 8, 64).read_only());
+        assert!(CallStack::new_read_only([0x0a; 32], 8, 64).read_only());
+
+        // Read calls are bounded like any other.
+        assert!(matches!(
+            CallStack::new_read_only([0x0a; 32], 0, 64).enter_external([0x0b; 32]),
+            Err(ExecutionError::CallDepthLimitExceededError(0))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn exec_receipt_test() -> Result<(), String> {
+        // Only opcodes running against a manager are traced.
+        let mut trace = ExecTrace::new();
+        trace.record([0x0a; 32], &Opcode::OP_TRUE(OP_TRUE));
+        trace.record([0x0a; 32], &Opcode::OP_SWRITE(OP_SWRITE));
+        trace.record([0x0b; 32], &Opcode::OP_TRANSFER(OP_TRANSFER));
+        assert_eq!(trace.ops().len(), 2);
+        assert_eq!(trace.ops()[0].target, ExecTraceTarget::StateManager);
+        assert_eq!(trace.ops()[1].target, ExecTraceTarget::CoinManager);
+        assert_eq!(trace.ops()[1].contract_id, [0x0b; 32]);
+
+        // A failed call has no ops or fees spent.
+        let receipt = ExecReceipt {
+            call_id: [0x01; 32],
+            timestamp: 1_700_000_000,
+            caller: [0x02; 32],
+            contracts: vec![[0x0a; 32], [0x0b; 32]],
+            trace: trace.ops().to_vec(),
+            reentered: Vec::new(),
+            ops_spent: None,
+            fees_spent: None,
+            error: Some(ExecutionError::CallCountLimitExceededError(64).to_string()),
+            usage: trace.usage(1_500),
+            host_reads: trace.host_reads().to_vec(),
+        };
+        assert!(!receipt.passed());
+
+        // Receipts survive a round trip.
+        let bytes = receipt.serialize().ok_or("serialize failed")?;
+        assert_eq!(ExecReceipt::deserialize(&bytes), Some(receipt.clone()));
+
+        let json = receipt.json();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["trace"][1]["opcode"], "OP_TRANSFER");
+        assert_eq!(json["trace"][1]["target"], "coin_manager");
+        assert!(json["ops_spent"].is_null());
+        assert_eq!(json["usage"]["wall_time_micros"], 1_500);
+
+        Ok(())
+    }
+
+    #[test]
+    fn exec_usage_test() -> Result<(), String> {
+        let mut trace = ExecTrace::new();
+
+        // Without a frame entered, nothing is counted.
+        trace.record_state_key(&Opcode::OP_SREAD(OP_SREAD), b"count");
+        trace.count_ops(10);
+        assert_eq!(trace.usage(0), ExecUsage::default());
+
+        // The called contract reads a key twice, writes it once and spends 120 ops.
+        trace.enter_frame([0x0a; 32], 0, 0);
+        trace.record_state_key(&Opcode::OP_SREAD(OP_SREAD), b"count");
+        trace.record_state_key(&Opcode::OP_SREAD(OP_SREAD), b"count");
+        trace.record_state_key(&Opcode::OP_SWRITE(OP_SWRITE), b"count");
+        trace.count_ops(120);
+
+        // It calls another contract, which reads the same key of its own storage, transfers and
+        // spends 80 more ops.
+        trace.enter_frame([0x0b; 32], 2, 120);
+        trace.record_state_key(&Opcode::OP_SREAD(OP_SREAD), b"count");
+        trace.record([0x0b; 32], &Opcode::OP_TRANSFER(OP_TRANSFER));
+        trace.count_ops(200);
+
+        let usage = trace.usage(2_000);
+        assert_eq!(usage.ops_used, 200);
+        assert_eq!(usage.state_keys_read, 2);
+        assert_eq!(usage.state_keys_written, 1);
+        assert_eq!(usage.coin_ops, 1);
+        assert_eq!(usage.wall_time_micros, 2_000);
+
+        // The usage is broken down per frame.
+        assert_eq!(usage.frames.len(), 2);
+        assert_eq!(usage.frames[0].ops_used, 120);
+        assert_eq!(usage.frames[0].state_keys_read, 1);
+        assert_eq!(usage.frames[0].coin_ops, 0);
+        assert_eq!(usage.frames[1].contract_id, [0x0b; 32]);
+        assert_eq!(usage.frames[1].method_index, 2);
+        assert_eq!(usage.frames[1].ops_used, 80);
+        assert_eq!(usage.frames[1].coin_ops, 1);
+
+        Ok(())
+    }
 }
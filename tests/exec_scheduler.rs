@@ -0,0 +1,29 @@
+#[cfg(test)]
+mod exec_scheduler_tests {
+    use cube::operative::tasks::engine_session::exec_scheduler::exec_priority::ExecPriority;
+
+    #[test]
+    fn exec_priority_test() -> Result<(), String> {
+        // Lower contract ranks outrank higher ones at equal flame value.
+        let top_ranked_call = ExecPriority::new(Some(1), 0, true);
+        let low_ranked_call = ExecPriority::new(Some(900), 0, true);
+        assert!(top_ranked_call.score() > low_ranked_call.score());
+
+        // Calls to unregistered contracts get no rank weight.
+        let unranked_call = ExecPriority::new(None, 0, true);
+        assert_eq!(unranked_call.rank_weight(), 0);
+
+        // Entries that do not call a contract sit in the middle.
+        let plain_entry = ExecPriority::new(None, 0, false);
+        assert!(plain_entry.score() < top_ranked_call.score());
+        assert!(plain_entry.score() > low_ranked_call.score());
+
+        // Caller flame value raises the score, up to a ceiling.
+        let flamed_entry = ExecPriority::new(None, 250_000, false);
+        assert_eq!(flamed_entry.score(), plain_entry.score() + 250);
+        let heavily_flamed_entry = ExecPriority::new(None, u64::MAX, false);
+        assert_eq!(heavily_flamed_entry.flame_weight(), 1_000);
+
+        Ok(())
+    }
+}
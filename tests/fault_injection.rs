@@ -0,0 +1,120 @@
+#[cfg(all(test, feature = "fault-injection"))]
+mod fault_injection_tests {
+    use cube::inscriptive::archival_manager::archival_manager::erase_archival_manager;
+    use cube::inscriptive::coin_manager::coin_manager::{
+        erase_coin_manager, CoinManager, COIN_MANAGER,
+    };
+    use cube::inscriptive::fault_injection::fault_injection::{
+        arm_fault, disarm_faults, fault_point, fired_faults,
+    };
+    use cube::inscriptive::fault_injection::fault_point::FaultPoint;
+    use cube::inscriptive::registery::registery::erase_registery;
+    use cube::inscriptive::state_manager::state_manager::erase_state_manager;
+    use cube::inscriptive::sync_manager::sync_manager::{
+        erase_sync_manager, SyncManager, SYNC_MANAGER,
+    };
+    use cube::operative::recovery::errors::ledger_inconsistency::LedgerInconsistency;
+    use cube::operative::recovery::recovery::check_ledger;
+    use cube::operative::run_args::chain::Chain;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    /// Account key.
+    const ACCOUNT_KEY: [u8; 32] = [0xa1; 32];
+
+    /// Erases the managers the ledger check opens.
+    fn erase_ledger(chain: Chain) {
+        erase_sync_manager(chain);
+        erase_coin_manager(chain);
+        erase_registery(chain);
+        erase_state_manager(chain);
+        erase_archival_manager(chain);
+    }
+
+    #[tokio::test]
+    async fn fault_injection_test() -> Result<(), String> {
+        // NOTE: Faults are process-wide, so everything is exercised in this one test.
+        let chain = Chain::Testbed;
+        erase_ledger(chain);
+        disarm_faults();
+
+        // 1 An armed fault fires on its given hit, and only once.
+        arm_fault(FaultPoint::UndoJournalWrite, 2);
+        fault_point(FaultPoint::UndoJournalWrite);
+        assert!(catch_unwind(|| fault_point(FaultPoint::UndoJournalWrite)).is_err());
+        fault_point(FaultPoint::UndoJournalWrite);
+        assert_eq!(fired_faults(), vec![FaultPoint::UndoJournalWrite]);
+
+        // 2 Crash midway through the coin manager changes of a commit.
+        {
+            // 2.1 Journal the start of the commit.
+            let sync_manager: SYNC_MANAGER =
+                SyncManager::new(chain).map_err(|e| format!("{:?}", e))?;
+            sync_manager.lock().await.begin_commit(1);
+
+            // 2.2 Register an account, and crash once it is written.
+            let coin_manager: COIN_MANAGER =
+                CoinManager::new(chain).map_err(|e| format!("{:?}", e))?;
+            let mut _coin_manager = coin_manager.lock().await;
+            _coin_manager
+                .register_account(ACCOUNT_KEY, 0)
+                .map_err(|e| format!("{:?}", e))?;
+            arm_fault(FaultPoint::CoinManagerAccountsRegistered, 1);
+            assert!(catch_unwind(AssertUnwindSafe(|| _coin_manager.apply_changes())).is_err());
+        }
+
+        // 3 On restart, the written account is found, and the commit is caught as incomplete.
+        {
+            let coin_manager: COIN_MANAGER =
+                CoinManager::new(chain).map_err(|e| format!("{:?}", e))?;
+            assert!(coin_manager.lock().await.is_account_registered(ACCOUNT_KEY));
+        }
+        assert_eq!(
+            check_ledger(chain).await.map_err(|e| format!("{:?}", e))?,
+            Some(LedgerInconsistency::IncompleteCommit(1))
+        );
+
+        // 4 Crash within the end of commit journal write.
+        {
+            let sync_manager: SYNC_MANAGER =
+                SyncManager::new(chain).map_err(|e| format!("{:?}", e))?;
+            let mut _sync_manager = sync_manager.lock().await;
+            arm_fault(FaultPoint::EndCommitJournalWrite, 1);
+            assert!(
+                catch_unwind(AssertUnwindSafe(|| _sync_manager.end_commit([0x11; 32]))).is_err()
+            );
+        }
+
+        // 5 On restart, the state root is written but the commit is still pending.
+        {
+            let sync_manager: SYNC_MANAGER =
+                SyncManager::new(chain).map_err(|e| format!("{:?}", e))?;
+            let _sync_manager = sync_manager.lock().await;
+            assert_eq!(_sync_manager.committed_state_root(), Some([0x11; 32]));
+            assert_eq!(_sync_manager.pending_commit_batch_height(), Some(1));
+        }
+
+        // 6 Once the commit completes, nothing is pending anymore.
+        {
+            let sync_manager: SYNC_MANAGER =
+                SyncManager::new(chain).map_err(|e| format!("{:?}", e))?;
+            sync_manager.lock().await.end_commit([0x11; 32]);
+            assert_eq!(
+                sync_manager.lock().await.pending_commit_batch_height(),
+                None
+            );
+        }
+        assert_eq!(
+            fired_faults(),
+            vec![
+                FaultPoint::UndoJournalWrite,
+                FaultPoint::CoinManagerAccountsRegistered,
+                FaultPoint::EndCommitJournalWrite
+            ]
+        );
+
+        // 7 Erase the managers.
+        erase_ledger(chain);
+
+        Ok(())
+    }
+}
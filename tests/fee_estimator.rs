@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod fee_estimator_tests {
+    use bitcoincore_rpc::json::EstimateMode;
+    use cube::operative::tasks::fee_estimator::fee_estimator::FeeEstimator;
+    use cube::operative::tasks::fee_estimator::fee_estimator_config::{
+        FeeEstimatorConfig, FeePurpose, FeeTarget,
+    };
+
+    #[test]
+    fn fee_target_test() -> Result<(), String> {
+        // Conservative unless economical is asked for.
+        assert_eq!(
+            FeeTarget::parse("3"),
+            Some(FeeTarget::new(3, EstimateMode::Conservative))
+        );
+        assert_eq!(
+            FeeTarget::parse(" 12 : Economical "),
+            Some(FeeTarget::new(12, EstimateMode::Economical))
+        );
+
+        // Targets are clamped to what Bitcoin Core estimates for.
+        assert_eq!(
+            FeeTarget::parse("0").map(|target| target.conf_target),
+            Some(1)
+        );
+        assert_eq!(
+            FeeTarget::parse("5000").map(|target| target.conf_target),
+            Some(1_008)
+        );
+
+        // Malformed targets.
+        assert_eq!(FeeTarget::parse(""), None);
+        assert_eq!(FeeTarget::parse("two"), None);
+        assert_eq!(FeeTarget::parse("2:fast"), None);
+
+        // Each purpose has its own target.
+        let config = FeeEstimatorConfig {
+            commitment: FeeTarget::new(2, EstimateMode::Conservative),
+            payout: FeeTarget::new(6, EstimateMode::Economical),
+            max_fee_rate: 1_000,
+        };
+        assert_eq!(config.target_for(FeePurpose::Commitment).conf_target, 2);
+        assert_eq!(
+            config.target_for(FeePurpose::Payout).mode_name(),
+            "economical"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn bounded_fee_rate_test() -> Result<(), String> {
+        // Estimates within bounds are kept.
+        assert_eq!(FeeEstimator::bounded_fee_rate(Some(12), 1, 1_000), 12);
+
+        // Never below the mempool minimum fee rate.
+        assert_eq!(FeeEstimator::bounded_fee_rate(Some(2), 5, 1_000), 5);
+
+        // Capped, unless the mempool minimum fee rate is above the cap.
+        assert_eq!(FeeEstimator::bounded_fee_rate(Some(2_500), 1, 1_000), 1_000);
+        assert_eq!(
+            FeeEstimator::bounded_fee_rate(Some(2_500), 1_200, 1_000),
+            1_200
+        );
+
+        // Without an estimate, the mempool minimum fee rate is used.
+        assert_eq!(FeeEstimator::bounded_fee_rate(None, 3, 1_000), 3);
+
+        Ok(())
+    }
+}
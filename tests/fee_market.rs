@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod fee_market_tests {
+    use cube::constructive::core_types::target::target::Target;
+    use cube::constructive::entity::account::account::account::Account;
+    use cube::constructive::entity::account::root_account::registered_and_configured_root_account::registered_and_configured_root_account::RegisteredAndConfiguredRootAccount;
+    use cube::constructive::entity::account::root_account::root_account::RootAccount;
+    use cube::constructive::entry::entry_kinds::r#move::r#move::Move;
+    use cube::operative::tasks::mempool::mempool_entry::{fee_rate, MempoolOrderKey};
+    use std::cmp::Reverse;
+
+    /// Returns a move of the given amount and priority fee.
+    fn move_entry(amount: u32, priority_fee: u32) -> Move {
+        Move::new(
+            RootAccount::RegisteredAndConfiguredRootAccount(
+                RegisteredAndConfiguredRootAccount::new([0xa1; 32], 0, [0x02; 48]),
+            ),
+            Account::new_registered_account([0xa2; 32], 1),
+            amount,
+            priority_fee,
+            Target::new(1),
+        )
+    }
+
+    #[test]
+    fn priority_fee_codec_test() -> Result<(), String> {
+        // 1 The priority fee round-trips through the SBE encoding.
+        let entry = move_entry(10_000, 250);
+        let bytes = entry.encode_sbe().map_err(|e| format!("{:?}", e))?;
+        let decoded = Move::decode_sbe(&bytes).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(decoded.amount, 10_000);
+        assert_eq!(decoded.priority_fee, 250);
+        assert_eq!(decoded.encode_sbe().map_err(|e| format!("{:?}", e))?, bytes);
+
+        // 2 A truncated priority fee is rejected.
+        assert!(Move::decode_sbe(&bytes[..bytes.len() - 10]).is_err());
+
+        // 3 The sighash commits to the priority fee.
+        assert_ne!(
+            entry.sighash().map_err(|e| format!("{:?}", e))?,
+            move_entry(10_000, 251)
+                .sighash()
+                .map_err(|e| format!("{:?}", e))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn fee_rate_order_test() {
+        // 1 The fee rate is the fee per 1000 bytes.
+        assert_eq!(fee_rate(100, 100), 1_000);
+        assert_eq!(fee_rate(1, 3), 333);
+        assert_eq!(fee_rate(100, 0), 0);
+        assert_eq!(fee_rate(u64::MAX, 1), u64::MAX);
+
+        // 2 Higher fee rates come first, regardless of the sender flame value and arrival.
+        let low: MempoolOrderKey = (Reverse(fee_rate(20, 100)), Reverse(1_000_000), 0);
+        let high: MempoolOrderKey = (Reverse(fee_rate(30, 100)), Reverse(0), 1);
+        assert!(high < low);
+
+        // 3 Equal fee rates fall back to the sender flame value, then to arrival.
+        let flamed: MempoolOrderKey = (Reverse(200), Reverse(10), 2);
+        let unflamed: MempoolOrderKey = (Reverse(200), Reverse(0), 0);
+        let later: MempoolOrderKey = (Reverse(200), Reverse(0), 1);
+        assert!(flamed < unflamed);
+        assert!(unflamed < later);
+    }
+}
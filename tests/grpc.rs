@@ -0,0 +1,372 @@
+#[cfg(all(test, feature = "grpc"))]
+mod grpc_tests {
+    use cube::communicative::event_stream::errors::subscription_filter_error::SubscriptionFilterError;
+    use cube::communicative::event_stream::event_stream::SubscriptionMessage;
+    use cube::communicative::event_stream::stream_event::{StreamEvent, StreamEventKind};
+    use cube::communicative::event_stream::subscription_filter::MAX_FILTER_KEYS;
+    use cube::communicative::grpc::conversions::{
+        event_kind, stream_event_kind, subscribe_reply, subscription_filter,
+    };
+    use cube::communicative::grpc::cube_query_server::CubeQueryServer;
+    use cube::communicative::grpc::messages::{
+        get_batch_request, subscribe_reply, EventKind, GetBatchRequest, SubscribeReply,
+        SubscribeRequest,
+    };
+    use prost::Message;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use tonic::server::NamedService;
+
+    /// A message field, or oneof member, as declared in the `.proto` file or the hand-written messages.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Field {
+        // Wire type as named by prost: a scalar type, `bytes`, `string`, `enumeration` or `message`.
+        kind: String,
+        // `repeated`, `optional`, or empty.
+        label: String,
+        // Field number.
+        tag: u32,
+    }
+
+    /// Messages by name, each with its fields by name; oneof members are keyed `oneof.member`.
+    type Messages = BTreeMap<String, BTreeMap<String, Field>>;
+
+    /// Reads a file of the crate.
+    fn read_crate_file(path: &str) -> Result<String, String> {
+        std::fs::read_to_string(format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path))
+            .map_err(|err| format!("Failed to read {}: {}", path, err))
+    }
+
+    /// Converts a snake case or screaming snake case name to camel case.
+    fn camel_case(name: &str) -> String {
+        name.split('_')
+            .map(|word| {
+                let word = word.to_lowercase();
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// Parses the messages, enums and rpcs declared in the `.proto` file.
+    fn parse_proto(proto: &str) -> Result<(Messages, BTreeMap<String, i32>, Vec<String>), String> {
+        let mut messages = Messages::new();
+        let mut enum_values = BTreeMap::new();
+        let mut rpcs = Vec::new();
+        let (mut message, mut oneof, mut in_enum) = (None::<String>, None::<String>, false);
+        for line in proto.lines() {
+            let line = line.split("//").next().unwrap_or("").trim();
+            let words: Vec<&str> = line
+                .split(|c: char| c.is_whitespace() || c == '=' || c == ';' || c == '(')
+                .filter(|word| !word.is_empty())
+                .collect();
+            match words.as_slice() {
+                ["message", name, ..] => {
+                    messages.insert(name.to_string(), BTreeMap::new());
+                    if !line.ends_with("{}") {
+                        message = Some(name.to_string());
+                    }
+                }
+                ["oneof", name, "{"] => oneof = Some(name.to_string()),
+                ["enum", _, "{"] => in_enum = true,
+                ["rpc", name, ..] => rpcs.push(name.to_string()),
+                ["}"] if oneof.is_some() => oneof = None,
+                ["}"] => (message, in_enum) = (None, false),
+                [value, number] if in_enum => {
+                    let number = number.parse().map_err(|_| line.to_string())?;
+                    enum_values.insert(value.to_string(), number);
+                }
+                [.., kind, name, number] if message.is_some() => {
+                    let label = match words[0] {
+                        "repeated" | "optional" => words[0].to_string(),
+                        _ => String::new(),
+                    };
+                    let kind = match *kind {
+                        "uint32" | "uint64" | "bytes" | "string" | "bool" => kind.to_string(),
+                        kind if kind.starts_with(char::is_uppercase) && kind.ends_with("Kind") => {
+                            "enumeration".to_string()
+                        }
+                        _ => "message".to_string(),
+                    };
+                    let tag = number.parse().map_err(|_| line.to_string())?;
+                    let name = match &oneof {
+                        Some(oneof) => format!("{}.{}", oneof, name),
+                        None => name.to_string(),
+                    };
+                    let message = message.as_ref().ok_or(line.to_string())?;
+                    messages
+                        .entry(message.clone())
+                        .or_default()
+                        .insert(name, Field { kind, label, tag });
+                }
+                _ => (),
+            }
+        }
+        Ok((messages, enum_values, rpcs))
+    }
+
+    /// Parses the hand-written prost messages, oneofs and enum.
+    fn parse_messages(source: &str) -> Result<(Messages, BTreeMap<String, i32>), String> {
+        let mut messages = Messages::new();
+        let mut enum_values = BTreeMap::new();
+        let (mut message, mut oneof_module) = (None::<String>, None::<String>);
+        let mut oneof_enum = None::<String>;
+        let mut attribute = None::<String>;
+        let mut in_enum = false;
+        for line in source.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("pub struct ") {
+                let name = name.trim_end_matches(" {}").trim_end_matches(" {");
+                messages.insert(name.to_string(), BTreeMap::new());
+                if !line.ends_with("{}") {
+                    message = Some(name.to_string());
+                }
+            } else if let Some(name) = line.strip_prefix("pub mod ") {
+                oneof_module = Some(name.trim_end_matches(" {").to_string());
+            } else if let Some(name) = line.strip_prefix("pub enum ") {
+                let name = name.trim_end_matches(" {").to_string();
+                match &oneof_module {
+                    Some(module) => oneof_enum = Some(format!("{}::{}", module, name)),
+                    None => in_enum = true,
+                }
+            } else if line.starts_with("#[prost(") {
+                attribute = Some(line.to_string());
+            } else if line == "}" {
+                match (&oneof_enum, &oneof_module) {
+                    (Some(_), _) => oneof_enum = None,
+                    (None, Some(_)) => oneof_module = None,
+                    (None, None) => (message, in_enum) = (None, false),
+                }
+            } else if in_enum {
+                if let Some((variant, value)) = line.trim_end_matches(',').split_once(" = ") {
+                    let value = value.parse().map_err(|_| line.to_string())?;
+                    enum_values.insert(variant.to_string(), value);
+                }
+            } else if let Some(attribute) = attribute.take() {
+                // 1 Read the kind, label and tag of the field out of its attribute.
+                let attribute = attribute
+                    .trim_start_matches("#[prost(")
+                    .trim_end_matches(")]");
+                let kind = attribute.split([',', ' ']).next().unwrap_or("").to_string();
+                let label = ["repeated", "optional"]
+                    .iter()
+                    .find(|label| attribute.contains(&format!(", {},", label)))
+                    .map(|label| label.to_string())
+                    .unwrap_or_default();
+                let tag = attribute
+                    .split("tag = \"")
+                    .nth(1)
+                    .and_then(|tag| tag.split('"').next())
+                    .and_then(|tag| tag.parse().ok());
+
+                // 2 Oneofs are resolved from their members.
+                if kind == "oneof" {
+                    continue;
+                }
+                let tag = tag.ok_or(attribute.to_string())?;
+
+                // 3 Record the field, or the oneof member.
+                let (message, name) = match &oneof_enum {
+                    Some(oneof_enum) => {
+                        let (module, oneof) = oneof_enum.split_once("::").unwrap_or_default();
+                        let member = line.split('(').next().unwrap_or("");
+                        (camel_case(module), format!("{}.{}", oneof, member))
+                    }
+                    None => {
+                        let name = line.trim_start_matches("pub ").split(':').next();
+                        let message = message.as_ref().ok_or(line.to_string())?;
+                        (message.clone(), name.unwrap_or("").to_string())
+                    }
+                };
+                messages
+                    .entry(message)
+                    .or_default()
+                    .insert(name, Field { kind, label, tag });
+            }
+        }
+        Ok((messages, enum_values))
+    }
+
+    #[test]
+    fn grpc_subscription_filter_test() -> Result<(), String> {
+        // 1 Convert a request following an account and a contract, for calls only.
+        let request = SubscribeRequest {
+            account_keys: vec![vec![0x01; 32]],
+            contract_ids: vec![vec![0x02; 32]],
+            kinds: vec![EventKind::Call as i32],
+            min_value: Some(1_000),
+        };
+        let filter = subscription_filter(&request).map_err(|e| e.to_string())?;
+        if !filter.account_keys.contains(&[0x01; 32]) || !filter.contract_ids.contains(&[0x02; 32])
+        {
+            return Err("Followed keys not converted.".to_string());
+        }
+        if !filter.kinds.contains(&StreamEventKind::Call) || filter.kinds.len() != 1 {
+            return Err("Event kinds not converted.".to_string());
+        }
+        if filter.min_value != Some(1_000) {
+            return Err("Minimum value not converted.".to_string());
+        }
+
+        // 2 Keys must be 32 bytes.
+        let request = SubscribeRequest {
+            account_keys: vec![vec![0x01; 31]],
+            ..Default::default()
+        };
+        match subscription_filter(&request) {
+            Err(SubscriptionFilterError::InvalidAccountKey(_)) => (),
+            _ => return Err("Short account key accepted.".to_string()),
+        }
+
+        // 3 Unspecified and unknown kinds are rejected.
+        for kind in [EventKind::Unspecified as i32, 42] {
+            let request = SubscribeRequest {
+                kinds: vec![kind],
+                ..Default::default()
+            };
+            match subscription_filter(&request) {
+                Err(SubscriptionFilterError::UnknownEventKind(_)) => (),
+                _ => return Err(format!("Event kind {} accepted.", kind)),
+            }
+        }
+
+        // 4 The number of followed keys is bounded like for WebSocket subscribers.
+        let request = SubscribeRequest {
+            account_keys: (0..=MAX_FILTER_KEYS).map(|_| vec![0x01; 32]).collect(),
+            ..Default::default()
+        };
+        match subscription_filter(&request) {
+            Err(SubscriptionFilterError::TooManyKeys(_)) => (),
+            _ => return Err("Too many keys accepted.".to_string()),
+        }
+
+        // 5 Every stream event kind maps back to itself.
+        for kind in [
+            StreamEventKind::Move,
+            StreamEventKind::Call,
+            StreamEventKind::Liftup,
+            StreamEventKind::Swapout,
+            StreamEventKind::Deploy,
+            StreamEventKind::Config,
+        ] {
+            if stream_event_kind(event_kind(kind)) != Some(kind) {
+                return Err(format!("Event kind {:?} does not round-trip.", kind));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn grpc_subscribe_reply_encoding_test() -> Result<(), String> {
+        // 1 Encode the reply of an applied entry.
+        let event = StreamEvent {
+            kind: StreamEventKind::Move,
+            batch_height: 7,
+            entry_id: [0x03; 32],
+            account_keys: vec![[0x01; 32]],
+            contract_ids: vec![],
+            value: Some(5_000),
+            entry: json!({ "kind": "move" }),
+        };
+        let reply = subscribe_reply(&SubscriptionMessage::Event(Arc::new(event)));
+        let bytes = reply.encode_to_vec();
+
+        // 2 Decode it back.
+        let decoded = SubscribeReply::decode(bytes.as_slice()).map_err(|e| e.to_string())?;
+        let event = match decoded.message {
+            Some(subscribe_reply::Message::Event(event)) => event,
+            _ => return Err("Event reply not decoded.".to_string()),
+        };
+        if event.kind != EventKind::Move as i32
+            || event.batch_height != 7
+            || event.entry_id != vec![0x03; 32]
+            || event.account_keys != vec![vec![0x01; 32]]
+            || event.value != Some(5_000)
+        {
+            return Err("Event fields do not round-trip.".to_string());
+        }
+        let entry: serde_json::Value =
+            serde_json::from_str(&event.entry_json).map_err(|e| e.to_string())?;
+        if entry != json!({ "kind": "move" }) {
+            return Err("Entry JSON does not round-trip.".to_string());
+        }
+
+        // 3 Lagged replies carry the number of skipped events.
+        let reply = subscribe_reply(&SubscriptionMessage::Lagged(3));
+        let decoded =
+            SubscribeReply::decode(reply.encode_to_vec().as_slice()).map_err(|e| e.to_string())?;
+        match decoded.message {
+            Some(subscribe_reply::Message::Lagged(3)) => (),
+            _ => return Err("Lagged reply not decoded.".to_string()),
+        }
+
+        // 4 The batch selector oneof round-trips.
+        let request = GetBatchRequest {
+            selector: Some(get_batch_request::Selector::Height(42)),
+        };
+        let decoded = GetBatchRequest::decode(request.encode_to_vec().as_slice())
+            .map_err(|e| e.to_string())?;
+        if decoded != request {
+            return Err("Batch request does not round-trip.".to_string());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn grpc_proto_drift_test() -> Result<(), String> {
+        // 1 Parse the `.proto` file, the hand-written messages and the routed method paths.
+        let (proto_messages, proto_enum_values, rpcs) =
+            parse_proto(&read_crate_file("proto/cube/v1/cube.proto")?)?;
+        let (messages, enum_values) =
+            parse_messages(&read_crate_file("src/communicative/grpc/messages.rs")?)?;
+        let server = read_crate_file("src/communicative/grpc/cube_query_server.rs")?;
+
+        // 2 The service is named after the package and the service of the `.proto` file.
+        assert_eq!(CubeQueryServer::NAME, "cube.v1.CubeQuery");
+
+        // 3 Every rpc is routed, and nothing else is.
+        let routed: Vec<&str> = server
+            .split("\"/cube.v1.CubeQuery/")
+            .skip(1)
+            .filter_map(|path| path.split('"').next())
+            .collect();
+        assert_eq!(routed.len(), rpcs.len(), "Routed methods: {:?}", routed);
+        for rpc in rpcs.iter() {
+            assert!(routed.contains(&rpc.as_str()), "{} is not routed.", rpc);
+        }
+
+        // 4 Every message has the same fields, with the same tags, kinds and labels.
+        // Oneof members are named after their camel case variant on the Rust side.
+        let proto_messages: Messages = proto_messages
+            .into_iter()
+            .map(|(message, fields)| {
+                let fields = fields
+                    .into_iter()
+                    .map(|(name, field)| match name.split_once('.') {
+                        Some((oneof, member)) => (
+                            format!("{}.{}", camel_case(oneof), camel_case(member)),
+                            field,
+                        ),
+                        None => (name, field),
+                    })
+                    .collect();
+                (message, fields)
+            })
+            .collect();
+        assert_eq!(messages, proto_messages);
+
+        // 5 The event kinds have the same values, without their enum name prefix on the Rust side.
+        let proto_enum_values: BTreeMap<String, i32> = proto_enum_values
+            .into_iter()
+            .map(|(value, number)| (camel_case(value.trim_start_matches("EVENT_KIND_")), number))
+            .collect();
+        assert_eq!(enum_values, proto_enum_values);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod halfagg_tests {
+    use cube::transmutative::secp::halfagg;
+    use cube::transmutative::secp::schnorr::{self, Bytes32, SchnorrSigningMode};
+
+    /// Signs a distinct message with a fresh key for each index.
+    fn signatures(count: u8, mode: SchnorrSigningMode) -> Vec<([u8; 32], [u8; 32], [u8; 64])> {
+        (0..count)
+            .map(|index| {
+                let secret_key = schnorr::generate_secret();
+                let public_key = secret_key.secret_to_public().unwrap();
+                let message = [index; 32];
+                let signature = schnorr::sign(secret_key, message, mode.clone()).unwrap();
+                (public_key, message, signature)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn half_aggregate() -> Result<(), String> {
+        let signatures = signatures(8, SchnorrSigningMode::Cube);
+        let public_keys_and_messages: Vec<([u8; 32], [u8; 32])> = signatures
+            .iter()
+            .map(|(public_key, message, _)| (*public_key, *message))
+            .collect();
+
+        let aggregate_signature =
+            halfagg::aggregate(&signatures).ok_or("Failed to aggregate signatures.".to_string())?;
+
+        // Half the commitments are saved.
+        assert_eq!(aggregate_signature.len(), 32 * 9);
+
+        assert!(halfagg::verify_aggregate(
+            &public_keys_and_messages,
+            &aggregate_signature,
+            SchnorrSigningMode::Cube
+        ));
+
+        // Only in the signing mode of the signatures.
+        assert!(!halfagg::verify_aggregate(
+            &public_keys_and_messages,
+            &aggregate_signature,
+            SchnorrSigningMode::BIP340
+        ));
+
+        // Only in the order of aggregation.
+        let mut reordered = public_keys_and_messages.clone();
+        reordered.swap(1, 2);
+        assert!(!halfagg::verify_aggregate(
+            &reordered,
+            &aggregate_signature,
+            SchnorrSigningMode::Cube
+        ));
+
+        // Not with a missing signer.
+        assert!(!halfagg::verify_aggregate(
+            &public_keys_and_messages[1..],
+            &aggregate_signature,
+            SchnorrSigningMode::Cube
+        ));
+
+        // Not with a tampered commitment sum.
+        let mut tampered = aggregate_signature.clone();
+        tampered[32 * 8] ^= 0x01;
+        assert!(!halfagg::verify_aggregate(
+            &public_keys_and_messages,
+            &tampered,
+            SchnorrSigningMode::Cube
+        ));
+
+        // An invalid signature invalidates the aggregate.
+        let mut forged = signatures.clone();
+        forged[3].2[63] ^= 0x01;
+        let forged_aggregate_signature =
+            halfagg::aggregate(&forged).ok_or("Failed to aggregate signatures.".to_string())?;
+        assert!(!halfagg::verify_aggregate(
+            &public_keys_and_messages,
+            &forged_aggregate_signature,
+            SchnorrSigningMode::Cube
+        ));
+
+        // Nothing to aggregate.
+        assert_eq!(halfagg::aggregate(&[]), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn half_aggregate_bip340() -> Result<(), String> {
+        let signatures = signatures(3, SchnorrSigningMode::BIP340);
+        let public_keys_and_messages: Vec<([u8; 32], [u8; 32])> = signatures
+            .iter()
+            .map(|(public_key, message, _)| (*public_key, *message))
+            .collect();
+
+        let aggregate_signature =
+            halfagg::aggregate(&signatures).ok_or("Failed to aggregate signatures.".to_string())?;
+
+        assert!(halfagg::verify_aggregate(
+            &public_keys_and_messages,
+            &aggregate_signature,
+            SchnorrSigningMode::BIP340
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+#[cfg(test)]
+mod handshake_tests {
+    use cube::communicative::handshake::announcement::{
+        negotiate_protocol_version, HandshakeAnnouncement, HANDSHAKE_PROTOCOL_VERSION,
+    };
+    use cube::communicative::handshake::capability::Capability;
+    use cube::communicative::handshake::errors::handshake_error::HandshakeError;
+    use cube::communicative::handshake::errors::heartbeat_error::HeartbeatError;
+    use cube::communicative::handshake::heartbeat::Heartbeat;
+    use cube::communicative::handshake::liveness::Liveness;
+    use cube::communicative::handshake::operator_sessions::OperatorSessions;
+    use cube::communicative::handshake::protocol_feature::ProtocolFeature;
+    use cube::communicative::handshake::session_params::SessionParams;
+    use cube::communicative::handshake::work_assignment::WorkAssignment;
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+
+    #[tokio::test]
+    async fn handshake_test() -> Result<(), String> {
+        let engine_keys = KeyHolder::new([0x11u8; 32]).expect("Failed to create key holder.");
+        let operator_keys = KeyHolder::new([0x22u8; 32]).expect("Failed to create key holder.");
+        let engine_key = engine_keys.secp_public_key_bytes();
+        let now = 1_700_000_000;
+
+        let announcement = HandshakeAnnouncement::sign(
+            &operator_keys,
+            vec![Capability::Mempool, Capability::InFlightSync],
+            42,
+            now,
+        )
+        .ok_or("Failed to sign the announcement.")?;
+        assert!(announcement.verify());
+
+        let operator_sessions = OperatorSessions::new();
+        let mut _operator_sessions = operator_sessions.lock().await;
+
+        // A tampered announcement is rejected.
+        let mut tampered = announcement.clone();
+        tampered.cube_batch_sync_height += 1;
+        assert_eq!(
+            _operator_sessions
+                .register(&tampered, engine_key, now)
+                .err(),
+            Some(HandshakeError::InvalidSignature)
+        );
+
+        // A stale announcement is rejected.
+        assert!(matches!(
+            _operator_sessions.register(&announcement, engine_key, now + 600),
+            Err(HandshakeError::ClockSkewTooLarge { .. })
+        ));
+
+        // The engine does not register with itself.
+        let engine_announcement = HandshakeAnnouncement::sign(&engine_keys, vec![], 42, now)
+            .ok_or("Failed to sign the announcement.")?;
+        assert_eq!(
+            _operator_sessions
+                .register(&engine_announcement, engine_key, now)
+                .err(),
+            Some(HandshakeError::EngineKeyCannotRegister)
+        );
+
+        // A valid announcement registers the operator.
+        let session_id = _operator_sessions
+            .register(&announcement, engine_key, now)
+            .map_err(|err| format!("{:?}", err))?
+            .session_id;
+        assert_eq!(_operator_sessions.len(), 1);
+
+        // Replaying the same announcement is rejected.
+        assert!(matches!(
+            _operator_sessions.register(&announcement, engine_key, now),
+            Err(HandshakeError::ReplayedAnnouncement { .. })
+        ));
+
+        // Session params verify against the engine key only.
+        let session_params = SessionParams::sign(
+            &engine_keys,
+            session_id,
+            announcement.operator_key,
+            announcement.protocol_version,
+            announcement.features,
+            100,
+            now,
+        )
+        .ok_or("Failed to sign the session params.")?;
+        assert!(session_params.verify(engine_key));
+        assert!(!session_params.verify(announcement.operator_key));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn protocol_negotiation_test() -> Result<(), String> {
+        let engine_key = KeyHolder::new([0x11u8; 32])
+            .expect("Failed to create key holder.")
+            .secp_public_key_bytes();
+        let operator_keys = KeyHolder::new([0x22u8; 32]).expect("Failed to create key holder.");
+        let now = 1_700_000_000;
+
+        // The highest common version wins, and disjoint ranges do not negotiate.
+        assert_eq!(negotiate_protocol_version(1, 3, 2, 5), Some(3));
+        assert_eq!(negotiate_protocol_version(2, 5, 1, 3), Some(3));
+        assert_eq!(negotiate_protocol_version(1, 1, 1, 1), Some(1));
+        assert_eq!(negotiate_protocol_version(1, 2, 3, 4), None);
+
+        // Feature bitmaps round-trip and ignore unknown bits.
+        let bitmap = ProtocolFeature::bitmap(&[ProtocolFeature::ReplayProtection]);
+        assert_eq!(
+            ProtocolFeature::from_bitmap(bitmap | (1 << 63)),
+            vec![ProtocolFeature::ReplayProtection]
+        );
+
+        let operator_sessions = OperatorSessions::new();
+        let mut _operator_sessions = operator_sessions.lock().await;
+
+        // An operator speaking only newer versions is refused with both ranges.
+        let mut announcement = HandshakeAnnouncement::sign(&operator_keys, vec![], 0, now)
+            .ok_or("Failed to sign the announcement.")?;
+        announcement.min_protocol_version = HANDSHAKE_PROTOCOL_VERSION + 1;
+        announcement.protocol_version = HANDSHAKE_PROTOCOL_VERSION + 2;
+        announcement.signature = schnorr::sign(
+            operator_keys.secp_secret_key_bytes(),
+            announcement.message(),
+            SchnorrSigningMode::Cube,
+        )
+        .ok_or("Failed to sign the announcement.")?;
+        assert_eq!(
+            _operator_sessions
+                .register(&announcement, engine_key, now)
+                .err(),
+            Some(HandshakeError::ProtocolVersionMismatch {
+                min_supported: HANDSHAKE_PROTOCOL_VERSION,
+                max_supported: HANDSHAKE_PROTOCOL_VERSION,
+                min_announced: HANDSHAKE_PROTOCOL_VERSION + 1,
+                max_announced: HANDSHAKE_PROTOCOL_VERSION + 2,
+            })
+        );
+
+        // An operator with an overlapping range registers at the highest common version, with the
+        // features both sides support.
+        announcement.min_protocol_version = HANDSHAKE_PROTOCOL_VERSION;
+        announcement.features = ProtocolFeature::bitmap(&[ProtocolFeature::Envelope]) | (1 << 63);
+        announcement.signature = schnorr::sign(
+            operator_keys.secp_secret_key_bytes(),
+            announcement.message(),
+            SchnorrSigningMode::Cube,
+        )
+        .ok_or("Failed to sign the announcement.")?;
+        let session = _operator_sessions
+            .register(&announcement, engine_key, now)
+            .map_err(|err| format!("{:?}", err))?;
+        assert_eq!(session.protocol_version, HANDSHAKE_PROTOCOL_VERSION);
+        assert!(session.has_feature(ProtocolFeature::Envelope));
+        assert!(!session.has_feature(ProtocolFeature::NoiseTransport));
+        assert_eq!(
+            session.features,
+            ProtocolFeature::bitmap(&[ProtocolFeature::Envelope])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn liveness_test() -> Result<(), String> {
+        let engine_key = KeyHolder::new([0x11u8; 32])
+            .expect("Failed to create key holder.")
+            .secp_public_key_bytes();
+        let first_keys = KeyHolder::new([0x22u8; 32]).expect("Failed to create key holder.");
+        let second_keys = KeyHolder::new([0x33u8; 32]).expect("Failed to create key holder.");
+        let now = 1_700_000_000;
+
+        let operator_sessions = OperatorSessions::new();
+        let mut _operator_sessions = operator_sessions.lock().await;
+
+        // Register two coordinators.
+        let mut session_ids = Vec::new();
+        for keys in [&first_keys, &second_keys] {
+            let announcement =
+                HandshakeAnnouncement::sign(keys, vec![Capability::DeltaCosign], 0, now)
+                    .ok_or("Failed to sign the announcement.")?;
+            let session_id = _operator_sessions
+                .register(&announcement, engine_key, now)
+                .map_err(|err| format!("{:?}", err))?
+                .session_id;
+            session_ids.push(session_id);
+        }
+
+        // The work goes to a single coordinator.
+        let work = WorkAssignment::DeltaCosign {
+            batch_height: 7,
+            batch_txid: [0xaau8; 32],
+        };
+        let assignees = _operator_sessions.assign(work, 1);
+        assert_eq!(assignees.len(), 1);
+        let (assignee_keys, other_keys, other_session_id) =
+            match assignees[0] == first_keys.secp_public_key_bytes() {
+                true => (&first_keys, &second_keys, session_ids[1]),
+                false => (&second_keys, &first_keys, session_ids[0]),
+            };
+
+        // Only the other coordinator keeps sending heartbeats.
+        let heartbeat = Heartbeat::sign(other_keys, other_session_id, 7, now + 30)
+            .ok_or("Failed to sign the heartbeat.")?;
+        assert!(_operator_sessions.heartbeat(&heartbeat, now + 30).is_ok());
+        assert!(matches!(
+            _operator_sessions.heartbeat(&heartbeat, now + 30),
+            Err(HeartbeatError::ReplayedHeartbeat { .. })
+        ));
+
+        // The silent assignee goes offline and its work moves to the other coordinator.
+        let timed_out_operators = _operator_sessions.sweep(now + 70);
+        assert_eq!(
+            timed_out_operators,
+            vec![assignee_keys.secp_public_key_bytes()]
+        );
+        let other_session = _operator_sessions
+            .session(other_keys.secp_public_key_bytes())
+            .ok_or("Missing session.")?;
+        assert_eq!(other_session.liveness, Liveness::Online);
+        assert_eq!(other_session.pending_work, vec![work]);
+        assert_eq!(_operator_sessions.online_count(), 1);
+
+        // The offline assignee has to register again.
+        let assignee_session_id = _operator_sessions
+            .session(assignee_keys.secp_public_key_bytes())
+            .ok_or("Missing session.")?
+            .session_id;
+        let heartbeat = Heartbeat::sign(assignee_keys, assignee_session_id, 7, now + 70)
+            .ok_or("Failed to sign the heartbeat.")?;
+        assert_eq!(
+            _operator_sessions.heartbeat(&heartbeat, now + 70).err(),
+            Some(HeartbeatError::SessionExpired(now + 70))
+        );
+
+        // Settling the batch drops the work.
+        _operator_sessions.settle(7);
+        let other_session = _operator_sessions
+            .session(other_keys.secp_public_key_bytes())
+            .ok_or("Missing session.")?;
+        assert!(other_session.pending_work.is_empty());
+
+        Ok(())
+    }
+}
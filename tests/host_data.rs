@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod host_data_tests {
+    use cube::executive::{
+        opcode::opcodes::callinfo::op_hostdata::OP_HOSTDATA,
+        stack::{
+            stack_error::{HostDataError, StackError},
+            stack_holder::StackHolder,
+            stack_item::StackItem,
+            stack_uint::{SafeConverter, StackItemUintExt, StackUint},
+        },
+        vm::program_execution::{
+            caller::Caller,
+            exec_trace::ExecTrace,
+            host_data::{AttestedHostData, ExecHostRead, HostData},
+        },
+    };
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::secp::schnorr;
+    use std::collections::BTreeMap;
+
+    /// Returns host data with a single `btcusd` price feed.
+    fn host_data() -> HostData {
+        HostData {
+            block_height: 840_000,
+            median_time: 1_713_571_767,
+            price_feeds: BTreeMap::from([("btcusd".to_string(), 64_000)]),
+        }
+    }
+
+    /// Returns a fresh stack holder of the given contract.
+    fn stack_holder(contract_id: [u8; 32]) -> Result<StackHolder, StackError> {
+        StackHolder::new(
+            Caller::new_account([0; 32]),
+            contract_id,
+            1715619200,
+            0,
+            10_000,
+            1,
+            0,
+            0,
+        )
+    }
+
+    /// Returns the stack item of the integer.
+    fn uint_item(value: u64) -> StackItem {
+        StackItem::from_stack_uint(StackUint::from_u64(value))
+    }
+
+    #[test]
+    fn host_data_value_test() {
+        let host_data = host_data();
+        assert_eq!(host_data.value(b"block_height"), Some(840_000));
+        assert_eq!(host_data.value(b"median_time"), Some(1_713_571_767));
+        assert_eq!(host_data.value(b"price/btcusd"), Some(64_000));
+        assert_eq!(host_data.value(b"price/ethusd"), None);
+        assert_eq!(host_data.value(b"price/"), None);
+        assert_eq!(host_data.value(b"btcusd"), None);
+    }
+
+    #[test]
+    fn host_data_attestation_test() -> Result<(), String> {
+        let coordinator = KeyHolder::new(schnorr::generate_secret())
+            .ok_or("Failed to construct coordinator key.".to_string())?;
+
+        // A signed attestation verifies.
+        let attested = AttestedHostData::sign(&coordinator, host_data())
+            .ok_or("Failed to sign host data.".to_string())?;
+        assert_eq!(
+            attested.coordinator_key,
+            coordinator.secp_public_key_bytes()
+        );
+        assert!(attested.verify());
+
+        // Tampering with any value breaks the attestation.
+        let mut tampered = attested.clone();
+        tampered
+            .host_data
+            .price_feeds
+            .insert("btcusd".to_string(), 1);
+        assert!(!tampered.verify());
+
+        let mut tampered = attested.clone();
+        tampered.host_data.block_height += 1;
+        assert!(!tampered.verify());
+
+        // Adding a feed breaks the attestation too.
+        let mut tampered = attested;
+        tampered
+            .host_data
+            .price_feeds
+            .insert("ethusd".to_string(), 3_000);
+        assert!(!tampered.verify());
+
+        Ok(())
+    }
+
+    #[test]
+    fn op_hostdata_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder([0xaa; 32])?;
+        let mut trace = ExecTrace::with_host_data(Some(host_data()));
+
+        // A set key pushes its value with true.
+        stack_holder.push(StackItem::new(b"price/btcusd".to_vec()))?;
+        OP_HOSTDATA::execute(&mut stack_holder, &mut trace)?;
+        assert_eq!(stack_holder.pop()?, StackItem::true_item());
+        assert_eq!(stack_holder.pop()?, uint_item(64_000));
+
+        // An unset key pushes false.
+        stack_holder.push(StackItem::new(b"price/ethusd".to_vec()))?;
+        OP_HOSTDATA::execute(&mut stack_holder, &mut trace)?;
+        assert_eq!(stack_holder.pop()?, StackItem::false_item());
+        assert_eq!(stack_holder.stack_items_count(), 0);
+
+        // Both reads are recorded, in order.
+        assert_eq!(
+            trace.host_reads(),
+            &[
+                ExecHostRead {
+                    contract_id: [0xaa; 32],
+                    key: b"price/btcusd".to_vec(),
+                    value: Some(64_000),
+                },
+                ExecHostRead {
+                    contract_id: [0xaa; 32],
+                    key: b"price/ethusd".to_vec(),
+                    value: None,
+                },
+            ]
+        );
+
+        // An empty key fails.
+        stack_holder.push(StackItem::new(vec![]))?;
+        assert!(matches!(
+            OP_HOSTDATA::execute(&mut stack_holder, &mut trace),
+            Err(StackError::HostDataError(
+                HostDataError::InvalidHostDataKeyLength(0)
+            ))
+        ));
+
+        // A key over 32 bytes fails.
+        stack_holder.push(StackItem::new(vec![0x01; 33]))?;
+        assert!(matches!(
+            OP_HOSTDATA::execute(&mut stack_holder, &mut trace),
+            Err(StackError::HostDataError(
+                HostDataError::InvalidHostDataKeyLength(33)
+            ))
+        ));
+
+        // Reading without host data fails.
+        let mut trace = ExecTrace::new();
+        stack_holder.push(StackItem::new(b"block_height".to_vec()))?;
+        assert!(matches!(
+            OP_HOSTDATA::execute(&mut stack_holder, &mut trace),
+            Err(StackError::HostDataError(
+                HostDataError::HostDataUnavailable
+            ))
+        ));
+        assert!(trace.host_reads().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod in_memory_storage_tests {
+    use cube::inscriptive::storage::storage::{erase_db, open_db, storage_mode};
+    use cube::inscriptive::storage::storage_mode::StorageMode;
+    use cube::operative::run_args::chain::Chain;
+    use std::path::Path;
+
+    /// A db path no manager uses.
+    const DB_PATH: &str = "storage/testbed/in_memory_storage_test";
+
+    #[test]
+    fn in_memory_storage_test() -> Result<(), String> {
+        // 1 Keep the Testbed chain in memory.
+        // NOTE: This is the only test of the binary, so the mode is read after it is set.
+        std::env::set_var("CUBE_TESTBED_STORAGE", "memory");
+        assert_eq!(storage_mode(Chain::Testbed), StorageMode::InMemory);
+        assert_eq!(storage_mode(Chain::Signet), StorageMode::OnDisk);
+        assert_eq!(storage_mode(Chain::Mainnet), StorageMode::OnDisk);
+
+        // 2 Open the db and insert a value.
+        erase_db(Chain::Testbed, DB_PATH);
+        let db = open_db(Chain::Testbed, DB_PATH).map_err(|e| format!("{:?}", e))?;
+        db.insert(b"key", b"value".to_vec())
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 3 Nothing is written under the storage path.
+        assert!(!Path::new(DB_PATH).exists());
+
+        // 4 The db is found again when opened again within the process.
+        drop(db);
+        let db = open_db(Chain::Testbed, DB_PATH).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            db.get(b"key")
+                .map_err(|e| format!("{:?}", e))?
+                .map(|value| value.to_vec()),
+            Some(b"value".to_vec())
+        );
+
+        // 5 An erased db is opened again empty.
+        drop(db);
+        erase_db(Chain::Testbed, DB_PATH);
+        let db = open_db(Chain::Testbed, DB_PATH).map_err(|e| format!("{:?}", e))?;
+        assert!(db.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod inbound_gate_tests {
+    use cube::communicative::inbound::inbound_gate::{
+        InboundAdmission, InboundGate, LOW_PRIORITY_PROCESSING_LIMIT, MAX_DEFERRED_PACKAGES,
+        MAX_PROCESSING_PACKAGES,
+    };
+    use cube::communicative::inbound::inbound_priority::InboundPriority;
+    use cube::communicative::tcp::package::PackageKind;
+
+    #[test]
+    fn inbound_priority_test() -> Result<(), String> {
+        // Entries and signing rounds are deferred, lookups are shed.
+        assert_eq!(
+            InboundPriority::of(PackageKind::MoveProtocol),
+            InboundPriority::High
+        );
+        assert_eq!(
+            InboundPriority::of(PackageKind::DeltaCosignProtocol),
+            InboundPriority::High
+        );
+        assert_eq!(InboundPriority::of(PackageKind::Ping), InboundPriority::Low);
+        assert_eq!(
+            InboundPriority::of(PackageKind::BatchContainerProtocol),
+            InboundPriority::Low
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn inbound_gate_test() -> Result<(), String> {
+        let mut inbound_gate = InboundGate::new();
+
+        // Low-priority packages are admitted until processing nears its bound.
+        for _ in 0..LOW_PRIORITY_PROCESSING_LIMIT {
+            assert_eq!(
+                inbound_gate.try_admit(InboundPriority::Low),
+                InboundAdmission::Admitted
+            );
+        }
+        assert_eq!(
+            inbound_gate.try_admit(InboundPriority::Low),
+            InboundAdmission::Shed
+        );
+        assert_eq!(inbound_gate.shed(InboundPriority::Low), 1);
+
+        // High-priority packages take the remaining slots.
+        for _ in LOW_PRIORITY_PROCESSING_LIMIT..MAX_PROCESSING_PACKAGES {
+            assert_eq!(
+                inbound_gate.try_admit(InboundPriority::High),
+                InboundAdmission::Admitted
+            );
+        }
+        assert_eq!(inbound_gate.processing(), MAX_PROCESSING_PACKAGES);
+
+        // Then they are deferred until the wait is full, and shed past it.
+        for _ in 0..MAX_DEFERRED_PACKAGES {
+            assert_eq!(
+                inbound_gate.try_admit(InboundPriority::High),
+                InboundAdmission::Deferred
+            );
+        }
+        assert_eq!(
+            inbound_gate.try_admit(InboundPriority::High),
+            InboundAdmission::Shed
+        );
+        assert_eq!(inbound_gate.shed(InboundPriority::High), 1);
+        assert_eq!(inbound_gate.deferred(), MAX_DEFERRED_PACKAGES);
+
+        // A deferred package only gets a slot once one is released.
+        assert!(!inbound_gate.try_admit_deferred());
+        inbound_gate.release();
+        assert!(inbound_gate.try_admit_deferred());
+        assert_eq!(inbound_gate.processing(), MAX_PROCESSING_PACKAGES);
+        assert_eq!(inbound_gate.deferred(), MAX_DEFERRED_PACKAGES - 1);
+
+        // Low-priority packages are shed while anything is waiting, even with free slots.
+        inbound_gate.release();
+        inbound_gate.release();
+        assert_eq!(
+            inbound_gate.try_admit(InboundPriority::Low),
+            InboundAdmission::Shed
+        );
+
+        // Abandoned packages give up their place in the wait.
+        for _ in 0..MAX_DEFERRED_PACKAGES - 1 {
+            inbound_gate.abandon_deferred();
+        }
+        assert_eq!(inbound_gate.deferred(), 0);
+        assert_eq!(
+            inbound_gate.try_admit(InboundPriority::High),
+            InboundAdmission::Admitted
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
 #[cfg(test)]
 mod key_tests {
-    use cube::transmutative::key::{FromNostrKeyStr, ToNostrKeyStr};
+    use bitcoin::bip32::{DerivationPath, Xpriv};
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::NetworkKind;
+    use cube::constructive::bitcoiny::taproot::{TapLeaf, TapRoot};
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::key::{
+        FromNcryptsecStr, FromNostrKeyStr, KeyHolder, KeyPurpose, ToNcryptsecStr, ToNostrKeyStr,
+    };
+    use cube::transmutative::secp::schnorr::{self, SchnorrSigningMode};
     use hex;
+    use std::str::FromStr;
 
     #[test]
     fn to_nsec() -> Result<(), String> {
@@ -80,4 +89,187 @@ mod key_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn derive_for_purpose() -> Result<(), String> {
+        let secret_key_bytes: [u8; 32] =
+            hex::decode("bceef655b5a034911f1c3718ce056531b45ef03b4c7b1f15629e867294011a7d")
+                .map_err(|_| format!("Failed to parse secret key hex."))?
+                .try_into()
+                .map_err(|_| "Invalid key length. Expected 32 bytes.".to_string())?;
+
+        let key_holder = KeyHolder::new(secret_key_bytes)
+            .ok_or_else(|| "Failed to construct key holder.".to_string())?;
+        assert!(key_holder.derivation_path().is_none());
+
+        let node_identity = key_holder
+            .derive_for(KeyPurpose::NodeIdentity)
+            .ok_or_else(|| "Failed to derive the node identity key.".to_string())?;
+        let operator_signing = key_holder
+            .derive_for(KeyPurpose::OperatorSigning)
+            .ok_or_else(|| "Failed to derive the operator signing key.".to_string())?;
+
+        // The derived key records its path.
+        assert_eq!(
+            node_identity.derivation_path(),
+            KeyPurpose::NodeIdentity.derivation_path().as_ref()
+        );
+
+        // Derivation is deterministic.
+        let node_identity_again = key_holder
+            .derive_for(KeyPurpose::NodeIdentity)
+            .ok_or_else(|| "Failed to derive the node identity key.".to_string())?;
+        assert_eq!(node_identity.npub(), node_identity_again.npub());
+
+        // Each purpose gets its own key, distinct from the root key.
+        assert_ne!(node_identity.npub(), operator_signing.npub());
+        assert_ne!(node_identity.npub(), key_holder.npub());
+        assert_ne!(
+            key_holder
+                .derive_for(KeyPurpose::Deposit(0))
+                .map(|key_holder| key_holder.npub()),
+            key_holder
+                .derive_for(KeyPurpose::Deposit(1))
+                .map(|key_holder| key_holder.npub())
+        );
+
+        // Aggregation keys do not collide with deposit keys at the same index.
+        assert_ne!(
+            key_holder
+                .derive_for(KeyPurpose::Aggregation(0))
+                .map(|key_holder| key_holder.npub()),
+            key_holder
+                .derive_for(KeyPurpose::Deposit(0))
+                .map(|key_holder| key_holder.npub())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_ncryptsec() -> Result<(), String> {
+        // NIP-49 test vector.
+        let ncryptsec_str = "ncryptsec1qgg9947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wclt0h4p";
+
+        let secret_key_bytes: [u8; 32] = ncryptsec_str
+            .from_ncryptsec("nostr")
+            .ok_or_else(|| "Failed to decrypt ncryptsec str to secret key.".to_string())?;
+
+        let expected_secret_key_bytes: [u8; 32] =
+            hex::decode("3501454135014541350145413501453fefb02227e449e57cf4d3a3ce05378683")
+                .map_err(|_| format!("Failed to parse secret key hex."))?
+                .try_into()
+                .map_err(|_| "Invalid key length. Expected 32 bytes.".to_string())?;
+
+        assert_eq!(expected_secret_key_bytes, secret_key_bytes);
+
+        // A wrong passphrase fails to decrypt.
+        assert_eq!(ncryptsec_str.from_ncryptsec("wrong"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_ncryptsec() -> Result<(), String> {
+        let secret_key_bytes: [u8; 32] =
+            hex::decode("bceef655b5a034911f1c3718ce056531b45ef03b4c7b1f15629e867294011a7d")
+                .map_err(|_| format!("Failed to parse secret key hex."))?
+                .try_into()
+                .map_err(|_| "Invalid key length. Expected 32 bytes.".to_string())?;
+
+        let ncryptsec = secret_key_bytes
+            .to_ncryptsec("passphrase", 4)
+            .ok_or_else(|| "Failed to encrypt secret key to ncryptsec.".to_string())?;
+
+        assert!(ncryptsec.starts_with("ncryptsec1"));
+
+        // Round trip.
+        assert_eq!(
+            ncryptsec.as_str().from_ncryptsec("passphrase"),
+            Some(secret_key_bytes)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn taproot_key_pair() -> Result<(), String> {
+        // BIP-86 test vector, m/86'/0'/0'/0/0 of "abandon abandon ... about".
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4")
+            .map_err(|_| "Failed to parse seed hex.".to_string())?;
+        let path = DerivationPath::from_str("m/86'/0'/0'/0/0")
+            .map_err(|_| "Failed to parse derivation path.".to_string())?;
+        let secret_key_bytes = Xpriv::new_master(NetworkKind::Main, &seed)
+            .and_then(|xpriv| xpriv.derive_priv(&Secp256k1::new(), &path))
+            .map_err(|_| "Failed to derive secret key.".to_string())?
+            .private_key
+            .secret_bytes();
+        let key_holder = KeyHolder::new(secret_key_bytes)
+            .ok_or_else(|| "Failed to construct key holder.".to_string())?;
+
+        assert_eq!(
+            hex::encode(key_holder.secp_public_key_bytes()),
+            "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115"
+        );
+
+        // Without a script tree, the key commits to no script path.
+        let key_pair = key_holder
+            .taproot_key_pair(None)
+            .ok_or_else(|| "Failed to tweak key pair.".to_string())?;
+        assert_eq!(
+            hex::encode(key_pair.output_key_bytes()),
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        assert_eq!(
+            key_holder.taproot_address(Chain::Mainnet, None),
+            Some("bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr".to_string())
+        );
+
+        // Signatures verify against the output key.
+        let sighash = [0xab; 32];
+        let signature = key_holder
+            .sign_taproot(sighash, None)
+            .ok_or_else(|| "Failed to sign.".to_string())?;
+        assert!(schnorr::verify_xonly(
+            key_pair.output_key_bytes(),
+            sighash,
+            signature,
+            SchnorrSigningMode::BIP340
+        ));
+        assert!(!schnorr::verify_xonly(
+            key_holder.secp_public_key_bytes(),
+            sighash,
+            signature,
+            SchnorrSigningMode::BIP340
+        ));
+
+        // With a script tree, the output key matches the taproot of the tree.
+        let tap_root = TapRoot::key_and_script_path_single(
+            key_holder.secp_public_key_point(),
+            TapLeaf::new(vec![0x51]),
+        );
+        let merkle_root = tap_root.tap_branch();
+        let key_pair = key_holder
+            .taproot_key_pair(Some(merkle_root))
+            .ok_or_else(|| "Failed to tweak key pair.".to_string())?;
+        let tweaked_key = tap_root
+            .tweaked_key()
+            .ok_or_else(|| "Failed to tweak taproot key.".to_string())?;
+        assert_eq!(key_pair.output_key_bytes(), tweaked_key.serialize_xonly());
+        assert_eq!(
+            key_pair.output_key_parity(),
+            tap_root.tweaked_key_parity().unwrap_or_default()
+        );
+        let signature = key_pair
+            .sign(sighash)
+            .ok_or_else(|| "Failed to sign.".to_string())?;
+        assert!(schnorr::verify_xonly(
+            key_pair.output_key_bytes(),
+            sighash,
+            signature,
+            SchnorrSigningMode::BIP340
+        ));
+
+        Ok(())
+    }
 }
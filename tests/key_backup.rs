@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod key_backup_tests {
+    use cube::operative::key_backup::backup_file::{KeyBackup, KEY_BACKUP_VERSION};
+    use cube::operative::key_backup::errors::key_backup_error::KeyBackupError;
+    use cube::operative::key_backup::key_backup;
+    use cube::transmutative::key::{KeyHolder, KeyPurpose};
+    use cube::transmutative::secp::schnorr;
+
+    #[test]
+    fn export_and_import() -> Result<(), String> {
+        let key_holder = KeyHolder::new(schnorr::generate_secret())
+            .ok_or("Failed to construct key holder.".to_string())?;
+
+        // 1 Export the backup with a low scrypt work factor.
+        let path = std::env::temp_dir().join(format!(
+            "cube-key-backup-{}.cube",
+            hex::encode(&key_holder.secp_public_key_bytes()[..8])
+        ));
+        let path = path.to_str().ok_or("Invalid path.".to_string())?;
+        let backup = key_backup::export(&key_holder, "passphrase", 4, path)
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 2 The backup lists the root and derived identities.
+        let node_identity = key_holder
+            .derive_for(KeyPurpose::NodeIdentity)
+            .ok_or("Failed to derive.".to_string())?;
+        assert_eq!(backup.identities.len(), 3);
+        assert_eq!(backup.identities[0].purpose, "root");
+        assert_eq!(backup.identities[0].npub, key_holder.npub());
+        assert_eq!(backup.identities[1].purpose, "node_identity");
+        assert_eq!(backup.identities[1].npub, node_identity.npub());
+        assert_eq!(
+            backup.identities[1].derivation_path.as_deref(),
+            Some("m/1129660997'/0'/0'")
+        );
+
+        // 3 Import it back.
+        let (imported_backup, imported_key_holder) =
+            key_backup::import(path, "passphrase").map_err(|e| format!("{:?}", e))?;
+        assert_eq!(imported_backup, backup);
+        assert_eq!(
+            imported_key_holder.secp_secret_key_bytes(),
+            key_holder.secp_secret_key_bytes()
+        );
+
+        // 4 A wrong passphrase fails to import.
+        assert!(matches!(
+            key_backup::import(path, "wrong"),
+            Err(KeyBackupError::WrongPassphrase)
+        ));
+
+        let _ = std::fs::remove_file(path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn tampered_backup() -> Result<(), String> {
+        let key_holder = KeyHolder::new(schnorr::generate_secret())
+            .ok_or("Failed to construct key holder.".to_string())?;
+        let other_key_holder = KeyHolder::new(schnorr::generate_secret())
+            .ok_or("Failed to construct key holder.".to_string())?;
+        let backup =
+            KeyBackup::new(&key_holder, "passphrase", 4, 0).map_err(|e| format!("{:?}", e))?;
+
+        // 1 Serialization round trip.
+        assert_eq!(
+            KeyBackup::from_bytes(&backup.to_bytes()),
+            Ok(backup.clone())
+        );
+
+        // 2 A listed identity swapped for another key is caught.
+        let mut tampered = backup.clone();
+        tampered.identities[2].npub = other_key_holder.npub();
+        assert_eq!(
+            tampered.decrypt("passphrase").map(|_| ()),
+            Err(KeyBackupError::IdentityMismatch(
+                "operator_signing".to_string()
+            ))
+        );
+
+        // 3 Unknown versions and other files are refused.
+        let bytes = String::from_utf8(backup.to_bytes()).map_err(|e| e.to_string())?;
+        let newer = bytes.replace(
+            &format!("\"version\": {}", KEY_BACKUP_VERSION),
+            &format!("\"version\": {}", KEY_BACKUP_VERSION + 1),
+        );
+        assert_eq!(
+            KeyBackup::from_bytes(newer.as_bytes()),
+            Err(KeyBackupError::UnsupportedVersion(KEY_BACKUP_VERSION + 1))
+        );
+        assert_eq!(
+            KeyBackup::from_bytes(b"{\"version\": 1}"),
+            Err(KeyBackupError::MalformedBackup)
+        );
+
+        Ok(())
+    }
+}
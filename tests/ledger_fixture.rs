@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod ledger_fixture_tests {
+    use cube::inscriptive::fixtures::{
+        coin_manager_fixture::CoinManagerFixture, ledger_fixture::LedgerFixture,
+        program_fixture::passing_program, state_manager_fixture::StateManagerFixture,
+    };
+
+    // Account key.
+    const ACCOUNT_KEY: [u8; 32] = [0xa1; 32];
+
+    // Contract id.
+    const CONTRACT_ID: [u8; 32] = [0xc1; 32];
+
+    #[tokio::test]
+    async fn coin_manager_fixture_test() -> Result<(), String> {
+        // 1 Build a coin manager with an account and a contract.
+        let coin_manager = CoinManagerFixture::new()
+            .with_account(ACCOUNT_KEY, 10_000)
+            .with_contract(CONTRACT_ID, 5_000)
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        {
+            let _coin_manager = coin_manager.lock().await;
+            assert_eq!(_coin_manager.get_account_balance(ACCOUNT_KEY), Some(10_000));
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID), Some(5_000));
+        }
+
+        // 2 Another fixture with the same account is kept apart.
+        let other_coin_manager = CoinManagerFixture::new()
+            .with_account(ACCOUNT_KEY, 1)
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        {
+            let _other_coin_manager = other_coin_manager.lock().await;
+            assert_eq!(
+                _other_coin_manager.get_account_balance(ACCOUNT_KEY),
+                Some(1)
+            );
+            assert!(!_other_coin_manager.is_contract_registered(CONTRACT_ID));
+        }
+
+        // 3 Registering the same account twice fails.
+        assert!(CoinManagerFixture::new()
+            .with_account(ACCOUNT_KEY, 0)
+            .with_account(ACCOUNT_KEY, 0)
+            .build()
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ledger_fixture_test() -> Result<(), String> {
+        // 1 Build a ledger with an account, and a contract with a state.
+        let ledger = LedgerFixture::new()
+            .with_account(ACCOUNT_KEY, 10_000)
+            .with_contract(
+                CONTRACT_ID,
+                passing_program("fixture").map_err(|e| format!("{:?}", e))?,
+                5_000,
+            )
+            .with_state(CONTRACT_ID, b"counter".to_vec(), vec![0x01])
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 2 The managers agree with each other.
+        {
+            let _registery = ledger.registery.lock().await;
+            let _coin_manager = ledger.coin_manager.lock().await;
+            let _state_manager = ledger.state_manager.lock().await;
+            assert_eq!(
+                _registery.permanently_registered_account_keys(),
+                vec![ACCOUNT_KEY]
+            );
+            assert_eq!(
+                _registery.permanently_registered_contract_ids(),
+                vec![CONTRACT_ID]
+            );
+            assert_eq!(_coin_manager.get_account_balance(ACCOUNT_KEY), Some(10_000));
+            assert_eq!(_coin_manager.get_contract_balance(CONTRACT_ID), Some(5_000));
+            assert_eq!(
+                _state_manager.get_state_value(CONTRACT_ID, &b"counter".to_vec()),
+                Some(vec![0x01])
+            );
+        }
+
+        // 3 A state manager fixture registers the contract of a state on its own.
+        let state_manager = StateManagerFixture::new()
+            .with_state(CONTRACT_ID, b"counter".to_vec(), vec![0x02])
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        assert!(state_manager
+            .lock()
+            .await
+            .is_contract_registered(CONTRACT_ID));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod memory_budget_tests {
+    use cube::inscriptive::memory_budget::errors::memory_budget_exceeded_error::MemoryBudgetExceededError;
+    use cube::inscriptive::memory_budget::memory_budget_config::MemoryBudgetConfig;
+    use cube::inscriptive::memory_budget::memory_footprint::BudgetedManager;
+
+    #[test]
+    fn memory_budget_test() -> Result<(), String> {
+        let mut config = MemoryBudgetConfig::default();
+
+        // Registery, coin, flame, state, privileges and archival usage.
+        let usage_bytes = [100, 0, 0, 0, 0, 400];
+
+        // An unlimited config always has headroom.
+        assert_eq!(config.check_registration_headroom(&usage_bytes), Ok(()));
+        assert_eq!(
+            config.allowance(BudgetedManager::ArchivalManager, &usage_bytes),
+            None
+        );
+
+        // Caches never block a registration on their own budget.
+        config
+            .manager_budget_bytes
+            .insert(BudgetedManager::ArchivalManager, 300);
+        assert_eq!(config.check_registration_headroom(&usage_bytes), Ok(()));
+        assert_eq!(
+            config.allowance(BudgetedManager::ArchivalManager, &usage_bytes),
+            Some(300)
+        );
+
+        // Non-cache managers do.
+        config
+            .manager_budget_bytes
+            .insert(BudgetedManager::Registery, 100);
+        assert_eq!(
+            config.check_registration_headroom(&usage_bytes),
+            Err(MemoryBudgetExceededError::ManagerBudgetExceeded {
+                manager: BudgetedManager::Registery,
+                usage_bytes: 100,
+                budget_bytes: 100,
+            })
+        );
+        config.manager_budget_bytes.clear();
+
+        // The global budget narrows each manager to what the others leave.
+        config.global_budget_bytes = Some(350);
+        assert_eq!(
+            config.allowance(BudgetedManager::ArchivalManager, &usage_bytes),
+            Some(250)
+        );
+        assert_eq!(
+            config.check_registration_headroom(&usage_bytes),
+            Err(MemoryBudgetExceededError::GlobalBudgetExceeded {
+                usage_bytes: 500,
+                budget_bytes: 350,
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod mempool_watch_tests {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::hashes::Hash;
+    use bitcoin::transaction::Version;
+    use bitcoin::{Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+    use cube::constructive::txo::lift::lift_versions::liftv1::liftv1::return_liftv1_scriptpubkey;
+    use cube::operative::tasks::mempool_watch::mempool_watch::MempoolWatch;
+    use std::collections::HashSet;
+
+    fn txn(inputs: Vec<OutPoint>, outputs: Vec<(u64, ScriptBuf)>) -> Transaction {
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| TxIn {
+                    previous_output,
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: outputs
+                .into_iter()
+                .map(|(value, script_pubkey)| TxOut {
+                    value: Amount::from_sat(value),
+                    script_pubkey,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn mempool_watch_test() -> Result<(), String> {
+        let engine_public_key: [u8; 32] =
+            hex::decode("f437f28e3d9dc4638fe24699feeb89c094544163706faea65b2b72f91cb7267a")
+                .map_err(|_| format!("Failed to parse public key hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert public key hex.".to_string())?;
+        let user_public_key: [u8; 32] =
+            hex::decode("06971ffe504c95152517d1be306b89b69e2f33c2c0e2a06b55f09d087b639d50")
+                .map_err(|_| format!("Failed to parse public key hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert public key hex.".to_string())?;
+
+        let lift_spk = ScriptBuf::from(
+            return_liftv1_scriptpubkey(user_public_key, engine_public_key)
+                .expect("Failed to get liftv1 scriptpubkey."),
+        );
+        let funding_outpoint = OutPoint::new(Txid::from_byte_array([1u8; 32]), 0);
+        let payload_tip_outpoint = OutPoint::new(Txid::from_byte_array([2u8; 32]), 0);
+
+        let mempool_watch = MempoolWatch::new(engine_public_key, user_public_key, false);
+        let mut mempool_watch = mempool_watch.try_lock().map_err(|e| e.to_string())?;
+
+        // Deposits to the account's lifts are tracked, unrelated transactions are not.
+        let deposit = txn(
+            vec![funding_outpoint],
+            vec![(5_000, lift_spk.clone()), (1_000, ScriptBuf::new())],
+        );
+        let unrelated = txn(vec![funding_outpoint], vec![(7_000, ScriptBuf::new())]);
+        let commitment = txn(vec![payload_tip_outpoint], vec![(330, ScriptBuf::new())]);
+
+        let mempool_txids: HashSet<Txid> = [&deposit, &unrelated, &commitment]
+            .iter()
+            .map(|txn| txn.compute_txid())
+            .collect();
+        assert_eq!(mempool_watch.unseen_txids(&mempool_txids).len(), 3);
+
+        let mut new_deposits = Vec::new();
+        for txn in [&deposit, &unrelated, &commitment] {
+            new_deposits.extend(mempool_watch.observe(txn, Some(payload_tip_outpoint), 1_000));
+        }
+        assert_eq!(new_deposits.len(), 1);
+        assert_eq!(new_deposits[0].value, 5_000);
+
+        // A deposit seen again is not reported twice.
+        assert!(mempool_watch
+            .observe(&deposit, Some(payload_tip_outpoint), 1_001)
+            .is_empty());
+        assert!(mempool_watch.unseen_txids(&mempool_txids).is_empty());
+        assert_eq!(mempool_watch.pending_deposits(), (1, 5_000));
+        assert_eq!(mempool_watch.watched_outpoints().len(), 2);
+
+        // Confirmed deposits stay pending until the node has synced the confirming block.
+        let deposit_outpoint = OutPoint::new(deposit.compute_txid(), 0);
+        mempool_watch.update_confirmations(&deposit_outpoint, Some(2), false, 100, 98);
+        assert_eq!(mempool_watch.pending_deposits(), (1, 5_000));
+        mempool_watch.update_confirmations(&deposit_outpoint, Some(3), false, 101, 99);
+        assert_eq!(mempool_watch.pending_deposits(), (0, 0));
+
+        // Commitments that left the mempool without confirming are dropped.
+        let commitment_outpoint = OutPoint::new(commitment.compute_txid(), 0);
+        mempool_watch.update_confirmations(&commitment_outpoint, None, true, 101, 99);
+        assert_eq!(mempool_watch.watched_outpoints().len(), 1);
+        mempool_watch.update_confirmations(&commitment_outpoint, None, false, 101, 99);
+        assert!(mempool_watch.watched_outpoints().is_empty());
+
+        Ok(())
+    }
+}
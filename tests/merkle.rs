@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod merkle_tests {
+    use cube::transmutative::merkle::{
+        merkle_proof, merkle_root, verify_adjacent_merkle_proofs, verify_first_merkle_proof,
+        verify_last_merkle_proof, verify_merkle_proof, MerkleTree,
+    };
+
+    #[test]
+    fn merkle_proof_test() -> Result<(), String> {
+        // Odd number of leaves to exercise the carried-up node.
+        let leaves: Vec<[u8; 32]> = (0u8..5).map(|i| [i; 32]).collect();
+        let root = merkle_root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = merkle_proof(&leaves, index).unwrap();
+            assert!(verify_merkle_proof(*leaf, &proof, root));
+            assert!(!verify_merkle_proof([0xffu8; 32], &proof, root));
+        }
+
+        assert!(merkle_proof(&leaves, leaves.len()).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn merkle_root_edge_cases_test() -> Result<(), String> {
+        assert_eq!(merkle_root(&[]), [0x00u8; 32]);
+        assert_eq!(merkle_root(&[[0x01u8; 32]]), [0x01u8; 32]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn merkle_tree_test() -> Result<(), String> {
+        for count in 0u8..9 {
+            let mut leaves: Vec<[u8; 32]> = (0..count).map(|i| [i; 32]).collect();
+            let mut tree = MerkleTree::new(leaves.clone());
+
+            // The tree matches the root and proofs built from the leaves.
+            assert_eq!(tree.root(), merkle_root(&leaves));
+            for index in 0..leaves.len() {
+                assert_eq!(tree.proof(index), merkle_proof(&leaves, index));
+            }
+            assert!(tree.proof(leaves.len()).is_none());
+
+            // Updating a leaf hashes its branch into the same root as rebuilding the tree.
+            for index in 0..leaves.len() {
+                leaves[index] = [0xf0 | index as u8; 32];
+                assert!(tree.update_leaf(index, leaves[index]));
+                assert_eq!(tree.root(), merkle_root(&leaves));
+                assert_eq!(tree, MerkleTree::new(leaves.clone()));
+            }
+            assert!(!tree.update_leaf(leaves.len(), [0xffu8; 32]));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn merkle_adjacent_proofs_test() -> Result<(), String> {
+        for count in 1u8..12 {
+            let leaves: Vec<[u8; 32]> = (0..count).map(|i| [i; 32]).collect();
+            let tree = MerkleTree::new(leaves.clone());
+            let root = tree.root();
+            let proofs: Vec<_> = (0..leaves.len())
+                .map(|index| tree.proof(index).unwrap())
+                .collect();
+
+            // Only the first leaf passes as the first, and only the last leaf as the last.
+            for index in 0..leaves.len() {
+                assert_eq!(
+                    verify_first_merkle_proof(leaves[index], &proofs[index], root),
+                    index == 0
+                );
+                assert_eq!(
+                    verify_last_merkle_proof(leaves[index], &proofs[index], root),
+                    index == leaves.len() - 1
+                );
+            }
+
+            // Only a leaf immediately followed by the other passes as adjacent.
+            for left in 0..leaves.len() {
+                for right in 0..leaves.len() {
+                    assert_eq!(
+                        verify_adjacent_merkle_proofs(
+                            leaves[left],
+                            &proofs[left],
+                            leaves[right],
+                            &proofs[right],
+                            root
+                        ),
+                        right == left + 1
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
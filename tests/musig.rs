@@ -1,7 +1,13 @@
 #[cfg(test)]
 mod musig_standalone {
+    use cube::operative::run_args::chain::Chain;
     use cube::transmutative::{
-        musig::{keyagg::MusigKeyAggCtx, session::MusigSessionCtx},
+        musig::{
+            keyagg::MusigKeyAggCtx,
+            nonce_guard::{erase_nonce_guard, MusigNonceGuard},
+            secret_nonces::MusigSecretNonces,
+            session::MusigSessionCtx,
+        },
         secp::schnorr::{self, SchnorrSigningMode},
     };
     use secp::{Point, Scalar};
@@ -131,13 +137,22 @@ mod musig_standalone {
 
         assert_eq!(agg_nonce, agg_nonce_expected);
 
+        // Signers consume their nonces through the nonce guard.
+
+        erase_nonce_guard(Chain::Signet);
+        let nonce_guard = MusigNonceGuard::new(Chain::Signet).unwrap();
+        let _nonce_guard = nonce_guard.blocking_lock();
+
         // Signer 1 partial signing:
 
         let signer_1_partial_sig = session_ctx
             .partial_sign(
+                &_nonce_guard,
                 signer_1_secret_key,
-                signer_1_hiding_secret_nonce,
-                signer_1_binding_secret_nonce,
+                &MusigSecretNonces::new(
+                    signer_1_hiding_secret_nonce,
+                    signer_1_binding_secret_nonce,
+                ),
             )
             .unwrap();
 
@@ -147,9 +162,12 @@ mod musig_standalone {
 
         let signer_2_partial_sig = session_ctx
             .partial_sign(
+                &_nonce_guard,
                 signer_2_secret_key,
-                signer_2_hiding_secret_nonce,
-                signer_2_binding_secret_nonce,
+                &MusigSecretNonces::new(
+                    signer_2_hiding_secret_nonce,
+                    signer_2_binding_secret_nonce,
+                ),
             )
             .unwrap();
 
@@ -159,9 +177,12 @@ mod musig_standalone {
 
         let signer_3_partial_sig = session_ctx
             .partial_sign(
+                &_nonce_guard,
                 signer_3_secret_key,
-                signer_3_hiding_secret_nonce,
-                signer_3_binding_secret_nonce,
+                &MusigSecretNonces::new(
+                    signer_3_hiding_secret_nonce,
+                    signer_3_binding_secret_nonce,
+                ),
             )
             .unwrap();
 
@@ -176,6 +197,10 @@ mod musig_standalone {
             SchnorrSigningMode::BIP340
         ));
 
+        drop(_nonce_guard);
+        drop(nonce_guard);
+        erase_nonce_guard(Chain::Signet);
+
         Ok(())
     }
 }
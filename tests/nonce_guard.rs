@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod nonce_guard_tests {
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::musig::error::MusigNonceGuardError;
+    use cube::transmutative::musig::keyagg::MusigKeyAggCtx;
+    use cube::transmutative::musig::nonce_guard::{
+        erase_nonce_guard, MusigNonceGuard, MUSIG_NONCE_GUARD,
+    };
+    use cube::transmutative::musig::secret_nonces::MusigSecretNonces;
+    use cube::transmutative::musig::session::MusigSessionCtx;
+    use cube::transmutative::secp::schnorr::{self, SchnorrSigningMode};
+    use secp::{Point, Scalar};
+
+    fn scalar(hex: &str) -> Result<Scalar, String> {
+        Scalar::from_hex(hex).map_err(|_| "Failed to parse scalar hex.".to_string())
+    }
+
+    /// Constructs a ready two-signer session for the message.
+    fn session(
+        secret_keys: &[Scalar; 2],
+        secret_nonces: &[(Scalar, Scalar); 2],
+        message: [u8; 32],
+    ) -> Result<MusigSessionCtx, String> {
+        let keys: Vec<Point> = secret_keys.iter().map(|key| key.base_point_mul()).collect();
+        let key_agg_ctx =
+            MusigKeyAggCtx::new(&keys, None).ok_or("Failed to aggregate keys.".to_string())?;
+
+        let mut session_ctx = MusigSessionCtx::new(&key_agg_ctx, message)
+            .ok_or("Failed to construct session.".to_string())?;
+        for (key, (hiding_nonce, binding_nonce)) in keys.iter().zip(secret_nonces.iter()) {
+            assert!(session_ctx.insert_nonce(
+                *key,
+                hiding_nonce.base_point_mul(),
+                binding_nonce.base_point_mul()
+            ));
+        }
+        assert!(session_ctx.ready());
+
+        Ok(session_ctx)
+    }
+
+    #[tokio::test]
+    async fn nonce_guard_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+
+        // 2 Erase first the nonce guard.
+        erase_nonce_guard(chain);
+
+        // 3 Construct the nonce guard.
+        let nonce_guard: MUSIG_NONCE_GUARD = MusigNonceGuard::new(chain).unwrap();
+
+        let secret_keys = [
+            scalar("1cc5906ab936b1e29db24fffe9f87b33a4c64f2d3b59aed6c3c4faeb8fcba6da")?,
+            scalar("4882eef979baa5c88fd9e62c698de201f0a991af65877becf683e988f3024b0f")?,
+        ];
+        let secret_nonces = [
+            (
+                scalar("e2d64e2bd20d5843d03a47199f059aebdf2a9904616a01fe961ee875a7748199")?,
+                scalar("4b978d3aac4135213f536194522f68fbb2ca4321a49d95560ae9726cd9d6a55d")?,
+            ),
+            (
+                scalar("d3b9f2f01f7caa9b0fe2e932ae752f71da9f8f1a652ec895504091333b97d007")?,
+                scalar("961a4d128a1f3cb5c41e71bc86fdc9e81050b7471f05112a6a5360a2240ff3cf")?,
+            ),
+        ];
+        let (hiding_nonce, binding_nonce) = secret_nonces[0];
+        let signer_nonces = MusigSecretNonces::new(hiding_nonce, binding_nonce);
+
+        let session_1 = session(&secret_keys, &secret_nonces, [0x01; 32])?;
+        let session_2 = session(&secret_keys, &secret_nonces, [0x02; 32])?;
+
+        let partial_sig = {
+            let _nonce_guard = nonce_guard.lock().await;
+
+            // 4 Fresh nonces are not consumed.
+            assert!(!_nonce_guard.is_nonce_consumed(
+                hiding_nonce.base_point_mul(),
+                binding_nonce.base_point_mul()
+            ));
+
+            // 5 Sign the first session.
+            let partial_sig = _nonce_guard
+                .partial_sign(&session_1, secret_keys[0], &signer_nonces)
+                .map_err(|e| format!("{:?}", e))?;
+            assert!(_nonce_guard.is_nonce_consumed(
+                hiding_nonce.base_point_mul(),
+                binding_nonce.base_point_mul()
+            ));
+
+            // 6 Signing another session with the same nonces is refused.
+            assert!(matches!(
+                _nonce_guard.partial_sign(&session_2, secret_keys[0], &signer_nonces),
+                Err(MusigNonceGuardError::NonceReuse)
+            ));
+
+            partial_sig
+        };
+
+        // 7 Construct the nonce guard again from disk, as after a crash.
+        drop(nonce_guard);
+        let nonce_guard: MUSIG_NONCE_GUARD = MusigNonceGuard::new(chain).unwrap();
+        let _nonce_guard = nonce_guard.lock().await;
+
+        // 7.1 The same session can be signed again, with the same result.
+        assert_eq!(
+            _nonce_guard
+                .partial_sign(&session_1, secret_keys[0], &signer_nonces)
+                .map_err(|e| format!("{:?}", e))?,
+            partial_sig
+        );
+
+        // 7.2 Other sessions are still refused.
+        assert!(matches!(
+            _nonce_guard.partial_sign(&session_2, secret_keys[0], &signer_nonces),
+            Err(MusigNonceGuardError::NonceReuse)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nonce_guard_session_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Signet;
+
+        // 2 Erase first the nonce guard.
+        erase_nonce_guard(chain);
+
+        // 3 Construct the nonce guard.
+        let nonce_guard: MUSIG_NONCE_GUARD = MusigNonceGuard::new(chain).unwrap();
+        let _nonce_guard = nonce_guard.lock().await;
+
+        let secret_keys = [
+            scalar("1cc5906ab936b1e29db24fffe9f87b33a4c64f2d3b59aed6c3c4faeb8fcba6da")?,
+            scalar("4882eef979baa5c88fd9e62c698de201f0a991af65877becf683e988f3024b0f")?,
+        ];
+        let keys: Vec<Point> = secret_keys.iter().map(|key| key.base_point_mul()).collect();
+        let key_agg_ctx =
+            MusigKeyAggCtx::new(&keys, None).ok_or("Failed to aggregate keys.".to_string())?;
+
+        // 4 Each signer generates its nonces through the nonce guard.
+        let mut session_1 = MusigSessionCtx::new(&key_agg_ctx, [0x01; 32])
+            .ok_or("Failed to construct session.".to_string())?;
+        let mut signer_nonces = Vec::new();
+        for key in keys.iter() {
+            signer_nonces.push(
+                session_1
+                    .generate_nonces(&_nonce_guard, *key)
+                    .map_err(|e| format!("{:?}", e))?,
+            );
+        }
+        assert!(session_1.ready());
+
+        // 5 Sign the session, consuming the nonces.
+        for ((key, secret_key), secret_nonces) in keys
+            .iter()
+            .zip(secret_keys.iter())
+            .zip(signer_nonces.iter())
+        {
+            let partial_sig = session_1
+                .partial_sign(&_nonce_guard, *secret_key, secret_nonces)
+                .map_err(|e| format!("{:?}", e))?;
+            assert!(session_1.insert_partial_sig(*key, partial_sig));
+        }
+        let full_agg_sig = session_1
+            .full_agg_sig()
+            .ok_or("Failed to aggregate signatures.".to_string())?;
+        assert!(schnorr::verify_xonly(
+            key_agg_ctx.agg_key().serialize_xonly(),
+            [0x01; 32],
+            full_agg_sig,
+            SchnorrSigningMode::BIP340
+        ));
+
+        // 6 The consumed nonces cannot sign another session.
+        let mut session_2 = MusigSessionCtx::new(&key_agg_ctx, [0x02; 32])
+            .ok_or("Failed to construct session.".to_string())?;
+        for (key, secret_nonces) in keys.iter().zip(signer_nonces.iter()) {
+            let (hiding_nonce, binding_nonce) = secret_nonces
+                .public_nonces()
+                .ok_or("Failed to derive public nonces.".to_string())?;
+            assert!(session_2.insert_nonce(*key, hiding_nonce, binding_nonce));
+        }
+        assert!(matches!(
+            session_2.partial_sign(&_nonce_guard, secret_keys[0], &signer_nonces[0]),
+            Err(MusigNonceGuardError::NonceReuse)
+        ));
+
+        // 7 Erase the nonce guard.
+        drop(_nonce_guard);
+        drop(nonce_guard);
+        erase_nonce_guard(chain);
+
+        Ok(())
+    }
+}
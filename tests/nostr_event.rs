@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod nostr_event_tests {
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::nostr::event::{NostrEvent, NOSTR_TEXT_NOTE_KIND};
+    use nostr_sdk::{EventBuilder, JsonUtil, Keys};
+
+    #[test]
+    fn sign_and_verify() -> Result<(), String> {
+        let secret_key_bytes: [u8; 32] =
+            hex::decode("bceef655b5a034911f1c3718ce056531b45ef03b4c7b1f15629e867294011a7d")
+                .map_err(|_| "Failed to parse secret key hex.".to_string())?
+                .try_into()
+                .map_err(|_| "Invalid key length. Expected 32 bytes.".to_string())?;
+        let key_holder = KeyHolder::new(secret_key_bytes)
+            .ok_or_else(|| "Failed to construct key holder.".to_string())?;
+
+        let event = NostrEvent::sign(
+            &key_holder,
+            1_700_000_000,
+            NOSTR_TEXT_NOTE_KIND,
+            vec![vec!["t".to_string(), "cube".to_string()]],
+            "127.0.0.1\n\"quoted\"".to_string(),
+        )
+        .ok_or("Failed to sign event.".to_string())?;
+        assert!(event.verify());
+
+        // The JSON form round-trips.
+        let json_str = event.json().to_string();
+        assert_eq!(NostrEvent::from_json_str(&json_str), Some(event.clone()));
+
+        // Other Nostr implementations accept the event.
+        let sdk_event = nostr_sdk::Event::from_json(&json_str)
+            .map_err(|_| "Failed to parse event with nostr-sdk.".to_string())?;
+        assert!(sdk_event.verify().is_ok());
+
+        // Tampering with the content invalidates the id.
+        let mut tampered = event.clone();
+        tampered.content = "10.0.0.1".to_string();
+        assert!(!tampered.verify());
+
+        // Tampering with the signature invalidates it.
+        let mut tampered = event;
+        tampered.sig[0] ^= 0x01;
+        assert!(!tampered.verify());
+
+        Ok(())
+    }
+
+    #[test]
+    fn verify_foreign_event() -> Result<(), String> {
+        let keys = Keys::generate();
+        let sdk_event = EventBuilder::text_note("127.0.0.1")
+            .sign_with_keys(&keys)
+            .map_err(|_| "Failed to sign event with nostr-sdk.".to_string())?;
+
+        let event = NostrEvent::from_json_str(&sdk_event.as_json())
+            .ok_or("Failed to parse event.".to_string())?;
+        assert_eq!(event.id, sdk_event.id.to_bytes());
+        assert_eq!(event.pubkey, keys.public_key().to_bytes());
+        assert!(event.verify());
+
+        Ok(())
+    }
+}
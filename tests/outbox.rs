@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod outbox_tests {
+    use cube::communicative::outbox::outbox::{erase_outbox, retry_backoff_secs, Outbox, OUTBOX};
+    use cube::communicative::tcp::package::PackageKind;
+    use cube::operative::run_args::chain::Chain;
+
+    #[test]
+    fn retry_backoff_test() {
+        // Backoff doubles with each failed attempt, up to five minutes.
+        assert_eq!(retry_backoff_secs(1), 2);
+        assert_eq!(retry_backoff_secs(2), 4);
+        assert_eq!(retry_backoff_secs(5), 32);
+        assert_eq!(retry_backoff_secs(9), 300);
+        assert_eq!(retry_backoff_secs(u32::MAX), 300);
+    }
+
+    #[tokio::test]
+    async fn outbox_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+        let (engine_key, coordinator_key) = ([0x01u8; 32], [0x02u8; 32]);
+
+        // 2 Erase first the outbox.
+        erase_outbox(chain);
+
+        // 3 Queue two messages to the engine and one to a coordinator.
+        let (first_id, second_id) = {
+            let outbox: OUTBOX = Outbox::new(chain)
+                .map_err(|err| format!("Error constructing outbox: {:?}", err))?;
+            let mut _outbox = outbox.lock().await;
+            let first_id = _outbox
+                .enqueue(engine_key, PackageKind::DeltaCosignProtocol, vec![0x01], 0)
+                .map_err(|err| format!("Error enqueuing message: {:?}", err))?;
+            let second_id = _outbox
+                .enqueue(engine_key, PackageKind::DeltaCosignProtocol, vec![0x02], 0)
+                .map_err(|err| format!("Error enqueuing message: {:?}", err))?;
+            _outbox
+                .enqueue(
+                    coordinator_key,
+                    PackageKind::HeartbeatProtocol,
+                    vec![0x03],
+                    0,
+                )
+                .map_err(|err| format!("Error enqueuing message: {:?}", err))?;
+            (first_id, second_id)
+        };
+
+        // 4 Reopen the outbox; the messages are persisted, in order.
+        let outbox: OUTBOX =
+            Outbox::new(chain).map_err(|err| format!("Error constructing outbox: {:?}", err))?;
+        let mut _outbox = outbox.lock().await;
+        let due = _outbox.due(engine_key, 0);
+        assert_eq!(
+            due.iter().map(|message| message.id).collect::<Vec<u64>>(),
+            vec![first_id, second_id]
+        );
+        assert!(due[0].package_kind() == Some(PackageKind::DeltaCosignProtocol));
+        assert_eq!(due[0].payload, vec![0x01]);
+        assert_eq!(_outbox.len(coordinator_key), 1);
+
+        // 5 New messages do not reuse the ids of the persisted ones.
+        let third_id = _outbox
+            .enqueue(engine_key, PackageKind::DeltaCosignProtocol, vec![0x04], 0)
+            .map_err(|err| format!("Error enqueuing message: {:?}", err))?;
+        assert!(third_id > second_id);
+
+        // 6 Acknowledged messages are removed.
+        _outbox
+            .acknowledge(engine_key, first_id)
+            .map_err(|err| format!("Error acknowledging message: {}", err))?;
+        assert_eq!(_outbox.len(engine_key), 2);
+
+        // 7 Failed messages are backed off.
+        _outbox
+            .record_failure(engine_key, second_id, "Timeout".to_string(), 10)
+            .map_err(|err| format!("Error recording failure: {}", err))?;
+        _outbox
+            .record_failure(engine_key, second_id, "Timeout".to_string(), 10)
+            .map_err(|err| format!("Error recording failure: {}", err))?;
+        assert_eq!(
+            _outbox
+                .due(engine_key, 13)
+                .iter()
+                .map(|message| message.id)
+                .collect::<Vec<u64>>(),
+            vec![third_id]
+        );
+        let retried = _outbox.due(engine_key, 14);
+        assert_eq!(retried.len(), 2);
+        assert_eq!(retried[0].attempts, 2);
+        assert_eq!(retried[0].last_error, Some("Timeout".to_string()));
+
+        // 8 Unacknowledged messages are given up on past the expiry.
+        let expired = _outbox
+            .expire(3_600)
+            .map_err(|err| format!("Error expiring messages: {}", err))?;
+        assert_eq!(expired.len(), 3);
+        assert!(_outbox.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(all(test, feature = "confidential"))]
+mod pedersen_tests {
+    use cube::transmutative::pedersen::commitment::{
+        blinding_excess, commitment_excess, is_balanced, random_blinding, PedersenCommitment,
+    };
+    use cube::transmutative::pedersen::generator::{hash_to_point, value_generator};
+    use cube::transmutative::pedersen::range_proof::RangeProof;
+    use secp::MaybePoint;
+
+    #[test]
+    fn value_generator_test() -> Result<(), String> {
+        // The value generator is deterministic and differs from other seeds.
+        assert_eq!(value_generator(), hash_to_point(b"value"));
+        assert_ne!(value_generator(), hash_to_point(b"other"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn commitment() -> Result<(), String> {
+        let blinding = random_blinding();
+        let commitment =
+            PedersenCommitment::commit(1000, blinding).ok_or("Failed to commit.".to_string())?;
+
+        // Opens to the value and blinding factor it was made with, and nothing else.
+        assert!(commitment.opens_to(1000, blinding));
+        assert!(!commitment.opens_to(1001, blinding));
+        assert!(!commitment.opens_to(1000, random_blinding()));
+
+        // Hiding: the same value with another blinding factor gives another commitment.
+        let other = PedersenCommitment::commit(1000, random_blinding())
+            .ok_or("Failed to commit.".to_string())?;
+        assert_ne!(commitment, other);
+
+        // Serialization round trip.
+        let bytes = commitment.serialize();
+        assert_eq!(PedersenCommitment::deserialize(&bytes), Some(commitment));
+
+        Ok(())
+    }
+
+    #[test]
+    fn homomorphism() -> Result<(), String> {
+        let (blinding_a, blinding_b) = (random_blinding(), random_blinding());
+        let a = PedersenCommitment::commit(700, blinding_a).ok_or("Failed to commit.")?;
+        let b = PedersenCommitment::commit(300, blinding_b).ok_or("Failed to commit.")?;
+
+        // C(a) + C(b) opens to a + b.
+        let sum = a.add(&b).ok_or("Failed to add.")?;
+        let sum_blinding = (blinding_a + blinding_b)
+            .not_zero()
+            .map_err(|_| "Zero blinding.".to_string())?;
+        assert!(sum.opens_to(1000, sum_blinding));
+
+        // C(a) − C(b) opens to a − b.
+        let difference = a.sub(&b).ok_or("Failed to subtract.")?;
+        let difference_blinding = (blinding_a - blinding_b)
+            .not_zero()
+            .map_err(|_| "Zero blinding.".to_string())?;
+        assert!(difference.opens_to(400, difference_blinding));
+
+        Ok(())
+    }
+
+    #[test]
+    fn balance() -> Result<(), String> {
+        // Inputs of 600 and 400, outputs of 900 and 100.
+        let input_blindings = vec![random_blinding(), random_blinding()];
+        let output_blindings = vec![random_blinding(), random_blinding()];
+        let inputs = vec![
+            PedersenCommitment::commit(600, input_blindings[0]).ok_or("Failed to commit.")?,
+            PedersenCommitment::commit(400, input_blindings[1]).ok_or("Failed to commit.")?,
+        ];
+        let outputs = vec![
+            PedersenCommitment::commit(900, output_blindings[0]).ok_or("Failed to commit.")?,
+            PedersenCommitment::commit(100, output_blindings[1]).ok_or("Failed to commit.")?,
+        ];
+
+        // The excess of a balanced set commits to zero.
+        let excess = blinding_excess(&input_blindings, &output_blindings);
+        assert!(is_balanced(&inputs, &outputs, excess));
+        assert_ne!(commitment_excess(&inputs, &outputs), MaybePoint::Infinity);
+
+        // Inflating an output breaks the balance.
+        let inflated = vec![
+            PedersenCommitment::commit(901, output_blindings[0]).ok_or("Failed to commit.")?,
+            outputs[1],
+        ];
+        assert!(!is_balanced(&inputs, &inflated, excess));
+
+        Ok(())
+    }
+
+    #[test]
+    fn range_proof() -> Result<(), String> {
+        for (value, bits) in [(0u64, 8u8), (1, 8), (255, 8), (12345, 16), (u64::MAX, 64)] {
+            let blinding = random_blinding();
+            let commitment =
+                PedersenCommitment::commit(value, blinding).ok_or("Failed to commit.")?;
+
+            let proof = RangeProof::prove(value, blinding, bits)
+                .ok_or(format!("Failed to prove {} in {} bits.", value, bits))?;
+            assert_eq!(proof.bits(), bits);
+            assert!(proof.verify(&commitment));
+
+            // Serialization round trip.
+            let bytes = proof.serialize();
+            assert_eq!(bytes.len(), 1 + bits as usize * 161);
+            let deserialized = RangeProof::deserialize(&bytes).ok_or("Failed to deserialize.")?;
+            assert_eq!(deserialized, proof);
+            assert!(deserialized.verify(&commitment));
+
+            // The proof does not verify for another commitment.
+            let other =
+                PedersenCommitment::commit(value, random_blinding()).ok_or("Failed to commit.")?;
+            assert!(!proof.verify(&other));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn range_proof_out_of_range() -> Result<(), String> {
+        let blinding = random_blinding();
+
+        assert!(RangeProof::prove(256, blinding, 8).is_none());
+        assert!(RangeProof::prove(1, blinding, 0).is_none());
+        assert!(RangeProof::prove(1, blinding, 65).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn range_proof_tampering() -> Result<(), String> {
+        let blinding = random_blinding();
+        let commitment = PedersenCommitment::commit(42, blinding).ok_or("Failed to commit.")?;
+        let bytes = RangeProof::prove(42, blinding, 8)
+            .ok_or("Failed to prove.")?
+            .serialize();
+
+        // Flipping a byte of a bit proof invalidates it.
+        let mut tampered = bytes.clone();
+        tampered[1 + 33 + 40] ^= 0x01;
+        if let Some(proof) = RangeProof::deserialize(&tampered) {
+            assert!(!proof.verify(&commitment));
+        }
+
+        // Swapping two bit commitments and their proofs breaks the position binding.
+        let mut swapped = bytes.clone();
+        let (first, rest) = swapped[1..].split_at_mut(161);
+        first.swap_with_slice(&mut rest[..161]);
+        let proof = RangeProof::deserialize(&swapped).ok_or("Failed to deserialize.")?;
+        assert!(!proof.verify(&commitment));
+
+        // Truncated proofs do not parse.
+        assert!(RangeProof::deserialize(&bytes[..bytes.len() - 1]).is_none());
+        assert!(RangeProof::deserialize(&[]).is_none());
+
+        Ok(())
+    }
+}
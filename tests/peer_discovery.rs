@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod peer_discovery_tests {
+    use cube::communicative::discovery::announcement::{PeerAnnouncement, PeerAnnouncementKind};
+    use cube::communicative::discovery::errors::discovery_error::PeerDiscoveryError;
+    use cube::communicative::discovery::peer_table::{PeerTable, ANNOUNCEMENT_TTL_SECS};
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::key::KeyHolder;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn peer_table_test() -> Result<(), String> {
+        let engine_keys = KeyHolder::new([0x11u8; 32]).expect("Failed to create key holder.");
+        let coordinator_keys = KeyHolder::new([0x22u8; 32]).expect("Failed to create key holder.");
+        let operator_keys = KeyHolder::new([0x33u8; 32]).expect("Failed to create key holder.");
+        let endpoint: SocketAddr = "203.0.113.7:6272".parse().map_err(|_| "Bad endpoint.")?;
+        let now = 1_700_000_000;
+
+        let mut peer_table = PeerTable::with_trusted_keys(
+            Chain::Signet,
+            engine_keys.secp_public_key_bytes(),
+            vec![coordinator_keys.secp_public_key_bytes()],
+        );
+
+        let announce =
+            |keys: &KeyHolder, kind: PeerAnnouncementKind, chains: Vec<Chain>, at: u64| {
+                PeerAnnouncement::sign(keys, kind, vec![endpoint], chains, vec![], at)
+                    .ok_or("Failed to sign the announcement.".to_string())
+            };
+
+        // A tampered announcement is rejected.
+        let mut tampered = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Operator,
+            vec![Chain::Signet],
+            now,
+        )?;
+        tampered
+            .endpoints
+            .push("198.51.100.1:6272".parse().map_err(|_| "Bad endpoint.")?);
+        assert_eq!(
+            peer_table.insert(tampered, now).err(),
+            Some(PeerDiscoveryError::InvalidSignature)
+        );
+
+        // Announcements for another chain are rejected.
+        let mainnet = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Operator,
+            vec![Chain::Mainnet],
+            now,
+        )?;
+        assert_eq!(
+            peer_table.insert(mainnet, now).err(),
+            Some(PeerDiscoveryError::UnsupportedChain)
+        );
+
+        // Only the trusted keys may announce themselves as the engine or a coordinator.
+        let fake_engine = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Engine,
+            vec![Chain::Signet],
+            now,
+        )?;
+        assert_eq!(
+            peer_table.insert(fake_engine, now).err(),
+            Some(PeerDiscoveryError::UnauthorizedPeerKind)
+        );
+        let fake_coordinator = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Coordinator,
+            vec![Chain::Signet],
+            now,
+        )?;
+        assert_eq!(
+            peer_table.insert(fake_coordinator, now).err(),
+            Some(PeerDiscoveryError::UnauthorizedPeerKind)
+        );
+
+        // Expired and future announcements are rejected.
+        let expired = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Operator,
+            vec![Chain::Signet],
+            now - ANNOUNCEMENT_TTL_SECS - 1,
+        )?;
+        assert!(matches!(
+            peer_table.insert(expired, now),
+            Err(PeerDiscoveryError::AnnouncementExpired(_))
+        ));
+        let future = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Operator,
+            vec![Chain::Signet],
+            now + 600,
+        )?;
+        assert!(matches!(
+            peer_table.insert(future, now),
+            Err(PeerDiscoveryError::ClockSkewTooLarge { .. })
+        ));
+
+        // Valid announcements build the peer table.
+        for (keys, kind) in [
+            (&engine_keys, PeerAnnouncementKind::Engine),
+            (&coordinator_keys, PeerAnnouncementKind::Coordinator),
+            (&operator_keys, PeerAnnouncementKind::Operator),
+        ] {
+            let announcement = announce(keys, kind, vec![Chain::Signet, Chain::Mainnet], now)?;
+            peer_table
+                .insert(announcement, now)
+                .map_err(|err| format!("{:?}", err))?;
+        }
+        assert_eq!(peer_table.len(), 3);
+        assert_eq!(
+            peer_table.peer_keys(PeerAnnouncementKind::Coordinator),
+            vec![coordinator_keys.secp_public_key_bytes()]
+        );
+        assert_eq!(
+            peer_table.endpoints(engine_keys.secp_public_key_bytes()),
+            vec![endpoint]
+        );
+
+        // Replaying an announcement is rejected, while a newer one replaces it.
+        let replayed = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Operator,
+            vec![Chain::Signet],
+            now,
+        )?;
+        assert!(matches!(
+            peer_table.insert(replayed, now),
+            Err(PeerDiscoveryError::ReplayedAnnouncement { .. })
+        ));
+        let refreshed = announce(
+            &operator_keys,
+            PeerAnnouncementKind::Operator,
+            vec![Chain::Signet],
+            now + 3_600,
+        )?;
+        peer_table
+            .insert(refreshed, now + 3_600)
+            .map_err(|err| format!("{:?}", err))?;
+
+        // Peers that stopped announcing themselves expire.
+        let pruned = peer_table.prune(now + ANNOUNCEMENT_TTL_SECS + 1);
+        assert_eq!(pruned.len(), 2);
+        assert_eq!(
+            peer_table.peer_keys(PeerAnnouncementKind::Operator),
+            vec![operator_keys.secp_public_key_bytes()]
+        );
+
+        // Announcements round-trip through their JSON encoding.
+        let announcement = peer_table
+            .announcement(operator_keys.secp_public_key_bytes())
+            .ok_or("Missing announcement.")?;
+        let encoded = serde_json::to_string(announcement).map_err(|err| err.to_string())?;
+        let decoded: PeerAnnouncement =
+            serde_json::from_str(&encoded).map_err(|err| err.to_string())?;
+        assert_eq!(&decoded, announcement);
+        assert!(decoded.verify());
+
+        Ok(())
+    }
+}
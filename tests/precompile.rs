@@ -0,0 +1,268 @@
+#[cfg(test)]
+mod precompile_tests {
+    use cube::executive::{
+        opcode::opcodes::precompile::op_precompile::OP_PRECOMPILE,
+        precompiles::precompile::Precompile,
+        stack::{
+            stack_error::{PrecompileError, StackError},
+            stack_fixed::StackFixed,
+            stack_holder::StackHolder,
+            stack_item::StackItem,
+            stack_uint::{StackItemUintExt, StackUint},
+        },
+        vm::program_execution::caller::Caller,
+    };
+
+    /// Returns a fresh stack holder with the given ops budget.
+    fn stack_holder(ops_budget: u32) -> Result<StackHolder, StackError> {
+        StackHolder::new(
+            Caller::new_account([0; 32]),
+            [0; 32],
+            1715619200,
+            0,
+            ops_budget,
+            1,
+            0,
+            0,
+        )
+    }
+
+    #[test]
+    fn precompile_registry_test() -> Result<(), String> {
+        // Every registered precompile is found back by its id.
+        for precompile in Precompile::all() {
+            assert_eq!(Precompile::from_id(precompile.id()), Some(precompile));
+        }
+
+        // Ids outside the registry are not found.
+        assert_eq!(Precompile::from_id(0x00), None);
+        assert_eq!(Precompile::from_id(0xff), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn precompile_hashing_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder(1_000)?;
+
+        // SHA-512 of "abc".
+        stack_holder.push(StackItem::new(b"abc".to_vec()))?;
+        stack_holder.push(StackItem::new(vec![Precompile::Sha512.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert_eq!(
+            hex::encode(stack_holder.pop()?.bytes()),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+
+        // HMAC-SHA256 of the message under the key.
+        stack_holder.push(StackItem::new(b"key".to_vec()))?;
+        stack_holder.push(StackItem::new(
+            b"The quick brown fox jumps over the lazy dog".to_vec(),
+        ))?;
+        stack_holder.push(StackItem::new(vec![Precompile::HmacSha256.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert_eq!(
+            hex::encode(stack_holder.pop()?.bytes()),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+
+        // Each call is charged the opcode ops plus the fixed precompile ops.
+        assert_eq!(stack_holder.internal_ops_counter(), 1 + 60 + 1 + 90);
+
+        Ok(())
+    }
+
+    #[test]
+    fn precompile_ecdsa_verify_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder(1_000)?;
+
+        // Public key of the secret key 0x11..11, and its signature over the digest 0x22..22.
+        let public_key =
+            hex::decode("034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa")
+                .unwrap();
+        let signature = hex::decode("cfd18ee918d6729134adbc61212142cf71fcf186dfc3123cfca8f7062e0fad5a703cc467d9857349ddb6e148bd1663f5050f3f6b9d788d64349c357f14eb4a5f").unwrap();
+
+        // The signature verifies over its digest.
+        stack_holder.push(StackItem::new(signature.clone()))?;
+        stack_holder.push(StackItem::new(vec![0x22; 32]))?;
+        stack_holder.push(StackItem::new(public_key.clone()))?;
+        stack_holder.push(StackItem::new(vec![Precompile::EcdsaVerify.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert!(stack_holder.pop()?.is_true());
+
+        // The signature does not verify over another digest.
+        stack_holder.push(StackItem::new(signature.clone()))?;
+        stack_holder.push(StackItem::new(vec![0x23; 32]))?;
+        stack_holder.push(StackItem::new(public_key.clone()))?;
+        stack_holder.push(StackItem::new(vec![Precompile::EcdsaVerify.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert!(stack_holder.pop()?.is_false());
+
+        // A malformed public key fails.
+        stack_holder.push(StackItem::new(signature))?;
+        stack_holder.push(StackItem::new(vec![0x22; 32]))?;
+        stack_holder.push(StackItem::new(public_key[1..].to_vec()))?;
+        stack_holder.push(StackItem::new(vec![Precompile::EcdsaVerify.id()]))?;
+        assert!(matches!(
+            OP_PRECOMPILE::execute(&mut stack_holder),
+            Err(StackError::PrecompileError(
+                PrecompileError::InvalidECDSAPublicKeyBytes
+            ))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn precompile_bigint_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder(10_000)?;
+
+        // 3^200 mod 1_000_000_007.
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(3)))?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(200)))?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(
+            1_000_000_007u64,
+        )))?;
+        stack_holder.push(StackItem::new(vec![Precompile::ModExp.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert_eq!(
+            stack_holder.pop()?.to_stack_uint(),
+            Some(StackUint::from(136318165u64))
+        );
+
+        // 2^(p - 1) mod p is 1 for the prime p = 2^255 - 19.
+        let p = StackUint::from(2).pow(StackUint::from(255)) - StackUint::from(19);
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(2)))?;
+        stack_holder.push(StackItem::from_stack_uint(p - StackUint::from(1)))?;
+        stack_holder.push(StackItem::from_stack_uint(p))?;
+        stack_holder.push(StackItem::new(vec![Precompile::ModExp.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert_eq!(
+            stack_holder.pop()?.to_stack_uint(),
+            Some(StackUint::from(1))
+        );
+
+        // (2^255 + 19) * (2^200 + 7) mod (2^61 - 1).
+        stack_holder.push(StackItem::from_stack_uint(
+            StackUint::from(2).pow(StackUint::from(255)) + StackUint::from(19),
+        ))?;
+        stack_holder.push(StackItem::from_stack_uint(
+            StackUint::from(2).pow(StackUint::from(200)) + StackUint::from(7),
+        ))?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(
+            (1u64 << 61) - 1,
+        )))?;
+        stack_holder.push(StackItem::new(vec![Precompile::ModMul.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert_eq!(
+            stack_holder.pop()?.to_stack_uint(),
+            Some(StackUint::from(270940293u64))
+        );
+
+        // A zero modulus fails.
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(3)))?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(4)))?;
+        stack_holder.push(StackItem::false_item())?;
+        stack_holder.push(StackItem::new(vec![Precompile::ModMul.id()]))?;
+        assert!(matches!(
+            OP_PRECOMPILE::execute(&mut stack_holder),
+            Err(StackError::PrecompileError(PrecompileError::ZeroModulus))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn precompile_fixed_point_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder(1_000)?;
+
+        // 1.05 as a 64.64 fixed-point number.
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(105)))?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(100)))?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedFromRatio.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        let rate = stack_holder.pop()?;
+        assert_eq!(
+            rate.to_stack_uint(),
+            Some(StackFixed::from_ratio(105, 100).unwrap().to_stack_uint())
+        );
+
+        // Compounded over 10 periods, applied to 1_000_000.
+        stack_holder.push(rate.clone())?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(10)))?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedPow.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        stack_holder.push(StackItem::from_stack_uint(StackUint::from(1_000_000)))?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedMulInt.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        assert_eq!(
+            stack_holder.pop()?.to_stack_uint(),
+            Some(StackUint::from(1628894))
+        );
+
+        // 1.05 * 1.05 / 1.05 is 1.05 give or take rounding.
+        stack_holder.push(rate.clone())?;
+        stack_holder.push(rate.clone())?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedMul.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        stack_holder.push(rate.clone())?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedDiv.id()]))?;
+        OP_PRECOMPILE::execute(&mut stack_holder)?;
+        let result =
+            StackFixed::from_stack_uint(&stack_holder.pop()?.to_stack_uint().unwrap()).unwrap();
+        assert_eq!(result.checked_mul_int(1000), Some(1049));
+
+        // A zero divisor fails.
+        stack_holder.push(rate)?;
+        stack_holder.push(StackItem::false_item())?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedDiv.id()]))?;
+        assert!(matches!(
+            OP_PRECOMPILE::execute(&mut stack_holder),
+            Err(StackError::PrecompileError(
+                PrecompileError::FixedPointDivisionByZero
+            ))
+        ));
+
+        // An overflowing product fails rather than wrapping.
+        stack_holder.push(StackItem::from_stack_uint(
+            StackFixed::from_int(u64::MAX).to_stack_uint(),
+        ))?;
+        stack_holder.push(StackItem::from_stack_uint(
+            StackFixed::from_int(2).to_stack_uint(),
+        ))?;
+        stack_holder.push(StackItem::new(vec![Precompile::FixedMul.id()]))?;
+        assert!(matches!(
+            OP_PRECOMPILE::execute(&mut stack_holder),
+            Err(StackError::PrecompileError(
+                PrecompileError::FixedPointOverflow
+            ))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn precompile_unknown_id_test() -> Result<(), StackError> {
+        let mut stack_holder = stack_holder(1_000)?;
+
+        // An unregistered id fails.
+        stack_holder.push(StackItem::new(vec![0xff]))?;
+        assert!(matches!(
+            OP_PRECOMPILE::execute(&mut stack_holder),
+            Err(StackError::PrecompileError(
+                PrecompileError::UnknownPrecompile(0xff)
+            ))
+        ));
+
+        // A multi-byte id fails.
+        stack_holder.push(StackItem::new(vec![0x01, 0x00]))?;
+        assert!(matches!(
+            OP_PRECOMPILE::execute(&mut stack_holder),
+            Err(StackError::PrecompileError(
+                PrecompileError::InvalidPrecompileIdBytes(_)
+            ))
+        ));
+
+        Ok(())
+    }
+}
@@ -170,7 +170,7 @@ mod executable_and_method_tests {
             let methods = vec![];
 
             let metadata = None;
-            let program = Executable::new(program_name, metadata, methods);
+            let program = Executable::new(program_name, metadata, false, methods);
 
             assert!(program.is_err());
         }
@@ -196,7 +196,7 @@ mod executable_and_method_tests {
 
             let metadata = None;
 
-            let program = Executable::new(program_name, metadata, methods);
+            let program = Executable::new(program_name, metadata, false, methods);
 
             assert!(program.is_err());
         }
@@ -236,7 +236,7 @@ mod executable_and_method_tests {
 
             let metadata = None;
 
-            let program = Executable::new(program_name, metadata, methods);
+            let program = Executable::new(program_name, metadata, false, methods);
 
             assert!(program.is_err());
         }
@@ -276,7 +276,7 @@ mod executable_and_method_tests {
 
             let metadata = None;
 
-            let program = Executable::new(program_name, metadata, methods);
+            let program = Executable::new(program_name, metadata, false, methods);
 
             assert!(program.is_err());
         }
@@ -318,7 +318,7 @@ mod executable_and_method_tests {
 
             let metadata = None;
 
-            let program = Executable::new(program_name, metadata, methods);
+            let program = Executable::new(program_name, metadata, false, methods);
 
             assert!(program.is_ok());
         }
@@ -360,7 +360,8 @@ mod executable_and_method_tests {
 
         let metadata = None;
 
-        let program = Executable::new(program_name, metadata, methods).unwrap();
+        let program =
+            Executable::new(program_name.clone(), metadata, false, methods.clone()).unwrap();
 
         let mut program_compiled_bytestream = program.compile().unwrap().into_iter();
 
@@ -368,6 +369,20 @@ mod executable_and_method_tests {
 
         assert_eq!(program, program_decompiled);
 
+        // The reentrancy opt-in survives a round trip, alongside metadata.
+        let reentrant_program =
+            Executable::new(program_name, Some(vec![0xde, 0xad]), true, methods).unwrap();
+
+        let mut reentrant_program_compiled_bytestream =
+            reentrant_program.compile().unwrap().into_iter();
+
+        let reentrant_program_decompiled =
+            Executable::decompile(&mut reentrant_program_compiled_bytestream).unwrap();
+
+        assert!(reentrant_program_decompiled.reentrant());
+        assert_eq!(reentrant_program, reentrant_program_decompiled);
+        assert_ne!(program.contract_id(), reentrant_program.contract_id());
+
         Ok(())
     }
 }
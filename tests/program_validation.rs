@@ -0,0 +1,258 @@
+#[cfg(test)]
+mod program_validation_tests {
+    use cube::{
+        constructive::calldata::element_type::CalldataElementType,
+        executive::{
+            executable::{
+                executable::Executable,
+                method::{
+                    limits::MIN_METHOD_OPCODE_COUNT, method_error::ScriptAnalysisError,
+                    method_type::MethodType, program_method::ProgramMethod,
+                },
+                program_error::ProgramValidationError,
+            },
+            opcode::{
+                opcode::Opcode,
+                opcodes::{
+                    arithmetic::op_add::OP_ADD,
+                    flow::{
+                        op_else::OP_ELSE, op_endif::OP_ENDIF, op_fail::OP_FAIL, op_if::OP_IF,
+                        op_jump::OP_JUMP, op_nop::OP_NOP, op_returnall::OP_RETURNALL,
+                        op_returnsome::OP_RETURNSOME,
+                    },
+                    push::{op_2::OP_2, op_true::OP_TRUE},
+                    stack::{op_2drop::OP_2DROP, op_drop::OP_DROP},
+                    storage::{op_sread::OP_SREAD, op_swrite::OP_SWRITE},
+                },
+            },
+        },
+    };
+
+    /// Returns a method with the given type, args and script, padding the script with `OP_NOP`s
+    /// up to the minimum opcode count.
+    fn method(
+        method_name: &str,
+        method_type: MethodType,
+        arg_types: Vec<CalldataElementType>,
+        mut script: Vec<Opcode>,
+    ) -> ProgramMethod {
+        while script.len() < MIN_METHOD_OPCODE_COUNT {
+            script.push(Opcode::OP_NOP(OP_NOP));
+        }
+        ProgramMethod::new(method_name.to_string(), method_type, arg_types, script).unwrap()
+    }
+
+    /// Returns a callable method without args.
+    fn callable(script: Vec<Opcode>) -> ProgramMethod {
+        method("call", MethodType::Callable, vec![], script)
+    }
+
+    #[test]
+    fn stack_analysis_test() {
+        // Popping from an empty stack underflows.
+        let underflow = callable(vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_ADD(OP_ADD),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(
+            underflow.analyze_script(),
+            Err(ScriptAnalysisError::StackUnderflow {
+                index: 1,
+                opcode: "OP_ADD".to_string(),
+                required: 2,
+                available: 1,
+            })
+        );
+
+        // The args are on the stack at the start.
+        let with_args = method(
+            "add",
+            MethodType::Callable,
+            vec![CalldataElementType::U32, CalldataElementType::U32],
+            vec![Opcode::OP_ADD(OP_ADD), Opcode::OP_RETURNALL(OP_RETURNALL)],
+        );
+        assert_eq!(with_args.analyze_script(), Ok(()));
+
+        // The depth after a conditional is the deepest of its branches.
+        let deepest_branch = callable(vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_2(OP_2),
+            Opcode::OP_ELSE(OP_ELSE),
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_2DROP(OP_2DROP),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(deepest_branch.analyze_script(), Ok(()));
+
+        // A failing branch does not count toward the depth.
+        let failing_branch = callable(vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_FAIL(OP_FAIL),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_DROP(OP_DROP),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert!(matches!(
+            failing_branch.analyze_script(),
+            Err(ScriptAnalysisError::StackUnderflow { index: 4, .. })
+        ));
+
+        // The stack is no longer followed past an opcode whose effect depends on runtime values.
+        let past_jump = callable(vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_JUMP(OP_JUMP),
+            Opcode::OP_2DROP(OP_2DROP),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(past_jump.analyze_script(), Ok(()));
+    }
+
+    #[test]
+    fn structure_analysis_test() {
+        // An `OP_ELSE` without a conditional.
+        let unexpected_else = callable(vec![
+            Opcode::OP_ELSE(OP_ELSE),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(
+            unexpected_else.analyze_script(),
+            Err(ScriptAnalysisError::UnexpectedElse(0))
+        );
+
+        // Two `OP_ELSE`s in the same conditional.
+        let duplicate_else = callable(vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_ELSE(OP_ELSE),
+            Opcode::OP_ELSE(OP_ELSE),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(
+            duplicate_else.analyze_script(),
+            Err(ScriptAnalysisError::DuplicateElse(3))
+        );
+
+        // An `OP_ENDIF` without a conditional.
+        let unexpected_endif = callable(vec![
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(
+            unexpected_endif.analyze_script(),
+            Err(ScriptAnalysisError::UnexpectedEndif(0))
+        );
+
+        // A conditional never closed.
+        let unclosed = callable(vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ]);
+        assert_eq!(
+            unclosed.analyze_script(),
+            Err(ScriptAnalysisError::UnclosedConditional(1))
+        );
+
+        // A script that never returns.
+        let no_return = callable(vec![Opcode::OP_TRUE(OP_TRUE), Opcode::OP_DROP(OP_DROP)]);
+        assert_eq!(
+            no_return.analyze_script(),
+            Err(ScriptAnalysisError::MissingReturn)
+        );
+    }
+
+    #[test]
+    fn banned_opcode_test() {
+        let script = vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_2(OP_2),
+            Opcode::OP_SWRITE(OP_SWRITE),
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_SREAD(OP_SREAD),
+            Opcode::OP_RETURNSOME(OP_RETURNSOME),
+        ];
+
+        // Callable methods may write to storage.
+        let callable_method = method("write", MethodType::Callable, vec![], script.clone());
+        assert_eq!(callable_method.analyze_script(), Ok(()));
+
+        // Read-only methods may not.
+        let read_only_method = method("write", MethodType::ReadOnly, vec![], script);
+        assert_eq!(
+            read_only_method.analyze_script(),
+            Err(ScriptAnalysisError::BannedOpcode {
+                index: 2,
+                opcode: "OP_SWRITE".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn program_validation_test() {
+        let returns = vec![Opcode::OP_TRUE(OP_TRUE), Opcode::OP_RETURNALL(OP_RETURNALL)];
+
+        // A valid program with a migration hook.
+        let valid = Executable::new(
+            "counter".to_string(),
+            None,
+            false,
+            vec![
+                method("increment", MethodType::Callable, vec![], returns.clone()),
+                method("migrate", MethodType::Internal, vec![], returns.clone()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(valid.validate(), Ok(()));
+
+        // A method named `migrate` that is not a migration hook.
+        let invalid_migration = Executable::new(
+            "counter".to_string(),
+            None,
+            false,
+            vec![
+                method("increment", MethodType::Callable, vec![], returns.clone()),
+                method(
+                    "migrate",
+                    MethodType::Internal,
+                    vec![CalldataElementType::U32],
+                    returns.clone(),
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            invalid_migration.validate(),
+            Err(ProgramValidationError::InvalidMigrationMethod)
+        );
+
+        // The failing method is named in the error.
+        let missing_return = Executable::new(
+            "counter".to_string(),
+            None,
+            false,
+            vec![
+                method("increment", MethodType::Callable, vec![], returns),
+                method(
+                    "decrement",
+                    MethodType::Callable,
+                    vec![],
+                    vec![Opcode::OP_TRUE(OP_TRUE)],
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            missing_return.validate(),
+            Err(ProgramValidationError::ScriptAnalysisError {
+                method_name: "decrement".to_string(),
+                error: ScriptAnalysisError::MissingReturn,
+            })
+        );
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod public_key_tests {
+    use cube::transmutative::key::{FromNostrKeyStr, ToNostrKeyStr};
+    use cube::transmutative::secp::public_key::{
+        account_key_from_slice, compressed_to_xonly, npub_to_xonly, parse_account_key,
+        validate_xonly, xonly_to_compressed, xonly_to_npub, PublicKeyError,
+    };
+    use cube::transmutative::secp::schnorr::Bytes32;
+
+    fn account_key() -> Result<[u8; 32], String> {
+        let secret_key: [u8; 32] =
+            hex::decode("bceef655b5a034911f1c3718ce056531b45ef03b4c7b1f15629e867294011a7d")
+                .map_err(|_| "Failed to parse secret key hex.".to_string())?
+                .try_into()
+                .map_err(|_| "Invalid key length. Expected 32 bytes.".to_string())?;
+        secret_key
+            .secret_to_public()
+            .ok_or("Failed to derive public key.".to_string())
+    }
+
+    #[test]
+    fn format_conversions() -> Result<(), String> {
+        let account_key = account_key()?;
+
+        // x-only <-> compressed SEC1.
+        let compressed = xonly_to_compressed(&account_key).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(compressed[0], 0x02);
+        assert_eq!(compressed[1..], account_key);
+        assert_eq!(compressed_to_xonly(&compressed), Ok(account_key));
+
+        // The parity of a compressed key is dropped.
+        let mut odd = compressed;
+        odd[0] = 0x03;
+        assert_eq!(compressed_to_xonly(&odd), Ok(account_key));
+
+        // x-only <-> npub, in agreement with the Nostr key traits.
+        let npub = xonly_to_npub(&account_key).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(Some(npub.clone()), account_key.to_npub());
+        assert_eq!(npub_to_xonly(&npub), Ok(account_key));
+        assert_eq!(npub.as_str().from_npub(), Some(account_key));
+
+        // Raw bytes of either length.
+        assert_eq!(account_key_from_slice(&account_key), Ok(account_key));
+        assert_eq!(account_key_from_slice(&compressed), Ok(account_key));
+
+        // Text inputs in any of the formats.
+        let xonly_hex = hex::encode(account_key);
+        let compressed_hex = hex::encode(compressed);
+        assert_eq!(parse_account_key(&npub), Ok(account_key));
+        assert_eq!(parse_account_key(&xonly_hex), Ok(account_key));
+        assert_eq!(
+            parse_account_key(&format!(" 0x{} ", xonly_hex)),
+            Ok(account_key)
+        );
+        assert_eq!(parse_account_key(&compressed_hex), Ok(account_key));
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_validation() -> Result<(), String> {
+        let account_key = account_key()?;
+
+        // x = 5 is not on the curve.
+        let mut off_curve = [0u8; 32];
+        off_curve[31] = 5;
+        assert_eq!(
+            validate_xonly(&off_curve),
+            Err(PublicKeyError::InvalidPoint)
+        );
+        assert_eq!(
+            account_key_from_slice(&off_curve),
+            Err(PublicKeyError::InvalidPoint)
+        );
+        assert_eq!(xonly_to_npub(&off_curve), Err(PublicKeyError::InvalidPoint));
+
+        // Prefixes other than 0x02 and 0x03.
+        let mut uncompressed_prefix = [0u8; 33];
+        uncompressed_prefix[0] = 0x04;
+        uncompressed_prefix[1..].copy_from_slice(&account_key);
+        assert_eq!(
+            compressed_to_xonly(&uncompressed_prefix),
+            Err(PublicKeyError::InvalidPrefix(0x04))
+        );
+
+        // Other lengths.
+        assert_eq!(
+            account_key_from_slice(&account_key[..31]),
+            Err(PublicKeyError::InvalidLength(31))
+        );
+        assert_eq!(
+            account_key_from_slice(&[0u8; 65]),
+            Err(PublicKeyError::InvalidLength(65))
+        );
+
+        // Malformed text inputs.
+        assert_eq!(parse_account_key("zz"), Err(PublicKeyError::InvalidHex));
+        assert_eq!(
+            parse_account_key("npub1qqqq"),
+            Err(PublicKeyError::InvalidNpub)
+        );
+        let nsec = account_key
+            .to_nsec()
+            .ok_or("Failed to encode nsec.".to_string())?;
+        assert_eq!(npub_to_xonly(&nsec), Err(PublicKeyError::InvalidNpub));
+
+        Ok(())
+    }
+}
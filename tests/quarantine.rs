@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod quarantine_tests {
+    use bitcoin::blockdata::constants::genesis_block;
+    use bitcoin::Network;
+    use cube::operative::tasks::chain_sync::quarantine::{BlockQuarantine, QuarantineReason};
+    use std::fs;
+
+    #[test]
+    fn quarantine_release_test() -> Result<(), String> {
+        let dir = std::env::temp_dir().join(format!("cube_quarantine_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let block_quarantine = BlockQuarantine::in_dir(dir.clone());
+        let block = genesis_block(Network::Bitcoin);
+
+        // A skipped block and a block halted on are both kept with their context.
+        block_quarantine
+            .quarantine(&block, 120, &QuarantineReason::MerkleRootMismatch, true)
+            .map_err(|err| err.to_string())?;
+        block_quarantine
+            .quarantine(
+                &block,
+                125,
+                &QuarantineReason::BatchExecutionFailed("boom".to_string()),
+                false,
+            )
+            .map_err(|err| err.to_string())?;
+        let quarantined_blocks = block_quarantine.list();
+        assert_eq!(quarantined_blocks.len(), 2);
+        assert_eq!(quarantined_blocks[0].height, 120);
+        assert_eq!(
+            quarantined_blocks[0].block_hash,
+            block.block_hash().to_string()
+        );
+        assert_eq!(block_quarantine.halted_on().map(|q| q.height), Some(125));
+        assert!(dir
+            .join(format!("120_{}", block.block_hash()))
+            .join("block.bin")
+            .exists());
+
+        // Releasing them clears the halt and marks the skipped block to be retried.
+        let released_blocks = block_quarantine
+            .release_all()
+            .map_err(|err| err.to_string())?;
+        assert_eq!(released_blocks.len(), 2);
+        assert!(block_quarantine.list().is_empty());
+        assert!(block_quarantine.halted_on().is_none());
+        assert_eq!(block_quarantine.retry_height(), Some(120));
+
+        block_quarantine.clear_retry_height();
+        assert_eq!(block_quarantine.retry_height(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+        Ok(())
+    }
+}
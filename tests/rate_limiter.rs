@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod rate_limiter_tests {
+    use cube::communicative::rate_limiter::token_bucket::TokenBucket;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn token_bucket_test() -> Result<(), String> {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(3, 2, start);
+
+        // The full burst is admitted at once.
+        for _ in 0..3 {
+            assert!(bucket.try_take(start).is_ok());
+        }
+
+        // The next request has to wait half a second for a refill.
+        let retry_after = bucket.try_take(start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        // A refilled token is admitted again.
+        let later = start + Duration::from_millis(500);
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_err());
+
+        // The bucket never refills beyond its capacity.
+        let much_later = start + Duration::from_secs(60);
+        assert!(bucket.is_full(much_later));
+        for _ in 0..3 {
+            assert!(bucket.try_take(much_later).is_ok());
+        }
+        assert!(bucket.try_take(much_later).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod read_call_cache_tests {
+    use cube::executive::{
+        stack::stack_item::StackItem,
+        vm::program_execution::read_call_cache::{ReadCallCache, ReadCallKey},
+    };
+
+    // Contract id.
+    const CONTRACT_ID: [u8; 32] = [0x0c; 32];
+
+    #[test]
+    fn read_call_cache_test() -> Result<(), String> {
+        let mut cache = ReadCallCache::new(2);
+        let args = vec![StackItem::new(vec![0x01])];
+        let key = ReadCallKey::new(CONTRACT_ID, 0, &args, cache.state_root());
+
+        // Nothing is cached yet.
+        assert_eq!(cache.get(&key), None);
+
+        // The result is served from the cache once cached.
+        cache.insert(key.clone(), vec![StackItem::true_item()]);
+        assert_eq!(cache.get(&key), Some(vec![StackItem::true_item()]));
+
+        // Calls with other args or to other methods are not.
+        let other_args = vec![StackItem::new(vec![0x02])];
+        assert_eq!(
+            cache.get(&ReadCallKey::new(
+                CONTRACT_ID,
+                0,
+                &other_args,
+                cache.state_root()
+            )),
+            None
+        );
+        assert_eq!(
+            cache.get(&ReadCallKey::new(CONTRACT_ID, 1, &args, cache.state_root())),
+            None
+        );
+
+        // The metrics count the lookups.
+        assert_eq!(cache.json()["hits"], 1);
+        assert_eq!(cache.json()["misses"], 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_call_cache_invalidation_test() -> Result<(), String> {
+        let mut cache = ReadCallCache::new(2);
+        let args = Vec::<StackItem>::new();
+        let key = ReadCallKey::new(CONTRACT_ID, 0, &args, cache.state_root());
+        cache.insert(key.clone(), vec![StackItem::true_item()]);
+
+        // Applying changes moves on to the new state root and drops the cached results.
+        cache.invalidate([0xaa; 32]);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.get(&key), None);
+
+        // Results executed against the previous state root are not cached.
+        cache.insert(key.clone(), vec![StackItem::true_item()]);
+        assert_eq!(cache.len(), 0);
+
+        // Results executed against the new one are.
+        let key = ReadCallKey::new(CONTRACT_ID, 0, &args, [0xaa; 32]);
+        cache.insert(key.clone(), vec![StackItem::false_item()]);
+        assert_eq!(cache.get(&key), Some(vec![StackItem::false_item()]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_call_cache_eviction_test() -> Result<(), String> {
+        let mut cache = ReadCallCache::new(2);
+        let keys: Vec<ReadCallKey> = (0..3)
+            .map(|method_index| {
+                ReadCallKey::new(CONTRACT_ID, method_index, &Vec::new(), cache.state_root())
+            })
+            .collect();
+
+        // At most two results are kept, the oldest evicted first.
+        for key in keys.iter() {
+            cache.insert(key.clone(), vec![StackItem::true_item()]);
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&keys[0]), None);
+        assert!(cache.get(&keys[1]).is_some());
+        assert!(cache.get(&keys[2]).is_some());
+
+        // A cache of size zero keeps nothing.
+        let mut cache = ReadCallCache::new(0);
+        cache.insert(keys[0].clone(), vec![StackItem::true_item()]);
+        assert_eq!(cache.len(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod rebroadcast_queue_tests {
+    use bitcoin::absolute::LockTime;
+    use bitcoin::transaction::Version;
+    use bitcoin::{Amount, ScriptBuf, Transaction, TxOut};
+    use cube::operative::run_args::chain::Chain;
+    use cube::operative::tasks::rebroadcast::broadcast_txn::BroadcastStatus;
+    use cube::operative::tasks::rebroadcast::rebroadcast_queue::{
+        erase_rebroadcast_queue, RebroadcastQueue, REBROADCAST_QUEUE,
+    };
+
+    fn transaction(value: u64) -> Transaction {
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn rebroadcast_queue_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+
+        // 2 Erase first the rebroadcast queue.
+        erase_rebroadcast_queue(chain);
+
+        // 3 Track two transactions.
+        let (confirming, expiring) = (transaction(1_000), transaction(2_000));
+        {
+            let rebroadcast_queue: REBROADCAST_QUEUE = RebroadcastQueue::new(chain)
+                .map_err(|err| format!("Error constructing rebroadcast queue: {:?}", err))?;
+            let mut _rebroadcast_queue = rebroadcast_queue.lock().await;
+            _rebroadcast_queue
+                .track(&confirming, None, 0)
+                .map_err(|err| format!("Error tracking transaction: {}", err))?;
+            _rebroadcast_queue
+                .track(&expiring, Some("Network error".to_string()), 0)
+                .map_err(|err| format!("Error tracking transaction: {}", err))?;
+        }
+
+        // 4 Reopen the rebroadcast queue; the transactions are persisted.
+        let rebroadcast_queue: REBROADCAST_QUEUE = RebroadcastQueue::new(chain)
+            .map_err(|err| format!("Error constructing rebroadcast queue: {:?}", err))?;
+        let mut _rebroadcast_queue = rebroadcast_queue.lock().await;
+        assert_eq!(_rebroadcast_queue.pending_txns().len(), 2);
+
+        // 5 Both are due for a rebroadcast once the interval elapses.
+        assert!(_rebroadcast_queue.due_for_rebroadcast(599).is_empty());
+        assert_eq!(_rebroadcast_queue.due_for_rebroadcast(600).len(), 2);
+        _rebroadcast_queue
+            .record_rebroadcast(&expiring.compute_txid(), None, 600)
+            .map_err(|err| format!("Error recording rebroadcast: {}", err))?;
+        let expiring_txn = _rebroadcast_queue
+            .get(&expiring.compute_txid())
+            .ok_or("Expiring transaction not tracked.")?;
+        assert_eq!(expiring_txn.broadcasts, 2);
+        assert_eq!(expiring_txn.last_error, None);
+
+        // 6 Confirmed transactions are not rebroadcast, and are settled once buried.
+        _rebroadcast_queue
+            .update_confirmations(&confirming.compute_txid(), 1)
+            .map_err(|err| format!("Error updating confirmations: {}", err))?;
+        assert_eq!(_rebroadcast_queue.due_for_rebroadcast(1_200).len(), 1);
+        _rebroadcast_queue
+            .update_confirmations(&confirming.compute_txid(), 6)
+            .map_err(|err| format!("Error updating confirmations: {}", err))?;
+        assert_eq!(
+            _rebroadcast_queue
+                .get(&confirming.compute_txid())
+                .map(|broadcast_txn| broadcast_txn.status),
+            Some(BroadcastStatus::Confirmed)
+        );
+
+        // 7 Unconfirmed transactions are given up on past the expiry.
+        let expired = _rebroadcast_queue
+            .expire(1_209_600)
+            .map_err(|err| format!("Error expiring transactions: {}", err))?;
+        assert_eq!(expired, vec![expiring.compute_txid()]);
+        assert_eq!(
+            _rebroadcast_queue
+                .get(&expiring.compute_txid())
+                .map(|broadcast_txn| broadcast_txn.status),
+            Some(BroadcastStatus::Expired)
+        );
+        assert!(_rebroadcast_queue.pending_txns().is_empty());
+
+        Ok(())
+    }
+}
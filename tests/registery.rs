@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod registery_tests {
+    use cube::inscriptive::fixtures::fixture_storage::fixture_namespace;
+    use cube::inscriptive::fixtures::registery_fixture::RegisteryFixture;
+    use cube::inscriptive::registery::registery::{
+        REGISTERY, SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD,
+    };
+
+    // Account key.
+    const ACCOUNT_KEY: [u8; 32] = [
+        0xfc, 0x7a, 0xca, 0xce, 0xf4, 0x50, 0x95, 0x60, 0x04, 0x27, 0xc6, 0x16, 0x87, 0x4a, 0x96,
+        0xb7, 0x0e, 0x16, 0xcd, 0x2a, 0xb2, 0xa0, 0xea, 0x31, 0xa4, 0xa6, 0xae, 0x83, 0x4d, 0xbf,
+        0x6f, 0x9d,
+    ];
+
+    #[tokio::test]
+    async fn secondary_aggregation_key_rotation_test() -> Result<(), String> {
+        let old_key = vec![0xaa; 32];
+        let new_key = vec![0xbb; 32];
+        let rotated_at: u64 = 1_000;
+
+        // 1 Construct the registery with the account registered with the old key.
+        let namespace = fixture_namespace().map_err(|e| format!("{:?}", e))?;
+        let registery: REGISTERY = RegisteryFixture::new()
+            .with_account_keys(ACCOUNT_KEY, None, Some(old_key.clone()))
+            .build_in(&namespace)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        {
+            let mut _registery = registery.lock().await;
+
+            // 1.1 No key was rotated away from yet.
+            assert!(_registery.is_account_secondary_aggregation_key_valid(
+                ACCOUNT_KEY,
+                &old_key,
+                0
+            ));
+            assert!(!_registery.is_account_secondary_aggregation_key_valid(
+                ACCOUNT_KEY,
+                &new_key,
+                0
+            ));
+
+            // 2 Rotate to the new key.
+            let previous_key = _registery
+                .set_or_update_account_secondary_aggregation_key(
+                    ACCOUNT_KEY,
+                    new_key.clone(),
+                    rotated_at,
+                )
+                .map_err(|e| format!("{:?}", e))?;
+            assert_eq!(previous_key, Some(old_key.clone()));
+            _registery.apply_changes().map_err(|e| format!("{:?}", e))?;
+            _registery.flush_delta();
+        }
+
+        // 3 Construct the registery again from what was applied.
+        drop(registery);
+        let registery: REGISTERY =
+            RegisteryFixture::reopen(&namespace).map_err(|e| format!("{:?}", e))?;
+        let _registery = registery.lock().await;
+
+        // 3.1 The previous key was persisted along with the rotation timestamp.
+        let account_body = _registery
+            .get_account_body_by_account_key(ACCOUNT_KEY)
+            .ok_or("Account body not found.".to_string())?;
+        assert_eq!(
+            account_body.secondary_aggregation_key,
+            Some(new_key.clone())
+        );
+        assert_eq!(
+            account_body.previous_secondary_aggregation_key,
+            Some((old_key.clone(), rotated_at))
+        );
+
+        // 3.2 The new key is valid at all times.
+        assert!(_registery.is_account_secondary_aggregation_key_valid(
+            ACCOUNT_KEY,
+            &new_key,
+            u64::MAX
+        ));
+
+        // 3.3 The old key is valid until the grace period ends.
+        let grace_period_end = rotated_at + SECONDARY_AGGREGATION_KEY_ROTATION_GRACE_PERIOD;
+        assert!(_registery.is_account_secondary_aggregation_key_valid(
+            ACCOUNT_KEY,
+            &old_key,
+            grace_period_end
+        ));
+        assert!(!_registery.is_account_secondary_aggregation_key_valid(
+            ACCOUNT_KEY,
+            &old_key,
+            grace_period_end + 1
+        ));
+
+        // 3.4 Unknown keys and accounts are not valid.
+        assert!(!_registery.is_account_secondary_aggregation_key_valid(
+            ACCOUNT_KEY,
+            &[0xcc; 32],
+            rotated_at
+        ));
+        assert!(!_registery
+            .is_account_secondary_aggregation_key_valid([0x02; 32], &new_key, rotated_at));
+
+        Ok(())
+    }
+}
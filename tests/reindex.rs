@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod reindex_tests {
+    use cube::operative::reindex::reindex::{
+        clear_reindex_in_progress, is_reindex_in_progress, mark_reindex_in_progress,
+    };
+    use cube::operative::run_args::chain::Chain;
+
+    #[test]
+    fn reindex_marker_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+
+        // 2 Clear first any leftover marker.
+        clear_reindex_in_progress(chain).map_err(|err| format!("{:?}", err))?;
+        assert!(!is_reindex_in_progress(chain));
+
+        // 3 A started reindex stays marked, as after a crash midway.
+        mark_reindex_in_progress(chain).map_err(|err| format!("{:?}", err))?;
+        assert!(is_reindex_in_progress(chain));
+
+        // 4 A completed reindex clears the marker, and clearing it again is harmless.
+        clear_reindex_in_progress(chain).map_err(|err| format!("{:?}", err))?;
+        assert!(!is_reindex_in_progress(chain));
+        clear_reindex_in_progress(chain).map_err(|err| format!("{:?}", err))?;
+
+        Ok(())
+    }
+}
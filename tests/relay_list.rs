@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod relay_list_tests {
+    use cube::communicative::nns::errors::relay_list_error::RelayListError;
+    use cube::communicative::nns::relay::DEFAULT_RELAY_LIST;
+    use cube::communicative::nns::relay_health::RelayHealth;
+    use cube::communicative::nns::relay_list::{
+        erase_relay_list, RelayList, PUBLISHING_RELAY_COUNT, RELAY_LIST,
+    };
+    use cube::operative::run_args::chain::Chain;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn relay_health_test() {
+        // 1 A fast and reliable relay scores better than an unmeasured one.
+        let mut fast = RelayHealth::new();
+        fast.record_success(100);
+        fast.record_success(100);
+        assert_eq!(fast.latency_ms(), Some(100));
+        assert!(fast.score() < RelayHealth::new().score());
+
+        // 2 Failures worsen the score of an otherwise equal relay.
+        let mut flaky = fast.clone();
+        flaky.record_failure(10);
+        flaky.record_failure(11);
+        assert_eq!(flaky.failure_rate(), 0.5);
+        assert!(flaky.score() > fast.score());
+
+        // 3 Latency is a moving average leaning towards the earlier measurements.
+        let mut slowing = fast.clone();
+        slowing.record_success(900);
+        assert_eq!(slowing.latency_ms(), Some(300));
+    }
+
+    #[tokio::test]
+    async fn relay_list_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+        let peer_key = [0x01u8; 32];
+        let addr: SocketAddr = "127.0.0.1:6272".parse().unwrap();
+
+        // 2 Erase first the relay list.
+        erase_relay_list(chain);
+
+        // 3 The relay list is seeded with the default relays.
+        {
+            let relay_list: RELAY_LIST = RelayList::new(chain)
+                .map_err(|err| format!("Error constructing relay list: {}", err))?;
+            let mut _relay_list = relay_list.lock().await;
+            assert_eq!(_relay_list.relays().len(), DEFAULT_RELAY_LIST.len());
+
+            // 3.a Add a relay and remove a default one.
+            assert_eq!(_relay_list.add_relay("wss://relay.example.com/"), Ok(true));
+            assert_eq!(_relay_list.add_relay("wss://relay.example.com"), Ok(false));
+            assert_eq!(_relay_list.remove_relay(DEFAULT_RELAY_LIST[0]), Ok(true));
+            assert_eq!(_relay_list.remove_relay(DEFAULT_RELAY_LIST[0]), Ok(false));
+            assert!(matches!(
+                _relay_list.add_relay("https://relay.example.com"),
+                Err(RelayListError::InvalidRelayUrl(_))
+            ));
+
+            // 3.b Set a direct peer.
+            assert_eq!(_relay_list.set_direct_peer(peer_key, addr), Ok(true));
+            assert_eq!(_relay_list.set_direct_peer(peer_key, addr), Ok(false));
+        }
+
+        // 4 Reopen the relay list; changes are persisted and defaults are not re-seeded.
+        let relay_list: RELAY_LIST = RelayList::new(chain)
+            .map_err(|err| format!("Error constructing relay list: {}", err))?;
+        let mut _relay_list = relay_list.lock().await;
+        let relays = _relay_list.relays();
+        assert_eq!(relays.len(), DEFAULT_RELAY_LIST.len());
+        assert!(relays.contains(&"wss://relay.example.com".to_string()));
+        assert!(!relays.contains(&DEFAULT_RELAY_LIST[0].to_string()));
+        assert_eq!(_relay_list.direct_peer(peer_key), Some(addr));
+
+        // 5 Publishing relays are the healthiest ones.
+        _relay_list.record_success("wss://relay.example.com", 50);
+        _relay_list.record_failure(DEFAULT_RELAY_LIST[1], 10);
+        let publishing_relays = _relay_list.publishing_relays();
+        assert_eq!(publishing_relays.len(), PUBLISHING_RELAY_COUNT);
+        assert_eq!(publishing_relays[0], "wss://relay.example.com");
+        assert_eq!(
+            _relay_list.relays().last(),
+            Some(&DEFAULT_RELAY_LIST[1].to_string())
+        );
+
+        // 6 The last relay cannot be removed.
+        for relay in _relay_list.relays().iter().skip(1) {
+            assert_eq!(_relay_list.remove_relay(relay), Ok(true));
+        }
+        assert_eq!(
+            _relay_list.remove_relay("wss://relay.example.com"),
+            Err(RelayListError::LastRelay)
+        );
+
+        // 7 Remove the direct peer.
+        assert_eq!(_relay_list.remove_direct_peer(peer_key), Ok(true));
+        assert_eq!(_relay_list.direct_peer(peer_key), None);
+
+        // 8 Erase the relay list.
+        drop(_relay_list);
+        drop(relay_list);
+        erase_relay_list(chain);
+
+        Ok(())
+    }
+}
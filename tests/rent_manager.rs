@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod rent_manager_tests {
+    use cube::inscriptive::rent_manager::rent_manager::{
+        erase_rent_manager, RentManager, RENT_MANAGER,
+    };
+    use cube::inscriptive::rent_manager::rent_policy::RentPolicy;
+    use cube::operative::run_args::chain::Chain;
+
+    /// Contract ID.
+    const CONTRACT_ID: [u8; 32] = [0xc1; 32];
+
+    /// A rent policy charging every 10 batches, with a grace window of 2 periods.
+    const POLICY: RentPolicy = RentPolicy {
+        period: 10,
+        per_kb_state_rent: 100,
+        per_alloc_rent: 5,
+        grace_periods: 2,
+    };
+
+    #[test]
+    fn rent_policy_test() {
+        // 1 Rent is charged per 1000 state bytes and per shadow allocation.
+        assert_eq!(POLICY.rent_per_period(2_000, 0), 200);
+        assert_eq!(POLICY.rent_per_period(2_000, 4), 220);
+        assert_eq!(POLICY.rent_per_period(0, 0), 0);
+        assert_eq!(POLICY.grace_window(), 20);
+
+        // 2 Rent is optional per chain.
+        assert!(RentPolicy::for_chain(Chain::Mainnet).is_none());
+        assert!(RentPolicy::for_chain(Chain::Testbed).is_none());
+        assert!(RentPolicy::for_chain(Chain::Signet).is_some());
+    }
+
+    #[tokio::test]
+    async fn rent_manager_test() -> Result<(), String> {
+        // 1 Erase and construct the rent manager.
+        let chain = Chain::Testbed;
+        erase_rent_manager(chain);
+        let rent_manager: RENT_MANAGER =
+            RentManager::new(chain, Some(POLICY)).map_err(|e| format!("{:?}", e))?;
+
+        {
+            let mut _rent_manager = rent_manager.lock().await;
+
+            // 2 A contract seen for the first time starts paid up.
+            assert_eq!(
+                _rent_manager.charge(CONTRACT_ID, 100, 1_000, 0, 1_000),
+                None
+            );
+            assert_eq!(
+                _rent_manager
+                    .get_status(CONTRACT_ID)
+                    .map(|status| status.paid_up_to),
+                Some(100)
+            );
+
+            // 3 Nothing is charged until a whole period has elapsed.
+            assert_eq!(
+                _rent_manager.charge(CONTRACT_ID, 109, 1_000, 0, 1_000),
+                None
+            );
+
+            // 4 Two elapsed periods are charged at once.
+            let charge = _rent_manager
+                .charge(CONTRACT_ID, 125, 1_000, 0, 1_000)
+                .ok_or("Expected a charge.".to_string())?;
+            assert_eq!((charge.charged, charge.shortfall), (200, 0));
+            assert!(!charge.tombstoned);
+            assert_eq!(
+                _rent_manager
+                    .get_status(CONTRACT_ID)
+                    .map(|status| status.paid_up_to),
+                Some(120)
+            );
+
+            // 5 A balance short of the rent is drained and the contract enters the grace window.
+            let charge = _rent_manager
+                .charge(CONTRACT_ID, 130, 1_000, 0, 40)
+                .ok_or("Expected a charge.".to_string())?;
+            assert_eq!((charge.charged, charge.shortfall), (40, 60));
+            assert!(!charge.tombstoned);
+            assert_eq!(
+                _rent_manager
+                    .get_status(CONTRACT_ID)
+                    .and_then(|status| status.insolvent_since),
+                Some(130)
+            );
+
+            // 6 Paying a period in full clears the contract.
+            let charge = _rent_manager
+                .charge(CONTRACT_ID, 140, 1_000, 0, 1_000)
+                .ok_or("Expected a charge.".to_string())?;
+            assert_eq!(charge.shortfall, 0);
+            assert_eq!(
+                _rent_manager
+                    .get_status(CONTRACT_ID)
+                    .and_then(|status| status.insolvent_since),
+                None
+            );
+
+            // 7 Staying insolvent past the grace window tombstones the contract.
+            _rent_manager.pre_execution();
+            let charge = _rent_manager
+                .charge(CONTRACT_ID, 150, 1_000, 0, 0)
+                .ok_or("Expected a charge.".to_string())?;
+            assert!(!charge.tombstoned);
+            let charge = _rent_manager
+                .charge(CONTRACT_ID, 160, 1_000, 0, 0)
+                .ok_or("Expected a charge.".to_string())?;
+            assert!(!charge.tombstoned);
+            let charge = _rent_manager
+                .charge(CONTRACT_ID, 170, 1_000, 0, 0)
+                .ok_or("Expected a charge.".to_string())?;
+            assert!(charge.tombstoned);
+            assert!(_rent_manager.is_tombstoned(CONTRACT_ID));
+            assert_eq!(_rent_manager.tombstoned_contracts(), vec![CONTRACT_ID]);
+
+            // 8 A tombstoned contract is no longer charged.
+            assert_eq!(
+                _rent_manager.charge(CONTRACT_ID, 200, 1_000, 0, 1_000),
+                None
+            );
+
+            // 9 Rolling back reverts the tombstoning.
+            _rent_manager.rollback_last();
+            assert!(!_rent_manager.is_tombstoned(CONTRACT_ID));
+
+            // 10 Apply the changes.
+            _rent_manager
+                .apply_changes()
+                .map_err(|e| format!("{:?}", e))?;
+        }
+
+        // 11 The rent statuses persist across a restart.
+        drop(rent_manager);
+        let rent_manager: RENT_MANAGER =
+            RentManager::new(chain, Some(POLICY)).map_err(|e| format!("{:?}", e))?;
+        {
+            let _rent_manager = rent_manager.lock().await;
+            assert_eq!(
+                _rent_manager
+                    .get_status(CONTRACT_ID)
+                    .map(|status| status.paid_up_to),
+                Some(140)
+            );
+        }
+
+        // 12 Erase the rent manager.
+        drop(rent_manager);
+        erase_rent_manager(chain);
+
+        Ok(())
+    }
+}
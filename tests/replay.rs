@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod replay_tests {
+    use cube::inscriptive::archival_manager::archival_manager::{
+        ArchivalManager, ARCHIVAL_MANAGER,
+    };
+    use cube::inscriptive::fixtures::fixture_storage::{fixture_namespace, FIXTURE_CHAIN};
+    use cube::inscriptive::storage::storage::with_storage_namespace;
+    use cube::operative::replay::{
+        errors::replay_error::ReplayError,
+        replay::replay,
+        replay_report::{ReplayMismatch, ReplayReport},
+    };
+    use cube::operative::run_args::chain::Chain;
+
+    #[test]
+    fn journaled_state_root_test() -> Result<(), String> {
+        // 1 Keep the dbs of the test in memory, under a namespace of its own.
+        let namespace = fixture_namespace().map_err(|e| format!("{:?}", e))?;
+
+        // 2 Run the test on a runtime of its own.
+        // NOTE: The runtime runs on this thread only, so the dbs the replay opens are kept under
+        // the namespace as well.
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("{:?}", e))?;
+        with_storage_namespace(&namespace, || {
+            runtime.block_on(journaled_state_root(FIXTURE_CHAIN))
+        })
+    }
+
+    async fn journaled_state_root(chain: Chain) -> Result<(), String> {
+        // 1 Construct the archival manager.
+        let archival_manager: ARCHIVAL_MANAGER =
+            ArchivalManager::new(chain).map_err(|e| format!("{:?}", e))?;
+
+        {
+            let mut _archival_manager = archival_manager.lock().await;
+
+            // 2 Journal the state roots of two batches.
+            _archival_manager
+                .insert_state_root(1, [0x01; 32])
+                .map_err(|e| format!("{:?}", e))?;
+            _archival_manager
+                .insert_state_root(2, [0x02; 32])
+                .map_err(|e| format!("{:?}", e))?;
+
+            // 3 Read them back.
+            assert_eq!(_archival_manager.state_root_by_height(1), Some([0x01; 32]));
+            assert_eq!(_archival_manager.state_root_by_height(2), Some([0x02; 32]));
+            assert_eq!(_archival_manager.state_root_by_height(3), None);
+        }
+
+        // 4 Construct the archival manager again from what was journaled.
+        drop(archival_manager);
+        let archival_manager: ARCHIVAL_MANAGER =
+            ArchivalManager::new(chain).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            archival_manager.lock().await.state_root_by_height(2),
+            Some([0x02; 32])
+        );
+        drop(archival_manager);
+
+        // 5 A batch without a journaled state root cannot be replayed, and nothing is wiped.
+        assert!(matches!(
+            replay(chain, 2, 3).await,
+            Err(ReplayError::MissingJournaledStateRoot(3))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_height_range_test() {
+        let chain = Chain::Testbed;
+
+        // Batch heights start from #1.
+        assert!(matches!(
+            replay(chain, 0, 1).await,
+            Err(ReplayError::InvalidHeightRange(0, 1))
+        ));
+
+        // The range must not be inverted.
+        assert!(matches!(
+            replay(chain, 5, 4).await,
+            Err(ReplayError::InvalidHeightRange(5, 4))
+        ));
+    }
+
+    #[test]
+    fn replay_report_test() {
+        let report = ReplayReport {
+            from_batch_height: 1,
+            to_batch_height: 3,
+            mismatches: vec![
+                ReplayMismatch::BatchRecord(2),
+                ReplayMismatch::StateRoot {
+                    batch_height: 2,
+                    journaled: [0x01; 32],
+                    replayed: [0x02; 32],
+                },
+            ],
+        };
+        assert!(!report.matches());
+        assert_eq!(report.mismatches[1].batch_height(), 2);
+        assert_eq!(report.json()["mismatches"][0]["kind"], "batch_record");
+        assert_eq!(
+            report.json()["mismatches"][1]["replayed"],
+            hex::encode([0x02; 32])
+        );
+
+        let clean = ReplayReport {
+            mismatches: vec![],
+            ..report
+        };
+        assert!(clean.matches());
+    }
+}
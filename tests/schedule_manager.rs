@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod schedule_manager_tests {
+    use cube::inscriptive::schedule_manager::deferred_call::DeferredCall;
+    use cube::inscriptive::schedule_manager::errors::cancel_error::ScheduleManagerCancelError;
+    use cube::inscriptive::schedule_manager::errors::schedule_error::ScheduleManagerScheduleError;
+    use cube::inscriptive::schedule_manager::schedule_manager::{
+        erase_schedule_manager, ScheduleManager, MAX_DEFERRED_CALLS_PER_HEIGHT,
+        MAX_SCHEDULE_HORIZON, SCHEDULE_MANAGER,
+    };
+    use cube::operative::run_args::chain::Chain;
+
+    /// Alice's account key.
+    const ALICE: [u8; 32] = [0xaa; 32];
+
+    /// Bob's account key.
+    const BOB: [u8; 32] = [0xbb; 32];
+
+    /// Returns a deferred call of the scheduler to execute at the batch height.
+    fn deferred_call(scheduler: [u8; 32], execute_at: u64, arg: u8) -> DeferredCall {
+        DeferredCall {
+            scheduler,
+            contract_id: [0xcc; 32],
+            method_index: 1,
+            args: vec![vec![arg]],
+            ops_budget: 1_000,
+            prepaid_fees: 100_000,
+            execute_at,
+            scheduled_at: 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn schedule_manager_test() -> Result<(), String> {
+        // 1 Erase and construct the schedule manager.
+        let chain = Chain::Testbed;
+        erase_schedule_manager(chain);
+        let schedule_manager: SCHEDULE_MANAGER =
+            ScheduleManager::new(chain).map_err(|e| format!("{:?}", e))?;
+
+        // 2 Schedule calls at batch heights #12, #11 and #12 again.
+        let (first, second, third) = (
+            deferred_call(ALICE, 12, 1),
+            deferred_call(BOB, 11, 2),
+            deferred_call(ALICE, 12, 3),
+        );
+        let (first_id, second_id, third_id) = {
+            let mut _schedule_manager = schedule_manager.lock().await;
+            _schedule_manager.pre_execution();
+            let first_id = _schedule_manager
+                .schedule(first.clone(), 10)
+                .map_err(|e| format!("{:?}", e))?;
+            let second_id = _schedule_manager
+                .schedule(second.clone(), 10)
+                .map_err(|e| format!("{:?}", e))?;
+            let third_id = _schedule_manager
+                .schedule(third.clone(), 10)
+                .map_err(|e| format!("{:?}", e))?;
+            (first_id, second_id, third_id)
+        };
+        assert_eq!(first_id, first.id());
+
+        // 3 Invalid schedules are rejected.
+        {
+            let mut _schedule_manager = schedule_manager.lock().await;
+
+            // 3.1 The current batch height is not in the future.
+            assert!(matches!(
+                _schedule_manager.schedule(deferred_call(ALICE, 10, 4), 10),
+                Err(ScheduleManagerScheduleError::ExecutionHeightNotInTheFuture(
+                    10, 10
+                ))
+            ));
+
+            // 3.2 Past the horizon.
+            assert!(matches!(
+                _schedule_manager.schedule(deferred_call(ALICE, 11 + MAX_SCHEDULE_HORIZON, 4), 10),
+                Err(ScheduleManagerScheduleError::ExecutionHeightBeyondHorizon(
+                    _,
+                    _
+                ))
+            ));
+
+            // 3.3 The same call twice.
+            assert!(matches!(
+                _schedule_manager.schedule(first.clone(), 10),
+                Err(ScheduleManagerScheduleError::DeferredCallAlreadyScheduled(
+                    _
+                ))
+            ));
+
+            // 3.4 No ops budget.
+            let mut zero_budget = deferred_call(ALICE, 12, 4);
+            zero_budget.ops_budget = 0;
+            assert!(matches!(
+                _schedule_manager.schedule(zero_budget, 10),
+                Err(ScheduleManagerScheduleError::ZeroOpsBudget)
+            ));
+        }
+
+        // 4 Only the scheduler may cancel a call.
+        {
+            let mut _schedule_manager = schedule_manager.lock().await;
+            assert!(matches!(
+                _schedule_manager.cancel(third_id, BOB),
+                Err(ScheduleManagerCancelError::NotTheScheduler(_, BOB))
+            ));
+            assert_eq!(
+                _schedule_manager
+                    .cancel(third_id, ALICE)
+                    .map_err(|e| format!("{:?}", e))?,
+                third
+            );
+            assert!(matches!(
+                _schedule_manager.cancel(third_id, ALICE),
+                Err(ScheduleManagerCancelError::DeferredCallNotFound(_))
+            ));
+        }
+
+        // 5 Apply the changes, and reload the schedule manager from disk.
+        {
+            let mut _schedule_manager = schedule_manager.lock().await;
+            _schedule_manager
+                .apply_changes()
+                .map_err(|e| format!("{:?}", e))?;
+        }
+        drop(schedule_manager);
+        let schedule_manager: SCHEDULE_MANAGER =
+            ScheduleManager::new(chain).map_err(|e| format!("{:?}", e))?;
+        let mut _schedule_manager = schedule_manager.lock().await;
+        assert_eq!(
+            _schedule_manager.deferred_calls_until(u64::MAX),
+            vec![second.clone(), first.clone()]
+        );
+        assert_eq!(_schedule_manager.get_deferred_call(third_id), None);
+
+        // 6 Draining is rolled back along with the execution.
+        _schedule_manager.pre_execution();
+        assert_eq!(_schedule_manager.drain_due(11), vec![second.clone()]);
+        assert_eq!(_schedule_manager.get_deferred_call(second_id), None);
+        _schedule_manager.rollback_last();
+        assert_eq!(
+            _schedule_manager.get_deferred_call(second_id),
+            Some(second.clone())
+        );
+
+        // 7 Due calls are drained in height order, including the ones past due.
+        _schedule_manager.pre_execution();
+        assert_eq!(_schedule_manager.drain_due(12), vec![second, first]);
+        assert!(_schedule_manager.drain_due(12).is_empty());
+        _schedule_manager
+            .apply_changes()
+            .map_err(|e| format!("{:?}", e))?;
+        assert!(_schedule_manager.deferred_calls_until(u64::MAX).is_empty());
+
+        // 8 A batch height holds a bounded number of calls.
+        for arg in 0..MAX_DEFERRED_CALLS_PER_HEIGHT as u8 {
+            _schedule_manager
+                .schedule(deferred_call(BOB, 20, arg), 10)
+                .map_err(|e| format!("{:?}", e))?;
+        }
+        assert!(matches!(
+            _schedule_manager.schedule(deferred_call(BOB, 20, 0xff), 10),
+            Err(ScheduleManagerScheduleError::BatchHeightScheduleFull(20))
+        ));
+
+        Ok(())
+    }
+}
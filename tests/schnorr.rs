@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod schnorr_tests {
+    use cube::transmutative::secp::context::GeneratorMul;
     use cube::transmutative::secp::schnorr::{self, SchnorrSigningMode};
     use hex;
+    use secp::{MaybePoint, MaybeScalar, Scalar};
 
     #[test]
     fn sign() -> Result<(), String> {
@@ -48,4 +50,105 @@ mod schnorr_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn rfc6979_nonce() -> Result<(), String> {
+        // Secp256k1 test vectors, signing the SHA-256 of "Satoshi Nakamoto".
+        let message: [u8; 32] =
+            hex::decode("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e")
+                .map_err(|_| format!("Failed to parse message hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert message hex.".to_string())?;
+
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+            ),
+        ];
+
+        for (secret_key_hex, expected_nonce_hex) in vectors {
+            let secret_key: [u8; 32] = hex::decode(secret_key_hex)
+                .map_err(|_| format!("Failed to parse secret key hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert secret key hex.".to_string())?;
+
+            let nonce = schnorr::rfc6979_nonce(secret_key, message, None)
+                .ok_or("Failed to derive nonce.".to_string())?;
+
+            assert_eq!(hex::encode(nonce.serialize()), expected_nonce_hex);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn sign_deterministic() -> Result<(), String> {
+        let message: [u8; 32] =
+            hex::decode("1dd8312636f6a0bf3d21fa2855e63072507453e93a5ced4301b364e91c9d87d6")
+                .map_err(|_| format!("Failed to parse message hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert message hex.".to_string())?;
+
+        let secret_key: [u8; 32] =
+            hex::decode("2795044ce0f83f718bc79c5f2add1e52521978df91ce9b7f82c9097191d33602")
+                .map_err(|_| format!("Failed to parse secret key hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert secret key hex.".to_string())?;
+
+        let public_key: [u8; 32] =
+            hex::decode("d0ea35e4a5d654109aef6b175672ea98099212a42d028fcf8bd4e38c137ff15a")
+                .map_err(|_| format!("Failed to parse public key hex."))?
+                .try_into()
+                .map_err(|_| "Failed to convert public key hex.".to_string())?;
+
+        let cube_sig = schnorr::sign(secret_key, message, SchnorrSigningMode::Cube).unwrap();
+        let bip340_sig = schnorr::sign(secret_key, message, SchnorrSigningMode::BIP340).unwrap();
+
+        // Signing is deterministic.
+        assert_eq!(
+            schnorr::sign(secret_key, message, SchnorrSigningMode::Cube),
+            Some(cube_sig)
+        );
+
+        // The two modes never share a nonce.
+        assert_ne!(cube_sig[..32], bip340_sig[..32]);
+
+        assert!(schnorr::verify_xonly(
+            public_key,
+            message,
+            cube_sig,
+            SchnorrSigningMode::Cube
+        ));
+        assert!(schnorr::verify_xonly(
+            public_key,
+            message,
+            bip340_sig,
+            SchnorrSigningMode::BIP340
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn generator_mul() -> Result<(), String> {
+        // The precomputed tables agree with the generic multiplication.
+        let mut scalars = vec![Scalar::one(), Scalar::two(), Scalar::max()];
+        for _ in 0..16 {
+            let secret = schnorr::generate_secret();
+            scalars.push(Scalar::from_slice(&secret).map_err(|_| "Invalid secret.".to_string())?);
+        }
+        for scalar in scalars {
+            assert_eq!(scalar.generator_mul(), scalar.base_point_mul());
+        }
+
+        // Zero maps to the point at infinity.
+        assert_eq!(MaybeScalar::Zero.generator_mul(), MaybePoint::Infinity);
+
+        Ok(())
+    }
 }
@@ -226,6 +226,7 @@ mod simul_tests {
             &Arc::clone(&privileges_manager),
             &Arc::clone(&params_manager),
             Some(Arc::clone(&archival_manager)),
+            None,
         );
 
         // 18 Begin the session.
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod simulation_tests {
+    use async_trait::async_trait;
+    use bitcoin::Block;
+    use cube::operative::simulation::sim_node::{NodeKey, SimActor, SimCtx, SimRole};
+    use cube::operative::simulation::simulation::{SimTraceEntry, SimTraceKind, Simulation};
+    use sha2::{Digest, Sha256};
+
+    /// Coordinator key.
+    const COORDINATOR: NodeKey = [0x01; 32];
+
+    /// Operator keys.
+    const OPERATORS: [NodeKey; 2] = [[0x02; 32], [0x03; 32]];
+
+    /// Node keys.
+    const NODES: [NodeKey; 2] = [[0x04; 32], [0x05; 32]];
+
+    /// A toy protocol: the coordinator proposes a batch on every block, operators acknowledge it,
+    /// and once every operator has acknowledged a batch the coordinator commits it to everyone.
+    /// Each node folds the committed batches into its state root.
+    struct ToyActor {
+        state_root: [u8; 32],
+        acks: Vec<(Vec<u8>, NodeKey)>,
+    }
+
+    impl ToyActor {
+        fn new() -> Box<dyn SimActor> {
+            Box::new(ToyActor {
+                state_root: [0; 32],
+                acks: Vec::new(),
+            })
+        }
+
+        fn commit(&mut self, batch: &[u8]) {
+            let mut hasher = Sha256::new();
+            hasher.update(self.state_root);
+            hasher.update(batch);
+            self.state_root = hasher.finalize().into();
+        }
+    }
+
+    #[async_trait]
+    impl SimActor for ToyActor {
+        async fn on_block(&mut self, ctx: &mut SimCtx, height: u64, _block: &Block) {
+            if ctx.role() == SimRole::Coordinator {
+                let mut proposal = b"propose:".to_vec();
+                proposal.extend(height.to_be_bytes());
+                ctx.send_to_role(SimRole::Operator, proposal);
+            }
+        }
+
+        async fn on_message(&mut self, ctx: &mut SimCtx, from: NodeKey, payload: Vec<u8>) {
+            match payload.split_at(payload.iter().position(|b| *b == b':').unwrap() + 1) {
+                (b"propose:", batch) => {
+                    let mut ack = b"ack:".to_vec();
+                    ack.extend(batch);
+                    ctx.send(from, ack);
+                }
+                (b"ack:", batch) => {
+                    self.acks.push((batch.to_vec(), from));
+                    let acked = self.acks.iter().filter(|(b, _)| b == batch).count();
+                    if acked == OPERATORS.len() {
+                        let mut commit = b"commit:".to_vec();
+                        commit.extend(batch);
+                        ctx.broadcast(commit);
+                        self.commit(batch);
+                    }
+                }
+                (b"commit:", batch) => self.commit(batch),
+                _ => {}
+            }
+        }
+
+        async fn state_root(&self) -> [u8; 32] {
+            self.state_root
+        }
+    }
+
+    /// Returns a simulation of one coordinator, two operators and two nodes.
+    fn simulation(seed: u64) -> Simulation {
+        let mut simulation = Simulation::new(seed, 100);
+        simulation.add_node(COORDINATOR, SimRole::Coordinator, ToyActor::new);
+        for key in OPERATORS {
+            simulation.add_node(key, SimRole::Operator, ToyActor::new);
+        }
+        for key in NODES {
+            simulation.add_node(key, SimRole::Node, ToyActor::new);
+        }
+        simulation.transport_mut().set_latency(20, 30);
+        simulation
+    }
+
+    /// Runs a simulation scripted with three blocks ten minutes apart, and returns its trace.
+    async fn run(seed: u64) -> (Vec<SimTraceEntry>, bool) {
+        let mut simulation = simulation(seed);
+        for i in 0..3 {
+            simulation.script_block(i * 600_000, vec![]);
+        }
+        simulation.run_until(3_600_000).await;
+        assert_eq!(simulation.chain().tip_height(), Some(102));
+        assert_eq!(simulation.clock().now(), 3_600_000);
+        (simulation.trace().to_vec(), simulation.converged().await)
+    }
+
+    #[tokio::test]
+    async fn simulation_test() {
+        // 1 Every node commits the three batches and ends on the same state root.
+        let (trace, converged) = run(7).await;
+        assert!(converged);
+
+        // 2 Each block reaches all five nodes.
+        let blocks = trace
+            .iter()
+            .filter(|entry| matches!(entry.kind, SimTraceKind::Block(_)))
+            .count();
+        assert_eq!(blocks, 15);
+
+        // 3 The same seed replays the same trace.
+        assert_eq!(run(7).await.0, trace);
+
+        // 4 Another seed draws other latencies.
+        assert_ne!(run(8).await.0, trace);
+    }
+
+    #[tokio::test]
+    async fn simulation_partition_test() {
+        // 1 Cut a node off from the coordinator.
+        let mut simulation = simulation(7);
+        simulation.transport_mut().partition(COORDINATOR, NODES[0]);
+
+        // 2 The node misses the commit of the batch.
+        simulation.mine_block(vec![]);
+        simulation.run_until_idle(1_000).await;
+        assert!(!simulation.converged().await);
+
+        // 3 Once the link is healed, the node catches up with the next batch, but its state root
+        // still diverges since it missed the first one.
+        simulation.transport_mut().heal_all();
+        simulation.mine_block(vec![]);
+        simulation.run_until_idle(1_000).await;
+        let state_roots = simulation.state_roots().await;
+        let diverging = state_roots
+            .iter()
+            .filter(|(_, _, state_root)| *state_root != state_roots[0].2)
+            .count();
+        assert_eq!(diverging, 1);
+        assert_eq!(simulation.transport_mut().in_flight_count(), 0);
+    }
+}
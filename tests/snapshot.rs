@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod snapshot_tests {
+    use cube::operative::bootstrap::snapshot::SnapshotBundle;
+    use cube::operative::bootstrap::trust_anchors::TrustAnchors;
+    use cube::operative::run_args::chain::Chain;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn trust_anchors_test() -> Result<(), String> {
+        let root_a = [0xaa; 32];
+        let root_b = [0xbb; 32];
+        let trust_anchors = TrustAnchors::parse(&format!(
+            "100:{}, 200:{}",
+            hex::encode(root_a),
+            hex::encode(root_b)
+        ))
+        .ok_or("Failed to parse the trust anchors.")?;
+
+        // A state root is only trusted at its own batch height.
+        assert!(trust_anchors.is_trusted(100, root_a));
+        assert!(trust_anchors.is_trusted(200, root_b));
+        assert!(!trust_anchors.is_trusted(100, root_b));
+        assert!(!trust_anchors.is_trusted(300, root_a));
+
+        // Malformed pairs are rejected; an empty list has no anchors.
+        assert!(TrustAnchors::parse("100").is_none());
+        assert!(TrustAnchors::parse("100:abcd").is_none());
+        assert!(TrustAnchors::parse("")
+            .ok_or("Empty list rejected.")?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_bundle_roundtrip_test() -> Result<(), String> {
+        let mut trees = BTreeMap::new();
+        trees.insert(
+            b"__sled__default".to_vec(),
+            vec![(vec![0x01, 0x02], vec![0x03]), (vec![0x04], vec![])],
+        );
+        let mut databases = BTreeMap::new();
+        databases.insert("coins/accounts".to_string(), trees);
+
+        let snapshot = SnapshotBundle {
+            chain: Chain::Signet,
+            bitcoin_sync_height: 250_000,
+            batch_height: 42,
+            state_root: [0x11; 32],
+            databases,
+        };
+
+        // The bundle survives the round trip.
+        let decoded = SnapshotBundle::from_bytes(&snapshot.to_bytes())
+            .ok_or("Failed to decode the snapshot bundle.")?;
+        assert_eq!(decoded, snapshot);
+
+        // Unknown databases and garbage are rejected.
+        let mut tampered = snapshot.clone();
+        let trees = tampered.databases.remove("coins/accounts").unwrap();
+        tampered.databases.insert("../elsewhere".to_string(), trees);
+        assert!(SnapshotBundle::from_bytes(&tampered.to_bytes()).is_none());
+        assert!(SnapshotBundle::from_bytes(b"not a snapshot").is_none());
+
+        Ok(())
+    }
+}
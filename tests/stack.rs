@@ -16,9 +16,11 @@ mod stack_tests {
             },
             splice::op_cat::OP_CAT,
         },
+        opcode::ops::{OP_SHADOW_DEALLOC_OPS, OP_SHADOW_DEALLOC_REFUND_OPS},
         stack::{
+            limits::{MAX_OPS_REFUND_QUOTIENT, OPS_LIMIT},
             stack::Stack,
-            stack_error::StackError,
+            stack_error::{OpsBudgetError, StackError},
             stack_holder::StackHolder,
             stack_item::StackItem,
             stack_uint::{StackItemUintExt, StackUint},
@@ -834,4 +836,77 @@ mod stack_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn ops_metering_test() -> Result<(), StackError> {
+        // Initialize stack with an ops budget of 10.
+        let mut stack_holder =
+            StackHolder::new(Caller::new_account([0; 32]), [0; 32], 0, 0, 10, 1, 0, 0)?;
+
+        // Ops are counted against the budget.
+        stack_holder.increment_ops(8)?;
+        assert_eq!(stack_holder.internal_ops_counter(), 8);
+        assert_eq!(stack_holder.external_ops_counter(), 8);
+
+        // Exceeding the budget fails, leaving the counters as they were.
+        assert!(matches!(
+            stack_holder.increment_ops(3),
+            Err(StackError::OpsBudgetError(
+                OpsBudgetError::InternalOpsBudgetExceeded
+            ))
+        ));
+        assert_eq!(stack_holder.internal_ops_counter(), 8);
+
+        // Refunds only give back internal ops, up to a fifth of the ops spent.
+        stack_holder.refund_ops(5);
+        assert_eq!(stack_holder.internal_ops_counter(), 7);
+        assert_eq!(stack_holder.external_ops_counter(), 8);
+        assert_eq!(stack_holder.refunded_ops(), 1);
+        stack_holder.refund_ops(5);
+        assert_eq!(stack_holder.internal_ops_counter(), 7);
+
+        // The refunded ops may be spent again.
+        stack_holder.increment_ops(3)?;
+        assert_eq!(stack_holder.internal_ops_counter(), 10);
+        assert_eq!(stack_holder.external_ops_counter(), 11);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ops_refund_cap_test() -> Result<(), StackError> {
+        // Initialize stack with an ops budget below the ops limit.
+        let mut stack_holder =
+            StackHolder::new(Caller::new_account([0; 32]), [0; 32], 0, 0, 50_000, 1, 0, 0)?;
+
+        // A loop jumping back to a dealloc, each dealloc refunding more than the loop costs, still
+        // runs out of budget.
+        let result = (0..OPS_LIMIT).try_for_each(|_| {
+            stack_holder.increment_ops(1)?;
+            stack_holder.increment_ops(OP_SHADOW_DEALLOC_OPS)?;
+            stack_holder.refund_ops(OP_SHADOW_DEALLOC_REFUND_OPS);
+            Ok::<(), StackError>(())
+        });
+        assert!(matches!(
+            result,
+            Err(StackError::OpsBudgetError(
+                OpsBudgetError::InternalOpsBudgetExceeded
+            ))
+        ));
+
+        // The ops refunded never exceed a fifth of the ops spent.
+        assert!(
+            stack_holder.refunded_ops()
+                <= stack_holder.external_ops_counter() / MAX_OPS_REFUND_QUOTIENT
+        );
+
+        // The cap holds across calls, with the ops refunded by the earlier calls carried over.
+        let mut stack_holder =
+            StackHolder::new(Caller::new_account([0; 32]), [0; 32], 0, 0, 50_000, 1, 8, 10)?;
+        stack_holder.carry_refunded_ops(2);
+        stack_holder.refund_ops(5);
+        assert_eq!(stack_holder.internal_ops_counter(), 8);
+
+        Ok(())
+    }
 }
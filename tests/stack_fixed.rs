@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod stack_fixed_tests {
+    use cube::executive::stack::{stack_fixed::StackFixed, stack_uint::StackUint};
+
+    #[test]
+    fn stack_fixed_test() -> Result<(), String> {
+        // Ratios round down.
+        let third = StackFixed::from_ratio(1, 3).ok_or("Ratio not found.")?;
+        assert_eq!(third.raw(), 6148914691236517205);
+        assert_eq!(
+            StackFixed::from_ratio(1, 2),
+            Some(
+                StackFixed::ONE
+                    .checked_div(&StackFixed::from_int(2))
+                    .unwrap()
+            )
+        );
+        assert_eq!(StackFixed::from_ratio(1, 0), None);
+
+        // 0.5 * 3 = 1.5
+        let half = StackFixed::from_ratio(1, 2).unwrap();
+        assert_eq!(
+            half.checked_mul(&StackFixed::from_int(3)),
+            Some(StackFixed::from_ratio(3, 2).unwrap())
+        );
+
+        // 1 / (1 / 3) is just above 3, as the third was rounded down.
+        let three = StackFixed::ONE.checked_div(&third).unwrap();
+        assert_eq!(three.floor(), 3);
+        assert_eq!(three.raw(), (3u128 << 64) + 3);
+
+        // Additions and subtractions are checked.
+        assert_eq!(half.checked_add(&half), Some(StackFixed::ONE));
+        assert_eq!(half.checked_sub(&StackFixed::ONE), None);
+        assert_eq!(
+            StackFixed::from_raw(u128::MAX).checked_add(&StackFixed::from_raw(1)),
+            None
+        );
+
+        // The raw representation round-trips through the stack integer.
+        assert_eq!(
+            StackFixed::from_stack_uint(&three.to_stack_uint()),
+            Some(three)
+        );
+        assert_eq!(
+            StackFixed::from_stack_uint(&(StackUint::from(1) << 128)),
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn stack_fixed_interest_test() -> Result<(), String> {
+        // A 5% rate.
+        let rate = StackFixed::from_ratio(105, 100).unwrap();
+
+        // 1000 at 5% is 1049, as 1.05 is not exact in binary and the product rounds down.
+        assert_eq!(rate.checked_mul_int(1000), Some(1049));
+
+        // 1.05^10 = 1.628894626...
+        let compounded = rate.checked_pow(10).unwrap();
+        assert_eq!(compounded.floor(), 1);
+        assert_eq!(compounded.checked_mul_int(1_000_000), Some(1628894));
+
+        // Anything to the power of zero is one.
+        assert_eq!(rate.checked_pow(0), Some(StackFixed::ONE));
+
+        Ok(())
+    }
+
+    #[test]
+    fn stack_fixed_overflow_test() -> Result<(), String> {
+        // The integer part holds up to 64 bits.
+        let max_int = StackFixed::from_int(u64::MAX);
+        assert_eq!(max_int.checked_mul(&StackFixed::from_int(2)), None);
+        assert_eq!(max_int.checked_div(&half()), None);
+
+        // 2^63 fits, 2^64 does not.
+        let two = StackFixed::from_int(2);
+        assert_eq!(two.checked_pow(63), Some(StackFixed::from_int(1 << 63)));
+        assert_eq!(two.checked_pow(64), None);
+
+        // Products down to an integer hold up to 128 bits.
+        assert_eq!(max_int.checked_mul_int(u128::MAX), None);
+
+        Ok(())
+    }
+
+    /// Returns the fixed-point number one half.
+    fn half() -> StackFixed {
+        StackFixed::from_ratio(1, 2).unwrap()
+    }
+}
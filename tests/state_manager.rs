@@ -1,9 +1,7 @@
 #[cfg(test)]
 mod state_manager_tests {
-    use cube::inscriptive::state_manager::state_manager::{
-        erase_state_manager, StateManager, STATE_MANAGER,
-    };
-    use cube::operative::run_args::chain::Chain;
+    use cube::inscriptive::fixtures::state_manager_fixture::StateManagerFixture;
+    use cube::inscriptive::state_manager::state_manager::STATE_MANAGER;
 
     // First contract ID.
     const CONTRACT_ID_1: [u8; 32] = [
@@ -33,40 +31,19 @@ mod state_manager_tests {
 
     #[tokio::test]
     async fn state_manager_tests() -> Result<(), String> {
-        // 1 Set the chain for local tests.
-        let chain = Chain::Testbed;
-
-        // 2 Erase first the state manager.
-        erase_state_manager(chain);
-
-        // 3 Construct the state manager.
-        let state_manager: STATE_MANAGER = StateManager::new(chain).unwrap();
-
-        // 4 Pre-execution.
-        {
-            state_manager.lock().await.pre_execution();
-        }
-
-        // 5 Register the first contract.
-        {
-            // 5.1 Lock the state manager.
-            let mut _state_manager = state_manager.lock().await;
-
-            // 5.2 Register the first contract.
-            let result = _state_manager.register_contract(CONTRACT_ID_1);
-            assert!(result.is_ok());
-        }
-
-        // 6 Apply changes.
-        let result = state_manager.lock().await.apply_changes();
-        assert!(result.is_ok());
-
-        // 7 Insert the first state.
+        // 1 Construct the state manager with the first contract registered.
+        let state_manager: STATE_MANAGER = StateManagerFixture::new()
+            .with_contract(CONTRACT_ID_1)
+            .build()
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 2 Insert the first state.
         {
-            // 7.1 Lock the state manager.
+            // 2.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 7.2 Insert the state.
+            // 2.2 Insert the state.
             let result = _state_manager.insert_update_state(
                 CONTRACT_ID_1,
                 &Vec::from(STATE_KEY_1),
@@ -76,16 +53,16 @@ mod state_manager_tests {
             assert!(result.is_ok());
         }
 
-        // 8 Apply changes.
+        // 3 Apply changes.
         let result = state_manager.lock().await.apply_changes();
         assert!(result.is_ok());
 
-        // 9 Insert the second state.
+        // 4 Insert the second state.
         {
-            // 9.1 Lock the state manager.
+            // 4.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 9.2 Insert the state.
+            // 4.2 Insert the state.
             let result = _state_manager.insert_update_state(
                 CONTRACT_ID_1,
                 &Vec::from(STATE_KEY_2),
@@ -95,34 +72,34 @@ mod state_manager_tests {
             assert!(result.is_ok());
         }
 
-        // 10 Apply changes.
+        // 5 Apply changes.
         let result = state_manager.lock().await.apply_changes();
         assert!(result.is_ok());
 
-        // 11 Read the first state.
+        // 6 Read the first state.
         {
-            // 11.1 Lock the state manager.
+            // 6.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 11.2 Read the state.
+            // 6.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_1, &Vec::from(STATE_KEY_1));
             assert!(result.is_some());
 
-            // 11.3 Assert match.
+            // 6.3 Assert match.
             assert_eq!(result.unwrap(), STATE_VALUE_1);
         }
 
-        // 12 Pre-execution before rollback test.
+        // 7 Pre-execution before rollback test.
         {
             state_manager.lock().await.pre_execution();
         }
 
-        // 13 Insert the third state.
+        // 8 Insert the third state.
         {
-            // 13.1 Lock the state manager.
+            // 8.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 13.2 Insert the state.
+            // 8.2 Insert the state.
             let result = _state_manager.insert_update_state(
                 CONTRACT_ID_1,
                 &Vec::from(STATE_KEY_3),
@@ -134,46 +111,46 @@ mod state_manager_tests {
 
         // NOTE: This time we are not applying changes.
 
-        // 14 Try to read the third state.
+        // 9 Try to read the third state.
         {
-            // 14.1 Lock the state manager.
+            // 9.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 14.2 Read the state.
+            // 9.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_1, &Vec::from(STATE_KEY_3));
             assert!(result.is_some());
 
-            // 14.3 Assert match.
+            // 9.3 Assert match.
             assert_eq!(result.unwrap(), STATE_VALUE_3);
         }
 
-        // 15 Rollback the changes.
+        // 10 Rollback the changes.
         {
-            // 15.1 Lock the state manager.
+            // 10.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 15.2 Restore old states by rolling back.
+            // 10.2 Restore old states by rolling back.
             _state_manager.rollback_last();
         }
 
-        // 16 Try to read the third state again post-rollback.
+        // 11 Try to read the third state again post-rollback.
         {
-            // 16.1 Lock the state manager.
+            // 11.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 16.2 Read the state.
+            // 11.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_1, &Vec::from(STATE_KEY_3));
 
-            // 16.3 Should be none as the state was rolled back.
+            // 11.3 Should be none as the state was rolled back.
             assert!(result.is_none());
         }
 
-        // 17 Insert the third state again.
+        // 12 Insert the third state again.
         {
-            // 17.1 Lock the state manager.
+            // 12.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 17.2 Insert the state.
+            // 12.2 Insert the state.
             let result = _state_manager.insert_update_state(
                 CONTRACT_ID_1,
                 &Vec::from(STATE_KEY_3),
@@ -183,109 +160,109 @@ mod state_manager_tests {
             assert!(result.is_ok());
         }
 
-        // 18 Apply changes.
+        // 13 Apply changes.
         let result = state_manager.lock().await.apply_changes();
         assert!(result.is_ok());
 
-        // 19 Remove the second state.
+        // 14 Remove the second state.
         {
-            // 19.1 Lock the state manager.
+            // 14.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 19.2 Remove the state.
+            // 14.2 Remove the state.
             let result = _state_manager.remove_state(CONTRACT_ID_1, &Vec::from(STATE_KEY_2), false);
             assert!(result.is_ok());
         }
 
-        // 20 Try to read the second state post-removal.
+        // 15 Try to read the second state post-removal.
         {
-            // 20.1 Lock the state manager.
+            // 15.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 20.2 Read the state.
+            // 15.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_1, &Vec::from(STATE_KEY_2));
 
-            // 20.3 Should be none as the state was removed.
+            // 15.3 Should be none as the state was removed.
             assert!(result.is_none());
         }
 
-        // 21 Apply changes.
+        // 16 Apply changes.
         let result = state_manager.lock().await.apply_changes();
         assert!(result.is_ok());
 
-        // 22 Pre-execution before second rollback test.
+        // 17 Pre-execution before second rollback test.
         {
             state_manager.lock().await.pre_execution();
         }
 
-        // 23 Remove the first state.
+        // 18 Remove the first state.
         {
-            // 23.1 Lock the state manager.
+            // 18.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 23.2 Remove the state.
+            // 18.2 Remove the state.
             let result = _state_manager.remove_state(CONTRACT_ID_1, &Vec::from(STATE_KEY_1), false);
             assert!(result.is_ok());
         }
 
         // NOTE: This time we are not applying changes.
 
-        // 24 Try to read the first state post-removal.
+        // 19 Try to read the first state post-removal.
         {
-            // 24.1 Lock the state manager.
+            // 19.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 24.2 Read the state.
+            // 19.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_1, &Vec::from(STATE_KEY_1));
 
-            // 24.3 Should be none as the state was removed.
+            // 19.3 Should be none as the state was removed.
             assert!(result.is_none());
         }
 
-        // 25 Rollback the changes.
+        // 20 Rollback the changes.
         {
-            // 25.1 Lock the state manager.
+            // 20.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 25.2 Restore old states by rolling back.
+            // 20.2 Restore old states by rolling back.
             _state_manager.rollback_last();
         }
 
-        // 26 Try to read the first state again post-rollback.
+        // 21 Try to read the first state again post-rollback.
         {
-            // 26.1 Lock the state manager.
+            // 21.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 26.2 Read the state.
+            // 21.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_1, &Vec::from(STATE_KEY_1));
 
-            // 26.3 Should be some as the removal was rolled back.
+            // 21.3 Should be some as the removal was rolled back.
             assert!(result.is_some());
 
-            // 26.4 Assert match.
+            // 21.4 Assert match.
             assert_eq!(result.unwrap(), STATE_VALUE_1);
         }
 
-        // 27 Register the second contract.
+        // 22 Register the second contract.
         {
-            // 27.1 Lock the state manager.
+            // 22.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 27.2 Register the second contract.
+            // 22.2 Register the second contract.
             let result = _state_manager.register_contract(CONTRACT_ID_2);
             assert!(result.is_ok());
         }
 
-        // 28 Apply changes.
+        // 23 Apply changes.
         let result = state_manager.lock().await.apply_changes();
         assert!(result.is_ok());
 
-        // 29 Insert the third state into the second contract.
+        // 24 Insert the third state into the second contract.
         {
-            // 29.1 Lock the state manager.
+            // 24.1 Lock the state manager.
             let mut _state_manager = state_manager.lock().await;
 
-            // 29.2 Insert the state.
+            // 24.2 Insert the state.
             let result = _state_manager.insert_update_state(
                 CONTRACT_ID_2,
                 &Vec::from(STATE_KEY_3),
@@ -295,20 +272,20 @@ mod state_manager_tests {
             assert!(result.is_ok());
         }
 
-        // 30 Apply changes.
+        // 25 Apply changes.
         let result = state_manager.lock().await.apply_changes();
         assert!(result.is_ok());
 
-        // 31 Try to read the third state from the second contract.
+        // 26 Try to read the third state from the second contract.
         {
-            // 31.1 Lock the state manager.
+            // 26.1 Lock the state manager.
             let _state_manager = state_manager.lock().await;
 
-            // 31.2 Read the state.
+            // 26.2 Read the state.
             let result = _state_manager.get_state_value(CONTRACT_ID_2, &Vec::from(STATE_KEY_3));
             assert!(result.is_some());
 
-            // 31.3 Assert match.
+            // 26.3 Assert match.
             assert_eq!(result.unwrap(), STATE_VALUE_3);
         }
 
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod sync_pipeline_tests {
+    use bitcoin::blockdata::constants::genesis_block;
+    use bitcoin::consensus::encode::serialize;
+    use bitcoin::Network;
+    use cube::operative::tasks::chain_sync::quarantine::QuarantineReason;
+    use cube::operative::tasks::chain_sync::sync_pipeline::{parse_block, verify_block};
+
+    #[test]
+    fn sync_pipeline_parse_verify_test() -> Result<(), String> {
+        let block = genesis_block(Network::Bitcoin);
+
+        // A block served by the Bitcoin node parses back into the same block and verifies.
+        let parsed_block =
+            parse_block(&hex::encode(serialize(&block))).ok_or("Failed to parse the block.")?;
+        assert_eq!(parsed_block, block);
+        let verified_block = verify_block(0, parsed_block);
+        assert_eq!(verified_block.height, 0);
+        assert_eq!(verified_block.invalid_reason, None);
+
+        // Malformed blocks are rejected.
+        assert!(parse_block("zz").is_none());
+        assert!(parse_block("0100").is_none());
+
+        // A block whose transactions were tampered with fails verification.
+        let mut tampered_block = block.clone();
+        tampered_block.txdata[0].output[0].value = bitcoin::Amount::from_sat(1);
+        assert_eq!(
+            verify_block(0, tampered_block).invalid_reason,
+            Some(QuarantineReason::MerkleRootMismatch)
+        );
+
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod taproot_tests {
     use cube::constructive::taproot::{ControlBlock, TapBranch, TapLeaf, TapRoot, TapTree};
+    use cube::operative::run_args::chain::Chain;
+    use cube::transmutative::codec::address::encode_p2tr;
+    use cube::transmutative::musig::keyagg::MusigKeyAggCtx;
     use secp::Point;
     use std::error::Error;
 
@@ -397,4 +400,66 @@ mod taproot_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_taproot_aggregated() -> Result<(), Box<dyn Error>> {
+        let keys = vec![
+            Point::from_slice(&hex::decode(
+                "028c17db0c798574086299e5041ffbcfa06bd501eb0e50914731bfbd2f3c9f980e",
+            )?)
+            .unwrap(),
+            Point::from_slice(&hex::decode(
+                "037b55a1c853b28c398141c8fdf4eb69469430f019983af4be4b5aa7512936f295",
+            )?)
+            .unwrap(),
+        ];
+        let agg_inner_key = MusigKeyAggCtx::new(&keys, None).unwrap().agg_inner_key();
+
+        // Test - Key-path only with the aggregated keys
+
+        let tap_root = TapRoot::key_path_aggregated(&keys).unwrap();
+        assert_eq!(tap_root.spk(), TapRoot::key_path_only(agg_inner_key).spk());
+
+        // Test - Key-and-script-path with the aggregated keys
+
+        let leaves = vec![
+            TapLeaf::new(vec![0xaa, 0xbb]),
+            TapLeaf::new(vec![0xcc, 0xdd]),
+            TapLeaf::new(vec![0xee, 0xff]),
+        ];
+        let tap_root = TapRoot::key_and_script_path_aggregated(&keys, leaves.clone()).unwrap();
+        let expected = TapRoot::key_and_script_path_multi(agg_inner_key, leaves);
+        assert_eq!(tap_root.spk(), expected.spk());
+
+        // Test - Script-path trees need at least one leaf
+
+        assert!(TapRoot::key_and_script_path_aggregated(&keys, vec![]).is_none());
+
+        // Test - Control blocks are looked up by tapscript
+
+        assert_eq!(tap_root.leaf_index(&vec![0xcc, 0xdd]), Some(1));
+        assert_eq!(tap_root.leaf_index(&vec![0x00]), None);
+        assert_eq!(
+            tap_root
+                .control_block_for_script(&vec![0xee, 0xff])
+                .unwrap()
+                .to_vec(),
+            tap_root.control_block(2).unwrap().to_vec()
+        );
+        assert!(TapRoot::key_path_aggregated(&keys)
+            .unwrap()
+            .control_block_for_script(&vec![0xaa, 0xbb])
+            .is_none());
+
+        // Test - Address encodes the output key
+
+        let output_key = tap_root.tweaked_key().unwrap().serialize_xonly();
+        assert_eq!(
+            tap_root.address(Chain::Mainnet),
+            encode_p2tr(Chain::Mainnet, output_key)
+        );
+        assert!(tap_root.address(Chain::Signet).unwrap().starts_with("tb1p"));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tcp_compression_tests {
+    use cube::communicative::tcp::compression::{
+        self, COMPRESSION_THRESHOLD, MAX_DECOMPRESSED_LEN,
+    };
+    use cube::communicative::tcp::compression_error::TCPCompressionError;
+    use cube::communicative::tcp::package::{PackageKind, TCPPackage};
+    use std::io::Write;
+
+    #[test]
+    fn compress_and_decompress() -> Result<(), String> {
+        // 1 Compress a large, repetitive package.
+        let payload = b"delta".repeat(4 * COMPRESSION_THRESHOLD);
+        let package = TCPPackage::new(PackageKind::InFlightSyncProtocol, 1_700_000_000, &payload);
+        let compressed_package = compression::compress(TCPPackage::new(
+            package.kind(),
+            package.timestamp(),
+            &package.payload(),
+        ));
+        assert!(compressed_package.kind() == PackageKind::Compressed);
+        assert_eq!(compressed_package.timestamp(), package.timestamp());
+        assert!(compressed_package.wire_len() < package.wire_len());
+
+        // 2 Decompress it back.
+        let decompressed_package = compression::decompress(compressed_package)
+            .map_err(|e| format!("Failed to decompress: {:?}", e))?;
+        assert!(decompressed_package.kind() == PackageKind::InFlightSyncProtocol);
+        assert_eq!(decompressed_package.timestamp(), package.timestamp());
+        assert_eq!(decompressed_package.payload(), payload);
+
+        Ok(())
+    }
+
+    #[test]
+    fn small_and_incompressible_packages_are_left_as_they_are() -> Result<(), String> {
+        // 1 A package below the threshold.
+        let small_package = compression::compress(TCPPackage::new(
+            PackageKind::HeartbeatProtocol,
+            1_700_000_000,
+            &[0u8; 64],
+        ));
+        assert!(small_package.kind() == PackageKind::HeartbeatProtocol);
+
+        // 2 A package that does not shrink.
+        let random_payload: Vec<u8> = (0..4 * COMPRESSION_THRESHOLD)
+            .map(|_| rand::random::<u8>())
+            .collect();
+        let random_package = compression::compress(TCPPackage::new(
+            PackageKind::BatchContainerProtocol,
+            1_700_000_000,
+            &random_payload,
+        ));
+        assert!(random_package.kind() == PackageKind::BatchContainerProtocol);
+        assert_eq!(random_package.payload(), random_payload);
+
+        // 3 Packages that are not compressed pass decompression through.
+        let passed_package = compression::decompress(random_package)
+            .map_err(|e| format!("Failed to pass through: {:?}", e))?;
+        assert_eq!(passed_package.payload(), random_payload);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_packages_are_refused() -> Result<(), String> {
+        // 1 A payload that is not a zstd frame.
+        let garbage_package =
+            TCPPackage::new(PackageKind::Compressed, 1_700_000_000, &[0xffu8; 32]);
+        assert!(compression::decompress(garbage_package).is_err());
+
+        // 2 A compressed package whose timestamp differs from the inner one.
+        let compressed_package = compression::compress(TCPPackage::new(
+            PackageKind::InFlightSyncProtocol,
+            1_700_000_000,
+            &[0u8; 4 * COMPRESSION_THRESHOLD],
+        ));
+        let respliced_package = TCPPackage::new(
+            PackageKind::Compressed,
+            1_700_000_001,
+            &compressed_package.payload(),
+        );
+        assert_eq!(
+            compression::decompress(respliced_package).err(),
+            Some(TCPCompressionError::MalformedPackage)
+        );
+
+        // 3 A compressed package nested in another.
+        let inner_package = TCPPackage::new(
+            PackageKind::Compressed,
+            1_700_000_000,
+            &compressed_package.payload(),
+        );
+        let nested_payload = zstd::encode_all(&inner_package.serialize()[..], 3)
+            .map_err(|e| format!("Failed to compress: {:?}", e))?;
+        let nested_package =
+            TCPPackage::new(PackageKind::Compressed, 1_700_000_000, &nested_payload);
+        assert_eq!(
+            compression::decompress(nested_package).err(),
+            Some(TCPCompressionError::MalformedPackage)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn decompression_is_bounded() -> Result<(), String> {
+        // A small frame expanding past the limit.
+        let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 19)
+            .map_err(|e| format!("Failed to create the encoder: {:?}", e))?;
+        let zeros = vec![0u8; 1024 * 1024];
+        for _ in 0..(MAX_DECOMPRESSED_LEN / zeros.len() + 1) {
+            encoder
+                .write_all(&zeros)
+                .map_err(|e| format!("Failed to compress: {:?}", e))?;
+        }
+        let bomb_payload = encoder
+            .finish()
+            .map_err(|e| format!("Failed to compress: {:?}", e))?;
+        assert!(bomb_payload.len() < 1024 * 1024);
+
+        let bomb_package = TCPPackage::new(PackageKind::Compressed, 1_700_000_000, &bomb_payload);
+        assert_eq!(
+            compression::decompress(bomb_package).err(),
+            Some(TCPCompressionError::PackageTooLarge)
+        );
+
+        Ok(())
+    }
+}
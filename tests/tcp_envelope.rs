@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tcp_envelope_tests {
+    use cube::communicative::tcp::envelope;
+    use cube::communicative::tcp::envelope_error::TCPEnvelopeError;
+    use cube::communicative::tcp::package::{PackageKind, TCPPackage};
+    use nostr_sdk::Keys;
+
+    #[test]
+    fn seal_and_open() -> Result<(), String> {
+        let node_keys = Keys::generate();
+        let engine_keys = Keys::generate();
+        let node_key = node_keys.public_key().to_bytes();
+        let engine_key = engine_keys.public_key().to_bytes();
+
+        // 1 Seal a package to the engine.
+        let package = TCPPackage::new(PackageKind::DeltaCosignProtocol, 1_700_000_000, b"cosign");
+        let sealed_package = envelope::seal(&node_keys, engine_key, 7, &package)
+            .map_err(|e| format!("Failed to seal: {:?}", e))?;
+        assert!(sealed_package.kind() == PackageKind::Envelope);
+        assert_eq!(sealed_package.timestamp(), package.timestamp());
+        assert!(!sealed_package
+            .payload()
+            .windows(6)
+            .any(|window| window == b"cosign"));
+
+        // 2 The engine opens it and learns the node key.
+        let (sender_key, sequence, opened_package) = envelope::open(&engine_keys, &sealed_package)
+            .map_err(|e| format!("Failed to open: {:?}", e))?;
+        assert_eq!(sender_key, node_key);
+        assert_eq!(sequence, 7);
+        assert_eq!(opened_package.serialize(), package.serialize());
+
+        // 3 Nobody else can open it.
+        assert_eq!(
+            envelope::open(&Keys::generate(), &sealed_package).err(),
+            Some(TCPEnvelopeError::RecipientKeyMismatch)
+        );
+
+        // 4 A package reflected back to its sender is refused, even with the header rewritten.
+        let mut reflected_payload = sealed_package.payload();
+        reflected_payload[1..33].copy_from_slice(&engine_key);
+        reflected_payload[33..65].copy_from_slice(&node_key);
+        let reflected_package = TCPPackage::new(
+            PackageKind::Envelope,
+            sealed_package.timestamp(),
+            &reflected_payload,
+        );
+        assert_eq!(
+            envelope::open(&node_keys, &reflected_package).err(),
+            Some(TCPEnvelopeError::SenderKeyMismatch)
+        );
+
+        // 5 A tampered package or timestamp is refused.
+        let mut tampered_payload = sealed_package.payload();
+        let last = tampered_payload.len() - 1;
+        tampered_payload[last] ^= 0x01;
+        let tampered_package = TCPPackage::new(
+            PackageKind::Envelope,
+            sealed_package.timestamp(),
+            &tampered_payload,
+        );
+        assert_eq!(
+            envelope::open(&engine_keys, &tampered_package).err(),
+            Some(TCPEnvelopeError::DecryptionError)
+        );
+        let retimed_package = TCPPackage::new(
+            PackageKind::Envelope,
+            sealed_package.timestamp() + 1,
+            &sealed_package.payload(),
+        );
+        assert_eq!(
+            envelope::open(&engine_keys, &retimed_package).err(),
+            Some(TCPEnvelopeError::MalformedPackage)
+        );
+
+        // 6 A rewritten sequence number is refused.
+        let mut resequenced_payload = sealed_package.payload();
+        resequenced_payload[67..75].copy_from_slice(&8u64.to_be_bytes());
+        let resequenced_package = TCPPackage::new(
+            PackageKind::Envelope,
+            sealed_package.timestamp(),
+            &resequenced_payload,
+        );
+        assert_eq!(
+            envelope::open(&engine_keys, &resequenced_package).err(),
+            Some(TCPEnvelopeError::MalformedEnvelope)
+        );
+
+        // 7 Unknown versions are refused.
+        let mut versioned_payload = sealed_package.payload();
+        versioned_payload[0] = 0x03;
+        let versioned_package = TCPPackage::new(
+            PackageKind::Envelope,
+            sealed_package.timestamp(),
+            &versioned_payload,
+        );
+        assert_eq!(
+            envelope::open(&engine_keys, &versioned_package).err(),
+            Some(TCPEnvelopeError::UnsupportedVersion(0x03))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn large_package() -> Result<(), String> {
+        let node_keys = Keys::generate();
+        let engine_keys = Keys::generate();
+
+        // 1 A package larger than a single NIP-44 payload is sealed in chunks.
+        let payload: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let package = TCPPackage::new(PackageKind::BatchContainerProtocol, 42, &payload);
+        let sealed_package =
+            envelope::seal(&engine_keys, node_keys.public_key().to_bytes(), 7, &package)
+                .map_err(|e| format!("Failed to seal: {:?}", e))?;
+
+        // 2 The chunks open back into the package.
+        let (_, _, opened_package) = envelope::open(&node_keys, &sealed_package)
+            .map_err(|e| format!("Failed to open: {:?}", e))?;
+        assert_eq!(opened_package.payload(), payload);
+
+        // 3 Dropping the last chunk is detected.
+        let mut truncated_payload = sealed_package.payload();
+        let chunk_count = u16::from_be_bytes([truncated_payload[65], truncated_payload[66]]);
+        assert_eq!(chunk_count, 4);
+        truncated_payload[65..67].copy_from_slice(&(chunk_count - 1).to_be_bytes());
+        let truncated_package = TCPPackage::new(PackageKind::Envelope, 42, &truncated_payload);
+        assert!(envelope::open(&node_keys, &truncated_package).is_err());
+
+        Ok(())
+    }
+}
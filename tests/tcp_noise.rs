@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tcp_noise_tests {
+    use cube::communicative::tcp::noise::{NoiseHandshake, NoiseRole, NoiseTransport};
+    use cube::communicative::tcp::noise_error::TCPNoiseError;
+    use cube::communicative::tcp::package::{PackageKind, TCPPackage};
+    use cube::transmutative::secp::schnorr::{self, Bytes32};
+
+    /// Runs a full XX handshake and returns the initiator and responder transports.
+    fn handshake(
+        initiator_secret: [u8; 32],
+        responder_secret: [u8; 32],
+    ) -> Result<(NoiseTransport, NoiseTransport), TCPNoiseError> {
+        let mut initiator = NoiseHandshake::new(NoiseRole::Initiator, initiator_secret)?;
+        let mut responder = NoiseHandshake::new(NoiseRole::Responder, responder_secret)?;
+
+        let message_1 = initiator.write_message()?;
+        responder.read_message(&message_1)?;
+        let message_2 = responder.write_message()?;
+        initiator.read_message(&message_2)?;
+        let message_3 = initiator.write_message()?;
+        responder.read_message(&message_3)?;
+
+        Ok((initiator.into_transport()?, responder.into_transport()?))
+    }
+
+    #[test]
+    fn handshake_test() -> Result<(), String> {
+        let node_secret = schnorr::generate_secret();
+        // An odd secret, whose x-only key is that of its negation.
+        let engine_secret = [0x03u8; 32];
+        let node_key = node_secret.secret_to_public().ok_or("Bad secret.")?;
+        let engine_key = engine_secret.secret_to_public().ok_or("Bad secret.")?;
+
+        // 1 Both parties learn each other's static key and share the handshake hash.
+        let (node_transport, engine_transport) =
+            handshake(node_secret, engine_secret).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(node_transport.remote_key(), engine_key);
+        assert_eq!(engine_transport.remote_key(), node_key);
+        assert_eq!(
+            node_transport.handshake_hash(),
+            engine_transport.handshake_hash()
+        );
+
+        // 2 Messages must be exchanged in turn.
+        let mut initiator = NoiseHandshake::new(NoiseRole::Initiator, node_secret)
+            .map_err(|e| format!("{:?}", e))?;
+        let mut responder = NoiseHandshake::new(NoiseRole::Responder, engine_secret)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            responder.write_message().err(),
+            Some(TCPNoiseError::OutOfTurn)
+        );
+        let message_1 = initiator.write_message().map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            initiator.write_message().err(),
+            Some(TCPNoiseError::OutOfTurn)
+        );
+        responder
+            .read_message(&message_1)
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 3 A tampered second message is refused.
+        let mut message_2 = responder.write_message().map_err(|e| format!("{:?}", e))?;
+        let last = message_2.len() - 1;
+        message_2[last] ^= 0x01;
+        assert_eq!(
+            initiator.read_message(&message_2).err(),
+            Some(TCPNoiseError::DecryptionError)
+        );
+
+        // 4 An incomplete handshake has no transport.
+        assert_eq!(
+            responder.into_transport().err(),
+            Some(TCPNoiseError::HandshakeIncomplete)
+        );
+
+        // 5 Invalid static secrets are refused.
+        assert_eq!(
+            NoiseHandshake::new(NoiseRole::Initiator, [0u8; 32]).err(),
+            Some(TCPNoiseError::InvalidLocalKey)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn transport_test() -> Result<(), String> {
+        let (mut node_transport, mut engine_transport) =
+            handshake(schnorr::generate_secret(), schnorr::generate_secret())
+                .map_err(|e| format!("{:?}", e))?;
+
+        // 1 A request goes one way and its response the other.
+        let request = TCPPackage::new(PackageKind::HeartbeatProtocol, 1_700_000_000, b"beat");
+        let sealed_request = node_transport
+            .seal(&request)
+            .map_err(|e| format!("{:?}", e))?;
+        assert!(sealed_request.kind() == PackageKind::NoiseTransport);
+        assert_eq!(sealed_request.timestamp(), request.timestamp());
+        let opened_request = engine_transport
+            .open(&sealed_request)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(opened_request.serialize(), request.serialize());
+
+        let response = TCPPackage::new(PackageKind::HeartbeatProtocol, 1_700_000_000, b"ack");
+        let sealed_response = engine_transport
+            .seal(&response)
+            .map_err(|e| format!("{:?}", e))?;
+        let opened_response = node_transport
+            .open(&sealed_response)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(opened_response.serialize(), response.serialize());
+
+        // 2 A replayed package is refused.
+        assert_eq!(
+            engine_transport.open(&sealed_request).err(),
+            Some(TCPNoiseError::ReplayedNonce)
+        );
+
+        // 3 A package sent one way cannot be reflected back.
+        let sealed_request = node_transport
+            .seal(&request)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            node_transport.open(&sealed_request).err(),
+            Some(TCPNoiseError::DecryptionError)
+        );
+
+        // 4 A dropped package does not desynchronize the session.
+        let skipped_request = TCPPackage::new(PackageKind::Ping, 1_700_000_001, &[0x01]);
+        let _ = node_transport
+            .seal(&skipped_request)
+            .map_err(|e| format!("{:?}", e))?;
+        engine_transport
+            .open(&sealed_request)
+            .map_err(|e| format!("{:?}", e))?;
+
+        // 5 A retimed package is refused.
+        let sealed_request = node_transport
+            .seal(&request)
+            .map_err(|e| format!("{:?}", e))?;
+        let retimed_request = TCPPackage::new(
+            PackageKind::NoiseTransport,
+            sealed_request.timestamp() + 1,
+            &sealed_request.payload(),
+        );
+        assert_eq!(
+            engine_transport.open(&retimed_request).err(),
+            Some(TCPNoiseError::MalformedPackage)
+        );
+
+        // 6 A package larger than a single Noise message is sent in chunks.
+        let payload: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let large_package = TCPPackage::new(PackageKind::BatchContainerProtocol, 42, &payload);
+        let sealed_package = engine_transport
+            .seal(&large_package)
+            .map_err(|e| format!("{:?}", e))?;
+        let sealed_payload = sealed_package.payload();
+        assert_eq!(
+            u16::from_be_bytes([sealed_payload[0], sealed_payload[1]]),
+            4
+        );
+        let opened_package = node_transport
+            .open(&sealed_package)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(opened_package.payload(), payload);
+
+        Ok(())
+    }
+}
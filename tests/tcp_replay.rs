@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tcp_replay_tests {
+    use cube::communicative::tcp::replay::{
+        self, ReplayGuard, ReplayWindow, MAX_PACKAGE_AGE_SECS, REPLAY_WINDOW_SIZE,
+    };
+    use cube::communicative::tcp::replay_error::TCPReplayError;
+
+    #[test]
+    fn replay_window_test() -> Result<(), String> {
+        let mut window = ReplayWindow::new();
+
+        // 1 New sequence numbers are accepted, once.
+        window.accept(1_000).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            window.accept(1_000).err(),
+            Some(TCPReplayError::DuplicateSequence(1_000))
+        );
+
+        // 2 Sequence numbers within the window may arrive out of order, once.
+        window.accept(1_005).map_err(|e| format!("{:?}", e))?;
+        window.accept(1_003).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            window.accept(1_003).err(),
+            Some(TCPReplayError::DuplicateSequence(1_003))
+        );
+        assert_eq!(window.highest(), 1_005);
+
+        // 3 Sequence numbers that slid out of the window are refused.
+        window
+            .accept(1_005 + REPLAY_WINDOW_SIZE)
+            .map_err(|e| format!("{:?}", e))?;
+        assert!(matches!(
+            window.accept(1_004),
+            Err(TCPReplayError::SequenceTooOld { .. })
+        ));
+        window.accept(1_006).map_err(|e| format!("{:?}", e))?;
+
+        // 4 Checking does not record.
+        window.check(1_007).map_err(|e| format!("{:?}", e))?;
+        window.accept(1_007).map_err(|e| format!("{:?}", e))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay_guard_test() -> Result<(), String> {
+        let replay_guard = ReplayGuard::new();
+        let mut replay_guard = replay_guard.try_lock().map_err(|e| e.to_string())?;
+        let node_key = [0x01u8; 32];
+        let operator_key = [0x02u8; 32];
+        let now = 1_700_000_000;
+
+        // 1 Stale and future packages are refused.
+        assert!(matches!(
+            replay_guard.check(node_key, 1, now - MAX_PACKAGE_AGE_SECS - 1, now),
+            Err(TCPReplayError::StalePackage { .. })
+        ));
+        assert!(matches!(
+            replay_guard.check(node_key, 1, now + MAX_PACKAGE_AGE_SECS + 1, now),
+            Err(TCPReplayError::StalePackage { .. })
+        ));
+
+        // 2 A replayed package is refused, while other peers keep their own window.
+        replay_guard
+            .check(node_key, 1, now, now)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(
+            replay_guard.check(node_key, 1, now, now + 1).err(),
+            Some(TCPReplayError::DuplicateSequence(1))
+        );
+        replay_guard
+            .check(operator_key, 1, now, now)
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(replay_guard.len(), 2);
+
+        // 3 Windows of peers gone quiet are dropped once their packages can no longer be fresh.
+        replay_guard.prune(now + MAX_PACKAGE_AGE_SECS + 1);
+        assert!(replay_guard.is_empty());
+        assert!(matches!(
+            replay_guard.check(node_key, 1, now, now + MAX_PACKAGE_AGE_SECS + 1),
+            Err(TCPReplayError::StalePackage { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn next_sequence_test() {
+        let first = replay::next_sequence();
+        let second = replay::next_sequence();
+        assert!(second > first);
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod telemetry_tests {
+    use cube::operative::run_args::chain::Chain;
+    use cube::operative::run_args::operating_kind::OperatingKind;
+    use cube::operative::run_args::resource_mode::ResourceMode;
+    use cube::operative::tasks::telemetry::telemetry::TelemetryReport;
+    use cube::operative::tasks::telemetry::telemetry_config::TelemetryConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn telemetry_report_test() -> Result<(), String> {
+        // 1 Construct a report two hours after starting from batch height #10.
+        let report = TelemetryReport::new(
+            Chain::Signet,
+            OperatingKind::Node,
+            ResourceMode::Archival,
+            200_000,
+            30,
+            10,
+            7_200,
+        );
+
+        // 2 The setup and heights are reported as is.
+        assert_eq!(report.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(report.chain, "signet");
+        assert_eq!(report.operating_kind, "node");
+        assert_eq!(report.resource_mode, "archival");
+        assert_eq!(report.bitcoin_sync_height_tip, 200_000);
+        assert_eq!(report.cube_batch_sync_height_tip, 30);
+
+        // 3 The performance stats are computed against the starting height.
+        assert_eq!(report.uptime_secs, 7_200);
+        assert_eq!(report.batches_synced_since_start, 20);
+        assert_eq!(report.batches_synced_per_hour, 10);
+
+        // 4 A report right after starting has no rate, and a height that went back syncs nothing.
+        let report = TelemetryReport::new(
+            Chain::Mainnet,
+            OperatingKind::Engine,
+            ResourceMode::Pruned,
+            0,
+            5,
+            10,
+            0,
+        );
+        assert_eq!(report.batches_synced_since_start, 0);
+        assert_eq!(report.batches_synced_per_hour, 0);
+
+        // 5 The report serializes to the documented fields only.
+        let json = serde_json::to_value(&report).map_err(|err| err.to_string())?;
+        let mut fields: Vec<&str> = json
+            .as_object()
+            .ok_or("The report is not a JSON object.")?
+            .keys()
+            .map(|key| key.as_str())
+            .collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec![
+                "batches_synced_per_hour",
+                "batches_synced_since_start",
+                "bitcoin_sync_height_tip",
+                "chain",
+                "cube_batch_sync_height_tip",
+                "operating_kind",
+                "resource_mode",
+                "uptime_secs",
+                "version",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn telemetry_config_test() -> Result<(), String> {
+        // 1 Telemetry stays off without an endpoint, or with a blank one.
+        std::env::remove_var("CUBE_TELEMETRY_ENDPOINT");
+        std::env::remove_var("CUBE_TELEMETRY_INTERVAL_SECS");
+        assert_eq!(TelemetryConfig::from_env(), None);
+        std::env::set_var("CUBE_TELEMETRY_ENDPOINT", "  ");
+        assert_eq!(TelemetryConfig::from_env(), None);
+
+        // 2 Opting in with an endpoint reports hourly by default.
+        std::env::set_var(
+            "CUBE_TELEMETRY_ENDPOINT",
+            " https://telemetry.example/report ",
+        );
+        assert_eq!(
+            TelemetryConfig::from_env(),
+            Some(TelemetryConfig {
+                endpoint: "https://telemetry.example/report".to_string(),
+                interval: Duration::from_secs(3_600),
+            })
+        );
+
+        // 3 The interval can be overridden, but not below a minute.
+        std::env::set_var("CUBE_TELEMETRY_INTERVAL_SECS", "10");
+        assert_eq!(
+            TelemetryConfig::from_env().map(|config| config.interval),
+            Some(Duration::from_secs(60))
+        );
+        std::env::set_var("CUBE_TELEMETRY_INTERVAL_SECS", "600");
+        assert_eq!(
+            TelemetryConfig::from_env().map(|config| config.interval),
+            Some(Duration::from_secs(600))
+        );
+
+        // 4 Clear the environment.
+        std::env::remove_var("CUBE_TELEMETRY_ENDPOINT");
+        std::env::remove_var("CUBE_TELEMETRY_INTERVAL_SECS");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod traffic_meter_tests {
+    use cube::communicative::tcp::package::PackageKind;
+    use cube::communicative::traffic::traffic_meter::{
+        TrafficCounter, TrafficDirection, TrafficMeter, MAX_METERED_PEERS,
+    };
+
+    #[test]
+    fn traffic_meter_test() -> Result<(), String> {
+        let mut traffic_meter = TrafficMeter::new();
+        let quiet_peer = [0x01u8; 32];
+        let noisy_peer = [0x02u8; 32];
+
+        // Traffic is accounted by peer, direction and message type.
+        traffic_meter.record(
+            Some(quiet_peer),
+            PackageKind::HeartbeatProtocol,
+            TrafficDirection::Sent,
+            100,
+        );
+        traffic_meter.record(
+            Some(quiet_peer),
+            PackageKind::HeartbeatProtocol,
+            TrafficDirection::Received,
+            120,
+        );
+        for _ in 0..3 {
+            traffic_meter.record(
+                Some(noisy_peer),
+                PackageKind::InFlightSyncProtocol,
+                TrafficDirection::Received,
+                1_000,
+            );
+        }
+        traffic_meter.record(
+            Some(noisy_peer),
+            PackageKind::Ping,
+            TrafficDirection::Received,
+            13,
+        );
+        assert_eq!(traffic_meter.len(), 2);
+
+        let noisy_traffic = traffic_meter
+            .peer(noisy_peer)
+            .ok_or("Noisy peer is not metered.")?;
+        assert_eq!(
+            noisy_traffic.counter(
+                PackageKind::InFlightSyncProtocol,
+                TrafficDirection::Received
+            ),
+            TrafficCounter {
+                messages: 3,
+                bytes: 3_000
+            }
+        );
+        assert_eq!(
+            noisy_traffic.total(TrafficDirection::Received),
+            TrafficCounter {
+                messages: 4,
+                bytes: 3_013
+            }
+        );
+        assert_eq!(
+            noisy_traffic.total(TrafficDirection::Sent),
+            TrafficCounter::default()
+        );
+
+        // The noisiest peer comes first.
+        let noisiest_peers = traffic_meter.noisiest_peers();
+        assert_eq!(noisiest_peers[0].0, noisy_peer);
+        assert_eq!(noisiest_peers[1].0, quiet_peer);
+
+        // Traffic without a peer key is unattributed.
+        traffic_meter.record(
+            None,
+            PackageKind::NoiseHandshake,
+            TrafficDirection::Received,
+            45,
+        );
+        assert_eq!(traffic_meter.len(), 2);
+        assert_eq!(
+            traffic_meter
+                .unattributed()
+                .total(TrafficDirection::Received),
+            TrafficCounter {
+                messages: 1,
+                bytes: 45
+            }
+        );
+
+        // Totals span every peer and the unattributed traffic.
+        assert_eq!(
+            traffic_meter.total(TrafficDirection::Received),
+            TrafficCounter {
+                messages: 6,
+                bytes: 3_178
+            }
+        );
+        assert_eq!(
+            traffic_meter.total(TrafficDirection::Sent),
+            TrafficCounter {
+                messages: 1,
+                bytes: 100
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn traffic_meter_cap_test() -> Result<(), String> {
+        let mut traffic_meter = TrafficMeter::new();
+
+        // Fill the meter up.
+        for index in 0..MAX_METERED_PEERS {
+            let mut peer_key = [0u8; 32];
+            peer_key[..8].copy_from_slice(&(index as u64).to_be_bytes());
+            traffic_meter.record(
+                Some(peer_key),
+                PackageKind::Ping,
+                TrafficDirection::Received,
+                13,
+            );
+        }
+        assert_eq!(traffic_meter.len(), MAX_METERED_PEERS);
+
+        // Peers beyond the cap are unattributed, while metered peers keep being accounted.
+        traffic_meter.record(
+            Some([0xffu8; 32]),
+            PackageKind::Ping,
+            TrafficDirection::Received,
+            13,
+        );
+        traffic_meter.record(
+            Some([0u8; 32]),
+            PackageKind::Ping,
+            TrafficDirection::Received,
+            13,
+        );
+        assert_eq!(traffic_meter.len(), MAX_METERED_PEERS);
+        assert!(traffic_meter.peer([0xffu8; 32]).is_none());
+        assert_eq!(
+            traffic_meter
+                .unattributed()
+                .total(TrafficDirection::Received)
+                .messages,
+            1
+        );
+        assert_eq!(
+            traffic_meter
+                .peer([0u8; 32])
+                .ok_or("First peer is not metered.")?
+                .total(TrafficDirection::Received)
+                .messages,
+            2
+        );
+
+        Ok(())
+    }
+}
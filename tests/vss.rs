@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod vss_tests {
+    use cube::communicative::federation::dkg::{self, DkgCommitment};
+    use cube::communicative::federation::errors::dkg_error::DkgError;
+    use cube::communicative::federation::federation::Federation;
+    use cube::transmutative::key::KeyHolder;
+    use cube::transmutative::secp::context::GeneratorMul;
+    use cube::transmutative::secp::schnorr;
+    use cube::transmutative::secp::vss::{self, VssCommitment};
+    use secp::Scalar;
+
+    #[test]
+    fn share_and_recover() -> Result<(), String> {
+        // 1 Share a secret 3-of-5.
+        let secret = schnorr::generate_scalar();
+        let (commitment, shares) = vss::deal(secret, 3, 5).ok_or("Failed to deal.".to_string())?;
+        assert_eq!(commitment.threshold(), 3);
+        assert_eq!(commitment.secret_commitment(), secret.generator_mul());
+        assert_eq!(shares.len(), 5);
+
+        // 2 Each share verifies at its own index only.
+        for (position, share) in shares.iter().enumerate() {
+            let index = position as u32 + 1;
+            assert!(commitment.verify_share(index, *share));
+            assert!(!commitment.verify_share(index % 5 + 1, *share));
+        }
+        assert!(!commitment.verify_share(0, secret));
+
+        // 3 Any three shares recover the secret, two do not.
+        let indexed: Vec<(u32, Scalar)> = shares
+            .iter()
+            .enumerate()
+            .map(|(position, share)| (position as u32 + 1, *share))
+            .collect();
+        assert_eq!(vss::recover(&indexed[..3]), Some(secret));
+        assert_eq!(
+            vss::recover(&[indexed[4], indexed[1], indexed[2]]),
+            Some(secret)
+        );
+        assert_ne!(vss::recover(&indexed[..2]), Some(secret));
+
+        // 4 Repeated or zero indexes are refused.
+        assert_eq!(vss::recover(&[indexed[0], indexed[0], indexed[1]]), None);
+        assert_eq!(vss::recover(&[(0, shares[0])]), None);
+
+        // 5 Out of bounds thresholds are refused.
+        assert!(vss::deal(secret, 0, 5).is_none());
+        assert!(vss::deal(secret, 6, 5).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn commitment_serialization() -> Result<(), String> {
+        let (commitment, _) =
+            vss::deal(schnorr::generate_scalar(), 4, 7).ok_or("Failed to deal.".to_string())?;
+
+        let bytes = commitment.serialize();
+        assert_eq!(bytes.len(), 1 + 4 * 33);
+        assert_eq!(VssCommitment::deserialize(&bytes), Some(commitment));
+
+        // Truncated and empty commitments do not parse.
+        assert_eq!(VssCommitment::deserialize(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(VssCommitment::deserialize(&[0]), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn federation_dkg() -> Result<(), String> {
+        // 1 Construct a 2-of-3 federation.
+        let coordinators: Vec<KeyHolder> = (0..3)
+            .map(|_| KeyHolder::new(schnorr::generate_secret()))
+            .collect::<Option<Vec<KeyHolder>>>()
+            .ok_or("Failed to construct coordinator keys.".to_string())?;
+        let coordinator_keys: Vec<[u8; 32]> = coordinators
+            .iter()
+            .map(|coordinator| coordinator.secp_public_key_bytes())
+            .collect();
+        let federation = Federation::new(coordinator_keys.clone(), 2)
+            .ok_or("Failed to construct federation.".to_string())?;
+        assert_eq!(federation.participant_index(coordinator_keys[2]), Some(3));
+
+        // 2 Each coordinator deals, and the commitments go over the wire.
+        let mut commitments = Vec::<DkgCommitment>::new();
+        let mut received_shares = vec![Vec::<[u8; 32]>::new(); 3];
+        for dealer_key in coordinator_keys.iter() {
+            let (commitment, shares) =
+                DkgCommitment::deal(&federation, *dealer_key).map_err(|e| format!("{:?}", e))?;
+            let bytes = commitment
+                .serialize()
+                .ok_or("Failed to serialize.".to_string())?;
+            let commitment =
+                DkgCommitment::deserialize(&bytes).ok_or("Failed to deserialize.".to_string())?;
+
+            // 2.1 Each receiver verifies their share.
+            for (position, (receiver_key, share)) in shares.iter().enumerate() {
+                commitment
+                    .verify_share(&federation, *receiver_key, *share)
+                    .map_err(|e| format!("{:?}", e))?;
+                received_shares[position].push(*share);
+            }
+
+            // 2.2 A share sent to the wrong receiver is caught.
+            assert_eq!(
+                commitment.verify_share(&federation, shares[1].0, shares[0].1),
+                Err(DkgError::InvalidShare(*dealer_key))
+            );
+
+            commitments.push(commitment);
+        }
+
+        // 3 Any two group shares recover the group secret behind the group key.
+        let group_key =
+            dkg::group_key(&federation, &commitments).map_err(|e| format!("{:?}", e))?;
+        let group_shares: Vec<(u32, Scalar)> = received_shares
+            .iter()
+            .enumerate()
+            .map(|(position, shares)| {
+                let group_share = dkg::group_share(shares).map_err(|e| format!("{:?}", e))?;
+                let group_share = Scalar::from_slice(&group_share).map_err(|e| e.to_string())?;
+                Ok((position as u32 + 1, group_share))
+            })
+            .collect::<Result<Vec<(u32, Scalar)>, String>>()?;
+        let group_secret =
+            vss::recover(&group_shares[1..]).ok_or("Failed to recover.".to_string())?;
+        assert_eq!(group_secret.generator_mul(), group_key);
+
+        // 4 A missing dealing is caught.
+        assert_eq!(
+            dkg::group_key(&federation, &commitments[..2]),
+            Err(DkgError::MissingDealing(coordinator_keys[2]))
+        );
+
+        // 5 A non-coordinator cannot deal.
+        assert_eq!(
+            DkgCommitment::deal(&federation, [0x01; 32]).map(|_| ()),
+            Err(DkgError::NotACoordinator([0x01; 32]))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod webhooks_tests {
+    use cube::communicative::webhook::errors::webhook_error::WebhookError;
+    use cube::communicative::webhook::webhook_event::WebhookEventKind;
+    use cube::communicative::webhook::webhook_signature::webhook_signature_header;
+    use cube::communicative::webhook::webhooks::{
+        erase_webhooks, webhook_retry_backoff_secs, WebhookFailureOutcome, Webhooks,
+        MAX_DELIVERY_ATTEMPTS, WEBHOOKS,
+    };
+    use cube::operative::run_args::chain::Chain;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn webhook_signature_test() {
+        // HMAC-SHA256 of `<timestamp>.<body>` under the endpoint secret.
+        assert_eq!(
+            webhook_signature_header(&[0x0b; 32], 1_700_000_000, r#"{"id":0}"#),
+            "sha256=f0473e75935b70edd395afddc11c90bea7b6ddfee79ed010798717270aefe556"
+        );
+    }
+
+    #[test]
+    fn webhook_retry_backoff_test() {
+        assert_eq!(webhook_retry_backoff_secs(1), 10);
+        assert_eq!(webhook_retry_backoff_secs(2), 20);
+        assert_eq!(webhook_retry_backoff_secs(3), 40);
+        assert_eq!(webhook_retry_backoff_secs(MAX_DELIVERY_ATTEMPTS), 3_600);
+    }
+
+    #[tokio::test]
+    async fn webhooks_test() -> Result<(), String> {
+        // 1 Set the chain for local tests.
+        let chain = Chain::Testbed;
+        let followed = [0x01u8; 32];
+        let other = [0x02u8; 32];
+
+        // 2 Erase first the webhooks.
+        erase_webhooks(chain);
+
+        {
+            let webhooks: WEBHOOKS = Webhooks::new(chain)
+                .map_err(|err| format!("Error constructing webhooks: {:?}", err))?;
+            let mut _webhooks = webhooks.lock().await;
+
+            // 3 Invalid endpoints are refused.
+            assert!(matches!(
+                _webhooks.add_endpoint(
+                    "http://hooks.example.com",
+                    vec![WebhookEventKind::DepositDetected],
+                    vec![],
+                    0
+                ),
+                Err(WebhookError::InvalidEndpointUrl(_))
+            ));
+            assert_eq!(
+                _webhooks.add_endpoint("https://hooks.example.com", vec![], vec![], 0),
+                Err(WebhookError::NoEventKinds)
+            );
+            assert_eq!(
+                _webhooks.add_endpoint(
+                    "https://hooks.example.com",
+                    vec![WebhookEventKind::BalanceChanged],
+                    vec![],
+                    0
+                ),
+                Err(WebhookError::NoFollowedAccounts)
+            );
+
+            // 4 Add an endpoint following an account and another one following deposits.
+            let balances = _webhooks
+                .add_endpoint(
+                    "https://hooks.example.com/balances",
+                    vec![WebhookEventKind::BalanceChanged],
+                    vec![followed],
+                    0,
+                )
+                .map_err(|err| err.to_string())?;
+            let deposits = _webhooks
+                .add_endpoint(
+                    "https://hooks.example.com/deposits",
+                    vec![
+                        WebhookEventKind::DepositDetected,
+                        WebhookEventKind::DepositDetected,
+                    ],
+                    vec![],
+                    0,
+                )
+                .map_err(|err| err.to_string())?;
+            assert_ne!(balances.secret, deposits.secret);
+            assert_eq!(deposits.kinds, vec![WebhookEventKind::DepositDetected]);
+            assert_eq!(_webhooks.followed_accounts().len(), 1);
+
+            // 5 Events are only queued for the endpoints subscribed to them.
+            let enqueue = |_webhooks: &mut Webhooks, kind, account_key| {
+                _webhooks.enqueue(kind, account_key, json!({ "value": 1 }), 100)
+            };
+            assert_eq!(
+                enqueue(
+                    &mut _webhooks,
+                    WebhookEventKind::BalanceChanged,
+                    Some(other)
+                ),
+                Ok(0)
+            );
+            assert_eq!(
+                enqueue(
+                    &mut _webhooks,
+                    WebhookEventKind::BalanceChanged,
+                    Some(followed)
+                ),
+                Ok(1)
+            );
+            assert_eq!(
+                enqueue(&mut _webhooks, WebhookEventKind::DepositDetected, None),
+                Ok(1)
+            );
+            assert_eq!(
+                enqueue(&mut _webhooks, WebhookEventKind::ExecutionFailed, None),
+                Ok(0)
+            );
+
+            // 6 The body carries the delivery id, the event kind and the event data.
+            let due = _webhooks.due(100);
+            assert_eq!(due.len(), 2);
+            let body: Value = serde_json::from_str(&due[0].body).map_err(|e| e.to_string())?;
+            assert_eq!(body["id"], json!(due[0].id));
+            assert_eq!(body["event"], json!("balance_changed"));
+            assert_eq!(body["data"], json!({ "value": 1 }));
+
+            // 7 A failed delivery is backed off, and the other one is acknowledged.
+            assert_eq!(
+                _webhooks.record_failure(due[0].id, "HTTP 503".to_string(), 100),
+                Ok(WebhookFailureOutcome::RetryAt(110))
+            );
+            _webhooks
+                .acknowledge(due[1].id)
+                .map_err(|err| err.to_string())?;
+            assert!(_webhooks.due(109).is_empty());
+            assert_eq!(_webhooks.due(110).len(), 1);
+        }
+
+        // 8 Endpoints and queued deliveries survive a restart.
+        {
+            let webhooks: WEBHOOKS = Webhooks::new(chain)
+                .map_err(|err| format!("Error constructing webhooks: {:?}", err))?;
+            let mut _webhooks = webhooks.lock().await;
+            assert_eq!(_webhooks.endpoints().len(), 2);
+            let due = _webhooks.due(u64::MAX);
+            assert_eq!(due.len(), 1);
+            assert_eq!(due[0].attempts, 1);
+
+            // 9 A delivery is given up on once out of attempts.
+            for attempt in 1..MAX_DELIVERY_ATTEMPTS {
+                let outcome = _webhooks
+                    .record_failure(due[0].id, "HTTP 503".to_string(), 200)
+                    .map_err(|err| err.to_string())?;
+                assert_eq!(
+                    outcome == WebhookFailureOutcome::GivenUp,
+                    attempt + 1 == MAX_DELIVERY_ATTEMPTS
+                );
+            }
+            assert_eq!(_webhooks.pending_len(), 0);
+
+            // 10 Removing an endpoint drops its queued deliveries.
+            let followed_id = _webhooks.endpoints()[0].id;
+            enqueue_balance_change(&mut _webhooks, followed)?;
+            assert_eq!(_webhooks.remove_endpoint(followed_id), Ok(true));
+            assert_eq!(_webhooks.remove_endpoint(followed_id), Ok(false));
+            assert_eq!(_webhooks.pending_len(), 0);
+            assert!(_webhooks.followed_accounts().is_empty());
+        }
+
+        // 11 Erase the webhooks.
+        erase_webhooks(chain);
+
+        Ok(())
+    }
+
+    /// Queues a balance change of the given account.
+    fn enqueue_balance_change(
+        webhooks: &mut Webhooks,
+        account_key: [u8; 32],
+    ) -> Result<(), String> {
+        webhooks
+            .enqueue(
+                WebhookEventKind::BalanceChanged,
+                Some(account_key),
+                json!({}),
+                300,
+            )
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+}